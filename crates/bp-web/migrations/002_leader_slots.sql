@@ -0,0 +1,8 @@
+-- Our leader slots for the current and next epoch, refreshed by the ingestion job.
+-- Lets the dashboard show "next block in ~X minutes" without a leader schedule RPC call per request.
+CREATE TABLE IF NOT EXISTS leader_slots (
+    slot INTEGER PRIMARY KEY,
+    epoch INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_leader_slots_epoch ON leader_slots(epoch);
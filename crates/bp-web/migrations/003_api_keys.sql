@@ -0,0 +1,16 @@
+-- API keys for programmatic access to the financial JSON endpoints (see src/api_keys.rs).
+-- Only SHA-256 hashes of the keys are stored; revoked keys are kept for the audit trail.
+CREATE TABLE IF NOT EXISTS api_keys (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    key_hash TEXT NOT NULL UNIQUE,
+    -- Comma-separated scopes (read, sql)
+    scopes TEXT NOT NULL,
+    rate_limit_per_minute INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    last_used_at TEXT,
+    revoked_at TEXT
+);
+
+-- One active key per name, so keys can be revoked by name
+CREATE UNIQUE INDEX IF NOT EXISTS idx_api_keys_active_name ON api_keys(name) WHERE revoked_at IS NULL;
@@ -0,0 +1,289 @@
+// "Stake with us" island for the /delegate page.
+//
+// Discovers wallets through the Wallet Standard (Phantom, Solflare, Backpack, ...), then
+// builds a legacy transaction that creates a stake account owned by the connected wallet
+// (createAccountWithSeed, so the wallet is the only signer), initializes it with the
+// wallet as stake and withdraw authority, and delegates it to our vote account. The
+// wallet signs and sends it. No dependencies: keys and instructions are serialized here.
+(() => {
+  'use strict';
+
+  const root = document.querySelector('[data-stake-widget]');
+  if (!root) return;
+
+  const ALPHABET = '123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz';
+  const SYSTEM_PROGRAM = '11111111111111111111111111111111';
+  const STAKE_PROGRAM = 'Stake11111111111111111111111111111111111111';
+  const STAKE_CONFIG = 'StakeConfig11111111111111111111111111111111';
+  const SYSVAR_CLOCK = 'SysvarC1ock11111111111111111111111111111111';
+  const SYSVAR_RENT = 'SysvarRent111111111111111111111111111111111';
+  const SYSVAR_STAKE_HISTORY = 'SysvarStakeHistory1111111111111111111111111';
+  const STAKE_ACCOUNT_SPACE = 200n;
+  const LAMPORTS_PER_SOL = 1000000000n;
+  const CHAIN = 'solana:mainnet';
+
+  const voteAccount = root.dataset.voteAccount;
+  const rpcUrl = root.dataset.rpc;
+  const island = root.querySelector('[data-stake-island]');
+  const walletSelect = root.querySelector('[data-wallet]');
+  const amountInput = root.querySelector('[data-amount]');
+  const stakeButton = root.querySelector('[data-stake]');
+  const status = root.querySelector('[data-status]');
+
+  // ── Encoding ──────────────────────────────────────────────────────────────
+
+  function b58decode(text) {
+    let n = 0n;
+    for (const c of text) {
+      const digit = ALPHABET.indexOf(c);
+      if (digit < 0) throw new Error('invalid base58');
+      n = n * 58n + BigInt(digit);
+    }
+    const bytes = [];
+    while (n > 0n) {
+      bytes.push(Number(n & 0xffn));
+      n >>= 8n;
+    }
+    for (const c of text) {
+      if (c !== '1') break;
+      bytes.push(0);
+    }
+    return Uint8Array.from(bytes.reverse());
+  }
+
+  function b58encode(bytes) {
+    let n = 0n;
+    for (const b of bytes) n = (n << 8n) + BigInt(b);
+    let out = '';
+    while (n > 0n) {
+      out = ALPHABET[Number(n % 58n)] + out;
+      n /= 58n;
+    }
+    for (const b of bytes) {
+      if (b !== 0) break;
+      out = '1' + out;
+    }
+    return out;
+  }
+
+  function pubkey(address) {
+    const bytes = b58decode(address);
+    if (bytes.length !== 32) throw new Error('invalid public key: ' + address);
+    return bytes;
+  }
+
+  function u32(value) {
+    const out = new Uint8Array(4);
+    new DataView(out.buffer).setUint32(0, value, true);
+    return out;
+  }
+
+  function u64(value) {
+    const out = new Uint8Array(8);
+    new DataView(out.buffer).setBigUint64(0, BigInt(value), true);
+    return out;
+  }
+
+  /** Solana's compact-u16 length prefix */
+  function shortvec(length) {
+    const out = [];
+    let rest = length;
+    for (;;) {
+      let byte = rest & 0x7f;
+      rest >>= 7;
+      if (rest === 0) {
+        out.push(byte);
+        return Uint8Array.from(out);
+      }
+      byte |= 0x80;
+      out.push(byte);
+    }
+  }
+
+  function concat(...parts) {
+    const out = new Uint8Array(parts.reduce((len, p) => len + p.length, 0));
+    let offset = 0;
+    for (const p of parts) {
+      out.set(p, offset);
+      offset += p.length;
+    }
+    return out;
+  }
+
+  /** Parse a decimal SOL amount into lamports without going through floats */
+  function parseSol(text) {
+    const m = /^\s*(\d+)(?:\.(\d{0,9}))?\s*$/.exec(text);
+    if (!m) return null;
+    return BigInt(m[1]) * LAMPORTS_PER_SOL + BigInt((m[2] || '').padEnd(9, '0'));
+  }
+
+  // ── Transaction ───────────────────────────────────────────────────────────
+
+  async function rpc(method, params) {
+    const resp = await fetch(rpcUrl, {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ jsonrpc: '2.0', id: 1, method, params }),
+    });
+    const body = await resp.json();
+    if (body.error) throw new Error(body.error.message);
+    return body.result;
+  }
+
+  /** Address of `createAccountWithSeed(base, seed, owner)` */
+  async function addressWithSeed(base, seed, owner) {
+    const data = concat(base, new TextEncoder().encode(seed), owner);
+    return new Uint8Array(await crypto.subtle.digest('SHA-256', data));
+  }
+
+  async function buildDelegateTransaction(wallet, lamports) {
+    const [{ value: latest }, rentExempt] = await Promise.all([
+      rpc('getLatestBlockhash', [{ commitment: 'confirmed' }]),
+      rpc('getMinimumBalanceForRentExemption', [Number(STAKE_ACCOUNT_SPACE)]),
+    ]);
+
+    // A fresh seed per stake account; seeds are limited to 32 bytes
+    const seed = 'bp-' + Date.now().toString(36);
+    const stakeProgram = pubkey(STAKE_PROGRAM);
+    const stakeAccount = await addressWithSeed(wallet, seed, stakeProgram);
+    const seedBytes = new TextEncoder().encode(seed);
+
+    // Account order: writable signer, writable non-signer, then read-only non-signers
+    const keys = [
+      wallet, // 0
+      stakeAccount, // 1
+      pubkey(voteAccount), // 2
+      pubkey(SYSTEM_PROGRAM), // 3
+      stakeProgram, // 4
+      pubkey(SYSVAR_CLOCK), // 5
+      pubkey(SYSVAR_RENT), // 6
+      pubkey(SYSVAR_STAKE_HISTORY), // 7
+      pubkey(STAKE_CONFIG), // 8
+    ];
+    const header = Uint8Array.from([1, 0, 7]);
+
+    const createWithSeed = {
+      program: 3,
+      accounts: [0, 1, 0],
+      data: concat(
+        u32(3),
+        wallet,
+        u64(seedBytes.length),
+        seedBytes,
+        u64(lamports + BigInt(rentExempt)),
+        u64(STAKE_ACCOUNT_SPACE),
+        stakeProgram,
+      ),
+    };
+    const initialize = {
+      program: 4,
+      accounts: [1, 6],
+      // Authorized { staker, withdrawer }, then an empty Lockup
+      data: concat(u32(0), wallet, wallet, u64(0), u64(0), new Uint8Array(32)),
+    };
+    const delegate = {
+      program: 4,
+      accounts: [1, 2, 5, 7, 8, 0],
+      data: u32(2),
+    };
+
+    const instructions = [createWithSeed, initialize, delegate].map((ix) =>
+      concat(
+        Uint8Array.from([ix.program]),
+        shortvec(ix.accounts.length),
+        Uint8Array.from(ix.accounts),
+        shortvec(ix.data.length),
+        ix.data,
+      ),
+    );
+
+    const message = concat(
+      header,
+      shortvec(keys.length),
+      ...keys,
+      pubkey(latest.blockhash),
+      shortvec(instructions.length),
+      ...instructions,
+    );
+    // One empty signature slot for the wallet to fill in
+    return { transaction: concat(shortvec(1), new Uint8Array(64), message), stakeAccount: b58encode(stakeAccount) };
+  }
+
+  // ── Wallets (Wallet Standard) ─────────────────────────────────────────────
+
+  const wallets = [];
+
+  function usable(wallet) {
+    return (
+      wallet.chains.includes(CHAIN) &&
+      'standard:connect' in wallet.features &&
+      'solana:signAndSendTransaction' in wallet.features
+    );
+  }
+
+  function register(...registered) {
+    for (const wallet of registered) {
+      if (!usable(wallet) || wallets.includes(wallet)) continue;
+      wallets.push(wallet);
+      const option = document.createElement('option');
+      option.value = String(wallets.length - 1);
+      option.textContent = wallet.name;
+      walletSelect.append(option);
+    }
+    island.hidden = wallets.length === 0;
+    return () => {};
+  }
+
+  const api = Object.freeze({ register });
+  window.addEventListener('wallet-standard:register-wallet', (event) => event.detail(api));
+  window.dispatchEvent(new CustomEvent('wallet-standard:app-ready', { detail: api }));
+
+  // ── Flow ──────────────────────────────────────────────────────────────────
+
+  function say(key, signature) {
+    status.textContent = root.dataset[key] || '';
+    if (signature) {
+      const link = document.createElement('a');
+      link.href = 'https://solscan.io/tx/' + signature;
+      link.target = '_blank';
+      link.rel = 'noopener noreferrer';
+      link.textContent = signature.slice(0, 16) + '…';
+      status.append(' ', link);
+    }
+  }
+
+  stakeButton.addEventListener('click', async () => {
+    const wallet = wallets[Number(walletSelect.value)];
+    const lamports = parseSol(amountInput.value);
+    if (!wallet) return;
+    if (!lamports || lamports <= 0n) {
+      say('msgAmount');
+      amountInput.focus();
+      return;
+    }
+
+    stakeButton.disabled = true;
+    try {
+      say('msgConnecting');
+      const { accounts } = await wallet.features['standard:connect'].connect();
+      const account = accounts.find((a) => a.chains.includes(CHAIN)) || accounts[0];
+      if (!account) throw new Error('no account');
+
+      say('msgBuilding');
+      const { transaction } = await buildDelegateTransaction(Uint8Array.from(account.publicKey), lamports);
+
+      say('msgApprove');
+      const [output] = await wallet.features['solana:signAndSendTransaction'].signAndSendTransaction({
+        account,
+        chain: CHAIN,
+        transaction,
+      });
+      say('msgSent', b58encode(output.signature));
+    } catch (err) {
+      console.error('[stake]', err);
+      say('msgError');
+    } finally {
+      stakeButton.disabled = false;
+    }
+  });
+})();
@@ -0,0 +1,177 @@
+//! IP geolocation for the infrastructure page: data-center location and network (ASN).
+//!
+//! Uses the MaxMind GeoLite2 web service when `MAXMIND_ACCOUNT_ID` and
+//! `MAXMIND_LICENSE_KEY` are set, otherwise ipinfo.io (`IPINFO_TOKEN` optional; the
+//! keyless tier is rate limited, which is plenty for one lookup per ingestion).
+
+use serde::{Deserialize, Serialize};
+
+/// Where an IP is hosted
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IpLocation {
+    pub ip: String,
+    pub city: Option<String>,
+    pub region: Option<String>,
+    /// ISO 3166-1 alpha-2
+    pub country: Option<String>,
+    pub asn: Option<u32>,
+    /// Network operator, e.g. "Hetzner Online GmbH"
+    pub org: Option<String>,
+    /// "maxmind" or "ipinfo"
+    pub source: String,
+}
+
+impl IpLocation {
+    /// "City, Region, CC", skipping unknown parts
+    pub fn place(&self) -> Option<String> {
+        let parts: Vec<&str> = [&self.city, &self.region, &self.country]
+            .into_iter()
+            .filter_map(|p| p.as_deref().filter(|s| !s.is_empty()))
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+}
+
+/// Split ipinfo's `org` field ("AS24940 Hetzner Online GmbH") into ASN and name
+#[cfg(feature = "ssr")]
+fn parse_ipinfo_org(org: &str) -> (Option<u32>, Option<String>) {
+    let org = org.trim();
+    let Some(rest) = org.strip_prefix("AS") else {
+        return (None, (!org.is_empty()).then(|| org.to_string()));
+    };
+    let (number, name) = rest.split_once(' ').unwrap_or((rest, ""));
+    match number.parse() {
+        Ok(asn) => (Some(asn), (!name.trim().is_empty()).then(|| name.trim().to_string())),
+        Err(_) => (None, Some(org.to_string())),
+    }
+}
+
+#[cfg(feature = "ssr")]
+mod ssr {
+    use super::*;
+    use crate::api::http::{get_json, get_json_authorized};
+    use base64::Engine;
+    use std::collections::HashMap;
+
+    #[derive(Deserialize)]
+    struct IpinfoResponse {
+        ip: String,
+        city: Option<String>,
+        region: Option<String>,
+        country: Option<String>,
+        org: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    struct Names {
+        #[serde(default)]
+        names: HashMap<String, String>,
+        iso_code: Option<String>,
+    }
+
+    impl Names {
+        fn english(&self) -> Option<String> {
+            self.names.get("en").cloned()
+        }
+    }
+
+    #[derive(Deserialize, Default)]
+    struct MaxmindTraits {
+        ip_address: Option<String>,
+        autonomous_system_number: Option<u32>,
+        autonomous_system_organization: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    struct MaxmindResponse {
+        city: Option<Names>,
+        country: Option<Names>,
+        #[serde(default)]
+        subdivisions: Vec<Names>,
+        #[serde(default)]
+        traits: MaxmindTraits,
+    }
+
+    fn env(name: &str) -> Option<String> {
+        std::env::var(name).ok().filter(|v| !v.trim().is_empty())
+    }
+
+    /// Look up where `ip` is hosted with whichever provider is configured
+    pub async fn lookup_ip(ip: &str) -> Option<IpLocation> {
+        match (env("MAXMIND_ACCOUNT_ID"), env("MAXMIND_LICENSE_KEY")) {
+            (Some(account), Some(key)) => lookup_maxmind(ip, &account, &key).await,
+            _ => lookup_ipinfo(ip).await,
+        }
+    }
+
+    async fn lookup_maxmind(ip: &str, account: &str, key: &str) -> Option<IpLocation> {
+        let url = format!("https://geolite.info/geoip/v2.1/city/{}", ip);
+        let credentials = base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", account, key));
+        let r: MaxmindResponse = get_json_authorized(&url, &format!("Basic {}", credentials)).await?;
+
+        Some(IpLocation {
+            ip: r.traits.ip_address.unwrap_or_else(|| ip.to_string()),
+            city: r.city.and_then(|c| c.english()),
+            region: r.subdivisions.first().and_then(Names::english),
+            country: r.country.and_then(|c| c.iso_code),
+            asn: r.traits.autonomous_system_number,
+            org: r.traits.autonomous_system_organization,
+            source: "maxmind".to_string(),
+        })
+    }
+
+    async fn lookup_ipinfo(ip: &str) -> Option<IpLocation> {
+        let url = format!("https://ipinfo.io/{}/json", ip);
+        let r: IpinfoResponse = match env("IPINFO_TOKEN") {
+            Some(token) => get_json_authorized(&url, &format!("Bearer {}", token)).await?,
+            None => get_json(&url).await?,
+        };
+        let (asn, org) = r.org.as_deref().map(parse_ipinfo_org).unwrap_or((None, None));
+
+        Some(IpLocation {
+            ip: r.ip,
+            city: r.city,
+            region: r.region,
+            country: r.country,
+            asn,
+            org,
+            source: "ipinfo".to_string(),
+        })
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub use ssr::lookup_ip;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn splits_ipinfo_org_into_asn_and_name() {
+        assert_eq!(
+            parse_ipinfo_org("AS24940 Hetzner Online GmbH"),
+            (Some(24940), Some("Hetzner Online GmbH".to_string()))
+        );
+        assert_eq!(parse_ipinfo_org("AS20326"), (Some(20326), None));
+        assert_eq!(
+            parse_ipinfo_org("Private network"),
+            (None, Some("Private network".to_string()))
+        );
+    }
+
+    #[test]
+    fn place_skips_unknown_parts() {
+        let location = IpLocation {
+            ip: "64.130.50.10".to_string(),
+            city: Some("Frankfurt am Main".to_string()),
+            region: None,
+            country: Some("DE".to_string()),
+            asn: None,
+            org: None,
+            source: "ipinfo".to_string(),
+        };
+        assert_eq!(location.place().as_deref(), Some("Frankfurt am Main, DE"));
+    }
+}
@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+use crate::config::CONFIG;
+
+#[cfg(feature = "ssr")]
+use super::http::post_json_cached;
+#[cfg(feature = "ssr")]
+use super::solana_rpc::RPC_ENDPOINT;
+
+/// Our validator's presence in cluster gossip (getClusterNodes)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GossipPresence {
+    /// Nodes currently visible in gossip
+    pub cluster_nodes: usize,
+    /// Our contact info, or None if the identity isn't in gossip
+    pub node: Option<GossipNode>,
+}
+
+/// Contact info a node advertises over gossip. Addresses are `ip:port`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GossipNode {
+    pub pubkey: String,
+    pub gossip: Option<String>,
+    pub tpu: Option<String>,
+    pub tpu_quic: Option<String>,
+    pub tpu_forwards: Option<String>,
+    pub tpu_vote: Option<String>,
+    pub serve_repair: Option<String>,
+    /// Only set when the node exposes a public RPC
+    pub rpc: Option<String>,
+    pub pubsub: Option<String>,
+    pub version: Option<String>,
+    pub feature_set: Option<u32>,
+    pub shred_version: Option<u16>,
+}
+
+impl GossipNode {
+    /// Public IP, taken from the gossip address
+    pub fn ip(&self) -> Option<&str> {
+        self.gossip
+            .as_deref()
+            .and_then(|addr| split_host_port(addr).map(|(ip, _)| ip))
+    }
+
+    /// Advertised ports by service name, skipping services the node doesn't advertise
+    pub fn ports(&self) -> Vec<(&'static str, u16)> {
+        [
+            ("gossip", &self.gossip),
+            ("tpu", &self.tpu),
+            ("tpu_quic", &self.tpu_quic),
+            ("tpu_forwards", &self.tpu_forwards),
+            ("tpu_vote", &self.tpu_vote),
+            ("serve_repair", &self.serve_repair),
+            ("rpc", &self.rpc),
+            ("pubsub", &self.pubsub),
+        ]
+        .into_iter()
+        .filter_map(|(name, addr)| Some((name, split_host_port(addr.as_deref()?)?.1)))
+        .collect()
+    }
+}
+
+/// Split `ip:port`, including bracketed IPv6 (`[::1]:8001`)
+fn split_host_port(addr: &str) -> Option<(&str, u16)> {
+    let (host, port) = addr.rsplit_once(':')?;
+    let host = host.strip_prefix('[').and_then(|h| h.strip_suffix(']')).unwrap_or(host);
+    Some((host, port.parse().ok()?))
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Deserialize)]
+struct ClusterNodesResponse {
+    result: Option<Vec<GossipNode>>,
+}
+
+/// Fetch the cluster's gossip table and pick out our identity
+#[cfg(feature = "ssr")]
+pub async fn get_gossip_presence() -> Option<GossipPresence> {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "getClusterNodes",
+    })
+    .to_string();
+    let nodes = post_json_cached::<ClusterNodesResponse>(RPC_ENDPOINT, &body)
+        .await?
+        .result?;
+
+    Some(GossipPresence {
+        cluster_nodes: nodes.len(),
+        node: nodes.into_iter().find(|n| n.pubkey == CONFIG.identity),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_ip_and_ports_from_contact_info() {
+        let node: GossipNode = serde_json::from_value(serde_json::json!({
+            "pubkey": "mD1afZhSisoXfJLT8nYwSFANqjr1KPoDUEpYTEfFX1e",
+            "gossip": "64.130.50.10:8001",
+            "tpu": "64.130.50.10:8003",
+            "tpuQuic": "64.130.50.10:8009",
+            "rpc": null,
+            "version": "3.0.14",
+            "featureSet": 3604001754u32,
+            "shredVersion": 50093
+        }))
+        .unwrap();
+
+        assert_eq!(node.ip(), Some("64.130.50.10"));
+        assert_eq!(node.ports(), [("gossip", 8001), ("tpu", 8003), ("tpu_quic", 8009)]);
+        assert_eq!(split_host_port("[2001:db8::1]:8001"), Some(("2001:db8::1", 8001)));
+        assert_eq!(split_host_port("64.130.50.10"), None);
+    }
+}
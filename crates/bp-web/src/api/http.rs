@@ -1,12 +1,24 @@
 //! HTTP client for SSR
 //! Uses reqwest on server with connection pooling and caching.
 //! All data fetching uses server functions, so no client-side HTTP is needed.
+//!
+//! Every upstream call goes through here: requests carry a `bp-web/<version>`
+//! user agent, each host gets a per-minute request budget (calls over it are
+//! refused locally rather than spending the upstream's quota), and per-host
+//! counters are exported in Prometheus format at `/metrics`.
+//!
+//! Cached calls also keep their last good response (see `get_last_good_age`). When the
+//! upstream is down (or the budget is spent) that copy is served instead of nothing;
+//! callers that need to say how old it is wrap the fetch in `track_staleness`.
 
 #[cfg(feature = "ssr")]
 mod ssr {
+    use chrono::{DateTime, Utc};
     use serde::de::DeserializeOwned;
-    use std::collections::HashMap;
-    use std::sync::RwLock;
+    use std::cell::Cell;
+    use std::collections::{BTreeMap, HashMap};
+    use std::future::Future;
+    use std::sync::{Mutex, RwLock};
     use std::time::{Duration, Instant};
 
     /// Shared HTTP client for connection pooling
@@ -15,12 +27,27 @@ mod ssr {
     /// Simple in-memory cache with TTL
     static CACHE: std::sync::OnceLock<RwLock<HashMap<String, CacheEntry>>> = std::sync::OnceLock::new();
 
+    /// Last good response per cache key, kept past its TTL as an outage fallback
+    static LAST_GOOD: std::sync::OnceLock<RwLock<HashMap<String, LastGood>>> = std::sync::OnceLock::new();
+
+    /// Per-host budget and counters
+    static HOSTS: Mutex<BTreeMap<String, HostStats>> = Mutex::new(BTreeMap::new());
+
     // Cache configuration
     const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60); // 1 minute default
     const RPC_CACHE_TTL: Duration = Duration::from_secs(300); // 5 minutes for heavy RPC calls
+    const EPOCH_INFO_CACHE_TTL: Duration = Duration::from_secs(30); // slot position moves ~75 slots per 30s
     const SFDP_CACHE_TTL: Duration = Duration::from_secs(3600); // 1 hour for SFDP (rarely changes)
     const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
     const MAX_CACHE_ENTRIES: usize = 50; // Hard limit to prevent DoS
+    const LAST_GOOD_MAX_AGE: Duration = Duration::from_secs(24 * 3600); // older copies are dropped, not served
+    const EPOCH_INFO_LAST_GOOD_MAX_AGE: Duration = Duration::from_secs(600); // a stale slot misplaces everything timed from it
+
+    // Request budgets (override with HTTP_BUDGET_PER_MINUTE)
+    const DEFAULT_BUDGET_PER_MINUTE: u32 = 120;
+    const BUDGET_WINDOW: Duration = Duration::from_secs(60);
+
+    const USER_AGENT: &str = concat!("bp-web/", env!("CARGO_PKG_VERSION"), " (+https://blockparliament.com)");
 
     struct CacheEntry {
         data: String,
@@ -28,9 +55,74 @@ mod ssr {
         inserted_at: Instant, // For LRU eviction
     }
 
-    /// Determine cache TTL based on URL patterns
+    struct LastGood {
+        data: String,
+        fetched_at: DateTime<Utc>,
+        stored_at: Instant,
+    }
+
+    tokio::task_local! {
+        /// Fetch time of the oldest last-good response served inside `track_staleness`
+        static OLDEST_SERVED: Cell<Option<DateTime<Utc>>>;
+    }
+
+    /// Requests in the current budget window
+    #[derive(Debug, Clone, Copy)]
+    struct Budget {
+        window_start: Instant,
+        used: u32,
+    }
+
+    impl Budget {
+        fn new(now: Instant) -> Self {
+            Self {
+                window_start: now,
+                used: 0,
+            }
+        }
+
+        /// Spend one request if the window has room (a new window starts every minute)
+        fn try_take(&mut self, now: Instant, limit: u32) -> bool {
+            if now.duration_since(self.window_start) >= BUDGET_WINDOW {
+                *self = Self::new(now);
+            }
+            if self.used >= limit {
+                return false;
+            }
+            self.used += 1;
+            true
+        }
+    }
+
+    /// Counters for one upstream host (monotonic since startup)
+    #[derive(Debug, Clone, Copy)]
+    struct HostStats {
+        requests: u64,
+        cache_hits: u64,
+        errors: u64,
+        budget_rejections: u64,
+        stale_served: u64,
+        budget: Budget,
+    }
+
+    impl HostStats {
+        fn new(now: Instant) -> Self {
+            Self {
+                requests: 0,
+                cache_hits: 0,
+                errors: 0,
+                budget_rejections: 0,
+                stale_served: 0,
+                budget: Budget::new(now),
+            }
+        }
+    }
+
+    /// Determine cache TTL based on URL patterns (POST cache keys include the request body)
     fn get_ttl_for_url(url: &str) -> Duration {
-        if url.contains("api.mainnet-beta.solana.com") {
+        if url.contains("api.mainnet-beta.solana.com") && url.contains("\"getEpochInfo\"") {
+            EPOCH_INFO_CACHE_TTL
+        } else if url.contains("api.mainnet-beta.solana.com") {
             RPC_CACHE_TTL
         } else if url.contains("api.solana.org") && url.contains("sfdp") {
             SFDP_CACHE_TTL
@@ -39,11 +131,21 @@ mod ssr {
         }
     }
 
+    /// How long a last good response may stand in for a failed fetch
+    fn get_last_good_age(key: &str) -> Duration {
+        if key.contains("api.mainnet-beta.solana.com") && key.contains("\"getEpochInfo\"") {
+            EPOCH_INFO_LAST_GOOD_MAX_AGE
+        } else {
+            LAST_GOOD_MAX_AGE
+        }
+    }
+
     fn get_client() -> &'static reqwest::Client {
         HTTP_CLIENT.get_or_init(|| {
             reqwest::Client::builder()
                 .timeout(REQUEST_TIMEOUT)
                 .pool_max_idle_per_host(5)
+                .user_agent(USER_AGENT)
                 .build()
                 .expect("failed to create HTTP client")
         })
@@ -53,10 +155,58 @@ mod ssr {
         CACHE.get_or_init(|| RwLock::new(HashMap::new()))
     }
 
+    fn budget_per_minute() -> u32 {
+        static BUDGET: std::sync::OnceLock<u32> = std::sync::OnceLock::new();
+        *BUDGET.get_or_init(|| {
+            std::env::var("HTTP_BUDGET_PER_MINUTE")
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(DEFAULT_BUDGET_PER_MINUTE)
+        })
+    }
+
+    /// Host a URL (or `url:body` POST cache key) is sent to
+    fn host_of(url: &str) -> String {
+        url.split_once("://")
+            .and_then(|(_, rest)| rest.split(['/', ':', '?', '#']).next())
+            .filter(|host| !host.is_empty())
+            .unwrap_or("unknown")
+            .to_string()
+    }
+
+    fn with_host<R>(url: &str, f: impl FnOnce(&mut HostStats) -> R) -> R {
+        let now = Instant::now();
+        let mut hosts = HOSTS.lock().unwrap_or_else(|e| e.into_inner());
+        f(hosts.entry(host_of(url)).or_insert_with(|| HostStats::new(now)))
+    }
+
+    /// Take a request from the host's budget, counting the request (or the refusal)
+    fn admit(url: &str) -> bool {
+        let limit = budget_per_minute();
+        let admitted = with_host(url, |stats| {
+            if stats.budget.try_take(Instant::now(), limit) {
+                stats.requests += 1;
+                true
+            } else {
+                stats.budget_rejections += 1;
+                false
+            }
+        });
+        if !admitted {
+            eprintln!("HTTP budget exhausted for {} ({} requests/minute)", host_of(url), limit);
+        }
+        admitted
+    }
+
+    fn record_error(url: &str) {
+        with_host(url, |stats| stats.errors += 1);
+    }
+
     fn get_cached(url: &str) -> Option<String> {
         let cache = get_cache().read().ok()?;
         let entry = cache.get(url)?;
         if entry.expires_at > Instant::now() {
+            with_host(url, |stats| stats.cache_hits += 1);
             Some(entry.data.clone())
         } else {
             None
@@ -90,38 +240,127 @@ mod ssr {
         }
     }
 
-    pub async fn get_json<T: DeserializeOwned>(url: &str) -> Option<T> {
-        // Check cache first
-        if let Some(cached) = get_cached(url) {
-            return serde_json::from_str(&cached).ok();
+    fn get_last_good_store() -> &'static RwLock<HashMap<String, LastGood>> {
+        LAST_GOOD.get_or_init(|| RwLock::new(HashMap::new()))
+    }
+
+    /// Keep a successful response as the fallback for `key`
+    fn remember(key: &str, data: &str) {
+        if let Ok(mut store) = get_last_good_store().write() {
+            let now = Instant::now();
+            store.retain(|_, v| now.duration_since(v.stored_at) < LAST_GOOD_MAX_AGE);
+            if !store.contains_key(key)
+                && store.len() >= MAX_CACHE_ENTRIES
+                && let Some(oldest_key) = store.iter().min_by_key(|(_, v)| v.stored_at).map(|(k, _)| k.clone())
+            {
+                store.remove(&oldest_key);
+            }
+            store.insert(
+                key.to_string(),
+                LastGood {
+                    data: data.to_string(),
+                    fetched_at: Utc::now(),
+                    stored_at: now,
+                },
+            );
         }
+    }
 
-        let response = get_client()
-            .get(url)
-            .header("Accept", "application/json")
-            .send()
+    /// The last good response for `key` after a failed fetch, if one is recent enough.
+    /// Counted per host and reported to an enclosing `track_staleness`.
+    fn last_good(key: &str) -> Option<String> {
+        let store = get_last_good_store().read().ok()?;
+        let entry = store
+            .get(key)
+            .filter(|v| v.stored_at.elapsed() < get_last_good_age(key))?;
+        eprintln!(
+            "Serving last good response for {} (fetched {})",
+            host_of(key),
+            entry.fetched_at.format("%Y-%m-%d %H:%M:%S UTC")
+        );
+        with_host(key, |stats| stats.stale_served += 1);
+        let _ = OLDEST_SERVED.try_with(|oldest| {
+            let fetched_at = oldest.get().map_or(entry.fetched_at, |t| t.min(entry.fetched_at));
+            oldest.set(Some(fetched_at));
+        });
+        Some(entry.data.clone())
+    }
+
+    /// Run `fetch`, also returning when the oldest last-good response it fell back on
+    /// was fetched (`None` when every upstream call was answered fresh or from cache)
+    pub async fn track_staleness<F: Future>(fetch: F) -> (F::Output, Option<DateTime<Utc>>) {
+        OLDEST_SERVED
+            .scope(Cell::new(None), async {
+                let output = fetch.await;
+                (output, OLDEST_SERVED.with(Cell::get))
+            })
             .await
-            .map_err(|e| eprintln!("HTTP request failed for {}: {}", url, e))
-            .ok()?;
+    }
 
-        if !response.status().is_success() {
-            eprintln!("HTTP error for {}: {}", url, response.status());
+    /// Send a request within the host's budget; None (logged and counted) on refusal,
+    /// transport failure or a non-success status
+    async fn send(request: reqwest::RequestBuilder, method: &str, url: &str) -> Option<reqwest::Response> {
+        if !admit(url) {
             return None;
         }
+        match request.send().await {
+            Ok(response) if response.status().is_success() => Some(response),
+            Ok(response) => {
+                eprintln!("HTTP error for {} {}: {}", method, url, response.status());
+                record_error(url);
+                None
+            }
+            Err(e) => {
+                eprintln!("HTTP {} failed for {}: {}", method, url, e);
+                record_error(url);
+                None
+            }
+        }
+    }
 
-        let text = response
+    async fn read_text(response: reqwest::Response, url: &str) -> Option<String> {
+        response
             .text()
             .await
-            .map_err(|e| eprintln!("Failed to read response body: {}", e))
-            .ok()?;
+            .map_err(|e| {
+                eprintln!("Failed to read response body: {}", e);
+                record_error(url);
+            })
+            .ok()
+    }
+
+    fn parse_json<T: DeserializeOwned>(text: &str, url: &str) -> Option<T> {
+        serde_json::from_str(text)
+            .map_err(|e| {
+                eprintln!("JSON parse error for {}: {}", url, e);
+                record_error(url);
+            })
+            .ok()
+    }
+
+    pub async fn get_json<T: DeserializeOwned>(url: &str) -> Option<T> {
+        // Check cache first
+        if let Some(cached) = get_cached(url) {
+            return serde_json::from_str(&cached).ok();
+        }
+
+        let request = get_client().get(url).header("Accept", "application/json");
+        let fetched = match send(request, "GET", url).await {
+            Some(response) => read_text(response, url).await,
+            None => None,
+        };
+        let Some(text) = fetched else {
+            return serde_json::from_str(&last_good(url)?).ok();
+        };
 
         // Parse JSON first - only cache if parsing succeeds
-        let parsed: T = serde_json::from_str(&text)
-            .map_err(|e| eprintln!("JSON parse error for {}: {}", url, e))
-            .ok()?;
+        let Some(parsed) = parse_json::<T>(&text, url) else {
+            return serde_json::from_str(&last_good(url)?).ok();
+        };
 
         // Cache only after successful parse
         let ttl = get_ttl_for_url(url);
+        remember(url, &text);
         set_cached(url, text, ttl);
 
         Some(parsed)
@@ -133,72 +372,90 @@ mod ssr {
             return Some(cached);
         }
 
-        let response = get_client()
-            .get(url)
-            .header("Accept", "application/json")
-            .send()
-            .await
-            .map_err(|e| eprintln!("HTTP request failed for {}: {}", url, e))
-            .ok()?;
-
-        if !response.status().is_success() {
-            eprintln!("HTTP error for {}: {}", url, response.status());
-            return None;
-        }
-
-        let text = response
-            .text()
-            .await
-            .map_err(|e| eprintln!("Failed to read response body: {}", e))
-            .ok()?;
+        let request = get_client().get(url).header("Accept", "application/json");
+        let fetched = match send(request, "GET", url).await {
+            Some(response) => read_text(response, url).await,
+            None => None,
+        };
+        let Some(text) = fetched else {
+            return last_good(url);
+        };
 
         // Basic validation: don't cache HTML error pages
         if text.starts_with("<!DOCTYPE") || text.starts_with("<html") {
             eprintln!("Received HTML instead of JSON for {}", url);
-            return None;
+            record_error(url);
+            return last_good(url);
         }
 
         // Cache the response
         let ttl = get_ttl_for_url(url);
+        remember(url, &text);
         set_cached(url, text.clone(), ttl);
 
         Some(text)
     }
 
     pub async fn post_json<T: DeserializeOwned>(url: &str, body: &str) -> Option<T> {
-        let response = get_client()
+        let request = get_client()
             .post(url)
             .header("Content-Type", "application/json")
-            .body(body.to_string())
-            .send()
-            .await
-            .map_err(|e| eprintln!("HTTP POST failed for {}: {}", url, e))
-            .ok()?;
-
-        if !response.status().is_success() {
-            eprintln!("HTTP error for {}: {}", url, response.status());
-            return None;
-        }
-
-        let text = response
-            .text()
-            .await
-            .map_err(|e| eprintln!("Failed to read response body: {}", e))
-            .ok()?;
+            .body(body.to_string());
+        let cache_key = format!("{}:{}", url, body);
+        let fetched = match send(request, "POST", url).await {
+            Some(response) => read_text(response, url).await,
+            None => None,
+        };
+        let Some(text) = fetched else {
+            return serde_json::from_str(&last_good(&cache_key)?).ok();
+        };
 
         // Parse and cache POST responses (they're idempotent RPC calls)
-        let parsed: T = serde_json::from_str(&text)
-            .map_err(|e| eprintln!("JSON parse error for {}: {}", url, e))
-            .ok()?;
+        let Some(parsed) = parse_json::<T>(&text, url) else {
+            return serde_json::from_str(&last_good(&cache_key)?).ok();
+        };
 
         // Cache RPC POST responses
-        let cache_key = format!("{}:{}", url, body);
-        let ttl = get_ttl_for_url(url);
+        let ttl = get_ttl_for_url(&cache_key);
+        remember(&cache_key, &text);
         set_cached(&cache_key, text, ttl);
 
         Some(parsed)
     }
 
+    /// GET JSON with an `Authorization` header, without caching (keyed lookup APIs; the
+    /// credential stays out of cache keys and logged URLs)
+    pub async fn get_json_authorized<T: DeserializeOwned>(url: &str, authorization: &str) -> Option<T> {
+        let request = get_client()
+            .get(url)
+            .header("Accept", "application/json")
+            .header("Authorization", authorization);
+        let text = read_text(send(request, "GET", url).await?, url).await?;
+        parse_json(&text, url)
+    }
+
+    /// POST a JSON body without caching (webhooks). Returns whether the server accepted it.
+    pub async fn post_json_uncached(url: &str, body: &str) -> bool {
+        let request = get_client()
+            .post(url)
+            .header("Content-Type", "application/json")
+            .body(body.to_string());
+        send(request, "POST", url).await.is_some()
+    }
+
+    /// GET without caching, discarding the body (heartbeat pings). Returns whether the
+    /// server answered with a success status.
+    pub async fn get_uncached(url: &str) -> bool {
+        send(get_client().get(url), "GET", url).await.is_some()
+    }
+
+    /// POST a urlencoded form without caching and parse the JSON response (verification APIs)
+    pub async fn post_form<T: DeserializeOwned>(url: &str, form: &[(&str, &str)]) -> Option<T> {
+        let request = get_client().post(url).form(form);
+        let text = read_text(send(request, "POST", url).await?, url).await?;
+        parse_json(&text, url)
+    }
+
     /// Check POST cache (for RPC calls)
     pub async fn post_json_cached<T: DeserializeOwned>(url: &str, body: &str) -> Option<T> {
         let cache_key = format!("{}:{}", url, body);
@@ -210,6 +467,98 @@ mod ssr {
 
         post_json(url, body).await
     }
+
+    /// Upstream counters in Prometheus text format (served at `/metrics`)
+    pub fn render_metrics() -> String {
+        let hosts = HOSTS.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        let series: [(&str, &str, fn(&HostStats) -> u64); 5] = [
+            ("requests", "Requests sent to upstream APIs", |s| s.requests),
+            ("cache_hits", "Upstream calls answered from the response cache", |s| {
+                s.cache_hits
+            }),
+            (
+                "errors",
+                "Upstream requests that failed (transport, status or parse)",
+                |s| s.errors,
+            ),
+            (
+                "budget_rejections",
+                "Upstream requests refused because the host's per-minute budget was spent",
+                |s| s.budget_rejections,
+            ),
+            (
+                "stale_served",
+                "Failed upstream calls answered with the last good response",
+                |s| s.stale_served,
+            ),
+        ];
+
+        let mut out = String::new();
+        for (name, help, value) in series {
+            out.push_str(&format!(
+                "# HELP bp_web_upstream_{name}_total {help}\n# TYPE bp_web_upstream_{name}_total counter\n"
+            ));
+            for (host, stats) in &hosts {
+                out.push_str(&format!(
+                    "bp_web_upstream_{}_total{{host=\"{}\"}} {}\n",
+                    name,
+                    host.replace('\\', "\\\\").replace('"', "\\\""),
+                    value(stats)
+                ));
+            }
+        }
+        out.push_str(&format!(
+            "# HELP bp_web_upstream_budget_per_minute Requests allowed per upstream host per minute\n\
+             # TYPE bp_web_upstream_budget_per_minute gauge\nbp_web_upstream_budget_per_minute {}\n",
+            budget_per_minute()
+        ));
+        out
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn budget_refuses_over_limit_until_the_window_rolls() {
+            let start = Instant::now();
+            let mut budget = Budget::new(start);
+            assert!(budget.try_take(start, 2));
+            assert!(budget.try_take(start, 2));
+            assert!(!budget.try_take(start + Duration::from_secs(59), 2));
+            assert!(budget.try_take(start + BUDGET_WINDOW, 2));
+        }
+
+        #[test]
+        fn counts_per_host() {
+            assert_eq!(host_of("https://api.stakewiz.com/validator/x"), "api.stakewiz.com");
+            assert_eq!(
+                host_of("https://api.mainnet-beta.solana.com:{\"method\":1}"),
+                "api.mainnet-beta.solana.com"
+            );
+            assert_eq!(host_of("not a url"), "unknown");
+
+            assert!(admit("https://metrics-test.invalid/a"));
+            record_error("https://metrics-test.invalid/b");
+            let metrics = render_metrics();
+            assert!(metrics.contains("bp_web_upstream_requests_total{host=\"metrics-test.invalid\"} 1"));
+            assert!(metrics.contains("bp_web_upstream_errors_total{host=\"metrics-test.invalid\"} 1"));
+        }
+
+        #[tokio::test]
+        async fn falls_back_to_last_good_and_reports_its_age() {
+            let key = "https://last-good-test.invalid/v1";
+            assert!(last_good(key).is_none());
+            remember(key, "{\"ok\":true}");
+
+            let (served, as_of) = track_staleness(async { last_good(key) }).await;
+            assert_eq!(served.as_deref(), Some("{\"ok\":true}"));
+            assert!(as_of.is_some_and(|t| t <= Utc::now()));
+
+            let (_, as_of) = track_staleness(async {}).await;
+            assert!(as_of.is_none());
+        }
+    }
 }
 
 #[cfg(feature = "ssr")]
@@ -1,21 +1,34 @@
-mod http;
+mod geoip;
+mod gossip;
+pub(crate) mod http;
 mod jito;
 mod sfdp;
 mod solana_rpc;
 mod stakewiz;
 
 // Types always available (for serialization on both sides)
+pub use geoip::IpLocation;
+pub use gossip::{GossipNode, GossipPresence};
 pub use jito::{JitoEpochReward, JitoMevHistory, format_lamports_to_sol};
 pub use sfdp::SfdpStatus;
-pub use solana_rpc::NetworkComparison;
-pub use stakewiz::{StakewizValidator, format_percent, format_stake};
+pub use solana_rpc::{EpochCalendar, EpochInfo, NetworkComparison, RPC_ENDPOINT, UpcomingLeaderSlot};
+pub use stakewiz::{NetworkApy, StakewizValidator, format_percent, format_stake};
 
 // Fetch functions only on server (avoids CORS issues from client-side requests)
 #[cfg(feature = "ssr")]
+pub use geoip::lookup_ip;
+#[cfg(feature = "ssr")]
+pub use gossip::get_gossip_presence;
+#[cfg(feature = "ssr")]
+pub use http::render_metrics as render_upstream_metrics;
+#[cfg(feature = "ssr")]
 pub use jito::get_jito_mev_history;
 #[cfg(feature = "ssr")]
 pub use sfdp::get_sfdp_status;
 #[cfg(feature = "ssr")]
-pub use solana_rpc::get_network_comparison;
+pub use solana_rpc::{
+    build_epoch_calendar, get_balance, get_epoch_calendar, get_epoch_info, get_last_produced_block, get_leader_slots,
+    get_network_comparison, minutes_until_slot,
+};
 #[cfg(feature = "ssr")]
-pub use stakewiz::get_validator_data;
+pub use stakewiz::{get_network_average_apy, get_validator_data};
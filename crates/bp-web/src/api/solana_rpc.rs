@@ -1,10 +1,21 @@
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "ssr")]
+use crate::config::CONFIG;
+
 #[cfg(feature = "ssr")]
 use super::http::post_json_cached;
 
+/// Public mainnet RPC. Also used by the in-browser stake flow on /delegate.
+pub const RPC_ENDPOINT: &str = "https://api.mainnet-beta.solana.com";
+
+/// Target slot time used to project slot numbers onto the wall clock
 #[cfg(feature = "ssr")]
-const RPC_ENDPOINT: &str = "https://api.mainnet-beta.solana.com";
+const SLOT_DURATION_MS: i64 = 400;
+
+/// Upcoming leader slots returned by the epoch calendar
+#[cfg(feature = "ssr")]
+const MAX_UPCOMING_LEADER_SLOTS: usize = 16;
 
 /// Network comparison stats for a validator
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +55,227 @@ struct VoteAccount {
     activated_stake: u64,
 }
 
+/// Current epoch position and our upcoming leader slots
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema))]
+pub struct EpochCalendar {
+    pub epoch: u64,
+    pub absolute_slot: u64,
+    pub slot_index: u64,
+    pub slots_in_epoch: u64,
+    pub progress_percent: f64,
+    /// RFC 3339, projected from the remaining slots at the target slot time
+    pub estimated_end_time: String,
+    /// Leader slots still ahead of us in this epoch
+    pub leader_slots_remaining: usize,
+    pub next_leader_slots: Vec<UpcomingLeaderSlot>,
+}
+
+/// A single upcoming leader slot with its projected time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema))]
+pub struct UpcomingLeaderSlot {
+    pub slot: u64,
+    /// RFC 3339 estimate
+    pub estimated_time: String,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Deserialize)]
+struct EpochInfoResponse {
+    result: Option<EpochInfo>,
+}
+
+/// Current position in the epoch (getEpochInfo)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochInfo {
+    pub epoch: u64,
+    pub absolute_slot: u64,
+    pub slot_index: u64,
+    pub slots_in_epoch: u64,
+}
+
+impl EpochInfo {
+    /// First slot of the current epoch
+    pub fn epoch_start_slot(&self) -> u64 {
+        self.absolute_slot.saturating_sub(self.slot_index)
+    }
+
+    /// How far through the epoch we are, in percent (two decimals)
+    pub fn progress_percent(&self) -> f64 {
+        if self.slots_in_epoch == 0 {
+            return 0.0;
+        }
+        (self.slot_index as f64 / self.slots_in_epoch as f64 * 10_000.0).round() / 100.0
+    }
+
+    /// Identifies the cached epoch position a calendar is built from; it only
+    /// changes when the cached getEpochInfo response is refreshed
+    pub fn data_version(&self) -> String {
+        format!("{}-{}", self.epoch, self.absolute_slot)
+    }
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Deserialize)]
+struct LeaderScheduleResponse {
+    /// Identity -> slot indices relative to the first slot of the epoch
+    result: Option<std::collections::HashMap<String, Vec<u64>>>,
+}
+
+/// Estimated wall-clock minutes until a slot, at the target slot time
+#[cfg(feature = "ssr")]
+pub fn minutes_until_slot(current_slot: u64, slot: u64) -> u64 {
+    slot.saturating_sub(current_slot) * SLOT_DURATION_MS as u64 / 60_000
+}
+
+/// Fetch the current epoch position (cached for 30 seconds)
+#[cfg(feature = "ssr")]
+pub async fn get_epoch_info() -> Option<EpochInfo> {
+    let request = RpcRequest {
+        jsonrpc: "2.0",
+        id: 1,
+        method: "getEpochInfo",
+        params: vec![serde_json::json!({"commitment": "confirmed"})],
+    };
+    let body = serde_json::to_string(&request).ok()?;
+    post_json_cached::<EpochInfoResponse>(RPC_ENDPOINT, &body).await?.result
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Deserialize)]
+struct BalanceResponse {
+    result: Option<BalanceResult>,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Deserialize)]
+struct BalanceResult {
+    value: u64,
+}
+
+/// Fetch an account's balance in lamports (cached like other RPC calls)
+#[cfg(feature = "ssr")]
+pub async fn get_balance(address: &str) -> Option<u64> {
+    let request = RpcRequest {
+        jsonrpc: "2.0",
+        id: 1,
+        method: "getBalance",
+        params: vec![
+            serde_json::json!(address),
+            serde_json::json!({"commitment": "confirmed"}),
+        ],
+    };
+    let body = serde_json::to_string(&request).ok()?;
+    post_json_cached::<BalanceResponse>(RPC_ENDPOINT, &body)
+        .await?
+        .result
+        .map(|r| r.value)
+}
+
+/// Fetch our leader slots (absolute, ascending) for the epoch starting at `epoch_start_slot`.
+///
+/// Returns None if the schedule isn't available yet (e.g. the epoch after next).
+#[cfg(feature = "ssr")]
+pub async fn get_leader_slots(epoch_start_slot: u64) -> Option<Vec<u64>> {
+    // Query by the epoch's first slot so a cached schedule never outlives its epoch
+    let request = RpcRequest {
+        jsonrpc: "2.0",
+        id: 1,
+        method: "getLeaderSchedule",
+        params: vec![
+            serde_json::json!(epoch_start_slot),
+            serde_json::json!({"identity": CONFIG.identity}),
+        ],
+    };
+    let body = serde_json::to_string(&request).ok()?;
+    let mut schedule = post_json_cached::<LeaderScheduleResponse>(RPC_ENDPOINT, &body)
+        .await?
+        .result?;
+
+    let mut slots: Vec<u64> = schedule
+        .remove(CONFIG.identity)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|i| epoch_start_slot + i)
+        .collect();
+    slots.sort_unstable();
+    Some(slots)
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Deserialize)]
+struct BlocksResponse {
+    result: Option<Vec<u64>>,
+}
+
+/// Most recent of `leader_slots` (ours, ascending, already past) that made it into a
+/// confirmed block. Checks at most `MAX_PRODUCED_BLOCK_CHECKS` slots, newest first.
+#[cfg(feature = "ssr")]
+pub async fn get_last_produced_block(leader_slots: &[u64]) -> Option<u64> {
+    const MAX_PRODUCED_BLOCK_CHECKS: usize = 8;
+
+    for &slot in leader_slots.iter().rev().take(MAX_PRODUCED_BLOCK_CHECKS) {
+        // The first confirmed block at or after our slot; equal means we produced it
+        let request = RpcRequest {
+            jsonrpc: "2.0",
+            id: 1,
+            method: "getBlocksWithLimit",
+            params: vec![
+                serde_json::json!(slot),
+                serde_json::json!(1),
+                serde_json::json!({"commitment": "confirmed"}),
+            ],
+        };
+        let body = serde_json::to_string(&request).ok()?;
+        let blocks = post_json_cached::<BlocksResponse>(RPC_ENDPOINT, &body).await?.result?;
+        if blocks.first() == Some(&slot) {
+            return Some(slot);
+        }
+    }
+    None
+}
+
+/// Fetch the current epoch position and our next leader slots
+#[cfg(feature = "ssr")]
+pub async fn get_epoch_calendar() -> Option<EpochCalendar> {
+    Some(build_epoch_calendar(get_epoch_info().await?).await)
+}
+
+/// Build the epoch calendar for an already-fetched epoch position
+#[cfg(feature = "ssr")]
+pub async fn build_epoch_calendar(info: EpochInfo) -> EpochCalendar {
+    let epoch_start_slot = info.epoch_start_slot();
+    let leader_slots = get_leader_slots(epoch_start_slot).await.unwrap_or_default();
+
+    let now = chrono::Utc::now();
+    let slot_time = |slot: u64| {
+        let slots_ahead = slot.saturating_sub(info.absolute_slot) as i64;
+        (now + chrono::Duration::milliseconds(slots_ahead * SLOT_DURATION_MS)).to_rfc3339()
+    };
+
+    let upcoming: Vec<u64> = leader_slots.into_iter().filter(|&s| s > info.absolute_slot).collect();
+
+    EpochCalendar {
+        epoch: info.epoch,
+        absolute_slot: info.absolute_slot,
+        slot_index: info.slot_index,
+        slots_in_epoch: info.slots_in_epoch,
+        progress_percent: info.progress_percent(),
+        estimated_end_time: slot_time(epoch_start_slot + info.slots_in_epoch),
+        leader_slots_remaining: upcoming.len(),
+        next_leader_slots: upcoming
+            .iter()
+            .take(MAX_UPCOMING_LEADER_SLOTS)
+            .map(|&slot| UpcomingLeaderSlot {
+                slot,
+                estimated_time: slot_time(slot),
+            })
+            .collect(),
+    }
+}
+
 /// Fetch network comparison data using getVoteAccounts
 /// Note: Skip rate percentile is estimated using a heuristic based on typical network average
 #[cfg(feature = "ssr")]
@@ -69,6 +69,68 @@ pub async fn get_validator_data() -> Option<StakewizValidator> {
     }
 }
 
+/// Stake-weighted average APYs across active validators (Stakewiz estimates)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkApy {
+    pub staking_apy: f64,
+    pub jito_apy: f64,
+    pub total_apy: f64,
+    pub validators: usize,
+}
+
+/// The fields of a Stakewiz validator list entry needed for network averages
+#[cfg(feature = "ssr")]
+#[derive(Debug, Deserialize)]
+struct StakewizListEntry {
+    #[serde(default)]
+    activated_stake: f64,
+    #[serde(default)]
+    delinquent: bool,
+    #[serde(default)]
+    staking_apy: Option<f64>,
+    #[serde(default)]
+    jito_apy: Option<f64>,
+    #[serde(default)]
+    total_apy: Option<f64>,
+}
+
+/// Fetch all validators from Stakewiz and compute stake-weighted network APYs
+#[cfg(feature = "ssr")]
+pub async fn get_network_average_apy() -> Option<NetworkApy> {
+    let text = get_text("https://api.stakewiz.com/validators").await?;
+    let entries: Vec<StakewizListEntry> = serde_json::from_str(&text)
+        .map_err(|e| eprintln!("Stakewiz: failed to parse validator list: {}", e))
+        .ok()?;
+    stake_weighted_apy(&entries)
+}
+
+/// Stake-weighted averages over active validators with a total APY estimate
+#[cfg(feature = "ssr")]
+fn stake_weighted_apy(entries: &[StakewizListEntry]) -> Option<NetworkApy> {
+    let active: Vec<&StakewizListEntry> = entries
+        .iter()
+        .filter(|v| !v.delinquent && v.activated_stake > 0.0 && v.total_apy.is_some())
+        .collect();
+    let total_stake: f64 = active.iter().map(|v| v.activated_stake).sum();
+    if total_stake <= 0.0 {
+        return None;
+    }
+
+    let weighted = |apy: fn(&StakewizListEntry) -> Option<f64>| {
+        active
+            .iter()
+            .map(|v| apy(v).unwrap_or(0.0) * v.activated_stake)
+            .sum::<f64>()
+            / total_stake
+    };
+    Some(NetworkApy {
+        staking_apy: weighted(|v| v.staking_apy),
+        jito_apy: weighted(|v| v.jito_apy),
+        total_apy: weighted(|v| v.total_apy),
+        validators: active.len(),
+    })
+}
+
 /// Format stake in SOL with commas
 pub fn format_stake(stake: f64) -> String {
     let rounded = stake.round() as i64;
@@ -87,3 +149,34 @@ pub fn format_stake(stake: f64) -> String {
 pub fn format_percent(value: f64, decimals: usize) -> String {
     format!("{:.prec$}%", value, prec = decimals)
 }
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::*;
+
+    fn entry(stake: f64, delinquent: bool, total_apy: Option<f64>) -> StakewizListEntry {
+        StakewizListEntry {
+            activated_stake: stake,
+            delinquent,
+            staking_apy: total_apy.map(|a| a - 1.0),
+            jito_apy: total_apy.map(|_| 1.0),
+            total_apy,
+        }
+    }
+
+    #[test]
+    fn network_apy_is_stake_weighted_over_active_validators() {
+        let entries = vec![
+            entry(300.0, false, Some(8.0)),
+            entry(100.0, false, Some(4.0)),
+            entry(1_000.0, true, Some(100.0)),
+            entry(1_000.0, false, None),
+        ];
+        let apy = stake_weighted_apy(&entries).unwrap();
+        assert_eq!(apy.validators, 2);
+        assert!((apy.total_apy - 7.0).abs() < 1e-9);
+        assert!((apy.staking_apy - 6.0).abs() < 1e-9);
+        assert!((apy.jito_apy - 1.0).abs() < 1e-9);
+        assert!(stake_weighted_apy(&[]).is_none());
+    }
+}
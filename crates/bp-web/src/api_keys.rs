@@ -0,0 +1,310 @@
+//! API keys for programmatic access to the financial JSON endpoints.
+//!
+//! Keys are shown once when created (`bp-web --create-api-key`) and stored only as
+//! SHA-256 hashes in bp.sqlite. Each key carries scopes and its own per-minute rate
+//! limit, so an analytics frontend can read the books without the human password and
+//! without being able to reach the SQL console or the admin pages.
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use sha2::{Digest, Sha256};
+
+use crate::db::pool;
+use crate::rate_limit::RateLimiter;
+
+/// Prefix of every key, so leaked keys are recognizable in logs and secret scanners
+pub const KEY_PREFIX: &str = "bpk_";
+/// Header carrying a key (`Authorization: Bearer <key>` works too)
+pub const API_KEY_HEADER: &str = "x-api-key";
+pub const DEFAULT_RATE_LIMIT_PER_MINUTE: u32 = 60;
+
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+const KEY_BYTES: usize = 32;
+
+/// What a key may call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    /// JSON reads: timeline chunks, epoch and delegator tables, GraphQL queries
+    Read,
+    /// Read-only SQL console queries
+    Sql,
+}
+
+impl Scope {
+    pub const ALL: [Scope; 2] = [Scope::Read, Scope::Sql];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Sql => "sql",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|scope| scope.as_str() == s.trim())
+    }
+}
+
+/// Parse a comma-separated scope list (`read,sql`); errors on unknown scopes
+pub fn parse_scopes(list: &str) -> Result<Vec<Scope>, String> {
+    let mut scopes = Vec::new();
+    for part in list.split(',').filter(|p| !p.trim().is_empty()) {
+        let scope = Scope::parse(part).ok_or_else(|| {
+            let known: Vec<&str> = Scope::ALL.iter().map(|s| s.as_str()).collect();
+            format!("unknown scope '{}' (expected {})", part.trim(), known.join(", "))
+        })?;
+        if !scopes.contains(&scope) {
+            scopes.push(scope);
+        }
+    }
+    if scopes.is_empty() {
+        return Err("at least one scope is required".to_string());
+    }
+    Ok(scopes)
+}
+
+/// A stored key (never the key itself)
+#[derive(Debug, Clone)]
+pub struct ApiKey {
+    pub id: i64,
+    pub name: String,
+    pub scopes: Vec<Scope>,
+    pub rate_limit_per_minute: u32,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+}
+
+const KEY_COLUMNS: &str = "id, name, scopes, rate_limit_per_minute, created_at, last_used_at";
+type KeyRow = (i64, String, String, i64, String, Option<String>);
+
+impl ApiKey {
+    fn from_row((id, name, scopes, rate_limit, created_at, last_used_at): KeyRow) -> Self {
+        Self {
+            id,
+            name,
+            scopes: scopes.split(',').filter_map(Scope::parse).collect(),
+            rate_limit_per_minute: rate_limit.max(0) as u32,
+            created_at,
+            last_used_at,
+        }
+    }
+}
+
+/// Why a request carrying a key was refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessError {
+    /// Unknown or revoked key
+    InvalidKey,
+    /// Valid key without the scope the endpoint needs
+    MissingScope,
+    RateLimited,
+    /// bp.sqlite could not be read
+    Unavailable,
+}
+
+/// The key sent with a request, if any
+pub fn presented_key(headers: &axum::http::HeaderMap) -> Option<&str> {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok()).map(str::trim);
+    header(API_KEY_HEADER)
+        .or_else(|| {
+            header("authorization")
+                .and_then(|v| v.strip_prefix("Bearer "))
+                .map(str::trim)
+        })
+        .filter(|key| key.starts_with(KEY_PREFIX))
+}
+
+fn hash_key(key: &str) -> String {
+    Sha256::digest(key.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn generate_key() -> std::io::Result<String> {
+    let mut bytes = [0u8; KEY_BYTES];
+    std::fs::File::open("/dev/urandom")?.read_exact(&mut bytes)?;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    Ok(format!("{}{}", KEY_PREFIX, hex))
+}
+
+fn scopes_column(scopes: &[Scope]) -> String {
+    scopes.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(",")
+}
+
+/// Create a key; returns the plaintext key, which is not stored anywhere
+pub async fn create(name: &str, scopes: &[Scope], rate_limit_per_minute: u32) -> anyhow::Result<String> {
+    let key = generate_key()?;
+    sqlx::query("INSERT INTO api_keys (name, key_hash, scopes, rate_limit_per_minute) VALUES (?, ?, ?, ?)")
+        .bind(name)
+        .bind(hash_key(&key))
+        .bind(scopes_column(scopes))
+        .bind(rate_limit_per_minute as i64)
+        .execute(pool())
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create API key '{}': {}", name, e))?;
+    Ok(key)
+}
+
+/// Revoke a key by name; returns false if there was no active key with that name
+pub async fn revoke(name: &str) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("UPDATE api_keys SET revoked_at = datetime('now') WHERE name = ? AND revoked_at IS NULL")
+        .bind(name)
+        .execute(pool())
+        .await?;
+    prune_limiters().await;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Active keys, oldest first
+pub async fn list() -> Result<Vec<ApiKey>, sqlx::Error> {
+    let rows: Vec<KeyRow> = sqlx::query_as(&format!(
+        "SELECT {} FROM api_keys WHERE revoked_at IS NULL ORDER BY id",
+        KEY_COLUMNS
+    ))
+    .fetch_all(pool())
+    .await?;
+    Ok(rows.into_iter().map(ApiKey::from_row).collect())
+}
+
+async fn find_active(key: &str) -> Result<Option<ApiKey>, sqlx::Error> {
+    let row: Option<KeyRow> = sqlx::query_as(&format!(
+        "SELECT {} FROM api_keys WHERE key_hash = ? AND revoked_at IS NULL",
+        KEY_COLUMNS
+    ))
+    .bind(hash_key(key))
+    .fetch_optional(pool())
+    .await?;
+    Ok(row.map(ApiKey::from_row))
+}
+
+/// One limiter per (key id, limit), so a changed limit starts a limiter of the new size
+static LIMITERS: OnceLock<Mutex<HashMap<(i64, u32), RateLimiter>>> = OnceLock::new();
+
+fn take_rate_limit(key: &ApiKey) -> bool {
+    let limiters = LIMITERS.get_or_init(|| Mutex::new(HashMap::new()));
+    let Ok(mut guard) = limiters.lock() else {
+        return false;
+    };
+    let limiter = guard
+        .entry((key.id, key.rate_limit_per_minute))
+        .or_insert_with(|| RateLimiter::new(RATE_WINDOW, key.rate_limit_per_minute as usize));
+    let now = Instant::now();
+    let id = key.id.to_string();
+    if !limiter.allows(&id, now) {
+        return false;
+    }
+    limiter.record(&id, now);
+    true
+}
+
+/// Keep only the limiters of `active` keys at their current limits
+fn retain_limiters(active: &[ApiKey]) {
+    let Some(limiters) = LIMITERS.get() else {
+        return;
+    };
+    if let Ok(mut guard) = limiters.lock() {
+        guard.retain(|(id, limit), _| active.iter().any(|k| k.id == *id && k.rate_limit_per_minute == *limit));
+    }
+}
+
+/// Drop the limiters of revoked keys and superseded limits (after a revoke and on config
+/// reload, which also picks up keys revoked from the command line)
+pub async fn prune_limiters() {
+    match list().await {
+        Ok(active) => retain_limiters(&active),
+        Err(e) => eprintln!("[api_keys] Error listing keys to prune rate limiters: {}", e),
+    }
+}
+
+/// Check a presented key for `scope`, counting the request against its rate limit
+pub async fn authorize(key: &str, scope: Scope) -> Result<ApiKey, AccessError> {
+    let api_key = match find_active(key).await {
+        Ok(Some(api_key)) => api_key,
+        Ok(None) => return Err(AccessError::InvalidKey),
+        Err(e) => {
+            eprintln!("[api_keys] Error looking up key: {}", e);
+            return Err(AccessError::Unavailable);
+        }
+    };
+    if !api_key.scopes.contains(&scope) {
+        return Err(AccessError::MissingScope);
+    }
+    if !take_rate_limit(&api_key) {
+        return Err(AccessError::RateLimited);
+    }
+    if let Err(e) = sqlx::query("UPDATE api_keys SET last_used_at = datetime('now') WHERE id = ?")
+        .bind(api_key.id)
+        .execute(pool())
+        .await
+    {
+        eprintln!("[api_keys] Error recording key use: {}", e);
+    }
+    Ok(api_key)
+}
+
+/// CORS origins allowed to call the JSON endpoints (CORS_ALLOWED_ORIGINS, comma-separated).
+/// Empty means no cross-origin access.
+pub fn cors_allowed_origins() -> Vec<String> {
+    std::env::var("CORS_ALLOWED_ORIGINS")
+        .unwrap_or_default()
+        .split(',')
+        .map(|origin| origin.trim().trim_end_matches('/').to_string())
+        .filter(|origin| !origin.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scopes_parse_and_reject_unknown_names() {
+        assert_eq!(parse_scopes("read, sql,read").unwrap(), vec![Scope::Read, Scope::Sql]);
+        assert!(parse_scopes("read,admin").unwrap_err().contains("admin"));
+        assert!(parse_scopes(" , ").is_err());
+    }
+
+    #[test]
+    fn pruning_drops_revoked_keys_and_old_limits() {
+        let key = |id: i64, rate_limit_per_minute: u32| ApiKey {
+            id,
+            name: format!("key-{}", id),
+            scopes: vec![Scope::Read],
+            rate_limit_per_minute,
+            created_at: String::new(),
+            last_used_at: None,
+        };
+        let (kept, revoked, raised) = (key(9_001, 60), key(9_002, 60), key(9_003, 10));
+        for k in [&kept, &revoked, &raised] {
+            assert!(take_rate_limit(k));
+        }
+
+        retain_limiters(&[kept.clone(), key(9_003, 20)]);
+        let guard = LIMITERS.get().unwrap().lock().unwrap();
+        assert!(guard.contains_key(&(9_001, 60)));
+        assert!(!guard.contains_key(&(9_002, 60)));
+        assert!(!guard.contains_key(&(9_003, 10)));
+    }
+
+    #[test]
+    fn keys_are_read_from_either_header() {
+        let key = generate_key().unwrap();
+        assert!(key.starts_with(KEY_PREFIX) && key.len() == KEY_PREFIX.len() + KEY_BYTES * 2);
+        assert_ne!(hash_key(&key), key);
+
+        let mut headers = axum::http::HeaderMap::new();
+        headers.insert("authorization", format!("Bearer {}", key).parse().unwrap());
+        assert_eq!(presented_key(&headers), Some(key.as_str()));
+        headers.insert(API_KEY_HEADER, "bpk_other".parse().unwrap());
+        assert_eq!(presented_key(&headers), Some("bpk_other"));
+
+        // Basic Auth is left to the password check
+        let mut basic = axum::http::HeaderMap::new();
+        basic.insert("authorization", "Basic dTpw".parse().unwrap());
+        assert_eq!(presented_key(&basic), None);
+    }
+}
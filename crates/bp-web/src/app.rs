@@ -3,7 +3,7 @@ use leptos_meta::provide_meta_context;
 use leptos_router::components::{Route, Router, Routes};
 use leptos_router::path;
 
-use crate::pages::{DelegatePage, HomePage, SecurityPage};
+use crate::pages::{ContactPage, DelegatePage, HomePage, InfrastructurePage, MarkdownPage, SecurityPage};
 
 #[component]
 pub fn App() -> impl IntoView {
@@ -15,6 +15,10 @@ pub fn App() -> impl IntoView {
                 <Route path=path!("/") view=HomePage />
                 <Route path=path!("/delegate") view=DelegatePage />
                 <Route path=path!("/security") view=SecurityPage />
+                <Route path=path!("/contact") view=ContactPage />
+                <Route path=path!("/infrastructure") view=InfrastructurePage />
+                // Markdown pages from the content directory; keep last so the pages above win
+                <Route path=path!("/:slug") view=MarkdownPage />
             </Routes>
         </Router>
     }
@@ -0,0 +1,136 @@
+//! Embeddable SVG status badge (`/badge.svg`), in the flat shields.io style.
+//! Rendered from the latest cached metrics snapshot, so serving it never calls an API.
+
+use crate::api::{StakewizValidator, format_percent, format_stake};
+
+const COLOR_LABEL: &str = "#555";
+const COLOR_ACTIVE: &str = "#4c1";
+const COLOR_DELINQUENT: &str = "#e05d44";
+const COLOR_INFO: &str = "#007ec6";
+const COLOR_UNKNOWN: &str = "#9f9f9f";
+
+/// Approximate advance width of an 11px Verdana character, and the padding on each side
+const CHAR_WIDTH: f64 = 6.5;
+const TEXT_PADDING: f64 = 6.0;
+
+/// What the badge shows (`?metric=`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadgeMetric {
+    Status,
+    Apy,
+    Stake,
+}
+
+impl BadgeMetric {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "status" => Some(Self::Status),
+            "apy" => Some(Self::Apy),
+            "stake" => Some(Self::Stake),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Status => "status",
+            Self::Apy => "apy",
+            Self::Stake => "stake",
+        }
+    }
+}
+
+/// Badge text and value colour
+#[derive(Debug, Clone, PartialEq)]
+pub struct Badge {
+    pub label: String,
+    pub value: String,
+    pub color: &'static str,
+}
+
+impl Badge {
+    /// Badge for `metric`, or an "unknown" badge when no metrics have been ingested yet
+    pub fn for_metric(label: &str, metric: BadgeMetric, validator: Option<&StakewizValidator>) -> Self {
+        let (value, color) = match (metric, validator) {
+            (_, None) => ("unknown".to_string(), COLOR_UNKNOWN),
+            (BadgeMetric::Status, Some(v)) if v.delinquent => ("delinquent".to_string(), COLOR_DELINQUENT),
+            (BadgeMetric::Status, Some(_)) => ("active".to_string(), COLOR_ACTIVE),
+            (BadgeMetric::Apy, Some(v)) => (format!("{} APY", format_percent(v.total_apy, 2)), COLOR_INFO),
+            (BadgeMetric::Stake, Some(v)) => (format!("{} SOL", format_stake(v.activated_stake)), COLOR_INFO),
+        };
+        Self {
+            label: label.to_string(),
+            value,
+            color,
+        }
+    }
+
+    /// Render as a standalone SVG document
+    pub fn to_svg(&self) -> String {
+        let label_width = text_width(&self.label);
+        let value_width = text_width(&self.value);
+        let width = label_width + value_width;
+        let label = xml_escape(&self.label);
+        let value = xml_escape(&self.value);
+
+        format!(
+            concat!(
+                r##"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="20" role="img" aria-label="{label}: {value}">"##,
+                r##"<title>{label}: {value}</title>"##,
+                r##"<linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient>"##,
+                r##"<clipPath id="r"><rect width="{w}" height="20" rx="3" fill="#fff"/></clipPath>"##,
+                r##"<g clip-path="url(#r)"><rect width="{lw}" height="20" fill="{label_color}"/><rect x="{lw}" width="{vw}" height="20" fill="{color}"/><rect width="{w}" height="20" fill="url(#s)"/></g>"##,
+                r##"<g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">"##,
+                r##"<text x="{lx}" y="15" fill="#010101" fill-opacity=".3">{label}</text><text x="{lx}" y="14">{label}</text>"##,
+                r##"<text x="{vx}" y="15" fill="#010101" fill-opacity=".3">{value}</text><text x="{vx}" y="14">{value}</text>"##,
+                r##"</g></svg>"##
+            ),
+            w = width,
+            lw = label_width,
+            vw = value_width,
+            lx = label_width / 2.0,
+            vx = label_width + value_width / 2.0,
+            label_color = COLOR_LABEL,
+            color = self.color,
+            label = label,
+            value = value,
+        )
+    }
+}
+
+fn text_width(text: &str) -> f64 {
+    (text.chars().count() as f64 * CHAR_WIDTH + 2.0 * TEXT_PADDING).round()
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_without_snapshot() {
+        let badge = Badge::for_metric("Block Parliament", BadgeMetric::Apy, None);
+        assert_eq!(badge.value, "unknown");
+        assert_eq!(badge.color, COLOR_UNKNOWN);
+    }
+
+    #[test]
+    fn svg_escapes_text_and_sizes_segments() {
+        let badge = Badge {
+            label: "a&b".to_string(),
+            value: "active".to_string(),
+            color: COLOR_ACTIVE,
+        };
+        let svg = badge.to_svg();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"83\""));
+        assert!(svg.contains(">a&amp;b</text>"));
+        assert!(svg.contains("fill=\"#4c1\""));
+        assert!(!svg.contains("a&b"));
+    }
+}
@@ -0,0 +1,32 @@
+use leptos::prelude::*;
+
+use crate::i18n::{Locale, use_locale};
+
+/// Language links ("English · Español"); the current language is shown unlinked.
+/// Choosing one reloads the page with `?lang=`, which is remembered in a cookie.
+#[component]
+pub fn LanguageSwitcher() -> impl IntoView {
+    let current = use_locale();
+
+    view! {
+        <nav aria-label=current.t("nav.language") class="mt-1 text-sm text-[var(--ink-light)]">
+            {Locale::ALL
+                .into_iter()
+                .enumerate()
+                .map(|(i, locale)| {
+                    let link = if locale == current {
+                        view! { <strong lang=locale.code()>{locale.native_name()}</strong> }.into_any()
+                    } else {
+                        view! {
+                            <a href=format!("?lang={}", locale.code()) lang=locale.code() hreflang=locale.code()>
+                                {locale.native_name()}
+                            </a>
+                        }
+                            .into_any()
+                    };
+                    view! { {(i > 0).then_some(" \u{00B7} ")} {link} }
+                })
+                .collect_view()}
+        </nav>
+    }
+}
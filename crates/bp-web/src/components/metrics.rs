@@ -1,10 +1,12 @@
 use crate::config::CONFIG;
+use crate::i18n::{Locale, use_locale};
 use leptos::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 use crate::api::{
-    JitoMevHistory, NetworkComparison, SfdpStatus, StakewizValidator, format_lamports_to_sol, format_percent,
-    format_stake,
+    GossipPresence, IpLocation, JitoMevHistory, NetworkApy, NetworkComparison, SfdpStatus, StakewizValidator,
+    format_lamports_to_sol, format_percent, format_stake,
 };
 
 /// All data needed for metrics display
@@ -14,6 +16,69 @@ pub struct MetricsData {
     pub mev_history: Option<JitoMevHistory>,
     pub network_comp: Option<NetworkComparison>,
     pub sfdp_status: Option<SfdpStatus>,
+    /// Stake-weighted network APYs (absent in snapshots from before it was collected)
+    #[serde(default)]
+    pub network_apy: Option<NetworkApy>,
+    /// Our gossip contact info (absent in older snapshots)
+    #[serde(default)]
+    pub gossip: Option<GossipPresence>,
+    /// Where the gossip IP is hosted (absent in older snapshots)
+    #[serde(default)]
+    pub ip_location: Option<IpLocation>,
+    /// Sources that were unreachable at this ingestion, with the time (UTC,
+    /// `YYYY-MM-DD HH:MM:SS`) of the last good values kept in their place
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub stale: BTreeMap<MetricsSource, String>,
+}
+
+/// Upstream behind a part of `MetricsData`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricsSource {
+    Stakewiz,
+    Jito,
+    Sfdp,
+    NetworkComparison,
+    NetworkApy,
+    Gossip,
+    IpLocation,
+}
+
+impl MetricsSource {
+    /// Name shown next to stale values
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Stakewiz | Self::NetworkComparison | Self::NetworkApy => "Stakewiz",
+            Self::Jito => "Jito",
+            Self::Sfdp => "Solana Foundation",
+            Self::Gossip => "Solana RPC",
+            Self::IpLocation => "IP lookup",
+        }
+    }
+}
+
+impl MetricsData {
+    /// When `source`'s values were fetched, for a snapshot saved at `fetched_at`
+    pub fn as_of(&self, source: MetricsSource, fetched_at: &str) -> String {
+        self.stale
+            .get(&source)
+            .cloned()
+            .unwrap_or_else(|| fetched_at.to_string())
+    }
+}
+
+/// "as of ..." note under values kept from an earlier fetch (renders nothing when fresh)
+pub fn stale_note(data: &MetricsData, source: MetricsSource) -> Option<AnyView> {
+    let locale = use_locale();
+    data.stale.get(&source).cloned().map(|ts| {
+        view! {
+            <div class="text-xs text-[var(--ink-light)]">
+                {locale.tr("metrics.stale", &[("source", source.label())])} " "
+                <time datetime=ts.clone()>{ts} " UTC"</time>
+            </div>
+        }
+        .into_any()
+    })
 }
 
 /// Response that includes the data plus when it was last updated
@@ -47,27 +112,123 @@ pub async fn fetch_metrics() -> Result<Option<MetricsResponse>, ServerFnError> {
     }))
 }
 
-/// Skeleton loading state for metrics
+/// Our next leader slot and roughly how far away it is
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NextLeaderSlot {
+    pub slot: u64,
+    pub minutes_away: u64,
+}
+
+/// Server function to find our next leader slot.
+/// Leader slots come from SQLite (cached by ingestion); only the current slot is fetched live.
+#[server(FetchNextLeaderSlot)]
+pub async fn fetch_next_leader_slot() -> Result<Option<NextLeaderSlot>, ServerFnError> {
+    use crate::api::{get_epoch_info, minutes_until_slot};
+    use crate::db;
+
+    let Some(info) = get_epoch_info().await else {
+        return Ok(None);
+    };
+
+    let next = db::get_next_leader_slot(info.absolute_slot)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+    Ok(next.map(|slot| NextLeaderSlot {
+        slot,
+        minutes_away: minutes_until_slot(info.absolute_slot, slot),
+    }))
+}
+
+/// Subscribes to `/api/public/metrics/stream` and rewrites every `data-live` value when
+/// ingestion publishes new numbers. Inline JavaScript since this is an SSR-only site.
+const LIVE_METRICS_JS: &str = "(() => { \
+    if (!window.EventSource) return; \
+    const line = document.querySelector('[data-live-line]'); \
+    const set = (name, text) => document.querySelectorAll('[data-live=\"' + name + '\"]') \
+        .forEach(el => { el.textContent = text; }); \
+    const whole = n => Math.round(n).toLocaleString('en-US'); \
+    new EventSource('/api/public/metrics/stream').addEventListener('metrics', e => { \
+        const m = JSON.parse(e.data); \
+        set('stake', whole(m.activated_stake)); \
+        set('epoch', m.epoch); \
+        set('progress', m.epoch_progress_percent.toFixed(1) + '%'); \
+        set('last_block', m.last_block_slot == null ? '-' : '#' + whole(m.last_block_slot)); \
+        if (line) line.hidden = false; \
+    }); \
+})()";
+
+/// Metric label padded to the value column (labels render in a monospace font)
+fn metric_label(locale: Locale, key: &'static str) -> String {
+    format!("{:<13} ", locale.t(key))
+}
+
+/// Format a minute count as "<1 min", "12 min", or "3h 05m"
+fn format_minutes(minutes: u64) -> String {
+    match minutes {
+        0 => "<1 min".to_string(),
+        m if m < 60 => format!("{} min", m),
+        m => format!("{}h {:02}m", m / 60, m % 60),
+    }
+}
+
+/// "next block in ~X" suffix for the status line (renders nothing if unknown)
+#[component]
+fn NextBlock() -> impl IntoView {
+    let next = Resource::new(|| (), |_| fetch_next_leader_slot());
+    let locale = use_locale();
+
+    view! {
+        <Suspense fallback=|| ()>
+            {move || {
+                next.get().and_then(|result| result.ok().flatten()).map(|n| view! {
+                    " \u{00B7} " {locale.t("metrics.next_block")} " ~" {format_minutes(n.minutes_away)}
+                })
+            }}
+        </Suspense>
+    }
+}
+
+/// Feature badge ("SFDP ✓"); the check mark is spelled out for screen readers
+#[component]
+fn FeatureBadge(label: &'static str) -> impl IntoView {
+    let locale = use_locale();
+
+    view! {
+        <li class="inline-block px-2 py-0.5 text-sm border border-[var(--rule)] bg-[var(--paper)]">
+            {label} " " <span aria-hidden="true">"\u{2713}"</span>
+            <span class="sr-only">{locale.t("a11y.enabled")}</span>
+        </li>
+    }
+}
+
+/// Skeleton loading state for metrics (announced once as "loading", the placeholder art is hidden)
 #[component]
 fn MetricsSkeleton() -> impl IntoView {
+    let locale = use_locale();
+
     view! {
-        <div class="space-y-4">
+        <div class="space-y-4" role="status" aria-busy="true">
+            <span class="sr-only">{locale.t("a11y.loading_metrics")}</span>
             // Hero APY skeleton
-            <div class="border border-dashed border-[var(--rule)] p-4 text-center">
-                <div class="skeleton-line">"TOTAL APY"</div>
+            <div class="border border-dashed border-[var(--rule)] p-4 text-center" aria-hidden="true">
+                <div class="skeleton-line">{locale.t("metrics.total_apy")}</div>
                 <div class="skeleton-line text-2xl font-bold">"\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}"</div>
-                <div class="skeleton-line text-sm">"\u{2591}\u{2591}\u{2591}\u{2591} staking + \u{2591}\u{2591}\u{2591}\u{2591} mev"</div>
+                <div class="skeleton-line text-sm">
+                    "\u{2591}\u{2591}\u{2591}\u{2591} " {locale.t("metrics.staking")} " + \u{2591}\u{2591}\u{2591}\u{2591} "
+                    {locale.t("metrics.mev")}
+                </div>
             </div>
             // Grouped boxes skeleton
-            <div class="grid grid-cols-1 md:grid-cols-2 gap-3">
+            <div class="grid grid-cols-1 md:grid-cols-2 gap-3" aria-hidden="true">
                 <div class="border border-dashed border-[var(--rule)] p-3">
-                    <div class="skeleton-line font-bold mb-2">"PERFORMANCE"</div>
+                    <div class="skeleton-line font-bold mb-2">{locale.t("metrics.performance")}</div>
                     <div class="skeleton-line">"\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}"</div>
                     <div class="skeleton-line">"\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}"</div>
                     <div class="skeleton-line">"\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}"</div>
                 </div>
                 <div class="border border-dashed border-[var(--rule)] p-3">
-                    <div class="skeleton-line font-bold mb-2">"STAKE"</div>
+                    <div class="skeleton-line font-bold mb-2">{locale.t("metrics.stake_fees")}</div>
                     <div class="skeleton-line">"\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}"</div>
                     <div class="skeleton-line">"\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}"</div>
                     <div class="skeleton-line">"\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}"</div>
@@ -81,6 +242,7 @@ fn MetricsSkeleton() -> impl IntoView {
 #[component]
 pub fn Metrics() -> impl IntoView {
     let metrics = Resource::new(|| (), |_| fetch_metrics());
+    let locale = use_locale();
 
     view! {
         <Suspense fallback=move || view! { <MetricsSkeleton /> }>
@@ -90,16 +252,18 @@ pub fn Metrics() -> impl IntoView {
                         Ok(Some(resp)) => view! { <MetricsContent data=resp.data fetched_at=resp.fetched_at /> }.into_any(),
                         Ok(None) => view! {
                             <div class="text-[var(--ink-light)]">
-                                "Metrics not yet available — waiting for first data ingestion. See "
+                                {locale.t("metrics.unavailable")} " "
+                                {locale.t("metrics.see")} " "
                                 <a href=CONFIG.links.stakewiz>"Stakewiz"</a>
-                                " for current data."
+                                " " {locale.t("metrics.see.after")}
                             </div>
                         }.into_any(),
                         Err(_) => view! {
                             <div class="text-[var(--ink-light)]">
-                                "Error loading metrics. See "
+                                {locale.t("metrics.error")} " "
+                                {locale.t("metrics.see")} " "
                                 <a href=CONFIG.links.stakewiz>"Stakewiz"</a>
-                                " for current data."
+                                " " {locale.t("metrics.see.after")}
                             </div>
                         }.into_any(),
                     }
@@ -111,9 +275,14 @@ pub fn Metrics() -> impl IntoView {
 
 #[component]
 fn MetricsContent(data: MetricsData, fetched_at: Option<String>) -> impl IntoView {
+    let locale = use_locale();
     let v = data.validator.clone();
     let status_icon = if v.delinquent { "\u{2717}" } else { "\u{2713}" };
-    let status_text = if v.delinquent { "DELINQUENT" } else { "ACTIVE" };
+    let status_text = locale.t(if v.delinquent {
+        "metrics.delinquent"
+    } else {
+        "metrics.active"
+    });
 
     let version = v.version.clone();
     let ip_city = v.ip_city.clone().unwrap_or_default();
@@ -126,88 +295,97 @@ fn MetricsContent(data: MetricsData, fetched_at: Option<String>) -> impl IntoVie
     let is_jito = v.is_jito;
     let mev_history = data.mev_history.clone();
     let network_comp = data.network_comp.clone();
+    let validator_stale = stale_note(&data, MetricsSource::Stakewiz);
+    let network_stale = stale_note(&data, MetricsSource::NetworkComparison);
+    let mev_stale = stale_note(&data, MetricsSource::Jito);
+    let sfdp_stale = stale_note(&data, MetricsSource::Sfdp);
 
     view! {
         <div class="space-y-4">
             // "Data last updated" timestamp
             {fetched_at.map(|ts| view! {
                 <div class="text-xs text-[var(--ink-light)] text-right">
-                    "data updated " {ts} " UTC"
+                    {locale.t("metrics.updated")} " " <time datetime=ts.clone()>{ts} " UTC"</time>
                 </div>
             })}
 
             // Hero APY - the number delegators care about most
-            <div class="border border-dashed border-[var(--rule)] p-4 text-center">
-                <div class="text-[var(--ink-light)] text-sm">"TOTAL APY"</div>
+            <div class="border border-dashed border-[var(--rule)] p-4 text-center" role="group" aria-labelledby="metrics-apy">
+                <div id="metrics-apy" class="text-[var(--ink-light)] text-sm">{locale.t("metrics.total_apy")}</div>
                 <div class="text-2xl font-bold">{format_percent(v.total_apy, 2)}</div>
                 <div class="text-sm text-[var(--ink-light)]">
-                    {format_percent(v.staking_apy, 2)} " staking + "
-                    {format_percent(v.jito_apy, 2)} " mev"
+                    {format_percent(v.staking_apy, 2)} " " {locale.t("metrics.staking")} " + "
+                    {format_percent(v.jito_apy, 2)} " " {locale.t("metrics.mev")}
                 </div>
+                {validator_stale}
             </div>
 
             // Status Line + Badges
             <div>
                 <div>
-                    <strong>{status_icon} " " {status_text}</strong>
+                    // State is spelled out; the icon is decorative
+                    <strong><span aria-hidden="true">{status_icon} " "</span>{status_text}</strong>
                     " \u{00B7} v" {version}
-                    " \u{00B7} rank #" {v.rank}
+                    " \u{00B7} " {locale.t("metrics.rank")} " #" {v.rank}
                     " \u{00B7} wiz " {format!("{:.0}", v.wiz_score)} "/100"
+                    <NextBlock />
                 </div>
-                <div class="flex flex-wrap gap-2 mt-2">
-                    {has_sfdp.then(|| view! {
-                        <span class="inline-block px-2 py-0.5 text-sm border border-[var(--rule)] bg-[var(--paper)]">
-                            "SFDP \u{2713}"
-                        </span>
-                    })}
-                    {is_jito.then(|| view! {
-                        <span class="inline-block px-2 py-0.5 text-sm border border-[var(--rule)] bg-[var(--paper)]">
-                            "JITO-BAM \u{2713}"
-                        </span>
-                    })}
-                    <span class="inline-block px-2 py-0.5 text-sm border border-[var(--rule)] bg-[var(--paper)]">
-                        "DOUBLEZERO \u{2713}"
-                    </span>
+                // Filled in and shown by the live metrics stream
+                <div data-live-line hidden aria-live="polite" class="text-sm text-[var(--ink-light)]">
+                    {locale.t("metrics.live.epoch")} " " <span data-live="epoch"></span>
+                    " \u{00B7} " <span data-live="progress"></span>
+                    " \u{00B7} " {locale.t("metrics.live.last_block")} " " <span data-live="last_block"></span>
                 </div>
+                <ul class="flex flex-wrap gap-2 mt-2 list-none" aria-label=locale.t("a11y.features")>
+                    {has_sfdp.then(|| view! { <FeatureBadge label="SFDP" /> })}
+                    {is_jito.then(|| view! { <FeatureBadge label="JITO-BAM" /> })}
+                    <FeatureBadge label="DOUBLEZERO" />
+                </ul>
+                {sfdp_stale}
             </div>
 
             // Grouped metric boxes
             <div class="grid grid-cols-1 md:grid-cols-2 gap-3">
                 // Performance box
-                <div class="border border-dashed border-[var(--rule)] p-3">
-                    <div class="font-bold mb-2 text-sm">"PERFORMANCE"</div>
-                    <div>"Vote Success  " {format_percent(v.vote_success, 2)}</div>
-                    <div>"Skip Rate     " {format_percent(v.skip_rate, 2)}</div>
-                    <div>"Uptime        " {format_percent(v.uptime, 1)}</div>
-                    <div>"Credit Ratio  " {format_percent(v.credit_ratio, 2)}</div>
+                <div class="border border-dashed border-[var(--rule)] p-3" role="group" aria-labelledby="metrics-performance">
+                    <div id="metrics-performance" class="font-bold mb-2 text-sm">{locale.t("metrics.performance")}</div>
+                    <div>{metric_label(locale, "metrics.vote_success")} {format_percent(v.vote_success, 2)}</div>
+                    <div>{metric_label(locale, "metrics.skip_rate")} {format_percent(v.skip_rate, 2)}</div>
+                    <div>{metric_label(locale, "metrics.uptime")} {format_percent(v.uptime, 1)}</div>
+                    <div>{metric_label(locale, "metrics.credit_ratio")} {format_percent(v.credit_ratio, 2)}</div>
                     {network_comp.map(|nc| view! {
                         <div class="mt-2 text-sm text-[var(--ink-light)]">
-                            "vs network (" {nc.total_validators} " validators)"
+                            {locale.tr("metrics.vs_network", &[("validators", &nc.total_validators.to_string())])}
                             <br />
-                            "skip: top " {nc.skip_rate_percentile} "%"
-                            " \u{00B7} stake: top " {nc.stake_percentile} "%"
+                            {locale.tr("metrics.skip_top", &[("pct", &nc.skip_rate_percentile.to_string())])}
+                            " \u{00B7} "
+                            {locale.tr("metrics.stake_top", &[("pct", &nc.stake_percentile.to_string())])}
                         </div>
                     })}
+                    {network_stale}
                 </div>
 
                 // Stake & Commission box
-                <div class="border border-dashed border-[var(--rule)] p-3">
-                    <div class="font-bold mb-2 text-sm">"STAKE & FEES"</div>
-                    <div>"Stake         " {format_stake(v.activated_stake)} " SOL"</div>
-                    <div>"Commission    " {v.commission} "%"</div>
-                    <div>"Jito MEV Fee  " {format!("{:.1}", v.jito_commission_bps as f64 / 100.0)} "%"</div>
+                <div class="border border-dashed border-[var(--rule)] p-3" role="group" aria-labelledby="metrics-stake">
+                    <div id="metrics-stake" class="font-bold mb-2 text-sm">{locale.t("metrics.stake_fees")}</div>
+                    <div>{metric_label(locale, "metrics.stake")} <span data-live="stake">{format_stake(v.activated_stake)}</span> " SOL"</div>
+                    <div>{metric_label(locale, "metrics.commission")} {v.commission} "%"</div>
+                    <div>
+                        {metric_label(locale, "metrics.mev_fee")}
+                        {format!("{:.1}", v.jito_commission_bps as f64 / 100.0)} "%"
+                    </div>
                 </div>
             </div>
 
             // MEV Rewards History
             <div>
-                <strong>"MEV REWARDS"</strong>
+                <strong>{locale.t("metrics.mev_rewards")}</strong>
                 {match mev_history {
                     Some(mh) if !mh.epochs.is_empty() => {
                         let epochs = mh.epochs.clone();
                         let count = epochs.len();
                         view! {
-                            " (last " {count} " epochs)"
+                            " " {locale.tr("metrics.last_epochs", &[("count", &count.to_string())])}
                             <div class="mt-1 text-sm font-mono">
                                 {epochs.into_iter().rev().take(5).collect::<Vec<_>>().into_iter().rev().map(|e| {
                                     let epoch = e.epoch;
@@ -223,12 +401,13 @@ fn MetricsContent(data: MetricsData, fetched_at: Option<String>) -> impl IntoVie
                     },
                     _ => view! {
                         <div class="mt-1 text-sm text-[var(--ink-light)]">
-                            "See "
+                            {locale.t("metrics.see")} " "
                             <a href=CONFIG.links.jito>"Jito"</a>
-                            " for MEV reward details"
+                            " " {locale.t("metrics.see_jito.after")}
                         </div>
                     }.into_any(),
                 }}
+                {mev_stale}
             </div>
 
             // Infrastructure
@@ -237,6 +416,7 @@ fn MetricsContent(data: MetricsData, fetched_at: Option<String>) -> impl IntoVie
                 <br />
                 {client} " \u{00B7} ASN " {asn} " \u{00B7} epoch " {v.epoch}
             </div>
+            <script inner_html=LIVE_METRICS_JS></script>
         </div>
     }
 }
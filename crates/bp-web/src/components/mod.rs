@@ -1,13 +1,25 @@
 mod copy_button;
 mod external_link;
+mod language_switcher;
 pub mod metrics;
+pub mod revenue_chart;
+mod rewards_calculator;
 
 mod owl_mark;
 mod section;
+mod stake_widget;
+mod structured_data;
+mod tip_jar;
 
 pub use copy_button::CopyButton;
 pub use external_link::ExternalLink;
+pub use language_switcher::LanguageSwitcher;
 pub use metrics::Metrics;
+pub use revenue_chart::RevenueChart;
+pub use rewards_calculator::RewardsCalculator;
 
 pub use owl_mark::AnimatedGradientDashBorder;
 pub use section::Section;
+pub use stake_widget::StakeWidget;
+pub use structured_data::StructuredData;
+pub use tip_jar::TipJar;
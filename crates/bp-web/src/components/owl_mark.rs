@@ -8,7 +8,8 @@ const PATTERN_A: &str = "▒ - - - ░ - - - ▒ - - - ▒ - - - ░ - - - ░ -
 const PATTERN_B: &str = "░ - - - ▒ - - - ░ - - - ░ - - - ▒ - - - ▒ - - - ░ - - - ▒ - - - ";
 
 /// Animated gradient dash border with title - pure CSS ticker animation
-/// Left and right sides have different patterns and are desynchronized via animation-delay
+/// Left and right sides have different patterns and are desynchronized via animation-delay.
+/// The title is the page's `<h1>`; the tickers are decorative and stop under reduced motion.
 #[component]
 pub fn AnimatedGradientDashBorder(#[prop(into)] title: String) -> impl IntoView {
     // Repeat patterns for seamless looping (pattern + one extra for shift buffer)
@@ -20,7 +21,7 @@ pub fn AnimatedGradientDashBorder(#[prop(into)] title: String) -> impl IntoView
             <span class="ticker" aria-hidden="true">
                 <span class="ticker__track ticker__track--left">{pattern_left}</span>
             </span>
-            <span class="font-bold px-4">{title}</span>
+            <h1 class="font-bold px-4">{title}</h1>
             <span class="ticker" aria-hidden="true">
                 <span class="ticker__track ticker__track--right">{pattern_right}</span>
             </span>
@@ -0,0 +1,248 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::use_locale;
+
+/// One month's income split by source, in percent of that month's total
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema))]
+pub struct RevenueShareMonth {
+    /// YYYY-MM
+    pub month: String,
+    pub commission_pct: f64,
+    pub leader_fees_pct: f64,
+    pub mev_pct: f64,
+    pub bam_pct: f64,
+}
+
+impl RevenueShareMonth {
+    /// Shares from per-source SOL amounts (commission, leader fees, MEV, BAM).
+    /// None for a month with no income.
+    pub fn from_amounts(month: &str, amounts: [f64; 4]) -> Option<Self> {
+        let total: f64 = amounts.iter().map(|a| a.max(0.0)).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let pct = |a: f64| (a.max(0.0) / total * 10_000.0).round() / 100.0;
+        Some(Self {
+            month: month.to_string(),
+            commission_pct: pct(amounts[0]),
+            leader_fees_pct: pct(amounts[1]),
+            mev_pct: pct(amounts[2]),
+            bam_pct: pct(amounts[3]),
+        })
+    }
+
+    /// Shares in stacking order (bottom to top), matching `SERIES`
+    fn shares(&self) -> [f64; 4] {
+        [self.commission_pct, self.leader_fees_pct, self.mev_pct, self.bam_pct]
+    }
+}
+
+/// Chart series in stacking order: label translation key, fill colour (readable on both
+/// themes) and a legend marker, so the legend doesn't rely on colour alone
+const SERIES: [(&str, &str, &str); 4] = [
+    ("revenue.commission", "#4f7fd1", "\u{25A0}"),
+    ("revenue.leader_fees", "#d08a2e", "\u{25B2}"),
+    ("revenue.mev", "#3a9f6e", "\u{25CF}"),
+    ("revenue.bam", "#8a63c9", "\u{25C6}"),
+];
+
+const CHART_WIDTH: f64 = 600.0;
+const CHART_HEIGHT: f64 = 160.0;
+
+/// Server function for the public revenue composition chart.
+/// Reads monthly totals from cache.sqlite (recomputed only when the cache changes)
+/// and returns shares only, not amounts.
+#[server(FetchRevenueComposition)]
+pub async fn fetch_revenue_composition() -> Result<Vec<RevenueShareMonth>, ServerFnError> {
+    let data_dir = crate::db::data_dir();
+    let composition = crate::financials::revenue_composition(&data_dir)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Revenue data unavailable: {:#}", e)))?;
+    Ok(revenue_shares(&composition.months))
+}
+
+/// `/api/public/revenue` response body
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct RevenueShares {
+    /// Months with income, oldest first
+    pub months: Vec<RevenueShareMonth>,
+}
+
+/// Monthly shares for every month with income
+#[cfg(feature = "ssr")]
+pub fn revenue_shares(months: &[crate::financials::types::MonthlyRevenue]) -> Vec<RevenueShareMonth> {
+    months
+        .iter()
+        .filter_map(|m| {
+            RevenueShareMonth::from_amounts(&m.month, [m.commission_sol, m.leader_fees_sol, m.mev_sol, m.bam_sol])
+        })
+        .collect()
+}
+
+/// SVG path data for each series of a 100% stacked area chart, in `SERIES` order.
+/// A single month is drawn across the full width.
+fn stacked_area_paths(months: &[RevenueShareMonth], width: f64, height: f64) -> Vec<String> {
+    let n = months.len();
+    let xs: Vec<f64> = match n {
+        0 => return vec![String::new(); SERIES.len()],
+        1 => vec![0.0, width],
+        _ => (0..n).map(|i| width * i as f64 / (n - 1) as f64).collect(),
+    };
+    let shares: Vec<[f64; 4]> = if n == 1 {
+        vec![months[0].shares(); 2]
+    } else {
+        months.iter().map(RevenueShareMonth::shares).collect()
+    };
+    let y = |pct: f64| height * (1.0 - pct.clamp(0.0, 100.0) / 100.0);
+
+    (0..SERIES.len())
+        .map(|k| {
+            let lower: Vec<f64> = shares.iter().map(|s| s[..k].iter().sum()).collect();
+            let upper: Vec<f64> = shares.iter().zip(&lower).map(|(s, l)| l + s[k]).collect();
+
+            let mut d = String::new();
+            for (i, (x, top)) in xs.iter().zip(&upper).enumerate() {
+                d.push_str(&format!("{}{:.1},{:.1} ", if i == 0 { "M" } else { "L" }, x, y(*top)));
+            }
+            for (x, bottom) in xs.iter().zip(&lower).rev() {
+                d.push_str(&format!("L{:.1},{:.1} ", x, y(*bottom)));
+            }
+            d.push('Z');
+            d
+        })
+        .collect()
+}
+
+/// Revenue composition chart - where validator income comes from, month by month
+#[component]
+pub fn RevenueChart() -> impl IntoView {
+    let composition = Resource::new(|| (), |_| fetch_revenue_composition());
+
+    view! {
+        <Suspense fallback=|| ()>
+            {move || {
+                composition
+                    .get()
+                    .and_then(|result| result.ok())
+                    .filter(|months| !months.is_empty())
+                    .map(|months| view! { <RevenueChartContent months=months /> })
+            }}
+        </Suspense>
+    }
+}
+
+#[component]
+fn RevenueChartContent(months: Vec<RevenueShareMonth>) -> impl IntoView {
+    let paths = stacked_area_paths(&months, CHART_WIDTH, CHART_HEIGHT);
+    let first_month = months.first().map(|m| m.month.clone()).unwrap_or_default();
+    let last = months.last().cloned();
+    let last_month = last.as_ref().map(|m| m.month.clone()).unwrap_or_default();
+    let latest_shares = last.map(|m| m.shares()).unwrap_or_default();
+    let locale = use_locale();
+    let table_rows = months.clone();
+
+    view! {
+        <div class="mt-4">
+            <strong>{locale.t("revenue.title")}</strong>
+            " (" {locale.t("revenue.subtitle")} ")"
+            <svg
+                viewBox=format!("0 0 {} {}", CHART_WIDTH, CHART_HEIGHT)
+                preserveAspectRatio="none"
+                role="img"
+                aria-label=locale.t("revenue.aria")
+                class="block w-full h-32 mt-2 border border-dashed border-[var(--rule)]"
+            >
+                {paths
+                    .into_iter()
+                    .zip(SERIES)
+                    .map(|(d, (label, color, _))| view! {
+                        <path d=d fill=color fill-opacity="0.8">
+                            <title>{locale.t(label)}</title>
+                        </path>
+                    })
+                    .collect_view()}
+            </svg>
+            <div class="flex justify-between text-xs text-[var(--ink-light)]">
+                <span>{first_month}</span>
+                <span>{last_month.clone()}</span>
+            </div>
+            <div class="flex flex-wrap gap-x-4 mt-1 text-sm">
+                {SERIES
+                    .iter()
+                    .zip(latest_shares)
+                    .map(|((label, color, marker), pct)| view! {
+                        <span>
+                            <span style=format!("color:{}", color) aria-hidden="true">{*marker} " "</span>
+                            {locale.t(*label)} " " {format!("{:.0}%", pct)}
+                        </span>
+                    })
+                    .collect_view()}
+                <span class="text-[var(--ink-light)]">"(" {last_month} ")"</span>
+            </div>
+            // The same data as a table, for screen readers
+            <table class="sr-only">
+                <caption>{locale.t("revenue.title")}</caption>
+                <thead>
+                    <tr>
+                        <th scope="col">{locale.t("revenue.month")}</th>
+                        {SERIES.iter().map(|(label, _, _)| view! { <th scope="col">{locale.t(*label)}</th> }).collect_view()}
+                    </tr>
+                </thead>
+                <tbody>
+                    {table_rows
+                        .into_iter()
+                        .map(|m| {
+                            let shares = m.shares();
+                            view! {
+                                <tr>
+                                    <th scope="row">{m.month}</th>
+                                    {shares.into_iter().map(|pct| view! { <td>{format!("{:.0}%", pct)}</td> }).collect_view()}
+                                </tr>
+                            }
+                        })
+                        .collect_view()}
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn month(m: &str, amounts: [f64; 4]) -> RevenueShareMonth {
+        RevenueShareMonth::from_amounts(m, amounts).unwrap()
+    }
+
+    #[test]
+    fn shares_sum_to_one_hundred() {
+        let m = month("2026-01", [3.0, 1.0, 0.5, 0.5]);
+        assert_eq!(m.shares(), [60.0, 20.0, 10.0, 10.0]);
+        assert!(RevenueShareMonth::from_amounts("2026-02", [0.0; 4]).is_none());
+    }
+
+    #[test]
+    fn areas_stack_bottom_to_top() {
+        let months = vec![
+            month("2026-01", [1.0, 1.0, 0.0, 0.0]),
+            month("2026-02", [1.0, 0.0, 0.0, 1.0]),
+        ];
+        let paths = stacked_area_paths(&months, 100.0, 100.0);
+        assert_eq!(paths.len(), 4);
+        // Commission: 50% both months, from the baseline
+        assert_eq!(paths[0], "M0.0,50.0 L100.0,50.0 L100.0,100.0 L0.0,100.0 Z");
+        // BAM sits on top of everything else
+        assert_eq!(paths[3], "M0.0,0.0 L100.0,0.0 L100.0,50.0 L0.0,0.0 Z");
+    }
+
+    #[test]
+    fn single_month_spans_full_width() {
+        let months = vec![month("2026-01", [1.0, 0.0, 0.0, 0.0])];
+        let paths = stacked_area_paths(&months, 100.0, 100.0);
+        assert_eq!(paths[0], "M0.0,0.0 L100.0,0.0 L100.0,100.0 L0.0,100.0 Z");
+    }
+}
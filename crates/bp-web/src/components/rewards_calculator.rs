@@ -0,0 +1,125 @@
+use leptos::prelude::*;
+
+use crate::api::format_percent;
+use crate::components::metrics::{MetricsData, fetch_metrics};
+use crate::i18n::use_locale;
+
+/// Stake amount the calculator opens with
+const DEFAULT_STAKE_SOL: f64 = 1_000.0;
+
+/// Recomputes every `data-apy` output in the calculator from the entered amount.
+/// Inline JavaScript since this is an SSR-only site without hydration.
+const RECALCULATE_JS: &str = "const sol = Math.max(0, parseFloat(this.value) || 0); \
+    this.closest('[data-calc]').querySelectorAll('[data-apy]').forEach(el => { \
+        const v = sol * parseFloat(el.dataset.apy) / 100; \
+        el.textContent = (el.dataset.signed && v >= 0 ? '+' : '') + v.toFixed(2) + ' SOL'; \
+    })";
+
+/// Projected annual rewards for `sol` at `apy` percent
+fn annual_rewards(sol: f64, apy: f64) -> f64 {
+    sol * apy / 100.0
+}
+
+/// Rewards text matching what `RECALCULATE_JS` renders
+fn format_rewards(sol: f64, apy: f64, signed: bool) -> String {
+    let value = annual_rewards(sol, apy);
+    let sign = if signed && value >= 0.0 { "+" } else { "" };
+    format!("{}{:.2} SOL", sign, value)
+}
+
+/// Rewards calculator - projected annual rewards for an entered stake, vs the network average
+#[component]
+pub fn RewardsCalculator() -> impl IntoView {
+    let metrics = Resource::new(|| (), |_| fetch_metrics());
+
+    view! {
+        <Suspense fallback=|| ()>
+            {move || {
+                metrics
+                    .get()
+                    .and_then(|result| result.ok().flatten())
+                    .map(|resp| view! { <RewardsCalculatorContent data=resp.data /> })
+            }}
+        </Suspense>
+    }
+}
+
+#[component]
+fn RewardsCalculatorContent(data: MetricsData) -> impl IntoView {
+    let locale = use_locale();
+    let v = data.validator;
+    let network = data.network_apy;
+    let mev_fee = format!("{:.1}", v.jito_commission_bps as f64 / 100.0);
+
+    let output = move |apy: f64, signed: bool| {
+        view! {
+            <span data-apy=apy.to_string() data-signed=signed.then_some("1")>
+                {format_rewards(DEFAULT_STAKE_SOL, apy, signed)}
+            </span>
+        }
+    };
+    // Labels padded to the value column (monospace font)
+    let label = move |key: &'static str| format!("{:<16} ", locale.t(key));
+    let per_year = locale.t("calculator.per_year");
+
+    view! {
+        <div data-calc="" class="border border-dashed border-[var(--rule)] p-3">
+            <label class="block mb-2">
+                {locale.t("calculator.stake")} " "
+                <input
+                    type="number"
+                    min="0"
+                    step="any"
+                    inputmode="decimal"
+                    value=DEFAULT_STAKE_SOL.to_string()
+                    oninput=RECALCULATE_JS
+                    aria-label=locale.t("calculator.stake.aria")
+                    class="w-32 px-2 border border-dashed border-[var(--rule)] bg-[var(--paper)]"
+                />
+                " SOL"
+            </label>
+            <div>{label("calculator.staking")} {output(v.staking_apy, false)} " " {per_year}</div>
+            <div>{label("calculator.mev")} {output(v.jito_apy, false)} " " {per_year}</div>
+            <div>
+                <strong>{label("calculator.total")} {output(v.total_apy, false)} " " {per_year}</strong>
+            </div>
+            {network.as_ref().map(|n| view! {
+                <div class="mt-2 text-[var(--ink-light)]">
+                    {label("calculator.network")} {output(n.total_apy, false)} " " {per_year}
+                </div>
+                <div>{label("calculator.difference")} {output(v.total_apy - n.total_apy, true)} " " {per_year}</div>
+            })}
+            <div class="mt-2 text-sm text-[var(--ink-light)]">
+                {locale.tr(
+                    "calculator.note",
+                    &[
+                        ("staking", &format_percent(v.staking_apy, 2)),
+                        ("mev", &format_percent(v.jito_apy, 2)),
+                        ("commission", &v.commission.to_string()),
+                        ("mev_fee", &mev_fee),
+                    ],
+                )}
+                {network.map(|n| {
+                    let note = locale.tr(
+                        "calculator.note.network",
+                        &[("validators", &n.validators.to_string()), ("apy", &format_percent(n.total_apy, 2))],
+                    );
+                    view! { " " {note} }
+                })}
+                " " {locale.t("calculator.note.vary")}
+            </div>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_like_the_inline_script() {
+        assert_eq!(format_rewards(1_000.0, 7.25, false), "72.50 SOL");
+        assert_eq!(format_rewards(1_000.0, 0.4, true), "+4.00 SOL");
+        assert_eq!(format_rewards(1_000.0, -0.4, true), "-4.00 SOL");
+    }
+}
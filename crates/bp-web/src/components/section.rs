@@ -1,15 +1,25 @@
 use leptos::prelude::*;
 
-/// Section component - wrapper with decorative ASCII border and anchor link
+use crate::i18n::use_locale;
+
+/// Section component - wrapper with decorative ASCII border and anchor link.
+/// The section is labelled by its heading; the box-drawing characters are hidden from
+/// screen readers and the anchor link gets a descriptive label.
 #[component]
 pub fn Section(#[prop(into)] id: String, #[prop(into)] title: String, children: Children) -> impl IntoView {
     let anchor_href = format!("#{}", id);
+    let heading_id = format!("{}-heading", id);
+    let anchor_label = use_locale().tr("a11y.section_link", &[("title", &title)]);
 
     view! {
-        <section id=id class="mb-8">
-            <h2 class="font-bold uppercase mb-3">
-                {format!("\u{2500}\u{2524} {} \u{251C}\u{2500}", title)}
-                <a href=anchor_href class="section-anchor ml-1">" \u{00A7}"</a>
+        <section id=id aria-labelledby=heading_id.clone() class="mb-8">
+            <h2 id=heading_id class="font-bold uppercase mb-3">
+                <span aria-hidden="true">"\u{2500}\u{2524} "</span>
+                {title}
+                <span aria-hidden="true">" \u{251C}\u{2500}"</span>
+                <a href=anchor_href class="section-anchor ml-1" aria-label=anchor_label>
+                    " \u{00A7}"
+                </a>
             </h2>
             <div class="pl-4 border-l border-dashed border-[var(--rule)]">
                 {children()}
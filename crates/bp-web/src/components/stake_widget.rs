@@ -0,0 +1,71 @@
+use leptos::prelude::*;
+
+use crate::api::RPC_ENDPOINT;
+use crate::components::ExternalLink;
+use crate::config::CONFIG;
+use crate::i18n::use_locale;
+use crate::wallet_links;
+
+/// "Stake with us": wallet deep links, plus an in-browser delegate form.
+///
+/// The form is an island driven by `/stake.js`. It stays hidden until a Wallet Standard
+/// wallet (browser extension or a wallet's in-app browser) registers, then builds the
+/// create-stake-account + delegate transaction locally and hands it to the wallet to
+/// sign and send. Funds never touch the site; the wallet keeps both authorities.
+#[component]
+pub fn StakeWidget() -> impl IntoView {
+    let locale = use_locale();
+    let page = wallet_links::stake_page_url(CONFIG.base_url);
+    let phantom = wallet_links::phantom_browse(&page, CONFIG.base_url);
+    let solflare = wallet_links::solflare_browse(&page, CONFIG.base_url);
+    let button = "px-3 py-1 border border-dashed border-[var(--rule)] hover:bg-[var(--rule)] transition-colors";
+    let field = "px-2 border border-dashed border-[var(--rule)] bg-[var(--paper)]";
+
+    view! {
+        <div
+            data-stake-widget
+            data-vote-account=CONFIG.vote_account
+            data-rpc=RPC_ENDPOINT
+            data-msg-amount=locale.t("delegate.stake.msg.amount")
+            data-msg-connecting=locale.t("delegate.stake.msg.connecting")
+            data-msg-building=locale.t("delegate.stake.msg.building")
+            data-msg-approve=locale.t("delegate.stake.msg.approve")
+            data-msg-sent=locale.t("delegate.stake.msg.sent")
+            data-msg-error=locale.t("delegate.stake.msg.error")
+        >
+            <p class="mb-3">{locale.t("delegate.stake.intro")}</p>
+
+            <div data-stake-island hidden class="mb-4 border border-dashed border-[var(--rule)] p-3 space-y-3">
+                <div class="flex flex-wrap items-end gap-3">
+                    <label class="block">
+                        {locale.t("delegate.stake.wallet")}
+                        <select data-wallet class=format!("block {}", field)></select>
+                    </label>
+                    <label class="block">
+                        {locale.t("delegate.stake.amount")}
+                        <input
+                            data-amount
+                            type="text"
+                            inputmode="decimal"
+                            autocomplete="off"
+                            placeholder="10"
+                            class=format!("block w-32 {}", field)
+                        />
+                    </label>
+                    <button data-stake type="button" class=format!("{} cursor-pointer", button)>
+                        {locale.t("delegate.stake.submit")}
+                    </button>
+                </div>
+                <p class="text-sm text-[var(--ink-light)]">{locale.t("delegate.stake.note")}</p>
+                <p data-status role="status" aria-live="polite" class="text-sm"></p>
+            </div>
+
+            <p class="text-sm text-[var(--ink-light)] mb-2">{locale.t("delegate.stake.mobile")}</p>
+            <div class="flex flex-wrap gap-2">
+                <ExternalLink href=phantom label=locale.t("delegate.stake.phantom") />
+                <ExternalLink href=solflare label=locale.t("delegate.stake.solflare") />
+            </div>
+        </div>
+        <script src="/stake.js" defer></script>
+    }
+}
@@ -0,0 +1,9 @@
+use leptos::prelude::*;
+
+use crate::seo::script_json;
+
+/// JSON-LD structured data block for search engines
+#[component]
+pub fn StructuredData(data: serde_json::Value) -> impl IntoView {
+    view! { <script type="application/ld+json" inner_html=script_json(&data)></script> }
+}
@@ -0,0 +1,56 @@
+use leptos::prelude::*;
+
+use crate::components::CopyButton;
+use crate::config::CONFIG;
+use crate::i18n::use_locale;
+use crate::solana_pay;
+
+/// QR code for the request, rendered on the server
+fn qr_svg(url: &str) -> Option<String> {
+    #[cfg(feature = "ssr")]
+    {
+        solana_pay::qr_svg(url)
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = url;
+        None
+    }
+}
+
+/// Solana Pay tip jar: QR code, wallet deep link and the donation address.
+/// Tips are picked up by the accounting transfer scan and booked as donation income.
+#[component]
+pub fn TipJar(
+    /// Donation address (base58)
+    address: &'static str,
+) -> impl IntoView {
+    let locale = use_locale();
+    let url = solana_pay::transfer_url(address, None, CONFIG.name, locale.t("tip.message"));
+    let qr = qr_svg(&url);
+
+    view! {
+        <p class="mb-4">{locale.t("tip.body")}</p>
+        <div class="flex flex-wrap items-start gap-4">
+            {qr.map(|svg| view! {
+                <div class="w-[200px] shrink-0 bg-white p-2" role="img" aria-label=locale.t("tip.qr_label") inner_html=svg></div>
+            })}
+            <div class="space-y-3 min-w-0">
+                <div>
+                    <strong>"ADDRESS"</strong>
+                    <code class="block break-all">{address}</code>
+                </div>
+                <div class="flex flex-wrap gap-2">
+                    <a
+                        href=url
+                        class="px-3 py-1 border border-dashed border-[var(--rule)] hover:bg-[var(--rule)] transition-colors inline-block"
+                    >
+                        {locale.t("tip.open_wallet")}
+                    </a>
+                    <CopyButton text=address.to_string() label=locale.t("tip.copy") />
+                </div>
+                <p class="text-sm text-[var(--ink-light)]">{locale.t("tip.scan_hint")}</p>
+            </div>
+        </div>
+    }
+}
@@ -8,6 +8,9 @@ pub struct Config {
     pub identity: &'static str,
     pub vote_account: &'static str,
     pub withdraw_authority: &'static str,
+    /// Tip address for the Solana Pay tip jar, or None to hide it. Must match
+    /// `donation_address` in the accounting config.toml so tips are booked as income.
+    pub donation_address: Option<&'static str>,
 
     pub contact: Contact,
     pub links: Links,
@@ -52,6 +55,7 @@ pub static CONFIG: Config = Config {
     identity: "mD1afZhSisoXfJLT8nYwSFANqjr1KPoDUEpYTEfFX1e",
     vote_account: "4PL2ZFoZJHgkbZ54US4qNC58X69Fa1FKtY4CaVKeuQPg",
     withdraw_authority: "AN58nFDFdehKbP7d3KALhnCJAsWNE7cWpCR6dLVAj9xm",
+    donation_address: None, // e.g., Some("Tips...address")
 
     contact: Contact { twitter: "grod220" },
 
@@ -0,0 +1,224 @@
+//! Contact / delegation-inquiry form handling.
+//!
+//! Submissions are screened (honeypot field, per-IP and global rate limits, optional
+//! hCaptcha) and forwarded to the operator through [`crate::notify`], so no email
+//! address is published on the site.
+
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+
+use crate::api::http::post_form;
+use crate::rate_limit::RateLimiter;
+
+const RATE_WINDOW: Duration = Duration::from_secs(3600);
+/// Submissions allowed per client IP per window
+const MAX_PER_IP: usize = 3;
+/// Submissions allowed across all clients per window (bounds webhook spam from rotating IPs)
+const MAX_GLOBAL: usize = 30;
+
+const MAX_NAME_CHARS: usize = 100;
+const MAX_REPLY_TO_CHARS: usize = 200;
+const MAX_STAKE_CHARS: usize = 40;
+const MAX_MESSAGE_CHARS: usize = 1500;
+
+const HCAPTCHA_VERIFY_URL: &str = "https://api.hcaptcha.com/siteverify";
+
+/// Form fields as posted by the `/contact` page. Missing fields parse as empty.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ContactForm {
+    pub name: String,
+    /// Email address, X handle, or other way to reply
+    pub reply_to: String,
+    /// Approximate stake the sender is considering (optional, free text)
+    pub stake: String,
+    pub message: String,
+    /// Honeypot: hidden from people, filled in by naive bots
+    pub website: String,
+    #[serde(rename = "h-captcha-response")]
+    pub hcaptcha_response: String,
+}
+
+impl ContactForm {
+    /// A filled-in honeypot means a bot
+    pub fn is_spam(&self) -> bool {
+        !self.website.trim().is_empty()
+    }
+
+    /// Required fields present and every field within its length limit
+    pub fn is_valid(&self) -> bool {
+        let within = |s: &str, max: usize| s.trim().chars().count() <= max;
+        !self.reply_to.trim().is_empty()
+            && !self.message.trim().is_empty()
+            && within(&self.name, MAX_NAME_CHARS)
+            && within(&self.reply_to, MAX_REPLY_TO_CHARS)
+            && within(&self.stake, MAX_STAKE_CHARS)
+            && within(&self.message, MAX_MESSAGE_CHARS)
+    }
+
+    /// Notification text for the operator
+    fn to_message(&self, client_ip: &str) -> String {
+        let or_dash = |s: &str| {
+            if s.trim().is_empty() {
+                "-".to_string()
+            } else {
+                s.trim().to_string()
+            }
+        };
+        format!(
+            "New contact form message\nFrom: {}\nReply to: {}\nStake: {}\nIP: {}\n\n{}",
+            or_dash(&self.name),
+            self.reply_to.trim(),
+            or_dash(&self.stake),
+            client_ip,
+            self.message.trim()
+        )
+    }
+}
+
+/// Why a submission was not forwarded. `code()` is passed back to the page as `?error=`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContactError {
+    Invalid,
+    RateLimited,
+    Captcha,
+    Delivery,
+}
+
+impl ContactError {
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Invalid => "invalid",
+            Self::RateLimited => "rate",
+            Self::Captcha => "captcha",
+            Self::Delivery => "delivery",
+        }
+    }
+}
+
+/// Per-IP and all-clients limiters
+static LIMITERS: OnceLock<Mutex<(RateLimiter, RateLimiter)>> = OnceLock::new();
+
+/// Key for the all-clients limiter
+const GLOBAL_KEY: &str = "*";
+
+/// Check both limits and, if allowed, count the submission against them
+fn take_rate_limit(client_ip: &str) -> bool {
+    let limiters = LIMITERS.get_or_init(|| {
+        Mutex::new((
+            RateLimiter::new(RATE_WINDOW, MAX_PER_IP),
+            RateLimiter::new(RATE_WINDOW, MAX_GLOBAL),
+        ))
+    });
+    let Ok(mut guard) = limiters.lock() else {
+        return false;
+    };
+    let (per_ip, global) = &mut *guard;
+    let now = Instant::now();
+    if !per_ip.allows(client_ip, now) || !global.allows(GLOBAL_KEY, now) {
+        return false;
+    }
+    per_ip.record(client_ip, now);
+    global.record(GLOBAL_KEY, now);
+    true
+}
+
+/// hCaptcha keys, when spam protection is enabled (HCAPTCHA_SITEKEY and HCAPTCHA_SECRET)
+pub struct HcaptchaConfig {
+    pub sitekey: String,
+    secret: String,
+}
+
+pub fn hcaptcha_config() -> Option<HcaptchaConfig> {
+    let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+    Some(HcaptchaConfig {
+        sitekey: var("HCAPTCHA_SITEKEY")?,
+        secret: var("HCAPTCHA_SECRET")?,
+    })
+}
+
+#[derive(Deserialize)]
+struct SiteverifyResponse {
+    success: bool,
+}
+
+async fn verify_hcaptcha(config: &HcaptchaConfig, token: &str, client_ip: &str) -> bool {
+    if token.is_empty() {
+        return false;
+    }
+    post_form::<SiteverifyResponse>(
+        HCAPTCHA_VERIFY_URL,
+        &[
+            ("secret", config.secret.as_str()),
+            ("response", token),
+            ("sitekey", config.sitekey.as_str()),
+            ("remoteip", client_ip),
+        ],
+    )
+    .await
+    .is_some_and(|r| r.success)
+}
+
+/// Client IP for rate limiting: Fly's edge header, then the first X-Forwarded-For hop
+pub fn client_ip(headers: &axum::http::HeaderMap) -> String {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    header("fly-client-ip")
+        .or_else(|| header("x-forwarded-for").and_then(|v| v.split(',').next()))
+        .map(str::trim)
+        .filter(|ip| !ip.is_empty())
+        .unwrap_or("unknown")
+        .to_string()
+}
+
+/// Screen a submission and forward it to the operator.
+///
+/// Honeypot hits are reported as success so bots get no signal.
+pub async fn submit(form: ContactForm, client_ip: &str) -> Result<(), ContactError> {
+    if form.is_spam() {
+        eprintln!("[contact] Dropped honeypot submission from {}", client_ip);
+        return Ok(());
+    }
+    if !form.is_valid() {
+        return Err(ContactError::Invalid);
+    }
+    if let Some(config) = hcaptcha_config()
+        && !verify_hcaptcha(&config, &form.hcaptcha_response, client_ip).await
+    {
+        return Err(ContactError::Captcha);
+    }
+    if !take_rate_limit(client_ip) {
+        return Err(ContactError::RateLimited);
+    }
+
+    crate::notify::send(&form.to_message(client_ip)).await.map_err(|e| {
+        eprintln!("[contact] Failed to forward message: {:#}", e);
+        ContactError::Delivery
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn form(reply_to: &str, message: &str) -> ContactForm {
+        ContactForm {
+            reply_to: reply_to.to_string(),
+            message: message.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn validates_required_fields_and_lengths() {
+        assert!(form("@delegator", "How do I stake 5k SOL?").is_valid());
+        assert!(!form("", "hi").is_valid());
+        assert!(!form("@delegator", "   ").is_valid());
+        assert!(!form("@delegator", &"x".repeat(MAX_MESSAGE_CHARS + 1)).is_valid());
+
+        let mut bot = form("@delegator", "hi");
+        bot.website = "http://spam.example".to_string();
+        assert!(bot.is_spam());
+    }
+}
@@ -0,0 +1,168 @@
+//! Markdown-driven content pages.
+//!
+//! Each `<slug>.md` in the content directory (`CONTENT_DIR`, default `./content`)
+//! is served at `/<slug>` with the site layout. A translation can sit next to it as
+//! `<slug>.<lang>.md` (e.g. `about.es.md`). Files are read on every request, so copy
+//! edits show up without a rebuild or restart.
+//!
+//! A file may start with a front matter block of `key: value` lines:
+//!
+//! ```text
+//! ---
+//! title: About Block Parliament
+//! description: Who runs the validator and why.
+//! ---
+//! ```
+//!
+//! Markdown is rendered as-is, including any raw HTML, so the directory must only be
+//! writable by people trusted to edit the site.
+
+use serde::{Deserialize, Serialize};
+
+/// A rendered content page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContentPage {
+    pub slug: String,
+    pub title: String,
+    pub description: Option<String>,
+    /// Rendered HTML body
+    pub html: String,
+}
+
+/// Slugs are lowercase letters, digits and dashes, which also keeps lookups inside
+/// the content directory
+pub fn is_valid_slug(slug: &str) -> bool {
+    !slug.is_empty()
+        && !slug.starts_with('-')
+        && slug
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+}
+
+/// Split front matter from the markdown body. Returns (`key -> value` pairs, body).
+#[cfg(feature = "ssr")]
+fn split_front_matter(source: &str) -> (Vec<(&str, &str)>, &str) {
+    let Some(rest) = source.strip_prefix("---\n").or_else(|| source.strip_prefix("---\r\n")) else {
+        return (Vec::new(), source);
+    };
+    let Some(end) = rest.find("\n---") else {
+        return (Vec::new(), source);
+    };
+    let fields = rest[..end]
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(k, v)| (k.trim(), v.trim()))
+        .collect();
+    let body = rest[end + 4..].trim_start_matches(['\r', '\n']);
+    (fields, body)
+}
+
+/// Title from the first `# heading`, for files without a front matter title
+#[cfg(feature = "ssr")]
+fn first_heading(body: &str) -> Option<&str> {
+    body.lines().find_map(|l| l.strip_prefix("# ")).map(str::trim)
+}
+
+/// Render a content file's source into a page
+#[cfg(feature = "ssr")]
+pub fn render(slug: &str, source: &str) -> ContentPage {
+    use pulldown_cmark::{Options, Parser, html};
+
+    let (fields, body) = split_front_matter(source);
+    let field = |name: &str| fields.iter().find(|(k, _)| *k == name).map(|(_, v)| v.to_string());
+
+    let title = field("title")
+        .or_else(|| first_heading(body).map(str::to_string))
+        .unwrap_or_else(|| slug.replace('-', " "));
+
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_HEADING_ATTRIBUTES;
+    let mut rendered = String::with_capacity(body.len() * 3 / 2);
+    html::push_html(&mut rendered, Parser::new_ext(body, options));
+
+    ContentPage {
+        slug: slug.to_string(),
+        title,
+        description: field("description"),
+        html: rendered,
+    }
+}
+
+/// Directory content pages are read from
+#[cfg(feature = "ssr")]
+pub fn content_dir() -> std::path::PathBuf {
+    std::env::var("CONTENT_DIR")
+        .unwrap_or_else(|_| "./content".to_string())
+        .into()
+}
+
+/// Load and render `/<slug>` in `locale` (falling back to the untranslated file),
+/// or None if there's no such page
+#[cfg(feature = "ssr")]
+pub fn load(slug: &str, locale: crate::i18n::Locale) -> Option<ContentPage> {
+    if !is_valid_slug(slug) {
+        return None;
+    }
+    let dir = content_dir();
+    let candidates = [
+        dir.join(format!("{}.{}.md", slug, locale.code())),
+        dir.join(format!("{}.md", slug)),
+    ];
+    candidates.iter().find_map(|path| match std::fs::read_to_string(path) {
+        Ok(source) => Some(render(slug, &source)),
+        Err(e) => {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("[content] Failed to read {}: {}", path.display(), e);
+            }
+            None
+        }
+    })
+}
+
+/// Slugs of every page in the content directory, sorted (for the sitemap)
+#[cfg(feature = "ssr")]
+pub fn slugs() -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(content_dir()) else {
+        return Vec::new();
+    };
+    let mut slugs: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.file_name().to_str()?.strip_suffix(".md").map(str::to_string))
+        .filter(|slug| is_valid_slug(slug))
+        .collect();
+    slugs.sort();
+    slugs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugs_stay_inside_the_content_dir() {
+        assert!(is_valid_slug("delegation-guide"));
+        assert!(!is_valid_slug("../secrets"));
+        assert!(!is_valid_slug("About"));
+        assert!(!is_valid_slug(""));
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn front_matter_is_optional() {
+        let (fields, body) = split_front_matter("---\ntitle: About\ndescription: Who: us\n---\n\n# Hi\n");
+        assert_eq!(fields, [("title", "About"), ("description", "Who: us")]);
+        assert_eq!(body, "# Hi\n");
+
+        let (fields, body) = split_front_matter("# Hi\n");
+        assert!(fields.is_empty());
+        assert_eq!(first_heading(body), Some("Hi"));
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn renders_markdown_with_title_fallbacks() {
+        let page = render("delegation-guide", "# Guide\n\n| a |\n|---|\n| 1 |\n");
+        assert_eq!(page.title, "Guide");
+        assert!(page.html.contains("<table>"));
+        assert_eq!(render("faq", "plain text").title, "faq");
+    }
+}
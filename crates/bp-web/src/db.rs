@@ -1,5 +1,5 @@
 //! SQLite database access for bp-web.
-//! Manages the metrics snapshot table and provides read/write helpers.
+//! Manages the metrics snapshot and leader slot tables and provides read/write helpers.
 
 #[cfg(feature = "ssr")]
 mod ssr {
@@ -8,11 +8,13 @@ mod ssr {
     use std::sync::OnceLock;
 
     static DB_POOL: OnceLock<SqlitePool> = OnceLock::new();
+    static DATA_DIR: OnceLock<String> = OnceLock::new();
 
     /// Initialize the database pool and run migrations.
     /// Must be called once at startup before any queries.
     pub async fn init_db(data_dir: &str) -> Result<(), sqlx::Error> {
         std::fs::create_dir_all(data_dir).ok();
+        let _ = DATA_DIR.set(data_dir.to_string());
         let db_path = format!("{}/bp.sqlite", data_dir);
         let url = format!("sqlite:{}?mode=rwc", db_path);
 
@@ -29,6 +31,16 @@ mod ssr {
         Ok(())
     }
 
+    /// Data directory holding bp.sqlite, cache.sqlite and config.toml: the one passed to
+    /// `init_db` (`--data-dir`, or `--demo`'s seeded directory), else `$DATA_DIR` or ./data.
+    pub fn data_dir() -> String {
+        DATA_DIR
+            .get()
+            .cloned()
+            .or_else(|| std::env::var("DATA_DIR").ok())
+            .unwrap_or_else(|| "./data".to_string())
+    }
+
     /// Get a reference to the database pool.
     /// Panics if called before init_db.
     pub fn pool() -> &'static SqlitePool {
@@ -62,6 +74,60 @@ mod ssr {
         Ok(row)
     }
 
+    /// Replace the cached leader slots for an epoch and drop epochs before `oldest_epoch`.
+    pub async fn save_leader_slots(epoch: u64, slots: &[u64], oldest_epoch: u64) -> Result<(), sqlx::Error> {
+        let mut tx = pool().begin().await?;
+
+        sqlx::query("DELETE FROM leader_slots WHERE epoch = ? OR epoch < ?")
+            .bind(epoch as i64)
+            .bind(oldest_epoch as i64)
+            .execute(&mut *tx)
+            .await?;
+
+        for slot in slots {
+            sqlx::query("INSERT OR REPLACE INTO leader_slots (slot, epoch) VALUES (?, ?)")
+                .bind(*slot as i64)
+                .bind(epoch as i64)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Cached leader slots of one epoch, ascending.
+    pub async fn get_epoch_leader_slots(epoch: u64) -> Result<Vec<u64>, sqlx::Error> {
+        let rows: Vec<(i64,)> = sqlx::query_as("SELECT slot FROM leader_slots WHERE epoch = ? ORDER BY slot")
+            .bind(epoch as i64)
+            .fetch_all(pool())
+            .await?;
+
+        Ok(rows.into_iter().map(|(slot,)| slot as u64).collect())
+    }
+
+    /// First cached leader slot after `current_slot`, if any.
+    pub async fn get_next_leader_slot(current_slot: u64) -> Result<Option<u64>, sqlx::Error> {
+        let row: Option<(i64,)> = sqlx::query_as("SELECT slot FROM leader_slots WHERE slot > ? ORDER BY slot LIMIT 1")
+            .bind(current_slot as i64)
+            .fetch_optional(pool())
+            .await?;
+
+        Ok(row.map(|(slot,)| slot as u64))
+    }
+
+    /// Our most recent cached leader slots at or before `current_slot`, ascending.
+    pub async fn get_recent_leader_slots(current_slot: u64, limit: u32) -> Result<Vec<u64>, sqlx::Error> {
+        let rows: Vec<(i64,)> =
+            sqlx::query_as("SELECT slot FROM leader_slots WHERE slot <= ? ORDER BY slot DESC LIMIT ?")
+                .bind(current_slot as i64)
+                .bind(limit as i64)
+                .fetch_all(pool())
+                .await?;
+
+        Ok(rows.into_iter().rev().map(|(slot,)| slot as u64).collect())
+    }
+
     /// Set a metadata key-value pair.
     pub async fn set_metadata(key: &str, value: &str) -> Result<(), sqlx::Error> {
         sqlx::query(
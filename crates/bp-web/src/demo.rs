@@ -0,0 +1,685 @@
+//! Synthetic sample data for `bp-web --demo`.
+//!
+//! Seeds a throwaway data directory with a `config.toml`, a `cache.sqlite` holding the
+//! tables bp-web reads (same shape validator-accounting writes) and a metrics snapshot,
+//! so the site, `/financials` and the public metrics render without API keys or real
+//! history. Rows are generated from a fixed seed and dated relative to today, and the
+//! directory is rebuilt on every start.
+
+use anyhow::{Context, Result};
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use sqlx::{Connection, Executor, SqliteConnection};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::api::{
+    GossipNode, GossipPresence, IpLocation, JitoEpochReward, JitoMevHistory, NetworkApy, NetworkComparison, SfdpStatus,
+    StakewizValidator,
+};
+use crate::components::metrics::MetricsData;
+use crate::config::CONFIG;
+use crate::financials::{self, config::ValidatorConfig};
+
+/// Subdirectory of `--data-dir` the demo is written to, so real data is never touched
+pub const SUBDIR: &str = "demo";
+
+/// `/financials` Basic Auth password in demo mode when FINANCIALS_PASSWORD is unset
+pub const PASSWORD: &str = "demo";
+
+/// Epochs of history to generate (~1 year at ~2 days per epoch)
+const EPOCHS: u64 = 180;
+/// Most recent generated epoch
+const LAST_EPOCH: u64 = 880;
+const SLOTS_PER_EPOCH: u64 = 432_000;
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+const COMMISSION_PERCENT: u8 = 5;
+const MEV_COMMISSION_BPS: u32 = 1_000;
+/// BAM and DoubleZero only cover the most recent epochs, like a validator that joined late
+const BAM_EPOCHS: u64 = 30;
+const DOUBLEZERO_EPOCHS: u64 = 60;
+
+const PERSONAL_WALLET: &str = "DemoPersonWa11et1111111111111111111111111111";
+const DONOR: &str = "DemoSupporter111111111111111111111111111111";
+const SFDP_REIMBURSEMENT: &str = "DtZWL3BPKa5hw7yQYvaFR29PcXThpLHVU2XAAZrcLiSe";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// True once `seed` has run in this process
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Write config.toml and a fresh cache.sqlite into `data_dir`.
+///
+/// Call `seed_metrics` after `db::init_db` to add the public metrics snapshot.
+pub async fn seed(data_dir: &str) -> Result<()> {
+    std::fs::create_dir_all(data_dir).with_context(|| format!("Failed to create {}", data_dir))?;
+
+    let today = Utc::now().date_naive();
+    let config_path = Path::new(data_dir).join("config.toml");
+    std::fs::write(&config_path, config_toml(epoch_date(today, LAST_EPOCH + 1 - EPOCHS)))
+        .with_context(|| format!("Failed to write {}", config_path.display()))?;
+    let config = ValidatorConfig::load(&config_path)?;
+
+    let cache_path = financials::db::cache_path(data_dir);
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{}", cache_path.display(), suffix));
+    }
+    let url = format!("sqlite:{}?mode=rwc", cache_path.display());
+    let mut conn = SqliteConnection::connect(&url)
+        .await
+        .with_context(|| format!("Failed to create demo cache at {}", data_dir))?;
+    seed_cache(&mut conn, &config, today).await?;
+
+    ENABLED.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Store a synthetic metrics snapshot in bp.sqlite (requires `db::init_db`)
+pub async fn seed_metrics() -> Result<()> {
+    let json = serde_json::to_string(&metrics_snapshot())?;
+    crate::db::save_metrics_snapshot(&json).await?;
+    Ok(())
+}
+
+/// Deterministic xorshift generator, so every demo start shows the same books
+struct Rng(u64);
+
+impl Rng {
+    fn next_f64(&mut self) -> f64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform in `[low, high)`
+    fn range(&mut self, low: f64, high: f64) -> f64 {
+        low + (high - low) * self.next_f64()
+    }
+}
+
+/// Epochs are ~2 days; `LAST_EPOCH` ends today
+fn epoch_date(today: NaiveDate, epoch: u64) -> NaiveDate {
+    today - Duration::days(2 * LAST_EPOCH.saturating_sub(epoch) as i64)
+}
+
+/// Smooth SOL/USD walk between roughly $120 and $220
+fn sol_price(date: NaiveDate) -> f64 {
+    let day = date.num_days_from_ce() as f64;
+    170.0 + 35.0 * (day / 45.0).sin() + 12.0 * (day / 11.0).sin()
+}
+
+/// Active stake ramps from 60k to 240k SOL over the year
+fn active_stake_sol(epoch: u64) -> f64 {
+    let progress = (epoch + EPOCHS - LAST_EPOCH) as f64 / EPOCHS as f64;
+    60_000.0 + 180_000.0 * progress.clamp(0.0, 1.0)
+}
+
+fn lamports(sol: f64) -> i64 {
+    (sol * LAMPORTS_PER_SOL).round() as i64
+}
+
+fn config_toml(bootstrap: NaiveDate) -> String {
+    format!(
+        r#"# Generated by `bp-web --demo`; rewritten on every start.
+[validator]
+vote_account = "{vote}"
+identity = "{identity}"
+withdraw_authority = "{withdraw}"
+personal_wallet = "{personal}"
+commission_percent = {commission}
+first_reward_epoch = {first_epoch}
+bootstrap_date = "{bootstrap}"
+sfdp_acceptance_date = "{bootstrap}"
+
+[reporting]
+estimated_tax_rate = 0.3
+"#,
+        vote = CONFIG.vote_account,
+        identity = CONFIG.identity,
+        withdraw = CONFIG.withdraw_authority,
+        personal = PERSONAL_WALLET,
+        commission = COMMISSION_PERCENT,
+        first_epoch = LAST_EPOCH + 1 - EPOCHS,
+        bootstrap = bootstrap.format("%Y-%m-%d"),
+    )
+}
+
+async fn seed_cache(conn: &mut SqliteConnection, config: &ValidatorConfig, today: NaiveDate) -> Result<()> {
+    conn.execute(SCHEMA).await?;
+
+    let mut tx = conn.begin().await?;
+    let mut rng = Rng(0x5eed_b10c_9a71_a4e7);
+    let first_epoch = LAST_EPOCH + 1 - EPOCHS;
+    let start = epoch_date(today, first_epoch);
+
+    let mut date = start;
+    while date <= today {
+        sqlx::query("INSERT INTO prices (date, usd_price) VALUES (?, ?)")
+            .bind(date.format("%Y-%m-%d").to_string())
+            .bind((sol_price(date) * 100.0).round() / 100.0)
+            .execute(&mut *tx)
+            .await?;
+        date += Duration::days(1);
+    }
+
+    // Vote fees per month, for the SFDP reimbursements paid at each month end
+    let mut monthly_vote_fees: std::collections::BTreeMap<(i32, u32), f64> = Default::default();
+
+    for epoch in first_epoch..=LAST_EPOCH {
+        let date = epoch_date(today, epoch);
+        let date_str = date.format("%Y-%m-%d").to_string();
+        let slot = (epoch * SLOTS_PER_EPOCH) as i64;
+        let stake = active_stake_sol(epoch);
+
+        // Inflation rewards at ~6.5% APY, of which the validator keeps its commission
+        let commission_sol = stake * rng.range(0.062, 0.068) / 182.5 * COMMISSION_PERCENT as f64 / 100.0;
+        sqlx::query(
+            "INSERT INTO epoch_rewards (epoch, amount_lamports, amount_sol, commission, effective_slot, date)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(epoch as i64)
+        .bind(lamports(commission_sol))
+        .bind(commission_sol)
+        .bind(COMMISSION_PERCENT as i64)
+        .bind(slot)
+        .bind(&date_str)
+        .execute(&mut *tx)
+        .await?;
+
+        // Leader slots come in groups of 4, in proportion to stake (~390M SOL staked network-wide)
+        let leader_slots = ((stake / 390_000_000.0 * SLOTS_PER_EPOCH as f64 / 4.0).round() as u64).max(1) * 4;
+        let skipped = (leader_slots as f64 * rng.range(0.0, 0.03)).round() as u64;
+        let blocks = leader_slots - skipped;
+        let fees_sol = blocks as f64 * rng.range(0.008, 0.016);
+        sqlx::query(
+            "INSERT INTO leader_fees (epoch, leader_slots, blocks_produced, skipped_slots, total_fees_lamports,
+                                      total_fees_sol, date)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(epoch as i64)
+        .bind(leader_slots as i64)
+        .bind(blocks as i64)
+        .bind(skipped as i64)
+        .bind(lamports(fees_sol))
+        .bind(fees_sol)
+        .bind(&date_str)
+        .execute(&mut *tx)
+        .await?;
+
+        let tips = lamports(blocks as f64 * rng.range(0.02, 0.06));
+        let mev_commission = tips * MEV_COMMISSION_BPS as i64 / 10_000;
+        sqlx::query(
+            "INSERT INTO mev_claims (epoch, provider, total_tips_lamports, commission_lamports, amount_sol, date)
+             VALUES (?, 'jito', ?, ?, ?, ?)",
+        )
+        .bind(epoch as i64)
+        .bind(tips)
+        .bind(mev_commission)
+        .bind(mev_commission as f64 / LAMPORTS_PER_SOL)
+        .bind(&date_str)
+        .execute(&mut *tx)
+        .await?;
+
+        // Roughly one vote per slot at 5000 lamports each
+        let votes = (SLOTS_PER_EPOCH as f64 * rng.range(0.95, 0.99)) as i64;
+        let vote_fee = votes * 5_000;
+        sqlx::query(
+            "INSERT INTO vote_costs (epoch, vote_count, total_fee_lamports, total_fee_sol, source, date)
+             VALUES (?, ?, ?, ?, 'estimated', ?)",
+        )
+        .bind(epoch as i64)
+        .bind(votes)
+        .bind(vote_fee)
+        .bind(vote_fee as f64 / LAMPORTS_PER_SOL)
+        .bind(&date_str)
+        .execute(&mut *tx)
+        .await?;
+        *monthly_vote_fees.entry((date.year(), date.month())).or_default() += vote_fee as f64 / LAMPORTS_PER_SOL;
+
+        // Up to 16 credits per timely vote
+        sqlx::query("INSERT INTO epoch_performance (epoch, vote_credits, active_stake_lamports) VALUES (?, ?, ?)")
+            .bind(epoch as i64)
+            .bind(votes * 15)
+            .bind(lamports(stake))
+            .execute(&mut *tx)
+            .await?;
+
+        if epoch + BAM_EPOCHS > LAST_EPOCH {
+            let jitosol = rng.range(0.05, 0.15);
+            let rate = 1.25 + (epoch % 100) as f64 * 0.0005;
+            sqlx::query(
+                "INSERT INTO bam_claims (tx_signature, epoch, amount_jitosol_lamports, amount_sol_equivalent,
+                                         jitosol_sol_rate, date)
+                 VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(format!("demo-bam-{}", epoch))
+            .bind(epoch as i64)
+            .bind(lamports(jitosol))
+            .bind(jitosol * rate)
+            .bind(rate)
+            .bind(&date_str)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        if epoch + DOUBLEZERO_EPOCHS > LAST_EPOCH {
+            let fee_base = lamports(fees_sol);
+            let liability = fee_base * 500 / 10_000;
+            sqlx::query(
+                "INSERT INTO doublezero_fees (epoch, fee_base_lamports, liability_lamports, liability_sol,
+                                              fee_rate_bps, date, source, is_estimate)
+                 VALUES (?, ?, ?, ?, 500, ?, 'computed', ?)",
+            )
+            .bind(epoch as i64)
+            .bind(fee_base)
+            .bind(liability)
+            .bind(liability as f64 / LAMPORTS_PER_SOL)
+            .bind(&date_str)
+            .bind((epoch == LAST_EPOCH) as i64)
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    // Transfers: seed capital, SFDP reimbursements, vote account top-ups, owner withdrawals, tips
+    let mut transfers: Vec<(NaiveDate, &str, &str, f64)> = vec![
+        (start, PERSONAL_WALLET, CONFIG.withdraw_authority, 40.0),
+        (start, PERSONAL_WALLET, CONFIG.identity, 10.0),
+    ];
+    for (&(year, month), fees) in &monthly_vote_fees {
+        let Some(month_end) = NaiveDate::from_ymd_opt(year, month, 1)
+            .and_then(|d| d.checked_add_months(chrono::Months::new(1)))
+            .map(|d| d.pred_opt().unwrap_or(d))
+            .filter(|d| *d <= today)
+        else {
+            continue;
+        };
+        let coverage = config.sfdp_coverage_percent(&month_end);
+        if coverage > 0.0 {
+            transfers.push((month_end, SFDP_REIMBURSEMENT, CONFIG.identity, fees * coverage));
+        }
+        transfers.push((month_end, CONFIG.withdraw_authority, CONFIG.identity, fees.ceil()));
+        if month % 2 == 0 {
+            transfers.push((month_end, CONFIG.withdraw_authority, PERSONAL_WALLET, 15.0));
+        }
+    }
+    if let Some(tip_jar) = CONFIG.donation_address {
+        transfers.push((today - Duration::days(20), DONOR, tip_jar, 0.5));
+    }
+    transfers.sort_by_key(|(date, ..)| *date);
+
+    for (i, (date, from, to, amount_sol)) in transfers.iter().enumerate() {
+        let timestamp = date.and_hms_opt(12, 0, 0).map(|dt| dt.and_utc().timestamp());
+        let slot = (LAST_EPOCH + 1) as i64 * SLOTS_PER_EPOCH as i64 - (today - *date).num_days() * 216_000 + i as i64;
+        sqlx::query(
+            "INSERT INTO sol_transfers (signature, slot, timestamp, date, from_address, to_address, amount_lamports,
+                                        amount_sol, from_label, to_label, from_category, to_category)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(format!("demo-transfer-{}", i))
+        .bind(slot)
+        .bind(timestamp)
+        .bind(date.format("%Y-%m-%d").to_string())
+        .bind(*from)
+        .bind(*to)
+        .bind(lamports(*amount_sol))
+        .bind(*amount_sol)
+        .bind(label(from))
+        .bind(label(to))
+        .bind(category(from))
+        .bind(category(to))
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    seed_expenses(&mut tx, start).await?;
+
+    sqlx::query(
+        "INSERT INTO ingestion_runs (triggered_by, finished_at, status, row_counts)
+         VALUES ('demo', datetime('now'), 'success', ?)",
+    )
+    .bind(format!(
+        r#"{{"epoch_rewards":{0},"leader_fees":{0},"mev_claims":{0}}}"#,
+        EPOCHS
+    ))
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+async fn seed_expenses(tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>, start: NaiveDate) -> Result<()> {
+    let start_str = start.format("%Y-%m-%d").to_string();
+    for (vendor, category, description, amount_usd) in [
+        ("Bare Metal Co", "Hosting", "Dedicated validator server", 1_150.0),
+        ("RPC Provider", "Software", "RPC and monitoring plan", 49.0),
+    ] {
+        sqlx::query(
+            "INSERT INTO recurring_expenses (vendor, category, description, amount_usd, paid_with, start_date)
+             VALUES (?, ?, ?, ?, 'USD', ?)",
+        )
+        .bind(vendor)
+        .bind(category)
+        .bind(description)
+        .bind(amount_usd)
+        .bind(&start_str)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    for (offset_days, vendor, category, description, amount_usd) in [
+        (
+            0,
+            "Hardware Store",
+            "Hardware",
+            "NVMe drives for ledger and accounts",
+            820.0,
+        ),
+        (
+            3,
+            "Sysadmin Contractor",
+            "Contractor",
+            "Initial server hardening",
+            600.0,
+        ),
+        (150, "Hardware Store", "Hardware", "Replacement NVMe drive", 310.0),
+    ] {
+        sqlx::query(
+            "INSERT INTO expenses (date, vendor, category, description, amount_usd, paid_with)
+             VALUES (?, ?, ?, ?, ?, 'USD')",
+        )
+        .bind((start + Duration::days(offset_days)).format("%Y-%m-%d").to_string())
+        .bind(vendor)
+        .bind(category)
+        .bind(description)
+        .bind(amount_usd)
+        .execute(&mut **tx)
+        .await?;
+    }
+    Ok(())
+}
+
+fn label(address: &str) -> &'static str {
+    match address {
+        a if a == CONFIG.vote_account => "Vote Account",
+        a if a == CONFIG.identity => "Validator Identity",
+        a if a == CONFIG.withdraw_authority => "Withdraw Authority",
+        PERSONAL_WALLET => "Personal Wallet",
+        SFDP_REIMBURSEMENT => "SFDP Vote Reimbursement",
+        _ => "Unknown",
+    }
+}
+
+fn category(address: &str) -> &'static str {
+    match address {
+        a if a == CONFIG.vote_account || a == CONFIG.identity || a == CONFIG.withdraw_authority => "ValidatorSelf",
+        PERSONAL_WALLET => "PersonalWallet",
+        SFDP_REIMBURSEMENT => "SolanaFoundation",
+        _ => "Unknown",
+    }
+}
+
+fn metrics_snapshot() -> MetricsData {
+    let stake = active_stake_sol(LAST_EPOCH);
+    MetricsData {
+        validator: StakewizValidator {
+            rank: 412,
+            identity: CONFIG.identity.to_string(),
+            vote_identity: CONFIG.vote_account.to_string(),
+            last_vote: LAST_EPOCH * SLOTS_PER_EPOCH + 200_000,
+            root_slot: LAST_EPOCH * SLOTS_PER_EPOCH + 199_968,
+            credits: 1_250_000_000,
+            epoch_credits: 6_800_000,
+            activated_stake: stake,
+            version: "2.3.6".to_string(),
+            delinquent: false,
+            skip_rate: 1.2,
+            name: Some(CONFIG.name.to_string()),
+            description: Some("Demo data".to_string()),
+            commission: COMMISSION_PERCENT,
+            is_jito: true,
+            jito_commission_bps: MEV_COMMISSION_BPS,
+            vote_success: 99.1,
+            wiz_score: 94.5,
+            uptime: 99.98,
+            ip_city: Some("Frankfurt".to_string()),
+            ip_country: Some("Germany".to_string()),
+            ip_org: Some("Example Hosting".to_string()),
+            epoch: LAST_EPOCH,
+            apy_estimate: Some(7.4),
+            staking_apy: 6.6,
+            jito_apy: 0.8,
+            total_apy: 7.4,
+            credit_ratio: 99.2,
+            stake_ratio: Some(stake / 390_000_000.0),
+            stake_weight: Some(stake / 390_000_000.0 * 100.0),
+            asn: Some("AS64500".to_string()),
+        },
+        mev_history: Some(JitoMevHistory {
+            vote_account: CONFIG.vote_account.to_string(),
+            epochs: (LAST_EPOCH - 4..=LAST_EPOCH)
+                .rev()
+                .map(|epoch| {
+                    let tips = (active_stake_sol(epoch) / 240_000.0 * 8.0 * LAMPORTS_PER_SOL) as u64;
+                    JitoEpochReward {
+                        epoch,
+                        mev_rewards: tips,
+                        mev_rewards_alt: 0,
+                        total_rewards: tips,
+                        mev_commission_earned: tips * MEV_COMMISSION_BPS as u64 / 10_000,
+                        commission_earned_alt: 0,
+                    }
+                })
+                .collect(),
+        }),
+        network_comp: Some(NetworkComparison {
+            total_validators: 1_050,
+            skip_rate_percentile: 72,
+            stake_percentile: 61,
+        }),
+        sfdp_status: Some(SfdpStatus {
+            is_participant: true,
+            program_name: Some("Solana Foundation Delegation Program".to_string()),
+            status: Some("Approved".to_string()),
+            onboarding_date: None,
+        }),
+        network_apy: Some(NetworkApy {
+            staking_apy: 6.5,
+            jito_apy: 0.7,
+            total_apy: 7.2,
+            validators: 1_050,
+        }),
+        gossip: Some(GossipPresence {
+            cluster_nodes: 4_900,
+            node: Some(GossipNode {
+                pubkey: CONFIG.identity.to_string(),
+                gossip: Some("203.0.113.10:8001".to_string()),
+                tpu: Some("203.0.113.10:8003".to_string()),
+                tpu_quic: Some("203.0.113.10:8009".to_string()),
+                tpu_forwards: Some("203.0.113.10:8004".to_string()),
+                tpu_vote: Some("203.0.113.10:8005".to_string()),
+                serve_repair: Some("203.0.113.10:8008".to_string()),
+                rpc: None,
+                pubsub: None,
+                version: Some("2.3.6".to_string()),
+                feature_set: Some(3_294_202_862),
+                shred_version: Some(50_093),
+            }),
+        }),
+        ip_location: Some(IpLocation {
+            ip: "203.0.113.10".to_string(),
+            city: Some("Frankfurt".to_string()),
+            region: Some("Hesse".to_string()),
+            country: Some("DE".to_string()),
+            asn: Some(64_500),
+            org: Some("Example Hosting".to_string()),
+            source: "demo".to_string(),
+        }),
+        stale: Default::default(),
+    }
+}
+
+/// The cache.sqlite tables bp-web reads, as created by validator-accounting's `init_schema`
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS epoch_rewards (
+    epoch INTEGER PRIMARY KEY,
+    amount_lamports INTEGER NOT NULL,
+    amount_sol REAL NOT NULL,
+    commission INTEGER NOT NULL,
+    effective_slot INTEGER NOT NULL,
+    date TEXT,
+    fetched_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+CREATE TABLE IF NOT EXISTS leader_fees (
+    epoch INTEGER PRIMARY KEY,
+    leader_slots INTEGER NOT NULL,
+    blocks_produced INTEGER NOT NULL,
+    skipped_slots INTEGER NOT NULL,
+    total_fees_lamports INTEGER NOT NULL,
+    total_fees_sol REAL NOT NULL,
+    date TEXT,
+    fetched_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+CREATE TABLE IF NOT EXISTS mev_claims (
+    epoch INTEGER NOT NULL,
+    provider TEXT NOT NULL DEFAULT 'jito',
+    total_tips_lamports INTEGER NOT NULL,
+    commission_lamports INTEGER NOT NULL,
+    amount_sol REAL NOT NULL,
+    date TEXT,
+    fetched_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (epoch, provider)
+);
+CREATE TABLE IF NOT EXISTS bam_claims (
+    tx_signature TEXT PRIMARY KEY,
+    epoch INTEGER NOT NULL,
+    amount_jitosol_lamports INTEGER NOT NULL,
+    amount_sol_equivalent REAL NOT NULL,
+    jitosol_sol_rate REAL,
+    claimed_at TEXT,
+    date TEXT NOT NULL,
+    fetched_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+CREATE TABLE IF NOT EXISTS vote_costs (
+    epoch INTEGER PRIMARY KEY,
+    vote_count INTEGER NOT NULL,
+    total_fee_lamports INTEGER NOT NULL,
+    total_fee_sol REAL NOT NULL,
+    source TEXT NOT NULL,
+    date TEXT,
+    fetched_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+CREATE TABLE IF NOT EXISTS epoch_performance (
+    epoch INTEGER PRIMARY KEY,
+    vote_credits INTEGER,
+    active_stake_lamports INTEGER,
+    fetched_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+CREATE TABLE IF NOT EXISTS reward_imports (
+    epoch INTEGER PRIMARY KEY,
+    source TEXT NOT NULL,
+    commission_lamports INTEGER,
+    activated_stake_lamports INTEGER,
+    imported_at TEXT NOT NULL DEFAULT (datetime('now')),
+    verified_at TEXT,
+    onchain_lamports INTEGER
+);
+CREATE TABLE IF NOT EXISTS doublezero_fees (
+    epoch INTEGER PRIMARY KEY,
+    fee_base_lamports INTEGER NOT NULL,
+    liability_lamports INTEGER NOT NULL,
+    liability_sol REAL NOT NULL,
+    fee_rate_bps INTEGER NOT NULL,
+    date TEXT,
+    source TEXT NOT NULL,
+    is_estimate INTEGER NOT NULL DEFAULT 0,
+    fetched_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+CREATE TABLE IF NOT EXISTS prices (
+    date TEXT PRIMARY KEY,
+    usd_price REAL NOT NULL,
+    fetched_at TEXT NOT NULL DEFAULT (datetime('now')),
+    source TEXT NOT NULL DEFAULT 'exact'
+);
+CREATE TABLE IF NOT EXISTS expenses (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    date TEXT NOT NULL,
+    vendor TEXT NOT NULL,
+    category TEXT NOT NULL,
+    description TEXT NOT NULL,
+    amount_usd REAL NOT NULL,
+    paid_with TEXT NOT NULL,
+    invoice_id TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+CREATE TABLE IF NOT EXISTS recurring_expenses (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    vendor TEXT NOT NULL,
+    category TEXT NOT NULL,
+    description TEXT NOT NULL,
+    amount_usd REAL NOT NULL,
+    paid_with TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+CREATE TABLE IF NOT EXISTS sol_transfers (
+    signature TEXT NOT NULL,
+    slot INTEGER NOT NULL,
+    timestamp INTEGER,
+    date TEXT,
+    from_address TEXT NOT NULL,
+    to_address TEXT NOT NULL,
+    amount_lamports INTEGER NOT NULL,
+    amount_sol REAL NOT NULL,
+    from_label TEXT NOT NULL,
+    to_label TEXT NOT NULL,
+    from_category TEXT NOT NULL,
+    to_category TEXT NOT NULL,
+    fetched_at TEXT NOT NULL DEFAULT (datetime('now')),
+    memo TEXT,
+    program TEXT,
+    PRIMARY KEY (signature, from_address, to_address, amount_lamports)
+);
+CREATE TABLE IF NOT EXISTS ingestion_runs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    triggered_by TEXT NOT NULL,
+    started_at TEXT NOT NULL DEFAULT (datetime('now')),
+    finished_at TEXT,
+    status TEXT NOT NULL DEFAULT 'running',
+    row_counts TEXT NOT NULL DEFAULT '{}',
+    errors TEXT NOT NULL DEFAULT '[]'
+);
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn demo_cache_builds_a_report() {
+        let dir = std::env::temp_dir().join(format!("bp-demo-{}", std::process::id()));
+        let data_dir = dir.to_str().unwrap();
+        seed(data_dir).await.unwrap();
+
+        let page = crate::financials::generate_report(data_dir).await;
+        let html = String::from_utf8(
+            page.body(crate::financials::precompressed::ContentEncoding::Identity)
+                .to_vec(),
+        )
+        .unwrap();
+        assert!(html.contains("cumulative_profit_usd"), "demo report has no timeline");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn epochs_are_two_days_apart_ending_today() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        assert_eq!(epoch_date(today, LAST_EPOCH), today);
+        assert_eq!(epoch_date(today, LAST_EPOCH - 1), today - Duration::days(2));
+    }
+}
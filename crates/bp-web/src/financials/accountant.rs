@@ -0,0 +1,317 @@
+//! Accountant view of `/financials` (`?view=accountant`).
+//!
+//! The full report is a timeline of epochs, SOL amounts and price sources, which is
+//! more than a CPA needs. This view rolls the operating timeline up into USD only:
+//! income and expenses by month, totals per category for each year, and CSV downloads,
+//! all under plain-English category names. Amounts are the timeline's, so they match
+//! the dashboard (vote fees net of the SFDP offset).
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::admin::{STYLE, escape_html};
+use super::sql_console::csv_field;
+use super::types::{Expense, ExpenseCategory, TimelineEvent};
+
+pub const INCOME: &str = "Income";
+pub const EXPENSE: &str = "Expense";
+
+/// Total of one category in one month (always positive; `kind` gives the sign)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CategoryMonth {
+    pub month: String,
+    pub kind: &'static str,
+    pub category: &'static str,
+    pub amount_usd: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MonthTotals {
+    pub month: String,
+    pub income_usd: f64,
+    pub expenses_usd: f64,
+    pub net_usd: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AccountantReport {
+    /// Oldest month first, income before expenses
+    pub categories: Vec<CategoryMonth>,
+    /// Oldest first
+    pub months: Vec<MonthTotals>,
+}
+
+/// Plain-English name of an on-chain timeline event, or None for events that
+/// aren't income or expenses (or are off-chain expenses, categorized separately)
+fn event_category(event_type: &str) -> Option<(&'static str, &'static str)> {
+    match event_type {
+        "commission" => Some((INCOME, "Staking commission")),
+        "leader_fees" => Some((INCOME, "Transaction fees from blocks produced")),
+        "mev" => Some((INCOME, "Block builder tips (Jito MEV)")),
+        "bam" => Some((INCOME, "Block auction rewards (BAM)")),
+        "donation" => Some((INCOME, "Donations received")),
+        "vote_cost" => Some((EXPENSE, "Network voting fees (after Foundation subsidy)")),
+        "doublezero" => Some((EXPENSE, "Network connectivity fees (DoubleZero)")),
+        _ => None,
+    }
+}
+
+fn expense_category(category: ExpenseCategory) -> &'static str {
+    match category {
+        ExpenseCategory::Hosting => "Server hosting",
+        ExpenseCategory::Contractor => "Contract labor",
+        ExpenseCategory::Hardware => "Hardware",
+        ExpenseCategory::Software => "Software subscriptions",
+        ExpenseCategory::VoteFees => "Network voting fees (paid off-chain)",
+        ExpenseCategory::FailedTxFees => "Network fees on failed transactions",
+        ExpenseCategory::OpsFees => "Network fees on operational transactions",
+        ExpenseCategory::Other => "Other expenses",
+    }
+}
+
+/// Roll the operating timeline and off-chain expenses up by month and category
+pub fn compute(timeline: &[TimelineEvent], expenses: &[Expense]) -> AccountantReport {
+    // (month, kind, category) -> USD; "Income" sorts before "Expense" via the flag
+    let mut totals: BTreeMap<(String, bool, &'static str), f64> = BTreeMap::new();
+    let mut add = |date: &str, kind: &'static str, category: &'static str, amount_usd: f64| {
+        if let Some(month) = date.get(..7).filter(|m| m.as_bytes().get(4) == Some(&b'-')) {
+            *totals
+                .entry((month.to_string(), kind == EXPENSE, category))
+                .or_default() += amount_usd;
+        }
+    };
+
+    for ev in timeline.iter().filter(|ev| ev.is_pnl) {
+        if let Some((kind, category)) = event_category(ev.event_type) {
+            add(&ev.date, kind, category, ev.amount_usd.abs());
+        }
+    }
+    for e in expenses {
+        add(&e.date, EXPENSE, expense_category(e.category), e.amount_usd);
+    }
+
+    let mut months: Vec<MonthTotals> = Vec::new();
+    let categories: Vec<CategoryMonth> = totals
+        .into_iter()
+        .map(|((month, is_expense, category), amount_usd)| {
+            if months.last().is_none_or(|m| m.month != month) {
+                months.push(MonthTotals {
+                    month: month.clone(),
+                    income_usd: 0.0,
+                    expenses_usd: 0.0,
+                    net_usd: 0.0,
+                });
+            }
+            let totals = months.last_mut().expect("pushed above");
+            if is_expense {
+                totals.expenses_usd += amount_usd;
+            } else {
+                totals.income_usd += amount_usd;
+            }
+            totals.net_usd = totals.income_usd - totals.expenses_usd;
+            CategoryMonth {
+                month,
+                kind: if is_expense { EXPENSE } else { INCOME },
+                category,
+                amount_usd,
+            }
+        })
+        .collect();
+
+    AccountantReport { categories, months }
+}
+
+/// Category totals for one year ("YYYY"), income first
+pub fn year_totals<'a>(report: &'a AccountantReport, year: &str) -> Vec<(&'static str, &'a str, f64)> {
+    let mut totals: BTreeMap<(bool, &str), f64> = BTreeMap::new();
+    for c in report.categories.iter().filter(|c| c.month.starts_with(year)) {
+        *totals.entry((c.kind == EXPENSE, c.category)).or_default() += c.amount_usd;
+    }
+    totals
+        .into_iter()
+        .map(|((is_expense, category), amount)| (if is_expense { EXPENSE } else { INCOME }, category, amount))
+        .collect()
+}
+
+/// Years covered by the report, newest first
+fn years(report: &AccountantReport) -> Vec<&str> {
+    let mut years: Vec<&str> = report.months.iter().filter_map(|m| m.month.get(..4)).collect();
+    years.dedup();
+    years.reverse();
+    years
+}
+
+/// Monthly income, expenses and net income
+pub fn months_csv(report: &AccountantReport) -> String {
+    let mut out = String::from("Month,Income (USD),Expenses (USD),Net Income (USD)\n");
+    for m in &report.months {
+        out.push_str(&format!(
+            "{},{:.2},{:.2},{:.2}\n",
+            m.month, m.income_usd, m.expenses_usd, m.net_usd
+        ));
+    }
+    out
+}
+
+/// Every category total by month
+pub fn categories_csv(report: &AccountantReport) -> String {
+    let mut out = String::from("Month,Type,Category,Amount (USD)\n");
+    for c in &report.categories {
+        out.push_str(&format!(
+            "{},{},{},{:.2}\n",
+            c.month,
+            c.kind,
+            csv_field(c.category),
+            c.amount_usd
+        ));
+    }
+    out
+}
+
+fn money(v: f64) -> String {
+    if v < 0.0 {
+        format!("-${:.2}", -v)
+    } else {
+        format!("${:.2}", v)
+    }
+}
+
+/// Per year: category totals, then the months
+pub fn render_page(report: Option<&AccountantReport>) -> String {
+    let body = match report {
+        None => "<p class='status-failed'>Financial data unavailable.</p>".to_string(),
+        Some(report) if report.months.is_empty() => "<p class='muted'>No income or expenses yet.</p>".to_string(),
+        Some(report) => {
+            let mut html = String::from(concat!(
+                "<p>Download: <a href='/financials?view=accountant&amp;download=monthly'>Monthly totals (CSV)</a> · ",
+                "<a href='/financials?view=accountant&amp;download=categories'>Categories by month (CSV)</a></p>"
+            ));
+            let ledgers: Vec<String> = super::ledger_csv::LEDGERS
+                .iter()
+                .map(|l| format!("<a href='/financials/ledgers/{}.csv'>{}</a>", l.name, l.label))
+                .collect();
+            html.push_str(&format!("<p>Ledgers (CSV): {}</p>", ledgers.join(" · ")));
+            for year in years(report) {
+                let totals = year_totals(report, year);
+                let income: f64 = totals.iter().filter(|t| t.0 == INCOME).map(|t| t.2).sum();
+                let expenses: f64 = totals.iter().filter(|t| t.0 == EXPENSE).map(|t| t.2).sum();
+
+                html.push_str(&format!(
+                    "<h2>{}</h2><table><tr><th>Category</th><th>Amount</th></tr>",
+                    escape_html(year)
+                ));
+                for (kind, label, total) in [(INCOME, "Total income", income), (EXPENSE, "Total expenses", expenses)] {
+                    for (_, category, amount) in totals.iter().filter(|t| t.0 == kind) {
+                        html.push_str(&format!(
+                            "<tr><td>{}</td><td>{}</td></tr>",
+                            escape_html(category),
+                            money(*amount)
+                        ));
+                    }
+                    html.push_str(&format!("<tr><th>{}</th><th>{}</th></tr>", label, money(total)));
+                }
+                html.push_str(&format!(
+                    "<tr><th>Net income</th><th>{}</th></tr></table>",
+                    money(income - expenses)
+                ));
+
+                html.push_str("<table><tr><th>Month</th><th>Income</th><th>Expenses</th><th>Net income</th></tr>");
+                for m in report.months.iter().rev().filter(|m| m.month.starts_with(year)) {
+                    html.push_str(&format!(
+                        "<tr><td>{}</td><td>{}</td><td>{}</td><td{}>{}</td></tr>",
+                        escape_html(&m.month),
+                        money(m.income_usd),
+                        money(m.expenses_usd),
+                        if m.net_usd < 0.0 { " class='status-failed'" } else { "" },
+                        money(m.net_usd)
+                    ));
+                }
+                html.push_str("</table>");
+            }
+            html
+        }
+    };
+
+    format!(
+        concat!(
+            "<!DOCTYPE html><html lang='en'><head><meta charset='utf-8'>",
+            "<meta name='viewport' content='width=device-width, initial-scale=1'>",
+            "<title>Block Parliament · Accountant Summary</title>",
+            "<link rel='icon' type='image/png' href='/logo/owl-64.png'>",
+            "<style>{}</style></head><body>",
+            "<h1>Accountant Summary</h1>",
+            "<p><a href='/financials'>&larr; full report</a></p>",
+            "<p class='muted'>All amounts in US dollars. Crypto income and fees are valued at the ",
+            "SOL/USD price on the day they occurred; expenses are counted in the month incurred.</p>",
+            "{}</body></html>"
+        ),
+        STYLE, body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::financials::types::EventDetail;
+
+    fn event(date: &str, event_type: &'static str, amount_usd: f64) -> TimelineEvent {
+        TimelineEvent {
+            date: date.to_string(),
+            epoch: None,
+            event_type,
+            label: String::new(),
+            sublabel: None,
+            amount_sol: 0.0,
+            amount_usd,
+            price_source: None,
+            signature: None,
+            source_id: String::new(),
+            detail: EventDetail::default(),
+            cumulative_profit_usd: 0.0,
+            cumulative_revenue_usd: 0.0,
+            cumulative_expenses_usd: 0.0,
+            is_pnl: event_type != "withdrawal",
+        }
+    }
+
+    #[test]
+    fn rolls_up_usd_by_month_and_category() {
+        let timeline = vec![
+            event("2025-12-30", "commission", 100.0),
+            event("2026-01-02", "commission", 50.0),
+            event("2026-01-02", "vote_cost", -20.0),
+            event("2026-01-05", "withdrawal", 500.0),
+            // Off-chain expenses come from the expense list, not the timeline
+            event("2026-01-10", "expense", -99.0),
+        ];
+        let expenses = vec![Expense {
+            date: "2026-01-10".into(),
+            vendor: "Latitude".into(),
+            category: ExpenseCategory::Hosting,
+            description: String::new(),
+            amount_usd: 99.0,
+            paid_with: "card".into(),
+            invoice_id: None,
+        }];
+
+        let report = compute(&timeline, &expenses);
+        assert_eq!(report.months.len(), 2);
+        let january = &report.months[1];
+        assert_eq!((january.income_usd, january.expenses_usd), (50.0, 119.0));
+        assert_eq!(january.net_usd, -69.0);
+
+        assert_eq!(
+            year_totals(&report, "2026"),
+            vec![
+                (INCOME, "Staking commission", 50.0),
+                (EXPENSE, "Network voting fees (after Foundation subsidy)", 20.0),
+                (EXPENSE, "Server hosting", 99.0),
+            ]
+        );
+        assert_eq!(months_csv(&report).lines().nth(2), Some("2026-01,50.00,119.00,-69.00"));
+
+        let page = render_page(Some(&report));
+        assert!(page.find("<h2>2026</h2>") < page.find("<h2>2025</h2>"));
+        assert!(!page.contains("SOL</th>"));
+    }
+}
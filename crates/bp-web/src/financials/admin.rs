@@ -0,0 +1,293 @@
+//! Operator admin page for `/financials/admin`.
+//!
+//! Plain server-rendered HTML (no template, no JS) showing pipeline health
+//! straight from `cache.sqlite`, so questions like "why is October missing?"
+//! can be answered without digging through rotated logs: recent ingestion
+//! runs, plus an epoch-by-source coverage matrix that makes data gaps visible.
+
+use anyhow::Result;
+
+use super::types::{EpochCoverage, IngestionRun};
+use super::{anomalies, db, doublezero, vote_topup};
+
+/// How many ingestion runs to show.
+const RUN_HISTORY_LIMIT: u32 = 50;
+
+/// Render the admin page, or an inline error notice if the cache isn't readable.
+/// `reload` is the outcome of a config reload just requested from the page.
+pub async fn generate_admin_page(data_dir: &str, reload: Option<&Result<(), Vec<String>>>) -> String {
+    let body = match try_render_runs(data_dir).await {
+        Ok(html) => html,
+        Err(e) => {
+            eprintln!("[financials] Error loading ingestion runs: {:#}", e);
+            format!(
+                "<p class='muted'>Ingestion history unavailable: {}</p>",
+                escape_html(&format!("{:#}", e))
+            )
+        }
+    };
+
+    let banner = render_reload(reload)
+        + &render_prepay_banner(data_dir).await
+        + &render_vote_topup(data_dir).await
+        + &render_anomalies(data_dir).await;
+
+    format!(
+        concat!(
+            "<!DOCTYPE html><html lang='en'><head><meta charset='utf-8'>",
+            "<meta name='viewport' content='width=device-width, initial-scale=1'>",
+            "<title>Block Parliament · Financials Admin</title>",
+            "<link rel='icon' type='image/png' href='/logo/owl-64.png'>",
+            "<style>{}</style></head><body>",
+            "<h1>Financials Admin</h1>",
+            "<p><a href='/financials'>&larr; back to report</a> · ",
+            "<a href='/financials/admin/expenses'>Expenses</a> · ",
+            "<a href='/financials/sql'>SQL console</a> · <a href='/financials/graphql'>GraphQL</a> · ",
+            "<a href='/financials/plan'>Withdrawal planner</a> · ",
+            "<a href='/financials/epochs'>Epoch profitability</a> · ",
+            "<a href='/financials/delegators'>Delegators</a> · ",
+            "<a href='/financials/expenses'>Expense trends</a> · ",
+            "<a href='/financials?view=accountant'>Accountant view</a></p>",
+            "<form method='post' action='/financials/admin/reload'>",
+            "<button type='submit'>Reload config.toml</button></form>",
+            "{}{}",
+            "</body></html>"
+        ),
+        STYLE, banner, body
+    )
+}
+
+/// Outcome of a config reload: applied, or the problems that stopped it.
+fn render_reload(reload: Option<&Result<(), Vec<String>>>) -> String {
+    match reload {
+        None => String::new(),
+        Some(Ok(())) => "<p class='banner status-success'>config.toml reloaded.</p>".to_string(),
+        Some(Err(problems)) => format!(
+            "<div class='banner status-failed'><strong>config.toml has problems, fix them and reload again:</strong><ul>{}</ul></div>",
+            problems
+                .iter()
+                .map(|p| format!("<li>{}</li>", escape_html(p)))
+                .collect::<String>()
+        ),
+    }
+}
+
+/// Identity top-up recommendation for the coming vote fees; a banner when one is due.
+async fn render_vote_topup(data_dir: &str) -> String {
+    match vote_topup::vote_topup(data_dir).await {
+        Ok(topup) if topup.transfer_sol > 0.0 => format!(
+            "<p class='banner status-failed'><strong>Vote fees:</strong> {}.</p>",
+            escape_html(&vote_topup::summary(&topup))
+        ),
+        Ok(topup) => format!("<p class='muted'>{}.</p>", escape_html(&vote_topup::summary(&topup))),
+        Err(e) => format!(
+            "<p class='muted'>Vote fee runway unavailable: {}</p>",
+            escape_html(&format!("{:#}", e))
+        ),
+    }
+}
+
+/// Epoch revenue far from its trailing baseline; a warning banner listing the epochs.
+async fn render_anomalies(data_dir: &str) -> String {
+    match super::revenue_anomalies(data_dir).await {
+        Ok(list) => anomalies::render_section(&list),
+        Err(e) => format!(
+            "<p class='muted'>Revenue anomaly check unavailable: {}</p>",
+            escape_html(&format!("{:#}", e))
+        ),
+    }
+}
+
+/// DoubleZero prepayment status; a warning banner when the deposit is running low.
+async fn render_prepay_banner(data_dir: &str) -> String {
+    match doublezero::prepay_status(data_dir).await {
+        Ok(Some(status)) if status.low => format!(
+            "<p class='banner status-failed'><strong>Top up DoubleZero:</strong> {}. Runs out within {} epochs.</p>",
+            escape_html(&doublezero::summary(&status)),
+            status.alert_epochs
+        ),
+        Ok(Some(status)) => format!("<p class='muted'>{}.</p>", escape_html(&doublezero::summary(&status))),
+        Ok(None) => String::new(),
+        Err(e) => format!(
+            "<p class='muted'>DoubleZero prepayment status unavailable: {}</p>",
+            escape_html(&format!("{:#}", e))
+        ),
+    }
+}
+
+async fn try_render_runs(data_dir: &str) -> Result<String> {
+    let pool = db::init_cache(data_dir).await?;
+    let runs = db::get_ingestion_runs(pool, RUN_HISTORY_LIMIT).await?;
+    let coverage = db::get_epoch_coverage(pool).await?;
+    Ok(render_runs(&runs) + &render_coverage(&coverage))
+}
+
+/// Epoch x source matrix, newest epoch first.
+fn render_coverage(coverage: &[EpochCoverage]) -> String {
+    let mut html = String::from("<h2>Epoch coverage</h2>");
+
+    if coverage.is_empty() {
+        html.push_str("<p class='muted'>No per-epoch data cached yet.</p>");
+        return html;
+    }
+
+    let gap_count = coverage.iter().filter(|c| c.has_gaps()).count();
+    html.push_str(&format!(
+        "<p class='muted'>Epochs {}&ndash;{}: {} of {} with missing sources.</p>",
+        coverage[0].epoch,
+        coverage[coverage.len() - 1].epoch,
+        gap_count,
+        coverage.len()
+    ));
+
+    html.push_str(
+        "<table><thead><tr><th>Epoch</th><th>Rewards</th><th>Leader fees</th><th>MEV</th>\
+         <th>BAM</th><th>Vote costs</th><th>DoubleZero</th></tr></thead><tbody>",
+    );
+
+    let cell = |present: bool, applies: bool| match (applies, present) {
+        (false, _) => "<td class='muted'>n/a</td>",
+        (true, true) => "<td class='status-success'>&#10003;</td>",
+        (true, false) => "<td class='status-failed'>missing</td>",
+    };
+
+    for c in coverage.iter().rev() {
+        html.push_str(&format!(
+            "<tr><td>{}</td>{}{}{}{}{}{}</tr>",
+            c.epoch,
+            cell(c.rewards, true),
+            cell(c.leader_fees, true),
+            cell(c.mev, true),
+            cell(c.bam, c.bam_applies()),
+            cell(c.vote_costs, true),
+            cell(c.doublezero, c.doublezero_applies()),
+        ));
+    }
+
+    html.push_str("</tbody></table>");
+    html
+}
+
+fn render_runs(runs: &[IngestionRun]) -> String {
+    let mut html = String::from("<h2>Ingestion runs</h2>");
+
+    if runs.is_empty() {
+        html.push_str("<p class='muted'>No ingestion runs recorded yet.</p>");
+        return html;
+    }
+
+    html.push_str(
+        "<table><thead><tr><th>ID</th><th>Started (UTC)</th><th>Finished (UTC)</th>\
+         <th>Trigger</th><th>Status</th><th>Rows by source</th><th>Errors</th></tr></thead><tbody>",
+    );
+
+    for run in runs {
+        let rows = run
+            .row_counts
+            .iter()
+            .map(|(source, count)| format!("{}={}", escape_html(source), count))
+            .collect::<Vec<_>>()
+            .join("<br>");
+        let errors = run
+            .errors
+            .iter()
+            .map(|e| escape_html(e))
+            .collect::<Vec<_>>()
+            .join("<br>");
+
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td class='status-{}'>{}</td><td>{}</td><td>{}</td></tr>",
+            run.id,
+            escape_html(&run.started_at),
+            escape_html(run.finished_at.as_deref().unwrap_or("-")),
+            escape_html(&run.triggered_by),
+            escape_html(&run.status),
+            escape_html(&run.status),
+            rows,
+            errors,
+        ));
+    }
+
+    html.push_str("</tbody></table>");
+    html
+}
+
+pub(super) fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+pub(super) static STYLE: &str = concat!(
+    ":root{--paper:#f8f6f1;--ink:#1a1a1a;--ink-light:#666;--rule:#ccc;--green:#227a4b;--red:#b91c1c}",
+    "@media (prefers-color-scheme: dark){:root{--paper:#1a1a1a;--ink:#e8e6e1;--ink-light:#999;--rule:#444}}",
+    "body{font-family:monospace;background:var(--paper);color:var(--ink);padding:2em;max-width:120ch;margin:auto}",
+    "h1,h2{margin:1em 0 .5em}a{color:inherit}.muted{color:var(--ink-light)}",
+    "table{border-collapse:collapse;width:100%}",
+    "th,td{border-bottom:1px dashed var(--rule);padding:.3em .6em;text-align:left;vertical-align:top}",
+    ".status-success{color:var(--green)}.status-failed{color:var(--red)}.status-partial{color:#b45309}",
+    ".banner{border:1px solid currentColor;padding:.6em 1em}"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn renders_runs_with_escaped_errors() {
+        let runs = vec![IngestionRun {
+            id: 7,
+            triggered_by: "scheduler".into(),
+            started_at: "2026-10-01 06:00:00".into(),
+            finished_at: None,
+            status: "partial".into(),
+            row_counts: BTreeMap::from([("epoch_rewards".to_string(), 12)]),
+            errors: vec!["mev_claims: <timeout>".into()],
+        }];
+
+        let html = render_runs(&runs);
+        assert!(html.contains("epoch_rewards=12"));
+        assert!(html.contains("mev_claims: &lt;timeout&gt;"));
+        assert!(html.contains("class='status-partial'"));
+        assert!(html.contains("<td>-</td>"));
+    }
+
+    #[test]
+    fn coverage_marks_missing_and_not_applicable_sources() {
+        let coverage = vec![
+            EpochCoverage {
+                epoch: 900,
+                rewards: true,
+                leader_fees: true,
+                mev: true,
+                vote_costs: true,
+                doublezero: true,
+                ..Default::default()
+            },
+            EpochCoverage {
+                epoch: 912,
+                rewards: true,
+                mev: true,
+                vote_costs: true,
+                doublezero: true,
+                ..Default::default()
+            },
+        ];
+
+        assert!(!coverage[0].has_gaps());
+        assert!(coverage[1].has_gaps());
+
+        let html = render_coverage(&coverage);
+        assert!(html.contains("1 of 2 with missing sources"));
+        assert!(html.contains("<td class='muted'>n/a</td>"));
+        assert_eq!(html.matches("status-failed").count(), 2);
+    }
+
+    #[test]
+    fn empty_history_shows_notice() {
+        assert!(render_runs(&[]).contains("No ingestion runs recorded yet."));
+    }
+}
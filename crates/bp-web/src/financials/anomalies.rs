@@ -0,0 +1,102 @@
+//! Per-epoch revenue anomalies (see `report_template::detect_anomalies`).
+//!
+//! Commission, leader fees or MEV far outside the trailing baseline usually mean a
+//! missed claim, a data gap or a performance problem. The admin page lists every
+//! flagged epoch; the scheduler posts anomalies in the latest epochs to the
+//! notification webhook, each epoch at most once.
+
+use report_template::RevenueAnomaly;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::admin::escape_html;
+
+/// Only anomalies this close to the current epoch are alerted, so a restart doesn't
+/// replay the whole history
+const ALERT_RECENT_EPOCHS: u64 = 3;
+
+/// Newest epoch already alerted (0 = none yet)
+static LAST_ALERT_EPOCH: AtomicU64 = AtomicU64::new(0);
+
+/// Anomalies not yet alerted among the recent epochs
+fn unalerted(anomalies: &[RevenueAnomaly], current_epoch: u64, last_alerted: u64) -> Vec<&RevenueAnomaly> {
+    anomalies
+        .iter()
+        .filter(|a| a.epoch > last_alerted && a.epoch + ALERT_RECENT_EPOCHS >= current_epoch)
+        .collect()
+}
+
+/// Admin page section: a warning banner listing the flagged epochs, newest first
+pub fn render_section(anomalies: &[RevenueAnomaly]) -> String {
+    if anomalies.is_empty() {
+        return "<p class='muted'>No revenue anomalies in the cached epochs.</p>".to_string();
+    }
+    let mut html = format!(
+        "<div class='banner status-failed'><strong>Revenue anomalies:</strong> {} epoch values far from their trailing baseline. \
+         Check for unclaimed MEV, missing cache rows, or delinquency.<ul>",
+        anomalies.len()
+    );
+    for a in anomalies.iter().rev() {
+        html.push_str(&format!("<li>{}</li>", escape_html(&a.summary())));
+    }
+    html.push_str("</ul></div>");
+    html
+}
+
+/// Post anomalies in the latest epochs to the webhook (called by the scheduler)
+pub async fn check_and_alert(data_dir: &str) {
+    let anomalies = match super::revenue_anomalies(data_dir).await {
+        Ok(anomalies) => anomalies,
+        Err(e) => {
+            eprintln!("[financials] Revenue anomaly check failed: {:#}", e);
+            return;
+        }
+    };
+    if anomalies.is_empty() || !crate::notify::is_configured() {
+        return;
+    }
+    let Some(current_epoch) = crate::api::get_epoch_info().await.map(|info| info.epoch) else {
+        return;
+    };
+
+    let last_alerted = LAST_ALERT_EPOCH.load(Ordering::Relaxed);
+    let fresh = unalerted(&anomalies, current_epoch, last_alerted);
+    let Some(newest) = fresh.iter().map(|a| a.epoch).max() else {
+        return;
+    };
+    let lines: Vec<String> = fresh.iter().map(|a| format!("• {}", a.summary())).collect();
+    let message = format!("Revenue anomaly detected:\n{}", lines.join("\n"));
+    eprintln!("[financials] {}", message);
+
+    LAST_ALERT_EPOCH.store(newest, Ordering::Relaxed);
+    if let Err(e) = crate::notify::send(&message).await {
+        eprintln!("[financials] Revenue anomaly alert not sent: {:#}", e);
+        LAST_ALERT_EPOCH.store(last_alerted, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use report_template::RevenueComponent;
+
+    fn anomaly(epoch: u64) -> RevenueAnomaly {
+        RevenueAnomaly {
+            epoch,
+            date: None,
+            component: RevenueComponent::Mev,
+            value_sol: 0.0,
+            baseline_mean_sol: 1.0,
+            baseline_stddev_sol: 0.1,
+            deviation_sigma: -10.0,
+        }
+    }
+
+    #[test]
+    fn alerts_only_recent_epochs_once() {
+        let anomalies = [anomaly(800), anomaly(896), anomaly(898)];
+        let epochs = |fresh: Vec<&RevenueAnomaly>| fresh.iter().map(|a| a.epoch).collect::<Vec<_>>();
+        assert_eq!(epochs(unalerted(&anomalies, 899, 0)), vec![896, 898]);
+        assert_eq!(epochs(unalerted(&anomalies, 899, 896)), vec![898]);
+        assert!(unalerted(&anomalies, 899, 898).is_empty());
+    }
+}
@@ -6,7 +6,7 @@
 use std::collections::HashSet;
 use std::sync::LazyLock;
 
-use super::config::ValidatorConfig;
+use super::config::{TransferBucket, TransferDirection, TransferRule, ValidatorConfig};
 use super::types::{CategorizedTransfers, SolTransfer};
 
 // ── Known address sets ────────────────────────────────────────────────────────
@@ -64,19 +64,56 @@ pub fn is_exchange(addr: &str) -> bool {
     EXCHANGE_ADDRESSES.contains(addr)
 }
 
+// ── Rent-exempt deposits ──────────────────────────────────────────────────────
+// Ported from validator-accounting/src/constants.rs.
+
+const STAKE_PROGRAM: &str = "Stake11111111111111111111111111111111111111";
+const SPL_TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const SPL_TOKEN_2022_PROGRAM: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+const ASSOCIATED_TOKEN_PROGRAM: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+
+/// Largest account-creation or close transfer treated as rent (larger ones carry principal)
+const RENT_MAX_SOL: f64 = 0.01;
+
+/// Rent-exempt deposit funding a new stake or token account
+fn is_rent_deposit(t: &SolTransfer) -> bool {
+    t.amount_sol <= RENT_MAX_SOL
+        && t.program.as_deref().is_some_and(|p| {
+            [
+                STAKE_PROGRAM,
+                SPL_TOKEN_PROGRAM,
+                SPL_TOKEN_2022_PROGRAM,
+                ASSOCIATED_TOKEN_PROGRAM,
+            ]
+            .contains(&p)
+        })
+}
+
+/// Rent returned by closing a token account
+fn is_rent_refund(t: &SolTransfer) -> bool {
+    t.amount_sol <= RENT_MAX_SOL
+        && t.program
+            .as_deref()
+            .is_some_and(|p| p == SPL_TOKEN_PROGRAM || p == SPL_TOKEN_2022_PROGRAM)
+}
+
 // ── Categorize transfers ──────────────────────────────────────────────────────
 
 /// Bucket transfers by purpose using string-based address matching.
 ///
-/// Logic mirrors `transactions.rs:categorize_transfers()` exactly:
+/// Operator rules from `[[categorization.rules]]` run first (first match wins; see
+/// `matching_rule`). Otherwise the logic mirrors `transactions.rs:categorize_transfers()`:
 ///   1. DZ deposit (to == dz_deposit_account && from is ours)
 ///   2. Incoming to our accounts:
 ///      - from personal wallet → seeding
+///      - to the donation address → donation
 ///      - from SF → SFDP reimbursement
 ///      - from Jito → MEV deposit
 ///      - from our account → vote funding (internal)
+///      - token account close refund → rent
 ///      - else → other
 ///   3. Outgoing from our accounts:
+///      - stake/token account creation → rent
 ///      - to exchange or personal wallet → withdrawal
 ///      - to our account → vote funding (internal)
 ///      - else → other
@@ -84,6 +121,30 @@ pub fn categorize_transfers(transfers: &[SolTransfer], config: &ValidatorConfig)
     let mut cat = CategorizedTransfers::default();
 
     for t in transfers {
+        if let Some(rule) = matching_rule(t, config) {
+            let mut t = t.clone();
+            if let Some(ref label) = rule.label {
+                if config.is_our_account(&t.to_address) && !config.is_our_account(&t.from_address) {
+                    t.from_label = label.clone();
+                } else {
+                    t.to_label = label.clone();
+                }
+            }
+            let bucket = match rule.bucket {
+                TransferBucket::Seeding => &mut cat.seeding,
+                TransferBucket::SfdpReimbursement => &mut cat.sfdp_reimbursements,
+                TransferBucket::MevDeposit => &mut cat.mev_deposits,
+                TransferBucket::DoublezeroPayment => &mut cat.doublezero_payments,
+                TransferBucket::Donation => &mut cat.donations,
+                TransferBucket::VoteFunding => &mut cat.vote_funding,
+                TransferBucket::Withdrawal => &mut cat.withdrawals,
+                TransferBucket::Rent => &mut cat.rent,
+                TransferBucket::Other => &mut cat.other,
+            };
+            bucket.push(t);
+            continue;
+        }
+
         // 1. DoubleZero deposit
         if let Some(ref dz) = config.doublezero_deposit_account
             && t.to_address == *dz
@@ -101,17 +162,26 @@ pub fn categorize_transfers(transfers: &[SolTransfer], config: &ValidatorConfig)
         if is_incoming {
             if config.is_personal_wallet(&t.from_address) {
                 cat.seeding.push(t.clone());
+            } else if config.donation_address.as_ref() == Some(&t.to_address) && !config.is_our_account(&t.from_address)
+            {
+                cat.donations.push(t.clone());
             } else if is_solana_foundation(&t.from_address) {
                 cat.sfdp_reimbursements.push(t.clone());
             } else if is_jito(&t.from_address) {
                 cat.mev_deposits.push(t.clone());
             } else if config.is_our_account(&t.from_address) {
                 cat.vote_funding.push(t.clone());
+            } else if is_rent_refund(t) {
+                cat.rent.push(t.clone());
             } else {
                 cat.other.push(t.clone());
             }
         } else if is_outgoing {
-            if is_exchange(&t.to_address) || config.is_personal_wallet(&t.to_address) {
+            // Stored categories include operator-tagged deposit addresses
+            let to_exchange = is_exchange(&t.to_address) || t.to_category == "Exchange";
+            if is_rent_deposit(t) {
+                cat.rent.push(t.clone());
+            } else if to_exchange || config.is_personal_wallet(&t.to_address) {
                 cat.withdrawals.push(t.clone());
             } else if config.is_our_account(&t.to_address) {
                 cat.vote_funding.push(t.clone());
@@ -123,3 +193,38 @@ pub fn categorize_transfers(transfers: &[SolTransfer], config: &ValidatorConfig)
 
     cat
 }
+
+/// First operator rule matching a transfer that touches our accounts.
+///
+/// The counterparty is the sender for incoming transfers and the recipient otherwise;
+/// its category comes from the `from_category`/`to_category` stored in the cache.
+fn matching_rule<'a>(t: &SolTransfer, config: &'a ValidatorConfig) -> Option<&'a TransferRule> {
+    let direction = match (
+        config.is_our_account(&t.from_address),
+        config.is_our_account(&t.to_address),
+    ) {
+        (true, true) => TransferDirection::Internal,
+        (false, true) => TransferDirection::Incoming,
+        (true, false) => TransferDirection::Outgoing,
+        (false, false) => return None,
+    };
+    let (counterparty, counterparty_category, account) = match direction {
+        TransferDirection::Incoming => (&t.from_address, &t.from_category, &t.to_address),
+        TransferDirection::Outgoing | TransferDirection::Internal => (&t.to_address, &t.to_category, &t.from_address),
+    };
+
+    config.transfer_rules.iter().find(|rule| {
+        rule.direction.is_none_or(|d| d == direction)
+            && rule.min_sol.is_none_or(|min| t.amount_sol >= min)
+            && rule.max_sol.is_none_or(|max| t.amount_sol <= max)
+            && (rule.counterparty.is_empty() || rule.counterparty.contains(counterparty))
+            && (rule.counterparty_category.is_empty() || rule.counterparty_category.contains(counterparty_category))
+            && (rule.account.is_empty() || rule.account.contains(account))
+            && (rule.program.is_empty() || t.program.as_ref().is_some_and(|p| rule.program.contains(p)))
+            && rule.memo_contains.as_ref().is_none_or(|needle| {
+                t.memo
+                    .as_ref()
+                    .is_some_and(|memo| memo.to_lowercase().contains(&needle.to_lowercase()))
+            })
+    })
+}
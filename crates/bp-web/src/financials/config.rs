@@ -9,6 +9,8 @@ use serde::Deserialize;
 use std::collections::HashSet;
 use std::path::Path;
 
+use report_template::{AnomalyConfig, ThemeConfig};
+
 // ── TOML shape ────────────────────────────────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
@@ -16,6 +18,22 @@ struct FileConfig {
     validator: ValidatorSection,
     #[serde(default)]
     doublezero: Option<DoubleZeroSection>,
+    #[serde(default)]
+    categorization: Option<CategorizationSection>,
+    #[serde(default)]
+    theme: Option<ThemeConfig>,
+    #[serde(default)]
+    reporting: Option<ReportingSection>,
+    #[serde(default)]
+    anomalies: Option<AnomalyConfig>,
+    #[serde(default)]
+    scheduler: Option<SchedulerConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReportingSection {
+    #[serde(default)]
+    estimated_tax_rate: Option<f64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -31,12 +49,112 @@ struct ValidatorSection {
     initial_treasury_sol: Option<f64>,
     #[serde(default)]
     sfdp_acceptance_date: Option<String>,
+    #[serde(default)]
+    donation_address: Option<String>,
+    #[serde(default)]
+    cluster: Cluster,
+}
+
+/// `[validator] cluster` (see validator-accounting's `cluster.rs`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Cluster {
+    #[default]
+    Mainnet,
+    Testnet,
+    Devnet,
+}
+
+impl Cluster {
+    /// Cache database filename validator-accounting writes for this cluster
+    pub fn cache_filename(&self) -> &'static str {
+        match self {
+            Self::Mainnet => "cache.sqlite",
+            Self::Testnet => "cache-testnet.sqlite",
+            Self::Devnet => "cache-devnet.sqlite",
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
 struct DoubleZeroSection {
     #[serde(default)]
     deposit_account: Option<String>,
+    #[serde(default)]
+    prepay_alert_epochs: Option<u64>,
+}
+
+/// `[scheduler]`: bp-web's background jobs. Unset keys fall back to the env vars.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub struct SchedulerConfig {
+    /// Hours between cycles (`INGESTION_INTERVAL_HOURS`)
+    #[serde(default)]
+    pub interval_hours: Option<u64>,
+    /// Refresh cache.sqlite each cycle (`FINANCIALS_REFRESH_ENABLED`)
+    #[serde(default)]
+    pub refresh_financials: Option<bool>,
+    /// Archive the prior month's report package (`MONTHLY_REPORTS_ENABLED`)
+    #[serde(default)]
+    pub monthly_reports: Option<bool>,
+}
+
+/// Default runway (epochs) below which the DoubleZero deposit is flagged
+const DEFAULT_PREPAY_ALERT_EPOCHS: u64 = 5;
+
+#[derive(Debug, Deserialize)]
+struct CategorizationSection {
+    #[serde(default)]
+    rules: Vec<TransferRule>,
+}
+
+/// Report bucket a categorization rule assigns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferBucket {
+    Seeding,
+    SfdpReimbursement,
+    MevDeposit,
+    DoublezeroPayment,
+    Donation,
+    VoteFunding,
+    Withdrawal,
+    Rent,
+    Other,
+}
+
+/// Direction a categorization rule applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferDirection {
+    Incoming,
+    Outgoing,
+    Internal,
+}
+
+/// One `[[categorization.rules]]` entry (see validator-accounting's `rules.rs`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct TransferRule {
+    #[allow(dead_code)]
+    pub name: String,
+    pub bucket: TransferBucket,
+    #[serde(default)]
+    pub direction: Option<TransferDirection>,
+    #[serde(default)]
+    pub counterparty: Vec<String>,
+    #[serde(default)]
+    pub counterparty_category: Vec<String>,
+    #[serde(default)]
+    pub account: Vec<String>,
+    #[serde(default)]
+    pub min_sol: Option<f64>,
+    #[serde(default)]
+    pub max_sol: Option<f64>,
+    #[serde(default)]
+    pub program: Vec<String>,
+    #[serde(default)]
+    pub memo_contains: Option<String>,
+    #[serde(default)]
+    pub label: Option<String>,
 }
 
 // ── Public config ─────────────────────────────────────────────────────────────
@@ -53,6 +171,22 @@ pub struct ValidatorConfig {
     pub initial_treasury_sol: f64,
     pub sfdp_acceptance_date: Option<String>,
     pub doublezero_deposit_account: Option<String>,
+    /// Flag the DoubleZero deposit when it will run out within this many epochs.
+    pub doublezero_alert_epochs: u64,
+    /// Tip address; incoming transfers are donation income.
+    pub donation_address: Option<String>,
+    /// Cluster the validator runs on; picks the cache file.
+    pub cluster: Cluster,
+    /// Operator categorization rules, evaluated before the built-in logic.
+    pub transfer_rules: Vec<TransferRule>,
+    /// Report branding (`[theme]`), with `template_dir` resolved.
+    pub theme: ThemeConfig,
+    /// Default flat rate for the withdrawal planner (`[reporting] estimated_tax_rate`).
+    pub estimated_tax_rate: Option<f64>,
+    /// Per-epoch revenue anomaly thresholds (`[anomalies]`).
+    pub anomalies: AnomalyConfig,
+    /// Background job schedule (`[scheduler]`), re-read on every cycle and on reload.
+    pub scheduler: SchedulerConfig,
 
     /// Business accounts for quick membership checks.
     our_accounts: HashSet<String>,
@@ -67,7 +201,21 @@ impl ValidatorConfig {
             toml::from_str(&content).with_context(|| format!("Failed to parse config: {}", path.display()))?;
 
         let v = file.validator;
+        let doublezero_alert_epochs = file
+            .doublezero
+            .as_ref()
+            .and_then(|dz| dz.prepay_alert_epochs)
+            .unwrap_or(DEFAULT_PREPAY_ALERT_EPOCHS);
         let dz_deposit = file.doublezero.and_then(|dz| dz.deposit_account);
+        let transfer_rules = file.categorization.map(|c| c.rules).unwrap_or_default();
+        let estimated_tax_rate = file
+            .reporting
+            .and_then(|r| r.estimated_tax_rate)
+            .filter(|rate| (0.0..=1.0).contains(rate));
+        let mut theme = file.theme.unwrap_or_default();
+        if let Some(dir) = path.parent() {
+            theme.resolve_paths(dir);
+        }
         let mut personal_wallets = vec![v.personal_wallet.clone()];
         for w in &v.personal_wallets {
             if !personal_wallets.contains(w) {
@@ -78,9 +226,10 @@ impl ValidatorConfig {
         let mut our_accounts = HashSet::new();
         our_accounts.insert(v.vote_account.clone());
         our_accounts.insert(v.identity.clone());
+        our_accounts.extend(v.donation_address.clone());
         // Business-source accounts for taxable withdrawal classification are
-        // vote + identity. Withdraw authority is treated as an external
-        // beneficiary account for distribution tracking.
+        // vote + identity (+ the donation address). Withdraw authority is treated
+        // as an external beneficiary account for distribution tracking.
 
         Ok(Self {
             vote_account: v.vote_account,
@@ -92,11 +241,42 @@ impl ValidatorConfig {
             initial_treasury_sol: v.initial_treasury_sol.unwrap_or(0.0).max(0.0),
             sfdp_acceptance_date: v.sfdp_acceptance_date,
             doublezero_deposit_account: dz_deposit,
+            doublezero_alert_epochs,
+            donation_address: v.donation_address,
+            cluster: v.cluster,
+            transfer_rules,
+            theme,
+            estimated_tax_rate,
+            anomalies: file.anomalies.filter(|a| a.sigma > 0.0).unwrap_or_default(),
+            scheduler: file.scheduler.unwrap_or_default(),
             our_accounts,
         })
     }
 
-    /// Is this one of our business-source accounts (vote, identity)?
+    /// Values `load` accepts but quietly replaces with a default; the startup self-check
+    /// and config reload refuse them instead.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let is_date = |d: &str| NaiveDate::parse_from_str(d, "%Y-%m-%d").is_ok();
+        if !is_date(&self.bootstrap_date) {
+            problems.push(format!(
+                "validator.bootstrap_date '{}' is not a YYYY-MM-DD date",
+                self.bootstrap_date
+            ));
+        }
+        if let Some(date) = self.sfdp_acceptance_date.as_deref().filter(|d| !is_date(d)) {
+            problems.push(format!(
+                "validator.sfdp_acceptance_date '{}' is not a YYYY-MM-DD date",
+                date
+            ));
+        }
+        if self.scheduler.interval_hours == Some(0) {
+            problems.push("scheduler.interval_hours must be at least 1".to_string());
+        }
+        problems
+    }
+
+    /// Is this one of our business-source accounts (vote, identity, donation address)?
     pub fn is_our_account(&self, address: &str) -> bool {
         self.our_accounts.contains(address)
     }
@@ -170,6 +350,14 @@ mod tests {
             initial_treasury_sol: 0.0,
             sfdp_acceptance_date: sfdp.map(|s| s.into()),
             doublezero_deposit_account: None,
+            doublezero_alert_epochs: DEFAULT_PREPAY_ALERT_EPOCHS,
+            donation_address: None,
+            cluster: Cluster::Mainnet,
+            transfer_rules: Vec::new(),
+            theme: Default::default(),
+            estimated_tax_rate: None,
+            anomalies: Default::default(),
+            scheduler: Default::default(),
             our_accounts: ["VOTE", "ID"].iter().map(|s| s.to_string()).collect(),
         }
     }
@@ -229,6 +417,19 @@ mod tests {
         assert!(!c.is_personal_wallet("random"));
     }
 
+    #[test]
+    fn problems_lists_values_load_would_paper_over() {
+        assert!(cfg(Some("2025-12-01")).problems().is_empty());
+
+        let mut c = cfg(Some("next spring"));
+        c.bootstrap_date = "2025-13-01".into();
+        c.scheduler.interval_hours = Some(0);
+        let problems = c.problems();
+        assert_eq!(problems.len(), 3);
+        assert!(problems[0].contains("bootstrap_date"));
+        assert!(problems[2].contains("interval_hours"));
+    }
+
     #[test]
     fn business_start_month_uses_bootstrap_month() {
         let c = cfg(None);
@@ -1,17 +1,38 @@
 //! Read-only queries against `cache.sqlite` (populated by validator-accounting).
 //!
+//! A testnet or devnet validator's cache is `cache-testnet.sqlite` / `cache-devnet.sqlite`;
+//! `cache_path` picks the file from `[validator] cluster` in config.toml.
+//!
 //! Opens the database lazily on the first `/financials` request.
 //! Uses `?mode=ro` for read-only safety — we never write to this database.
 
 use anyhow::{Context, Result};
 use sqlx::sqlite::SqlitePoolOptions;
 use sqlx::{Row, SqlitePool};
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
+use super::config::{Cluster, ValidatorConfig};
 use super::types::*;
 
 static CACHE_POOL: OnceLock<SqlitePool> = OnceLock::new();
 
+/// Path of the cache database validator-accounting writes for the configured cluster.
+///
+/// Without a readable config.toml this is the mainnet `cache.sqlite` (the self-check
+/// reports a broken config separately).
+pub fn cache_path(data_dir: &str) -> PathBuf {
+    let config_path = Path::new(data_dir).join("config.toml");
+    let cluster = if config_path.exists() {
+        ValidatorConfig::load(&config_path)
+            .map(|c| c.cluster)
+            .unwrap_or_default()
+    } else {
+        Cluster::default()
+    };
+    Path::new(data_dir).join(cluster.cache_filename())
+}
+
 /// Initialize the read-only cache.sqlite pool.
 /// Safe to call multiple times — only the first call connects.
 pub async fn init_cache(data_dir: &str) -> Result<&'static SqlitePool> {
@@ -19,14 +40,14 @@ pub async fn init_cache(data_dir: &str) -> Result<&'static SqlitePool> {
         return Ok(pool);
     }
 
-    let db_path = format!("{}/cache.sqlite", data_dir);
-    let url = format!("sqlite:{}?mode=ro", db_path);
+    let db_path = cache_path(data_dir);
+    let url = format!("sqlite:{}?mode=ro", db_path.display());
 
     let pool = SqlitePoolOptions::new()
         .max_connections(3)
         .connect(&url)
         .await
-        .with_context(|| format!("Failed to open cache.sqlite at {}", db_path))?;
+        .with_context(|| format!("Failed to open cache at {}", db_path.display()))?;
 
     // Ignore if already set (race between concurrent requests)
     let _ = CACHE_POOL.set(pool);
@@ -133,6 +154,104 @@ pub async fn get_vote_costs(pool: &SqlitePool) -> Result<Vec<EpochVoteCost>> {
         .collect())
 }
 
+/// Vote credits and stake per epoch (on-chain, else aggregator-imported stake).
+/// Empty for caches written before validator-accounting tracked them.
+pub async fn get_epoch_performance(pool: &SqlitePool) -> Result<Vec<EpochPerformance>> {
+    if !table_exists(pool, "epoch_performance").await? {
+        return Ok(Vec::new());
+    }
+
+    let rows = sqlx::query(
+        "SELECT epoch, MAX(vote_credits) AS vote_credits,
+                COALESCE(MAX(onchain_stake), MAX(imported_stake)) AS stake
+         FROM (
+             SELECT epoch, vote_credits, active_stake_lamports AS onchain_stake, NULL AS imported_stake
+             FROM epoch_performance
+             UNION ALL
+             SELECT epoch, NULL, NULL, activated_stake_lamports FROM reward_imports
+         )
+         GROUP BY epoch
+         ORDER BY epoch",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .iter()
+        .map(|r| EpochPerformance {
+            epoch: r.get::<i64, _>("epoch") as u64,
+            vote_credits: r.get::<Option<i64>, _>("vote_credits").map(|v| v as u64),
+            active_stake_lamports: r.get::<Option<i64>, _>("stake").map(|v| v as u64),
+        })
+        .collect())
+}
+
+/// Delegator stake snapshots (empty for caches written before they were taken).
+pub async fn get_delegator_stake(pool: &SqlitePool) -> Result<Vec<DelegatorStake>> {
+    if !table_exists(pool, "delegator_stake").await? {
+        return Ok(Vec::new());
+    }
+
+    let rows = sqlx::query(
+        "SELECT epoch, delegator, stake_lamports, accounts
+         FROM delegator_stake ORDER BY epoch, stake_lamports DESC",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .iter()
+        .map(|r| DelegatorStake {
+            epoch: r.get::<i64, _>("epoch") as u64,
+            delegator: r.get("delegator"),
+            stake_lamports: r.get::<i64, _>("stake_lamports") as u64,
+            accounts: r.get::<i64, _>("accounts") as u32,
+        })
+        .collect())
+}
+
+/// Tables every report build reads; the others are optional (see `table_exists` uses)
+pub const REQUIRED_TABLES: &[&str] = &[
+    "epoch_rewards",
+    "leader_fees",
+    "mev_claims",
+    "bam_claims",
+    "vote_costs",
+    "doublezero_fees",
+    "expenses",
+    "recurring_expenses",
+    "prices",
+    "sol_transfers",
+];
+
+/// `REQUIRED_TABLES` missing from cache.sqlite (startup self-check)
+pub async fn missing_tables(pool: &SqlitePool) -> Result<Vec<&'static str>> {
+    let mut missing = Vec::new();
+    for table in REQUIRED_TABLES {
+        if !table_exists(pool, table).await? {
+            missing.push(*table);
+        }
+    }
+    Ok(missing)
+}
+
+async fn table_exists(pool: &SqlitePool, table: &str) -> Result<bool> {
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?")
+        .bind(table)
+        .fetch_one(pool)
+        .await?;
+    Ok(count > 0)
+}
+
+async fn column_exists(pool: &SqlitePool, table: &str, column: &str) -> Result<bool> {
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pragma_table_info(?) WHERE name = ?")
+        .bind(table)
+        .bind(column)
+        .fetch_one(pool)
+        .await?;
+    Ok(count > 0)
+}
+
 pub async fn get_doublezero_fees(pool: &SqlitePool) -> Result<Vec<DoubleZeroFee>> {
     let rows = sqlx::query(
         "SELECT epoch, liability_sol, fee_base_lamports, fee_rate_bps, date, is_estimate
@@ -154,11 +273,18 @@ pub async fn get_doublezero_fees(pool: &SqlitePool) -> Result<Vec<DoubleZeroFee>
         .collect())
 }
 
+/// Expenses the reports count: approved ones (every row, on caches from before review status)
 pub async fn get_expenses(pool: &SqlitePool) -> Result<Vec<Expense>> {
-    let rows = sqlx::query(
+    let filter = if column_exists(pool, "expenses", "status").await? {
+        "WHERE status = 'approved'"
+    } else {
+        ""
+    };
+    let rows = sqlx::query(&format!(
         "SELECT date, vendor, category, description, amount_usd, paid_with, invoice_id
-         FROM expenses ORDER BY date",
-    )
+         FROM expenses {} ORDER BY date",
+        filter
+    ))
     .fetch_all(pool)
     .await?;
 
@@ -176,6 +302,26 @@ pub async fn get_expenses(pool: &SqlitePool) -> Result<Vec<Expense>> {
         .collect())
 }
 
+pub async fn get_withdrawal_splits(pool: &SqlitePool) -> Result<Vec<WithdrawalSplit>> {
+    if !table_exists(pool, "withdrawal_splits").await? {
+        return Ok(Vec::new());
+    }
+
+    let rows = sqlx::query("SELECT signature, business_pct, category, description FROM withdrawal_splits")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows
+        .iter()
+        .map(|r| WithdrawalSplit {
+            signature: r.get("signature"),
+            business_pct: r.get("business_pct"),
+            category: ExpenseCategory::from_str_lossy(r.get::<&str, _>("category")),
+            description: r.get("description"),
+        })
+        .collect())
+}
+
 pub async fn get_recurring_expenses(pool: &SqlitePool) -> Result<Vec<RecurringExpense>> {
     let rows = sqlx::query(
         "SELECT vendor, category, description, amount_usd, paid_with, start_date, end_date
@@ -198,16 +344,28 @@ pub async fn get_recurring_expenses(pool: &SqlitePool) -> Result<Vec<RecurringEx
         .collect())
 }
 
+/// Daily prices with their stored source. Caches validator-accounting has not migrated
+/// yet have no `source` column; their prices read as exact.
 pub async fn get_prices(pool: &SqlitePool) -> Result<PriceMap> {
-    let rows = sqlx::query("SELECT date, usd_price FROM prices")
-        .fetch_all(pool)
-        .await?;
+    let has_source = sqlx::query("SELECT 1 FROM pragma_table_info('prices') WHERE name = 'source'")
+        .fetch_optional(pool)
+        .await?
+        .is_some();
+    let sql = if has_source {
+        "SELECT date, usd_price, source FROM prices"
+    } else {
+        "SELECT date, usd_price, 'exact' AS source FROM prices"
+    };
+    let rows = sqlx::query(sql).fetch_all(pool).await?;
 
     Ok(rows
         .iter()
         .map(|r| {
             let date: String = r.get("date");
-            let price: f64 = r.get("usd_price");
+            let price = CachedPrice {
+                usd_price: r.get("usd_price"),
+                source: PriceSource::parse(r.get("source")).unwrap_or(PriceSource::Exact),
+            };
             (date, price)
         })
         .collect())
@@ -215,7 +373,8 @@ pub async fn get_prices(pool: &SqlitePool) -> Result<PriceMap> {
 
 pub async fn get_sol_transfers(pool: &SqlitePool) -> Result<Vec<SolTransfer>> {
     let rows = sqlx::query(
-        "SELECT signature, date, from_address, to_address, amount_sol, from_label, to_label
+        "SELECT signature, date, from_address, to_address, amount_sol, from_label, to_label,
+                from_category, to_category, memo, program
          FROM sol_transfers ORDER BY slot",
     )
     .fetch_all(pool)
@@ -231,6 +390,128 @@ pub async fn get_sol_transfers(pool: &SqlitePool) -> Result<Vec<SolTransfer>> {
             amount_sol: r.get("amount_sol"),
             from_label: r.get("from_label"),
             to_label: r.get("to_label"),
+            from_category: r.get("from_category"),
+            to_category: r.get("to_category"),
+            memo: r.get("memo"),
+            program: r.get("program"),
         })
         .collect())
 }
+
+pub async fn get_ingestion_runs(pool: &SqlitePool, limit: u32) -> Result<Vec<IngestionRun>> {
+    let rows = sqlx::query(
+        "SELECT id, triggered_by, started_at, finished_at, status, row_counts, errors
+         FROM ingestion_runs ORDER BY id DESC LIMIT ?",
+    )
+    .bind(limit as i64)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .iter()
+        .map(|r| IngestionRun {
+            id: r.get("id"),
+            triggered_by: r.get("triggered_by"),
+            started_at: r.get("started_at"),
+            finished_at: r.get("finished_at"),
+            status: r.get("status"),
+            row_counts: serde_json::from_str(r.get::<&str, _>("row_counts")).unwrap_or_default(),
+            errors: serde_json::from_str(r.get::<&str, _>("errors")).unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Income per source and calendar month (by each row's attributed date), oldest first.
+pub async fn get_monthly_revenue(pool: &SqlitePool) -> Result<Vec<MonthlyRevenue>> {
+    let rows = sqlx::query(
+        "SELECT substr(date, 1, 7) AS month, source, SUM(amount) AS total
+         FROM (
+             SELECT date, 'commission' AS source, amount_sol AS amount FROM epoch_rewards
+             UNION ALL SELECT date, 'leader_fees', total_fees_sol FROM leader_fees
+             UNION ALL SELECT date, 'mev', amount_sol FROM mev_claims
+             UNION ALL SELECT date, 'bam', amount_sol_equivalent FROM bam_claims
+         )
+         WHERE date IS NOT NULL AND date != 'unknown'
+         GROUP BY month, source
+         ORDER BY month",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut months: Vec<MonthlyRevenue> = Vec::new();
+    for r in &rows {
+        let month: String = r.get("month");
+        if months.last().is_none_or(|m| m.month != month) {
+            months.push(MonthlyRevenue {
+                month,
+                ..Default::default()
+            });
+        }
+        let entry = months.last_mut().expect("pushed above");
+        let total: f64 = r.get("total");
+        match r.get::<&str, _>("source") {
+            "commission" => entry.commission_sol = total,
+            "leader_fees" => entry.leader_fees_sol = total,
+            "mev" => entry.mev_sol = total,
+            _ => entry.bam_sol = total,
+        }
+    }
+    Ok(months)
+}
+
+/// Identifies the current cache contents: the latest ingestion run (id and finish
+/// time), or None if no run has been recorded.
+pub async fn get_data_version(pool: &SqlitePool) -> Result<Option<String>> {
+    let row = sqlx::query(
+        "SELECT id, COALESCE(finished_at, started_at) AS at
+         FROM ingestion_runs ORDER BY id DESC LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|r| {
+        let at: String = r.get("at");
+        let at: String = at.chars().filter(char::is_ascii_alphanumeric).collect();
+        format!("{}-{}", r.get::<i64, _>("id"), at)
+    }))
+}
+
+/// Coverage matrix from the first reward epoch to the latest epoch in any per-epoch table.
+pub async fn get_epoch_coverage(pool: &SqlitePool) -> Result<Vec<EpochCoverage>> {
+    let rewards = get_cached_epochs(pool, "epoch_rewards").await?;
+    let leader_fees = get_cached_epochs(pool, "leader_fees").await?;
+    let mev = get_cached_epochs(pool, "mev_claims").await?;
+    let bam = get_cached_epochs(pool, "bam_claims").await?;
+    let vote_costs = get_cached_epochs(pool, "vote_costs").await?;
+    let doublezero = get_cached_epochs(pool, "doublezero_fees").await?;
+
+    let all = [&rewards, &leader_fees, &mev, &bam, &vote_costs, &doublezero];
+    let start = rewards
+        .first()
+        .copied()
+        .or_else(|| all.iter().filter_map(|s| s.first().copied()).min());
+    let end = all.iter().filter_map(|s| s.last().copied()).max();
+    let (Some(start), Some(end)) = (start, end) else {
+        return Ok(Vec::new());
+    };
+
+    Ok((start..=end)
+        .map(|epoch| EpochCoverage {
+            epoch,
+            rewards: rewards.contains(&epoch),
+            leader_fees: leader_fees.contains(&epoch),
+            mev: mev.contains(&epoch),
+            bam: bam.contains(&epoch),
+            vote_costs: vote_costs.contains(&epoch),
+            doublezero: doublezero.contains(&epoch),
+        })
+        .collect())
+}
+
+async fn get_cached_epochs(pool: &SqlitePool, table: &str) -> Result<std::collections::BTreeSet<u64>> {
+    let rows = sqlx::query(&format!("SELECT DISTINCT epoch FROM {}", table))
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows.iter().map(|r| r.get::<i64, _>("epoch") as u64).collect())
+}
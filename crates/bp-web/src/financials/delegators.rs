@@ -0,0 +1,276 @@
+//! Delegator concentration and churn for `/financials/delegators` (mirrors
+//! validator-accounting's `delegators.rs`, which takes the stake snapshots).
+//!
+//! Per snapshot epoch: delegator count, the share of stake held by the largest
+//! delegators, and who arrived or left since the previous snapshot.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::admin::{STYLE, escape_html};
+use super::types::DelegatorStake;
+
+/// Largest delegators counted in the concentration share and listed on the page
+pub const TOP_N: usize = 10;
+
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+/// Concentration and churn of one snapshot epoch
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DelegatorEpoch {
+    pub epoch: u64,
+    pub delegators: usize,
+    pub total_stake_sol: f64,
+    pub top_n_share_percent: f64,
+    pub largest_share_percent: f64,
+    /// Herfindahl-Hirschman index of stake shares (0-10,000)
+    pub hhi: f64,
+    pub previous_epoch: Option<u64>,
+    pub new_delegators: usize,
+    pub left_delegators: usize,
+    pub stake_added_sol: f64,
+    pub stake_removed_sol: f64,
+    pub churn_percent: Option<f64>,
+}
+
+/// One of the largest delegators in the latest snapshot
+#[derive(Debug, Clone, Serialize)]
+pub struct TopDelegator {
+    pub delegator: String,
+    pub stake_sol: f64,
+    pub share_percent: f64,
+    pub accounts: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DelegatorReport {
+    /// Oldest first
+    pub epochs: Vec<DelegatorEpoch>,
+    pub top: Vec<TopDelegator>,
+}
+
+pub fn compute(rows: &[DelegatorStake]) -> DelegatorReport {
+    let mut snapshots: BTreeMap<u64, BTreeMap<&str, &DelegatorStake>> = BTreeMap::new();
+    for r in rows {
+        snapshots.entry(r.epoch).or_default().insert(r.delegator.as_str(), r);
+    }
+
+    let to_sol = |lamports: u64| lamports as f64 / LAMPORTS_PER_SOL;
+    let mut epochs = Vec::with_capacity(snapshots.len());
+    let mut previous: Option<(u64, &BTreeMap<&str, &DelegatorStake>)> = None;
+    for (&epoch, stakes) in &snapshots {
+        let total: u64 = stakes.values().map(|s| s.stake_lamports).sum();
+        let share = |lamports: u64| {
+            if total > 0 {
+                lamports as f64 / total as f64 * 100.0
+            } else {
+                0.0
+            }
+        };
+        let mut sorted: Vec<u64> = stakes.values().map(|s| s.stake_lamports).collect();
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+
+        let mut row = DelegatorEpoch {
+            epoch,
+            delegators: stakes.len(),
+            total_stake_sol: to_sol(total),
+            top_n_share_percent: share(sorted.iter().take(TOP_N).sum()),
+            largest_share_percent: share(sorted.first().copied().unwrap_or(0)),
+            hhi: sorted.iter().map(|&s| share(s).powi(2)).sum(),
+            previous_epoch: None,
+            new_delegators: 0,
+            left_delegators: 0,
+            stake_added_sol: 0.0,
+            stake_removed_sol: 0.0,
+            churn_percent: None,
+        };
+        if let Some((previous_epoch, before)) = previous {
+            let stake_of = |m: &BTreeMap<&str, &DelegatorStake>, d: &str| m.get(d).map_or(0, |s| s.stake_lamports);
+            let (mut added, mut removed) = (0u64, 0u64);
+            for d in stakes.keys().chain(before.keys().filter(|d| !stakes.contains_key(*d))) {
+                let (now, then) = (stake_of(stakes, *d), stake_of(before, *d));
+                added += now.saturating_sub(then);
+                removed += then.saturating_sub(now);
+            }
+            row.previous_epoch = Some(previous_epoch);
+            row.new_delegators = stakes.keys().filter(|d| !before.contains_key(*d)).count();
+            row.left_delegators = before.keys().filter(|d| !stakes.contains_key(*d)).count();
+            row.stake_added_sol = to_sol(added);
+            row.stake_removed_sol = to_sol(removed);
+            row.churn_percent = (!before.is_empty()).then(|| row.left_delegators as f64 / before.len() as f64 * 100.0);
+        }
+        previous = Some((epoch, stakes));
+        epochs.push(row);
+    }
+
+    let top = match snapshots.values().next_back() {
+        Some(latest) => {
+            let total: u64 = latest.values().map(|s| s.stake_lamports).sum();
+            let mut top: Vec<&DelegatorStake> = latest.values().copied().collect();
+            top.sort_by(|a, b| b.stake_lamports.cmp(&a.stake_lamports));
+            top.into_iter()
+                .take(TOP_N)
+                .map(|s| TopDelegator {
+                    delegator: s.delegator.clone(),
+                    stake_sol: to_sol(s.stake_lamports),
+                    share_percent: if total > 0 {
+                        s.stake_lamports as f64 / total as f64 * 100.0
+                    } else {
+                        0.0
+                    },
+                    accounts: s.accounts,
+                })
+                .collect()
+        }
+        None => Vec::new(),
+    };
+
+    DelegatorReport { epochs, top }
+}
+
+/// Same columns as validator-accounting's delegators.csv
+pub fn to_csv(report: &DelegatorReport) -> String {
+    let mut out = format!(
+        "Epoch,Delegators,Total_Stake_SOL,Top{}_Share_Pct,Largest_Share_Pct,HHI,Previous_Epoch,\
+         New_Delegators,Left_Delegators,Stake_Added_SOL,Stake_Removed_SOL,Churn_Pct\n",
+        TOP_N
+    );
+    for e in &report.epochs {
+        out.push_str(&format!(
+            "{},{},{:.4},{:.2},{:.2},{:.0},{},{},{},{:.4},{:.4},{}\n",
+            e.epoch,
+            e.delegators,
+            e.total_stake_sol,
+            e.top_n_share_percent,
+            e.largest_share_percent,
+            e.hhi,
+            e.previous_epoch.map_or(String::new(), |p| p.to_string()),
+            e.new_delegators,
+            e.left_delegators,
+            e.stake_added_sol,
+            e.stake_removed_sol,
+            e.churn_percent.map_or(String::new(), |c| format!("{:.1}", c))
+        ));
+    }
+    out
+}
+
+/// Latest concentration with the largest delegators, then churn per snapshot (newest first)
+pub fn render_page(report: Option<&DelegatorReport>) -> String {
+    let body = match report {
+        None => "<p class='status-failed'>Financial data unavailable.</p>".to_string(),
+        Some(report) if report.epochs.is_empty() => {
+            "<p class='muted'>No delegator snapshots yet (taken on each validator-accounting run).</p>".to_string()
+        }
+        Some(report) => {
+            let latest = &report.epochs[report.epochs.len() - 1];
+            let mut html = format!(
+                concat!(
+                    "<p>Epoch {}: {} delegators, {:.0} SOL · top {} hold {:.1}% · largest {:.1}% · HHI {:.0} · ",
+                    "<a href='/financials/delegators?format=csv'>CSV</a> · ",
+                    "<a href='/financials/delegators?format=json'>JSON</a></p>",
+                    "<h2>Largest delegators</h2>",
+                    "<table><tr><th>Withdraw authority</th><th>Stake SOL</th><th>Share</th><th>Accounts</th></tr>"
+                ),
+                latest.epoch,
+                latest.delegators,
+                latest.total_stake_sol,
+                TOP_N,
+                latest.top_n_share_percent,
+                latest.largest_share_percent,
+                latest.hhi
+            );
+            for t in &report.top {
+                html.push_str(&format!(
+                    "<tr><td><code>{}</code></td><td>{:.2}</td><td>{:.1}%</td><td>{}</td></tr>",
+                    escape_html(&t.delegator),
+                    t.stake_sol,
+                    t.share_percent,
+                    t.accounts
+                ));
+            }
+            html.push_str(concat!(
+                "</table><h2>Churn</h2>",
+                "<table><tr><th>Epoch</th><th>Since</th><th>Delegators</th><th>New</th><th>Left</th>",
+                "<th>Stake added</th><th>Stake removed</th><th>Churn</th><th>Top share</th></tr>"
+            ));
+            for e in report.epochs.iter().rev() {
+                html.push_str(&format!(
+                    concat!(
+                        "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td{}>{}</td>",
+                        "<td>{:.2}</td><td>{:.2}</td><td>{}</td><td>{:.1}%</td></tr>"
+                    ),
+                    e.epoch,
+                    e.previous_epoch.map_or("-".to_string(), |p| p.to_string()),
+                    e.delegators,
+                    e.new_delegators,
+                    if e.left_delegators > 0 {
+                        " class='status-failed'"
+                    } else {
+                        ""
+                    },
+                    e.left_delegators,
+                    e.stake_added_sol,
+                    e.stake_removed_sol,
+                    e.churn_percent.map_or("-".to_string(), |c| format!("{:.1}%", c)),
+                    e.top_n_share_percent
+                ));
+            }
+            html.push_str("</table>");
+            html
+        }
+    };
+
+    format!(
+        concat!(
+            "<!DOCTYPE html><html lang='en'><head><meta charset='utf-8'>",
+            "<meta name='viewport' content='width=device-width, initial-scale=1'>",
+            "<title>Block Parliament · Delegators</title>",
+            "<link rel='icon' type='image/png' href='/logo/owl-64.png'>",
+            "<style>{}</style></head><body>",
+            "<h1>Delegators</h1>",
+            "<p><a href='/financials/admin'>&larr; back to admin</a></p>",
+            "<p class='muted'>Stake per withdraw authority from each run's snapshot. ",
+            "Concentrated or churning stake makes commission revenue volatile.</p>",
+            "{}</body></html>"
+        ),
+        STYLE, body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stake(epoch: u64, delegator: &str, sol: u64) -> DelegatorStake {
+        DelegatorStake {
+            epoch,
+            delegator: delegator.to_string(),
+            stake_lamports: sol * 1_000_000_000,
+            accounts: 1,
+        }
+    }
+
+    #[test]
+    fn tracks_arrivals_and_departures_between_snapshots() {
+        let report = compute(&[
+            stake(800, "a", 600),
+            stake(800, "b", 300),
+            stake(800, "c", 100),
+            stake(802, "a", 600),
+            stake(802, "b", 200),
+            stake(802, "d", 200),
+        ]);
+        assert_eq!(report.epochs.len(), 2);
+        assert!((report.epochs[0].hhi - 4_600.0).abs() < 1e-6);
+
+        let latest = &report.epochs[1];
+        assert_eq!((latest.new_delegators, latest.left_delegators), (1, 1));
+        assert_eq!((latest.stake_added_sol, latest.stake_removed_sol), (200.0, 200.0));
+        assert_eq!(report.top[0].delegator, "a");
+        assert_eq!(report.top[0].share_percent, 60.0);
+
+        assert!(to_csv(&report).lines().nth(2).unwrap().starts_with("802,3,"));
+        assert!(render_page(Some(&report)).contains("top 10 hold 100.0%"));
+    }
+}
@@ -0,0 +1,173 @@
+//! DoubleZero prepayment monitor.
+//!
+//! DoubleZero debits its fee from a prepaid deposit PDA, so a forgotten top-up
+//! interrupts service once the balance runs out. This compares the PDA's on-chain
+//! balance against the recent accrual run-rate from `doublezero_fees` and flags the
+//! deposit when it will be exhausted within `[doublezero] prepay_alert_epochs`.
+//! The admin page shows the status as a banner; the scheduler posts an alert to the
+//! notification webhook (at most once per epoch).
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::config::ValidatorConfig;
+use super::db;
+use super::types::DoubleZeroFee;
+
+/// Completed epochs the accrual run-rate is averaged over
+const RUN_RATE_EPOCHS: u64 = 10;
+
+/// Epoch of the last webhook alert (0 = none yet), so a low balance alerts once per epoch
+static LAST_ALERT_EPOCH: AtomicU64 = AtomicU64::new(0);
+
+/// Prepaid balance versus the accrual run-rate
+#[derive(Debug, Clone, Serialize)]
+pub struct PrepayStatus {
+    pub deposit_account: String,
+    pub balance_sol: f64,
+    /// Average liability per epoch over the last `RUN_RATE_EPOCHS` completed epochs
+    pub run_rate_sol_per_epoch: f64,
+    /// `None` when nothing is accruing
+    pub epochs_remaining: Option<f64>,
+    pub alert_epochs: u64,
+    /// The balance runs out within `alert_epochs`
+    pub low: bool,
+}
+
+/// Assess a deposit balance against the fees cached so far
+fn assess(deposit_account: &str, balance_lamports: u64, fees: &[DoubleZeroFee], alert_epochs: u64) -> PrepayStatus {
+    let completed: Vec<&DoubleZeroFee> = fees.iter().filter(|f| !f.is_estimate).collect();
+    // Epochs without leader slots have no fee row; average over the epoch span, not the rows
+    let run_rate_sol_per_epoch = match completed.iter().map(|f| f.epoch).max() {
+        Some(latest) => {
+            let first = latest.saturating_sub(RUN_RATE_EPOCHS - 1);
+            let total: f64 = completed
+                .iter()
+                .filter(|f| f.epoch >= first)
+                .map(|f| f.liability_sol)
+                .sum();
+            total / RUN_RATE_EPOCHS as f64
+        }
+        None => 0.0,
+    };
+
+    let balance_sol = balance_lamports as f64 / 1e9;
+    let epochs_remaining = (run_rate_sol_per_epoch > 0.0).then(|| balance_sol / run_rate_sol_per_epoch);
+    PrepayStatus {
+        deposit_account: deposit_account.to_string(),
+        balance_sol,
+        run_rate_sol_per_epoch,
+        epochs_remaining,
+        alert_epochs,
+        low: epochs_remaining.is_some_and(|e| e < alert_epochs as f64),
+    }
+}
+
+/// Current prepayment status, or `None` when no deposit account is configured
+pub async fn prepay_status(data_dir: &str) -> Result<Option<PrepayStatus>> {
+    let config_path = std::path::Path::new(data_dir).join("config.toml");
+    let config = ValidatorConfig::load(&config_path)?;
+    let Some(deposit_account) = config.doublezero_deposit_account.as_deref() else {
+        return Ok(None);
+    };
+
+    let pool = db::init_cache(data_dir).await?;
+    let fees = db::get_doublezero_fees(pool).await?;
+    let balance = crate::api::get_balance(deposit_account)
+        .await
+        .context("Failed to fetch the DoubleZero deposit balance")?;
+    Ok(Some(assess(
+        deposit_account,
+        balance,
+        &fees,
+        config.doublezero_alert_epochs,
+    )))
+}
+
+/// One-line summary for the banner and the webhook
+pub fn summary(status: &PrepayStatus) -> String {
+    match status.epochs_remaining {
+        Some(epochs) => format!(
+            "DoubleZero deposit {} holds {:.4} SOL, about {:.1} epochs at {:.4} SOL/epoch",
+            status.deposit_account, status.balance_sol, epochs, status.run_rate_sol_per_epoch
+        ),
+        None => format!(
+            "DoubleZero deposit {} holds {:.4} SOL (no recent accruals)",
+            status.deposit_account, status.balance_sol
+        ),
+    }
+}
+
+/// Check the deposit and post a top-up alert when it's low (called by the scheduler)
+pub async fn check_and_alert(data_dir: &str) {
+    let status = match prepay_status(data_dir).await {
+        Ok(Some(status)) => status,
+        Ok(None) => return,
+        Err(e) => {
+            eprintln!("[financials] DoubleZero prepayment check failed: {:#}", e);
+            return;
+        }
+    };
+    if !status.low {
+        return;
+    }
+    eprintln!("[financials] {} — top up soon", summary(&status));
+
+    if !crate::notify::is_configured() {
+        return;
+    }
+    let Some(epoch) = crate::api::get_epoch_info().await.map(|info| info.epoch) else {
+        return;
+    };
+    if LAST_ALERT_EPOCH.swap(epoch, Ordering::Relaxed) == epoch {
+        return;
+    }
+    let message = format!(
+        "DoubleZero prepayment running low: {}. Top up within {} epochs to avoid interruption.",
+        summary(&status),
+        status.alert_epochs
+    );
+    if let Err(e) = crate::notify::send(&message).await {
+        eprintln!("[financials] DoubleZero alert not sent: {:#}", e);
+        LAST_ALERT_EPOCH.store(0, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fee(epoch: u64, liability_sol: f64, is_estimate: bool) -> DoubleZeroFee {
+        DoubleZeroFee {
+            epoch,
+            liability_sol,
+            fee_base_lamports: 0,
+            fee_rate_bps: 500,
+            date: None,
+            is_estimate,
+        }
+    }
+
+    #[test]
+    fn run_rate_spans_epochs_without_fee_rows() {
+        // 0.2 SOL over the last 10 completed epochs (two epochs had no leader slots)
+        let mut fees: Vec<DoubleZeroFee> = (901..=908).map(|e| fee(e, 0.025, false)).collect();
+        fees.push(fee(1, 50.0, false)); // outside the window
+        fees.push(fee(911, 9.0, true)); // current epoch estimate
+        let status = assess("DZ", 100_000_000, &fees, 5);
+        assert!((status.run_rate_sol_per_epoch - 0.02).abs() < 1e-12);
+        assert!((status.epochs_remaining.unwrap() - 5.0).abs() < 1e-9);
+        assert!(!status.low);
+
+        let low = assess("DZ", 90_000_000, &fees, 5);
+        assert!(low.low);
+    }
+
+    #[test]
+    fn no_accruals_is_never_low() {
+        let status = assess("DZ", 0, &[], 5);
+        assert_eq!(status.epochs_remaining, None);
+        assert!(!status.low);
+    }
+}
@@ -0,0 +1,57 @@
+//! Concurrency limit for the `/financials` downloads (CSV and JSON exports).
+//!
+//! An export serializes a whole report (or runs a console query) on the request
+//! task, so a handful of scripts pulling at once can pin the CPU and the cache
+//! pool. At most `FINANCIALS_MAX_CONCURRENT_DOWNLOADS` exports run at a time;
+//! others queue for up to `QUEUE_TIMEOUT` and are then turned away with a 503.
+
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+/// Exports allowed to run at once when `FINANCIALS_MAX_CONCURRENT_DOWNLOADS` is unset
+pub const DEFAULT_MAX_CONCURRENT: usize = 2;
+
+/// How long an export waits for a slot before the request is rejected
+pub const QUEUE_TIMEOUT: Duration = Duration::from_secs(30);
+
+static SLOTS: OnceLock<Semaphore> = OnceLock::new();
+
+/// `FINANCIALS_MAX_CONCURRENT_DOWNLOADS`, falling back to the default when unset or invalid
+fn parse_limit(value: Option<&str>) -> usize {
+    value
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|&limit| limit > 0)
+        .unwrap_or(DEFAULT_MAX_CONCURRENT)
+}
+
+fn slots() -> &'static Semaphore {
+    SLOTS.get_or_init(|| {
+        Semaphore::new(parse_limit(
+            std::env::var("FINANCIALS_MAX_CONCURRENT_DOWNLOADS").ok().as_deref(),
+        ))
+    })
+}
+
+/// Wait for an export slot; `None` if none freed up within `QUEUE_TIMEOUT`.
+/// The slot is released when the permit is dropped.
+pub async fn acquire() -> Option<SemaphorePermit<'static>> {
+    tokio::time::timeout(QUEUE_TIMEOUT, slots().acquire())
+        .await
+        .ok()
+        .and_then(Result::ok)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limit_falls_back_to_default() {
+        assert_eq!(parse_limit(Some("4")), 4);
+        assert_eq!(parse_limit(Some(" 1 ")), 1);
+        assert_eq!(parse_limit(Some("0")), DEFAULT_MAX_CONCURRENT);
+        assert_eq!(parse_limit(Some("many")), DEFAULT_MAX_CONCURRENT);
+        assert_eq!(parse_limit(None), DEFAULT_MAX_CONCURRENT);
+    }
+}
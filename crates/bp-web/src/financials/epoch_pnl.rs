@@ -0,0 +1,254 @@
+//! Per-epoch profitability for `/financials/epochs` (mirrors validator-accounting's
+//! `epoch_pnl.rs`).
+//!
+//! Revenue by source (commission, leader fees, MEV, BAM) against the epoch's vote
+//! fees and DoubleZero accrual, with net margin and a flag on loss-making epochs.
+//! Off-chain expenses stay monthly and are not allocated to epochs.
+
+use report_template::EpochRevenue;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::admin::{STYLE, escape_html};
+use super::sql_console::csv_field;
+use super::types::{ReportData, get_price_with_source};
+
+/// Revenue, costs and margin of one epoch
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EpochPnl {
+    pub epoch: u64,
+    pub date: Option<String>,
+    pub commission_sol: f64,
+    pub leader_fees_sol: f64,
+    pub mev_sol: f64,
+    pub bam_sol: f64,
+    pub revenue_sol: f64,
+    pub vote_cost_sol: f64,
+    pub doublezero_sol: f64,
+    pub costs_sol: f64,
+    pub net_sol: f64,
+    pub price_usd: f64,
+    pub net_usd: f64,
+    /// Net as a percentage of revenue (None without revenue)
+    pub margin_percent: Option<f64>,
+    pub loss_making: bool,
+}
+
+fn row<'a>(epochs: &'a mut BTreeMap<u64, EpochPnl>, epoch: u64, date: &Option<String>) -> &'a mut EpochPnl {
+    let row = epochs.entry(epoch).or_insert_with(|| EpochPnl {
+        epoch,
+        ..Default::default()
+    });
+    if row.date.is_none() {
+        row.date = date.clone();
+    }
+    row
+}
+
+/// One row per epoch with any revenue or cost, oldest first
+pub fn compute(data: &ReportData) -> Vec<EpochPnl> {
+    let mut epochs: BTreeMap<u64, EpochPnl> = BTreeMap::new();
+    for r in data.rewards {
+        row(&mut epochs, r.epoch, &r.date).commission_sol += r.amount_sol;
+    }
+    for f in data.leader_fees {
+        row(&mut epochs, f.epoch, &f.date).leader_fees_sol += f.total_fees_sol;
+    }
+    for c in data.mev_claims {
+        row(&mut epochs, c.epoch, &c.date).mev_sol += c.amount_sol;
+    }
+    for c in data.bam_claims {
+        row(&mut epochs, c.epoch, &c.date).bam_sol += c.amount_sol_equivalent;
+    }
+    for v in data.vote_costs {
+        row(&mut epochs, v.epoch, &v.date).vote_cost_sol += v.total_fee_sol;
+    }
+    for d in data.doublezero_fees {
+        row(&mut epochs, d.epoch, &d.date).doublezero_sol += d.liability_sol;
+    }
+
+    epochs
+        .into_values()
+        .map(|mut r| {
+            r.revenue_sol = r.commission_sol + r.leader_fees_sol + r.mev_sol + r.bam_sol;
+            r.costs_sol = r.vote_cost_sol + r.doublezero_sol;
+            r.net_sol = r.revenue_sol - r.costs_sol;
+            r.price_usd = r
+                .date
+                .as_deref()
+                .map_or(0.0, |d| get_price_with_source(data.prices, d).0);
+            r.net_usd = r.net_sol * r.price_usd;
+            r.margin_percent = (r.revenue_sol > 0.0).then(|| r.net_sol / r.revenue_sol * 100.0);
+            r.loss_making = r.net_sol < 0.0;
+            r
+        })
+        .collect()
+}
+
+/// Epochs that earned anything, as the shared report statistics take them
+pub fn revenue_epochs(rows: &[EpochPnl]) -> Vec<EpochRevenue> {
+    rows.iter()
+        .filter(|r| r.revenue_sol > 0.0)
+        .map(|r| EpochRevenue {
+            epoch: r.epoch,
+            date: r.date.clone(),
+            revenue_sol: r.revenue_sol,
+            commission_sol: r.commission_sol,
+            leader_fees_sol: r.leader_fees_sol,
+            mev_sol: r.mev_sol,
+        })
+        .collect()
+}
+
+/// Same columns as validator-accounting's epoch_pnl.csv
+pub fn to_csv(rows: &[EpochPnl]) -> String {
+    let mut out = String::from(
+        "Epoch,Date (YYYY-MM-DD),Commission_SOL,Leader_Fees_SOL,MEV_SOL,BAM_SOL,Revenue_SOL,\
+         Vote_Cost_SOL,DoubleZero_SOL,Costs_SOL,Net_SOL,SOL_Price_USD,Net_USD,Net_Margin_Pct,Loss_Making\n",
+    );
+    for r in rows {
+        out.push_str(&format!(
+            "{},{},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.2},{:.2},{},{}\n",
+            r.epoch,
+            csv_field(r.date.as_deref().unwrap_or("")),
+            r.commission_sol,
+            r.leader_fees_sol,
+            r.mev_sol,
+            r.bam_sol,
+            r.revenue_sol,
+            r.vote_cost_sol,
+            r.doublezero_sol,
+            r.costs_sol,
+            r.net_sol,
+            r.price_usd,
+            r.net_usd,
+            r.margin_percent.map_or(String::new(), |m| format!("{:.1}", m)),
+            if r.loss_making { "yes" } else { "no" }
+        ));
+    }
+    out
+}
+
+/// The epoch table, newest first, loss-making epochs highlighted
+pub fn render_page(rows: Option<&[EpochPnl]>) -> String {
+    let body = match rows {
+        None => "<p class='status-failed'>Financial data unavailable.</p>".to_string(),
+        Some([]) => "<p class='muted'>No epochs cached yet.</p>".to_string(),
+        Some(rows) => {
+            let losses = rows.iter().filter(|r| r.loss_making).count();
+            let net_sol: f64 = rows.iter().map(|r| r.net_sol).sum();
+            let mut html = format!(
+                concat!(
+                    "<p>{} epochs, <span class='{}'>{} loss-making</span>, net {:.4} SOL · ",
+                    "<a href='/financials/epochs?format=csv'>CSV</a> · ",
+                    "<a href='/financials/epochs?format=json'>JSON</a></p>",
+                    "<table><tr><th>Epoch</th><th>Date</th><th>Commission</th><th>Leader</th>",
+                    "<th>MEV</th><th>BAM</th><th>Vote fees</th><th>DoubleZero</th>",
+                    "<th>Net SOL</th><th>Net USD</th><th>Margin</th></tr>"
+                ),
+                rows.len(),
+                if losses > 0 { "status-failed" } else { "muted" },
+                losses,
+                net_sol
+            );
+            for r in rows.iter().rev() {
+                html.push_str(&format!(
+                    concat!(
+                        "<tr{}><td>{}</td><td>{}</td><td>{:.4}</td><td>{:.4}</td><td>{:.4}</td>",
+                        "<td>{:.4}</td><td>{:.4}</td><td>{:.4}</td><td>{:.4}</td><td>${:.2}</td><td>{}</td></tr>"
+                    ),
+                    if r.loss_making { " class='status-failed'" } else { "" },
+                    r.epoch,
+                    escape_html(r.date.as_deref().unwrap_or("")),
+                    r.commission_sol,
+                    r.leader_fees_sol,
+                    r.mev_sol,
+                    r.bam_sol,
+                    r.vote_cost_sol,
+                    r.doublezero_sol,
+                    r.net_sol,
+                    r.net_usd,
+                    r.margin_percent.map_or("-".to_string(), |m| format!("{:.1}%", m))
+                ));
+            }
+            html.push_str("</table>");
+            html
+        }
+    };
+
+    format!(
+        concat!(
+            "<!DOCTYPE html><html lang='en'><head><meta charset='utf-8'>",
+            "<meta name='viewport' content='width=device-width, initial-scale=1'>",
+            "<title>Block Parliament · Epoch Profitability</title>",
+            "<link rel='icon' type='image/png' href='/logo/owl-64.png'>",
+            "<style>{}</style></head><body>",
+            "<h1>Epoch Profitability</h1>",
+            "<p><a href='/financials/admin'>&larr; back to admin</a></p>",
+            "<p class='muted'>Per-epoch revenue against vote fees and DoubleZero accrual. ",
+            "Off-chain expenses are monthly and not included.</p>",
+            "{}</body></html>"
+        ),
+        STYLE, body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::financials::types::{CategorizedTransfers, EpochReward, EpochVoteCost, PriceMap};
+
+    #[test]
+    fn flags_epochs_where_vote_fees_exceed_revenue() {
+        let rewards = vec![EpochReward {
+            epoch: 800,
+            amount_sol: 3.0,
+            commission: 5,
+            date: Some("2025-06-01".into()),
+        }];
+        let vote_costs = vec![
+            EpochVoteCost {
+                epoch: 800,
+                vote_count: 400_000,
+                total_fee_sol: 2.0,
+                source: "measured".into(),
+                date: Some("2025-06-01".into()),
+            },
+            EpochVoteCost {
+                epoch: 801,
+                vote_count: 400_000,
+                total_fee_sol: 2.0,
+                source: "measured".into(),
+                date: Some("2025-06-03".into()),
+            },
+        ];
+        let prices = PriceMap::from([
+            ("2025-06-01".to_string(), 100.0.into()),
+            ("2025-06-03".to_string(), 100.0.into()),
+        ]);
+        let categorized = CategorizedTransfers::default();
+        let data = ReportData {
+            rewards: &rewards,
+            categorized: &categorized,
+            mev_claims: &[],
+            bam_claims: &[],
+            leader_fees: &[],
+            doublezero_fees: &[],
+            vote_costs: &vote_costs,
+            expenses: &[],
+            prices: &prices,
+            sfdp_acceptance_date: None,
+        };
+
+        let rows = compute(&data);
+        assert_eq!(rows.len(), 2);
+        assert!(!rows[0].loss_making);
+        assert!((rows[0].net_usd - 100.0).abs() < 1e-9);
+        assert!(rows[1].loss_making);
+        assert_eq!(rows[1].margin_percent, None);
+
+        let csv = to_csv(&rows);
+        assert!(csv.lines().nth(2).unwrap().ends_with(",yes"));
+        assert!(render_page(Some(&rows)).contains("1 loss-making"));
+    }
+}
@@ -0,0 +1,831 @@
+//! Expense entry for `/financials/admin/expenses`.
+//!
+//! Server-rendered forms (no JS, so they work from a phone) to list, add, edit and
+//! delete one-time and recurring expenses. Rows go into the same `expenses` and
+//! `recurring_expenses` tables the `validator-accounting expense` / `recurring`
+//! commands write, through a read-write connection used only here; the rest of
+//! bp-web keeps reading `cache.sqlite` read-only. Notion-synced rows are listed but
+//! not editable, since the next sync replaces them.
+//!
+//! Imported and Notion-synced expenses arrive pending; the page lists them first with
+//! approve/reject buttons, and only approved ones reach the reports.
+
+use anyhow::{Context, Result, bail};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::{Row, SqlitePool};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use super::admin::{STYLE, escape_html};
+use super::types::ExpenseCategory;
+
+static WRITE_POOL: OnceLock<SqlitePool> = OnceLock::new();
+
+/// How long a write waits for an ingestion run holding the database lock
+const BUSY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// `paid_with` values owned by the Notion sync (rewritten on every run)
+const NOTION_PAID_WITH: [&str; 2] = ["Notion Paid", "Notion Unpaid"];
+
+/// Longest vendor, description, payment method or invoice id accepted
+const MAX_FIELD_CHARS: usize = 200;
+
+/// Review status the reports count (`expenses.status`)
+const APPROVED: &str = "approved";
+
+/// One-time expense or monthly recurring template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpenseKind {
+    OneTime,
+    Recurring,
+}
+
+impl ExpenseKind {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "one_time" => Some(Self::OneTime),
+            "recurring" => Some(Self::Recurring),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::OneTime => "one_time",
+            Self::Recurring => "recurring",
+        }
+    }
+
+    fn table(self) -> &'static str {
+        match self {
+            Self::OneTime => "expenses",
+            Self::Recurring => "recurring_expenses",
+        }
+    }
+}
+
+/// An expense as stored (or about to be stored)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpenseEntry {
+    /// None for a new entry
+    pub id: Option<i64>,
+    pub kind: ExpenseKind,
+    /// One-time: the expense date; recurring: the start date
+    pub date: String,
+    /// Recurring only; None runs indefinitely
+    pub end_date: Option<String>,
+    pub vendor: String,
+    pub category: ExpenseCategory,
+    pub description: String,
+    pub amount_usd: f64,
+    pub paid_with: String,
+    /// One-time only
+    pub invoice_id: Option<String>,
+    /// "pending", "approved" or "rejected"; recurring templates are always approved
+    pub status: String,
+}
+
+impl ExpenseEntry {
+    /// Written by the Notion sync, so read-only here
+    pub fn is_synced(&self) -> bool {
+        NOTION_PAID_WITH.contains(&self.paid_with.as_str())
+    }
+
+    /// Left out of the reports until approved
+    pub fn is_unapproved(&self) -> bool {
+        self.status != APPROVED
+    }
+}
+
+/// Form body for `POST /financials/admin/expenses`, as typed (re-shown on errors)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ExpenseForm {
+    /// "save" (default), "delete", "approve", "reject" or "approve_all"
+    pub action: String,
+    /// "one_time" or "recurring"
+    pub kind: String,
+    /// Empty for a new entry
+    pub id: String,
+    pub date: String,
+    pub end_date: String,
+    pub vendor: String,
+    /// `ExpenseCategory::db_name`
+    pub category: String,
+    pub description: String,
+    pub amount_usd: String,
+    pub paid_with: String,
+    pub invoice_id: String,
+}
+
+impl ExpenseForm {
+    /// Blank form for a new expense dated `today`
+    pub fn new_entry(today: &str) -> Self {
+        Self {
+            kind: ExpenseKind::OneTime.as_str().to_string(),
+            date: today.to_string(),
+            category: ExpenseCategory::Hosting.db_name().to_string(),
+            paid_with: "USD".to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Form pre-filled from a stored entry
+    pub fn from_entry(entry: &ExpenseEntry) -> Self {
+        Self {
+            action: String::new(),
+            kind: entry.kind.as_str().to_string(),
+            id: entry.id.map_or(String::new(), |id| id.to_string()),
+            date: entry.date.clone(),
+            end_date: entry.end_date.clone().unwrap_or_default(),
+            vendor: entry.vendor.clone(),
+            category: entry.category.db_name().to_string(),
+            description: entry.description.clone(),
+            amount_usd: entry.amount_usd.to_string(),
+            paid_with: entry.paid_with.clone(),
+            invoice_id: entry.invoice_id.clone().unwrap_or_default(),
+        }
+    }
+
+    pub fn is_delete(&self) -> bool {
+        self.action == "delete"
+    }
+
+    /// Status to set for "approve" / "reject" posts
+    pub fn review_status(&self) -> Option<&'static str> {
+        match self.action.as_str() {
+            "approve" => Some(APPROVED),
+            "reject" => Some("rejected"),
+            _ => None,
+        }
+    }
+
+    pub fn is_approve_all(&self) -> bool {
+        self.action == "approve_all"
+    }
+
+    /// Entry kind and id, for deletes and reviews (which skip field validation)
+    pub fn target(&self) -> Result<(ExpenseKind, i64), String> {
+        let kind = ExpenseKind::parse(&self.kind).ok_or_else(|| "Unknown expense kind".to_string())?;
+        let id = self
+            .id
+            .trim()
+            .parse()
+            .map_err(|_| "Missing or invalid expense id".to_string())?;
+        Ok((kind, id))
+    }
+
+    /// Check every field, returning all problems at once
+    pub fn validate(&self) -> Result<ExpenseEntry, Vec<String>> {
+        let mut errors = Vec::new();
+
+        let kind = ExpenseKind::parse(&self.kind);
+        if kind.is_none() {
+            errors.push("Choose one-time or recurring".to_string());
+        }
+        let id = match self.id.trim() {
+            "" => None,
+            id => match id.parse::<i64>() {
+                Ok(id) => Some(id),
+                Err(_) => {
+                    errors.push("Invalid expense id".to_string());
+                    None
+                }
+            },
+        };
+
+        let date_label = if kind == Some(ExpenseKind::Recurring) {
+            "Start date"
+        } else {
+            "Date"
+        };
+        let date = parse_date(&self.date);
+        if date.is_none() {
+            errors.push(format!("{} must be a date (YYYY-MM-DD)", date_label));
+        }
+        let end_date = match self.end_date.trim() {
+            "" => None,
+            raw => match parse_date(raw) {
+                Some(end) if date.is_some_and(|start| end < start) => {
+                    errors.push("End date is before the start date".to_string());
+                    None
+                }
+                Some(end) => Some(end),
+                None => {
+                    errors.push("End date must be a date (YYYY-MM-DD)".to_string());
+                    None
+                }
+            },
+        };
+
+        let mut text = |value: &str, label: &str, required: bool| -> Option<String> {
+            let value = value.trim();
+            if required && value.is_empty() {
+                errors.push(format!("{} is required", label));
+            } else if value.chars().count() > MAX_FIELD_CHARS {
+                errors.push(format!("{} is longer than {} characters", label, MAX_FIELD_CHARS));
+            }
+            (!value.is_empty()).then(|| value.to_string())
+        };
+        let vendor = text(&self.vendor, "Vendor", true);
+        let description = text(&self.description, "Description", true);
+        let paid_with = text(&self.paid_with, "Payment method", true);
+        let invoice_id = text(&self.invoice_id, "Invoice id", false);
+
+        let category = ExpenseCategory::ALL.into_iter().find(|c| c.db_name() == self.category);
+        if category.is_none() {
+            errors.push("Choose a category".to_string());
+        }
+        let amount_usd = self
+            .amount_usd
+            .trim()
+            .trim_start_matches('$')
+            .replace(',', "")
+            .parse::<f64>()
+            .ok()
+            .filter(|a| a.is_finite() && *a > 0.0);
+        if amount_usd.is_none() {
+            errors.push("Amount must be a positive USD amount".to_string());
+        }
+        if paid_with.as_deref().is_some_and(|p| NOTION_PAID_WITH.contains(&p)) {
+            errors.push("That payment method is reserved for Notion-synced rows".to_string());
+        }
+
+        match (kind, date, vendor, description, paid_with, category, amount_usd) {
+            (
+                Some(kind),
+                Some(date),
+                Some(vendor),
+                Some(description),
+                Some(paid_with),
+                Some(category),
+                Some(amount_usd),
+            ) if errors.is_empty() => Ok(ExpenseEntry {
+                id,
+                kind,
+                date: date.to_string(),
+                end_date: end_date
+                    .filter(|_| kind == ExpenseKind::Recurring)
+                    .map(|d| d.to_string()),
+                vendor,
+                category,
+                description,
+                amount_usd,
+                paid_with,
+                invoice_id: invoice_id.filter(|_| kind == ExpenseKind::OneTime),
+                status: APPROVED.to_string(),
+            }),
+            _ => Err(errors),
+        }
+    }
+}
+
+fn parse_date(s: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").ok()
+}
+
+/// Whether a form post came from this site: its `Origin` (when the browser sent one)
+/// must name the host the request was sent to. Basic Auth credentials ride along on
+/// cross-site posts, so writes can't rely on them alone.
+pub fn is_same_origin(origin: Option<&str>, host: Option<&str>) -> bool {
+    let Some(origin) = origin else {
+        return true;
+    };
+    let origin_host = origin
+        .split_once("://")
+        .map_or(origin, |(_, rest)| rest)
+        .trim_end_matches('/');
+    host.is_some_and(|host| origin_host.eq_ignore_ascii_case(host))
+}
+
+// ── Database ─────────────────────────────────────────────────────────────────
+
+/// Read-write pool on cache.sqlite (never creates the file: validator-accounting owns the schema)
+async fn write_pool(data_dir: &str) -> Result<&'static SqlitePool> {
+    if let Some(pool) = WRITE_POOL.get() {
+        return Ok(pool);
+    }
+
+    let db_path = super::db::cache_path(data_dir);
+    let options =
+        SqliteConnectOptions::from_str(&format!("sqlite:{}?mode=rw", db_path.display()))?.busy_timeout(BUSY_TIMEOUT);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await
+        .with_context(|| format!("Failed to open cache for writing at {}", db_path.display()))?;
+
+    // Ignore if already set (race between concurrent requests)
+    let _ = WRITE_POOL.set(pool);
+    Ok(WRITE_POOL.get().unwrap())
+}
+
+/// One-time expenses (newest first), then recurring templates (newest start first)
+pub async fn list_entries(data_dir: &str) -> Result<Vec<ExpenseEntry>> {
+    let pool = write_pool(data_dir).await?;
+
+    // Caches from before review status have no column: every row there counts
+    let has_status: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM pragma_table_info('expenses') WHERE name = 'status'")
+            .fetch_one(pool)
+            .await?;
+    let one_time = sqlx::query(&format!(
+        "SELECT id, date, vendor, category, description, amount_usd, paid_with, invoice_id, {} AS status
+         FROM expenses ORDER BY date DESC, id DESC",
+        if has_status > 0 { "status" } else { "'approved'" }
+    ))
+    .fetch_all(pool)
+    .await?;
+    let recurring = sqlx::query(
+        "SELECT id, vendor, category, description, amount_usd, paid_with, start_date, end_date
+         FROM recurring_expenses ORDER BY start_date DESC, id DESC",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let one_time = one_time.iter().map(|r| ExpenseEntry {
+        id: Some(r.get("id")),
+        kind: ExpenseKind::OneTime,
+        date: r.get("date"),
+        end_date: None,
+        vendor: r.get("vendor"),
+        category: ExpenseCategory::from_str_lossy(r.get::<&str, _>("category")),
+        description: r.get("description"),
+        amount_usd: r.get("amount_usd"),
+        paid_with: r.get("paid_with"),
+        invoice_id: r.get("invoice_id"),
+        status: r.get("status"),
+    });
+    let recurring = recurring.iter().map(|r| ExpenseEntry {
+        id: Some(r.get("id")),
+        kind: ExpenseKind::Recurring,
+        date: r.get("start_date"),
+        end_date: r.get("end_date"),
+        vendor: r.get("vendor"),
+        category: ExpenseCategory::from_str_lossy(r.get::<&str, _>("category")),
+        description: r.get("description"),
+        amount_usd: r.get("amount_usd"),
+        paid_with: r.get("paid_with"),
+        invoice_id: None,
+        status: APPROVED.to_string(),
+    });
+    Ok(one_time.chain(recurring).collect())
+}
+
+/// Insert a new entry or update an existing one; returns its id
+pub async fn save_entry(data_dir: &str, entry: &ExpenseEntry) -> Result<i64> {
+    let pool = write_pool(data_dir).await?;
+
+    let result = match (entry.kind, entry.id) {
+        (ExpenseKind::OneTime, None) => {
+            sqlx::query(
+                "INSERT INTO expenses (date, vendor, category, description, amount_usd, paid_with, invoice_id)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&entry.date)
+            .bind(&entry.vendor)
+            .bind(entry.category.db_name())
+            .bind(&entry.description)
+            .bind(entry.amount_usd)
+            .bind(&entry.paid_with)
+            .bind(&entry.invoice_id)
+            .execute(pool)
+            .await?
+        }
+        (ExpenseKind::OneTime, Some(id)) => {
+            sqlx::query(
+                "UPDATE expenses SET date = ?, vendor = ?, category = ?, description = ?, amount_usd = ?,
+                 paid_with = ?, invoice_id = ?
+                 WHERE id = ? AND paid_with NOT IN ('Notion Paid', 'Notion Unpaid')",
+            )
+            .bind(&entry.date)
+            .bind(&entry.vendor)
+            .bind(entry.category.db_name())
+            .bind(&entry.description)
+            .bind(entry.amount_usd)
+            .bind(&entry.paid_with)
+            .bind(&entry.invoice_id)
+            .bind(id)
+            .execute(pool)
+            .await?
+        }
+        (ExpenseKind::Recurring, None) => {
+            sqlx::query(
+                "INSERT INTO recurring_expenses (vendor, category, description, amount_usd, paid_with, start_date, end_date)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&entry.vendor)
+            .bind(entry.category.db_name())
+            .bind(&entry.description)
+            .bind(entry.amount_usd)
+            .bind(&entry.paid_with)
+            .bind(&entry.date)
+            .bind(&entry.end_date)
+            .execute(pool)
+            .await?
+        }
+        (ExpenseKind::Recurring, Some(id)) => {
+            sqlx::query(
+                "UPDATE recurring_expenses SET vendor = ?, category = ?, description = ?, amount_usd = ?,
+                 paid_with = ?, start_date = ?, end_date = ?
+                 WHERE id = ?",
+            )
+            .bind(&entry.vendor)
+            .bind(entry.category.db_name())
+            .bind(&entry.description)
+            .bind(entry.amount_usd)
+            .bind(&entry.paid_with)
+            .bind(&entry.date)
+            .bind(&entry.end_date)
+            .bind(id)
+            .execute(pool)
+            .await?
+        }
+    };
+
+    match entry.id {
+        None => Ok(result.last_insert_rowid()),
+        Some(id) if result.rows_affected() > 0 => Ok(id),
+        Some(id) => bail!("No editable {} with id {}", entry.kind.table(), id),
+    }
+}
+
+/// Delete an entry; false if there was no such (editable) row
+pub async fn delete_entry(data_dir: &str, kind: ExpenseKind, id: i64) -> Result<bool> {
+    let pool = write_pool(data_dir).await?;
+    let sql = match kind {
+        ExpenseKind::OneTime => {
+            "DELETE FROM expenses WHERE id = ? AND paid_with NOT IN ('Notion Paid', 'Notion Unpaid')"
+        }
+        ExpenseKind::Recurring => "DELETE FROM recurring_expenses WHERE id = ?",
+    };
+    let result = sqlx::query(sql).bind(id).execute(pool).await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Set the review status of a one-time expense (Notion rows included: the sync keeps
+/// it per page); false if there was no such row
+pub async fn set_status(data_dir: &str, id: i64, status: &str) -> Result<bool> {
+    let pool = write_pool(data_dir).await?;
+    let result = sqlx::query("UPDATE expenses SET status = ? WHERE id = ?")
+        .bind(status)
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Approve every pending expense; returns how many there were
+pub async fn approve_all_pending(data_dir: &str) -> Result<u64> {
+    let pool = write_pool(data_dir).await?;
+    let result = sqlx::query("UPDATE expenses SET status = 'approved' WHERE status = 'pending'")
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected())
+}
+
+// ── Rendering ────────────────────────────────────────────────────────────────
+
+const FORM_STYLE: &str = concat!(
+    "form.entry label{display:block;margin:.4em 0}",
+    "form.entry input,form.entry select{font:inherit;background:inherit;color:inherit;width:100%;max-width:40ch}",
+    "form.inline{display:inline}button{font:inherit}"
+);
+
+/// The expense page: notice or errors, the review queue, the add/edit form, then both lists
+pub fn render_page(entries: &[ExpenseEntry], form: &ExpenseForm, errors: &[String], notice: Option<&str>) -> String {
+    let messages = if errors.is_empty() {
+        notice.map_or(String::new(), |n| {
+            format!("<p class='status-success'>{}</p>", escape_html(n))
+        })
+    } else {
+        format!(
+            "<ul class='status-failed'>{}</ul>",
+            errors
+                .iter()
+                .map(|e| format!("<li>{}</li>", escape_html(e)))
+                .collect::<String>()
+        )
+    };
+
+    format!(
+        concat!(
+            "<!DOCTYPE html><html lang='en'><head><meta charset='utf-8'>",
+            "<meta name='viewport' content='width=device-width, initial-scale=1'>",
+            "<title>Block Parliament · Expenses</title>",
+            "<link rel='icon' type='image/png' href='/logo/owl-64.png'>",
+            "<style>{}{}</style></head><body>",
+            "<h1>Expenses</h1>",
+            "<p><a href='/financials/admin'>&larr; back to admin</a></p>",
+            "{}{}{}{}",
+            "</body></html>"
+        ),
+        STYLE,
+        FORM_STYLE,
+        messages,
+        render_review_queue(entries),
+        render_form(form),
+        render_lists(entries)
+    )
+}
+
+fn render_form(form: &ExpenseForm) -> String {
+    let editing = !form.id.trim().is_empty();
+    let kind = ExpenseKind::parse(&form.kind).unwrap_or(ExpenseKind::OneTime);
+    let kind_options = [
+        (ExpenseKind::OneTime, "One-time"),
+        (ExpenseKind::Recurring, "Recurring (monthly)"),
+    ]
+    .into_iter()
+    .map(|(k, label)| {
+        format!(
+            "<option value='{}'{}>{}</option>",
+            k.as_str(),
+            if k == kind { " selected" } else { "" },
+            label
+        )
+    })
+    .collect::<String>();
+    let category_options = ExpenseCategory::ALL
+        .into_iter()
+        .map(|c| {
+            format!(
+                "<option value='{}'{}>{}</option>",
+                c.db_name(),
+                if c.db_name() == form.category { " selected" } else { "" },
+                c
+            )
+        })
+        .collect::<String>();
+    // An existing entry keeps its kind; the other table has no row with its id
+    let kind_field = if editing {
+        format!(
+            "<input type='hidden' name='kind' value='{}'><p class='muted'>Editing {} expense #{}</p>",
+            kind.as_str(),
+            if kind == ExpenseKind::Recurring {
+                "recurring"
+            } else {
+                "one-time"
+            },
+            escape_html(&form.id)
+        )
+    } else {
+        format!("<label>Kind <select name='kind'>{}</select></label>", kind_options)
+    };
+
+    format!(
+        concat!(
+            "<h2>{}</h2>",
+            "<form class='entry' method='post' action='/financials/admin/expenses'>",
+            "<input type='hidden' name='action' value='save'>",
+            "<input type='hidden' name='id' value='{}'>",
+            "{}",
+            "<label>Date (start date if recurring) <input name='date' type='date' value='{}' required></label>",
+            "<label>End date (recurring, optional) <input name='end_date' type='date' value='{}'></label>",
+            "<label>Vendor <input name='vendor' value='{}' maxlength='{}' required></label>",
+            "<label>Category <select name='category'>{}</select></label>",
+            "<label>Description <input name='description' value='{}' maxlength='{}' required></label>",
+            "<label>Amount (USD) <input name='amount_usd' inputmode='decimal' value='{}' required></label>",
+            "<label>Paid with <input name='paid_with' value='{}' maxlength='{}' required></label>",
+            "<label>Invoice id (one-time, optional) <input name='invoice_id' value='{}' maxlength='{}'></label>",
+            "<button type='submit'>{}</button>{}",
+            "</form>"
+        ),
+        if editing { "Edit expense" } else { "Add expense" },
+        escape_html(&form.id),
+        kind_field,
+        escape_html(&form.date),
+        escape_html(&form.end_date),
+        escape_html(&form.vendor),
+        MAX_FIELD_CHARS,
+        category_options,
+        escape_html(&form.description),
+        MAX_FIELD_CHARS,
+        escape_html(&form.amount_usd),
+        escape_html(&form.paid_with),
+        MAX_FIELD_CHARS,
+        escape_html(&form.invoice_id),
+        MAX_FIELD_CHARS,
+        if editing { "Save changes" } else { "Add" },
+        if editing {
+            " <a href='/financials/admin/expenses'>cancel</a>"
+        } else {
+            ""
+        }
+    )
+}
+
+/// Pending expenses with approve/reject buttons (nothing when the queue is empty)
+fn render_review_queue(entries: &[ExpenseEntry]) -> String {
+    let pending: Vec<_> = entries.iter().filter(|e| e.status == "pending").collect();
+    if pending.is_empty() {
+        return String::new();
+    }
+    let total: f64 = pending.iter().map(|e| e.amount_usd).sum();
+    format!(
+        concat!(
+            "<h2>Pending review</h2>",
+            "<p class='muted'>{} imported or synced expense(s), ${:.2}, not in the reports until approved.</p>",
+            "<form class='inline' method='post' action='/financials/admin/expenses'>",
+            "<input type='hidden' name='action' value='approve_all'>",
+            "<button type='submit'>approve all</button></form>",
+            "{}"
+        ),
+        pending.len(),
+        total,
+        render_table(&pending, "Date")
+    )
+}
+
+fn render_lists(entries: &[ExpenseEntry]) -> String {
+    let (one_time, recurring): (Vec<_>, Vec<_>) = entries.iter().partition(|e| e.kind == ExpenseKind::OneTime);
+    let mut html = String::from("<h2>One-time expenses</h2>");
+    html.push_str(&render_table(&one_time, "Date"));
+    html.push_str("<h2>Recurring expenses</h2>");
+    html.push_str(&render_table(&recurring, "Start &ndash; end"));
+    html
+}
+
+fn render_table(entries: &[&ExpenseEntry], date_heading: &str) -> String {
+    if entries.is_empty() {
+        return "<p class='muted'>None yet.</p>".to_string();
+    }
+
+    let mut html = format!(
+        "<table><thead><tr><th>{}</th><th>Vendor</th><th>Category</th><th>Description</th>\
+         <th>USD</th><th>Paid with</th><th></th></tr></thead><tbody>",
+        date_heading
+    );
+    for entry in entries {
+        let date = match (&entry.kind, &entry.end_date) {
+            (ExpenseKind::Recurring, Some(end)) => format!("{} &ndash; {}", escape_html(&entry.date), escape_html(end)),
+            (ExpenseKind::Recurring, None) => format!("{} &ndash;", escape_html(&entry.date)),
+            (ExpenseKind::OneTime, _) => escape_html(&entry.date),
+        };
+        let review = match entry.id {
+            Some(id) if entry.is_unapproved() => {
+                let button = |action: &str| {
+                    format!(
+                        concat!(
+                            "<form class='inline' method='post' action='/financials/admin/expenses'>",
+                            "<input type='hidden' name='action' value='{action}'>",
+                            "<input type='hidden' name='kind' value='one_time'>",
+                            "<input type='hidden' name='id' value='{id}'>",
+                            "<button type='submit'>{action}</button></form> "
+                        ),
+                        action = action,
+                        id = id
+                    )
+                };
+                let reject = if entry.status == "pending" {
+                    button("reject")
+                } else {
+                    String::new()
+                };
+                format!(
+                    "<span class='status-failed'>{}</span> {}{}",
+                    escape_html(&entry.status),
+                    button("approve"),
+                    reject
+                )
+            }
+            _ => String::new(),
+        };
+        let actions = match entry.id {
+            Some(_) if entry.is_synced() => "<span class='muted'>synced from Notion</span>".to_string(),
+            Some(id) => format!(
+                concat!(
+                    "<a href='/financials/admin/expenses?edit={id}&amp;kind={kind}'>edit</a> ",
+                    "<form class='inline' method='post' action='/financials/admin/expenses'>",
+                    "<input type='hidden' name='action' value='delete'>",
+                    "<input type='hidden' name='kind' value='{kind}'>",
+                    "<input type='hidden' name='id' value='{id}'>",
+                    "<button type='submit'>delete</button></form>"
+                ),
+                id = id,
+                kind = entry.kind.as_str()
+            ),
+            None => String::new(),
+        };
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{:.2}</td><td>{}</td><td>{}</td></tr>",
+            date,
+            escape_html(&entry.vendor),
+            entry.category,
+            escape_html(&entry.description),
+            entry.amount_usd,
+            escape_html(&entry.paid_with),
+            review + &actions
+        ));
+    }
+    html.push_str("</tbody></table>");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn form(kind: &str) -> ExpenseForm {
+        ExpenseForm {
+            kind: kind.to_string(),
+            date: "2026-09-14".to_string(),
+            vendor: "Latitude".to_string(),
+            category: "Hosting".to_string(),
+            description: "Bare metal, September".to_string(),
+            amount_usd: "$1,250.50".to_string(),
+            paid_with: "Card".to_string(),
+            invoice_id: "INV-9".to_string(),
+            end_date: "2026-12-31".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn validates_and_normalizes_entries() {
+        let one_time = form("one_time").validate().unwrap();
+        assert_eq!(one_time.kind, ExpenseKind::OneTime);
+        assert_eq!(one_time.id, None);
+        assert_eq!(one_time.category, ExpenseCategory::Hosting);
+        assert!((one_time.amount_usd - 1250.5).abs() < 1e-9);
+        assert_eq!(one_time.invoice_id.as_deref(), Some("INV-9"));
+        assert_eq!(one_time.end_date, None);
+
+        let recurring = form("recurring").validate().unwrap();
+        assert_eq!(recurring.end_date.as_deref(), Some("2026-12-31"));
+        assert_eq!(recurring.invoice_id, None);
+    }
+
+    #[test]
+    fn reports_every_invalid_field() {
+        let bad = ExpenseForm {
+            date: "14/09/2026".to_string(),
+            vendor: " ".to_string(),
+            category: "Snacks".to_string(),
+            amount_usd: "-3".to_string(),
+            paid_with: "Notion Paid".to_string(),
+            ..form("recurring")
+        };
+        let errors = bad.validate().unwrap_err();
+        assert_eq!(errors.len(), 5, "{:?}", errors);
+        assert!(errors[0].starts_with("Start date"));
+
+        let backwards = ExpenseForm {
+            end_date: "2026-01-01".to_string(),
+            ..form("recurring")
+        };
+        assert_eq!(
+            backwards.validate().unwrap_err(),
+            vec!["End date is before the start date".to_string()]
+        );
+    }
+
+    #[test]
+    fn rejects_cross_site_posts() {
+        assert!(is_same_origin(None, Some("blockparliament.xyz")));
+        assert!(is_same_origin(
+            Some("https://blockparliament.xyz"),
+            Some("blockparliament.xyz")
+        ));
+        assert!(!is_same_origin(
+            Some("https://evil.example"),
+            Some("blockparliament.xyz")
+        ));
+        assert!(!is_same_origin(Some("https://blockparliament.xyz"), None));
+    }
+
+    #[test]
+    fn synced_rows_have_no_edit_controls() {
+        let mut entry = form("one_time").validate().unwrap();
+        entry.id = Some(3);
+        let editable = render_table(&[&entry], "Date");
+        assert!(editable.contains("edit=3&amp;kind=one_time"));
+
+        entry.paid_with = "Notion Unpaid".to_string();
+        let synced = render_table(&[&entry], "Date");
+        assert!(!synced.contains("edit="));
+        assert!(synced.contains("synced from Notion"));
+    }
+
+    #[test]
+    fn pending_rows_get_review_controls() {
+        let mut entry = form("one_time").validate().unwrap();
+        entry.id = Some(7);
+        assert!(render_review_queue(&[entry.clone()]).is_empty());
+        assert!(!render_table(&[&entry], "Date").contains("value='approve'"));
+
+        entry.status = "pending".to_string();
+        let queue = render_review_queue(&[entry.clone()]);
+        assert!(queue.contains("Pending review"));
+        assert!(queue.contains("value='approve_all'"));
+        assert!(queue.contains("value='approve'") && queue.contains("value='reject'"));
+
+        entry.status = "rejected".to_string();
+        let rejected = render_table(&[&entry], "Date");
+        assert!(rejected.contains("value='approve'") && !rejected.contains("value='reject'"));
+        assert!(render_review_queue(&[entry]).is_empty());
+    }
+}
@@ -0,0 +1,451 @@
+//! Expense trends for `/financials/expenses`.
+//!
+//! Monthly off-chain expenses stacked by category, a per-category drill-down by
+//! vendor (`?category=Hosting`), and the vendors whose spend moved most between the
+//! last two complete months, so creep like a hosting upgrade or extra contractor
+//! hours shows up without diffing CSVs.
+
+use chrono::{Months, NaiveDate};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::admin::{STYLE, escape_html};
+use super::sql_console::csv_field;
+use super::types::{Expense, ExpenseCategory};
+
+/// Vendors listed in the largest-movers table
+pub const MOVERS: usize = 10;
+
+/// Months shown in the vendor drill-down
+const DRILL_DOWN_MONTHS: usize = 12;
+
+/// Bar fill per category, in `ExpenseCategory::ALL` order
+const PALETTE: [&str; 8] = [
+    "#2563eb", "#d97706", "#7c3aed", "#059669", "#dc2626", "#0891b2", "#db2777", "#6b7280",
+];
+
+const CHART_HEIGHT: f64 = 220.0;
+const BAR_WIDTH: f64 = 18.0;
+const BAR_GAP: f64 = 6.0;
+const AXIS_HEIGHT: f64 = 20.0;
+
+/// One category's spend per month, aligned with `ExpenseTrends::months`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CategorySeries {
+    pub category: ExpenseCategory,
+    pub monthly_usd: Vec<f64>,
+    pub total_usd: f64,
+}
+
+/// One vendor's spend in one month
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VendorMonth {
+    pub month: String,
+    pub category: ExpenseCategory,
+    pub vendor: String,
+    pub amount_usd: f64,
+}
+
+/// A vendor's change in spend between the last two complete months
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Mover {
+    pub category: ExpenseCategory,
+    pub vendor: String,
+    pub previous_usd: f64,
+    pub latest_usd: f64,
+    pub change_usd: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExpenseTrends {
+    /// Every month from the first expense to the last, oldest first (gaps filled)
+    pub months: Vec<String>,
+    /// Categories with any spend, in `ExpenseCategory::ALL` order
+    pub categories: Vec<CategorySeries>,
+    /// Oldest month first, largest vendor first within a month
+    pub vendors: Vec<VendorMonth>,
+    /// The two complete months compared for `movers` (previous, latest)
+    pub compared: Option<(String, String)>,
+    /// Largest absolute change first
+    pub movers: Vec<Mover>,
+}
+
+fn next_month(month: &str) -> Option<String> {
+    let first = NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d").ok()?;
+    Some((first + Months::new(1)).format("%Y-%m").to_string())
+}
+
+fn palette(category: ExpenseCategory) -> &'static str {
+    let index = ExpenseCategory::ALL
+        .iter()
+        .position(|c| *c == category)
+        .unwrap_or(PALETTE.len() - 1);
+    PALETTE[index]
+}
+
+/// Roll expenses up by month, category and vendor. `current_month` (YYYY-MM) is still
+/// running, so movers compare the two months before it.
+pub fn compute(expenses: &[Expense], current_month: &str) -> ExpenseTrends {
+    let mut by_vendor: BTreeMap<(String, ExpenseCategory, String), f64> = BTreeMap::new();
+    for e in expenses {
+        let Some(month) = e.date.get(..7).filter(|m| m.as_bytes().get(4) == Some(&b'-')) else {
+            continue;
+        };
+        let vendor = match e.vendor.trim() {
+            "" => "(no vendor)",
+            v => v,
+        };
+        *by_vendor
+            .entry((month.to_string(), e.category, vendor.to_string()))
+            .or_default() += e.amount_usd;
+    }
+
+    let mut months = Vec::new();
+    if let (Some(first), Some(last)) = (by_vendor.keys().next(), by_vendor.keys().next_back()) {
+        let mut month = Some(first.0.clone());
+        while let Some(m) = month.filter(|m| *m <= last.0) {
+            month = next_month(&m);
+            months.push(m);
+        }
+    }
+    let index: BTreeMap<&str, usize> = months.iter().enumerate().map(|(i, m)| (m.as_str(), i)).collect();
+
+    let categories: Vec<CategorySeries> = ExpenseCategory::ALL
+        .into_iter()
+        .filter_map(|category| {
+            let mut monthly_usd = vec![0.0; months.len()];
+            for ((month, c, _), amount) in &by_vendor {
+                if *c == category
+                    && let Some(&i) = index.get(month.as_str())
+                {
+                    monthly_usd[i] += amount;
+                }
+            }
+            let total_usd: f64 = monthly_usd.iter().sum();
+            (total_usd != 0.0).then_some(CategorySeries {
+                category,
+                monthly_usd,
+                total_usd,
+            })
+        })
+        .collect();
+
+    let mut vendors: Vec<VendorMonth> = by_vendor
+        .iter()
+        .map(|((month, category, vendor), &amount_usd)| VendorMonth {
+            month: month.clone(),
+            category: *category,
+            vendor: vendor.clone(),
+            amount_usd,
+        })
+        .collect();
+    vendors.sort_by(|a, b| a.month.cmp(&b.month).then(b.amount_usd.total_cmp(&a.amount_usd)));
+
+    let complete: Vec<&String> = months.iter().filter(|m| m.as_str() < current_month).collect();
+    let compared = match complete.as_slice() {
+        [.., previous, latest] => Some(((*previous).clone(), (*latest).clone())),
+        _ => None,
+    };
+    let mut movers = Vec::new();
+    if let Some((previous, latest)) = &compared {
+        let mut pairs: BTreeMap<(ExpenseCategory, &str), (f64, f64)> = BTreeMap::new();
+        for ((month, category, vendor), amount) in &by_vendor {
+            let entry = pairs.entry((*category, vendor.as_str())).or_default();
+            if month == previous {
+                entry.0 += amount;
+            } else if month == latest {
+                entry.1 += amount;
+            }
+        }
+        movers = pairs
+            .into_iter()
+            .map(|((category, vendor), (previous_usd, latest_usd))| Mover {
+                category,
+                vendor: vendor.to_string(),
+                previous_usd,
+                latest_usd,
+                change_usd: latest_usd - previous_usd,
+            })
+            .filter(|m| m.change_usd.abs() >= 0.01)
+            .collect();
+        movers.sort_by(|a, b| b.change_usd.abs().total_cmp(&a.change_usd.abs()));
+        movers.truncate(MOVERS);
+    }
+
+    ExpenseTrends {
+        months,
+        categories,
+        vendors,
+        compared,
+        movers,
+    }
+}
+
+/// Vendor spend by month
+pub fn to_csv(trends: &ExpenseTrends) -> String {
+    let mut out = String::from("Month,Category,Vendor,Amount (USD)\n");
+    for v in &trends.vendors {
+        out.push_str(&format!(
+            "{},{},{},{:.2}\n",
+            v.month,
+            v.category.db_name(),
+            csv_field(&v.vendor),
+            v.amount_usd
+        ));
+    }
+    out
+}
+
+/// Category named in a `?category=` link (its `db_name`)
+pub fn parse_category(s: &str) -> Option<ExpenseCategory> {
+    ExpenseCategory::ALL
+        .into_iter()
+        .find(|c| c.db_name().eq_ignore_ascii_case(s.trim()))
+}
+
+fn money(v: f64) -> String {
+    if v < 0.0 {
+        format!("-${:.2}", -v)
+    } else {
+        format!("${:.2}", v)
+    }
+}
+
+/// Stacked monthly bars as inline SVG, one `<title>` tooltip per segment
+fn render_chart(trends: &ExpenseTrends) -> String {
+    let totals: Vec<f64> = (0..trends.months.len())
+        .map(|i| trends.categories.iter().map(|c| c.monthly_usd[i].max(0.0)).sum())
+        .collect();
+    let max = totals.iter().copied().fold(0.0, f64::max);
+    let scale = if max > 0.0 { CHART_HEIGHT / max } else { 0.0 };
+    let width = trends.months.len() as f64 * (BAR_WIDTH + BAR_GAP) + BAR_GAP;
+    let label_every = trends.months.len().div_ceil(12).max(1);
+
+    let mut svg = format!(
+        concat!(
+            "<svg viewBox='0 0 {:.0} {:.0}' width='100%' role='img' ",
+            "aria-label='Monthly expenses by category' style='max-height:320px'>"
+        ),
+        width,
+        CHART_HEIGHT + AXIS_HEIGHT
+    );
+    for (i, month) in trends.months.iter().enumerate() {
+        let x = BAR_GAP + i as f64 * (BAR_WIDTH + BAR_GAP);
+        let mut y = CHART_HEIGHT;
+        for series in &trends.categories {
+            let height = series.monthly_usd[i].max(0.0) * scale;
+            if height <= 0.0 {
+                continue;
+            }
+            y -= height;
+            svg.push_str(&format!(
+                "<rect x='{:.1}' y='{:.1}' width='{:.0}' height='{:.1}' fill='{}'><title>{} {}: {}</title></rect>",
+                x,
+                y,
+                BAR_WIDTH,
+                height,
+                palette(series.category),
+                escape_html(month),
+                series.category,
+                money(series.monthly_usd[i])
+            ));
+        }
+        if i % label_every == 0 {
+            svg.push_str(&format!(
+                "<text x='{:.1}' y='{:.0}' font-size='9' fill='currentColor'>{}</text>",
+                x,
+                CHART_HEIGHT + AXIS_HEIGHT - 6.0,
+                escape_html(month)
+            ));
+        }
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Vendors of one category over the latest months, largest total first
+fn render_drill_down(trends: &ExpenseTrends, category: ExpenseCategory) -> String {
+    let months = &trends.months[trends.months.len().saturating_sub(DRILL_DOWN_MONTHS)..];
+    let mut rows: BTreeMap<&str, Vec<f64>> = BTreeMap::new();
+    for v in trends.vendors.iter().filter(|v| v.category == category) {
+        if let Some(i) = months.iter().position(|m| *m == v.month) {
+            rows.entry(v.vendor.as_str()).or_insert_with(|| vec![0.0; months.len()])[i] += v.amount_usd;
+        }
+    }
+    let mut rows: Vec<(&str, Vec<f64>)> = rows.into_iter().collect();
+    rows.sort_by(|a, b| b.1.iter().sum::<f64>().total_cmp(&a.1.iter().sum::<f64>()));
+
+    let mut html = format!(
+        "<h2>{} by vendor</h2><p><a href='/financials/expenses'>&larr; all categories</a></p>",
+        category
+    );
+    if rows.is_empty() {
+        html.push_str("<p class='muted'>No spend in this category in the latest months.</p>");
+        return html;
+    }
+    html.push_str("<table><tr><th>Vendor</th>");
+    for m in months {
+        html.push_str(&format!("<th>{}</th>", escape_html(m)));
+    }
+    html.push_str("<th>Total</th></tr>");
+    for (vendor, amounts) in &rows {
+        html.push_str(&format!("<tr><td>{}</td>", escape_html(vendor)));
+        for amount in amounts {
+            if *amount == 0.0 {
+                html.push_str("<td class='muted'>-</td>");
+            } else {
+                html.push_str(&format!("<td>{:.2}</td>", amount));
+            }
+        }
+        html.push_str(&format!("<td>{}</td></tr>", money(amounts.iter().sum())));
+    }
+    html.push_str("</table>");
+    html
+}
+
+fn render_movers(trends: &ExpenseTrends) -> String {
+    let Some((previous, latest)) = &trends.compared else {
+        return "<p class='muted'>Movers need two complete months of expenses.</p>".to_string();
+    };
+    let mut html = format!(
+        "<h2>Largest movers, {} &rarr; {}</h2>",
+        escape_html(previous),
+        escape_html(latest)
+    );
+    if trends.movers.is_empty() {
+        html.push_str("<p class='muted'>No vendor's spend changed.</p>");
+        return html;
+    }
+    html.push_str(concat!(
+        "<table><tr><th>Vendor</th><th>Category</th><th>Previous</th>",
+        "<th>Latest</th><th>Change</th></tr>"
+    ));
+    for m in &trends.movers {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td class='{}'>{}{}</td></tr>",
+            escape_html(&m.vendor),
+            m.category,
+            money(m.previous_usd),
+            money(m.latest_usd),
+            // More spend is the bad direction here
+            if m.change_usd > 0.0 {
+                "status-failed"
+            } else {
+                "status-success"
+            },
+            if m.change_usd > 0.0 { "+" } else { "" },
+            money(m.change_usd)
+        ));
+    }
+    html.push_str("</table>");
+    html
+}
+
+/// Stacked chart with a category legend, then the drill-down or the movers
+pub fn render_page(trends: Option<&ExpenseTrends>, category: Option<ExpenseCategory>) -> String {
+    let body = match trends {
+        None => "<p class='status-failed'>Financial data unavailable.</p>".to_string(),
+        Some(trends) if trends.months.is_empty() => "<p class='muted'>No expenses recorded yet.</p>".to_string(),
+        Some(trends) => {
+            let total: f64 = trends.categories.iter().map(|c| c.total_usd).sum();
+            let mut html = format!(
+                concat!(
+                    "<p>{} months, {} total · ",
+                    "<a href='/financials/expenses?format=csv'>CSV</a> · ",
+                    "<a href='/financials/expenses?format=json'>JSON</a></p>"
+                ),
+                trends.months.len(),
+                money(total)
+            );
+            html.push_str(&render_chart(trends));
+            html.push_str("<p>");
+            for series in &trends.categories {
+                html.push_str(&format!(
+                    concat!(
+                        "<span style='display:inline-block;width:.8em;height:.8em;background:{}'></span> ",
+                        "<a href='/financials/expenses?category={}'>{}</a> {} &nbsp; "
+                    ),
+                    palette(series.category),
+                    series.category.db_name(),
+                    series.category,
+                    money(series.total_usd)
+                ));
+            }
+            html.push_str("</p>");
+            match category {
+                Some(category) => html.push_str(&render_drill_down(trends, category)),
+                None => html.push_str(&render_movers(trends)),
+            }
+            html
+        }
+    };
+
+    format!(
+        concat!(
+            "<!DOCTYPE html><html lang='en'><head><meta charset='utf-8'>",
+            "<meta name='viewport' content='width=device-width, initial-scale=1'>",
+            "<title>Block Parliament · Expense Trends</title>",
+            "<link rel='icon' type='image/png' href='/logo/owl-64.png'>",
+            "<style>{}</style></head><body>",
+            "<h1>Expense Trends</h1>",
+            "<p><a href='/financials/admin'>&larr; back to admin</a></p>",
+            "<p class='muted'>Off-chain expenses (including recurring ones) by month. ",
+            "Pick a category for its vendors.</p>",
+            "{}</body></html>"
+        ),
+        STYLE, body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expense(date: &str, vendor: &str, category: ExpenseCategory, amount_usd: f64) -> Expense {
+        Expense {
+            date: date.into(),
+            vendor: vendor.into(),
+            category,
+            description: String::new(),
+            amount_usd,
+            paid_with: "USD".into(),
+            invoice_id: None,
+        }
+    }
+
+    #[test]
+    fn stacks_categories_and_ranks_movers() {
+        let expenses = vec![
+            expense("2026-01-03", "Latitude", ExpenseCategory::Hosting, 1000.0),
+            expense("2026-01-20", "Alice", ExpenseCategory::Contractor, 300.0),
+            // February has no expenses but still gets a bar
+            expense("2026-03-03", "Latitude", ExpenseCategory::Hosting, 1500.0),
+            expense("2026-04-03", "Latitude", ExpenseCategory::Hosting, 1500.0),
+            expense("2026-04-10", "Alice", ExpenseCategory::Contractor, 900.0),
+            expense("2026-04-12", "GitHub", ExpenseCategory::Software, 4.0),
+            // The current month doesn't count toward movers
+            expense("2026-05-01", "Latitude", ExpenseCategory::Hosting, 5000.0),
+        ];
+
+        let trends = compute(&expenses, "2026-05");
+        assert_eq!(trends.months, ["2026-01", "2026-02", "2026-03", "2026-04", "2026-05"]);
+        let hosting = &trends.categories[0];
+        assert_eq!(hosting.category, ExpenseCategory::Hosting);
+        assert_eq!(hosting.monthly_usd, [1000.0, 0.0, 1500.0, 1500.0, 5000.0]);
+
+        assert_eq!(trends.compared, Some(("2026-03".to_string(), "2026-04".to_string())));
+        let movers: Vec<(&str, f64)> = trends
+            .movers
+            .iter()
+            .map(|m| (m.vendor.as_str(), m.change_usd))
+            .collect();
+        assert_eq!(movers, [("Alice", 900.0), ("GitHub", 4.0)]);
+
+        assert_eq!(to_csv(&trends).lines().nth(1), Some("2026-01,Hosting,Latitude,1000.00"));
+
+        let page = render_page(Some(&trends), None);
+        assert!(page.contains("<svg") && page.contains("Largest movers, 2026-03 &rarr; 2026-04"));
+        let drill = render_page(Some(&trends), parse_category("contractor"));
+        assert!(drill.contains("Contractor by vendor") && drill.contains("<td>Alice</td>"));
+    }
+}
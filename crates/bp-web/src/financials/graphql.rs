@@ -0,0 +1,248 @@
+//! GraphQL view of `cache.sqlite` for ad-hoc questions ("MEV by weekday", "expenses by
+//! vendor by quarter") without adding a REST route for each one.
+//!
+//! Served at `/financials/graphql` behind the same Basic Auth as `/financials`
+//! (a GET opens GraphiQL). Every table is read-only and reuses the queries in
+//! [`super::db`]; list fields take optional inclusive `from`/`to` date (YYYY-MM-DD)
+//! or epoch bounds, applied after loading.
+
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Result, Schema, SimpleObject};
+
+use super::db;
+use super::types::*;
+
+/// Deepest selection a query may nest
+const MAX_DEPTH: usize = 8;
+/// Upper bound on a query's field count
+const MAX_COMPLEXITY: usize = 500;
+
+pub type FinancialsSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Build the schema for the cache in `data_dir`
+pub fn schema(data_dir: &str) -> FinancialsSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(DataDir(data_dir.to_string()))
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .finish()
+}
+
+struct DataDir(String);
+
+async fn pool<'a>(ctx: &Context<'a>) -> Result<&'static sqlx::SqlitePool> {
+    Ok(db::init_cache(&ctx.data::<DataDir>()?.0).await?)
+}
+
+/// Whether an optional date falls within inclusive, optional `from`/`to` bounds.
+/// Undated rows only match when no bound is given.
+fn date_in_range(date: Option<&str>, from: Option<&str>, to: Option<&str>) -> bool {
+    if from.is_none() && to.is_none() {
+        return true;
+    }
+    let Some(date) = date.filter(|d| *d != "unknown") else {
+        return false;
+    };
+    // Compare on the day so full timestamps match a `to` date too
+    let day = date.get(..10).unwrap_or(date);
+    from.is_none_or(|f| day >= f) && to.is_none_or(|t| day <= t)
+}
+
+fn epoch_in_range(epoch: u64, from: Option<u64>, to: Option<u64>) -> bool {
+    from.is_none_or(|f| epoch >= f) && to.is_none_or(|t| epoch <= t)
+}
+
+/// One day's SOL/USD close
+#[derive(SimpleObject)]
+pub struct Price {
+    pub date: String,
+    pub usd_price: f64,
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Staking commission per epoch
+    async fn epoch_rewards(
+        &self,
+        ctx: &Context<'_>,
+        from_epoch: Option<u64>,
+        to_epoch: Option<u64>,
+        from: Option<String>,
+        to: Option<String>,
+    ) -> Result<Vec<EpochReward>> {
+        let rows = db::get_epoch_rewards(pool(ctx).await?).await?;
+        Ok(rows
+            .into_iter()
+            .filter(|r| epoch_in_range(r.epoch, from_epoch, to_epoch))
+            .filter(|r| date_in_range(r.date.as_deref(), from.as_deref(), to.as_deref()))
+            .collect())
+    }
+
+    /// Block production fees per epoch
+    async fn leader_fees(
+        &self,
+        ctx: &Context<'_>,
+        from_epoch: Option<u64>,
+        to_epoch: Option<u64>,
+        from: Option<String>,
+        to: Option<String>,
+    ) -> Result<Vec<EpochLeaderFees>> {
+        let rows = db::get_leader_fees(pool(ctx).await?).await?;
+        Ok(rows
+            .into_iter()
+            .filter(|r| epoch_in_range(r.epoch, from_epoch, to_epoch))
+            .filter(|r| date_in_range(r.date.as_deref(), from.as_deref(), to.as_deref()))
+            .collect())
+    }
+
+    /// Jito MEV commission per epoch
+    async fn mev_claims(
+        &self,
+        ctx: &Context<'_>,
+        from_epoch: Option<u64>,
+        to_epoch: Option<u64>,
+        from: Option<String>,
+        to: Option<String>,
+    ) -> Result<Vec<MevClaim>> {
+        let rows = db::get_mev_claims(pool(ctx).await?).await?;
+        Ok(rows
+            .into_iter()
+            .filter(|r| epoch_in_range(r.epoch, from_epoch, to_epoch))
+            .filter(|r| date_in_range(r.date.as_deref(), from.as_deref(), to.as_deref()))
+            .collect())
+    }
+
+    /// Jito BAM rewards (jitoSOL) per claim
+    async fn bam_claims(
+        &self,
+        ctx: &Context<'_>,
+        from_epoch: Option<u64>,
+        to_epoch: Option<u64>,
+        from: Option<String>,
+        to: Option<String>,
+    ) -> Result<Vec<BamClaim>> {
+        let rows = db::get_bam_claims(pool(ctx).await?).await?;
+        Ok(rows
+            .into_iter()
+            .filter(|r| epoch_in_range(r.epoch, from_epoch, to_epoch))
+            .filter(|r| date_in_range(r.date.as_deref(), from.as_deref(), to.as_deref()))
+            .collect())
+    }
+
+    /// Income per source and calendar month, in SOL
+    async fn monthly_revenue(&self, ctx: &Context<'_>) -> Result<Vec<MonthlyRevenue>> {
+        Ok(db::get_monthly_revenue(pool(ctx).await?).await?)
+    }
+
+    /// Vote transaction costs per epoch
+    async fn vote_costs(
+        &self,
+        ctx: &Context<'_>,
+        from_epoch: Option<u64>,
+        to_epoch: Option<u64>,
+        from: Option<String>,
+        to: Option<String>,
+    ) -> Result<Vec<EpochVoteCost>> {
+        let rows = db::get_vote_costs(pool(ctx).await?).await?;
+        Ok(rows
+            .into_iter()
+            .filter(|r| epoch_in_range(r.epoch, from_epoch, to_epoch))
+            .filter(|r| date_in_range(r.date.as_deref(), from.as_deref(), to.as_deref()))
+            .collect())
+    }
+
+    /// DoubleZero fee liability per epoch
+    async fn doublezero_fees(
+        &self,
+        ctx: &Context<'_>,
+        from_epoch: Option<u64>,
+        to_epoch: Option<u64>,
+        from: Option<String>,
+        to: Option<String>,
+    ) -> Result<Vec<DoubleZeroFee>> {
+        let rows = db::get_doublezero_fees(pool(ctx).await?).await?;
+        Ok(rows
+            .into_iter()
+            .filter(|r| epoch_in_range(r.epoch, from_epoch, to_epoch))
+            .filter(|r| date_in_range(r.date.as_deref(), from.as_deref(), to.as_deref()))
+            .collect())
+    }
+
+    /// Off-chain expenses, optionally for one vendor (case-insensitive) or category
+    async fn expenses(
+        &self,
+        ctx: &Context<'_>,
+        from: Option<String>,
+        to: Option<String>,
+        vendor: Option<String>,
+        category: Option<ExpenseCategory>,
+    ) -> Result<Vec<Expense>> {
+        let rows = db::get_expenses(pool(ctx).await?).await?;
+        Ok(rows
+            .into_iter()
+            .filter(|e| date_in_range(Some(&e.date), from.as_deref(), to.as_deref()))
+            .filter(|e| vendor.as_ref().is_none_or(|v| e.vendor.eq_ignore_ascii_case(v)))
+            .filter(|e| category.is_none_or(|c| e.category == c))
+            .collect())
+    }
+
+    /// Recurring expense templates (not expanded into months)
+    async fn recurring_expenses(&self, ctx: &Context<'_>) -> Result<Vec<RecurringExpense>> {
+        Ok(db::get_recurring_expenses(pool(ctx).await?).await?)
+    }
+
+    /// SOL transfers to and from tracked accounts
+    async fn sol_transfers(
+        &self,
+        ctx: &Context<'_>,
+        from: Option<String>,
+        to: Option<String>,
+    ) -> Result<Vec<SolTransfer>> {
+        let rows = db::get_sol_transfers(pool(ctx).await?).await?;
+        Ok(rows
+            .into_iter()
+            .filter(|t| date_in_range(t.date.as_deref(), from.as_deref(), to.as_deref()))
+            .collect())
+    }
+
+    /// Daily SOL/USD prices, oldest first
+    async fn prices(&self, ctx: &Context<'_>, from: Option<String>, to: Option<String>) -> Result<Vec<Price>> {
+        let prices = db::get_prices(pool(ctx).await?).await?;
+        let mut rows: Vec<Price> = prices
+            .into_iter()
+            .filter(|(date, _)| date_in_range(Some(date), from.as_deref(), to.as_deref()))
+            .map(|(date, usd_price)| Price { date, usd_price })
+            .collect();
+        rows.sort_by(|a, b| a.date.cmp(&b.date));
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_bounds_are_inclusive_and_skip_undated_rows() {
+        assert!(date_in_range(None, None, None));
+        assert!(date_in_range(
+            Some("2026-03-31"),
+            Some("2026-01-01"),
+            Some("2026-03-31")
+        ));
+        assert!(date_in_range(Some("2026-03-31T12:00:00Z"), None, Some("2026-03-31")));
+        assert!(!date_in_range(Some("2026-04-01"), None, Some("2026-03-31")));
+        assert!(!date_in_range(None, Some("2026-01-01"), None));
+        assert!(!date_in_range(Some("unknown"), Some("2026-01-01"), None));
+    }
+
+    #[test]
+    fn schema_exposes_the_cache_tables() {
+        let sdl = schema("/nonexistent").sdl();
+        for field in ["epochRewards(", "mevClaims(", "expenses(", "solTransfers(", "prices("] {
+            assert!(sdl.contains(field), "missing {field}");
+        }
+        assert!(sdl.contains("enum ExpenseCategory"));
+    }
+}
@@ -0,0 +1,162 @@
+//! Streaming CSV downloads of the cached ledgers (`/financials/ledgers/{name}.csv`).
+//!
+//! The site runs on a small VPS, so these never hold a whole ledger in memory: rows
+//! are read from an SQLite cursor on a dedicated read-only connection and written to
+//! the response in chunks as they arrive (chunked transfer encoding). A bounded channel
+//! between the reader task and the response applies backpressure, so a slow client
+//! slows the cursor rather than piling up rows, and a client that goes away stops it.
+
+use anyhow::{Context, Result};
+use axum::body::{Body, Bytes};
+use futures::TryStreamExt;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{Column, ConnectOptions, Executor, Row, Statement};
+
+use super::sql_console::{cell, csv_field, display};
+
+/// Bytes of CSV collected before a chunk is sent
+const CHUNK_BYTES: usize = 16 * 1024;
+
+/// Chunks buffered between the cursor and the response
+const CHANNEL_CHUNKS: usize = 4;
+
+/// A downloadable ledger and the query that produces it
+pub struct Ledger {
+    /// URL name, `/financials/ledgers/{name}.csv`
+    pub name: &'static str,
+    pub label: &'static str,
+    sql: &'static str,
+}
+
+/// Every ledger, in the order the accountant page lists them. Epoch-based ledgers
+/// carry the cached SOL/USD price of their date.
+pub const LEDGERS: &[Ledger] = &[
+    Ledger {
+        name: "rewards",
+        label: "Commission rewards",
+        sql: "SELECT r.epoch, r.date, r.commission, r.amount_sol, p.usd_price
+              FROM epoch_rewards r LEFT JOIN prices p ON p.date = r.date ORDER BY r.epoch",
+    },
+    Ledger {
+        name: "leader_fees",
+        label: "Leader fees",
+        sql: "SELECT f.epoch, f.date, f.blocks_produced, f.skipped_slots, f.total_fees_sol, p.usd_price
+              FROM leader_fees f LEFT JOIN prices p ON p.date = f.date ORDER BY f.epoch",
+    },
+    Ledger {
+        name: "mev_claims",
+        label: "MEV claims",
+        sql: "SELECT m.epoch, m.date, m.total_tips_lamports, m.commission_lamports, m.amount_sol, p.usd_price
+              FROM mev_claims m LEFT JOIN prices p ON p.date = m.date ORDER BY m.epoch",
+    },
+    Ledger {
+        name: "bam_claims",
+        label: "BAM claims",
+        sql: "SELECT b.epoch, b.date, b.amount_jitosol_lamports, b.jitosol_sol_rate, b.amount_sol_equivalent,
+                     b.tx_signature, p.usd_price
+              FROM bam_claims b LEFT JOIN prices p ON p.date = b.date ORDER BY b.epoch",
+    },
+    Ledger {
+        name: "vote_costs",
+        label: "Vote costs",
+        sql: "SELECT v.epoch, v.date, v.vote_count, v.total_fee_sol, v.source, p.usd_price
+              FROM vote_costs v LEFT JOIN prices p ON p.date = v.date ORDER BY v.epoch",
+    },
+    Ledger {
+        name: "doublezero_fees",
+        label: "DoubleZero fees",
+        sql: "SELECT d.epoch, d.date, d.fee_base_lamports, d.fee_rate_bps, d.liability_sol, d.is_estimate, p.usd_price
+              FROM doublezero_fees d LEFT JOIN prices p ON p.date = d.date ORDER BY d.epoch",
+    },
+    Ledger {
+        name: "transfers",
+        label: "SOL transfers",
+        sql: "SELECT t.date, t.signature, t.from_address, t.from_label, t.from_category, t.to_address, t.to_label,
+                     t.to_category, t.amount_sol, t.memo, t.program, p.usd_price
+              FROM sol_transfers t LEFT JOIN prices p ON p.date = t.date ORDER BY t.slot",
+    },
+    Ledger {
+        name: "expenses",
+        label: "Expenses",
+        sql: "SELECT * FROM expenses ORDER BY date, id",
+    },
+    Ledger {
+        name: "prices",
+        label: "Daily SOL prices",
+        sql: "SELECT date, usd_price FROM prices ORDER BY date",
+    },
+];
+
+/// Ledger for a URL segment (`rewards.csv`)
+pub fn find(segment: &str) -> Option<&'static Ledger> {
+    let name = segment.strip_suffix(".csv")?;
+    LEDGERS.iter().find(|l| l.name == name)
+}
+
+/// Stream `ledger` as CSV. The query is prepared before returning, so a missing table
+/// is an error here rather than a truncated download. `guard` (e.g. a download slot)
+/// is held until the last row is sent.
+pub async fn stream(data_dir: &str, ledger: &'static Ledger, guard: impl Send + 'static) -> Result<Body> {
+    let path = super::db::cache_path(data_dir);
+    let mut conn = SqliteConnectOptions::new()
+        .filename(&path)
+        .read_only(true)
+        .connect()
+        .await
+        .with_context(|| format!("Failed to open cache at {}", path.display()))?;
+    let statement = conn
+        .prepare(ledger.sql)
+        .await
+        .with_context(|| format!("The {} ledger is not available", ledger.name))?;
+    let header: Vec<String> = statement.columns().iter().map(|c| csv_field(c.name())).collect();
+
+    let (tx, rx) = tokio::sync::mpsc::channel::<std::io::Result<Bytes>>(CHANNEL_CHUNKS);
+    tokio::spawn(async move {
+        let _guard = guard;
+        let mut buf = header.join(",");
+        buf.push('\n');
+
+        let mut rows = sqlx::query(ledger.sql).fetch(&mut conn);
+        loop {
+            match rows.try_next().await {
+                Ok(Some(row)) => {
+                    let fields: Vec<String> = (0..row.len()).map(|i| csv_field(&display(&cell(&row, i)))).collect();
+                    buf.push_str(&fields.join(","));
+                    buf.push('\n');
+                    if buf.len() >= CHUNK_BYTES && tx.send(Ok(Bytes::from(std::mem::take(&mut buf)))).await.is_err() {
+                        return; // client went away
+                    }
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    // Abort the response so a partial file isn't mistaken for the ledger
+                    eprintln!("[financials] {} ledger download failed: {}", ledger.name, e);
+                    let _ = tx.send(Err(std::io::Error::other(e))).await;
+                    return;
+                }
+            }
+        }
+        if !buf.is_empty() {
+            let _ = tx.send(Ok(Bytes::from(buf))).await;
+        }
+    });
+
+    let chunks = futures::stream::unfold(rx, |mut rx| async move { rx.recv().await.map(|chunk| (chunk, rx)) });
+    Ok(Body::from_stream(chunks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_ledgers_by_csv_name() {
+        assert_eq!(find("rewards.csv").map(|l| l.name), Some("rewards"));
+        assert!(find("rewards").is_none());
+        assert!(find("sqlite_master.csv").is_none());
+        let mut names: Vec<&str> = LEDGERS.iter().map(|l| l.name).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), LEDGERS.len());
+    }
+}
@@ -1,22 +1,49 @@
 //! Dynamic financial report generation for `/financials`.
 //!
 //! Queries `cache.sqlite` at request time, builds operating + tax timelines,
-//! and injects them into the self-contained HTML template.
+//! and injects them into the self-contained HTML template. Only the most recent
+//! months are embedded in the page; older months are served in month-sized chunks
+//! by `/financials/timeline.json` and fetched by the page in the background.
+//! The rendered page is stored pre-compressed alongside the cached timelines.
 
+pub mod accountant;
+pub mod admin;
+pub mod anomalies;
 pub mod categorize;
 pub mod config;
 pub mod db;
+pub mod delegators;
+pub mod doublezero;
+pub mod downloads;
+pub mod epoch_pnl;
+pub mod expense_admin;
+pub mod expense_trends;
+pub mod graphql;
+pub mod ledger_csv;
+pub mod precompressed;
+pub mod sql_console;
 pub mod timeline;
 pub mod types;
+pub mod unit_economics;
+pub mod vote_topup;
+pub mod withdrawal_plan;
 
 use anyhow::{Context, Result};
 use chrono::{NaiveDate, Utc};
+use serde::Serialize;
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{Duration, Instant};
 
 use self::config::ValidatorConfig;
+use self::precompressed::PrecompressedHtml;
 use self::types::*;
+use self::withdrawal_plan::PlanningInputs;
+use report_template::{
+    HistoryCursors, ReportContext, ReportTheme, RevenueAnomaly, RevenueStats, UnitEconomics, detect_anomalies,
+};
 
-/// The HTML template with `__TIMELINE_JSON__`, `__TAX_TIMELINE_JSON__`,
-/// and `__TAX_YEAR__` placeholders (embedded at compile time).
+/// The report template, rendered with `report_template::render` (embedded at
+/// compile time; `[theme] template_dir` can replace it).
 static TEMPLATE: &str = include_str!("template.html");
 
 /// Fallback HTML when cache.sqlite doesn't exist yet.
@@ -28,17 +55,284 @@ static FALLBACK: &str = concat!(
     "</body></html>"
 );
 
+/// Months of each timeline embedded in the initial page
+const INITIAL_MONTHS: usize = 3;
+
+/// Months per `/financials/timeline.json` chunk when the request doesn't say
+pub const DEFAULT_CHUNK_MONTHS: usize = 6;
+
+/// Upper bound on months per chunk request
+pub const MAX_CHUNK_MONTHS: usize = 24;
+
+/// How long built timelines (and the page rendered from them) are reused, so the
+/// page and its chunk requests don't each rebuild everything from cache.sqlite
+const TIMELINE_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Both timelines, built together from one snapshot of cache.sqlite
+struct Timelines {
+    operating: Vec<TimelineEvent>,
+    tax: Vec<TimelineEvent>,
+    /// Branding and template override, read with the snapshot
+    theme: ReportTheme,
+    /// Remaining seed capital and today's price for the withdrawal planner
+    planning: PlanningInputs,
+    /// Per-epoch profitability for `/financials/epochs`
+    epoch_pnl: Arc<Vec<epoch_pnl::EpochPnl>>,
+    /// KPI row on the dashboard
+    unit_economics: UnitEconomics,
+    /// Revenue volatility panel on the dashboard
+    revenue_stats: RevenueStats,
+    /// Per-epoch revenue anomalies for the admin page and webhook alerts
+    anomalies: Arc<Vec<RevenueAnomaly>>,
+    /// Delegator concentration and churn for `/financials/delegators`
+    delegators: Arc<delegators::DelegatorReport>,
+    /// USD-only rollup for `/financials?view=accountant`
+    accountant: Arc<accountant::AccountantReport>,
+    /// Monthly expenses by category and vendor for `/financials/expenses`
+    expense_trends: Arc<expense_trends::ExpenseTrends>,
+    /// The page rendered from this snapshot, compressed on first request
+    page: tokio::sync::OnceCell<Arc<PrecompressedHtml>>,
+}
+
+static FALLBACK_PAGE: OnceLock<Arc<PrecompressedHtml>> = OnceLock::new();
+
+struct CachedTimelines {
+    data_dir: String,
+    built_at: Instant,
+    timelines: Arc<Timelines>,
+}
+
+static TIMELINE_CACHE: OnceLock<RwLock<Option<CachedTimelines>>> = OnceLock::new();
+
+/// Held while timelines are built, so concurrent requests that find the cache stale
+/// wait for that one build instead of each loading cache.sqlite in parallel
+static TIMELINE_BUILD: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Which timeline a chunk request is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimelineView {
+    Operating,
+    Tax,
+}
+
+impl TimelineView {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "operating" => Some(Self::Operating),
+            "tax" => Some(Self::Tax),
+            _ => None,
+        }
+    }
+}
+
+/// A run of whole months from one timeline, oldest first.
+///
+/// `next_before` is the cursor for the next (older) chunk, or `None` once the
+/// start of the timeline has been reached.
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineChunk {
+    pub events: Vec<TimelineEvent>,
+    pub next_before: Option<String>,
+}
+
 /// Generate the full HTML report dynamically from cache.sqlite.
 ///
-/// Returns the rendered HTML string or the fallback if the DB isn't available.
-pub async fn generate_report(data_dir: &str) -> String {
+/// Returns the rendered page (in every stored encoding) or the fallback if the DB
+/// isn't available. The page is rendered and compressed once per timeline snapshot.
+pub async fn generate_report(data_dir: &str) -> Arc<PrecompressedHtml> {
     match try_generate(data_dir).await {
-        Ok(html) => html,
+        Ok(page) => page,
         Err(e) => {
             eprintln!("[financials] Error generating report: {:#}", e);
-            FALLBACK.to_string()
+            Arc::clone(FALLBACK_PAGE.get_or_init(|| Arc::new(PrecompressedHtml::new(FALLBACK.to_string()))))
+        }
+    }
+}
+
+/// Monthly income by source for the public revenue chart, tagged with the cache
+/// data version it was computed from.
+#[derive(Debug, Clone)]
+pub struct RevenueComposition {
+    /// `db::get_data_version`; None when no ingestion run has been recorded
+    pub version: Option<String>,
+    /// Months from the business start, oldest first
+    pub months: Vec<MonthlyRevenue>,
+}
+
+static REVENUE_CACHE: OnceLock<RwLock<Option<Arc<RevenueComposition>>>> = OnceLock::new();
+
+/// Drop the cached revenue composition so the next request recomputes it (after a
+/// config reload, which can move the business start month without a new data version).
+pub fn invalidate_revenue() {
+    if let Some(cache) = REVENUE_CACHE.get()
+        && let Ok(mut guard) = cache.write()
+    {
+        *guard = None;
+    }
+}
+
+/// Current cache data version (see `db::get_data_version`).
+pub async fn data_version(data_dir: &str) -> Result<Option<String>> {
+    let pool = db::init_cache(data_dir).await?;
+    db::get_data_version(pool).await
+}
+
+/// Monthly revenue composition, recomputed only when the cache data version changes.
+pub async fn revenue_composition(data_dir: &str) -> Result<Arc<RevenueComposition>> {
+    let pool = db::init_cache(data_dir).await?;
+    let version = db::get_data_version(pool).await?;
+
+    let cache = REVENUE_CACHE.get_or_init(|| RwLock::new(None));
+    if version.is_some()
+        && let Ok(guard) = cache.read()
+        && let Some(cached) = guard.as_ref()
+        && cached.version == version
+    {
+        return Ok(Arc::clone(cached));
+    }
+
+    let config_path = std::path::Path::new(data_dir).join("config.toml");
+    let start_month = ValidatorConfig::load(&config_path)?.business_start_month();
+    let mut months = db::get_monthly_revenue(pool).await?;
+    months.retain(|m| m.month >= start_month);
+
+    let composition = Arc::new(RevenueComposition { version, months });
+    if let Ok(mut guard) = cache.write() {
+        *guard = Some(Arc::clone(&composition));
+    }
+    Ok(composition)
+}
+
+/// Serve one chunk of a timeline: up to `months` whole months ending just before
+/// the month `before` (or the latest months when `before` is `None`).
+pub async fn timeline_chunk(
+    data_dir: &str,
+    view: TimelineView,
+    before: Option<&str>,
+    months: usize,
+) -> Result<TimelineChunk> {
+    let timelines = load_timelines(data_dir).await?;
+    let events = match view {
+        TimelineView::Operating => &timelines.operating,
+        TimelineView::Tax => &timelines.tax,
+    };
+    Ok(month_chunk(events, before, months.clamp(1, MAX_CHUNK_MONTHS)))
+}
+
+/// Withdrawal planner inputs from the current timeline snapshot.
+pub async fn planning_inputs(data_dir: &str) -> Result<PlanningInputs> {
+    Ok(load_timelines(data_dir).await?.planning)
+}
+
+/// Per-epoch profitability from the current snapshot
+pub async fn epoch_pnl(data_dir: &str) -> Result<Arc<Vec<epoch_pnl::EpochPnl>>> {
+    Ok(Arc::clone(&load_timelines(data_dir).await?.epoch_pnl))
+}
+
+/// Per-epoch revenue anomalies from the current snapshot, oldest first
+pub async fn revenue_anomalies(data_dir: &str) -> Result<Arc<Vec<RevenueAnomaly>>> {
+    Ok(Arc::clone(&load_timelines(data_dir).await?.anomalies))
+}
+
+/// Delegator concentration and churn from the current snapshot
+pub async fn delegators(data_dir: &str) -> Result<Arc<delegators::DelegatorReport>> {
+    Ok(Arc::clone(&load_timelines(data_dir).await?.delegators))
+}
+
+/// Accountant rollup from the current snapshot
+pub async fn accountant(data_dir: &str) -> Result<Arc<accountant::AccountantReport>> {
+    Ok(Arc::clone(&load_timelines(data_dir).await?.accountant))
+}
+
+/// Expense trends from the current snapshot
+pub async fn expense_trends(data_dir: &str) -> Result<Arc<expense_trends::ExpenseTrends>> {
+    Ok(Arc::clone(&load_timelines(data_dir).await?.expense_trends))
+}
+
+/// Month key exactly as the page groups events (first 7 chars of the date)
+fn event_month(ev: &TimelineEvent) -> &str {
+    ev.date.get(..7).unwrap_or(&ev.date)
+}
+
+/// Slice whole months out of a sorted timeline. Months are taken in timeline
+/// order rather than compared as strings so undated ("unknown") events stay at the
+/// front. An unknown `before` cursor yields an empty chunk.
+fn month_chunk(events: &[TimelineEvent], before: Option<&str>, months: usize) -> TimelineChunk {
+    // Start index of each month run
+    let mut starts: Vec<usize> = Vec::new();
+    for (i, ev) in events.iter().enumerate() {
+        if i == 0 || event_month(&events[i - 1]) != event_month(ev) {
+            starts.push(i);
         }
     }
+
+    let end_group = match before {
+        None => starts.len(),
+        Some(month) => match starts.iter().position(|&i| event_month(&events[i]) == month) {
+            Some(group) => group,
+            None => {
+                return TimelineChunk {
+                    events: Vec::new(),
+                    next_before: None,
+                };
+            }
+        },
+    };
+    let start_group = end_group.saturating_sub(months);
+    if start_group == end_group {
+        return TimelineChunk {
+            events: Vec::new(),
+            next_before: None,
+        };
+    }
+
+    let start = starts[start_group];
+    let end = starts.get(end_group).copied().unwrap_or(events.len());
+    TimelineChunk {
+        events: events[start..end].to_vec(),
+        next_before: (start_group > 0).then(|| event_month(&events[start]).to_string()),
+    }
+}
+
+/// Drop the cached timelines so the next request rebuilds them (after bp-web itself
+/// writes to cache.sqlite, rather than waiting out `TIMELINE_CACHE_TTL`).
+pub fn invalidate_timelines() {
+    if let Some(cache) = TIMELINE_CACHE.get()
+        && let Ok(mut guard) = cache.write()
+    {
+        *guard = None;
+    }
+}
+
+fn cached_timelines(data_dir: &str) -> Option<Arc<Timelines>> {
+    let guard = TIMELINE_CACHE.get()?.read().ok()?;
+    let cached = guard.as_ref()?;
+    (cached.data_dir == data_dir && cached.built_at.elapsed() < TIMELINE_CACHE_TTL)
+        .then(|| Arc::clone(&cached.timelines))
+}
+
+/// Built timelines for `data_dir`, reused for `TIMELINE_CACHE_TTL`.
+async fn load_timelines(data_dir: &str) -> Result<Arc<Timelines>> {
+    if let Some(timelines) = cached_timelines(data_dir) {
+        return Ok(timelines);
+    }
+
+    // Single flight: whoever waited on the lock finds the build that held it
+    let _building = TIMELINE_BUILD.lock().await;
+    if let Some(timelines) = cached_timelines(data_dir) {
+        return Ok(timelines);
+    }
+
+    let cache = TIMELINE_CACHE.get_or_init(|| RwLock::new(None));
+    let timelines = Arc::new(build_timelines(data_dir).await?);
+    if let Ok(mut guard) = cache.write() {
+        *guard = Some(CachedTimelines {
+            data_dir: data_dir.to_string(),
+            built_at: Instant::now(),
+            timelines: Arc::clone(&timelines),
+        });
+    }
+    Ok(timelines)
 }
 
 fn within_actual_window(date: &str, cutoff: NaiveDate, today: NaiveDate) -> bool {
@@ -55,7 +349,43 @@ fn month_key_from_date(date: &str) -> Option<String> {
     }
 }
 
-async fn try_generate(data_dir: &str) -> Result<String> {
+async fn try_generate(data_dir: &str) -> Result<Arc<PrecompressedHtml>> {
+    let timelines = load_timelines(data_dir).await?;
+    let page = timelines
+        .page
+        .get_or_try_init(|| async {
+            let html = render_page(&timelines)?;
+            let page = tokio::task::spawn_blocking(move || PrecompressedHtml::new(html))
+                .await
+                .context("Failed to compress report")?;
+            Ok::<_, anyhow::Error>(Arc::new(page))
+        })
+        .await?;
+    Ok(Arc::clone(page))
+}
+
+fn render_page(timelines: &Timelines) -> Result<String> {
+    // ── Embed the latest months; the page pages in the rest ─────────────
+    let operating = month_chunk(&timelines.operating, None, INITIAL_MONTHS);
+    let tax = month_chunk(&timelines.tax, None, INITIAL_MONTHS);
+
+    let context = ReportContext {
+        timeline: &operating.events,
+        tax_timeline: &tax.events,
+        history: Some(HistoryCursors {
+            operating: operating.next_before.as_deref(),
+            tax: tax.next_before.as_deref(),
+        }),
+        tax_year: None,
+        timezone: None,
+        unit_economics: Some(&timelines.unit_economics),
+        revenue_stats: (!timelines.revenue_stats.is_empty()).then_some(&timelines.revenue_stats),
+        metadata: None,
+    };
+    report_template::render(TEMPLATE, &timelines.theme, &context)
+}
+
+async fn build_timelines(data_dir: &str) -> Result<Timelines> {
     // ── Load config ─────────────────────────────────────────────────────
     let config_path = std::path::Path::new(data_dir).join("config.toml");
     let config = ValidatorConfig::load(&config_path)?;
@@ -75,6 +405,9 @@ async fn try_generate(data_dir: &str) -> Result<String> {
         recurring_expenses,
         prices,
         mut transfers,
+        epoch_performance,
+        delegator_stake,
+        withdrawal_splits,
     ) = tokio::try_join!(
         db::get_epoch_rewards(pool),
         db::get_leader_fees(pool),
@@ -86,6 +419,9 @@ async fn try_generate(data_dir: &str) -> Result<String> {
         db::get_recurring_expenses(pool),
         db::get_prices(pool),
         db::get_sol_transfers(pool),
+        db::get_epoch_performance(pool),
+        db::get_delegator_stake(pool),
+        db::get_withdrawal_splits(pool),
     )
     .context("Failed to query cache.sqlite")?;
 
@@ -178,7 +514,11 @@ async fn try_generate(data_dir: &str) -> Result<String> {
     all_expenses.retain(|e| within_actual_window(&e.date, cutoff, today));
 
     // ── Categorize transfers ────────────────────────────────────────────
-    let categorized = categorize::categorize_transfers(&transfers, &config);
+    let mut categorized = categorize::categorize_transfers(&transfers, &config);
+    categorized.splits = withdrawal_splits;
+
+    // Business shares of split withdrawals are expenses (the rest is an owner draw)
+    all_expenses.extend(timeline::split_expenses(&categorized, &prices));
 
     // ── Build report data bundle ────────────────────────────────────────
     let report_data = ReportData {
@@ -194,22 +534,101 @@ async fn try_generate(data_dir: &str) -> Result<String> {
         sfdp_acceptance_date: config.sfdp_acceptance_date.clone(),
     };
 
+    let (sol_price_usd, price_source) = get_price_with_source(&prices, &today.format("%Y-%m-%d").to_string());
+    let planning = PlanningInputs {
+        remaining_capital_sol: timeline::remaining_seed_capital(&report_data, &config),
+        sol_price_usd,
+        price_source,
+        default_tax_rate: config.estimated_tax_rate,
+    };
+
     // ── Build timelines ─────────────────────────────────────────────────
     let operating = timeline::build_timeline(&report_data);
-    let tax = timeline::build_tax_timeline(&report_data, &config);
+    let accountant = accountant::compute(&operating, &all_expenses);
+    let epoch_pnl = epoch_pnl::compute(&report_data);
+    let revenue_epochs = epoch_pnl::revenue_epochs(&epoch_pnl);
+    let expense_trends = expense_trends::compute(&all_expenses, &today.format("%Y-%m").to_string());
+    Ok(Timelines {
+        operating,
+        tax: timeline::build_tax_timeline(&report_data, &config),
+        theme: config.theme.load().unwrap_or_else(|e| {
+            eprintln!("[financials] Ignoring [theme]: {:#}", e);
+            ReportTheme::default()
+        }),
+        planning,
+        revenue_stats: RevenueStats::from_epochs(&revenue_epochs),
+        anomalies: Arc::new(detect_anomalies(&revenue_epochs, &config.anomalies)),
+        epoch_pnl: Arc::new(epoch_pnl),
+        unit_economics: unit_economics::compute(&report_data, &epoch_performance),
+        delegators: Arc::new(delegators::compute(&delegator_stake)),
+        accountant: Arc::new(accountant),
+        expense_trends: Arc::new(expense_trends),
+        page: tokio::sync::OnceCell::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(date: &str) -> TimelineEvent {
+        TimelineEvent {
+            date: date.to_string(),
+            epoch: None,
+            event_type: "commission",
+            label: String::new(),
+            sublabel: None,
+            amount_sol: 0.0,
+            amount_usd: 0.0,
+            price_source: None,
+            signature: None,
+            source_id: String::new(),
+            detail: EventDetail::default(),
+            cumulative_profit_usd: 0.0,
+            cumulative_revenue_usd: 0.0,
+            cumulative_expenses_usd: 0.0,
+            is_pnl: true,
+        }
+    }
+
+    fn dates(chunk: &TimelineChunk) -> Vec<&str> {
+        chunk.events.iter().map(|e| e.date.as_str()).collect()
+    }
 
-    // ── Serialize & inject into template ────────────────────────────────
-    let timeline_json = serde_json::to_string(&operating)?;
-    let tax_timeline_json = serde_json::to_string(&tax)?;
+    #[test]
+    fn chunks_walk_back_by_whole_months() {
+        let events: Vec<TimelineEvent> = [
+            "unknown",
+            "2025-11-20",
+            "2025-12-01",
+            "2025-12-31",
+            "2026-01-05",
+            "2026-02-01",
+        ]
+        .into_iter()
+        .map(event)
+        .collect();
 
-    // Escape "</script>" inside JSON strings to prevent premature script close
-    let timeline_json = timeline_json.replace("</", r"<\/");
-    let tax_timeline_json = tax_timeline_json.replace("</", r"<\/");
+        let latest = month_chunk(&events, None, 2);
+        assert_eq!(dates(&latest), ["2026-01-05", "2026-02-01"]);
+        assert_eq!(latest.next_before.as_deref(), Some("2026-01"));
 
-    let html = TEMPLATE
-        .replacen("__TIMELINE_JSON__", &timeline_json, 1)
-        .replacen("__TAX_TIMELINE_JSON__", &tax_timeline_json, 1)
-        .replacen("__TAX_YEAR__", "null", 1);
+        let older = month_chunk(&events, latest.next_before.as_deref(), 2);
+        assert_eq!(dates(&older), ["2025-11-20", "2025-12-01", "2025-12-31"]);
+        assert_eq!(older.next_before.as_deref(), Some("2025-11"));
 
-    Ok(html)
+        // Undated events sort first and come back as their own final chunk
+        let oldest = month_chunk(&events, older.next_before.as_deref(), 2);
+        assert_eq!(dates(&oldest), ["unknown"]);
+        assert_eq!(oldest.next_before, None);
+    }
+
+    #[test]
+    fn unknown_cursor_returns_empty_chunk() {
+        let events = vec![event("2026-01-05")];
+        let chunk = month_chunk(&events, Some("2024-01"), 3);
+        assert!(chunk.events.is_empty());
+        assert_eq!(chunk.next_before, None);
+        assert_eq!(month_chunk(&events, None, 3).next_before, None);
+    }
 }
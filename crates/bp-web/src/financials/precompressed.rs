@@ -0,0 +1,149 @@
+//! Pre-compressed variants of the rendered `/financials` page.
+//!
+//! The page is rendered once per timeline snapshot and compressed with brotli and
+//! gzip up front; each request then picks a variant by `Accept-Encoding` instead of
+//! re-rendering and re-compressing megabytes of HTML.
+
+use std::io::Write;
+
+use flate2::Compression;
+use flate2::write::GzEncoder;
+
+/// Brotli quality for the cached variant (compressed once per snapshot, so favour size)
+const BROTLI_QUALITY: u32 = 9;
+const BROTLI_WINDOW: u32 = 22;
+
+/// Response encodings the page is stored in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentEncoding {
+    Brotli,
+    Gzip,
+    Identity,
+}
+
+impl ContentEncoding {
+    /// `Content-Encoding` header value (`None` for identity)
+    pub fn header_value(self) -> Option<&'static str> {
+        match self {
+            Self::Brotli => Some("br"),
+            Self::Gzip => Some("gzip"),
+            Self::Identity => None,
+        }
+    }
+}
+
+/// The rendered page in every stored encoding
+#[derive(Debug)]
+pub struct PrecompressedHtml {
+    html: Vec<u8>,
+    brotli: Vec<u8>,
+    gzip: Vec<u8>,
+}
+
+impl PrecompressedHtml {
+    /// Compress `html` into each variant. CPU-bound; run it off the async runtime.
+    pub fn new(html: String) -> Self {
+        let html = html.into_bytes();
+
+        // Writes into a Vec can't fail
+        let mut brotli = Vec::new();
+        {
+            let mut writer = brotli::CompressorWriter::new(&mut brotli, 4096, BROTLI_QUALITY, BROTLI_WINDOW);
+            writer.write_all(&html).expect("in-memory brotli compression");
+        }
+
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::best());
+        gzip.write_all(&html).expect("in-memory gzip compression");
+        let gzip = gzip.finish().expect("in-memory gzip compression");
+
+        Self { html, brotli, gzip }
+    }
+
+    /// Body bytes for `encoding`
+    pub fn body(&self, encoding: ContentEncoding) -> &[u8] {
+        match encoding {
+            ContentEncoding::Brotli => &self.brotli,
+            ContentEncoding::Gzip => &self.gzip,
+            ContentEncoding::Identity => &self.html,
+        }
+    }
+}
+
+/// Pick the stored encoding to send for an `Accept-Encoding` header.
+///
+/// Prefers brotli, then gzip, among codings the client accepts with a non-zero
+/// q-value (a `*` entry covers codings it doesn't name). Falls back to identity.
+pub fn negotiate_encoding(accept_encoding: Option<&str>) -> ContentEncoding {
+    let Some(header) = accept_encoding else {
+        return ContentEncoding::Identity;
+    };
+
+    let mut wildcard = None;
+    let mut named: Vec<(String, f32)> = Vec::new();
+    for item in header.split(',') {
+        let mut parts = item.split(';');
+        let coding = parts.next().unwrap_or("").trim().to_ascii_lowercase();
+        if coding.is_empty() {
+            continue;
+        }
+        let q = parts
+            .filter_map(|p| p.trim().strip_prefix("q="))
+            .find_map(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        if coding == "*" {
+            wildcard = Some(q);
+        } else {
+            named.push((coding, q));
+        }
+    }
+
+    let accepts = |coding: &str| match named.iter().find(|(c, _)| c == coding) {
+        Some((_, q)) => *q > 0.0,
+        None => wildcard.is_some_and(|q| q > 0.0),
+    };
+
+    if accepts("br") {
+        ContentEncoding::Brotli
+    } else if accepts("gzip") {
+        ContentEncoding::Gzip
+    } else {
+        ContentEncoding::Identity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn negotiation_respects_preference_and_q_values() {
+        assert_eq!(negotiate_encoding(None), ContentEncoding::Identity);
+        assert_eq!(negotiate_encoding(Some("gzip, deflate, br")), ContentEncoding::Brotli);
+        assert_eq!(negotiate_encoding(Some("gzip, br;q=0")), ContentEncoding::Gzip);
+        assert_eq!(negotiate_encoding(Some("deflate")), ContentEncoding::Identity);
+        assert_eq!(negotiate_encoding(Some("*")), ContentEncoding::Brotli);
+        assert_eq!(negotiate_encoding(Some("br;q=0, *;q=0.5")), ContentEncoding::Gzip);
+        assert_eq!(negotiate_encoding(Some("identity, *;q=0")), ContentEncoding::Identity);
+    }
+
+    #[test]
+    fn variants_round_trip() {
+        let html = "<html>".to_string() + &"<p>epoch</p>".repeat(500) + "</html>";
+        let page = PrecompressedHtml::new(html.clone());
+        assert_eq!(page.body(ContentEncoding::Identity), html.as_bytes());
+        assert!(page.body(ContentEncoding::Brotli).len() < html.len() / 10);
+
+        let mut gunzipped = String::new();
+        flate2::read::GzDecoder::new(page.body(ContentEncoding::Gzip))
+            .read_to_string(&mut gunzipped)
+            .unwrap();
+        assert_eq!(gunzipped, html);
+
+        let mut unbrotlied = String::new();
+        brotli::Decompressor::new(page.body(ContentEncoding::Brotli), 4096)
+            .read_to_string(&mut unbrotlied)
+            .unwrap();
+        assert_eq!(unbrotlied, html);
+    }
+}
@@ -0,0 +1,371 @@
+//! Read-only SQL console for `/financials/sql`.
+//!
+//! Runs ad-hoc queries against `cache.sqlite` for the questions no canned report
+//! answers. Each query gets its own read-only connection with an SQLite authorizer
+//! that only permits reads (SELECT, table reads, CTEs and safe functions), so even a
+//! query that slips past `?mode=ro` can't write, attach, or change pragmas. Queries are
+//! also capped in run time and result rows.
+
+use anyhow::{Context, Result, ensure};
+use futures::TryStreamExt;
+use libsqlite3_sys as ffi;
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteRow};
+use sqlx::{Column, ConnectOptions, Executor, Row, Statement, TypeInfo, ValueRef};
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int, c_void};
+use std::time::{Duration, Instant};
+
+use super::admin::{STYLE, escape_html};
+
+/// Rows returned before the result is cut off
+const MAX_ROWS: usize = 5_000;
+/// Longest query accepted
+pub const MAX_QUERY_CHARS: usize = 10_000;
+/// Queries are aborted after running this long
+const QUERY_TIMEOUT: Duration = Duration::from_secs(10);
+/// Virtual machine steps between timeout checks
+const PROGRESS_CHECK_OPS: i32 = 10_000;
+
+/// Functions that could reach outside the database, denied even though they're "reads"
+const DENIED_FUNCTIONS: &[&str] = &["load_extension", "readfile", "writefile", "edit", "fts3_tokenizer"];
+
+const EXAMPLE_QUERY: &str = "SELECT strftime('%w', date) AS weekday, ROUND(SUM(amount_sol), 4) AS mev_sol\n\
+                             FROM mev_claims WHERE date IS NOT NULL\n\
+                             GROUP BY weekday ORDER BY weekday";
+
+/// Column names and rows of a query, values typed as SQLite returned them
+#[derive(Debug, Serialize)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+    /// More than `MAX_ROWS` rows matched
+    pub truncated: bool,
+}
+
+/// Whether the console may perform an SQLite authorizer action
+fn is_allowed(action: c_int, function: Option<&str>) -> bool {
+    match action {
+        ffi::SQLITE_SELECT | ffi::SQLITE_READ | ffi::SQLITE_RECURSIVE => true,
+        ffi::SQLITE_FUNCTION => function.is_some_and(|f| !DENIED_FUNCTIONS.contains(&f.to_ascii_lowercase().as_str())),
+        _ => false,
+    }
+}
+
+extern "C" fn authorizer(
+    _user_data: *mut c_void,
+    action: c_int,
+    _arg1: *const c_char,
+    arg2: *const c_char,
+    _database: *const c_char,
+    _trigger: *const c_char,
+) -> c_int {
+    // For SQLITE_FUNCTION, the second argument is the function name
+    let function = if arg2.is_null() {
+        None
+    } else {
+        #[allow(unsafe_code)]
+        // SAFETY: SQLite passes a valid NUL-terminated string that outlives the callback
+        let name = unsafe { CStr::from_ptr(arg2) };
+        name.to_str().ok()
+    };
+    if is_allowed(action, function) {
+        ffi::SQLITE_OK
+    } else {
+        ffi::SQLITE_DENY
+    }
+}
+
+/// A dedicated read-only connection to cache.sqlite with the authorizer and timeout installed
+async fn open_console_connection(data_dir: &str) -> Result<sqlx::SqliteConnection> {
+    let path = super::db::cache_path(data_dir);
+    let mut conn = SqliteConnectOptions::new()
+        .filename(&path)
+        .read_only(true)
+        .connect()
+        .await
+        .with_context(|| format!("Failed to open cache at {}", path.display()))?;
+
+    let mut handle = conn.lock_handle().await?;
+    #[allow(unsafe_code)]
+    // SAFETY: the handle is a live connection we hold the lock on; the callback is a plain
+    // function with no user data
+    let rc =
+        unsafe { ffi::sqlite3_set_authorizer(handle.as_raw_handle().as_ptr(), Some(authorizer), std::ptr::null_mut()) };
+    ensure!(
+        rc == ffi::SQLITE_OK,
+        "Failed to install the read-only authorizer (SQLite error {})",
+        rc
+    );
+
+    let deadline = Instant::now() + QUERY_TIMEOUT;
+    // Returning false interrupts the running statement
+    handle.set_progress_handler(PROGRESS_CHECK_OPS, move || Instant::now() < deadline);
+    drop(handle);
+
+    Ok(conn)
+}
+
+/// A column value as JSON (blobs as hex)
+pub(super) fn cell(row: &SqliteRow, index: usize) -> Value {
+    let Ok(raw) = row.try_get_raw(index) else {
+        return Value::Null;
+    };
+    if raw.is_null() {
+        return Value::Null;
+    }
+    let value = match raw.type_info().name() {
+        "INTEGER" => row.try_get_unchecked::<i64, _>(index).map(Value::from),
+        "REAL" => row.try_get_unchecked::<f64, _>(index).map(Value::from),
+        "BLOB" => row
+            .try_get_unchecked::<Vec<u8>, _>(index)
+            .map(|bytes| Value::from(bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>())),
+        _ => row.try_get_unchecked::<String, _>(index).map(Value::from),
+    };
+    value.unwrap_or(Value::Null)
+}
+
+/// Run one read-only query against cache.sqlite
+pub async fn run_query(data_dir: &str, sql: &str) -> Result<QueryResult> {
+    let sql = sql.trim();
+    ensure!(!sql.is_empty(), "Enter a query");
+    ensure!(
+        sql.chars().count() <= MAX_QUERY_CHARS,
+        "Query is longer than {} characters",
+        MAX_QUERY_CHARS
+    );
+
+    let mut conn = open_console_connection(data_dir).await?;
+    let statement = conn.prepare(sql).await?;
+    let columns = statement.columns().iter().map(|c| c.name().to_string()).collect();
+
+    let mut rows = Vec::new();
+    let mut truncated = false;
+    let mut stream = statement.query().fetch(&mut conn);
+    while let Some(row) = stream.try_next().await? {
+        if rows.len() == MAX_ROWS {
+            truncated = true;
+            break;
+        }
+        rows.push((0..row.len()).map(|i| cell(&row, i)).collect());
+    }
+
+    Ok(QueryResult {
+        columns,
+        rows,
+        truncated,
+    })
+}
+
+/// Plain-text form of a value (strings unquoted, NULL empty)
+pub(super) fn display(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+pub(super) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Result as CSV with a header row
+pub fn to_csv(result: &QueryResult) -> String {
+    let mut out = String::new();
+    let header: Vec<String> = result.columns.iter().map(|c| csv_field(c)).collect();
+    out.push_str(&header.join(","));
+    out.push('\n');
+    for row in &result.rows {
+        let fields: Vec<String> = row.iter().map(|v| csv_field(&display(v))).collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+fn render_result(result: &QueryResult) -> String {
+    let mut html = format!(
+        "<p class='muted'>{} row{}{}</p>",
+        result.rows.len(),
+        if result.rows.len() == 1 { "" } else { "s" },
+        if result.truncated {
+            format!(" (cut off at {})", MAX_ROWS)
+        } else {
+            String::new()
+        }
+    );
+    html.push_str("<table><thead><tr>");
+    for column in &result.columns {
+        html.push_str(&format!("<th>{}</th>", escape_html(column)));
+    }
+    html.push_str("</tr></thead><tbody>");
+    for row in &result.rows {
+        html.push_str("<tr>");
+        for value in row {
+            match value {
+                Value::Null => html.push_str("<td class='muted'>NULL</td>"),
+                other => html.push_str(&format!("<td>{}</td>", escape_html(&display(other)))),
+            }
+        }
+        html.push_str("</tr>");
+    }
+    html.push_str("</tbody></table>");
+    html
+}
+
+/// The console page: query form, then the result or error of the last run (if any)
+pub fn render_page(sql: Option<&str>, outcome: Option<&Result<QueryResult>>) -> String {
+    let body = match outcome {
+        None => String::new(),
+        Some(Ok(result)) => render_result(result),
+        Some(Err(e)) => format!("<p class='status-failed'>{}</p>", escape_html(&format!("{:#}", e))),
+    };
+
+    format!(
+        concat!(
+            "<!DOCTYPE html><html lang='en'><head><meta charset='utf-8'>",
+            "<meta name='viewport' content='width=device-width, initial-scale=1'>",
+            "<title>Block Parliament · SQL Console</title>",
+            "<link rel='icon' type='image/png' href='/logo/owl-64.png'>",
+            "<style>{}textarea{{width:100%;font:inherit;background:inherit;color:inherit}}</style>",
+            "</head><body>",
+            "<h1>SQL Console</h1>",
+            "<p><a href='/financials/admin'>&larr; back to admin</a></p>",
+            "<p class='muted'>Read-only queries against cache.sqlite. ",
+            "List tables with <code>SELECT name, sql FROM sqlite_master WHERE type = 'table'</code>.</p>",
+            "<form method='post' action='/financials/sql'>",
+            "<textarea name='query' rows='8' maxlength='{}' spellcheck='false'>{}</textarea>",
+            "<p><button type='submit' name='format' value='html'>Run</button> ",
+            "<button type='submit' name='format' value='csv'>Download CSV</button></p>",
+            "</form>{}</body></html>"
+        ),
+        STYLE,
+        MAX_QUERY_CHARS,
+        escape_html(sql.unwrap_or(EXAMPLE_QUERY)),
+        body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn authorizer_only_permits_reads() {
+        assert!(is_allowed(ffi::SQLITE_SELECT, None));
+        assert!(is_allowed(ffi::SQLITE_READ, None));
+        assert!(is_allowed(ffi::SQLITE_FUNCTION, Some("strftime")));
+        assert!(!is_allowed(ffi::SQLITE_FUNCTION, Some("LOAD_EXTENSION")));
+        for action in [
+            ffi::SQLITE_INSERT,
+            ffi::SQLITE_UPDATE,
+            ffi::SQLITE_DELETE,
+            ffi::SQLITE_ATTACH,
+            ffi::SQLITE_PRAGMA,
+            ffi::SQLITE_DROP_TABLE,
+        ] {
+            assert!(!is_allowed(action, None), "action {action} allowed");
+        }
+    }
+
+    #[test]
+    fn csv_quotes_only_when_needed() {
+        let result = QueryResult {
+            columns: vec!["vendor".into(), "amount".into()],
+            rows: vec![
+                vec![Value::from("Hetzner, Inc."), Value::from(120.5)],
+                vec![Value::from("say \"hi\""), Value::Null],
+            ],
+            truncated: false,
+        };
+        assert_eq!(
+            to_csv(&result),
+            "vendor,amount\n\"Hetzner, Inc.\",120.5\n\"say \"\"hi\"\"\",\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn console_reads_but_cannot_write() {
+        let dir = std::env::temp_dir().join(format!("bp-sql-console-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let data_dir = dir.to_str().unwrap();
+        {
+            let url = format!("sqlite:{}/cache.sqlite?mode=rwc", data_dir);
+            let mut conn = <sqlx::SqliteConnection as sqlx::Connection>::connect(&url)
+                .await
+                .unwrap();
+            conn.execute(
+                "CREATE TABLE IF NOT EXISTS prices (date TEXT PRIMARY KEY, usd_price REAL NOT NULL);
+                          INSERT OR REPLACE INTO prices VALUES ('2026-01-01', 190.25);",
+            )
+            .await
+            .unwrap();
+        }
+
+        let result = run_query(data_dir, "SELECT date, usd_price, NULL AS missing FROM prices")
+            .await
+            .unwrap();
+        assert_eq!(result.columns, ["date", "usd_price", "missing"]);
+        assert_eq!(
+            result.rows,
+            [[Value::from("2026-01-01"), Value::from(190.25), Value::Null]]
+        );
+
+        for sql in [
+            "DELETE FROM prices",
+            "PRAGMA journal_mode = DELETE",
+            "ATTACH DATABASE ':memory:' AS other",
+        ] {
+            assert!(run_query(data_dir, sql).await.is_err(), "{sql} was allowed");
+        }
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn console_reads_the_configured_clusters_cache() {
+        let dir = std::env::temp_dir().join(format!("bp-sql-console-testnet-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let data_dir = dir.to_str().unwrap();
+        std::fs::write(
+            dir.join("config.toml"),
+            r#"[validator]
+vote_account = "VOTE"
+identity = "ID"
+withdraw_authority = "WA"
+personal_wallet = "PW"
+bootstrap_date = "2025-11-19"
+cluster = "testnet"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            crate::financials::db::cache_path(data_dir),
+            dir.join("cache-testnet.sqlite")
+        );
+        {
+            let url = format!("sqlite:{}/cache-testnet.sqlite?mode=rwc", data_dir);
+            let mut conn = <sqlx::SqliteConnection as sqlx::Connection>::connect(&url)
+                .await
+                .unwrap();
+            conn.execute(
+                "CREATE TABLE prices (date TEXT PRIMARY KEY, usd_price REAL NOT NULL);
+                          INSERT INTO prices VALUES ('2026-01-01', 0.0);",
+            )
+            .await
+            .unwrap();
+        }
+
+        let result = run_query(data_dir, "SELECT COUNT(*) AS n FROM prices").await;
+        let mainnet_cache_created = dir.join("cache.sqlite").exists();
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(result.unwrap().rows, [[Value::from(1)]]);
+        assert!(!mainnet_cache_created);
+    }
+}
@@ -24,6 +24,7 @@ fn type_order(event_type: &str) -> u8 {
         "leader_fees" => 1,
         "mev" => 2,
         "bam" => 3,
+        "donation" => 3,
         "vote_cost" => 4,
         "doublezero" => 5,
         "expense" => 6,
@@ -47,6 +48,41 @@ fn type_order(event_type: &str) -> u8 {
 
 const FALLBACK_DATE: &str = "2025-12-15";
 
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+/// SOL amount as whole lamports (sign dropped; the event amount carries it)
+fn lamports(sol: f64) -> u64 {
+    (sol.abs() * LAMPORTS_PER_SOL).round() as u64
+}
+
+/// "Transfer from A to B" with labels where known, full addresses otherwise
+fn transfer_provenance(t: &SolTransfer) -> String {
+    let side = |label: &str, address: &str| {
+        if label.is_empty() {
+            address.to_string()
+        } else {
+            format!("{} ({})", label, address)
+        }
+    };
+    let mut text = format!(
+        "Transfer from {} to {}",
+        side(&t.from_label, &t.from_address),
+        side(&t.to_label, &t.to_address)
+    );
+    if let Some(memo) = t.memo.as_deref().filter(|m| !m.is_empty()) {
+        text.push_str(&format!(", memo \"{}\"", memo));
+    }
+    text
+}
+
+/// Expenses are keyed by invoice when there is one; recurring expansions have none
+fn expense_source_id(expense: &Expense) -> String {
+    match &expense.invoice_id {
+        Some(invoice) => format!("expenses:{}", invoice),
+        None => format!("expenses:{}/{}", expense.date, expense.vendor),
+    }
+}
+
 /// Walk forward through sorted events, accumulating running totals.
 fn accumulate(events: &mut [TimelineEvent]) {
     let mut cum_profit = 0.0_f64;
@@ -148,6 +184,39 @@ pub fn expand_recurring_expenses(
     expenses
 }
 
+/// Expenses for the business share of split withdrawals (`split set`), valued at the
+/// withdrawal date's price like validator-accounting's `owner_draw::split_expenses`.
+pub fn split_expenses(categorized: &CategorizedTransfers, prices: &PriceMap) -> Vec<Expense> {
+    categorized
+        .withdrawals
+        .iter()
+        .filter_map(|transfer| {
+            let split = categorized.split_for(&transfer.signature)?;
+            let date = transfer.date.clone()?;
+            let business_sol = transfer.amount_sol * split.business_pct / 100.0;
+            if business_sol <= 0.0 {
+                return None;
+            }
+            let (price, _) = get_price_with_source(prices, &date);
+            Some(Expense {
+                date,
+                vendor: "Owner-paid (withdrawal split)".into(),
+                category: split.category,
+                description: format!(
+                    "{} ({:.0}% of {:.6} SOL withdrawal {})",
+                    split.description,
+                    split.business_pct,
+                    transfer.amount_sol,
+                    &transfer.signature[..transfer.signature.len().min(16)]
+                ),
+                amount_usd: business_sol * price,
+                paid_with: "SOL".into(),
+                invoice_id: Some(format!("split:{}", transfer.signature)),
+            })
+        })
+        .collect()
+}
+
 // ══════════════════════════════════════════════════════════════════════════════
 // OPERATING TIMELINE
 // ══════════════════════════════════════════════════════════════════════════════
@@ -159,7 +228,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
     // ── Commission rewards ──────────────────────────────────────────────
     for reward in data.rewards {
         let date = reward.date.clone().unwrap_or_else(|| "unknown".into());
-        let price = get_price(data.prices, &date);
+        let (price, price_source) = get_price_with_source(data.prices, &date);
         let usd = reward.amount_sol * price;
         events.push(TimelineEvent {
             date,
@@ -169,6 +238,17 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
             sublabel: Some(format!("Epoch {}", reward.epoch)),
             amount_sol: reward.amount_sol,
             amount_usd: usd,
+            price_source: Some(price_source),
+            signature: None,
+            source_id: format!("epoch_rewards:{}", reward.epoch),
+            detail: EventDetail {
+                lamports: Some(lamports(reward.amount_sol)),
+                price_usd: Some(price),
+                provenance: format!(
+                    "Inflation reward to the vote account at {}% commission",
+                    reward.commission
+                ),
+            },
             cumulative_profit_usd: 0.0,
             cumulative_revenue_usd: 0.0,
             cumulative_expenses_usd: 0.0,
@@ -179,7 +259,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
     // ── Leader fees ─────────────────────────────────────────────────────
     for fees in data.leader_fees {
         let date = fees.date.clone().unwrap_or_else(|| "unknown".into());
-        let price = get_price(data.prices, &date);
+        let (price, price_source) = get_price_with_source(data.prices, &date);
         let usd = fees.total_fees_sol * price;
         events.push(TimelineEvent {
             date,
@@ -189,6 +269,17 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
             sublabel: Some(format!("Epoch {} \u{00b7} {} blocks", fees.epoch, fees.blocks_produced)),
             amount_sol: fees.total_fees_sol,
             amount_usd: usd,
+            price_source: Some(price_source),
+            signature: None,
+            source_id: format!("leader_fees:{}", fees.epoch),
+            detail: EventDetail {
+                lamports: Some(lamports(fees.total_fees_sol)),
+                price_usd: Some(price),
+                provenance: format!(
+                    "Fees and priority fees from {} produced blocks ({} leader slots skipped)",
+                    fees.blocks_produced, fees.skipped_slots
+                ),
+            },
             cumulative_profit_usd: 0.0,
             cumulative_revenue_usd: 0.0,
             cumulative_expenses_usd: 0.0,
@@ -200,7 +291,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
     if data.mev_claims.is_empty() {
         for transfer in &data.categorized.mev_deposits {
             let date = transfer.date.clone().unwrap_or_else(|| "unknown".into());
-            let price = get_price(data.prices, &date);
+            let (price, price_source) = get_price_with_source(data.prices, &date);
             let usd = transfer.amount_sol * price;
             events.push(TimelineEvent {
                 date,
@@ -210,6 +301,14 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
                 sublabel: None,
                 amount_sol: transfer.amount_sol,
                 amount_usd: usd,
+                price_source: Some(price_source),
+                signature: Some(transfer.signature.clone()),
+                source_id: format!("sol_transfers:{}", transfer.signature),
+                detail: EventDetail {
+                    lamports: Some(lamports(transfer.amount_sol)),
+                    price_usd: Some(price),
+                    provenance: format!("Deposit from {} (no per-epoch MEV claims cached)", transfer.from_label),
+                },
                 cumulative_profit_usd: 0.0,
                 cumulative_revenue_usd: 0.0,
                 cumulative_expenses_usd: 0.0,
@@ -219,7 +318,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
     } else {
         for claim in data.mev_claims {
             let date = claim.date.clone().unwrap_or_else(|| "unknown".into());
-            let price = get_price(data.prices, &date);
+            let (price, price_source) = get_price_with_source(data.prices, &date);
             let usd = claim.amount_sol * price;
             events.push(TimelineEvent {
                 date,
@@ -229,6 +328,17 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
                 sublabel: Some(format!("Epoch {}", claim.epoch)),
                 amount_sol: claim.amount_sol,
                 amount_usd: usd,
+                price_source: Some(price_source),
+                signature: None,
+                source_id: format!("mev_claims:{}", claim.epoch),
+                detail: EventDetail {
+                    lamports: Some(claim.commission_lamports),
+                    price_usd: Some(price),
+                    provenance: format!(
+                        "Jito tip distribution: our commission on {} lamports of epoch tips",
+                        claim.total_tips_lamports
+                    ),
+                },
                 cumulative_profit_usd: 0.0,
                 cumulative_revenue_usd: 0.0,
                 cumulative_expenses_usd: 0.0,
@@ -237,10 +347,38 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
         }
     }
 
+    // ── Donations ───────────────────────────────────────────────────────
+    for transfer in &data.categorized.donations {
+        let date = transfer.date.clone().unwrap_or_else(|| "unknown".into());
+        let (price, price_source) = get_price_with_source(data.prices, &date);
+        let usd = transfer.amount_sol * price;
+        events.push(TimelineEvent {
+            date,
+            epoch: None,
+            event_type: "donation",
+            label: "Donation".into(),
+            sublabel: Some(transfer.from_label.clone()),
+            amount_sol: transfer.amount_sol,
+            amount_usd: usd,
+            price_source: Some(price_source),
+            signature: Some(transfer.signature.clone()),
+            source_id: format!("sol_transfers:{}", transfer.signature),
+            detail: EventDetail {
+                lamports: Some(lamports(transfer.amount_sol)),
+                price_usd: Some(price),
+                provenance: transfer_provenance(transfer),
+            },
+            cumulative_profit_usd: 0.0,
+            cumulative_revenue_usd: 0.0,
+            cumulative_expenses_usd: 0.0,
+            is_pnl: true,
+        });
+    }
+
     // ── BAM claims ──────────────────────────────────────────────────────
     for claim in data.bam_claims {
         let date = claim.date.clone().unwrap_or_else(|| "unknown".into());
-        let price = get_price(data.prices, &date);
+        let (price, price_source) = get_price_with_source(data.prices, &date);
         let usd = claim.amount_sol_equivalent * price;
         events.push(TimelineEvent {
             date,
@@ -250,6 +388,20 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
             sublabel: Some(format!("Epoch {} \u{00b7} jitoSOL reward", claim.epoch)),
             amount_sol: claim.amount_sol_equivalent,
             amount_usd: usd,
+            price_source: Some(price_source),
+            signature: Some(claim.tx_signature.clone()).filter(|s| !s.is_empty()),
+            source_id: format!("bam_claims:{}", claim.epoch),
+            detail: EventDetail {
+                lamports: Some(lamports(claim.amount_sol_equivalent)),
+                price_usd: Some(price),
+                provenance: match claim.jitosol_sol_rate {
+                    Some(rate) => format!(
+                        "{} jitoSOL lamports claimed, valued at {} SOL per jitoSOL",
+                        claim.amount_jitosol_lamports, rate
+                    ),
+                    None => format!("{} jitoSOL lamports claimed", claim.amount_jitosol_lamports),
+                },
+            },
             cumulative_profit_usd: 0.0,
             cumulative_revenue_usd: 0.0,
             cumulative_expenses_usd: 0.0,
@@ -260,7 +412,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
     // ── Vote costs (net of SFDP) ────────────────────────────────────────
     for cost in data.vote_costs {
         let date = cost.date.clone().unwrap_or_else(|| "unknown".into());
-        let price = get_price(data.prices, &date);
+        let (price, price_source) = get_price_with_source(data.prices, &date);
         let gross_usd = cost.total_fee_sol * price;
 
         let parsed = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
@@ -293,6 +445,19 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
             sublabel,
             amount_sol: -net_sol,
             amount_usd: -net_usd,
+            price_source: Some(price_source),
+            signature: None,
+            source_id: format!("vote_costs:{}", cost.epoch),
+            detail: EventDetail {
+                lamports: Some(lamports(cost.total_fee_sol)),
+                price_usd: Some(price),
+                provenance: format!(
+                    "{} vote transactions (source: {}), {:.0}% offset by SFDP",
+                    cost.vote_count,
+                    cost.source,
+                    coverage * 100.0
+                ),
+            },
             cumulative_profit_usd: 0.0,
             cumulative_revenue_usd: 0.0,
             cumulative_expenses_usd: 0.0,
@@ -303,7 +468,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
     // ── DoubleZero fees ─────────────────────────────────────────────────
     for fee in data.doublezero_fees {
         let date = fee.date.clone().unwrap_or_else(|| "unknown".into());
-        let price = get_price(data.prices, &date);
+        let (price, price_source) = get_price_with_source(data.prices, &date);
         let usd = fee.liability_sol * price;
         events.push(TimelineEvent {
             date,
@@ -313,6 +478,19 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
             sublabel: Some(format!("Epoch {}", fee.epoch)),
             amount_sol: -fee.liability_sol,
             amount_usd: -usd,
+            price_source: Some(price_source),
+            signature: None,
+            source_id: format!("doublezero_fees:{}", fee.epoch),
+            detail: EventDetail {
+                lamports: Some(lamports(fee.liability_sol)),
+                price_usd: Some(price),
+                provenance: format!(
+                    "{}bps of a {} lamport fee base{}",
+                    fee.fee_rate_bps,
+                    fee.fee_base_lamports,
+                    if fee.is_estimate { " (estimate)" } else { "" }
+                ),
+            },
             cumulative_profit_usd: 0.0,
             cumulative_revenue_usd: 0.0,
             cumulative_expenses_usd: 0.0,
@@ -330,6 +508,14 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
             sublabel: Some(expense.description.clone()),
             amount_sol: 0.0,
             amount_usd: -expense.amount_usd,
+            price_source: None,
+            signature: None,
+            source_id: expense_source_id(expense),
+            detail: EventDetail {
+                lamports: None,
+                price_usd: None,
+                provenance: format!("Off-chain expense paid with {}", expense.paid_with),
+            },
             cumulative_profit_usd: 0.0,
             cumulative_revenue_usd: 0.0,
             cumulative_expenses_usd: 0.0,
@@ -340,7 +526,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
     // ── Balance-sheet: seeding ───────────────────────────────────────────
     for transfer in &data.categorized.seeding {
         let date = transfer.date.clone().unwrap_or_else(|| "unknown".into());
-        let price = get_price(data.prices, &date);
+        let (price, price_source) = get_price_with_source(data.prices, &date);
         let usd = transfer.amount_sol * price;
         events.push(TimelineEvent {
             date,
@@ -350,6 +536,14 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
             sublabel: Some(format!("{} \u{2192} {}", transfer.from_label, transfer.to_label)),
             amount_sol: transfer.amount_sol,
             amount_usd: usd,
+            price_source: Some(price_source),
+            signature: Some(transfer.signature.clone()),
+            source_id: format!("sol_transfers:{}", transfer.signature),
+            detail: EventDetail {
+                lamports: Some(lamports(transfer.amount_sol)),
+                price_usd: Some(price),
+                provenance: transfer_provenance(transfer),
+            },
             cumulative_profit_usd: 0.0,
             cumulative_revenue_usd: 0.0,
             cumulative_expenses_usd: 0.0,
@@ -360,16 +554,33 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
     // ── Balance-sheet: withdrawals ───────────────────────────────────────
     for transfer in &data.categorized.withdrawals {
         let date = transfer.date.clone().unwrap_or_else(|| "unknown".into());
-        let price = get_price(data.prices, &date);
+        let (price, price_source) = get_price_with_source(data.prices, &date);
         let usd = transfer.amount_sol * price;
         events.push(TimelineEvent {
             date,
             epoch: None,
             event_type: "withdrawal",
             label: "Withdrawal".into(),
-            sublabel: Some(format!("\u{2192} {}", transfer.to_label)),
+            sublabel: Some(match data.categorized.split_for(&transfer.signature) {
+                Some(split) => format!(
+                    "\u{2192} {} ({:.0}% owner draw, {:.0}% {} expense)",
+                    transfer.to_label,
+                    100.0 - split.business_pct,
+                    split.business_pct,
+                    split.category
+                ),
+                None => format!("\u{2192} {}", transfer.to_label),
+            }),
             amount_sol: transfer.amount_sol,
             amount_usd: usd,
+            price_source: Some(price_source),
+            signature: Some(transfer.signature.clone()),
+            source_id: format!("sol_transfers:{}", transfer.signature),
+            detail: EventDetail {
+                lamports: Some(lamports(transfer.amount_sol)),
+                price_usd: Some(price),
+                provenance: transfer_provenance(transfer),
+            },
             cumulative_profit_usd: 0.0,
             cumulative_revenue_usd: 0.0,
             cumulative_expenses_usd: 0.0,
@@ -380,7 +591,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
     // ── Balance-sheet: DoubleZero prepayments ────────────────────────────
     for transfer in &data.categorized.doublezero_payments {
         let date = transfer.date.clone().unwrap_or_else(|| "unknown".into());
-        let price = get_price(data.prices, &date);
+        let (price, price_source) = get_price_with_source(data.prices, &date);
         let usd = transfer.amount_sol * price;
         events.push(TimelineEvent {
             date,
@@ -390,6 +601,14 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
             sublabel: Some("Deposit to DoubleZero PDA".into()),
             amount_sol: transfer.amount_sol,
             amount_usd: usd,
+            price_source: Some(price_source),
+            signature: Some(transfer.signature.clone()),
+            source_id: format!("sol_transfers:{}", transfer.signature),
+            detail: EventDetail {
+                lamports: Some(lamports(transfer.amount_sol)),
+                price_usd: Some(price),
+                provenance: transfer_provenance(transfer),
+            },
             cumulative_profit_usd: 0.0,
             cumulative_revenue_usd: 0.0,
             cumulative_expenses_usd: 0.0,
@@ -531,12 +750,35 @@ fn is_taxable_external_withdrawal_candidate(t: &SolTransfer, config: &ValidatorC
     if t.from_address == config.identity {
         return t.to_address == config.withdraw_authority
             || config.is_personal_wallet(&t.to_address)
-            || super::categorize::is_exchange(&t.to_address);
+            || super::categorize::is_exchange(&t.to_address)
+            || t.to_category == "Exchange";
     }
 
     true
 }
 
+/// Seed capital (SOL) not yet returned by withdrawals: what the next withdrawal
+/// can still take out before any of it is taxable.
+pub fn remaining_seed_capital(data: &ReportData, config: &ValidatorConfig) -> f64 {
+    let outgoing: Vec<&SolTransfer> = data
+        .categorized
+        .withdrawals
+        .iter()
+        .chain(&data.categorized.other)
+        .filter(|t| is_taxable_external_withdrawal_candidate(t, config))
+        .collect();
+    let withdrawn: f64 = merge_withdrawals(&outgoing)
+        .iter()
+        .map(|w| w.amount_sol * data.categorized.draw_fraction(&w.signature))
+        .sum();
+    (total_seeded_sol(data, config) - withdrawn).max(0.0)
+}
+
+/// Initial treasury plus every seeding transfer
+fn total_seeded_sol(data: &ReportData, config: &ValidatorConfig) -> f64 {
+    config.initial_treasury_sol + data.categorized.seeding.iter().map(|s| s.amount_sol).sum::<f64>()
+}
+
 /// Build tax rows from financial data (ported from tax_report.rs).
 fn build_tax_rows(data: &ReportData, config: &ValidatorConfig) -> Vec<TaxRow> {
     let mut rows = Vec::new();
@@ -554,9 +796,13 @@ fn build_tax_rows(data: &ReportData, config: &ValidatorConfig) -> Vec<TaxRow> {
             all_outgoing.push(t);
         }
     }
-    let total_seeded_sol: f64 =
-        config.initial_treasury_sol + data.categorized.seeding.iter().map(|s| s.amount_sol).sum::<f64>();
-    add_withdrawal_rows(&mut rows, &all_outgoing, data.prices, total_seeded_sol);
+    add_withdrawal_rows(
+        &mut rows,
+        data.categorized,
+        &all_outgoing,
+        data.prices,
+        total_seeded_sol(data, config),
+    );
 
     // ── Expenses: vote fees (net of SFDP) ───────────────────────────────
     add_vote_cost_rows(
@@ -662,17 +908,25 @@ fn merge_withdrawals(withdrawals: &[&SolTransfer]) -> Vec<MergedWithdrawal> {
     out
 }
 
-fn add_withdrawal_rows(rows: &mut Vec<TaxRow>, withdrawals: &[&SolTransfer], prices: &PriceMap, total_seeded_sol: f64) {
+fn add_withdrawal_rows(
+    rows: &mut Vec<TaxRow>,
+    categorized: &CategorizedTransfers,
+    withdrawals: &[&SolTransfer],
+    prices: &PriceMap,
+    total_seeded_sol: f64,
+) {
     let merged = merge_withdrawals(withdrawals);
 
     let mut remaining_capital = total_seeded_sol;
 
     for w in merged {
-        let capital_portion = w.amount_sol.min(remaining_capital);
-        let revenue_portion = w.amount_sol - capital_portion;
+        // Only the owner-draw share of a split withdrawal is a distribution
+        let draw_sol = w.amount_sol * categorized.draw_fraction(&w.signature);
+        let capital_portion = draw_sol.min(remaining_capital);
+        let revenue_portion = draw_sol - capital_portion;
         remaining_capital -= capital_portion;
 
-        let price = get_price(prices, &w.date);
+        let (price, price_source) = get_price_with_source(prices, &w.date);
         let dest_label = if w.to_label.is_empty() {
             shorten_pubkey(&w.to_address)
         } else {
@@ -687,9 +941,15 @@ fn add_withdrawal_rows(rows: &mut Vec<TaxRow>, withdrawals: &[&SolTransfer], pri
                 description: format!("Return of seed capital to {}", dest_label),
                 sol_amount: Some(capital_portion),
                 sol_price_usd: Some(price),
+                price_source: Some(price_source),
                 usd_value: capital_portion * price,
                 destination: dest_label.clone(),
                 tx_signature: w.signature.clone(),
+                source_id: format!("sol_transfers:{}", w.signature),
+                provenance: format!(
+                    "{} SOL sent to {}; {} SOL of it offsets seed capital",
+                    w.amount_sol, w.to_address, capital_portion
+                ),
             });
         }
 
@@ -701,9 +961,15 @@ fn add_withdrawal_rows(rows: &mut Vec<TaxRow>, withdrawals: &[&SolTransfer], pri
                 description: format!("External withdrawal to {}", dest_label),
                 sol_amount: Some(revenue_portion),
                 sol_price_usd: Some(price),
+                price_source: Some(price_source),
                 usd_value: revenue_portion * price,
                 destination: dest_label,
+                source_id: format!("sol_transfers:{}", w.signature),
                 tx_signature: w.signature,
+                provenance: format!(
+                    "{} SOL sent to {}; {} SOL above remaining seed capital is taxable",
+                    w.amount_sol, w.to_address, revenue_portion
+                ),
             });
         }
     }
@@ -717,7 +983,7 @@ fn add_vote_cost_rows(
 ) {
     for vc in vote_costs {
         let date = vc.date.as_deref().unwrap_or("unknown");
-        let price = get_price(prices, date);
+        let (price, price_source) = get_price_with_source(prices, date);
         let gross_usd = vc.total_fee_sol * price;
 
         let coverage = sfdp_acceptance_date
@@ -749,9 +1015,12 @@ fn add_vote_cost_rows(
             description,
             sol_amount: Some(vc.total_fee_sol),
             sol_price_usd: Some(price),
+            price_source: Some(price_source),
             usd_value: gross_usd,
             destination: String::new(),
             tx_signature: String::new(),
+            source_id: format!("vote_costs:{}", vc.epoch),
+            provenance: format!("{} vote transactions (source: {})", vc.vote_count, vc.source),
         });
 
         if reimbursed_sol > 0.0 {
@@ -766,9 +1035,16 @@ fn add_vote_cost_rows(
                 ),
                 sol_amount: Some(reimbursed_sol),
                 sol_price_usd: Some(price),
+                price_source: Some(price_source),
                 usd_value: reimbursed_usd,
                 destination: String::new(),
                 tx_signature: String::new(),
+                source_id: format!("vote_costs:{}", vc.epoch),
+                provenance: format!(
+                    "{:.0}% of {} SOL in vote fees, per the SFDP coverage schedule",
+                    coverage * 100.0,
+                    vc.total_fee_sol
+                ),
             });
         }
     }
@@ -777,7 +1053,7 @@ fn add_vote_cost_rows(
 fn add_doublezero_rows(rows: &mut Vec<TaxRow>, fees: &[DoubleZeroFee], prices: &PriceMap) {
     for fee in fees {
         let date = fee.date.as_deref().unwrap_or("unknown");
-        let price = get_price(prices, date);
+        let (price, price_source) = get_price_with_source(prices, date);
         let usd_value = fee.liability_sol * price;
 
         rows.push(TaxRow {
@@ -790,9 +1066,17 @@ fn add_doublezero_rows(rows: &mut Vec<TaxRow>, fees: &[DoubleZeroFee], prices: &
             ),
             sol_amount: Some(fee.liability_sol),
             sol_price_usd: Some(price),
+            price_source: Some(price_source),
             usd_value,
             destination: String::new(),
             tx_signature: String::new(),
+            source_id: format!("doublezero_fees:{}", fee.epoch),
+            provenance: format!(
+                "{}bps of a {} lamport fee base{}",
+                fee.fee_rate_bps,
+                fee.fee_base_lamports,
+                if fee.is_estimate { " (estimate)" } else { "" }
+            ),
         });
     }
 }
@@ -806,9 +1090,12 @@ fn add_offchain_expense_rows(rows: &mut Vec<TaxRow>, expenses: &[Expense]) {
             description: format!("{} - {}", exp.vendor, exp.description),
             sol_amount: None,
             sol_price_usd: None,
+            price_source: None,
             usd_value: exp.amount_usd,
             destination: String::new(),
             tx_signature: String::new(),
+            source_id: expense_source_id(exp),
+            provenance: format!("Off-chain expense paid with {}", exp.paid_with),
         });
     }
 }
@@ -832,6 +1119,14 @@ pub fn build_tax_timeline(data: &ReportData, config: &ValidatorConfig) -> Vec<Ti
                 sublabel,
                 amount_sol,
                 amount_usd,
+                price_source: row.price_source,
+                signature: Some(row.tx_signature).filter(|s| !s.is_empty()),
+                source_id: row.source_id,
+                detail: EventDetail {
+                    lamports: row.sol_amount.map(lamports),
+                    price_usd: row.sol_price_usd,
+                    provenance: row.provenance,
+                },
                 cumulative_profit_usd: 0.0,
                 cumulative_revenue_usd: 0.0,
                 cumulative_expenses_usd: 0.0,
@@ -889,6 +1184,10 @@ bootstrap_date = "2025-11-19"
             amount_sol,
             from_label: from.to_string(),
             to_label: to_label.to_string(),
+            from_category: "Unknown".to_string(),
+            to_category: "Unknown".to_string(),
+            memo: None,
+            program: None,
         }
     }
 
@@ -908,7 +1207,7 @@ bootstrap_date = "2025-11-19"
             ..Default::default()
         };
 
-        let prices: PriceMap = HashMap::from([(String::from("2026-02-28"), 100.0)]);
+        let prices: PriceMap = HashMap::from([(String::from("2026-02-28"), 100.0.into())]);
         let rewards: Vec<EpochReward> = Vec::new();
         let mev_claims: Vec<MevClaim> = Vec::new();
         let bam_claims: Vec<BamClaim> = Vec::new();
@@ -955,9 +1254,127 @@ bootstrap_date = "2025-11-19"
         }));
     }
 
+    #[test]
+    fn remaining_seed_capital_nets_out_taxable_withdrawals() {
+        let config = test_config();
+        let categorized = CategorizedTransfers {
+            seeding: vec![transfer("sig-seed", "PW", "VOTE", 200.0, "Vote Account")],
+            withdrawals: vec![transfer("sig-personal", "ID", "PW", 0.5, "Personal Wallet")],
+            other: vec![
+                transfer("sig-vote-wa", "VOTE", "WA", 88.0, "Withdraw Authority"),
+                // Operational identity outflow: never consumes capital
+                transfer("sig-id-micro", "ID", "X_MICRO", 0.002, "XMic...1234"),
+            ],
+            ..Default::default()
+        };
+        let prices = PriceMap::new();
+        let data = ReportData {
+            rewards: &[],
+            categorized: &categorized,
+            mev_claims: &[],
+            bam_claims: &[],
+            leader_fees: &[],
+            doublezero_fees: &[],
+            vote_costs: &[],
+            expenses: &[],
+            prices: &prices,
+            sfdp_acceptance_date: None,
+        };
+
+        assert!((remaining_seed_capital(&data, &config) - 111.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn split_withdrawal_taxes_only_the_owner_draw_share() {
+        let config = test_config();
+        let categorized = CategorizedTransfers {
+            withdrawals: vec![transfer("sig-mixed", "VOTE", "PW", 10.0, "Personal Wallet")],
+            splits: vec![WithdrawalSplit {
+                signature: "sig-mixed".into(),
+                business_pct: 40.0,
+                category: ExpenseCategory::Hardware,
+                description: "NVMe drives".into(),
+            }],
+            ..Default::default()
+        };
+        let prices: PriceMap = HashMap::from([(String::from("2026-02-28"), 100.0.into())]);
+        let expenses = split_expenses(&categorized, &prices);
+        assert_eq!(expenses.len(), 1);
+        assert!((expenses[0].amount_usd - 400.0).abs() < 1e-9);
+
+        let data = ReportData {
+            rewards: &[],
+            categorized: &categorized,
+            mev_claims: &[],
+            bam_claims: &[],
+            leader_fees: &[],
+            doublezero_fees: &[],
+            vote_costs: &[],
+            expenses: &expenses,
+            prices: &prices,
+            sfdp_acceptance_date: None,
+        };
+        let rows = build_tax_rows(&data, &config);
+        let revenue: f64 = rows
+            .iter()
+            .filter(|r| r.entry_type == "Revenue")
+            .filter_map(|r| r.sol_amount)
+            .sum();
+        assert!((revenue - 6.0).abs() < 1e-9);
+        assert!(
+            rows.iter()
+                .any(|r| r.entry_type == "Expense" && r.category == "Hardware" && (r.usd_value - 400.0).abs() < 1e-9)
+        );
+    }
+
+    #[test]
+    fn timeline_events_carry_their_evidence() {
+        let categorized = CategorizedTransfers {
+            withdrawals: vec![transfer("sig-full", "VOTE", "PW", 1.25, "Personal Wallet")],
+            ..Default::default()
+        };
+        let prices: PriceMap = HashMap::from([(String::from("2026-02-28"), 100.0.into())]);
+        let rewards = vec![EpochReward {
+            epoch: 912,
+            amount_sol: 0.5,
+            commission: 5,
+            date: Some("2026-02-28".to_string()),
+        }];
+        let data = ReportData {
+            rewards: &rewards,
+            categorized: &categorized,
+            mev_claims: &[],
+            bam_claims: &[],
+            leader_fees: &[],
+            doublezero_fees: &[],
+            vote_costs: &[],
+            expenses: &[],
+            prices: &prices,
+            sfdp_acceptance_date: None,
+        };
+
+        let timeline = build_timeline(&data);
+        let reward = timeline.iter().find(|e| e.event_type == "commission").unwrap();
+        assert_eq!(reward.signature, None);
+        assert_eq!(reward.source_id, "epoch_rewards:912");
+        assert_eq!(reward.detail.lamports, Some(500_000_000));
+        assert_eq!(reward.detail.price_usd, Some(100.0));
+
+        let withdrawal = timeline.iter().find(|e| e.event_type == "withdrawal").unwrap();
+        assert_eq!(withdrawal.signature.as_deref(), Some("sig-full"));
+        assert_eq!(withdrawal.source_id, "sol_transfers:sig-full");
+        assert_eq!(withdrawal.detail.lamports, Some(1_250_000_000));
+        assert!(withdrawal.detail.provenance.contains("Personal Wallet (PW)"));
+
+        let tax = build_tax_timeline(&data, &test_config());
+        let distribution = tax.iter().find(|e| e.event_type == "tax_revenue").unwrap();
+        assert_eq!(distribution.signature.as_deref(), Some("sig-full"));
+        assert_eq!(distribution.source_id, "sol_transfers:sig-full");
+    }
+
     #[test]
     fn withdrawal_rows_merge_same_signature_and_destination() {
-        let prices: PriceMap = HashMap::from([(String::from("2026-01-22"), 100.0)]);
+        let prices: PriceMap = HashMap::from([(String::from("2026-01-22"), 100.0.into())]);
         let mut rows = Vec::new();
 
         let t1 = transfer("sig-merge", "WA", "DEST", 2.0, "DestLabel");
@@ -974,7 +1391,7 @@ bootstrap_date = "2025-11-19"
 
     #[test]
     fn withdrawal_rows_drop_fee_delta_mirror_duplicates() {
-        let prices: PriceMap = HashMap::from([(String::from("2026-01-22"), 100.0)]);
+        let prices: PriceMap = HashMap::from([(String::from("2026-01-22"), 100.0.into())]);
         let mut rows = Vec::new();
 
         // Mirror duplicate artifacts for the same path in one tx:
@@ -2,14 +2,16 @@
 //!
 //! These mirror the validator-accounting types but use `String` for addresses
 //! instead of `solana_sdk::Pubkey`, keeping bp-web free of Solana SDK dependencies.
+//! Table row types double as GraphQL objects for `/financials/graphql`.
 
+use async_graphql::{Enum, SimpleObject};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 // ── Revenue types ───────────────────────────────────────────────────────────
 
 /// Staking commission earned per epoch.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, SimpleObject)]
 pub struct EpochReward {
     pub epoch: u64,
     pub amount_sol: f64,
@@ -18,7 +20,7 @@ pub struct EpochReward {
 }
 
 /// Block production fees earned per epoch.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, SimpleObject)]
 pub struct EpochLeaderFees {
     pub epoch: u64,
     pub total_fees_sol: f64,
@@ -28,7 +30,7 @@ pub struct EpochLeaderFees {
 }
 
 /// Jito MEV tips commission per epoch.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, SimpleObject)]
 pub struct MevClaim {
     pub epoch: u64,
     pub amount_sol: f64,
@@ -38,7 +40,7 @@ pub struct MevClaim {
 }
 
 /// Jito BAM reward (jitoSOL) per epoch.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, SimpleObject)]
 pub struct BamClaim {
     pub epoch: u64,
     pub amount_sol_equivalent: f64,
@@ -48,10 +50,21 @@ pub struct BamClaim {
     pub date: Option<String>,
 }
 
+/// Income per source for one calendar month, in SOL (BAM at its SOL equivalent).
+#[derive(Debug, Clone, Default, PartialEq, SimpleObject)]
+pub struct MonthlyRevenue {
+    /// YYYY-MM
+    pub month: String,
+    pub commission_sol: f64,
+    pub leader_fees_sol: f64,
+    pub mev_sol: f64,
+    pub bam_sol: f64,
+}
+
 // ── Expense types ───────────────────────────────────────────────────────────
 
 /// On-chain vote transaction costs per epoch.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, SimpleObject)]
 pub struct EpochVoteCost {
     pub epoch: u64,
     pub vote_count: u64,
@@ -60,8 +73,25 @@ pub struct EpochVoteCost {
     pub date: Option<String>,
 }
 
-/// DoubleZero block-reward-sharing fee per epoch.
+/// Vote credits and delegated stake of one epoch (either may be unknown).
 #[derive(Debug, Clone)]
+pub struct EpochPerformance {
+    pub epoch: u64,
+    pub vote_credits: Option<u64>,
+    pub active_stake_lamports: Option<u64>,
+}
+
+/// Effective stake of one delegator (withdraw authority) in one snapshot epoch.
+#[derive(Debug, Clone)]
+pub struct DelegatorStake {
+    pub epoch: u64,
+    pub delegator: String,
+    pub stake_lamports: u64,
+    pub accounts: u32,
+}
+
+/// DoubleZero block-reward-sharing fee per epoch.
+#[derive(Debug, Clone, SimpleObject)]
 pub struct DoubleZeroFee {
     pub epoch: u64,
     pub liability_sol: f64,
@@ -72,13 +102,15 @@ pub struct DoubleZeroFee {
 }
 
 /// Off-chain expense category.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Enum)]
 pub enum ExpenseCategory {
     Hosting,
     Contractor,
     Hardware,
     Software,
     VoteFees,
+    FailedTxFees,
+    OpsFees,
     Other,
 }
 
@@ -90,12 +122,40 @@ impl std::fmt::Display for ExpenseCategory {
             Self::Hardware => write!(f, "Hardware"),
             Self::Software => write!(f, "Software"),
             Self::VoteFees => write!(f, "Vote Fees"),
+            Self::FailedTxFees => write!(f, "Failed Tx Fees"),
+            Self::OpsFees => write!(f, "Ops Fees"),
             Self::Other => write!(f, "Other"),
         }
     }
 }
 
 impl ExpenseCategory {
+    /// Every category, in the order forms list them
+    pub const ALL: [Self; 8] = [
+        Self::Hosting,
+        Self::Contractor,
+        Self::Hardware,
+        Self::Software,
+        Self::VoteFees,
+        Self::FailedTxFees,
+        Self::OpsFees,
+        Self::Other,
+    ];
+
+    /// Name stored in cache.sqlite (validator-accounting's variant name)
+    pub fn db_name(self) -> &'static str {
+        match self {
+            Self::Hosting => "Hosting",
+            Self::Contractor => "Contractor",
+            Self::Hardware => "Hardware",
+            Self::Software => "Software",
+            Self::VoteFees => "VoteFees",
+            Self::FailedTxFees => "FailedTxFees",
+            Self::OpsFees => "OpsFees",
+            Self::Other => "Other",
+        }
+    }
+
     pub fn from_str_lossy(s: &str) -> Self {
         match s.to_lowercase().as_str() {
             "hosting" => Self::Hosting,
@@ -103,13 +163,15 @@ impl ExpenseCategory {
             "hardware" => Self::Hardware,
             "software" => Self::Software,
             "votefees" | "vote fees" => Self::VoteFees,
+            "failedtxfees" | "failed tx fees" => Self::FailedTxFees,
+            "opsfees" | "ops fees" => Self::OpsFees,
             _ => Self::Other,
         }
     }
 }
 
 /// Off-chain expense entry (hosting, contractors, etc.).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, SimpleObject)]
 pub struct Expense {
     pub date: String,
     pub vendor: String,
@@ -121,7 +183,7 @@ pub struct Expense {
 }
 
 /// Recurring expense template that expands into monthly `Expense` entries.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, SimpleObject)]
 pub struct RecurringExpense {
     pub vendor: String,
     pub category: ExpenseCategory,
@@ -135,7 +197,7 @@ pub struct RecurringExpense {
 // ── Transfer types ──────────────────────────────────────────────────────────
 
 /// SOL transfer between addresses (read from cache.sqlite sol_transfers table).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, SimpleObject)]
 pub struct SolTransfer {
     pub signature: String,
     pub date: Option<String>,
@@ -144,6 +206,13 @@ pub struct SolTransfer {
     pub amount_sol: f64,
     pub from_label: String,
     pub to_label: String,
+    /// Address categories as stored by validator-accounting (e.g. "Exchange")
+    pub from_category: String,
+    pub to_category: String,
+    /// SPL memo attached to the transaction, if any
+    pub memo: Option<String>,
+    /// Program that moved the SOL (or owns a newly created account), if known
+    pub program: Option<String>,
 }
 
 /// Transfers bucketed by purpose.
@@ -153,16 +222,43 @@ pub struct CategorizedTransfers {
     pub sfdp_reimbursements: Vec<SolTransfer>,
     pub mev_deposits: Vec<SolTransfer>,
     pub doublezero_payments: Vec<SolTransfer>,
+    pub donations: Vec<SolTransfer>,
     pub vote_funding: Vec<SolTransfer>,
     pub withdrawals: Vec<SolTransfer>,
+    /// Rent-exempt deposits for new accounts and refunds from closed ones
+    pub rent: Vec<SolTransfer>,
     pub other: Vec<SolTransfer>,
+    /// Owner draw vs business expense splits of withdrawals (`withdrawal_splits`)
+    pub splits: Vec<WithdrawalSplit>,
+}
+
+impl CategorizedTransfers {
+    /// The split recorded for a withdrawal, if any
+    pub fn split_for(&self, signature: &str) -> Option<&WithdrawalSplit> {
+        self.splits.iter().find(|s| s.signature == signature)
+    }
+
+    /// Fraction of a withdrawal that left as an owner draw (1.0 unless split)
+    pub fn draw_fraction(&self, signature: &str) -> f64 {
+        self.split_for(signature).map_or(1.0, |s| 1.0 - s.business_pct / 100.0)
+    }
+}
+
+/// Share of a mixed-use withdrawal that reimbursed a business expense.
+#[derive(Debug, Clone)]
+pub struct WithdrawalSplit {
+    pub signature: String,
+    /// Percent of the withdrawal that was a business expense; the rest is an owner draw
+    pub business_pct: f64,
+    pub category: ExpenseCategory,
+    pub description: String,
 }
 
 // ── Timeline event (matches html_report_template.html contract) ─────────────
 
 /// One atomic financial event in the timeline.
 ///
-/// The JS frontend expects this exact shape via `__TIMELINE_JSON__`.
+/// The JS frontend expects this exact shape via `{{ timeline|tojson }}`.
 #[derive(Debug, Clone, Serialize)]
 pub struct TimelineEvent {
     pub date: String,
@@ -172,12 +268,32 @@ pub struct TimelineEvent {
     pub sublabel: Option<String>,
     pub amount_sol: f64,
     pub amount_usd: f64,
+    /// How `amount_usd` was priced (None for USD-denominated events)
+    pub price_source: Option<PriceSource>,
+    /// Full transaction signature when the event is a single on-chain transaction
+    pub signature: Option<String>,
+    /// cache.sqlite record the event was built from, as `table:key`
+    /// (e.g. `epoch_rewards:912`, `sol_transfers:<signature>`)
+    pub source_id: String,
+    pub detail: EventDetail,
     pub cumulative_profit_usd: f64,
     pub cumulative_revenue_usd: f64,
     pub cumulative_expenses_usd: f64,
     pub is_pnl: bool,
 }
 
+/// Evidence behind a timeline amount, shown in the row's expandable detail pane.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EventDetail {
+    /// Amount as recorded at the source, in lamports (before SFDP offsets or signs);
+    /// None for USD-denominated events
+    pub lamports: Option<u64>,
+    /// SOL/USD price the event was valued at
+    pub price_usd: Option<f64>,
+    /// Where the number comes from
+    pub provenance: String,
+}
+
 // ── Tax row (intermediate for tax timeline) ─────────────────────────────────
 
 /// A single row in the tax computation before timeline conversion.
@@ -189,45 +305,146 @@ pub struct TaxRow {
     pub description: String,
     pub sol_amount: Option<f64>,
     pub sol_price_usd: Option<f64>,
+    pub price_source: Option<PriceSource>,
     pub usd_value: f64,
     pub destination: String,
     pub tx_signature: String,
+    /// Record the row was built from (see `TimelineEvent::source_id`)
+    pub source_id: String,
+    /// How the amount was derived, for the timeline detail pane
+    pub provenance: String,
 }
 
 // ── Prices ──────────────────────────────────────────────────────────────────
 
 /// Daily SOL/USD prices keyed by ISO date string.
-pub type PriceMap = HashMap<String, f64>;
+pub type PriceMap = HashMap<String, CachedPrice>;
+
+/// A cached daily price and how validator-accounting stored it (`prices.source`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CachedPrice {
+    pub usd_price: f64,
+    pub source: PriceSource,
+}
+
+impl From<f64> for CachedPrice {
+    fn from(usd_price: f64) -> Self {
+        Self {
+            usd_price,
+            source: PriceSource::Exact,
+        }
+    }
+}
 
 /// Fallback price when date is missing from the cache.
 const FALLBACK_PRICE: f64 = 170.0;
 
-/// Look up the SOL/USD price for a date, falling back gracefully.
-pub fn get_price(prices: &PriceMap, date: &str) -> f64 {
-    if let Some(&p) = prices.get(date) {
-        return p;
+/// Furthest the nearest cached date may be from a date without a price (validator-accounting
+/// `DAILY_PRICE_MAX_GAP_DAYS`).
+const MAX_NEAREST_GAP_DAYS: i64 = 3;
+
+/// How a USD price was obtained (mirrors validator-accounting `PriceSource`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PriceSource {
+    Exact,
+    Interpolated,
+    Fallback,
+}
+
+impl PriceSource {
+    /// Parse a stored `prices.source` value.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "exact" => Some(Self::Exact),
+            "interpolated" => Some(Self::Interpolated),
+            "fallback" => Some(Self::Fallback),
+            _ => None,
+        }
+    }
+}
+
+/// Look up the SOL/USD price for a date (falling back gracefully) along with how it was obtained.
+pub fn get_price_with_source(prices: &PriceMap, date: &str) -> (f64, PriceSource) {
+    if let Some(cached) = prices.get(date) {
+        return (cached.usd_price, cached.source);
     }
 
     // Match validator-accounting behavior exactly:
-    // use the closest available cached date (no fixed +/- window).
-    if let Ok(target) = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d") {
-        let mut closest_price = FALLBACK_PRICE;
-        let mut closest_diff = i64::MAX;
-
-        for (d, p) in prices {
-            if let Ok(cached_date) = chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d") {
-                let diff = (target - cached_date).num_days().abs();
-                if diff < closest_diff {
-                    closest_diff = diff;
-                    closest_price = *p;
-                }
+    // use the closest cached date within `MAX_NEAREST_GAP_DAYS`.
+    let Ok(target) = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d") else {
+        return (FALLBACK_PRICE, PriceSource::Fallback);
+    };
+
+    let mut closest: Option<(i64, CachedPrice)> = None;
+    for (d, p) in prices {
+        if let Ok(cached_date) = chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d") {
+            let diff = (target - cached_date).num_days().abs();
+            if diff <= MAX_NEAREST_GAP_DAYS && closest.is_none_or(|(best, _)| diff < best) {
+                closest = Some((diff, *p));
             }
         }
+    }
 
-        return closest_price;
+    match closest {
+        Some((_, p)) if p.source == PriceSource::Fallback => (p.usd_price, PriceSource::Fallback),
+        Some((_, p)) => (p.usd_price, PriceSource::Interpolated),
+        None => (FALLBACK_PRICE, PriceSource::Fallback),
     }
+}
+
+// ── Ingestion history ───────────────────────────────────────────────────────
+
+/// One validator-accounting ingestion attempt (read from `ingestion_runs`).
+#[derive(Debug, Clone)]
+pub struct IngestionRun {
+    pub id: i64,
+    pub triggered_by: String,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub status: String,
+    pub row_counts: BTreeMap<String, u64>,
+    pub errors: Vec<String>,
+}
+
+// ── Epoch coverage ──────────────────────────────────────────────────────────
+
+/// First epoch with BAM rewards (mirrors validator-accounting `BAM_FIRST_EPOCH`).
+pub const BAM_FIRST_EPOCH: u64 = 912;
+
+/// First epoch with DoubleZero fees (mirrors validator-accounting `DOUBLEZERO_FIRST_EPOCH`).
+pub const DOUBLEZERO_FIRST_EPOCH: u64 = 859;
 
-    FALLBACK_PRICE
+/// Which per-epoch sources have a row in `cache.sqlite` for one epoch.
+#[derive(Debug, Clone, Default)]
+pub struct EpochCoverage {
+    pub epoch: u64,
+    pub rewards: bool,
+    pub leader_fees: bool,
+    pub mev: bool,
+    pub bam: bool,
+    pub vote_costs: bool,
+    pub doublezero: bool,
+}
+
+impl EpochCoverage {
+    pub fn bam_applies(&self) -> bool {
+        self.epoch >= BAM_FIRST_EPOCH
+    }
+
+    pub fn doublezero_applies(&self) -> bool {
+        self.epoch >= DOUBLEZERO_FIRST_EPOCH
+    }
+
+    /// True if any applicable source is missing.
+    pub fn has_gaps(&self) -> bool {
+        !(self.rewards
+            && self.leader_fees
+            && self.mev
+            && self.vote_costs
+            && (self.bam || !self.bam_applies())
+            && (self.doublezero || !self.doublezero_applies()))
+    }
 }
 
 // ── Report data bundle ──────────────────────────────────────────────────────
@@ -0,0 +1,133 @@
+//! Unit economics KPIs for the `/financials` dashboard (mirrors validator-accounting's
+//! `unit_economics.rs`; the ratios themselves live in `report_template`).
+//!
+//! Leader slots are produced + skipped blocks. Delegated stake is averaged over the
+//! epochs that earned commission in the business window.
+
+use report_template::{UnitEconomics, UnitTotals};
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::types::{EpochPerformance, ReportData, get_price_with_source};
+
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+fn price(data: &ReportData, date: &Option<String>) -> f64 {
+    date.as_deref().map_or(0.0, |d| get_price_with_source(data.prices, d).0)
+}
+
+/// KPIs over everything in `data`
+pub fn compute(data: &ReportData, performance: &[EpochPerformance]) -> UnitEconomics {
+    let mut t = UnitTotals::default();
+
+    for r in data.rewards {
+        t.revenue_sol += r.amount_sol;
+        t.revenue_usd += r.amount_sol * price(data, &r.date);
+    }
+    for f in data.leader_fees {
+        t.revenue_sol += f.total_fees_sol;
+        t.revenue_usd += f.total_fees_sol * price(data, &f.date);
+        t.blocks_produced += f.blocks_produced;
+        t.leader_slots += f.blocks_produced + f.skipped_slots;
+    }
+    for c in data.mev_claims {
+        t.revenue_sol += c.amount_sol;
+        t.revenue_usd += c.amount_sol * price(data, &c.date);
+    }
+    for c in data.bam_claims {
+        t.revenue_sol += c.amount_sol_equivalent;
+        t.revenue_usd += c.amount_sol_equivalent * price(data, &c.date);
+    }
+
+    let credits: BTreeMap<u64, u64> = performance
+        .iter()
+        .filter_map(|p| Some((p.epoch, p.vote_credits?)))
+        .collect();
+    for v in data.vote_costs {
+        t.costs_usd += v.total_fee_sol * price(data, &v.date);
+        if let Some(&epoch_credits) = credits.get(&v.epoch) {
+            t.credited_vote_fee_lamports += (v.total_fee_sol * LAMPORTS_PER_SOL).round() as u64;
+            t.vote_credits += epoch_credits;
+        }
+    }
+    for d in data.doublezero_fees {
+        t.costs_usd += d.liability_sol * price(data, &d.date);
+    }
+    t.costs_usd += data.expenses.iter().map(|e| e.amount_usd).sum::<f64>();
+
+    let reward_epochs: BTreeSet<u64> = data.rewards.iter().map(|r| r.epoch).collect();
+    for p in performance {
+        if let Some(stake) = p.active_stake_lamports
+            && reward_epochs.contains(&p.epoch)
+        {
+            t.delegated_sol_epochs += stake as f64 / LAMPORTS_PER_SOL;
+            t.stake_epochs += 1;
+        }
+    }
+
+    UnitEconomics::from_totals(&t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::financials::types::{CategorizedTransfers, EpochLeaderFees, EpochReward, EpochVoteCost, PriceMap};
+
+    #[test]
+    fn credits_only_count_epochs_with_vote_fees() {
+        let date = Some("2025-06-01".to_string());
+        let rewards = vec![EpochReward {
+            epoch: 800,
+            amount_sol: 2.0,
+            commission: 5,
+            date: date.clone(),
+        }];
+        let leader_fees = vec![EpochLeaderFees {
+            epoch: 800,
+            total_fees_sol: 2.0,
+            blocks_produced: 36,
+            skipped_slots: 4,
+            date: date.clone(),
+        }];
+        let vote_costs = vec![EpochVoteCost {
+            epoch: 800,
+            vote_count: 400_000,
+            total_fee_sol: 2.0,
+            source: "measured".into(),
+            date: date.clone(),
+        }];
+        let performance = vec![
+            EpochPerformance {
+                epoch: 800,
+                vote_credits: Some(4_000_000),
+                active_stake_lamports: Some(100_000 * 1_000_000_000),
+            },
+            // No vote fees cached for this epoch, so its credits are left out
+            EpochPerformance {
+                epoch: 801,
+                vote_credits: Some(4_000_000),
+                active_stake_lamports: None,
+            },
+        ];
+        let prices = PriceMap::from([("2025-06-01".to_string(), 100.0.into())]);
+        let categorized = CategorizedTransfers::default();
+        let data = ReportData {
+            rewards: &rewards,
+            categorized: &categorized,
+            mev_claims: &[],
+            bam_claims: &[],
+            leader_fees: &leader_fees,
+            doublezero_fees: &[],
+            vote_costs: &vote_costs,
+            expenses: &[],
+            prices: &prices,
+            sfdp_acceptance_date: None,
+        };
+
+        let kpis = compute(&data, &performance);
+        assert_eq!(kpis.leader_slots, 40);
+        assert_eq!(kpis.revenue_per_leader_slot_sol, Some(0.1));
+        assert_eq!(kpis.vote_fee_per_credit_lamports, Some(500.0));
+        assert_eq!(kpis.avg_delegated_sol, Some(100_000.0));
+        assert_eq!(kpis.usd_per_delegated_sol, Some(0.004));
+    }
+}
@@ -0,0 +1,170 @@
+//! Identity top-up recommendation for vote fees (mirrors validator-accounting's
+//! `vote_topup.rs`).
+//!
+//! Projects vote fees for the rest of the epoch plus one buffer epoch from recent
+//! cached vote costs and compares them with the identity balance. When it falls
+//! short, the admin page shows "transfer X SOL to identity by <date>", the date
+//! being when the current balance runs out.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::config::ValidatorConfig;
+use super::db;
+use super::types::EpochVoteCost;
+
+/// Recent epochs the vote fee run-rate is averaged over
+const RUN_RATE_EPOCHS: usize = 5;
+
+/// Full epochs of vote fees to hold beyond the current one
+const BUFFER_EPOCHS: f64 = 1.0;
+
+/// Vote fees per epoch when no measured epochs are cached (~431k votes × 5000 lamports)
+const TYPICAL_COST_PER_EPOCH_SOL: f64 = 2.155;
+
+/// Rent-exempt reserve of a 3762-byte vote account
+const VOTE_ACCOUNT_RENT_LAMPORTS: u64 = 27_074_400;
+
+/// Target slot time used to project slots onto the wall clock
+const SLOT_DURATION_MS: f64 = 400.0;
+
+/// Identity runway against projected vote fees
+#[derive(Debug, Clone, Serialize)]
+pub struct VoteTopUp {
+    pub identity_balance_sol: f64,
+    /// Vote account balance above its rent-exempt reserve (a funding source)
+    pub vote_withdrawable_sol: f64,
+    pub cost_per_epoch_sol: f64,
+    pub rest_of_epoch_cost_sol: f64,
+    /// Rest of the epoch plus the buffer epoch
+    pub target_sol: f64,
+    /// SOL to move to identity (0 when the balance already covers the target)
+    pub transfer_sol: f64,
+    /// UTC date the identity balance runs out at the current rate
+    pub runs_out_on: Option<String>,
+}
+
+/// Average vote cost over the latest measured (non-estimated) epochs
+fn recent_cost_per_epoch(costs: &[EpochVoteCost]) -> f64 {
+    let mut measured: Vec<&EpochVoteCost> = costs.iter().filter(|c| c.source != "estimated").collect();
+    measured.sort_by_key(|c| std::cmp::Reverse(c.epoch));
+    measured.truncate(RUN_RATE_EPOCHS);
+    if measured.is_empty() {
+        return TYPICAL_COST_PER_EPOCH_SOL;
+    }
+    measured.iter().map(|c| c.total_fee_sol).sum::<f64>() / measured.len() as f64
+}
+
+fn assess(
+    identity_lamports: u64,
+    vote_lamports: u64,
+    cost_per_epoch_sol: f64,
+    slot_index: u64,
+    slots_in_epoch: u64,
+    now: DateTime<Utc>,
+) -> VoteTopUp {
+    let identity_balance_sol = identity_lamports as f64 / 1e9;
+    let cost_per_slot = if slots_in_epoch == 0 {
+        0.0
+    } else {
+        cost_per_epoch_sol / slots_in_epoch as f64
+    };
+    let rest_of_epoch_cost_sol = slots_in_epoch.saturating_sub(slot_index) as f64 * cost_per_slot;
+    let target_sol = rest_of_epoch_cost_sol + BUFFER_EPOCHS * cost_per_epoch_sol;
+    let transfer_sol = ((target_sol - identity_balance_sol).max(0.0) * 100.0).ceil() / 100.0;
+    let runs_out_on = (cost_per_slot > 0.0).then(|| {
+        let ms = identity_balance_sol / cost_per_slot * SLOT_DURATION_MS;
+        (now + chrono::Duration::milliseconds(ms as i64))
+            .format("%Y-%m-%d")
+            .to_string()
+    });
+
+    VoteTopUp {
+        identity_balance_sol,
+        vote_withdrawable_sol: vote_lamports.saturating_sub(VOTE_ACCOUNT_RENT_LAMPORTS) as f64 / 1e9,
+        cost_per_epoch_sol,
+        rest_of_epoch_cost_sol,
+        target_sol,
+        transfer_sol,
+        runs_out_on,
+    }
+}
+
+/// Current recommendation from live balances and cached vote costs
+pub async fn vote_topup(data_dir: &str) -> Result<VoteTopUp> {
+    let config_path = std::path::Path::new(data_dir).join("config.toml");
+    let config = ValidatorConfig::load(&config_path)?;
+    let pool = db::init_cache(data_dir).await?;
+    let costs = db::get_vote_costs(pool).await?;
+
+    let (identity, vote, epoch) = futures::join!(
+        crate::api::get_balance(&config.identity),
+        crate::api::get_balance(&config.vote_account),
+        crate::api::get_epoch_info(),
+    );
+    let epoch = epoch.context("Failed to fetch the current epoch")?;
+    Ok(assess(
+        identity.context("Failed to fetch the identity balance")?,
+        vote.context("Failed to fetch the vote account balance")?,
+        recent_cost_per_epoch(&costs),
+        epoch.slot_index,
+        epoch.slots_in_epoch,
+        Utc::now(),
+    ))
+}
+
+/// One-line recommendation for the admin page
+pub fn summary(topup: &VoteTopUp) -> String {
+    if topup.transfer_sol > 0.0 {
+        format!(
+            "Transfer {:.2} SOL to identity by {} (holds {:.4} SOL; rest of epoch + {} buffer epoch needs {:.4} SOL; vote account has {:.4} SOL withdrawable)",
+            topup.transfer_sol,
+            topup.runs_out_on.as_deref().unwrap_or("as soon as possible"),
+            topup.identity_balance_sol,
+            BUFFER_EPOCHS,
+            topup.target_sol,
+            topup.vote_withdrawable_sol
+        )
+    } else {
+        format!(
+            "Identity holds {:.4} SOL, enough for projected vote fees (~{:.4} SOL/epoch)",
+            topup.identity_balance_sol, topup.cost_per_epoch_sol
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recommends_a_transfer_when_identity_falls_short() {
+        let now = DateTime::from_timestamp(1_767_225_600, 0).unwrap(); // 2026-01-01 00:00 UTC
+        let topup = assess(1_500_000_000, 10_027_074_400, 2.0, 216_000, 432_000, now);
+        assert!((topup.transfer_sol - 1.5).abs() < 1e-9);
+        assert_eq!(topup.runs_out_on.as_deref(), Some("2026-01-02"));
+        assert!((topup.vote_withdrawable_sol - 10.0).abs() < 1e-9);
+        assert!(summary(&topup).starts_with("Transfer 1.50 SOL to identity by 2026-01-02"));
+
+        assert_eq!(assess(5_000_000_000, 0, 2.0, 216_000, 432_000, now).transfer_sol, 0.0);
+    }
+
+    #[test]
+    fn run_rate_skips_estimates() {
+        let cost = |epoch, total_fee_sol, source: &str| EpochVoteCost {
+            epoch,
+            vote_count: 0,
+            total_fee_sol,
+            source: source.to_string(),
+            date: None,
+        };
+        let costs = vec![
+            cost(900, 2.0, "rpc"),
+            cost(901, 2.4, "dune"),
+            cost(902, 9.0, "estimated"),
+        ];
+        assert!((recent_cost_per_epoch(&costs) - 2.2).abs() < 1e-12);
+        assert_eq!(recent_cost_per_epoch(&[]), TYPICAL_COST_PER_EPOCH_SOL);
+    }
+}
@@ -0,0 +1,201 @@
+//! Withdrawal planner for `/financials/plan` (mirrors validator-accounting's
+//! `withdrawal_plan.rs`).
+//!
+//! Given a USD target, shows the SOL to move at today's price and how it splits
+//! under the tax timeline's method: remaining seed capital comes back first
+//! (non-taxable), the rest is taxable revenue. The tax estimate is a flat rate
+//! from `[reporting] estimated_tax_rate` or the form, not tax advice.
+
+use anyhow::{Result, ensure};
+use serde::Serialize;
+
+use super::admin::{STYLE, escape_html};
+use super::types::PriceSource;
+
+/// Remaining seed capital and today's price, taken from one timeline snapshot
+#[derive(Debug, Clone, Copy)]
+pub struct PlanningInputs {
+    pub remaining_capital_sol: f64,
+    pub sol_price_usd: f64,
+    pub price_source: PriceSource,
+    /// `[reporting] estimated_tax_rate`, the form's default
+    pub default_tax_rate: Option<f64>,
+}
+
+/// What a withdrawal of `target_usd` would look like today
+#[derive(Debug, Clone, Serialize)]
+pub struct WithdrawalPlan {
+    pub target_usd: f64,
+    pub sol_price_usd: f64,
+    pub price_source: PriceSource,
+    /// SOL to move out of the business accounts
+    pub sol_to_move: f64,
+    /// Portion that returns seed capital (non-taxable)
+    pub return_of_capital_sol: f64,
+    pub return_of_capital_usd: f64,
+    /// Portion beyond the remaining seed capital (taxable revenue)
+    pub taxable_sol: f64,
+    pub taxable_usd: f64,
+    pub tax_rate: Option<f64>,
+    pub estimated_tax_usd: Option<f64>,
+    /// Seed capital (SOL) not yet returned, before and after this withdrawal
+    pub remaining_capital_before_sol: f64,
+    pub remaining_capital_after_sol: f64,
+}
+
+/// Split a USD withdrawal into return of capital and taxable revenue
+pub fn plan_withdrawal(inputs: &PlanningInputs, target_usd: f64, tax_rate: Option<f64>) -> Result<WithdrawalPlan> {
+    ensure!(
+        target_usd.is_finite() && target_usd > 0.0,
+        "Withdrawal amount must be a positive USD amount"
+    );
+    ensure!(
+        inputs.sol_price_usd.is_finite() && inputs.sol_price_usd > 0.0,
+        "No usable SOL price"
+    );
+    if let Some(rate) = tax_rate {
+        ensure!((0.0..=1.0).contains(&rate), "Tax rate must be between 0 and 1");
+    }
+
+    let price = inputs.sol_price_usd;
+    let remaining = inputs.remaining_capital_sol.max(0.0);
+    let sol_to_move = target_usd / price;
+    let return_of_capital_sol = sol_to_move.min(remaining);
+    let taxable_sol = sol_to_move - return_of_capital_sol;
+    let taxable_usd = taxable_sol * price;
+
+    Ok(WithdrawalPlan {
+        target_usd,
+        sol_price_usd: price,
+        price_source: inputs.price_source,
+        sol_to_move,
+        return_of_capital_sol,
+        return_of_capital_usd: return_of_capital_sol * price,
+        taxable_sol,
+        taxable_usd,
+        tax_rate,
+        estimated_tax_usd: tax_rate.map(|rate| taxable_usd * rate),
+        remaining_capital_before_sol: remaining,
+        remaining_capital_after_sol: remaining - return_of_capital_sol,
+    })
+}
+
+fn render_plan(plan: &WithdrawalPlan) -> String {
+    let tax = match (plan.tax_rate, plan.estimated_tax_usd) {
+        (Some(rate), Some(tax)) => format!(
+            "<tr><th>Estimated tax</th><td>${:.2} at {:.1}% (net ${:.2})</td></tr>",
+            tax,
+            rate * 100.0,
+            plan.target_usd - tax
+        ),
+        _ => "<tr><th>Estimated tax</th><td class='muted'>enter a tax rate</td></tr>".to_string(),
+    };
+    let warning = if plan.price_source == PriceSource::Fallback {
+        "<p class='status-failed'>No cached SOL price near today; the plan uses a fallback price.</p>"
+    } else {
+        ""
+    };
+    format!(
+        concat!(
+            "<table>",
+            "<tr><th>SOL to move</th><td>{:.6} SOL at ${:.2}/SOL ({})</td></tr>",
+            "<tr><th>Return of capital</th><td>{:.6} SOL = ${:.2} (non-taxable)</td></tr>",
+            "<tr><th>Taxable revenue</th><td>{:.6} SOL = ${:.2}</td></tr>",
+            "{}",
+            "<tr><th>Seed capital left</th><td>{:.6} SOL before, {:.6} SOL after</td></tr>",
+            "</table>{}"
+        ),
+        plan.sol_to_move,
+        plan.sol_price_usd,
+        format!("{:?}", plan.price_source).to_lowercase(),
+        plan.return_of_capital_sol,
+        plan.return_of_capital_usd,
+        plan.taxable_sol,
+        plan.taxable_usd,
+        tax,
+        plan.remaining_capital_before_sol,
+        plan.remaining_capital_after_sol,
+        warning
+    )
+}
+
+/// The planner page: the form (pre-filled with the last request), then the plan or error
+pub fn render_page(
+    inputs: Option<&PlanningInputs>,
+    usd: Option<f64>,
+    tax_rate: Option<f64>,
+    outcome: Option<&Result<WithdrawalPlan>>,
+) -> String {
+    let body = match outcome {
+        None => String::new(),
+        Some(Ok(plan)) => render_plan(plan),
+        Some(Err(e)) => format!("<p class='status-failed'>{}</p>", escape_html(&format!("{:#}", e))),
+    };
+    let capital = inputs.map_or(String::new(), |i| {
+        format!(
+            "<p class='muted'>{:.6} SOL of seed capital has not been returned yet.</p>",
+            i.remaining_capital_sol
+        )
+    });
+    let tax_rate = tax_rate.or(inputs.and_then(|i| i.default_tax_rate));
+
+    format!(
+        concat!(
+            "<!DOCTYPE html><html lang='en'><head><meta charset='utf-8'>",
+            "<meta name='viewport' content='width=device-width, initial-scale=1'>",
+            "<title>Block Parliament · Withdrawal Planner</title>",
+            "<link rel='icon' type='image/png' href='/logo/owl-64.png'>",
+            "<style>{}input{{font:inherit;background:inherit;color:inherit}}</style>",
+            "</head><body>",
+            "<h1>Withdrawal Planner</h1>",
+            "<p><a href='/financials/admin'>&larr; back to admin</a></p>",
+            "<p class='muted'>Advisory only, not tax advice. Uses the tax timeline's method: ",
+            "withdrawals return remaining seed capital first, the rest is taxable revenue.</p>",
+            "{}",
+            "<form method='get' action='/financials/plan'>",
+            "<label>Withdraw (USD) <input name='usd' type='number' min='0' step='0.01' value='{}' required></label> ",
+            "<label>Tax rate (0-1) <input name='tax_rate' type='number' min='0' max='1' step='0.01' value='{}'></label> ",
+            "<button type='submit'>Plan</button>",
+            "</form>{}</body></html>"
+        ),
+        STYLE,
+        capital,
+        usd.map_or(String::new(), |v| v.to_string()),
+        tax_rate.map_or(String::new(), |v| v.to_string()),
+        body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(remaining_capital_sol: f64) -> PlanningInputs {
+        PlanningInputs {
+            remaining_capital_sol,
+            sol_price_usd: 200.0,
+            price_source: PriceSource::Exact,
+            default_tax_rate: None,
+        }
+    }
+
+    #[test]
+    fn capital_is_returned_before_anything_is_taxable() {
+        let plan = plan_withdrawal(&inputs(10.0), 5_000.0, Some(0.3)).unwrap();
+        assert!((plan.sol_to_move - 25.0).abs() < 1e-9);
+        assert!((plan.return_of_capital_sol - 10.0).abs() < 1e-9);
+        assert!((plan.taxable_usd - 3_000.0).abs() < 1e-6);
+        assert!((plan.estimated_tax_usd.unwrap() - 900.0).abs() < 1e-6);
+        assert_eq!(plan.remaining_capital_after_sol, 0.0);
+
+        let untaxed = plan_withdrawal(&inputs(10.0), 1_000.0, None).unwrap();
+        assert_eq!(untaxed.taxable_sol, 0.0);
+        assert_eq!(untaxed.estimated_tax_usd, None);
+    }
+
+    #[test]
+    fn rejects_unusable_inputs() {
+        assert!(plan_withdrawal(&inputs(0.0), -5.0, None).is_err());
+        assert!(plan_withdrawal(&inputs(0.0), 100.0, Some(2.0)).is_err());
+    }
+}
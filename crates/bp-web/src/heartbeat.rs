@@ -0,0 +1,47 @@
+//! Dead man's switch pings.
+//!
+//! After a job succeeds, its `HEARTBEAT_<JOB>_URL` (a healthchecks.io-style check URL)
+//! is requested, so an external monitor alerts when the pings stop, including when the
+//! whole process is down. Jobs without a URL are not pinged.
+
+use crate::api::http::get_uncached;
+
+/// Metrics ingestion (`ingestion::run_ingestion` wrote a snapshot)
+pub const INGESTION: &str = "ingestion";
+/// Financial cache refresh (validator-accounting run by the scheduler)
+pub const FINANCIALS: &str = "financials";
+/// One full scheduler cycle, every step of which succeeded
+pub const SCHEDULER: &str = "scheduler";
+
+fn env_var(job: &str) -> String {
+    format!("HEARTBEAT_{}_URL", job.to_ascii_uppercase())
+}
+
+fn url(job: &str) -> Option<String> {
+    std::env::var(env_var(job))
+        .ok()
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+}
+
+/// Ping `job`'s heartbeat URL, if one is configured. Failures are logged, never returned:
+/// a missed ping is exactly what the monitor is there to notice.
+pub async fn ping(job: &str) {
+    let Some(url) = url(job) else {
+        return;
+    };
+    if !get_uncached(&url).await {
+        eprintln!("[heartbeat] {} ping failed", job);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_var_names_follow_job() {
+        assert_eq!(env_var(INGESTION), "HEARTBEAT_INGESTION_URL");
+        assert_eq!(env_var(SCHEDULER), "HEARTBEAT_SCHEDULER_URL");
+    }
+}
@@ -0,0 +1,84 @@
+//! Conditional GET (ETag / If-None-Match) for the public API routes.
+//!
+//! Validators are derived from the version of the cached data a response is built
+//! from, so a poller that already has the current data gets a 304 before the response
+//! is recomputed.
+
+#[cfg(feature = "ssr")]
+mod ssr {
+    use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+    use axum::response::{IntoResponse, Response};
+
+    /// Weak validator for `resource` built from data at `version`. Weak because two
+    /// responses built from the same data may still differ byte-for-byte (e.g. times
+    /// projected from the request time).
+    pub fn weak_etag(resource: &str, version: &str) -> String {
+        format!("W/\"{}-{}\"", resource, version)
+    }
+
+    /// Opaque tag with any weak prefix removed (weak comparison, RFC 9110 §8.8.3.2)
+    fn opaque_tag(tag: &str) -> &str {
+        let tag = tag.trim();
+        tag.strip_prefix("W/").unwrap_or(tag)
+    }
+
+    /// Whether the request's `If-None-Match` names `etag` (or is `*`).
+    pub fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+        headers
+            .get_all(header::IF_NONE_MATCH)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .any(|tag| tag.trim() == "*" || opaque_tag(tag) == opaque_tag(etag))
+    }
+
+    /// 304 for a request whose cached copy is still current.
+    pub fn not_modified(etag: &str, cache_control: &'static str) -> Response {
+        with_etag(
+            (StatusCode::NOT_MODIFIED, [(header::CACHE_CONTROL, cache_control)]).into_response(),
+            etag,
+        )
+    }
+
+    /// Attach `etag` to a response.
+    pub fn with_etag(mut response: Response, etag: &str) -> Response {
+        if let Ok(value) = HeaderValue::from_str(etag) {
+            response.headers_mut().insert(header::ETAG, value);
+        }
+        response
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn headers(if_none_match: &str) -> HeaderMap {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::IF_NONE_MATCH, HeaderValue::from_str(if_none_match).unwrap());
+            headers
+        }
+
+        #[test]
+        fn if_none_match_uses_weak_comparison() {
+            let etag = weak_etag("epoch", "912-393984000");
+            assert_eq!(etag, "W/\"epoch-912-393984000\"");
+
+            assert!(if_none_match(&headers("W/\"epoch-912-393984000\""), &etag));
+            assert!(if_none_match(&headers("\"epoch-912-393984000\""), &etag));
+            assert!(if_none_match(&headers("\"other\", W/\"epoch-912-393984000\""), &etag));
+            assert!(if_none_match(&headers("*"), &etag));
+            assert!(!if_none_match(&headers("W/\"epoch-912-393983925\""), &etag));
+            assert!(!if_none_match(&HeaderMap::new(), &etag));
+        }
+
+        #[test]
+        fn not_modified_carries_validator() {
+            let response = not_modified("W/\"epoch-1-2\"", "public, max-age=30");
+            assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+            assert_eq!(response.headers()[header::ETAG], "W/\"epoch-1-2\"");
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub use ssr::*;
@@ -0,0 +1,198 @@
+//! Internationalization for the public site.
+//!
+//! Translations live in `locales/<code>.json` as flat `key -> text` maps and are
+//! compiled into the binary. The request locale is negotiated once per request
+//! (`?lang=` override, then the `lang` cookie, then `Accept-Language`) and provided
+//! as context; components read it with [`use_locale`].
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use leptos::prelude::*;
+
+/// Cookie remembering an explicit `?lang=` choice
+pub const LOCALE_COOKIE: &str = "lang";
+
+/// Supported site languages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+}
+
+type Catalog = HashMap<String, String>;
+
+static CATALOGS: OnceLock<[Catalog; 2]> = OnceLock::new();
+
+fn catalogs() -> &'static [Catalog; 2] {
+    CATALOGS.get_or_init(|| {
+        let parse = |code: &str, json: &str| -> Catalog {
+            serde_json::from_str(json).unwrap_or_else(|e| panic!("invalid locales/{}.json: {}", code, e))
+        };
+        [
+            parse("en", include_str!("../locales/en.json")),
+            parse("es", include_str!("../locales/es.json")),
+        ]
+    })
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Es];
+
+    /// BCP 47 language code, used for `<html lang>` and `?lang=`
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Es => "es",
+        }
+    }
+
+    /// Language name in that language, for the switcher
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Self::En => "English",
+            Self::Es => "Español",
+        }
+    }
+
+    /// Match a language tag by its primary subtag ("es-MX" -> Es)
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.trim().split(['-', '_']).next()?.to_ascii_lowercase();
+        Self::ALL.into_iter().find(|l| l.code() == primary)
+    }
+
+    /// Pick the best supported locale for an `Accept-Language` header, by q-value.
+    /// Falls back to English.
+    pub fn negotiate(accept_language: Option<&str>) -> Self {
+        let Some(header) = accept_language else {
+            return Self::default();
+        };
+
+        let mut ranked: Vec<(Self, f32)> = header
+            .split(',')
+            .filter_map(|item| {
+                let mut parts = item.split(';');
+                let locale = Self::from_tag(parts.next()?)?;
+                let q = parts
+                    .filter_map(|p| p.trim().strip_prefix("q="))
+                    .find_map(|q| q.trim().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (q > 0.0).then_some((locale, q))
+            })
+            .collect();
+        // Stable sort keeps header order among equal q-values
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranked.first().map(|(l, _)| *l).unwrap_or_default()
+    }
+
+    fn catalog(self) -> &'static Catalog {
+        &catalogs()[self as usize]
+    }
+
+    /// Translated text for `key`, falling back to English and then to the key itself
+    pub fn t(self, key: &'static str) -> &'static str {
+        self.catalog()
+            .get(key)
+            .or_else(|| Self::En.catalog().get(key))
+            .map(String::as_str)
+            .unwrap_or(key)
+    }
+
+    /// Translated text with `{name}` placeholders filled in
+    pub fn tr(self, key: &'static str, args: &[(&str, &str)]) -> String {
+        args.iter().fold(self.t(key).to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+    }
+}
+
+/// Locale for the current request (English outside a request, e.g. route generation)
+pub fn use_locale() -> Locale {
+    use_context::<Locale>().unwrap_or_default()
+}
+
+/// Negotiate the locale for the current request and provide it as context.
+/// An explicit `?lang=` is remembered in a cookie so it survives navigation.
+#[cfg(feature = "ssr")]
+pub fn provide_request_locale() -> Locale {
+    use http::header::{ACCEPT_LANGUAGE, COOKIE, SET_COOKIE, VARY};
+    use http::{HeaderValue, request::Parts};
+    use leptos_axum::ResponseOptions;
+
+    let parts = use_context::<Parts>();
+    let response = use_context::<ResponseOptions>();
+
+    let query_locale = parts
+        .as_ref()
+        .and_then(|p| p.uri.query())
+        .and_then(|q| q.split('&').find_map(|pair| pair.strip_prefix("lang=")))
+        .and_then(Locale::from_tag);
+    let cookie_locale = parts.as_ref().and_then(|p| {
+        p.headers
+            .get_all(COOKIE)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(';'))
+            .find_map(|c| c.trim().strip_prefix(LOCALE_COOKIE)?.strip_prefix('='))
+            .and_then(Locale::from_tag)
+    });
+
+    let locale = match (query_locale, cookie_locale) {
+        (Some(locale), _) => {
+            if let Some(response) = &response
+                && let Ok(cookie) = HeaderValue::from_str(&format!(
+                    "{}={}; Path=/; Max-Age=31536000; SameSite=Lax",
+                    LOCALE_COOKIE,
+                    locale.code()
+                ))
+            {
+                response.append_header(SET_COOKIE, cookie);
+            }
+            locale
+        }
+        (None, Some(locale)) => locale,
+        (None, None) => Locale::negotiate(
+            parts
+                .as_ref()
+                .and_then(|p| p.headers.get(ACCEPT_LANGUAGE))
+                .and_then(|v| v.to_str().ok()),
+        ),
+    };
+
+    if let Some(response) = &response {
+        response.append_header(VARY, HeaderValue::from_static("Accept-Language, Cookie"));
+    }
+    provide_context(locale);
+    locale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negotiation_uses_q_values_and_primary_subtags() {
+        assert_eq!(Locale::negotiate(None), Locale::En);
+        assert_eq!(Locale::negotiate(Some("es-MX,es;q=0.9,en;q=0.8")), Locale::Es);
+        assert_eq!(Locale::negotiate(Some("en;q=0.5, es")), Locale::Es);
+        assert_eq!(Locale::negotiate(Some("de-DE, fr;q=0.9")), Locale::En);
+        assert_eq!(Locale::negotiate(Some("es;q=0, en")), Locale::En);
+    }
+
+    #[test]
+    fn catalogs_have_the_same_keys() {
+        let [en, es] = catalogs();
+        let mut missing: Vec<_> = en.keys().filter(|k| !es.contains_key(*k)).collect();
+        missing.extend(es.keys().filter(|k| !en.contains_key(*k)));
+        assert!(missing.is_empty(), "keys missing from a catalog: {:?}", missing);
+    }
+
+    #[test]
+    fn placeholders_are_filled() {
+        assert_eq!(
+            Locale::Es.tr("delegate.liquid.step.3", &[("symbol", "bpSOL")]),
+            "Recibes tokens bpSOL que representan tu SOL en staking"
+        );
+    }
+}
@@ -1,22 +1,51 @@
 //! Data ingestion: fetches external API data and writes metrics snapshots to SQLite.
 //! Called by the scheduler (daily) or manually via CLI.
+//!
+//! A source that is down doesn't blank its part of the page: its last good values are
+//! kept (from the HTTP layer's fallback, or else the previous snapshot) and marked in
+//! `MetricsData::stale` with the time they were fetched.
 
 #[cfg(feature = "ssr")]
 mod ssr {
-    use crate::api::{get_jito_mev_history, get_network_comparison, get_sfdp_status, get_validator_data};
-    use crate::components::metrics::MetricsData;
+    use crate::api::http::track_staleness;
+    use crate::api::{
+        get_epoch_info, get_gossip_presence, get_jito_mev_history, get_leader_slots, get_network_average_apy,
+        get_network_comparison, get_sfdp_status, get_validator_data, lookup_ip,
+    };
+    use crate::components::metrics::{MetricsData, MetricsSource};
     use crate::db;
+    use chrono::{DateTime, Utc};
+    use std::collections::BTreeMap;
+
+    /// `metrics_snapshots.fetched_at` format (UTC), also used for stale marks
+    const AS_OF_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
 
     /// Run one ingestion cycle: fetch all APIs, write snapshot to DB.
     /// Returns Ok(true) if data was written, Ok(false) if no data available.
     pub async fn run_ingestion() -> Result<bool, Box<dyn std::error::Error>> {
+        ingest(false).await
+    }
+
+    /// Fetch everything `run_ingestion` would, but only print what it would write
+    /// (`--update-now --dry-run`). Returns Ok(false) if no data was available.
+    pub async fn preview_ingestion() -> Result<bool, Box<dyn std::error::Error>> {
+        ingest(true).await
+    }
+
+    async fn ingest(dry_run: bool) -> Result<bool, Box<dyn std::error::Error>> {
         println!("[ingestion] Starting metrics fetch...");
 
+        let mut stale = BTreeMap::new();
+
         // Fetch Stakewiz data first (required for other calculations)
-        let Some(validator) = get_validator_data().await else {
-            eprintln!("[ingestion] Failed to fetch Stakewiz validator data — skipping this cycle");
+        let (validator, validator_as_of) = track_staleness(get_validator_data()).await;
+        let Some(validator) = validator else {
+            eprintln!(
+                "[ingestion] Failed to fetch Stakewiz validator data — skipping this cycle (the last snapshot stays up)"
+            );
             return Ok(false);
         };
+        mark_stale(&mut stale, MetricsSource::Stakewiz, validator_as_of);
 
         println!(
             "[ingestion] Stakewiz OK: rank #{}, stake {:.0} SOL, APY {:.2}%",
@@ -24,11 +53,24 @@ mod ssr {
         );
 
         // Fetch remaining data in parallel — each can fail independently
-        let (mev_result, sfdp_result, network_result) = futures::join!(
-            get_jito_mev_history(5),
-            get_sfdp_status(),
-            get_network_comparison(validator.skip_rate, validator.activated_stake),
+        let (
+            (mev_result, mev_as_of),
+            (sfdp_result, sfdp_as_of),
+            (network_result, network_as_of),
+            (network_apy_result, network_apy_as_of),
+            (gossip_result, gossip_as_of),
+        ) = futures::join!(
+            track_staleness(get_jito_mev_history(5)),
+            track_staleness(get_sfdp_status()),
+            track_staleness(get_network_comparison(validator.skip_rate, validator.activated_stake)),
+            track_staleness(get_network_average_apy()),
+            track_staleness(get_gossip_presence()),
         );
+        mark_stale(&mut stale, MetricsSource::Jito, mev_as_of);
+        mark_stale(&mut stale, MetricsSource::Sfdp, sfdp_as_of);
+        mark_stale(&mut stale, MetricsSource::NetworkComparison, network_as_of);
+        mark_stale(&mut stale, MetricsSource::NetworkApy, network_apy_as_of);
+        mark_stale(&mut stale, MetricsSource::Gossip, gossip_as_of);
 
         if mev_result.is_some() {
             println!("[ingestion] Jito MEV OK");
@@ -45,26 +87,188 @@ mod ssr {
         } else {
             eprintln!("[ingestion] Network comparison fetch failed (non-fatal)");
         }
+        if network_apy_result.is_some() {
+            println!("[ingestion] Network APY OK");
+        } else {
+            eprintln!("[ingestion] Network APY fetch failed (non-fatal)");
+        }
+        match &gossip_result {
+            Some(g) if g.node.is_some() => println!("[ingestion] Gossip OK ({} cluster nodes)", g.cluster_nodes),
+            Some(_) => eprintln!("[ingestion] Identity not found in gossip (non-fatal)"),
+            None => eprintln!("[ingestion] Cluster nodes fetch failed (non-fatal)"),
+        }
+
+        // Geolocate the address we advertise in gossip
+        let gossip_ip = gossip_result.as_ref().and_then(|g| g.node.as_ref()?.ip());
+        let (ip_location, ip_as_of) = match gossip_ip {
+            Some(ip) => track_staleness(lookup_ip(ip)).await,
+            None => (None, None),
+        };
+        mark_stale(&mut stale, MetricsSource::IpLocation, ip_as_of);
+        if let Some(location) = &ip_location {
+            println!("[ingestion] IP lookup OK via {}", location.source);
+        } else if gossip_ip.is_some() {
+            eprintln!("[ingestion] IP lookup failed (non-fatal)");
+        }
+        let ip_lookup_failed = gossip_ip.is_some() && ip_location.is_none();
 
-        let data = MetricsData {
+        let activated_stake = validator.activated_stake;
+        let mut data = MetricsData {
             validator,
             mev_history: mev_result,
             network_comp: network_result,
             sfdp_status: sfdp_result,
+            network_apy: network_apy_result,
+            gossip: gossip_result,
+            ip_location,
+            stale,
         };
+        keep_previous_values(&mut data, ip_lookup_failed).await;
 
         let json = serde_json::to_string(&data)?;
+        if dry_run {
+            println!(
+                "[dry-run] Would save a {} byte metrics snapshot (rank #{}, stake {:.0} SOL)",
+                json.len(),
+                data.validator.rank,
+                activated_stake
+            );
+            refresh_leader_slots(true).await;
+            println!("[dry-run] Nothing was written; live metrics not published");
+            return Ok(true);
+        }
         db::save_metrics_snapshot(&json).await.map_err(|e| {
             eprintln!("[ingestion] Failed to save snapshot: {}", e);
             e
         })?;
 
+        refresh_leader_slots(false).await;
+
+        // Push the new hero numbers to open home pages
+        match crate::live::collect(activated_stake).await {
+            Some(live) => crate::live::publish(live),
+            None => eprintln!("[ingestion] Live metrics not published: epoch info unavailable (non-fatal)"),
+        }
+
         let now = chrono::Utc::now().to_rfc3339();
         db::set_metadata("last_ingestion", &now).await.ok();
 
         println!("[ingestion] Snapshot saved at {}", now);
         Ok(true)
     }
+
+    /// Record that `source` was answered with a last good response from `as_of`
+    fn mark_stale(stale: &mut BTreeMap<MetricsSource, String>, source: MetricsSource, as_of: Option<DateTime<Utc>>) {
+        if let Some(as_of) = as_of {
+            stale.insert(source, as_of.format(AS_OF_FORMAT).to_string());
+        }
+    }
+
+    /// Fill the sources that failed this cycle with the previous snapshot's values, marked
+    /// stale as of when those were fetched. A gossip answer without our node, or no IP to
+    /// look up, is a result rather than a failure and is left as is.
+    async fn keep_previous_values(data: &mut MetricsData, ip_lookup_failed: bool) {
+        fn keep<T: Clone>(current: &mut Option<T>, previous: &Option<T>) -> bool {
+            if current.is_none() && previous.is_some() {
+                current.clone_from(previous);
+                true
+            } else {
+                false
+            }
+        }
+
+        let previous = match db::get_latest_metrics().await {
+            Ok(Some((json, fetched_at))) => serde_json::from_str::<MetricsData>(&json)
+                .ok()
+                .map(|data| (data, fetched_at)),
+            Ok(None) => None,
+            Err(e) => {
+                eprintln!("[ingestion] Failed to read the previous snapshot (non-fatal): {}", e);
+                None
+            }
+        };
+        let Some((previous, fetched_at)) = previous else {
+            return;
+        };
+
+        let gossip_kept = keep(&mut data.gossip, &previous.gossip);
+        let kept = [
+            (MetricsSource::Jito, keep(&mut data.mev_history, &previous.mev_history)),
+            (MetricsSource::Sfdp, keep(&mut data.sfdp_status, &previous.sfdp_status)),
+            (
+                MetricsSource::NetworkComparison,
+                keep(&mut data.network_comp, &previous.network_comp),
+            ),
+            (
+                MetricsSource::NetworkApy,
+                keep(&mut data.network_apy, &previous.network_apy),
+            ),
+            (MetricsSource::Gossip, gossip_kept),
+            (
+                MetricsSource::IpLocation,
+                (ip_lookup_failed || gossip_kept) && keep(&mut data.ip_location, &previous.ip_location),
+            ),
+        ];
+        for (source, _) in kept.into_iter().filter(|(_, kept)| *kept) {
+            let as_of = previous.as_of(source, &fetched_at);
+            println!(
+                "[ingestion] Keeping {:?} values as of {} (source unavailable)",
+                source, as_of
+            );
+            data.stale.insert(source, as_of);
+        }
+    }
+
+    /// Cache our leader slots for the current and next epoch (non-fatal on failure).
+    /// With `dry_run`, only compare them with the cached schedule.
+    async fn refresh_leader_slots(dry_run: bool) {
+        let Some(info) = get_epoch_info().await else {
+            eprintln!("[ingestion] Epoch info fetch failed (non-fatal) — leader slots not refreshed");
+            return;
+        };
+
+        let current_start = info.epoch_start_slot();
+        let epochs = [
+            (info.epoch, current_start),
+            (info.epoch + 1, current_start + info.slots_in_epoch),
+        ];
+        for (epoch, start_slot) in epochs {
+            // The next epoch's schedule may not be published yet
+            let Some(slots) = get_leader_slots(start_slot).await else {
+                continue;
+            };
+            if dry_run {
+                match db::get_epoch_leader_slots(epoch).await {
+                    Ok(cached) if cached == slots => {
+                        println!(
+                            "[dry-run] Leader schedule for epoch {} unchanged ({} slots)",
+                            epoch,
+                            slots.len()
+                        )
+                    }
+                    Ok(cached) => println!(
+                        "[dry-run] Would replace {} cached leader slots for epoch {} with {}",
+                        cached.len(),
+                        epoch,
+                        slots.len()
+                    ),
+                    Err(e) => eprintln!(
+                        "[dry-run] Failed to read cached leader slots for epoch {}: {}",
+                        epoch, e
+                    ),
+                }
+                continue;
+            }
+            match db::save_leader_slots(epoch, &slots, info.epoch).await {
+                Ok(()) => println!(
+                    "[ingestion] Leader schedule OK: epoch {} ({} slots)",
+                    epoch,
+                    slots.len()
+                ),
+                Err(e) => eprintln!("[ingestion] Failed to save leader slots for epoch {}: {}", epoch, e),
+            }
+        }
+    }
 }
 
 #[cfg(feature = "ssr")]
@@ -1,10 +1,32 @@
 pub mod api;
+#[cfg(feature = "ssr")]
+pub mod api_keys;
 pub mod app;
+pub mod badge;
 pub mod components;
 pub mod config;
+#[cfg(feature = "ssr")]
+pub mod contact;
+pub mod content;
 pub mod db;
 #[cfg(feature = "ssr")]
+pub mod demo;
+#[cfg(feature = "ssr")]
 pub mod financials;
+#[cfg(feature = "ssr")]
+pub mod heartbeat;
+pub mod http_cache;
+pub mod i18n;
 pub mod ingestion;
+pub mod live;
+#[cfg(feature = "ssr")]
+pub mod notify;
 pub mod pages;
+#[cfg(feature = "ssr")]
+pub mod rate_limit;
 pub mod scheduler;
+#[cfg(feature = "ssr")]
+pub mod selfcheck;
+pub mod seo;
+pub mod solana_pay;
+pub mod wallet_links;
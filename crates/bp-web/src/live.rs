@@ -0,0 +1,85 @@
+//! Live hero metrics for the home page.
+//!
+//! Each ingestion run publishes a fresh [`LiveMetrics`]; `/api/public/metrics/stream`
+//! relays them to connected browsers as server-sent events, so the stake, epoch progress
+//! and last produced block update without a reload.
+
+use serde::{Deserialize, Serialize};
+
+/// Hero numbers pushed to the home page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiveMetrics {
+    /// Activated stake in SOL
+    pub activated_stake: f64,
+    pub epoch: u64,
+    pub epoch_progress_percent: f64,
+    /// Our most recent leader slot that landed in a confirmed block
+    pub last_block_slot: Option<u64>,
+    /// RFC 3339
+    pub updated_at: String,
+}
+
+#[cfg(feature = "ssr")]
+mod ssr {
+    use super::LiveMetrics;
+    use crate::api::{get_epoch_info, get_last_produced_block};
+    use crate::db;
+    use std::sync::{OnceLock, RwLock};
+    use tokio::sync::broadcast;
+
+    /// Leader slots checked when looking for the last produced block (one or two leader groups)
+    const RECENT_LEADER_SLOTS: u32 = 8;
+    /// Updates buffered per subscriber; a slow client only ever needs the newest
+    const CHANNEL_CAPACITY: usize = 4;
+
+    struct Hub {
+        sender: broadcast::Sender<LiveMetrics>,
+        latest: RwLock<Option<LiveMetrics>>,
+    }
+
+    static HUB: OnceLock<Hub> = OnceLock::new();
+
+    fn hub() -> &'static Hub {
+        HUB.get_or_init(|| Hub {
+            sender: broadcast::channel(CHANNEL_CAPACITY).0,
+            latest: RwLock::new(None),
+        })
+    }
+
+    /// Collect the live metrics for the current epoch position
+    pub async fn collect(activated_stake: f64) -> Option<LiveMetrics> {
+        let info = get_epoch_info().await?;
+        let recent = db::get_recent_leader_slots(info.absolute_slot, RECENT_LEADER_SLOTS)
+            .await
+            .unwrap_or_default();
+
+        Some(LiveMetrics {
+            activated_stake,
+            epoch: info.epoch,
+            epoch_progress_percent: info.progress_percent(),
+            last_block_slot: get_last_produced_block(&recent).await,
+            updated_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// Remember `metrics` for new subscribers and push it to connected ones
+    pub fn publish(metrics: LiveMetrics) {
+        if let Ok(mut latest) = hub().latest.write() {
+            *latest = Some(metrics.clone());
+        }
+        // No receivers is fine: nobody has the page open
+        let _ = hub().sender.send(metrics);
+    }
+
+    /// The latest published metrics (if any) and a receiver for the ones after it
+    pub fn subscribe() -> (Option<LiveMetrics>, broadcast::Receiver<LiveMetrics>) {
+        let hub = hub();
+        // Subscribe before reading so nothing published in between is missed
+        let receiver = hub.sender.subscribe();
+        let latest = hub.latest.read().ok().and_then(|l| l.clone());
+        (latest, receiver)
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub use ssr::*;
@@ -1,14 +1,18 @@
-/// Dynamic financial report handler.
+/// Basic Auth gate shared by the `/financials` routes (FINANCIALS_PASSWORD env var).
 ///
-/// Authenticates via Basic Auth (FINANCIALS_PASSWORD env var), then queries
-/// cache.sqlite at request time to build an always-fresh HTML report.
+/// Returns the 401 response to send if the request is not authorized.
 #[cfg(feature = "ssr")]
-async fn financials_handler(headers: axum::http::HeaderMap) -> axum::response::Response {
-    use axum::http::{HeaderName, StatusCode, header};
+fn require_financials_auth(headers: &axum::http::HeaderMap) -> Option<axum::response::Response> {
+    use axum::http::{StatusCode, header};
     use axum::response::IntoResponse;
     use base64::Engine;
 
-    let password = std::env::var("FINANCIALS_PASSWORD").unwrap_or_default();
+    // `--demo` works out of the box: without FINANCIALS_PASSWORD the password is "demo"
+    let password = std::env::var("FINANCIALS_PASSWORD")
+        .ok()
+        .filter(|p| !p.is_empty())
+        .or_else(|| bp_web::demo::enabled().then(|| bp_web::demo::PASSWORD.to_string()))
+        .unwrap_or_default();
 
     let authorized = headers
         .get(header::AUTHORIZATION)
@@ -22,8 +26,12 @@ async fn financials_handler(headers: axum::http::HeaderMap) -> axum::response::R
         })
         .unwrap_or(false);
 
-    if !authorized {
-        return (
+    if authorized {
+        return None;
+    }
+
+    Some(
+        (
             StatusCode::UNAUTHORIZED,
             [
                 (header::WWW_AUTHENTICATE, "Basic realm=\"Block Parliament Financials\""),
@@ -31,20 +39,61 @@ async fn financials_handler(headers: axum::http::HeaderMap) -> axum::response::R
             ],
             "",
         )
-            .into_response();
+            .into_response(),
+    )
+}
+
+/// Gate for the financial JSON endpoints: an API key with `scope` (see `bp_web::api_keys`)
+/// or, without one, the same Basic Auth as the pages.
+///
+/// Returns the error response to send if the request is not authorized.
+#[cfg(feature = "ssr")]
+async fn require_api_access(
+    headers: &axum::http::HeaderMap,
+    scope: bp_web::api_keys::Scope,
+) -> Option<axum::response::Response> {
+    use axum::http::{StatusCode, header};
+    use axum::response::IntoResponse;
+    use bp_web::api_keys::{AccessError, authorize, presented_key};
+
+    let Some(key) = presented_key(headers) else {
+        return require_financials_auth(headers);
+    };
+    let (status, message) = match authorize(key, scope).await {
+        Ok(_) => return None,
+        Err(AccessError::InvalidKey) => (StatusCode::UNAUTHORIZED, "Invalid API key"),
+        Err(AccessError::MissingScope) => (StatusCode::FORBIDDEN, "API key lacks the required scope"),
+        Err(AccessError::RateLimited) => (StatusCode::TOO_MANY_REQUESTS, "API key rate limit exceeded"),
+        Err(AccessError::Unavailable) => (StatusCode::SERVICE_UNAVAILABLE, "API keys unavailable"),
+    };
+    let mut response = (
+        status,
+        [(header::CACHE_CONTROL, "no-store")],
+        axum::Json(serde_json::json!({ "error": message })),
+    )
+        .into_response();
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, axum::http::HeaderValue::from_static("60"));
     }
+    Some(response)
+}
 
-    // Build report dynamically from cache.sqlite
-    let data_dir = std::env::var("DATA_DIR").unwrap_or_else(|_| "./data".to_string());
-    let html = bp_web::financials::generate_report(&data_dir).await;
+/// Wrap private HTML in a no-store, noindex response.
+#[cfg(feature = "ssr")]
+fn private_html_response(html: impl Into<axum::body::Body>) -> axum::response::Response {
+    use axum::http::{HeaderName, StatusCode, header};
+    use axum::response::IntoResponse;
 
+    let body: axum::body::Body = html.into();
     let mut response = (
         StatusCode::OK,
         [
             (header::CONTENT_TYPE, "text/html; charset=utf-8"),
             (header::CACHE_CONTROL, "private, no-store"),
         ],
-        html,
+        body,
     )
         .into_response();
     response.headers_mut().insert(
@@ -54,6 +103,1067 @@ async fn financials_handler(headers: axum::http::HeaderMap) -> axum::response::R
     response
 }
 
+/// Slot for a CSV/JSON export (see `bp_web::financials::downloads`); `Err` is the 503 to
+/// send when none frees up in time. Page views (`download == false`) need no slot.
+#[cfg(feature = "ssr")]
+async fn download_slot(
+    download: bool,
+) -> Result<Option<tokio::sync::SemaphorePermit<'static>>, axum::response::Response> {
+    use axum::http::{StatusCode, header};
+    use axum::response::IntoResponse;
+
+    if !download {
+        return Ok(None);
+    }
+    match bp_web::financials::downloads::acquire().await {
+        Some(permit) => Ok(Some(permit)),
+        None => Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::CACHE_CONTROL, "no-store"), (header::RETRY_AFTER, "30")],
+            axum::Json(serde_json::json!({ "error": "Too many downloads in progress, try again shortly" })),
+        )
+            .into_response()),
+    }
+}
+
+/// Query string for `GET /financials`.
+#[cfg(feature = "ssr")]
+#[derive(serde::Deserialize)]
+struct FinancialsQuery {
+    /// "accountant" for the USD-only summary
+    view: Option<String>,
+    /// Accountant view CSV: "monthly" or "categories"
+    download: Option<String>,
+}
+
+/// Dynamic financial report handler.
+///
+/// Authenticates via Basic Auth (FINANCIALS_PASSWORD env var), then serves the
+/// report built from cache.sqlite, in the pre-compressed variant the client accepts.
+/// `?view=accountant` serves the simplified USD summary instead.
+#[cfg(feature = "ssr")]
+async fn financials_handler(
+    headers: axum::http::HeaderMap,
+    axum::extract::Query(query): axum::extract::Query<FinancialsQuery>,
+) -> axum::response::Response {
+    use axum::http::{HeaderValue, header};
+    use bp_web::financials::precompressed::negotiate_encoding;
+
+    if let Some(unauthorized) = require_financials_auth(&headers) {
+        return unauthorized;
+    }
+
+    if query.view.as_deref() == Some("accountant") {
+        return financials_accountant_response(query.download.as_deref()).await;
+    }
+
+    // Build report from cache.sqlite (reused while the timeline snapshot is fresh)
+    let data_dir = bp_web::db::data_dir();
+    let page = bp_web::financials::generate_report(&data_dir).await;
+
+    let accept_encoding = headers.get(header::ACCEPT_ENCODING).and_then(|v| v.to_str().ok());
+    let encoding = negotiate_encoding(accept_encoding);
+    let mut response = private_html_response(page.body(encoding).to_vec());
+    let response_headers = response.headers_mut();
+    // A Content-Encoding header also keeps CompressionLayer from compressing it again
+    if let Some(value) = encoding.header_value() {
+        response_headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static(value));
+    }
+    response_headers.insert(header::VARY, HeaderValue::from_static("accept-encoding"));
+    response
+}
+
+/// `/financials?view=accountant`: the page, or one of its CSV downloads.
+#[cfg(feature = "ssr")]
+async fn financials_accountant_response(download: Option<&str>) -> axum::response::Response {
+    use axum::http::{StatusCode, header};
+    use axum::response::IntoResponse;
+    use bp_web::financials::accountant::{AccountantReport, categories_csv, months_csv, render_page};
+
+    let _slot = match download_slot(download.is_some()).await {
+        Ok(slot) => slot,
+        Err(busy) => return busy,
+    };
+    let data_dir = bp_web::db::data_dir();
+    let report = bp_web::financials::accountant(&data_dir).await;
+
+    let csv: Option<(&str, fn(&AccountantReport) -> String)> = match download {
+        Some("monthly") => Some(("accountant_monthly.csv", months_csv)),
+        Some("categories") => Some(("accountant_categories.csv", categories_csv)),
+        _ => None,
+    };
+    match (csv, report) {
+        (Some((filename, to_csv)), Ok(report)) => (
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}\"", filename),
+                ),
+                (header::CACHE_CONTROL, "private, no-store".to_string()),
+            ],
+            to_csv(&report),
+        )
+            .into_response(),
+        (Some(_), Err(e)) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::CACHE_CONTROL, "no-store")],
+            axum::Json(serde_json::json!({ "error": format!("{:#}", e) })),
+        )
+            .into_response(),
+        (None, report) => {
+            if let Err(e) = &report {
+                eprintln!("[financials] Accountant view unavailable: {:#}", e);
+            }
+            private_html_response(render_page(report.as_ref().ok().map(|r| r.as_ref())))
+        }
+    }
+}
+
+/// GraphQL schema over cache.sqlite, built on first use
+#[cfg(feature = "ssr")]
+static FINANCIALS_SCHEMA: std::sync::OnceLock<bp_web::financials::graphql::FinancialsSchema> =
+    std::sync::OnceLock::new();
+
+#[cfg(feature = "ssr")]
+fn financials_schema() -> &'static bp_web::financials::graphql::FinancialsSchema {
+    FINANCIALS_SCHEMA.get_or_init(|| {
+        let data_dir = bp_web::db::data_dir();
+        bp_web::financials::graphql::schema(&data_dir)
+    })
+}
+
+/// GraphQL queries over cache.sqlite (see `bp_web::financials::graphql`).
+#[cfg(feature = "ssr")]
+async fn financials_graphql_handler(
+    headers: axum::http::HeaderMap,
+    axum::Json(request): axum::Json<async_graphql::Request>,
+) -> axum::response::Response {
+    use axum::http::header;
+    use axum::response::IntoResponse;
+
+    if let Some(unauthorized) = require_api_access(&headers, bp_web::api_keys::Scope::Read).await {
+        return unauthorized;
+    }
+
+    let response = financials_schema().execute(request).await;
+    ([(header::CACHE_CONTROL, "private, no-store")], axum::Json(response)).into_response()
+}
+
+/// GraphiQL explorer for `/financials/graphql`.
+#[cfg(feature = "ssr")]
+async fn financials_graphiql_handler(headers: axum::http::HeaderMap) -> axum::response::Response {
+    if let Some(unauthorized) = require_financials_auth(&headers) {
+        return unauthorized;
+    }
+
+    let html = async_graphql::http::GraphiQLSource::build()
+        .endpoint("/financials/graphql")
+        .finish();
+    private_html_response(html)
+}
+
+/// Query string for `/financials/timeline.json`.
+#[cfg(feature = "ssr")]
+#[derive(serde::Deserialize)]
+struct TimelineChunkQuery {
+    /// "operating" (default) or "tax"
+    view: Option<String>,
+    /// Month cursor (YYYY-MM) from the previous chunk's `next_before`; omit for the latest months
+    before: Option<String>,
+    months: Option<usize>,
+}
+
+/// Older timeline months for the `/financials` page, one chunk of whole months per request.
+#[cfg(feature = "ssr")]
+async fn financials_timeline_handler(
+    headers: axum::http::HeaderMap,
+    axum::extract::Query(query): axum::extract::Query<TimelineChunkQuery>,
+) -> axum::response::Response {
+    use axum::http::{StatusCode, header};
+    use axum::response::IntoResponse;
+    use bp_web::financials::{DEFAULT_CHUNK_MONTHS, TimelineView};
+
+    if let Some(unauthorized) = require_api_access(&headers, bp_web::api_keys::Scope::Read).await {
+        return unauthorized;
+    }
+
+    let Some(view) = TimelineView::parse(query.view.as_deref().unwrap_or("operating")) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            [(header::CACHE_CONTROL, "no-store")],
+            "Unknown view",
+        )
+            .into_response();
+    };
+
+    let data_dir = bp_web::db::data_dir();
+    let months = query.months.unwrap_or(DEFAULT_CHUNK_MONTHS);
+    match bp_web::financials::timeline_chunk(&data_dir, view, query.before.as_deref(), months).await {
+        Ok(chunk) => ([(header::CACHE_CONTROL, "private, no-store")], axum::Json(chunk)).into_response(),
+        Err(e) => {
+            eprintln!("[financials] Error loading timeline chunk: {:#}", e);
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                [(header::CACHE_CONTROL, "no-store")],
+                "Timeline data unavailable",
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Operator admin page (ingestion run history), behind the same Basic Auth.
+#[cfg(feature = "ssr")]
+async fn financials_admin_handler(headers: axum::http::HeaderMap) -> axum::response::Response {
+    if let Some(unauthorized) = require_financials_auth(&headers) {
+        return unauthorized;
+    }
+
+    let data_dir = bp_web::db::data_dir();
+    let html = bp_web::financials::admin::generate_admin_page(&data_dir, None).await;
+
+    private_html_response(html)
+}
+
+/// Reload config.toml from the admin page (see `bp_web::selfcheck::reload`), then show
+/// the page with the outcome.
+#[cfg(feature = "ssr")]
+async fn financials_admin_reload_handler(headers: axum::http::HeaderMap) -> axum::response::Response {
+    use axum::http::{StatusCode, header};
+    use axum::response::IntoResponse;
+    use bp_web::financials::expense_admin::is_same_origin;
+
+    if let Some(unauthorized) = require_financials_auth(&headers) {
+        return unauthorized;
+    }
+    let header_str = |name| headers.get(name).and_then(|v| v.to_str().ok());
+    if !is_same_origin(header_str(header::ORIGIN), header_str(header::HOST)) {
+        return (
+            StatusCode::FORBIDDEN,
+            [(header::CACHE_CONTROL, "no-store")],
+            "Cross-site form post refused",
+        )
+            .into_response();
+    }
+
+    let outcome = bp_web::selfcheck::reload();
+    let data_dir = bp_web::db::data_dir();
+    let html = bp_web::financials::admin::generate_admin_page(&data_dir, Some(&outcome)).await;
+    let mut response = private_html_response(html);
+    if outcome.is_err() {
+        *response.status_mut() = StatusCode::UNPROCESSABLE_ENTITY;
+    }
+    response
+}
+
+/// Read-only SQL console over cache.sqlite, behind the same Basic Auth.
+#[cfg(feature = "ssr")]
+async fn financials_sql_page_handler(headers: axum::http::HeaderMap) -> axum::response::Response {
+    if let Some(unauthorized) = require_financials_auth(&headers) {
+        return unauthorized;
+    }
+
+    private_html_response(bp_web::financials::sql_console::render_page(None, None))
+}
+
+/// Query string for `GET /financials/plan`.
+#[cfg(feature = "ssr")]
+#[derive(serde::Deserialize)]
+struct WithdrawalPlanQuery {
+    /// Target withdrawal in USD; omit to show the empty form
+    usd: Option<f64>,
+    /// Flat rate (0-1); defaults to `[reporting] estimated_tax_rate`
+    tax_rate: Option<f64>,
+    /// "html" (default) or "json"
+    format: Option<String>,
+}
+
+/// Withdrawal planner: SOL to move for a USD target and its return-of-capital /
+/// taxable split, behind the same Basic Auth.
+#[cfg(feature = "ssr")]
+async fn financials_plan_handler(
+    headers: axum::http::HeaderMap,
+    axum::extract::Query(query): axum::extract::Query<WithdrawalPlanQuery>,
+) -> axum::response::Response {
+    use axum::http::{StatusCode, header};
+    use axum::response::IntoResponse;
+    use bp_web::financials::withdrawal_plan::{plan_withdrawal, render_page};
+
+    if let Some(unauthorized) = require_financials_auth(&headers) {
+        return unauthorized;
+    }
+
+    let data_dir = bp_web::db::data_dir();
+    let inputs = bp_web::financials::planning_inputs(&data_dir).await;
+    let outcome = match (&inputs, query.usd) {
+        (_, None) => None,
+        (Ok(inputs), Some(usd)) => Some(plan_withdrawal(inputs, usd, query.tax_rate.or(inputs.default_tax_rate))),
+        (Err(e), Some(_)) => Some(Err(anyhow::anyhow!("Financial data unavailable: {:#}", e))),
+    };
+
+    if query.format.as_deref() == Some("json") {
+        return match outcome {
+            Some(Ok(plan)) => ([(header::CACHE_CONTROL, "private, no-store")], axum::Json(plan)).into_response(),
+            Some(Err(e)) => (
+                StatusCode::BAD_REQUEST,
+                [(header::CACHE_CONTROL, "no-store")],
+                axum::Json(serde_json::json!({ "error": format!("{:#}", e) })),
+            )
+                .into_response(),
+            None => (
+                StatusCode::BAD_REQUEST,
+                [(header::CACHE_CONTROL, "no-store")],
+                axum::Json(serde_json::json!({ "error": "usd is required" })),
+            )
+                .into_response(),
+        };
+    }
+
+    private_html_response(render_page(
+        inputs.as_ref().ok(),
+        query.usd,
+        query.tax_rate,
+        outcome.as_ref(),
+    ))
+}
+
+/// Query string for `GET /financials/epochs` and `GET /financials/delegators`.
+#[cfg(feature = "ssr")]
+#[derive(serde::Deserialize)]
+struct EpochPnlQuery {
+    /// "html" (default), "csv" or "json"
+    format: Option<String>,
+}
+
+/// Per-epoch profitability table, also as CSV (same columns as epoch_pnl.csv) or JSON.
+#[cfg(feature = "ssr")]
+async fn financials_epochs_handler(
+    headers: axum::http::HeaderMap,
+    axum::extract::Query(query): axum::extract::Query<EpochPnlQuery>,
+) -> axum::response::Response {
+    use axum::http::{StatusCode, header};
+    use axum::response::IntoResponse;
+    use bp_web::financials::epoch_pnl::{render_page, to_csv};
+
+    if let Some(unauthorized) = require_api_access(&headers, bp_web::api_keys::Scope::Read).await {
+        return unauthorized;
+    }
+
+    let _slot = match download_slot(matches!(query.format.as_deref(), Some("csv" | "json"))).await {
+        Ok(slot) => slot,
+        Err(busy) => return busy,
+    };
+    let data_dir = bp_web::db::data_dir();
+    let rows = bp_web::financials::epoch_pnl(&data_dir).await;
+
+    match (query.format.as_deref(), rows) {
+        (Some("csv"), Ok(rows)) => (
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+                (header::CONTENT_DISPOSITION, "attachment; filename=\"epoch_pnl.csv\""),
+                (header::CACHE_CONTROL, "private, no-store"),
+            ],
+            to_csv(&rows),
+        )
+            .into_response(),
+        (Some("json"), Ok(rows)) => (
+            [(header::CACHE_CONTROL, "private, no-store")],
+            axum::Json(rows.as_ref().clone()),
+        )
+            .into_response(),
+        (Some("csv" | "json"), Err(e)) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::CACHE_CONTROL, "no-store")],
+            axum::Json(serde_json::json!({ "error": format!("{:#}", e) })),
+        )
+            .into_response(),
+        (_, rows) => {
+            if let Err(e) = &rows {
+                eprintln!("[financials] Epoch profitability unavailable: {:#}", e);
+            }
+            private_html_response(render_page(rows.as_ref().ok().map(|r| r.as_slice())))
+        }
+    }
+}
+
+/// Delegator concentration and churn, also as CSV (same columns as delegators.csv) or JSON.
+#[cfg(feature = "ssr")]
+async fn financials_delegators_handler(
+    headers: axum::http::HeaderMap,
+    axum::extract::Query(query): axum::extract::Query<EpochPnlQuery>,
+) -> axum::response::Response {
+    use axum::http::{StatusCode, header};
+    use axum::response::IntoResponse;
+    use bp_web::financials::delegators::{render_page, to_csv};
+
+    if let Some(unauthorized) = require_api_access(&headers, bp_web::api_keys::Scope::Read).await {
+        return unauthorized;
+    }
+
+    let _slot = match download_slot(matches!(query.format.as_deref(), Some("csv" | "json"))).await {
+        Ok(slot) => slot,
+        Err(busy) => return busy,
+    };
+    let data_dir = bp_web::db::data_dir();
+    let report = bp_web::financials::delegators(&data_dir).await;
+
+    match (query.format.as_deref(), report) {
+        (Some("csv"), Ok(report)) => (
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+                (header::CONTENT_DISPOSITION, "attachment; filename=\"delegators.csv\""),
+                (header::CACHE_CONTROL, "private, no-store"),
+            ],
+            to_csv(&report),
+        )
+            .into_response(),
+        (Some("json"), Ok(report)) => (
+            [(header::CACHE_CONTROL, "private, no-store")],
+            axum::Json(report.as_ref().clone()),
+        )
+            .into_response(),
+        (Some("csv" | "json"), Err(e)) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::CACHE_CONTROL, "no-store")],
+            axum::Json(serde_json::json!({ "error": format!("{:#}", e) })),
+        )
+            .into_response(),
+        (_, report) => {
+            if let Err(e) = &report {
+                eprintln!("[financials] Delegator analytics unavailable: {:#}", e);
+            }
+            private_html_response(render_page(report.as_ref().ok().map(|r| r.as_ref())))
+        }
+    }
+}
+
+/// One cached ledger as a streamed CSV download (see `bp_web::financials::ledger_csv`).
+#[cfg(feature = "ssr")]
+async fn financials_ledger_handler(
+    headers: axum::http::HeaderMap,
+    axum::extract::Path(file): axum::extract::Path<String>,
+) -> axum::response::Response {
+    use axum::http::{StatusCode, header};
+    use axum::response::IntoResponse;
+    use bp_web::financials::ledger_csv;
+
+    if let Some(unauthorized) = require_api_access(&headers, bp_web::api_keys::Scope::Read).await {
+        return unauthorized;
+    }
+    let Some(ledger) = ledger_csv::find(&file) else {
+        return (
+            StatusCode::NOT_FOUND,
+            [(header::CACHE_CONTROL, "no-store")],
+            "Unknown ledger",
+        )
+            .into_response();
+    };
+    let slot = match download_slot(true).await {
+        Ok(slot) => slot,
+        Err(busy) => return busy,
+    };
+
+    let data_dir = bp_web::db::data_dir();
+    match ledger_csv::stream(&data_dir, ledger, slot).await {
+        Ok(body) => (
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}.csv\"", ledger.name),
+                ),
+                (header::CACHE_CONTROL, "private, no-store".to_string()),
+            ],
+            body,
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::CACHE_CONTROL, "no-store")],
+            axum::Json(serde_json::json!({ "error": format!("{:#}", e) })),
+        )
+            .into_response(),
+    }
+}
+
+/// Query string for `GET /financials/expenses`.
+#[cfg(feature = "ssr")]
+#[derive(serde::Deserialize)]
+struct ExpenseTrendsQuery {
+    /// "html" (default), "csv" or "json"
+    format: Option<String>,
+    /// Category to drill into by vendor (e.g. "Hosting")
+    category: Option<String>,
+}
+
+/// Monthly expenses by category with a vendor drill-down and largest movers,
+/// also as CSV (vendor spend by month) or JSON.
+#[cfg(feature = "ssr")]
+async fn financials_expense_trends_handler(
+    headers: axum::http::HeaderMap,
+    axum::extract::Query(query): axum::extract::Query<ExpenseTrendsQuery>,
+) -> axum::response::Response {
+    use axum::http::{StatusCode, header};
+    use axum::response::IntoResponse;
+    use bp_web::financials::expense_trends::{parse_category, render_page, to_csv};
+
+    if let Some(unauthorized) = require_api_access(&headers, bp_web::api_keys::Scope::Read).await {
+        return unauthorized;
+    }
+
+    let _slot = match download_slot(matches!(query.format.as_deref(), Some("csv" | "json"))).await {
+        Ok(slot) => slot,
+        Err(busy) => return busy,
+    };
+    let data_dir = bp_web::db::data_dir();
+    let trends = bp_web::financials::expense_trends(&data_dir).await;
+
+    match (query.format.as_deref(), trends) {
+        (Some("csv"), Ok(trends)) => (
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+                (
+                    header::CONTENT_DISPOSITION,
+                    "attachment; filename=\"expense_trends.csv\"",
+                ),
+                (header::CACHE_CONTROL, "private, no-store"),
+            ],
+            to_csv(&trends),
+        )
+            .into_response(),
+        (Some("json"), Ok(trends)) => (
+            [(header::CACHE_CONTROL, "private, no-store")],
+            axum::Json(trends.as_ref().clone()),
+        )
+            .into_response(),
+        (Some("csv" | "json"), Err(e)) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::CACHE_CONTROL, "no-store")],
+            axum::Json(serde_json::json!({ "error": format!("{:#}", e) })),
+        )
+            .into_response(),
+        (_, trends) => {
+            if let Err(e) = &trends {
+                eprintln!("[financials] Expense trends unavailable: {:#}", e);
+            }
+            let category = query.category.as_deref().and_then(parse_category);
+            private_html_response(render_page(trends.as_ref().ok().map(|t| t.as_ref()), category))
+        }
+    }
+}
+
+/// Form body for `POST /financials/sql`.
+#[cfg(feature = "ssr")]
+#[derive(serde::Deserialize)]
+struct SqlConsoleForm {
+    query: String,
+    /// "html" (default), "csv" or "json"
+    format: Option<String>,
+}
+
+/// Run a console query; renders the result as a page, a CSV download or JSON
+/// (`format=json` for scripted use).
+#[cfg(feature = "ssr")]
+async fn financials_sql_query_handler(
+    headers: axum::http::HeaderMap,
+    axum::Form(form): axum::Form<SqlConsoleForm>,
+) -> axum::response::Response {
+    use axum::http::{StatusCode, header};
+    use axum::response::IntoResponse;
+    use bp_web::financials::sql_console::{render_page, run_query, to_csv};
+
+    if let Some(unauthorized) = require_api_access(&headers, bp_web::api_keys::Scope::Sql).await {
+        return unauthorized;
+    }
+
+    let _slot = match download_slot(matches!(form.format.as_deref(), Some("csv" | "json"))).await {
+        Ok(slot) => slot,
+        Err(busy) => return busy,
+    };
+    let data_dir = bp_web::db::data_dir();
+    let outcome = run_query(&data_dir, &form.query).await;
+
+    match (form.format.as_deref(), outcome) {
+        (Some("csv"), Ok(result)) => {
+            let disposition = format!(
+                "attachment; filename=\"query-{}.csv\"",
+                chrono::Utc::now().format("%Y%m%d-%H%M%S")
+            );
+            (
+                [
+                    (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                    (header::CONTENT_DISPOSITION, disposition),
+                    (header::CACHE_CONTROL, "private, no-store".to_string()),
+                ],
+                to_csv(&result),
+            )
+                .into_response()
+        }
+        (Some("json"), Ok(result)) => {
+            ([(header::CACHE_CONTROL, "private, no-store")], axum::Json(result)).into_response()
+        }
+        (Some("json"), Err(e)) => (
+            StatusCode::BAD_REQUEST,
+            [(header::CACHE_CONTROL, "no-store")],
+            axum::Json(serde_json::json!({ "error": format!("{:#}", e) })),
+        )
+            .into_response(),
+        (_, outcome) => private_html_response(render_page(Some(&form.query), Some(&outcome))),
+    }
+}
+
+/// Query string for `GET /financials/admin/expenses`.
+#[cfg(feature = "ssr")]
+#[derive(serde::Deserialize)]
+struct ExpenseAdminQuery {
+    /// Id of the entry to load into the form
+    edit: Option<i64>,
+    /// "one_time" (default) or "recurring", for `edit`
+    kind: Option<String>,
+    /// "saved", "deleted", "approved", "rejected" or "reviewed" after a successful post
+    notice: Option<String>,
+}
+
+/// Expense entry page: list, add and edit one-time and recurring expenses, behind the
+/// same Basic Auth.
+#[cfg(feature = "ssr")]
+async fn financials_expenses_page_handler(
+    headers: axum::http::HeaderMap,
+    axum::extract::Query(query): axum::extract::Query<ExpenseAdminQuery>,
+) -> axum::response::Response {
+    use bp_web::financials::expense_admin::{ExpenseForm, ExpenseKind, list_entries, render_page};
+
+    if let Some(unauthorized) = require_financials_auth(&headers) {
+        return unauthorized;
+    }
+
+    let data_dir = bp_web::db::data_dir();
+    let (entries, errors) = match list_entries(&data_dir).await {
+        Ok(entries) => (entries, Vec::new()),
+        Err(e) => {
+            eprintln!("[financials] Error loading expenses: {:#}", e);
+            (Vec::new(), vec![format!("Expenses unavailable: {:#}", e)])
+        }
+    };
+
+    let kind = query
+        .kind
+        .as_deref()
+        .and_then(ExpenseKind::parse)
+        .unwrap_or(ExpenseKind::OneTime);
+    let form = query
+        .edit
+        .and_then(|id| {
+            entries
+                .iter()
+                .find(|e| e.kind == kind && e.id == Some(id) && !e.is_synced())
+        })
+        .map(ExpenseForm::from_entry)
+        .unwrap_or_else(|| ExpenseForm::new_entry(&chrono::Utc::now().format("%Y-%m-%d").to_string()));
+    let notice = match query.notice.as_deref() {
+        Some("saved") => Some("Expense saved."),
+        Some("deleted") => Some("Expense deleted."),
+        Some("approved") => Some("Expense approved."),
+        Some("rejected") => Some("Expense rejected."),
+        Some("reviewed") => Some("Pending expenses approved."),
+        _ => None,
+    };
+
+    private_html_response(render_page(&entries, &form, &errors, notice))
+}
+
+/// Save, delete, approve or reject an expense, then redirect back to the list; invalid input re-renders
+/// the form with every problem listed.
+#[cfg(feature = "ssr")]
+async fn financials_expenses_submit_handler(
+    headers: axum::http::HeaderMap,
+    axum::Form(form): axum::Form<bp_web::financials::expense_admin::ExpenseForm>,
+) -> axum::response::Response {
+    use axum::http::{StatusCode, header};
+    use axum::response::{IntoResponse, Redirect};
+    use bp_web::financials::expense_admin::{
+        approve_all_pending, delete_entry, is_same_origin, list_entries, render_page, save_entry, set_status,
+    };
+
+    if let Some(unauthorized) = require_financials_auth(&headers) {
+        return unauthorized;
+    }
+    let header_str = |name| headers.get(name).and_then(|v| v.to_str().ok());
+    if !is_same_origin(header_str(header::ORIGIN), header_str(header::HOST)) {
+        return (
+            StatusCode::FORBIDDEN,
+            [(header::CACHE_CONTROL, "no-store")],
+            "Cross-site form post refused",
+        )
+            .into_response();
+    }
+
+    let data_dir = bp_web::db::data_dir();
+    let outcome = if form.is_approve_all() {
+        approve_all_pending(&data_dir)
+            .await
+            .map(|_| "reviewed")
+            .map_err(|e| vec![format!("{:#}", e)])
+    } else if let Some(status) = form.review_status() {
+        match form.target() {
+            Ok((_, id)) => match set_status(&data_dir, id, status).await {
+                Ok(true) => Ok(if status == "approved" { "approved" } else { "rejected" }),
+                Ok(false) => Err(vec![format!("No expense with id {}", id)]),
+                Err(e) => Err(vec![format!("{:#}", e)]),
+            },
+            Err(e) => Err(vec![e]),
+        }
+    } else if form.is_delete() {
+        match form.target() {
+            Ok((kind, id)) => match delete_entry(&data_dir, kind, id).await {
+                Ok(true) => Ok("deleted"),
+                Ok(false) => Err(vec![format!("No editable expense with id {}", id)]),
+                Err(e) => Err(vec![format!("{:#}", e)]),
+            },
+            Err(e) => Err(vec![e]),
+        }
+    } else {
+        match form.validate() {
+            Ok(entry) => save_entry(&data_dir, &entry)
+                .await
+                .map(|_| "saved")
+                .map_err(|e| vec![format!("{:#}", e)]),
+            Err(errors) => Err(errors),
+        }
+    };
+
+    match outcome {
+        Ok(notice) => {
+            // The report should show the change now, not when the timeline cache expires
+            bp_web::financials::invalidate_timelines();
+            Redirect::to(&format!("/financials/admin/expenses?notice={}", notice)).into_response()
+        }
+        Err(errors) => {
+            let entries = list_entries(&data_dir).await.unwrap_or_default();
+            let mut response = private_html_response(render_page(&entries, &form, &errors, None));
+            *response.status_mut() = StatusCode::UNPROCESSABLE_ENTITY;
+            response
+        }
+    }
+}
+
+/// Public epoch calendar: current epoch progress, estimated end, and our next leader slots.
+///
+/// Tagged with the cached epoch position it is built from, so pollers sending
+/// `If-None-Match` get a 304 until that position is refreshed.
+#[cfg(feature = "ssr")]
+#[utoipa::path(
+    get,
+    path = "/api/public/epoch",
+    tag = "public",
+    responses(
+        (status = 200, description = "Current epoch calendar", body = bp_web::api::EpochCalendar),
+        (status = 304, description = "Unchanged since the `If-None-Match` ETag"),
+        (status = 503, description = "Epoch data unavailable"),
+    )
+)]
+async fn epoch_api_handler(headers: axum::http::HeaderMap) -> axum::response::Response {
+    use axum::http::{StatusCode, header};
+    use axum::response::IntoResponse;
+    use bp_web::http_cache::{if_none_match, not_modified, weak_etag, with_etag};
+
+    const CACHE_CONTROL: &str = "public, max-age=30";
+
+    let Some(info) = bp_web::api::get_epoch_info().await else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::CACHE_CONTROL, "no-store")],
+            "Epoch data unavailable",
+        )
+            .into_response();
+    };
+
+    let etag = weak_etag("epoch", &info.data_version());
+    if if_none_match(&headers, &etag) {
+        return not_modified(&etag, CACHE_CONTROL);
+    }
+
+    let calendar = bp_web::api::build_epoch_calendar(info).await;
+    with_etag(
+        ([(header::CACHE_CONTROL, CACHE_CONTROL)], axum::Json(calendar)).into_response(),
+        &etag,
+    )
+}
+
+/// Public revenue composition: each month's income share by source (no amounts).
+///
+/// Tagged with the cache data version (latest ingestion run), so pollers get a 304
+/// without the monthly aggregation being rerun.
+#[cfg(feature = "ssr")]
+#[utoipa::path(
+    get,
+    path = "/api/public/revenue",
+    tag = "public",
+    responses(
+        (status = 200, description = "Monthly income shares by source", body = bp_web::components::revenue_chart::RevenueShares),
+        (status = 304, description = "Unchanged since the `If-None-Match` ETag"),
+        (status = 503, description = "Revenue data unavailable"),
+    )
+)]
+async fn revenue_api_handler(headers: axum::http::HeaderMap) -> axum::response::Response {
+    use axum::http::{StatusCode, header};
+    use axum::response::IntoResponse;
+    use bp_web::components::revenue_chart::{RevenueShares, revenue_shares};
+    use bp_web::http_cache::{if_none_match, not_modified, weak_etag, with_etag};
+
+    const CACHE_CONTROL: &str = "public, max-age=300";
+
+    let unavailable = || {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::CACHE_CONTROL, "no-store")],
+            "Revenue data unavailable",
+        )
+            .into_response()
+    };
+
+    let data_dir = bp_web::db::data_dir();
+    let etag = match bp_web::financials::data_version(&data_dir).await {
+        Ok(version) => version.map(|v| weak_etag("revenue", &v)),
+        Err(e) => {
+            eprintln!("[api] Error reading cache data version: {:#}", e);
+            return unavailable();
+        }
+    };
+    if let Some(etag) = &etag
+        && if_none_match(&headers, etag)
+    {
+        return not_modified(etag, CACHE_CONTROL);
+    }
+
+    let composition = match bp_web::financials::revenue_composition(&data_dir).await {
+        Ok(composition) => composition,
+        Err(e) => {
+            eprintln!("[api] Error building revenue composition: {:#}", e);
+            return unavailable();
+        }
+    };
+    let body = RevenueShares {
+        months: revenue_shares(&composition.months),
+    };
+    let response = ([(header::CACHE_CONTROL, CACHE_CONTROL)], axum::Json(body)).into_response();
+    match &etag {
+        Some(etag) => with_etag(response, etag),
+        None => response,
+    }
+}
+
+/// OpenAPI description of the public JSON API, generated from the handlers and their
+/// response types so the spec cannot drift from what the server sends.
+/// `scripts/generate_api_clients.sh` builds the TypeScript and Rust clients from it.
+#[cfg(feature = "ssr")]
+#[derive(utoipa::OpenApi)]
+#[openapi(
+    info(title = "Block Parliament API", description = "Public validator data from blockparliament.com"),
+    paths(epoch_api_handler, revenue_api_handler),
+    tags((name = "public", description = "Unauthenticated, cacheable JSON endpoints"))
+)]
+struct ApiDoc;
+
+/// The OpenAPI spec (`/api/openapi.json`)
+#[cfg(feature = "ssr")]
+async fn openapi_handler() -> axum::response::Response {
+    use axum::http::header;
+    use axum::response::IntoResponse;
+    use utoipa::OpenApi;
+
+    (
+        [(header::CACHE_CONTROL, "public, max-age=3600")],
+        axum::Json(ApiDoc::openapi()),
+    )
+        .into_response()
+}
+
+/// CORS for the JSON endpoints, when CORS_ALLOWED_ORIGINS lists any origins.
+/// Credentials are not allowed: cross-origin callers authenticate with an API key.
+#[cfg(feature = "ssr")]
+fn cors_layer() -> Option<tower_http::cors::CorsLayer> {
+    use axum::http::{HeaderName, HeaderValue, Method, header};
+    use tower_http::cors::{AllowOrigin, CorsLayer};
+
+    let origins: Vec<HeaderValue> = bp_web::api_keys::cors_allowed_origins()
+        .iter()
+        .filter_map(|origin| match HeaderValue::from_str(origin) {
+            Ok(value) => Some(value),
+            Err(_) => {
+                eprintln!("[cors] Ignoring invalid origin {:?}", origin);
+                None
+            }
+        })
+        .collect();
+    if origins.is_empty() {
+        return None;
+    }
+    println!("[cors] Allowing {} origin(s) on the JSON endpoints", origins.len());
+    Some(
+        CorsLayer::new()
+            .allow_origin(AllowOrigin::list(origins))
+            .allow_methods([Method::GET, Method::POST])
+            .allow_headers([
+                header::AUTHORIZATION,
+                header::CONTENT_TYPE,
+                header::IF_NONE_MATCH,
+                HeaderName::from_static(bp_web::api_keys::API_KEY_HEADER),
+            ])
+            .expose_headers([header::ETAG, header::RETRY_AFTER])
+            .max_age(std::time::Duration::from_secs(3600)),
+    )
+}
+
+/// Live hero metrics for the home page (server-sent events).
+///
+/// Sends the latest values on connect, then a `metrics` event after each ingestion run.
+#[cfg(feature = "ssr")]
+async fn metrics_stream_handler() -> axum::response::Response {
+    use axum::response::IntoResponse;
+    use axum::response::sse::{Event, KeepAlive, Sse};
+    use bp_web::live::LiveMetrics;
+    use futures::stream::{self, StreamExt};
+    use tokio::sync::broadcast::error::RecvError;
+
+    fn metrics_event(metrics: &LiveMetrics) -> Option<Event> {
+        Event::default().event("metrics").json_data(metrics).ok()
+    }
+
+    let (latest, receiver) = bp_web::live::subscribe();
+    let initial = stream::iter(latest.as_ref().and_then(metrics_event));
+    let updates = stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(metrics) => return Some((metrics, receiver)),
+                // Fell behind: the next receive returns the newest values
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    })
+    .filter_map(|metrics| async move { metrics_event(&metrics) });
+
+    let events = initial.chain(updates).map(Ok::<_, std::convert::Infallible>);
+    Sse::new(events).keep_alive(KeepAlive::default()).into_response()
+}
+
+/// Query string for `/badge.svg`.
+#[cfg(feature = "ssr")]
+#[derive(serde::Deserialize)]
+struct BadgeQuery {
+    /// "status" (default), "apy" or "stake"
+    metric: Option<String>,
+}
+
+/// Prometheus counters for upstream API usage (requests, cache hits, errors and budget
+/// refusals per host), to see which upstream is burning its quota.
+#[cfg(feature = "ssr")]
+async fn prometheus_metrics_handler() -> axum::response::Response {
+    use axum::http::header;
+    use axum::response::IntoResponse;
+
+    (
+        [
+            (header::CONTENT_TYPE, "text/plain; version=0.0.4"),
+            (header::CACHE_CONTROL, "no-store"),
+        ],
+        bp_web::api::render_upstream_metrics(),
+    )
+        .into_response()
+}
+
+/// Embeddable status badge, rendered from the latest cached metrics snapshot.
+#[cfg(feature = "ssr")]
+async fn badge_handler(
+    headers: axum::http::HeaderMap,
+    axum::extract::Query(query): axum::extract::Query<BadgeQuery>,
+) -> axum::response::Response {
+    use axum::http::{StatusCode, header};
+    use axum::response::IntoResponse;
+    use bp_web::badge::{Badge, BadgeMetric};
+    use bp_web::components::metrics::MetricsData;
+    use bp_web::http_cache::{if_none_match, not_modified, weak_etag, with_etag};
+
+    const CACHE_CONTROL: &str = "public, max-age=300";
+
+    let Some(metric) = BadgeMetric::parse(query.metric.as_deref().unwrap_or("status")) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            [(header::CACHE_CONTROL, "no-store")],
+            "Unknown metric",
+        )
+            .into_response();
+    };
+
+    let snapshot = bp_web::db::get_latest_metrics().await.unwrap_or_else(|e| {
+        eprintln!("[badge] Failed to read metrics snapshot: {}", e);
+        None
+    });
+    let etag = snapshot.as_ref().map(|(_, fetched_at)| {
+        let version: String = fetched_at.chars().filter(char::is_ascii_alphanumeric).collect();
+        weak_etag(&format!("badge-{}", metric.as_str()), &version)
+    });
+    if let Some(etag) = &etag
+        && if_none_match(&headers, etag)
+    {
+        return not_modified(etag, CACHE_CONTROL);
+    }
+
+    let data = snapshot.and_then(|(json, _)| serde_json::from_str::<MetricsData>(&json).ok());
+    let svg = Badge::for_metric(bp_web::config::CONFIG.name, metric, data.as_ref().map(|d| &d.validator)).to_svg();
+
+    let response = (
+        [
+            (header::CONTENT_TYPE, "image/svg+xml; charset=utf-8"),
+            (header::CACHE_CONTROL, CACHE_CONTROL),
+        ],
+        svg,
+    )
+        .into_response();
+    match &etag {
+        Some(etag) => with_etag(response, etag),
+        None => response,
+    }
+}
+
+/// Contact form submission: screens the message, forwards it to the operator, and
+/// redirects back to the form with the outcome (post/redirect/get).
+#[cfg(feature = "ssr")]
+async fn contact_submit_handler(
+    headers: axum::http::HeaderMap,
+    axum::Form(form): axum::Form<bp_web::contact::ContactForm>,
+) -> axum::response::Response {
+    use axum::response::{IntoResponse, Redirect};
+
+    let client_ip = bp_web::contact::client_ip(&headers);
+    let location = match bp_web::contact::submit(form, &client_ip).await {
+        Ok(()) => "/contact?sent=1".to_string(),
+        Err(e) => format!("/contact?error={}", e.code()),
+    };
+    Redirect::to(&location).into_response()
+}
+
+/// sitemap.xml, rendered once at startup from the Leptos route list
+#[cfg(feature = "ssr")]
+static SITEMAP_XML: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+
+#[cfg(feature = "ssr")]
+async fn sitemap_handler() -> axum::response::Response {
+    use axum::http::header;
+    use axum::response::IntoResponse;
+
+    (
+        [
+            (header::CONTENT_TYPE, "application/xml; charset=utf-8"),
+            (header::CACHE_CONTROL, "public, max-age=3600"),
+        ],
+        SITEMAP_XML.get().cloned().unwrap_or_default(),
+    )
+        .into_response()
+}
+
 #[cfg(feature = "ssr")]
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -62,7 +1172,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     use leptos::prelude::*;
     use leptos_axum::{LeptosRoutes, generate_route_list};
     use tower_http::compression::CompressionLayer;
-    use tower_http::services::ServeDir;
+    use tower_http::services::{ServeDir, ServeFile};
 
     #[derive(Parser)]
     #[command(name = "bp-web", about = "Block Parliament web server")]
@@ -71,24 +1181,157 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         #[arg(long)]
         update_now: bool,
 
+        /// With --update-now: fetch everything but write nothing, printing what would change
+        #[arg(long, requires = "update_now")]
+        dry_run: bool,
+
         /// Data directory for SQLite database and reports
         #[arg(long, env = "DATA_DIR", default_value = "./data")]
         data_dir: String,
+
+        /// Serve synthetic sample data (seeded into <data-dir>/demo) instead of real books;
+        /// needs no API keys and skips scheduled ingestion
+        #[arg(long, conflicts_with = "update_now")]
+        demo: bool,
+
+        /// Print the public API's OpenAPI spec (JSON) and exit
+        #[arg(long)]
+        print_openapi: bool,
+
+        /// Create an API key with this name, print it once and exit
+        #[arg(long, value_name = "NAME", conflicts_with_all = ["revoke_api_key", "list_api_keys"])]
+        create_api_key: Option<String>,
+
+        /// Scopes for --create-api-key, comma-separated (read, sql)
+        #[arg(long, default_value = "read", requires = "create_api_key")]
+        scopes: String,
+
+        /// Requests per minute for --create-api-key
+        #[arg(long, default_value_t = bp_web::api_keys::DEFAULT_RATE_LIMIT_PER_MINUTE, requires = "create_api_key")]
+        rate_limit: u32,
+
+        /// Revoke the active API key with this name and exit
+        #[arg(long, value_name = "NAME", conflicts_with = "list_api_keys")]
+        revoke_api_key: Option<String>,
+
+        /// List active API keys and exit
+        #[arg(long)]
+        list_api_keys: bool,
+
+        /// Run the startup self-check (data dir, config.toml, database schema) and exit
+        #[arg(long, conflicts_with = "update_now")]
+        check: bool,
     }
 
     let cli = Cli::parse();
 
+    if cli.print_openapi {
+        use utoipa::OpenApi;
+        println!("{}", ApiDoc::openapi().to_pretty_json()?);
+        return Ok(());
+    }
+
+    let data_dir = if cli.demo {
+        let demo_dir = format!("{}/{}", cli.data_dir.trim_end_matches('/'), bp_web::demo::SUBDIR);
+        bp_web::demo::seed(&demo_dir).await.map_err(|e| {
+            eprintln!("Failed to seed demo data: {:#}", e);
+            e
+        })?;
+        demo_dir
+    } else {
+        cli.data_dir.clone()
+    };
+
+    // Refuse to serve (or --check) with a broken data dir or config
+    let serving =
+        !(cli.update_now || cli.create_api_key.is_some() || cli.revoke_api_key.is_some() || cli.list_api_keys);
+    if serving || cli.check {
+        let problems = bp_web::selfcheck::run(&data_dir).await;
+        if !problems.is_empty() {
+            eprintln!("Self-check failed for {}:", data_dir);
+            for problem in &problems {
+                eprintln!("  - {}", problem);
+            }
+            std::process::exit(1);
+        }
+    }
+
     // Initialize database
-    bp_web::db::init_db(&cli.data_dir).await.map_err(|e| {
+    bp_web::db::init_db(&data_dir).await.map_err(|e| {
         eprintln!("Failed to initialize database: {}", e);
         e
     })?;
 
+    if cli.check {
+        println!("Self-check passed for {}", data_dir);
+        return Ok(());
+    }
+
+    if let Some(name) = &cli.create_api_key {
+        let scopes = bp_web::api_keys::parse_scopes(&cli.scopes)?;
+        let key = bp_web::api_keys::create(name, &scopes, cli.rate_limit).await?;
+        println!("API key '{}' created. It is shown only once:\n\n{}\n", name, key);
+        println!("Send it as `X-API-Key: <key>` or `Authorization: Bearer <key>`.");
+        return Ok(());
+    }
+    if let Some(name) = &cli.revoke_api_key {
+        if bp_web::api_keys::revoke(name).await? {
+            println!("API key '{}' revoked.", name);
+        } else {
+            eprintln!("No active API key named '{}'.", name);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if cli.list_api_keys {
+        let keys = bp_web::api_keys::list().await?;
+        if keys.is_empty() {
+            println!("No active API keys.");
+        }
+        for key in keys {
+            let scopes: Vec<&str> = key.scopes.iter().map(|s| s.as_str()).collect();
+            println!(
+                "{:<24} scopes={:<10} {}/min  created {}  last used {}",
+                key.name,
+                scopes.join(","),
+                key.rate_limit_per_minute,
+                key.created_at,
+                key.last_used_at.as_deref().unwrap_or("never")
+            );
+        }
+        return Ok(());
+    }
+
+    // --update-now --dry-run: preview ingestion and the financial refresh, write nothing
+    if cli.update_now && cli.dry_run {
+        println!("Previewing metrics ingestion (dry run)...");
+        match bp_web::ingestion::preview_ingestion().await {
+            Ok(true) => {}
+            Ok(false) => eprintln!("Ingestion returned no data."),
+            Err(e) => {
+                eprintln!("Ingestion preview failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        println!("Previewing financial cache refresh (dry run)...");
+        match bp_web::scheduler::preview_financial_refresh().await {
+            Ok(summary) => println!("{}", summary.trim_end()),
+            Err(e) => {
+                eprintln!("Financial refresh preview failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
     // --update-now: run ingestion once and exit (no web server)
     if cli.update_now {
         println!("Running one-time metrics ingestion...");
         match bp_web::ingestion::run_ingestion().await {
-            Ok(true) => println!("Ingestion completed successfully."),
+            Ok(true) => {
+                println!("Ingestion completed successfully.");
+                bp_web::heartbeat::ping(bp_web::heartbeat::INGESTION).await;
+            }
             Ok(false) => eprintln!("Ingestion returned no data."),
             Err(e) => {
                 eprintln!("Ingestion failed: {}", e);
@@ -98,8 +1341,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
-    // Start background scheduler for periodic ingestion
-    bp_web::scheduler::spawn_scheduler();
+    if cli.demo {
+        bp_web::demo::seed_metrics().await?;
+        println!(
+            "Demo mode: serving sample data from {} (/financials password: {})",
+            data_dir,
+            if std::env::var("FINANCIALS_PASSWORD").is_ok_and(|p| !p.is_empty()) {
+                "FINANCIALS_PASSWORD"
+            } else {
+                bp_web::demo::PASSWORD
+            }
+        );
+    } else {
+        // Start background scheduler for periodic ingestion
+        bp_web::scheduler::spawn_scheduler();
+    }
+    #[cfg(unix)]
+    bp_web::selfcheck::spawn_sighup_reload();
 
     let conf = get_configuration(None).map_err(|e| {
         eprintln!("Failed to load Leptos configuration: {}", e);
@@ -108,17 +1366,86 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let leptos_options = conf.leptos_options;
     let addr = leptos_options.site_addr;
     let routes = generate_route_list(bp_web::app::App);
+    SITEMAP_XML.get_or_init(|| {
+        // Markdown pages share one `/:slug` route, so list the content files individually
+        let content_paths: Vec<String> = bp_web::content::slugs()
+            .iter()
+            .map(|slug| format!("/{}", slug))
+            .collect();
+        bp_web::seo::sitemap_xml(
+            bp_web::config::CONFIG.base_url,
+            routes
+                .iter()
+                .map(|route| route.path())
+                .chain(content_paths.iter().map(String::as_str)),
+        )
+    });
 
     let site_root = leptos_options.site_root.clone();
+    // Endpoints scripts and other frontends call: API keys work here, and so does CORS
+    let json_api = Router::new()
+        .route(
+            "/financials/sql",
+            axum::routing::get(financials_sql_page_handler).post(financials_sql_query_handler),
+        )
+        .route("/financials/epochs", axum::routing::get(financials_epochs_handler))
+        .route(
+            "/financials/delegators",
+            axum::routing::get(financials_delegators_handler),
+        )
+        .route(
+            "/financials/expenses",
+            axum::routing::get(financials_expense_trends_handler),
+        )
+        .route(
+            "/financials/ledgers/{file}",
+            axum::routing::get(financials_ledger_handler),
+        )
+        .route(
+            "/financials/timeline.json",
+            axum::routing::get(financials_timeline_handler),
+        )
+        .route(
+            "/financials/graphql",
+            axum::routing::get(financials_graphiql_handler).post(financials_graphql_handler),
+        )
+        .route("/api/public/epoch", axum::routing::get(epoch_api_handler))
+        .route("/api/public/revenue", axum::routing::get(revenue_api_handler))
+        .route("/api/public/metrics/stream", axum::routing::get(metrics_stream_handler))
+        .route("/api/openapi.json", axum::routing::get(openapi_handler));
+    let json_api = match cors_layer() {
+        Some(cors) => json_api.layer(cors),
+        None => json_api,
+    };
+
     let app = Router::new()
         .route("/financials", axum::routing::get(financials_handler))
+        .route("/financials/admin", axum::routing::get(financials_admin_handler))
+        .route(
+            "/financials/admin/reload",
+            axum::routing::post(financials_admin_reload_handler),
+        )
+        .route(
+            "/financials/admin/expenses",
+            axum::routing::get(financials_expenses_page_handler).post(financials_expenses_submit_handler),
+        )
+        .route("/financials/plan", axum::routing::get(financials_plan_handler))
+        .merge(json_api)
+        .route("/metrics", axum::routing::get(prometheus_metrics_handler))
+        .route("/badge.svg", axum::routing::get(badge_handler))
+        .route("/sitemap.xml", axum::routing::get(sitemap_handler))
+        .route("/contact", axum::routing::post(contact_submit_handler))
+        // Top-level static files must be routed explicitly, or `/:slug` content pages would catch them
+        .route_service("/robots.txt", ServeFile::new(format!("{}/robots.txt", site_root)))
+        .route_service("/stake.js", ServeFile::new(format!("{}/stake.js", site_root)))
         .leptos_routes(&leptos_options, routes, {
             move || {
                 use bp_web::app::App;
                 use leptos_meta::MetaTags;
+                let locale = bp_web::i18n::provide_request_locale();
                 view! {
                     <!DOCTYPE html>
-                    <html lang="en">
+                    <html lang=locale.code()>
                         <head>
                             <meta charset="utf-8" />
                             <meta name="viewport" content="width=device-width, initial-scale=1" />
@@ -129,6 +1456,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             <MetaTags />
                         </head>
                         <body>
+                            <a href="#main" class="skip-link">
+                                {locale.t("a11y.skip")}
+                            </a>
                             <App />
                         </body>
                     </html>
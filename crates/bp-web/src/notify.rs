@@ -0,0 +1,53 @@
+//! Operator notifications.
+//!
+//! Messages are posted to a chat webhook (`NOTIFY_WEBHOOK_URL`). The payload carries
+//! the text as both `text` (Slack) and `content` (Discord), so either works.
+
+use anyhow::{Context, Result, bail};
+
+use crate::api::http::post_json_uncached;
+
+/// Longest message sent in one post (Discord rejects content over 2000 characters)
+const MAX_MESSAGE_CHARS: usize = 1900;
+
+/// Whether a notification webhook is configured
+pub fn is_configured() -> bool {
+    webhook_url().is_some()
+}
+
+fn webhook_url() -> Option<String> {
+    std::env::var("NOTIFY_WEBHOOK_URL")
+        .ok()
+        .filter(|url| !url.trim().is_empty())
+}
+
+/// Post `message` to the notification webhook
+pub async fn send(message: &str) -> Result<()> {
+    let url = webhook_url().context("NOTIFY_WEBHOOK_URL is not set")?;
+
+    let message = truncate_chars(message, MAX_MESSAGE_CHARS);
+    let body = serde_json::json!({ "text": message, "content": message }).to_string();
+    if !post_json_uncached(&url, &body).await {
+        bail!("notification webhook rejected the message");
+    }
+    Ok(())
+}
+
+/// First `max` characters of `s`, with an ellipsis when cut
+fn truncate_chars(s: &str, max: usize) -> String {
+    match s.char_indices().nth(max) {
+        Some((idx, _)) => format!("{}\u{2026}", &s[..idx]),
+        None => s.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncates_on_char_boundaries() {
+        assert_eq!(truncate_chars("hello", 10), "hello");
+        assert_eq!(truncate_chars("señor", 3), "señ\u{2026}");
+    }
+}
@@ -0,0 +1,163 @@
+use crate::config::CONFIG;
+use leptos::prelude::*;
+use leptos_meta::{Link, Meta, Title};
+use leptos_router::hooks::use_query_map;
+
+use crate::components::{LanguageSwitcher, Section};
+use crate::i18n::use_locale;
+
+/// hCaptcha site key when the form's captcha is enabled
+fn hcaptcha_sitekey() -> Option<String> {
+    #[cfg(feature = "ssr")]
+    {
+        crate::contact::hcaptcha_config().map(|c| c.sitekey)
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        None
+    }
+}
+
+/// Whether submissions can be forwarded (a notification webhook is configured)
+fn form_enabled() -> bool {
+    #[cfg(feature = "ssr")]
+    {
+        crate::notify::is_configured()
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        false
+    }
+}
+
+/// Contact / delegation-inquiry form. Posts to the `/contact` handler, which redirects
+/// back here with `?sent=1` or `?error=<code>`.
+#[component]
+pub fn ContactPage() -> impl IntoView {
+    let locale = use_locale();
+    let canonical = format!("{}/contact", CONFIG.base_url);
+    let query = use_query_map();
+    let sent = query.with_untracked(|q| q.get("sent").is_some());
+    let error = query.with_untracked(|q| q.get("error"));
+    let sitekey = hcaptcha_sitekey();
+
+    let status = if sent {
+        Some(("status", locale.t("contact.sent")))
+    } else {
+        error.map(|code| {
+            let key = match code.as_str() {
+                "rate" => "contact.error.rate",
+                "captcha" => "contact.error.captcha",
+                "delivery" => "contact.error.delivery",
+                _ => "contact.error.invalid",
+            };
+            ("alert", locale.t(key))
+        })
+    };
+
+    view! {
+        <Title text=locale.t("contact.meta.title") />
+        <Meta name="description" content=locale.t("contact.meta.description") />
+        <Link rel="canonical" href=canonical />
+        <main id="main" tabindex="-1" class="max-w-[80ch] mx-auto px-4 py-4 md:py-8">
+            <header class="mb-8 text-center">
+                <h1 class="text-xl font-bold mb-2">
+                    <span aria-hidden="true">"\u{2500}\u{2524} "</span>
+                    {locale.t("contact.heading")}
+                    <span aria-hidden="true">" \u{251C}\u{2500}"</span>
+                </h1>
+                <div class="mt-2">
+                    <a href="/" class="text-sm">"\u{2190} " {locale.t("nav.back_home")}</a>
+                </div>
+                <LanguageSwitcher />
+            </header>
+
+            <Section id="message" title=locale.t("contact.title")>
+                <p class="mb-4">{locale.t("contact.intro")}</p>
+
+                {status.map(|(role, text)| view! {
+                    <p role=role class="mb-4 border border-dashed border-[var(--rule)] p-3">
+                        <strong>{text}</strong>
+                    </p>
+                })}
+
+                {if form_enabled() {
+                    view! {
+                        <form method="post" action="/contact" class="space-y-3">
+                            <label class="block">
+                                {locale.t("contact.name")}
+                                <input
+                                    type="text"
+                                    name="name"
+                                    maxlength="100"
+                                    autocomplete="name"
+                                    class="block w-full px-2 border border-dashed border-[var(--rule)] bg-[var(--paper)]"
+                                />
+                            </label>
+                            <label class="block">
+                                {locale.t("contact.reply_to")} " *"
+                                <input
+                                    type="text"
+                                    name="reply_to"
+                                    required
+                                    maxlength="200"
+                                    autocomplete="email"
+                                    class="block w-full px-2 border border-dashed border-[var(--rule)] bg-[var(--paper)]"
+                                />
+                            </label>
+                            <label class="block">
+                                {locale.t("contact.stake")}
+                                <input
+                                    type="text"
+                                    name="stake"
+                                    maxlength="40"
+                                    inputmode="decimal"
+                                    class="block w-full px-2 border border-dashed border-[var(--rule)] bg-[var(--paper)]"
+                                />
+                            </label>
+                            <label class="block">
+                                {locale.t("contact.message")} " *"
+                                <textarea
+                                    name="message"
+                                    required
+                                    maxlength="1500"
+                                    rows="6"
+                                    class="block w-full px-2 border border-dashed border-[var(--rule)] bg-[var(--paper)]"
+                                ></textarea>
+                            </label>
+                            // Honeypot - hidden from people and assistive tech, filled in by bots
+                            <div class="hp-field" aria-hidden="true">
+                                <label>
+                                    "Website"
+                                    <input type="text" name="website" tabindex="-1" autocomplete="off" />
+                                </label>
+                            </div>
+                            {sitekey.map(|key| view! {
+                                <script src="https://js.hcaptcha.com/1/api.js" async defer></script>
+                                <div class="h-captcha" data-sitekey=key></div>
+                            })}
+                            <button
+                                type="submit"
+                                class="px-3 py-1 border border-dashed border-[var(--rule)] hover:bg-[var(--rule)] transition-colors cursor-pointer"
+                            >
+                                {locale.t("contact.send")}
+                            </button>
+                        </form>
+                    }
+                        .into_any()
+                } else {
+                    let twitter_url = format!("https://x.com/{}", CONFIG.contact.twitter);
+                    view! {
+                        <p class="text-[var(--ink-light)]">
+                            {locale.t("contact.unavailable")} " "
+                            <a href=twitter_url target="_blank" rel="noopener noreferrer">
+                                "@" {CONFIG.contact.twitter}
+                            </a>
+                        </p>
+                    }
+                        .into_any()
+                }}
+            </Section>
+        </main>
+    }
+}
@@ -2,85 +2,104 @@ use crate::config::CONFIG;
 use leptos::prelude::*;
 use leptos_meta::{Link, Meta, Title};
 
-use crate::components::{CopyButton, ExternalLink, Section};
+use crate::components::{
+    CopyButton, ExternalLink, LanguageSwitcher, RewardsCalculator, Section, StakeWidget, StructuredData,
+};
+use crate::i18n::{Locale, use_locale};
 
 #[component]
 pub fn DelegatePage() -> impl IntoView {
     let twitter_url = format!("https://x.com/{}", CONFIG.contact.twitter);
     let twitter_url2 = twitter_url.clone();
     let canonical = format!("{}/delegate", CONFIG.base_url);
+    let locale = use_locale();
 
     view! {
-        <Title text="Delegate SOL to Block Parliament - Staking Guide" />
-        <Meta name="description" content="Delegate SOL to Block Parliament validator. Non-custodial native staking and liquid staking guides for Phantom, Solflare, and other wallets." />
+        <Title text=locale.t("delegate.meta.title") />
+        <Meta name="description" content=locale.t("delegate.meta.description") />
         <Link rel="canonical" href=canonical />
-        <main class="max-w-[80ch] mx-auto px-4 py-4 md:py-8">
+        <StructuredData data=crate::seo::faq_page(&faq_entries(locale)) />
+        <main id="main" tabindex="-1" class="max-w-[80ch] mx-auto px-4 py-4 md:py-8">
             // Header - responsive, uses Section-style pattern instead of fixed-width ASCII box
             <header class="mb-8 text-center">
                 <h1 class="text-xl font-bold mb-2">
-                    "\u{2500}\u{2524} Delegate to Block Parliament \u{251C}\u{2500}"
+                    <span aria-hidden="true">"\u{2500}\u{2524} "</span>
+                    {locale.t("delegate.heading")}
+                    <span aria-hidden="true">" \u{251C}\u{2500}"</span>
                 </h1>
                 <div class="mt-2">
-                    <a href="/" class="text-sm">"\u{2190} back to home"</a>
+                    <a href="/" class="text-sm">"\u{2190} " {locale.t("nav.back_home")}</a>
                 </div>
+                <LanguageSwitcher />
             </header>
 
             // Quick actions
-            <Section id="quick" title="Quick Actions">
+            <Section id="quick" title=locale.t("delegate.quick.title")>
                 <div class="mb-4">
                     <div class="mb-3 border border-dashed border-[var(--rule)] p-3">
-                        <div class="text-[var(--ink-light)] text-sm mb-1">"VOTE ACCOUNT"</div>
+                        <div class="text-[var(--ink-light)] text-sm mb-1">{locale.t("delegate.quick.vote_account")}</div>
                         <code class="break-all">{CONFIG.vote_account}</code>
                     </div>
                     <div class="flex flex-wrap gap-2">
-                        <CopyButton text=CONFIG.vote_account.to_string() label="Copy vote account".to_string() />
-                        <ExternalLink href=CONFIG.links.solscan.to_string() label="Open in Solscan".to_string() />
-                        <ExternalLink href=CONFIG.links.stakewiz.to_string() label="View on StakeWiz".to_string() />
+                        <CopyButton text=CONFIG.vote_account.to_string() label=locale.t("delegate.quick.copy") />
+                        <ExternalLink href=CONFIG.links.solscan.to_string() label=locale.t("delegate.quick.solscan") />
+                        <ExternalLink href=CONFIG.links.stakewiz.to_string() label=locale.t("delegate.quick.stakewiz") />
                         <ExternalLink href=CONFIG.links.validators_app.to_string() label="validators.app".to_string() />
                     </div>
                 </div>
             </Section>
 
+            // Stake from the browser or a wallet's in-app browser
+            <Section id="stake" title=locale.t("delegate.stake.title")>
+                <StakeWidget />
+            </Section>
+
+            // Rewards calculator
+            <Section id="calculator" title=locale.t("delegate.calculator.title")>
+                <RewardsCalculator />
+            </Section>
+
             // Native Staking Instructions
-            <Section id="native" title="Delegate SOL (Native Staking)">
+            <Section id="native" title=locale.t("delegate.native.title")>
                 <p class="mb-4">
-                    "Delegation is " <strong>"non-custodial"</strong> ": your SOL moves to a stake account "
-                    "that remains under your control. The validator cannot access, move, or withdraw "
-                    "your delegated stake."
+                    {locale.t("delegate.native.intro")}
                 </p>
 
                 <div class="space-y-6">
                     // Phantom
                     <div>
-                        <h3 class="font-bold mb-2">"Phantom Wallet"</h3>
+                        <h3 class="font-bold mb-2">{locale.t("delegate.native.phantom")}</h3>
                         <ol class="list-none space-y-1 pl-4 border-l border-dashed border-[var(--ink-light)]">
-                            <li>"1. Open Phantom \u{2192} click \"Stake\" button on home screen"</li>
-                            <li>"2. Tap \"Search for a validator\" at the top"</li>
-                            <li>"3. Search \"Block Parliament\" or paste the vote address"</li>
-                            <li>"4. Select Block Parliament from results"</li>
-                            <li>"5. Enter amount you want to stake"</li>
-                            <li>"6. Review details \u{2192} tap \"Stake\""</li>
+                            {steps(&[
+                                "delegate.native.phantom.1",
+                                "delegate.native.phantom.2",
+                                "delegate.native.phantom.3",
+                                "delegate.native.phantom.4",
+                                "delegate.native.phantom.5",
+                                "delegate.native.phantom.6",
+                            ])}
                         </ol>
                     </div>
 
                     // Solflare
                     <div>
-                        <h3 class="font-bold mb-2">"Solflare Wallet"</h3>
+                        <h3 class="font-bold mb-2">{locale.t("delegate.native.solflare")}</h3>
                         <ol class="list-none space-y-1 pl-4 border-l border-dashed border-[var(--ink-light)]">
-                            <li>"1. Open Solflare \u{2192} go to \"Staking\" tab"</li>
-                            <li>"2. Tap \"Stake SOL\" or \"+\" to add new stake"</li>
-                            <li>"3. Search \"Block Parliament\" or paste vote address"</li>
-                            <li>"4. Enter stake amount"</li>
-                            <li>"5. Confirm the transaction"</li>
+                            {steps(&[
+                                "delegate.native.solflare.1",
+                                "delegate.native.solflare.2",
+                                "delegate.native.solflare.3",
+                                "delegate.native.solflare.4",
+                                "delegate.native.solflare.5",
+                            ])}
                         </ol>
                     </div>
 
                     // Other wallets
                     <div>
-                        <h3 class="font-bold mb-2">"Other Wallets"</h3>
+                        <h3 class="font-bold mb-2">{locale.t("delegate.native.other")}</h3>
                         <p class="pl-4 border-l border-dashed border-[var(--ink-light)]">
-                            "Most Solana wallets support staking. Look for a \"Stake\" or \"Earn\" "
-                            "section, search for \"Block Parliament\", or paste the vote account address: "
+                            {locale.t("delegate.native.other.body")} " "
                             <code class="text-sm bg-[var(--rule)] px-1 break-all">{CONFIG.vote_account}</code>
                         </p>
                     </div>
@@ -88,32 +107,39 @@ pub fn DelegatePage() -> impl IntoView {
             </Section>
 
             // Liquid Staking
-            <Section id="liquid" title="Liquid Stake">
+            <Section id="liquid" title=locale.t("delegate.liquid.title")>
                 {move || {
                     if let (Some(symbol), Some(url)) = (CONFIG.lst.symbol, CONFIG.lst.primary_url) {
                         view! {
                             <div>
                                 <p class="mb-4">
-                                    "Liquid staking lets you stake while keeping your capital liquid. "
-                                    "Stake SOL \u{2192} receive " <strong>{symbol}</strong> " tokens that can be used in DeFi."
+                                    {locale.tr("delegate.liquid.intro", &[("symbol", symbol)])}
                                 </p>
                                 <div class="space-y-3">
                                     <div>
-                                        <h3 class="font-bold mb-2">"How it works"</h3>
+                                        <h3 class="font-bold mb-2">{locale.t("delegate.liquid.how")}</h3>
                                         <ol class="list-none space-y-1 pl-4 border-l border-dashed border-[var(--ink-light)]">
-                                            <li>"1. Connect your wallet to the liquid staking app"</li>
-                                            <li>"2. Enter the amount of SOL to stake"</li>
-                                            <li>"3. Receive " {symbol} " tokens representing your staked SOL"</li>
-                                            <li>"4. Use " {symbol} " in DeFi or hold to accumulate rewards"</li>
-                                            <li>"5. Unstake anytime by swapping " {symbol} " back to SOL"</li>
+                                            {[
+                                                "delegate.liquid.step.1",
+                                                "delegate.liquid.step.2",
+                                                "delegate.liquid.step.3",
+                                                "delegate.liquid.step.4",
+                                                "delegate.liquid.step.5",
+                                            ]
+                                                .into_iter()
+                                                .enumerate()
+                                                .map(|(i, key)| view! {
+                                                    <li>{format!("{}. {}", i + 1, locale.tr(key, &[("symbol", symbol)]))}</li>
+                                                })
+                                                .collect_view()}
                                         </ol>
                                     </div>
                                     <div class="flex flex-wrap gap-2">
-                                        <ExternalLink href=url.to_string() label="Liquid stake now".to_string() />
+                                        <ExternalLink href=url.to_string() label=locale.t("delegate.liquid.cta") />
                                         {CONFIG.lst.mint_address.map(|mint| {
                                             let explorer_url = format!("https://solscan.io/token/{}", mint);
                                             view! {
-                                                <ExternalLink href=explorer_url label="View token on Solscan".to_string() />
+                                                <ExternalLink href=explorer_url label=locale.t("delegate.liquid.token") />
                                             }
                                         })}
                                     </div>
@@ -124,10 +150,10 @@ pub fn DelegatePage() -> impl IntoView {
                         view! {
                             <div>
                                 <p class="mb-4 text-[var(--ink-light)]">
-                                    "Single-validator liquid staking token is in development."
+                                    {locale.t("delegate.liquid.pending")}
                                 </p>
                                 <p>
-                                    "Follow "
+                                    {locale.t("delegate.liquid.follow")} " "
                                     <a
                                         href=twitter_url.clone()
                                         target="_blank"
@@ -135,7 +161,7 @@ pub fn DelegatePage() -> impl IntoView {
                                     >
                                         "@" {CONFIG.contact.twitter}
                                     </a>
-                                    " for updates on when liquid staking becomes available."
+                                    " " {locale.t("delegate.liquid.follow.after")}
                                 </p>
                             </div>
                         }.into_any()
@@ -144,52 +170,44 @@ pub fn DelegatePage() -> impl IntoView {
             </Section>
 
             // FAQ
-            <Section id="faq" title="FAQ">
+            <Section id="faq" title=locale.t("delegate.faq.title")>
                 <div class="space-y-4">
                     <div>
-                        <h3 class="font-bold">"Is staking custodial?"</h3>
+                        <h3 class="font-bold">{locale.t("delegate.faq.custodial.q")}</h3>
                         <p class="mt-1 pl-4 border-l border-dashed border-[var(--ink-light)]">
-                            <strong>"No."</strong> " When you delegate, your SOL moves to a stake account "
-                            "that you control. The validator cannot access your funds. You can undelegate "
-                            "at any time without the validator's permission."
+                            <strong>{locale.t("delegate.faq.no")}</strong> " " {locale.t("delegate.faq.custodial.a")}
                         </p>
                     </div>
 
                     <div>
-                        <h3 class="font-bold">"Can the validator move my SOL?"</h3>
+                        <h3 class="font-bold">{locale.t("delegate.faq.move.q")}</h3>
                         <p class="mt-1 pl-4 border-l border-dashed border-[var(--ink-light)]">
-                            <strong>"No."</strong> " Validators only have authority to use your stake for voting. "
-                            "They cannot withdraw, transfer, or access your SOL in any way. The withdrawal "
-                            "authority (the key that can move funds) remains with you."
+                            <strong>{locale.t("delegate.faq.no")}</strong> " " {locale.t("delegate.faq.move.a")}
                         </p>
                     </div>
 
                     <div>
-                        <h3 class="font-bold">"How long to activate/deactivate stake?"</h3>
+                        <h3 class="font-bold">{locale.t("delegate.faq.timing.q")}</h3>
                         <p class="mt-1 pl-4 border-l border-dashed border-[var(--ink-light)]">
-                            "Solana uses a warmup/cooldown period. Stake activates at the start of the "
-                            "next epoch (epochs are ~2-3 days). Deactivation also takes until the end of "
-                            "the current epoch. During cooldown, your stake doesn't earn rewards but "
-                            "remains in your control."
+                            {locale.t("delegate.faq.timing.a")}
                         </p>
                     </div>
 
                     <div>
-                        <h3 class="font-bold">"What are the risks?"</h3>
+                        <h3 class="font-bold">{locale.t("delegate.faq.risks.q")}</h3>
                         <div class="mt-1 pl-4 border-l border-dashed border-[var(--ink-light)]">
                             <ul class="list-none space-y-1">
                                 <li>
-                                    "\u{2022} " <strong>"Performance risk:"</strong> " If the validator performs poorly "
-                                    "(high skip rate, downtime), you may earn lower rewards than other validators."
+                                    "\u{2022} " <strong>{locale.t("delegate.faq.risks.performance")}</strong> " "
+                                    {locale.t("delegate.faq.risks.performance.body")}
                                 </li>
                                 <li>
-                                    "\u{2022} " <strong>"Slashing risk:"</strong> " Solana does not currently implement "
-                                    "slashing, but this may change in the future."
+                                    "\u{2022} " <strong>{locale.t("delegate.faq.risks.slashing")}</strong> " "
+                                    {locale.t("delegate.faq.risks.slashing.body")}
                                 </li>
                                 <li>
-                                    "\u{2022} " <strong>"Smart contract risk (LST only):"</strong> " Liquid staking "
-                                    "involves smart contracts that could have bugs. Native staking has no smart "
-                                    "contract risk."
+                                    "\u{2022} " <strong>{locale.t("delegate.faq.risks.contract")}</strong> " "
+                                    {locale.t("delegate.faq.risks.contract.body")}
                                 </li>
                             </ul>
                         </div>
@@ -199,34 +217,76 @@ pub fn DelegatePage() -> impl IntoView {
             </Section>
 
             // Security Warning
-            <Section id="security" title="Security Notice">
+            <Section id="security" title=locale.t("delegate.security.title")>
                 <div class="border border-dashed border-[var(--rule)] p-3 mb-4">
-                    <p class="font-bold mb-2">"\u{26A0} We will never:"</p>
+                    <p class="font-bold mb-2">"\u{26A0} " {locale.t("delegate.security.never")}</p>
                     <ul class="list-none space-y-1">
-                        <li>"\u{2022} DM you asking for seed phrases or private keys"</li>
-                        <li>"\u{2022} Ask you to connect your wallet to unknown sites"</li>
-                        <li>"\u{2022} Request you send SOL to receive rewards"</li>
-                        <li>"\u{2022} Ask for remote access to your device"</li>
+                        {[
+                            "delegate.security.never.1",
+                            "delegate.security.never.2",
+                            "delegate.security.never.3",
+                            "delegate.security.never.4",
+                        ]
+                            .into_iter()
+                            .map(|key| view! { <li>"\u{2022} " {locale.t(key)}</li> })
+                            .collect_view()}
                     </ul>
                 </div>
                 <p>
-                    "If someone contacts you claiming to be from Block Parliament asking for sensitive "
-                    "information, it's a scam. Report suspicious activity to "
+                    {locale.t("delegate.security.scam")} " "
                     <a href=twitter_url2 target="_blank" rel="noopener noreferrer">
                         "@" {CONFIG.contact.twitter}
                     </a>
                     "."
                 </p>
                 <p class="mt-3">
-                    "Read our full " <a href="/security">"security policy"</a> " for details on how we "
-                    "protect validator operations."
+                    {locale.t("delegate.security.policy.before")} " "
+                    <a href="/security">{locale.t("delegate.security.policy.link")}</a>
+                    " " {locale.t("delegate.security.policy.after")}
                 </p>
             </Section>
 
             // Footer
             <footer class="mt-8 pt-4 border-t border-dashed border-[var(--rule)] text-center text-[var(--ink-light)] text-sm">
-                <a href="/">"\u{2190} back to home"</a>
+                <a href="/">"\u{2190} " {locale.t("nav.back_home")}</a>
             </footer>
         </main>
     }
 }
+
+/// FAQ questions and plain-text answers, for structured data (mirrors the FAQ section)
+fn faq_entries(locale: Locale) -> Vec<(String, String)> {
+    let no = locale.t("delegate.faq.no");
+    let risks = [
+        ("delegate.faq.risks.performance", "delegate.faq.risks.performance.body"),
+        ("delegate.faq.risks.slashing", "delegate.faq.risks.slashing.body"),
+        ("delegate.faq.risks.contract", "delegate.faq.risks.contract.body"),
+    ]
+    .map(|(title, body)| format!("{} {}", locale.t(title), locale.t(body)))
+    .join(" ");
+
+    vec![
+        (
+            locale.t("delegate.faq.custodial.q").to_string(),
+            format!("{} {}", no, locale.t("delegate.faq.custodial.a")),
+        ),
+        (
+            locale.t("delegate.faq.move.q").to_string(),
+            format!("{} {}", no, locale.t("delegate.faq.move.a")),
+        ),
+        (
+            locale.t("delegate.faq.timing.q").to_string(),
+            locale.t("delegate.faq.timing.a").to_string(),
+        ),
+        (locale.t("delegate.faq.risks.q").to_string(), risks),
+    ]
+}
+
+/// Numbered wallet steps, one `<li>` per translation key
+fn steps(keys: &'static [&'static str]) -> impl IntoView {
+    let locale = use_locale();
+    keys.iter()
+        .enumerate()
+        .map(|(i, key)| view! { <li>{format!("{}. {}", i + 1, locale.t(*key))}</li> })
+        .collect_view()
+}
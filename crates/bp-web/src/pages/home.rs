@@ -2,22 +2,28 @@ use crate::config::CONFIG;
 use leptos::prelude::*;
 use leptos_meta::{Link, Meta, Title};
 
-use crate::components::{AnimatedGradientDashBorder, ExternalLink, Metrics, Section};
+use crate::components::{
+    AnimatedGradientDashBorder, ExternalLink, LanguageSwitcher, Metrics, RevenueChart, Section, StructuredData, TipJar,
+};
+use crate::i18n::use_locale;
 
 #[component]
 pub fn HomePage() -> impl IntoView {
     let title = format!("{} \u{1F989}", CONFIG.name); // owl emoji
     let canonical = format!("{}/", CONFIG.base_url);
+    let locale = use_locale();
 
     view! {
-        <Title text="Block Parliament - Anza Core Dev Validator" />
-        <Meta name="description" content="Block Parliament - Solana validator operated by an Anza core developer. 5% commission, Jito MEV enabled." />
+        <Title text=locale.t("home.meta.title") />
+        <Meta name="description" content=locale.t("home.meta.description") />
         <Link rel="canonical" href=canonical />
-        <main class="max-w-[80ch] mx-auto px-4 py-4 md:py-8">
+        <StructuredData data=crate::seo::organization(locale) />
+        <main id="main" tabindex="-1" class="max-w-[80ch] mx-auto px-4 py-4 md:py-8">
             // Header with animated border
             <header class="mb-8 text-center">
                 <AnimatedGradientDashBorder title=title />
                 <div class="text-[var(--ink-light)] mt-2">{CONFIG.tagline}</div>
+                <LanguageSwitcher />
             </header>
 
             // Addresses - prominent at top
@@ -32,14 +38,14 @@ pub fn HomePage() -> impl IntoView {
                     <strong>"NETWORK"</strong> "   mainnet-beta"
                 </div>
                 <div>
-                    <strong>"ROUTING"</strong> "   DoubleZero enabled"
+                    <strong>"ROUTING"</strong> "   " {locale.t("home.routing.doublezero")}
                 </div>
             </div>
 
             // About
-            <Section id="about" title="About">
+            <Section id="about" title=locale.t("home.about.title")>
                 <p>
-                    "Operated by " <strong>"Gabe Rodriguez"</strong> " ("
+                    {locale.t("home.about.operated_by")} " " <strong>"Gabe Rodriguez"</strong> " ("
                     <a
                         href=format!("https://x.com/{}", CONFIG.contact.twitter)
                         target="_blank"
@@ -47,38 +53,51 @@ pub fn HomePage() -> impl IntoView {
                     >
                         "@" {CONFIG.contact.twitter}
                     </a>
-                    "), a core contributor to Solana's Agave validator client and on-chain programs at Anza. A way to experience Solana from the operator's seat, not just the codebase."
+                    ")" {locale.t("home.about.body")}
                 </p>
             </Section>
 
             // Pages
-            <Section id="pages" title="Pages">
+            <Section id="pages" title=locale.t("home.pages.title")>
                 <div class="space-y-1">
                     <div>
-                        <a href="/delegate">"delegate \u{2192}"</a>
+                        <a href="/delegate">{locale.t("home.pages.delegate")} " \u{2192}"</a>
                     </div>
                     <div>
-                        <a href="/security">"security policy \u{2192}"</a>
+                        <a href="/security">{locale.t("home.pages.security")} " \u{2192}"</a>
+                    </div>
+                    <div>
+                        <a href="/infrastructure">{locale.t("home.pages.infrastructure")} " \u{2192}"</a>
+                    </div>
+                    <div>
+                        <a href="/contact">{locale.t("home.pages.contact")} " \u{2192}"</a>
                     </div>
                 </div>
             </Section>
 
             // Metrics
-            <Section id="metrics" title="Metrics">
+            <Section id="metrics" title=locale.t("home.metrics.title")>
                 <Metrics />
+                <RevenueChart />
             </Section>
 
             // Delegate CTA
-            <Section id="delegate" title="Delegate">
+            <Section id="delegate" title=locale.t("home.delegate.title")>
                 <p>
-                    "Earn staking rewards while supporting independent infrastructure. "
-                    "Fully non-custodial\u{2014}your SOL never leaves your control. "
-                    <a href="/delegate">"How to delegate \u{2192}"</a>
+                    {locale.t("home.delegate.body")} " "
+                    <a href="/delegate">{locale.t("home.delegate.link")} " \u{2192}"</a>
                 </p>
             </Section>
 
+            // Tip jar (when a donation address is configured)
+            {CONFIG.donation_address.map(|address| view! {
+                <Section id="tip" title=locale.t("home.tip.title")>
+                    <TipJar address=address />
+                </Section>
+            })}
+
             // External Links
-            <Section id="links" title="External Links">
+            <Section id="links" title=locale.t("home.links.title")>
                 <div class="flex flex-wrap gap-2">
                     <ExternalLink href=CONFIG.links.validators_app.to_string() label="validators.app".to_string() />
                     <ExternalLink href=CONFIG.links.ibrl.to_string() label="ibrl".to_string() />
@@ -90,7 +109,7 @@ pub fn HomePage() -> impl IntoView {
             </Section>
 
             // Changelog - timeline style
-            <Section id="changelog" title="Changelog">
+            <Section id="changelog" title=locale.t("home.changelog.title")>
                 <div class="pl-3 border-l border-dashed border-[var(--ink-light)]">
                     {CONFIG.changelog.iter().map(|entry| view! {
                         <div class="mb-1">
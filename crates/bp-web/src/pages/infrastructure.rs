@@ -0,0 +1,182 @@
+use crate::config::CONFIG;
+use leptos::prelude::*;
+use leptos_meta::{Link, Meta, Title};
+
+use crate::api::{GossipPresence, IpLocation};
+use crate::components::metrics::{MetricsSource, fetch_metrics, stale_note};
+use crate::components::{LanguageSwitcher, Section};
+use crate::i18n::use_locale;
+
+/// Infrastructure transparency: what the validator advertises in gossip and where it's hosted.
+/// Reads the ingestion snapshot, like the home page metrics.
+#[component]
+pub fn InfrastructurePage() -> impl IntoView {
+    let locale = use_locale();
+    let canonical = format!("{}/infrastructure", CONFIG.base_url);
+    let metrics = Resource::new(|| (), |_| fetch_metrics());
+
+    view! {
+        <Title text=locale.t("infra.meta.title") />
+        <Meta name="description" content=locale.t("infra.meta.description") />
+        <Link rel="canonical" href=canonical />
+        <main id="main" tabindex="-1" class="max-w-[80ch] mx-auto px-4 py-4 md:py-8">
+            <header class="mb-8 text-center">
+                <h1 class="text-xl font-bold mb-2">
+                    <span aria-hidden="true">"\u{2500}\u{2524} "</span>
+                    {locale.t("infra.heading")}
+                    <span aria-hidden="true">" \u{251C}\u{2500}"</span>
+                </h1>
+                <div class="mt-2">
+                    <a href="/" class="text-sm">"\u{2190} " {locale.t("nav.back_home")}</a>
+                </div>
+                <LanguageSwitcher />
+            </header>
+
+            <p class="mb-6">{locale.t("infra.intro")}</p>
+
+            <Suspense fallback=move || view! {
+                <p role="status" class="text-[var(--ink-light)]">{locale.t("a11y.loading_metrics")}</p>
+            }>
+                {move || {
+                    metrics.get().map(|result| match result {
+                        Ok(Some(resp)) => {
+                            let gossip_stale = stale_note(&resp.data, MetricsSource::Gossip);
+                            let location_stale = stale_note(&resp.data, MetricsSource::IpLocation);
+                            view! {
+                            {resp.fetched_at.map(|ts| view! {
+                                <div class="text-xs text-[var(--ink-light)] text-right mb-2">
+                                    {locale.t("metrics.updated")} " " <time datetime=ts.clone()>{ts} " UTC"</time>
+                                </div>
+                            })}
+                            <GossipSection gossip=resp.data.gossip stale=gossip_stale />
+                            <LocationSection location=resp.data.ip_location stale=location_stale />
+                            }
+                            .into_any()
+                        }
+                        _ => view! { <p class="text-[var(--ink-light)]">{locale.t("infra.unavailable")}</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+
+            <footer class="mt-8 pt-4 border-t border-dashed border-[var(--rule)] text-center text-[var(--ink-light)] text-sm">
+                <a href="/">"\u{2190} " {locale.t("nav.back_home")}</a>
+            </footer>
+        </main>
+    }
+}
+
+/// One `LABEL   value` row
+fn row(label: &'static str, value: String) -> impl IntoView {
+    view! {
+        <div>
+            <strong>{format!("{:<14}", label)}</strong>
+            <code class="break-all">{value}</code>
+        </div>
+    }
+}
+
+#[component]
+fn GossipSection(gossip: Option<GossipPresence>, stale: Option<AnyView>) -> impl IntoView {
+    let locale = use_locale();
+
+    let body = match gossip {
+        None => view! { <p class="text-[var(--ink-light)]">{locale.t("infra.gossip.unknown")}</p> }.into_any(),
+        Some(GossipPresence {
+            cluster_nodes,
+            node: None,
+        }) => view! {
+            <p>
+                <span aria-hidden="true">"\u{2717} "</span>
+                {locale.tr("infra.gossip.missing", &[("nodes", &cluster_nodes.to_string())])}
+            </p>
+        }
+        .into_any(),
+        Some(GossipPresence {
+            cluster_nodes,
+            node: Some(node),
+        }) => {
+            let ports = node.ports();
+            let has_rpc = node.rpc.is_some();
+            let dash = || "-".to_string();
+            view! {
+                <p class="mb-3">
+                    <span aria-hidden="true">"\u{2713} "</span>
+                    {locale.tr("infra.gossip.visible", &[("nodes", &cluster_nodes.to_string())])}
+                </p>
+                <div class="space-y-1 mb-4 whitespace-pre">
+                    {row("IDENTITY", node.pubkey.clone())}
+                    {row("IP", node.ip().map(str::to_string).unwrap_or_else(dash))}
+                    {row("VERSION", node.version.clone().unwrap_or_else(dash))}
+                    {row("SHRED VERSION", node.shred_version.map(|v| v.to_string()).unwrap_or_else(dash))}
+                    {row("FEATURE SET", node.feature_set.map(|v| v.to_string()).unwrap_or_else(dash))}
+                </div>
+                <table class="w-full text-sm mb-3">
+                    <caption class="sr-only">{locale.t("infra.ports.caption")}</caption>
+                    <thead>
+                        <tr class="text-left border-b border-dashed border-[var(--rule)]">
+                            <th scope="col">{locale.t("infra.ports.service")}</th>
+                            <th scope="col">{locale.t("infra.ports.port")}</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {ports
+                            .into_iter()
+                            .map(|(service, port)| view! {
+                                <tr>
+                                    <td><code>{service}</code></td>
+                                    <td><code>{port}</code></td>
+                                </tr>
+                            })
+                            .collect_view()}
+                    </tbody>
+                </table>
+                <p class="text-sm text-[var(--ink-light)]">
+                    {locale.t(if has_rpc { "infra.rpc.public" } else { "infra.rpc.private" })}
+                </p>
+            }
+            .into_any()
+        }
+    };
+
+    view! {
+        <Section id="gossip" title=locale.t("infra.gossip.title")>
+            {body}
+            {stale}
+        </Section>
+    }
+}
+
+#[component]
+fn LocationSection(location: Option<IpLocation>, stale: Option<AnyView>) -> impl IntoView {
+    let locale = use_locale();
+    let dash = || "-".to_string();
+
+    let body = match location {
+        None => view! { <p class="text-[var(--ink-light)]">{locale.t("infra.location.unknown")}</p> }.into_any(),
+        Some(location) => {
+            let source = if location.source == "maxmind" {
+                "MaxMind GeoLite2"
+            } else {
+                "ipinfo.io"
+            };
+            view! {
+                <div class="space-y-1 mb-3 whitespace-pre">
+                    {row("LOCATION", location.place().unwrap_or_else(dash))}
+                    {row("ASN", location.asn.map(|asn| format!("AS{}", asn)).unwrap_or_else(dash))}
+                    {row("NETWORK", location.org.clone().unwrap_or_else(dash))}
+                </div>
+                <p class="text-sm text-[var(--ink-light)]">
+                    {locale.tr("infra.location.source", &[("source", source)])}
+                </p>
+            }
+            .into_any()
+        }
+    };
+
+    view! {
+        <Section id="location" title=locale.t("infra.location.title")>
+            {body}
+            {stale}
+        </Section>
+    }
+}
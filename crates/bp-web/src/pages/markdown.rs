@@ -0,0 +1,67 @@
+use crate::config::CONFIG;
+use leptos::prelude::*;
+use leptos_meta::{Link, Meta, Title};
+use leptos_router::hooks::use_params_map;
+
+use crate::components::LanguageSwitcher;
+use crate::content::ContentPage;
+use crate::i18n::{Locale, use_locale};
+
+/// Content page for the current request, read from the content directory
+fn load_page(slug: &str, locale: Locale) -> Option<ContentPage> {
+    #[cfg(feature = "ssr")]
+    {
+        let page = crate::content::load(slug, locale);
+        if page.is_none()
+            && let Some(response) = use_context::<leptos_axum::ResponseOptions>()
+        {
+            response.set_status(http::StatusCode::NOT_FOUND);
+        }
+        page
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = (slug, locale);
+        None
+    }
+}
+
+/// Markdown-driven page at `/<slug>` (see `crate::content`)
+#[component]
+pub fn MarkdownPage() -> impl IntoView {
+    let locale = use_locale();
+    let slug = use_params_map().with_untracked(|p| p.get("slug")).unwrap_or_default();
+
+    let Some(page) = load_page(&slug, locale) else {
+        return view! { <p>"404 - Page not found"</p> }.into_any();
+    };
+
+    let canonical = format!("{}/{}", CONFIG.base_url, page.slug);
+    let title = format!("{} - {}", page.title, CONFIG.name);
+
+    view! {
+        <Title text=title />
+        {page.description.map(|description| view! { <Meta name="description" content=description /> })}
+        <Link rel="canonical" href=canonical />
+        <main id="main" tabindex="-1" class="max-w-[80ch] mx-auto px-4 py-4 md:py-8">
+            <header class="mb-8 text-center">
+                <h1 class="text-xl font-bold mb-2">
+                    <span aria-hidden="true">"\u{2500}\u{2524} "</span>
+                    {page.title}
+                    <span aria-hidden="true">" \u{251C}\u{2500}"</span>
+                </h1>
+                <div class="mt-2">
+                    <a href="/" class="text-sm">"\u{2190} " {locale.t("nav.back_home")}</a>
+                </div>
+                <LanguageSwitcher />
+            </header>
+
+            <article class="content" inner_html=page.html></article>
+
+            <footer class="mt-8 pt-4 border-t border-dashed border-[var(--rule)] text-center text-[var(--ink-light)] text-sm">
+                <a href="/">"\u{2190} " {locale.t("nav.back_home")}</a>
+            </footer>
+        </main>
+    }
+    .into_any()
+}
@@ -1,7 +1,13 @@
+mod contact;
 mod delegate;
 mod home;
+mod infrastructure;
+mod markdown;
 mod security;
 
+pub use contact::ContactPage;
 pub use delegate::DelegatePage;
 pub use home::HomePage;
+pub use infrastructure::InfrastructurePage;
+pub use markdown::MarkdownPage;
 pub use security::SecurityPage;
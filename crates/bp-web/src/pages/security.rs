@@ -15,11 +15,13 @@ pub fn SecurityPage() -> impl IntoView {
         <Title text="Security Policy - Block Parliament Validator" />
         <Meta name="description" content="Block Parliament validator security policy. Key management, infrastructure hardening, access control, monitoring, and incident response procedures." />
         <Link rel="canonical" href=canonical />
-        <main class="max-w-[80ch] mx-auto px-4 py-4 md:py-8">
+        <main id="main" tabindex="-1" class="max-w-[80ch] mx-auto px-4 py-4 md:py-8">
             // Header - responsive, matches Section-style pattern
             <header class="mb-8 text-center">
                 <h1 class="text-xl font-bold mb-2">
-                    "\u{2500}\u{2524} " {CONFIG.name} " Security Policy \u{251C}\u{2500}"
+                    <span aria-hidden="true">"\u{2500}\u{2524} "</span>
+                    {CONFIG.name} " Security Policy"
+                    <span aria-hidden="true">" \u{251C}\u{2500}"</span>
                 </h1>
                 <div class="text-[var(--ink-light)]">
                     "Last updated: January 2026"
@@ -0,0 +1,61 @@
+//! Sliding-window rate limiting shared by the contact form and API keys.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Sliding-window counter of recent submissions per key
+pub struct RateLimiter {
+    window: Duration,
+    max: usize,
+    hits: HashMap<String, VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new(window: Duration, max: usize) -> Self {
+        Self {
+            window,
+            max,
+            hits: HashMap::new(),
+        }
+    }
+
+    /// Whether `key` may submit now (doesn't record anything)
+    pub fn allows(&mut self, key: &str, now: Instant) -> bool {
+        self.prune(now);
+        self.hits.get(key).is_none_or(|hits| hits.len() < self.max)
+    }
+
+    /// Record a submission by `key` at `now`
+    pub fn record(&mut self, key: &str, now: Instant) {
+        self.hits.entry(key.to_string()).or_default().push_back(now);
+    }
+
+    fn prune(&mut self, now: Instant) {
+        let window = self.window;
+        self.hits.retain(|_, hits| {
+            while hits.front().is_some_and(|t| now.duration_since(*t) >= window) {
+                hits.pop_front();
+            }
+            !hits.is_empty()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limiter_slides_its_window() {
+        let mut limiter = RateLimiter::new(Duration::from_secs(60), 2);
+        let start = Instant::now();
+        for _ in 0..2 {
+            assert!(limiter.allows("1.2.3.4", start));
+            limiter.record("1.2.3.4", start);
+        }
+        assert!(!limiter.allows("1.2.3.4", start + Duration::from_secs(30)));
+        assert!(limiter.allows("5.6.7.8", start + Duration::from_secs(30)));
+        assert!(limiter.allows("1.2.3.4", start + Duration::from_secs(60)));
+        assert!(limiter.hits.get("1.2.3.4").is_none(), "expired keys are dropped");
+    }
+}
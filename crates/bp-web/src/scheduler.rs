@@ -1,92 +1,312 @@
 //! Background scheduler that runs the data ingestion job periodically.
-//! Uses a simple tokio::time::interval — no external cron dependency needed.
+//! Uses a simple tokio sleep loop — no external cron dependency needed.
+//! Each successful job, and each cycle where every job succeeded, pings its
+//! heartbeat URL (see `heartbeat`).
+//!
+//! The schedule comes from config.toml's `[scheduler]` section, falling back to the
+//! env vars. It is re-read before every cycle, and `reload` (SIGHUP or the admin
+//! page) applies a changed interval to the wait already in progress.
+//!
+//! Once a month has closed, the first cycle after a successful financial refresh also
+//! archives that month's report package (ledgers, report.html and the tax package
+//! zip with its PDF cover sheet) into `$DATA_DIR/reports/YYYY-MM/` and posts a
+//! notification. A missed first-of-the-month (downtime) is caught up on the next cycle.
+
+/// The month before `today`'s, as `YYYY-MM`
+#[cfg(feature = "ssr")]
+fn prior_month(today: chrono::NaiveDate) -> String {
+    use chrono::Datelike;
+    let first = today.with_day(1).unwrap_or(today);
+    (first - chrono::Duration::days(1)).format("%Y-%m").to_string()
+}
 
 #[cfg(feature = "ssr")]
 mod ssr {
+    use crate::financials::config::ValidatorConfig;
+    use crate::heartbeat;
     use crate::ingestion;
+    use crate::notify;
     use std::time::Duration;
     use tokio::process::Command;
+    use tokio::sync::Notify;
+    use tokio::time::Instant;
 
     const DEFAULT_INTERVAL_HOURS: u64 = 6;
     const DEFAULT_REFRESH_FINANCIALS: bool = true;
+    const DEFAULT_MONTHLY_REPORTS: bool = true;
+    /// Exit code validator-accounting uses when the cache ingestion lock is held.
+    const INGESTION_LOCKED_EXIT_CODE: i32 = 75;
+
+    /// Wakes the scheduler loop to re-read its schedule
+    static RELOAD: Notify = Notify::const_new();
+
+    /// Effective schedule: config.toml `[scheduler]`, then env vars, then defaults
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Schedule {
+        interval_hours: u64,
+        refresh_financials: bool,
+        monthly_reports: bool,
+    }
+
+    impl Schedule {
+        fn load() -> Self {
+            let config_path = std::path::Path::new(&financials_data_dir()).join("config.toml");
+            let file = ValidatorConfig::load(&config_path)
+                .map(|c| c.scheduler)
+                .unwrap_or_default();
+            Self {
+                interval_hours: file
+                    .interval_hours
+                    .or_else(|| parse_interval_env().ok().flatten())
+                    .filter(|hours| *hours > 0)
+                    .unwrap_or(DEFAULT_INTERVAL_HOURS),
+                refresh_financials: file
+                    .refresh_financials
+                    .or_else(|| parse_bool_env("FINANCIALS_REFRESH_ENABLED"))
+                    .unwrap_or(DEFAULT_REFRESH_FINANCIALS),
+                monthly_reports: file
+                    .monthly_reports
+                    .or_else(|| parse_bool_env("MONTHLY_REPORTS_ENABLED"))
+                    .unwrap_or(DEFAULT_MONTHLY_REPORTS),
+            }
+        }
+
+        /// Pick up a changed schedule, logging it
+        fn refresh(&mut self) {
+            let reloaded = Self::load();
+            if reloaded != *self {
+                reloaded.log();
+                *self = reloaded;
+            }
+        }
+
+        fn interval(&self) -> Duration {
+            Duration::from_secs(self.interval_hours * 3600)
+        }
+
+        fn log(&self) {
+            println!(
+                "[scheduler] Running background ingestion every {} hours",
+                self.interval_hours
+            );
+            println!(
+                "[scheduler] Financial cache refresh is {}",
+                if self.refresh_financials { "enabled" } else { "disabled" }
+            );
+            println!(
+                "[scheduler] Monthly report packages are {}",
+                if self.refresh_financials && self.monthly_reports {
+                    "enabled"
+                } else {
+                    "disabled"
+                }
+            );
+        }
+    }
 
     /// Spawn the background ingestion scheduler.
     /// Runs immediately on startup, then every `interval_hours` hours.
     pub fn spawn_scheduler() {
-        let interval_hours = std::env::var("INGESTION_INTERVAL_HOURS")
-            .ok()
-            .and_then(|v| v.parse::<u64>().ok())
-            .unwrap_or(DEFAULT_INTERVAL_HOURS);
-        let refresh_financials = parse_bool_env("FINANCIALS_REFRESH_ENABLED").unwrap_or(DEFAULT_REFRESH_FINANCIALS);
-
-        println!(
-            "[scheduler] Starting background ingestion every {} hours",
-            interval_hours
-        );
-        println!(
-            "[scheduler] Financial cache refresh is {}",
-            if refresh_financials { "enabled" } else { "disabled" }
-        );
+        let mut schedule = Schedule::load();
+        schedule.log();
 
         tokio::spawn(async move {
-            // Run immediately on startup
-            run_once(refresh_financials).await;
-
-            // Then loop on the interval
-            let mut interval = tokio::time::interval(Duration::from_secs(interval_hours * 3600));
-            interval.tick().await; // skip the first (immediate) tick
             loop {
-                interval.tick().await;
-                run_once(refresh_financials).await;
+                let started = Instant::now();
+                run_once(schedule.refresh_financials, schedule.monthly_reports).await;
+
+                // Wait out the interval; a reload re-reads the schedule and resumes the
+                // wait with the new interval (still counted from the last cycle's start)
+                loop {
+                    let due = tokio::select! {
+                        _ = tokio::time::sleep_until(started + schedule.interval()) => true,
+                        _ = RELOAD.notified() => false,
+                    };
+                    schedule.refresh();
+                    if due {
+                        break;
+                    }
+                }
             }
         });
     }
 
-    async fn run_once(refresh_financials: bool) {
-        match ingestion::run_ingestion().await {
-            Ok(true) => println!("[scheduler] Ingestion completed successfully"),
-            Ok(false) => eprintln!("[scheduler] Ingestion skipped (no data available)"),
-            Err(e) => eprintln!("[scheduler] Ingestion failed: {}", e),
+    /// Re-read the schedule now rather than at the next cycle
+    pub fn reload() {
+        RELOAD.notify_one();
+    }
+
+    /// Scheduler env vars that are set but unusable (they would silently fall back)
+    pub fn env_problems() -> Vec<String> {
+        let mut problems = Vec::new();
+        if let Err(raw) = parse_interval_env() {
+            problems.push(format!(
+                "INGESTION_INTERVAL_HOURS '{}' is not a whole number of hours of at least 1",
+                raw
+            ));
+        }
+        for name in ["FINANCIALS_REFRESH_ENABLED", "MONTHLY_REPORTS_ENABLED"] {
+            if let Ok(raw) = std::env::var(name)
+                && parse_bool_env(name).is_none()
+            {
+                problems.push(format!("{} '{}' is not true/false", name, raw));
+            }
+        }
+        problems
+    }
+
+    /// `INGESTION_INTERVAL_HOURS`: None when unset, Err(raw value) when unusable
+    fn parse_interval_env() -> Result<Option<u64>, String> {
+        let Ok(raw) = std::env::var("INGESTION_INTERVAL_HOURS") else {
+            return Ok(None);
+        };
+        match raw.trim().parse::<u64>() {
+            Ok(hours) if hours > 0 => Ok(Some(hours)),
+            _ => Err(raw),
         }
+    }
+
+    async fn run_once(refresh_financials: bool, monthly_reports: bool) {
+        let mut healthy = match ingestion::run_ingestion().await {
+            Ok(true) => {
+                println!("[scheduler] Ingestion completed successfully");
+                heartbeat::ping(heartbeat::INGESTION).await;
+                true
+            }
+            Ok(false) => {
+                eprintln!("[scheduler] Ingestion skipped (no data available)");
+                false
+            }
+            Err(e) => {
+                eprintln!("[scheduler] Ingestion failed: {}", e);
+                false
+            }
+        };
 
         if refresh_financials {
-            if let Err(e) = refresh_financial_cache().await {
-                eprintln!("[scheduler] Financial refresh failed: {}", e);
-            } else {
-                println!("[scheduler] Financial cache refresh completed successfully");
+            match refresh_financial_cache().await {
+                Ok(true) => {
+                    println!("[scheduler] Financial cache refresh completed successfully");
+                    heartbeat::ping(heartbeat::FINANCIALS).await;
+                    // Only from a fresh cache, so the package has the month's final numbers
+                    if monthly_reports && let Err(e) = archive_monthly_package_if_due().await {
+                        eprintln!("[scheduler] Monthly report package failed: {}", e);
+                        healthy = false;
+                        notify_operator(&format!("Monthly report package failed: {}", e)).await;
+                    }
+                }
+                // Another run is refreshing the same cache, so the cycle still counts as healthy
+                Ok(false) => println!("[scheduler] Financial refresh skipped: another ingestion holds the cache lock"),
+                Err(e) => {
+                    eprintln!("[scheduler] Financial refresh failed: {}", e);
+                    healthy = false;
+                }
             }
+            // Alert on a low DoubleZero deposit once its fees are up to date
+            crate::financials::doublezero::check_and_alert(&financials_data_dir()).await;
+            // ...and on epoch revenue far outside its trailing baseline
+            crate::financials::anomalies::check_and_alert(&financials_data_dir()).await;
+        }
+
+        if healthy {
+            heartbeat::ping(heartbeat::SCHEDULER).await;
         }
     }
 
-    async fn refresh_financial_cache() -> Result<(), String> {
+    fn financials_data_dir() -> String {
         let data_dir = std::env::var("DATA_DIR").unwrap_or_else(|_| "/data".to_string());
-        let data_dir = data_dir.trim_end_matches('/').to_string();
-        let config_path = format!("{}/config.toml", data_dir);
-        let output_dir = format!("{}/output", data_dir);
+        data_dir.trim_end_matches('/').to_string()
+    }
+
+    /// Returns Ok(false) if validator-accounting skipped because another
+    /// ingestion (e.g. a manual CLI run) already holds the cache lock.
+    async fn refresh_financial_cache() -> Result<bool, String> {
+        let output_dir = format!("{}/output", financials_data_dir());
+        let output = run_accounting(&output_dir, &[]).await?;
+
+        if output.status.success() {
+            return Ok(true);
+        }
+        if output.status.code() == Some(INGESTION_LOCKED_EXIT_CODE) {
+            return Ok(false);
+        }
+        Err(failure(&output))
+    }
+
+    /// Run the financial refresh with `--dry-run` and return its change summary.
+    /// validator-accounting syncs into a scratch copy, so the cache and reports are untouched.
+    pub async fn preview_financial_refresh() -> Result<String, String> {
+        let output_dir = format!("{}/output", financials_data_dir());
+        let output = run_accounting(&output_dir, &["--dry-run"]).await?;
+        if !output.status.success() {
+            return Err(failure(&output));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Generate and archive the prior month's package unless it already exists.
+    /// Reports are built from the cache alone (`--offline`) just after a refresh.
+    async fn archive_monthly_package_if_due() -> Result<(), String> {
+        let month = super::prior_month(chrono::Utc::now().date_naive());
+        let dir = format!("{}/reports/{}", financials_data_dir(), month);
+        let package = format!("{}/package_{}.zip", dir, month);
+        if std::path::Path::new(&package).exists() {
+            return Ok(());
+        }
+
+        println!("[scheduler] Archiving the {} report package to {}", month, dir);
+        let period = format!("{}..{}", month, month);
+        for args in [
+            vec!["--offline", "--period", &period],
+            vec!["--offline", "tax-package", "--period", &period, "--path", &package],
+        ] {
+            let output = run_accounting(&dir, &args).await?;
+            if !output.status.success() {
+                return Err(failure(&output));
+            }
+        }
+
+        println!("[scheduler] Monthly report package written: {}", package);
+        notify_operator(&format!("Report package for {} is ready: {}", month, package)).await;
+        Ok(())
+    }
 
-        let output = Command::new("/app/validator-accounting")
+    /// Run validator-accounting against the volume's config and cache
+    async fn run_accounting(output_dir: &str, args: &[&str]) -> Result<std::process::Output, String> {
+        let data_dir = financials_data_dir();
+        Command::new("/app/validator-accounting")
             .arg("--config")
-            .arg(&config_path)
+            .arg(format!("{}/config.toml", data_dir))
             .arg("--data-dir")
             .arg(&data_dir)
             .arg("--output-dir")
-            .arg(&output_dir)
+            .arg(output_dir)
+            .arg("--run-trigger")
+            .arg("scheduler")
+            .args(args)
             .output()
             .await
-            .map_err(|e| format!("failed to spawn validator-accounting: {}", e))?;
-
-        if output.status.success() {
-            return Ok(());
-        }
+            .map_err(|e| format!("failed to spawn validator-accounting: {}", e))
+    }
 
+    fn failure(output: &std::process::Output) -> String {
         let stderr = String::from_utf8_lossy(&output.stderr);
         let stdout = String::from_utf8_lossy(&output.stdout);
-        Err(format!(
+        format!(
             "exit status {} (stderr: {}; stdout: {})",
             output.status,
             truncate_for_log(&stderr),
             truncate_for_log(&stdout)
-        ))
+        )
+    }
+
+    async fn notify_operator(message: &str) {
+        if !notify::is_configured() {
+            return;
+        }
+        if let Err(e) = notify::send(message).await {
+            eprintln!("[scheduler] Notification failed: {}", e);
+        }
     }
 
     fn parse_bool_env(name: &str) -> Option<bool> {
@@ -109,3 +329,15 @@ mod ssr {
 
 #[cfg(feature = "ssr")]
 pub use ssr::*;
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn prior_month_crosses_year_boundary() {
+        assert_eq!(prior_month(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()), "2025-12");
+        assert_eq!(prior_month(NaiveDate::from_ymd_opt(2026, 3, 31).unwrap()), "2026-02");
+    }
+}
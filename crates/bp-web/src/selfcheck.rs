@@ -0,0 +1,137 @@
+//! Startup self-check and config reload.
+//!
+//! Before serving, bp-web checks that the data directory is writable, that config.toml
+//! (when present) parses and holds no values that would be silently replaced, that the
+//! scheduler env vars are usable, and that cache.sqlite (when present) has the tables the
+//! financial pages read. Any problem stops startup with the full list, rather than turning
+//! up later as a fallback page or a scheduler quietly running on defaults. The bp.sqlite
+//! schema is checked by `db::init_db`, which refuses to start on a failed migration.
+//! `--check` runs the same checks and exits.
+//!
+//! `reload` applies an edited config.toml without a restart (SIGHUP, or the admin page's
+//! reload button): once the file checks out, the cached report snapshot and revenue
+//! composition are dropped so thresholds, labels and rules apply to the next request, the
+//! scheduler re-reads its `[scheduler]` section, and API key rate limiters of keys revoked
+//! since are dropped.
+
+use std::path::Path;
+
+use crate::financials::{self, config::ValidatorConfig};
+use crate::scheduler;
+
+/// Everything wrong with `data_dir` and its contents, empty when bp-web can start
+pub async fn run(data_dir: &str) -> Vec<String> {
+    let mut problems = Vec::new();
+    problems.extend(check_data_dir(data_dir));
+    problems.extend(check_config(data_dir));
+    problems.extend(scheduler::env_problems());
+    problems.extend(check_cache(data_dir).await);
+    problems
+}
+
+/// The data directory exists (or can be created) and accepts writes
+fn check_data_dir(data_dir: &str) -> Option<String> {
+    let dir = Path::new(data_dir);
+    if let Err(e) = std::fs::create_dir_all(dir) {
+        return Some(format!("DATA_DIR {} cannot be created: {}", data_dir, e));
+    }
+    let probe = dir.join(".bp-web-write-check");
+    match std::fs::write(&probe, b"ok") {
+        Ok(()) => {
+            let _ = std::fs::remove_file(&probe);
+            None
+        }
+        Err(e) => Some(format!("DATA_DIR {} is not writable: {}", data_dir, e)),
+    }
+}
+
+/// config.toml loads and none of its values would be replaced by a default
+fn check_config(data_dir: &str) -> Vec<String> {
+    let path = Path::new(data_dir).join("config.toml");
+    if !path.exists() {
+        return Vec::new();
+    }
+    match ValidatorConfig::load(&path) {
+        Ok(config) => config
+            .problems()
+            .into_iter()
+            .map(|p| format!("config.toml: {}", p))
+            .collect(),
+        Err(e) => vec![format!("config.toml: {:#}", e)],
+    }
+}
+
+/// cache.sqlite opens and has the tables every report build reads. A missing file is
+/// fine: the first financial refresh creates it.
+async fn check_cache(data_dir: &str) -> Vec<String> {
+    if !financials::db::cache_path(data_dir).exists() {
+        return Vec::new();
+    }
+    let missing = match financials::db::init_cache(data_dir).await {
+        Ok(pool) => financials::db::missing_tables(pool).await,
+        Err(e) => Err(e),
+    };
+    match missing {
+        Ok(tables) if tables.is_empty() => Vec::new(),
+        Ok(tables) => vec![format!(
+            "cache.sqlite is missing tables {} (run validator-accounting to migrate it)",
+            tables.join(", ")
+        )],
+        Err(e) => vec![format!("cache.sqlite: {:#}", e)],
+    }
+}
+
+/// Apply an edited config.toml to the running server; `Err` lists what is wrong with it
+pub fn reload() -> Result<(), Vec<String>> {
+    let problems = check_config(&crate::db::data_dir());
+    if !problems.is_empty() {
+        eprintln!("[reload] config.toml has problems: {}", problems.join("; "));
+        return Err(problems);
+    }
+    financials::invalidate_timelines();
+    financials::invalidate_revenue();
+    scheduler::reload();
+    tokio::spawn(crate::api_keys::prune_limiters());
+    println!("[reload] config.toml reloaded");
+    Ok(())
+}
+
+/// Reload config.toml on every SIGHUP (`kill -HUP <pid>`)
+#[cfg(unix)]
+pub fn spawn_sighup_reload() {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    tokio::spawn(async {
+        let mut hangups = match signal(SignalKind::hangup()) {
+            Ok(hangups) => hangups,
+            Err(e) => {
+                eprintln!("[reload] SIGHUP handler unavailable: {}", e);
+                return;
+            }
+        };
+        while hangups.recv().await.is_some() {
+            println!("[reload] SIGHUP received");
+            let _ = reload();
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_unreadable_config() {
+        let dir = std::env::temp_dir().join(format!("bp-web-selfcheck-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let data_dir = dir.to_str().unwrap();
+        assert!(check_data_dir(data_dir).is_none());
+        assert!(check_config(data_dir).is_empty());
+
+        std::fs::write(dir.join("config.toml"), "bootstrap_date = [").unwrap();
+        let problems = check_config(data_dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].starts_with("config.toml: "));
+    }
+}
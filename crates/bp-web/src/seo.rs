@@ -0,0 +1,132 @@
+//! Machine-readable metadata for search engines: `sitemap.xml` built from the
+//! Leptos route list, and JSON-LD structured data embedded in pages.
+
+use serde_json::{Value, json};
+
+use crate::config::CONFIG;
+use crate::i18n::Locale;
+
+/// How often a page changes and its relative priority, for the sitemap
+fn sitemap_hints(path: &str) -> (&'static str, &'static str) {
+    match path {
+        "/" => ("weekly", "1.0"),
+        "/delegate" => ("monthly", "0.8"),
+        _ => ("monthly", "0.6"),
+    }
+}
+
+/// Render `sitemap.xml` for the app's route paths.
+///
+/// Routes with parameters or wildcards are skipped (there's no way to enumerate
+/// them here). Each page lists its translations as `hreflang` alternates.
+pub fn sitemap_xml<'a>(base_url: &str, paths: impl IntoIterator<Item = &'a str>) -> String {
+    let mut paths: Vec<&str> = paths
+        .into_iter()
+        .filter(|p| p.starts_with('/') && !p.contains([':', '*']))
+        .collect();
+    // Home first, then alphabetical
+    paths.sort_by_key(|p| (*p != "/", *p));
+    paths.dedup();
+
+    let mut xml = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\" ",
+        "xmlns:xhtml=\"http://www.w3.org/1999/xhtml\">\n",
+    ));
+    for path in paths {
+        let loc = format!("{}{}", base_url.trim_end_matches('/'), path);
+        let (changefreq, priority) = sitemap_hints(path);
+        xml.push_str("  <url>\n");
+        xml.push_str(&format!("    <loc>{}</loc>\n", loc));
+        for locale in Locale::ALL {
+            xml.push_str(&format!(
+                "    <xhtml:link rel=\"alternate\" hreflang=\"{}\" href=\"{}?lang={}\"/>\n",
+                locale.code(),
+                loc,
+                locale.code()
+            ));
+        }
+        xml.push_str(&format!("    <changefreq>{}</changefreq>\n", changefreq));
+        xml.push_str(&format!("    <priority>{}</priority>\n", priority));
+        xml.push_str("  </url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+/// Schema.org `Organization` for the validator
+pub fn organization(locale: Locale) -> Value {
+    json!({
+        "@context": "https://schema.org",
+        "@type": "Organization",
+        "name": CONFIG.name,
+        "url": format!("{}/", CONFIG.base_url),
+        "logo": format!("{}/logo/owl-512.png", CONFIG.base_url),
+        "description": locale.t("home.meta.description"),
+        "sameAs": [
+            format!("https://x.com/{}", CONFIG.contact.twitter),
+            CONFIG.links.stakewiz,
+            CONFIG.links.validators_app,
+            CONFIG.links.solscan,
+        ],
+    })
+}
+
+/// Schema.org `FAQPage` from question/answer pairs
+pub fn faq_page(entries: &[(String, String)]) -> Value {
+    let questions: Vec<Value> = entries
+        .iter()
+        .map(|(question, answer)| {
+            json!({
+                "@type": "Question",
+                "name": question,
+                "acceptedAnswer": { "@type": "Answer", "text": answer },
+            })
+        })
+        .collect();
+    json!({
+        "@context": "https://schema.org",
+        "@type": "FAQPage",
+        "mainEntity": questions,
+    })
+}
+
+/// Serialize JSON-LD for embedding in a `<script>` element.
+/// `<` is escaped so text can never close the script early.
+pub fn script_json(value: &Value) -> String {
+    value.to_string().replace('<', "\\u003c")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sitemap_lists_static_routes_home_first() {
+        let xml = sitemap_xml(
+            "https://example.com/",
+            ["/security", "/delegate", "/", "/validators/:id", "/delegate"],
+        );
+        let locs: Vec<&str> = xml
+            .lines()
+            .filter_map(|l| l.trim().strip_prefix("<loc>")?.strip_suffix("</loc>"))
+            .collect();
+        assert_eq!(
+            locs,
+            [
+                "https://example.com/",
+                "https://example.com/delegate",
+                "https://example.com/security"
+            ]
+        );
+        assert!(xml.contains("hreflang=\"es\" href=\"https://example.com/delegate?lang=es\""));
+    }
+
+    #[test]
+    fn script_json_cannot_close_the_script() {
+        let value = faq_page(&[("Q".to_string(), "</script><b>".to_string())]);
+        let out = script_json(&value);
+        assert!(!out.contains("</script>"));
+        assert_eq!(serde_json::from_str::<Value>(&out).unwrap(), value);
+    }
+}
@@ -0,0 +1,95 @@
+//! Solana Pay transfer requests (`solana:` URLs) for the tip jar.
+//! See <https://docs.solanapay.com/spec#transfer-request>.
+
+/// Build a Solana Pay transfer request URL. With no amount the wallet asks the payer
+/// how much to send.
+pub fn transfer_url(recipient: &str, amount_sol: Option<f64>, label: &str, message: &str) -> String {
+    let mut params = Vec::new();
+    if let Some(amount) = amount_sol.filter(|a| a.is_finite() && *a > 0.0) {
+        // Decimal notation only (no exponent), trailing zeros trimmed
+        let amount = format!("{:.9}", amount);
+        params.push(format!("amount={}", amount.trim_end_matches('0').trim_end_matches('.')));
+    }
+    if !label.is_empty() {
+        params.push(format!("label={}", encode_component(label)));
+    }
+    if !message.is_empty() {
+        params.push(format!("message={}", encode_component(message)));
+    }
+
+    if params.is_empty() {
+        format!("solana:{}", recipient)
+    } else {
+        format!("solana:{}?{}", recipient, params.join("&"))
+    }
+}
+
+/// Percent-encode everything but RFC 3986 unreserved characters
+pub(crate) fn encode_component(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    out
+}
+
+/// QR code for `data` as an inline `<svg>` element (black on white, so any wallet can scan it)
+#[cfg(feature = "ssr")]
+pub fn qr_svg(data: &str) -> Option<String> {
+    use qrcode::QrCode;
+    use qrcode::render::svg;
+
+    let svg = QrCode::new(data.as_bytes())
+        .ok()?
+        .render::<svg::Color>()
+        .min_dimensions(200, 200)
+        .dark_color(svg::Color("#000000"))
+        .light_color(svg::Color("#ffffff"))
+        .build();
+    // Drop the XML declaration; the SVG is embedded in HTML
+    let start = svg.find("<svg")?;
+    Some(svg[start..].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "mD1afZhSisoXfJLT8nYwSFANqjr1KPoDUEpYTEfFX1e";
+
+    #[test]
+    fn builds_transfer_requests() {
+        assert_eq!(
+            transfer_url(ADDRESS, None, "Block Parliament", "Thanks for the tip!"),
+            format!(
+                "solana:{}?label=Block%20Parliament&message=Thanks%20for%20the%20tip%21",
+                ADDRESS
+            )
+        );
+        assert_eq!(
+            transfer_url(ADDRESS, Some(0.5), "", ""),
+            format!("solana:{}?amount=0.5", ADDRESS)
+        );
+        assert_eq!(
+            transfer_url(ADDRESS, Some(1.0), "", ""),
+            format!("solana:{}?amount=1", ADDRESS)
+        );
+        assert_eq!(transfer_url(ADDRESS, Some(0.0), "", ""), format!("solana:{}", ADDRESS));
+    }
+
+    #[test]
+    fn encodes_non_ascii_labels() {
+        assert_eq!(encode_component("Año & más"), "A%C3%B1o%20%26%20m%C3%A1s");
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn qr_code_is_inline_svg() {
+        let svg = qr_svg(&transfer_url(ADDRESS, None, "Block Parliament", "")).unwrap();
+        assert!(svg.starts_with("<svg"), "{svg}");
+    }
+}
@@ -0,0 +1,51 @@
+//! Mobile wallet deep links for the "Stake with us" flow.
+//!
+//! Phantom and Solflare can't pre-fill a stake from a URL, but both can open a page in
+//! their in-app browser. Linking to `/delegate#stake` there puts the delegator on the
+//! in-browser stake form with the wallet already injected and our vote account filled in.
+//! See <https://docs.phantom.com/phantom-deeplinks/other-methods/browse> and
+//! <https://docs.solflare.com/solflare/technical/deeplinks/other-methods/browse>.
+
+use crate::solana_pay::encode_component;
+
+/// Page the wallet browsers open: the stake section of the delegate page
+pub fn stake_page_url(base_url: &str) -> String {
+    format!("{}/delegate#stake", base_url.trim_end_matches('/'))
+}
+
+/// Open `url` in Phantom's in-app browser
+pub fn phantom_browse(url: &str, referrer: &str) -> String {
+    format!(
+        "https://phantom.app/ul/browse/{}?ref={}",
+        encode_component(url),
+        encode_component(referrer)
+    )
+}
+
+/// Open `url` in Solflare's in-app browser
+pub fn solflare_browse(url: &str, referrer: &str) -> String {
+    format!(
+        "https://solflare.com/ul/v1/browse/{}?ref={}",
+        encode_component(url),
+        encode_component(referrer)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn browse_links_encode_the_stake_page() {
+        let page = stake_page_url("https://blockparliament.com/");
+        assert_eq!(page, "https://blockparliament.com/delegate#stake");
+        assert_eq!(
+            phantom_browse(&page, "https://blockparliament.com"),
+            "https://phantom.app/ul/browse/https%3A%2F%2Fblockparliament.com%2Fdelegate%23stake\
+             ?ref=https%3A%2F%2Fblockparliament.com"
+        );
+        assert!(solflare_browse(&page, "https://blockparliament.com").starts_with(
+            "https://solflare.com/ul/v1/browse/https%3A%2F%2Fblockparliament.com%2Fdelegate%23stake?ref="
+        ));
+    }
+}
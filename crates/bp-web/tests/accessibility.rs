@@ -0,0 +1,54 @@
+//! Accessibility pass over the shared page components and the stylesheet:
+//! landmarks are labelled, decoration is hidden from screen readers, keyboard focus
+//! is visible, and looping animations stop under `prefers-reduced-motion`.
+
+const STYLESHEET: &str = include_str!("../style/tailwind.css");
+
+#[cfg(feature = "ssr")]
+#[test]
+fn section_is_labelled_by_its_heading() {
+    use bp_web::components::Section;
+    use leptos::prelude::*;
+
+    let html = Owner::new().with(|| view! { <Section id="about" title="About">"body"</Section> }.to_html());
+
+    assert!(html.contains("aria-labelledby=\"about-heading\""), "{html}");
+    assert!(html.contains("id=\"about-heading\""), "{html}");
+    assert!(html.contains("aria-label=\"Link to section: About\""), "{html}");
+
+    // Box-drawing characters sit in aria-hidden spans
+    let decoration = html.find("\u{2500}\u{2524}").expect("section decoration");
+    assert!(html[..decoration].ends_with("aria-hidden=\"true\">"), "{html}");
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn banner_title_is_a_heading_and_tickers_are_hidden() {
+    use bp_web::components::AnimatedGradientDashBorder;
+    use leptos::prelude::*;
+
+    let html = Owner::new().with(|| view! { <AnimatedGradientDashBorder title="Block Parliament" /> }.to_html());
+
+    assert!(html.contains("<h1"), "{html}");
+    assert_eq!(
+        html.matches("class=\"ticker\" aria-hidden=\"true\"").count(),
+        2,
+        "{html}"
+    );
+}
+
+#[test]
+fn stylesheet_shows_focus_and_honours_reduced_motion() {
+    assert!(STYLESHEET.contains(":focus-visible {"));
+    assert!(STYLESHEET.contains(".section-anchor:focus-visible"));
+    assert!(STYLESHEET.contains(".skip-link:focus"));
+
+    let reduced = STYLESHEET
+        .split("@media (prefers-reduced-motion: reduce)")
+        .nth(1)
+        .expect("reduced-motion block");
+    for class in [".ticker__track", ".skeleton-line"] {
+        assert!(reduced.contains(class), "{class} keeps animating under reduced motion");
+    }
+    assert!(reduced.contains("animation: none"));
+}
@@ -15,7 +15,12 @@ async fn generate_report_produces_valid_html() {
         return;
     }
 
-    let html = bp_web::financials::generate_report(&data_dir).await;
+    let page = bp_web::financials::generate_report(&data_dir).await;
+    let html = String::from_utf8(
+        page.body(bp_web::financials::precompressed::ContentEncoding::Identity)
+            .to_vec(),
+    )
+    .expect("Report should be UTF-8");
 
     // Basic assertions
     assert!(
@@ -30,6 +35,10 @@ async fn generate_report_produces_valid_html() {
         !html.contains("__TAX_TIMELINE_JSON__"),
         "Tax timeline JSON placeholder should be replaced"
     );
+    assert!(
+        !html.contains("__TIMELINE_NEXT_BEFORE_JSON__"),
+        "Timeline paging placeholder should be replaced"
+    );
     assert!(
         !html.contains("__TAX_YEAR__"),
         "Tax year placeholder should be replaced"
@@ -53,7 +62,7 @@ async fn generate_report_produces_valid_html() {
     println!("✓ Generated {} bytes of HTML", html.len());
 
     // Extract the timeline JSON and verify it has events.
-    // Template injects: `const TIMELINE = __TIMELINE_JSON__;`
+    // Template injects: `const TIMELINE = {{ timeline|tojson }};`
     let marker = "const TIMELINE = ";
     if let Some(start) = html.find(marker) {
         let rest = &html[start + marker.len()..];
@@ -0,0 +1,172 @@
+//! Anomaly detection on per-epoch revenue (`[anomalies]`).
+//!
+//! Each epoch's commission, leader fees and MEV are compared with the same source's
+//! trailing baseline (the preceding `baseline_epochs` epochs). A value more than
+//! `sigma` standard deviations from the baseline mean is flagged: a drop usually
+//! means a missed MEV claim, a gap in the data or a performance problem; a spike
+//! usually means a double-counted row. validator-accounting prints the flags in its
+//! summary; bp-web shows them on the admin page and posts new ones to the webhook.
+
+use serde::{Deserialize, Serialize};
+
+use crate::revenue_stats::{EpochRevenue, mean_stddev};
+
+/// Fewest baseline epochs a value is judged against
+pub const MIN_BASELINE_EPOCHS: usize = 5;
+
+/// `[anomalies]` section of config.toml
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AnomalyConfig {
+    /// Standard deviations from the baseline mean that count as anomalous (default: 3)
+    #[serde(default = "default_sigma")]
+    pub sigma: f64,
+    /// Preceding epochs the baseline is computed over (default: 20)
+    #[serde(default = "default_baseline_epochs")]
+    pub baseline_epochs: usize,
+}
+
+fn default_sigma() -> f64 {
+    3.0
+}
+
+fn default_baseline_epochs() -> usize {
+    20
+}
+
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        Self {
+            sigma: default_sigma(),
+            baseline_epochs: default_baseline_epochs(),
+        }
+    }
+}
+
+/// Revenue source checked for anomalies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RevenueComponent {
+    Commission,
+    LeaderFees,
+    Mev,
+}
+
+impl RevenueComponent {
+    pub const ALL: [Self; 3] = [Self::Commission, Self::LeaderFees, Self::Mev];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Commission => "Commission",
+            Self::LeaderFees => "Leader fees",
+            Self::Mev => "MEV",
+        }
+    }
+
+    fn value(self, epoch: &EpochRevenue) -> f64 {
+        match self {
+            Self::Commission => epoch.commission_sol,
+            Self::LeaderFees => epoch.leader_fees_sol,
+            Self::Mev => epoch.mev_sol,
+        }
+    }
+}
+
+/// One source of one epoch outside its baseline
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RevenueAnomaly {
+    pub epoch: u64,
+    pub date: Option<String>,
+    pub component: RevenueComponent,
+    pub value_sol: f64,
+    pub baseline_mean_sol: f64,
+    pub baseline_stddev_sol: f64,
+    /// Signed distance from the baseline mean, in standard deviations
+    pub deviation_sigma: f64,
+}
+
+impl RevenueAnomaly {
+    /// One line for the console, the admin page and the webhook
+    pub fn summary(&self) -> String {
+        format!(
+            "Epoch {}{}: {} {:.4} SOL is {:.1}σ {} the trailing mean of {:.4} ± {:.4} SOL",
+            self.epoch,
+            self.date.as_deref().map(|d| format!(" ({})", d)).unwrap_or_default(),
+            self.component.label(),
+            self.value_sol,
+            self.deviation_sigma.abs(),
+            if self.deviation_sigma < 0.0 { "below" } else { "above" },
+            self.baseline_mean_sol,
+            self.baseline_stddev_sol,
+        )
+    }
+}
+
+/// Flags over `epochs` (oldest first), oldest epoch first. Values are only judged once
+/// `MIN_BASELINE_EPOCHS` epochs precede them, and a constant baseline (zero spread) is
+/// never flagged.
+pub fn detect_anomalies(epochs: &[EpochRevenue], config: &AnomalyConfig) -> Vec<RevenueAnomaly> {
+    let window = config.baseline_epochs.max(MIN_BASELINE_EPOCHS);
+    let mut anomalies = Vec::new();
+    for (i, epoch) in epochs.iter().enumerate().skip(MIN_BASELINE_EPOCHS) {
+        let baseline = &epochs[i.saturating_sub(window)..i];
+        for component in RevenueComponent::ALL {
+            let values: Vec<f64> = baseline.iter().map(|e| component.value(e)).collect();
+            let (mean, stddev) = mean_stddev(&values);
+            if stddev <= f64::EPSILON {
+                continue;
+            }
+            let value = component.value(epoch);
+            let deviation_sigma = (value - mean) / stddev;
+            if deviation_sigma.abs() > config.sigma {
+                anomalies.push(RevenueAnomaly {
+                    epoch: epoch.epoch,
+                    date: epoch.date.clone(),
+                    component,
+                    value_sol: value,
+                    baseline_mean_sol: mean,
+                    baseline_stddev_sol: stddev,
+                    deviation_sigma,
+                });
+            }
+        }
+    }
+    anomalies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn epoch(epoch: u64, commission_sol: f64, mev_sol: f64) -> EpochRevenue {
+        EpochRevenue {
+            epoch,
+            commission_sol,
+            leader_fees_sol: 0.1,
+            mev_sol,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn flags_drops_and_spikes_against_trailing_baseline() {
+        let mut epochs: Vec<EpochRevenue> = (0..10)
+            .map(|i| epoch(i, 1.0 + (i % 2) as f64 * 0.1, 0.5 + (i % 3) as f64 * 0.05))
+            .collect();
+        epochs.push(epoch(10, 1.05, 0.0)); // missed MEV claim
+        epochs.push(epoch(11, 5.0, 0.55)); // commission counted twice over
+
+        let config = AnomalyConfig::default();
+        let anomalies = detect_anomalies(&epochs, &config);
+        let flagged: Vec<(u64, RevenueComponent)> = anomalies.iter().map(|a| (a.epoch, a.component)).collect();
+        // Leader fees are constant, so never flagged
+        assert_eq!(
+            flagged,
+            vec![(10, RevenueComponent::Mev), (11, RevenueComponent::Commission)]
+        );
+        assert!(anomalies[0].deviation_sigma < -3.0);
+        assert!(anomalies[0].summary().contains("below"));
+
+        // Too little history to judge anything
+        assert!(detect_anomalies(&epochs[..MIN_BASELINE_EPOCHS], &config).is_empty());
+    }
+}
@@ -0,0 +1,168 @@
+//! Financial report HTML rendering, shared by validator-accounting (`report.html`)
+//! and bp-web (`/financials`).
+//!
+//! Each crate keeps its own template (the web page pages in older months; the file
+//! report embeds everything), but both are MiniJinja templates rendered here from a
+//! typed [`ReportContext`] plus the operator's [`ReportTheme`]. Templates are
+//! HTML-autoescaped, and data for inline `<script>` blocks goes through `tojson`,
+//! which escapes `<`, `>`, `&` and `'` so no value can close the script early.
+
+mod anomaly;
+mod revenue_stats;
+mod theme;
+mod unit_economics;
+
+pub use anomaly::{AnomalyConfig, MIN_BASELINE_EPOCHS, RevenueAnomaly, RevenueComponent, detect_anomalies};
+pub use revenue_stats::{EpochRevenue, MEV_TOP_EPOCHS, MevConcentration, RevenueStats, WeekdayEffect, mean_stddev};
+pub use theme::{DEFAULT_REPORT_TITLE, ReportTheme, ThemeConfig};
+pub use unit_economics::{UnitEconomics, UnitTotals};
+
+use anyhow::{Context, Result};
+use minijinja::{AutoEscape, Environment, UndefinedBehavior};
+use serde::Serialize;
+
+/// Name the template is registered under (the `.html` suffix is informational;
+/// autoescaping is forced on regardless)
+const TEMPLATE_NAME: &str = "report.html";
+
+/// Month cursors for the next older chunk of each timeline (`None` once fully loaded)
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct HistoryCursors<'a> {
+    pub operating: Option<&'a str>,
+    pub tax: Option<&'a str>,
+}
+
+/// Everything a report template can reference besides the theme
+#[derive(Debug, Serialize)]
+pub struct ReportContext<'a, E: Serialize> {
+    /// Operating timeline (`{{ timeline|tojson }}`)
+    pub timeline: &'a [E],
+    /// Tax-view timeline (`{{ tax_timeline|tojson }}`)
+    pub tax_timeline: &'a [E],
+    /// Where paging resumes when only the latest months are embedded
+    pub history: Option<HistoryCursors<'a>>,
+    /// Label of the reporting period, e.g. "2025"; `None` for all time
+    pub tax_year: Option<&'a str>,
+    /// IANA timezone the report's dates are in
+    pub timezone: Option<&'a str>,
+    /// KPI row on the dashboard (`None` hides it)
+    pub unit_economics: Option<&'a UnitEconomics>,
+    /// Revenue volatility and seasonality panel (`None` hides it)
+    pub revenue_stats: Option<&'a RevenueStats>,
+    /// Label/value rows describing how the report was generated (`None` hides the footer)
+    pub metadata: Option<&'a [(String, String)]>,
+}
+
+/// Template variables: the report data plus the theme's title and stylesheet
+#[derive(Serialize)]
+struct Page<'a, E: Serialize> {
+    title: &'a str,
+    /// Validated CSS (see `ThemeConfig::load`), emitted unescaped inside `<style>`
+    theme_style: &'a str,
+    #[serde(flatten)]
+    report: &'a ReportContext<'a, E>,
+}
+
+/// Render `default_template` (or the theme's replacement) with `context`
+pub fn render<E: Serialize>(
+    default_template: &str,
+    theme: &ReportTheme,
+    context: &ReportContext<'_, E>,
+) -> Result<String> {
+    let mut env = Environment::new();
+    env.set_auto_escape_callback(|_| AutoEscape::Html);
+    // A typo in an override template should fail loudly rather than render blank
+    env.set_undefined_behavior(UndefinedBehavior::Strict);
+    env.add_template(TEMPLATE_NAME, theme.template(default_template))
+        .context("Failed to parse report template")?;
+
+    let page = Page {
+        title: theme.title(),
+        theme_style: theme.style(),
+        report: context,
+    };
+    env.get_template(TEMPLATE_NAME)?
+        .render(&page)
+        .context("Failed to render report template")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = concat!(
+        "<title>{{ title }}</title>",
+        "{% if theme_style %}<style>{{ theme_style|safe }}</style>{% endif %}",
+        "<span title=\"Dates are in {{ timezone }}\"></span>",
+        "<script>const T = {{ timeline|tojson }}; const Y = {{ tax_year|tojson }};</script>"
+    );
+
+    #[derive(Serialize)]
+    struct Event {
+        label: String,
+    }
+
+    fn context(events: &[Event]) -> ReportContext<'_, Event> {
+        ReportContext {
+            timeline: events,
+            tax_timeline: &[],
+            history: None,
+            tax_year: None,
+            timezone: Some("America/New_York"),
+            unit_economics: None,
+            revenue_stats: None,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn script_data_cannot_close_the_script_block() {
+        let events = [Event {
+            label: "</script><script>alert('x')</script>".to_string(),
+        }];
+        let html = render(TEMPLATE, &ReportTheme::default(), &context(&events)).unwrap();
+        assert!(!html.contains("</script><script>"));
+        assert!(html.contains(r"\u003c/script\u003e\u003cscript\u003ealert(\u0027x\u0027)"));
+        assert!(html.contains("const Y = null;"));
+
+        // Still the same data once parsed back as JSON
+        let start = html.find("const T = ").unwrap() + "const T = ".len();
+        let end = html[start..].find("; const Y").unwrap() + start;
+        let parsed: serde_json::Value = serde_json::from_str(&html[start..end]).unwrap();
+        assert_eq!(parsed[0]["label"], "</script><script>alert('x')</script>");
+    }
+
+    #[test]
+    fn text_is_html_escaped() {
+        let theme = ThemeConfig {
+            title: Some("Acme <Stake> & \"Co\"".to_string()),
+            ..Default::default()
+        }
+        .load()
+        .unwrap();
+        let mut ctx = context(&[]);
+        ctx.timezone = Some("\"><b>");
+        let html = render(TEMPLATE, &theme, &ctx).unwrap();
+        assert!(html.starts_with("<title>Acme &lt;Stake&gt; &amp; &quot;Co&quot;</title>"));
+        assert!(html.contains("Dates are in &quot;&gt;&lt;b&gt;"));
+        assert!(!html.contains("<style>"));
+    }
+
+    #[test]
+    fn marker_like_text_in_data_is_left_alone() {
+        let events = [Event {
+            label: "{{ title }} __TAX_YEAR__".to_string(),
+        }];
+        let html = render(TEMPLATE, &ReportTheme::default(), &context(&events)).unwrap();
+        assert!(html.contains(r#""label":"{{ title }} __TAX_YEAR__""#));
+    }
+
+    #[test]
+    fn undefined_variables_in_a_template_are_errors() {
+        let theme = ReportTheme {
+            template: Some("{{ timline|tojson }}".to_string()),
+            ..Default::default()
+        };
+        assert!(render(TEMPLATE, &theme, &context(&[])).is_err());
+    }
+}
@@ -0,0 +1,213 @@
+//! Revenue seasonality and volatility for the report dashboard.
+//!
+//! Both crates reduce their data to per-epoch revenue ([`EpochRevenue`]) and derive
+//! the statistics here: the spread of per-epoch revenue, how much of each month's MEV
+//! came from its best few epochs, and whether epochs starting on some weekdays earn
+//! more than others. The numbers set expectations for a normal epoch, so an unusual
+//! one stands out.
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Epochs per month counted in the MEV concentration share
+pub const MEV_TOP_EPOCHS: usize = 3;
+
+/// One epoch's revenue, in SOL
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EpochRevenue {
+    pub epoch: u64,
+    /// YYYY-MM-DD the epoch is dated by; undated epochs count toward the spread only
+    pub date: Option<String>,
+    /// Commission, leader fees, MEV and BAM
+    pub revenue_sol: f64,
+    pub commission_sol: f64,
+    pub leader_fees_sol: f64,
+    pub mev_sol: f64,
+}
+
+/// Share of one month's MEV earned in its best `MEV_TOP_EPOCHS` epochs
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MevConcentration {
+    /// YYYY-MM
+    pub month: String,
+    pub epochs: usize,
+    pub mev_sol: f64,
+    pub top_share_percent: f64,
+}
+
+/// Average revenue of the epochs dated on one weekday
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WeekdayEffect {
+    /// "Mon" through "Sun"
+    pub weekday: &'static str,
+    pub epochs: usize,
+    pub mean_sol: f64,
+    /// Difference from the overall mean, as a percentage of it
+    pub vs_mean_percent: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RevenueStats {
+    pub epochs: usize,
+    pub mean_sol: f64,
+    /// Sample standard deviation (0 with fewer than two epochs)
+    pub stddev_sol: f64,
+    /// Standard deviation as a percentage of the mean
+    pub cv_percent: Option<f64>,
+    pub min_sol: f64,
+    pub max_sol: f64,
+    /// Epochs per month counted in `MevConcentration::top_share_percent`
+    pub mev_top_epochs: usize,
+    /// Months with MEV, oldest first
+    pub mev_concentration: Vec<MevConcentration>,
+    /// Average of the monthly top-epoch shares
+    pub avg_mev_top_share_percent: Option<f64>,
+    /// Weekdays with at least one epoch, Monday first
+    pub weekdays: Vec<WeekdayEffect>,
+}
+
+/// Mean and sample standard deviation
+pub fn mean_stddev(values: &[f64]) -> (f64, f64) {
+    if values.is_empty() {
+        return (0.0, 0.0);
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    if values.len() < 2 {
+        return (mean, 0.0);
+    }
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, variance.sqrt())
+}
+
+fn weekday_label(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Mon",
+        Weekday::Tue => "Tue",
+        Weekday::Wed => "Wed",
+        Weekday::Thu => "Thu",
+        Weekday::Fri => "Fri",
+        Weekday::Sat => "Sat",
+        Weekday::Sun => "Sun",
+    }
+}
+
+impl RevenueStats {
+    pub fn from_epochs(epochs: &[EpochRevenue]) -> Self {
+        let revenue: Vec<f64> = epochs.iter().map(|e| e.revenue_sol).collect();
+        let (mean_sol, stddev_sol) = mean_stddev(&revenue);
+
+        let mut mev_by_month: BTreeMap<&str, Vec<f64>> = BTreeMap::new();
+        let mut by_weekday: BTreeMap<u32, Vec<f64>> = BTreeMap::new();
+        for e in epochs {
+            let Some(date) = e.date.as_deref() else {
+                continue;
+            };
+            if let Some(month) = date.get(..7) {
+                mev_by_month.entry(month).or_default().push(e.mev_sol);
+            }
+            if let Ok(day) = NaiveDate::parse_from_str(date.get(..10).unwrap_or(date), "%Y-%m-%d") {
+                by_weekday
+                    .entry(day.weekday().num_days_from_monday())
+                    .or_default()
+                    .push(e.revenue_sol);
+            }
+        }
+
+        let mev_concentration: Vec<MevConcentration> = mev_by_month
+            .into_iter()
+            .filter_map(|(month, mut mev)| {
+                let mev_sol: f64 = mev.iter().sum();
+                if mev_sol <= 0.0 {
+                    return None;
+                }
+                mev.sort_by(|a, b| b.total_cmp(a));
+                let top: f64 = mev.iter().take(MEV_TOP_EPOCHS).sum();
+                Some(MevConcentration {
+                    month: month.to_string(),
+                    epochs: mev.len(),
+                    mev_sol,
+                    top_share_percent: top / mev_sol * 100.0,
+                })
+            })
+            .collect();
+        let avg_mev_top_share_percent = (!mev_concentration.is_empty()).then(|| {
+            mev_concentration.iter().map(|m| m.top_share_percent).sum::<f64>() / mev_concentration.len() as f64
+        });
+
+        let weekdays = by_weekday
+            .into_iter()
+            .map(|(day, values)| {
+                let (day_mean, _) = mean_stddev(&values);
+                WeekdayEffect {
+                    weekday: weekday_label(Weekday::try_from(day as u8).unwrap_or(Weekday::Mon)),
+                    epochs: values.len(),
+                    mean_sol: day_mean,
+                    vs_mean_percent: (mean_sol > 0.0).then(|| (day_mean - mean_sol) / mean_sol * 100.0),
+                }
+            })
+            .collect();
+
+        Self {
+            epochs: epochs.len(),
+            mean_sol,
+            stddev_sol,
+            cv_percent: (mean_sol > 0.0).then(|| stddev_sol / mean_sol * 100.0),
+            min_sol: revenue.iter().copied().reduce(f64::min).unwrap_or(0.0),
+            max_sol: revenue.iter().copied().reduce(f64::max).unwrap_or(0.0),
+            mev_top_epochs: MEV_TOP_EPOCHS,
+            mev_concentration,
+            avg_mev_top_share_percent,
+            weekdays,
+        }
+    }
+
+    /// Whether there is anything worth showing
+    pub fn is_empty(&self) -> bool {
+        self.epochs == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn epoch(epoch: u64, date: &str, revenue_sol: f64, mev_sol: f64) -> EpochRevenue {
+        EpochRevenue {
+            epoch,
+            date: Some(date.to_string()),
+            revenue_sol,
+            mev_sol,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn spread_concentration_and_weekdays() {
+        let stats = RevenueStats::from_epochs(&[
+            // 2025-06-02 is a Monday
+            epoch(800, "2025-06-02", 2.0, 1.0),
+            epoch(801, "2025-06-04", 4.0, 1.0),
+            epoch(802, "2025-06-06", 4.0, 1.0),
+            epoch(803, "2025-06-09", 6.0, 7.0),
+            epoch(804, "2025-07-01", 4.0, 0.0),
+        ]);
+        assert_eq!(stats.epochs, 5);
+        assert_eq!(stats.mean_sol, 4.0);
+        assert!((stats.stddev_sol - 2.0_f64.sqrt()).abs() < 1e-12);
+        assert_eq!((stats.min_sol, stats.max_sol), (2.0, 6.0));
+
+        // June: the top three epochs earned 9 of 10 SOL; July had no MEV
+        assert_eq!(stats.mev_concentration.len(), 1);
+        assert_eq!(stats.mev_concentration[0].top_share_percent, 90.0);
+
+        let mondays = &stats.weekdays[0];
+        assert_eq!((mondays.weekday, mondays.epochs, mondays.mean_sol), ("Mon", 2, 4.0));
+        assert_eq!(mondays.vs_mean_percent, Some(0.0));
+        assert_eq!(stats.weekdays[1].weekday, "Tue");
+        assert_eq!(stats.weekdays[1].vs_mean_percent, Some(0.0));
+
+        assert!(RevenueStats::from_epochs(&[]).is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+//! Branding for the report (`[theme]` in config.toml)
+//!
+//! Lets other validators re-skin the report without patching the embedded
+//! template: a title, CSS variable overrides for light and dark mode, and an
+//! optional directory holding a replacement `report.html` (a MiniJinja template
+//! with the same variables as the built-in one) and/or a `theme.css` appended
+//! after the built-in styles.
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Title used when `[theme] title` is unset
+pub const DEFAULT_REPORT_TITLE: &str = "Block Parliament · Validator Financials";
+
+/// Template file looked up in `template_dir`
+const TEMPLATE_FILE: &str = "report.html";
+/// Stylesheet looked up in `template_dir`
+const STYLESHEET_FILE: &str = "theme.css";
+
+/// `[theme]` section
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ThemeConfig {
+    /// Report title, shown in the browser tab and the page header
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Directory with `report.html` and/or `theme.css` overrides; relative paths
+    /// are resolved against the directory holding config.toml
+    #[serde(default)]
+    pub template_dir: Option<PathBuf>,
+    /// CSS variable overrides, e.g. `paper = "#ffffff"` for `--paper`
+    #[serde(default)]
+    pub colors: BTreeMap<String, String>,
+    /// CSS variable overrides applied under `prefers-color-scheme: dark`
+    #[serde(default)]
+    pub dark_colors: BTreeMap<String, String>,
+}
+
+/// A theme with its override files read, ready to render with
+#[derive(Debug, Clone, Default)]
+pub struct ReportTheme {
+    pub(crate) title: Option<String>,
+    /// Replacement for the embedded template
+    pub(crate) template: Option<String>,
+    /// CSS emitted in a `<style>` block after the built-in styles
+    pub(crate) style: String,
+}
+
+impl ThemeConfig {
+    /// Resolve a relative `template_dir` against `base` (the config file's directory)
+    pub fn resolve_paths(&mut self, base: &Path) {
+        if let Some(dir) = &self.template_dir
+            && dir.is_relative()
+        {
+            self.template_dir = Some(base.join(dir));
+        }
+    }
+
+    /// Validate the colors and read any override files
+    pub fn load(&self) -> Result<ReportTheme> {
+        let mut style = String::new();
+        if !self.colors.is_empty() {
+            style.push_str(&format!(":root{{{}}}", css_variables(&self.colors)?));
+        }
+        if !self.dark_colors.is_empty() {
+            style.push_str(&format!(
+                "@media (prefers-color-scheme: dark){{:root{{{}}}}}",
+                css_variables(&self.dark_colors)?
+            ));
+        }
+
+        let mut template = None;
+        if let Some(dir) = &self.template_dir {
+            if !dir.is_dir() {
+                bail!("theme.template_dir {} is not a directory", dir.display());
+            }
+            let template_path = dir.join(TEMPLATE_FILE);
+            if template_path.exists() {
+                let html = std::fs::read_to_string(&template_path)
+                    .with_context(|| format!("Failed to read {}", template_path.display()))?;
+                template = Some(html);
+            }
+            let css_path = dir.join(STYLESHEET_FILE);
+            if css_path.exists() {
+                let css = std::fs::read_to_string(&css_path)
+                    .with_context(|| format!("Failed to read {}", css_path.display()))?;
+                if css.to_ascii_lowercase().contains("</style") {
+                    bail!("{} must not contain </style>", css_path.display());
+                }
+                style.push_str(&css);
+            }
+        }
+
+        Ok(ReportTheme {
+            title: self.title.clone(),
+            template,
+            style,
+        })
+    }
+}
+
+/// `--name:value;` declarations, rejecting anything that could escape the rule
+fn css_variables(colors: &BTreeMap<String, String>) -> Result<String> {
+    let mut css = String::new();
+    for (name, value) in colors {
+        let name = name.trim_start_matches("--");
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            bail!("theme color name '{}' must be letters, digits and dashes", name);
+        }
+        if value.trim().is_empty() || value.contains(['<', '>', '{', '}', ';']) {
+            bail!("theme color '{}' has an invalid value '{}'", name, value);
+        }
+        css.push_str(&format!("--{}:{};", name, value.trim()));
+    }
+    Ok(css)
+}
+
+impl ReportTheme {
+    /// The override template, or `default` when the theme doesn't replace it
+    pub fn template<'a>(&'a self, default: &'a str) -> &'a str {
+        self.template.as_deref().unwrap_or(default)
+    }
+
+    /// Page title (unescaped)
+    pub fn title(&self) -> &str {
+        self.title.as_deref().unwrap_or(DEFAULT_REPORT_TITLE)
+    }
+
+    /// Validated CSS to append after the built-in styles (may be empty)
+    pub fn style(&self) -> &str {
+        &self.style
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_title_and_color_overrides() {
+        let config = ThemeConfig {
+            title: Some("Acme <Stake>".to_string()),
+            colors: BTreeMap::from([("--paper".to_string(), "#ffffff".to_string())]),
+            dark_colors: BTreeMap::from([("link".to_string(), "#ff00ff".to_string())]),
+            ..Default::default()
+        };
+        let theme = config.load().unwrap();
+        assert_eq!(theme.title(), "Acme <Stake>");
+        assert_eq!(
+            theme.style(),
+            ":root{--paper:#ffffff;}@media (prefers-color-scheme: dark){:root{--link:#ff00ff;}}"
+        );
+    }
+
+    #[test]
+    fn default_theme_keeps_the_stock_look() {
+        let theme = ReportTheme::default();
+        assert_eq!(theme.title(), DEFAULT_REPORT_TITLE);
+        assert_eq!(theme.style(), "");
+    }
+
+    #[test]
+    fn rejects_colors_that_escape_the_rule() {
+        for (name, value) in [
+            ("paper", "red;}body{display:none"),
+            ("paper", "</style>"),
+            ("pa per", "red"),
+        ] {
+            let config = ThemeConfig {
+                colors: BTreeMap::from([(name.to_string(), value.to_string())]),
+                ..Default::default()
+            };
+            assert!(config.load().is_err(), "{name} = {value} accepted");
+        }
+    }
+}
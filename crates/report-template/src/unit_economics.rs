@@ -0,0 +1,94 @@
+//! Unit economics KPIs for the report dashboard.
+//!
+//! Both crates sum their own data into [`UnitTotals`] and derive the ratios here,
+//! so the CLI summary, `report.html` and `/financials` agree on the definitions.
+
+use serde::Serialize;
+
+/// Period totals the KPIs are derived from
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct UnitTotals {
+    pub revenue_sol: f64,
+    pub revenue_usd: f64,
+    /// All operating costs in USD (vote fees, DoubleZero, off-chain expenses)
+    pub costs_usd: f64,
+    pub blocks_produced: u64,
+    /// Leader slots assigned (produced + skipped)
+    pub leader_slots: u64,
+    /// Vote fees of the epochs that have a credit count, so the two line up
+    pub credited_vote_fee_lamports: u64,
+    pub vote_credits: u64,
+    /// Sum of per-epoch delegated stake over `stake_epochs` epochs
+    pub delegated_sol_epochs: f64,
+    pub stake_epochs: u64,
+}
+
+/// Derived KPIs; a ratio is `None` when its denominator is zero or unknown
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UnitEconomics {
+    pub blocks_produced: u64,
+    pub leader_slots: u64,
+    pub vote_credits: u64,
+    /// Average delegated stake over the epochs it is known for
+    pub avg_delegated_sol: Option<f64>,
+    pub cost_per_block_usd: Option<f64>,
+    pub revenue_per_leader_slot_sol: Option<f64>,
+    pub vote_fee_per_credit_lamports: Option<f64>,
+    /// Revenue (USD) earned per SOL of average delegated stake
+    pub usd_per_delegated_sol: Option<f64>,
+}
+
+impl UnitEconomics {
+    pub fn from_totals(t: &UnitTotals) -> Self {
+        let ratio = |num: f64, den: f64| (den > 0.0).then(|| num / den);
+        let avg_delegated_sol = ratio(t.delegated_sol_epochs, t.stake_epochs as f64);
+        Self {
+            blocks_produced: t.blocks_produced,
+            leader_slots: t.leader_slots,
+            vote_credits: t.vote_credits,
+            avg_delegated_sol,
+            cost_per_block_usd: ratio(t.costs_usd, t.blocks_produced as f64),
+            revenue_per_leader_slot_sol: ratio(t.revenue_sol, t.leader_slots as f64),
+            vote_fee_per_credit_lamports: ratio(t.credited_vote_fee_lamports as f64, t.vote_credits as f64),
+            usd_per_delegated_sol: avg_delegated_sol.and_then(|stake| ratio(t.revenue_usd, stake)),
+        }
+    }
+
+    /// Whether there is anything worth showing
+    pub fn is_empty(&self) -> bool {
+        self.blocks_produced == 0
+            && self.leader_slots == 0
+            && self.vote_credits == 0
+            && self.avg_delegated_sol.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ratios_need_a_denominator() {
+        let kpis = UnitEconomics::from_totals(&UnitTotals {
+            revenue_sol: 40.0,
+            revenue_usd: 8_000.0,
+            costs_usd: 2_000.0,
+            blocks_produced: 100,
+            leader_slots: 80,
+            credited_vote_fee_lamports: 5_000_000,
+            vote_credits: 1_000,
+            delegated_sol_epochs: 200_000.0,
+            stake_epochs: 2,
+        });
+        assert_eq!(kpis.cost_per_block_usd, Some(20.0));
+        assert_eq!(kpis.revenue_per_leader_slot_sol, Some(0.5));
+        assert_eq!(kpis.vote_fee_per_credit_lamports, Some(5_000.0));
+        assert_eq!(kpis.avg_delegated_sol, Some(100_000.0));
+        assert_eq!(kpis.usd_per_delegated_sol, Some(0.08));
+
+        let empty = UnitEconomics::from_totals(&UnitTotals::default());
+        assert!(empty.is_empty());
+        assert_eq!(empty.cost_per_block_usd, None);
+        assert_eq!(empty.usd_per_delegated_sol, None);
+    }
+}
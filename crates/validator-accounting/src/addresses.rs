@@ -1,16 +1,17 @@
 //! Known address labels for transaction categorization
 //!
 //! This module contains mappings of known Solana addresses to human-readable labels.
-//! These are used to automatically categorize transactions.
+//! These are used to automatically categorize transactions. Labels come from the bundled
+//! `labels.toml`, optional community lists (`[labels] sources`) and operator tags.
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 use std::collections::HashMap;
 use std::str::FromStr;
-use std::sync::LazyLock;
+use std::sync::{LazyLock, RwLock};
 
 /// Address category for classification
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub enum AddressCategory {
     /// Solana Foundation (SFDP reimbursements, delegations)
@@ -37,6 +38,51 @@ pub enum AddressCategory {
     Unknown,
 }
 
+impl FromStr for AddressCategory {
+    type Err = anyhow::Error;
+
+    /// Parse a category name as shown in reports ("Exchange", "DeFiProtocol", ...), ignoring case
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let category = match s.to_ascii_lowercase().as_str() {
+            "solanafoundation" => AddressCategory::SolanaFoundation,
+            "jitomev" => AddressCategory::JitoMev,
+            "bamrewards" => AddressCategory::BamRewards,
+            "exchange" => AddressCategory::Exchange,
+            "validatorself" => AddressCategory::ValidatorSelf,
+            "personalwallet" => AddressCategory::PersonalWallet,
+            "defiprotocol" => AddressCategory::DeFiProtocol,
+            "systemprogram" => AddressCategory::SystemProgram,
+            "stakeprogram" => AddressCategory::StakeProgram,
+            "voteprogram" => AddressCategory::VoteProgram,
+            "unknown" => AddressCategory::Unknown,
+            _ => anyhow::bail!("Unknown address category: {}", s),
+        };
+        Ok(category)
+    }
+}
+
+/// Where a label came from, recorded in ledger notes so an accountant can see why a
+/// counterparty was classified the way it was
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LabelSource {
+    /// `labels.toml` compiled into the binary
+    Bundled,
+    /// Community label list from `[labels] sources` (URL or file path)
+    Community(String),
+    /// Operator tag (`addresses tag`)
+    Tag,
+}
+
+impl std::fmt::Display for LabelSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LabelSource::Bundled => write!(f, "bundled label list"),
+            LabelSource::Community(source) => write!(f, "community label list {}", source),
+            LabelSource::Tag => write!(f, "operator tag"),
+        }
+    }
+}
+
 /// Label information for an address
 #[derive(Debug, Clone)]
 pub struct AddressLabel {
@@ -44,386 +90,128 @@ pub struct AddressLabel {
     pub name: String,
     #[allow(dead_code)]
     pub description: Option<String>,
+    /// None for unlabeled addresses
+    pub source: Option<LabelSource>,
 }
 
-/// Static map of known addresses
-/// Sources: Solscan labels, Solana documentation, Jito documentation
-pub static KNOWN_ADDRESSES: LazyLock<HashMap<Pubkey, AddressLabel>> = LazyLock::new(|| {
-    let mut map = HashMap::new();
-
-    // =========================================================================
-    // Solana Foundation Addresses
-    // These are used for SFDP reimbursements and delegations
-    // =========================================================================
-
-    // Solana Foundation main addresses (from Solscan labels)
-    add_address(
-        &mut map,
-        "mpa4abUkjQoAvPzREkh5Mo75hZhPFQ2FSH6w7dWKuQ5",
-        AddressCategory::SolanaFoundation,
-        "Solana Foundation",
-        Some("Main SF wallet for SFDP operations"),
-    );
-
-    add_address(
-        &mut map,
-        "7K8DVxtNJGnMtUY1CQJT5jcs8sFGSZTDiG7kowvFpECh",
-        AddressCategory::SolanaFoundation,
-        "Solana Foundation Stake Authority",
-        Some("SF stake authority for delegations"),
-    );
-
-    // Common SF delegation/reimbursement wallets
-    add_address(
-        &mut map,
-        "DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy",
-        AddressCategory::SolanaFoundation,
-        "SF Delegation Program",
-        Some("SFDP delegation operations"),
-    );
-
-    add_address(
-        &mut map,
-        "4ZJhPQAgUseCsWhKvJLTmmRRUV74fdoTpQLNfKoHtFSP",
-        AddressCategory::SolanaFoundation,
-        "Solana Foundation Operations",
-        Some("SF operational wallet"),
-    );
-
-    // SFDP Vote Cost Reimbursement wallet (confirmed from on-chain transfers)
-    add_address(
-        &mut map,
-        "DtZWL3BPKa5hw7yQYvaFR29PcXThpLHVU2XAAZrcLiSe",
-        AddressCategory::SolanaFoundation,
-        "SFDP Vote Reimbursement",
-        Some("Solana Foundation vote cost reimbursements"),
-    );
-
-    // =========================================================================
-    // Jito MEV Addresses (Mainnet - Frankfurt)
-    // =========================================================================
-
-    add_address(
-        &mut map,
-        "T1pyyaTNZsKv2WcRAB8oVnk93mLJw2XzjtVYqCsaHqt",
-        AddressCategory::JitoMev,
-        "Jito Tip Payment Program",
-        Some("Program ID for tip payments"),
-    );
-
-    add_address(
-        &mut map,
-        "4R3gSG8BpU4t19KYj8CfnbtRpnT8gtk4dvTHxVRwc2r7",
-        AddressCategory::JitoMev,
-        "Jito Tip Distribution Program",
-        Some("Program ID for tip distribution"),
-    );
-
-    add_address(
-        &mut map,
-        "8F4jGUmxF36vQ6yabnsxX6AQVXdKBhs8kGSUuRKSg8Xt",
-        AddressCategory::JitoMev,
-        "Jito Merkle Root Upload Authority",
-        Some("Authority for merkle root uploads"),
-    );
-
-    // Jito tip accounts (the 8 tip payment accounts)
-    add_address(
-        &mut map,
-        "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5",
-        AddressCategory::JitoMev,
-        "Jito Tip Account 1",
-        None,
-    );
-    add_address(
-        &mut map,
-        "HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe",
-        AddressCategory::JitoMev,
-        "Jito Tip Account 2",
-        None,
-    );
-    add_address(
-        &mut map,
-        "Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY",
-        AddressCategory::JitoMev,
-        "Jito Tip Account 3",
-        None,
-    );
-    add_address(
-        &mut map,
-        "ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49",
-        AddressCategory::JitoMev,
-        "Jito Tip Account 4",
-        None,
-    );
-    add_address(
-        &mut map,
-        "DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh",
-        AddressCategory::JitoMev,
-        "Jito Tip Account 5",
-        None,
-    );
-    add_address(
-        &mut map,
-        "ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt",
-        AddressCategory::JitoMev,
-        "Jito Tip Account 6",
-        None,
-    );
-    add_address(
-        &mut map,
-        "DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL",
-        AddressCategory::JitoMev,
-        "Jito Tip Account 7",
-        None,
-    );
-    add_address(
-        &mut map,
-        "3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT",
-        AddressCategory::JitoMev,
-        "Jito Tip Account 8",
-        None,
-    );
-
-    // =========================================================================
-    // Jito BAM Boost (JIP-31 - jitoSOL rewards for validators)
-    // =========================================================================
-
-    add_address(
-        &mut map,
-        "BoostxbPp2ENYHGcTLYt1obpcY13HE4NojdqNWdzqSSb",
-        AddressCategory::BamRewards,
-        "Jito BAM Boost Program",
-        Some("JIP-31 Block Assembly Marketplace program"),
-    );
-
-    add_address(
-        &mut map,
-        "J1toso1uCk3RLmjorhTtrVwY9HJ7X8V9yYac6Y7kGCPn",
-        AddressCategory::BamRewards,
-        "jitoSOL Mint",
-        Some("jitoSOL SPL token mint"),
-    );
-
-    // =========================================================================
-    // Exchanges
-    // =========================================================================
-
-    add_address(
-        &mut map,
-        "H8sMJSCQxfKiFTCfDR3DUMLPwcRbM61LGFJ8N4dK3WjS",
-        AddressCategory::Exchange,
-        "Coinbase",
-        Some("Coinbase main wallet"),
-    );
-
-    add_address(
-        &mut map,
-        "2AQdpHJ2JpcEgPiATUXjQxA8QmafFegfQwSLWSprPicm",
-        AddressCategory::Exchange,
-        "Binance",
-        Some("Binance hot wallet"),
-    );
-
-    add_address(
-        &mut map,
-        "5tzFkiKscXHK5ZXCGbXZxdw7gTjjD1mBwuoFbhUvuAi9",
-        AddressCategory::Exchange,
-        "Kraken",
-        Some("Kraken wallet"),
-    );
-
-    // =========================================================================
-    // DeFi Protocols (DEX aggregators, AMMs, liquid staking)
-    // These are intermediate routing addresses, not external destinations
-    // =========================================================================
-
-    // Jupiter Aggregator
-    add_address(
-        &mut map,
-        "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4",
-        AddressCategory::DeFiProtocol,
-        "Jupiter v6",
-        Some("Jupiter aggregator program"),
-    );
-    add_address(
-        &mut map,
-        "JUP4Fb2cqiRUcaTHdrPC8h2gNsA2ETXiPDD33WcGuJB",
-        AddressCategory::DeFiProtocol,
-        "Jupiter v4",
-        Some("Jupiter aggregator v4"),
-    );
-
-    // Raydium AMM
-    add_address(
-        &mut map,
-        "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8",
-        AddressCategory::DeFiProtocol,
-        "Raydium AMM",
-        Some("Raydium AMM program"),
-    );
-
-    // Orca Whirlpool
-    add_address(
-        &mut map,
-        "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc",
-        AddressCategory::DeFiProtocol,
-        "Orca Whirlpool",
-        Some("Orca concentrated liquidity"),
-    );
-
-    // Marinade (liquid staking)
-    add_address(
-        &mut map,
-        "MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD",
-        AddressCategory::DeFiProtocol,
-        "Marinade Finance",
-        Some("Marinade liquid staking"),
-    );
-
-    // Phoenix DEX
-    add_address(
-        &mut map,
-        "PhoeNiXZ8ByJGLkxNfZRnkUfjvmuYqLR89jjFHGqdXY",
-        AddressCategory::DeFiProtocol,
-        "Phoenix DEX",
-        Some("Phoenix order book DEX"),
-    );
-
-    // Meteora
-    add_address(
-        &mut map,
-        "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo",
-        AddressCategory::DeFiProtocol,
-        "Meteora DLMM",
-        Some("Meteora dynamic liquidity"),
-    );
-
-    // Token Program (SPL Token)
-    add_address(
-        &mut map,
-        "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
-        AddressCategory::DeFiProtocol,
-        "SPL Token Program",
-        Some("Token program for swaps"),
-    );
-
-    // Associated Token Account Program
-    add_address(
-        &mut map,
-        "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
-        AddressCategory::DeFiProtocol,
-        "ATA Program",
-        Some("Associated token accounts"),
-    );
-
-    // Jupiter pool/market accounts (specific to swap transactions)
-    add_address(
-        &mut map,
-        "GyY4VgEpJQhiKZRAJJmoM4hv5Q2xC4pvX68MGrGidxyG",
-        AddressCategory::DeFiProtocol,
-        "Jupiter Pool",
-        Some("Jupiter swap routing"),
-    );
-
-    // SolFi market (wSOL-USDC)
-    add_address(
-        &mut map,
-        "CRo8DBwrmd97DJfAnvCv96tZPL5Mktf2NZy2ZnhDer1A",
-        AddressCategory::DeFiProtocol,
-        "SolFi wSOL-USDC",
-        Some("SolFi market token account"),
-    );
-    add_address(
-        &mut map,
-        "65ZHSArs5XxPseKQbB1B4r16vDxMWnCxHMzogDAqiDUc",
-        AddressCategory::DeFiProtocol,
-        "SolFi Market Owner",
-        Some("SolFi wSOL-USDC market owner"),
-    );
-
-    // Common wSOL intermediate accounts used in swaps
-    add_address(
-        &mut map,
-        "CTyFguG69kwYrzk24P3UuBvY1rR5atu9kf2S6XEwAU8X",
-        AddressCategory::DeFiProtocol,
-        "wSOL Swap Account",
-        Some("Wrapped SOL intermediate for swaps"),
-    );
-    add_address(
-        &mut map,
-        "EHBeyyQwD6MLa48fdxSjEaMHLur6BrcGtVcJ5c66AvaC",
-        AddressCategory::DeFiProtocol,
-        "wSOL Swap Account",
-        Some("Wrapped SOL intermediate for swaps"),
-    );
-
-    // =========================================================================
-    // System Programs
-    // =========================================================================
-
-    add_address(
-        &mut map,
-        "11111111111111111111111111111111",
-        AddressCategory::SystemProgram,
-        "System Program",
-        None,
-    );
-
-    add_address(
-        &mut map,
-        "Stake11111111111111111111111111111111111111",
-        AddressCategory::StakeProgram,
-        "Stake Program",
-        None,
-    );
+/// Bundled label list (exchanges, protocols, Foundation addresses)
+const BUNDLED_LABELS: &str = include_str!("labels.toml");
+
+/// One `[[label]]` entry of a label list
+#[derive(Debug, Deserialize)]
+struct LabelEntry {
+    address: String,
+    category: String,
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+}
 
-    add_address(
-        &mut map,
-        "Vote111111111111111111111111111111111111111",
-        AddressCategory::VoteProgram,
-        "Vote Program",
-        None,
-    );
+#[derive(Debug, Deserialize)]
+struct LabelFile {
+    #[serde(default)]
+    label: Vec<LabelEntry>,
+}
 
-    map
-});
+/// Parse a label list: TOML with `[[label]]` tables (the bundled format) or a JSON array of
+/// the same entries. Fails on the first invalid address or category.
+pub fn parse_label_list(content: &str, source: LabelSource) -> anyhow::Result<HashMap<Pubkey, AddressLabel>> {
+    let entries = if content.trim_start().starts_with('[') && !content.trim_start().starts_with("[[") {
+        serde_json::from_str::<Vec<LabelEntry>>(content)?
+    } else {
+        toml::from_str::<LabelFile>(content)?.label
+    };
 
-/// Helper to add an address to the map
-fn add_address(
-    map: &mut HashMap<Pubkey, AddressLabel>,
-    address: &str,
-    category: AddressCategory,
-    name: &str,
-    description: Option<&str>,
-) {
-    if let Ok(pubkey) = Pubkey::from_str(address) {
+    let mut map = HashMap::new();
+    for entry in entries {
+        let pubkey = Pubkey::from_str(&entry.address)
+            .map_err(|_| anyhow::anyhow!("Invalid address in label list: {}", entry.address))?;
         map.insert(
             pubkey,
             AddressLabel {
-                category,
-                name: name.to_string(),
-                description: description.map(|s| s.to_string()),
+                category: entry.category.parse()?,
+                name: entry.name,
+                description: entry.description,
+                source: Some(source.clone()),
             },
         );
     }
+    Ok(map)
+}
+
+/// Known addresses from the bundled label list
+pub static KNOWN_ADDRESSES: LazyLock<HashMap<Pubkey, AddressLabel>> =
+    LazyLock::new(|| parse_label_list(BUNDLED_LABELS, LabelSource::Bundled).expect("bundled labels.toml is valid"));
+
+/// Labels from community lists (`[labels] sources`), checked before the bundled list
+static COMMUNITY_LABELS: LazyLock<RwLock<HashMap<Pubkey, AddressLabel>>> = LazyLock::new(Default::default);
+
+/// Load community label lists (URLs or file paths) and install them. Later sources override
+/// earlier ones. A source that can't be fetched or parsed is skipped with a warning so a
+/// flaky list never blocks a run. Returns the number of labels installed.
+pub async fn load_community_labels(sources: &[String]) -> usize {
+    let mut labels = HashMap::new();
+    for source in sources {
+        match read_label_source(source).await {
+            Ok(list) => labels.extend(list),
+            Err(e) => eprintln!("Warning: Skipping label list {}: {}", source, e),
+        }
+    }
+    let count = labels.len();
+    *COMMUNITY_LABELS.write().unwrap_or_else(|e| e.into_inner()) = labels;
+    count
+}
+
+async fn read_label_source(source: &str) -> anyhow::Result<HashMap<Pubkey, AddressLabel>> {
+    let content = if source.starts_with("https://") || source.starts_with("http://") {
+        crate::offline::ensure_online("Fetching a remote label list")?;
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .build()?;
+        client.get(source).send().await?.error_for_status()?.text().await?
+    } else {
+        std::fs::read_to_string(source)?
+    };
+    parse_label_list(&content, LabelSource::Community(source.to_string()))
+}
+
+/// Operator-tagged addresses (`addresses tag`, stored in the cache), checked before the
+/// label lists so a rotating exchange deposit address keeps its classification
+static ADDRESS_TAGS: LazyLock<RwLock<HashMap<Pubkey, AddressLabel>>> = LazyLock::new(Default::default);
+
+/// Replace the operator address tags (called at startup and after tagging)
+pub fn install_tags(tags: HashMap<Pubkey, AddressLabel>) {
+    *ADDRESS_TAGS.write().unwrap_or_else(|e| e.into_inner()) = tags;
+}
+
+/// Operator tag first, then community lists, then the bundled list
+fn lookup(pubkey: &Pubkey) -> Option<AddressLabel> {
+    let tags = ADDRESS_TAGS.read().unwrap_or_else(|e| e.into_inner());
+    let community = COMMUNITY_LABELS.read().unwrap_or_else(|e| e.into_inner());
+    tags.get(pubkey)
+        .or_else(|| community.get(pubkey))
+        .or_else(|| KNOWN_ADDRESSES.get(pubkey))
+        .cloned()
+}
+
+/// Which label source matched an address, if any
+pub fn label_source(pubkey: &Pubkey) -> Option<LabelSource> {
+    lookup(pubkey).and_then(|l| l.source)
 }
 
 /// Get label for an address, or return "Unknown" with the address
 pub fn get_label(pubkey: &Pubkey) -> AddressLabel {
-    KNOWN_ADDRESSES.get(pubkey).cloned().unwrap_or_else(|| AddressLabel {
+    lookup(pubkey).unwrap_or_else(|| AddressLabel {
         category: AddressCategory::Unknown,
         name: format!("{}...{}", &pubkey.to_string()[..4], &pubkey.to_string()[40..]),
         description: None,
+        source: None,
     })
 }
 
 /// Get category for an address
 pub fn get_category(pubkey: &Pubkey) -> AddressCategory {
-    KNOWN_ADDRESSES
-        .get(pubkey)
-        .map(|l| l.category)
-        .unwrap_or(AddressCategory::Unknown)
+    lookup(pubkey).map(|l| l.category).unwrap_or(AddressCategory::Unknown)
 }
 
 /// Check if address is from Solana Foundation
@@ -456,3 +244,37 @@ pub fn get_defi_protocol_addresses() -> Vec<String> {
         .map(|(pubkey, _)| pubkey.to_string())
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_labels_parse() {
+        let sf = Pubkey::from_str("mpa4abUkjQoAvPzREkh5Mo75hZhPFQ2FSH6w7dWKuQ5").unwrap();
+        let label = KNOWN_ADDRESSES.get(&sf).unwrap();
+        assert_eq!(label.category, AddressCategory::SolanaFoundation);
+        assert_eq!(label.source, Some(LabelSource::Bundled));
+        assert!(KNOWN_ADDRESSES.len() >= 40);
+    }
+
+    #[test]
+    fn community_list_accepts_json_and_rejects_bad_categories() {
+        let address = Pubkey::new_unique().to_string();
+        let json = format!(
+            r#"[{{"address": "{}", "category": "exchange", "name": "Kraken"}}]"#,
+            address
+        );
+        let source = LabelSource::Community("labels.json".to_string());
+        let labels = parse_label_list(&json, source.clone()).unwrap();
+        let label = &labels[&Pubkey::from_str(&address).unwrap()];
+        assert_eq!(label.category, AddressCategory::Exchange);
+        assert_eq!(label.source, Some(source.clone()));
+
+        let toml = format!(
+            "[[label]]\naddress = \"{}\"\ncategory = \"Casino\"\nname = \"?\"\n",
+            address
+        );
+        assert!(parse_label_list(&toml, source).is_err());
+    }
+}
@@ -0,0 +1,31 @@
+//! Per-epoch revenue anomalies (`[anomalies]`)
+//!
+//! Commission, leader fees and MEV far outside their trailing baseline usually mean
+//! a missed claim, a data gap or a performance problem. Detection lives in
+//! `report_template` so bp-web's admin page and webhook alerts flag the same epochs.
+
+use report_template::{RevenueAnomaly, detect_anomalies};
+
+use crate::reports::ReportData;
+use crate::revenue_stats;
+
+/// Anomalies in the epochs dated on days accepted by `include`. Baselines use the
+/// full history, so the first epochs of a period are judged like any other.
+pub fn compute(data: &ReportData, include: impl Fn(&str) -> bool) -> Vec<RevenueAnomaly> {
+    detect_anomalies(&revenue_stats::epochs(data), &data.config.anomalies)
+        .into_iter()
+        .filter(|a| a.date.as_deref().is_some_and(&include))
+        .collect()
+}
+
+/// Console section for the financial summary
+pub fn print(anomalies: &[RevenueAnomaly], sigma: f64) {
+    if anomalies.is_empty() {
+        return;
+    }
+    println!("\nREVENUE ANOMALIES (> {}σ from the trailing baseline):", sigma);
+    for a in anomalies {
+        println!("  {}", a.summary());
+    }
+    println!("  Check for unclaimed MEV, missing cache rows, or delinquency in those epochs");
+}
@@ -0,0 +1,408 @@
+//! Portable dataset archive (`export-archive` / `import-archive`)
+//!
+//! A versioned `.tar.zst` holding every cache table as JSON Lines plus a manifest and the
+//! config with secrets removed. Rows are plain JSON, so an archive outlives the SQLite file
+//! it came from: it moves a dataset between machines, restores into a newer schema, and with
+//! `--anonymize` can be attached to a bug report without revealing whose validator it is.
+//!
+//! Layout:
+//! - `manifest.json`: format version, cluster, validator metadata, per-table columns and row counts
+//! - `config.toml`: the config used for the export, secrets stripped (absent if none was found)
+//! - `tables/<name>.jsonl`: one JSON object per row
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Bumped when the layout changes in a way older readers can't handle
+pub const ARCHIVE_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_PATH: &str = "manifest.json";
+const CONFIG_PATH: &str = "config.toml";
+const TABLES_DIR: &str = "tables/";
+
+/// Config keys whose values never leave the machine
+const SECRET_KEY_PARTS: &[&str] = &["api_key", "token", "secret", "password", "webhook"];
+
+/// Credential fields of known sections whose names don't look like secrets
+const SECRET_FIELDS: &[(&str, &[&str])] = &[
+    ("s3", &["access_key_id", "secret_access_key", "kms_key_id", "endpoint"]),
+    ("gsheets", &["credentials_path", "spreadsheet_id"]),
+    ("keeper", &["keypair_path"]),
+];
+
+/// Archive metadata
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub format_version: u32,
+    pub created_at: String,
+    pub tool_version: String,
+    pub cluster: String,
+    pub anonymized: bool,
+    pub vote_account: Option<String>,
+    pub identity: Option<String>,
+    pub commission_percent: Option<u8>,
+    pub tables: Vec<ArchiveTable>,
+}
+
+/// One archived table
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArchiveTable {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub rows: u64,
+}
+
+/// A fully read archive
+pub struct Archive {
+    pub manifest: ArchiveManifest,
+    pub config_toml: Option<String>,
+    pub tables: BTreeMap<String, Vec<Map<String, Value>>>,
+}
+
+/// Remove secrets from config.toml text: the `[api_keys]` table, the known credential fields
+/// of `[s3]`, `[gsheets]` and `[keeper]`, and any key that looks like a credential
+pub fn redact_config(config_toml: &str) -> Result<toml::Table> {
+    fn strip(table: &mut toml::Table) {
+        table.retain(|key, _| {
+            let key = key.to_lowercase();
+            !SECRET_KEY_PARTS.iter().any(|part| key.contains(part))
+        });
+        for value in table.values_mut() {
+            match value {
+                toml::Value::Table(inner) => strip(inner),
+                toml::Value::Array(items) => {
+                    for item in items {
+                        if let toml::Value::Table(inner) = item {
+                            strip(inner);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut table: toml::Table = toml::from_str(config_toml).context("Failed to parse config for archive")?;
+    table.remove("api_keys");
+    for (section, fields) in SECRET_FIELDS {
+        if let Some(toml::Value::Table(inner)) = table.get_mut(*section) {
+            inner.retain(|key, _| !fields.contains(&key.as_str()));
+        }
+    }
+    strip(&mut table);
+    Ok(table)
+}
+
+/// Replaces addresses and signatures with stand-ins, consistently across the whole archive
+///
+/// Stand-ins are valid base58 of the same kind, so an anonymized archive still loads and
+/// reports still join on addresses. Free text (expense vendors, notes) is left alone.
+#[derive(Default)]
+pub struct Anonymizer {
+    replacements: HashMap<String, String>,
+}
+
+impl Anonymizer {
+    fn replacement(&mut self, original: &str) -> Option<String> {
+        if let Some(existing) = self.replacements.get(original) {
+            return Some(existing.clone());
+        }
+        // Counter bytes go at the end so stand-ins read as distinct at a glance
+        let n = (self.replacements.len() as u64 + 1).to_be_bytes();
+        let replacement = if original.len() <= 44 && Pubkey::from_str(original).is_ok() {
+            let mut bytes = [0u8; 32];
+            bytes[24..].copy_from_slice(&n);
+            Pubkey::new_from_array(bytes).to_string()
+        } else if original.len() >= 64 && Signature::from_str(original).is_ok() {
+            let mut bytes = [0u8; 64];
+            bytes[56..].copy_from_slice(&n);
+            Signature::from(bytes).to_string()
+        } else {
+            return None;
+        };
+        self.replacements.insert(original.to_string(), replacement.clone());
+        Some(replacement)
+    }
+
+    /// Anonymize every string in a JSON value, recursively
+    pub fn anonymize(&mut self, value: &mut Value) {
+        match value {
+            Value::String(s) => {
+                // Base58 only: skip dates, categories and other short strings cheaply
+                if s.len() >= 32
+                    && s.chars().all(|c| c.is_ascii_alphanumeric())
+                    && let Some(replacement) = self.replacement(s)
+                {
+                    *s = replacement;
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|v| self.anonymize(v)),
+            Value::Object(map) => map.values_mut().for_each(|v| self.anonymize(v)),
+            _ => {}
+        }
+    }
+
+    /// Anonymize addresses and signatures embedded in free text (log lines, error messages)
+    pub fn anonymize_text(&mut self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut token = String::new();
+        for c in text.chars().chain(std::iter::once('\n')) {
+            if c.is_ascii_alphanumeric() {
+                token.push(c);
+                continue;
+            }
+            let replacement = if token.len() >= 32 {
+                self.replacement(&token)
+            } else {
+                None
+            };
+            out.push_str(replacement.as_deref().unwrap_or(&token));
+            token.clear();
+            out.push(c);
+        }
+        out.pop();
+        out
+    }
+
+    /// Anonymize a config table via its JSON form
+    pub fn anonymize_config(&mut self, config: toml::Table) -> Result<toml::Table> {
+        let mut json = serde_json::to_value(config)?;
+        self.anonymize(&mut json);
+        Ok(serde_json::from_value(json)?)
+    }
+}
+
+fn append(builder: &mut tar::Builder<impl Write>, path: &str, data: &[u8]) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(chrono::Utc::now().timestamp().max(0) as u64);
+    header.set_cksum();
+    builder.append_data(&mut header, path, data)?;
+    Ok(())
+}
+
+/// Write an archive; `tables` pairs each manifest entry with its rows
+pub fn write(
+    path: &Path,
+    manifest: &ArchiveManifest,
+    config: Option<&toml::Table>,
+    tables: &BTreeMap<String, Vec<Value>>,
+) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = std::fs::File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let encoder = zstd::Encoder::new(file, 0)?;
+    let mut builder = tar::Builder::new(encoder);
+
+    append(&mut builder, MANIFEST_PATH, &serde_json::to_vec_pretty(manifest)?)?;
+    if let Some(config) = config {
+        append(&mut builder, CONFIG_PATH, toml::to_string_pretty(config)?.as_bytes())?;
+    }
+    for (name, rows) in tables {
+        let mut jsonl = Vec::new();
+        for row in rows {
+            serde_json::to_writer(&mut jsonl, row)?;
+            jsonl.push(b'\n');
+        }
+        append(&mut builder, &format!("{}{}.jsonl", TABLES_DIR, name), &jsonl)?;
+    }
+
+    builder.into_inner()?.finish()?.sync_all()?;
+    Ok(())
+}
+
+/// Read and check an archive
+pub fn read(path: &Path) -> Result<Archive> {
+    let file = std::fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let decoder = zstd::Decoder::new(file)?;
+    let mut tar = tar::Archive::new(decoder);
+
+    let mut manifest = None;
+    let mut config_toml = None;
+    let mut tables = BTreeMap::new();
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().into_owned();
+        let mut data = String::new();
+        entry
+            .read_to_string(&mut data)
+            .with_context(|| format!("Failed to read {} from archive", name))?;
+
+        if name == MANIFEST_PATH {
+            manifest = Some(serde_json::from_str::<ArchiveManifest>(&data).context("Invalid archive manifest")?);
+        } else if name == CONFIG_PATH {
+            config_toml = Some(data);
+        } else if let Some(table) = name.strip_prefix(TABLES_DIR).and_then(|n| n.strip_suffix(".jsonl")) {
+            let rows = data
+                .lines()
+                .filter(|l| !l.trim().is_empty())
+                .map(serde_json::from_str)
+                .collect::<Result<Vec<Map<String, Value>>, _>>()
+                .with_context(|| format!("Invalid row in {}", name))?;
+            tables.insert(table.to_string(), rows);
+        }
+    }
+
+    let manifest = manifest.context("Not a validator-accounting archive (no manifest.json)")?;
+    anyhow::ensure!(
+        manifest.format_version <= ARCHIVE_FORMAT_VERSION,
+        "Archive format v{} is newer than this build supports (v{}); upgrade validator-accounting",
+        manifest.format_version,
+        ARCHIVE_FORMAT_VERSION
+    );
+    for table in &manifest.tables {
+        let found = tables.get(&table.name).map_or(0, |rows| rows.len() as u64);
+        anyhow::ensure!(
+            found == table.rows,
+            "Archive is truncated: {} has {} rows, manifest says {}",
+            table.name,
+            found,
+            table.rows
+        );
+    }
+
+    Ok(Archive {
+        manifest,
+        config_toml,
+        tables,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_config_strips_secrets() {
+        let config = redact_config(
+            r#"
+            [validator]
+            vote_account = "abc"
+            [api_keys]
+            helius = "secret"
+            [notion]
+            api_token = "secret"
+            database_id = "db"
+            "#,
+        )
+        .unwrap();
+        assert!(!config.contains_key("api_keys"));
+        assert_eq!(config["validator"]["vote_account"].as_str(), Some("abc"));
+        let notion = config["notion"].as_table().unwrap();
+        assert!(!notion.contains_key("api_token"));
+        assert!(notion.contains_key("database_id"));
+    }
+
+    #[test]
+    fn test_redact_config_strips_storage_credentials() {
+        let config = redact_config(
+            r#"
+            [s3]
+            bucket = "reports"
+            access_key_id = "AKIAEXAMPLE"
+            secret_access_key = "secret"
+            kms_key_id = "arn:aws:kms:us-east-1:123456789012:key/abc"
+            [gsheets]
+            spreadsheet_id = "sheet"
+            credentials_path = "/home/op/service-account.json"
+            [keeper]
+            keypair_path = "/home/op/withdrawer.json"
+            "#,
+        )
+        .unwrap();
+        let s3 = config["s3"].as_table().unwrap();
+        assert_eq!(s3.keys().collect::<Vec<_>>(), ["bucket"]);
+        assert!(config["gsheets"].as_table().unwrap().is_empty());
+        assert!(config["keeper"].as_table().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_anonymizer_is_consistent_and_skips_text() {
+        let vote = Pubkey::new_unique().to_string();
+        let other = Pubkey::new_unique().to_string();
+        let mut value = serde_json::json!({
+            "from_address": vote,
+            "to_address": other,
+            "nested": [vote],
+            "description": "AWS hosting invoice",
+            "date": "2025-01-01",
+        });
+        let mut anonymizer = Anonymizer::default();
+        anonymizer.anonymize(&mut value);
+
+        let from = value["from_address"].as_str().unwrap();
+        assert_ne!(from, vote);
+        assert!(Pubkey::from_str(from).is_ok());
+        assert_eq!(value["nested"][0].as_str(), Some(from));
+        assert_ne!(value["to_address"].as_str(), Some(from));
+        assert_eq!(value["description"], "AWS hosting invoice");
+        assert_eq!(value["date"], "2025-01-01");
+    }
+
+    #[test]
+    fn test_anonymize_text_keeps_surrounding_text() {
+        let vote = Pubkey::new_unique().to_string();
+        let mut anonymizer = Anonymizer::default();
+        let line = format!("rpc: account {} not found (epoch 800)", vote);
+        let replaced = anonymizer.anonymize_text(&line);
+        assert!(!replaced.contains(&vote));
+        assert!(replaced.starts_with("rpc: account "));
+        assert!(replaced.ends_with(" not found (epoch 800)"));
+        // Same stand-in as structured values
+        let mut value = Value::String(vote);
+        anonymizer.anonymize(&mut value);
+        assert!(replaced.contains(value.as_str().unwrap()));
+    }
+
+    #[test]
+    fn test_anonymizer_replaces_signatures() {
+        let signature = Signature::from([7u8; 64]).to_string();
+        let mut value = Value::String(signature.clone());
+        Anonymizer::default().anonymize(&mut value);
+        let replaced = value.as_str().unwrap();
+        assert_ne!(replaced, signature);
+        assert!(Signature::from_str(replaced).is_ok());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dir = std::env::temp_dir().join(format!("va-archive-{}", std::process::id()));
+        let path = dir.join("test.tar.zst");
+        let rows = vec![serde_json::json!({"epoch": 800, "amount_sol": 1.5, "date": null})];
+        let manifest = ArchiveManifest {
+            format_version: ARCHIVE_FORMAT_VERSION,
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            tool_version: "test".to_string(),
+            cluster: "mainnet".to_string(),
+            anonymized: false,
+            vote_account: None,
+            identity: None,
+            commission_percent: Some(5),
+            tables: vec![ArchiveTable {
+                name: "epoch_rewards".to_string(),
+                columns: vec!["epoch".to_string(), "amount_sol".to_string(), "date".to_string()],
+                rows: 1,
+            }],
+        };
+        let tables = BTreeMap::from([("epoch_rewards".to_string(), rows)]);
+        write(&path, &manifest, None, &tables).unwrap();
+
+        let archive = read(&path).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(archive.manifest.commission_percent, Some(5));
+        assert!(archive.config_toml.is_none());
+        let row = &archive.tables["epoch_rewards"][0];
+        assert_eq!(row["epoch"], 800);
+        assert_eq!(row["amount_sol"], 1.5);
+        assert!(row["date"].is_null());
+    }
+}
@@ -9,8 +9,11 @@
 //! - 10-epoch claim window before expiration
 //! - First available starting epoch 912-913
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde::Deserialize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
 use std::time::Duration;
 use tokio::time::sleep;
 
@@ -142,7 +145,58 @@ pub async fn fetch_bam_claims(config: &Config, start_epoch: u64, end_epoch: u64)
 /// The API returns eligibility data even for unclaimed rewards. We only record
 /// claims where claim_status_address is present (indicating the claim PDA exists).
 async fn fetch_bam_claim_for_epoch(client: &reqwest::Client, config: &Config, epoch: u64) -> Result<Option<BamClaim>> {
-    let url = format!("{}/{}/{}", constants::JITO_BAM_API_BASE, epoch, config.identity);
+    let Some(api_response) = fetch_bam_api_response(client, config, epoch).await? else {
+        return Ok(None);
+    };
+
+    // CRITICAL: Skip unclaimed rewards to prevent double-counting
+    // The API returns eligibility even for unclaimed epochs.
+    // Only record when claim_status_address exists (claim PDA created).
+    // This ensures cash-basis accounting and prevents duplicate entries.
+    if api_response.claim_status_address.is_empty() {
+        return Ok(None);
+    }
+
+    // Convert to BamClaim - claim_status_address is guaranteed non-empty
+    Ok(Some(process_bam_api_response(epoch, api_response, config)))
+}
+
+/// BAM eligibility for one epoch as published by the Jito API, claimed or not
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BamEligibility {
+    /// Amount in jitoSOL lamports the validator can claim for the epoch
+    pub amount_jitosol_lamports: u64,
+    /// Whether the claim status PDA exists (the reward was claimed)
+    pub claimed: bool,
+}
+
+/// Fetch BAM eligibility for a single epoch, including rewards not yet claimed
+///
+/// Returns None when the epoch has no rewards for this identity. Not for income:
+/// use `fetch_bam_claims`, which only returns claimed rewards.
+pub async fn fetch_bam_eligibility(
+    client: &reqwest::Client,
+    config: &Config,
+    epoch: u64,
+) -> Result<Option<BamEligibility>> {
+    Ok(fetch_bam_api_response(client, config, epoch)
+        .await?
+        .map(|data| BamEligibility {
+            amount_jitosol_lamports: data.amount,
+            claimed: !data.claim_status_address.is_empty(),
+        }))
+}
+
+/// Fetch the raw BAM API response for a single epoch with retry logic
+///
+/// Returns None if there are no rewards (amount = 0, empty claimant, or 404).
+async fn fetch_bam_api_response(
+    client: &reqwest::Client,
+    config: &Config,
+    epoch: u64,
+) -> Result<Option<JitoBamApiResponse>> {
+    let identity = config.identity_on(&epoch_to_date(epoch));
+    let url = format!("{}/{}/{}", constants::JITO_BAM_API_BASE, epoch, identity);
 
     // Retry with exponential backoff
     let max_retries = 3;
@@ -172,17 +226,7 @@ async fn fetch_bam_claim_for_epoch(client: &reqwest::Client, config: &Config, ep
                                 return Ok(None);
                             }
 
-                            // CRITICAL: Skip unclaimed rewards to prevent double-counting
-                            // The API returns eligibility even for unclaimed epochs.
-                            // Only record when claim_status_address exists (claim PDA created).
-                            // This ensures cash-basis accounting and prevents duplicate entries.
-                            if api_response.claim_status_address.is_empty() {
-                                return Ok(None);
-                            }
-
-                            // Convert to BamClaim - claim_status_address is guaranteed non-empty
-                            let claim = process_bam_api_response(epoch, api_response, config);
-                            return Ok(Some(claim));
+                            return Ok(Some(api_response));
                         }
                         Err(e) => {
                             // Parse errors are not retryable (schema mismatch won't fix itself)
@@ -244,6 +288,27 @@ fn process_bam_api_response(epoch: u64, data: JitoBamApiResponse, config: &Confi
     }
 }
 
+impl BamClaim {
+    /// Block time of the claim transaction as a unix timestamp, if known
+    pub fn claimed_at_timestamp(&self) -> Option<i64> {
+        self.claimed_at
+            .as_deref()
+            .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.timestamp())
+    }
+}
+
+/// Look up when a claim happened on-chain.
+///
+/// `tx_signature` holds the claim status PDA, which is created by the claim
+/// transaction, so its oldest signature's block time is the claim time.
+pub fn fetch_claim_block_time(client: &RpcClient, claim: &BamClaim) -> Result<Option<i64>> {
+    let claim_status = Pubkey::from_str(&claim.tx_signature)
+        .with_context(|| format!("Invalid claim status address: {}", claim.tx_signature))?;
+    let signatures = client.get_signatures_for_address(&claim_status)?;
+    Ok(signatures.last().and_then(|s| s.block_time))
+}
+
 /// Get total BAM rewards in jitoSOL
 pub fn total_bam_jitosol(claims: &[BamClaim]) -> f64 {
     claims.iter().map(|c| c.amount_jitosol_lamports as f64 / 1e9).sum()
@@ -257,37 +322,11 @@ pub fn total_bam_sol_equivalent(claims: &[BamClaim]) -> f64 {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    /// Create a minimal test config for BAM tests
-    fn test_config() -> Config {
-        use solana_sdk::pubkey::Pubkey;
-        let personal_wallet = Pubkey::new_unique();
-        Config {
-            vote_account: Pubkey::new_unique(),
-            identity: Pubkey::new_unique(),
-            withdraw_authority: Pubkey::new_unique(),
-            personal_wallets: vec![personal_wallet],
-            rpc_url: "https://test.rpc".to_string(),
-            coingecko_api_key: "test".to_string(),
-            dune_api_key: None,
-            commission_percent: 5,
-            first_reward_epoch: 900,
-            sfdp_acceptance_date: None,
-            bootstrap_date: "2025-01-01".to_string(),
-            initial_treasury_lamports: 0,
-            bam_enabled: true,
-            bam_first_epoch: 912,
-            bam_jitosol_rate: 1.0, // Default rate
-            doublezero_enabled: false,
-            doublezero_fee_rate: 0.05,
-            doublezero_first_epoch: 859,
-            doublezero_deposit_account: None,
-        }
-    }
+    use crate::config::tests::test_config;
 
     #[test]
     fn test_process_bam_api_response() {
-        let config = test_config();
+        let config = test_config(None);
         let response = JitoBamApiResponse {
             amount: 1_304_802_961, // ~1.3 jitoSOL
             claimant: "mD1afZhSisoXfJLT8nYwSFANqjr1KPoDUEpYTEfFX1e".to_string(),
@@ -308,7 +347,7 @@ mod tests {
 
     #[test]
     fn test_process_bam_api_response_custom_rate() {
-        let mut config = test_config();
+        let mut config = test_config(None);
         config.bam_jitosol_rate = 1.10; // Realistic rate
 
         let response = JitoBamApiResponse {
@@ -0,0 +1,335 @@
+//! BAM (JIP-31) payout verification
+//!
+//! Claimed BAM rewards show up as income, but an epoch whose reward was never
+//! claimed, expired after the claim window, or was never published at all leaves no
+//! trace in the books. This recomputes the expected payout per epoch from the
+//! JIP-31 parameters in `[bam]` and the validator's active stake, and compares it
+//! with the Jito API's eligibility and the cached claims.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::time::sleep;
+
+use crate::bam::{self, BamEligibility};
+use crate::cache::Cache;
+use crate::config::{BamConfig, Config};
+use crate::{constants, jito};
+
+/// JIP-31 parameters used to compute the expected payout
+#[derive(Debug, Clone, Copy)]
+pub struct BamParams {
+    /// jitoSOL per SOL of active stake per epoch (None: only eligibility vs claims is checked)
+    pub expected_jitosol_per_sol: Option<f64>,
+    pub min_stake_lamports: u64,
+    pub tolerance_pct: f64,
+}
+
+impl BamParams {
+    pub fn from_config(bam: Option<&BamConfig>) -> Self {
+        match bam {
+            Some(bam) => Self {
+                expected_jitosol_per_sol: bam.expected_jitosol_per_sol,
+                min_stake_lamports: (bam.min_stake_sol * 1e9) as u64,
+                tolerance_pct: bam.payout_tolerance_pct,
+            },
+            None => Self {
+                expected_jitosol_per_sol: None,
+                min_stake_lamports: 0,
+                tolerance_pct: 5.0,
+            },
+        }
+    }
+
+    /// Expected payout in jitoSOL lamports (None when the rate or the stake is unknown)
+    pub fn expected_lamports(&self, active_stake: Option<u64>) -> Option<u64> {
+        let rate = self.expected_jitosol_per_sol?;
+        let stake = active_stake?;
+        if stake < self.min_stake_lamports {
+            return Some(0);
+        }
+        Some((stake as f64 * rate).round() as u64)
+    }
+}
+
+/// Outcome of the check for one epoch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum PayoutStatus {
+    /// Claimed, and not short of the expected amount
+    Ok,
+    /// Claimed, but short of the expected amount beyond the tolerance
+    Short,
+    /// Eligible but unclaimed; claim before the window closes
+    Unclaimed { expires_after_epoch: u64 },
+    /// Eligible, never claimed, and the claim window has passed (lost income)
+    Expired,
+    /// A payout was expected but the Jito API has none for this epoch
+    Missing,
+    /// Nothing expected and nothing published
+    NotEligible,
+}
+
+impl PayoutStatus {
+    /// Whether the epoch needs attention
+    pub fn is_flagged(&self) -> bool {
+        !matches!(self, Self::Ok | Self::NotEligible)
+    }
+}
+
+/// Expected vs published vs claimed BAM rewards for one epoch
+#[derive(Debug, Clone, Serialize)]
+pub struct EpochPayoutCheck {
+    pub epoch: u64,
+    pub active_stake_sol: Option<f64>,
+    pub expected_jitosol: Option<f64>,
+    pub eligible_jitosol: Option<f64>,
+    pub claimed_jitosol: Option<f64>,
+    #[serde(flatten)]
+    pub status: PayoutStatus,
+}
+
+/// Classify one epoch
+///
+/// `claimed` is the cached claim amount; the API's claim flag covers claims that
+/// haven't been ingested yet.
+pub fn check_epoch(
+    epoch: u64,
+    expected: Option<u64>,
+    eligibility: Option<BamEligibility>,
+    claimed: Option<u64>,
+    current_epoch: u64,
+    tolerance_pct: f64,
+) -> PayoutStatus {
+    let claimed = claimed.or(eligibility.filter(|e| e.claimed).map(|e| e.amount_jitosol_lamports));
+
+    if let Some(claimed) = claimed {
+        return match expected {
+            Some(expected) if (claimed as f64) < expected as f64 * (1.0 - tolerance_pct / 100.0) => PayoutStatus::Short,
+            _ => PayoutStatus::Ok,
+        };
+    }
+
+    if eligibility.is_some() {
+        let expires_after_epoch = epoch + constants::BAM_CLAIM_WINDOW_EPOCHS;
+        return if current_epoch > expires_after_epoch {
+            PayoutStatus::Expired
+        } else {
+            PayoutStatus::Unclaimed { expires_after_epoch }
+        };
+    }
+
+    match expected {
+        Some(expected) if expected > 0 => PayoutStatus::Missing,
+        _ => PayoutStatus::NotEligible,
+    }
+}
+
+/// Check every completed epoch in `start_epoch..=end_epoch` against the Jito API
+pub async fn verify(
+    cache: &Cache,
+    config: &Config,
+    params: &BamParams,
+    start_epoch: u64,
+    end_epoch: u64,
+    current_epoch: u64,
+) -> Result<Vec<EpochPayoutCheck>> {
+    let start_epoch = start_epoch.max(config.bam_first_epoch).max(constants::BAM_FIRST_EPOCH);
+    let end_epoch = end_epoch.min(current_epoch.saturating_sub(1));
+    if start_epoch > end_epoch {
+        return Ok(Vec::new());
+    }
+
+    let stake = if params.expected_jitosol_per_sol.is_some() {
+        jito::fetch_active_stake(config).await.unwrap_or_else(|e| {
+            eprintln!(
+                "  Warning: Could not fetch active stake, expected payouts unavailable: {}",
+                e
+            );
+            Default::default()
+        })
+    } else {
+        Default::default()
+    };
+    let claimed: HashMap<u64, u64> = cache
+        .get_bam_claims(start_epoch, end_epoch)
+        .await?
+        .into_iter()
+        .map(|c| (c.epoch, c.amount_jitosol_lamports))
+        .collect();
+
+    println!("  Checking BAM eligibility for epochs {}-{}...", start_epoch, end_epoch);
+    let client = reqwest::Client::builder().timeout(Duration::from_secs(10)).build()?;
+    let mut checks = Vec::new();
+    for epoch in start_epoch..=end_epoch {
+        let eligibility = bam::fetch_bam_eligibility(&client, config, epoch)
+            .await
+            .with_context(|| format!("BAM API lookup failed for epoch {}", epoch))?;
+        let active_stake = stake.get(&epoch).copied();
+        let expected = params.expected_lamports(active_stake);
+        let claimed_lamports = claimed.get(&epoch).copied();
+        let status = check_epoch(
+            epoch,
+            expected,
+            eligibility,
+            claimed_lamports,
+            current_epoch,
+            params.tolerance_pct,
+        );
+
+        checks.push(EpochPayoutCheck {
+            epoch,
+            active_stake_sol: active_stake.map(|s| s as f64 / 1e9),
+            expected_jitosol: expected.map(|l| l as f64 / 1e9),
+            eligible_jitosol: eligibility.map(|e| e.amount_jitosol_lamports as f64 / 1e9),
+            claimed_jitosol: claimed_lamports
+                .or(eligibility.filter(|e| e.claimed).map(|e| e.amount_jitosol_lamports))
+                .map(|l| l as f64 / 1e9),
+            status,
+        });
+
+        // Rate limiting between epoch queries
+        sleep(Duration::from_millis(100)).await;
+    }
+
+    Ok(checks)
+}
+
+/// Print the per-epoch check, flagged epochs last
+pub fn print_checks(checks: &[EpochPayoutCheck], params: &BamParams) {
+    println!("\nBAM Payout Check (JIP-31)");
+    println!("=========================\n");
+
+    if checks.is_empty() {
+        println!("No completed BAM epochs in range.");
+        return;
+    }
+    if params.expected_jitosol_per_sol.is_none() {
+        println!("No [bam] expected_jitosol_per_sol set: checking eligibility against claims only.\n");
+    }
+
+    let fmt = |v: Option<f64>| v.map_or_else(|| "-".to_string(), |v| format!("{:.6}", v));
+    println!(
+        "{:>6}  {:>12}  {:>12}  {:>12}  {:>12}  Status",
+        "Epoch", "Stake SOL", "Expected", "Eligible", "Claimed"
+    );
+    for check in checks {
+        println!(
+            "{:>6}  {:>12}  {:>12}  {:>12}  {:>12}  {}",
+            check.epoch,
+            check
+                .active_stake_sol
+                .map_or_else(|| "-".to_string(), |s| format!("{:.0}", s)),
+            fmt(check.expected_jitosol),
+            fmt(check.eligible_jitosol),
+            fmt(check.claimed_jitosol),
+            status_label(&check.status)
+        );
+    }
+
+    let flagged: Vec<&EpochPayoutCheck> = checks.iter().filter(|c| c.status.is_flagged()).collect();
+    if flagged.is_empty() {
+        println!("\nAll expected BAM payouts arrived.");
+        return;
+    }
+
+    println!("\n{} epoch(s) need attention:", flagged.len());
+    for check in flagged {
+        match check.status {
+            PayoutStatus::Unclaimed { expires_after_epoch } => println!(
+                "  Epoch {}: {} jitoSOL unclaimed - claim before epoch {} ends",
+                check.epoch,
+                fmt(check.eligible_jitosol),
+                expires_after_epoch
+            ),
+            PayoutStatus::Expired => println!(
+                "  Epoch {}: {} jitoSOL was never claimed and has expired",
+                check.epoch,
+                fmt(check.eligible_jitosol)
+            ),
+            PayoutStatus::Missing => println!(
+                "  Epoch {}: expected ~{} jitoSOL but the Jito API lists no reward (ask Jito)",
+                check.epoch,
+                fmt(check.expected_jitosol)
+            ),
+            PayoutStatus::Short => println!(
+                "  Epoch {}: claimed {} jitoSOL, expected ~{}",
+                check.epoch,
+                fmt(check.claimed_jitosol),
+                fmt(check.expected_jitosol)
+            ),
+            PayoutStatus::Ok | PayoutStatus::NotEligible => {}
+        }
+    }
+}
+
+fn status_label(status: &PayoutStatus) -> &'static str {
+    match status {
+        PayoutStatus::Ok => "ok",
+        PayoutStatus::Short => "SHORT",
+        PayoutStatus::Unclaimed { .. } => "UNCLAIMED",
+        PayoutStatus::Expired => "EXPIRED",
+        PayoutStatus::Missing => "MISSING",
+        PayoutStatus::NotEligible => "-",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOL: u64 = 1_000_000_000;
+
+    fn eligible(amount: u64, claimed: bool) -> Option<BamEligibility> {
+        Some(BamEligibility {
+            amount_jitosol_lamports: amount,
+            claimed,
+        })
+    }
+
+    #[test]
+    fn expected_payout_from_stake() {
+        let params = BamParams {
+            expected_jitosol_per_sol: Some(0.0001),
+            min_stake_lamports: 10_000 * SOL,
+            tolerance_pct: 5.0,
+        };
+        assert_eq!(params.expected_lamports(Some(50_000 * SOL)), Some(5 * SOL));
+        assert_eq!(params.expected_lamports(Some(5_000 * SOL)), Some(0));
+        assert_eq!(params.expected_lamports(None), None);
+    }
+
+    #[test]
+    fn classifies_claims_and_gaps() {
+        // Claimed (cache or API flag), within tolerance
+        assert_eq!(check_epoch(920, Some(SOL), None, Some(SOL), 950, 5.0), PayoutStatus::Ok);
+        assert_eq!(
+            check_epoch(920, None, eligible(SOL, true), None, 950, 5.0),
+            PayoutStatus::Ok
+        );
+        // Claimed 10% short with 5% tolerance
+        assert_eq!(
+            check_epoch(920, Some(SOL), eligible(SOL * 9 / 10, true), None, 950, 5.0),
+            PayoutStatus::Short
+        );
+        // Eligible, unclaimed, window still open vs passed
+        assert_eq!(
+            check_epoch(940, None, eligible(SOL, false), None, 945, 5.0),
+            PayoutStatus::Unclaimed {
+                expires_after_epoch: 950
+            }
+        );
+        assert_eq!(
+            check_epoch(930, None, eligible(SOL, false), None, 945, 5.0),
+            PayoutStatus::Expired
+        );
+        // Expected but never published
+        assert_eq!(check_epoch(920, Some(SOL), None, None, 950, 5.0), PayoutStatus::Missing);
+        assert_eq!(
+            check_epoch(920, Some(0), None, None, 950, 5.0),
+            PayoutStatus::NotEligible
+        );
+        assert_eq!(check_epoch(920, None, None, None, 950, 5.0), PayoutStatus::NotEligible);
+    }
+}
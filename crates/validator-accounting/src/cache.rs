@@ -5,29 +5,50 @@
 //! Expenses are stored persistently for financial tracking.
 
 use anyhow::{Context, Result};
+use serde::Serialize;
 use sqlx::{FromRow, SqlitePool};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::Path;
 
 use crate::addresses::AddressCategory;
 use crate::bam::BamClaim;
+use crate::card::StatementLine;
+use crate::client_metrics::MetricSample;
+use crate::client_uplift::NetworkFeeMedian;
 use crate::config::Config;
 use crate::constants;
+use crate::delegators::DelegatorStake;
 use crate::doublezero::DoubleZeroFee;
-use crate::expenses::{Expense, ExpenseCategory, RecurringExpense};
+use crate::exchange::ExchangeDeposit;
+use crate::expenses::{Expense, ExpenseCategory, ExpenseStatus, RecurringExpense};
+use crate::failed_fees::{FailedTxFee, ScanCursor, ScanResume, ScanState};
+use crate::fiat::FiatDeposit;
+use crate::fingerprint::{Fingerprint, StoredFingerprint};
+use crate::history_import::ImportedEpoch;
+use crate::income_lots::IncomeLot;
 use crate::jito::MevClaim;
 use crate::leader_fees::EpochLeaderFees;
+use crate::ops_fees::OpsFee;
+use crate::owner_draw::WithdrawalSplit;
 use crate::positions::{StakeAccountInfo, ValidatorPosition};
-use crate::prices::PriceCache;
-use crate::transactions::{EpochReward, SolTransfer, StakeEpochReward, TokenFlow};
+use crate::price_audit::AuditedPrice;
+use crate::prices::{CachedPrice, HourlyPriceCache, PriceCache, PriceProvider, PriceQuotes, PriceSource, get_price};
+use crate::timezone;
+use crate::transactions::{EpochReward, SolTransfer, StakeEpochReward, TokenFlow, epoch_to_date};
+use crate::unit_economics::EpochPerformance;
 use crate::vote_costs::EpochVoteCost;
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 
 /// Cache database wrapper
+#[derive(Clone)]
 pub struct Cache {
     pool: SqlitePool,
 }
 
+/// Metadata key recording the timezone cached dates were attributed in
+const ACCOUNTING_TIMEZONE_KEY: &str = "accounting_timezone";
+
 /// Row type for epoch rewards query
 #[derive(FromRow)]
 struct EpochRewardRow {
@@ -55,12 +76,58 @@ struct LeaderFeesRow {
 #[derive(FromRow)]
 struct MevClaimRow {
     epoch: i64,
+    provider: String,
     total_tips_lamports: i64,
     commission_lamports: i64,
     amount_sol: f64,
     date: Option<String>,
 }
 
+/// Row type for network fee medians query
+#[derive(FromRow)]
+struct NetworkFeeMedianRow {
+    epoch: i64,
+    median_block_fees_lamports: i64,
+    blocks_sampled: i64,
+    date: Option<String>,
+}
+
+/// Row type for aggregator imports
+#[derive(FromRow)]
+struct RewardImportRow {
+    epoch: i64,
+    source: String,
+    commission_lamports: Option<i64>,
+    activated_stake_lamports: Option<i64>,
+    imported_at: String,
+    verified_at: Option<String>,
+    onchain_lamports: Option<i64>,
+}
+
+/// Row type for client metric samples
+#[derive(FromRow)]
+struct MetricSampleRow {
+    scraped_at: i64,
+    slot: i64,
+    epoch: i64,
+    produced: Option<i64>,
+    skipped: Option<i64>,
+    vote_latency_slots: Option<f64>,
+}
+
+impl From<MetricSampleRow> for MetricSample {
+    fn from(r: MetricSampleRow) -> Self {
+        Self {
+            scraped_at: r.scraped_at,
+            slot: r.slot as u64,
+            epoch: r.epoch as u64,
+            produced: r.produced.map(|v| v as u64),
+            skipped: r.skipped.map(|v| v as u64),
+            vote_latency_slots: r.vote_latency_slots,
+        }
+    }
+}
+
 /// Row type for vote costs query
 #[derive(FromRow)]
 struct VoteCostRow {
@@ -109,6 +176,8 @@ struct ExpenseRow {
     amount_usd: f64,
     paid_with: String,
     invoice_id: Option<String>,
+    entity: Option<String>,
+    status: String,
 }
 
 /// Row type for recurring expenses query
@@ -122,6 +191,47 @@ struct RecurringExpenseRow {
     paid_with: String,
     start_date: String,
     end_date: Option<String>,
+    entity: Option<String>,
+}
+
+/// Row type for ingestion_runs query
+#[derive(FromRow)]
+struct IngestionRunRow {
+    id: i64,
+    triggered_by: String,
+    started_at: String,
+    finished_at: Option<String>,
+    status: String,
+    row_counts: String,
+    errors: String,
+}
+
+/// Row type for source_health query
+#[derive(FromRow)]
+struct SourceHealthRow {
+    name: String,
+    last_success_at: Option<String>,
+    last_error_at: Option<String>,
+    last_error: Option<String>,
+    consecutive_failures: i64,
+    last_rows: i64,
+}
+
+/// Row type for balance_history query
+#[derive(FromRow)]
+struct BalanceSnapshotRow {
+    date: String,
+    epoch: i64,
+    snapshot_slot: i64,
+    vote_account_lamports: i64,
+    identity_lamports: i64,
+    withdraw_authority_lamports: i64,
+    squads_vault_lamports: i64,
+    business_wallets_lamports: i64,
+    stake_liquid_lamports: i64,
+    stake_locked_lamports: i64,
+    jitosol_lamports: i64,
+    total_lamports: i64,
 }
 
 /// Row type for sol_transfers query
@@ -139,6 +249,24 @@ struct SolTransferRow {
     to_label: String,
     from_category: String,
     to_category: String,
+    memo: Option<String>,
+    program: Option<String>,
+}
+
+/// Row type for income_lots query
+#[derive(FromRow)]
+struct IncomeLotRow {
+    source: String,
+    reference: String,
+    epoch: Option<i64>,
+    date: String,
+    timestamp: Option<i64>,
+    asset: String,
+    amount: f64,
+    amount_sol: f64,
+    price_usd: f64,
+    price_source: String,
+    fmv_usd: f64,
 }
 
 impl Cache {
@@ -189,6 +317,24 @@ impl Cache {
         .execute(&self.pool)
         .await?;
 
+        sqlx::query(
+            "
+            -- Historical rewards/stake imported from aggregators (`import fetch`)
+            -- verified_at/onchain_lamports are set once the on-chain backfill refetches the epoch
+            CREATE TABLE IF NOT EXISTS reward_imports (
+                epoch INTEGER PRIMARY KEY,
+                source TEXT NOT NULL,
+                commission_lamports INTEGER,
+                activated_stake_lamports INTEGER,
+                imported_at TEXT NOT NULL DEFAULT (datetime('now')),
+                verified_at TEXT,
+                onchain_lamports INTEGER
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await?;
+
         sqlx::query(
             "
             -- Stake-account inflation rewards per epoch (self-stake)
@@ -232,6 +378,19 @@ impl Cache {
             .execute(&self.pool)
             .await?;
 
+        sqlx::query(
+            "
+            -- Assigned leader slots per epoch (from getLeaderSchedule, for completeness checks)
+            CREATE TABLE IF NOT EXISTS leader_schedule (
+                epoch INTEGER PRIMARY KEY,
+                slot_count INTEGER NOT NULL,
+                fetched_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await?;
+
         sqlx::query(
             "
             -- Leader slot fees per epoch
@@ -252,13 +411,31 @@ impl Cache {
 
         sqlx::query(
             "
-            -- Jito MEV claims per epoch
+            -- MEV claims per epoch and provider (Jito API or a scanned tip-distribution program)
             CREATE TABLE IF NOT EXISTS mev_claims (
-                epoch INTEGER PRIMARY KEY,
+                epoch INTEGER NOT NULL,
+                provider TEXT NOT NULL DEFAULT 'jito',
                 total_tips_lamports INTEGER NOT NULL,
                 commission_lamports INTEGER NOT NULL,
                 amount_sol REAL NOT NULL,
                 date TEXT,
+                fetched_at TEXT NOT NULL DEFAULT (datetime('now')),
+                PRIMARY KEY (epoch, provider)
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await?;
+        self.maybe_migrate_mev_claims().await?;
+
+        sqlx::query(
+            "
+            -- Network-wide median leader fee per block (vanilla-client baseline, from Dune)
+            CREATE TABLE IF NOT EXISTS network_fee_medians (
+                epoch INTEGER PRIMARY KEY,
+                median_block_fees_lamports INTEGER NOT NULL,
+                blocks_sampled INTEGER NOT NULL,
+                date TEXT,
                 fetched_at TEXT NOT NULL DEFAULT (datetime('now'))
             )
             ",
@@ -266,6 +443,22 @@ impl Cache {
         .execute(&self.pool)
         .await?;
 
+        sqlx::query(
+            "
+            -- Scrapes of the local validator client's metrics endpoint (`metrics scrape`)
+            CREATE TABLE IF NOT EXISTS client_metric_samples (
+                scraped_at INTEGER NOT NULL,
+                slot INTEGER PRIMARY KEY,
+                epoch INTEGER NOT NULL,
+                produced INTEGER,
+                skipped INTEGER,
+                vote_latency_slots REAL
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await?;
+
         sqlx::query(
             "
             -- Jito BAM claims (jitoSOL rewards per JIP-31)
@@ -290,6 +483,36 @@ impl Cache {
             .execute(&self.pool)
             .await?;
 
+        sqlx::query(
+            "
+            -- Vote credits earned and delegated stake per epoch (unit economics)
+            CREATE TABLE IF NOT EXISTS epoch_performance (
+                epoch INTEGER PRIMARY KEY,
+                vote_credits INTEGER,
+                active_stake_lamports INTEGER,
+                fetched_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            "
+            -- Effective stake per delegator (withdraw authority), one snapshot per epoch
+            CREATE TABLE IF NOT EXISTS delegator_stake (
+                epoch INTEGER NOT NULL,
+                delegator TEXT NOT NULL,
+                stake_lamports INTEGER NOT NULL,
+                accounts INTEGER NOT NULL,
+                fetched_at TEXT NOT NULL DEFAULT (datetime('now')),
+                PRIMARY KEY (epoch, delegator)
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await?;
+
         sqlx::query(
             "
             -- Vote transaction costs per epoch
@@ -332,6 +555,35 @@ impl Cache {
             CREATE TABLE IF NOT EXISTS prices (
                 date TEXT PRIMARY KEY,
                 usd_price REAL NOT NULL,
+                fetched_at TEXT NOT NULL DEFAULT (datetime('now')),
+                source TEXT NOT NULL DEFAULT 'exact'
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            "
+            -- Provider and response timestamp of each cached daily price (`prices verify`)
+            CREATE TABLE IF NOT EXISTS price_audit (
+                date TEXT PRIMARY KEY,
+                usd_price REAL NOT NULL,
+                provider TEXT NOT NULL,
+                source_timestamp INTEGER,
+                fetched_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            "
+            -- Hourly SOL prices around large events (keyed by unix timestamp of the UTC hour)
+            CREATE TABLE IF NOT EXISTS hourly_prices (
+                hour_ts INTEGER PRIMARY KEY,
+                usd_price REAL NOT NULL,
                 fetched_at TEXT NOT NULL DEFAULT (datetime('now'))
             )
             ",
@@ -389,6 +641,11 @@ impl Cache {
         .execute(&self.pool)
         .await?;
 
+        self.maybe_migrate_expense_entities().await?;
+        self.maybe_migrate_expense_status().await?;
+        self.maybe_migrate_price_source().await?;
+        self.maybe_migrate_scan_resume().await?;
+
         // SOL transfers table:
         // We store each distinct SOL movement once, keyed by (signature, from, to, amount).
         // This avoids silently dropping multi-transfer transactions and avoids double-counting
@@ -414,6 +671,8 @@ impl Cache {
                 from_category TEXT NOT NULL,
                 to_category TEXT NOT NULL,
                 fetched_at TEXT NOT NULL DEFAULT (datetime('now')),
+                memo TEXT,
+                program TEXT,
                 PRIMARY KEY (signature, from_address, to_address, amount_lamports)
             )
             ",
@@ -421,6 +680,19 @@ impl Cache {
         .execute(&self.pool)
         .await?;
 
+        // Memo and invoking program were added later; older caches get them as NULL columns.
+        let columns: Vec<(String,)> = sqlx::query_as("SELECT name FROM pragma_table_info('sol_transfers')")
+            .fetch_all(&self.pool)
+            .await?;
+        if !columns.iter().any(|(name,)| name == "memo") {
+            sqlx::query("ALTER TABLE sol_transfers ADD COLUMN memo TEXT")
+                .execute(&self.pool)
+                .await?;
+            sqlx::query("ALTER TABLE sol_transfers ADD COLUMN program TEXT")
+                .execute(&self.pool)
+                .await?;
+        }
+
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_transfers_slot ON sol_transfers(slot)")
             .execute(&self.pool)
             .await?;
@@ -473,9 +745,12 @@ impl Cache {
                 date TEXT NOT NULL,
                 epoch INTEGER NOT NULL,
                 snapshot_slot INTEGER NOT NULL,
+                block_time INTEGER,
                 vote_account_lamports INTEGER NOT NULL,
                 identity_lamports INTEGER NOT NULL,
                 withdraw_authority_lamports INTEGER NOT NULL,
+                squads_vault_lamports INTEGER NOT NULL DEFAULT 0,
+                business_wallets_lamports INTEGER NOT NULL DEFAULT 0,
                 token_accounts_lamports INTEGER NOT NULL DEFAULT 0,
                 token_accounts_withdrawable_lamports INTEGER NOT NULL DEFAULT 0,
                 stake_liquid_lamports INTEGER NOT NULL DEFAULT 0,
@@ -497,366 +772,576 @@ impl Cache {
 
         self.maybe_migrate_balance_history().await?;
 
-        // Index for withdrawal tracking
+        // =====================================================================
+        // Ingestion History
+        // =====================================================================
+
         sqlx::query(
-            "CREATE INDEX IF NOT EXISTS idx_transfers_withdrawal
-             ON sol_transfers(to_category)
-             WHERE to_category IN ('Exchange', 'PersonalWallet')",
+            "
+            -- One row per ingestion attempt (CLI report run, bp-web scheduler refresh)
+            -- row_counts is a JSON object of source -> rows loaded, errors a JSON array
+            CREATE TABLE IF NOT EXISTS ingestion_runs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                triggered_by TEXT NOT NULL,
+                started_at TEXT NOT NULL DEFAULT (datetime('now')),
+                finished_at TEXT,
+                status TEXT NOT NULL DEFAULT 'running',
+                row_counts TEXT NOT NULL DEFAULT '{}',
+                errors TEXT NOT NULL DEFAULT '[]'
+            )
+            ",
         )
         .execute(&self.pool)
-        .await
-        .ok(); // Ignore error if partial index not supported
-
-        Ok(())
-    }
-
-    async fn maybe_migrate_balance_history(&self) -> Result<()> {
-        let table_exists: Option<(String,)> =
-            sqlx::query_as("SELECT name FROM sqlite_master WHERE type='table' AND name='balance_history'")
-                .fetch_optional(&self.pool)
-                .await?;
-        if table_exists.is_none() {
-            return Ok(());
-        }
-
-        let columns: Vec<(String,)> = sqlx::query_as("SELECT name FROM pragma_table_info('balance_history')")
-            .fetch_all(&self.pool)
-            .await?;
+        .await?;
 
-        let has_token_accounts = columns.iter().any(|(name,)| name == "token_accounts_lamports");
-        if !has_token_accounts {
-            eprintln!("Migrating balance_history schema (adding token account columns)...");
-            // SQLite allows ADD COLUMN with DEFAULT. Existing rows will read as the default value.
-            sqlx::query("ALTER TABLE balance_history ADD COLUMN token_accounts_lamports INTEGER NOT NULL DEFAULT 0")
-                .execute(&self.pool)
-                .await?;
-            sqlx::query(
-                "ALTER TABLE balance_history ADD COLUMN token_accounts_withdrawable_lamports INTEGER NOT NULL DEFAULT 0",
-            )
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_ingestion_runs_started ON ingestion_runs(started_at)")
             .execute(&self.pool)
             .await?;
-        }
-
-        Ok(())
-    }
-
-    async fn maybe_migrate_sol_transfers(&self) -> Result<()> {
-        // Check if table exists and whether it has the legacy `account_key` column.
-        let table_exists: Option<(String,)> =
-            sqlx::query_as("SELECT name FROM sqlite_master WHERE type='table' AND name='sol_transfers'")
-                .fetch_optional(&self.pool)
-                .await?;
-        if table_exists.is_none() {
-            return Ok(());
-        }
-
-        let columns: Vec<(String,)> = sqlx::query_as("SELECT name FROM pragma_table_info('sol_transfers')")
-            .fetch_all(&self.pool)
-            .await?;
-        let has_account_key = columns.iter().any(|(name,)| name == "account_key");
-        if !has_account_key {
-            return Ok(());
-        }
-
-        eprintln!("Migrating legacy sol_transfers schema (dropping account_key, improving dedupe)...");
-
-        let mut tx = self.pool.begin().await?;
-
-        sqlx::query("DROP TABLE IF EXISTS sol_transfers_new")
-            .execute(&mut *tx)
-            .await?;
 
         sqlx::query(
             "
-            CREATE TABLE IF NOT EXISTS sol_transfers_new (
-                signature TEXT NOT NULL,
-                slot INTEGER NOT NULL,
-                timestamp INTEGER,
-                date TEXT,
-                from_address TEXT NOT NULL,
-                to_address TEXT NOT NULL,
-                amount_lamports INTEGER NOT NULL,
-                amount_sol REAL NOT NULL,
-                from_label TEXT NOT NULL,
-                to_label TEXT NOT NULL,
-                from_category TEXT NOT NULL,
-                to_category TEXT NOT NULL,
-                fetched_at TEXT NOT NULL DEFAULT (datetime('now')),
-                PRIMARY KEY (signature, from_address, to_address, amount_lamports)
+            -- Health of each registered data source (see sources.rs), updated on every sync
+            CREATE TABLE IF NOT EXISTS source_health (
+                name TEXT PRIMARY KEY,
+                last_success_at TEXT,
+                last_error_at TEXT,
+                last_error TEXT,
+                consecutive_failures INTEGER NOT NULL DEFAULT 0,
+                last_rows INTEGER NOT NULL DEFAULT 0
             )
             ",
         )
-        .execute(&mut *tx)
+        .execute(&self.pool)
         .await?;
 
-        // Insert one row per distinct transfer key, choosing the max slot/timestamp/date for that key.
         sqlx::query(
             "
-            INSERT OR IGNORE INTO sol_transfers_new
-                (signature, slot, timestamp, date, from_address, to_address,
-                 amount_lamports, amount_sol, from_label, to_label,
-                 from_category, to_category, fetched_at)
-            SELECT
-                signature,
-                MAX(slot) as slot,
-                MAX(timestamp) as timestamp,
-                MAX(date) as date,
-                from_address,
-                to_address,
-                amount_lamports,
-                MAX(amount_sol) as amount_sol,
-                MAX(from_label) as from_label,
-                MAX(to_label) as to_label,
-                MAX(from_category) as from_category,
-                MAX(to_category) as to_category,
-                MIN(fetched_at) as fetched_at
-            FROM sol_transfers
-            GROUP BY signature, from_address, to_address, amount_lamports
+            -- Cooperative lock so only one full sync (CLI or bp-web scheduler) writes at a time
+            -- Single row; a lock past expires_at is stale and may be taken over
+            CREATE TABLE IF NOT EXISTS ingestion_lock (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                holder TEXT NOT NULL,
+                acquired_at TEXT NOT NULL,
+                expires_at TEXT NOT NULL
+            )
             ",
         )
-        .execute(&mut *tx)
+        .execute(&self.pool)
         .await?;
 
-        sqlx::query("DROP TABLE sol_transfers").execute(&mut *tx).await?;
-        sqlx::query("ALTER TABLE sol_transfers_new RENAME TO sol_transfers")
-            .execute(&mut *tx)
-            .await?;
-
-        sqlx::query("DROP INDEX IF EXISTS idx_transfers_account")
-            .execute(&mut *tx)
-            .await
-            .ok();
-        sqlx::query("CREATE INDEX IF NOT EXISTS idx_transfers_slot ON sol_transfers(slot)")
-            .execute(&mut *tx)
-            .await?;
         sqlx::query(
-            "CREATE INDEX IF NOT EXISTS idx_transfers_withdrawal
-             ON sol_transfers(to_category)
-             WHERE to_category IN ('Exchange', 'PersonalWallet')",
+            "
+            -- Operator address tags (e.g. an exchange deposit address); override built-in labels
+            CREATE TABLE IF NOT EXISTS address_tags (
+                address TEXT PRIMARY KEY,
+                label TEXT NOT NULL,
+                category TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )
+            ",
         )
-        .execute(&mut *tx)
-        .await
-        .ok();
-
-        tx.commit().await?;
-        Ok(())
-    }
-
-    // =========================================================================
-    // Epoch Rewards (Commission)
-    // =========================================================================
+        .execute(&self.pool)
+        .await?;
 
-    /// Get cached epoch rewards
-    pub async fn get_epoch_rewards(&self, start_epoch: u64, end_epoch: u64) -> Result<Vec<EpochReward>> {
-        let rows: Vec<EpochRewardRow> = sqlx::query_as(
-            "SELECT epoch, amount_lamports, amount_sol, commission, effective_slot, date
-             FROM epoch_rewards
-             WHERE epoch >= ? AND epoch <= ?
-             ORDER BY epoch",
+        sqlx::query(
+            "
+            -- Owner draw vs business expense splits of mixed-use withdrawals (`split set`)
+            CREATE TABLE IF NOT EXISTS withdrawal_splits (
+                signature TEXT PRIMARY KEY,
+                business_pct REAL NOT NULL,
+                category TEXT NOT NULL,
+                description TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )
+            ",
         )
-        .bind(start_epoch as i64)
-        .bind(end_epoch as i64)
-        .fetch_all(&self.pool)
+        .execute(&self.pool)
         .await?;
 
-        Ok(rows
-            .into_iter()
-            .map(|r| EpochReward {
-                epoch: r.epoch as u64,
-                amount_lamports: r.amount_lamports as u64,
-                amount_sol: r.amount_sol,
-                commission: r.commission as u8,
-                effective_slot: r.effective_slot as u64,
-                date: r.date,
-            })
-            .collect())
-    }
+        sqlx::query(
+            "
+            -- Deposits into (negative: transfers out of) the off-chain USD cash account (`fiat deposit`)
+            CREATE TABLE IF NOT EXISTS fiat_deposits (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                date TEXT NOT NULL,
+                amount_usd REAL NOT NULL,
+                memo TEXT NOT NULL DEFAULT '',
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await?;
 
-    /// Get epochs that are missing from cache
-    pub async fn get_missing_reward_epochs(&self, start_epoch: u64, end_epoch: u64) -> Result<Vec<u64>> {
-        let rows: Vec<(i64,)> = sqlx::query_as("SELECT epoch FROM epoch_rewards WHERE epoch >= ? AND epoch <= ?")
-            .bind(start_epoch as i64)
-            .bind(end_epoch as i64)
-            .fetch_all(&self.pool)
-            .await?;
+        sqlx::query(
+            "
+            -- SOL deposits from imported exchange history exports (`exchange import`)
+            CREATE TABLE IF NOT EXISTS exchange_deposits (
+                exchange TEXT NOT NULL,
+                reference_id TEXT NOT NULL,
+                timestamp INTEGER NOT NULL,
+                amount_sol REAL NOT NULL,
+                imported_at TEXT NOT NULL DEFAULT (datetime('now')),
+                PRIMARY KEY (exchange, reference_id)
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await?;
 
-        let cached: Vec<u64> = rows.into_iter().map(|(e,)| e as u64).collect();
+        sqlx::query(
+            "
+            -- Charges from imported credit card statements (`card import`)
+            CREATE TABLE IF NOT EXISTS card_statement_lines (
+                reference TEXT PRIMARY KEY,
+                date TEXT NOT NULL,
+                description TEXT NOT NULL,
+                amount_usd REAL NOT NULL,
+                imported_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await?;
 
-        let missing: Vec<u64> = (start_epoch..=end_epoch).filter(|e| !cached.contains(e)).collect();
+        sqlx::query(
+            "
+            -- Withdrawals confirmed received by an exchange (`exchange match`), rebuilt on each match
+            CREATE TABLE IF NOT EXISTS withdrawal_receipts (
+                signature TEXT PRIMARY KEY,
+                exchange TEXT NOT NULL,
+                reference_id TEXT NOT NULL,
+                credited_at INTEGER NOT NULL,
+                matched_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await?;
 
-        Ok(missing)
-    }
+        sqlx::query(
+            "
+            -- Fees our signers paid on failed non-vote transactions (`failed_tx_fees` source)
+            CREATE TABLE IF NOT EXISTS failed_tx_fees (
+                signature TEXT PRIMARY KEY,
+                account TEXT NOT NULL,
+                slot INTEGER NOT NULL,
+                block_time INTEGER,
+                date TEXT,
+                fee_lamports INTEGER NOT NULL,
+                error TEXT NOT NULL
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await?;
 
-    /// Store epoch rewards (in a transaction for atomicity)
-    pub async fn store_epoch_rewards(&self, rewards: &[EpochReward]) -> Result<()> {
-        if rewards.is_empty() {
-            return Ok(());
-        }
+        sqlx::query(
+            "
+            -- Newest signature each signer's failed-fee scan has covered, and where a
+            -- pass cut short by the signature limit resumes
+            CREATE TABLE IF NOT EXISTS failed_tx_scan_cursors (
+                account TEXT PRIMARY KEY,
+                signature TEXT NOT NULL,
+                slot INTEGER NOT NULL,
+                resume_top_signature TEXT,
+                resume_top_slot INTEGER,
+                resume_before_signature TEXT,
+                resume_before_slot INTEGER,
+                updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await?;
 
-        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "
+            -- Fees our signers paid on successful non-vote transactions (`ops_fees` source)
+            CREATE TABLE IF NOT EXISTS ops_fees (
+                signature TEXT PRIMARY KEY,
+                account TEXT NOT NULL,
+                slot INTEGER NOT NULL,
+                block_time INTEGER,
+                date TEXT,
+                fee_lamports INTEGER NOT NULL,
+                priority_fee_lamports INTEGER NOT NULL,
+                program TEXT
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await?;
 
-        for reward in rewards {
-            sqlx::query(
-                "INSERT OR REPLACE INTO epoch_rewards
-                 (epoch, amount_lamports, amount_sol, commission, effective_slot, date)
-                 VALUES (?, ?, ?, ?, ?, ?)",
+        sqlx::query(
+            "
+            -- Newest signature each signer's ops-fee scan has covered, and where a pass
+            -- cut short by the signature limit resumes
+            CREATE TABLE IF NOT EXISTS ops_fee_scan_cursors (
+                account TEXT PRIMARY KEY,
+                signature TEXT NOT NULL,
+                slot INTEGER NOT NULL,
+                resume_top_signature TEXT,
+                resume_top_slot INTEGER,
+                resume_before_signature TEXT,
+                resume_before_slot INTEGER,
+                updated_at TEXT NOT NULL DEFAULT (datetime('now'))
             )
-            .bind(reward.epoch as i64)
-            .bind(reward.amount_lamports as i64)
-            .bind(reward.amount_sol)
-            .bind(reward.commission as i64)
-            .bind(reward.effective_slot as i64)
-            .bind(&reward.date)
-            .execute(&mut *tx)
-            .await?;
-        }
+            ",
+        )
+        .execute(&self.pool)
+        .await?;
 
-        tx.commit().await?;
-        Ok(())
-    }
+        sqlx::query(
+            "
+            -- Merkle roots of generated reports (`verify-report`); a file regenerated with the
+            -- same content keeps its first timestamp
+            CREATE TABLE IF NOT EXISTS report_fingerprints (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                file_name TEXT NOT NULL,
+                root_hash TEXT NOT NULL,
+                leaf_count INTEGER NOT NULL,
+                generated_at TEXT NOT NULL DEFAULT (datetime('now')),
+                UNIQUE (file_name, root_hash)
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await?;
 
-    // =========================================================================
-    // Leader Fees
-    // =========================================================================
+        sqlx::query(
+            "
+            -- Reviewed likely-duplicate pairs (`dedupe dismiss` / `dedupe drop`), skipped by later scans
+            CREATE TABLE IF NOT EXISTS duplicate_resolutions (
+                pair_id TEXT PRIMARY KEY,
+                key_a TEXT NOT NULL,
+                key_b TEXT NOT NULL,
+                resolution TEXT NOT NULL,
+                resolved_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await?;
 
-    /// Get cached leader fees
-    pub async fn get_leader_fees(&self, start_epoch: u64, end_epoch: u64) -> Result<Vec<EpochLeaderFees>> {
-        let rows: Vec<LeaderFeesRow> = sqlx::query_as(
-            "SELECT epoch, leader_slots, blocks_produced, skipped_slots,
-                    total_fees_lamports, total_fees_sol, date
-             FROM leader_fees
-             WHERE epoch >= ? AND epoch <= ?
-             ORDER BY epoch",
+        sqlx::query(
+            "
+            -- Income receipts at FMV on receipt (`[reporting] income_lots`), one per reward/MEV/BAM event
+            CREATE TABLE IF NOT EXISTS income_lots (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                source TEXT NOT NULL,
+                reference TEXT NOT NULL,
+                epoch INTEGER,
+                date TEXT NOT NULL,
+                timestamp INTEGER,
+                asset TEXT NOT NULL,
+                amount REAL NOT NULL,
+                amount_sol REAL NOT NULL,
+                price_usd REAL NOT NULL,
+                price_source TEXT NOT NULL,
+                fmv_usd REAL NOT NULL,
+                recorded_at TEXT NOT NULL DEFAULT (datetime('now')),
+                UNIQUE(source, reference)
+            )
+            ",
         )
-        .bind(start_epoch as i64)
-        .bind(end_epoch as i64)
-        .fetch_all(&self.pool)
+        .execute(&self.pool)
         .await?;
 
-        Ok(rows
-            .into_iter()
-            .map(|r| EpochLeaderFees {
-                epoch: r.epoch as u64,
-                leader_slots: r.leader_slots as u64,
-                blocks_produced: r.blocks_produced as u64,
-                skipped_slots: r.skipped_slots as u64,
-                total_fees_lamports: r.total_fees_lamports as u64,
-                total_fees_sol: r.total_fees_sol,
-                date: r.date,
-            })
-            .collect())
+        // Index for withdrawal tracking
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_transfers_withdrawal
+             ON sol_transfers(to_category)
+             WHERE to_category IN ('Exchange', 'PersonalWallet')",
+        )
+        .execute(&self.pool)
+        .await
+        .ok(); // Ignore error if partial index not supported
+
+        Ok(())
     }
 
-    /// Get epochs missing leader fee data
-    pub async fn get_missing_leader_fee_epochs(&self, start_epoch: u64, end_epoch: u64) -> Result<Vec<u64>> {
-        let rows: Vec<(i64,)> = sqlx::query_as("SELECT epoch FROM leader_fees WHERE epoch >= ? AND epoch <= ?")
-            .bind(start_epoch as i64)
-            .bind(end_epoch as i64)
+    /// Add the paying entity column (`[[entities]]` id, NULL = primary entity) to expense tables
+    async fn maybe_migrate_expense_entities(&self) -> Result<()> {
+        for table in ["expenses", "recurring_expenses"] {
+            let columns: Vec<(String,)> = sqlx::query_as(&format!("SELECT name FROM pragma_table_info('{}')", table))
+                .fetch_all(&self.pool)
+                .await?;
+            if !columns.iter().any(|(name,)| name == "entity") {
+                sqlx::query(&format!("ALTER TABLE {} ADD COLUMN entity TEXT", table))
+                    .execute(&self.pool)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Review status on expenses; rows that predate it were entered by hand, so approved
+    async fn maybe_migrate_expense_status(&self) -> Result<()> {
+        let columns: Vec<(String,)> = sqlx::query_as("SELECT name FROM pragma_table_info('expenses')")
             .fetch_all(&self.pool)
             .await?;
-
-        let cached: Vec<u64> = rows.into_iter().map(|(e,)| e as u64).collect();
-
-        let missing: Vec<u64> = (start_epoch..=end_epoch).filter(|e| !cached.contains(e)).collect();
-
-        Ok(missing)
+        if !columns.iter().any(|(name,)| name == "status") {
+            sqlx::query("ALTER TABLE expenses ADD COLUMN status TEXT NOT NULL DEFAULT 'approved'")
+                .execute(&self.pool)
+                .await?;
+        }
+        Ok(())
     }
 
-    /// Store leader fees (in a transaction for atomicity)
-    pub async fn store_leader_fees(&self, fees: &[EpochLeaderFees]) -> Result<()> {
-        if fees.is_empty() {
+    /// Record how each daily price was obtained. Older versions persisted
+    /// `FALLBACK_SOL_PRICE` when every source failed, without provenance; rows holding that
+    /// value with no `price_audit` entry are those placeholders.
+    async fn maybe_migrate_price_source(&self) -> Result<()> {
+        let columns: Vec<(String,)> = sqlx::query_as("SELECT name FROM pragma_table_info('prices')")
+            .fetch_all(&self.pool)
+            .await?;
+        if columns.iter().any(|(name,)| name == "source") {
             return Ok(());
         }
 
         let mut tx = self.pool.begin().await?;
+        sqlx::query("ALTER TABLE prices ADD COLUMN source TEXT NOT NULL DEFAULT 'exact'")
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            "UPDATE prices SET source = 'fallback'
+             WHERE usd_price = ? AND date NOT IN (SELECT date FROM price_audit)",
+        )
+        .bind(constants::FALLBACK_SOL_PRICE)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
 
-        for fee in fees {
+    /// Resume points for signature scans cut short by the signature limit
+    async fn maybe_migrate_scan_resume(&self) -> Result<()> {
+        for table in ["failed_tx_scan_cursors", "ops_fee_scan_cursors"] {
+            let columns: Vec<(String,)> = sqlx::query_as(&format!("SELECT name FROM pragma_table_info('{}')", table))
+                .fetch_all(&self.pool)
+                .await?;
+            for (column, kind) in [
+                ("resume_top_signature", "TEXT"),
+                ("resume_top_slot", "INTEGER"),
+                ("resume_before_signature", "TEXT"),
+                ("resume_before_slot", "INTEGER"),
+            ] {
+                if !columns.iter().any(|(name,)| name == column) {
+                    sqlx::query(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, kind))
+                        .execute(&self.pool)
+                        .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn maybe_migrate_balance_history(&self) -> Result<()> {
+        let table_exists: Option<(String,)> =
+            sqlx::query_as("SELECT name FROM sqlite_master WHERE type='table' AND name='balance_history'")
+                .fetch_optional(&self.pool)
+                .await?;
+        if table_exists.is_none() {
+            return Ok(());
+        }
+
+        let columns: Vec<(String,)> = sqlx::query_as("SELECT name FROM pragma_table_info('balance_history')")
+            .fetch_all(&self.pool)
+            .await?;
+
+        let has_token_accounts = columns.iter().any(|(name,)| name == "token_accounts_lamports");
+        if !has_token_accounts {
+            eprintln!("Migrating balance_history schema (adding token account columns)...");
+            // SQLite allows ADD COLUMN with DEFAULT. Existing rows will read as the default value.
+            sqlx::query("ALTER TABLE balance_history ADD COLUMN token_accounts_lamports INTEGER NOT NULL DEFAULT 0")
+                .execute(&self.pool)
+                .await?;
             sqlx::query(
-                "INSERT OR REPLACE INTO leader_fees
-                 (epoch, leader_slots, blocks_produced, skipped_slots, total_fees_lamports, total_fees_sol, date)
-                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+                "ALTER TABLE balance_history ADD COLUMN token_accounts_withdrawable_lamports INTEGER NOT NULL DEFAULT 0",
             )
-            .bind(fee.epoch as i64)
-            .bind(fee.leader_slots as i64)
-            .bind(fee.blocks_produced as i64)
-            .bind(fee.skipped_slots as i64)
-            .bind(fee.total_fees_lamports as i64)
-            .bind(fee.total_fees_sol)
-            .bind(&fee.date)
-            .execute(&mut *tx)
+            .execute(&self.pool)
             .await?;
         }
 
-        tx.commit().await?;
+        for column in ["squads_vault_lamports", "business_wallets_lamports"] {
+            if !columns.iter().any(|(name,)| name == column) {
+                sqlx::query(&format!(
+                    "ALTER TABLE balance_history ADD COLUMN {} INTEGER NOT NULL DEFAULT 0",
+                    column
+                ))
+                .execute(&self.pool)
+                .await?;
+            }
+        }
+
+        // Snapshot block time, so a timezone change can re-date the snapshot. Older rows
+        // have none and keep the date they were stored with.
+        if !columns.iter().any(|(name,)| name == "block_time") {
+            sqlx::query("ALTER TABLE balance_history ADD COLUMN block_time INTEGER")
+                .execute(&self.pool)
+                .await?;
+        }
+
         Ok(())
     }
 
-    // =========================================================================
-    // MEV Claims
-    // =========================================================================
+    /// Older caches keyed mev_claims by epoch alone (Jito only); rebuild with a provider column
+    async fn maybe_migrate_mev_claims(&self) -> Result<()> {
+        let columns: Vec<(String,)> = sqlx::query_as("SELECT name FROM pragma_table_info('mev_claims')")
+            .fetch_all(&self.pool)
+            .await?;
+        if columns.iter().any(|(name,)| name == "provider") {
+            return Ok(());
+        }
 
-    /// Get cached MEV claims
-    pub async fn get_mev_claims(&self, start_epoch: u64, end_epoch: u64) -> Result<Vec<MevClaim>> {
-        let rows: Vec<MevClaimRow> = sqlx::query_as(
-            "SELECT epoch, total_tips_lamports, commission_lamports, amount_sol, date
-             FROM mev_claims
-             WHERE epoch >= ? AND epoch <= ?
-             ORDER BY epoch",
+        eprintln!("Migrating mev_claims schema (adding provider column)...");
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "
+            CREATE TABLE mev_claims_new (
+                epoch INTEGER NOT NULL,
+                provider TEXT NOT NULL DEFAULT 'jito',
+                total_tips_lamports INTEGER NOT NULL,
+                commission_lamports INTEGER NOT NULL,
+                amount_sol REAL NOT NULL,
+                date TEXT,
+                fetched_at TEXT NOT NULL DEFAULT (datetime('now')),
+                PRIMARY KEY (epoch, provider)
+            )
+            ",
         )
-        .bind(start_epoch as i64)
-        .bind(end_epoch as i64)
-        .fetch_all(&self.pool)
+        .execute(&mut *tx)
         .await?;
+        sqlx::query(
+            "INSERT INTO mev_claims_new
+                 (epoch, provider, total_tips_lamports, commission_lamports, amount_sol, date, fetched_at)
+             SELECT epoch, 'jito', total_tips_lamports, commission_lamports, amount_sol, date, fetched_at
+             FROM mev_claims",
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("DROP TABLE mev_claims").execute(&mut *tx).await?;
+        sqlx::query("ALTER TABLE mev_claims_new RENAME TO mev_claims")
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
 
-        Ok(rows
-            .into_iter()
-            .map(|r| MevClaim {
-                epoch: r.epoch as u64,
-                total_tips_lamports: r.total_tips_lamports as u64,
-                commission_lamports: r.commission_lamports as u64,
-                amount_sol: r.amount_sol,
-                date: r.date,
-            })
-            .collect())
+        Ok(())
     }
 
-    /// Store MEV claims (in a transaction for atomicity)
-    pub async fn store_mev_claims(&self, claims: &[MevClaim]) -> Result<()> {
-        if claims.is_empty() {
+    async fn maybe_migrate_sol_transfers(&self) -> Result<()> {
+        // Check if table exists and whether it has the legacy `account_key` column.
+        let table_exists: Option<(String,)> =
+            sqlx::query_as("SELECT name FROM sqlite_master WHERE type='table' AND name='sol_transfers'")
+                .fetch_optional(&self.pool)
+                .await?;
+        if table_exists.is_none() {
+            return Ok(());
+        }
+
+        let columns: Vec<(String,)> = sqlx::query_as("SELECT name FROM pragma_table_info('sol_transfers')")
+            .fetch_all(&self.pool)
+            .await?;
+        let has_account_key = columns.iter().any(|(name,)| name == "account_key");
+        if !has_account_key {
             return Ok(());
         }
 
+        eprintln!("Migrating legacy sol_transfers schema (dropping account_key, improving dedupe)...");
+
         let mut tx = self.pool.begin().await?;
 
-        for claim in claims {
-            sqlx::query(
-                "INSERT OR REPLACE INTO mev_claims
-                 (epoch, total_tips_lamports, commission_lamports, amount_sol, date)
-                 VALUES (?, ?, ?, ?, ?)",
+        sqlx::query("DROP TABLE IF EXISTS sol_transfers_new")
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            "
+            CREATE TABLE IF NOT EXISTS sol_transfers_new (
+                signature TEXT NOT NULL,
+                slot INTEGER NOT NULL,
+                timestamp INTEGER,
+                date TEXT,
+                from_address TEXT NOT NULL,
+                to_address TEXT NOT NULL,
+                amount_lamports INTEGER NOT NULL,
+                amount_sol REAL NOT NULL,
+                from_label TEXT NOT NULL,
+                to_label TEXT NOT NULL,
+                from_category TEXT NOT NULL,
+                to_category TEXT NOT NULL,
+                fetched_at TEXT NOT NULL DEFAULT (datetime('now')),
+                PRIMARY KEY (signature, from_address, to_address, amount_lamports)
             )
-            .bind(claim.epoch as i64)
-            .bind(claim.total_tips_lamports as i64)
-            .bind(claim.commission_lamports as i64)
-            .bind(claim.amount_sol)
-            .bind(&claim.date)
+            ",
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // Insert one row per distinct transfer key, choosing the max slot/timestamp/date for that key.
+        sqlx::query(
+            "
+            INSERT OR IGNORE INTO sol_transfers_new
+                (signature, slot, timestamp, date, from_address, to_address,
+                 amount_lamports, amount_sol, from_label, to_label,
+                 from_category, to_category, fetched_at)
+            SELECT
+                signature,
+                MAX(slot) as slot,
+                MAX(timestamp) as timestamp,
+                MAX(date) as date,
+                from_address,
+                to_address,
+                amount_lamports,
+                MAX(amount_sol) as amount_sol,
+                MAX(from_label) as from_label,
+                MAX(to_label) as to_label,
+                MAX(from_category) as from_category,
+                MAX(to_category) as to_category,
+                MIN(fetched_at) as fetched_at
+            FROM sol_transfers
+            GROUP BY signature, from_address, to_address, amount_lamports
+            ",
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DROP TABLE sol_transfers").execute(&mut *tx).await?;
+        sqlx::query("ALTER TABLE sol_transfers_new RENAME TO sol_transfers")
             .execute(&mut *tx)
             .await?;
-        }
+
+        sqlx::query("DROP INDEX IF EXISTS idx_transfers_account")
+            .execute(&mut *tx)
+            .await
+            .ok();
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_transfers_slot ON sol_transfers(slot)")
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_transfers_withdrawal
+             ON sol_transfers(to_category)
+             WHERE to_category IN ('Exchange', 'PersonalWallet')",
+        )
+        .execute(&mut *tx)
+        .await
+        .ok();
 
         tx.commit().await?;
         Ok(())
     }
 
     // =========================================================================
-    // BAM Claims (jitoSOL rewards)
+    // Epoch Rewards (Commission)
     // =========================================================================
 
-    /// Get cached BAM claims for an epoch range
-    pub async fn get_bam_claims(&self, start_epoch: u64, end_epoch: u64) -> Result<Vec<BamClaim>> {
-        let rows: Vec<BamClaimRow> = sqlx::query_as(
-            "SELECT tx_signature, epoch, amount_jitosol_lamports, amount_sol_equivalent,
-                    jitosol_sol_rate, claimed_at, date
-             FROM bam_claims
+    /// Get cached epoch rewards
+    pub async fn get_epoch_rewards(&self, start_epoch: u64, end_epoch: u64) -> Result<Vec<EpochReward>> {
+        let rows: Vec<EpochRewardRow> = sqlx::query_as(
+            "SELECT epoch, amount_lamports, amount_sol, commission, effective_slot, date
+             FROM epoch_rewards
              WHERE epoch >= ? AND epoch <= ?
              ORDER BY epoch",
         )
@@ -867,54 +1352,69 @@ impl Cache {
 
         Ok(rows
             .into_iter()
-            .map(|r| BamClaim {
+            .map(|r| EpochReward {
                 epoch: r.epoch as u64,
-                amount_jitosol_lamports: r.amount_jitosol_lamports as u64,
-                amount_sol_equivalent: r.amount_sol_equivalent,
-                jitosol_sol_rate: r.jitosol_sol_rate,
-                claimed_at: r.claimed_at,
-                tx_signature: r.tx_signature,
-                date: Some(r.date),
+                amount_lamports: r.amount_lamports as u64,
+                amount_sol: r.amount_sol,
+                commission: r.commission as u8,
+                effective_slot: r.effective_slot as u64,
+                date: r.date,
             })
             .collect())
     }
 
-    /// Get epochs that have BAM claims cached
-    pub async fn get_cached_bam_epochs(&self, start_epoch: u64, end_epoch: u64) -> Result<Vec<u64>> {
-        let rows: Vec<(i64,)> = sqlx::query_as("SELECT DISTINCT epoch FROM bam_claims WHERE epoch >= ? AND epoch <= ?")
-            .bind(start_epoch as i64)
-            .bind(end_epoch as i64)
-            .fetch_all(&self.pool)
-            .await?;
+    /// Get epochs that are missing from cache (epochs seeded by `import fetch` count as
+    /// missing until the on-chain backfill has verified them)
+    pub async fn get_missing_reward_epochs(&self, start_epoch: u64, end_epoch: u64) -> Result<Vec<u64>> {
+        let rows: Vec<(i64,)> = sqlx::query_as(
+            "SELECT epoch FROM epoch_rewards
+             WHERE epoch >= ? AND epoch <= ?
+               AND epoch NOT IN (
+                   SELECT epoch FROM reward_imports
+                   WHERE verified_at IS NULL AND commission_lamports IS NOT NULL
+               )",
+        )
+        .bind(start_epoch as i64)
+        .bind(end_epoch as i64)
+        .fetch_all(&self.pool)
+        .await?;
 
-        Ok(rows.into_iter().map(|(e,)| e as u64).collect())
+        let cached: Vec<u64> = rows.into_iter().map(|(e,)| e as u64).collect();
+
+        let missing: Vec<u64> = (start_epoch..=end_epoch).filter(|e| !cached.contains(e)).collect();
+
+        Ok(missing)
     }
 
-    /// Store BAM claims (uses INSERT OR REPLACE to allow updates on re-fetch)
-    ///
-    /// Matches the pattern used by other epoch-based tables (epoch_rewards, mev_claims, etc.)
-    /// so that re-running reports can update cached data if needed.
-    pub async fn store_bam_claims(&self, claims: &[BamClaim]) -> Result<()> {
-        if claims.is_empty() {
+    /// Store epoch rewards (in a transaction for atomicity)
+    pub async fn store_epoch_rewards(&self, rewards: &[EpochReward]) -> Result<()> {
+        if rewards.is_empty() {
             return Ok(());
         }
 
         let mut tx = self.pool.begin().await?;
 
-        for claim in claims {
+        for reward in rewards {
             sqlx::query(
-                "INSERT OR REPLACE INTO bam_claims
-                 (tx_signature, epoch, amount_jitosol_lamports, amount_sol_equivalent,
-                  jitosol_sol_rate, claimed_at, date)
-                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+                "INSERT OR REPLACE INTO epoch_rewards
+                 (epoch, amount_lamports, amount_sol, commission, effective_slot, date)
+                 VALUES (?, ?, ?, ?, ?, ?)",
             )
-            .bind(&claim.tx_signature)
-            .bind(claim.epoch as i64)
-            .bind(claim.amount_jitosol_lamports as i64)
-            .bind(claim.amount_sol_equivalent)
-            .bind(claim.jitosol_sol_rate)
-            .bind(&claim.claimed_at)
-            .bind(claim.date.as_deref().unwrap_or("unknown"))
+            .bind(reward.epoch as i64)
+            .bind(reward.amount_lamports as i64)
+            .bind(reward.amount_sol)
+            .bind(reward.commission as i64)
+            .bind(reward.effective_slot as i64)
+            .bind(&reward.date)
+            .execute(&mut *tx)
+            .await?;
+
+            // On-chain data verifies any aggregator import for the epoch
+            sqlx::query(
+                "UPDATE reward_imports SET verified_at = datetime('now'), onchain_lamports = ? WHERE epoch = ?",
+            )
+            .bind(reward.amount_lamports as i64)
+            .bind(reward.epoch as i64)
             .execute(&mut *tx)
             .await?;
         }
@@ -924,14 +1424,118 @@ impl Cache {
     }
 
     // =========================================================================
-    // Vote Costs
+    // Aggregator Imports (onboarding)
     // =========================================================================
 
-    /// Get cached vote costs
-    pub async fn get_vote_costs(&self, start_epoch: u64, end_epoch: u64) -> Result<Vec<EpochVoteCost>> {
-        let rows: Vec<VoteCostRow> = sqlx::query_as(
-            "SELECT epoch, vote_count, total_fee_lamports, total_fee_sol, source, date
-             FROM vote_costs
+    /// Record imported history, keeping the verification state of epochs seen before
+    pub async fn store_reward_imports(&self, imports: &[ImportedEpoch]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for import in imports {
+            sqlx::query(
+                "INSERT INTO reward_imports (epoch, source, commission_lamports, activated_stake_lamports)
+                 VALUES (?, ?, ?, ?)
+                 ON CONFLICT(epoch) DO UPDATE SET
+                     source = excluded.source,
+                     commission_lamports = excluded.commission_lamports,
+                     activated_stake_lamports = excluded.activated_stake_lamports,
+                     imported_at = datetime('now')",
+            )
+            .bind(import.epoch as i64)
+            .bind(&import.source)
+            .bind(import.commission_lamports.map(|v| v as i64))
+            .bind(import.activated_stake_lamports.map(|v| v as i64))
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Seed reward rows from an import without marking them verified
+    pub async fn seed_imported_rewards(&self, rewards: &[EpochReward]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for reward in rewards {
+            sqlx::query(
+                "INSERT OR REPLACE INTO epoch_rewards
+                 (epoch, amount_lamports, amount_sol, commission, effective_slot, date)
+                 VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(reward.epoch as i64)
+            .bind(reward.amount_lamports as i64)
+            .bind(reward.amount_sol)
+            .bind(reward.commission as i64)
+            .bind(reward.effective_slot as i64)
+            .bind(&reward.date)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Mark imports for epochs already cached from chain as verified against the cached amount
+    pub async fn verify_imports_from_cache(&self, epochs: &[u64]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for epoch in epochs {
+            sqlx::query(
+                "UPDATE reward_imports SET
+                     verified_at = datetime('now'),
+                     onchain_lamports = (SELECT amount_lamports FROM epoch_rewards WHERE epoch = ?)
+                 WHERE epoch = ?",
+            )
+            .bind(*epoch as i64)
+            .bind(*epoch as i64)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Epochs whose cached reward came from an import the chain hasn't confirmed yet
+    pub async fn get_unverified_import_epochs(&self) -> Result<BTreeSet<u64>> {
+        let rows: Vec<(i64,)> = sqlx::query_as(
+            "SELECT epoch FROM reward_imports WHERE verified_at IS NULL AND commission_lamports IS NOT NULL",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(|(e,)| e as u64).collect())
+    }
+
+    /// All imported epochs, oldest first
+    pub async fn get_reward_imports(&self) -> Result<Vec<RewardImport>> {
+        let rows: Vec<RewardImportRow> = sqlx::query_as(
+            "SELECT epoch, source, commission_lamports, activated_stake_lamports, imported_at,
+                    verified_at, onchain_lamports
+             FROM reward_imports
+             ORDER BY epoch",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|r| RewardImport {
+                epoch: r.epoch as u64,
+                source: r.source,
+                commission_lamports: r.commission_lamports.map(|v| v as u64),
+                activated_stake_lamports: r.activated_stake_lamports.map(|v| v as u64),
+                imported_at: r.imported_at,
+                verified_at: r.verified_at,
+                onchain_lamports: r.onchain_lamports.map(|v| v as u64),
+            })
+            .collect())
+    }
+
+    // =========================================================================
+    // Leader Fees
+    // =========================================================================
+
+    /// Get cached leader fees
+    pub async fn get_leader_fees(&self, start_epoch: u64, end_epoch: u64) -> Result<Vec<EpochLeaderFees>> {
+        let rows: Vec<LeaderFeesRow> = sqlx::query_as(
+            "SELECT epoch, leader_slots, blocks_produced, skipped_slots,
+                    total_fees_lamports, total_fees_sol, date
+             FROM leader_fees
              WHERE epoch >= ? AND epoch <= ?
              ORDER BY epoch",
         )
@@ -942,37 +1546,54 @@ impl Cache {
 
         Ok(rows
             .into_iter()
-            .map(|r| EpochVoteCost {
+            .map(|r| EpochLeaderFees {
                 epoch: r.epoch as u64,
-                vote_count: r.vote_count as u64,
-                total_fee_lamports: r.total_fee_lamports as u64,
-                total_fee_sol: r.total_fee_sol,
-                source: r.source,
+                leader_slots: r.leader_slots as u64,
+                blocks_produced: r.blocks_produced as u64,
+                skipped_slots: r.skipped_slots as u64,
+                total_fees_lamports: r.total_fees_lamports as u64,
+                total_fees_sol: r.total_fees_sol,
                 date: r.date,
             })
             .collect())
     }
 
-    /// Store vote costs (in a transaction for atomicity)
-    pub async fn store_vote_costs(&self, costs: &[EpochVoteCost]) -> Result<()> {
-        if costs.is_empty() {
+    /// Get epochs missing leader fee data
+    pub async fn get_missing_leader_fee_epochs(&self, start_epoch: u64, end_epoch: u64) -> Result<Vec<u64>> {
+        let rows: Vec<(i64,)> = sqlx::query_as("SELECT epoch FROM leader_fees WHERE epoch >= ? AND epoch <= ?")
+            .bind(start_epoch as i64)
+            .bind(end_epoch as i64)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let cached: Vec<u64> = rows.into_iter().map(|(e,)| e as u64).collect();
+
+        let missing: Vec<u64> = (start_epoch..=end_epoch).filter(|e| !cached.contains(e)).collect();
+
+        Ok(missing)
+    }
+
+    /// Store leader fees (in a transaction for atomicity)
+    pub async fn store_leader_fees(&self, fees: &[EpochLeaderFees]) -> Result<()> {
+        if fees.is_empty() {
             return Ok(());
         }
 
         let mut tx = self.pool.begin().await?;
 
-        for cost in costs {
+        for fee in fees {
             sqlx::query(
-                "INSERT OR REPLACE INTO vote_costs
-                 (epoch, vote_count, total_fee_lamports, total_fee_sol, source, date)
-                 VALUES (?, ?, ?, ?, ?, ?)",
+                "INSERT OR REPLACE INTO leader_fees
+                 (epoch, leader_slots, blocks_produced, skipped_slots, total_fees_lamports, total_fees_sol, date)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
             )
-            .bind(cost.epoch as i64)
-            .bind(cost.vote_count as i64)
-            .bind(cost.total_fee_lamports as i64)
-            .bind(cost.total_fee_sol)
-            .bind(&cost.source)
-            .bind(&cost.date)
+            .bind(fee.epoch as i64)
+            .bind(fee.leader_slots as i64)
+            .bind(fee.blocks_produced as i64)
+            .bind(fee.skipped_slots as i64)
+            .bind(fee.total_fees_lamports as i64)
+            .bind(fee.total_fees_sol)
+            .bind(&fee.date)
             .execute(&mut *tx)
             .await?;
         }
@@ -981,19 +1602,39 @@ impl Cache {
         Ok(())
     }
 
+    /// Get assigned leader slot counts for an epoch range (epoch -> slot count)
+    pub async fn get_leader_schedule_counts(&self, start_epoch: u64, end_epoch: u64) -> Result<BTreeMap<u64, u64>> {
+        let rows: Vec<(i64, i64)> =
+            sqlx::query_as("SELECT epoch, slot_count FROM leader_schedule WHERE epoch >= ? AND epoch <= ?")
+                .bind(start_epoch as i64)
+                .bind(end_epoch as i64)
+                .fetch_all(&self.pool)
+                .await?;
+
+        Ok(rows.into_iter().map(|(e, c)| (e as u64, c as u64)).collect())
+    }
+
+    /// Store the assigned leader slot count for an epoch
+    pub async fn store_leader_schedule_count(&self, epoch: u64, slot_count: u64) -> Result<()> {
+        sqlx::query("INSERT OR REPLACE INTO leader_schedule (epoch, slot_count) VALUES (?, ?)")
+            .bind(epoch as i64)
+            .bind(slot_count as i64)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
     // =========================================================================
-    // DoubleZero Fees
+    // MEV Claims
     // =========================================================================
 
-    /// Get cached DoubleZero fees
-    #[allow(dead_code)]
-    pub async fn get_doublezero_fees(&self, start_epoch: u64, end_epoch: u64) -> Result<Vec<DoubleZeroFee>> {
-        let rows: Vec<DoubleZeroFeeRow> = sqlx::query_as(
-            "SELECT epoch, fee_base_lamports, liability_lamports, liability_sol,
-                    fee_rate_bps, date, source, is_estimate
-             FROM doublezero_fees
+    /// Get cached MEV claims
+    pub async fn get_mev_claims(&self, start_epoch: u64, end_epoch: u64) -> Result<Vec<MevClaim>> {
+        let rows: Vec<MevClaimRow> = sqlx::query_as(
+            "SELECT epoch, provider, total_tips_lamports, commission_lamports, amount_sol, date
+             FROM mev_claims
              WHERE epoch >= ? AND epoch <= ?
-             ORDER BY epoch",
+             ORDER BY epoch, provider",
         )
         .bind(start_epoch as i64)
         .bind(end_epoch as i64)
@@ -1002,42 +1643,37 @@ impl Cache {
 
         Ok(rows
             .into_iter()
-            .map(|r| DoubleZeroFee {
+            .map(|r| MevClaim {
                 epoch: r.epoch as u64,
-                fee_base_lamports: r.fee_base_lamports as u64,
-                liability_lamports: r.liability_lamports as u64,
-                liability_sol: r.liability_sol,
-                fee_rate_bps: r.fee_rate_bps as u64,
+                provider: r.provider,
+                total_tips_lamports: r.total_tips_lamports as u64,
+                commission_lamports: r.commission_lamports as u64,
+                amount_sol: r.amount_sol,
                 date: r.date,
-                source: r.source,
-                is_estimate: r.is_estimate != 0,
             })
             .collect())
     }
 
-    /// Store DoubleZero fees (in a transaction for atomicity)
-    pub async fn store_doublezero_fees(&self, fees: &[DoubleZeroFee]) -> Result<()> {
-        if fees.is_empty() {
+    /// Store MEV claims (in a transaction for atomicity)
+    pub async fn store_mev_claims(&self, claims: &[MevClaim]) -> Result<()> {
+        if claims.is_empty() {
             return Ok(());
         }
 
         let mut tx = self.pool.begin().await?;
 
-        for fee in fees {
+        for claim in claims {
             sqlx::query(
-                "INSERT OR REPLACE INTO doublezero_fees
-                 (epoch, fee_base_lamports, liability_lamports, liability_sol,
-                  fee_rate_bps, date, source, is_estimate)
-                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+                "INSERT OR REPLACE INTO mev_claims
+                 (epoch, provider, total_tips_lamports, commission_lamports, amount_sol, date)
+                 VALUES (?, ?, ?, ?, ?, ?)",
             )
-            .bind(fee.epoch as i64)
-            .bind(fee.fee_base_lamports as i64)
-            .bind(fee.liability_lamports as i64)
-            .bind(fee.liability_sol)
-            .bind(fee.fee_rate_bps as i64)
-            .bind(&fee.date)
-            .bind(&fee.source)
-            .bind(if fee.is_estimate { 1i64 } else { 0i64 })
+            .bind(claim.epoch as i64)
+            .bind(&claim.provider)
+            .bind(claim.total_tips_lamports as i64)
+            .bind(claim.commission_lamports as i64)
+            .bind(claim.amount_sol)
+            .bind(&claim.date)
             .execute(&mut *tx)
             .await?;
         }
@@ -1047,32 +1683,53 @@ impl Cache {
     }
 
     // =========================================================================
-    // Prices
+    // Network Fee Medians
     // =========================================================================
 
-    /// Get cached prices
-    pub async fn get_prices(&self) -> Result<PriceCache> {
-        let rows: Vec<(String, f64)> = sqlx::query_as("SELECT date, usd_price FROM prices")
-            .fetch_all(&self.pool)
-            .await?;
+    /// Get cached network median block fees
+    pub async fn get_network_fee_medians(&self, start_epoch: u64, end_epoch: u64) -> Result<Vec<NetworkFeeMedian>> {
+        let rows: Vec<NetworkFeeMedianRow> = sqlx::query_as(
+            "SELECT epoch, median_block_fees_lamports, blocks_sampled, date
+             FROM network_fee_medians
+             WHERE epoch >= ? AND epoch <= ?
+             ORDER BY epoch",
+        )
+        .bind(start_epoch as i64)
+        .bind(end_epoch as i64)
+        .fetch_all(&self.pool)
+        .await?;
 
-        Ok(rows.into_iter().collect())
+        Ok(rows
+            .into_iter()
+            .map(|r| NetworkFeeMedian {
+                epoch: r.epoch as u64,
+                median_block_fees_lamports: r.median_block_fees_lamports as u64,
+                blocks_sampled: r.blocks_sampled as u64,
+                date: r.date,
+            })
+            .collect())
     }
 
-    /// Store prices (in a transaction for atomicity)
-    pub async fn store_prices(&self, prices: &PriceCache) -> Result<()> {
-        if prices.is_empty() {
+    /// Store network median block fees (in a transaction for atomicity)
+    pub async fn store_network_fee_medians(&self, medians: &[NetworkFeeMedian]) -> Result<()> {
+        if medians.is_empty() {
             return Ok(());
         }
 
         let mut tx = self.pool.begin().await?;
 
-        for (date, price) in prices {
-            sqlx::query("INSERT OR REPLACE INTO prices (date, usd_price) VALUES (?, ?)")
-                .bind(date)
-                .bind(price)
-                .execute(&mut *tx)
-                .await?;
+        for median in medians {
+            sqlx::query(
+                "INSERT OR REPLACE INTO network_fee_medians
+                 (epoch, median_block_fees_lamports, blocks_sampled, date)
+                 VALUES (?, ?, ?, ?)",
+            )
+            .bind(median.epoch as i64)
+            .bind(median.median_block_fees_lamports as i64)
+            .bind(median.blocks_sampled as i64)
+            .bind(&median.date)
+            .execute(&mut *tx)
+            .await?;
         }
 
         tx.commit().await?;
@@ -1080,153 +1737,122 @@ impl Cache {
     }
 
     // =========================================================================
-    // Metadata
+    // Client Metrics
     // =========================================================================
 
-    /// Get metadata value
-    #[allow(dead_code)]
-    pub async fn get_metadata(&self, key: &str) -> Result<Option<String>> {
-        let row: Option<(String,)> = sqlx::query_as("SELECT value FROM metadata WHERE key = ?")
-            .bind(key)
-            .fetch_optional(&self.pool)
-            .await?;
-
-        Ok(row.map(|(v,)| v))
+    /// Store a client metrics sample
+    pub async fn store_metric_sample(&self, sample: &MetricSample) -> Result<()> {
+        sqlx::query(
+            "INSERT OR REPLACE INTO client_metric_samples
+             (scraped_at, slot, epoch, produced, skipped, vote_latency_slots)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(sample.scraped_at)
+        .bind(sample.slot as i64)
+        .bind(sample.epoch as i64)
+        .bind(sample.produced.map(|v| v as i64))
+        .bind(sample.skipped.map(|v| v as i64))
+        .bind(sample.vote_latency_slots)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
     }
 
-    /// Set metadata value
-    #[allow(dead_code)]
-    pub async fn set_metadata(&self, key: &str, value: &str) -> Result<()> {
-        sqlx::query("INSERT OR REPLACE INTO metadata (key, value) VALUES (?, ?)")
-            .bind(key)
-            .bind(value)
-            .execute(&self.pool)
-            .await?;
-        Ok(())
+    /// Client metrics samples for an epoch, oldest first
+    pub async fn get_metric_samples(&self, epoch: u64) -> Result<Vec<MetricSample>> {
+        let rows: Vec<MetricSampleRow> = sqlx::query_as(
+            "SELECT scraped_at, slot, epoch, produced, skipped, vote_latency_slots
+             FROM client_metric_samples
+             WHERE epoch = ?
+             ORDER BY slot",
+        )
+        .bind(epoch as i64)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(MetricSample::from).collect())
+    }
+
+    /// Most recent client metrics sample, if any
+    pub async fn get_latest_metric_sample(&self) -> Result<Option<MetricSample>> {
+        let row: Option<MetricSampleRow> = sqlx::query_as(
+            "SELECT scraped_at, slot, epoch, produced, skipped, vote_latency_slots
+             FROM client_metric_samples
+             ORDER BY slot DESC
+             LIMIT 1",
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.map(MetricSample::from))
     }
 
     // =========================================================================
-    // Expenses
+    // BAM Claims (jitoSOL rewards)
     // =========================================================================
 
-    /// Get all expenses
-    pub async fn get_expenses(&self) -> Result<Vec<Expense>> {
-        let rows: Vec<ExpenseRow> = sqlx::query_as(
-            "SELECT id, date, vendor, category, description, amount_usd, paid_with, invoice_id
-             FROM expenses
-             ORDER BY date, id",
+    /// Get cached BAM claims for an epoch range
+    pub async fn get_bam_claims(&self, start_epoch: u64, end_epoch: u64) -> Result<Vec<BamClaim>> {
+        let rows: Vec<BamClaimRow> = sqlx::query_as(
+            "SELECT tx_signature, epoch, amount_jitosol_lamports, amount_sol_equivalent,
+                    jitosol_sol_rate, claimed_at, date
+             FROM bam_claims
+             WHERE epoch >= ? AND epoch <= ?
+             ORDER BY epoch",
         )
+        .bind(start_epoch as i64)
+        .bind(end_epoch as i64)
         .fetch_all(&self.pool)
         .await?;
 
         Ok(rows
             .into_iter()
-            .map(|r| {
-                let category = match r.category.as_str() {
-                    "Hosting" => ExpenseCategory::Hosting,
-                    "Contractor" => ExpenseCategory::Contractor,
-                    "Hardware" => ExpenseCategory::Hardware,
-                    "Software" => ExpenseCategory::Software,
-                    "VoteFees" => ExpenseCategory::VoteFees,
-                    _ => ExpenseCategory::Other,
-                };
-
-                Expense {
-                    id: Some(r.id),
-                    date: r.date,
-                    vendor: r.vendor,
-                    category,
-                    description: r.description,
-                    amount_usd: r.amount_usd,
-                    paid_with: r.paid_with,
-                    invoice_id: r.invoice_id,
-                }
+            .map(|r| BamClaim {
+                epoch: r.epoch as u64,
+                amount_jitosol_lamports: r.amount_jitosol_lamports as u64,
+                amount_sol_equivalent: r.amount_sol_equivalent,
+                jitosol_sol_rate: r.jitosol_sol_rate,
+                claimed_at: r.claimed_at,
+                tx_signature: r.tx_signature,
+                date: Some(r.date),
             })
             .collect())
     }
 
-    /// Add a new expense, returns the ID
-    pub async fn add_expense(&self, expense: &Expense) -> Result<i64> {
-        let category_str = match expense.category {
-            ExpenseCategory::Hosting => "Hosting",
-            ExpenseCategory::Contractor => "Contractor",
-            ExpenseCategory::Hardware => "Hardware",
-            ExpenseCategory::Software => "Software",
-            ExpenseCategory::VoteFees => "VoteFees",
-            ExpenseCategory::Other => "Other",
-        };
-
-        let result = sqlx::query(
-            "INSERT INTO expenses (date, vendor, category, description, amount_usd, paid_with, invoice_id)
-             VALUES (?, ?, ?, ?, ?, ?, ?)",
-        )
-        .bind(&expense.date)
-        .bind(&expense.vendor)
-        .bind(category_str)
-        .bind(&expense.description)
-        .bind(expense.amount_usd)
-        .bind(&expense.paid_with)
-        .bind(&expense.invoice_id)
-        .execute(&self.pool)
-        .await?;
-
-        Ok(result.last_insert_rowid())
-    }
-
-    /// Delete an expense by ID
-    pub async fn delete_expense(&self, id: i64) -> Result<bool> {
-        let result = sqlx::query("DELETE FROM expenses WHERE id = ?")
-            .bind(id)
-            .execute(&self.pool)
+    /// Get epochs that have BAM claims cached
+    pub async fn get_cached_bam_epochs(&self, start_epoch: u64, end_epoch: u64) -> Result<Vec<u64>> {
+        let rows: Vec<(i64,)> = sqlx::query_as("SELECT DISTINCT epoch FROM bam_claims WHERE epoch >= ? AND epoch <= ?")
+            .bind(start_epoch as i64)
+            .bind(end_epoch as i64)
+            .fetch_all(&self.pool)
             .await?;
 
-        Ok(result.rows_affected() > 0)
+        Ok(rows.into_iter().map(|(e,)| e as u64).collect())
     }
 
-    /// Import multiple expenses (for bulk import from CSV)
-    pub async fn import_expenses(&self, expenses: &[Expense]) -> Result<usize> {
-        let mut count = 0;
-        for expense in expenses {
-            self.add_expense(expense).await?;
-            count += 1;
+    /// Store BAM claims (uses INSERT OR REPLACE to allow updates on re-fetch)
+    ///
+    /// Matches the pattern used by other epoch-based tables (epoch_rewards, mev_claims, etc.)
+    /// so that re-running reports can update cached data if needed.
+    pub async fn store_bam_claims(&self, claims: &[BamClaim]) -> Result<()> {
+        if claims.is_empty() {
+            return Ok(());
         }
-        Ok(count)
-    }
 
-    /// Replace Notion-derived contractor expenses atomically.
-    ///
-    /// Rows are identified by `paid_with IN ('Notion Paid', 'Notion Unpaid')`.
-    /// Manual expenses are untouched.
-    pub async fn sync_notion_expenses(&self, notion_expenses: &[Expense]) -> Result<()> {
         let mut tx = self.pool.begin().await?;
 
-        // Remove previous Notion-derived rows.
-        sqlx::query("DELETE FROM expenses WHERE paid_with IN ('Notion Paid', 'Notion Unpaid')")
-            .execute(&mut *tx)
-            .await?;
-
-        // Insert the fresh snapshot.
-        for expense in notion_expenses {
-            let category_str = match expense.category {
-                ExpenseCategory::Hosting => "Hosting",
-                ExpenseCategory::Contractor => "Contractor",
-                ExpenseCategory::Hardware => "Hardware",
-                ExpenseCategory::Software => "Software",
-                ExpenseCategory::VoteFees => "VoteFees",
-                ExpenseCategory::Other => "Other",
-            };
-
+        for claim in claims {
             sqlx::query(
-                "INSERT INTO expenses (date, vendor, category, description, amount_usd, paid_with, invoice_id)
+                "INSERT OR REPLACE INTO bam_claims
+                 (tx_signature, epoch, amount_jitosol_lamports, amount_sol_equivalent,
+                  jitosol_sol_rate, claimed_at, date)
                  VALUES (?, ?, ?, ?, ?, ?, ?)",
             )
-            .bind(&expense.date)
-            .bind(&expense.vendor)
-            .bind(category_str)
-            .bind(&expense.description)
-            .bind(expense.amount_usd)
-            .bind(&expense.paid_with)
-            .bind(&expense.invoice_id)
+            .bind(&claim.tx_signature)
+            .bind(claim.epoch as i64)
+            .bind(claim.amount_jitosol_lamports as i64)
+            .bind(claim.amount_sol_equivalent)
+            .bind(claim.jitosol_sol_rate)
+            .bind(&claim.claimed_at)
+            .bind(claim.date.as_deref().unwrap_or("unknown"))
             .execute(&mut *tx)
             .await?;
         }
@@ -1236,154 +1862,227 @@ impl Cache {
     }
 
     // =========================================================================
-    // Recurring Expenses
+    // Vote Costs
     // =========================================================================
 
-    /// Get all recurring expenses
-    pub async fn get_recurring_expenses(&self) -> Result<Vec<RecurringExpense>> {
-        let rows: Vec<RecurringExpenseRow> = sqlx::query_as(
-            "SELECT id, vendor, category, description, amount_usd, paid_with, start_date, end_date
-             FROM recurring_expenses
-             ORDER BY vendor, start_date",
+    /// Store vote credits / stake, keeping values already known when a row leaves one out
+    pub async fn store_epoch_performance(&self, rows: &[EpochPerformance]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for row in rows {
+            sqlx::query(
+                "INSERT INTO epoch_performance (epoch, vote_credits, active_stake_lamports)
+                 VALUES (?, ?, ?)
+                 ON CONFLICT(epoch) DO UPDATE SET
+                     vote_credits = COALESCE(excluded.vote_credits, vote_credits),
+                     active_stake_lamports = COALESCE(excluded.active_stake_lamports, active_stake_lamports),
+                     fetched_at = datetime('now')",
+            )
+            .bind(row.epoch as i64)
+            .bind(row.vote_credits.map(|v| v as i64))
+            .bind(row.active_stake_lamports.map(|v| v as i64))
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Vote credits and stake per epoch; stake falls back to aggregator imports (`import fetch`)
+    pub async fn get_epoch_performance(&self, start_epoch: u64, end_epoch: u64) -> Result<Vec<EpochPerformance>> {
+        let rows: Vec<(i64, Option<i64>, Option<i64>)> = sqlx::query_as(
+            "SELECT epoch, MAX(vote_credits), COALESCE(MAX(onchain_stake), MAX(imported_stake))
+             FROM (
+                 SELECT epoch, vote_credits, active_stake_lamports AS onchain_stake, NULL AS imported_stake
+                 FROM epoch_performance
+                 UNION ALL
+                 SELECT epoch, NULL, NULL, activated_stake_lamports FROM reward_imports
+             )
+             WHERE epoch >= ? AND epoch <= ?
+             GROUP BY epoch
+             HAVING MAX(vote_credits) IS NOT NULL OR MAX(onchain_stake) IS NOT NULL OR MAX(imported_stake) IS NOT NULL
+             ORDER BY epoch",
         )
+        .bind(start_epoch as i64)
+        .bind(end_epoch as i64)
         .fetch_all(&self.pool)
         .await?;
 
         Ok(rows
             .into_iter()
-            .map(|r| {
-                let category = match r.category.as_str() {
-                    "Hosting" => ExpenseCategory::Hosting,
-                    "Contractor" => ExpenseCategory::Contractor,
-                    "Hardware" => ExpenseCategory::Hardware,
-                    "Software" => ExpenseCategory::Software,
-                    "VoteFees" => ExpenseCategory::VoteFees,
-                    _ => ExpenseCategory::Other,
-                };
-
-                RecurringExpense {
-                    id: Some(r.id),
-                    vendor: r.vendor,
-                    category,
-                    description: r.description,
-                    amount_usd: r.amount_usd,
-                    paid_with: r.paid_with,
-                    start_date: r.start_date,
-                    end_date: r.end_date,
-                }
+            .map(|(epoch, credits, stake)| EpochPerformance {
+                epoch: epoch as u64,
+                vote_credits: credits.map(|v| v as u64),
+                active_stake_lamports: stake.map(|v| v as u64),
             })
             .collect())
     }
 
-    /// Add a new recurring expense, returns the ID
-    pub async fn add_recurring_expense(&self, expense: &RecurringExpense) -> Result<i64> {
-        let category_str = match expense.category {
-            ExpenseCategory::Hosting => "Hosting",
-            ExpenseCategory::Contractor => "Contractor",
-            ExpenseCategory::Hardware => "Hardware",
-            ExpenseCategory::Software => "Software",
-            ExpenseCategory::VoteFees => "VoteFees",
-            ExpenseCategory::Other => "Other",
-        };
-
-        let result = sqlx::query(
-            "INSERT INTO recurring_expenses (vendor, category, description, amount_usd, paid_with, start_date, end_date)
-             VALUES (?, ?, ?, ?, ?, ?, ?)",
-        )
-        .bind(&expense.vendor)
-        .bind(category_str)
-        .bind(&expense.description)
-        .bind(expense.amount_usd)
-        .bind(&expense.paid_with)
-        .bind(&expense.start_date)
-        .bind(&expense.end_date)
-        .execute(&self.pool)
-        .await?;
-
-        Ok(result.last_insert_rowid())
-    }
+    // =========================================================================
+    // Delegators
+    // =========================================================================
 
-    /// Delete a recurring expense by ID
-    pub async fn delete_recurring_expense(&self, id: i64) -> Result<bool> {
-        let result = sqlx::query("DELETE FROM recurring_expenses WHERE id = ?")
-            .bind(id)
-            .execute(&self.pool)
+    /// Replace the delegator snapshot of `epoch` (a snapshot is always complete)
+    pub async fn store_delegator_snapshot(&self, epoch: u64, rows: &[DelegatorStake]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM delegator_stake WHERE epoch = ?")
+            .bind(epoch as i64)
+            .execute(&mut *tx)
             .await?;
-
-        Ok(result.rows_affected() > 0)
+        for row in rows {
+            sqlx::query(
+                "INSERT INTO delegator_stake (epoch, delegator, stake_lamports, accounts)
+                 VALUES (?, ?, ?, ?)",
+            )
+            .bind(epoch as i64)
+            .bind(&row.delegator)
+            .bind(row.stake_lamports as i64)
+            .bind(row.accounts as i64)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
     }
 
-    // =========================================================================
-    // SOL Transfers
-    // =========================================================================
-
-    /// Get all cached transfers
-    pub async fn get_all_transfers(&self) -> Result<Vec<SolTransfer>> {
-        let rows: Vec<SolTransferRow> = sqlx::query_as(
-            "SELECT signature, slot, timestamp, date, from_address, to_address,
-                    amount_lamports, amount_sol, from_label, to_label,
-                    from_category, to_category
-             FROM sol_transfers
-             ORDER BY slot DESC",
+    /// Delegator snapshots in an epoch range
+    pub async fn get_delegator_stake(&self, start_epoch: u64, end_epoch: u64) -> Result<Vec<DelegatorStake>> {
+        let rows: Vec<(i64, String, i64, i64)> = sqlx::query_as(
+            "SELECT epoch, delegator, stake_lamports, accounts
+             FROM delegator_stake
+             WHERE epoch >= ? AND epoch <= ?
+             ORDER BY epoch, stake_lamports DESC",
         )
+        .bind(start_epoch as i64)
+        .bind(end_epoch as i64)
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(rows.into_iter().filter_map(row_to_transfer).collect())
+        Ok(rows
+            .into_iter()
+            .map(|(epoch, delegator, stake_lamports, accounts)| DelegatorStake {
+                epoch: epoch as u64,
+                delegator,
+                stake_lamports: stake_lamports as u64,
+                accounts: accounts as u32,
+            })
+            .collect())
     }
 
-    /// Get the highest slot we've checked for an account (even if no transfers were found)
-    /// This is useful for accounts with only versioned/undecodable transactions
-    pub async fn get_account_progress(&self, account_key: &str) -> Result<Option<u64>> {
-        // We track per-account progress independently of transfer storage.
-        let progress_row: Option<(i64,)> =
-            sqlx::query_as("SELECT highest_slot FROM account_progress WHERE account_key = ?")
-                .bind(account_key)
-                .fetch_optional(&self.pool)
-                .await?;
+    // =========================================================================
+    // Vote Costs
+    // =========================================================================
 
-        let progress_slot = progress_row.map(|(s,)| s as u64);
-        Ok(progress_slot)
+    /// Get cached vote costs
+    pub async fn get_vote_costs(&self, start_epoch: u64, end_epoch: u64) -> Result<Vec<EpochVoteCost>> {
+        let rows: Vec<VoteCostRow> = sqlx::query_as(
+            "SELECT epoch, vote_count, total_fee_lamports, total_fee_sol, source, date
+             FROM vote_costs
+             WHERE epoch >= ? AND epoch <= ?
+             ORDER BY epoch",
+        )
+        .bind(start_epoch as i64)
+        .bind(end_epoch as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| EpochVoteCost {
+                epoch: r.epoch as u64,
+                vote_count: r.vote_count as u64,
+                total_fee_lamports: r.total_fee_lamports as u64,
+                total_fee_sol: r.total_fee_sol,
+                source: r.source,
+                date: r.date,
+            })
+            .collect())
     }
 
-    /// Store the highest slot we've checked for an account
-    pub async fn set_account_progress(&self, account_key: &str, highest_slot: u64) -> Result<()> {
-        sqlx::query("INSERT OR REPLACE INTO account_progress (account_key, highest_slot) VALUES (?, ?)")
-            .bind(account_key)
-            .bind(highest_slot as i64)
-            .execute(&self.pool)
+    /// Store vote costs (in a transaction for atomicity)
+    pub async fn store_vote_costs(&self, costs: &[EpochVoteCost]) -> Result<()> {
+        if costs.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        for cost in costs {
+            sqlx::query(
+                "INSERT OR REPLACE INTO vote_costs
+                 (epoch, vote_count, total_fee_lamports, total_fee_sol, source, date)
+                 VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(cost.epoch as i64)
+            .bind(cost.vote_count as i64)
+            .bind(cost.total_fee_lamports as i64)
+            .bind(cost.total_fee_sol)
+            .bind(&cost.source)
+            .bind(&cost.date)
+            .execute(&mut *tx)
             .await?;
+        }
+
+        tx.commit().await?;
         Ok(())
     }
 
-    /// Store transfers (in a transaction for atomicity)
-    pub async fn store_transfers(&self, transfers: &[SolTransfer]) -> Result<()> {
-        if transfers.is_empty() {
+    // =========================================================================
+    // DoubleZero Fees
+    // =========================================================================
+
+    /// Get cached DoubleZero fees
+    #[allow(dead_code)]
+    pub async fn get_doublezero_fees(&self, start_epoch: u64, end_epoch: u64) -> Result<Vec<DoubleZeroFee>> {
+        let rows: Vec<DoubleZeroFeeRow> = sqlx::query_as(
+            "SELECT epoch, fee_base_lamports, liability_lamports, liability_sol,
+                    fee_rate_bps, date, source, is_estimate
+             FROM doublezero_fees
+             WHERE epoch >= ? AND epoch <= ?
+             ORDER BY epoch",
+        )
+        .bind(start_epoch as i64)
+        .bind(end_epoch as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| DoubleZeroFee {
+                epoch: r.epoch as u64,
+                fee_base_lamports: r.fee_base_lamports as u64,
+                liability_lamports: r.liability_lamports as u64,
+                liability_sol: r.liability_sol,
+                fee_rate_bps: r.fee_rate_bps as u64,
+                date: r.date,
+                source: r.source,
+                is_estimate: r.is_estimate != 0,
+            })
+            .collect())
+    }
+
+    /// Store DoubleZero fees (in a transaction for atomicity)
+    pub async fn store_doublezero_fees(&self, fees: &[DoubleZeroFee]) -> Result<()> {
+        if fees.is_empty() {
             return Ok(());
         }
 
         let mut tx = self.pool.begin().await?;
 
-        for transfer in transfers {
+        for fee in fees {
             sqlx::query(
-                "INSERT OR REPLACE INTO sol_transfers
-                 (signature, slot, timestamp, date, from_address, to_address,
-                  amount_lamports, amount_sol, from_label, to_label,
-                  from_category, to_category)
-                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                "INSERT OR REPLACE INTO doublezero_fees
+                 (epoch, fee_base_lamports, liability_lamports, liability_sol,
+                  fee_rate_bps, date, source, is_estimate)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
             )
-            .bind(&transfer.signature)
-            .bind(transfer.slot as i64)
-            .bind(transfer.timestamp)
-            .bind(&transfer.date)
-            .bind(transfer.from.to_string())
-            .bind(transfer.to.to_string())
-            .bind(transfer.amount_lamports as i64)
-            .bind(transfer.amount_sol)
-            .bind(&transfer.from_label)
-            .bind(&transfer.to_label)
-            .bind(category_to_string(&transfer.from_category))
-            .bind(category_to_string(&transfer.to_category))
+            .bind(fee.epoch as i64)
+            .bind(fee.fee_base_lamports as i64)
+            .bind(fee.liability_lamports as i64)
+            .bind(fee.liability_sol)
+            .bind(fee.fee_rate_bps as i64)
+            .bind(&fee.date)
+            .bind(&fee.source)
+            .bind(if fee.is_estimate { 1i64 } else { 0i64 })
             .execute(&mut *tx)
             .await?;
         }
@@ -1393,845 +2092,2559 @@ impl Cache {
     }
 
     // =========================================================================
-    // Token Flows (SPL tokens)
+    // Prices
     // =========================================================================
 
-    /// Store SPL token balance deltas by tx signature.
-    pub async fn store_token_flows(&self, flows: &[TokenFlow]) -> Result<()> {
-        if flows.is_empty() {
+    /// Get cached prices
+    pub async fn get_prices(&self) -> Result<PriceCache> {
+        let rows: Vec<(String, f64, String)> = sqlx::query_as("SELECT date, usd_price, source FROM prices")
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(date, usd_price, source)| {
+                let source = PriceSource::parse(&source).unwrap_or(PriceSource::Exact);
+                (date, CachedPrice { usd_price, source })
+            })
+            .collect())
+    }
+
+    /// Store fetched prices, recording their provenance in `price_audit` (in a transaction)
+    pub async fn store_price_quotes(&self, quotes: &PriceQuotes) -> Result<()> {
+        if quotes.is_empty() {
             return Ok(());
         }
 
         let mut tx = self.pool.begin().await?;
-        for f in flows {
+
+        for (date, quote) in quotes {
+            sqlx::query("INSERT OR REPLACE INTO prices (date, usd_price, source) VALUES (?, ?, 'exact')")
+                .bind(date)
+                .bind(quote.usd_price)
+                .execute(&mut *tx)
+                .await?;
             sqlx::query(
-                "INSERT OR REPLACE INTO token_flows
-                 (signature, slot, timestamp, owner, mint, delta_amount, owner_sol_delta_lamports)
-                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+                "INSERT OR REPLACE INTO price_audit (date, usd_price, provider, source_timestamp)
+                 VALUES (?, ?, ?, ?)",
             )
-            .bind(&f.signature)
-            .bind(f.slot as i64)
-            .bind(f.timestamp)
-            .bind(f.owner.to_string())
-            .bind(f.mint.to_string())
-            .bind(f.delta_amount)
-            .bind(f.owner_sol_delta_lamports)
+            .bind(date)
+            .bind(quote.usd_price)
+            .bind(quote.provider.as_str())
+            .bind(quote.source_timestamp)
             .execute(&mut *tx)
             .await?;
         }
+
         tx.commit().await?;
         Ok(())
     }
 
-    /// Sum of reward-like token inflows for a given owner/mint (in token base units).
-    ///
-    /// We classify "reward-like" as: token delta is positive and the owner system account didn't
-    /// spend more than `max_owner_sol_out_lamports` SOL (fees/rent) in the same tx.
-    #[allow(dead_code)]
-    pub async fn get_reward_like_token_inflows(
-        &self,
-        owner: &Pubkey,
-        mint: &Pubkey,
-        max_owner_sol_out_lamports: i64,
-    ) -> Result<u64> {
-        let lower_bound = -max_owner_sol_out_lamports.abs();
-
-        let row: (Option<i64>,) = sqlx::query_as(
-            "SELECT SUM(delta_amount) FROM token_flows
-             WHERE owner = ?
-               AND mint = ?
-               AND delta_amount > 0
-               AND owner_sol_delta_lamports < 0
-               AND owner_sol_delta_lamports >= ?",
+    /// Cached daily prices in `from..=to` (YYYY-MM-DD) with their provenance, oldest first.
+    /// Prices cached before the audit table existed have no provider.
+    pub async fn get_audited_prices(&self, from: &str, to: &str) -> Result<Vec<AuditedPrice>> {
+        let rows: Vec<(String, f64, Option<String>, Option<i64>, Option<String>)> = sqlx::query_as(
+            "SELECT p.date, p.usd_price, a.provider, a.source_timestamp, a.fetched_at
+             FROM prices p
+             LEFT JOIN price_audit a ON a.date = p.date AND a.usd_price = p.usd_price
+             WHERE p.date BETWEEN ? AND ?
+             ORDER BY p.date",
         )
-        .bind(owner.to_string())
-        .bind(mint.to_string())
-        .bind(lower_bound)
-        .fetch_one(&self.pool)
-        .await
-        .unwrap_or((None,));
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
 
-        Ok(row.0.unwrap_or(0).max(0) as u64)
+        Ok(rows
+            .into_iter()
+            .map(
+                |(date, usd_price, provider, source_timestamp, fetched_at)| AuditedPrice {
+                    date,
+                    usd_price,
+                    provider: provider.as_deref().and_then(PriceProvider::parse),
+                    source_timestamp,
+                    fetched_at,
+                },
+            )
+            .collect())
     }
 
-    pub async fn get_reward_like_token_inflows_up_to_slot(
-        &self,
-        owner: &Pubkey,
-        mint: &Pubkey,
-        max_owner_sol_out_lamports: i64,
-        max_slot: u64,
-    ) -> Result<u64> {
-        let lower_bound = -max_owner_sol_out_lamports.abs();
+    /// Get cached hourly prices
+    pub async fn get_hourly_prices(&self) -> Result<HourlyPriceCache> {
+        let rows: Vec<(i64, f64)> = sqlx::query_as("SELECT hour_ts, usd_price FROM hourly_prices")
+            .fetch_all(&self.pool)
+            .await?;
 
-        let row: (Option<i64>,) = sqlx::query_as(
-            "SELECT SUM(delta_amount) FROM token_flows
-             WHERE owner = ?
-               AND mint = ?
-               AND slot <= ?
-               AND delta_amount > 0
-               AND owner_sol_delta_lamports < 0
-               AND owner_sol_delta_lamports >= ?",
+        Ok(rows.into_iter().collect())
+    }
+
+    /// Store hourly prices (in a transaction for atomicity)
+    pub async fn store_hourly_prices(&self, prices: &HourlyPriceCache) -> Result<()> {
+        if prices.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        for (hour_ts, price) in prices {
+            sqlx::query("INSERT OR REPLACE INTO hourly_prices (hour_ts, usd_price) VALUES (?, ?)")
+                .bind(hour_ts)
+                .bind(price)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    // =========================================================================
+    // Metadata
+    // =========================================================================
+
+    /// Get metadata value
+    pub async fn get_metadata(&self, key: &str) -> Result<Option<String>> {
+        let row: Option<(String,)> = sqlx::query_as("SELECT value FROM metadata WHERE key = ?")
+            .bind(key)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(|(v,)| v))
+    }
+
+    /// Set metadata value
+    pub async fn set_metadata(&self, key: &str, value: &str) -> Result<()> {
+        sqlx::query("INSERT OR REPLACE INTO metadata (key, value) VALUES (?, ?)")
+            .bind(key)
+            .bind(value)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Whether the cache was dated under a different timezone than `timezone`, so
+    /// `sync_accounting_timezone` will rewrite it
+    pub async fn accounting_timezone_changed(&self, timezone: &str) -> Result<bool> {
+        let previous = self.get_metadata(ACCOUNTING_TIMEZONE_KEY).await?;
+        Ok(previous.as_deref().unwrap_or("UTC") != timezone)
+    }
+
+    /// Re-date cached rows if they were attributed under a different accounting timezone
+    ///
+    /// Transfers, failed and ops fees and balance snapshots are re-dated from their block
+    /// timestamps and epoch-based rows from `epoch_to_date` (which must already use the new
+    /// timezone). Daily prices are keyed by local day, so they are dropped and refetched,
+    /// along with the income lots valued at them. Caches that predate the setting
+    /// were built in UTC. Returns the previous timezone when rows were re-dated.
+    pub async fn sync_accounting_timezone(&self, timezone: &str) -> Result<Option<String>> {
+        let previous = self
+            .get_metadata(ACCOUNTING_TIMEZONE_KEY)
+            .await?
+            .unwrap_or_else(|| "UTC".to_string());
+        if previous == timezone {
+            self.set_metadata(ACCOUNTING_TIMEZONE_KEY, timezone).await?;
+            return Ok(None);
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        // (table, block time column)
+        let timestamp_tables = [
+            ("sol_transfers", "timestamp"),
+            ("failed_tx_fees", "block_time"),
+            ("ops_fees", "block_time"),
+            ("balance_history", "block_time"),
+        ];
+        for (table, column) in timestamp_tables {
+            let timestamps: Vec<(i64,)> = sqlx::query_as(&format!(
+                "SELECT DISTINCT {column} FROM {table} WHERE {column} IS NOT NULL"
+            ))
+            .fetch_all(&mut *tx)
+            .await?;
+            for (ts,) in timestamps {
+                if let Some(date) = timezone::timestamp_to_date(ts) {
+                    sqlx::query(&format!("UPDATE {table} SET date = ? WHERE {column} = ?"))
+                        .bind(date)
+                        .bind(ts)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+            }
+        }
+
+        // (table, epoch offset): DoubleZero accruals are dated at the epoch's end
+        let epoch_tables = [
+            ("epoch_rewards", 0),
+            ("leader_fees", 0),
+            ("mev_claims", 0),
+            ("bam_claims", 0),
+            ("vote_costs", 0),
+            ("doublezero_fees", 1),
+        ];
+        for (table, offset) in epoch_tables {
+            let epochs: Vec<(i64,)> = sqlx::query_as(&format!("SELECT DISTINCT epoch FROM {}", table))
+                .fetch_all(&mut *tx)
+                .await?;
+            for (epoch,) in epochs {
+                let date = epoch_to_date((epoch as u64).saturating_add(offset));
+                sqlx::query(&format!("UPDATE {} SET date = ? WHERE epoch = ?", table))
+                    .bind(date)
+                    .bind(epoch)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        // Income lots are dated and priced per day; the next report run records them again
+        sqlx::query("DELETE FROM prices").execute(&mut *tx).await?;
+        sqlx::query("DELETE FROM price_audit").execute(&mut *tx).await?;
+        sqlx::query("DELETE FROM income_lots").execute(&mut *tx).await?;
+        sqlx::query("INSERT OR REPLACE INTO metadata (key, value) VALUES (?, ?)")
+            .bind(ACCOUNTING_TIMEZONE_KEY)
+            .bind(timezone)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(Some(previous))
+    }
+
+    // =========================================================================
+    // Expenses
+    // =========================================================================
+
+    /// Get all expenses
+    pub async fn get_expenses(&self) -> Result<Vec<Expense>> {
+        self.query_expenses("1 = 1").await
+    }
+
+    /// Approved expenses only: what the reports count
+    pub async fn get_approved_expenses(&self) -> Result<Vec<Expense>> {
+        self.query_expenses("status = 'approved'").await
+    }
+
+    /// Pending and rejected expenses, listed apart from the reports
+    pub async fn get_unapproved_expenses(&self) -> Result<Vec<Expense>> {
+        self.query_expenses("status != 'approved'").await
+    }
+
+    async fn query_expenses(&self, filter: &str) -> Result<Vec<Expense>> {
+        let rows: Vec<ExpenseRow> = sqlx::query_as(&format!(
+            "SELECT id, date, vendor, category, description, amount_usd, paid_with, invoice_id, entity, status
+             FROM expenses
+             WHERE {}
+             ORDER BY date, id",
+            filter
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| Expense {
+                id: Some(r.id),
+                date: r.date,
+                vendor: r.vendor,
+                category: string_to_expense_category(&r.category),
+                description: r.description,
+                amount_usd: r.amount_usd,
+                paid_with: r.paid_with,
+                invoice_id: r.invoice_id,
+                entity: r.entity,
+                status: ExpenseStatus::from_db(&r.status),
+            })
+            .collect())
+    }
+
+    /// Add a new expense, returns the ID
+    pub async fn add_expense(&self, expense: &Expense) -> Result<i64> {
+        let category_str = match expense.category {
+            ExpenseCategory::Hosting => "Hosting",
+            ExpenseCategory::Contractor => "Contractor",
+            ExpenseCategory::Hardware => "Hardware",
+            ExpenseCategory::Software => "Software",
+            ExpenseCategory::VoteFees => "VoteFees",
+            ExpenseCategory::FailedTxFees => "FailedTxFees",
+            ExpenseCategory::OpsFees => "OpsFees",
+            ExpenseCategory::Other => "Other",
+        };
+
+        let result = sqlx::query(
+            "INSERT INTO expenses (date, vendor, category, description, amount_usd, paid_with, invoice_id, entity, status)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&expense.date)
+        .bind(&expense.vendor)
+        .bind(category_str)
+        .bind(&expense.description)
+        .bind(expense.amount_usd)
+        .bind(&expense.paid_with)
+        .bind(&expense.invoice_id)
+        .bind(&expense.entity)
+        .bind(expense.status.as_str())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Set the review status of the given expenses, returns how many rows changed
+    pub async fn set_expense_status(&self, ids: &[i64], status: ExpenseStatus) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        let mut updated = 0;
+        for id in ids {
+            updated += sqlx::query("UPDATE expenses SET status = ? WHERE id = ?")
+                .bind(status.as_str())
+                .bind(id)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+        }
+        tx.commit().await?;
+        Ok(updated)
+    }
+
+    /// Approve every pending expense, returns how many there were
+    pub async fn approve_pending_expenses(&self) -> Result<u64> {
+        let result = sqlx::query("UPDATE expenses SET status = 'approved' WHERE status = 'pending'")
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Delete an expense by ID
+    pub async fn delete_expense(&self, id: i64) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM expenses WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Import multiple expenses (for bulk import from CSV); they arrive pending review
+    pub async fn import_expenses(&self, expenses: &[Expense]) -> Result<usize> {
+        let mut count = 0;
+        for expense in expenses {
+            let pending = Expense {
+                status: ExpenseStatus::Pending,
+                ..expense.clone()
+            };
+            self.add_expense(&pending).await?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Replace Notion-derived contractor expenses atomically.
+    ///
+    /// Rows are identified by `paid_with IN ('Notion Paid', 'Notion Unpaid')`.
+    /// Manual expenses are untouched. A page that was already reviewed keeps its status
+    /// (matched on the page id in `invoice_id`); new pages arrive pending.
+    pub async fn sync_notion_expenses(&self, notion_expenses: &[Expense]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let reviewed: HashMap<String, String> = sqlx::query_as::<_, (String, String)>(
+            "SELECT invoice_id, status FROM expenses
+             WHERE paid_with IN ('Notion Paid', 'Notion Unpaid') AND invoice_id IS NOT NULL",
+        )
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .collect();
+
+        // Remove previous Notion-derived rows.
+        sqlx::query("DELETE FROM expenses WHERE paid_with IN ('Notion Paid', 'Notion Unpaid')")
+            .execute(&mut *tx)
+            .await?;
+
+        // Insert the fresh snapshot.
+        for expense in notion_expenses {
+            let category_str = match expense.category {
+                ExpenseCategory::Hosting => "Hosting",
+                ExpenseCategory::Contractor => "Contractor",
+                ExpenseCategory::Hardware => "Hardware",
+                ExpenseCategory::Software => "Software",
+                ExpenseCategory::VoteFees => "VoteFees",
+                ExpenseCategory::FailedTxFees => "FailedTxFees",
+                ExpenseCategory::OpsFees => "OpsFees",
+                ExpenseCategory::Other => "Other",
+            };
+
+            let status = expense
+                .invoice_id
+                .as_ref()
+                .and_then(|page| reviewed.get(page))
+                .map_or(expense.status.as_str(), String::as_str);
+
+            sqlx::query(
+                "INSERT INTO expenses (date, vendor, category, description, amount_usd, paid_with, invoice_id, entity, status)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&expense.date)
+            .bind(&expense.vendor)
+            .bind(category_str)
+            .bind(&expense.description)
+            .bind(expense.amount_usd)
+            .bind(&expense.paid_with)
+            .bind(&expense.invoice_id)
+            .bind(&expense.entity)
+            .bind(status)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    // =========================================================================
+    // Recurring Expenses
+    // =========================================================================
+
+    /// Get all recurring expenses
+    pub async fn get_recurring_expenses(&self) -> Result<Vec<RecurringExpense>> {
+        let rows: Vec<RecurringExpenseRow> = sqlx::query_as(
+            "SELECT id, vendor, category, description, amount_usd, paid_with, start_date, end_date, entity
+             FROM recurring_expenses
+             ORDER BY vendor, start_date",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| {
+                let category = match r.category.as_str() {
+                    "Hosting" => ExpenseCategory::Hosting,
+                    "Contractor" => ExpenseCategory::Contractor,
+                    "Hardware" => ExpenseCategory::Hardware,
+                    "Software" => ExpenseCategory::Software,
+                    "VoteFees" => ExpenseCategory::VoteFees,
+                    "FailedTxFees" => ExpenseCategory::FailedTxFees,
+                    "OpsFees" => ExpenseCategory::OpsFees,
+                    _ => ExpenseCategory::Other,
+                };
+
+                RecurringExpense {
+                    id: Some(r.id),
+                    vendor: r.vendor,
+                    category,
+                    description: r.description,
+                    amount_usd: r.amount_usd,
+                    paid_with: r.paid_with,
+                    start_date: r.start_date,
+                    end_date: r.end_date,
+                    entity: r.entity,
+                }
+            })
+            .collect())
+    }
+
+    /// Add a new recurring expense, returns the ID
+    pub async fn add_recurring_expense(&self, expense: &RecurringExpense) -> Result<i64> {
+        let category_str = match expense.category {
+            ExpenseCategory::Hosting => "Hosting",
+            ExpenseCategory::Contractor => "Contractor",
+            ExpenseCategory::Hardware => "Hardware",
+            ExpenseCategory::Software => "Software",
+            ExpenseCategory::VoteFees => "VoteFees",
+            ExpenseCategory::FailedTxFees => "FailedTxFees",
+            ExpenseCategory::OpsFees => "OpsFees",
+            ExpenseCategory::Other => "Other",
+        };
+
+        let result = sqlx::query(
+            "INSERT INTO recurring_expenses (vendor, category, description, amount_usd, paid_with, start_date, end_date, entity)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&expense.vendor)
+        .bind(category_str)
+        .bind(&expense.description)
+        .bind(expense.amount_usd)
+        .bind(&expense.paid_with)
+        .bind(&expense.start_date)
+        .bind(&expense.end_date)
+        .bind(&expense.entity)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Delete a recurring expense by ID
+    pub async fn delete_recurring_expense(&self, id: i64) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM recurring_expenses WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // =========================================================================
+    // SOL Transfers
+    // =========================================================================
+
+    /// Rewrite the labels and categories of already-cached transfers (amounts and dates untouched)
+    pub async fn update_transfer_labels(&self, transfers: &[SolTransfer]) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        let mut updated = 0;
+
+        for transfer in transfers {
+            updated += sqlx::query(
+                "UPDATE sol_transfers
+                 SET from_label = ?, to_label = ?, from_category = ?, to_category = ?
+                 WHERE signature = ? AND from_address = ? AND to_address = ? AND amount_lamports = ?",
+            )
+            .bind(&transfer.from_label)
+            .bind(&transfer.to_label)
+            .bind(category_to_string(&transfer.from_category))
+            .bind(category_to_string(&transfer.to_category))
+            .bind(&transfer.signature)
+            .bind(transfer.from.to_string())
+            .bind(transfer.to.to_string())
+            .bind(transfer.amount_lamports as i64)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        }
+
+        tx.commit().await?;
+        Ok(updated)
+    }
+
+    /// Get all cached transfers
+    pub async fn get_all_transfers(&self) -> Result<Vec<SolTransfer>> {
+        let rows: Vec<SolTransferRow> = sqlx::query_as(
+            "SELECT signature, slot, timestamp, date, from_address, to_address,
+                    amount_lamports, amount_sol, from_label, to_label,
+                    from_category, to_category, memo, program
+             FROM sol_transfers
+             ORDER BY slot DESC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().filter_map(row_to_transfer).collect())
+    }
+
+    /// Get the highest slot we've checked for an account (even if no transfers were found)
+    /// This is useful for accounts with only versioned/undecodable transactions
+    pub async fn get_account_progress(&self, account_key: &str) -> Result<Option<u64>> {
+        // We track per-account progress independently of transfer storage.
+        let progress_row: Option<(i64,)> =
+            sqlx::query_as("SELECT highest_slot FROM account_progress WHERE account_key = ?")
+                .bind(account_key)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        let progress_slot = progress_row.map(|(s,)| s as u64);
+        Ok(progress_slot)
+    }
+
+    /// Store the highest slot we've checked for an account
+    pub async fn set_account_progress(&self, account_key: &str, highest_slot: u64) -> Result<()> {
+        sqlx::query("INSERT OR REPLACE INTO account_progress (account_key, highest_slot) VALUES (?, ?)")
+            .bind(account_key)
+            .bind(highest_slot as i64)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Store transfers (in a transaction for atomicity)
+    ///
+    /// Re-storing a transfer replaces it, except that a known memo/program is kept when the
+    /// new copy has none (Dune backfills don't carry them).
+    pub async fn store_transfers(&self, transfers: &[SolTransfer]) -> Result<()> {
+        if transfers.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        for transfer in transfers {
+            sqlx::query(
+                "INSERT INTO sol_transfers
+                 (signature, slot, timestamp, date, from_address, to_address,
+                  amount_lamports, amount_sol, from_label, to_label,
+                  from_category, to_category, memo, program)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                 ON CONFLICT (signature, from_address, to_address, amount_lamports) DO UPDATE SET
+                    slot = excluded.slot,
+                    timestamp = excluded.timestamp,
+                    date = excluded.date,
+                    amount_sol = excluded.amount_sol,
+                    from_label = excluded.from_label,
+                    to_label = excluded.to_label,
+                    from_category = excluded.from_category,
+                    to_category = excluded.to_category,
+                    fetched_at = datetime('now'),
+                    memo = COALESCE(excluded.memo, sol_transfers.memo),
+                    program = COALESCE(excluded.program, sol_transfers.program)",
+            )
+            .bind(&transfer.signature)
+            .bind(transfer.slot as i64)
+            .bind(transfer.timestamp)
+            .bind(&transfer.date)
+            .bind(transfer.from.to_string())
+            .bind(transfer.to.to_string())
+            .bind(transfer.amount_lamports as i64)
+            .bind(transfer.amount_sol)
+            .bind(&transfer.from_label)
+            .bind(&transfer.to_label)
+            .bind(category_to_string(&transfer.from_category))
+            .bind(category_to_string(&transfer.to_category))
+            .bind(&transfer.memo)
+            .bind(&transfer.program)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    // =========================================================================
+    // Token Flows (SPL tokens)
+    // =========================================================================
+
+    /// Store SPL token balance deltas by tx signature.
+    pub async fn store_token_flows(&self, flows: &[TokenFlow]) -> Result<()> {
+        if flows.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self.pool.begin().await?;
+        for f in flows {
+            sqlx::query(
+                "INSERT OR REPLACE INTO token_flows
+                 (signature, slot, timestamp, owner, mint, delta_amount, owner_sol_delta_lamports)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&f.signature)
+            .bind(f.slot as i64)
+            .bind(f.timestamp)
+            .bind(f.owner.to_string())
+            .bind(f.mint.to_string())
+            .bind(f.delta_amount)
+            .bind(f.owner_sol_delta_lamports)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Sum of reward-like token inflows for a given owner/mint (in token base units).
+    ///
+    /// We classify "reward-like" as: token delta is positive and the owner system account didn't
+    /// spend more than `max_owner_sol_out_lamports` SOL (fees/rent) in the same tx.
+    #[allow(dead_code)]
+    pub async fn get_reward_like_token_inflows(
+        &self,
+        owner: &Pubkey,
+        mint: &Pubkey,
+        max_owner_sol_out_lamports: i64,
+    ) -> Result<u64> {
+        let lower_bound = -max_owner_sol_out_lamports.abs();
+
+        let row: (Option<i64>,) = sqlx::query_as(
+            "SELECT SUM(delta_amount) FROM token_flows
+             WHERE owner = ?
+               AND mint = ?
+               AND delta_amount > 0
+               AND owner_sol_delta_lamports < 0
+               AND owner_sol_delta_lamports >= ?",
+        )
+        .bind(owner.to_string())
+        .bind(mint.to_string())
+        .bind(lower_bound)
+        .fetch_one(&self.pool)
+        .await
+        .unwrap_or((None,));
+
+        Ok(row.0.unwrap_or(0).max(0) as u64)
+    }
+
+    pub async fn get_reward_like_token_inflows_up_to_slot(
+        &self,
+        owner: &Pubkey,
+        mint: &Pubkey,
+        max_owner_sol_out_lamports: i64,
+        max_slot: u64,
+    ) -> Result<u64> {
+        let lower_bound = -max_owner_sol_out_lamports.abs();
+
+        let row: (Option<i64>,) = sqlx::query_as(
+            "SELECT SUM(delta_amount) FROM token_flows
+             WHERE owner = ?
+               AND mint = ?
+               AND slot <= ?
+               AND delta_amount > 0
+               AND owner_sol_delta_lamports < 0
+               AND owner_sol_delta_lamports >= ?",
+        )
+        .bind(owner.to_string())
+        .bind(mint.to_string())
+        .bind(max_slot as i64)
+        .bind(lower_bound)
+        .fetch_one(&self.pool)
+        .await
+        .unwrap_or((None,));
+
+        Ok(row.0.unwrap_or(0).max(0) as u64)
+    }
+
+    // =========================================================================
+    // Position Tracking
+    // =========================================================================
+
+    /// Get stake reward keys present for an epoch range (for cache backfill checks)
+    pub async fn get_stake_reward_keys(
+        &self,
+        start_epoch: u64,
+        end_epoch: u64,
+    ) -> Result<std::collections::HashSet<String>> {
+        if end_epoch < start_epoch {
+            return Ok(std::collections::HashSet::new());
+        }
+
+        let rows: Vec<(i64, String)> =
+            sqlx::query_as("SELECT epoch, stake_account FROM stake_rewards WHERE epoch >= ? AND epoch <= ?")
+                .bind(start_epoch as i64)
+                .bind(end_epoch as i64)
+                .fetch_all(&self.pool)
+                .await
+                .unwrap_or_default();
+
+        Ok(rows
+            .into_iter()
+            .map(|(epoch, account)| format!("{}:{}", epoch, account))
+            .collect())
+    }
+
+    /// Store stake-account inflation rewards.
+    ///
+    /// Uses INSERT OR REPLACE so re-fetching is idempotent.
+    pub async fn store_stake_rewards(&self, rewards: &[StakeEpochReward]) -> Result<()> {
+        if rewards.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self.pool.begin().await?;
+        for r in rewards {
+            sqlx::query(
+                "INSERT OR REPLACE INTO stake_rewards (epoch, stake_account, amount_lamports)
+                 VALUES (?, ?, ?)",
+            )
+            .bind(r.epoch as i64)
+            .bind(r.stake_account.to_string())
+            .bind(r.amount_lamports as i64)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Store stake accounts
+    /// Uses a single transaction for DELETE + INSERT to prevent data loss on failure
+    pub async fn store_stake_accounts(&self, stakes: &[StakeAccountInfo]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        // Clear old stake accounts first (inside transaction)
+        sqlx::query("DELETE FROM stake_accounts").execute(&mut *tx).await?;
+
+        // Insert new stake accounts
+        for s in stakes {
+            sqlx::query(
+                "INSERT INTO stake_accounts
+                 (account, balance_lamports, state, voter, lockup_epoch, is_liquid, snapshot_slot)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(s.account.to_string())
+            .bind(s.balance_lamports as i64)
+            .bind(s.state.to_string())
+            .bind(s.voter.map(|v| v.to_string()))
+            .bind(s.lockup_epoch.map(|e| e as i64))
+            .bind(if s.is_liquid { 1i64 } else { 0i64 })
+            .bind(s.snapshot_slot as i64)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Store a historical balance snapshot
+    pub async fn store_balance_snapshot(
+        &self,
+        position: &ValidatorPosition,
+        date: &str,
+        block_time: Option<i64>,
+        epoch: u64,
+    ) -> Result<()> {
+        sqlx::query(
+            "INSERT OR REPLACE INTO balance_history
+             (date, epoch, snapshot_slot, block_time, vote_account_lamports, identity_lamports,
+              withdraw_authority_lamports, squads_vault_lamports, business_wallets_lamports,
+              token_accounts_lamports, token_accounts_withdrawable_lamports, stake_liquid_lamports,
+              stake_locked_lamports, jitosol_lamports, jitosol_rate, total_lamports,
+              cumulative_income_lamports, cumulative_expenses_lamports, cumulative_withdrawals_lamports,
+              cumulative_deposits_lamports)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(date)
+        .bind(epoch as i64)
+        .bind(position.snapshot_slot as i64)
+        .bind(block_time)
+        .bind(position.vote_account_lamports as i64)
+        .bind(position.identity_lamports as i64)
+        .bind(position.withdraw_authority_lamports as i64)
+        .bind(position.squads_vault_lamports as i64)
+        .bind(position.business_wallets_lamports as i64)
+        .bind(position.token_accounts_lamports as i64)
+        .bind(position.token_accounts_withdrawable_lamports as i64)
+        .bind(position.stake_accounts_liquid as i64)
+        .bind(position.stake_accounts_locked as i64)
+        .bind(position.jitosol_lamports as i64)
+        .bind(position.jitosol_sol_rate)
+        .bind(position.total_assets_lamports as i64)
+        .bind(position.lifetime_income_lamports as i64)
+        .bind(position.lifetime_expenses_lamports as i64)
+        .bind(position.lifetime_withdrawals_lamports as i64)
+        .bind(position.lifetime_deposits_lamports as i64)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Most recent balance snapshot (from `position snapshot`), if any
+    pub async fn get_latest_balance_snapshot(&self) -> Result<Option<BalanceSnapshot>> {
+        let row: Option<BalanceSnapshotRow> = sqlx::query_as(
+            "SELECT date, epoch, snapshot_slot, vote_account_lamports, identity_lamports,
+                    withdraw_authority_lamports, squads_vault_lamports, business_wallets_lamports,
+                    stake_liquid_lamports, stake_locked_lamports, COALESCE(jitosol_lamports, 0) AS jitosol_lamports, total_lamports
+             FROM balance_history
+             ORDER BY snapshot_slot DESC
+             LIMIT 1",
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|r| BalanceSnapshot {
+            date: r.date,
+            epoch: r.epoch as u64,
+            snapshot_slot: r.snapshot_slot as u64,
+            vote_account_lamports: r.vote_account_lamports as u64,
+            identity_lamports: r.identity_lamports as u64,
+            withdraw_authority_lamports: r.withdraw_authority_lamports as u64,
+            squads_vault_lamports: r.squads_vault_lamports as u64,
+            business_wallets_lamports: r.business_wallets_lamports as u64,
+            stake_liquid_lamports: r.stake_liquid_lamports as u64,
+            stake_locked_lamports: r.stake_locked_lamports as u64,
+            jitosol_lamports: r.jitosol_lamports as u64,
+            total_lamports: r.total_lamports as u64,
+        }))
+    }
+
+    // =========================================================================
+    // Income/Expense Aggregation for Reconciliation
+    // =========================================================================
+
+    /// Get total lifetime income in lamports
+    /// Includes: staking rewards (commission), leader fees, MEV tips, BAM/LST rewards (token-aware)
+    #[allow(dead_code)]
+    pub async fn get_total_income_lamports(&self, config: &Config, jitosol_rate: f64) -> Result<u64> {
+        // Staking commission rewards
+        let rewards: (Option<i64>,) = sqlx::query_as("SELECT SUM(amount_lamports) FROM epoch_rewards")
+            .fetch_one(&self.pool)
+            .await?;
+        let rewards_lamports = rewards.0.unwrap_or(0).max(0) as u64;
+
+        // Stake-account inflation rewards (self-stake)
+        let stake: (Option<i64>,) = sqlx::query_as("SELECT SUM(amount_lamports) FROM stake_rewards")
+            .fetch_one(&self.pool)
+            .await
+            .unwrap_or((None,));
+        let stake_lamports = stake.0.unwrap_or(0).max(0) as u64;
+
+        // Leader slot fees
+        let leader: (Option<i64>,) = sqlx::query_as("SELECT SUM(total_fees_lamports) FROM leader_fees")
+            .fetch_one(&self.pool)
+            .await?;
+        let leader_lamports = leader.0.unwrap_or(0).max(0) as u64;
+
+        // Jito MEV commission
+        let mev: (Option<i64>,) = sqlx::query_as("SELECT SUM(commission_lamports) FROM mev_claims")
+            .fetch_one(&self.pool)
+            .await?;
+        let mev_lamports = mev.0.unwrap_or(0).max(0) as u64;
+
+        // BAM rewards are paid in jitoSOL. For reconciliation, we value reward-like jitoSOL inflows
+        // at the current jitoSOL->SOL rate (mark-to-market) so expected balance can match the
+        // current asset snapshot (which also values current holdings at the current rate).
+        let jitosol_mint = Pubkey::from_str(constants::JITOSOL_MINT).expect("Invalid JITOSOL_MINT constant");
+        let owners = config.signing_keys();
+        let mut jitosol_reward_token_lamports: u64 = 0;
+        for owner in owners {
+            jitosol_reward_token_lamports = jitosol_reward_token_lamports.saturating_add(
+                self.get_reward_like_token_inflows(
+                    &owner,
+                    &jitosol_mint,
+                    constants::TOKEN_REWARD_MAX_OWNER_SOL_OUT_LAMPORTS,
+                )
+                .await?,
+            );
+        }
+        let jitosol_income_lamports =
+            ((jitosol_reward_token_lamports as f64) * jitosol_rate).min(u64::MAX as f64) as u64;
+
+        Ok(rewards_lamports
+            .saturating_add(stake_lamports)
+            .saturating_add(leader_lamports)
+            .saturating_add(mev_lamports)
+            .saturating_add(jitosol_income_lamports))
+    }
+
+    pub async fn get_total_income_lamports_up_to(
+        &self,
+        config: &Config,
+        jitosol_rate: f64,
+        max_epoch: u64,
+        max_slot: u64,
+    ) -> Result<u64> {
+        let max_epoch_i64 = (max_epoch.min(i64::MAX as u64)) as i64;
+
+        // Staking commission rewards
+        let rewards: (Option<i64>,) = sqlx::query_as("SELECT SUM(amount_lamports) FROM epoch_rewards WHERE epoch <= ?")
+            .bind(max_epoch_i64)
+            .fetch_one(&self.pool)
+            .await?;
+        let rewards_lamports = rewards.0.unwrap_or(0).max(0) as u64;
+
+        // Stake-account inflation rewards (self-stake)
+        let stake: (Option<i64>,) = sqlx::query_as("SELECT SUM(amount_lamports) FROM stake_rewards WHERE epoch <= ?")
+            .bind(max_epoch_i64)
+            .fetch_one(&self.pool)
+            .await
+            .unwrap_or((None,));
+        let stake_lamports = stake.0.unwrap_or(0).max(0) as u64;
+
+        // Leader slot fees
+        let leader: (Option<i64>,) =
+            sqlx::query_as("SELECT SUM(total_fees_lamports) FROM leader_fees WHERE epoch <= ?")
+                .bind(max_epoch_i64)
+                .fetch_one(&self.pool)
+                .await?;
+        let leader_lamports = leader.0.unwrap_or(0).max(0) as u64;
+
+        // Jito MEV commission
+        let mev: (Option<i64>,) = sqlx::query_as("SELECT SUM(commission_lamports) FROM mev_claims WHERE epoch <= ?")
+            .bind(max_epoch_i64)
+            .fetch_one(&self.pool)
+            .await?;
+        let mev_lamports = mev.0.unwrap_or(0).max(0) as u64;
+
+        // BAM rewards are paid in jitoSOL. For reconciliation, we value reward-like jitoSOL inflows
+        // at the current jitoSOL->SOL rate (mark-to-market) so expected balance can match the
+        // current asset snapshot (which also values current holdings at the current rate).
+        let jitosol_mint = Pubkey::from_str(constants::JITOSOL_MINT).expect("Invalid JITOSOL_MINT constant");
+        let owners = config.signing_keys();
+        let mut jitosol_reward_token_lamports: u64 = 0;
+        for owner in owners {
+            jitosol_reward_token_lamports = jitosol_reward_token_lamports.saturating_add(
+                self.get_reward_like_token_inflows_up_to_slot(
+                    &owner,
+                    &jitosol_mint,
+                    constants::TOKEN_REWARD_MAX_OWNER_SOL_OUT_LAMPORTS,
+                    max_slot,
+                )
+                .await?,
+            );
+        }
+        let jitosol_income_lamports =
+            ((jitosol_reward_token_lamports as f64) * jitosol_rate).min(u64::MAX as f64) as u64;
+
+        Ok(rewards_lamports
+            .saturating_add(stake_lamports)
+            .saturating_add(leader_lamports)
+            .saturating_add(mev_lamports)
+            .saturating_add(jitosol_income_lamports))
+    }
+
+    /// Get total lifetime expenses in lamports (cash expenses).
+    ///
+    /// Includes:
+    /// - vote transaction costs
+    ///
+    /// Excludes:
+    /// - DoubleZero liabilities (accruals), since those are not necessarily paid yet and any
+    ///   actual on-chain payment will already appear as a withdrawal transfer.
+    ///
+    /// Note: USD expenses are not included (would need price conversion).
+    #[allow(dead_code)]
+    pub async fn get_total_expenses_lamports(&self) -> Result<u64> {
+        let vote_costs: (Option<i64>,) = sqlx::query_as("SELECT SUM(total_fee_lamports) FROM vote_costs")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(vote_costs.0.unwrap_or(0).max(0) as u64)
+    }
+
+    pub async fn get_total_expenses_lamports_up_to(&self, max_epoch: u64) -> Result<u64> {
+        let max_epoch_i64 = (max_epoch.min(i64::MAX as u64)) as i64;
+        let vote_costs: (Option<i64>,) =
+            sqlx::query_as("SELECT SUM(total_fee_lamports) FROM vote_costs WHERE epoch <= ?")
+                .bind(max_epoch_i64)
+                .fetch_one(&self.pool)
+                .await?;
+        Ok(vote_costs.0.unwrap_or(0).max(0) as u64)
+    }
+
+    pub async fn get_total_doublezero_liability_lamports_up_to(&self, max_epoch: u64) -> Result<u64> {
+        let max_epoch_i64 = (max_epoch.min(i64::MAX as u64)) as i64;
+        let row: (Option<i64>,) =
+            sqlx::query_as("SELECT SUM(liability_lamports) FROM doublezero_fees WHERE epoch <= ?")
+                .bind(max_epoch_i64)
+                .fetch_one(&self.pool)
+                .await
+                .unwrap_or((None,));
+        Ok(row.0.unwrap_or(0).max(0) as u64)
+    }
+
+    /// Get total lifetime withdrawals in lamports
+    /// Includes: transfers to exchanges or personal wallets
+    #[allow(dead_code)]
+    pub async fn get_total_withdrawals_lamports(&self, config: &Config) -> Result<u64> {
+        // Avoid relying on stored categories (which may be missing/wrong in older caches).
+        // Treat withdrawals as SOL leaving our validator treasury (vote/identity/withdraw authority)
+        // to external destinations. Exclude known internal routing destinations:
+        // - our core accounts
+        // - our stake accounts
+        //
+        // Important: we do NOT exclude DeFi/swaps here. If SOL is sent to a swap pool/router and we
+        // don't also include the resulting token holdings in the position snapshot, excluding those
+        // destinations would make reconciliation look artificially "high" (missing withdrawals).
+        // If/when we add comprehensive SPL-token balance tracking, we can revisit this.
+        //
+        // Note: transfers to the personal wallet are counted as withdrawals because the personal
+        // wallet is not part of the validator position snapshot.
+
+        let mut internal: std::collections::HashSet<String> = std::collections::HashSet::new();
+        for account in config.ledger_accounts() {
+            internal.insert(account.to_string());
+        }
+
+        // Token routing often involves wrapping SOL into the wallet's wSOL ATA or moving SOL through
+        // other common token ATAs. Those are still internal assets and should not be treated as withdrawals.
+        for wallet in config.ledger_accounts() {
+            for ata in crate::positions::compute_common_atas(&wallet) {
+                internal.insert(ata);
+            }
+        }
+
+        // Stake accounts are owned by the same withdraw authority and are part of validator assets.
+        // If we classify transfers to stake accounts as withdrawals, reconciliation will be wrong.
+        let stake_rows: Vec<(String,)> = sqlx::query_as("SELECT account FROM stake_accounts")
+            .fetch_all(&self.pool)
+            .await
+            .unwrap_or_default();
+        for (addr,) in stake_rows {
+            internal.insert(addr);
+        }
+
+        let treasury = config.ledger_accounts();
+        let personal_wallets: std::collections::HashSet<String> =
+            config.personal_wallets.iter().map(ToString::to_string).collect();
+
+        let sql = format!(
+            "SELECT to_address, amount_lamports
+             FROM sol_transfers
+             WHERE from_address IN ({})",
+            sql_placeholders(treasury.len())
+        );
+        let mut query = sqlx::query_as::<_, (String, i64)>(&sql);
+        for account in &treasury {
+            query = query.bind(account.to_string());
+        }
+        let rows = query.fetch_all(&self.pool).await?;
+
+        let mut total: u64 = 0;
+        for (to_str, amount) in rows {
+            if amount <= 0 {
+                continue;
+            }
+            // Always count transfers to external personal wallets as withdrawals.
+            if personal_wallets.contains(&to_str) && !internal.contains(&to_str) {
+                total = total.saturating_add(amount as u64);
+                continue;
+            }
+            // Count if destination is not internal.
+            if !internal.contains(&to_str) {
+                total = total.saturating_add(amount as u64);
+            }
+        }
+
+        Ok(total)
+    }
+
+    pub async fn get_total_withdrawals_lamports_up_to(&self, config: &Config, max_slot: u64) -> Result<u64> {
+        // Same logic as `get_total_withdrawals_lamports`, but bounded by slot for snapshot consistency.
+        let mut internal: std::collections::HashSet<String> = std::collections::HashSet::new();
+        for account in config.ledger_accounts() {
+            internal.insert(account.to_string());
+        }
+
+        for wallet in config.ledger_accounts() {
+            for ata in crate::positions::compute_common_atas(&wallet) {
+                internal.insert(ata);
+            }
+        }
+
+        let stake_rows: Vec<(String,)> = sqlx::query_as("SELECT account FROM stake_accounts")
+            .fetch_all(&self.pool)
+            .await
+            .unwrap_or_default();
+        for (addr,) in stake_rows {
+            internal.insert(addr);
+        }
+
+        let treasury = config.ledger_accounts();
+        let personal_wallets: std::collections::HashSet<String> =
+            config.personal_wallets.iter().map(ToString::to_string).collect();
+
+        let sql = format!(
+            "SELECT to_address, amount_lamports
+             FROM sol_transfers
+             WHERE slot <= ?
+               AND from_address IN ({})",
+            sql_placeholders(treasury.len())
+        );
+        let mut query = sqlx::query_as::<_, (String, i64)>(&sql).bind(max_slot as i64);
+        for account in &treasury {
+            query = query.bind(account.to_string());
+        }
+        let rows = query.fetch_all(&self.pool).await?;
+
+        let mut total: u64 = 0;
+        for (to_str, amount) in rows {
+            if amount <= 0 {
+                continue;
+            }
+            if personal_wallets.contains(&to_str) && !internal.contains(&to_str) {
+                total = total.saturating_add(amount as u64);
+                continue;
+            }
+            if !internal.contains(&to_str) {
+                total = total.saturating_add(amount as u64);
+            }
+        }
+
+        Ok(total)
+    }
+
+    /// Get total lifetime deposits in lamports
+    ///
+    /// For reconciliation, "deposits" means any external SOL inflow to the validator treasury
+    /// (vote/identity/withdraw authority). This includes:
+    /// - transfers from the configured personal wallet (capital contributions)
+    /// - SFDP reimbursements to validator accounts (vote cost reimbursements)
+    /// - swap/DeFi proceeds that return SOL to our treasury accounts
+    ///
+    /// Note: we exclude transfers from internal addresses (core accounts, stake accounts, common ATAs)
+    /// and exclude known Jito MEV distribution addresses to avoid double-counting with `mev_claims`.
+    #[allow(dead_code)]
+    pub async fn get_total_deposits_lamports(&self, config: &Config) -> Result<u64> {
+        let mut internal: std::collections::HashSet<String> = std::collections::HashSet::new();
+        for account in config.ledger_accounts() {
+            internal.insert(account.to_string());
+        }
+
+        for wallet in config.ledger_accounts() {
+            for ata in crate::positions::compute_common_atas(&wallet) {
+                internal.insert(ata);
+            }
+        }
+
+        let stake_rows: Vec<(String,)> = sqlx::query_as("SELECT account FROM stake_accounts")
+            .fetch_all(&self.pool)
+            .await
+            .unwrap_or_default();
+        for (addr,) in stake_rows {
+            internal.insert(addr);
+        }
+
+        let treasury = config.ledger_accounts();
+        let sql = format!(
+            "SELECT from_address, amount_lamports
+             FROM sol_transfers
+             WHERE to_address IN ({})",
+            sql_placeholders(treasury.len())
+        );
+        let mut query = sqlx::query_as::<_, (String, i64)>(&sql);
+        for account in &treasury {
+            query = query.bind(account.to_string());
+        }
+        let rows = query.fetch_all(&self.pool).await?;
+
+        let mut total: u64 = 0;
+        for (from_str, amount) in rows {
+            if amount <= 0 {
+                continue;
+            }
+            if internal.contains(&from_str) {
+                continue;
+            }
+
+            // Avoid double-counting MEV deposits: those are accounted via `mev_claims`.
+            if let Ok(pk) = solana_sdk::pubkey::Pubkey::from_str(&from_str)
+                && crate::addresses::is_jito(&pk)
+            {
+                continue;
+            }
+
+            total = total.saturating_add(amount as u64);
+        }
+
+        Ok(total)
+    }
+
+    pub async fn get_total_deposits_lamports_up_to(&self, config: &Config, max_slot: u64) -> Result<u64> {
+        let mut internal: std::collections::HashSet<String> = std::collections::HashSet::new();
+        for account in config.ledger_accounts() {
+            internal.insert(account.to_string());
+        }
+
+        for wallet in config.ledger_accounts() {
+            for ata in crate::positions::compute_common_atas(&wallet) {
+                internal.insert(ata);
+            }
+        }
+
+        let stake_rows: Vec<(String,)> = sqlx::query_as("SELECT account FROM stake_accounts")
+            .fetch_all(&self.pool)
+            .await
+            .unwrap_or_default();
+        for (addr,) in stake_rows {
+            internal.insert(addr);
+        }
+
+        let treasury = config.ledger_accounts();
+        let sql = format!(
+            "SELECT from_address, amount_lamports
+             FROM sol_transfers
+             WHERE slot <= ?
+               AND to_address IN ({})",
+            sql_placeholders(treasury.len())
+        );
+        let mut query = sqlx::query_as::<_, (String, i64)>(&sql).bind(max_slot as i64);
+        for account in &treasury {
+            query = query.bind(account.to_string());
+        }
+        let rows = query.fetch_all(&self.pool).await?;
+
+        let mut total: u64 = 0;
+        for (from_str, amount) in rows {
+            if amount <= 0 {
+                continue;
+            }
+            if internal.contains(&from_str) {
+                continue;
+            }
+            if let Ok(pk) = solana_sdk::pubkey::Pubkey::from_str(&from_str)
+                && crate::addresses::is_jito(&pk)
+            {
+                continue;
+            }
+            total = total.saturating_add(amount as u64);
+        }
+
+        Ok(total)
+    }
+
+    /// Get all income/expense data for reconciliation
+    #[allow(dead_code)]
+    pub async fn get_reconciliation_data(
+        &self,
+        config: &Config,
+        jitosol_rate: f64,
+    ) -> Result<crate::positions::IncomeData> {
+        let total_income = self.get_total_income_lamports(config, jitosol_rate).await?;
+        let total_expenses = self.get_total_expenses_lamports().await?;
+        let total_withdrawals = self.get_total_withdrawals_lamports(config).await?;
+        let total_deposits = self.get_total_deposits_lamports(config).await?;
+
+        Ok(crate::positions::IncomeData {
+            total_income_lamports: total_income,
+            total_expenses_lamports: total_expenses,
+            total_withdrawals_lamports: total_withdrawals,
+            total_deposits_lamports: total_deposits,
+            token_deposits_lamports: 0,
+            internal_adjustment_lamports: 0,
+        })
+    }
+
+    pub async fn get_reconciliation_data_at_slot(
+        &self,
+        config: &Config,
+        jitosol_rate: f64,
+        max_slot: u64,
+    ) -> Result<crate::positions::IncomeData> {
+        let max_epoch = max_slot / constants::SLOTS_PER_EPOCH;
+
+        let total_income = self
+            .get_total_income_lamports_up_to(config, jitosol_rate, max_epoch, max_slot)
+            .await?;
+        let total_expenses = self.get_total_expenses_lamports_up_to(max_epoch).await?;
+        let total_withdrawals = self.get_total_withdrawals_lamports_up_to(config, max_slot).await?;
+        let sol_deposits = self.get_total_deposits_lamports_up_to(config, max_slot).await?;
+        let token_deposits = self
+            .get_jitosol_token_deposits_value_lamports_up_to_slot(config, jitosol_rate, max_slot)
+            .await?;
+        let total_deposits = sol_deposits.saturating_add(token_deposits);
+        let internal_adjustment = self
+            .get_jitosol_purchase_adjustment_lamports_up_to_slot(config, max_slot)
+            .await?
+            .min(i64::MAX as u64) as i64;
+
+        Ok(crate::positions::IncomeData {
+            total_income_lamports: total_income,
+            total_expenses_lamports: total_expenses,
+            total_withdrawals_lamports: total_withdrawals,
+            total_deposits_lamports: total_deposits,
+            token_deposits_lamports: token_deposits,
+            internal_adjustment_lamports: internal_adjustment,
+        })
+    }
+
+    /// Value (in lamports) of external jitoSOL transferred into the validator treasury.
+    ///
+    /// This captures cases where jitoSOL is acquired outside the treasury (e.g. personal wallet)
+    /// and then transferred into identity/withdraw authority, which would otherwise appear as an
+    /// unexplained surplus when jitoSOL is included in assets.
+    ///
+    /// Approximation: sum net jitoSOL delta across our owners for transactions where our owner SOL
+    /// delta is exactly 0 (we did not pay a fee and did not move SOL). Internal transfers between
+    /// our owners net to ~0 under this rule.
+    pub async fn get_jitosol_token_deposits_value_lamports_up_to_slot(
+        &self,
+        config: &Config,
+        jitosol_rate: f64,
+        max_slot: u64,
+    ) -> Result<u64> {
+        let jitosol_mint = Pubkey::from_str(constants::JITOSOL_MINT).expect("Invalid JITOSOL_MINT constant");
+
+        let owners = config.signing_keys();
+        let sql = format!(
+            "SELECT SUM(delta_amount) AS net_in
+             FROM token_flows
+             WHERE slot <= ?
+               AND mint = ?
+               AND owner IN ({})
+               AND owner_sol_delta_lamports = 0",
+            sql_placeholders(owners.len())
+        );
+        let mut query = sqlx::query_as::<_, (Option<i64>,)>(&sql)
+            .bind(max_slot as i64)
+            .bind(jitosol_mint.to_string());
+        for owner in &owners {
+            query = query.bind(owner.to_string());
+        }
+        let row = query.fetch_one(&self.pool).await.unwrap_or((None,));
+
+        let net_token_units = row.0.unwrap_or(0).max(0) as u64;
+        Ok(((net_token_units as f64) * jitosol_rate).min(u64::MAX as f64) as u64)
+    }
+
+    /// If SOL is swapped into jitoSOL (which we include in assets), the outgoing SOL transfer often
+    /// looks like a "withdrawal" to a DeFi router/pool. To keep reconciliation accurate, add an
+    /// internal adjustment equal to the SOL outflow associated with jitoSOL inflows.
+    ///
+    /// We approximate "purchase-like" inflows as: jitoSOL delta > 0 and owner SOL delta is more
+    /// negative than the reward-like threshold (i.e., not just fees/rent).
+    pub async fn get_jitosol_purchase_adjustment_lamports_up_to_slot(
+        &self,
+        config: &Config,
+        max_slot: u64,
+    ) -> Result<u64> {
+        let jitosol_mint = Pubkey::from_str(constants::JITOSOL_MINT).expect("Invalid JITOSOL_MINT constant");
+        let threshold = constants::TOKEN_REWARD_MAX_OWNER_SOL_OUT_LAMPORTS.abs();
+
+        let owners = config.signing_keys();
+        let sql = format!(
+            "SELECT SUM(-owner_sol_delta_lamports) AS sol_spent
+             FROM token_flows
+             WHERE slot <= ?
+               AND mint = ?
+               AND delta_amount > 0
+               AND owner IN ({})
+               AND owner_sol_delta_lamports < ?",
+            sql_placeholders(owners.len())
+        );
+        let mut query = sqlx::query_as::<_, (Option<i64>,)>(&sql)
+            .bind(max_slot as i64)
+            .bind(jitosol_mint.to_string());
+        for owner in &owners {
+            query = query.bind(owner.to_string());
+        }
+        let row = query.bind(-threshold).fetch_one(&self.pool).await.unwrap_or((None,));
+
+        Ok(row.0.unwrap_or(0).max(0) as u64)
+    }
+
+    /// Get external transfer summary for reconciliation
+    /// Returns transfers to/from external addresses (excludes internal validator account transfers)
+    ///
+    /// `internal_addresses` - addresses to exclude (vote account, identity, withdraw authority)
+    pub async fn get_external_transfer_summary(
+        &self,
+        internal_addresses: &[String],
+    ) -> Result<ExternalTransferSummary> {
+        // Build exclusion list for SQL
+        let internal_set: std::collections::HashSet<&str> = internal_addresses.iter().map(|s| s.as_str()).collect();
+
+        // Deposits IN from external addresses (exclude internal-to-internal)
+        let all_deposits: Vec<(String, String, String, String, i64)> = sqlx::query_as(
+            "SELECT from_address, to_address, from_label, from_category, SUM(amount_lamports) as total
+             FROM sol_transfers
+             GROUP BY from_address, to_address, from_label, from_category
+             ORDER BY total DESC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        // Filter: from_address is external (not in internal_addresses)
+        let mut deposits_in: Vec<ExternalAddressFlow> = all_deposits
+            .iter()
+            .filter(|(from, to, _, _, _)| {
+                // Include if FROM is external and TO is internal (deposit to us)
+                !internal_set.contains(from.as_str()) && internal_set.contains(to.as_str())
+            })
+            .fold(
+                std::collections::HashMap::new(),
+                |mut acc, (from, _, label, category, amount)| {
+                    let entry = acc
+                        .entry(from.clone())
+                        .or_insert((label.clone(), category.clone(), 0i64));
+                    entry.2 += amount;
+                    acc
+                },
+            )
+            .into_iter()
+            .map(|(addr, (label, category, amount))| ExternalAddressFlow {
+                address: addr,
+                label: if label.is_empty() { category } else { label },
+                amount_lamports: amount.max(0) as u64,
+            })
+            .collect();
+        deposits_in.sort_by_key(|a| std::cmp::Reverse(a.amount_lamports));
+        deposits_in.truncate(10);
+
+        // Withdrawals OUT to external addresses
+        let all_withdrawals: Vec<(String, String, String, String, i64)> = sqlx::query_as(
+            "SELECT from_address, to_address, to_label, to_category, SUM(amount_lamports) as total
+             FROM sol_transfers
+             GROUP BY from_address, to_address, to_label, to_category
+             ORDER BY total DESC",
         )
-        .bind(owner.to_string())
-        .bind(mint.to_string())
-        .bind(max_slot as i64)
-        .bind(lower_bound)
-        .fetch_one(&self.pool)
-        .await
-        .unwrap_or((None,));
+        .fetch_all(&self.pool)
+        .await?;
 
-        Ok(row.0.unwrap_or(0).max(0) as u64)
+        // Filter: to_address is external (not in internal_addresses)
+        let mut withdrawals_out: Vec<ExternalAddressFlow> = all_withdrawals
+            .iter()
+            .filter(|(from, to, _, _, _)| {
+                // Include if FROM is internal and TO is external (withdrawal from us)
+                internal_set.contains(from.as_str()) && !internal_set.contains(to.as_str())
+            })
+            .fold(
+                std::collections::HashMap::new(),
+                |mut acc, (_, to, label, category, amount)| {
+                    let entry = acc.entry(to.clone()).or_insert((label.clone(), category.clone(), 0i64));
+                    entry.2 += amount;
+                    acc
+                },
+            )
+            .into_iter()
+            .map(|(addr, (label, category, amount))| ExternalAddressFlow {
+                address: addr,
+                label: if label.is_empty() { category } else { label },
+                amount_lamports: amount.max(0) as u64,
+            })
+            .collect();
+        withdrawals_out.sort_by_key(|a| std::cmp::Reverse(a.amount_lamports));
+        withdrawals_out.truncate(10);
+
+        Ok(ExternalTransferSummary {
+            deposits_in,
+            withdrawals_out,
+        })
     }
 
     // =========================================================================
-    // Position Tracking
+    // Epoch Coverage
     // =========================================================================
 
-    /// Get stake reward keys present for an epoch range (for cache backfill checks)
-    pub async fn get_stake_reward_keys(
+    /// Which per-epoch sources have cached rows, for every epoch in a window.
+    ///
+    /// The window defaults to the first cached reward epoch through the latest
+    /// epoch seen in any per-epoch table.
+    pub async fn get_epoch_coverage(
         &self,
-        start_epoch: u64,
-        end_epoch: u64,
-    ) -> Result<std::collections::HashSet<String>> {
-        if end_epoch < start_epoch {
-            return Ok(std::collections::HashSet::new());
-        }
-
-        let rows: Vec<(i64, String)> =
-            sqlx::query_as("SELECT epoch, stake_account FROM stake_rewards WHERE epoch >= ? AND epoch <= ?")
-                .bind(start_epoch as i64)
-                .bind(end_epoch as i64)
-                .fetch_all(&self.pool)
-                .await
-                .unwrap_or_default();
+        start_epoch: Option<u64>,
+        end_epoch: Option<u64>,
+    ) -> Result<Vec<EpochCoverage>> {
+        let rewards = self.get_cached_epochs("epoch_rewards").await?;
+        let leader_fees = self.get_cached_epochs("leader_fees").await?;
+        let mev = self.get_cached_epochs("mev_claims").await?;
+        let bam = self.get_cached_epochs("bam_claims").await?;
+        let vote_costs = self.get_cached_epochs("vote_costs").await?;
+        let doublezero = self.get_cached_epochs("doublezero_fees").await?;
+
+        let all = [&rewards, &leader_fees, &mev, &bam, &vote_costs, &doublezero];
+        let Some(start) = start_epoch
+            .or_else(|| rewards.first().copied())
+            .or_else(|| all.iter().filter_map(|s| s.first().copied()).min())
+        else {
+            return Ok(Vec::new());
+        };
+        let Some(end) = end_epoch.or_else(|| all.iter().filter_map(|s| s.last().copied()).max()) else {
+            return Ok(Vec::new());
+        };
 
-        Ok(rows
-            .into_iter()
-            .map(|(epoch, account)| format!("{}:{}", epoch, account))
+        Ok((start..=end)
+            .map(|epoch| EpochCoverage {
+                epoch,
+                rewards: rewards.contains(&epoch),
+                leader_fees: leader_fees.contains(&epoch),
+                mev: mev.contains(&epoch),
+                bam: bam.contains(&epoch),
+                vote_costs: vote_costs.contains(&epoch),
+                doublezero: doublezero.contains(&epoch),
+            })
             .collect())
     }
 
-    /// Store stake-account inflation rewards.
-    ///
-    /// Uses INSERT OR REPLACE so re-fetching is idempotent.
-    pub async fn store_stake_rewards(&self, rewards: &[StakeEpochReward]) -> Result<()> {
-        if rewards.is_empty() {
-            return Ok(());
-        }
+    /// Newest epoch with a row in a per-epoch table
+    pub async fn latest_cached_epoch(&self, table: &str) -> Result<Option<u64>> {
+        let (epoch,): (Option<i64>,) = sqlx::query_as(&format!("SELECT MAX(epoch) FROM {}", table))
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(epoch.map(|e| e as u64))
+    }
 
-        let mut tx = self.pool.begin().await?;
-        for r in rewards {
-            sqlx::query(
-                "INSERT OR REPLACE INTO stake_rewards (epoch, stake_account, amount_lamports)
-                 VALUES (?, ?, ?)",
-            )
-            .bind(r.epoch as i64)
-            .bind(r.stake_account.to_string())
-            .bind(r.amount_lamports as i64)
-            .execute(&mut *tx)
+    /// Newest `date` (YYYY-MM-DD) in a dated table
+    pub async fn latest_cached_date(&self, table: &str) -> Result<Option<String>> {
+        let (date,): (Option<String>,) = sqlx::query_as(&format!("SELECT MAX(date) FROM {}", table))
+            .fetch_one(&self.pool)
             .await?;
-        }
-        tx.commit().await?;
-        Ok(())
+        Ok(date)
     }
 
-    /// Store stake accounts
-    /// Uses a single transaction for DELETE + INSERT to prevent data loss on failure
-    pub async fn store_stake_accounts(&self, stakes: &[StakeAccountInfo]) -> Result<()> {
-        let mut tx = self.pool.begin().await?;
+    /// Distinct epochs with at least one row in a per-epoch table
+    async fn get_cached_epochs(&self, table: &str) -> Result<BTreeSet<u64>> {
+        let rows: Vec<(i64,)> = sqlx::query_as(&format!("SELECT DISTINCT epoch FROM {}", table))
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows.into_iter().map(|(e,)| e as u64).collect())
+    }
 
-        // Clear old stake accounts first (inside transaction)
-        sqlx::query("DELETE FROM stake_accounts").execute(&mut *tx).await?;
+    // =========================================================================
+    // Ingestion Runs
+    // =========================================================================
 
-        // Insert new stake accounts
-        for s in stakes {
-            sqlx::query(
-                "INSERT INTO stake_accounts
-                 (account, balance_lamports, state, voter, lockup_epoch, is_liquid, snapshot_slot)
-                 VALUES (?, ?, ?, ?, ?, ?, ?)",
-            )
-            .bind(s.account.to_string())
-            .bind(s.balance_lamports as i64)
-            .bind(s.state.to_string())
-            .bind(s.voter.map(|v| v.to_string()))
-            .bind(s.lockup_epoch.map(|e| e as i64))
-            .bind(if s.is_liquid { 1i64 } else { 0i64 })
-            .bind(s.snapshot_slot as i64)
-            .execute(&mut *tx)
+    /// Record the start of an ingestion run, returns the run ID
+    pub async fn start_ingestion_run(&self, triggered_by: &str) -> Result<i64> {
+        let result = sqlx::query("INSERT INTO ingestion_runs (triggered_by, status) VALUES (?, 'running')")
+            .bind(triggered_by)
+            .execute(&self.pool)
             .await?;
-        }
 
-        tx.commit().await?;
-        Ok(())
+        Ok(result.last_insert_rowid())
     }
 
-    /// Store a historical balance snapshot
-    pub async fn store_balance_snapshot(&self, position: &ValidatorPosition, date: &str, epoch: u64) -> Result<()> {
+    /// Mark an ingestion run as finished.
+    ///
+    /// Status is `failed` if the pipeline aborted, `partial` if any source reported
+    /// a non-fatal error, and `success` otherwise.
+    pub async fn finish_ingestion_run(
+        &self,
+        id: i64,
+        log: &IngestionRunLog,
+        failure: Option<&anyhow::Error>,
+    ) -> Result<()> {
+        let mut errors = log.errors.clone();
+        let status = match failure {
+            Some(e) => {
+                errors.push(format!("{:#}", e));
+                "failed"
+            }
+            None if !errors.is_empty() => "partial",
+            None => "success",
+        };
+
         sqlx::query(
-            "INSERT OR REPLACE INTO balance_history
-             (date, epoch, snapshot_slot, vote_account_lamports, identity_lamports,
-              withdraw_authority_lamports, token_accounts_lamports, token_accounts_withdrawable_lamports,
-              stake_liquid_lamports, stake_locked_lamports,
-              jitosol_lamports, jitosol_rate, total_lamports, cumulative_income_lamports,
-              cumulative_expenses_lamports, cumulative_withdrawals_lamports,
-              cumulative_deposits_lamports)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            "UPDATE ingestion_runs
+             SET finished_at = datetime('now'), status = ?, row_counts = ?, errors = ?
+             WHERE id = ?",
         )
-        .bind(date)
-        .bind(epoch as i64)
-        .bind(position.snapshot_slot as i64)
-        .bind(position.vote_account_lamports as i64)
-        .bind(position.identity_lamports as i64)
-        .bind(position.withdraw_authority_lamports as i64)
-        .bind(position.token_accounts_lamports as i64)
-        .bind(position.token_accounts_withdrawable_lamports as i64)
-        .bind(position.stake_accounts_liquid as i64)
-        .bind(position.stake_accounts_locked as i64)
-        .bind(position.jitosol_lamports as i64)
-        .bind(position.jitosol_sol_rate)
-        .bind(position.total_assets_lamports as i64)
-        .bind(position.lifetime_income_lamports as i64)
-        .bind(position.lifetime_expenses_lamports as i64)
-        .bind(position.lifetime_withdrawals_lamports as i64)
-        .bind(position.lifetime_deposits_lamports as i64)
+        .bind(status)
+        .bind(serde_json::to_string(&log.row_counts)?)
+        .bind(serde_json::to_string(&errors)?)
+        .bind(id)
         .execute(&self.pool)
         .await?;
 
         Ok(())
     }
 
-    // =========================================================================
-    // Income/Expense Aggregation for Reconciliation
-    // =========================================================================
-
-    /// Get total lifetime income in lamports
-    /// Includes: staking rewards (commission), leader fees, MEV tips, BAM/LST rewards (token-aware)
-    #[allow(dead_code)]
-    pub async fn get_total_income_lamports(&self, config: &Config, jitosol_rate: f64) -> Result<u64> {
-        // Staking commission rewards
-        let rewards: (Option<i64>,) = sqlx::query_as("SELECT SUM(amount_lamports) FROM epoch_rewards")
-            .fetch_one(&self.pool)
-            .await?;
-        let rewards_lamports = rewards.0.unwrap_or(0).max(0) as u64;
+    /// Get the most recent ingestion runs (newest first)
+    pub async fn get_ingestion_runs(&self, limit: u32) -> Result<Vec<IngestionRun>> {
+        let rows: Vec<IngestionRunRow> = sqlx::query_as(
+            "SELECT id, triggered_by, started_at, finished_at, status, row_counts, errors
+             FROM ingestion_runs
+             ORDER BY id DESC
+             LIMIT ?",
+        )
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
 
-        // Stake-account inflation rewards (self-stake)
-        let stake: (Option<i64>,) = sqlx::query_as("SELECT SUM(amount_lamports) FROM stake_rewards")
-            .fetch_one(&self.pool)
-            .await
-            .unwrap_or((None,));
-        let stake_lamports = stake.0.unwrap_or(0).max(0) as u64;
+        Ok(rows
+            .into_iter()
+            .map(|r| IngestionRun {
+                id: r.id,
+                triggered_by: r.triggered_by,
+                started_at: r.started_at,
+                finished_at: r.finished_at,
+                status: r.status,
+                row_counts: serde_json::from_str(&r.row_counts).unwrap_or_default(),
+                errors: serde_json::from_str(&r.errors).unwrap_or_default(),
+            })
+            .collect())
+    }
 
-        // Leader slot fees
-        let leader: (Option<i64>,) = sqlx::query_as("SELECT SUM(total_fees_lamports) FROM leader_fees")
-            .fetch_one(&self.pool)
-            .await?;
-        let leader_lamports = leader.0.unwrap_or(0).max(0) as u64;
+    /// Record a run that never started because another ingestion held the lock
+    pub async fn record_skipped_ingestion_run(&self, triggered_by: &str, reason: &str) -> Result<()> {
+        let errors = vec![reason.to_string()];
+        sqlx::query(
+            "INSERT INTO ingestion_runs (triggered_by, finished_at, status, errors)
+             VALUES (?, datetime('now'), 'skipped', ?)",
+        )
+        .bind(triggered_by)
+        .bind(serde_json::to_string(&errors)?)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
 
-        // Jito MEV commission
-        let mev: (Option<i64>,) = sqlx::query_as("SELECT SUM(commission_lamports) FROM mev_claims")
-            .fetch_one(&self.pool)
-            .await?;
-        let mev_lamports = mev.0.unwrap_or(0).max(0) as u64;
+    // =========================================================================
+    // Source Health
+    // =========================================================================
 
-        // BAM rewards are paid in jitoSOL. For reconciliation, we value reward-like jitoSOL inflows
-        // at the current jitoSOL->SOL rate (mark-to-market) so expected balance can match the
-        // current asset snapshot (which also values current holdings at the current rate).
-        let jitosol_mint = Pubkey::from_str(constants::JITOSOL_MINT).expect("Invalid JITOSOL_MINT constant");
-        let mut owners = vec![config.identity, config.withdraw_authority];
-        owners.sort();
-        owners.dedup();
-        let mut jitosol_reward_token_lamports: u64 = 0;
-        for owner in owners {
-            jitosol_reward_token_lamports = jitosol_reward_token_lamports.saturating_add(
-                self.get_reward_like_token_inflows(
-                    &owner,
-                    &jitosol_mint,
-                    constants::TOKEN_REWARD_MAX_OWNER_SOL_OUT_LAMPORTS,
-                )
-                .await?,
-            );
-        }
-        let jitosol_income_lamports =
-            ((jitosol_reward_token_lamports as f64) * jitosol_rate).min(u64::MAX as f64) as u64;
+    /// Record a successful sync of a data source
+    pub async fn record_source_success(&self, name: &str, rows: usize) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO source_health (name, last_success_at, consecutive_failures, last_rows)
+             VALUES (?, datetime('now'), 0, ?)
+             ON CONFLICT(name) DO UPDATE SET
+                last_success_at = excluded.last_success_at,
+                consecutive_failures = 0,
+                last_rows = excluded.last_rows",
+        )
+        .bind(name)
+        .bind(rows as i64)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
 
-        Ok(rewards_lamports
-            .saturating_add(stake_lamports)
-            .saturating_add(leader_lamports)
-            .saturating_add(mev_lamports)
-            .saturating_add(jitosol_income_lamports))
+    /// Record a failed sync of a data source
+    pub async fn record_source_failure(&self, name: &str, error: &str) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO source_health (name, last_error_at, last_error, consecutive_failures)
+             VALUES (?, datetime('now'), ?, 1)
+             ON CONFLICT(name) DO UPDATE SET
+                last_error_at = excluded.last_error_at,
+                last_error = excluded.last_error,
+                consecutive_failures = consecutive_failures + 1",
+        )
+        .bind(name)
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
     }
 
-    pub async fn get_total_income_lamports_up_to(
-        &self,
-        config: &Config,
-        jitosol_rate: f64,
-        max_epoch: u64,
-        max_slot: u64,
-    ) -> Result<u64> {
-        let max_epoch_i64 = (max_epoch.min(i64::MAX as u64)) as i64;
+    /// Health of every data source that has synced at least once
+    pub async fn get_source_health(&self) -> Result<Vec<SourceHealth>> {
+        let rows: Vec<SourceHealthRow> = sqlx::query_as(
+            "SELECT name, last_success_at, last_error_at, last_error, consecutive_failures, last_rows
+             FROM source_health
+             ORDER BY name",
+        )
+        .fetch_all(&self.pool)
+        .await?;
 
-        // Staking commission rewards
-        let rewards: (Option<i64>,) = sqlx::query_as("SELECT SUM(amount_lamports) FROM epoch_rewards WHERE epoch <= ?")
-            .bind(max_epoch_i64)
-            .fetch_one(&self.pool)
-            .await?;
-        let rewards_lamports = rewards.0.unwrap_or(0).max(0) as u64;
+        Ok(rows
+            .into_iter()
+            .map(|r| SourceHealth {
+                name: r.name,
+                last_success_at: r.last_success_at,
+                last_error_at: r.last_error_at,
+                last_error: r.last_error,
+                consecutive_failures: r.consecutive_failures as u32,
+                last_rows: r.last_rows as u64,
+            })
+            .collect())
+    }
 
-        // Stake-account inflation rewards (self-stake)
-        let stake: (Option<i64>,) = sqlx::query_as("SELECT SUM(amount_lamports) FROM stake_rewards WHERE epoch <= ?")
-            .bind(max_epoch_i64)
-            .fetch_one(&self.pool)
-            .await
-            .unwrap_or((None,));
-        let stake_lamports = stake.0.unwrap_or(0).max(0) as u64;
+    // =========================================================================
+    // Address Tags
+    // =========================================================================
 
-        // Leader slot fees
-        let leader: (Option<i64>,) =
-            sqlx::query_as("SELECT SUM(total_fees_lamports) FROM leader_fees WHERE epoch <= ?")
-                .bind(max_epoch_i64)
-                .fetch_one(&self.pool)
-                .await?;
-        let leader_lamports = leader.0.unwrap_or(0).max(0) as u64;
+    /// Tag an address (replacing any existing tag)
+    pub async fn upsert_address_tag(&self, address: &str, label: &str, category: AddressCategory) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO address_tags (address, label, category) VALUES (?, ?, ?)
+             ON CONFLICT(address) DO UPDATE SET label = excluded.label, category = excluded.category",
+        )
+        .bind(address)
+        .bind(label)
+        .bind(category_to_string(&category))
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
 
-        // Jito MEV commission
-        let mev: (Option<i64>,) = sqlx::query_as("SELECT SUM(commission_lamports) FROM mev_claims WHERE epoch <= ?")
-            .bind(max_epoch_i64)
-            .fetch_one(&self.pool)
+    /// Remove an address tag. Returns false if the address wasn't tagged.
+    pub async fn delete_address_tag(&self, address: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM address_tags WHERE address = ?")
+            .bind(address)
+            .execute(&self.pool)
             .await?;
-        let mev_lamports = mev.0.unwrap_or(0).max(0) as u64;
+        Ok(result.rows_affected() > 0)
+    }
 
-        // BAM rewards are paid in jitoSOL. For reconciliation, we value reward-like jitoSOL inflows
-        // at the current jitoSOL->SOL rate (mark-to-market) so expected balance can match the
-        // current asset snapshot (which also values current holdings at the current rate).
-        let jitosol_mint = Pubkey::from_str(constants::JITOSOL_MINT).expect("Invalid JITOSOL_MINT constant");
-        let mut owners = vec![config.identity, config.withdraw_authority];
-        owners.sort();
-        owners.dedup();
-        let mut jitosol_reward_token_lamports: u64 = 0;
-        for owner in owners {
-            jitosol_reward_token_lamports = jitosol_reward_token_lamports.saturating_add(
-                self.get_reward_like_token_inflows_up_to_slot(
-                    &owner,
-                    &jitosol_mint,
-                    constants::TOKEN_REWARD_MAX_OWNER_SOL_OUT_LAMPORTS,
-                    max_slot,
-                )
-                .await?,
-            );
-        }
-        let jitosol_income_lamports =
-            ((jitosol_reward_token_lamports as f64) * jitosol_rate).min(u64::MAX as f64) as u64;
+    /// All address tags, oldest first
+    pub async fn get_address_tags(&self) -> Result<Vec<AddressTag>> {
+        let rows: Vec<(String, String, String, String)> = sqlx::query_as(
+            "SELECT address, label, category, created_at FROM address_tags ORDER BY created_at, address",
+        )
+        .fetch_all(&self.pool)
+        .await?;
 
-        Ok(rewards_lamports
-            .saturating_add(stake_lamports)
-            .saturating_add(leader_lamports)
-            .saturating_add(mev_lamports)
-            .saturating_add(jitosol_income_lamports))
+        Ok(rows
+            .into_iter()
+            .map(|(address, label, category, created_at)| AddressTag {
+                address,
+                label,
+                category: string_to_category(&category),
+                created_at,
+            })
+            .collect())
     }
 
-    /// Get total lifetime expenses in lamports (cash expenses).
-    ///
-    /// Includes:
-    /// - vote transaction costs
-    ///
-    /// Excludes:
-    /// - DoubleZero liabilities (accruals), since those are not necessarily paid yet and any
-    ///   actual on-chain payment will already appear as a withdrawal transfer.
-    ///
-    /// Note: USD expenses are not included (would need price conversion).
-    #[allow(dead_code)]
-    pub async fn get_total_expenses_lamports(&self) -> Result<u64> {
-        let vote_costs: (Option<i64>,) = sqlx::query_as("SELECT SUM(total_fee_lamports) FROM vote_costs")
-            .fetch_one(&self.pool)
+    // =========================================================================
+    // Withdrawal Splits
+    // =========================================================================
+
+    /// Record (or replace) the owner draw / business expense split of a withdrawal
+    pub async fn upsert_withdrawal_split(&self, split: &WithdrawalSplit) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO withdrawal_splits (signature, business_pct, category, description) VALUES (?, ?, ?, ?)
+             ON CONFLICT(signature) DO UPDATE SET business_pct = excluded.business_pct,
+                 category = excluded.category, description = excluded.description",
+        )
+        .bind(&split.signature)
+        .bind(split.business_pct)
+        .bind(format!("{:?}", split.category))
+        .bind(&split.description)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Remove a withdrawal's split. Returns false if it wasn't split.
+    pub async fn delete_withdrawal_split(&self, signature: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM withdrawal_splits WHERE signature = ?")
+            .bind(signature)
+            .execute(&self.pool)
             .await?;
-        Ok(vote_costs.0.unwrap_or(0).max(0) as u64)
+        Ok(result.rows_affected() > 0)
     }
 
-    pub async fn get_total_expenses_lamports_up_to(&self, max_epoch: u64) -> Result<u64> {
-        let max_epoch_i64 = (max_epoch.min(i64::MAX as u64)) as i64;
-        let vote_costs: (Option<i64>,) =
-            sqlx::query_as("SELECT SUM(total_fee_lamports) FROM vote_costs WHERE epoch <= ?")
-                .bind(max_epoch_i64)
-                .fetch_one(&self.pool)
-                .await?;
-        Ok(vote_costs.0.unwrap_or(0).max(0) as u64)
+    /// All withdrawal splits
+    pub async fn get_withdrawal_splits(&self) -> Result<Vec<WithdrawalSplit>> {
+        let rows: Vec<(String, f64, String, String)> = sqlx::query_as(
+            "SELECT signature, business_pct, category, description FROM withdrawal_splits ORDER BY created_at, signature",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(signature, business_pct, category, description)| WithdrawalSplit {
+                signature,
+                business_pct,
+                category: string_to_expense_category(&category),
+                description,
+            })
+            .collect())
     }
 
-    pub async fn get_total_doublezero_liability_lamports_up_to(&self, max_epoch: u64) -> Result<u64> {
-        let max_epoch_i64 = (max_epoch.min(i64::MAX as u64)) as i64;
-        let row: (Option<i64>,) =
-            sqlx::query_as("SELECT SUM(liability_lamports) FROM doublezero_fees WHERE epoch <= ?")
-                .bind(max_epoch_i64)
-                .fetch_one(&self.pool)
-                .await
-                .unwrap_or((None,));
-        Ok(row.0.unwrap_or(0).max(0) as u64)
+    // =========================================================================
+    // Fiat Deposits
+    // =========================================================================
+
+    /// Record a deposit into the USD cash account. Returns its id.
+    pub async fn add_fiat_deposit(&self, deposit: &FiatDeposit) -> Result<i64> {
+        let result = sqlx::query("INSERT INTO fiat_deposits (date, amount_usd, memo) VALUES (?, ?, ?)")
+            .bind(&deposit.date)
+            .bind(deposit.amount_usd)
+            .bind(&deposit.memo)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.last_insert_rowid())
     }
 
-    /// Get total lifetime withdrawals in lamports
-    /// Includes: transfers to exchanges or personal wallets
-    #[allow(dead_code)]
-    pub async fn get_total_withdrawals_lamports(&self, config: &Config) -> Result<u64> {
-        // Avoid relying on stored categories (which may be missing/wrong in older caches).
-        // Treat withdrawals as SOL leaving our validator treasury (vote/identity/withdraw authority)
-        // to external destinations. Exclude known internal routing destinations:
-        // - our core accounts
-        // - our stake accounts
-        //
-        // Important: we do NOT exclude DeFi/swaps here. If SOL is sent to a swap pool/router and we
-        // don't also include the resulting token holdings in the position snapshot, excluding those
-        // destinations would make reconciliation look artificially "high" (missing withdrawals).
-        // If/when we add comprehensive SPL-token balance tracking, we can revisit this.
-        //
-        // Note: transfers to the personal wallet are counted as withdrawals because the personal
-        // wallet is not part of the validator position snapshot.
+    /// Remove a deposit. Returns false if there was none with that id.
+    pub async fn delete_fiat_deposit(&self, id: i64) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM fiat_deposits WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
 
-        let mut internal: std::collections::HashSet<String> = std::collections::HashSet::new();
-        internal.insert(config.vote_account.to_string());
-        internal.insert(config.identity.to_string());
-        internal.insert(config.withdraw_authority.to_string());
+    /// All deposits, oldest first
+    pub async fn get_fiat_deposits(&self) -> Result<Vec<FiatDeposit>> {
+        let rows: Vec<(i64, String, f64, String)> =
+            sqlx::query_as("SELECT id, date, amount_usd, memo FROM fiat_deposits ORDER BY date, id")
+                .fetch_all(&self.pool)
+                .await?;
 
-        // Token routing often involves wrapping SOL into the wallet's wSOL ATA or moving SOL through
-        // other common token ATAs. Those are still internal assets and should not be treated as withdrawals.
-        for wallet in [&config.vote_account, &config.identity, &config.withdraw_authority] {
-            for ata in crate::positions::compute_common_atas(wallet) {
-                internal.insert(ata);
-            }
-        }
+        Ok(rows
+            .into_iter()
+            .map(|(id, date, amount_usd, memo)| FiatDeposit {
+                id: Some(id),
+                date,
+                amount_usd,
+                memo,
+            })
+            .collect())
+    }
 
-        // Stake accounts are owned by the same withdraw authority and are part of validator assets.
-        // If we classify transfers to stake accounts as withdrawals, reconciliation will be wrong.
-        let stake_rows: Vec<(String,)> = sqlx::query_as("SELECT account FROM stake_accounts")
-            .fetch_all(&self.pool)
-            .await
-            .unwrap_or_default();
-        for (addr,) in stake_rows {
-            internal.insert(addr);
-        }
+    // =========================================================================
+    // Exchange Deposits
+    // =========================================================================
 
-        let from_vote = config.vote_account.to_string();
-        let from_identity = config.identity.to_string();
-        let from_withdraw = config.withdraw_authority.to_string();
-        let personal_wallets: std::collections::HashSet<String> =
-            config.personal_wallets.iter().map(ToString::to_string).collect();
+    /// Store imported exchange deposits. Returns how many were new (re-imports are ignored).
+    pub async fn store_exchange_deposits(&self, deposits: &[ExchangeDeposit]) -> Result<usize> {
+        let mut inserted = 0;
+        for deposit in deposits {
+            let result = sqlx::query(
+                "INSERT OR IGNORE INTO exchange_deposits (exchange, reference_id, timestamp, amount_sol)
+                 VALUES (?, ?, ?, ?)",
+            )
+            .bind(&deposit.exchange)
+            .bind(&deposit.reference_id)
+            .bind(deposit.timestamp)
+            .bind(deposit.amount_sol)
+            .execute(&self.pool)
+            .await?;
+            inserted += result.rows_affected() as usize;
+        }
+        Ok(inserted)
+    }
 
-        let rows: Vec<(String, i64)> = sqlx::query_as(
-            "SELECT to_address, amount_lamports
-             FROM sol_transfers
-             WHERE from_address IN (?, ?, ?)",
+    /// All imported exchange deposits, oldest first
+    pub async fn get_exchange_deposits(&self) -> Result<Vec<ExchangeDeposit>> {
+        let rows: Vec<(String, String, i64, f64)> = sqlx::query_as(
+            "SELECT exchange, reference_id, timestamp, amount_sol FROM exchange_deposits
+             ORDER BY timestamp, exchange, reference_id",
         )
-        .bind(from_vote)
-        .bind(from_identity)
-        .bind(from_withdraw)
         .fetch_all(&self.pool)
         .await?;
 
-        let mut total: u64 = 0;
-        for (to_str, amount) in rows {
-            if amount <= 0 {
-                continue;
-            }
-            // Always count transfers to external personal wallets as withdrawals.
-            if personal_wallets.contains(&to_str) && !internal.contains(&to_str) {
-                total = total.saturating_add(amount as u64);
-                continue;
-            }
-            // Count if destination is not internal.
-            if !internal.contains(&to_str) {
-                total = total.saturating_add(amount as u64);
-            }
-        }
+        Ok(rows
+            .into_iter()
+            .map(|(exchange, reference_id, timestamp, amount_sol)| ExchangeDeposit {
+                exchange,
+                reference_id,
+                timestamp,
+                amount_sol,
+            })
+            .collect())
+    }
 
-        Ok(total)
+    /// Replace the confirmed-received withdrawals with a fresh match (signature, deposit)
+    pub async fn replace_withdrawal_receipts(&self, receipts: &[(&str, &ExchangeDeposit)]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM withdrawal_receipts").execute(&mut *tx).await?;
+        for (signature, deposit) in receipts {
+            sqlx::query(
+                "INSERT INTO withdrawal_receipts (signature, exchange, reference_id, credited_at) VALUES (?, ?, ?, ?)",
+            )
+            .bind(signature)
+            .bind(&deposit.exchange)
+            .bind(&deposit.reference_id)
+            .bind(deposit.timestamp)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
     }
 
-    pub async fn get_total_withdrawals_lamports_up_to(&self, config: &Config, max_slot: u64) -> Result<u64> {
-        // Same logic as `get_total_withdrawals_lamports`, but bounded by slot for snapshot consistency.
-        let mut internal: std::collections::HashSet<String> = std::collections::HashSet::new();
-        internal.insert(config.vote_account.to_string());
-        internal.insert(config.identity.to_string());
-        internal.insert(config.withdraw_authority.to_string());
+    // =========================================================================
+    // Card Statement Lines
+    // =========================================================================
 
-        for wallet in [&config.vote_account, &config.identity, &config.withdraw_authority] {
-            for ata in crate::positions::compute_common_atas(wallet) {
-                internal.insert(ata);
-            }
+    /// Store imported card statement charges. Returns how many were new (re-imports are ignored).
+    pub async fn store_statement_lines(&self, lines: &[StatementLine]) -> Result<usize> {
+        let mut inserted = 0;
+        for line in lines {
+            let result = sqlx::query(
+                "INSERT OR IGNORE INTO card_statement_lines (reference, date, description, amount_usd)
+                 VALUES (?, ?, ?, ?)",
+            )
+            .bind(&line.reference)
+            .bind(&line.date)
+            .bind(&line.description)
+            .bind(line.amount_usd)
+            .execute(&self.pool)
+            .await?;
+            inserted += result.rows_affected() as usize;
         }
+        Ok(inserted)
+    }
 
-        let stake_rows: Vec<(String,)> = sqlx::query_as("SELECT account FROM stake_accounts")
-            .fetch_all(&self.pool)
-            .await
-            .unwrap_or_default();
-        for (addr,) in stake_rows {
-            internal.insert(addr);
-        }
+    /// All imported card statement charges, oldest first
+    pub async fn get_statement_lines(&self) -> Result<Vec<StatementLine>> {
+        let rows: Vec<(String, String, String, f64)> = sqlx::query_as(
+            "SELECT reference, date, description, amount_usd FROM card_statement_lines ORDER BY date, reference",
+        )
+        .fetch_all(&self.pool)
+        .await?;
 
-        let from_vote = config.vote_account.to_string();
-        let from_identity = config.identity.to_string();
-        let from_withdraw = config.withdraw_authority.to_string();
-        let personal_wallets: std::collections::HashSet<String> =
-            config.personal_wallets.iter().map(ToString::to_string).collect();
+        Ok(rows
+            .into_iter()
+            .map(|(reference, date, description, amount_usd)| StatementLine {
+                reference,
+                date,
+                description,
+                amount_usd,
+            })
+            .collect())
+    }
 
-        let rows: Vec<(String, i64)> = sqlx::query_as(
-            "SELECT to_address, amount_lamports
-             FROM sol_transfers
-             WHERE slot <= ?
-               AND from_address IN (?, ?, ?)",
+    // =========================================================================
+    // Report Fingerprints
+    // =========================================================================
+
+    /// Record a report's fingerprint. Returns false if this file already had this content.
+    pub async fn store_report_fingerprint(&self, file_name: &str, fingerprint: &Fingerprint) -> Result<bool> {
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO report_fingerprints (file_name, root_hash, leaf_count) VALUES (?, ?, ?)",
         )
-        .bind(max_slot as i64)
-        .bind(from_vote)
-        .bind(from_identity)
-        .bind(from_withdraw)
-        .fetch_all(&self.pool)
+        .bind(file_name)
+        .bind(&fingerprint.root)
+        .bind(fingerprint.leaves as i64)
+        .execute(&self.pool)
         .await?;
+        Ok(result.rows_affected() > 0)
+    }
 
-        let mut total: u64 = 0;
-        for (to_str, amount) in rows {
-            if amount <= 0 {
-                continue;
-            }
-            if personal_wallets.contains(&to_str) && !internal.contains(&to_str) {
-                total = total.saturating_add(amount as u64);
-                continue;
-            }
-            if !internal.contains(&to_str) {
-                total = total.saturating_add(amount as u64);
-            }
-        }
+    /// Reports generated with this Merkle root, oldest first
+    pub async fn find_report_fingerprints(&self, root: &str) -> Result<Vec<StoredFingerprint>> {
+        self.query_report_fingerprints("root_hash = ?", root).await
+    }
 
-        Ok(total)
+    /// Fingerprints recorded for a file name, newest first
+    pub async fn get_report_fingerprints_for(&self, file_name: &str) -> Result<Vec<StoredFingerprint>> {
+        let mut fingerprints = self.query_report_fingerprints("file_name = ?", file_name).await?;
+        fingerprints.reverse();
+        Ok(fingerprints)
     }
 
-    /// Get total lifetime deposits in lamports
-    ///
-    /// For reconciliation, "deposits" means any external SOL inflow to the validator treasury
-    /// (vote/identity/withdraw authority). This includes:
-    /// - transfers from the configured personal wallet (capital contributions)
-    /// - SFDP reimbursements to validator accounts (vote cost reimbursements)
-    /// - swap/DeFi proceeds that return SOL to our treasury accounts
-    ///
-    /// Note: we exclude transfers from internal addresses (core accounts, stake accounts, common ATAs)
-    /// and exclude known Jito MEV distribution addresses to avoid double-counting with `mev_claims`.
-    #[allow(dead_code)]
-    pub async fn get_total_deposits_lamports(&self, config: &Config) -> Result<u64> {
-        let mut internal: std::collections::HashSet<String> = std::collections::HashSet::new();
-        internal.insert(config.vote_account.to_string());
-        internal.insert(config.identity.to_string());
-        internal.insert(config.withdraw_authority.to_string());
+    async fn query_report_fingerprints(&self, filter: &str, value: &str) -> Result<Vec<StoredFingerprint>> {
+        let rows: Vec<(String, String, i64, String)> = sqlx::query_as(&format!(
+            "SELECT file_name, root_hash, leaf_count, generated_at FROM report_fingerprints
+             WHERE {} ORDER BY generated_at, id",
+            filter
+        ))
+        .bind(value)
+        .fetch_all(&self.pool)
+        .await?;
 
-        for wallet in [&config.vote_account, &config.identity, &config.withdraw_authority] {
-            for ata in crate::positions::compute_common_atas(wallet) {
-                internal.insert(ata);
-            }
-        }
+        Ok(rows
+            .into_iter()
+            .map(|(file_name, root, leaves, generated_at)| StoredFingerprint {
+                file_name,
+                root,
+                leaves: leaves as usize,
+                generated_at,
+            })
+            .collect())
+    }
 
-        let stake_rows: Vec<(String,)> = sqlx::query_as("SELECT account FROM stake_accounts")
+    // =========================================================================
+    // Duplicate Resolutions
+    // =========================================================================
+
+    /// Ids of every reviewed duplicate pair
+    pub async fn get_resolved_duplicate_ids(&self) -> Result<std::collections::HashSet<String>> {
+        let rows: Vec<(String,)> = sqlx::query_as("SELECT pair_id FROM duplicate_resolutions")
             .fetch_all(&self.pool)
-            .await
-            .unwrap_or_default();
-        for (addr,) in stake_rows {
-            internal.insert(addr);
-        }
+            .await?;
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
 
-        let rows: Vec<(String, i64)> = sqlx::query_as(
-            "SELECT from_address, amount_lamports
-             FROM sol_transfers
-             WHERE to_address IN (?, ?, ?)",
+    /// Record how a duplicate pair was resolved ("not_duplicate" or "dropped <key>")
+    pub async fn resolve_duplicate(&self, pair_id: &str, key_a: &str, key_b: &str, resolution: &str) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO duplicate_resolutions (pair_id, key_a, key_b, resolution) VALUES (?, ?, ?, ?)
+             ON CONFLICT(pair_id) DO UPDATE SET resolution = excluded.resolution, resolved_at = datetime('now')",
         )
-        .bind(config.vote_account.to_string())
-        .bind(config.identity.to_string())
-        .bind(config.withdraw_authority.to_string())
-        .fetch_all(&self.pool)
+        .bind(pair_id)
+        .bind(key_a)
+        .bind(key_b)
+        .bind(resolution)
+        .execute(&self.pool)
         .await?;
+        Ok(())
+    }
 
-        let mut total: u64 = 0;
-        for (from_str, amount) in rows {
-            if amount <= 0 {
-                continue;
-            }
-            if internal.contains(&from_str) {
-                continue;
-            }
-
-            // Avoid double-counting MEV deposits: those are accounted via `mev_claims`.
-            if let Ok(pk) = solana_sdk::pubkey::Pubkey::from_str(&from_str)
-                && crate::addresses::is_jito(&pk)
-            {
-                continue;
-            }
+    // =========================================================================
+    // Failed Transaction Fees
+    // =========================================================================
 
-            total = total.saturating_add(amount as u64);
+    /// Store failed-transaction fees and the signer's scan state, atomically
+    pub async fn store_failed_tx_fees(&self, account: &str, fees: &[FailedTxFee], state: &ScanState) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for fee in fees {
+            sqlx::query(
+                "INSERT OR REPLACE INTO failed_tx_fees (signature, account, slot, block_time, date, fee_lamports, error)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&fee.signature)
+            .bind(&fee.account)
+            .bind(fee.slot as i64)
+            .bind(fee.timestamp)
+            .bind(&fee.date)
+            .bind(fee.fee_lamports as i64)
+            .bind(&fee.error)
+            .execute(&mut *tx)
+            .await?;
         }
+        store_scan_state(&mut tx, "failed_tx_scan_cursors", account, state).await?;
+        tx.commit().await?;
+        Ok(())
+    }
 
-        Ok(total)
+    /// Where the last failed-fee scan of `account` stopped
+    pub async fn get_failed_tx_scan_state(&self, account: &str) -> Result<ScanState> {
+        self.get_scan_state("failed_tx_scan_cursors", account).await
     }
 
-    pub async fn get_total_deposits_lamports_up_to(&self, config: &Config, max_slot: u64) -> Result<u64> {
-        let mut internal: std::collections::HashSet<String> = std::collections::HashSet::new();
-        internal.insert(config.vote_account.to_string());
-        internal.insert(config.identity.to_string());
-        internal.insert(config.withdraw_authority.to_string());
+    /// A signer's scan state from a `*_scan_cursors` table
+    async fn get_scan_state(&self, table: &str, account: &str) -> Result<ScanState> {
+        type ScanStateRow = (String, i64, Option<String>, Option<i64>, Option<String>, Option<i64>);
+        let row: Option<ScanStateRow> = sqlx::query_as(&format!(
+            "SELECT signature, slot, resume_top_signature, resume_top_slot, resume_before_signature, resume_before_slot
+             FROM {} WHERE account = ?",
+            table
+        ))
+        .bind(account)
+        .fetch_optional(&self.pool)
+        .await?;
+        let Some((signature, slot, top_sig, top_slot, before_sig, before_slot)) = row else {
+            return Ok(ScanState::default());
+        };
+        let resume = match (top_sig, top_slot, before_sig, before_slot) {
+            (Some(top_sig), Some(top_slot), Some(before_sig), Some(before_slot)) => Some(ScanResume {
+                top: ScanCursor {
+                    signature: top_sig,
+                    slot: top_slot as u64,
+                },
+                before: ScanCursor {
+                    signature: before_sig,
+                    slot: before_slot as u64,
+                },
+            }),
+            _ => None,
+        };
+        Ok(ScanState {
+            cursor: Some(ScanCursor {
+                signature,
+                slot: slot as u64,
+            }),
+            resume,
+        })
+    }
 
-        for wallet in [&config.vote_account, &config.identity, &config.withdraw_authority] {
-            for ata in crate::positions::compute_common_atas(wallet) {
-                internal.insert(ata);
-            }
-        }
+    /// All cached failed-transaction fees, oldest first
+    pub async fn get_failed_tx_fees(&self) -> Result<Vec<FailedTxFee>> {
+        let rows: Vec<(String, String, i64, Option<i64>, Option<String>, i64, String)> = sqlx::query_as(
+            "SELECT signature, account, slot, block_time, date, fee_lamports, error
+             FROM failed_tx_fees ORDER BY slot",
+        )
+        .fetch_all(&self.pool)
+        .await?;
 
-        let stake_rows: Vec<(String,)> = sqlx::query_as("SELECT account FROM stake_accounts")
-            .fetch_all(&self.pool)
-            .await
-            .unwrap_or_default();
-        for (addr,) in stake_rows {
-            internal.insert(addr);
+        Ok(rows
+            .into_iter()
+            .map(
+                |(signature, account, slot, timestamp, date, fee_lamports, error)| FailedTxFee {
+                    signature,
+                    account,
+                    slot: slot as u64,
+                    timestamp,
+                    date,
+                    fee_lamports: fee_lamports as u64,
+                    error,
+                },
+            )
+            .collect())
+    }
+
+    // =========================================================================
+    // Ops Transaction Fees
+    // =========================================================================
+
+    /// Store ops-transaction fees and the signer's scan state, atomically
+    pub async fn store_ops_fees(&self, account: &str, fees: &[OpsFee], state: &ScanState) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for fee in fees {
+            sqlx::query(
+                "INSERT OR REPLACE INTO ops_fees
+                 (signature, account, slot, block_time, date, fee_lamports, priority_fee_lamports, program)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&fee.signature)
+            .bind(&fee.account)
+            .bind(fee.slot as i64)
+            .bind(fee.timestamp)
+            .bind(&fee.date)
+            .bind(fee.fee_lamports as i64)
+            .bind(fee.priority_fee_lamports as i64)
+            .bind(&fee.program)
+            .execute(&mut *tx)
+            .await?;
         }
+        store_scan_state(&mut tx, "ops_fee_scan_cursors", account, state).await?;
+        tx.commit().await?;
+        Ok(())
+    }
 
-        let rows: Vec<(String, i64)> = sqlx::query_as(
-            "SELECT from_address, amount_lamports
-             FROM sol_transfers
-             WHERE slot <= ?
-               AND to_address IN (?, ?, ?)",
+    /// Where the last ops-fee scan of `account` stopped
+    pub async fn get_ops_fee_scan_state(&self, account: &str) -> Result<ScanState> {
+        self.get_scan_state("ops_fee_scan_cursors", account).await
+    }
+
+    /// All cached ops-transaction fees, oldest first
+    pub async fn get_ops_fees(&self) -> Result<Vec<OpsFee>> {
+        let rows: Vec<(
+            String,
+            String,
+            i64,
+            Option<i64>,
+            Option<String>,
+            i64,
+            i64,
+            Option<String>,
+        )> = sqlx::query_as(
+            "SELECT signature, account, slot, block_time, date, fee_lamports, priority_fee_lamports, program
+                 FROM ops_fees ORDER BY slot",
         )
-        .bind(max_slot as i64)
-        .bind(config.vote_account.to_string())
-        .bind(config.identity.to_string())
-        .bind(config.withdraw_authority.to_string())
         .fetch_all(&self.pool)
         .await?;
 
-        let mut total: u64 = 0;
-        for (from_str, amount) in rows {
-            if amount <= 0 {
-                continue;
-            }
-            if internal.contains(&from_str) {
-                continue;
-            }
-            if let Ok(pk) = solana_sdk::pubkey::Pubkey::from_str(&from_str)
-                && crate::addresses::is_jito(&pk)
-            {
-                continue;
-            }
-            total = total.saturating_add(amount as u64);
-        }
+        Ok(rows
+            .into_iter()
+            .map(
+                |(signature, account, slot, timestamp, date, fee_lamports, priority_fee_lamports, program)| OpsFee {
+                    signature,
+                    account,
+                    slot: slot as u64,
+                    timestamp,
+                    date,
+                    fee_lamports: fee_lamports as u64,
+                    priority_fee_lamports: priority_fee_lamports as u64,
+                    program,
+                },
+            )
+            .collect())
+    }
 
-        Ok(total)
+    // =========================================================================
+    // Summary Aggregates
+    // =========================================================================
+
+    /// Per-epoch income and costs summed in SQL (joined to daily prices) for the summary path.
+    /// Only dated rows in the epoch range are included, matching the report inputs.
+    pub async fn get_monthly_aggregates(&self, start_epoch: u64, end_epoch: u64) -> Result<MonthlyAggregates> {
+        Ok(MonthlyAggregates {
+            commission: self
+                .grouped_amounts("epoch_rewards", "amount_sol", (start_epoch, end_epoch), true)
+                .await?,
+            leader_fees: self
+                .grouped_amounts("leader_fees", "total_fees_sol", (start_epoch, end_epoch), true)
+                .await?,
+            mev_claims: self
+                .grouped_amounts("mev_claims", "amount_sol", (start_epoch, end_epoch), true)
+                .await?,
+            doublezero_fees: self
+                .grouped_amounts("doublezero_fees", "liability_sol", (start_epoch, end_epoch), true)
+                .await?,
+            vote_costs: self
+                .grouped_amounts("vote_costs", "total_fee_sol", (start_epoch, end_epoch), false)
+                .await?,
+        })
     }
 
-    /// Get all income/expense data for reconciliation
-    #[allow(dead_code)]
-    pub async fn get_reconciliation_data(
+    /// Sum `amount_column` of `table` by month (or by date) at the exact-date price; rows with no
+    /// price for their date, or only a stored fallback price, are returned per date for the
+    /// caller to value the way the ledgers do (`get_price`)
+    async fn grouped_amounts(
         &self,
-        config: &Config,
-        jitosol_rate: f64,
-    ) -> Result<crate::positions::IncomeData> {
-        let total_income = self.get_total_income_lamports(config, jitosol_rate).await?;
-        let total_expenses = self.get_total_expenses_lamports().await?;
-        let total_withdrawals = self.get_total_withdrawals_lamports(config).await?;
-        let total_deposits = self.get_total_deposits_lamports(config).await?;
+        table: &str,
+        amount_column: &str,
+        (start_epoch, end_epoch): (u64, u64),
+        by_month: bool,
+    ) -> Result<GroupedAmounts> {
+        let key = if by_month { "substr(r.date, 1, 7)" } else { "r.date" };
+        let priced: Vec<(String, f64, f64)> = sqlx::query_as(&format!(
+            "SELECT {key} AS bucket, SUM(r.{amount_column}), SUM(r.{amount_column} * p.usd_price)
+             FROM {table} r JOIN prices p ON p.date = r.date AND p.source != 'fallback'
+             WHERE r.epoch BETWEEN ? AND ? AND length(r.date) >= 10
+             GROUP BY bucket"
+        ))
+        .bind(start_epoch as i64)
+        .bind(end_epoch as i64)
+        .fetch_all(&self.pool)
+        .await?;
 
-        Ok(crate::positions::IncomeData {
-            total_income_lamports: total_income,
-            total_expenses_lamports: total_expenses,
-            total_withdrawals_lamports: total_withdrawals,
-            total_deposits_lamports: total_deposits,
-            token_deposits_lamports: 0,
-            internal_adjustment_lamports: 0,
+        let unpriced: Vec<(String, f64)> = sqlx::query_as(&format!(
+            "SELECT r.date, SUM(r.{amount_column})
+             FROM {table} r LEFT JOIN prices p ON p.date = r.date AND p.source != 'fallback'
+             WHERE r.epoch BETWEEN ? AND ? AND length(r.date) >= 10 AND p.date IS NULL
+             GROUP BY r.date"
+        ))
+        .bind(start_epoch as i64)
+        .bind(end_epoch as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(GroupedAmounts {
+            priced: priced.into_iter().map(|(k, sol, usd)| (k, (sol, usd))).collect(),
+            unpriced: unpriced.into_iter().collect(),
+            by_month,
         })
     }
 
-    pub async fn get_reconciliation_data_at_slot(
-        &self,
-        config: &Config,
-        jitosol_rate: f64,
-        max_slot: u64,
-    ) -> Result<crate::positions::IncomeData> {
-        let max_epoch = max_slot / constants::SLOTS_PER_EPOCH;
+    // =========================================================================
+    // Income Lots
+    // =========================================================================
 
-        let total_income = self
-            .get_total_income_lamports_up_to(config, jitosol_rate, max_epoch, max_slot)
-            .await?;
-        let total_expenses = self.get_total_expenses_lamports_up_to(max_epoch).await?;
-        let total_withdrawals = self.get_total_withdrawals_lamports_up_to(config, max_slot).await?;
-        let sol_deposits = self.get_total_deposits_lamports_up_to(config, max_slot).await?;
-        let token_deposits = self
-            .get_jitosol_token_deposits_value_lamports_up_to_slot(config, jitosol_rate, max_slot)
+    /// Insert or revalue income lots (keyed by source + reference). Returns rows written.
+    pub async fn upsert_income_lots(&self, lots: &[IncomeLot]) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        let mut written = 0;
+        for lot in lots {
+            let result = sqlx::query(
+                "INSERT INTO income_lots
+                    (source, reference, epoch, date, timestamp, asset, amount, amount_sol, price_usd, price_source, fmv_usd)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                 ON CONFLICT(source, reference) DO UPDATE SET
+                    epoch = excluded.epoch,
+                    date = excluded.date,
+                    timestamp = excluded.timestamp,
+                    asset = excluded.asset,
+                    amount = excluded.amount,
+                    amount_sol = excluded.amount_sol,
+                    price_usd = excluded.price_usd,
+                    price_source = excluded.price_source,
+                    fmv_usd = excluded.fmv_usd",
+            )
+            .bind(&lot.source)
+            .bind(&lot.reference)
+            .bind(lot.epoch.map(|e| e as i64))
+            .bind(&lot.date)
+            .bind(lot.timestamp)
+            .bind(&lot.asset)
+            .bind(lot.amount)
+            .bind(lot.amount_sol)
+            .bind(lot.price_usd)
+            .bind(&lot.price_source)
+            .bind(lot.fmv_usd)
+            .execute(&mut *tx)
             .await?;
-        let total_deposits = sol_deposits.saturating_add(token_deposits);
-        let internal_adjustment = self
-            .get_jitosol_purchase_adjustment_lamports_up_to_slot(config, max_slot)
-            .await?
-            .min(i64::MAX as u64) as i64;
+            written += result.rows_affected();
+        }
+        tx.commit().await?;
+        Ok(written)
+    }
 
-        Ok(crate::positions::IncomeData {
-            total_income_lamports: total_income,
-            total_expenses_lamports: total_expenses,
-            total_withdrawals_lamports: total_withdrawals,
-            total_deposits_lamports: total_deposits,
-            token_deposits_lamports: token_deposits,
-            internal_adjustment_lamports: internal_adjustment,
-        })
+    /// All income lots in receipt order
+    pub async fn get_income_lots(&self) -> Result<Vec<IncomeLot>> {
+        let rows = sqlx::query_as::<_, IncomeLotRow>(
+            "SELECT source, reference, epoch, date, timestamp, asset, amount, amount_sol, price_usd, price_source, fmv_usd
+             FROM income_lots ORDER BY date, COALESCE(timestamp, 0), id",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| IncomeLot {
+                source: r.source,
+                reference: r.reference,
+                epoch: r.epoch.map(|e| e as u64),
+                date: r.date,
+                timestamp: r.timestamp,
+                asset: r.asset,
+                amount: r.amount,
+                amount_sol: r.amount_sol,
+                price_usd: r.price_usd,
+                price_source: r.price_source,
+                fmv_usd: r.fmv_usd,
+            })
+            .collect())
     }
 
-    /// Value (in lamports) of external jitoSOL transferred into the validator treasury.
-    ///
-    /// This captures cases where jitoSOL is acquired outside the treasury (e.g. personal wallet)
-    /// and then transferred into identity/withdraw authority, which would otherwise appear as an
-    /// unexplained surplus when jitoSOL is included in assets.
+    // =========================================================================
+    // Ingestion Lock
+    // =========================================================================
+
+    /// Try to take the ingestion lock. Returns false if someone else holds a live lock.
     ///
-    /// Approximation: sum net jitoSOL delta across our owners for transactions where our owner SOL
-    /// delta is exactly 0 (we did not pay a fee and did not move SOL). Internal transfers between
-    /// our owners net to ~0 under this rule.
-    pub async fn get_jitosol_token_deposits_value_lamports_up_to_slot(
-        &self,
-        config: &Config,
-        jitosol_rate: f64,
-        max_slot: u64,
-    ) -> Result<u64> {
-        let jitosol_mint = Pubkey::from_str(constants::JITOSOL_MINT).expect("Invalid JITOSOL_MINT constant");
+    /// Expired locks (holder crashed or was killed) are taken over.
+    pub async fn try_acquire_ingestion_lock(&self, holder: &str) -> Result<bool> {
+        let lease = format!("+{} minutes", constants::INGESTION_LOCK_LEASE_MINUTES);
+        let result = sqlx::query(
+            "INSERT INTO ingestion_lock (id, holder, acquired_at, expires_at)
+             VALUES (1, ?, datetime('now'), datetime('now', ?))
+             ON CONFLICT(id) DO UPDATE SET
+                holder = excluded.holder,
+                acquired_at = excluded.acquired_at,
+                expires_at = excluded.expires_at
+             WHERE ingestion_lock.expires_at < datetime('now')",
+        )
+        .bind(holder)
+        .bind(&lease)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Push the lease out by another `INGESTION_LOCK_LEASE_MINUTES`. Returns false if
+    /// `holder` no longer holds the lock.
+    pub async fn renew_ingestion_lock(&self, holder: &str) -> Result<bool> {
+        let lease = format!("+{} minutes", constants::INGESTION_LOCK_LEASE_MINUTES);
+        let result = sqlx::query(
+            "UPDATE ingestion_lock SET expires_at = datetime('now', ?)
+             WHERE id = 1 AND holder = ? AND expires_at >= datetime('now')",
+        )
+        .bind(&lease)
+        .bind(holder)
+        .execute(&self.pool)
+        .await?;
 
-        let row: (Option<i64>,) = sqlx::query_as(
-            "SELECT SUM(delta_amount) AS net_in
-             FROM token_flows
-             WHERE slot <= ?
-               AND mint = ?
-               AND owner IN (?, ?)
-               AND owner_sol_delta_lamports = 0",
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Current lock holder and acquisition time, if the lock is live
+    pub async fn get_ingestion_lock_holder(&self) -> Result<Option<(String, String)>> {
+        let row: Option<(String, String)> = sqlx::query_as(
+            "SELECT holder, acquired_at FROM ingestion_lock WHERE id = 1 AND expires_at >= datetime('now')",
         )
-        .bind(max_slot as i64)
-        .bind(jitosol_mint.to_string())
-        .bind(config.identity.to_string())
-        .bind(config.withdraw_authority.to_string())
-        .fetch_one(&self.pool)
-        .await
-        .unwrap_or((None,));
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row)
+    }
 
-        let net_token_units = row.0.unwrap_or(0).max(0) as u64;
-        Ok(((net_token_units as f64) * jitosol_rate).min(u64::MAX as f64) as u64)
+    /// Release the ingestion lock (no-op if we no longer hold it)
+    pub async fn release_ingestion_lock(&self, holder: &str) -> Result<()> {
+        sqlx::query("DELETE FROM ingestion_lock WHERE id = 1 AND holder = ?")
+            .bind(holder)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
     }
 
-    /// If SOL is swapped into jitoSOL (which we include in assets), the outgoing SOL transfer often
-    /// looks like a "withdrawal" to a DeFi router/pool. To keep reconciliation accurate, add an
-    /// internal adjustment equal to the SOL outflow associated with jitoSOL inflows.
-    ///
-    /// We approximate "purchase-like" inflows as: jitoSOL delta > 0 and owner SOL delta is more
-    /// negative than the reward-like threshold (i.e., not just fees/rent).
-    pub async fn get_jitosol_purchase_adjustment_lamports_up_to_slot(
-        &self,
-        config: &Config,
-        max_slot: u64,
-    ) -> Result<u64> {
-        let jitosol_mint = Pubkey::from_str(constants::JITOSOL_MINT).expect("Invalid JITOSOL_MINT constant");
-        let threshold = constants::TOKEN_REWARD_MAX_OWNER_SOL_OUT_LAMPORTS.abs();
+    // =========================================================================
+    // Dry Run (--dry-run)
+    // =========================================================================
 
-        let row: (Option<i64>,) = sqlx::query_as(
-            "SELECT SUM(-owner_sol_delta_lamports) AS sol_spent
-             FROM token_flows
-             WHERE slot <= ?
-               AND mint = ?
-               AND delta_amount > 0
-               AND owner IN (?, ?)
-               AND owner_sol_delta_lamports < ?",
-        )
-        .bind(max_slot as i64)
-        .bind(jitosol_mint.to_string())
-        .bind(config.identity.to_string())
-        .bind(config.withdraw_authority.to_string())
-        .bind(-threshold)
-        .fetch_one(&self.pool)
-        .await
-        .unwrap_or((None,));
+    /// Write a consistent copy of the database to `path` (which must not exist yet)
+    pub async fn snapshot_to(&self, path: &Path) -> Result<()> {
+        sqlx::query("VACUUM INTO ?")
+            .bind(path.to_string_lossy().into_owned())
+            .execute(&self.pool)
+            .await
+            .with_context(|| format!("Failed to snapshot the cache to {}", path.display()))?;
+        Ok(())
+    }
 
-        Ok(row.0.unwrap_or(0).max(0) as u64)
+    /// Primary key columns of a table in key order (empty for rowid-only tables)
+    pub async fn primary_key(&self, table: &str) -> Result<Vec<String>> {
+        let rows: Vec<(String,)> = sqlx::query_as("SELECT name FROM pragma_table_info(?) WHERE pk > 0 ORDER BY pk")
+            .bind(table)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows.into_iter().map(|(name,)| name).collect())
     }
 
-    /// Get external transfer summary for reconciliation
-    /// Returns transfers to/from external addresses (excludes internal validator account transfers)
-    ///
-    /// `internal_addresses` - addresses to exclude (vote account, identity, withdraw authority)
-    pub async fn get_external_transfer_summary(
-        &self,
-        internal_addresses: &[String],
-    ) -> Result<ExternalTransferSummary> {
-        // Build exclusion list for SQL
-        let internal_set: std::collections::HashSet<&str> = internal_addresses.iter().map(|s| s.as_str()).collect();
+    /// Run single-value `COUNT` queries on one connection with the database at `other`
+    /// attached as `base`
+    pub async fn count_against(&self, other: &Path, queries: &[String]) -> Result<Vec<u64>> {
+        let mut conn = self.pool.acquire().await?;
+        sqlx::query("ATTACH DATABASE ? AS base")
+            .bind(other.to_string_lossy().into_owned())
+            .execute(&mut *conn)
+            .await?;
 
-        // Deposits IN from external addresses (exclude internal-to-internal)
-        let all_deposits: Vec<(String, String, String, String, i64)> = sqlx::query_as(
-            "SELECT from_address, to_address, from_label, from_category, SUM(amount_lamports) as total
-             FROM sol_transfers
-             GROUP BY from_address, to_address, from_label, from_category
-             ORDER BY total DESC",
-        )
-        .fetch_all(&self.pool)
-        .await?;
+        let mut counts = Vec::with_capacity(queries.len());
+        for sql in queries {
+            let (count,): (i64,) = sqlx::query_as(sql).fetch_one(&mut *conn).await?;
+            counts.push(count as u64);
+        }
 
-        // Filter: from_address is external (not in internal_addresses)
-        let mut deposits_in: Vec<ExternalAddressFlow> = all_deposits
-            .iter()
-            .filter(|(from, to, _, _, _)| {
-                // Include if FROM is external and TO is internal (deposit to us)
-                !internal_set.contains(from.as_str()) && internal_set.contains(to.as_str())
-            })
-            .fold(
-                std::collections::HashMap::new(),
-                |mut acc, (from, _, label, category, amount)| {
-                    let entry = acc
-                        .entry(from.clone())
-                        .or_insert((label.clone(), category.clone(), 0i64));
-                    entry.2 += amount;
-                    acc
-                },
-            )
-            .into_iter()
-            .map(|(addr, (label, category, amount))| ExternalAddressFlow {
-                address: addr,
-                label: if label.is_empty() { category } else { label },
-                amount_lamports: amount.max(0) as u64,
-            })
-            .collect();
-        deposits_in.sort_by_key(|a| std::cmp::Reverse(a.amount_lamports));
-        deposits_in.truncate(10);
+        sqlx::query("DETACH DATABASE base").execute(&mut *conn).await?;
+        Ok(counts)
+    }
 
-        // Withdrawals OUT to external addresses
-        let all_withdrawals: Vec<(String, String, String, String, i64)> = sqlx::query_as(
-            "SELECT from_address, to_address, to_label, to_category, SUM(amount_lamports) as total
-             FROM sol_transfers
-             GROUP BY from_address, to_address, to_label, to_category
-             ORDER BY total DESC",
+    // =========================================================================
+    // Portable Archive (export-archive / import-archive)
+    // =========================================================================
+
+    /// Tables worth archiving, by name (the ingestion lock is per-machine state)
+    pub async fn archive_table_names(&self) -> Result<Vec<String>> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            "SELECT name FROM sqlite_master
+             WHERE type = 'table' AND name NOT LIKE 'sqlite_%' AND name != 'ingestion_lock'
+             ORDER BY name",
         )
         .fetch_all(&self.pool)
         .await?;
+        Ok(rows.into_iter().map(|(name,)| name).collect())
+    }
 
-        // Filter: to_address is external (not in internal_addresses)
-        let mut withdrawals_out: Vec<ExternalAddressFlow> = all_withdrawals
+    /// Column names of a table in declaration order
+    pub async fn table_columns(&self, table: &str) -> Result<Vec<String>> {
+        let rows: Vec<(String,)> = sqlx::query_as("SELECT name FROM pragma_table_info(?) ORDER BY cid")
+            .bind(table)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows.into_iter().map(|(name,)| name).collect())
+    }
+
+    /// Row count of one table (as listed by `archive_table_names`)
+    pub async fn table_row_count(&self, table: &str) -> Result<u64> {
+        let (count,): (i64,) = sqlx::query_as(&format!("SELECT COUNT(*) FROM \"{}\"", table))
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(count as u64)
+    }
+
+    /// Version of the SQLite library in use
+    pub async fn sqlite_version(&self) -> Result<String> {
+        let (version,): (String,) = sqlx::query_as("SELECT sqlite_version()").fetch_one(&self.pool).await?;
+        Ok(version)
+    }
+
+    /// Every row of a table as a JSON object (SQLite builds the JSON, so no per-table row types)
+    pub async fn export_table(&self, table: &str) -> Result<(Vec<String>, Vec<serde_json::Value>)> {
+        let columns = self.table_columns(table).await?;
+        anyhow::ensure!(!columns.is_empty(), "Unknown cache table: {}", table);
+
+        let fields = columns
             .iter()
-            .filter(|(from, to, _, _, _)| {
-                // Include if FROM is internal and TO is external (withdrawal from us)
-                internal_set.contains(from.as_str()) && !internal_set.contains(to.as_str())
-            })
-            .fold(
-                std::collections::HashMap::new(),
-                |mut acc, (_, to, label, category, amount)| {
-                    let entry = acc.entry(to.clone()).or_insert((label.clone(), category.clone(), 0i64));
-                    entry.2 += amount;
-                    acc
-                },
-            )
+            .map(|c| format!("'{}', \"{}\"", c, c))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let sql = format!("SELECT json_object({}) FROM \"{}\" ORDER BY rowid", fields, table);
+        let rows: Vec<(String,)> = sqlx::query_as(&sql).fetch_all(&self.pool).await?;
+
+        let rows = rows
             .into_iter()
-            .map(|(addr, (label, category, amount))| ExternalAddressFlow {
-                address: addr,
-                label: if label.is_empty() { category } else { label },
-                amount_lamports: amount.max(0) as u64,
-            })
-            .collect();
-        withdrawals_out.sort_by_key(|a| std::cmp::Reverse(a.amount_lamports));
-        withdrawals_out.truncate(10);
+            .map(|(json,)| serde_json::from_str(&json))
+            .collect::<Result<Vec<_>, _>>()
+            .with_context(|| format!("Failed to export {}", table))?;
+        Ok((columns, rows))
+    }
 
-        Ok(ExternalTransferSummary {
-            deposits_in,
-            withdrawals_out,
-        })
+    /// Load archived rows in one transaction, returning the number of rows written per table
+    ///
+    /// Only tables and columns the current schema knows are written, so archives from older or
+    /// newer versions load as far as they overlap. `replace` empties each table first;
+    /// otherwise rows are upserted by primary key.
+    pub async fn import_tables(
+        &self,
+        tables: &BTreeMap<String, Vec<serde_json::Map<String, serde_json::Value>>>,
+        replace: bool,
+    ) -> Result<Vec<(String, u64)>> {
+        let known = self.archive_table_names().await?;
+        let mut schema = BTreeMap::new();
+        for table in tables.keys().filter(|t| known.contains(t)) {
+            schema.insert(table.clone(), self.table_columns(table).await?);
+        }
+
+        let mut written = Vec::new();
+        let mut tx = self.pool.begin().await?;
+        for (table, columns) in &schema {
+            if replace {
+                sqlx::query(&format!("DELETE FROM \"{}\"", table))
+                    .execute(&mut *tx)
+                    .await?;
+            }
+
+            let mut count = 0;
+            for row in &tables[table] {
+                let present: Vec<&String> = columns.iter().filter(|c| row.contains_key(*c)).collect();
+                if present.is_empty() {
+                    continue;
+                }
+                let sql = format!(
+                    "INSERT OR REPLACE INTO \"{}\" ({}) VALUES ({})",
+                    table,
+                    present
+                        .iter()
+                        .map(|c| format!("\"{}\"", c))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    vec!["?"; present.len()].join(", ")
+                );
+                let mut query = sqlx::query(&sql);
+                for column in &present {
+                    query = match &row[*column] {
+                        serde_json::Value::Null => query.bind(None::<String>),
+                        serde_json::Value::Bool(b) => query.bind(*b as i64),
+                        serde_json::Value::Number(n) => match n.as_i64() {
+                            Some(i) => query.bind(i),
+                            None => query.bind(n.as_f64()),
+                        },
+                        serde_json::Value::String(s) => query.bind(s.clone()),
+                        other => query.bind(other.to_string()),
+                    };
+                }
+                query
+                    .execute(&mut *tx)
+                    .await
+                    .with_context(|| format!("Failed to import a row into {}", table))?;
+                count += 1;
+            }
+            written.push((table.clone(), count));
+        }
+        tx.commit().await?;
+        Ok(written)
     }
 
     // =========================================================================
@@ -2312,6 +4725,45 @@ impl Cache {
 // Helper functions
 // =============================================================================
 
+/// `?, ?, ?` placeholder list for an `IN (...)` clause with `n` bound values
+fn sql_placeholders(n: usize) -> String {
+    vec!["?"; n].join(", ")
+}
+
+/// Write a signer's scan state to a `*_scan_cursors` table (nothing before its first cursor)
+async fn store_scan_state(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    table: &str,
+    account: &str,
+    state: &ScanState,
+) -> Result<()> {
+    let Some(cursor) = &state.cursor else {
+        return Ok(());
+    };
+    let resume = state.resume.as_ref();
+    sqlx::query(&format!(
+        "INSERT INTO {table} (account, signature, slot, resume_top_signature, resume_top_slot,
+                              resume_before_signature, resume_before_slot)
+         VALUES (?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(account) DO UPDATE SET
+         signature = excluded.signature, slot = excluded.slot,
+         resume_top_signature = excluded.resume_top_signature, resume_top_slot = excluded.resume_top_slot,
+         resume_before_signature = excluded.resume_before_signature,
+         resume_before_slot = excluded.resume_before_slot,
+         updated_at = datetime('now')"
+    ))
+    .bind(account)
+    .bind(&cursor.signature)
+    .bind(cursor.slot as i64)
+    .bind(resume.map(|r| r.top.signature.as_str()))
+    .bind(resume.map(|r| r.top.slot as i64))
+    .bind(resume.map(|r| r.before.signature.as_str()))
+    .bind(resume.map(|r| r.before.slot as i64))
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
 /// Convert a SolTransferRow to a SolTransfer
 fn row_to_transfer(r: SolTransferRow) -> Option<SolTransfer> {
     let from = Pubkey::from_str(&r.from_address).ok()?;
@@ -2330,6 +4782,8 @@ fn row_to_transfer(r: SolTransferRow) -> Option<SolTransfer> {
         to_label: r.to_label,
         from_category: string_to_category(&r.from_category),
         to_category: string_to_category(&r.to_category),
+        memo: r.memo,
+        program: r.program,
     })
 }
 
@@ -2351,6 +4805,19 @@ fn category_to_string(cat: &AddressCategory) -> &'static str {
 }
 
 /// Convert string to AddressCategory
+fn string_to_expense_category(s: &str) -> ExpenseCategory {
+    match s {
+        "Hosting" => ExpenseCategory::Hosting,
+        "Contractor" => ExpenseCategory::Contractor,
+        "Hardware" => ExpenseCategory::Hardware,
+        "Software" => ExpenseCategory::Software,
+        "VoteFees" => ExpenseCategory::VoteFees,
+        "FailedTxFees" => ExpenseCategory::FailedTxFees,
+        "OpsFees" => ExpenseCategory::OpsFees,
+        _ => ExpenseCategory::Other,
+    }
+}
+
 fn string_to_category(s: &str) -> AddressCategory {
     match s {
         "SolanaFoundation" => AddressCategory::SolanaFoundation,
@@ -2368,7 +4835,7 @@ fn string_to_category(s: &str) -> AddressCategory {
 }
 
 /// Cache statistics
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct CacheStats {
     pub epoch_rewards: u64,
     pub stake_rewards: u64,
@@ -2406,7 +4873,7 @@ impl std::fmt::Display for CacheStats {
 }
 
 /// Summary of external transfers for reconciliation
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize)]
 pub struct ExternalTransferSummary {
     /// Deposits received from external addresses
     pub deposits_in: Vec<ExternalAddressFlow>,
@@ -2415,7 +4882,7 @@ pub struct ExternalTransferSummary {
 }
 
 /// SOL flow to/from an external address
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct ExternalAddressFlow {
     /// The external address
     pub address: String,
@@ -2424,3 +4891,415 @@ pub struct ExternalAddressFlow {
     /// Total amount in lamports
     pub amount_lamports: u64,
 }
+
+/// A stored balance snapshot (subset of balance_history used for display)
+#[derive(Debug, Clone)]
+pub struct BalanceSnapshot {
+    pub date: String,
+    pub epoch: u64,
+    pub snapshot_slot: u64,
+    pub vote_account_lamports: u64,
+    pub identity_lamports: u64,
+    pub withdraw_authority_lamports: u64,
+    pub squads_vault_lamports: u64,
+    pub business_wallets_lamports: u64,
+    pub stake_liquid_lamports: u64,
+    pub stake_locked_lamports: u64,
+    pub jitosol_lamports: u64,
+    pub total_lamports: u64,
+}
+
+/// Which per-epoch sources have a cached row for one epoch
+#[derive(Debug, Clone)]
+pub struct EpochCoverage {
+    pub epoch: u64,
+    pub rewards: bool,
+    pub leader_fees: bool,
+    pub mev: bool,
+    pub bam: bool,
+    pub vote_costs: bool,
+    pub doublezero: bool,
+}
+
+impl EpochCoverage {
+    /// BAM rewards only exist from `BAM_FIRST_EPOCH`
+    pub fn bam_applies(&self) -> bool {
+        self.epoch >= constants::BAM_FIRST_EPOCH
+    }
+
+    /// DoubleZero fees only accrue from `DOUBLEZERO_FIRST_EPOCH`
+    pub fn doublezero_applies(&self) -> bool {
+        self.epoch >= constants::DOUBLEZERO_FIRST_EPOCH
+    }
+
+    /// Names of the sources missing for this epoch (sources that don't apply yet are ignored)
+    pub fn missing_sources(&self) -> Vec<&'static str> {
+        let checks = [
+            ("rewards", self.rewards, true),
+            ("leader_fees", self.leader_fees, true),
+            ("mev", self.mev, true),
+            ("bam", self.bam, self.bam_applies()),
+            ("vote_costs", self.vote_costs, true),
+            ("doublezero", self.doublezero, self.doublezero_applies()),
+        ];
+        checks
+            .into_iter()
+            .filter(|&(_, present, applies)| applies && !present)
+            .map(|(name, _, _)| name)
+            .collect()
+    }
+}
+
+/// Per-source outcome of an ingestion run, accumulated while the pipeline executes
+#[derive(Debug, Default)]
+pub struct IngestionRunLog {
+    /// Rows loaded per source (e.g. "epoch_rewards" -> 42)
+    pub row_counts: BTreeMap<String, u64>,
+    /// Non-fatal errors, prefixed with the source that produced them
+    pub errors: Vec<String>,
+}
+
+impl IngestionRunLog {
+    /// Record how many rows a source produced
+    pub fn record_rows(&mut self, source: &str, rows: usize) {
+        self.row_counts.insert(source.to_string(), rows as u64);
+    }
+
+    /// Record a non-fatal error for a source
+    pub fn record_error(&mut self, source: &str, error: impl std::fmt::Display) {
+        self.errors.push(format!("{}: {}", source, error));
+    }
+}
+
+/// Last sync outcome of a data source
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceHealth {
+    pub name: String,
+    pub last_success_at: Option<String>,
+    pub last_error_at: Option<String>,
+    pub last_error: Option<String>,
+    /// Failed syncs since the last success
+    pub consecutive_failures: u32,
+    /// Rows loaded by the last successful sync
+    pub last_rows: u64,
+}
+
+impl SourceHealth {
+    /// "ok", "failing", or "degraded" (failed since an earlier success)
+    pub fn status(&self) -> &'static str {
+        match (self.consecutive_failures, &self.last_success_at) {
+            (0, _) => "ok",
+            (_, Some(_)) => "degraded",
+            (_, None) => "failing",
+        }
+    }
+}
+
+/// SOL amounts summed in SQL, keyed by month ("YYYY-MM") or date, with USD at the exact-date price
+#[derive(Debug, Default, Clone)]
+pub struct GroupedAmounts {
+    /// Key -> (SOL, USD) for rows with a cached price for their date
+    pub priced: BTreeMap<String, (f64, f64)>,
+    /// Date -> SOL for rows without one, or with only a stored fallback price (valued by
+    /// `get_price`, which reports them as fallback-priced)
+    pub unpriced: BTreeMap<String, f64>,
+    by_month: bool,
+}
+
+impl GroupedAmounts {
+    /// The grouping `Cache::grouped_amounts` computes in SQL, over rows already in memory
+    /// (used when `validation` quarantines rows the SQL sums included)
+    pub fn from_rows<'a>(
+        rows: impl IntoIterator<Item = (Option<&'a str>, f64)>,
+        prices: &PriceCache,
+        by_month: bool,
+    ) -> Self {
+        let mut grouped = Self {
+            by_month,
+            ..Default::default()
+        };
+        for (date, sol) in rows {
+            let Some(date) = date.filter(|d| d.len() >= 10) else {
+                continue;
+            };
+            match prices.get(date).filter(|p| p.source != PriceSource::Fallback) {
+                Some(price) => {
+                    let key = if by_month { &date[..7] } else { date };
+                    let entry = grouped.priced.entry(key.to_string()).or_default();
+                    entry.0 += sol;
+                    entry.1 += sol * price.usd_price;
+                }
+                None => *grouped.unpriced.entry(date.to_string()).or_default() += sol,
+            }
+        }
+        grouped
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.priced.is_empty() && self.unpriced.is_empty()
+    }
+
+    /// (SOL, USD) per key, valuing unpriced rows the same way the ledgers do
+    pub fn valued(&self, prices: &PriceCache) -> BTreeMap<String, (f64, f64)> {
+        let mut out = self.priced.clone();
+        for (date, sol) in &self.unpriced {
+            let key = if self.by_month { &date[..7] } else { date.as_str() };
+            let entry = out.entry(key.to_string()).or_default();
+            entry.0 += sol;
+            entry.1 += sol * get_price(prices, date);
+        }
+        out
+    }
+}
+
+/// Summary-path aggregates for the per-epoch tables (`Cache::get_monthly_aggregates`)
+#[derive(Debug, Default, Clone)]
+pub struct MonthlyAggregates {
+    pub commission: GroupedAmounts,
+    pub leader_fees: GroupedAmounts,
+    pub mev_claims: GroupedAmounts,
+    pub doublezero_fees: GroupedAmounts,
+    /// Keyed by date: SFDP coverage of vote costs depends on the day
+    pub vote_costs: GroupedAmounts,
+}
+
+/// One epoch imported from an aggregator (`import fetch`)
+#[derive(Debug, Clone, Serialize)]
+pub struct RewardImport {
+    pub epoch: u64,
+    pub source: String,
+    pub commission_lamports: Option<u64>,
+    pub activated_stake_lamports: Option<u64>,
+    pub imported_at: String,
+    /// Set once the epoch has been fetched on-chain
+    pub verified_at: Option<String>,
+    pub onchain_lamports: Option<u64>,
+}
+
+/// An operator-tagged address (`addresses tag`)
+#[derive(Debug, Clone)]
+pub struct AddressTag {
+    pub address: String,
+    pub label: String,
+    pub category: AddressCategory,
+    pub created_at: String,
+}
+
+/// A recorded ingestion run
+#[derive(Debug, Serialize)]
+pub struct IngestionRun {
+    pub id: i64,
+    /// What started the run ("cli", "scheduler", ...)
+    pub triggered_by: String,
+    pub started_at: String,
+    /// None while the run is in progress (or if the process died mid-run)
+    pub finished_at: Option<String>,
+    /// running, success, partial, failed, or skipped
+    pub status: String,
+    pub row_counts: BTreeMap<String, u64>,
+    pub errors: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sums = BTreeMap<String, (f64, f64)>;
+
+    /// Row-level sums, valued per row with `get_price` the way the ledgers do
+    fn row_sums<'a>(
+        rows: impl IntoIterator<Item = (Option<&'a str>, f64)>,
+        prices: &PriceCache,
+        by_month: bool,
+    ) -> Sums {
+        let mut sums = Sums::new();
+        for (date, sol) in rows {
+            let date = date.unwrap();
+            let key = if by_month { &date[..7] } else { date };
+            let entry = sums.entry(key.to_string()).or_default();
+            entry.0 += sol;
+            entry.1 += sol * get_price(prices, date);
+        }
+        sums
+    }
+
+    fn assert_same(what: &str, sql: &Sums, rows: &Sums) {
+        assert_eq!(
+            sql.keys().collect::<Vec<_>>(),
+            rows.keys().collect::<Vec<_>>(),
+            "{}",
+            what
+        );
+        for (key, (sol, usd)) in sql {
+            let (row_sol, row_usd) = rows[key];
+            assert!(
+                (sol - row_sol).abs() < 1e-9,
+                "{} {} SOL: {} vs {}",
+                what,
+                key,
+                sol,
+                row_sol
+            );
+            assert!(
+                (usd - row_usd).abs() < 1e-6,
+                "{} {} USD: {} vs {}",
+                what,
+                key,
+                usd,
+                row_usd
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_monthly_aggregates_match_row_level_sums() {
+        let dir = std::env::temp_dir().join(format!("va-aggregates-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let cache = Cache::open(&dir.join("cache.sqlite")).await.unwrap();
+
+        // An exact price, a stored fallback, a day with no price (2 days from the next
+        // exact one) and a day with no price near it at all
+        for (date, price, source) in [
+            ("2025-06-10", 150.0, "exact"),
+            ("2025-06-20", constants::FALLBACK_SOL_PRICE, "fallback"),
+            ("2025-07-17", 170.0, "exact"),
+        ] {
+            sqlx::query("INSERT INTO prices (date, usd_price, source) VALUES (?, ?, ?)")
+                .bind(date)
+                .bind(price)
+                .bind(source)
+                .execute(&cache.pool)
+                .await
+                .unwrap();
+        }
+        let days = [
+            (800, "2025-06-10", 1.5),
+            (801, "2025-06-20", 2.0),
+            (802, "2025-07-15", 0.75),
+            (803, "2025-07-17", 3.0),
+            (804, "2025-08-30", 0.5),
+        ];
+        let date = |d: &str| Some(d.to_string());
+        let lamports = |sol: f64| (sol * 1e9) as u64;
+
+        let rewards: Vec<_> = days
+            .iter()
+            .map(|&(epoch, d, sol)| EpochReward {
+                epoch,
+                amount_lamports: lamports(sol),
+                amount_sol: sol,
+                commission: 5,
+                effective_slot: epoch * 432_000,
+                date: date(d),
+            })
+            .collect();
+        let leader_fees: Vec<_> = days
+            .iter()
+            .map(|&(epoch, d, sol)| EpochLeaderFees {
+                epoch,
+                leader_slots: 4,
+                blocks_produced: 4,
+                skipped_slots: 0,
+                total_fees_lamports: lamports(sol / 10.0),
+                total_fees_sol: sol / 10.0,
+                date: date(d),
+            })
+            .collect();
+        let mev_claims: Vec<_> = days
+            .iter()
+            .map(|&(epoch, d, sol)| MevClaim {
+                epoch,
+                provider: "jito".to_string(),
+                total_tips_lamports: lamports(sol * 2.0),
+                commission_lamports: lamports(sol / 5.0),
+                amount_sol: sol / 5.0,
+                date: date(d),
+            })
+            .collect();
+        let vote_costs: Vec<_> = days
+            .iter()
+            .map(|&(epoch, d, sol)| EpochVoteCost {
+                epoch,
+                vote_count: 1_000,
+                total_fee_lamports: lamports(sol / 3.0),
+                total_fee_sol: sol / 3.0,
+                source: "rpc".to_string(),
+                date: date(d),
+            })
+            .collect();
+        let doublezero_fees: Vec<_> = days
+            .iter()
+            .map(|&(epoch, d, sol)| DoubleZeroFee {
+                epoch,
+                fee_base_lamports: lamports(sol / 10.0),
+                liability_lamports: lamports(sol / 200.0),
+                liability_sol: sol / 200.0,
+                fee_rate_bps: 500,
+                date: date(d),
+                source: "computed".to_string(),
+                is_estimate: false,
+            })
+            .collect();
+        cache.store_epoch_rewards(&rewards).await.unwrap();
+        cache.store_leader_fees(&leader_fees).await.unwrap();
+        cache.store_mev_claims(&mev_claims).await.unwrap();
+        cache.store_vote_costs(&vote_costs).await.unwrap();
+        cache.store_doublezero_fees(&doublezero_fees).await.unwrap();
+
+        let prices = cache.get_prices().await.unwrap();
+        let aggregates = cache.get_monthly_aggregates(800, 804).await.unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        let cases: [(&str, &GroupedAmounts, Vec<(Option<&str>, f64)>, bool); 5] = [
+            (
+                "commission",
+                &aggregates.commission,
+                rewards.iter().map(|r| (r.date.as_deref(), r.amount_sol)).collect(),
+                true,
+            ),
+            (
+                "leader fees",
+                &aggregates.leader_fees,
+                leader_fees
+                    .iter()
+                    .map(|f| (f.date.as_deref(), f.total_fees_sol))
+                    .collect(),
+                true,
+            ),
+            (
+                "mev",
+                &aggregates.mev_claims,
+                mev_claims.iter().map(|c| (c.date.as_deref(), c.amount_sol)).collect(),
+                true,
+            ),
+            (
+                "doublezero",
+                &aggregates.doublezero_fees,
+                doublezero_fees
+                    .iter()
+                    .map(|f| (f.date.as_deref(), f.liability_sol))
+                    .collect(),
+                true,
+            ),
+            (
+                "vote costs",
+                &aggregates.vote_costs,
+                vote_costs
+                    .iter()
+                    .map(|c| (c.date.as_deref(), c.total_fee_sol))
+                    .collect(),
+                false,
+            ),
+        ];
+        for (what, sql, rows, by_month) in cases {
+            let expected = row_sums(rows.iter().copied(), &prices, by_month);
+            assert_same(what, &sql.valued(&prices), &expected);
+            let in_memory = GroupedAmounts::from_rows(rows, &prices, by_month);
+            assert_same(what, &in_memory.valued(&prices), &expected);
+        }
+
+        // The fallback-priced day is valued like the ledgers value it, not as a priced day
+        assert!(aggregates.commission.unpriced.contains_key("2025-06-20"));
+        assert!(!aggregates.commission.unpriced.contains_key("2025-06-10"));
+    }
+}
@@ -0,0 +1,560 @@
+//! Credit card statement reconciliation (`card import` / `card match`)
+//!
+//! Hosting bills and subscriptions are often charged to a card and entered as expenses
+//! by hand, so a bill can be forgotten or entered twice. The operator exports the card
+//! statement as CSV, imports it into the cache, and the matcher pairs each charge with a
+//! card-paid expense of the same amount dated within a few days of it, preferring the
+//! expense whose vendor appears in the card descriptor. Charges nothing matched are
+//! flagged as potentially missing expenses; card-paid expenses in the statement period
+//! with no charge are flagged as potentially duplicated (or recorded with the wrong
+//! payment method).
+
+use anyhow::{Context, Result, bail};
+use chrono::NaiveDate;
+use csv::{ReaderBuilder, StringRecord, WriterBuilder};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::constants;
+use crate::expenses::Expense;
+
+/// Default distance in days between a charge and the expense it pays
+pub const DEFAULT_WINDOW_DAYS: i64 = 5;
+
+/// Default amount tolerance (one cent)
+pub const DEFAULT_TOLERANCE_USD: f64 = 0.01;
+
+/// `paid_with` value of the expenses reconciled by default
+pub const DEFAULT_PAID_WITH: &str = "Credit Card";
+
+/// Share of vendor name tokens that must appear in the card descriptor for a vendor match
+const MIN_VENDOR_SCORE: f64 = 0.5;
+
+/// Header names accepted for each column, in order of preference
+const DATE_COLUMNS: [&str; 5] = ["Transaction Date", "Trans. Date", "Date", "Posted Date", "Post Date"];
+const DESCRIPTION_COLUMNS: [&str; 4] = ["Description", "Merchant", "Payee", "Name"];
+const AMOUNT_COLUMNS: [&str; 2] = ["Amount", "Amount (USD)"];
+const DEBIT_COLUMNS: [&str; 2] = ["Debit", "Charges"];
+const REFERENCE_COLUMNS: [&str; 3] = ["Reference", "Reference Number", "Transaction ID"];
+
+/// One charge from an imported card statement
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatementLine {
+    /// Bank reference when the export has one, otherwise derived from the row itself
+    /// (so re-importing an overlapping statement doesn't duplicate lines)
+    pub reference: String,
+    /// YYYY-MM-DD
+    pub date: String,
+    pub description: String,
+    /// Charge amount (always positive)
+    pub amount_usd: f64,
+}
+
+/// Column positions found in the header row
+struct Columns {
+    date: usize,
+    description: usize,
+    amount: Amount,
+    reference: Option<usize>,
+}
+
+/// How an export records charges
+enum Amount {
+    /// One signed column; charges are whichever sign most rows have
+    Signed(usize),
+    /// Separate debit (charge) and credit (payment/refund) columns
+    Debit(usize),
+}
+
+impl Columns {
+    fn detect(header: &StringRecord) -> Option<Self> {
+        let find = |names: &[&str]| {
+            names
+                .iter()
+                .find_map(|name| header.iter().position(|h| h.trim().eq_ignore_ascii_case(name)))
+        };
+        let amount = match (find(&AMOUNT_COLUMNS), find(&DEBIT_COLUMNS)) {
+            (_, Some(debit)) => Amount::Debit(debit),
+            (Some(amount), None) => Amount::Signed(amount),
+            (None, None) => return None,
+        };
+        Some(Self {
+            date: find(&DATE_COLUMNS)?,
+            description: find(&DESCRIPTION_COLUMNS)?,
+            amount,
+            reference: find(&REFERENCE_COLUMNS),
+        })
+    }
+}
+
+/// Parse charges from a card statement export, detecting its columns from the header row
+pub fn import_statement(path: &Path) -> Result<Vec<StatementLine>> {
+    let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    parse_statement(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Parse charges from statement CSV text. Payments and refunds are skipped.
+pub fn parse_statement(content: &str) -> Result<Vec<StatementLine>> {
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(content.as_bytes());
+
+    let mut columns: Option<Columns> = None;
+    // (date, description, signed amount, reference)
+    let mut rows = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let record = record?;
+        let Some(cols) = &columns else {
+            columns = Columns::detect(&record);
+            continue;
+        };
+        let field = |i: usize| record.get(i).map(str::trim).unwrap_or("");
+        if field(cols.date).is_empty() {
+            continue;
+        }
+        let row = index + 1;
+        let date = parse_date(field(cols.date)).with_context(|| format!("row {}", row))?;
+        let amount = match cols.amount {
+            Amount::Signed(i) => parse_amount(field(i)).with_context(|| format!("row {}", row))?,
+            Amount::Debit(i) if field(i).is_empty() => continue,
+            Amount::Debit(i) => parse_amount(field(i)).with_context(|| format!("row {}", row))?.abs(),
+        };
+        let reference = cols.reference.map(field).unwrap_or("").to_string();
+        rows.push((date, field(cols.description).to_string(), amount, reference));
+    }
+
+    if columns.is_none() {
+        bail!("Unrecognized statement: expected a header with date, description and amount (or debit) columns");
+    }
+
+    // Issuers disagree on the sign of a charge; a statement is mostly charges
+    let negatives = rows.iter().filter(|r| r.2 < 0.0).count();
+    let positives = rows.iter().filter(|r| r.2 > 0.0).count();
+    let charge_sign = if negatives > positives { -1.0 } else { 1.0 };
+
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    Ok(rows
+        .into_iter()
+        .filter(|(_, _, amount, _)| amount * charge_sign > 0.0)
+        .map(|(date, description, amount, reference)| {
+            let amount_usd = amount.abs();
+            let reference = if reference.is_empty() {
+                // Same-day identical charges are told apart by their order in the file
+                let base = format!("{}|{}|{:.2}", date, description, amount_usd);
+                let n = seen.entry(base.clone()).or_default();
+                *n += 1;
+                format!("{}|{}", base, n)
+            } else {
+                reference
+            };
+            StatementLine {
+                reference,
+                date,
+                description,
+                amount_usd,
+            }
+        })
+        .collect())
+}
+
+/// Parse "2025-03-01", "03/01/2025" or "03/01/25" as YYYY-MM-DD
+fn parse_date(value: &str) -> Result<String> {
+    ["%Y-%m-%d", "%m/%d/%Y", "%m/%d/%y"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .with_context(|| format!("Invalid date '{}'", value))
+}
+
+/// Parse "-1,234.56", "$1,234.56" or "(1,234.56)" (negative)
+fn parse_amount(value: &str) -> Result<f64> {
+    let (negative, digits) = match value.strip_prefix('(').and_then(|v| v.strip_suffix(')')) {
+        Some(inner) => (true, inner),
+        None => (false, value),
+    };
+    let cleaned: String = digits.chars().filter(|c| !matches!(c, '$' | ',' | ' ')).collect();
+    let amount = cleaned
+        .parse::<f64>()
+        .with_context(|| format!("Invalid amount '{}'", value))?;
+    Ok(if negative { -amount } else { amount })
+}
+
+/// Lowercase letters and digits only
+fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Share of the vendor's name tokens found in the card descriptor, 0.0 to 1.0.
+///
+/// Descriptors are truncated and punctuated unpredictably ("LATITUDE.SH* INV 1234"), so
+/// tokens are compared with punctuation stripped; tokens shorter than three characters
+/// ("sh", "co") are ignored unless the name has nothing else.
+pub fn vendor_score(vendor: &str, description: &str) -> f64 {
+    let descriptor = normalize(description);
+    let tokens: Vec<String> = vendor
+        .split(|c: char| !c.is_alphanumeric())
+        .map(normalize)
+        .filter(|t| !t.is_empty())
+        .collect();
+    let significant: Vec<&String> = tokens.iter().filter(|t| t.len() >= 3).collect();
+    let tokens: Vec<&String> = if significant.is_empty() {
+        tokens.iter().collect()
+    } else {
+        significant
+    };
+    if tokens.is_empty() || descriptor.is_empty() {
+        return 0.0;
+    }
+    let found = tokens.iter().filter(|t| descriptor.contains(t.as_str())).count();
+    found as f64 / tokens.len() as f64
+}
+
+/// Outcome for one statement line or expense
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardStatus {
+    /// Charge paired with an expense whose vendor appears in the descriptor
+    Matched,
+    /// Paired on amount and date only; the descriptor doesn't name the vendor
+    AmountOnly,
+    /// Charge with no expense: potentially missing from the books
+    MissingExpense,
+    /// Card-paid expense with no charge: potentially duplicated or not paid by card
+    PossibleDuplicate,
+}
+
+impl CardStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Matched => "matched",
+            Self::AmountOnly => "matched_amount_only",
+            Self::MissingExpense => "missing_expense",
+            Self::PossibleDuplicate => "possible_duplicate",
+        }
+    }
+}
+
+/// A reconciliation row: a statement line, an expense, or both
+#[derive(Debug, Clone)]
+pub struct CardMatch<'a> {
+    pub line: Option<&'a StatementLine>,
+    pub expense: Option<&'a Expense>,
+    pub status: CardStatus,
+    pub vendor_score: f64,
+}
+
+/// Whether an expense was charged to the card
+fn paid_by_card(expense: &Expense, paid_with: &str) -> bool {
+    expense.paid_with.trim().eq_ignore_ascii_case(paid_with.trim())
+}
+
+fn days_between(a: &str, b: &str) -> Option<i64> {
+    let a = NaiveDate::parse_from_str(a, "%Y-%m-%d").ok()?;
+    let b = NaiveDate::parse_from_str(b, "%Y-%m-%d").ok()?;
+    Some((a - b).num_days().abs())
+}
+
+/// Pair statement lines with card-paid expenses one-to-one.
+///
+/// Only expenses with `paid_with` equal to `paid_with` and dated within the statement
+/// period (widened by `window_days`) take part. Lines are taken oldest first in two
+/// passes: the first pairs each line with the unused expense within `tolerance_usd` and
+/// `window_days` whose vendor best matches the descriptor; the second pairs what's left
+/// on amount and date alone, closest date first. Returns every line, then every
+/// unpaired expense.
+pub fn reconcile<'a>(
+    lines: &'a [StatementLine],
+    expenses: &'a [Expense],
+    paid_with: &str,
+    window_days: i64,
+    tolerance_usd: f64,
+) -> Vec<CardMatch<'a>> {
+    let mut ordered: Vec<&StatementLine> = lines.iter().collect();
+    ordered.sort_by(|a, b| a.date.cmp(&b.date).then(a.reference.cmp(&b.reference)));
+
+    let parse = |date: &str| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+    let (Some(first), Some(last)) = (
+        ordered.first().and_then(|l| parse(&l.date)),
+        ordered.last().and_then(|l| parse(&l.date)),
+    ) else {
+        return Vec::new();
+    };
+    let window = chrono::Duration::days(window_days);
+    let in_period = |date: &str| parse(date).is_some_and(|d| d >= first - window && d <= last + window);
+    let card_expenses: Vec<&Expense> = expenses
+        .iter()
+        .filter(|e| paid_by_card(e, paid_with) && in_period(&e.date))
+        .collect();
+
+    let candidates = |line: &StatementLine, used: &HashSet<usize>| -> Vec<(usize, i64, f64, f64)> {
+        card_expenses
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !used.contains(i))
+            .filter_map(|(i, e)| {
+                let days = days_between(&e.date, &line.date)?;
+                let diff = (e.amount_usd - line.amount_usd).abs();
+                (days <= window_days && diff <= tolerance_usd)
+                    .then(|| (i, days, diff, vendor_score(&e.vendor, &line.description)))
+            })
+            .collect()
+    };
+
+    let mut used: HashSet<usize> = HashSet::new();
+    let mut paired: HashMap<&str, (usize, f64)> = HashMap::new();
+    for line in &ordered {
+        let best = candidates(line, &used)
+            .into_iter()
+            .filter(|c| c.3 >= MIN_VENDOR_SCORE)
+            .max_by(|a, b| a.3.total_cmp(&b.3).then(b.1.cmp(&a.1)).then(b.2.total_cmp(&a.2)));
+        if let Some((i, _, _, score)) = best {
+            used.insert(i);
+            paired.insert(line.reference.as_str(), (i, score));
+        }
+    }
+    for line in ordered.iter().filter(|l| !paired.contains_key(l.reference.as_str())) {
+        let best = candidates(line, &used)
+            .into_iter()
+            .min_by(|a, b| a.1.cmp(&b.1).then(a.2.total_cmp(&b.2)));
+        if let Some((i, _, _, score)) = best {
+            used.insert(i);
+            paired.insert(line.reference.as_str(), (i, score));
+        }
+    }
+
+    let mut matches: Vec<CardMatch> = ordered
+        .iter()
+        .map(|line| match paired.get(line.reference.as_str()) {
+            Some(&(i, score)) => CardMatch {
+                line: Some(line),
+                expense: Some(card_expenses[i]),
+                status: if score >= MIN_VENDOR_SCORE {
+                    CardStatus::Matched
+                } else {
+                    CardStatus::AmountOnly
+                },
+                vendor_score: score,
+            },
+            None => CardMatch {
+                line: Some(line),
+                expense: None,
+                status: CardStatus::MissingExpense,
+                vendor_score: 0.0,
+            },
+        })
+        .collect();
+    matches.extend(
+        card_expenses
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !used.contains(i))
+            .map(|(_, e)| CardMatch {
+                line: None,
+                expense: Some(e),
+                status: CardStatus::PossibleDuplicate,
+                vendor_score: 0.0,
+            }),
+    );
+    matches
+}
+
+/// Write the reconciliation CSV: every statement line with its status, then unpaired expenses
+pub fn write_reconciliation_csv(
+    output_dir: &Path,
+    matches: &[CardMatch],
+    paid_with: &str,
+    window_days: i64,
+) -> Result<()> {
+    let path = output_dir.join(constants::CARD_RECONCILIATION_FILENAME);
+    let mut wtr = WriterBuilder::new().flexible(true).from_path(&path)?;
+
+    wtr.write_record([format!(
+        "Card statement charges matched to expenses paid with '{}' by amount within {} days. \
+         missing_expense: charge with no expense recorded. possible_duplicate: expense with no charge.",
+        paid_with, window_days
+    )])?;
+    wtr.write_record([""])?;
+    wtr.write_record([
+        "Status",
+        "Statement_Date",
+        "Statement_Description",
+        "Statement_Amount_USD",
+        "Statement_Reference",
+        "Expense_ID",
+        "Expense_Date",
+        "Expense_Vendor",
+        "Expense_Amount_USD",
+        "Vendor_Score",
+    ])?;
+
+    for m in matches {
+        let (line_date, description, line_amount, reference) = match m.line {
+            Some(l) => (
+                l.date.clone(),
+                l.description.clone(),
+                format!("{:.2}", l.amount_usd),
+                l.reference.clone(),
+            ),
+            None => Default::default(),
+        };
+        let (expense_id, expense_date, vendor, expense_amount) = match m.expense {
+            Some(e) => (
+                e.id.map(|id| id.to_string()).unwrap_or_else(|| "recurring".to_string()),
+                e.date.clone(),
+                e.vendor.clone(),
+                format!("{:.2}", e.amount_usd),
+            ),
+            None => Default::default(),
+        };
+        let score = if m.line.is_some() && m.expense.is_some() {
+            format!("{:.2}", m.vendor_score)
+        } else {
+            String::new()
+        };
+        wtr.write_record([
+            m.status.as_str().to_string(),
+            line_date,
+            description,
+            line_amount,
+            reference,
+            expense_id,
+            expense_date,
+            vendor,
+            expense_amount,
+            score,
+        ])?;
+    }
+
+    wtr.flush()?;
+    println!("  Generated: {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expenses::{ExpenseCategory, ExpenseStatus};
+
+    fn line(date: &str, description: &str, amount_usd: f64) -> StatementLine {
+        StatementLine {
+            reference: format!("{}|{}", date, description),
+            date: date.to_string(),
+            description: description.to_string(),
+            amount_usd,
+        }
+    }
+
+    fn expense(id: i64, date: &str, vendor: &str, amount_usd: f64, paid_with: &str) -> Expense {
+        Expense {
+            id: Some(id),
+            date: date.to_string(),
+            vendor: vendor.to_string(),
+            category: ExpenseCategory::Hosting,
+            description: String::new(),
+            amount_usd,
+            paid_with: paid_with.to_string(),
+            invoice_id: None,
+            entity: None,
+            status: ExpenseStatus::Approved,
+        }
+    }
+
+    #[test]
+    fn test_parse_chase_style_negative_charges() {
+        let csv = "Transaction Date,Post Date,Description,Category,Type,Amount,Memo\n\
+                   03/01/2025,03/02/2025,LATITUDE.SH* INV 1234,Bills,Sale,-1250.00,\n\
+                   03/05/2025,03/05/2025,PAYMENT THANK YOU,,Payment,1250.00,\n\
+                   03/09/2025,03/10/2025,GITHUB,Software,Sale,-4.00,\n\
+                   03/09/2025,03/10/2025,GITHUB,Software,Sale,-4.00,\n";
+        let lines = parse_statement(csv).unwrap();
+        let summary: Vec<(&str, &str, f64)> = lines
+            .iter()
+            .map(|l| (l.date.as_str(), l.description.as_str(), l.amount_usd))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("2025-03-01", "LATITUDE.SH* INV 1234", 1250.0),
+                ("2025-03-09", "GITHUB", 4.0),
+                ("2025-03-09", "GITHUB", 4.0),
+            ]
+        );
+        // Identical same-day charges keep distinct references
+        assert_ne!(lines[1].reference, lines[2].reference);
+    }
+
+    #[test]
+    fn test_parse_debit_credit_columns_and_reject_unknown() {
+        let csv = "Date,Description,Debit,Credit,Reference\n\
+                   2025-03-01,\"HETZNER ONLINE\",\"$1,020.50\",,R1\n\
+                   2025-03-03,REFUND,,20.00,R2\n";
+        let lines = parse_statement(csv).unwrap();
+        assert_eq!(
+            lines,
+            vec![StatementLine {
+                reference: "R1".to_string(),
+                date: "2025-03-01".to_string(),
+                description: "HETZNER ONLINE".to_string(),
+                amount_usd: 1020.5,
+            }]
+        );
+
+        assert!(parse_statement("a,b,c\n1,2,3\n").is_err());
+    }
+
+    #[test]
+    fn test_vendor_score_ignores_punctuation_and_short_tokens() {
+        assert_eq!(vendor_score("Latitude.sh", "LATITUDE.SH* INV 1234"), 1.0);
+        assert_eq!(vendor_score("Hetzner Online GmbH", "HETZNER ONLINE"), 2.0 / 3.0);
+        assert_eq!(vendor_score("AWS", "AMAZON WEB SERVICES"), 0.0);
+    }
+
+    #[test]
+    fn test_reconcile_flags_missing_and_duplicated() {
+        let lines = vec![
+            line("2025-03-01", "LATITUDE.SH* INV 1234", 1250.0),
+            line("2025-03-02", "HETZNER ONLINE", 40.0),
+            line("2025-03-10", "NEW VENDOR", 99.0),
+        ];
+        let expenses = vec![
+            // Same amount, but the vendor match wins over the closer date
+            expense(1, "2025-03-02", "Other Host", 1250.0, "Credit Card"),
+            expense(2, "2025-02-28", "Latitude.sh", 1250.0, "credit card"),
+            expense(3, "2025-03-04", "Server Co", 40.0, "Credit Card"),
+            // Paid in USD, not part of the card reconciliation
+            expense(4, "2025-03-10", "New Vendor", 99.0, "USD"),
+            // Outside the statement period
+            expense(5, "2025-06-01", "Latitude.sh", 1250.0, "Credit Card"),
+        ];
+
+        let matches = reconcile(
+            &lines,
+            &expenses,
+            DEFAULT_PAID_WITH,
+            DEFAULT_WINDOW_DAYS,
+            DEFAULT_TOLERANCE_USD,
+        );
+        let outcome: Vec<(Option<&str>, Option<i64>, &str)> = matches
+            .iter()
+            .map(|m| {
+                (
+                    m.line.map(|l| l.description.as_str()),
+                    m.expense.and_then(|e| e.id),
+                    m.status.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            outcome,
+            vec![
+                (Some("LATITUDE.SH* INV 1234"), Some(2), "matched"),
+                (Some("HETZNER ONLINE"), Some(3), "matched_amount_only"),
+                (Some("NEW VENDOR"), None, "missing_expense"),
+                (None, Some(1), "possible_duplicate"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,255 @@
+//! Validator client metrics (Agave / Firedancer), optional
+//!
+//! Scrapes the local validator's metrics endpoint, either Prometheus text
+//! (Firedancer, or Agave behind an exporter) or InfluxDB line protocol (Agave's
+//! metrics), for produced/skipped leader slots and vote latency. Samples are stored
+//! in the cache so the TUI can show them without touching the network, and
+//! `metrics check` cross-checks the client's slot counters against
+//! `getBlockProduction` over the same slot range.
+//!
+//! Metric names differ between clients and versions, so they are configurable under
+//! `[metrics]`. Counters are cumulative since the client started; per-epoch numbers
+//! come from the difference between the first and latest sample of the epoch.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcBlockProductionConfig, RpcBlockProductionConfigRange};
+use solana_commitment_config::CommitmentConfig;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::cache::Cache;
+use crate::config::{Config, MetricsConfig};
+use crate::constants;
+
+/// Exposition format of the metrics endpoint
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricsFormat {
+    #[default]
+    Prometheus,
+    Influx,
+}
+
+/// One scrape of the client's metrics
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricSample {
+    /// Unix timestamp of the scrape
+    pub scraped_at: i64,
+    pub slot: u64,
+    pub epoch: u64,
+    /// Cumulative leader slots produced since the client started
+    pub produced: Option<u64>,
+    /// Cumulative leader slots skipped since the client started
+    pub skipped: Option<u64>,
+    /// Vote latency in slots (gauge, or the histogram mean)
+    pub vote_latency_slots: Option<f64>,
+}
+
+/// Parse Prometheus text exposition into name -> value, summing across label sets
+pub fn parse_prometheus(text: &str) -> HashMap<String, f64> {
+    let mut values = HashMap::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        // `name{labels} value [timestamp]`; label values may contain spaces
+        let (name, rest) = match line.find('{') {
+            Some(open) => match line[open..].find('}') {
+                Some(close) => (&line[..open], &line[open + close + 1..]),
+                None => continue,
+            },
+            None => match line.split_once(char::is_whitespace) {
+                Some((name, rest)) => (name, rest),
+                None => continue,
+            },
+        };
+        let Some(value) = rest.split_whitespace().next().and_then(|v| v.parse::<f64>().ok()) else {
+            continue;
+        };
+        *values.entry(name.to_string()).or_insert(0.0) += value;
+    }
+    values
+}
+
+/// Parse InfluxDB line protocol into `measurement.field` -> value (last point wins)
+pub fn parse_influx(text: &str) -> HashMap<String, f64> {
+    let mut values = HashMap::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut parts = line.split(' ');
+        let (Some(series), Some(fields)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let measurement = series.split(',').next().unwrap_or(series);
+        for field in fields.split(',') {
+            let Some((key, raw)) = field.split_once('=') else {
+                continue;
+            };
+            let raw = raw.trim_end_matches(['i', 'u']);
+            if let Ok(value) = raw.parse::<f64>() {
+                values.insert(format!("{}.{}", measurement, key), value);
+            }
+        }
+    }
+    values
+}
+
+/// Value of `name`, or the mean of a histogram/summary's `name_sum` / `name_count`
+fn metric(values: &HashMap<String, f64>, name: &str) -> Option<f64> {
+    values.get(name).copied().or_else(|| {
+        let sum = values.get(&format!("{}_sum", name))?;
+        let count = values.get(&format!("{}_count", name)).filter(|c| **c > 0.0)?;
+        Some(sum / count)
+    })
+}
+
+/// Pick the configured metrics out of a parsed scrape
+pub fn extract(values: &HashMap<String, f64>, metrics: &MetricsConfig) -> (Option<u64>, Option<u64>, Option<f64>) {
+    (
+        metric(values, &metrics.produced_metric).map(|v| v.max(0.0) as u64),
+        metric(values, &metrics.skipped_metric).map(|v| v.max(0.0) as u64),
+        metric(values, &metrics.vote_latency_metric),
+    )
+}
+
+/// Scrape the endpoint once and store the sample
+pub async fn scrape(cache: &Cache, config: &Config, metrics: &MetricsConfig) -> Result<MetricSample> {
+    let client = reqwest::Client::builder().timeout(Duration::from_secs(5)).build()?;
+    let body = client
+        .get(&metrics.url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .with_context(|| format!("Failed to scrape {}", metrics.url))?
+        .text()
+        .await?;
+    let values = match metrics.format {
+        MetricsFormat::Prometheus => parse_prometheus(&body),
+        MetricsFormat::Influx => parse_influx(&body),
+    };
+    let (produced, skipped, vote_latency_slots) = extract(&values, metrics);
+    if produced.is_none() && skipped.is_none() && vote_latency_slots.is_none() {
+        anyhow::bail!(
+            "None of the configured metrics ({}, {}, {}) were found at {}",
+            metrics.produced_metric,
+            metrics.skipped_metric,
+            metrics.vote_latency_metric,
+            metrics.url
+        );
+    }
+
+    let rpc_client = crate::rpc::new_rpc_client(&config.rpc_url, CommitmentConfig::confirmed());
+    let slot = rpc_client.get_slot()?;
+    let sample = MetricSample {
+        scraped_at: chrono::Utc::now().timestamp(),
+        slot,
+        epoch: slot / constants::SLOTS_PER_EPOCH,
+        produced,
+        skipped,
+        vote_latency_slots,
+    };
+    cache.store_metric_sample(&sample).await?;
+    Ok(sample)
+}
+
+/// Produced/skipped leader slots between two samples, per the client and per the chain
+#[derive(Debug, Clone, Serialize)]
+pub struct CrossCheck {
+    pub epoch: u64,
+    pub first_slot: u64,
+    pub last_slot: u64,
+    pub client_produced: Option<u64>,
+    pub client_skipped: Option<u64>,
+    pub chain_produced: u64,
+    pub chain_skipped: u64,
+}
+
+impl CrossCheck {
+    /// Whether the client's counters agree with the chain (missing counters aren't disagreements)
+    pub fn matches(&self) -> bool {
+        self.client_produced.is_none_or(|p| p == self.chain_produced)
+            && self.client_skipped.is_none_or(|s| s == self.chain_skipped)
+    }
+}
+
+/// Counter increase between two samples (None if missing or reset by a client restart)
+fn counter_delta(first: Option<u64>, last: Option<u64>) -> Option<u64> {
+    last?.checked_sub(first?)
+}
+
+/// Compare the client's counters for the current epoch with `getBlockProduction`
+pub fn cross_check(rpc_client: &RpcClient, config: &Config, samples: &[MetricSample]) -> Result<Option<CrossCheck>> {
+    let (Some(first), Some(last)) = (samples.first(), samples.last()) else {
+        return Ok(None);
+    };
+    if last.slot <= first.slot {
+        return Ok(None);
+    }
+
+    let production = rpc_client
+        .get_block_production_with_config(RpcBlockProductionConfig {
+            identity: Some(config.identity.to_string()),
+            range: Some(RpcBlockProductionConfigRange {
+                first_slot: first.slot + 1,
+                last_slot: Some(last.slot),
+            }),
+            commitment: Some(CommitmentConfig::confirmed()),
+        })?
+        .value;
+    let (leader_slots, produced) = production
+        .by_identity
+        .get(&config.identity.to_string())
+        .map(|(leader, produced)| (*leader as u64, *produced as u64))
+        .unwrap_or((0, 0));
+
+    Ok(Some(CrossCheck {
+        epoch: last.epoch,
+        first_slot: first.slot + 1,
+        last_slot: last.slot,
+        client_produced: counter_delta(first.produced, last.produced),
+        client_skipped: counter_delta(first.skipped, last.skipped),
+        chain_produced: produced,
+        chain_skipped: leader_slots.saturating_sub(produced),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_prometheus_text() {
+        let text = "# HELP slots_produced Leader slots produced\n\
+                    # TYPE slots_produced counter\n\
+                    slots_produced{tile=\"pack 0\"} 10\n\
+                    slots_produced{tile=\"pack 1\"} 5 1700000000000\n\
+                    vote_latency_sum 30\n\
+                    vote_latency_count 20\n";
+        let values = parse_prometheus(text);
+        assert_eq!(values["slots_produced"], 15.0);
+        assert_eq!(metric(&values, "vote_latency"), Some(1.5));
+        assert_eq!(metric(&values, "missing"), None);
+    }
+
+    #[test]
+    fn parses_influx_lines() {
+        let text = "replay-slot-stats,host_id=abc produced=40i,skipped=2i 1700000000000000000\n\
+                    tower-vote latency=1.25 1700000000000000000\n\
+                    replay-slot-stats,host_id=abc produced=41i,skipped=2i 1700000001000000000\n";
+        let values = parse_influx(text);
+        assert_eq!(values["replay-slot-stats.produced"], 41.0);
+        assert_eq!(values["replay-slot-stats.skipped"], 2.0);
+        assert_eq!(values["tower-vote.latency"], 1.25);
+    }
+
+    #[test]
+    fn counter_deltas_ignore_resets() {
+        assert_eq!(counter_delta(Some(10), Some(14)), Some(4));
+        assert_eq!(counter_delta(Some(10), Some(3)), None);
+        assert_eq!(counter_delta(None, Some(3)), None);
+    }
+}
@@ -0,0 +1,247 @@
+//! Jito vs vanilla client what-if
+//!
+//! Estimates what running the Jito client is worth: per epoch, realized block
+//! revenue (leader fees plus the MEV commission) is compared against a vanilla-client
+//! baseline of `blocks_produced × network median fees per block`. A vanilla client
+//! earns no tips, and the network median block stands in for what it would have
+//! packed in fees. The medians come from Dune (`dune network-fees`), so the
+//! comparison only covers epochs that have been imported.
+
+use anyhow::Result;
+use csv::Writer;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::constants;
+use crate::jito::MevClaim;
+use crate::leader_fees::EpochLeaderFees;
+
+/// Network-wide median leader fee per produced block for one epoch
+#[derive(Debug, Clone)]
+pub struct NetworkFeeMedian {
+    pub epoch: u64,
+    pub median_block_fees_lamports: u64,
+    /// Blocks the median was taken over
+    pub blocks_sampled: u64,
+    pub date: Option<String>,
+}
+
+/// Realized vs vanilla-baseline block revenue for one epoch
+#[derive(Debug, Clone, Serialize)]
+pub struct EpochUplift {
+    pub epoch: u64,
+    pub date: Option<String>,
+    pub blocks_produced: u64,
+    pub leader_fees_sol: f64,
+    pub mev_commission_sol: f64,
+    pub realized_sol: f64,
+    pub median_block_fees_sol: f64,
+    pub baseline_sol: f64,
+    pub uplift_sol: f64,
+}
+
+/// Totals across the compared epochs
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UpliftTotals {
+    pub epochs: usize,
+    pub realized_sol: f64,
+    pub baseline_sol: f64,
+    pub uplift_sol: f64,
+    /// Uplift as a percentage of the baseline (None when the baseline is zero)
+    pub uplift_pct: Option<f64>,
+}
+
+/// Compare each epoch with leader fees and a network median (epochs missing either are skipped)
+pub fn compare(
+    leader_fees: &[EpochLeaderFees],
+    mev_claims: &[MevClaim],
+    medians: &[NetworkFeeMedian],
+) -> Vec<EpochUplift> {
+    let mut mev_by_epoch: HashMap<u64, f64> = HashMap::new();
+    for claim in mev_claims {
+        *mev_by_epoch.entry(claim.epoch).or_default() += claim.amount_sol;
+    }
+    let median_by_epoch: HashMap<u64, u64> = medians
+        .iter()
+        .map(|m| (m.epoch, m.median_block_fees_lamports))
+        .collect();
+
+    let mut rows: Vec<EpochUplift> = leader_fees
+        .iter()
+        .filter_map(|fees| {
+            let median = *median_by_epoch.get(&fees.epoch)?;
+            let mev_commission_sol = mev_by_epoch.get(&fees.epoch).copied().unwrap_or(0.0);
+            let realized_sol = fees.total_fees_sol + mev_commission_sol;
+            let median_block_fees_sol = median as f64 / 1e9;
+            let baseline_sol = fees.blocks_produced as f64 * median_block_fees_sol;
+            Some(EpochUplift {
+                epoch: fees.epoch,
+                date: fees.date.clone(),
+                blocks_produced: fees.blocks_produced,
+                leader_fees_sol: fees.total_fees_sol,
+                mev_commission_sol,
+                realized_sol,
+                median_block_fees_sol,
+                baseline_sol,
+                uplift_sol: realized_sol - baseline_sol,
+            })
+        })
+        .collect();
+    rows.sort_by_key(|r| r.epoch);
+    rows
+}
+
+pub fn totals(rows: &[EpochUplift]) -> UpliftTotals {
+    let realized_sol: f64 = rows.iter().map(|r| r.realized_sol).sum();
+    let baseline_sol: f64 = rows.iter().map(|r| r.baseline_sol).sum();
+    let uplift_sol = realized_sol - baseline_sol;
+    UpliftTotals {
+        epochs: rows.len(),
+        realized_sol,
+        baseline_sol,
+        uplift_sol,
+        uplift_pct: (baseline_sol > 0.0).then(|| uplift_sol / baseline_sol * 100.0),
+    }
+}
+
+/// Print the per-epoch comparison and totals
+pub fn print_comparison(rows: &[EpochUplift], missing_medians: &[u64]) {
+    println!("Jito vs Vanilla Client (what-if)");
+    println!("================================\n");
+
+    if rows.is_empty() {
+        println!("No epochs with both leader fees and network medians.");
+        println!("Import medians with: validator-accounting dune network-fees --since <YYYY-MM-DD>");
+        return;
+    }
+
+    println!(
+        "{:>6}  {:>6}  {:>12}  {:>12}  {:>12}  {:>12}  {:>12}",
+        "Epoch", "Blocks", "Leader fees", "MEV", "Realized", "Vanilla", "Uplift"
+    );
+    for row in rows {
+        println!(
+            "{:>6}  {:>6}  {:>12.4}  {:>12.4}  {:>12.4}  {:>12.4}  {:>+12.4}",
+            row.epoch,
+            row.blocks_produced,
+            row.leader_fees_sol,
+            row.mev_commission_sol,
+            row.realized_sol,
+            row.baseline_sol,
+            row.uplift_sol
+        );
+    }
+
+    let totals = totals(rows);
+    println!("\nOver {} epochs:", totals.epochs);
+    println!("  Realized (fees + MEV):   {:>12.4} SOL", totals.realized_sol);
+    println!("  Vanilla baseline:        {:>12.4} SOL", totals.baseline_sol);
+    match totals.uplift_pct {
+        Some(pct) => println!(
+            "  Jito client uplift:      {:>+12.4} SOL ({:+.1}%)",
+            totals.uplift_sol, pct
+        ),
+        None => println!("  Jito client uplift:      {:>+12.4} SOL", totals.uplift_sol),
+    }
+    println!("\nBaseline = blocks produced × network median fees per block, no MEV.");
+
+    if !missing_medians.is_empty() {
+        println!(
+            "\n{} epoch(s) with leader fees have no network median yet and were skipped.",
+            missing_medians.len()
+        );
+    }
+}
+
+/// Generate client_uplift.csv
+pub fn generate_csv(output_dir: &Path, rows: &[EpochUplift]) -> Result<()> {
+    let path = output_dir.join(constants::CLIENT_UPLIFT_FILENAME);
+    let mut wtr = Writer::from_path(&path)?;
+
+    wtr.write_record([
+        "Epoch",
+        "Date (YYYY-MM-DD)",
+        "Blocks_Produced",
+        "Leader_Fees_SOL",
+        "MEV_Commission_SOL",
+        "Realized_SOL",
+        "Network_Median_Block_Fees_SOL",
+        "Vanilla_Baseline_SOL",
+        "Jito_Uplift_SOL",
+    ])?;
+
+    for row in rows {
+        wtr.write_record([
+            row.epoch.to_string(),
+            row.date.clone().unwrap_or_default(),
+            row.blocks_produced.to_string(),
+            format!("{:.6}", row.leader_fees_sol),
+            format!("{:.6}", row.mev_commission_sol),
+            format!("{:.6}", row.realized_sol),
+            format!("{:.9}", row.median_block_fees_sol),
+            format!("{:.6}", row.baseline_sol),
+            format!("{:.6}", row.uplift_sol),
+        ])?;
+    }
+
+    wtr.flush()?;
+    println!("  Generated: {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fees(epoch: u64, blocks: u64, sol: f64) -> EpochLeaderFees {
+        EpochLeaderFees {
+            epoch,
+            leader_slots: blocks,
+            blocks_produced: blocks,
+            skipped_slots: 0,
+            total_fees_lamports: (sol * 1e9) as u64,
+            total_fees_sol: sol,
+            date: None,
+        }
+    }
+
+    fn median(epoch: u64, lamports: u64) -> NetworkFeeMedian {
+        NetworkFeeMedian {
+            epoch,
+            median_block_fees_lamports: lamports,
+            blocks_sampled: 400_000,
+            date: None,
+        }
+    }
+
+    #[test]
+    fn compares_realized_against_median_baseline() {
+        let leader_fees = [fees(800, 40, 1.2), fees(801, 20, 0.5), fees(802, 10, 0.3)];
+        let mev = [MevClaim {
+            epoch: 800,
+            provider: "jito".to_string(),
+            total_tips_lamports: 10_000_000_000,
+            commission_lamports: 800_000_000,
+            amount_sol: 0.8,
+            date: None,
+        }];
+        // 0.02 SOL median per block; no median for epoch 802
+        let medians = [median(800, 20_000_000), median(801, 20_000_000)];
+
+        let rows = compare(&leader_fees, &mev, &medians);
+        assert_eq!(rows.len(), 2);
+
+        assert!((rows[0].realized_sol - 2.0).abs() < 1e-9);
+        assert!((rows[0].baseline_sol - 0.8).abs() < 1e-9);
+        assert!((rows[0].uplift_sol - 1.2).abs() < 1e-9);
+
+        // No MEV: fees alone against the baseline
+        assert!((rows[1].uplift_sol - 0.1).abs() < 1e-9);
+
+        let totals = totals(&rows);
+        assert_eq!(totals.epochs, 2);
+        assert!((totals.uplift_sol - 1.3).abs() < 1e-9);
+        assert!((totals.uplift_pct.unwrap() - 108.333_333).abs() < 1e-3);
+    }
+}
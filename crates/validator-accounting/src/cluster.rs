@@ -0,0 +1,106 @@
+//! Solana cluster selection (mainnet, testnet, devnet)
+//!
+//! `[validator] cluster` lets the whole pipeline run against a testnet or devnet
+//! validator before it is pointed at mainnet books. The cluster picks the default RPC
+//! endpoint, gets its own cache file so test data never mixes with real history, and
+//! switches off USD valuation: testnet and devnet SOL has no market price, so every
+//! price comes back as zero with source `n/a`. It is installed once at startup,
+//! like the accounting timezone.
+
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::constants;
+
+const HELIUS_DEVNET_RPC_BASE: &str = "https://devnet.helius-rpc.com/?api-key=";
+const TESTNET_RPC_URL: &str = "https://api.testnet.solana.com";
+
+static CLUSTER: OnceLock<Cluster> = OnceLock::new();
+
+/// Solana cluster the validator runs on
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Cluster {
+    #[default]
+    Mainnet,
+    Testnet,
+    Devnet,
+}
+
+impl Cluster {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Mainnet => "mainnet",
+            Self::Testnet => "testnet",
+            Self::Devnet => "devnet",
+        }
+    }
+
+    pub fn is_mainnet(&self) -> bool {
+        *self == Self::Mainnet
+    }
+
+    /// RPC endpoint used when `--rpc-url` is not given
+    ///
+    /// Helius has no testnet endpoint, so testnet falls back to the public RPC.
+    pub fn default_rpc_url(&self, helius_api_key: &str) -> String {
+        match self {
+            Self::Mainnet => format!("{}{}", constants::HELIUS_RPC_BASE, helius_api_key),
+            Self::Devnet => format!("{}{}", HELIUS_DEVNET_RPC_BASE, helius_api_key),
+            Self::Testnet => TESTNET_RPC_URL.to_string(),
+        }
+    }
+
+    /// Cache database filename; mainnet keeps the historical name so existing caches still open
+    pub fn cache_filename(&self) -> String {
+        match self {
+            Self::Mainnet => constants::CACHE_FILENAME.to_string(),
+            other => format!("cache-{}.sqlite", other.as_str()),
+        }
+    }
+}
+
+/// Install the cluster (first call wins; defaults to mainnet if never called)
+pub fn install(cluster: Cluster) {
+    let _ = CLUSTER.set(cluster);
+}
+
+/// The cluster in effect
+pub fn current() -> Cluster {
+    CLUSTER.get().copied().unwrap_or_default()
+}
+
+/// Whether prices should be looked up (mainnet only)
+pub fn prices_enabled() -> bool {
+    current().is_mainnet()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_is_isolated_per_cluster() {
+        assert_eq!(Cluster::Mainnet.cache_filename(), "cache.sqlite");
+        assert_eq!(Cluster::Testnet.cache_filename(), "cache-testnet.sqlite");
+        assert_eq!(Cluster::Devnet.cache_filename(), "cache-devnet.sqlite");
+    }
+
+    #[test]
+    fn test_default_rpc_url_per_cluster() {
+        assert!(Cluster::Mainnet.default_rpc_url("k").starts_with("https://mainnet."));
+        assert!(Cluster::Devnet.default_rpc_url("k").ends_with("api-key=k"));
+        assert_eq!(Cluster::Testnet.default_rpc_url("k"), TESTNET_RPC_URL);
+    }
+
+    #[test]
+    fn test_parses_lowercase_names() {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            cluster: Cluster,
+        }
+        let w: Wrapper = toml::from_str("cluster = \"testnet\"").unwrap();
+        assert_eq!(w.cluster, Cluster::Testnet);
+    }
+}
@@ -2,12 +2,23 @@
 
 use anyhow::{Context, Result};
 use chrono::Datelike;
+use chrono_tz::Tz;
+use report_template::{AnomalyConfig, ThemeConfig};
 use serde::Deserialize;
 use solana_sdk::pubkey::Pubkey;
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
+use crate::addresses::AddressCategory;
+use crate::client_metrics::MetricsFormat;
+use crate::cluster::Cluster;
 use crate::constants;
+use crate::entities::Entity;
+use crate::key_rotation::{KeyRole, RetiredKey};
+use crate::mev::TipDistributionSource;
+use crate::rules::{TransferBucket, TransferDirection, TransferRule};
+use crate::squads;
 
 // =============================================================================
 // File-based Configuration (config.toml)
@@ -21,9 +32,44 @@ pub struct FileConfig {
     #[serde(default)]
     pub notion: Option<NotionConfig>,
     #[serde(default)]
+    pub gsheets: Option<GSheetsConfig>,
+    #[serde(default)]
+    pub s3: Option<S3Config>,
+    #[serde(default)]
     pub bam: Option<BamConfig>,
     #[serde(default)]
+    pub mev: Option<MevConfig>,
+    #[serde(default)]
     pub doublezero: Option<DoubleZeroConfig>,
+    #[serde(default)]
+    pub pricing: Option<PricingConfig>,
+    #[serde(default)]
+    pub reporting: Option<ReportingConfig>,
+    #[serde(default)]
+    pub categorization: Option<CategorizationConfig>,
+    #[serde(default)]
+    pub squads: Option<SquadsConfig>,
+    #[serde(default)]
+    pub labels: Option<LabelsConfig>,
+    #[serde(default)]
+    pub theme: Option<ThemeConfig>,
+    #[serde(default)]
+    pub keeper: Option<KeeperConfig>,
+    #[serde(default)]
+    pub metrics: Option<MetricsConfig>,
+    /// Per data source overrides (`[sources.<name>]`), keyed by source name
+    #[serde(default)]
+    pub sources: BTreeMap<String, SourceSettings>,
+    /// Legal entities for multi-entity operators (`[[entities]]`); the first owns the validator
+    #[serde(default)]
+    pub entities: Vec<EntityConfig>,
+    /// Commands run after reports are generated (`[[hooks]]`), in order
+    #[serde(default)]
+    pub hooks: Vec<HookConfig>,
+    #[serde(default)]
+    pub fiat: Option<FiatConfig>,
+    #[serde(default)]
+    pub anomalies: Option<AnomalyConfig>,
 }
 
 /// Jito BAM (Block Assembly Marketplace) configuration
@@ -40,6 +86,45 @@ pub struct BamConfig {
     /// accounting, or a realistic value like 1.10 for more accurate reporting.
     #[serde(default = "default_jitosol_rate")]
     pub jitosol_rate: f64,
+    /// Published JIP-31 incentive: jitoSOL per SOL of active stake per epoch (for `bam-check`)
+    #[serde(default)]
+    pub expected_jitosol_per_sol: Option<f64>,
+    /// Minimum active stake for BAM eligibility in SOL (default: 0)
+    #[serde(default)]
+    pub min_stake_sol: f64,
+    /// Shortfall against the expected payout tolerated before flagging, in percent (default: 5)
+    #[serde(default = "default_bam_payout_tolerance_pct")]
+    pub payout_tolerance_pct: f64,
+}
+
+/// MEV tip sources (`[mev]`); Jito only when the section is absent
+#[derive(Debug, Clone, Deserialize)]
+pub struct MevConfig {
+    /// Fetch tips from the Jito API (default: true)
+    #[serde(default = "default_true")]
+    pub jito: bool,
+    /// Additional tip routers found by scanning their tip-distribution accounts
+    #[serde(default)]
+    pub tip_distribution: Vec<TipDistributionConfig>,
+}
+
+/// A Jito-style tip-distribution program (`[[mev.tip_distribution]]`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct TipDistributionConfig {
+    /// Provider name recorded with each claim (e.g. "paladin")
+    pub name: String,
+    /// Tip distribution program id
+    pub program_id: String,
+    /// PDA seed prefix for the per-epoch distribution account (default: "TIP_DISTRIBUTION_ACCOUNT")
+    #[serde(default = "default_tip_distribution_seed")]
+    pub seed: String,
+    /// Account the claims pay out to (default: the vote account)
+    #[serde(default)]
+    pub recipient: Option<String>,
+}
+
+fn default_tip_distribution_seed() -> String {
+    "TIP_DISTRIBUTION_ACCOUNT".to_string()
 }
 
 /// DoubleZero fee configuration (block reward sharing)
@@ -59,6 +144,237 @@ pub struct DoubleZeroConfig {
     pub deposit_account: Option<String>,
 }
 
+/// Price valuation configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct PricingConfig {
+    /// Value large withdrawals and BAM claims at the nearest hourly price (default: true if section present)
+    #[serde(default = "default_true")]
+    pub hourly_enabled: bool,
+    /// Withdrawals at or above this size (SOL) get hourly pricing (default: 100)
+    #[serde(default = "default_hourly_min_withdrawal_sol")]
+    pub hourly_min_withdrawal_sol: f64,
+}
+
+/// Reporting period configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReportingConfig {
+    /// First month of the fiscal year, 1-12 (default: 1 = calendar year)
+    #[serde(default = "default_fiscal_year_start_month")]
+    pub fiscal_year_start_month: u32,
+    /// IANA timezone the books are kept in (default: "UTC")
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+    /// Record every reward/MEV/BAM receipt as an income lot at FMV (default: false)
+    #[serde(default)]
+    pub income_lots: bool,
+    /// Flat rate (0-1) for `plan-withdrawal` tax estimates (optional)
+    #[serde(default)]
+    pub estimated_tax_rate: Option<f64>,
+}
+
+/// Transfer categorization rules (`[[categorization.rules]]`)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CategorizationConfig {
+    /// Evaluated in order before the built-in rules; the first match wins
+    #[serde(default)]
+    pub rules: Vec<TransferRuleConfig>,
+}
+
+/// One categorization rule; omitted conditions match anything
+#[derive(Debug, Clone, Deserialize)]
+pub struct TransferRuleConfig {
+    /// Name shown when explaining a categorization
+    pub name: String,
+    /// Report bucket for matching transfers (seeding, sfdp_reimbursement, mev_deposit,
+    /// doublezero_payment, donation, vote_funding, withdrawal, rent, other)
+    pub bucket: TransferBucket,
+    /// incoming, outgoing or internal
+    #[serde(default)]
+    pub direction: Option<TransferDirection>,
+    /// Counterparty addresses (the sender for incoming transfers, the recipient otherwise)
+    #[serde(default)]
+    pub counterparty: Vec<String>,
+    /// Counterparty categories (e.g. "Exchange", "DeFiProtocol", "Unknown")
+    #[serde(default)]
+    pub counterparty_category: Vec<AddressCategory>,
+    /// Our side of the transfer (the recipient for incoming transfers, the sender otherwise)
+    #[serde(default)]
+    pub account: Vec<String>,
+    /// Minimum amount in SOL (inclusive)
+    #[serde(default)]
+    pub min_sol: Option<f64>,
+    /// Maximum amount in SOL (inclusive)
+    #[serde(default)]
+    pub max_sol: Option<f64>,
+    /// Program that moved the SOL (top-level program of the transaction, or the owner of a
+    /// newly created account)
+    #[serde(default)]
+    pub program: Vec<String>,
+    /// Memo must contain this text (case-insensitive)
+    #[serde(default)]
+    pub memo_contains: Option<String>,
+    /// Label to show for the counterparty on matched transfers
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+/// Squads v4 multisig treasury configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct SquadsConfig {
+    /// Multisig account address (not the vault)
+    pub multisig: String,
+    /// Vault index holding the treasury funds (default: 0)
+    #[serde(default)]
+    pub vault_index: u8,
+}
+
+/// Keeper mode: scheduled commission withdrawals with a hot withdraw authority (`[keeper]`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct KeeperConfig {
+    /// Must be set explicitly; the keeper refuses to run otherwise (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Withdraw authority keypair file (JSON byte array, as written by solana-keygen)
+    pub keypair_path: PathBuf,
+    /// Withdraw once the vote account holds at least this much above rent (SOL)
+    pub threshold_sol: f64,
+    /// Hard cap on a single withdrawal (SOL)
+    pub max_withdrawal_sol: f64,
+    /// Hard cap on vote-account withdrawals per accounting day, manual ones included (SOL)
+    pub max_daily_sol: f64,
+    /// Minutes between checks (default: 60)
+    #[serde(default = "default_keeper_interval_minutes")]
+    pub interval_minutes: u64,
+    /// Log and notify what would be withdrawn without sending (default: false)
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+fn default_keeper_interval_minutes() -> u64 {
+    60
+}
+
+/// A command run after every report generation (`[[hooks]]`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct HookConfig {
+    /// Name shown in progress and error messages
+    pub name: String,
+    /// Program and arguments, e.g. `["rclone", "copy", "reports", "drive:books"]` (no shell)
+    pub command: Vec<String>,
+    /// Seconds before the hook is killed (default: 300)
+    #[serde(default = "default_hook_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Fail the run when the hook fails, instead of warning (default: false)
+    #[serde(default)]
+    pub required: bool,
+}
+
+fn default_hook_timeout_secs() -> u64 {
+    300
+}
+
+/// Off-chain USD cash account (`[fiat]`): the bank account USD expenses are paid from.
+/// Deposits into it are recorded with `fiat deposit`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FiatConfig {
+    /// Name shown in reports (default: "Operating cash")
+    #[serde(default = "default_fiat_account_name")]
+    pub account_name: String,
+    /// Balance on `opening_date` (YYYY-MM-DD); earlier expenses and deposits are ignored
+    pub opening_balance_usd: f64,
+    pub opening_date: String,
+    /// Expense `paid_with` values drawn from this account (default: ["USD"])
+    #[serde(default = "default_fiat_paid_with")]
+    pub paid_with: Vec<String>,
+    /// Trailing complete months averaged for the runway estimate (default: 3)
+    #[serde(default = "default_fiat_runway_months")]
+    pub runway_months: usize,
+}
+
+fn default_fiat_account_name() -> String {
+    "Operating cash".to_string()
+}
+
+fn default_fiat_paid_with() -> Vec<String> {
+    vec!["USD".to_string()]
+}
+
+fn default_fiat_runway_months() -> usize {
+    3
+}
+
+/// Local validator client metrics endpoint (`[metrics]`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetricsConfig {
+    /// Metrics URL (e.g. http://127.0.0.1:7999/metrics)
+    pub url: String,
+    /// "prometheus" or "influx" line protocol (default: prometheus)
+    #[serde(default)]
+    pub format: MetricsFormat,
+    /// Cumulative produced leader slots counter
+    #[serde(default = "default_produced_metric")]
+    pub produced_metric: String,
+    /// Cumulative skipped leader slots counter
+    #[serde(default = "default_skipped_metric")]
+    pub skipped_metric: String,
+    /// Vote latency in slots (gauge, or histogram/summary base name)
+    #[serde(default = "default_vote_latency_metric")]
+    pub vote_latency_metric: String,
+    /// Seconds between scrapes with `metrics scrape --watch` (default: 30)
+    #[serde(default = "default_metrics_interval_secs")]
+    pub interval_secs: u64,
+}
+
+fn default_produced_metric() -> String {
+    "slots_produced".to_string()
+}
+
+fn default_skipped_metric() -> String {
+    "slots_skipped".to_string()
+}
+
+fn default_vote_latency_metric() -> String {
+    "vote_latency".to_string()
+}
+
+fn default_metrics_interval_secs() -> u64 {
+    30
+}
+
+/// Overrides for one data source (`[sources.<name>]`); unset fields keep the source's defaults
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SourceSettings {
+    /// Sync this source during report runs
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// Attempts per request, including the first
+    #[serde(default)]
+    pub max_attempts: Option<u32>,
+    /// Minimum milliseconds between requests to the source
+    #[serde(default)]
+    pub min_interval_ms: Option<u64>,
+}
+
+/// One legal entity (`[[entities]]`), e.g. the LLC running the validator or an S-corp paying its bills
+#[derive(Debug, Clone, Deserialize)]
+pub struct EntityConfig {
+    /// Short id used in reports and `expense add --entity` (e.g. "llc")
+    pub id: String,
+    /// Legal name shown in reports
+    pub name: String,
+    /// Wallets owned by this entity; transfers between them and the validator are inter-entity
+    #[serde(default)]
+    pub wallets: Vec<String>,
+}
+
+/// Community address label lists layered over the bundled labels
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LabelsConfig {
+    /// URLs or file paths of label lists, loaded at startup (later sources win)
+    #[serde(default)]
+    pub sources: Vec<String>,
+}
+
 fn default_true() -> bool {
     true
 }
@@ -71,6 +387,10 @@ fn default_jitosol_rate() -> f64 {
     1.0 // Conservative default
 }
 
+fn default_bam_payout_tolerance_pct() -> f64 {
+    5.0
+}
+
 fn default_doublezero_fee_rate() -> f64 {
     constants::DOUBLEZERO_FEE_RATE
 }
@@ -79,6 +399,18 @@ fn default_doublezero_first_epoch() -> u64 {
     constants::DOUBLEZERO_FIRST_EPOCH
 }
 
+fn default_hourly_min_withdrawal_sol() -> f64 {
+    constants::DEFAULT_HOURLY_MIN_WITHDRAWAL_SOL
+}
+
+fn default_fiscal_year_start_month() -> u32 {
+    1
+}
+
+fn default_timezone() -> String {
+    "UTC".to_string()
+}
+
 /// Validator-specific configuration
 #[derive(Debug, Deserialize)]
 pub struct ValidatorConfig {
@@ -93,6 +425,13 @@ pub struct ValidatorConfig {
     /// Additional personal wallet addresses (optional)
     #[serde(default)]
     pub personal_wallets: Vec<String>,
+    /// Additional watch-only business wallets (e.g. cold storage, an operating token account)
+    #[serde(default)]
+    pub business_wallets: Vec<String>,
+    /// Public address for tips (shown on the website as a Solana Pay QR code). Transfers
+    /// into it are scanned and reported as donation income.
+    #[serde(default)]
+    pub donation_address: Option<String>,
     /// Commission percentage (0-100)
     pub commission_percent: u8,
     /// First epoch with staking rewards
@@ -106,11 +445,33 @@ pub struct ValidatorConfig {
     /// SFDP acceptance date (optional - only if in SFDP program)
     #[serde(default)]
     pub sfdp_acceptance_date: Option<String>,
+    /// Date SFDP stake is expected to be withdrawn (defaults to `SFDP_STAKE_MONTHS` after acceptance)
+    #[serde(default)]
+    pub sfdp_stake_end_date: Option<String>,
+    /// Cluster the validator runs on: "mainnet" (default), "testnet" or "devnet"
+    #[serde(default)]
+    pub cluster: Cluster,
+    /// Identity, withdraw authority or vote account keys rotated out, with the dates they were in use
+    #[serde(default)]
+    pub retired_keys: Vec<RetiredKeyConfig>,
+}
+
+/// A rotated-out validator key (`[[validator.retired_keys]]`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct RetiredKeyConfig {
+    /// "identity", "withdraw_authority" or "vote_account"
+    pub role: KeyRole,
+    pub address: String,
+    /// First day the key was in use (YYYY-MM-DD, optional)
+    #[serde(default)]
+    pub from: Option<String>,
+    /// Last day the key was in use (YYYY-MM-DD, inclusive)
+    pub until: String,
 }
 
 /// API keys section.
 /// All fields default to empty strings so they can be provided entirely via
-/// environment variables (HELIUS_API_KEY, COINGECKO_API_KEY, DUNE_API_KEY).
+/// environment variables (HELIUS_API_KEY, COINGECKO_API_KEY, DUNE_API_KEY, SOLANABEACH_API_KEY).
 #[derive(Debug, Deserialize)]
 pub struct ApiKeys {
     #[serde(default)]
@@ -119,6 +480,9 @@ pub struct ApiKeys {
     pub coingecko: String,
     #[serde(default)]
     pub dune: Option<String>,
+    /// Solana Beach key, only needed for `import fetch --source solana-beach`
+    #[serde(default)]
+    pub solanabeach: Option<String>,
 }
 
 /// Notion integration configuration.
@@ -132,9 +496,65 @@ pub struct NotionConfig {
     pub hours_database_id: String,
 }
 
+/// Google Sheets export (`[gsheets]`).
+/// Fields may also come from GSHEETS_SPREADSHEET_ID and GOOGLE_APPLICATION_CREDENTIALS.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GSheetsConfig {
+    /// Spreadsheet ID (the part of the sheet URL after `/d/`)
+    #[serde(default)]
+    pub spreadsheet_id: String,
+    /// Service account key file (JSON); share the sheet with the account's email as an editor
+    #[serde(default)]
+    pub credentials_path: PathBuf,
+    /// Push the reports after every ingestion run (default: true)
+    #[serde(default = "default_true")]
+    pub sync_after_ingestion: bool,
+}
+
+/// S3-compatible report publishing (`[s3]`).
+/// Credentials may also come from AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY.
+#[derive(Debug, Clone, Deserialize)]
+pub struct S3Config {
+    pub bucket: String,
+    /// Region used for request signing (default: "us-east-1")
+    #[serde(default = "default_s3_region")]
+    pub region: String,
+    /// Endpoint URL for S3-compatible stores (default: AWS for `region`)
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// Key prefix; objects land under `<prefix>/<YYYY-MM>/<run timestamp>/` (default: "reports")
+    #[serde(default = "default_s3_prefix")]
+    pub prefix: String,
+    /// Server-side encryption: "AES256" or "aws:kms" (default: "AES256")
+    #[serde(default = "default_s3_encryption")]
+    pub server_side_encryption: String,
+    /// KMS key for "aws:kms" (default: the bucket's AWS-managed key)
+    #[serde(default)]
+    pub kms_key_id: Option<String>,
+    #[serde(default)]
+    pub access_key_id: String,
+    #[serde(default)]
+    pub secret_access_key: String,
+    /// Upload after every report run (default: true)
+    #[serde(default = "default_true")]
+    pub upload_after_run: bool,
+}
+
+fn default_s3_region() -> String {
+    "us-east-1".to_string()
+}
+
+fn default_s3_prefix() -> String {
+    "reports".to_string()
+}
+
+fn default_s3_encryption() -> String {
+    "AES256".to_string()
+}
+
 impl ApiKeys {
     /// Override API keys from environment variables if set.
-    /// Env vars: HELIUS_API_KEY, COINGECKO_API_KEY, DUNE_API_KEY
+    /// Env vars: HELIUS_API_KEY, COINGECKO_API_KEY, DUNE_API_KEY, SOLANABEACH_API_KEY
     pub fn apply_env_overrides(&mut self) {
         if let Ok(val) = std::env::var("HELIUS_API_KEY") {
             self.helius = val;
@@ -145,6 +565,9 @@ impl ApiKeys {
         if let Ok(val) = std::env::var("DUNE_API_KEY") {
             self.dune = Some(val);
         }
+        if let Ok(val) = std::env::var("SOLANABEACH_API_KEY") {
+            self.solanabeach = Some(val);
+        }
     }
 }
 
@@ -161,6 +584,32 @@ impl NotionConfig {
     }
 }
 
+impl GSheetsConfig {
+    /// Override the spreadsheet and credentials from environment variables if set.
+    /// Env vars: GSHEETS_SPREADSHEET_ID, GOOGLE_APPLICATION_CREDENTIALS
+    pub fn apply_env_overrides(&mut self) {
+        if let Ok(val) = std::env::var("GSHEETS_SPREADSHEET_ID") {
+            self.spreadsheet_id = val;
+        }
+        if let Ok(val) = std::env::var("GOOGLE_APPLICATION_CREDENTIALS") {
+            self.credentials_path = PathBuf::from(val);
+        }
+    }
+}
+
+impl S3Config {
+    /// Override credentials from environment variables if set.
+    /// Env vars: AWS_ACCESS_KEY_ID, AWS_SECRET_ACCESS_KEY
+    pub fn apply_env_overrides(&mut self) {
+        if let Ok(val) = std::env::var("AWS_ACCESS_KEY_ID") {
+            self.access_key_id = val;
+        }
+        if let Ok(val) = std::env::var("AWS_SECRET_ACCESS_KEY") {
+            self.secret_access_key = val;
+        }
+    }
+}
+
 impl FileConfig {
     /// Load configuration from a TOML file
     pub fn load(path: &Path) -> Result<Self> {
@@ -180,9 +629,45 @@ impl FileConfig {
         if let Some(ref mut notion) = config.notion {
             notion.apply_env_overrides();
         }
+        if let Some(ref mut gsheets) = config.gsheets {
+            if let Some(dir) = path.parent()
+                && gsheets.credentials_path.is_relative()
+            {
+                gsheets.credentials_path = dir.join(&gsheets.credentials_path);
+            }
+            gsheets.apply_env_overrides();
+        }
+        if let Some(ref mut s3) = config.s3 {
+            s3.apply_env_overrides();
+        }
+        if let Some(ref mut theme) = config.theme
+            && let Some(dir) = path.parent()
+        {
+            theme.resolve_paths(dir);
+        }
 
         Ok(config)
     }
+
+    /// Community label list sources from `[labels]`
+    pub fn label_sources(&self) -> &[String] {
+        self.labels.as_ref().map(|l| l.sources.as_slice()).unwrap_or_default()
+    }
+
+    /// Accounting timezone from `[reporting] timezone` (UTC when unset)
+    pub fn accounting_timezone(&self) -> Result<Tz> {
+        let name = self
+            .reporting
+            .as_ref()
+            .map(|r| r.timezone.clone())
+            .unwrap_or_else(default_timezone);
+        Tz::from_str(&name).map_err(|_| {
+            anyhow::anyhow!(
+                "reporting.timezone '{}' is not a valid IANA timezone (e.g. \"UTC\", \"America/New_York\")",
+                name
+            )
+        })
+    }
 }
 
 // =============================================================================
@@ -199,6 +684,14 @@ pub struct Config {
     pub withdraw_authority: Pubkey,
     /// Personal wallets (primary + any additional configured personal wallets)
     pub personal_wallets: Vec<Pubkey>,
+    /// Watch-only business wallets, part of the treasury like the core accounts
+    pub business_wallets: Vec<Pubkey>,
+    /// Legal entities (empty for single-entity operators); the first owns the validator
+    pub entities: Vec<Entity>,
+    /// Tip address; part of the treasury, incoming transfers are donation income
+    pub donation_address: Option<Pubkey>,
+    /// Rotated-out identity, withdraw authority and vote account keys (see `key_rotation`)
+    pub retired_keys: Vec<RetiredKey>,
     /// RPC URL
     pub rpc_url: String,
     /// CoinGecko API key
@@ -212,6 +705,8 @@ pub struct Config {
     pub first_reward_epoch: u64,
     /// SFDP acceptance date (for calculating coverage schedule)
     pub sfdp_acceptance_date: Option<String>,
+    /// Expected end of Foundation delegation (see `sfdp_stake_end`)
+    pub sfdp_stake_end_date: Option<String>,
     /// Bootstrap date (for finding initial seeding)
     pub bootstrap_date: String,
     /// Initial SOL in the validator treasury at `bootstrap_date` (for reconciliation baseline)
@@ -222,6 +717,10 @@ pub struct Config {
     pub bam_first_epoch: u64,
     /// jitoSOL to SOL exchange rate for BAM reward valuation
     pub bam_jitosol_rate: f64,
+    /// MEV tips from the Jito API (`[mev] jito`)
+    pub jito_mev_enabled: bool,
+    /// Additional tip-distribution programs (`[[mev.tip_distribution]]`)
+    pub tip_distribution_sources: Vec<TipDistributionSource>,
     /// DoubleZero fee tracking enabled
     pub doublezero_enabled: bool,
     /// DoubleZero fee rate (e.g., 0.05 = 5%)
@@ -230,6 +729,32 @@ pub struct Config {
     pub doublezero_first_epoch: u64,
     /// DoubleZero deposit account PDA (optional)
     pub doublezero_deposit_account: Option<Pubkey>,
+    /// Hourly pricing for large withdrawals and BAM claims enabled
+    pub hourly_pricing_enabled: bool,
+    /// Minimum withdrawal size (SOL) that gets hourly pricing
+    pub hourly_min_withdrawal_sol: f64,
+    /// First month of the fiscal year (1 = calendar year)
+    pub fiscal_year_start_month: u32,
+    /// Timezone used to attribute block timestamps to dates
+    pub accounting_timezone: Tz,
+    /// Record income lots at FMV on receipt (`[reporting] income_lots`)
+    pub income_lots_enabled: bool,
+    /// Flat tax rate for withdrawal planning (`[reporting] estimated_tax_rate`)
+    pub estimated_tax_rate: Option<f64>,
+    /// Operator categorization rules, evaluated before the built-in rules
+    pub transfer_rules: Vec<TransferRule>,
+    /// Squads multisig account (optional)
+    pub squads_multisig: Option<Pubkey>,
+    /// Squads vault PDA derived from the multisig; treated as a treasury account
+    pub squads_vault: Option<Pubkey>,
+    /// HTML report branding (`[theme]`)
+    pub theme: ThemeConfig,
+    /// Post-generation commands (`[[hooks]]`)
+    pub hooks: Vec<HookConfig>,
+    /// USD cash account tracking (`[fiat]`)
+    pub fiat: Option<FiatConfig>,
+    /// Per-epoch revenue anomaly thresholds (`[anomalies]`)
+    pub anomalies: AnomalyConfig,
 }
 
 impl Config {
@@ -249,6 +774,77 @@ impl Config {
             }
         }
 
+        let mut business_wallets = Vec::new();
+        for addr in &validator.business_wallets {
+            let parsed =
+                Pubkey::from_str(addr).with_context(|| format!("Invalid business_wallets address: {}", addr))?;
+            if personal_wallets.contains(&parsed) {
+                anyhow::bail!("{} is listed in both personal_wallets and business_wallets", addr);
+            }
+            if !business_wallets.contains(&parsed) {
+                business_wallets.push(parsed);
+            }
+        }
+
+        let entities = file_config
+            .entities
+            .iter()
+            .map(Entity::from_config)
+            .collect::<Result<Vec<_>>>()?;
+        for (i, entity) in entities.iter().enumerate() {
+            if entities[..i].iter().any(|e| e.id == entity.id) {
+                anyhow::bail!("Entity id '{}' is listed more than once in [[entities]]", entity.id);
+            }
+            for wallet in &entity.wallets {
+                if personal_wallets.contains(wallet) || business_wallets.contains(wallet) {
+                    anyhow::bail!(
+                        "Entity '{}' wallet {} is also a personal or business wallet",
+                        entity.id,
+                        wallet
+                    );
+                }
+            }
+        }
+        if let Some(primary) = entities.first()
+            && !primary.wallets.is_empty()
+        {
+            anyhow::bail!(
+                "Entity '{}' owns the validator; list its extra wallets under business_wallets instead",
+                primary.id
+            );
+        }
+
+        let donation_address = match validator.donation_address.as_deref() {
+            Some(addr) => {
+                let parsed =
+                    Pubkey::from_str(addr).with_context(|| format!("Invalid donation_address address: {}", addr))?;
+                if personal_wallets.contains(&parsed) {
+                    anyhow::bail!("donation_address {} is also listed as a personal wallet", addr);
+                }
+                Some(parsed)
+            }
+            None => None,
+        };
+
+        let vote_account = Pubkey::from_str(&validator.vote_account).with_context(|| "Invalid vote_account address")?;
+        let identity = Pubkey::from_str(&validator.identity).with_context(|| "Invalid identity address")?;
+        let withdraw_authority =
+            Pubkey::from_str(&validator.withdraw_authority).with_context(|| "Invalid withdraw_authority address")?;
+
+        let retired_keys = validator
+            .retired_keys
+            .iter()
+            .map(RetiredKey::from_config)
+            .collect::<Result<Vec<_>>>()?;
+        for key in &retired_keys {
+            if [vote_account, identity, withdraw_authority].contains(&key.pubkey) {
+                anyhow::bail!("Retired key {} is still a current validator key", key.pubkey);
+            }
+            if personal_wallets.contains(&key.pubkey) || business_wallets.contains(&key.pubkey) {
+                anyhow::bail!("Retired key {} is also a personal or business wallet", key.pubkey);
+            }
+        }
+
         // Validate that required API keys are present (from either TOML or env)
         anyhow::ensure!(
             !file_config.api_keys.helius.is_empty(),
@@ -285,17 +881,87 @@ impl Config {
                 ),
             };
 
+        // Hourly pricing defaults (disabled unless section present)
+        let (hourly_pricing_enabled, hourly_min_withdrawal_sol) = match &file_config.pricing {
+            Some(pricing) => (pricing.hourly_enabled, pricing.hourly_min_withdrawal_sol),
+            None => (false, constants::DEFAULT_HOURLY_MIN_WITHDRAWAL_SOL),
+        };
+
+        let fiscal_year_start_month = file_config
+            .reporting
+            .as_ref()
+            .map(|r| r.fiscal_year_start_month)
+            .unwrap_or_else(default_fiscal_year_start_month);
+        anyhow::ensure!(
+            (1..=12).contains(&fiscal_year_start_month),
+            "reporting.fiscal_year_start_month must be between 1 and 12 (got {})",
+            fiscal_year_start_month
+        );
+        let accounting_timezone = file_config.accounting_timezone()?;
+        let income_lots_enabled = file_config.reporting.as_ref().is_some_and(|r| r.income_lots);
+        let estimated_tax_rate = file_config.reporting.as_ref().and_then(|r| r.estimated_tax_rate);
+        if let Some(rate) = estimated_tax_rate {
+            anyhow::ensure!(
+                (0.0..=1.0).contains(&rate),
+                "reporting.estimated_tax_rate must be between 0 and 1 (got {})",
+                rate
+            );
+        }
+
+        let transfer_rules = file_config
+            .categorization
+            .as_ref()
+            .map(|c| {
+                c.rules
+                    .iter()
+                    .map(TransferRule::from_config)
+                    .collect::<Result<Vec<_>>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        let (jito_mev_enabled, tip_distribution_sources) = match &file_config.mev {
+            Some(mev) => (
+                mev.jito,
+                mev.tip_distribution
+                    .iter()
+                    .map(TipDistributionSource::from_config)
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+            None => (true, Vec::new()),
+        };
+
+        let fiat = file_config.fiat.clone();
+        if let Some(fiat) = &fiat {
+            chrono::NaiveDate::parse_from_str(&fiat.opening_date, "%Y-%m-%d")
+                .with_context(|| format!("fiat.opening_date must be YYYY-MM-DD (got {:?})", fiat.opening_date))?;
+            anyhow::ensure!(fiat.runway_months > 0, "fiat.runway_months must be at least 1");
+        }
+
+        let anomalies = file_config.anomalies.clone().unwrap_or_default();
+        anyhow::ensure!(anomalies.sigma > 0.0, "anomalies.sigma must be positive");
+
+        let (squads_multisig, squads_vault) = match &file_config.squads {
+            Some(squads) => {
+                let multisig = Pubkey::from_str(&squads.multisig).with_context(|| "Invalid squads.multisig address")?;
+                (Some(multisig), Some(squads::vault_pda(&multisig, squads.vault_index)))
+            }
+            None => (None, None),
+        };
+
         Ok(Self {
-            // Parse validator addresses from config
-            vote_account: Pubkey::from_str(&validator.vote_account).with_context(|| "Invalid vote_account address")?,
-            identity: Pubkey::from_str(&validator.identity).with_context(|| "Invalid identity address")?,
-            withdraw_authority: Pubkey::from_str(&validator.withdraw_authority)
-                .with_context(|| "Invalid withdraw_authority address")?,
+            // Validator addresses
+            vote_account,
+            identity,
+            withdraw_authority,
             personal_wallets,
+            business_wallets,
+            entities,
+            donation_address,
+            retired_keys,
 
-            // Helius RPC endpoint (has historical transaction data)
-            rpc_url: rpc_url
-                .unwrap_or_else(|| format!("{}{}", constants::HELIUS_RPC_BASE, &file_config.api_keys.helius)),
+            // Helius RPC endpoint (has historical transaction data); testnet uses the public RPC
+            rpc_url: rpc_url.unwrap_or_else(|| validator.cluster.default_rpc_url(&file_config.api_keys.helius)),
 
             // CoinGecko API key for price lookups
             coingecko_api_key: file_config.api_keys.coingecko.clone(),
@@ -311,6 +977,7 @@ impl Config {
 
             // SFDP acceptance date (optional)
             sfdp_acceptance_date: validator.sfdp_acceptance_date.clone(),
+            sfdp_stake_end_date: validator.sfdp_stake_end_date.clone(),
 
             // Bootstrap date (when validator was first set up)
             bootstrap_date: validator.bootstrap_date.clone(),
@@ -327,17 +994,73 @@ impl Config {
             bam_first_epoch,
             bam_jitosol_rate,
 
+            // MEV tip sources
+            jito_mev_enabled,
+            tip_distribution_sources,
+
             // DoubleZero fee tracking
             doublezero_enabled,
             doublezero_fee_rate,
             doublezero_first_epoch,
             doublezero_deposit_account,
+
+            // Intraday pricing for large events
+            hourly_pricing_enabled,
+            hourly_min_withdrawal_sol,
+
+            // Fiscal year for --year, summary YTD and annual totals
+            fiscal_year_start_month,
+            accounting_timezone,
+            income_lots_enabled,
+            estimated_tax_rate,
+
+            // Operator-defined transfer categorization
+            transfer_rules,
+
+            // Squads multisig treasury
+            squads_multisig,
+            squads_vault,
+
+            // HTML report branding
+            theme: file_config.theme.clone().unwrap_or_default(),
+
+            // Post-generation hooks
+            hooks: file_config.hooks.clone(),
+
+            // Off-chain USD cash account
+            fiat,
+
+            // Revenue anomaly detection
+            anomalies,
         })
     }
 
+    /// Accounts whose balances make up the validator treasury: vote, identity, withdraw
+    /// authority, the Squads vault (if configured), business wallets and the donation
+    /// address, deduplicated
+    pub fn treasury_accounts(&self) -> Vec<Pubkey> {
+        let mut accounts = vec![self.vote_account, self.identity, self.withdraw_authority];
+        accounts.extend(self.squads_vault);
+        accounts.extend(&self.business_wallets);
+        accounts.extend(self.donation_address);
+        let mut seen = std::collections::HashSet::new();
+        accounts.retain(|a| seen.insert(*a));
+        accounts
+    }
+
     /// Check if a pubkey is one of our validator accounts
     pub fn is_our_account(&self, pubkey: &Pubkey) -> bool {
-        *pubkey == self.vote_account || *pubkey == self.identity || *pubkey == self.withdraw_authority
+        *pubkey == self.vote_account
+            || *pubkey == self.identity
+            || *pubkey == self.withdraw_authority
+            || self.squads_vault.as_ref() == Some(pubkey)
+            || self.is_business_wallet(pubkey)
+            || self.donation_address.as_ref() == Some(pubkey)
+    }
+
+    /// Check if a pubkey is a configured watch-only business wallet
+    pub fn is_business_wallet(&self, pubkey: &Pubkey) -> bool {
+        self.business_wallets.contains(pubkey)
     }
 
     /// Check if a pubkey is any account we care about (including personal wallet)
@@ -390,37 +1113,69 @@ impl Config {
             0.0
         }
     }
+
+    /// When Foundation stake is expected to leave: `sfdp_stake_end_date`, else
+    /// `SFDP_STAKE_MONTHS` after acceptance (None outside the program)
+    pub fn sfdp_stake_end(&self) -> Option<chrono::NaiveDate> {
+        use chrono::NaiveDate;
+
+        if let Some(end) = &self.sfdp_stake_end_date {
+            return NaiveDate::parse_from_str(end, "%Y-%m-%d").ok();
+        }
+        let acceptance = NaiveDate::parse_from_str(self.sfdp_acceptance_date.as_deref()?, "%Y-%m-%d").ok()?;
+        acceptance.checked_add_months(chrono::Months::new(constants::SFDP_STAKE_MONTHS))
+    }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     use chrono::NaiveDate;
     use solana_sdk::pubkey::Pubkey;
 
     /// Create a minimal Config for testing SFDP calculations
-    fn test_config(sfdp_date: Option<&str>) -> Config {
+    pub(crate) fn test_config(sfdp_date: Option<&str>) -> Config {
         let personal_wallet = Pubkey::new_unique();
         Config {
             vote_account: Pubkey::new_unique(),
             identity: Pubkey::new_unique(),
             withdraw_authority: Pubkey::new_unique(),
             personal_wallets: vec![personal_wallet],
+            business_wallets: Vec::new(),
+            entities: Vec::new(),
+            donation_address: None,
+            retired_keys: Vec::new(),
             rpc_url: String::new(),
             coingecko_api_key: String::new(),
             dune_api_key: None,
             commission_percent: 10,
             first_reward_epoch: 900,
             sfdp_acceptance_date: sfdp_date.map(|s| s.to_string()),
+            sfdp_stake_end_date: None,
             bootstrap_date: "2025-11-01".to_string(),
             initial_treasury_lamports: 0,
             bam_enabled: true,
             bam_first_epoch: 912,
             bam_jitosol_rate: 1.0,
+            jito_mev_enabled: true,
+            tip_distribution_sources: Vec::new(),
             doublezero_enabled: false,
             doublezero_fee_rate: 0.05,
             doublezero_first_epoch: 859,
             doublezero_deposit_account: None,
+            hourly_pricing_enabled: false,
+            hourly_min_withdrawal_sol: 100.0,
+            fiscal_year_start_month: 1,
+            accounting_timezone: Tz::UTC,
+            income_lots_enabled: false,
+            estimated_tax_rate: None,
+            transfer_rules: Vec::new(),
+            squads_multisig: None,
+            squads_vault: None,
+            theme: Default::default(),
+            hooks: Vec::new(),
+            fiat: None,
+            anomalies: Default::default(),
         }
     }
 
@@ -517,4 +1272,13 @@ mod tests {
         let date = NaiveDate::from_ymd_opt(2025, 12, 15).unwrap();
         assert_eq!(config.sfdp_coverage_percent(&date), 0.0);
     }
+
+    #[test]
+    fn test_sfdp_stake_end_defaults_from_acceptance() {
+        let mut config = test_config(Some("2025-12-16"));
+        assert_eq!(config.sfdp_stake_end(), NaiveDate::from_ymd_opt(2027, 6, 16));
+        config.sfdp_stake_end_date = Some("2026-12-31".to_string());
+        assert_eq!(config.sfdp_stake_end(), NaiveDate::from_ymd_opt(2026, 12, 31));
+        assert_eq!(test_config(None).sfdp_stake_end(), None);
+    }
 }
@@ -24,7 +24,7 @@ pub const COINGECKO_API_BASE: &str = "https://api.coingecko.com/api/v3";
 pub const COINGECKO_MARKET_CHART: &str = "/coins/solana/market_chart/range?vs_currency=usd";
 
 /// CoinGecko current price endpoint
-pub const COINGECKO_SIMPLE_PRICE: &str = "/simple/price?ids=solana&vs_currencies=usd";
+pub const COINGECKO_SIMPLE_PRICE: &str = "/simple/price?ids=solana&vs_currencies=usd&include_last_updated_at=true";
 
 /// Binance API base URL (fallback price source — no API key required)
 pub const BINANCE_API_BASE: &str = "https://api.binance.com/api/v3";
@@ -35,6 +35,12 @@ pub const BINANCE_KLINES: &str = "/klines?symbol=SOLUSDT&interval=1d";
 /// Binance current ticker price endpoint
 pub const BINANCE_TICKER: &str = "/ticker/price?symbol=SOLUSDT";
 
+/// Stakewiz API base URL (historical epoch data for onboarding imports)
+pub const STAKEWIZ_API_BASE: &str = "https://api.stakewiz.com";
+
+/// Solana Beach API base URL (historical validator rewards; requires an API key)
+pub const SOLANA_BEACH_API_BASE: &str = "https://api.solanabeach.io/v1";
+
 // =============================================================================
 // Solana Network Constants
 // =============================================================================
@@ -80,9 +86,28 @@ pub const JITO_STAKE_POOL: &str = "Jito4APyf642JPZPx3hGc6WWJ8zPKtRbRs4P815Awbb";
 #[allow(dead_code)]
 pub const BAM_BOOST_PROGRAM: &str = "BoostxbPp2ENYHGcTLYt1obpcY13HE4NojdqNWdzqSSb";
 
+/// Squads v4 multisig program ID (vault PDAs, proposals and vault transaction execution)
+pub const SQUADS_V4_PROGRAM: &str = "SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf";
+
 /// First epoch with BAM rewards available (Block Assembly Marketplace started ~epoch 912)
 pub const BAM_FIRST_EPOCH: u64 = 912;
 
+/// JIP-31 claim window: BAM rewards expire if not claimed within this many epochs
+pub const BAM_CLAIM_WINDOW_EPOCHS: u64 = 10;
+
+// =============================================================================
+// SFDP Stake Match
+// =============================================================================
+
+/// Foundation stake matched 1:1 against residential stake, up to this much (SOL)
+pub const SFDP_MATCH_CAP_SOL: f64 = 100_000.0;
+
+/// Months of Foundation delegation assumed when `sfdp_stake_end_date` isn't set
+pub const SFDP_STAKE_MONTHS: u32 = 18;
+
+/// Epochs averaged for the SFDP revenue-at-risk projection (~30 days)
+pub const SFDP_TRAILING_EPOCHS: usize = 15;
+
 // =============================================================================
 // DoubleZero Network Fees
 // =============================================================================
@@ -108,6 +133,29 @@ pub const SYSTEM_ACCOUNT_SIZE: usize = 0;
 /// This is the size of an SPL Token `Account` (used by associated token accounts).
 pub const SPL_TOKEN_ACCOUNT_SIZE: usize = 165;
 
+/// Stake account data size (bytes) - for the `getProgramAccounts` size filter
+pub const STAKE_ACCOUNT_SIZE: u64 = 200;
+
+// =============================================================================
+// Rent-Exempt Deposits
+// =============================================================================
+
+/// Stake program (owner of new stake accounts)
+pub const STAKE_PROGRAM: &str = "Stake11111111111111111111111111111111111111";
+
+/// SPL Token program (token accounts; `closeAccount` refunds their rent)
+pub const SPL_TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+/// SPL Token-2022 program
+pub const SPL_TOKEN_2022_PROGRAM: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+/// Associated Token Account program (creates ATAs, funding their rent from the payer)
+pub const ASSOCIATED_TOKEN_PROGRAM: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+
+/// Largest account-creation or close transfer treated as a rent-exempt deposit/refund
+/// (0.01 SOL). Token and stake accounts need ~0.002 SOL; larger amounts carry principal.
+pub const RENT_MAX_LAMPORTS: u64 = 10_000_000;
+
 // =============================================================================
 // Position Tracking
 // =============================================================================
@@ -137,17 +185,82 @@ pub const EXPENSES_FILENAME: &str = "expenses.csv";
 /// Income ledger CSV filename
 pub const INCOME_LEDGER_FILENAME: &str = "income_ledger.csv";
 
+/// Every income receipt in lamports with its on-chain evidence (`income-proof`)
+pub const INCOME_PROOF_FILENAME: &str = "income_proof.csv";
+
 /// Expense ledger CSV filename
 pub const EXPENSE_LEDGER_FILENAME: &str = "expense_ledger.csv";
 
+/// Pending and rejected expenses, left out of the ledgers until approved
+pub const UNAPPROVED_EXPENSES_FILENAME: &str = "unapproved_expenses.csv";
+
 /// Treasury ledger CSV filename
 pub const TREASURY_LEDGER_FILENAME: &str = "treasury_ledger.csv";
 
 /// Summary CSV filename
 pub const SUMMARY_FILENAME: &str = "summary.csv";
 
+/// Rows valued with the fallback SOL price (soft USD values)
+pub const PRICE_WARNINGS_FILENAME: &str = "price_warnings.csv";
+
+/// Rows that failed the sanity checks and were left out of the reports
+pub const EXCEPTIONS_FILENAME: &str = "exceptions.csv";
+
+/// Jito vs vanilla client what-if per epoch (`client-uplift`)
+pub const CLIENT_UPLIFT_FILENAME: &str = "client_uplift.csv";
+
+/// Per-epoch revenue, costs and net margin
+pub const EPOCH_PNL_FILENAME: &str = "epoch_pnl.csv";
+
+/// Monthly unit economics (cost per block, vote fee per credit, ...)
+pub const UNIT_ECONOMICS_FILENAME: &str = "unit_economics.csv";
+
+/// Per-epoch revenue spread, MEV concentration and weekday effects
+pub const REVENUE_STATS_FILENAME: &str = "revenue_stats.csv";
+
+/// Delegator concentration and churn per stake snapshot
+pub const DELEGATORS_FILENAME: &str = "delegators.csv";
+
+/// Revenue split between SFDP-matched and organic stake per epoch
+pub const SFDP_STAKE_FILENAME: &str = "sfdp_stake.csv";
+
+/// Largest delegators counted in the concentration share
+pub const DELEGATOR_TOP_N: usize = 10;
+
+/// Advisory unrealized-loss / wash-sale window analysis (`loss-harvest`)
+pub const LOSS_HARVEST_FILENAME: &str = "loss_harvest_advisory.csv";
+
+/// On-chain withdrawals matched to exchange deposit records (`exchange match`)
+pub const EXCHANGE_RECONCILIATION_FILENAME: &str = "exchange_reconciliation.csv";
+
+/// Card statement charges matched to card-paid expenses (`card match`)
+pub const CARD_RECONCILIATION_FILENAME: &str = "card_reconciliation.csv";
+
+/// Report files, fingerprints and metadata handed to post-generation hooks (`[[hooks]]`)
+pub const REPORT_MANIFEST_FILENAME: &str = "manifest.json";
+
+/// Likely-duplicate income/expense pairs awaiting review (`dedupe scan`)
+pub const DUPLICATES_FILENAME: &str = "duplicates.csv";
+
+/// Per-entity report sets (`[[entities]]`), one subdirectory per entity id
+pub const ENTITIES_DIRNAME: &str = "entities";
+
+/// Expenses an entity paid (all validator expenses for the primary entity)
+pub const ENTITY_EXPENSE_LEDGER_FILENAME: &str = "expense_ledger.csv";
+
+/// Due-to/due-from ledger between the primary entity and the others
+pub const INTER_ENTITY_LEDGER_FILENAME: &str = "inter_entity_ledger.csv";
+
+/// Standalone totals and inter-entity balance for one entity
+pub const ENTITY_SUMMARY_FILENAME: &str = "entity_summary.csv";
+
+/// All entities with inter-entity balances eliminated
+pub const CONSOLIDATED_SUMMARY_FILENAME: &str = "consolidated_summary.csv";
+
+/// Monthly roll-forward of the off-chain USD cash account (`[fiat]`)
+pub const FIAT_CASH_FILENAME: &str = "fiat_cash.csv";
+
 /// Glossary / data dictionary for accountants
-#[allow(dead_code)]
 pub const GLOSSARY_FILENAME: &str = "glossary.csv";
 
 // =============================================================================
@@ -170,6 +283,67 @@ pub const EPOCH_REWARD_DELAY_MS: u64 = 100;
 /// Note: Increased from 500 to handle longer transaction history
 pub const MAX_SIGNATURES_PER_ACCOUNT: usize = 2000;
 
+/// Signatures checked per signer per failed-fee or ops-fee scan. The identity signs every
+/// vote, so the first scan only reaches back this far; a later scan that stops here short
+/// of the previous cursor resumes from where it stopped on the next run.
+pub const FAILED_FEES_MAX_SIGNATURES: usize = 50_000;
+
+/// Base fee per transaction signature; anything an ops transaction paid above this is
+/// priority fee
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// Compute Budget program (sets the priority fee; not the "program" of an ops transaction)
+pub const COMPUTE_BUDGET_PROGRAM: &str = "ComputeBudget111111111111111111111111111111";
+
+// =============================================================================
+// Ingestion Lock
+// =============================================================================
+
+/// How long an ingestion lock is honoured before it is considered stale (minutes).
+/// Guards against a crashed process holding the lock forever; must exceed the
+/// longest full sync (leader fee backfills can take hours).
+pub const INGESTION_LOCK_LEASE_MINUTES: i64 = 240;
+
+/// How often a running holder renews its lease (minutes); well inside the lease so a
+/// slow renewal never lets a live lock lapse
+pub const INGESTION_LOCK_RENEW_MINUTES: u64 = 60;
+
+/// Poll interval while waiting for another ingestion to release the lock (seconds)
+pub const INGESTION_LOCK_POLL_SECS: u64 = 5;
+
+/// Process exit code when the ingestion lock is held by someone else (EX_TEMPFAIL).
+/// bp-web's scheduler treats this as "skipped", not "failed".
+pub const EXIT_INGESTION_LOCKED: i32 = 75;
+
+/// `--strict` exit codes, in order of severity (a run reports the most severe one).
+/// Reconciliation variance beyond `RECONCILIATION_TOLERANCE_LAMPORTS`:
+pub const EXIT_STRICT_RECONCILIATION_VARIANCE: i32 = 3;
+/// An epoch in the report window is missing data from an applicable source:
+pub const EXIT_STRICT_MISSING_EPOCH_DATA: i32 = 4;
+/// At least one ledger row was valued with `FALLBACK_SOL_PRICE`:
+pub const EXIT_STRICT_FALLBACK_PRICES: i32 = 5;
+
+// =============================================================================
+// Intraday Pricing
+// =============================================================================
+
+/// Half-width of the CoinGecko window fetched around a large event (seconds).
+/// A 2-day range makes CoinGecko return hourly points.
+pub const HOURLY_PRICE_WINDOW_SECS: i64 = 86_400;
+
+/// Furthest a cached hourly price may be from the block time and still be used (seconds)
+pub const HOURLY_PRICE_MAX_GAP_SECS: i64 = 3_600;
+
+/// Furthest the nearest cached daily price may be from a date with no price of its own
+/// and still be used (days); beyond it the row is valued at `FALLBACK_SOL_PRICE`
+pub const DAILY_PRICE_MAX_GAP_DAYS: i64 = 3;
+
+/// Default minimum withdrawal size that gets hourly pricing (SOL)
+pub const DEFAULT_HOURLY_MIN_WITHDRAWAL_SOL: f64 = 100.0;
+
+/// Delay between hourly price window fetches (ms) - CoinGecko demo keys allow ~30 calls/min
+pub const HOURLY_PRICE_FETCH_DELAY_MS: u64 = 2_500;
+
 // =============================================================================
 // Thresholds
 // =============================================================================
@@ -180,6 +354,10 @@ pub const MIN_TRANSFER_LAMPORTS: i64 = 1_000_000; // 0.001 SOL
 /// Fallback SOL price if API fails
 pub const FALLBACK_SOL_PRICE: f64 = 185.0;
 
+/// Default difference between two providers' daily prices that `prices verify` reports (%).
+/// Providers sample the day differently (midnight point, daily close, daily average).
+pub const PRICE_DISCREPANCY_TOLERANCE_PCT: f64 = 3.0;
+
 /// Fallback date for missing dates in SFDP calculations
 /// Used when epoch->date conversion fails or date is unknown
 /// This should be updated to a reasonable current date periodically
@@ -0,0 +1,398 @@
+//! Likely-duplicate detection across income and expense sources (`dedupe`)
+//!
+//! Double-counting is the easiest way to break the books: a receipt entered by hand and
+//! then imported from CSV, a Notion hours page logged again as a manual expense, or an
+//! owner-paid bill recorded both as an expense and as a withdrawal split. The scan puts
+//! every income and expense row, whatever its source, into one list and flags pairs on
+//! the same side dated within a day of each other, for the same amount, with the same
+//! counterparty. Pairs are only flagged; the operator dismisses them (not a duplicate)
+//! or drops one side (a stored expense, which is marked rejected).
+
+use anyhow::Result;
+use chrono::NaiveDate;
+use csv::Writer;
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::constants;
+use crate::expenses::{Expense, ExpenseStatus};
+use crate::prices::get_price;
+use crate::reports::ReportData;
+
+/// Default distance in days between two rows still treated as the same event
+pub const DEFAULT_WINDOW_DAYS: i64 = 1;
+
+/// Largest USD difference treated as the same amount (one cent)
+const USD_TOLERANCE: f64 = 0.005;
+
+/// Largest SOL difference treated as the same amount (one thousand lamports)
+const SOL_TOLERANCE: f64 = 0.000_001;
+
+/// Which side of the books a row is on (pairs never mix sides)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    /// Compared in SOL
+    Income,
+    /// Compared in USD
+    Expense,
+}
+
+impl Side {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Side::Income => "income",
+            Side::Expense => "expense",
+        }
+    }
+
+    fn unit(self) -> &'static str {
+        match self {
+            Side::Income => "SOL",
+            Side::Expense => "USD",
+        }
+    }
+}
+
+/// One income or expense row, whatever source it came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct LedgerEntry {
+    /// Stable id across scans (`expense:<id>` for stored expenses, which `dedupe drop` accepts)
+    pub key: String,
+    /// Where the row came from (expense, notion, recurring, split, failed-fees, doublezero, ...)
+    pub source: &'static str,
+    pub side: Side,
+    pub date: NaiveDate,
+    /// USD for expenses, SOL for income
+    pub amount: f64,
+    pub counterparty: String,
+    pub description: String,
+}
+
+/// Two rows that look like the same event
+#[derive(Debug, Clone)]
+pub struct DuplicatePair<'a> {
+    /// Short stable id for `dedupe dismiss` / `dedupe drop`
+    pub id: String,
+    pub first: &'a LedgerEntry,
+    pub second: &'a LedgerEntry,
+    pub days_apart: i64,
+}
+
+impl DuplicatePair<'_> {
+    pub fn contains(&self, key: &str) -> bool {
+        self.first.key == key || self.second.key == key
+    }
+}
+
+/// Source and key of an expense row, from how each source fills `id` and `invoice_id`
+fn expense_origin(expense: &Expense) -> (&'static str, String) {
+    if let Some(id) = expense.id {
+        let source = if expense.paid_with.starts_with("Notion ") {
+            "notion"
+        } else {
+            "expense"
+        };
+        return (source, format!("expense:{}", id));
+    }
+    match expense.invoice_id.as_deref() {
+        Some(invoice) if invoice.starts_with("split:") => ("split", invoice.to_string()),
+        Some(invoice) if invoice.starts_with("failed-fees:") => ("failed-fees", invoice.to_string()),
+        Some(invoice) if invoice.starts_with("ops-fees:") => ("ops-fees", invoice.to_string()),
+        Some(invoice) => ("derived", invoice.to_string()),
+        None => ("recurring", format!("recurring:{}:{}", expense.vendor, expense.date)),
+    }
+}
+
+fn parse_date(date: Option<&str>) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date?, "%Y-%m-%d").ok()
+}
+
+/// Every income and expense row the scan compares: expenses from all sources (rejected
+/// ones excluded), DoubleZero fees, and SOL income that arrives as transfers or claims.
+///
+/// Staking rewards, leader fees and vote costs are one row per epoch from a single
+/// source, so they can't be entered twice and are left out.
+pub fn collect_entries(data: &ReportData) -> Vec<LedgerEntry> {
+    let mut entries = Vec::new();
+
+    for expense in data.expenses {
+        if expense.status == ExpenseStatus::Rejected {
+            continue;
+        }
+        let Some(date) = parse_date(Some(&expense.date)) else {
+            continue;
+        };
+        let (source, key) = expense_origin(expense);
+        entries.push(LedgerEntry {
+            key,
+            source,
+            side: Side::Expense,
+            date,
+            amount: expense.amount_usd,
+            counterparty: expense.vendor.clone(),
+            description: expense.description.clone(),
+        });
+    }
+
+    for fee in data.doublezero_fees {
+        let Some(date) = parse_date(fee.date.as_deref()) else {
+            continue;
+        };
+        entries.push(LedgerEntry {
+            key: format!("doublezero:epoch-{}", fee.epoch),
+            source: "doublezero",
+            side: Side::Expense,
+            date,
+            amount: fee.liability_sol * get_price(data.prices, &date.to_string()),
+            counterparty: "DoubleZero".to_string(),
+            description: format!("Network fee, epoch {}", fee.epoch),
+        });
+    }
+
+    let transfers = [
+        ("donation", &data.categorized.donations),
+        ("sfdp", &data.categorized.sfdp_reimbursements),
+    ];
+    for (source, bucket) in transfers {
+        for transfer in bucket.iter() {
+            let Some(date) = parse_date(transfer.date.as_deref()) else {
+                continue;
+            };
+            entries.push(LedgerEntry {
+                key: format!("tx:{}", transfer.signature),
+                source,
+                side: Side::Income,
+                date,
+                amount: transfer.amount_sol,
+                counterparty: transfer.from_label.clone(),
+                description: transfer.memo.clone().unwrap_or_default(),
+            });
+        }
+    }
+
+    for claim in data.mev_claims {
+        let Some(date) = parse_date(claim.date.as_deref()) else {
+            continue;
+        };
+        entries.push(LedgerEntry {
+            key: format!("mev:{}", claim.reference()),
+            source: "mev",
+            side: Side::Income,
+            date,
+            amount: claim.amount_sol,
+            counterparty: claim.provider.clone(),
+            description: format!("MEV commission, epoch {}", claim.epoch),
+        });
+    }
+
+    for claim in data.bam_claims {
+        let Some(date) = parse_date(claim.date.as_deref()) else {
+            continue;
+        };
+        entries.push(LedgerEntry {
+            key: format!("bam:{}", claim.tx_signature),
+            source: "bam",
+            side: Side::Income,
+            date,
+            amount: claim.amount_sol_equivalent,
+            counterparty: "BAM".to_string(),
+            description: format!("BAM rewards, epoch {}", claim.epoch),
+        });
+    }
+
+    entries
+}
+
+/// Lowercase letters and digits only, so "Latitude.sh" and "latitude sh" compare equal
+fn normalize_counterparty(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Same counterparty when the normalized names are equal or one contains the other
+/// ("Jito" vs "Jito Tip Distribution"); an empty name never matches
+fn same_counterparty(a: &str, b: &str) -> bool {
+    let (a, b) = (normalize_counterparty(a), normalize_counterparty(b));
+    !a.is_empty() && !b.is_empty() && (a.contains(&b) || b.contains(&a))
+}
+
+/// FNV-1a over both keys in sorted order, so the id doesn't depend on scan order
+pub fn pair_id(key_a: &str, key_b: &str) -> String {
+    let (low, high) = if key_a <= key_b { (key_a, key_b) } else { (key_b, key_a) };
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in low.bytes().chain([b'|']).chain(high.bytes()) {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:08x}", hash as u32)
+}
+
+/// Flag pairs on the same side within `window_days` of each other, for the same amount,
+/// with the same counterparty. Pairs whose id is in `resolved` are skipped.
+///
+/// Ordered by date, then key, so output is stable between runs.
+pub fn find_duplicates<'a>(
+    entries: &'a [LedgerEntry],
+    window_days: i64,
+    resolved: &HashSet<String>,
+) -> Vec<DuplicatePair<'a>> {
+    let mut sorted: Vec<&LedgerEntry> = entries.iter().collect();
+    sorted.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.key.cmp(&b.key)));
+
+    let mut pairs = Vec::new();
+    for (i, first) in sorted.iter().enumerate() {
+        for second in &sorted[i + 1..] {
+            let days_apart = (second.date - first.date).num_days();
+            if days_apart > window_days {
+                break;
+            }
+            let tolerance = match first.side {
+                Side::Income => SOL_TOLERANCE,
+                Side::Expense => USD_TOLERANCE,
+            };
+            if first.side != second.side
+                || first.key == second.key
+                || (first.amount - second.amount).abs() > tolerance
+                || !same_counterparty(&first.counterparty, &second.counterparty)
+            {
+                continue;
+            }
+            let id = pair_id(&first.key, &second.key);
+            if resolved.contains(&id) {
+                continue;
+            }
+            pairs.push(DuplicatePair {
+                id,
+                first,
+                second,
+                days_apart,
+            });
+        }
+    }
+    pairs
+}
+
+/// Write every flagged pair, one row per side, for review alongside the ledgers
+pub fn write_duplicates_csv(output_dir: &Path, pairs: &[DuplicatePair]) -> Result<()> {
+    let path = output_dir.join(constants::DUPLICATES_FILENAME);
+    let mut wtr = Writer::from_path(&path)?;
+    wtr.write_record([
+        "Pair_ID",
+        "Side",
+        "Date",
+        "Source",
+        "Key",
+        "Counterparty",
+        "Amount",
+        "Unit",
+        "Description",
+        "Days_Apart",
+    ])?;
+    for pair in pairs {
+        for entry in [pair.first, pair.second] {
+            wtr.write_record([
+                pair.id.as_str(),
+                entry.side.as_str(),
+                &entry.date.to_string(),
+                entry.source,
+                entry.key.as_str(),
+                entry.counterparty.as_str(),
+                &format!("{:.6}", entry.amount),
+                entry.side.unit(),
+                entry.description.as_str(),
+                &pair.days_apart.to_string(),
+            ])?;
+        }
+    }
+    wtr.flush()?;
+    println!("  Generated: {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, side: Side, date: &str, amount: f64, counterparty: &str) -> LedgerEntry {
+        LedgerEntry {
+            key: key.to_string(),
+            source: "expense",
+            side,
+            date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            amount,
+            counterparty: counterparty.to_string(),
+            description: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_flags_same_amount_and_counterparty_within_a_day() {
+        let entries = vec![
+            entry("expense:1", Side::Expense, "2025-03-01", 1250.0, "Latitude.sh"),
+            entry("expense:2", Side::Expense, "2025-03-02", 1250.001, "latitude sh"),
+            // Three days later: a second bill, not a duplicate
+            entry("expense:3", Side::Expense, "2025-03-05", 1250.0, "Latitude.sh"),
+            // Same day and amount, other vendor
+            entry("expense:4", Side::Expense, "2025-03-01", 1250.0, "Hetzner"),
+            // Same numbers on the income side never pair with an expense
+            entry("tx:abc", Side::Income, "2025-03-01", 1250.0, "Latitude"),
+        ];
+
+        let pairs = find_duplicates(&entries, DEFAULT_WINDOW_DAYS, &HashSet::new());
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].first.key, "expense:1");
+        assert_eq!(pairs[0].second.key, "expense:2");
+        assert_eq!(pairs[0].days_apart, 1);
+        assert!(pairs[0].contains("expense:2"));
+
+        let resolved = HashSet::from([pairs[0].id.clone()]);
+        assert!(find_duplicates(&entries, DEFAULT_WINDOW_DAYS, &resolved).is_empty());
+    }
+
+    #[test]
+    fn test_counterparty_containment_and_pair_id_order() {
+        assert!(same_counterparty("Jito", "Jito Tip Distribution"));
+        assert!(!same_counterparty("", "Jito"));
+        assert!(!same_counterparty("Jito", "BAM"));
+        assert_eq!(pair_id("a", "b"), pair_id("b", "a"));
+        assert_ne!(pair_id("a", "b"), pair_id("a", "c"));
+    }
+
+    #[test]
+    fn test_expense_origin_by_source() {
+        let base = Expense {
+            id: None,
+            date: "2025-03-01".to_string(),
+            vendor: "Latitude".to_string(),
+            category: crate::expenses::ExpenseCategory::Hosting,
+            description: String::new(),
+            amount_usd: 10.0,
+            paid_with: "USD".to_string(),
+            invoice_id: None,
+            entity: None,
+            status: ExpenseStatus::Approved,
+        };
+        let stored = Expense {
+            id: Some(7),
+            ..base.clone()
+        };
+        let notion = Expense {
+            id: Some(8),
+            paid_with: "Notion Paid".to_string(),
+            ..base.clone()
+        };
+        let split = Expense {
+            invoice_id: Some("split:sig".to_string()),
+            ..base.clone()
+        };
+        assert_eq!(expense_origin(&stored), ("expense", "expense:7".to_string()));
+        assert_eq!(expense_origin(&notion), ("notion", "expense:8".to_string()));
+        assert_eq!(expense_origin(&split), ("split", "split:sig".to_string()));
+        assert_eq!(
+            expense_origin(&base),
+            ("recurring", "recurring:Latitude:2025-03-01".to_string())
+        );
+    }
+}
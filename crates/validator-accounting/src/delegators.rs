@@ -0,0 +1,318 @@
+//! Delegator concentration and churn (delegators.csv)
+//!
+//! Each run snapshots the stake accounts delegated to the vote account
+//! (`getProgramAccounts` on the stake program, filtered on the voter) and stores the
+//! effective stake per delegator for the current epoch. A delegator is the stake
+//! account's withdraw authority, so a holder spreading stake over several accounts
+//! counts once. Across snapshots this shows how much stake the largest delegators
+//! hold and who arrived or left - the context behind swings in commission revenue.
+//! Warmup and cooldown are ignored: stake counts in full from the epoch after
+//! activation until its deactivation epoch.
+
+use anyhow::{Context, Result};
+use csv::Writer;
+use serde::Serialize;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::constants;
+use crate::positions::{self, DelegatedStake};
+
+/// Offset of `Delegation.voter_pubkey` in stake account data (discriminant + Meta)
+const VOTER_OFFSET: usize = 124;
+
+/// Effective stake of one delegator in one snapshot epoch
+#[derive(Debug, Clone, PartialEq)]
+pub struct DelegatorStake {
+    pub epoch: u64,
+    /// Withdraw authority of the stake accounts
+    pub delegator: String,
+    pub stake_lamports: u64,
+    pub accounts: u32,
+}
+
+/// Concentration and churn of one snapshot epoch
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DelegatorEpoch {
+    pub epoch: u64,
+    pub delegators: usize,
+    pub total_stake_sol: f64,
+    /// Share of stake held by the `DELEGATOR_TOP_N` largest delegators (percent)
+    pub top_n_share_percent: f64,
+    pub largest_share_percent: f64,
+    /// Herfindahl-Hirschman index of stake shares (0-10,000)
+    pub hhi: f64,
+    /// Snapshot compared against (None for the first one)
+    pub previous_epoch: Option<u64>,
+    pub new_delegators: usize,
+    pub left_delegators: usize,
+    pub stake_added_sol: f64,
+    pub stake_removed_sol: f64,
+    /// Delegators that left as a percentage of the previous snapshot's
+    pub churn_percent: Option<f64>,
+}
+
+/// Whether the delegation earns in `epoch`
+fn is_effective(d: &DelegatedStake, epoch: u64) -> bool {
+    d.activation_epoch < epoch && epoch <= d.deactivation_epoch
+}
+
+/// Sum effective stake per withdraw authority, largest first
+pub fn aggregate(delegations: &[DelegatedStake], epoch: u64) -> Vec<DelegatorStake> {
+    let mut by_delegator: BTreeMap<String, (u64, u32)> = BTreeMap::new();
+    for d in delegations.iter().filter(|d| is_effective(d, epoch)) {
+        let entry = by_delegator.entry(d.withdrawer.to_string()).or_default();
+        entry.0 += d.stake_lamports;
+        entry.1 += 1;
+    }
+    let mut rows: Vec<DelegatorStake> = by_delegator
+        .into_iter()
+        .map(|(delegator, (stake_lamports, accounts))| DelegatorStake {
+            epoch,
+            delegator,
+            stake_lamports,
+            accounts,
+        })
+        .collect();
+    rows.sort_by(|a, b| b.stake_lamports.cmp(&a.stake_lamports));
+    rows
+}
+
+/// Snapshot the delegators of `vote_account` for `epoch`
+pub fn fetch(rpc_client: &RpcClient, vote_account: &Pubkey, epoch: u64) -> Result<Vec<DelegatorStake>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::DataSize(constants::STAKE_ACCOUNT_SIZE),
+            RpcFilterType::Memcmp(Memcmp::new(
+                VOTER_OFFSET,
+                MemcmpEncodedBytes::Base58(vote_account.to_string()),
+            )),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(solana_account_decoder::UiAccountEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let stake_program_id =
+        Pubkey::from_str("Stake11111111111111111111111111111111111111").context("Invalid stake program ID")?;
+
+    #[allow(deprecated)]
+    let accounts = rpc_client
+        .get_program_accounts_with_config(&stake_program_id, config)
+        .context("Failed to fetch delegated stake accounts")?;
+
+    let mut delegations = Vec::with_capacity(accounts.len());
+    for (pubkey, account) in accounts {
+        match positions::parse_delegation(&account.data) {
+            Ok(Some(d)) if d.voter == *vote_account => delegations.push(d),
+            Ok(_) => {}
+            Err(e) => eprintln!("Warning: Failed to parse stake account {}: {}", pubkey, e),
+        }
+    }
+    Ok(aggregate(&delegations, epoch))
+}
+
+/// Concentration and churn for every snapshot epoch, oldest first
+pub fn analyze(rows: &[DelegatorStake]) -> Vec<DelegatorEpoch> {
+    let mut snapshots: BTreeMap<u64, BTreeMap<&str, u64>> = BTreeMap::new();
+    for r in rows {
+        snapshots
+            .entry(r.epoch)
+            .or_default()
+            .insert(r.delegator.as_str(), r.stake_lamports);
+    }
+
+    let to_sol = |lamports: u64| lamports as f64 / constants::LAMPORTS_PER_SOL;
+    let mut previous: Option<(u64, &BTreeMap<&str, u64>)> = None;
+    let mut out = Vec::with_capacity(snapshots.len());
+    for (&epoch, stakes) in &snapshots {
+        let total: u64 = stakes.values().sum();
+        let mut sorted: Vec<u64> = stakes.values().copied().collect();
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+        let share = |lamports: u64| {
+            if total > 0 {
+                lamports as f64 / total as f64 * 100.0
+            } else {
+                0.0
+            }
+        };
+
+        let mut row = DelegatorEpoch {
+            epoch,
+            delegators: stakes.len(),
+            total_stake_sol: to_sol(total),
+            top_n_share_percent: share(sorted.iter().take(constants::DELEGATOR_TOP_N).sum()),
+            largest_share_percent: share(sorted.first().copied().unwrap_or(0)),
+            hhi: sorted.iter().map(|&s| share(s).powi(2)).sum(),
+            previous_epoch: None,
+            new_delegators: 0,
+            left_delegators: 0,
+            stake_added_sol: 0.0,
+            stake_removed_sol: 0.0,
+            churn_percent: None,
+        };
+
+        if let Some((previous_epoch, before)) = previous {
+            row.previous_epoch = Some(previous_epoch);
+            row.new_delegators = stakes.keys().filter(|d| !before.contains_key(*d)).count();
+            row.left_delegators = before.keys().filter(|d| !stakes.contains_key(*d)).count();
+            let (mut added, mut removed) = (0u64, 0u64);
+            for delegator in stakes.keys().chain(before.keys().filter(|d| !stakes.contains_key(*d))) {
+                let now = stakes.get(delegator).copied().unwrap_or(0);
+                let then = before.get(delegator).copied().unwrap_or(0);
+                added += now.saturating_sub(then);
+                removed += then.saturating_sub(now);
+            }
+            row.stake_added_sol = to_sol(added);
+            row.stake_removed_sol = to_sol(removed);
+            row.churn_percent = (!before.is_empty()).then(|| row.left_delegators as f64 / before.len() as f64 * 100.0);
+        }
+
+        previous = Some((epoch, stakes));
+        out.push(row);
+    }
+    out
+}
+
+/// Generate delegators.csv
+pub fn generate_csv(output_dir: &Path, epochs: &[DelegatorEpoch]) -> Result<()> {
+    let path = output_dir.join(constants::DELEGATORS_FILENAME);
+    let mut wtr = Writer::from_path(&path)?;
+
+    let top_n_header = format!("Top{}_Share_Pct", constants::DELEGATOR_TOP_N);
+    wtr.write_record([
+        "Epoch",
+        "Delegators",
+        "Total_Stake_SOL",
+        top_n_header.as_str(),
+        "Largest_Share_Pct",
+        "HHI",
+        "Previous_Epoch",
+        "New_Delegators",
+        "Left_Delegators",
+        "Stake_Added_SOL",
+        "Stake_Removed_SOL",
+        "Churn_Pct",
+    ])?;
+
+    for e in epochs {
+        wtr.write_record([
+            e.epoch.to_string(),
+            e.delegators.to_string(),
+            format!("{:.4}", e.total_stake_sol),
+            format!("{:.2}", e.top_n_share_percent),
+            format!("{:.2}", e.largest_share_percent),
+            format!("{:.0}", e.hhi),
+            e.previous_epoch.map_or(String::new(), |p| p.to_string()),
+            e.new_delegators.to_string(),
+            e.left_delegators.to_string(),
+            format!("{:.4}", e.stake_added_sol),
+            format!("{:.4}", e.stake_removed_sol),
+            e.churn_percent.map_or(String::new(), |c| format!("{:.1}", c)),
+        ])?;
+    }
+
+    wtr.flush()?;
+    println!("  Generated: {}", path.display());
+    Ok(())
+}
+
+/// Console section for the financial summary (latest snapshot)
+pub fn print(epochs: &[DelegatorEpoch]) {
+    let Some(latest) = epochs.last() else {
+        return;
+    };
+    println!("\nDELEGATORS (epoch {}):", latest.epoch);
+    println!(
+        "  Delegators:         {:>10}  ({:.0} SOL)",
+        latest.delegators, latest.total_stake_sol
+    );
+    println!(
+        "  Top {} share:       {:>9.1}%",
+        constants::DELEGATOR_TOP_N,
+        latest.top_n_share_percent
+    );
+    println!("  Largest share:      {:>9.1}%", latest.largest_share_percent);
+    if let Some(previous) = latest.previous_epoch {
+        println!(
+            "  Since epoch {}:    +{} / -{} delegators, +{:.0} / -{:.0} SOL",
+            previous, latest.new_delegators, latest.left_delegators, latest.stake_added_sol, latest.stake_removed_sol
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stake(epoch: u64, delegator: &str, sol: u64) -> DelegatorStake {
+        DelegatorStake {
+            epoch,
+            delegator: delegator.to_string(),
+            stake_lamports: sol * constants::LAMPORTS_PER_SOL_U64,
+            accounts: 1,
+        }
+    }
+
+    #[test]
+    fn test_aggregate_counts_effective_stake_per_withdrawer() {
+        let owner = Pubkey::new_unique();
+        let voter = Pubkey::new_unique();
+        let delegation = |stake_lamports, activation_epoch, deactivation_epoch| DelegatedStake {
+            withdrawer: owner,
+            voter,
+            stake_lamports,
+            activation_epoch,
+            deactivation_epoch,
+        };
+        let rows = aggregate(
+            &[
+                delegation(100, 700, u64::MAX),
+                delegation(50, 790, u64::MAX),
+                // Activating this epoch, not yet earning
+                delegation(25, 800, u64::MAX),
+                // Deactivated last epoch
+                delegation(10, 700, 799),
+            ],
+            800,
+        );
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].stake_lamports, 150);
+        assert_eq!(rows[0].accounts, 2);
+    }
+
+    #[test]
+    fn test_analyze_concentration_and_churn() {
+        let rows = vec![
+            stake(800, "a", 600),
+            stake(800, "b", 300),
+            stake(800, "c", 100),
+            stake(802, "a", 600),
+            stake(802, "b", 200),
+            stake(802, "d", 200),
+        ];
+        let epochs = analyze(&rows);
+        assert_eq!(epochs.len(), 2);
+
+        let first = &epochs[0];
+        assert_eq!(first.largest_share_percent, 60.0);
+        assert_eq!(first.top_n_share_percent, 100.0);
+        assert!((first.hhi - 4_600.0).abs() < 1e-6);
+        assert_eq!(first.churn_percent, None);
+
+        let second = &epochs[1];
+        assert_eq!(second.previous_epoch, Some(800));
+        assert_eq!((second.new_delegators, second.left_delegators), (1, 1));
+        assert_eq!(second.stake_added_sol, 200.0);
+        assert_eq!(second.stake_removed_sol, 200.0);
+        assert!((second.churn_percent.unwrap() - 100.0 / 3.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,161 @@
+//! Anonymized bug-report bundle (`diagnostics bundle`)
+//!
+//! Collects what is needed to debug a data issue - tool and schema versions, row counts,
+//! the config, recent ingestion runs with their errors, and any log files the user points
+//! at - into a zip that can be attached to a GitHub issue. Nothing financial leaves the
+//! machine: no table rows are included, secrets are stripped from the config, and every
+//! address, signature and URL credential is replaced with a stand-in (the same stand-in
+//! everywhere, so an error mentioning the vote account still lines up with the config).
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+
+use crate::archive::Anonymizer;
+use crate::cache::IngestionRun;
+
+/// Only the tail of each log file is kept
+pub const MAX_LOG_LINES: usize = 5_000;
+
+/// URL/query parameters whose values are credentials
+const CREDENTIAL_PARAMS: &[&str] = &["api-key=", "api_key=", "apikey=", "token=", "key="];
+
+/// Environment and schema facts for `summary.json`
+#[derive(Debug, Serialize)]
+pub struct BundleSummary {
+    pub created_at: String,
+    pub tool_version: String,
+    pub os: String,
+    pub arch: String,
+    pub cluster: String,
+    pub sqlite_version: String,
+    /// Stable hash of table and column names; differs when the schema differs
+    pub schema_fingerprint: String,
+    pub schema: BTreeMap<String, Vec<String>>,
+    pub row_counts: BTreeMap<String, u64>,
+    pub config_found: bool,
+    pub logs: Vec<String>,
+}
+
+/// FNV-1a over `table:col,col;` in table order (stable across builds, unlike `DefaultHasher`)
+pub fn schema_fingerprint(schema: &BTreeMap<String, Vec<String>>) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for (table, columns) in schema {
+        let entry = format!("{}:{};", table, columns.join(","));
+        for byte in entry.bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+/// Mask credentials passed as URL parameters (`?api-key=...`)
+pub fn mask_credentials(text: &str) -> String {
+    let mut out = text.to_string();
+    for param in CREDENTIAL_PARAMS {
+        let mut search_from = 0;
+        while let Some(found) = out[search_from..].find(param) {
+            let start = search_from + found + param.len();
+            let end = out[start..]
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+                .map_or(out.len(), |i| start + i);
+            if end > start {
+                out.replace_range(start..end, "****");
+            }
+            search_from = start;
+        }
+    }
+    out
+}
+
+/// Redact one log file: keep the last `MAX_LOG_LINES`, mask credentials, swap addresses
+pub fn redact_log(text: &str, anonymizer: &mut Anonymizer) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let tail = &lines[lines.len().saturating_sub(MAX_LOG_LINES)..];
+    tail.iter()
+        .map(|line| anonymizer.anonymize_text(&mask_credentials(line)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Redact error messages in ingestion runs
+pub fn redact_runs(runs: &mut [IngestionRun], anonymizer: &mut Anonymizer) {
+    for run in runs {
+        for error in &mut run.errors {
+            *error = anonymizer.anonymize_text(&mask_credentials(error));
+        }
+    }
+}
+
+/// Write the bundle zip
+pub fn write(
+    path: &Path,
+    summary: &BundleSummary,
+    config_toml: Option<&str>,
+    runs: &[IngestionRun],
+    logs: &[(String, String)],
+) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = std::fs::File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    zip.start_file("summary.json", options)?;
+    zip.write_all(&serde_json::to_vec_pretty(summary)?)?;
+
+    if let Some(config) = config_toml {
+        zip.start_file("config.toml", options)?;
+        zip.write_all(config.as_bytes())?;
+    }
+
+    zip.start_file("ingestion_runs.json", options)?;
+    zip.write_all(&serde_json::to_vec_pretty(runs)?)?;
+
+    for (name, text) in logs {
+        zip.start_file(format!("logs/{}", name), options)?;
+        zip.write_all(text.as_bytes())?;
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_credentials() {
+        assert_eq!(
+            mask_credentials("GET https://mainnet.helius-rpc.com/?api-key=abc-123 failed"),
+            "GET https://mainnet.helius-rpc.com/?api-key=**** failed"
+        );
+        assert_eq!(mask_credentials("a?token=x&token=y"), "a?token=****&token=****");
+        assert_eq!(mask_credentials("no secrets here"), "no secrets here");
+    }
+
+    #[test]
+    fn test_schema_fingerprint_tracks_columns() {
+        let mut schema = BTreeMap::from([("epoch_rewards".to_string(), vec!["epoch".to_string()])]);
+        let before = schema_fingerprint(&schema);
+        assert_eq!(before, schema_fingerprint(&schema));
+        schema.get_mut("epoch_rewards").unwrap().push("date".to_string());
+        assert_ne!(before, schema_fingerprint(&schema));
+    }
+
+    #[test]
+    fn test_redact_log_keeps_tail() {
+        let text = (0..MAX_LOG_LINES + 10)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let redacted = redact_log(&text, &mut Anonymizer::default());
+        assert_eq!(redacted.lines().count(), MAX_LOG_LINES);
+        assert!(redacted.starts_with("10\n"));
+    }
+}
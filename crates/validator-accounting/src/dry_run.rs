@@ -0,0 +1,177 @@
+//! `--dry-run`: preview what a sync would change before it reaches the books
+//!
+//! The sync runs as usual, but against a scratch copy of the cache, with reports written
+//! to a scratch directory and no hooks or uploads. Afterwards the copy is compared with
+//! the snapshot it started from, table by table: rows with a new primary key were added,
+//! rows whose key is gone were removed, and rows whose other columns differ were changed.
+//! Run bookkeeping (history, lock, scan cursors) and fetch timestamps are ignored, so
+//! re-fetching unchanged data doesn't show up as a change.
+
+use anyhow::Result;
+use std::path::Path;
+
+use crate::cache::Cache;
+
+/// Tables that record how syncs ran rather than what they found
+const IGNORED_TABLES: &[&str] = &[
+    "ingestion_runs",
+    "ingestion_lock",
+    "source_health",
+    "account_progress",
+    "failed_tx_scan_cursors",
+    "ops_fee_scan_cursors",
+    "report_fingerprints",
+];
+
+/// Columns stamped with the time a row was written
+const IGNORED_COLUMNS: &[&str] = &[
+    "fetched_at",
+    "updated_at",
+    "created_at",
+    "imported_at",
+    "generated_at",
+    "recorded_at",
+];
+
+/// Row changes in one table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableChanges {
+    pub table: String,
+    pub added: u64,
+    pub changed: u64,
+    pub removed: u64,
+}
+
+impl TableChanges {
+    fn is_empty(&self) -> bool {
+        self.added == 0 && self.changed == 0 && self.removed == 0
+    }
+}
+
+/// COUNT queries for the rows added, changed and removed in `main.<table>` relative to
+/// `base.<table>`. Without a primary key, rows are compared whole (no "changed").
+fn change_queries(table: &str, columns: &[String], key: &[String]) -> [String; 3] {
+    let same = |cols: &[&String]| {
+        cols.iter()
+            .map(|c| format!("o.\"{c}\" IS n.\"{c}\""))
+            .collect::<Vec<_>>()
+            .join(" AND ")
+    };
+
+    if key.is_empty() {
+        let list = columns
+            .iter()
+            .map(|c| format!("\"{}\"", c))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let except = |a: &str, b: &str| {
+            format!(
+                "SELECT COUNT(*) FROM (SELECT {list} FROM {a}.\"{table}\" EXCEPT SELECT {list} FROM {b}.\"{table}\")"
+            )
+        };
+        return [except("main", "base"), "SELECT 0".to_string(), except("base", "main")];
+    }
+
+    let key_refs: Vec<&String> = key.iter().collect();
+    let key_match = same(&key_refs);
+    let values: Vec<&String> = columns.iter().filter(|c| !key.contains(c)).collect();
+    let changed = if values.is_empty() {
+        "SELECT 0".to_string()
+    } else {
+        format!(
+            "SELECT COUNT(*) FROM main.\"{table}\" n JOIN base.\"{table}\" o ON {key_match} WHERE NOT ({})",
+            same(&values)
+        )
+    };
+    [
+        format!(
+            "SELECT COUNT(*) FROM main.\"{table}\" n WHERE NOT EXISTS (SELECT 1 FROM base.\"{table}\" o WHERE {key_match})"
+        ),
+        changed,
+        format!(
+            "SELECT COUNT(*) FROM base.\"{table}\" o WHERE NOT EXISTS (SELECT 1 FROM main.\"{table}\" n WHERE {key_match})"
+        ),
+    ]
+}
+
+/// Per-table changes in `synced` since the snapshot at `base`, tables with changes only
+pub async fn compare(synced: &Cache, base: &Path) -> Result<Vec<TableChanges>> {
+    let mut tables = Vec::new();
+    let mut queries = Vec::new();
+    for table in synced.archive_table_names().await? {
+        if IGNORED_TABLES.contains(&table.as_str()) {
+            continue;
+        }
+        let columns: Vec<String> = synced
+            .table_columns(&table)
+            .await?
+            .into_iter()
+            .filter(|c| !IGNORED_COLUMNS.contains(&c.as_str()))
+            .collect();
+        if columns.is_empty() {
+            continue;
+        }
+        let key = synced.primary_key(&table).await?;
+        queries.extend(change_queries(&table, &columns, &key));
+        tables.push(table);
+    }
+
+    let counts = synced.count_against(base, &queries).await?;
+    Ok(tables
+        .into_iter()
+        .zip(counts.chunks(3))
+        .map(|(table, c)| TableChanges {
+            table,
+            added: c[0],
+            changed: c[1],
+            removed: c[2],
+        })
+        .filter(|t| !t.is_empty())
+        .collect())
+}
+
+/// Console summary
+pub fn print(changes: &[TableChanges]) {
+    println!("\nDRY RUN: nothing was written to the cache or the output directory");
+    if changes.is_empty() {
+        println!("  No changes: the cache already matches what the sources returned");
+        return;
+    }
+    println!("  {:<28} {:>8} {:>8} {:>8}", "Table", "Added", "Changed", "Removed");
+    for t in changes {
+        println!("  {:<28} {:>8} {:>8} {:>8}", t.table, t.added, t.changed, t.removed);
+    }
+    println!("  Run again without --dry-run to apply these changes");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_change_queries_match_on_primary_key() {
+        let [added, changed, removed] = change_queries(
+            "epoch_rewards",
+            &strings(&["epoch", "amount_sol", "date"]),
+            &strings(&["epoch"]),
+        );
+        assert!(added.contains("FROM main.\"epoch_rewards\" n WHERE NOT EXISTS"));
+        assert!(added.ends_with("WHERE o.\"epoch\" IS n.\"epoch\")"));
+        assert!(changed.ends_with("WHERE NOT (o.\"amount_sol\" IS n.\"amount_sol\" AND o.\"date\" IS n.\"date\")"));
+        assert!(removed.starts_with("SELECT COUNT(*) FROM base.\"epoch_rewards\" o"));
+
+        // Key-only and key-less tables
+        let [_, changed, _] = change_queries("t", &strings(&["a"]), &strings(&["a"]));
+        assert_eq!(changed, "SELECT 0");
+        let [added, changed, _] = change_queries("t", &strings(&["a", "b"]), &[]);
+        assert_eq!(
+            added,
+            "SELECT COUNT(*) FROM (SELECT \"a\", \"b\" FROM main.\"t\" EXCEPT SELECT \"a\", \"b\" FROM base.\"t\")"
+        );
+        assert_eq!(changed, "SELECT 0");
+    }
+}
@@ -17,9 +17,12 @@ use std::time::Duration;
 use tokio::time::sleep;
 
 use crate::addresses::get_label;
+use crate::client_uplift::NetworkFeeMedian;
 use crate::config::Config;
 use crate::constants;
+use crate::key_rotation::{KeyRole, RetiredKey};
 use crate::leader_fees::EpochLeaderFees;
+use crate::timezone;
 use crate::transactions::{SolTransfer, epoch_to_date};
 use crate::vote_costs::EpochVoteCost;
 
@@ -82,6 +85,12 @@ pub struct DuneClient {
     withdraw_authority: String,
     /// Personal wallet addresses (for transfer queries: seeding/withdrawals)
     personal_wallets: Vec<String>,
+    /// Squads vault address (for transfer queries, when the treasury is a multisig)
+    squads_vault: Option<String>,
+    /// Watch-only business wallet addresses (for transfer queries)
+    business_wallets: Vec<String>,
+    /// Rotated-out identity, withdraw authority and vote account keys (for fee, vote and transfer queries)
+    retired_keys: Vec<RetiredKey>,
     /// Commission percentage (for reward records)
     commission_percent: u8,
 }
@@ -110,6 +119,9 @@ impl DuneClient {
             identity: config.identity.to_string(),
             withdraw_authority: config.withdraw_authority.to_string(),
             personal_wallets: config.personal_wallets.iter().map(ToString::to_string).collect(),
+            squads_vault: config.squads_vault.map(|v| v.to_string()),
+            business_wallets: config.business_wallets.iter().map(ToString::to_string).collect(),
+            retired_keys: config.retired_keys.clone(),
             commission_percent: config.commission_percent,
         }
     }
@@ -235,13 +247,15 @@ impl DuneClient {
               MIN(block_time) as reward_time
             FROM solana.rewards
             WHERE reward_type = 'Voting'
-              AND recipient = '{}'
+              AND {}
               AND block_date >= DATE '{}'
               {}
             GROUP BY FLOOR(block_slot / 432000)
             ORDER BY epoch
             "#,
-            self.vote_account, start_date, min_slot_clause
+            self.key_filter("recipient", &self.vote_account, KeyRole::VoteAccount),
+            start_date,
+            min_slot_clause
         );
 
         let rows = self.execute_query(&sql).await?;
@@ -288,13 +302,15 @@ impl DuneClient {
               SUM(lamports) / 1e9 as total_fees_sol
             FROM solana.rewards
             WHERE reward_type = 'Fee'
-              AND recipient = '{}'
+              AND {}
               AND block_date >= DATE '{}'
               {}
             GROUP BY FLOOR(block_slot / 432000)
             ORDER BY epoch
             "#,
-            self.identity, start_date, min_slot_clause
+            self.key_filter("recipient", &self.identity, KeyRole::Identity),
+            start_date,
+            min_slot_clause
         );
 
         let rows = self.execute_query(&sql).await?;
@@ -321,6 +337,47 @@ impl DuneClient {
         Ok(fees)
     }
 
+    /// Fetch network-wide median leader fees per block from Dune
+    ///
+    /// Queries Fee rewards to every leader, not just ours, for the vanilla-client
+    /// baseline in `client-uplift`. This scans the whole network's blocks, so keep
+    /// the date range tight.
+    pub async fn fetch_network_fee_medians(&self, start_date: &str) -> Result<Vec<NetworkFeeMedian>> {
+        Self::validate_date(start_date)?;
+        println!("  Querying Dune for network median block fees...");
+
+        let sql = format!(
+            r#"
+            SELECT
+              FLOOR(block_slot / 432000) as epoch,
+              approx_percentile(lamports, 0.5) as median_fee_lamports,
+              COUNT(*) as blocks
+            FROM solana.rewards
+            WHERE reward_type = 'Fee'
+              AND block_date >= DATE '{}'
+            GROUP BY FLOOR(block_slot / 432000)
+            ORDER BY epoch
+            "#,
+            start_date
+        );
+
+        let rows = self.execute_query(&sql).await?;
+        println!("    Found {} epochs with network fees", rows.len());
+
+        let mut medians = Vec::new();
+        for row in rows {
+            let epoch = get_u64(&row, "epoch")?;
+            medians.push(NetworkFeeMedian {
+                epoch,
+                median_block_fees_lamports: get_u64(&row, "median_fee_lamports")?,
+                blocks_sampled: get_u64(&row, "blocks")?,
+                date: Some(epoch_to_date(epoch)),
+            });
+        }
+
+        Ok(medians)
+    }
+
     // =========================================================================
     // Vote Costs
     // =========================================================================
@@ -341,13 +398,15 @@ impl DuneClient {
               COUNT(*) as vote_count,
               SUM(fee) / 1e9 as total_fee_sol
             FROM solana.vote_transactions
-            WHERE signer = '{}'
+            WHERE {}
               AND block_date >= DATE '{}'
               {}
             GROUP BY FLOOR(block_slot / 432000)
             ORDER BY epoch
             "#,
-            self.identity, start_date, min_slot_clause
+            self.key_filter("signer", &self.identity, KeyRole::Identity),
+            start_date,
+            min_slot_clause
         );
 
         let rows = self.execute_query(&sql).await?;
@@ -387,7 +446,12 @@ impl DuneClient {
         Self::validate_address(&self.identity)?;
         Self::validate_address(&self.withdraw_authority)?;
         Self::validate_address(&self.vote_account)?;
-        for wallet in &self.personal_wallets {
+        for wallet in self
+            .personal_wallets
+            .iter()
+            .chain(&self.squads_vault)
+            .chain(&self.business_wallets)
+        {
             Self::validate_address(wallet)?;
         }
         println!("  Querying Dune for SOL transfers...");
@@ -398,9 +462,16 @@ impl DuneClient {
             self.withdraw_authority.as_str(),
             self.vote_account.as_str(),
         ];
-        for wallet in &self.personal_wallets {
+        for wallet in self
+            .personal_wallets
+            .iter()
+            .chain(&self.squads_vault)
+            .chain(&self.business_wallets)
+        {
             accounts.push(wallet.as_str());
         }
+        let retired: Vec<String> = self.retired_keys.iter().map(|k| k.pubkey.to_string()).collect();
+        accounts.extend(retired.iter().map(String::as_str));
         accounts.sort_unstable();
         accounts.dedup();
         let account_list = accounts
@@ -472,8 +543,11 @@ impl DuneClient {
                 let to_str = get_string(&row, "to_owner")?;
                 let amount_sol = get_f64(&row, "amount_sol")?;
                 let signature = get_string(&row, "signature")?;
-                let date = get_string_opt(&row, "block_date");
                 let timestamp = get_timestamp_opt(&row, "block_time");
+                // Dune's block_date is the UTC day; re-derive it in the accounting timezone
+                let date = timestamp
+                    .and_then(timezone::timestamp_to_date)
+                    .or_else(|| get_string_opt(&row, "block_date"));
 
                 // Always advance the cursor based on the raw row ordering (even if we skip).
                 cursor = Some((slot, signature.clone()));
@@ -494,8 +568,8 @@ impl DuneClient {
                     Err(_) => continue,
                 };
 
-                let (from_label, from_category) = self.label_and_category(&from);
-                let (to_label, to_category) = self.label_and_category(&to);
+                let (from_label, from_category) = self.label_and_category(&from, date.as_deref());
+                let (to_label, to_category) = self.label_and_category(&to, date.as_deref());
 
                 transfers.push(SolTransfer {
                     signature,
@@ -510,6 +584,8 @@ impl DuneClient {
                     to_label,
                     from_category,
                     to_category,
+                    memo: None,
+                    program: None,
                 });
             }
 
@@ -529,7 +605,27 @@ impl DuneClient {
         Ok(transfers)
     }
 
-    fn label_and_category(&self, pubkey: &Pubkey) -> (String, crate::addresses::AddressCategory) {
+    /// SQL condition matching `column` against the current `role` key, or a retired one on
+    /// the days it was in use. A retired identity or vote account stopped earning at the
+    /// hand-over, so summing per epoch merges the histories; anything it receives after
+    /// `until` is no longer ours.
+    fn key_filter(&self, column: &str, current: &str, role: KeyRole) -> String {
+        let mut terms = vec![format!("{} = '{}'", column, current)];
+        for key in self.retired_keys.iter().filter(|k| k.role == role) {
+            let from = key
+                .from
+                .as_ref()
+                .map(|from| format!(" AND block_date >= DATE '{}'", from))
+                .unwrap_or_default();
+            terms.push(format!(
+                "({} = '{}'{} AND block_date <= DATE '{}')",
+                column, key.pubkey, from, key.until
+            ));
+        }
+        format!("({})", terms.join(" OR "))
+    }
+
+    fn label_and_category(&self, pubkey: &Pubkey, date: Option<&str>) -> (String, crate::addresses::AddressCategory) {
         // Ensure categories for our own accounts so reconciliation queries work.
         let s = pubkey.to_string();
         if s == self.vote_account {
@@ -547,11 +643,23 @@ impl DuneClient {
                 "Withdraw Authority".to_string(),
                 crate::addresses::AddressCategory::ValidatorSelf,
             )
+        } else if self.squads_vault.as_deref() == Some(s.as_str()) {
+            (
+                "Squads Vault".to_string(),
+                crate::addresses::AddressCategory::ValidatorSelf,
+            )
+        } else if self.business_wallets.contains(&s) {
+            (
+                "Business Wallet".to_string(),
+                crate::addresses::AddressCategory::ValidatorSelf,
+            )
         } else if self.personal_wallets.iter().any(|w| w == &s) {
             (
                 "Personal Wallet".to_string(),
                 crate::addresses::AddressCategory::PersonalWallet,
             )
+        } else if let Some(key) = self.retired_keys.iter().find(|k| k.pubkey == *pubkey && k.covers(date)) {
+            (key.label(), crate::addresses::AddressCategory::ValidatorSelf)
         } else {
             let label = get_label(pubkey);
             (label.name, label.category)
@@ -709,3 +817,37 @@ fn get_timestamp_opt(row: &HashMap<String, serde_json::Value>, key: &str) -> Opt
         .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
         .map(|dt| dt.timestamp())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::tests::test_config;
+
+    #[test]
+    fn test_retired_vote_account_rewards_stop_at_migration() {
+        let mut config = test_config(None);
+        let old_vote = Pubkey::new_unique();
+        config.retired_keys.push(RetiredKey {
+            role: KeyRole::VoteAccount,
+            pubkey: old_vote,
+            from: Some("2025-01-10".to_string()),
+            until: "2025-06-30".to_string(),
+        });
+        let client = DuneClient::new(String::new(), &config);
+
+        // Rewards paid to the old vote account after 2025-06-30 are not ours
+        let filter = client.key_filter("recipient", &client.vote_account, KeyRole::VoteAccount);
+        assert_eq!(
+            filter,
+            format!(
+                "(recipient = '{}' OR (recipient = '{}' AND block_date >= DATE '2025-01-10' AND block_date <= DATE '2025-06-30'))",
+                config.vote_account, old_vote
+            )
+        );
+        // The old vote account is not an identity, so identity queries ignore it
+        assert_eq!(
+            client.key_filter("signer", &client.identity, KeyRole::Identity),
+            format!("(signer = '{}')", config.identity)
+        );
+    }
+}
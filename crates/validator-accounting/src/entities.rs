@@ -0,0 +1,579 @@
+//! Multi-entity books (`[[entities]]`)
+//!
+//! Some operators run the validator in one legal entity (the first `[[entities]]` entry, which
+//! owns the configured accounts) and pay some of its bills from another (e.g. an S-corp).
+//! Expenses stay the validator entity's costs whoever paid them; an expense paid by another
+//! entity creates a payable to that entity instead of a cash outflow. SOL sent from the
+//! validator accounts to an entity's wallet settles the payable, SOL received from one adds
+//! to it. The running balance is the due-to/due-from ledger; each entity gets a standalone
+//! report set, and the consolidated summary eliminates the inter-entity balances.
+
+use anyhow::Result;
+use csv::Writer;
+use solana_sdk::pubkey::Pubkey;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::config::{Config, EntityConfig};
+use crate::constants;
+use crate::expenses::Expense;
+use crate::period::ReportPeriod;
+use crate::prices::{HourlyPriceCache, PriceCache, get_event_price};
+use crate::transactions::{CategorizedTransfers, SolTransfer};
+
+/// A legal entity from `[[entities]]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entity {
+    pub id: String,
+    pub name: String,
+    pub wallets: Vec<Pubkey>,
+}
+
+impl Entity {
+    pub fn from_config(entity: &EntityConfig) -> Result<Self> {
+        // The id names the entity's report directory
+        if entity.id.is_empty()
+            || !entity
+                .id
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+        {
+            anyhow::bail!(
+                "Invalid entity id '{}': use lowercase letters, digits, '-' or '_'",
+                entity.id
+            );
+        }
+        let wallets = entity
+            .wallets
+            .iter()
+            .map(|w| {
+                Pubkey::from_str(w)
+                    .map_err(|e| anyhow::anyhow!("Invalid wallet {} for entity '{}': {}", w, entity.id, e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            id: entity.id.clone(),
+            name: entity.name.clone(),
+            wallets,
+        })
+    }
+}
+
+impl Config {
+    /// The entity that owns the validator accounts (None for single-entity operators)
+    pub fn primary_entity(&self) -> Option<&Entity> {
+        self.entities.first()
+    }
+
+    pub fn entity(&self, id: &str) -> Option<&Entity> {
+        self.entities.iter().find(|e| e.id == id)
+    }
+
+    /// The non-primary entity owning a wallet, if any
+    pub fn entity_for_wallet(&self, pubkey: &Pubkey) -> Option<&Entity> {
+        self.entities.iter().skip(1).find(|e| e.wallets.contains(pubkey))
+    }
+
+    /// Normalize an expense's paying entity: None (or the primary's id) means the primary paid
+    pub fn paying_entity(&self, entity: Option<&str>) -> Option<&Entity> {
+        let id = entity?;
+        self.entity(id)
+            .filter(|e| self.primary_entity().is_some_and(|p| p.id != e.id))
+    }
+}
+
+/// What moved a balance between the primary entity and another entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterEntityKind {
+    /// The entity paid a validator expense (primary owes it more)
+    ExpensePaid,
+    /// The primary sent SOL to the entity's wallet (settles what it owes)
+    SettlementPaid,
+    /// The entity sent SOL to the validator accounts (primary owes it more)
+    FundsReceived,
+}
+
+impl std::fmt::Display for InterEntityKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InterEntityKind::ExpensePaid => write!(f, "Expense Paid on Behalf"),
+            InterEntityKind::SettlementPaid => write!(f, "Settlement Paid"),
+            InterEntityKind::FundsReceived => write!(f, "Funds Received"),
+        }
+    }
+}
+
+/// One due-to/due-from ledger row between the primary entity and `entity_id`
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterEntityEntry {
+    pub date: String,
+    pub entity_id: String,
+    pub kind: InterEntityKind,
+    pub description: String,
+    /// Expense id or transaction signature
+    pub reference: String,
+    pub amount_sol: Option<f64>,
+    /// Signed change in what the primary owes the entity
+    pub amount_usd: f64,
+    /// What the primary owes the entity after this row (negative = the entity owes the primary)
+    pub balance_usd: f64,
+}
+
+/// Build the inter-entity ledger over all history, in date order with running balances per entity
+pub fn inter_entity_ledger(
+    config: &Config,
+    expenses: &[Expense],
+    categorized: &CategorizedTransfers,
+    prices: &PriceCache,
+    hourly_prices: &HourlyPriceCache,
+) -> Vec<InterEntityEntry> {
+    let mut entries = Vec::new();
+
+    for expense in expenses {
+        let Some(entity) = config.paying_entity(expense.entity.as_deref()) else {
+            continue;
+        };
+        entries.push(InterEntityEntry {
+            date: expense.date.clone(),
+            entity_id: entity.id.clone(),
+            kind: InterEntityKind::ExpensePaid,
+            description: format!("{}: {}", expense.vendor, expense.description),
+            reference: expense.id.map(|id| format!("expense:{}", id)).unwrap_or_default(),
+            amount_sol: None,
+            amount_usd: expense.amount_usd,
+            balance_usd: 0.0,
+        });
+    }
+
+    for transfer in all_transfers(categorized) {
+        let (entity, kind) = if let Some(entity) = config.entity_for_wallet(&transfer.to) {
+            (entity, InterEntityKind::SettlementPaid)
+        } else if let Some(entity) = config.entity_for_wallet(&transfer.from) {
+            (entity, InterEntityKind::FundsReceived)
+        } else {
+            continue;
+        };
+        let Some(date) = transfer.date.clone() else {
+            continue;
+        };
+        let (price, _) = get_event_price(prices, hourly_prices, &date, transfer.timestamp);
+        let usd = transfer.amount_sol * price;
+        entries.push(InterEntityEntry {
+            date,
+            entity_id: entity.id.clone(),
+            kind,
+            description: format!("{} -> {}", transfer.from_label, transfer.to_label),
+            reference: transfer.signature.clone(),
+            amount_sol: Some(transfer.amount_sol),
+            amount_usd: if kind == InterEntityKind::SettlementPaid {
+                -usd
+            } else {
+                usd
+            },
+            balance_usd: 0.0,
+        });
+    }
+
+    entries.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.reference.cmp(&b.reference)));
+    let mut balances = std::collections::HashMap::new();
+    for entry in &mut entries {
+        let balance = balances.entry(entry.entity_id.clone()).or_insert(0.0);
+        *balance += entry.amount_usd;
+        entry.balance_usd = *balance;
+    }
+    entries
+}
+
+fn all_transfers(categorized: &CategorizedTransfers) -> impl Iterator<Item = &SolTransfer> {
+    categorized
+        .seeding
+        .iter()
+        .chain(&categorized.sfdp_reimbursements)
+        .chain(&categorized.mev_deposits)
+        .chain(&categorized.doublezero_payments)
+        .chain(&categorized.donations)
+        .chain(&categorized.vote_funding)
+        .chain(&categorized.withdrawals)
+        .chain(&categorized.rent)
+        .chain(&categorized.other)
+}
+
+/// Totals for one non-primary entity over a period
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EntityPosition {
+    pub expenses_paid_usd: f64,
+    pub settlements_paid_usd: f64,
+    pub funds_received_usd: f64,
+    /// What the primary owes the entity at the end of the period
+    pub closing_balance_usd: f64,
+}
+
+/// Period totals and closing balance for `entity_id` (the balance includes earlier history)
+pub fn position(entries: &[InterEntityEntry], entity_id: &str, period: Option<&ReportPeriod>) -> EntityPosition {
+    let start = period.map(|p| p.start.format("%Y-%m-%d").to_string());
+    let mut position = EntityPosition::default();
+    for entry in entries.iter().filter(|e| e.entity_id == entity_id) {
+        if let Some(p) = period
+            && !p.contains(&entry.date)
+        {
+            // Earlier rows carry the opening balance; later ones are after the period closed
+            if start.as_deref().is_some_and(|s| entry.date.as_str() < s) {
+                position.closing_balance_usd = entry.balance_usd;
+            }
+            continue;
+        }
+        match entry.kind {
+            InterEntityKind::ExpensePaid => position.expenses_paid_usd += entry.amount_usd,
+            InterEntityKind::SettlementPaid => position.settlements_paid_usd -= entry.amount_usd,
+            InterEntityKind::FundsReceived => position.funds_received_usd += entry.amount_usd,
+        }
+        position.closing_balance_usd = entry.balance_usd;
+    }
+    position
+}
+
+/// Write the per-entity report sets under `entities/<id>/` and the consolidated summary
+pub fn generate_reports(
+    output_dir: &Path,
+    config: &Config,
+    expenses: &[Expense],
+    entries: &[InterEntityEntry],
+    period: Option<&ReportPeriod>,
+) -> Result<()> {
+    let Some(primary) = config.primary_entity() else {
+        return Ok(());
+    };
+    let root = output_dir.join(constants::ENTITIES_DIRNAME);
+    let in_period = |date: &str| period.is_none_or(|p| p.contains(date));
+
+    for entity in &config.entities {
+        let dir = root.join(&entity.id);
+        std::fs::create_dir_all(&dir)?;
+        let is_primary = entity.id == primary.id;
+
+        // Primary: every validator expense; others: the expenses they paid on its behalf
+        let paid: Vec<&Expense> = expenses
+            .iter()
+            .filter(|e| in_period(&e.date))
+            .filter(|e| {
+                is_primary
+                    || config
+                        .paying_entity(e.entity.as_deref())
+                        .is_some_and(|p| p.id == entity.id)
+            })
+            .collect();
+        write_expense_ledger(&dir, config, &paid)?;
+
+        let rows: Vec<&InterEntityEntry> = entries
+            .iter()
+            .filter(|e| in_period(&e.date))
+            .filter(|e| is_primary || e.entity_id == entity.id)
+            .collect();
+        write_inter_entity_ledger(&dir, config, is_primary, &rows)?;
+
+        write_entity_summary(&dir, config, entity, is_primary, &paid, entries, period)?;
+    }
+
+    write_consolidated_summary(&root, config, expenses, entries, period)
+}
+
+fn write_expense_ledger(dir: &Path, config: &Config, expenses: &[&Expense]) -> Result<()> {
+    let path = dir.join(constants::ENTITY_EXPENSE_LEDGER_FILENAME);
+    let mut wtr = Writer::from_path(&path)?;
+    wtr.write_record([
+        "Date (YYYY-MM-DD)",
+        "Vendor",
+        "Category",
+        "Description",
+        "Amount_USD",
+        "Paid_With",
+        "Paid_By_Entity",
+        "Invoice_ID",
+    ])?;
+    for e in expenses {
+        let paid_by = config
+            .paying_entity(e.entity.as_deref())
+            .or(config.primary_entity())
+            .map_or(String::new(), |p| p.id.clone());
+        wtr.write_record([
+            e.date.clone(),
+            e.vendor.clone(),
+            format!("{}", e.category),
+            e.description.clone(),
+            format!("{:.2}", e.amount_usd),
+            e.paid_with.clone(),
+            paid_by,
+            e.invoice_id.clone().unwrap_or_default(),
+        ])?;
+    }
+    wtr.flush()?;
+    println!("  Generated: {}", path.display());
+    Ok(())
+}
+
+fn write_inter_entity_ledger(dir: &Path, config: &Config, is_primary: bool, rows: &[&InterEntityEntry]) -> Result<()> {
+    let path = dir.join(constants::INTER_ENTITY_LEDGER_FILENAME);
+    let mut wtr = Writer::from_path(&path)?;
+    wtr.write_record([
+        "Date (YYYY-MM-DD)",
+        "Counterparty_Entity",
+        "Type",
+        "Description",
+        "Reference",
+        "Amount_SOL",
+        "Amount_USD",
+        "Balance_USD",
+        "Position",
+    ])?;
+    let primary_id = config.primary_entity().map_or("", |p| p.id.as_str());
+    for row in rows {
+        // The primary sees a payable, the other entity the mirror-image receivable
+        let sign = if is_primary { 1.0 } else { -1.0 };
+        let counterparty = if is_primary { row.entity_id.as_str() } else { primary_id };
+        wtr.write_record([
+            row.date.clone(),
+            counterparty.to_string(),
+            row.kind.to_string(),
+            row.description.clone(),
+            row.reference.clone(),
+            row.amount_sol.map_or(String::new(), |s| format!("{:.6}", s)),
+            format!("{:.2}", sign * row.amount_usd),
+            format!("{:.2}", sign * row.balance_usd),
+            due_label(sign * row.balance_usd).to_string(),
+        ])?;
+    }
+    wtr.flush()?;
+    println!("  Generated: {}", path.display());
+    Ok(())
+}
+
+/// Positive balances are owed by the reporting entity
+fn due_label(balance: f64) -> &'static str {
+    if balance > 0.005 {
+        "Due To"
+    } else if balance < -0.005 {
+        "Due From"
+    } else {
+        "Settled"
+    }
+}
+
+fn write_entity_summary(
+    dir: &Path,
+    config: &Config,
+    entity: &Entity,
+    is_primary: bool,
+    paid: &[&Expense],
+    entries: &[InterEntityEntry],
+    period: Option<&ReportPeriod>,
+) -> Result<()> {
+    let path = dir.join(constants::ENTITY_SUMMARY_FILENAME);
+    let mut wtr = Writer::from_path(&path)?;
+    wtr.write_record(["Metric", "Value"])?;
+    wtr.write_record(["Entity", entity.id.as_str()])?;
+    wtr.write_record(["Name", entity.name.as_str()])?;
+    wtr.write_record([
+        "Role",
+        if is_primary {
+            "Validator owner"
+        } else {
+            "Related entity"
+        },
+    ])?;
+    wtr.write_record(["Period", period.map_or("All time", |p| p.label.as_str())])?;
+
+    let expenses_usd: f64 = paid.iter().map(|e| e.amount_usd).sum();
+    if is_primary {
+        wtr.write_record(["Expenses_USD", format!("{:.2}", expenses_usd).as_str()])?;
+        for other in config.entities.iter().skip(1) {
+            let pos = position(entries, &other.id, period);
+            wtr.write_record([
+                format!("Due_To_{}_USD", other.id),
+                format!("{:.2}", pos.closing_balance_usd),
+            ])?;
+        }
+    } else {
+        let pos = position(entries, &entity.id, period);
+        wtr.write_record([
+            "Expenses_Paid_On_Behalf_USD",
+            format!("{:.2}", pos.expenses_paid_usd).as_str(),
+        ])?;
+        wtr.write_record([
+            "Funds_Sent_To_Validator_USD",
+            format!("{:.2}", pos.funds_received_usd).as_str(),
+        ])?;
+        wtr.write_record([
+            "Settlements_Received_USD",
+            format!("{:.2}", pos.settlements_paid_usd).as_str(),
+        ])?;
+        let primary_id = config.primary_entity().map_or("", |p| p.id.as_str());
+        wtr.write_record([
+            format!("Due_From_{}_USD", primary_id),
+            format!("{:.2}", pos.closing_balance_usd),
+        ])?;
+    }
+    wtr.flush()?;
+    println!("  Generated: {}", path.display());
+    Ok(())
+}
+
+fn write_consolidated_summary(
+    root: &Path,
+    config: &Config,
+    expenses: &[Expense],
+    entries: &[InterEntityEntry],
+    period: Option<&ReportPeriod>,
+) -> Result<()> {
+    let path = root.join(constants::CONSOLIDATED_SUMMARY_FILENAME);
+    let mut wtr = Writer::from_path(&path)?;
+    wtr.write_record([
+        "Entity",
+        "Name",
+        "Expenses_Paid_USD",
+        "Standalone_Inter_Entity_Balance_USD",
+        "Elimination_USD",
+        "Consolidated_Inter_Entity_Balance_USD",
+    ])?;
+
+    let in_period = |date: &str| period.is_none_or(|p| p.contains(date));
+    let primary_id = config.primary_entity().map_or("", |p| p.id.as_str());
+    let mut total_expenses = 0.0;
+    for entity in &config.entities {
+        let is_primary = entity.id == primary_id;
+        let paid: f64 = expenses
+            .iter()
+            .filter(|e| in_period(&e.date))
+            .filter(|e| match config.paying_entity(e.entity.as_deref()) {
+                Some(p) => p.id == entity.id,
+                None => is_primary,
+            })
+            .map(|e| e.amount_usd)
+            .sum();
+        total_expenses += paid;
+        // Receivable (+) for related entities, payable (-) for the primary; they net to zero
+        let balance = if is_primary {
+            -config
+                .entities
+                .iter()
+                .skip(1)
+                .map(|o| position(entries, &o.id, period).closing_balance_usd)
+                .sum::<f64>()
+        } else {
+            position(entries, &entity.id, period).closing_balance_usd
+        };
+        wtr.write_record([
+            entity.id.clone(),
+            entity.name.clone(),
+            format!("{:.2}", paid),
+            format!("{:.2}", balance),
+            format!("{:.2}", -balance),
+            "0.00".to_string(),
+        ])?;
+    }
+    wtr.write_record([
+        "Consolidated".to_string(),
+        String::new(),
+        format!("{:.2}", total_expenses),
+        "0.00".to_string(),
+        "0.00".to_string(),
+        "0.00".to_string(),
+    ])?;
+    wtr.flush()?;
+    println!("  Generated: {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::addresses::AddressCategory;
+    use crate::expenses::ExpenseCategory;
+
+    fn entity(id: &str, wallets: Vec<Pubkey>) -> Entity {
+        Entity {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            wallets,
+        }
+    }
+
+    fn expense(date: &str, amount_usd: f64, entity: Option<&str>) -> Expense {
+        Expense {
+            id: None,
+            date: date.to_string(),
+            vendor: "Colo".to_string(),
+            category: ExpenseCategory::Hosting,
+            description: "Rack".to_string(),
+            amount_usd,
+            paid_with: "Credit Card".to_string(),
+            invoice_id: None,
+            entity: entity.map(str::to_string),
+            status: Default::default(),
+        }
+    }
+
+    fn transfer(signature: &str, date: &str, from: Pubkey, to: Pubkey, amount_sol: f64) -> SolTransfer {
+        SolTransfer {
+            signature: signature.to_string(),
+            slot: 0,
+            timestamp: None,
+            date: Some(date.to_string()),
+            from,
+            to,
+            amount_lamports: (amount_sol * 1e9) as u64,
+            amount_sol,
+            from_label: String::new(),
+            to_label: String::new(),
+            from_category: AddressCategory::ValidatorSelf,
+            to_category: AddressCategory::Unknown,
+            memo: None,
+            program: None,
+        }
+    }
+
+    #[test]
+    fn test_inter_entity_ledger_balances() {
+        let scorp_wallet = Pubkey::new_unique();
+        let mut config = crate::config::tests::test_config(None);
+        config.entities = vec![entity("llc", Vec::new()), entity("scorp", vec![scorp_wallet])];
+
+        let expenses = vec![
+            expense("2025-01-05", 500.0, Some("scorp")),
+            expense("2025-01-06", 200.0, None),
+            expense("2025-01-07", 50.0, Some("llc")),
+        ];
+        let categorized = CategorizedTransfers {
+            other: vec![transfer("settle", "2025-02-01", config.vote_account, scorp_wallet, 2.0)],
+            ..Default::default()
+        };
+        let mut prices = PriceCache::new();
+        prices.insert("2025-02-01".to_string(), 100.0.into());
+
+        let entries = inter_entity_ledger(&config, &expenses, &categorized, &prices, &HourlyPriceCache::new());
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].kind, InterEntityKind::ExpensePaid);
+        assert_eq!(entries[0].balance_usd, 500.0);
+        assert_eq!(entries[1].kind, InterEntityKind::SettlementPaid);
+        assert!((entries[1].balance_usd - 300.0).abs() < 1e-9);
+
+        let january = ReportPeriod::parse("2025-01..2025-01").unwrap();
+        let pos = position(&entries, "scorp", Some(&january));
+        assert_eq!(pos.expenses_paid_usd, 500.0);
+        assert_eq!(pos.closing_balance_usd, 500.0);
+        let all = position(&entries, "scorp", None);
+        assert!((all.settlements_paid_usd - 200.0).abs() < 1e-9);
+        assert!((all.closing_balance_usd - 300.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_entity_id_validation() {
+        let config = |id: &str| EntityConfig {
+            id: id.to_string(),
+            name: "Holdings".to_string(),
+            wallets: Vec::new(),
+        };
+        assert!(Entity::from_config(&config("s-corp")).is_ok());
+        assert!(Entity::from_config(&config("../x")).is_err());
+        assert!(Entity::from_config(&config("")).is_err());
+    }
+}
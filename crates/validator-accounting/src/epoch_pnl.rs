@@ -0,0 +1,188 @@
+//! Per-epoch profitability (epoch_pnl.csv)
+//!
+//! Monthly totals average a month of epochs together, which hides what operators
+//! actually tune: commission, leader performance and MEV in a given epoch against that
+//! epoch's vote fees and DoubleZero accrual. One row per epoch with revenue by source,
+//! costs, net and margin, flagging the epochs that lost money. Off-chain expenses are
+//! not allocated to epochs; they stay in the monthly summary.
+
+use anyhow::Result;
+use csv::Writer;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::constants;
+use crate::prices::get_price;
+use crate::reports::ReportData;
+
+/// Revenue and costs of one epoch
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EpochPnl {
+    pub epoch: u64,
+    pub date: Option<String>,
+    pub commission_sol: f64,
+    pub leader_fees_sol: f64,
+    pub mev_sol: f64,
+    pub bam_sol: f64,
+    pub vote_cost_sol: f64,
+    pub doublezero_sol: f64,
+    pub price_usd: f64,
+}
+
+impl EpochPnl {
+    pub fn revenue_sol(&self) -> f64 {
+        self.commission_sol + self.leader_fees_sol + self.mev_sol + self.bam_sol
+    }
+
+    pub fn costs_sol(&self) -> f64 {
+        self.vote_cost_sol + self.doublezero_sol
+    }
+
+    pub fn net_sol(&self) -> f64 {
+        self.revenue_sol() - self.costs_sol()
+    }
+
+    pub fn net_usd(&self) -> f64 {
+        self.net_sol() * self.price_usd
+    }
+
+    /// Net as a percentage of revenue (None without revenue)
+    pub fn margin_percent(&self) -> Option<f64> {
+        let revenue = self.revenue_sol();
+        (revenue > 0.0).then(|| self.net_sol() / revenue * 100.0)
+    }
+
+    pub fn is_loss(&self) -> bool {
+        self.net_sol() < 0.0
+    }
+}
+
+/// Row for `epoch`, taking the first date any source has for it
+fn row<'a>(epochs: &'a mut BTreeMap<u64, EpochPnl>, epoch: u64, date: &Option<String>) -> &'a mut EpochPnl {
+    let row = epochs.entry(epoch).or_insert_with(|| EpochPnl {
+        epoch,
+        ..Default::default()
+    });
+    if row.date.is_none() {
+        row.date = date.clone();
+    }
+    row
+}
+
+/// One row per epoch that has any revenue or cost, oldest first
+pub fn compute(data: &ReportData) -> Vec<EpochPnl> {
+    let mut epochs: BTreeMap<u64, EpochPnl> = BTreeMap::new();
+
+    for r in data.rewards {
+        row(&mut epochs, r.epoch, &r.date).commission_sol += r.amount_sol;
+    }
+    for f in data.leader_fees {
+        row(&mut epochs, f.epoch, &f.date).leader_fees_sol += f.total_fees_sol;
+    }
+    for c in data.mev_claims {
+        row(&mut epochs, c.epoch, &c.date).mev_sol += c.amount_sol;
+    }
+    for c in data.bam_claims {
+        row(&mut epochs, c.epoch, &c.date).bam_sol += c.amount_sol_equivalent;
+    }
+    for v in data.vote_costs {
+        row(&mut epochs, v.epoch, &v.date).vote_cost_sol += v.total_fee_sol;
+    }
+    for d in data.doublezero_fees {
+        row(&mut epochs, d.epoch, &d.date).doublezero_sol += d.liability_sol;
+    }
+
+    epochs
+        .into_values()
+        .map(|mut row| {
+            row.price_usd = row.date.as_deref().map_or(0.0, |d| get_price(data.prices, d));
+            row
+        })
+        .collect()
+}
+
+/// Generate epoch_pnl.csv
+pub fn generate_csv(output_dir: &Path, rows: &[EpochPnl]) -> Result<()> {
+    let path = output_dir.join(constants::EPOCH_PNL_FILENAME);
+    let mut wtr = Writer::from_path(&path)?;
+
+    wtr.write_record([
+        "Epoch",
+        "Date (YYYY-MM-DD)",
+        "Commission_SOL",
+        "Leader_Fees_SOL",
+        "MEV_SOL",
+        "BAM_SOL",
+        "Revenue_SOL",
+        "Vote_Cost_SOL",
+        "DoubleZero_SOL",
+        "Costs_SOL",
+        "Net_SOL",
+        "SOL_Price_USD",
+        "Net_USD",
+        "Net_Margin_Pct",
+        "Loss_Making",
+    ])?;
+
+    for row in rows {
+        wtr.write_record([
+            row.epoch.to_string(),
+            row.date.clone().unwrap_or_default(),
+            format!("{:.6}", row.commission_sol),
+            format!("{:.6}", row.leader_fees_sol),
+            format!("{:.6}", row.mev_sol),
+            format!("{:.6}", row.bam_sol),
+            format!("{:.6}", row.revenue_sol()),
+            format!("{:.6}", row.vote_cost_sol),
+            format!("{:.6}", row.doublezero_sol),
+            format!("{:.6}", row.costs_sol()),
+            format!("{:.6}", row.net_sol()),
+            format!("{:.2}", row.price_usd),
+            format!("{:.2}", row.net_usd()),
+            row.margin_percent().map_or(String::new(), |m| format!("{:.1}", m)),
+            if row.is_loss() { "yes" } else { "no" }.to_string(),
+        ])?;
+    }
+
+    wtr.flush()?;
+    println!("  Generated: {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_margin_and_loss_flag() {
+        let profitable = EpochPnl {
+            commission_sol: 3.0,
+            leader_fees_sol: 1.0,
+            vote_cost_sol: 1.0,
+            price_usd: 100.0,
+            ..Default::default()
+        };
+        assert_eq!(profitable.net_sol(), 3.0);
+        assert_eq!(profitable.net_usd(), 300.0);
+        assert_eq!(profitable.margin_percent(), Some(75.0));
+        assert!(!profitable.is_loss());
+
+        let loss = EpochPnl {
+            commission_sol: 0.5,
+            vote_cost_sol: 1.0,
+            doublezero_sol: 0.1,
+            ..Default::default()
+        };
+        assert!(loss.is_loss());
+        assert!(loss.margin_percent().unwrap() < 0.0);
+
+        // Vote fees with no revenue at all (e.g. delinquent epoch)
+        let idle = EpochPnl {
+            vote_cost_sol: 1.0,
+            ..Default::default()
+        };
+        assert_eq!(idle.margin_percent(), None);
+        assert!(idle.is_loss());
+    }
+}
@@ -0,0 +1,423 @@
+//! Exchange deposit matching (`exchange import` / `exchange match`)
+//!
+//! Withdrawals leave the validator accounts on-chain, but nothing on-chain proves the
+//! exchange credited them. The operator exports the exchange's history (Kraken ledgers CSV
+//! or Coinbase transaction history CSV), imports it into the cache, and the matcher links
+//! each on-chain withdrawal to an exchange deposit of the same amount that landed within a
+//! window after it. Matched withdrawals are recorded as confirmed received; unmatched ones
+//! are flagged in the reconciliation CSV for follow-up.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use csv::{ReaderBuilder, StringRecord, WriterBuilder};
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::addresses::AddressCategory;
+use crate::constants;
+use crate::transactions::SolTransfer;
+
+/// Default window after a withdrawal in which the exchange must credit it
+pub const DEFAULT_WINDOW_HOURS: u64 = 72;
+
+/// Default amount tolerance (exchanges round displayed amounts)
+pub const DEFAULT_TOLERANCE_SOL: f64 = 0.0001;
+
+/// A SOL deposit credited by an exchange, from its exported history
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExchangeDeposit {
+    /// "kraken" or "coinbase"
+    pub exchange: String,
+    /// Exchange-side id (Kraken refid, Coinbase transaction id)
+    pub reference_id: String,
+    /// Unix timestamp (UTC)
+    pub timestamp: i64,
+    pub amount_sol: f64,
+}
+
+/// Supported export formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Kraken "Ledgers" export
+    Kraken,
+    /// Coinbase "Transaction history" export
+    Coinbase,
+}
+
+impl ExportFormat {
+    fn detect(header: &StringRecord) -> Option<Self> {
+        let has = |name: &str| header.iter().any(|h| h.trim() == name);
+        if has("refid") && has("aclass") && has("amount") {
+            Some(Self::Kraken)
+        } else if has("Timestamp") && has("Transaction Type") && has("Quantity Transacted") {
+            Some(Self::Coinbase)
+        } else {
+            None
+        }
+    }
+
+    fn exchange(self) -> &'static str {
+        match self {
+            Self::Kraken => "kraken",
+            Self::Coinbase => "coinbase",
+        }
+    }
+}
+
+/// Parse SOL deposits from an exchange export, detecting the format from its header row
+pub fn import_deposits(path: &Path) -> Result<Vec<ExchangeDeposit>> {
+    let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    parse_deposits(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Parse SOL deposits from export CSV text (Coinbase exports have preamble lines before the header)
+pub fn parse_deposits(content: &str) -> Result<Vec<ExchangeDeposit>> {
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(content.as_bytes());
+
+    let mut header: Option<(ExportFormat, StringRecord)> = None;
+    let mut deposits = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let record = record?;
+        let Some((format, header)) = &header else {
+            header = ExportFormat::detect(&record).map(|format| (format, record));
+            continue;
+        };
+        let column = |name: &str| {
+            header
+                .iter()
+                .position(|h| h.trim() == name)
+                .and_then(|i| record.get(i))
+                .map(str::trim)
+                .unwrap_or("")
+        };
+        let row = index + 1;
+        let deposit = match format {
+            ExportFormat::Kraken => {
+                if column("type") != "deposit" || column("asset") != "SOL" {
+                    continue;
+                }
+                ExchangeDeposit {
+                    exchange: format.exchange().to_string(),
+                    reference_id: column("refid").to_string(),
+                    timestamp: parse_timestamp(column("time")).with_context(|| format!("row {}", row))?,
+                    amount_sol: parse_amount(column("amount")).with_context(|| format!("row {}", row))?,
+                }
+            }
+            ExportFormat::Coinbase => {
+                if column("Transaction Type") != "Receive" || column("Asset") != "SOL" {
+                    continue;
+                }
+                let timestamp = parse_timestamp(column("Timestamp")).with_context(|| format!("row {}", row))?;
+                let amount_sol = parse_amount(column("Quantity Transacted")).with_context(|| format!("row {}", row))?;
+                // Older exports have no ID column
+                let reference_id = match column("ID") {
+                    "" => format!("{}:{}", timestamp, amount_sol),
+                    id => id.to_string(),
+                };
+                ExchangeDeposit {
+                    exchange: format.exchange().to_string(),
+                    reference_id,
+                    timestamp,
+                    amount_sol,
+                }
+            }
+        };
+        deposits.push(deposit);
+    }
+
+    if header.is_none() {
+        anyhow::bail!("Unrecognized export: expected a Kraken ledgers or Coinbase transaction history CSV");
+    }
+    Ok(deposits)
+}
+
+/// Parse "2024-01-02 03:04:05", "2024-01-02 03:04:05 UTC" or "2024-01-02T03:04:05Z" as UTC
+fn parse_timestamp(value: &str) -> Result<i64> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(dt.timestamp());
+    }
+    let naive = value.trim_end_matches(" UTC");
+    let naive = NaiveDateTime::parse_from_str(naive, "%Y-%m-%d %H:%M:%S%.f")
+        .with_context(|| format!("Invalid timestamp '{}'", value))?;
+    Ok(naive.and_utc().timestamp())
+}
+
+fn parse_amount(value: &str) -> Result<f64> {
+    value
+        .trim_start_matches('+')
+        .parse::<f64>()
+        .map(f64::abs)
+        .with_context(|| format!("Invalid amount '{}'", value))
+}
+
+/// Outcome for one on-chain withdrawal
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchStatus {
+    /// Linked to an exchange deposit
+    ConfirmedReceived(ExchangeDeposit),
+    /// No deposit found; flagged unless it went to a personal wallet
+    Unmatched,
+    /// Sent to a personal wallet, not expected in exchange records
+    PersonalWallet,
+}
+
+impl MatchStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ConfirmedReceived(_) => "confirmed_received",
+            Self::Unmatched => "unmatched",
+            Self::PersonalWallet => "personal_wallet",
+        }
+    }
+}
+
+/// A withdrawal with its match outcome
+#[derive(Debug, Clone)]
+pub struct WithdrawalMatch<'a> {
+    pub withdrawal: &'a SolTransfer,
+    pub status: MatchStatus,
+}
+
+/// Link withdrawals to deposits one-to-one.
+///
+/// Withdrawals are taken oldest first; each claims the unused deposit whose amount is
+/// within `tolerance_sol` and that was credited no earlier than the withdrawal and within
+/// `window_hours` after it, preferring the closest amount and then the earliest credit.
+/// Withdrawals without a block time can't be matched.
+pub fn match_withdrawals<'a>(
+    withdrawals: &'a [SolTransfer],
+    deposits: &[ExchangeDeposit],
+    window_hours: u64,
+    tolerance_sol: f64,
+) -> Vec<WithdrawalMatch<'a>> {
+    let window_secs = window_hours as i64 * 3600;
+    let mut ordered: Vec<&SolTransfer> = withdrawals.iter().collect();
+    ordered.sort_by_key(|w| (w.timestamp.unwrap_or(i64::MAX), w.slot));
+
+    let mut used = HashSet::new();
+    ordered
+        .into_iter()
+        .map(|withdrawal| {
+            let best = withdrawal.timestamp.and_then(|sent| {
+                deposits
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, d)| {
+                        !used.contains(i)
+                            && (d.amount_sol - withdrawal.amount_sol).abs() <= tolerance_sol
+                            && d.timestamp >= sent
+                            && d.timestamp - sent <= window_secs
+                    })
+                    .min_by(|(_, a), (_, b)| {
+                        let diff_a = (a.amount_sol - withdrawal.amount_sol).abs();
+                        let diff_b = (b.amount_sol - withdrawal.amount_sol).abs();
+                        diff_a.total_cmp(&diff_b).then(a.timestamp.cmp(&b.timestamp))
+                    })
+            });
+
+            let status = match best {
+                Some((i, deposit)) => {
+                    used.insert(i);
+                    MatchStatus::ConfirmedReceived(deposit.clone())
+                }
+                None if withdrawal.to_category == AddressCategory::PersonalWallet => MatchStatus::PersonalWallet,
+                None => MatchStatus::Unmatched,
+            };
+            WithdrawalMatch { withdrawal, status }
+        })
+        .collect()
+}
+
+fn format_time(timestamp: i64) -> String {
+    DateTime::<Utc>::from_timestamp(timestamp, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+/// Write the reconciliation CSV: every withdrawal with its status, then deposits nothing matched
+pub fn write_reconciliation_csv(
+    output_dir: &Path,
+    matches: &[WithdrawalMatch],
+    deposits: &[ExchangeDeposit],
+    window_hours: u64,
+) -> Result<()> {
+    let path = output_dir.join(constants::EXCHANGE_RECONCILIATION_FILENAME);
+    let mut wtr = WriterBuilder::new().flexible(true).from_path(&path)?;
+
+    wtr.write_record([format!(
+        "On-chain withdrawals matched to exchange deposits by amount within {} hours after sending. \
+         Unmatched withdrawals need follow-up with the exchange.",
+        window_hours
+    )])?;
+    wtr.write_record([""])?;
+    wtr.write_record([
+        "Date",
+        "Signature",
+        "Destination",
+        "Amount_SOL",
+        "Status",
+        "Exchange",
+        "Exchange_Reference",
+        "Credited_At (UTC)",
+        "Delay_Hours",
+    ])?;
+
+    for m in matches {
+        let w = m.withdrawal;
+        let (exchange, reference, credited, delay) = match &m.status {
+            MatchStatus::ConfirmedReceived(d) => (
+                d.exchange.clone(),
+                d.reference_id.clone(),
+                format_time(d.timestamp),
+                w.timestamp
+                    .map(|t| format!("{:.1}", (d.timestamp - t) as f64 / 3600.0))
+                    .unwrap_or_default(),
+            ),
+            _ => Default::default(),
+        };
+        wtr.write_record([
+            w.date.as_deref().unwrap_or(""),
+            w.signature.as_str(),
+            w.to_label.as_str(),
+            &format!("{:.9}", w.amount_sol),
+            m.status.as_str(),
+            exchange.as_str(),
+            reference.as_str(),
+            credited.as_str(),
+            delay.as_str(),
+        ])?;
+    }
+
+    let matched: HashSet<(&str, &str)> = matches
+        .iter()
+        .filter_map(|m| match &m.status {
+            MatchStatus::ConfirmedReceived(d) => Some((d.exchange.as_str(), d.reference_id.as_str())),
+            _ => None,
+        })
+        .collect();
+    let unclaimed: Vec<&ExchangeDeposit> = deposits
+        .iter()
+        .filter(|d| !matched.contains(&(d.exchange.as_str(), d.reference_id.as_str())))
+        .collect();
+    if !unclaimed.is_empty() {
+        wtr.write_record([""])?;
+        wtr.write_record(["Exchange deposits with no matching on-chain withdrawal (e.g. sent from another wallet)"])?;
+        wtr.write_record(["Credited_At (UTC)", "Exchange", "Exchange_Reference", "Amount_SOL"])?;
+        for d in unclaimed {
+            wtr.write_record([
+                format_time(d.timestamp),
+                d.exchange.clone(),
+                d.reference_id.clone(),
+                format!("{:.9}", d.amount_sol),
+            ])?;
+        }
+    }
+
+    wtr.flush()?;
+    println!("  Generated: {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn withdrawal(signature: &str, timestamp: i64, amount_sol: f64, to_category: AddressCategory) -> SolTransfer {
+        SolTransfer {
+            signature: signature.to_string(),
+            slot: 0,
+            timestamp: Some(timestamp),
+            date: None,
+            from: Pubkey::new_unique(),
+            to: Pubkey::new_unique(),
+            amount_lamports: (amount_sol * 1e9) as u64,
+            amount_sol,
+            from_label: String::new(),
+            to_label: String::new(),
+            from_category: AddressCategory::ValidatorSelf,
+            to_category,
+            memo: None,
+            program: None,
+        }
+    }
+
+    fn deposit(reference_id: &str, timestamp: i64, amount_sol: f64) -> ExchangeDeposit {
+        ExchangeDeposit {
+            exchange: "kraken".to_string(),
+            reference_id: reference_id.to_string(),
+            timestamp,
+            amount_sol,
+        }
+    }
+
+    #[test]
+    fn test_parse_kraken_ledgers() {
+        let csv = "\"txid\",\"refid\",\"time\",\"type\",\"subtype\",\"aclass\",\"asset\",\"wallet\",\"amount\",\"fee\",\"balance\"\n\
+                   \"L1\",\"R1\",\"2025-03-01 12:00:00\",\"deposit\",\"\",\"currency\",\"SOL\",\"spot / main\",\"25.0000000000\",\"0\",\"25\"\n\
+                   \"L2\",\"R2\",\"2025-03-01 13:00:00\",\"trade\",\"\",\"currency\",\"SOL\",\"spot / main\",\"-25.0000000000\",\"0\",\"0\"\n\
+                   \"L3\",\"R3\",\"2025-03-02 09:00:00\",\"deposit\",\"\",\"currency\",\"USD\",\"spot / main\",\"100\",\"0\",\"100\"\n";
+        let deposits = parse_deposits(csv).unwrap();
+        assert_eq!(deposits, vec![deposit("R1", 1_740_830_400, 25.0)]);
+    }
+
+    #[test]
+    fn test_parse_coinbase_history_with_preamble() {
+        let csv = "Transactions\nUser,someone@example.com\n\n\
+                   ID,Timestamp,Transaction Type,Asset,Quantity Transacted,Price Currency,Price at Transaction,Subtotal,Total (inclusive of fees and/or spread),Fees and/or Spread,Notes\n\
+                   abc,2025-03-01 12:00:00 UTC,Receive,SOL,10.5,USD,$140.00,$1470.00,$1470.00,$0.00,Received 10.5 SOL\n\
+                   def,2025-03-01 12:30:00 UTC,Sell,SOL,-10.5,USD,$140.00,$1470.00,$1460.00,$10.00,\n";
+        let deposits = parse_deposits(csv).unwrap();
+        assert_eq!(deposits.len(), 1);
+        assert_eq!(deposits[0].exchange, "coinbase");
+        assert_eq!(deposits[0].reference_id, "abc");
+        assert_eq!(deposits[0].timestamp, 1_740_830_400);
+        assert_eq!(deposits[0].amount_sol, 10.5);
+
+        assert!(parse_deposits("a,b,c\n1,2,3\n").is_err());
+    }
+
+    #[test]
+    fn test_match_withdrawals_by_amount_and_window() {
+        let withdrawals = vec![
+            withdrawal("sig1", 1_000, 25.0, AddressCategory::Exchange),
+            withdrawal("sig2", 2_000, 25.0, AddressCategory::Exchange),
+            withdrawal("sig3", 3_000, 7.0, AddressCategory::Exchange),
+            withdrawal("sig4", 4_000, 3.0, AddressCategory::PersonalWallet),
+        ];
+        let deposits = vec![
+            // Before the first withdrawal: can't be its receipt
+            deposit("early", 500, 25.0),
+            deposit("a", 1_600, 25.0),
+            deposit("b", 2_600, 25.00001),
+            // Outside the 1-hour window
+            deposit("late", 3_000 + 7_200, 7.0),
+        ];
+
+        let matches = match_withdrawals(&withdrawals, &deposits, 1, DEFAULT_TOLERANCE_SOL);
+        let outcome: Vec<(&str, &str)> = matches
+            .iter()
+            .map(|m| (m.withdrawal.signature.as_str(), m.status.as_str()))
+            .collect();
+        assert_eq!(
+            outcome,
+            vec![
+                ("sig1", "confirmed_received"),
+                ("sig2", "confirmed_received"),
+                ("sig3", "unmatched"),
+                ("sig4", "personal_wallet"),
+            ]
+        );
+        let MatchStatus::ConfirmedReceived(first) = &matches[0].status else {
+            unreachable!()
+        };
+        assert_eq!(first.reference_id, "a");
+        let MatchStatus::ConfirmedReceived(second) = &matches[1].status else {
+            unreachable!()
+        };
+        assert_eq!(second.reference_id, "b");
+    }
+}
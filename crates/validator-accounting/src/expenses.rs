@@ -20,6 +20,51 @@ pub struct Expense {
     pub amount_usd: f64,
     pub paid_with: String, // "USD", "SOL", "Credit Card"
     pub invoice_id: Option<String>,
+    /// Paying entity id from `[[entities]]` (None = the entity that owns the validator)
+    #[serde(default)]
+    pub entity: Option<String>,
+    /// Review state; only approved expenses reach the reports
+    #[serde(default)]
+    pub status: ExpenseStatus,
+}
+
+/// Review state of a stored expense
+///
+/// Manual entries are approved when added. Rows from CSV imports and the Notion sync
+/// arrive pending and stay out of the reports (listed separately) until reviewed.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ExpenseStatus {
+    Pending,
+    #[default]
+    Approved,
+    Rejected,
+}
+
+impl ExpenseStatus {
+    /// Value stored in the `status` column
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ExpenseStatus::Pending => "pending",
+            ExpenseStatus::Approved => "approved",
+            ExpenseStatus::Rejected => "rejected",
+        }
+    }
+
+    /// Parse a stored value (unknown values read as pending, so they stay out of reports)
+    pub fn from_db(s: &str) -> Self {
+        match s {
+            "approved" => ExpenseStatus::Approved,
+            "rejected" => ExpenseStatus::Rejected,
+            _ => ExpenseStatus::Pending,
+        }
+    }
+}
+
+impl std::fmt::Display for ExpenseStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Expense category
@@ -30,6 +75,10 @@ pub enum ExpenseCategory {
     Hardware,
     Software,
     VoteFees,
+    /// Fees paid on failed (non-vote) transactions
+    FailedTxFees,
+    /// Fees paid on successful non-vote (operational) transactions
+    OpsFees,
     Other,
 }
 
@@ -41,6 +90,8 @@ impl std::fmt::Display for ExpenseCategory {
             ExpenseCategory::Hardware => write!(f, "Hardware"),
             ExpenseCategory::Software => write!(f, "Software"),
             ExpenseCategory::VoteFees => write!(f, "Vote Fees"),
+            ExpenseCategory::FailedTxFees => write!(f, "Failed Tx Fees"),
+            ExpenseCategory::OpsFees => write!(f, "Ops Fees"),
             ExpenseCategory::Other => write!(f, "Other"),
         }
     }
@@ -121,6 +172,9 @@ pub struct RecurringExpense {
     pub start_date: String,
     /// Last month this expense applies (None = ongoing)
     pub end_date: Option<String>,
+    /// Paying entity id from `[[entities]]` (None = the entity that owns the validator)
+    #[serde(default)]
+    pub entity: Option<String>,
 }
 
 impl RecurringExpense {
@@ -185,6 +239,8 @@ pub fn expand_recurring_expenses(
                         amount_usd: rec.amount_usd,
                         paid_with: rec.paid_with.clone(),
                         invoice_id: None,
+                        entity: rec.entity.clone(),
+                        status: ExpenseStatus::Approved,
                     });
                 }
             }
@@ -0,0 +1,234 @@
+//! Google Sheets sync (`[gsheets]`, `sync-sheets`)
+//!
+//! Pushes the summary and ledgers into one tab each of a configured spreadsheet,
+//! authenticating as a service account (RS256-signed JWT exchanged for an access token).
+//! Tabs are cleared and rewritten in place, so formulas and charts on other tabs that
+//! reference them keep working; missing tabs are added. Values are written RAW: numbers
+//! become numbers, everything else stays text, so no cell is ever read as a formula.
+
+use anyhow::{Context, Result, bail};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::path::Path;
+
+use crate::config::GSheetsConfig;
+use crate::constants;
+
+const SHEETS_API_BASE: &str = "https://sheets.googleapis.com/v4/spreadsheets";
+const SHEETS_SCOPE: &str = "https://www.googleapis.com/auth/spreadsheets";
+const JWT_BEARER_GRANT: &str = "urn:ietf:params:oauth:grant-type:jwt-bearer";
+/// Access tokens are requested for this long (Google's maximum)
+const TOKEN_LIFETIME_SECS: i64 = 3600;
+
+/// Reports pushed, one tab each (named after the file without `.csv`)
+const SYNCED_REPORTS: [&str; 4] = [
+    constants::SUMMARY_FILENAME,
+    constants::INCOME_LEDGER_FILENAME,
+    constants::EXPENSE_LEDGER_FILENAME,
+    constants::TREASURY_LEDGER_FILENAME,
+];
+
+/// The fields used from a service account key file
+#[derive(Debug, Deserialize)]
+struct ServiceAccountKey {
+    client_email: String,
+    private_key: String,
+    token_uri: String,
+}
+
+#[derive(Debug, Serialize)]
+struct Claims<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: i64,
+    exp: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Spreadsheet {
+    #[serde(default)]
+    sheets: Vec<Sheet>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Sheet {
+    properties: SheetProperties,
+}
+
+#[derive(Debug, Deserialize)]
+struct SheetProperties {
+    title: String,
+}
+
+/// One tab's content
+#[derive(Debug)]
+struct Tab {
+    title: String,
+    rows: Vec<Vec<Value>>,
+}
+
+/// Push the reports in `output_dir` to the configured spreadsheet; returns the tabs written
+pub async fn sync(config: &GSheetsConfig, output_dir: &Path) -> Result<usize> {
+    if config.spreadsheet_id.is_empty() {
+        bail!("[gsheets] spreadsheet_id is not set");
+    }
+    let tabs = SYNCED_REPORTS
+        .iter()
+        .map(|name| output_dir.join(name))
+        .filter(|path| path.exists())
+        .map(|path| read_tab(&path))
+        .collect::<Result<Vec<_>>>()?;
+    if tabs.is_empty() {
+        bail!("No reports to sync in {}; generate them first", output_dir.display());
+    }
+
+    let client = reqwest::Client::new();
+    let token = access_token(&client, &config.credentials_path).await?;
+    let base = format!("{}/{}", SHEETS_API_BASE, config.spreadsheet_id);
+
+    let spreadsheet: Spreadsheet = send(
+        client
+            .get(format!("{}?fields=sheets.properties.title", base))
+            .bearer_auth(&token),
+    )
+    .await
+    .context("Failed to open the spreadsheet (is it shared with the service account?)")?;
+    let missing: Vec<Value> = tabs
+        .iter()
+        .filter(|tab| !spreadsheet.sheets.iter().any(|s| s.properties.title == tab.title))
+        .map(|tab| json!({ "addSheet": { "properties": { "title": tab.title } } }))
+        .collect();
+    if !missing.is_empty() {
+        let _: Value = send(
+            client
+                .post(format!("{}:batchUpdate", base))
+                .bearer_auth(&token)
+                .json(&json!({ "requests": missing })),
+        )
+        .await
+        .context("Failed to add tabs")?;
+    }
+
+    let ranges: Vec<String> = tabs.iter().map(|tab| quoted_range(&tab.title)).collect();
+    let _: Value = send(
+        client
+            .post(format!("{}/values:batchClear", base))
+            .bearer_auth(&token)
+            .json(&json!({ "ranges": ranges })),
+    )
+    .await
+    .context("Failed to clear tabs")?;
+
+    let data: Vec<Value> = tabs
+        .iter()
+        .map(|tab| json!({ "range": format!("{}!A1", quoted_range(&tab.title)), "values": tab.rows }))
+        .collect();
+    let _: Value = send(
+        client
+            .post(format!("{}/values:batchUpdate", base))
+            .bearer_auth(&token)
+            .json(&json!({ "valueInputOption": "RAW", "data": data })),
+    )
+    .await
+    .context("Failed to write tabs")?;
+
+    Ok(tabs.len())
+}
+
+/// Exchange a signed service-account JWT for an access token
+async fn access_token(client: &reqwest::Client, credentials_path: &Path) -> Result<String> {
+    let text = std::fs::read_to_string(credentials_path)
+        .with_context(|| format!("Failed to read service account key {}", credentials_path.display()))?;
+    let key: ServiceAccountKey = serde_json::from_str(&text).context("Invalid service account key file")?;
+
+    let now = chrono::Utc::now().timestamp();
+    let claims = Claims {
+        iss: &key.client_email,
+        scope: SHEETS_SCOPE,
+        aud: &key.token_uri,
+        iat: now,
+        exp: now + TOKEN_LIFETIME_SECS,
+    };
+    let signing_key =
+        EncodingKey::from_rsa_pem(key.private_key.as_bytes()).context("Invalid service account private key")?;
+    let assertion = jsonwebtoken::encode(&Header::new(Algorithm::RS256), &claims, &signing_key)?;
+
+    let response: TokenResponse = send(
+        client
+            .post(&key.token_uri)
+            .form(&[("grant_type", JWT_BEARER_GRANT), ("assertion", assertion.as_str())]),
+    )
+    .await
+    .context("Failed to get a Google access token")?;
+    Ok(response.access_token)
+}
+
+/// Send a request, failing with the response body on non-success statuses
+async fn send<T: serde::de::DeserializeOwned>(request: reqwest::RequestBuilder) -> Result<T> {
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        bail!("Google API error {}: {}", status, text);
+    }
+    Ok(response.json().await?)
+}
+
+/// A1 range covering a whole tab (titles are quoted, with `'` doubled)
+fn quoted_range(title: &str) -> String {
+    format!("'{}'", title.replace('\'', "''"))
+}
+
+fn read_tab(path: &Path) -> Result<Tab> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_path(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let rows = reader
+        .records()
+        .map(|record| -> Result<Vec<Value>> { Ok(record?.iter().map(cell_value).collect()) })
+        .collect::<Result<_>>()?;
+    Ok(Tab {
+        title: path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        rows,
+    })
+}
+
+/// Numbers as numbers; anything else (dates, signatures, zero-padded IDs) as text
+fn cell_value(field: &str) -> Value {
+    let digits = field.trim_start_matches('-');
+    let zero_padded = digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.");
+    match field.parse::<f64>() {
+        Ok(number) if number.is_finite() && !zero_padded && !field.contains(['e', 'E']) => json!(number),
+        _ => json!(field),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cell_values_keep_text_that_only_looks_numeric() {
+        assert_eq!(cell_value("1234.56"), json!(1234.56));
+        assert_eq!(cell_value("-0.25"), json!(-0.25));
+        assert_eq!(cell_value("700"), json!(700.0));
+        assert_eq!(cell_value("2025-01-01"), json!("2025-01-01"));
+        assert_eq!(cell_value("007"), json!("007"));
+        assert_eq!(cell_value("1e5"), json!("1e5"));
+        assert_eq!(cell_value("inf"), json!("inf"));
+        assert_eq!(cell_value("=HYPERLINK(\"x\")"), json!("=HYPERLINK(\"x\")"));
+        assert_eq!(quoted_range("Bob's"), "'Bob''s'");
+    }
+}
@@ -0,0 +1,7 @@
+//! Pushing generated reports to where the bookkeeping happens
+//!
+//! Each export reads the report files from the output directory after a run, so it sees
+//! exactly what was handed to the accountant (metadata block included).
+
+pub mod gsheets;
+pub mod s3;
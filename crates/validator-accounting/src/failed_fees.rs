@@ -0,0 +1,407 @@
+//! Fees paid on failed transactions
+//!
+//! A transaction that lands but fails still charges its fee payer (base plus priority fee).
+//! Vote transactions are already covered by the vote cost tracking; everything else the
+//! identity or withdraw authority pays for and gets back an error from (a claim that lost
+//! a race, a transfer that ran out of balance) is a real cost nothing else records. The
+//! scan walks each signer's signatures newest first down to where the previous scan
+//! stopped, fetches only the failed ones for their fee, and caches them; the report adds
+//! one expense per month under its own category.
+//!
+//! A scan checks at most `constants::FAILED_FEES_MAX_SIGNATURES` signatures. One that stops
+//! there short of the previous cursor leaves a resume point, and the next scan carries on
+//! below it before the cursor moves, so no range of signatures is skipped.
+
+use anyhow::Result;
+use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, UiInstruction, UiMessage, UiParsedInstruction,
+    UiTransactionEncoding,
+};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::config::Config;
+use crate::constants;
+use crate::expenses::{Expense, ExpenseCategory, ExpenseStatus};
+use crate::key_rotation::KeyRole;
+use crate::prices::{PriceCache, get_price};
+use crate::timezone;
+use crate::transactions;
+use crate::tx_builder;
+
+/// Vendor shown on failed-fee expenses
+pub const FAILED_FEES_VENDOR: &str = "Solana Network";
+
+/// Fee paid by one of our accounts on a transaction that failed
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailedTxFee {
+    pub signature: String,
+    /// Fee payer (one of our signing accounts)
+    pub account: String,
+    pub slot: u64,
+    pub timestamp: Option<i64>,
+    pub date: Option<String>,
+    pub fee_lamports: u64,
+    pub error: String,
+}
+
+/// A signature a scan stopped at
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanCursor {
+    pub signature: String,
+    pub slot: u64,
+}
+
+/// How far a signer's signatures have been checked
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScanState {
+    /// Newest signature everything older than has been checked (None before the first scan)
+    pub cursor: Option<ScanCursor>,
+    /// A pass that stopped at the signature limit before reaching `cursor`
+    pub resume: Option<ScanResume>,
+}
+
+/// A pass cut short by the signature limit: it started at `top` and got down to `before`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanResume {
+    pub top: ScanCursor,
+    pub before: ScanCursor,
+}
+
+/// Accounts that sign and pay for non-vote transactions: the current identity and
+/// withdraw authority, then their retired keys
+pub fn signing_accounts(config: &Config) -> Vec<(KeyRole, Pubkey)> {
+    let mut accounts = vec![(KeyRole::Identity, config.identity)];
+    if config.withdraw_authority != config.identity {
+        accounts.push((KeyRole::WithdrawAuthority, config.withdraw_authority));
+    }
+    for key in &config.retired_keys {
+        if key.role != KeyRole::VoteAccount && !accounts.iter().any(|(_, k)| *k == key.pubkey) {
+            accounts.push((key.role, key.pubkey));
+        }
+    }
+    accounts
+}
+
+/// Scan `account` for failed transactions it paid for, newest first, from where `state`
+/// says the previous scan stopped.
+///
+/// Returns the failed fees found and the state to store for the next scan.
+pub fn scan(client: &RpcClient, account: &Pubkey, state: &ScanState) -> Result<(Vec<FailedTxFee>, ScanState)> {
+    let (signatures, next) = list_signatures(client, account, state)?;
+    let failed_sigs = signatures.into_iter().filter_map(|s| {
+        let error = format!("{:?}", s.err?);
+        Some((s.signature, error))
+    });
+
+    let mut fees = Vec::new();
+    for (signature, error) in failed_sigs {
+        let Some(tx) = fetch_transaction(client, &signature)? else {
+            continue;
+        };
+        let Some(meta) = tx.transaction.meta.as_ref() else {
+            continue;
+        };
+        // Only fees we paid, and not votes (counted in vote costs)
+        if !is_fee_payer(&tx.transaction.transaction, account) || is_vote_transaction(&tx.transaction.transaction) {
+            continue;
+        }
+        fees.push(FailedTxFee {
+            signature,
+            account: account.to_string(),
+            slot: tx.slot,
+            timestamp: tx.block_time,
+            date: tx.block_time.and_then(timezone::timestamp_to_date),
+            fee_lamports: meta.fee,
+            error,
+        });
+    }
+
+    Ok((fees, next))
+}
+
+/// Signatures of `account` not yet checked according to `state`, newest first, up to
+/// `constants::FAILED_FEES_MAX_SIGNATURES` of them
+///
+/// Returns them with the state for the next scan.
+pub(crate) fn list_signatures(
+    client: &RpcClient,
+    account: &Pubkey,
+    state: &ScanState,
+) -> Result<(Vec<RpcConfirmedTransactionStatusWithSignature>, ScanState)> {
+    let (signatures, next) = page_signatures(
+        |before, until| fetch_signature_page(client, account, before, until),
+        state,
+        constants::FAILED_FEES_MAX_SIGNATURES,
+    )?;
+    if next.resume.is_some() {
+        eprintln!(
+            "      Scanned the limit of {} signatures for {}; the next scan continues from there",
+            constants::FAILED_FEES_MAX_SIGNATURES,
+            account
+        );
+    } else if state.cursor.is_none() && signatures.len() >= constants::FAILED_FEES_MAX_SIGNATURES {
+        eprintln!(
+            "      First scan of {} stopped at the limit of {} signatures; older ones are not checked",
+            account,
+            constants::FAILED_FEES_MAX_SIGNATURES
+        );
+    }
+    Ok((signatures, next))
+}
+
+/// Page through signatures (`fetch_page(before, until)`, newest first) from where `state`
+/// left off, stopping at its cursor, when they run out, or after `limit`
+///
+/// The cursor only moves once a pass reaches it (or runs out of signatures); a pass cut
+/// short by `limit` is recorded as a resume point instead. A first scan (no cursor) has
+/// nothing to reach, so it starts the cursor wherever it stops.
+pub(crate) fn page_signatures<F>(
+    mut fetch_page: F,
+    state: &ScanState,
+    limit: usize,
+) -> Result<(Vec<RpcConfirmedTransactionStatusWithSignature>, ScanState)>
+where
+    F: FnMut(Option<Signature>, Option<Signature>) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>>,
+{
+    let until = state
+        .cursor
+        .as_ref()
+        .and_then(|c| Signature::from_str(&c.signature).ok());
+    let (mut top, mut last) = match &state.resume {
+        Some(resume) => (Some(resume.top.clone()), Some(resume.before.clone())),
+        None => (None, None),
+    };
+    let mut signatures = Vec::new();
+    let mut finished = true;
+
+    loop {
+        let before = last.as_ref().map(|c| Signature::from_str(&c.signature)).transpose()?;
+        let batch = fetch_page(before, until)?;
+        let Some(oldest) = batch.last() else {
+            break;
+        };
+        last = Some(ScanCursor {
+            signature: oldest.signature.clone(),
+            slot: oldest.slot,
+        });
+        if top.is_none() {
+            top = batch.first().map(|s| ScanCursor {
+                signature: s.signature.clone(),
+                slot: s.slot,
+            });
+        }
+        signatures.extend(batch);
+        if signatures.len() >= limit {
+            finished = state.cursor.is_none();
+            break;
+        }
+    }
+
+    let next = match (finished, top, last) {
+        (false, Some(top), Some(before)) => ScanState {
+            cursor: state.cursor.clone(),
+            resume: Some(ScanResume { top, before }),
+        },
+        (_, top, _) => ScanState {
+            cursor: top.or_else(|| state.cursor.clone()),
+            resume: None,
+        },
+    };
+    Ok((signatures, next))
+}
+
+/// Signatures of `account`, newest first, down to the first one older than `min_slot`
+///
+/// Not capped: the slot range bounds it, and a cap would leave the older end unlisted.
+pub(crate) fn list_signatures_since(
+    client: &RpcClient,
+    account: &Pubkey,
+    min_slot: u64,
+) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+    let mut before = None;
+    let mut signatures = Vec::new();
+    loop {
+        let batch = fetch_signature_page(client, account, before, None)?;
+        let Some(oldest) = batch.last() else {
+            break;
+        };
+        let reached_min_slot = oldest.slot < min_slot;
+        before = Signature::from_str(&oldest.signature).ok();
+        signatures.extend(batch.into_iter().filter(|s| s.slot >= min_slot));
+        if reached_min_slot || before.is_none() {
+            break;
+        }
+    }
+    Ok(signatures)
+}
+
+/// One page (up to 1000) of `account`'s signatures, newest first, between `until` and `before`
+fn fetch_signature_page(
+    client: &RpcClient,
+    account: &Pubkey,
+    before: Option<Signature>,
+    until: Option<Signature>,
+) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+    Ok(client.get_signatures_for_address_with_config(
+        account,
+        GetConfirmedSignaturesForAddress2Config {
+            before,
+            until,
+            limit: Some(1000),
+            commitment: Some(CommitmentConfig::confirmed()),
+        },
+    )?)
+}
+
+/// Fetch a transaction with parsed instructions (None for an unparseable signature)
+pub(crate) fn fetch_transaction(
+    client: &RpcClient,
+    signature: &str,
+) -> Result<Option<EncodedConfirmedTransactionWithStatusMeta>> {
+    let Ok(sig) = Signature::from_str(signature) else {
+        return Ok(None);
+    };
+    let tx = client.get_transaction_with_config(
+        &sig,
+        RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::JsonParsed),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        },
+    )?;
+    Ok(Some(tx))
+}
+
+/// Whether `account` is the transaction's fee payer (first account key)
+pub(crate) fn is_fee_payer(tx: &EncodedTransaction, account: &Pubkey) -> bool {
+    transactions::extract_account_keys(tx, false).is_some_and(|keys| keys.first() == Some(account))
+}
+
+/// Whether every top-level instruction is a Vote program instruction
+pub(crate) fn is_vote_transaction(tx: &EncodedTransaction) -> bool {
+    let EncodedTransaction::Json(ui_tx) = tx else {
+        return false;
+    };
+    let UiMessage::Parsed(message) = &ui_tx.message else {
+        return false;
+    };
+    !message.instructions.is_empty()
+        && message.instructions.iter().all(|ix| match ix {
+            UiInstruction::Parsed(UiParsedInstruction::Parsed(pi)) => pi.program_id == tx_builder::VOTE_PROGRAM_ID,
+            UiInstruction::Parsed(UiParsedInstruction::PartiallyDecoded(pd)) => {
+                pd.program_id == tx_builder::VOTE_PROGRAM_ID
+            }
+            UiInstruction::Compiled(_) => false,
+        })
+}
+
+/// One expense per month: the month's failed-transaction fees, each valued at its day's price
+pub fn monthly_expenses(fees: &[FailedTxFee], prices: &PriceCache) -> Vec<Expense> {
+    // month -> (latest date, count, SOL, USD)
+    let mut months: BTreeMap<String, (String, usize, f64, f64)> = BTreeMap::new();
+    for fee in fees {
+        let Some(date) = fee.date.as_deref().filter(|d| d.len() >= 10) else {
+            continue;
+        };
+        let sol = fee.fee_lamports as f64 / 1e9;
+        let entry = months.entry(date[..7].to_string()).or_default();
+        if entry.0.as_str() < date {
+            entry.0 = date.to_string();
+        }
+        entry.1 += 1;
+        entry.2 += sol;
+        entry.3 += sol * get_price(prices, date);
+    }
+
+    months
+        .into_iter()
+        .map(|(month, (date, count, sol, usd))| Expense {
+            id: None,
+            date,
+            vendor: FAILED_FEES_VENDOR.to_string(),
+            category: ExpenseCategory::FailedTxFees,
+            description: format!(
+                "Fees for {} failed transaction(s) paid by our accounts in {} ({:.6} SOL)",
+                count, month, sol
+            ),
+            amount_usd: usd,
+            paid_with: "SOL".to_string(),
+            invoice_id: Some(format!("failed-fees:{}", month)),
+            entity: None,
+            status: ExpenseStatus::Approved,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::tests::test_config;
+    use crate::key_rotation::RetiredKey;
+
+    fn fee(signature: &str, date: &str, fee_lamports: u64) -> FailedTxFee {
+        FailedTxFee {
+            signature: signature.to_string(),
+            account: Pubkey::new_unique().to_string(),
+            slot: 0,
+            timestamp: None,
+            date: Some(date.to_string()),
+            fee_lamports,
+            error: "InstructionError(0, Custom(1))".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_monthly_expenses_group_and_value_fees() {
+        let fees = vec![
+            fee("a", "2025-06-03", 5_000_000),
+            fee("b", "2025-06-20", 15_000_000),
+            fee("c", "2025-07-01", 10_000_000),
+        ];
+        let mut prices = PriceCache::new();
+        prices.insert("2025-06-03".to_string(), 100.0.into());
+        prices.insert("2025-06-20".to_string(), 200.0.into());
+        prices.insert("2025-07-01".to_string(), 150.0.into());
+
+        let expenses = monthly_expenses(&fees, &prices);
+        assert_eq!(expenses.len(), 2);
+        assert_eq!(expenses[0].date, "2025-06-20");
+        assert_eq!(expenses[0].category, ExpenseCategory::FailedTxFees);
+        // 0.005 SOL at $100 + 0.015 SOL at $200
+        assert!((expenses[0].amount_usd - 3.5).abs() < 1e-9);
+        assert_eq!(expenses[0].invoice_id.as_deref(), Some("failed-fees:2025-06"));
+        assert!((expenses[1].amount_usd - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_signing_accounts_include_retired_signers() {
+        let mut config = test_config(None);
+        let old_identity = Pubkey::new_unique();
+        let old_vote = Pubkey::new_unique();
+        for (role, pubkey) in [(KeyRole::Identity, old_identity), (KeyRole::VoteAccount, old_vote)] {
+            config.retired_keys.push(RetiredKey {
+                role,
+                pubkey,
+                from: None,
+                until: "2025-06-30".to_string(),
+            });
+        }
+
+        let accounts = signing_accounts(&config);
+        assert_eq!(
+            accounts,
+            vec![
+                (KeyRole::Identity, config.identity),
+                (KeyRole::WithdrawAuthority, config.withdraw_authority),
+                (KeyRole::Identity, old_identity),
+            ]
+        );
+    }
+}
@@ -0,0 +1,258 @@
+//! Off-chain USD cash account (`[fiat]`)
+//!
+//! Expenses paid in USD come out of a bank account the on-chain treasury never sees.
+//! With an opening balance from config and deposits recorded by `fiat deposit` (e.g.
+//! exchange off-ramps landing in the bank), the account is rolled forward month by
+//! month: opening + deposits - USD expenses = closing. The runway divides the current
+//! balance by the average monthly USD spend over the last few complete months.
+
+use anyhow::Result;
+use chrono::{Datelike, NaiveDate};
+use csv::Writer;
+use serde::Serialize;
+use std::path::Path;
+
+use crate::config::FiatConfig;
+use crate::constants;
+use crate::expenses::Expense;
+
+/// A deposit into (negative: transfer out of) the cash account
+#[derive(Debug, Clone, PartialEq)]
+pub struct FiatDeposit {
+    /// Database ID (None before it is saved)
+    pub id: Option<i64>,
+    /// YYYY-MM-DD
+    pub date: String,
+    pub amount_usd: f64,
+    pub memo: String,
+}
+
+/// One month of the account roll-forward
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FiatMonth {
+    /// YYYY-MM
+    pub month: String,
+    pub opening_usd: f64,
+    pub deposits_usd: f64,
+    pub expenses_usd: f64,
+    pub closing_usd: f64,
+}
+
+/// Months of spend the current balance covers
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Runway {
+    /// Average USD spend over the trailing complete months (0 when there are none)
+    pub avg_monthly_expenses_usd: f64,
+    /// Complete months averaged
+    pub months_averaged: usize,
+    /// None when there is no spend to divide by
+    pub months: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FiatStatement {
+    pub account_name: String,
+    /// From the opening month through the current month
+    pub months: Vec<FiatMonth>,
+    pub balance_usd: f64,
+    pub runway: Runway,
+}
+
+/// Whether an expense was paid from the cash account
+fn paid_from_account(config: &FiatConfig, expense: &Expense) -> bool {
+    config
+        .paid_with
+        .iter()
+        .any(|method| method.eq_ignore_ascii_case(expense.paid_with.trim()))
+}
+
+fn first_of_next_month(date: NaiveDate) -> NaiveDate {
+    let (year, month) = if date.month() == 12 {
+        (date.year() + 1, 1)
+    } else {
+        (date.year(), date.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1).expect("first of month is valid")
+}
+
+/// Roll the account forward from `opening_date` through `today`'s month
+pub fn statement(
+    config: &FiatConfig,
+    expenses: &[Expense],
+    deposits: &[FiatDeposit],
+    today: NaiveDate,
+) -> FiatStatement {
+    // Validated in `Config::from_file`
+    let opening = NaiveDate::parse_from_str(&config.opening_date, "%Y-%m-%d").unwrap_or(today);
+    let opening_str = opening.format("%Y-%m-%d").to_string();
+    let today_str = today.format("%Y-%m-%d").to_string();
+    let in_range = |date: &str| date >= opening_str.as_str() && date <= today_str.as_str();
+
+    let mut months = Vec::new();
+    let mut balance = config.opening_balance_usd;
+    let mut month_start = opening.with_day(1).expect("day 1 is valid");
+    while month_start <= today {
+        let month = month_start.format("%Y-%m").to_string();
+        let in_month = |date: &str| date.starts_with(&month) && in_range(date);
+        let deposits_usd: f64 = deposits
+            .iter()
+            .filter(|d| in_month(&d.date))
+            .map(|d| d.amount_usd)
+            .sum();
+        let expenses_usd: f64 = expenses
+            .iter()
+            .filter(|e| in_month(&e.date) && paid_from_account(config, e))
+            .map(|e| e.amount_usd)
+            .sum();
+        let closing = balance + deposits_usd - expenses_usd;
+        months.push(FiatMonth {
+            month,
+            opening_usd: balance,
+            deposits_usd,
+            expenses_usd,
+            closing_usd: closing,
+        });
+        balance = closing;
+        month_start = first_of_next_month(month_start);
+    }
+
+    // The current month is still running, so only complete months count toward the burn
+    let complete = &months[..months.len().saturating_sub(1)];
+    let trailing = &complete[complete.len().saturating_sub(config.runway_months)..];
+    let avg_monthly_expenses_usd = if trailing.is_empty() {
+        0.0
+    } else {
+        trailing.iter().map(|m| m.expenses_usd).sum::<f64>() / trailing.len() as f64
+    };
+    let runway = Runway {
+        avg_monthly_expenses_usd,
+        months_averaged: trailing.len(),
+        months: (avg_monthly_expenses_usd > 0.0).then(|| balance.max(0.0) / avg_monthly_expenses_usd),
+    };
+
+    FiatStatement {
+        account_name: config.account_name.clone(),
+        months,
+        balance_usd: balance,
+        runway,
+    }
+}
+
+/// Write fiat_cash.csv (one row per month)
+pub fn generate_csv(output_dir: &Path, statement: &FiatStatement) -> Result<()> {
+    let path = output_dir.join(constants::FIAT_CASH_FILENAME);
+    let mut wtr = Writer::from_path(&path)?;
+
+    wtr.write_record([
+        "Month",
+        "Account",
+        "Opening_USD",
+        "Deposits_USD",
+        "Expenses_USD",
+        "Closing_USD",
+    ])?;
+    for m in &statement.months {
+        wtr.write_record([
+            m.month.clone(),
+            statement.account_name.clone(),
+            format!("{:.2}", m.opening_usd),
+            format!("{:.2}", m.deposits_usd),
+            format!("{:.2}", m.expenses_usd),
+            format!("{:.2}", m.closing_usd),
+        ])?;
+    }
+
+    wtr.flush()?;
+    println!("  Generated: {}", path.display());
+    Ok(())
+}
+
+/// Console section for the financial summary and `position`
+pub fn print(statement: &FiatStatement) {
+    println!("\nFIAT CASH ({}):", statement.account_name);
+    if let (Some(first), Some(last)) = (statement.months.first(), statement.months.last()) {
+        let deposits: f64 = statement.months.iter().map(|m| m.deposits_usd).sum();
+        let expenses: f64 = statement.months.iter().map(|m| m.expenses_usd).sum();
+        println!("  Opening ({}):              ${:>10.2}", first.month, first.opening_usd);
+        println!("  Deposits:                       ${:>10.2}", deposits);
+        println!("  USD Expenses:                  -${:>10.2}", expenses);
+        println!(
+            "  Balance ({}):              ${:>10.2}",
+            last.month, statement.balance_usd
+        );
+    }
+    let runway = &statement.runway;
+    match runway.months {
+        Some(months) => println!(
+            "  Runway:              {:>10.1} months at ${:.2}/month ({}-month average)",
+            months, runway.avg_monthly_expenses_usd, runway.months_averaged
+        ),
+        None => println!("  Runway:              no USD spend in the last complete months"),
+    }
+    if statement.balance_usd < 0.0 {
+        println!("  Warning: balance is negative; record missing deposits with 'validator-accounting fiat deposit'");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expenses::{ExpenseCategory, ExpenseStatus};
+
+    fn expense(date: &str, amount_usd: f64, paid_with: &str) -> Expense {
+        Expense {
+            id: None,
+            date: date.to_string(),
+            vendor: "Host".to_string(),
+            category: ExpenseCategory::Hosting,
+            description: String::new(),
+            amount_usd,
+            paid_with: paid_with.to_string(),
+            invoice_id: None,
+            entity: None,
+            status: ExpenseStatus::Approved,
+        }
+    }
+
+    fn deposit(date: &str, amount_usd: f64) -> FiatDeposit {
+        FiatDeposit {
+            id: None,
+            date: date.to_string(),
+            amount_usd,
+            memo: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_statement_rolls_forward_usd_expenses_and_deposits() {
+        let config = FiatConfig {
+            account_name: "Operating cash".to_string(),
+            opening_balance_usd: 1000.0,
+            opening_date: "2025-01-15".to_string(),
+            paid_with: vec!["USD".to_string()],
+            runway_months: 2,
+        };
+        let expenses = [
+            expense("2025-01-10", 999.0, "USD"), // before the opening date
+            expense("2025-01-20", 100.0, "usd"),
+            expense("2025-01-21", 50.0, "SOL"), // paid on-chain
+            expense("2025-02-05", 300.0, "USD"),
+            expense("2025-03-05", 500.0, "USD"),
+            expense("2025-04-02", 80.0, "USD"),
+        ];
+        let deposits = [deposit("2025-02-10", 600.0), deposit("2025-04-20", 100.0)];
+        let today = NaiveDate::from_ymd_opt(2025, 4, 10).unwrap();
+
+        let s = statement(&config, &expenses, &deposits, today);
+        let closings: Vec<f64> = s.months.iter().map(|m| m.closing_usd).collect();
+        // The April deposit is after today, so not yet in the balance
+        assert_eq!(closings, vec![900.0, 1200.0, 700.0, 620.0]);
+        assert_eq!(s.months[1].opening_usd, 900.0);
+        assert_eq!(s.balance_usd, 620.0);
+
+        // February and March are the two latest complete months
+        assert_eq!(s.runway.months_averaged, 2);
+        assert_eq!(s.runway.avg_monthly_expenses_usd, 400.0);
+        assert_eq!(s.runway.months, Some(1.55));
+    }
+}
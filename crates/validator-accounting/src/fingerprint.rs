@@ -0,0 +1,173 @@
+//! Tamper-evident report fingerprints (`verify-report`)
+//!
+//! Every report written is fingerprinted with a Merkle root over its canonical content:
+//! each CSV record (fields re-joined, so quoting and line endings don't matter) or each
+//! line of other text files is a SHA-256 leaf, and leaves are hashed pairwise up to one
+//! root. The root is stored in `report_fingerprints` with the generation time. Anyone
+//! holding a copy of the cache can then confirm a CSV handed to the accountant still has
+//! exactly the rows that were generated, whatever it was renamed to.
+
+use anyhow::{Context, Result};
+use csv::ReaderBuilder;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Extensions of generated reports (the zips and PDFs bundle these, so aren't fingerprinted)
+const REPORT_EXTENSIONS: [&str; 3] = ["csv", "html", "json"];
+
+/// Leaf and node hashes are domain-separated so a row can't pose as an inner node
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Merkle fingerprint of one report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fingerprint {
+    /// Hex SHA-256 Merkle root
+    pub root: String,
+    /// Records (CSV) or lines (other files) hashed
+    pub leaves: usize,
+}
+
+/// A fingerprint recorded when a report was written
+#[derive(Debug, Clone)]
+pub struct StoredFingerprint {
+    /// Path relative to the output directory
+    pub file_name: String,
+    pub root: String,
+    pub leaves: usize,
+    /// UTC, `YYYY-MM-DD HH:MM:SS`
+    pub generated_at: String,
+}
+
+/// Canonical leaves: CSV records with fields joined by U+001F, otherwise lines with
+/// line endings normalized; trailing blank lines are dropped either way
+fn canonical_leaves(path: &Path, content: &str) -> Result<Vec<String>> {
+    let is_csv = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+    let mut leaves: Vec<String> = if is_csv {
+        let mut reader = ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(content.as_bytes());
+        reader
+            .records()
+            .map(|record| -> Result<String> { Ok(record?.iter().collect::<Vec<_>>().join("\u{1f}")) })
+            .collect::<Result<_>>()?
+    } else {
+        content
+            .lines()
+            .map(|line| line.trim_end_matches('\r').to_string())
+            .collect()
+    };
+    while leaves.last().is_some_and(|leaf| leaf.trim().is_empty()) {
+        leaves.pop();
+    }
+    Ok(leaves)
+}
+
+/// Merkle root over `leaves`; an odd node moves up a level unpaired (pairing it with itself
+/// would let a duplicated last row keep the same root), and no leaves hash the empty string
+pub fn merkle_root(leaves: &[String]) -> String {
+    let mut level: Vec<[u8; 32]> = leaves
+        .iter()
+        .map(|leaf| {
+            let mut hasher = Sha256::new();
+            hasher.update([LEAF_PREFIX]);
+            hasher.update(leaf.as_bytes());
+            hasher.finalize().into()
+        })
+        .collect();
+    if level.is_empty() {
+        level.push(Sha256::digest(b"").into());
+    }
+
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => {
+                    let mut hasher = Sha256::new();
+                    hasher.update([NODE_PREFIX]);
+                    hasher.update(left);
+                    hasher.update(right);
+                    hasher.finalize().into()
+                }
+                _ => pair[0],
+            })
+            .collect();
+    }
+    level[0].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Fingerprint of a report file's content
+pub fn fingerprint_file(path: &Path) -> Result<Fingerprint> {
+    let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let leaves = canonical_leaves(path, &content).with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok(Fingerprint {
+        root: merkle_root(&leaves),
+        leaves: leaves.len(),
+    })
+}
+
+/// Every report file under `output_dir` (entity subdirectories included), sorted
+pub fn report_files(output_dir: &Path) -> Result<Vec<PathBuf>> {
+    files_with_extensions(output_dir, &REPORT_EXTENSIONS)
+}
+
+/// Files under `dir` with one of `extensions`, sorted; hidden directories (scratch space
+/// such as the tax package staging directory) are skipped
+pub fn files_with_extensions(dir: &Path, extensions: &[&str]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in std::fs::read_dir(&dir).with_context(|| format!("Failed to list {}", dir.display()))? {
+            let path = entry?.path();
+            if path.is_dir() {
+                if !path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+                {
+                    dirs.push(path);
+                }
+            } else if path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| extensions.contains(&ext))
+            {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(name: &str, content: &str) -> Vec<String> {
+        canonical_leaves(Path::new(name), content).unwrap()
+    }
+
+    #[test]
+    fn test_csv_canonical_form_ignores_quoting_and_line_endings() {
+        let plain = leaves("a.csv", "Date,Amount\n2025-01-01,1.50\n");
+        let quoted = leaves("a.csv", "\"Date\",\"Amount\"\r\n\"2025-01-01\",\"1.50\"\r\n\r\n");
+        assert_eq!(plain, quoted);
+        assert_eq!(merkle_root(&plain), merkle_root(&quoted));
+
+        let edited = leaves("a.csv", "Date,Amount\n2025-01-01,1.51\n");
+        assert_ne!(merkle_root(&plain), merkle_root(&edited));
+    }
+
+    #[test]
+    fn test_merkle_root_depends_on_order_and_count() {
+        let rows = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let abc = merkle_root(&rows(&["a", "b", "c"]));
+        assert_eq!(abc.len(), 64);
+        assert_ne!(abc, merkle_root(&rows(&["b", "a", "c"])));
+        // A duplicated last row changes the root
+        assert_ne!(abc, merkle_root(&rows(&["a", "b", "c", "c"])));
+        assert_ne!(merkle_root(&[]), merkle_root(&rows(&[""])));
+    }
+}
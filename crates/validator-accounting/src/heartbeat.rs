@@ -0,0 +1,44 @@
+//! Dead man's switch pings
+//!
+//! After a job succeeds, its `HEARTBEAT_<JOB>_URL` (a healthchecks.io-style check URL,
+//! the same variables bp-web reads) is requested, so an external monitor alerts when the
+//! pings stop. Jobs without a URL are not pinged.
+
+use anyhow::{Result, bail};
+use std::time::Duration;
+
+/// A full report run (ingestion) that finished without error
+pub const REPORT: &str = "report";
+/// A keeper check that finished without error
+pub const KEEPER: &str = "keeper";
+
+fn env_var(job: &str) -> String {
+    format!("HEARTBEAT_{}_URL", job.to_ascii_uppercase())
+}
+
+fn url(job: &str) -> Option<String> {
+    std::env::var(env_var(job))
+        .ok()
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+}
+
+/// Ping `job`'s heartbeat URL, if one is configured. A failed ping only warns: a missed
+/// ping is exactly what the monitor is there to notice.
+pub async fn ping(job: &str) {
+    let Some(url) = url(job) else {
+        return;
+    };
+    if let Err(e) = get(&url).await {
+        eprintln!("Warning: {} heartbeat ping failed: {:#}", job, e);
+    }
+}
+
+async fn get(url: &str) -> Result<()> {
+    let client = reqwest::Client::builder().timeout(Duration::from_secs(10)).build()?;
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        bail!("heartbeat URL returned {}", response.status());
+    }
+    Ok(())
+}
@@ -0,0 +1,330 @@
+//! Onboarding import of historical rewards and stake from public aggregators
+//!
+//! A validator adopting this tool after a year of operation would otherwise wait on an
+//! epoch-by-epoch RPC backfill (with Dune for pruned epochs) before the first report.
+//! `import fetch` pulls per-epoch commission rewards and active stake from Stakewiz and
+//! Solana Beach and seeds the cache right away. Seeded reward rows are recorded as
+//! unverified in `reward_imports`: the next report run treats them as missing, refetches
+//! them on-chain and stores the on-chain amount next to the imported one, and
+//! `import status` lists any epoch where the two disagree.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::constants;
+use crate::transactions::{EpochReward, epoch_to_date};
+
+/// Imported and on-chain amounts further apart than this (0.001 SOL) are a mismatch;
+/// aggregators publish SOL floats, so a few lamports of rounding is expected
+pub const VERIFY_TOLERANCE_LAMPORTS: u64 = 1_000_000;
+
+/// Public aggregator to import from
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportSource {
+    /// Stakewiz epoch history (no key needed)
+    Stakewiz,
+    /// Solana Beach validator rewards (requires `api_keys.solanabeach`)
+    SolanaBeach,
+}
+
+impl ImportSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Stakewiz => "stakewiz",
+            Self::SolanaBeach => "solanabeach",
+        }
+    }
+}
+
+/// One epoch of aggregator history
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImportedEpoch {
+    pub epoch: u64,
+    /// Source(s) the row came from, `+`-joined when merged
+    pub source: String,
+    /// Commission earned by the vote account (inflation rewards)
+    pub commission_lamports: Option<u64>,
+    /// Active stake delegated to the validator
+    pub activated_stake_lamports: Option<u64>,
+    pub commission_percent: Option<u8>,
+}
+
+/// Verification state of an imported epoch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum VerifyStatus {
+    /// Still waiting for the on-chain backfill
+    Pending,
+    /// On-chain amount agrees within `VERIFY_TOLERANCE_LAMPORTS`
+    Verified,
+    /// On-chain amount differs; the cache now holds the on-chain value
+    Mismatch { delta_lamports: i64 },
+    /// Only stake was imported, nothing to compare
+    StakeOnly,
+}
+
+/// Compare an imported amount with what the chain reported
+pub fn verify_status(imported: Option<u64>, onchain: Option<u64>) -> VerifyStatus {
+    match (imported, onchain) {
+        (None, _) => VerifyStatus::StakeOnly,
+        (Some(_), None) => VerifyStatus::Pending,
+        (Some(imported), Some(onchain)) if imported.abs_diff(onchain) <= VERIFY_TOLERANCE_LAMPORTS => {
+            VerifyStatus::Verified
+        }
+        (Some(imported), Some(onchain)) => VerifyStatus::Mismatch {
+            delta_lamports: onchain as i64 - imported as i64,
+        },
+    }
+}
+
+fn sol_to_lamports(sol: f64) -> u64 {
+    (sol.max(0.0) * constants::LAMPORTS_PER_SOL_U64 as f64).round() as u64
+}
+
+/// Stakewiz `validator_epoch_history` row (stake in SOL)
+#[derive(Debug, Deserialize)]
+struct StakewizEpoch {
+    epoch: u64,
+    #[serde(default)]
+    activated_stake: Option<f64>,
+    #[serde(default)]
+    commission: Option<f64>,
+    /// Vote account inflation rewards in SOL (absent for older epochs)
+    #[serde(default, alias = "vote_rewards")]
+    commission_rewards: Option<f64>,
+}
+
+/// Solana Beach validator reward row (amounts in lamports)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SolanaBeachEpoch {
+    epoch: u64,
+    #[serde(default)]
+    amount: Option<u64>,
+    #[serde(default)]
+    activated_stake: Option<u64>,
+    #[serde(default)]
+    commission: Option<u8>,
+}
+
+pub fn parse_stakewiz(json: &str) -> Result<Vec<ImportedEpoch>> {
+    let rows: Vec<StakewizEpoch> = serde_json::from_str(json).context("Unexpected Stakewiz response")?;
+    Ok(rows
+        .into_iter()
+        .map(|r| ImportedEpoch {
+            epoch: r.epoch,
+            source: ImportSource::Stakewiz.as_str().to_string(),
+            commission_lamports: r.commission_rewards.map(sol_to_lamports),
+            activated_stake_lamports: r.activated_stake.map(sol_to_lamports),
+            commission_percent: r.commission.map(|c| c.round().clamp(0.0, 100.0) as u8),
+        })
+        .collect())
+}
+
+pub fn parse_solana_beach(json: &str) -> Result<Vec<ImportedEpoch>> {
+    let rows: Vec<SolanaBeachEpoch> = serde_json::from_str(json).context("Unexpected Solana Beach response")?;
+    Ok(rows
+        .into_iter()
+        .map(|r| ImportedEpoch {
+            epoch: r.epoch,
+            source: ImportSource::SolanaBeach.as_str().to_string(),
+            commission_lamports: r.amount,
+            activated_stake_lamports: r.activated_stake,
+            commission_percent: r.commission,
+        })
+        .collect())
+}
+
+/// Fetch one source's per-epoch history for `vote_account`
+pub async fn fetch(
+    source: ImportSource,
+    vote_account: &str,
+    solana_beach_key: Option<&str>,
+) -> Result<Vec<ImportedEpoch>> {
+    let client = reqwest::Client::new();
+    let request = match source {
+        ImportSource::Stakewiz => client.get(format!(
+            "{}/validator_epoch_history/{}",
+            constants::STAKEWIZ_API_BASE,
+            vote_account
+        )),
+        ImportSource::SolanaBeach => {
+            let key = solana_beach_key
+                .filter(|k| !k.is_empty())
+                .context("Solana Beach needs api_keys.solanabeach (or SOLANABEACH_API_KEY)")?;
+            client
+                .get(format!(
+                    "{}/validator/{}/rewards",
+                    constants::SOLANA_BEACH_API_BASE,
+                    vote_account
+                ))
+                .bearer_auth(key)
+        }
+    };
+
+    let response = request.header("Accept", "application/json").send().await?;
+    let status = response.status();
+    let body = response.text().await?;
+    anyhow::ensure!(
+        status.is_success(),
+        "{} API error {}: {}",
+        source.as_str(),
+        status,
+        body.chars().take(200).collect::<String>()
+    );
+
+    match source {
+        ImportSource::Stakewiz => parse_stakewiz(&body),
+        ImportSource::SolanaBeach => parse_solana_beach(&body),
+    }
+}
+
+/// Combine sources per epoch; earlier sources win where both have a value
+pub fn merge(sets: Vec<Vec<ImportedEpoch>>) -> Vec<ImportedEpoch> {
+    let mut by_epoch: BTreeMap<u64, ImportedEpoch> = BTreeMap::new();
+    for row in sets.into_iter().flatten() {
+        match by_epoch.get_mut(&row.epoch) {
+            None => {
+                by_epoch.insert(row.epoch, row);
+            }
+            Some(existing) => {
+                let mut contributed = false;
+                if existing.commission_lamports.is_none() && row.commission_lamports.is_some() {
+                    existing.commission_lamports = row.commission_lamports;
+                    contributed = true;
+                }
+                if existing.activated_stake_lamports.is_none() && row.activated_stake_lamports.is_some() {
+                    existing.activated_stake_lamports = row.activated_stake_lamports;
+                    contributed = true;
+                }
+                if existing.commission_percent.is_none() && row.commission_percent.is_some() {
+                    existing.commission_percent = row.commission_percent;
+                    contributed = true;
+                }
+                if contributed && !existing.source.split('+').any(|s| s == row.source) {
+                    existing.source = format!("{}+{}", existing.source, row.source);
+                }
+            }
+        }
+    }
+    by_epoch.into_values().collect()
+}
+
+/// Reward rows to seed: imported epochs with an amount that the chain hasn't filled yet
+pub fn seed_rows(
+    imports: &[ImportedEpoch],
+    onchain_epochs: &BTreeSet<u64>,
+    default_commission: u8,
+) -> Vec<EpochReward> {
+    imports
+        .iter()
+        .filter(|i| !onchain_epochs.contains(&i.epoch))
+        .filter_map(|i| {
+            let lamports = i.commission_lamports?;
+            Some(EpochReward {
+                epoch: i.epoch,
+                amount_lamports: lamports,
+                amount_sol: lamports as f64 / constants::LAMPORTS_PER_SOL_U64 as f64,
+                commission: i.commission_percent.unwrap_or(default_commission),
+                effective_slot: (i.epoch + 1) * constants::SLOTS_PER_EPOCH,
+                date: Some(epoch_to_date(i.epoch)),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(epoch: u64, source: &str, commission: Option<u64>, stake: Option<u64>) -> ImportedEpoch {
+        ImportedEpoch {
+            epoch,
+            source: source.to_string(),
+            commission_lamports: commission,
+            activated_stake_lamports: stake,
+            commission_percent: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_stakewiz_converts_sol() {
+        let rows = parse_stakewiz(
+            r#"[{"epoch": 800, "activated_stake": 150000.5, "commission": 5, "commission_rewards": 2.25},
+                {"epoch": 801, "activated_stake": 151000}]"#,
+        )
+        .unwrap();
+        assert_eq!(rows[0].commission_lamports, Some(2_250_000_000));
+        assert_eq!(rows[0].activated_stake_lamports, Some(150_000_500_000_000));
+        assert_eq!(rows[0].commission_percent, Some(5));
+        assert_eq!(rows[1].commission_lamports, None);
+    }
+
+    #[test]
+    fn test_parse_solana_beach_lamports() {
+        let rows =
+            parse_solana_beach(r#"[{"epoch": 800, "amount": 2250000000, "activatedStake": 7, "commission": 5}]"#)
+                .unwrap();
+        assert_eq!(
+            rows,
+            vec![ImportedEpoch {
+                epoch: 800,
+                source: "solanabeach".to_string(),
+                commission_lamports: Some(2_250_000_000),
+                activated_stake_lamports: Some(7),
+                commission_percent: Some(5),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_merge_fills_gaps_from_later_sources() {
+        let merged = merge(vec![
+            vec![
+                row(800, "stakewiz", None, Some(10)),
+                row(801, "stakewiz", Some(5), Some(11)),
+            ],
+            vec![
+                row(800, "solanabeach", Some(4), Some(99)),
+                row(801, "solanabeach", Some(6), None),
+            ],
+        ]);
+        assert_eq!(merged[0].commission_lamports, Some(4));
+        assert_eq!(merged[0].activated_stake_lamports, Some(10));
+        assert_eq!(merged[0].source, "stakewiz+solanabeach");
+        // Nothing taken from the second source
+        assert_eq!(merged[1].commission_lamports, Some(5));
+        assert_eq!(merged[1].source, "stakewiz");
+    }
+
+    #[test]
+    fn test_seed_rows_skip_onchain_and_stake_only_epochs() {
+        let imports = vec![
+            row(800, "stakewiz", Some(1_000), None),
+            row(801, "stakewiz", None, Some(5)),
+            row(802, "stakewiz", Some(2_000), None),
+        ];
+        let onchain = BTreeSet::from([802]);
+        let rows = seed_rows(&imports, &onchain, 5);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].epoch, 800);
+        assert_eq!(rows[0].commission, 5);
+    }
+
+    #[test]
+    fn test_verify_status() {
+        assert_eq!(verify_status(None, Some(1)), VerifyStatus::StakeOnly);
+        assert_eq!(verify_status(Some(1), None), VerifyStatus::Pending);
+        assert_eq!(
+            verify_status(Some(2_000_000_000), Some(2_000_000_500)),
+            VerifyStatus::Verified
+        );
+        assert_eq!(
+            verify_status(Some(2_000_000_000), Some(2_100_000_000)),
+            VerifyStatus::Mismatch {
+                delta_lamports: 100_000_000
+            }
+        );
+    }
+}
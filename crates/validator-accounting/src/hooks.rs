@@ -0,0 +1,154 @@
+//! Post-generation hooks (`[[hooks]]`)
+//!
+//! After a report run, `manifest.json` is written to the output directory - the report
+//! metadata plus every report file with its size and fingerprint - and each configured
+//! command runs in order with `BP_OUTPUT_DIR` and `BP_REPORT_MANIFEST` set, so operators
+//! can upload to Drive or push to their ERP without patching the crate. Commands run
+//! without a shell, with the output directory as working directory. WASM plugins run
+//! the same way through their runtime (`command = ["wasmtime", "--dir", ".", "plugin.wasm"]`).
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::config::HookConfig;
+use crate::constants;
+use crate::fingerprint;
+use crate::report_metadata::ReportMetadata;
+
+/// Environment variable holding the output directory
+pub const OUTPUT_DIR_ENV: &str = "BP_OUTPUT_DIR";
+/// Environment variable holding the manifest path
+pub const MANIFEST_ENV: &str = "BP_REPORT_MANIFEST";
+
+/// What a hook is told about the reports just generated
+#[derive(Debug, Serialize)]
+pub struct Manifest {
+    pub output_dir: PathBuf,
+    /// Report metadata (see `report_metadata`), label to value
+    pub metadata: Vec<(String, String)>,
+    pub files: Vec<ManifestFile>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ManifestFile {
+    /// Relative to the output directory, `/`-separated
+    pub path: String,
+    pub bytes: u64,
+    /// Merkle root recorded for `verify-report`
+    pub fingerprint: String,
+    pub rows: usize,
+}
+
+/// Describe every report file in `output_dir`
+pub fn build_manifest(output_dir: &Path, metadata: &ReportMetadata) -> Result<Manifest> {
+    let mut files = Vec::new();
+    for path in fingerprint::report_files(output_dir)? {
+        let relative = path.strip_prefix(output_dir).unwrap_or(&path);
+        if relative == Path::new(constants::REPORT_MANIFEST_FILENAME) {
+            continue;
+        }
+        let fingerprint = fingerprint::fingerprint_file(&path)?;
+        files.push(ManifestFile {
+            path: relative.to_string_lossy().replace('\\', "/"),
+            bytes: std::fs::metadata(&path)?.len(),
+            fingerprint: fingerprint.root,
+            rows: fingerprint.leaves,
+        });
+    }
+    Ok(Manifest {
+        output_dir: output_dir.to_path_buf(),
+        metadata: metadata.fields(),
+        files,
+    })
+}
+
+/// Write the manifest and run each hook; a failing hook warns unless it is `required`
+pub async fn run(hooks: &[HookConfig], output_dir: &Path, metadata: &ReportMetadata) -> Result<()> {
+    if hooks.is_empty() {
+        return Ok(());
+    }
+    let manifest = build_manifest(output_dir, metadata)?;
+    let manifest_path = output_dir.join(constants::REPORT_MANIFEST_FILENAME);
+    std::fs::write(&manifest_path, serde_json::to_vec_pretty(&manifest)?)
+        .with_context(|| format!("Failed to write {}", manifest_path.display()))?;
+
+    println!("Running {} post-generation hook(s)...", hooks.len());
+    for hook in hooks {
+        match run_hook(hook, output_dir, &manifest_path).await {
+            Ok(()) => println!("  Hook '{}' finished", hook.name),
+            Err(e) if hook.required => return Err(e.context(format!("Required hook '{}' failed", hook.name))),
+            Err(e) => eprintln!("  Warning: hook '{}' failed: {:#}", hook.name, e),
+        }
+    }
+    Ok(())
+}
+
+async fn run_hook(hook: &HookConfig, output_dir: &Path, manifest_path: &Path) -> Result<()> {
+    let Some((program, args)) = hook.command.split_first() else {
+        bail!("hook '{}' has an empty command", hook.name);
+    };
+    let output_dir = std::path::absolute(output_dir)?;
+    let manifest_path = std::path::absolute(manifest_path)?;
+    let mut child = tokio::process::Command::new(program)
+        .args(args)
+        .current_dir(&output_dir)
+        .env(OUTPUT_DIR_ENV, &output_dir)
+        .env(MANIFEST_ENV, &manifest_path)
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to start {}", program))?;
+
+    let status = tokio::time::timeout(Duration::from_secs(hook.timeout_secs), child.wait())
+        .await
+        .with_context(|| format!("timed out after {}s", hook.timeout_secs))??;
+    if !status.success() {
+        bail!("{} exited with {}", program, status);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook(command: &[&str], required: bool) -> HookConfig {
+        HookConfig {
+            name: "test".to_string(),
+            command: command.iter().map(|s| s.to_string()).collect(),
+            timeout_secs: 10,
+            required,
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_hooks_see_manifest_and_required_failures_stop_the_run() {
+        let dir = std::env::temp_dir().join(format!("va-hooks-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(constants::SUMMARY_FILENAME), "Month,Revenue\n2025-01,1.50\n").unwrap();
+        let metadata = ReportMetadata {
+            generated_at: "2025-06-01 12:00:00".to_string(),
+            data_version: "run #1".to_string(),
+            epochs: None,
+            period: "All time".to_string(),
+            pricing_policy: String::new(),
+            tool_version: "0.1.0".to_string(),
+            config_hash: "none".to_string(),
+        };
+
+        let copy = hook(&["sh", "-c", "cp \"$BP_REPORT_MANIFEST\" copied.json"], true);
+        run(&[copy], &dir, &metadata).await.unwrap();
+        let manifest: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.join("copied.json")).unwrap()).unwrap();
+        assert_eq!(manifest["files"][0]["path"], constants::SUMMARY_FILENAME);
+        assert_eq!(manifest["files"][0]["rows"], 2);
+
+        // Optional hooks only warn; required ones fail the run
+        run(&[hook(&["false"], false)], &dir, &metadata).await.unwrap();
+        assert!(run(&[hook(&["false"], true)], &dir, &metadata).await.is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -5,13 +5,18 @@
 //! and those numbers "rewind" to what they were at any point in history as the user scrolls.
 
 use anyhow::Result;
+use report_template::ReportContext;
 use serde::Serialize;
 use std::path::Path;
 
 use crate::constants;
-use crate::prices::get_price;
+use crate::period::ReportPeriod;
+use crate::prices::{PriceSource, get_event_price, get_price_with_source};
+use crate::report_metadata::ReportMetadata;
 use crate::reports::ReportData;
+use crate::revenue_stats;
 use crate::tax_report::{self, TaxReportData, TaxRow};
+use crate::unit_economics;
 
 /// One atomic financial event in the timeline.
 #[derive(Debug, Clone, Serialize)]
@@ -23,6 +28,8 @@ pub struct TimelineEvent {
     pub sublabel: Option<String>,
     pub amount_sol: f64,
     pub amount_usd: f64,
+    /// How `amount_usd` was priced (None for USD-denominated events)
+    pub price_source: Option<PriceSource>,
     /// Running total AFTER this event (chronological order, pre-computed)
     pub cumulative_profit_usd: f64,
     pub cumulative_revenue_usd: f64,
@@ -45,12 +52,14 @@ fn type_order(event_type: &str) -> u8 {
         "leader_fees" => 1,
         "mev" => 2,
         "bam" => 3,
+        "donation" => 3,
         "vote_cost" => 4,
         "doublezero" => 5,
         "expense" => 6,
         "seeding" => 7,
         "withdrawal" => 8,
         "doublezero_payment" => 9,
+        "key_change" => 10,
         // Tax timeline types — matches the CSV sort order:
         // Revenue > Return of Capital > Reimbursement > Expenses
         "tax_revenue" => 0,
@@ -74,7 +83,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
     // ── Commission rewards ─────────────────────────────────────────────────
     for reward in data.rewards {
         let date = reward.date.clone().unwrap_or_else(|| "unknown".to_string());
-        let price = get_price(data.prices, &date);
+        let (price, price_source) = get_price_with_source(data.prices, &date);
         let usd = reward.amount_sol * price;
         events.push(TimelineEvent {
             date,
@@ -84,6 +93,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
             sublabel: Some(format!("Epoch {}", reward.epoch)),
             amount_sol: reward.amount_sol,
             amount_usd: usd,
+            price_source: Some(price_source),
             cumulative_profit_usd: 0.0,
             cumulative_revenue_usd: 0.0,
             cumulative_expenses_usd: 0.0,
@@ -94,7 +104,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
     // ── Leader fees ────────────────────────────────────────────────────────
     for fees in data.leader_fees {
         let date = fees.date.clone().unwrap_or_else(|| "unknown".to_string());
-        let price = get_price(data.prices, &date);
+        let (price, price_source) = get_price_with_source(data.prices, &date);
         let usd = fees.total_fees_sol * price;
         events.push(TimelineEvent {
             date,
@@ -104,6 +114,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
             sublabel: Some(format!("Epoch {} · {} blocks", fees.epoch, fees.blocks_produced)),
             amount_sol: fees.total_fees_sol,
             amount_usd: usd,
+            price_source: Some(price_source),
             cumulative_profit_usd: 0.0,
             cumulative_revenue_usd: 0.0,
             cumulative_expenses_usd: 0.0,
@@ -116,7 +127,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
     if data.mev_claims.is_empty() {
         for transfer in &data.categorized.mev_deposits {
             let date = transfer.date.clone().unwrap_or_else(|| "unknown".to_string());
-            let price = get_price(data.prices, &date);
+            let (price, price_source) = get_price_with_source(data.prices, &date);
             let usd = transfer.amount_sol * price;
             events.push(TimelineEvent {
                 date,
@@ -126,6 +137,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
                 sublabel: None,
                 amount_sol: transfer.amount_sol,
                 amount_usd: usd,
+                price_source: Some(price_source),
                 cumulative_profit_usd: 0.0,
                 cumulative_revenue_usd: 0.0,
                 cumulative_expenses_usd: 0.0,
@@ -135,16 +147,17 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
     } else {
         for claim in data.mev_claims {
             let date = claim.date.clone().unwrap_or_else(|| "unknown".to_string());
-            let price = get_price(data.prices, &date);
+            let (price, price_source) = get_price_with_source(data.prices, &date);
             let usd = claim.amount_sol * price;
             events.push(TimelineEvent {
                 date,
                 epoch: Some(claim.epoch),
                 event_type: "mev",
-                label: "MEV tips (Jito)".to_string(),
+                label: format!("MEV tips ({})", claim.provider_label()),
                 sublabel: Some(format!("Epoch {}", claim.epoch)),
                 amount_sol: claim.amount_sol,
                 amount_usd: usd,
+                price_source: Some(price_source),
                 cumulative_profit_usd: 0.0,
                 cumulative_revenue_usd: 0.0,
                 cumulative_expenses_usd: 0.0,
@@ -153,10 +166,32 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
         }
     }
 
+    // ── Donations (tips to the donation address) ───────────────────────────
+    for transfer in &data.categorized.donations {
+        let date = transfer.date.clone().unwrap_or_else(|| "unknown".to_string());
+        let (price, price_source) = get_price_with_source(data.prices, &date);
+        let usd = transfer.amount_sol * price;
+        events.push(TimelineEvent {
+            date,
+            epoch: None,
+            event_type: "donation",
+            label: "Donation".to_string(),
+            sublabel: Some(transfer.from_label.clone()),
+            amount_sol: transfer.amount_sol,
+            amount_usd: usd,
+            price_source: Some(price_source),
+            cumulative_profit_usd: 0.0,
+            cumulative_revenue_usd: 0.0,
+            cumulative_expenses_usd: 0.0,
+            is_pnl: true,
+        });
+    }
+
     // ── BAM claims ─────────────────────────────────────────────────────────
     for claim in data.bam_claims {
         let date = claim.date.clone().unwrap_or_else(|| "unknown".to_string());
-        let price = get_price(data.prices, &date);
+        let (price, price_source) =
+            get_event_price(data.prices, data.hourly_prices, &date, claim.claimed_at_timestamp());
         let usd = claim.amount_sol_equivalent * price;
         events.push(TimelineEvent {
             date,
@@ -166,6 +201,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
             sublabel: Some(format!("Epoch {} · jitoSOL reward", claim.epoch)),
             amount_sol: claim.amount_sol_equivalent,
             amount_usd: usd,
+            price_source: Some(price_source),
             cumulative_profit_usd: 0.0,
             cumulative_revenue_usd: 0.0,
             cumulative_expenses_usd: 0.0,
@@ -176,7 +212,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
     // ── Vote costs ─────────────────────────────────────────────────────────
     for cost in data.vote_costs {
         let date = cost.date.clone().unwrap_or_else(|| "unknown".to_string());
-        let price = get_price(data.prices, &date);
+        let (price, price_source) = get_price_with_source(data.prices, &date);
         let gross_usd = cost.total_fee_sol * price;
 
         let parsed = chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")
@@ -199,6 +235,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
             sublabel,
             amount_sol: -net_sol,
             amount_usd: -net_usd,
+            price_source: Some(price_source),
             cumulative_profit_usd: 0.0,
             cumulative_revenue_usd: 0.0,
             cumulative_expenses_usd: 0.0,
@@ -209,7 +246,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
     // ── DoubleZero fees ────────────────────────────────────────────────────
     for fee in data.doublezero_fees {
         let date = fee.date.clone().unwrap_or_else(|| "unknown".to_string());
-        let price = get_price(data.prices, &date);
+        let (price, price_source) = get_price_with_source(data.prices, &date);
         let usd = fee.liability_sol * price;
         events.push(TimelineEvent {
             date,
@@ -219,6 +256,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
             sublabel: Some(format!("Epoch {}", fee.epoch)),
             amount_sol: -fee.liability_sol,
             amount_usd: -usd,
+            price_source: Some(price_source),
             cumulative_profit_usd: 0.0,
             cumulative_revenue_usd: 0.0,
             cumulative_expenses_usd: 0.0,
@@ -236,6 +274,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
             sublabel: Some(expense.description.clone()),
             amount_sol: 0.0,
             amount_usd: -expense.amount_usd,
+            price_source: None,
             cumulative_profit_usd: 0.0,
             cumulative_revenue_usd: 0.0,
             cumulative_expenses_usd: 0.0,
@@ -246,7 +285,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
     // ── Balance-sheet: seeding ─────────────────────────────────────────────
     for transfer in &data.categorized.seeding {
         let date = transfer.date.clone().unwrap_or_else(|| "unknown".to_string());
-        let price = get_price(data.prices, &date);
+        let (price, price_source) = get_price_with_source(data.prices, &date);
         let usd = transfer.amount_sol * price;
         events.push(TimelineEvent {
             date,
@@ -256,6 +295,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
             sublabel: Some(format!("{} → {}", transfer.from_label, transfer.to_label)),
             amount_sol: transfer.amount_sol,
             amount_usd: usd,
+            price_source: Some(price_source),
             cumulative_profit_usd: 0.0,
             cumulative_revenue_usd: 0.0,
             cumulative_expenses_usd: 0.0,
@@ -266,16 +306,26 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
     // ── Balance-sheet: withdrawals ─────────────────────────────────────────
     for transfer in &data.categorized.withdrawals {
         let date = transfer.date.clone().unwrap_or_else(|| "unknown".to_string());
-        let price = get_price(data.prices, &date);
+        let (price, price_source) = get_event_price(data.prices, data.hourly_prices, &date, transfer.timestamp);
         let usd = transfer.amount_sol * price;
         events.push(TimelineEvent {
             date,
             epoch: None,
             event_type: "withdrawal",
             label: "Withdrawal".to_string(),
-            sublabel: Some(format!("→ {}", transfer.to_label)),
+            sublabel: Some(match data.categorized.split_for(&transfer.signature) {
+                Some(split) => format!(
+                    "→ {} ({:.0}% owner draw, {:.0}% {} expense)",
+                    transfer.to_label,
+                    100.0 - split.business_pct,
+                    split.business_pct,
+                    split.category
+                ),
+                None => format!("→ {}", transfer.to_label),
+            }),
             amount_sol: transfer.amount_sol,
             amount_usd: usd,
+            price_source: Some(price_source),
             cumulative_profit_usd: 0.0,
             cumulative_revenue_usd: 0.0,
             cumulative_expenses_usd: 0.0,
@@ -286,7 +336,7 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
     // ── Balance-sheet: DoubleZero prepayments ─────────────────────────────
     for transfer in &data.categorized.doublezero_payments {
         let date = transfer.date.clone().unwrap_or_else(|| "unknown".to_string());
-        let price = get_price(data.prices, &date);
+        let (price, price_source) = get_price_with_source(data.prices, &date);
         let usd = transfer.amount_sol * price;
         events.push(TimelineEvent {
             date,
@@ -296,6 +346,29 @@ pub fn build_timeline(data: &ReportData) -> Vec<TimelineEvent> {
             sublabel: Some("Deposit to DoubleZero PDA".to_string()),
             amount_sol: transfer.amount_sol,
             amount_usd: usd,
+            price_source: Some(price_source),
+            cumulative_profit_usd: 0.0,
+            cumulative_revenue_usd: 0.0,
+            cumulative_expenses_usd: 0.0,
+            is_pnl: false,
+        });
+    }
+
+    // ── Markers: key rotations and vote account migrations ─────────────────
+    for change in data.config.key_changes() {
+        let short = |key: &solana_sdk::pubkey::Pubkey| {
+            let key = key.to_string();
+            format!("{}…{}", &key[..4], &key[key.len() - 4..])
+        };
+        events.push(TimelineEvent {
+            date: change.date.clone(),
+            epoch: None,
+            event_type: "key_change",
+            label: change.title().to_string(),
+            sublabel: Some(format!("{} → {}", short(&change.from), short(&change.to))),
+            amount_sol: 0.0,
+            amount_usd: 0.0,
+            price_source: None,
             cumulative_profit_usd: 0.0,
             cumulative_revenue_usd: 0.0,
             cumulative_expenses_usd: 0.0,
@@ -424,6 +497,7 @@ pub fn build_tax_timeline(data: &ReportData) -> Vec<TimelineEvent> {
         vote_costs: data.vote_costs,
         expenses: data.expenses,
         prices: data.prices,
+        hourly_prices: data.hourly_prices,
     };
     let (rows, _skipped_unknown_dates) = tax_report::build_tax_rows(&tax_data, None);
 
@@ -441,6 +515,7 @@ pub fn build_tax_timeline(data: &ReportData) -> Vec<TimelineEvent> {
             sublabel,
             amount_sol,
             amount_usd,
+            price_source: row.price_source,
             cumulative_profit_usd: 0.0,
             cumulative_revenue_usd: 0.0,
             cumulative_expenses_usd: 0.0,
@@ -475,36 +550,36 @@ pub fn build_tax_timeline(data: &ReportData) -> Vec<TimelineEvent> {
     events
 }
 
+/// The report template, embedded at compile time (`[theme] template_dir` can replace it)
+const TEMPLATE: &str = include_str!("html_report_template.html");
+
 /// Write a self-contained `report.html` to `output_dir`.
-pub fn generate_html_report(output_dir: &Path, data: &ReportData, year_filter: Option<i32>) -> Result<()> {
+pub fn generate_html_report(
+    output_dir: &Path,
+    data: &ReportData,
+    period: Option<&ReportPeriod>,
+    metadata: &ReportMetadata,
+) -> Result<()> {
     let timeline = build_timeline(data);
     let tax_timeline = build_tax_timeline(data);
-    let timeline_json = serde_json::to_string(&timeline)?;
-    let tax_timeline_json = serde_json::to_string(&tax_timeline)?;
-
-    // Prevent "</script>" in string values (vendor names, descriptions, labels)
-    // from closing the inline <script> block prematurely.
-    // Escaping the forward slash (\/) is valid JSON and parsed transparently.
-    let timeline_json = timeline_json.replace("</", r"<\/");
-    let tax_timeline_json = tax_timeline_json.replace("</", r"<\/");
-
-    let html = build_html(&timeline_json, &tax_timeline_json, year_filter);
+    let kpis = unit_economics::compute(data, |d| period.is_none_or(|p| p.contains(d)));
+    let stats = revenue_stats::compute(data, |d| period.is_none_or(|p| p.contains(d)));
+
+    let theme = data.config.theme.load()?;
+    let metadata = metadata.fields();
+    let context = ReportContext {
+        timeline: &timeline,
+        tax_timeline: &tax_timeline,
+        history: None,
+        tax_year: period.map(|p| p.label.as_str()),
+        timezone: Some(data.config.accounting_timezone.name()),
+        unit_economics: Some(&kpis),
+        revenue_stats: (!stats.is_empty()).then_some(&stats),
+        metadata: Some(&metadata),
+    };
+    let html = report_template::render(TEMPLATE, &theme, &context)?;
     let path = output_dir.join("report.html");
     std::fs::write(&path, html)?;
     println!("  Generated: {}", path.display());
     Ok(())
 }
-
-fn build_html(timeline_json: &str, tax_timeline_json: &str, year_filter: Option<i32>) -> String {
-    // The HTML template is a raw string literal embedded at compile time.
-    // The JSON data is injected at a single marker so the template stays readable.
-    let template = include_str!("html_report_template.html");
-    let tax_year_js = match year_filter {
-        Some(y) => y.to_string(),
-        None => "null".to_string(),
-    };
-    template
-        .replacen("__TIMELINE_JSON__", timeline_json, 1)
-        .replacen("__TAX_TIMELINE_JSON__", tax_timeline_json, 1)
-        .replacen("__TAX_YEAR__", &tax_year_js, 1)
-}
@@ -0,0 +1,159 @@
+//! Income lots: every reward, MEV, BAM and donation receipt recorded at fair market value on receipt
+//!
+//! Enabled with `[reporting] income_lots = true`. Each lot is a receipt-basis income event and
+//! the starting cost basis for later disposals; the default reports stay withdrawal-based.
+//! Lots are valued exactly like the income ledger rows (daily price, hourly for BAM claims).
+
+use serde::Serialize;
+
+use crate::prices::get_event_price;
+use crate::reports::ReportData;
+
+/// Lot sources, as stored in `income_lots.source`
+pub const SOURCE_COMMISSION: &str = "commission";
+pub const SOURCE_LEADER_FEES: &str = "leader_fees";
+pub const SOURCE_MEV: &str = "mev";
+pub const SOURCE_BAM: &str = "bam";
+pub const SOURCE_DONATION: &str = "donation";
+
+/// An income receipt valued at fair market value when received
+#[derive(Debug, Clone, Serialize)]
+pub struct IncomeLot {
+    /// One of the `SOURCE_*` constants
+    pub source: String,
+    /// `epoch-N` for per-epoch income, otherwise the transaction signature
+    pub reference: String,
+    pub epoch: Option<u64>,
+    pub date: String,
+    pub timestamp: Option<i64>,
+    /// Asset received: "SOL" or "jitoSOL"
+    pub asset: String,
+    /// Amount in units of `asset`
+    pub amount: f64,
+    /// SOL-equivalent amount (jitoSOL converted at the claim's rate)
+    pub amount_sol: f64,
+    /// USD per SOL at receipt
+    pub price_usd: f64,
+    /// hourly/exact/interpolated/fallback
+    pub price_source: String,
+    /// Fair market value at receipt (amount_sol * price_usd); the lot's cost basis
+    pub fmv_usd: f64,
+}
+
+/// Build one lot per income receipt. Receipts without a date can't be valued and are skipped;
+/// the second value is how many were skipped.
+pub fn build_income_lots(data: &ReportData) -> (Vec<IncomeLot>, usize) {
+    let mut lots = Vec::new();
+    let mut skipped = 0;
+
+    let mut push = |source: &str,
+                    reference: String,
+                    epoch: Option<u64>,
+                    date: Option<&str>,
+                    timestamp: Option<i64>,
+                    asset: &str,
+                    amount: f64,
+                    amount_sol: f64| {
+        let Some(date) = date else {
+            skipped += 1;
+            return;
+        };
+        let (price, price_source) = get_event_price(data.prices, data.hourly_prices, date, timestamp);
+        lots.push(IncomeLot {
+            source: source.to_string(),
+            reference,
+            epoch,
+            date: date.to_string(),
+            timestamp,
+            asset: asset.to_string(),
+            amount,
+            amount_sol,
+            price_usd: price,
+            price_source: price_source.as_str().to_string(),
+            fmv_usd: amount_sol * price,
+        });
+    };
+
+    for reward in data.rewards {
+        push(
+            SOURCE_COMMISSION,
+            format!("epoch-{}", reward.epoch),
+            Some(reward.epoch),
+            reward.date.as_deref(),
+            None,
+            "SOL",
+            reward.amount_sol,
+            reward.amount_sol,
+        );
+    }
+
+    for fees in data.leader_fees {
+        push(
+            SOURCE_LEADER_FEES,
+            format!("epoch-{}", fees.epoch),
+            Some(fees.epoch),
+            fees.date.as_deref(),
+            None,
+            "SOL",
+            fees.total_fees_sol,
+            fees.total_fees_sol,
+        );
+    }
+
+    // Same MEV source of truth as the income ledger: provider claims, or detected deposits
+    // when there are no claims at all
+    for claim in data.mev_claims {
+        push(
+            SOURCE_MEV,
+            claim.reference(),
+            Some(claim.epoch),
+            claim.date.as_deref(),
+            None,
+            "SOL",
+            claim.amount_sol,
+            claim.amount_sol,
+        );
+    }
+    if data.mev_claims.is_empty() {
+        for transfer in &data.categorized.mev_deposits {
+            push(
+                SOURCE_MEV,
+                transfer.signature.clone(),
+                None,
+                transfer.date.as_deref(),
+                None,
+                "SOL",
+                transfer.amount_sol,
+                transfer.amount_sol,
+            );
+        }
+    }
+
+    for claim in data.bam_claims {
+        push(
+            SOURCE_BAM,
+            claim.tx_signature.clone(),
+            Some(claim.epoch),
+            claim.date.as_deref(),
+            claim.claimed_at_timestamp(),
+            "jitoSOL",
+            claim.amount_jitosol_lamports as f64 / 1e9,
+            claim.amount_sol_equivalent,
+        );
+    }
+
+    for transfer in &data.categorized.donations {
+        push(
+            SOURCE_DONATION,
+            transfer.signature.clone(),
+            None,
+            transfer.date.as_deref(),
+            transfer.timestamp,
+            "SOL",
+            transfer.amount_sol,
+            transfer.amount_sol,
+        );
+    }
+
+    (lots, skipped)
+}
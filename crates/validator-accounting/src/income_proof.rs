@@ -0,0 +1,509 @@
+//! Lamport-exact income proof (`income-proof` / `verify-income`)
+//!
+//! The income ledger rounds to SOL and USD; an auditor wants to trace every lamport back
+//! to the chain. The proof export lists each income receipt in base units with the
+//! evidence behind it (reward record slot, claim or transfer signature, block time) and
+//! a command that reproduces it against any RPC node. `verify-income` re-checks a
+//! sample of those rows against the configured RPC and reports every mismatch.
+
+use anyhow::Result;
+use csv::Writer;
+use serde::Serialize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+use tokio::time::sleep;
+
+use crate::config::Config;
+use crate::income_lots::{SOURCE_BAM, SOURCE_COMMISSION, SOURCE_DONATION, SOURCE_LEADER_FEES, SOURCE_MEV};
+use crate::reports::ReportData;
+use crate::{constants, failed_fees, jito, transactions};
+
+/// Where a receipt's amount can be checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Evidence {
+    /// `getInflationReward` for the vote account and epoch
+    InflationReward,
+    /// A confirmed transaction crediting one of our accounts
+    Transaction,
+    /// Fees summed over the epoch's produced blocks (not re-checked: one RPC call per block)
+    LeaderBlocks,
+    /// Jito's published per-epoch commission (off-chain API)
+    ProviderApi,
+    /// Claims summed from another provider's tip-distribution account (not re-checked)
+    TipDistribution,
+}
+
+impl Evidence {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::InflationReward => "inflation_reward",
+            Self::Transaction => "transaction",
+            Self::LeaderBlocks => "leader_blocks",
+            Self::ProviderApi => "provider_api",
+            Self::TipDistribution => "tip_distribution",
+        }
+    }
+
+    /// Whether `verify-income` can re-check it against RPC
+    fn is_rpc_checkable(self) -> bool {
+        matches!(self, Self::InflationReward | Self::Transaction)
+    }
+}
+
+/// One income receipt in base units, with its on-chain evidence
+#[derive(Debug, Clone, Serialize)]
+pub struct IncomeProof {
+    /// One of the `income_lots::SOURCE_*` constants
+    pub source: &'static str,
+    /// `epoch-N` for per-epoch income, otherwise the transaction signature
+    pub reference: String,
+    pub epoch: Option<u64>,
+    /// Date the income ledger books it on
+    pub date: Option<String>,
+    /// "SOL" or "jitoSOL"
+    pub asset: &'static str,
+    /// Amount in base units of `asset` (lamports)
+    pub amount_lamports: u64,
+    /// Reward effective slot, or the transaction's slot
+    pub slot: Option<u64>,
+    pub signature: Option<String>,
+    pub block_time: Option<i64>,
+    /// Account credited (transfers only; the balance change checked by `verify-income`)
+    pub account: Option<String>,
+    pub evidence: Evidence,
+}
+
+impl IncomeProof {
+    /// Shell command reproducing the evidence (`$RPC_URL` left for the auditor to set);
+    /// empty for tip-distribution claims, which take a scan of the provider's account
+    pub fn verify_command(&self, config: &Config) -> String {
+        match self.evidence {
+            Evidence::InflationReward => format!(
+                "curl -s $RPC_URL -X POST -H 'Content-Type: application/json' -d \
+                 '{{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"getInflationReward\",\"params\":[[\"{}\"],{{\"epoch\":{}}}]}}'",
+                config.vote_account,
+                self.epoch.unwrap_or_default()
+            ),
+            Evidence::Transaction => format!(
+                "solana confirm -v {} --url $RPC_URL",
+                self.signature.as_deref().unwrap_or_default()
+            ),
+            Evidence::LeaderBlocks => format!(
+                "solana block-production --epoch {} --url $RPC_URL",
+                self.epoch.unwrap_or_default()
+            ),
+            Evidence::ProviderApi => format!(
+                "curl -s {}/validators/{}",
+                constants::JITO_API_BASE,
+                config.vote_account
+            ),
+            Evidence::TipDistribution => String::new(),
+        }
+    }
+}
+
+/// Every income receipt the income ledger counts, in the same order and from the same
+/// sources as the income lots (MEV deposits only when there are no provider claims)
+pub fn build_proofs(data: &ReportData) -> Vec<IncomeProof> {
+    let mut proofs = Vec::new();
+
+    for reward in data.rewards {
+        proofs.push(IncomeProof {
+            source: SOURCE_COMMISSION,
+            reference: format!("epoch-{}", reward.epoch),
+            epoch: Some(reward.epoch),
+            date: reward.date.clone(),
+            asset: "SOL",
+            amount_lamports: reward.amount_lamports,
+            slot: Some(reward.effective_slot),
+            signature: None,
+            block_time: None,
+            account: None,
+            evidence: Evidence::InflationReward,
+        });
+    }
+
+    for fees in data.leader_fees {
+        proofs.push(IncomeProof {
+            source: SOURCE_LEADER_FEES,
+            reference: format!("epoch-{}", fees.epoch),
+            epoch: Some(fees.epoch),
+            date: fees.date.clone(),
+            asset: "SOL",
+            amount_lamports: fees.total_fees_lamports,
+            slot: None,
+            signature: None,
+            block_time: None,
+            account: None,
+            evidence: Evidence::LeaderBlocks,
+        });
+    }
+
+    for claim in data.mev_claims {
+        proofs.push(IncomeProof {
+            source: SOURCE_MEV,
+            reference: claim.reference(),
+            epoch: Some(claim.epoch),
+            date: claim.date.clone(),
+            asset: "SOL",
+            amount_lamports: claim.commission_lamports,
+            slot: None,
+            signature: None,
+            block_time: None,
+            account: None,
+            evidence: if claim.provider == jito::PROVIDER {
+                Evidence::ProviderApi
+            } else {
+                Evidence::TipDistribution
+            },
+        });
+    }
+    if data.mev_claims.is_empty() {
+        for transfer in &data.categorized.mev_deposits {
+            proofs.push(transfer_proof(SOURCE_MEV, transfer));
+        }
+    }
+
+    for claim in data.bam_claims {
+        proofs.push(IncomeProof {
+            source: SOURCE_BAM,
+            reference: claim.tx_signature.clone(),
+            epoch: Some(claim.epoch),
+            date: claim.date.clone(),
+            asset: "jitoSOL",
+            amount_lamports: claim.amount_jitosol_lamports,
+            slot: None,
+            signature: Some(claim.tx_signature.clone()),
+            block_time: claim.claimed_at_timestamp(),
+            account: None,
+            evidence: Evidence::Transaction,
+        });
+    }
+
+    for transfer in &data.categorized.donations {
+        proofs.push(transfer_proof(SOURCE_DONATION, transfer));
+    }
+
+    proofs
+}
+
+fn transfer_proof(source: &'static str, transfer: &transactions::SolTransfer) -> IncomeProof {
+    IncomeProof {
+        source,
+        reference: transfer.signature.clone(),
+        epoch: None,
+        date: transfer.date.clone(),
+        asset: "SOL",
+        amount_lamports: transfer.amount_lamports,
+        slot: Some(transfer.slot),
+        signature: Some(transfer.signature.clone()),
+        block_time: transfer.timestamp,
+        account: Some(transfer.to.to_string()),
+        evidence: Evidence::Transaction,
+    }
+}
+
+/// Write the proof CSV with a lamport total per source at the end
+pub fn write_proof_csv(output_dir: &Path, proofs: &[IncomeProof], config: &Config) -> Result<()> {
+    let path = output_dir.join(constants::INCOME_PROOF_FILENAME);
+    let mut wtr = Writer::from_path(&path)?;
+    wtr.write_record([
+        "Date",
+        "Source",
+        "Reference",
+        "Epoch",
+        "Asset",
+        "Amount_Lamports",
+        "Slot",
+        "Tx_Signature",
+        "Block_Time (UTC)",
+        "Credited_Account",
+        "Evidence",
+        "Verify_Command",
+    ])?;
+
+    let mut totals: Vec<(&str, &str, u64)> = Vec::new();
+    for proof in proofs {
+        wtr.write_record([
+            proof.date.as_deref().unwrap_or(""),
+            proof.source,
+            proof.reference.as_str(),
+            &proof.epoch.map(|e| e.to_string()).unwrap_or_default(),
+            proof.asset,
+            &proof.amount_lamports.to_string(),
+            &proof.slot.map(|s| s.to_string()).unwrap_or_default(),
+            proof.signature.as_deref().unwrap_or(""),
+            &proof.block_time.map(format_time).unwrap_or_default(),
+            proof.account.as_deref().unwrap_or(""),
+            proof.evidence.as_str(),
+            &proof.verify_command(config),
+        ])?;
+        match totals
+            .iter_mut()
+            .find(|(source, asset, _)| *source == proof.source && *asset == proof.asset)
+        {
+            Some((_, _, total)) => *total += proof.amount_lamports,
+            None => totals.push((proof.source, proof.asset, proof.amount_lamports)),
+        }
+    }
+
+    for (source, asset, total) in totals {
+        wtr.write_record([
+            "",
+            &format!("TOTAL {}", source),
+            "",
+            "",
+            asset,
+            &total.to_string(),
+            "",
+            "",
+            "",
+            "",
+            "",
+            "",
+        ])?;
+    }
+    wtr.flush()?;
+    println!("  Generated: {}", path.display());
+    Ok(())
+}
+
+fn format_time(timestamp: i64) -> String {
+    chrono::DateTime::<chrono::Utc>::from_timestamp(timestamp, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+/// Up to `size` RPC-checkable proofs spread evenly over the list (all of them when `size` is 0)
+pub fn sample(proofs: &[IncomeProof], size: usize) -> Vec<&IncomeProof> {
+    let checkable: Vec<&IncomeProof> = proofs.iter().filter(|p| p.evidence.is_rpc_checkable()).collect();
+    if size == 0 || checkable.len() <= size {
+        return checkable;
+    }
+    (0..size).map(|i| checkable[i * checkable.len() / size]).collect()
+}
+
+/// Result of re-checking one proof row
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", content = "detail", rename_all = "snake_case")]
+pub enum CheckOutcome {
+    Verified,
+    /// The chain disagrees with the cached row (what differs)
+    Mismatch(String),
+    /// No record to check against (no reward for the epoch, or an unparseable signature)
+    NotFound,
+    /// The check itself failed (RPC error)
+    Error(String),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProofCheck {
+    pub source: &'static str,
+    pub reference: String,
+    #[serde(flatten)]
+    pub outcome: CheckOutcome,
+}
+
+/// Re-check each sampled proof against RPC
+///
+/// Transfers from the same transaction to the same account are checked together against
+/// that account's balance change (plus the fee, when it paid it).
+pub async fn verify(client: &RpcClient, config: &Config, proofs: &[&IncomeProof]) -> Vec<ProofCheck> {
+    let mut expected_credit: HashMap<(&str, &str), u64> = HashMap::new();
+    for proof in proofs {
+        if let (Some(signature), Some(account)) = (&proof.signature, &proof.account) {
+            *expected_credit
+                .entry((signature.as_str(), account.as_str()))
+                .or_default() += proof.amount_lamports;
+        }
+    }
+
+    let mut checks = Vec::with_capacity(proofs.len());
+    for proof in proofs {
+        sleep(Duration::from_millis(constants::EPOCH_REWARD_DELAY_MS)).await;
+        let outcome = match proof.evidence {
+            Evidence::InflationReward => check_reward(client, config, proof),
+            _ => check_transaction(client, proof, &expected_credit),
+        }
+        .unwrap_or_else(|e| CheckOutcome::Error(format!("{:#}", e)));
+        checks.push(ProofCheck {
+            source: proof.source,
+            reference: proof.reference.clone(),
+            outcome,
+        });
+    }
+    checks
+}
+
+fn check_reward(client: &RpcClient, config: &Config, proof: &IncomeProof) -> Result<CheckOutcome> {
+    let Some(epoch) = proof.epoch else {
+        return Ok(CheckOutcome::NotFound);
+    };
+    let result = client.get_inflation_reward(&[config.vote_account], Some(epoch))?;
+    let Some(Some(reward)) = result.first() else {
+        return Ok(CheckOutcome::NotFound);
+    };
+
+    let mut differences = Vec::new();
+    if reward.amount != proof.amount_lamports {
+        differences.push(format!(
+            "amount {} on chain vs {} cached",
+            reward.amount, proof.amount_lamports
+        ));
+    }
+    if Some(reward.effective_slot) != proof.slot {
+        differences.push(format!(
+            "effective slot {} on chain vs {:?} cached",
+            reward.effective_slot, proof.slot
+        ));
+    }
+    Ok(outcome(differences))
+}
+
+fn check_transaction(
+    client: &RpcClient,
+    proof: &IncomeProof,
+    expected_credit: &HashMap<(&str, &str), u64>,
+) -> Result<CheckOutcome> {
+    let Some(signature) = proof.signature.as_deref() else {
+        return Ok(CheckOutcome::NotFound);
+    };
+    let Some(tx) = failed_fees::fetch_transaction(client, signature)? else {
+        return Ok(CheckOutcome::NotFound);
+    };
+
+    let mut differences = Vec::new();
+    if let Some(slot) = proof.slot
+        && slot != tx.slot
+    {
+        differences.push(format!("slot {} on chain vs {} cached", tx.slot, slot));
+    }
+    if let Some(block_time) = proof.block_time
+        && tx.block_time.is_some_and(|t| t != block_time)
+    {
+        differences.push(format!(
+            "block time {} on chain vs {} cached",
+            tx.block_time.map(format_time).unwrap_or_default(),
+            format_time(block_time)
+        ));
+    }
+    let Some(meta) = tx.transaction.meta.as_ref() else {
+        differences.push("no transaction status".to_string());
+        return Ok(outcome(differences));
+    };
+    if meta.err.is_some() {
+        differences.push("transaction failed on chain".to_string());
+    }
+
+    if let Some(account) = proof.account.as_deref()
+        && let Ok(pubkey) = Pubkey::from_str(account)
+        && let Some(keys) = transactions::extract_account_keys(&tx.transaction.transaction, false)
+    {
+        let expected = expected_credit
+            .get(&(signature, account))
+            .copied()
+            .unwrap_or(proof.amount_lamports);
+        let credit = keys.iter().position(|k| *k == pubkey).and_then(|i| {
+            let delta = *meta.post_balances.get(i)? as i128 - *meta.pre_balances.get(i)? as i128;
+            // The fee came out of the same balance when this account paid it
+            Some(if i == 0 { delta + meta.fee as i128 } else { delta })
+        });
+        match credit {
+            Some(credit) if credit == expected as i128 => {}
+            Some(credit) => differences.push(format!(
+                "{} credited {} lamports on chain vs {} cached",
+                account, credit, expected
+            )),
+            None => differences.push(format!("{} is not in the transaction", account)),
+        }
+    }
+
+    Ok(outcome(differences))
+}
+
+fn outcome(differences: Vec<String>) -> CheckOutcome {
+    if differences.is_empty() {
+        CheckOutcome::Verified
+    } else {
+        CheckOutcome::Mismatch(differences.join("; "))
+    }
+}
+
+/// Print each check, then the counts
+pub fn print_checks(checks: &[ProofCheck], total_proofs: usize) {
+    println!("{:<12} {:<20} {:<10}  Detail", "Source", "Reference", "Status");
+    println!("{}", "-".repeat(80));
+    for check in checks {
+        let (status, detail) = match &check.outcome {
+            CheckOutcome::Verified => ("ok", String::new()),
+            CheckOutcome::Mismatch(detail) => ("MISMATCH", detail.clone()),
+            CheckOutcome::NotFound => ("not found", "nothing on chain to compare with".to_string()),
+            CheckOutcome::Error(detail) => ("error", detail.clone()),
+        };
+        let reference: String = check.reference.chars().take(20).collect();
+        println!("{:<12} {:<20} {:<10}  {}", check.source, reference, status, detail);
+    }
+    println!("{}", "-".repeat(80));
+
+    let count = |f: fn(&CheckOutcome) -> bool| checks.iter().filter(|c| f(&c.outcome)).count();
+    println!(
+        "Checked {} of {} income rows: {} verified, {} mismatched, {} not found, {} errors",
+        checks.len(),
+        total_proofs,
+        count(|o| *o == CheckOutcome::Verified),
+        count(|o| matches!(o, CheckOutcome::Mismatch(_))),
+        count(|o| *o == CheckOutcome::NotFound),
+        count(|o| matches!(o, CheckOutcome::Error(_))),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proof(evidence: Evidence, reference: &str) -> IncomeProof {
+        IncomeProof {
+            source: SOURCE_DONATION,
+            reference: reference.to_string(),
+            epoch: None,
+            date: None,
+            asset: "SOL",
+            amount_lamports: 1,
+            slot: None,
+            signature: None,
+            block_time: None,
+            account: None,
+            evidence,
+        }
+    }
+
+    #[test]
+    fn test_sample_spreads_over_checkable_rows() {
+        let mut proofs: Vec<IncomeProof> = (0..10)
+            .map(|i| proof(Evidence::Transaction, &format!("sig{}", i)))
+            .collect();
+        proofs.push(proof(Evidence::LeaderBlocks, "epoch-1"));
+        proofs.push(proof(Evidence::ProviderApi, "epoch-2"));
+
+        let all = sample(&proofs, 0);
+        assert_eq!(all.len(), 10);
+
+        let picked: Vec<&str> = sample(&proofs, 4).iter().map(|p| p.reference.as_str()).collect();
+        assert_eq!(picked, vec!["sig0", "sig2", "sig5", "sig7"]);
+        assert_eq!(sample(&proofs, 50).len(), 10);
+    }
+
+    #[test]
+    fn test_outcome_joins_differences() {
+        assert_eq!(outcome(Vec::new()), CheckOutcome::Verified);
+        assert_eq!(
+            outcome(vec!["a".to_string(), "b".to_string()]),
+            CheckOutcome::Mismatch("a; b".to_string())
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! Cross-process ingestion lock
+//!
+//! Commands that rewrite the cache hold the `ingestion_lock` row while they run, so a
+//! manual CLI run and bp-web's scheduler never sync into the same tables at once. The
+//! lock is a lease: a background task renews it while the holder is alive, and a holder
+//! that crashes without releasing it is taken over once the lease runs out.
+
+use anyhow::Result;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+use crate::cache::Cache;
+use crate::constants;
+
+/// A held ingestion lock. Call `release` when done; dropping it only stops the lease
+/// renewal, leaving the lease to expire.
+pub struct IngestionLock {
+    cache: Cache,
+    holder: String,
+    renewer: JoinHandle<()>,
+}
+
+impl IngestionLock {
+    /// Take the lock, waiting up to `wait_secs` for another holder to finish.
+    ///
+    /// Returns None (after explaining who holds it) if the lock could not be taken.
+    pub async fn acquire(cache: &Cache, holder: &str, wait_secs: u64) -> Result<Option<Self>> {
+        let deadline = std::time::Instant::now() + Duration::from_secs(wait_secs);
+        let mut announced = false;
+
+        loop {
+            if cache.try_acquire_ingestion_lock(holder).await? {
+                return Ok(Some(Self {
+                    cache: cache.clone(),
+                    holder: holder.to_string(),
+                    renewer: spawn_renewer(cache.clone(), holder.to_string()),
+                }));
+            }
+
+            let current = cache.get_ingestion_lock_holder().await?;
+            let (other, since) = current.unwrap_or_else(|| ("unknown".to_string(), "unknown".to_string()));
+
+            if std::time::Instant::now() >= deadline {
+                eprintln!(
+                    "Another ingestion is already running ({}, started {} UTC); skipping.",
+                    other, since
+                );
+                eprintln!("Use --lock-wait <SECONDS> to wait for it to finish instead.");
+                return Ok(None);
+            }
+
+            if !announced {
+                println!(
+                    "Waiting up to {}s for ingestion lock held by {} (since {} UTC)...",
+                    wait_secs, other, since
+                );
+                announced = true;
+            }
+            tokio::time::sleep(Duration::from_secs(constants::INGESTION_LOCK_POLL_SECS)).await;
+        }
+    }
+
+    /// Stop renewing and give the lock up
+    pub async fn release(self) {
+        self.renewer.abort();
+        if let Err(e) = self.cache.release_ingestion_lock(&self.holder).await {
+            eprintln!("Warning: Failed to release ingestion lock: {}", e);
+        }
+    }
+}
+
+impl Drop for IngestionLock {
+    fn drop(&mut self) {
+        self.renewer.abort();
+    }
+}
+
+/// Extend the lease every `INGESTION_LOCK_RENEW_MINUTES` for as long as the lock is held
+fn spawn_renewer(cache: Cache, holder: String) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let period = Duration::from_secs(constants::INGESTION_LOCK_RENEW_MINUTES * 60);
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+        loop {
+            interval.tick().await;
+            match cache.renew_ingestion_lock(&holder).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    eprintln!("Warning: ingestion lock lease expired and was taken over; stopped renewing");
+                    return;
+                }
+                Err(e) => eprintln!("Warning: Failed to renew ingestion lock: {}", e),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_release_lets_the_next_holder_in() {
+        let dir = std::env::temp_dir().join(format!("va-ingestion-lock-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let cache = Cache::open(&dir.join("cache.sqlite")).await.unwrap();
+        let lock = IngestionLock::acquire(&cache, "cli", 0).await.unwrap().unwrap();
+        assert!(IngestionLock::acquire(&cache, "scheduler", 0).await.unwrap().is_none());
+        assert!(cache.renew_ingestion_lock("cli").await.unwrap());
+        assert!(!cache.renew_ingestion_lock("scheduler").await.unwrap());
+
+        lock.release().await;
+        let next = IngestionLock::acquire(&cache, "scheduler", 0).await.unwrap();
+        assert!(next.is_some());
+        next.unwrap().release().await;
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -5,24 +5,50 @@
 
 use anyhow::Result;
 use serde::Deserialize;
-use std::time::Duration;
-use tokio::time::sleep;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::BTreeMap;
 
 use crate::config::Config;
 use crate::constants;
+use crate::key_rotation::KeyRole;
+use crate::sources::{self, RetryPolicy};
 use crate::transactions::epoch_to_date;
 
-/// MEV reward for a single epoch
+/// Provider name for claims from the Jito API
+pub const PROVIDER: &str = "jito";
+
+/// MEV reward for a single epoch from one provider
 #[derive(Debug, Clone)]
 pub struct MevClaim {
     pub epoch: u64,
+    /// Tip source (`jito`, or the name of a `[[mev.tip_distribution]]` entry)
+    pub provider: String,
     pub total_tips_lamports: u64,
-    #[allow(dead_code)]
     pub commission_lamports: u64, // Validator's share (from API mev_commission_bps)
-    pub amount_sol: f64, // Commission in SOL
+    pub amount_sol: f64,          // Commission in SOL
     pub date: Option<String>,
 }
 
+impl MevClaim {
+    /// Ledger/income-lot reference: `epoch-N` for Jito (as before providers), `epoch-N-<provider>` otherwise
+    pub fn reference(&self) -> String {
+        if self.provider == PROVIDER {
+            format!("epoch-{}", self.epoch)
+        } else {
+            format!("epoch-{}-{}", self.epoch, self.provider)
+        }
+    }
+
+    /// Provider name for display
+    pub fn provider_label(&self) -> &str {
+        if self.provider == PROVIDER {
+            "Jito"
+        } else {
+            &self.provider
+        }
+    }
+}
+
 /// Per-epoch MEV data from Jito API
 #[derive(Debug, Deserialize)]
 struct JitoEpochData {
@@ -35,59 +61,69 @@ struct JitoEpochData {
     #[serde(default)]
     #[allow(dead_code)]
     priority_fee_rewards: u64,
+    /// Active stake delegated to the validator in the epoch (lamports)
+    #[serde(default)]
+    active_stake: Option<u64>,
 }
 
 /// Fetch MEV claims from Jito API with retry logic
 pub async fn fetch_mev_claims(config: &Config) -> Result<Vec<MevClaim>> {
-    let client = reqwest::Client::new();
-
-    let url = format!("{}/validators/{}", constants::JITO_API_BASE, config.vote_account);
-    println!("    Querying Jito API...");
+    process_jito_epochs(fetch_validator_epochs(config).await?)
+}
 
-    // Retry with exponential backoff (longer delays for rate limiting)
-    let max_retries = 4;
-    let mut last_error = None;
-    let mut was_rate_limited = false;
-
-    for attempt in 0..max_retries {
-        if attempt > 0 {
-            // Use longer backoff for rate limiting (30s base) vs normal errors (2s base)
-            let base_delay = if was_rate_limited { 30 } else { 2 };
-            let delay = Duration::from_secs(base_delay * 2u64.pow(attempt as u32 - 1));
-            println!(
-                "    Retry {}/{} after {:?}{}...",
-                attempt,
-                max_retries - 1,
-                delay,
-                if was_rate_limited { " (rate limited)" } else { "" }
-            );
-            sleep(delay).await;
+/// Fetch active stake per epoch (epoch -> lamports) from the same Jito API endpoint
+pub async fn fetch_active_stake(config: &Config) -> Result<BTreeMap<u64, u64>> {
+    let mut stake = BTreeMap::new();
+    for e in fetch_validator_epochs(config).await? {
+        if let Some(active_stake) = e.active_stake {
+            *stake.entry(e.epoch).or_insert(0) += active_stake;
         }
+    }
+    Ok(stake)
+}
 
-        match client.get(&url).header("Accept", "application/json").send().await {
-            Ok(response) => {
-                if response.status().is_success() {
-                    // API returns an array of epoch data directly
-                    let epochs: Vec<JitoEpochData> = response.json().await?;
-                    return process_jito_epochs(epochs);
-                } else if response.status().as_u16() == 429 {
-                    // Rate limited - use longer backoff
-                    was_rate_limited = true;
-                    last_error = Some(anyhow::anyhow!("Rate limited (429)"));
-                    continue;
-                } else {
-                    was_rate_limited = false;
-                    last_error = Some(anyhow::anyhow!("Jito API returned status: {}", response.status()));
-                }
-            }
-            Err(e) => {
-                was_rate_limited = false;
-                last_error = Some(anyhow::anyhow!("Request failed: {}", e));
-            }
-        }
+/// Fetch the validator's per-epoch Jito data for every vote account it has used; a
+/// retired vote account only contributes the epochs it was ours
+async fn fetch_validator_epochs(config: &Config) -> Result<Vec<JitoEpochData>> {
+    let mut epochs = Vec::new();
+    for vote_account in config.keys_for(KeyRole::VoteAccount) {
+        let data = fetch_vote_account_epochs(&vote_account).await?;
+        epochs.extend(
+            data.into_iter()
+                .filter(|e| config.vote_accounts_for_epoch(e.epoch).contains(&vote_account)),
+        );
     }
+    Ok(epochs)
+}
 
-    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("Failed after {} retries", max_retries)))
+/// Fetch one vote account's per-epoch Jito data with retry logic
+async fn fetch_vote_account_epochs(vote_account: &Pubkey) -> Result<Vec<JitoEpochData>> {
+    let client = reqwest::Client::new();
+
+    let url = format!("{}/validators/{}", constants::JITO_API_BASE, vote_account);
+    println!("    Querying Jito API...");
+
+    // The Jito API rate limits aggressively, so allow one more attempt than the default
+    let policy = RetryPolicy {
+        max_attempts: 4,
+        ..RetryPolicy::default()
+    };
+    let (client, url) = (&client, &url);
+    sources::request(PROVIDER, policy, move || async move {
+        let response = client
+            .get(url)
+            .header("Accept", "application/json")
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Request failed: {}", e))?;
+        match response.status() {
+            // API returns an array of epoch data directly
+            status if status.is_success() => Ok(response.json().await?),
+            status if status.as_u16() == 429 => anyhow::bail!("Rate limited (429)"),
+            status => anyhow::bail!("Jito API returned status: {}", status),
+        }
+    })
+    .await
 }
 
 /// Process Jito epoch data into MevClaims
@@ -105,6 +141,7 @@ fn process_jito_epochs(epochs: Vec<JitoEpochData>) -> Result<Vec<MevClaim>> {
 
         claims.push(MevClaim {
             epoch: epoch_data.epoch,
+            provider: PROVIDER.to_string(),
             total_tips_lamports: epoch_data.mev_rewards,
             commission_lamports,
             amount_sol,
@@ -120,8 +157,17 @@ fn process_jito_epochs(epochs: Vec<JitoEpochData>) -> Result<Vec<MevClaim>> {
         );
     }
 
-    // Sort by epoch
+    // Sort by epoch, merging an epoch paid to both sides of a vote account migration
     claims.sort_by_key(|a| a.epoch);
+    claims.dedup_by(|later, earlier| {
+        if later.epoch != earlier.epoch {
+            return false;
+        }
+        earlier.total_tips_lamports += later.total_tips_lamports;
+        earlier.commission_lamports += later.commission_lamports;
+        earlier.amount_sol += later.amount_sol;
+        true
+    });
 
     Ok(claims)
 }
@@ -0,0 +1,429 @@
+//! Keeper mode: scheduled withdrawal of vote-account commission (opt-in)
+//!
+//! For operators comfortable keeping the withdraw authority hot: every
+//! `interval_minutes` the keeper checks the vote account, and once the balance above
+//! rent reaches `threshold_sol` it withdraws to the withdraw authority, bounded by a
+//! per-withdrawal cap and a per-day cap (which counts manual withdrawals from the
+//! cache too, and everything this process sent today whether or not it made it into the
+//! cache). Each withdrawal is recorded in `sol_transfers` immediately, so reports
+//! don't wait for the next ingestion, and announced on the notification webhook. The
+//! send and the recording happen under the ingestion lock; while a sync holds it (past
+//! `--lock-wait`), the withdrawal waits for the next check.
+//! `dry_run` (or `--dry-run`) logs and notifies without sending anything. Each check
+//! that succeeds pings `HEARTBEAT_KEEPER_URL` when set.
+
+use anyhow::{Context, Result, bail, ensure};
+use solana_client::rpc_client::RpcClient;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::signer::Signer;
+use solana_sdk::signer::keypair::{Keypair, read_keypair_file};
+use solana_sdk::transaction::Transaction;
+use std::time::Duration;
+
+use crate::cache::Cache;
+use crate::config::{Config, KeeperConfig};
+use crate::ingestion_lock::IngestionLock;
+use crate::transactions::{self, SolTransfer};
+use crate::{constants, heartbeat, notify, rpc, timezone, tx_builder};
+
+/// Attempts at recording a sent withdrawal in the cache before giving up on it
+const RECORD_ATTEMPTS: usize = 3;
+
+/// What the keeper should do on one check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    /// Not enough above rent to bother
+    BelowThreshold,
+    /// Today's cap is used up
+    DailyCapReached,
+    /// Withdraw this many lamports (`capped` when a cap cut the amount)
+    Withdraw { lamports: u64, capped: bool },
+}
+
+/// Keeper limits in lamports
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    pub threshold: u64,
+    pub max_withdrawal: u64,
+    pub max_daily: u64,
+}
+
+impl Limits {
+    pub fn from_config(keeper: &KeeperConfig) -> Result<Self> {
+        let lamports = |name: &str, sol: f64| -> Result<u64> {
+            ensure!(
+                sol.is_finite() && sol > 0.0,
+                "keeper.{} must be a positive SOL amount (got {})",
+                name,
+                sol
+            );
+            Ok((sol * 1e9).round() as u64)
+        };
+        Ok(Self {
+            threshold: lamports("threshold_sol", keeper.threshold_sol)?,
+            max_withdrawal: lamports("max_withdrawal_sol", keeper.max_withdrawal_sol)?,
+            max_daily: lamports("max_daily_sol", keeper.max_daily_sol)?,
+        })
+    }
+}
+
+/// Decide how much to withdraw given what's available above rent and what already left today
+pub fn decide(available: u64, withdrawn_today: u64, limits: &Limits) -> Decision {
+    if available < limits.threshold {
+        return Decision::BelowThreshold;
+    }
+    let daily_left = limits.max_daily.saturating_sub(withdrawn_today);
+    if daily_left == 0 {
+        return Decision::DailyCapReached;
+    }
+    let lamports = available.min(limits.max_withdrawal).min(daily_left);
+    Decision::Withdraw {
+        lamports,
+        capped: lamports < available,
+    }
+}
+
+/// Withdrawals this process has sent today (signature, lamports)
+///
+/// Counted toward the daily cap alongside the cache, so a withdrawal whose recording
+/// failed still uses up its share of the cap.
+#[derive(Debug, Default)]
+pub struct SentToday {
+    date: String,
+    sent: Vec<(String, u64)>,
+}
+
+impl SentToday {
+    fn note(&mut self, date: &str, signature: &str, lamports: u64) {
+        if self.date != date {
+            self.date = date.to_string();
+            self.sent.clear();
+        }
+        self.sent.push((signature.to_string(), lamports));
+    }
+
+    /// Lamports sent on `date` that aren't among `cached` signatures
+    fn uncached(&self, date: &str, cached: &[&str]) -> u64 {
+        if self.date != date {
+            return 0;
+        }
+        self.sent
+            .iter()
+            .filter(|(signature, _)| !cached.contains(&signature.as_str()))
+            .map(|(_, lamports)| lamports)
+            .sum()
+    }
+}
+
+/// Lamports withdrawn from the vote account on `date`: cached transfers plus whatever this
+/// process sent that the cache doesn't have
+async fn withdrawn_on(cache: &Cache, config: &Config, sent: &SentToday, date: &str) -> Result<u64> {
+    let transfers = cache.get_all_transfers().await?;
+    let today: Vec<_> = transfers
+        .iter()
+        .filter(|t| t.from == config.vote_account && t.date.as_deref() == Some(date))
+        .collect();
+    let cached: Vec<&str> = today.iter().map(|t| t.signature.as_str()).collect();
+    let recorded: u64 = today.iter().map(|t| t.amount_lamports).sum();
+    Ok(recorded + sent.uncached(date, &cached))
+}
+
+/// Run the keeper: one check with `once`, otherwise every `interval_minutes` until stopped
+pub async fn run(
+    cache: &Cache,
+    config: &Config,
+    keeper: &KeeperConfig,
+    once: bool,
+    dry_run: bool,
+    lock_wait: u64,
+) -> Result<()> {
+    if !keeper.enabled {
+        bail!("Keeper is disabled. Set `enabled = true` under [keeper] in config.toml to opt in.");
+    }
+    let limits = Limits::from_config(keeper)?;
+    let dry_run = dry_run || keeper.dry_run;
+    let authority = load_authority(keeper, config)?;
+
+    println!(
+        "Keeper: withdrawing above {:.4} SOL (max {:.4}/withdrawal, {:.4}/day) every {} min{}",
+        keeper.threshold_sol,
+        keeper.max_withdrawal_sol,
+        keeper.max_daily_sol,
+        keeper.interval_minutes,
+        if dry_run { " [dry run]" } else { "" }
+    );
+
+    let mut sent = SentToday::default();
+    let mut interval = tokio::time::interval(Duration::from_secs(keeper.interval_minutes.max(1) * 60));
+    loop {
+        interval.tick().await;
+        match check_once(cache, config, &authority, &limits, &mut sent, dry_run, lock_wait).await {
+            Ok(()) => heartbeat::ping(heartbeat::KEEPER).await,
+            Err(e) => {
+                eprintln!("Keeper check failed: {:#}", e);
+                if let Err(e) = notify::send(&format!("Keeper check failed: {:#}", e)).await {
+                    eprintln!("  Warning: notification not sent: {:#}", e);
+                }
+            }
+        }
+        if once {
+            return Ok(());
+        }
+    }
+}
+
+/// Read the withdraw authority keypair and make sure it is the configured one
+fn load_authority(keeper: &KeeperConfig, config: &Config) -> Result<Keypair> {
+    let keypair = read_keypair_file(&keeper.keypair_path)
+        .map_err(|e| anyhow::anyhow!("{}", e))
+        .with_context(|| format!("Failed to read keeper keypair {}", keeper.keypair_path.display()))?;
+    ensure!(
+        keypair.pubkey() == config.withdraw_authority,
+        "Keeper keypair {} is not the configured withdraw authority {}",
+        keypair.pubkey(),
+        config.withdraw_authority
+    );
+    Ok(keypair)
+}
+
+async fn check_once(
+    cache: &Cache,
+    config: &Config,
+    authority: &Keypair,
+    limits: &Limits,
+    sent: &mut SentToday,
+    dry_run: bool,
+    lock_wait: u64,
+) -> Result<()> {
+    let client = rpc::new_rpc_client(&config.rpc_url, CommitmentConfig::confirmed());
+    let balance = client.get_balance(&config.vote_account)?;
+    let rent = client.get_minimum_balance_for_rent_exemption(constants::VOTE_ACCOUNT_SIZE)?;
+    let available = balance.saturating_sub(rent);
+
+    let today = timezone::today().format("%Y-%m-%d").to_string();
+    let withdrawn_today = withdrawn_on(cache, config, sent, &today).await?;
+
+    let (lamports, capped) = match decide(available, withdrawn_today, limits) {
+        Decision::BelowThreshold => {
+            println!("Keeper: {:.4} SOL available, below threshold", available as f64 / 1e9);
+            return Ok(());
+        }
+        Decision::DailyCapReached => {
+            println!(
+                "Keeper: daily cap reached ({:.4} SOL withdrawn today)",
+                withdrawn_today as f64 / 1e9
+            );
+            return Ok(());
+        }
+        Decision::Withdraw { lamports, capped } => (lamports, capped),
+    };
+    let sol = lamports as f64 / 1e9;
+    let cap_note = if capped { " (capped)" } else { "" };
+
+    if dry_run {
+        let message = format!(
+            "Keeper [dry run]: would withdraw {:.4} SOL{} from vote account {} to {}",
+            sol, cap_note, config.vote_account, config.withdraw_authority
+        );
+        println!("{}", message);
+        return notify::send(&message).await;
+    }
+
+    let holder = format!("keeper (pid {})", std::process::id());
+    let Some(lock) = IngestionLock::acquire(cache, &holder, lock_wait).await? else {
+        println!("Keeper: cache is busy with an ingestion; withdrawing on the next check");
+        return Ok(());
+    };
+    let result = withdraw_and_record(cache, &client, config, authority, lamports, sent).await;
+    lock.release().await;
+    let (signature, recorded) = result?;
+
+    let mut message = format!(
+        "Keeper withdrew {:.4} SOL{} from vote account {} to {} ({})",
+        sol, cap_note, config.vote_account, config.withdraw_authority, signature
+    );
+    if let Err(e) = recorded {
+        message.push_str(&format!(
+            "; not recorded in the cache ({:#}), the next ingestion picks it up",
+            e
+        ));
+    }
+    println!("{}", message);
+    notify::send(&message).await
+}
+
+/// Send the withdrawal and record it. Once sent, it counts toward today's cap in `sent`
+/// even if recording it fails, so the second result is only reported.
+async fn withdraw_and_record(
+    cache: &Cache,
+    client: &RpcClient,
+    config: &Config,
+    authority: &Keypair,
+    lamports: u64,
+    sent: &mut SentToday,
+) -> Result<(String, Result<()>)> {
+    // Taken before sending, so nothing after the send depends on the RPC node
+    let slot = client.get_slot()?;
+    let signature = send_withdrawal(client, config, authority, lamports)?;
+    let recorded = note_and_record(cache, config, sent, &signature, slot, lamports).await;
+    Ok((signature, recorded))
+}
+
+/// Count a sent withdrawal toward today's cap, then record it, retrying the cache write
+async fn note_and_record(
+    cache: &Cache,
+    config: &Config,
+    sent: &mut SentToday,
+    signature: &str,
+    slot: u64,
+    lamports: u64,
+) -> Result<()> {
+    let timestamp = chrono::Utc::now().timestamp();
+    let date = timezone::timestamp_to_date(timestamp).unwrap_or_default();
+    sent.note(&date, signature, lamports);
+
+    let mut attempt = 1;
+    loop {
+        match record_withdrawal(cache, config, signature, slot, timestamp, lamports).await {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt >= RECORD_ATTEMPTS => return Err(e),
+            Err(e) => {
+                eprintln!(
+                    "  Warning: recording withdrawal {} failed (attempt {}): {:#}",
+                    signature, attempt, e
+                );
+                attempt += 1;
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+        }
+    }
+}
+
+fn send_withdrawal(client: &RpcClient, config: &Config, authority: &Keypair, lamports: u64) -> Result<String> {
+    let instruction = tx_builder::vote_withdraw(
+        &config.vote_account,
+        &authority.pubkey(),
+        &config.withdraw_authority,
+        lamports,
+    );
+    let blockhash = client.get_latest_blockhash()?;
+    let tx = Transaction::new_signed_with_payer(&[instruction], Some(&authority.pubkey()), &[authority], blockhash);
+    let signature = client
+        .send_and_confirm_transaction(&tx)
+        .context("Withdrawal transaction failed")?;
+    Ok(signature.to_string())
+}
+
+/// Store the withdrawal as a transfer right away (ingestion later upserts the same row)
+async fn record_withdrawal(
+    cache: &Cache,
+    config: &Config,
+    signature: &str,
+    slot: u64,
+    timestamp: i64,
+    lamports: u64,
+) -> Result<()> {
+    let (from_label, from_category) = transactions::label_and_category_for_address(&config.vote_account, None, config);
+    let (to_label, to_category) =
+        transactions::label_and_category_for_address(&config.withdraw_authority, None, config);
+    let transfer = SolTransfer {
+        signature: signature.to_string(),
+        slot,
+        timestamp: Some(timestamp),
+        date: timezone::timestamp_to_date(timestamp),
+        from: config.vote_account,
+        to: config.withdraw_authority,
+        amount_lamports: lamports,
+        amount_sol: lamports as f64 / 1e9,
+        from_label,
+        to_label,
+        from_category,
+        to_category,
+        memo: None,
+        program: Some(tx_builder::VOTE_PROGRAM_ID.to_string()),
+    };
+    cache.store_transfers(&[transfer]).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::tests::test_config;
+
+    const LIMITS: Limits = Limits {
+        threshold: 1_000_000_000,
+        max_withdrawal: 5_000_000_000,
+        max_daily: 8_000_000_000,
+    };
+
+    #[test]
+    fn withdraws_only_above_threshold_and_within_caps() {
+        assert_eq!(decide(999_999_999, 0, &LIMITS), Decision::BelowThreshold);
+        assert_eq!(
+            decide(2_000_000_000, 0, &LIMITS),
+            Decision::Withdraw {
+                lamports: 2_000_000_000,
+                capped: false
+            }
+        );
+        // Per-withdrawal cap
+        assert_eq!(
+            decide(20_000_000_000, 0, &LIMITS),
+            Decision::Withdraw {
+                lamports: 5_000_000_000,
+                capped: true
+            }
+        );
+        // Daily cap leaves 3 SOL
+        assert_eq!(
+            decide(20_000_000_000, 5_000_000_000, &LIMITS),
+            Decision::Withdraw {
+                lamports: 3_000_000_000,
+                capped: true
+            }
+        );
+        assert_eq!(
+            decide(20_000_000_000, 8_000_000_000, &LIMITS),
+            Decision::DailyCapReached
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unrecorded_withdrawal_still_counts_toward_daily_cap() {
+        let dir = std::env::temp_dir().join(format!("va-keeper-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("cache.sqlite");
+        let cache = Cache::open(&path).await.unwrap();
+        let config = test_config(None);
+
+        // The send went through, but every attempt at caching it fails
+        let pool = sqlx::SqlitePool::connect(&format!("sqlite://{}", path.display()))
+            .await
+            .unwrap();
+        sqlx::query(
+            "CREATE TRIGGER no_inserts BEFORE INSERT ON sol_transfers BEGIN SELECT RAISE(FAIL, 'disk full'); END",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let mut sent = SentToday::default();
+        let recorded = note_and_record(&cache, &config, &mut sent, "sig", 1, 8_000_000_000).await;
+        assert!(recorded.is_err());
+
+        let today = timezone::today().format("%Y-%m-%d").to_string();
+        let withdrawn = withdrawn_on(&cache, &config, &sent, &today).await.unwrap();
+        assert_eq!(withdrawn, 8_000_000_000);
+        assert_eq!(decide(20_000_000_000, withdrawn, &LIMITS), Decision::DailyCapReached);
+
+        // Once ingestion caches it, it isn't counted twice
+        sqlx::query("DROP TRIGGER no_inserts").execute(&pool).await.unwrap();
+        let timestamp = chrono::Utc::now().timestamp();
+        record_withdrawal(&cache, &config, "sig", 1, timestamp, 8_000_000_000)
+            .await
+            .unwrap();
+        let withdrawn = withdrawn_on(&cache, &config, &sent, &today).await.unwrap();
+        pool.close().await;
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(withdrawn, 8_000_000_000);
+    }
+}
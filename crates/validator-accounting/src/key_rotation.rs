@@ -0,0 +1,328 @@
+//! Rotated validator keys (`[[validator.retired_keys]]`)
+//!
+//! Rotating the identity or withdraw authority, or migrating to a new vote account, leaves
+//! part of the history on the old key. Each retired key is listed with the dates it was in
+//! use (`from` is optional, `until` is the last day, inclusive). Within that range the old
+//! key is one of our accounts: its transfers are scanned, labelled and categorized like the
+//! current key's, leader fees and BAM rewards for epochs in the range are looked up under
+//! the identity that earned them, and inflation rewards and MEV tips under the vote account
+//! that was paid. Rewards from both sides of a migration merge into one per-epoch history,
+//! and `key_changes` lists the hand-overs for the reports to annotate. Outside the range the
+//! old key is an ordinary counterparty, so whatever reuses or receives it later doesn't leak
+//! into the books.
+//!
+//! Position snapshots still read the current keys only: SOL left on a retired key after
+//! `until` is not counted as a treasury asset, so sweep it to the new key when rotating.
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+use crate::config::{Config, RetiredKeyConfig};
+use crate::transactions::epoch_to_date;
+
+/// Which validator key a retired key used to be
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyRole {
+    Identity,
+    WithdrawAuthority,
+    VoteAccount,
+}
+
+impl KeyRole {
+    pub fn label(self) -> &'static str {
+        match self {
+            KeyRole::Identity => "Identity Account",
+            KeyRole::WithdrawAuthority => "Withdraw Authority",
+            KeyRole::VoteAccount => "Vote Account",
+        }
+    }
+}
+
+/// A key that held `role` between `from` and `until` (YYYY-MM-DD, inclusive)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetiredKey {
+    pub role: KeyRole,
+    pub pubkey: Pubkey,
+    /// First day in use (None: since before the books start)
+    pub from: Option<String>,
+    /// Last day in use
+    pub until: String,
+}
+
+fn parse_day(value: &str, field: &str, address: &str) -> Result<String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").with_context(|| {
+        format!(
+            "retired key {}: {} must be YYYY-MM-DD (got {:?})",
+            address, field, value
+        )
+    })?;
+    Ok(value.to_string())
+}
+
+impl RetiredKey {
+    pub fn from_config(key: &RetiredKeyConfig) -> Result<Self> {
+        let pubkey =
+            Pubkey::from_str(&key.address).with_context(|| format!("Invalid retired_keys address: {}", key.address))?;
+        let until = parse_day(&key.until, "until", &key.address)?;
+        let from = key
+            .from
+            .as_deref()
+            .map(|from| parse_day(from, "from", &key.address))
+            .transpose()?;
+        if let Some(from) = &from {
+            anyhow::ensure!(
+                *from <= until,
+                "retired key {}: from ({}) is after until ({})",
+                key.address,
+                from,
+                until
+            );
+        }
+        Ok(Self {
+            role: key.role,
+            pubkey,
+            from,
+            until,
+        })
+    }
+
+    /// Whether the key was in use on `date` (YYYY-MM-DD). Undated activity is attributed
+    /// to the key, so history without a block time isn't dropped.
+    pub fn covers(&self, date: Option<&str>) -> bool {
+        let Some(date) = date else {
+            return true;
+        };
+        self.overlaps(date, date)
+    }
+
+    /// Whether the key was in use on any day from `start` to `end`
+    fn overlaps(&self, start: &str, end: &str) -> bool {
+        self.from.as_deref().is_none_or(|from| from <= end) && start <= self.until.as_str()
+    }
+
+    /// Label for the old key's side of a transfer, e.g. "Identity Account (retired 2025-06-30)"
+    pub fn label(&self) -> String {
+        format!("{} (retired {})", self.role.label(), self.until)
+    }
+}
+
+/// The day `role` moved from one key to the next
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyChange {
+    pub role: KeyRole,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    /// First day on the new key (the day after the old key's `until`)
+    pub date: String,
+}
+
+impl KeyChange {
+    pub fn title(&self) -> &'static str {
+        match self.role {
+            KeyRole::VoteAccount => "Vote account migrated",
+            KeyRole::Identity => "Identity rotated",
+            KeyRole::WithdrawAuthority => "Withdraw authority rotated",
+        }
+    }
+
+    /// One-line description for report notes
+    pub fn describe(&self) -> String {
+        format!("{} from {} to {} on {}", self.title(), self.from, self.to, self.date)
+    }
+}
+
+fn next_day(date: &str) -> String {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.succ_opt())
+        .map_or_else(|| date.to_string(), |d| d.format("%Y-%m-%d").to_string())
+}
+
+impl Config {
+    fn current_key(&self, role: KeyRole) -> Pubkey {
+        match role {
+            KeyRole::Identity => self.identity,
+            KeyRole::WithdrawAuthority => self.withdraw_authority,
+            KeyRole::VoteAccount => self.vote_account,
+        }
+    }
+
+    /// The retired key `pubkey` if it was one of ours on `date`
+    pub fn retired_key(&self, pubkey: &Pubkey, date: Option<&str>) -> Option<&RetiredKey> {
+        self.retired_keys.iter().find(|k| k.pubkey == *pubkey && k.covers(date))
+    }
+
+    /// Like `is_our_account`, also counting keys retired after `date`
+    pub fn is_our_account_on(&self, pubkey: &Pubkey, date: Option<&str>) -> bool {
+        self.is_our_account(pubkey) || self.retired_key(pubkey, date).is_some()
+    }
+
+    /// Like `is_relevant_account`, also counting keys retired after `date`
+    pub fn is_relevant_account_on(&self, pubkey: &Pubkey, date: Option<&str>) -> bool {
+        self.is_relevant_account(pubkey) || self.retired_key(pubkey, date).is_some()
+    }
+
+    /// Whether `pubkey` is the current `role` key, or held `role` on `date`
+    pub fn holds_role(&self, pubkey: &Pubkey, role: KeyRole, date: Option<&str>) -> bool {
+        *pubkey == self.current_key(role) || self.retired_key(pubkey, date).is_some_and(|k| k.role == role)
+    }
+
+    /// The `role` key in use on `date` (YYYY-MM-DD)
+    fn key_on(&self, role: KeyRole, date: &str) -> Pubkey {
+        self.retired_keys
+            .iter()
+            .find(|k| k.role == role && k.covers(Some(date)))
+            .map_or(self.current_key(role), |k| k.pubkey)
+    }
+
+    /// The identity that signed blocks on `date` (YYYY-MM-DD)
+    pub fn identity_on(&self, date: &str) -> Pubkey {
+        self.key_on(KeyRole::Identity, date)
+    }
+
+    /// Vote accounts that may have been paid for `epoch`: the current one, plus any retired
+    /// one in use during the epoch. Epoch dates are approximate, so both sides of a
+    /// migration are asked about the epochs around it; the account that wasn't staked
+    /// simply has nothing.
+    pub fn vote_accounts_for_epoch(&self, epoch: u64) -> Vec<Pubkey> {
+        let (start, end) = (epoch_to_date(epoch), epoch_to_date(epoch + 1));
+        let mut accounts = vec![self.vote_account];
+        for key in &self.retired_keys {
+            if key.role == KeyRole::VoteAccount && key.overlaps(&start, &end) && !accounts.contains(&key.pubkey) {
+                accounts.push(key.pubkey);
+            }
+        }
+        accounts
+    }
+
+    /// Every key that has held `role`: the current one first, then the retired ones
+    pub fn keys_for(&self, role: KeyRole) -> Vec<Pubkey> {
+        let mut keys = vec![self.current_key(role)];
+        for key in self.retired_keys.iter().filter(|k| k.role == role) {
+            if !keys.contains(&key.pubkey) {
+                keys.push(key.pubkey);
+            }
+        }
+        keys
+    }
+
+    /// Current and retired identity and withdraw authority keys, deduplicated (the owners
+    /// of the treasury's jitoSOL)
+    pub fn signing_keys(&self) -> Vec<Pubkey> {
+        let mut keys = self.keys_for(KeyRole::Identity);
+        for key in self.keys_for(KeyRole::WithdrawAuthority) {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        keys
+    }
+
+    /// Every hand-over from a retired key to its successor, oldest first. The successor is
+    /// the next retired key of the same role, or the current key after the last one.
+    pub fn key_changes(&self) -> Vec<KeyChange> {
+        let mut retired: Vec<&RetiredKey> = self.retired_keys.iter().collect();
+        retired.sort_by(|a, b| a.until.cmp(&b.until));
+        let mut changes: Vec<KeyChange> = retired
+            .iter()
+            .enumerate()
+            .map(|(i, key)| {
+                let successor = retired[i + 1..]
+                    .iter()
+                    .find(|k| k.role == key.role)
+                    .map_or(self.current_key(key.role), |k| k.pubkey);
+                KeyChange {
+                    role: key.role,
+                    from: key.pubkey,
+                    to: successor,
+                    date: next_day(&key.until),
+                }
+            })
+            .collect();
+        changes.sort_by(|a, b| a.date.cmp(&b.date));
+        changes
+    }
+
+    /// `treasury_accounts` plus every retired key, for reconciling the whole history:
+    /// moving SOL from an old key to its successor is internal, not a withdrawal
+    pub fn ledger_accounts(&self) -> Vec<Pubkey> {
+        let mut accounts = self.treasury_accounts();
+        for key in &self.retired_keys {
+            if !accounts.contains(&key.pubkey) {
+                accounts.push(key.pubkey);
+            }
+        }
+        accounts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retired_identity_applies_within_its_dates() {
+        let mut config = crate::config::tests::test_config(None);
+        let old_identity = Pubkey::new_unique();
+        config.retired_keys = vec![
+            RetiredKey::from_config(&RetiredKeyConfig {
+                role: KeyRole::Identity,
+                address: old_identity.to_string(),
+                from: Some("2025-01-10".to_string()),
+                until: "2025-06-30".to_string(),
+            })
+            .unwrap(),
+        ];
+
+        assert_eq!(config.identity_on("2025-06-30"), old_identity);
+        assert_eq!(config.identity_on("2025-07-01"), config.identity);
+        assert_eq!(config.identity_on("2025-01-09"), config.identity);
+        assert!(config.is_our_account_on(&old_identity, Some("2025-03-01")));
+        assert!(!config.is_our_account_on(&old_identity, Some("2025-08-01")));
+        assert!(config.is_our_account_on(&old_identity, None));
+        assert!(!config.is_our_account(&old_identity));
+        assert!(config.holds_role(&old_identity, KeyRole::Identity, Some("2025-06-30")));
+        assert!(!config.holds_role(&old_identity, KeyRole::WithdrawAuthority, Some("2025-06-30")));
+        assert_eq!(config.keys_for(KeyRole::Identity), vec![config.identity, old_identity]);
+        assert!(config.ledger_accounts().contains(&old_identity));
+
+        let backwards = RetiredKeyConfig {
+            role: KeyRole::WithdrawAuthority,
+            address: old_identity.to_string(),
+            from: Some("2025-07-01".to_string()),
+            until: "2025-06-30".to_string(),
+        };
+        assert!(RetiredKey::from_config(&backwards).is_err());
+    }
+
+    #[test]
+    fn test_vote_account_migration_merges_epochs() {
+        let mut config = crate::config::tests::test_config(None);
+        let old_vote = Pubkey::new_unique();
+        let migrated_on = epoch_to_date(900);
+        config.retired_keys = vec![
+            RetiredKey::from_config(&RetiredKeyConfig {
+                role: KeyRole::VoteAccount,
+                address: old_vote.to_string(),
+                from: None,
+                until: migrated_on.clone(),
+            })
+            .unwrap(),
+        ];
+
+        assert_eq!(config.vote_accounts_for_epoch(880), vec![config.vote_account, old_vote]);
+        assert_eq!(config.vote_accounts_for_epoch(900), vec![config.vote_account, old_vote]);
+        assert_eq!(config.vote_accounts_for_epoch(910), vec![config.vote_account]);
+
+        let changes = config.key_changes();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].role, KeyRole::VoteAccount);
+        assert_eq!((changes[0].from, changes[0].to), (old_vote, config.vote_account));
+        assert_eq!(changes[0].date, next_day(&migrated_on));
+        assert!(changes[0].describe().starts_with("Vote account migrated from"));
+    }
+}
@@ -7,7 +7,7 @@
 
 use anyhow::{Context, Result};
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 use std::time::Duration;
 use tokio::time::sleep;
@@ -43,7 +43,6 @@ pub struct EpochLeaderFees {
     pub leader_slots: u64,
     pub blocks_produced: u64,
     pub skipped_slots: u64,
-    #[allow(dead_code)]
     pub total_fees_lamports: u64,
     pub total_fees_sol: f64,
     pub date: Option<String>,
@@ -117,7 +116,8 @@ pub async fn fetch_leader_fees(
 /// Fetch leader fees for a single epoch
 async fn fetch_epoch_leader_fees(client: &reqwest::Client, config: &Config, epoch: u64) -> Result<EpochLeaderFees> {
     let epoch_start_slot = epoch * constants::SLOTS_PER_EPOCH;
-    let identity = config.identity.to_string();
+    // A rotated identity keeps the epochs it led
+    let identity = config.identity_on(&epoch_to_date(epoch)).to_string();
 
     // Get leader schedule for this epoch
     let leader_slots = get_leader_schedule(client, &config.rpc_url, epoch_start_slot, &identity)
@@ -212,6 +212,23 @@ async fn fetch_epoch_leader_fees(client: &reqwest::Client, config: &Config, epoc
     })
 }
 
+/// Fetch how many leader slots the schedule assigns us in an epoch
+///
+/// RPC nodes only keep schedules for recent epochs, so this is meant to be
+/// called for the current and next epoch and cached as the history grows.
+pub async fn fetch_leader_slot_count(config: &Config, epoch: u64) -> Result<u64> {
+    let client = reqwest::Client::new();
+    let slots = get_leader_schedule(
+        &client,
+        &config.rpc_url,
+        epoch * constants::SLOTS_PER_EPOCH,
+        &config.identity_on(&epoch_to_date(epoch)).to_string(),
+    )
+    .await
+    .context("Failed to get leader schedule")?;
+    Ok(slots.len() as u64)
+}
+
 /// Get current epoch from RPC
 async fn get_current_epoch(client: &reqwest::Client, rpc_url: &str) -> Result<u64> {
     let body = serde_json::json!({
@@ -289,6 +306,39 @@ async fn get_block_fee_reward(
     Ok(None)
 }
 
+/// An epoch whose leader fee row accounts for fewer slots than the schedule assigned
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeaderFeeGap {
+    pub epoch: u64,
+    /// Slots assigned by the leader schedule
+    pub expected_slots: u64,
+    /// Blocks produced plus skipped slots in the leader fee row (0 if the epoch has no row)
+    pub observed_slots: u64,
+}
+
+/// Compare leader fee rows against the cached leader schedule
+///
+/// Only epochs with a known schedule are checked. Dune-sourced rows carry no skipped
+/// slot count, so they show up here whenever the validator skipped a block.
+pub fn find_leader_fee_gaps(fees: &[EpochLeaderFees], expected: &BTreeMap<u64, u64>) -> Vec<LeaderFeeGap> {
+    let observed: HashMap<u64, u64> = fees
+        .iter()
+        .map(|f| (f.epoch, f.blocks_produced + f.skipped_slots))
+        .collect();
+
+    expected
+        .iter()
+        .filter_map(|(&epoch, &expected_slots)| {
+            let observed_slots = observed.get(&epoch).copied().unwrap_or(0);
+            (observed_slots < expected_slots).then_some(LeaderFeeGap {
+                epoch,
+                expected_slots,
+                observed_slots,
+            })
+        })
+        .collect()
+}
+
 /// Get total leader fees in SOL
 pub fn total_leader_fees_sol(fees: &[EpochLeaderFees]) -> f64 {
     fees.iter().map(|f| f.total_fees_sol).sum()
@@ -312,7 +362,7 @@ pub fn load_historical_slots(path: &Path) -> Result<HistoricalLeaderSlots> {
 /// to get the actual fee amounts from RPC.
 pub async fn fetch_fees_for_slots(config: &Config, epoch: u64, slots: &[u64]) -> Result<EpochLeaderFees> {
     let client = reqwest::Client::new();
-    let identity = config.identity.to_string();
+    let identity = config.identity_on(&epoch_to_date(epoch)).to_string();
 
     let mut total_fees: u64 = 0;
     let mut blocks_produced: u64 = 0;
@@ -423,4 +473,37 @@ mod tests {
         assert_eq!(904 * constants::SLOTS_PER_EPOCH, 390_528_000);
         assert_eq!(912 * constants::SLOTS_PER_EPOCH, 393_984_000);
     }
+
+    #[test]
+    fn test_find_leader_fee_gaps() {
+        let fee = |epoch, blocks_produced, skipped_slots| EpochLeaderFees {
+            epoch,
+            leader_slots: blocks_produced + skipped_slots,
+            blocks_produced,
+            skipped_slots,
+            total_fees_lamports: 0,
+            total_fees_sol: 0.0,
+            date: None,
+        };
+        let fees = vec![fee(900, 40, 0), fee(901, 36, 2)];
+        let expected = BTreeMap::from([(900, 40), (901, 40), (902, 12)]);
+
+        let gaps = find_leader_fee_gaps(&fees, &expected);
+
+        assert_eq!(
+            gaps,
+            vec![
+                LeaderFeeGap {
+                    epoch: 901,
+                    expected_slots: 40,
+                    observed_slots: 38
+                },
+                LeaderFeeGap {
+                    epoch: 902,
+                    expected_slots: 12,
+                    observed_slots: 0
+                },
+            ]
+        );
+    }
 }
@@ -0,0 +1,314 @@
+//! Loss-harvesting and wash-sale window analysis (advisory only)
+//!
+//! Works from the income lots (`income_lots`, FMV at receipt = cost basis) and treats
+//! withdrawals as SOL dispositions, consumed first-in first-out. What's left is the held
+//! position; each remaining lot is compared to the current SOL price to estimate unrealized
+//! gains/losses. Staking income arrives every epoch, so any sale has replacement acquisitions
+//! in its ±30-day window; the CSV lists them so the accountant can judge whether wash-sale
+//! treatment applies. Nothing here is authoritative tax advice.
+
+use anyhow::Result;
+use chrono::{Duration, NaiveDate};
+use csv::WriterBuilder;
+use std::path::Path;
+
+use crate::constants;
+use crate::income_lots::IncomeLot;
+
+/// Days on either side of a sale that replacement purchases may trigger wash-sale treatment
+const WASH_SALE_WINDOW_DAYS: i64 = 30;
+
+/// Holding period (days) after which a gain/loss is long-term
+const LONG_TERM_DAYS: i64 = 365;
+
+/// SOL leaving the business (a withdrawal), consumed against SOL lots first-in first-out
+#[derive(Debug, Clone)]
+pub struct Disposition {
+    pub date: String,
+    pub amount_sol: f64,
+}
+
+/// A lot still held after dispositions, with its estimated unrealized gain/loss
+#[derive(Debug, Clone)]
+pub struct HeldLot {
+    pub lot: IncomeLot,
+    /// Remaining amount in units of `lot.asset`
+    pub remaining_amount: f64,
+    /// Cost basis of the remaining amount (USD)
+    pub basis_usd: f64,
+    /// Remaining amount valued at the as-of price (USD)
+    pub value_usd: f64,
+    pub unrealized_usd: f64,
+    pub holding_days: i64,
+}
+
+impl HeldLot {
+    pub fn harvestable_loss_usd(&self) -> f64 {
+        (-self.unrealized_usd).max(0.0)
+    }
+
+    pub fn term(&self) -> &'static str {
+        if self.holding_days > LONG_TERM_DAYS {
+            "long"
+        } else {
+            "short"
+        }
+    }
+}
+
+/// Replacement acquisitions (income receipts) of an asset inside the look-back half of the
+/// wash-sale window ending on the as-of date
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WindowAcquisitions {
+    pub count: usize,
+    pub amount: f64,
+}
+
+/// Consume dispositions against SOL lots (FIFO, only lots received on or before the
+/// disposition date) and value what remains at `price` USD per SOL. jitoSOL lots aren't
+/// consumed by SOL withdrawals and are valued at `jitosol_rate` SOL per jitoSOL.
+pub fn held_lots(
+    lots: &[IncomeLot],
+    dispositions: &[Disposition],
+    as_of: NaiveDate,
+    price: f64,
+    jitosol_rate: f64,
+) -> Vec<HeldLot> {
+    let mut lots: Vec<&IncomeLot> = lots
+        .iter()
+        .filter(|lot| lot_date(lot).is_some_and(|d| d <= as_of))
+        .collect();
+    lots.sort_by(|a, b| a.date.cmp(&b.date).then(a.timestamp.cmp(&b.timestamp)));
+    let mut remaining: Vec<f64> = lots.iter().map(|lot| lot.amount).collect();
+
+    let as_of_date = as_of.to_string();
+    let mut dispositions: Vec<&Disposition> = dispositions.iter().filter(|d| d.date <= as_of_date).collect();
+    dispositions.sort_by(|a, b| a.date.cmp(&b.date));
+    for disposition in dispositions {
+        let mut left = disposition.amount_sol;
+        for (lot, remaining) in lots.iter().zip(remaining.iter_mut()) {
+            if left <= 0.0 {
+                break;
+            }
+            if lot.asset != "SOL" || lot.date > disposition.date || *remaining <= 0.0 {
+                continue;
+            }
+            let taken = left.min(*remaining);
+            *remaining -= taken;
+            left -= taken;
+        }
+        // Anything left over came from seed capital or pre-bootstrap funds, which have no lots
+    }
+
+    lots.into_iter()
+        .zip(remaining)
+        .filter(|(_, remaining)| *remaining > 1e-9)
+        .map(|(lot, remaining_amount)| {
+            let fraction = if lot.amount > 0.0 {
+                remaining_amount / lot.amount
+            } else {
+                0.0
+            };
+            let sol_now = if lot.asset == "SOL" {
+                remaining_amount
+            } else {
+                remaining_amount * jitosol_rate
+            };
+            let basis_usd = lot.fmv_usd * fraction;
+            let value_usd = sol_now * price;
+            HeldLot {
+                holding_days: lot_date(lot).map_or(0, |d| (as_of - d).num_days()),
+                lot: lot.clone(),
+                remaining_amount,
+                basis_usd,
+                value_usd,
+                unrealized_usd: value_usd - basis_usd,
+            }
+        })
+        .collect()
+}
+
+/// Income receipts of `asset` in the 30 days up to and including `as_of`
+pub fn window_acquisitions(lots: &[IncomeLot], asset: &str, as_of: NaiveDate) -> WindowAcquisitions {
+    let start = as_of - Duration::days(WASH_SALE_WINDOW_DAYS);
+    lots.iter()
+        .filter(|lot| lot.asset == asset && lot_date(lot).is_some_and(|d| d >= start && d <= as_of))
+        .fold(WindowAcquisitions::default(), |acc, lot| WindowAcquisitions {
+            count: acc.count + 1,
+            amount: acc.amount + lot.amount,
+        })
+}
+
+fn lot_date(lot: &IncomeLot) -> Option<NaiveDate> {
+    lot.date
+        .get(..10)
+        .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+}
+
+/// Write the advisory CSV. Returns the total estimated harvestable loss (USD).
+pub fn write_advisory_csv(
+    output_dir: &Path,
+    all_lots: &[IncomeLot],
+    held: &[HeldLot],
+    as_of: NaiveDate,
+    price: f64,
+    price_source: &str,
+) -> Result<f64> {
+    let path = output_dir.join(constants::LOSS_HARVEST_FILENAME);
+    let mut wtr = WriterBuilder::new().flexible(true).from_path(&path)?;
+
+    wtr.write_record([
+        "ADVISORY ONLY - NOT AUTHORITATIVE AND NOT TAX ADVICE. Estimates from cached income lots (FMV at receipt) \
+         with withdrawals consumed FIFO; review with your accountant before acting.",
+    ])?;
+    wtr.write_record([format!(
+        "As of {} at {:.2} USD/SOL ({} price). Wash-sale window: {} days before and after a sale.",
+        as_of, price, price_source, WASH_SALE_WINDOW_DAYS
+    )])?;
+    wtr.write_record([""])?;
+
+    wtr.write_record([
+        "Lot_Date",
+        "Source",
+        "Reference",
+        "Asset",
+        "Remaining_Amount",
+        "Basis_USD",
+        "Value_USD",
+        "Unrealized_Gain_Loss_USD",
+        "Harvestable_Loss_USD",
+        "Holding_Days",
+        "Term (short/long)",
+        "Wash_Window_Start (if sold on as-of date)",
+        "Wash_Window_End",
+        "Acquisitions_In_Window_Before (count)",
+        "Acquired_In_Window_Before (amount)",
+        "Notes",
+    ])?;
+
+    let window_start = as_of - Duration::days(WASH_SALE_WINDOW_DAYS);
+    let window_end = as_of + Duration::days(WASH_SALE_WINDOW_DAYS);
+    let mut total_loss = 0.0;
+    for held_lot in held {
+        let acquisitions = window_acquisitions(all_lots, &held_lot.lot.asset, as_of);
+        let loss = held_lot.harvestable_loss_usd();
+        total_loss += loss;
+        let note = if loss <= 0.0 {
+            "No unrealized loss."
+        } else if acquisitions.count > 0 {
+            "Loss lot. Income receipts inside the window are replacement acquisitions; \
+             continuing rewards keep the window open."
+        } else {
+            "Loss lot. No receipts in the look-back window; watch receipts in the 30 days after a sale."
+        };
+
+        wtr.write_record([
+            held_lot.lot.date.as_str(),
+            held_lot.lot.source.as_str(),
+            held_lot.lot.reference.as_str(),
+            held_lot.lot.asset.as_str(),
+            &format!("{:.6}", held_lot.remaining_amount),
+            &format!("{:.2}", held_lot.basis_usd),
+            &format!("{:.2}", held_lot.value_usd),
+            &format!("{:.2}", held_lot.unrealized_usd),
+            &format!("{:.2}", loss),
+            &held_lot.holding_days.to_string(),
+            held_lot.term(),
+            &window_start.to_string(),
+            &window_end.to_string(),
+            &acquisitions.count.to_string(),
+            &format!("{:.6}", acquisitions.amount),
+            note,
+        ])?;
+    }
+
+    wtr.write_record([""])?;
+    wtr.write_record([
+        "TOTAL (advisory estimate)".to_string(),
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+        format!("{:.2}", held.iter().map(|h| h.basis_usd).sum::<f64>()),
+        format!("{:.2}", held.iter().map(|h| h.value_usd).sum::<f64>()),
+        format!("{:.2}", held.iter().map(|h| h.unrealized_usd).sum::<f64>()),
+        format!("{:.2}", total_loss),
+    ])?;
+
+    wtr.flush()?;
+    println!("  Generated: {}", path.display());
+    Ok(total_loss)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lot(date: &str, asset: &str, amount: f64, price: f64) -> IncomeLot {
+        IncomeLot {
+            source: "commission".to_string(),
+            reference: format!("epoch-{}", date),
+            epoch: None,
+            date: date.to_string(),
+            timestamp: None,
+            asset: asset.to_string(),
+            amount,
+            amount_sol: amount,
+            price_usd: price,
+            price_source: "exact".to_string(),
+            fmv_usd: amount * price,
+        }
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn withdrawals_consume_sol_lots_fifo() {
+        let lots = vec![
+            lot("2025-01-01", "SOL", 2.0, 200.0),
+            lot("2025-02-01", "SOL", 2.0, 250.0),
+            lot("2025-02-15", "jitoSOL", 1.0, 250.0),
+        ];
+        let dispositions = vec![Disposition {
+            date: "2025-03-01".to_string(),
+            amount_sol: 3.0,
+        }];
+
+        let held = held_lots(&lots, &dispositions, date("2025-06-01"), 150.0, 1.1);
+        assert_eq!(held.len(), 2);
+        assert_eq!(held[0].lot.date, "2025-02-01");
+        assert!((held[0].remaining_amount - 1.0).abs() < 1e-9);
+        assert!((held[0].basis_usd - 250.0).abs() < 1e-9);
+        assert!((held[0].harvestable_loss_usd() - 100.0).abs() < 1e-9);
+        // jitoSOL isn't consumed by SOL withdrawals and is valued at the jitoSOL rate
+        assert_eq!(held[1].lot.asset, "jitoSOL");
+        assert!((held[1].value_usd - 165.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn dispositions_only_consume_earlier_lots() {
+        let lots = vec![lot("2025-03-01", "SOL", 1.0, 100.0)];
+        let dispositions = vec![Disposition {
+            date: "2025-02-01".to_string(),
+            amount_sol: 5.0,
+        }];
+
+        let held = held_lots(&lots, &dispositions, date("2025-04-01"), 100.0, 1.0);
+        assert_eq!(held.len(), 1);
+        assert!((held[0].remaining_amount - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn window_counts_recent_receipts() {
+        let lots = vec![
+            lot("2025-01-01", "SOL", 1.0, 100.0),
+            lot("2025-05-20", "SOL", 0.5, 100.0),
+            lot("2025-05-30", "SOL", 0.25, 100.0),
+        ];
+        let acquisitions = window_acquisitions(&lots, "SOL", date("2025-06-01"));
+        assert_eq!(acquisitions.count, 2);
+        assert!((acquisitions.amount - 0.75).abs() < 1e-9);
+    }
+}
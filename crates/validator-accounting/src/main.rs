@@ -4,27 +4,79 @@
 //! on-chain data and labeling known addresses.
 
 mod addresses;
+mod anomalies;
+mod archive;
 mod bam;
+mod bam_verify;
 mod cache;
+mod card;
+mod client_metrics;
+mod client_uplift;
+mod cluster;
 mod config;
 mod constants;
+mod dedupe;
+mod delegators;
+mod diagnostics;
 mod doublezero;
+mod dry_run;
 mod dune;
+mod entities;
+mod epoch_pnl;
+mod exchange;
 mod expenses;
+mod exports;
+mod failed_fees;
+mod fiat;
+mod fingerprint;
+mod heartbeat;
+mod history_import;
+mod hooks;
 mod html_report;
+mod income_lots;
+mod income_proof;
+mod ingestion_lock;
 mod jito;
+mod keeper;
+mod key_rotation;
 mod leader_fees;
+mod loss_harvest;
+mod mev;
+mod notify;
 mod notion;
+mod offline;
+mod ops_fees;
+mod output;
+mod owner_draw;
+mod period;
 mod positions;
+mod price_audit;
 mod prices;
+mod report_compare;
+mod report_metadata;
 mod reports;
+mod revenue_stats;
 mod rpc;
+mod rules;
+mod sfdp;
+mod sources;
+mod squads;
+mod strict;
+mod tax_package;
 mod tax_report;
+mod timezone;
 mod transactions;
+mod tui;
+mod tx_builder;
+mod unit_economics;
+mod validation;
 mod vote_costs;
+mod vote_topup;
+mod withdrawal_plan;
 
 use anyhow::{Context, Result};
-use chrono::{NaiveDate, Utc};
+use chrono::NaiveDate;
+use chrono_tz::Tz;
 use clap::{Parser, Subcommand};
 use solana_commitment_config::CommitmentConfig;
 use solana_sdk::pubkey::Pubkey;
@@ -32,9 +84,11 @@ use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time::Duration;
 
-use cache::Cache;
+use cache::{Cache, IngestionRunLog};
 use config::FileConfig;
-use expenses::{Expense, ExpenseCategory, RecurringExpense};
+use expenses::{Expense, ExpenseCategory, ExpenseStatus, RecurringExpense};
+use ingestion_lock::IngestionLock;
+use output::OutputFormat;
 
 /// Default config file name
 const CONFIG_FILE: &str = "config.toml";
@@ -42,9 +96,9 @@ const CONFIG_FILE: &str = "config.toml";
 /// Possible locations to find config.toml
 const CONFIG_SEARCH_PATHS: &[&str] = &["config.toml", "crates/validator-accounting/config.toml"];
 
-/// Load config file or exit with helpful message
-fn load_config_file(config_path: Option<&PathBuf>) -> Result<FileConfig> {
-    let path = if let Some(p) = config_path {
+/// Config path from --config, else the first known location that exists
+fn resolve_config_path(config_path: Option<&PathBuf>) -> PathBuf {
+    if let Some(p) = config_path {
         p.clone()
     } else {
         // Search for config in known locations
@@ -53,7 +107,12 @@ fn load_config_file(config_path: Option<&PathBuf>) -> Result<FileConfig> {
             .map(PathBuf::from)
             .find(|p| p.exists())
             .unwrap_or_else(|| PathBuf::from(CONFIG_FILE))
-    };
+    }
+}
+
+/// Load config file or exit with helpful message
+fn load_config_file(config_path: Option<&PathBuf>) -> Result<FileConfig> {
+    let path = resolve_config_path(config_path);
 
     if !path.exists() {
         anyhow::bail!(
@@ -121,9 +180,8 @@ struct Args {
     #[arg(long)]
     end_epoch: Option<u64>,
 
-    /// Filter reports to a specific year (e.g., 2025)
-    #[arg(long)]
-    year: Option<i32>,
+    #[command(flatten)]
+    period: PeriodArgs,
 
     /// RPC URL (uses private endpoint by default)
     #[arg(long)]
@@ -140,6 +198,47 @@ struct Args {
     /// Verbose output
     #[arg(short, long)]
     verbose: bool,
+
+    /// Label recorded in ingestion run history (e.g. "scheduler")
+    #[arg(long, default_value = "cli", global = true)]
+    run_trigger: String,
+
+    /// Seconds to wait if another ingestion holds the cache lock (0 = skip immediately)
+    #[arg(long, default_value = "0", global = true)]
+    lock_wait: u64,
+
+    /// Exit non-zero on reconciliation variance (3), epochs missing data (4) or
+    /// fallback-priced rows (5), for cron and CI checks
+    #[arg(long, global = true)]
+    strict: bool,
+
+    /// Work from the cache only: reports are built without fetching, and commands that
+    /// need the network fail immediately, listing which cached data is stale
+    #[arg(long, global = true)]
+    offline: bool,
+    /// Sync into a scratch copy of the cache and print the rows each table would gain,
+    /// change or lose; the cache and output directory are left untouched
+    #[arg(long, conflicts_with = "offline")]
+    dry_run: bool,
+}
+
+/// Report period selection (`--year` or `--period`)
+#[derive(clap::Args, Debug, Clone)]
+struct PeriodArgs {
+    /// Filter reports to a specific year (e.g., 2025); the fiscal year ending in
+    /// that year if reporting.fiscal_year_start_month is set
+    #[arg(long, conflicts_with = "period")]
+    year: Option<i32>,
+
+    /// Filter reports to a custom period (e.g., 2025-07..2026-06)
+    #[arg(long)]
+    period: Option<String>,
+}
+
+impl PeriodArgs {
+    fn resolve(&self, config: &config::Config) -> Result<Option<period::ReportPeriod>> {
+        period::resolve(self.year, self.period.as_deref(), config.fiscal_year_start_month)
+    }
 }
 
 #[derive(Subcommand, Debug)]
@@ -174,263 +273,2981 @@ enum Command {
         action: DuneCommand,
     },
 
+    /// Seed historical rewards and stake from public aggregators (verified on-chain later)
+    Import {
+        #[command(subcommand)]
+        action: ImportCommand,
+    },
+
     /// Position tracking (balance sheet view - where is the money now?)
     Position {
         #[command(subcommand)]
         action: PositionCommand,
     },
 
-    /// Generate withdrawal-based tax report
-    Tax {
-        /// Filter to a specific tax year (e.g., 2025)
+    /// Manage the cached SOL/USD price history
+    Prices {
+        #[command(subcommand)]
+        action: PricesCommand,
+    },
+
+    /// Show which per-epoch sources are cached for each epoch (data gap matrix)
+    Coverage {
+        /// First epoch to show (default: first cached reward epoch)
         #[arg(long)]
-        year: Option<i32>,
+        from: Option<u64>,
 
-        /// RPC URL (uses private endpoint by default)
+        /// Last epoch to show (default: latest cached epoch)
         #[arg(long)]
-        rpc_url: Option<String>,
+        to: Option<u64>,
 
-        /// Force refresh all data (ignore cache)
+        /// Only list epochs with missing sources
         #[arg(long)]
-        no_cache: bool,
+        gaps_only: bool,
+    },
 
-        /// Verbose output
-        #[arg(short, long)]
-        verbose: bool,
+    /// Re-apply current address labels and categorization rules to every cached transfer
+    Recategorize {
+        /// Report what would change without rewriting the cache
+        #[arg(long)]
+        dry_run: bool,
     },
-}
 
-#[derive(Subcommand, Debug)]
-enum VoteCostsCommand {
-    /// Import vote costs from Dune Analytics JSON export
-    Import {
-        /// Path to JSON file with historical vote cost data
+    /// Tag counterparty addresses (e.g. your exchange deposit address) so transfers to them are classified
+    Addresses {
+        #[command(subcommand)]
+        action: AddressesCommand,
+    },
+
+    /// Split mixed-use withdrawals into an owner draw and a reimbursed business expense
+    Split {
+        #[command(subcommand)]
+        action: SplitCommand,
+    },
+
+    /// Track the off-chain USD cash account (`[fiat]`): deposits, balance and runway
+    Fiat {
+        #[command(subcommand)]
+        action: FiatCommand,
+    },
+
+    /// Import exchange deposit history and match it to on-chain withdrawals
+    Exchange {
+        #[command(subcommand)]
+        action: ExchangeCommand,
+    },
+
+    /// Import credit card statements and reconcile them against card-paid expenses
+    Card {
+        #[command(subcommand)]
+        action: CardCommand,
+    },
+
+    /// Flag likely duplicate income/expense rows across sources and resolve them
+    Dedupe {
+        #[command(subcommand)]
+        action: DedupeCommand,
+    },
+
+    /// Ingestion run history (what ran, when, and what it loaded)
+    Runs {
+        #[command(subcommand)]
+        action: RunsCommand,
+    },
+
+    /// External data sources synced by report runs (enabled state and health)
+    Sources {
+        #[command(subcommand)]
+        action: SourcesCommand,
+    },
+
+    /// Scrape the local validator client's metrics endpoint (requires [metrics])
+    Metrics {
+        #[command(subcommand)]
+        action: MetricsCommand,
+    },
+
+    /// Print the P&L summary from cached data without writing reports
+    Summary {
+        #[command(flatten)]
+        period: PeriodArgs,
+
+        /// Console output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output: OutputFormat,
+    },
+
+    /// List income lots (receipts at FMV; requires `[reporting] income_lots = true`)
+    IncomeLots {
+        #[command(flatten)]
+        period: PeriodArgs,
+
+        /// Console output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output: OutputFormat,
+    },
+
+    /// Push the summary and ledgers in the output directory to the `[gsheets]` spreadsheet
+    SyncSheets,
+
+    /// Upload the report artifacts in the output directory to the `[s3]` bucket
+    PublishS3,
+
+    /// Diff two generated report sets per month and category, explaining what changed
+    CompareReports {
+        /// Earlier report directory
+        dir_a: PathBuf,
+
+        /// Later report directory
+        dir_b: PathBuf,
+
+        /// Console output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output: OutputFormat,
+    },
+
+    /// Confirm a report file still has the content it was generated with (fingerprint check)
+    VerifyReport {
+        /// Report file (CSV or HTML), possibly renamed or copied elsewhere
         file: PathBuf,
     },
 
-    /// Show cached vote cost data
-    List,
+    /// Export every income receipt in lamports with its on-chain evidence (income_proof.csv)
+    IncomeProof {
+        #[command(flatten)]
+        period: PeriodArgs,
+    },
 
-    /// Estimate vote costs for missing epochs
-    Estimate {
-        /// Starting epoch
+    /// Re-check a sample of income proof rows against RPC and report mismatches
+    VerifyIncome {
+        #[command(flatten)]
+        period: PeriodArgs,
+
+        /// Rows to check, spread over the history (0 = every row)
+        #[arg(long, default_value = "20")]
+        sample: usize,
+
+        /// Console output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output: OutputFormat,
+    },
+
+    /// Advisory CSV of unrealized losses in held income lots and wash-sale windows (not tax advice)
+    LossHarvest {
+        /// Evaluate as of this date (YYYY-MM-DD, default: today)
         #[arg(long)]
-        start: u64,
+        as_of: Option<String>,
+    },
 
-        /// Ending epoch
+    /// Plan a withdrawal: SOL to move for a USD target, return of capital vs taxable, estimated tax
+    PlanWithdrawal {
+        /// Amount to withdraw in USD
         #[arg(long)]
-        end: u64,
+        usd: f64,
+
+        /// Flat tax rate (0-1) for the estimate (default: [reporting] estimated_tax_rate)
+        #[arg(long)]
+        tax_rate: Option<f64>,
+
+        /// Console output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output: OutputFormat,
     },
-}
 
-#[derive(Subcommand, Debug)]
-enum LeaderSlotsCommand {
-    /// Import leader slots from Dune Analytics JSON export
-    Import {
-        /// Path to JSON file with historical leader slot data
-        file: PathBuf,
+    /// Check BAM (JIP-31) payouts per epoch: expected from stake vs published vs claimed
+    BamCheck {
+        /// First epoch to check (default: [bam] first_epoch)
+        #[arg(long)]
+        from: Option<u64>,
 
-        /// RPC URL (uses private endpoint by default)
+        /// Last epoch to check (default: last completed epoch)
         #[arg(long)]
-        rpc_url: Option<String>,
+        to: Option<u64>,
+
+        /// Console output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output: OutputFormat,
     },
 
-    /// Show cached leader fee data
-    List,
-}
+    /// What-if: realized leader fees + MEV vs a vanilla-client baseline from network median block fees
+    ClientUplift {
+        /// Console output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output: OutputFormat,
+    },
 
-#[derive(Subcommand, Debug)]
-enum ExpenseCommand {
-    /// List all expenses
-    List,
+    /// Withdraw vote-account commission on a schedule with a hot withdraw authority (opt-in via [keeper])
+    Keeper {
+        /// Run a single check and exit (for cron)
+        #[arg(long)]
+        once: bool,
 
-    /// Add a new expense
-    Add {
-        /// Date (YYYY-MM-DD)
+        /// Log and notify what would be withdrawn without sending
         #[arg(long)]
-        date: String,
+        dry_run: bool,
+    },
 
-        /// Vendor name
+    /// Prepare unsigned transactions for routine treasury operations (offline; never loads keys)
+    Tx {
+        #[command(subcommand)]
+        action: TxCommand,
+    },
+
+    /// Export every cache table and the (secret-free) config as a portable tar.zst archive
+    ExportArchive {
+        /// Archive path (default: <output-dir>/validator-archive-<date>.tar.zst)
         #[arg(long)]
-        vendor: String,
+        path: Option<PathBuf>,
 
-        /// Category: Hosting, Contractor, Hardware, Software, VoteFees, Other
+        /// Replace addresses and signatures with stand-ins (for sharing in bug reports)
         #[arg(long)]
-        category: String,
+        anonymize: bool,
+    },
 
-        /// Description
+    /// Load an archive from export-archive into the cache
+    ImportArchive {
+        /// Archive to load
+        path: PathBuf,
+
+        /// Empty each archived table before loading (default: upsert by primary key)
         #[arg(long)]
-        description: String,
+        replace: bool,
 
-        /// Amount in USD
+        /// Also write the archived config.toml here (never overwrites an existing file)
         #[arg(long)]
-        amount: f64,
+        config_out: Option<PathBuf>,
+    },
 
-        /// Payment method (e.g., "Credit Card", "USD", "SOL")
-        #[arg(long, default_value = "USD")]
-        paid_with: String,
+    /// Collect anonymized diagnostics for bug reports
+    Diagnostics {
+        #[command(subcommand)]
+        action: DiagnosticsCommand,
+    },
 
-        /// Invoice ID (optional)
-        #[arg(long)]
-        invoice_id: Option<String>,
+    /// Show cache row counts per table
+    Stats {
+        /// Console output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output: OutputFormat,
     },
 
-    /// Delete an expense by ID
-    Delete {
-        /// Expense ID to delete
-        id: i64,
+    /// Live terminal dashboard (position, month-to-date P&L, transfers, ingestion status)
+    Tui {
+        /// Seconds between cache refreshes
+        #[arg(long, default_value = "30")]
+        refresh: u64,
     },
 
-    /// Import expenses from CSV file
-    Import {
-        /// Path to CSV file
-        file: PathBuf,
+    /// Generate withdrawal-based tax report
+    Tax {
+        #[command(flatten)]
+        period: PeriodArgs,
+
+        /// RPC URL (uses private endpoint by default)
+        #[arg(long)]
+        rpc_url: Option<String>,
+
+        /// Force refresh all data (ignore cache)
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Verbose output
+        #[arg(short, long)]
+        verbose: bool,
     },
 
-    /// Export expenses to CSV file
-    Export {
-        /// Path to output CSV file
-        file: PathBuf,
+    /// Build the year-end CPA package: ledgers, tax report, Schedule C, reconciliation, cover sheet
+    TaxPackage {
+        #[command(flatten)]
+        period: PeriodArgs,
+
+        /// Zip path (default: <output-dir>/tax_package_<period>.zip)
+        #[arg(long)]
+        path: Option<PathBuf>,
     },
 }
 
 #[derive(Subcommand, Debug)]
-enum RecurringCommand {
-    /// List all recurring expenses
-    List,
-
-    /// Add a new recurring expense
-    Add {
-        /// Vendor name
+enum PricesCommand {
+    /// Fetch and cache every missing daily price in one pass
+    Backfill {
+        /// First date to check (YYYY-MM-DD)
         #[arg(long)]
-        vendor: String,
+        from: String,
 
-        /// Category: Hosting, Contractor, Hardware, Software, VoteFees, Other
+        /// Last date to check (YYYY-MM-DD, default: today)
         #[arg(long)]
-        category: String,
+        to: Option<String>,
+    },
 
-        /// Description
+    /// Regenerate reports from cached data only, revaluing USD amounts with current cached prices
+    Revalue {
+        #[command(flatten)]
+        period: PeriodArgs,
+    },
+
+    /// Re-check cached prices against a second provider and list material discrepancies
+    Verify {
+        /// First date to check (YYYY-MM-DD)
         #[arg(long)]
-        description: String,
+        from: String,
 
-        /// Monthly amount in USD
+        /// Last date to check (YYYY-MM-DD, default: today)
         #[arg(long)]
-        amount: f64,
+        to: Option<String>,
 
-        /// Payment method (e.g., "Credit Card", "USD", "SOL")
-        #[arg(long, default_value = "USD")]
-        paid_with: String,
+        /// Provider to check against
+        #[arg(long, value_enum, default_value = "binance")]
+        against: prices::PriceProvider,
 
-        /// Start date (YYYY-MM-DD) - day is used as billing day each month
-        #[arg(long)]
-        start_date: String,
+        /// Difference between the two prices that counts as material (percent)
+        #[arg(long, default_value_t = constants::PRICE_DISCREPANCY_TOLERANCE_PCT)]
+        tolerance_pct: f64,
+    },
+}
 
-        /// End date (YYYY-MM-DD) - optional, omit for ongoing expenses
+#[derive(Subcommand, Debug)]
+enum AddressesCommand {
+    /// Tag an address and relabel cached transfers to it
+    Tag {
+        /// Address to tag (e.g. an exchange deposit address)
+        address: String,
+
+        /// Label to show on transfers (e.g. "Kraken deposit")
         #[arg(long)]
-        end_date: Option<String>,
+        label: String,
+
+        /// Category: Exchange, DeFiProtocol, Validator, SolanaSystem, Unknown
+        #[arg(long, default_value = "Exchange")]
+        category: String,
     },
 
-    /// Delete a recurring expense by ID
-    Delete {
-        /// Recurring expense ID to delete
-        id: i64,
+    /// Remove a tag and relabel cached transfers to the address
+    Untag {
+        /// Tagged address
+        address: String,
     },
+
+    /// List tagged addresses
+    List,
 }
 
 #[derive(Subcommand, Debug)]
-enum DuneCommand {
-    /// Import inflation rewards from Dune
-    Rewards {
-        /// Start date for query (YYYY-MM-DD)
+enum MetricsCommand {
+    /// Record a sample of produced/skipped slots and vote latency
+    Scrape {
+        /// Keep scraping every [metrics] interval_secs until stopped
         #[arg(long)]
-        since: String,
+        watch: bool,
     },
 
-    /// Import leader slot fees from Dune
-    LeaderFees {
-        /// Start date for query (YYYY-MM-DD)
-        #[arg(long)]
-        since: String,
+    /// Cross-check this epoch's client slot counters against getBlockProduction
+    Check {
+        /// Console output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output: OutputFormat,
     },
+}
 
-    /// Import vote transaction costs from Dune
-    VoteCosts {
-        /// Start date for query (YYYY-MM-DD)
-        #[arg(long)]
-        since: String,
+#[derive(Subcommand, Debug)]
+enum RunsCommand {
+    /// List recent ingestion runs (newest first)
+    List {
+        /// Maximum number of runs to show
+        #[arg(long, default_value = "20")]
+        limit: u32,
+
+        /// Show per-source row counts and errors for each run
+        #[arg(short, long)]
+        verbose: bool,
     },
+}
 
-    /// Import SOL transfers from Dune
-    Transfers {
-        /// Start date for query (YYYY-MM-DD)
+#[derive(Subcommand, Debug)]
+enum SplitCommand {
+    /// Mark part of a withdrawal as a business expense (replaces any existing split)
+    Set {
+        /// Withdrawal transaction signature
+        signature: String,
+
+        /// Percent of the withdrawal that paid business costs (the rest is an owner draw)
         #[arg(long)]
-        since: String,
-    },
+        business_pct: f64,
 
-    /// Import all data types from Dune
-    All {
-        /// Start date for query (YYYY-MM-DD)
+        /// Expense category: Hosting, Contractor, Hardware, Software, VoteFees, Other
         #[arg(long)]
-        since: String,
+        category: String,
+
+        /// What the business share paid for
+        #[arg(long)]
+        description: String,
+    },
+
+    /// Remove a withdrawal's split (it becomes a plain withdrawal again)
+    Remove {
+        /// Withdrawal transaction signature
+        signature: String,
     },
+
+    /// List split withdrawals
+    List,
 }
 
 #[derive(Subcommand, Debug)]
-enum PositionCommand {
-    /// Show current position (balance sheet snapshot)
-    Now,
+enum FiatCommand {
+    /// Record a deposit into the cash account (a negative amount records a transfer out)
+    Deposit {
+        /// Date (YYYY-MM-DD)
+        #[arg(long)]
+        date: String,
+
+        /// Amount in USD
+        #[arg(long, allow_hyphen_values = true)]
+        amount: f64,
+
+        /// Where the money came from (e.g. "Kraken off-ramp")
+        #[arg(long, default_value = "")]
+        memo: String,
+    },
+
+    /// Delete a deposit by ID
+    Delete {
+        /// Deposit ID to delete
+        id: i64,
+    },
+
+    /// List recorded deposits
+    List,
+
+    /// Print the monthly roll-forward, balance and runway
+    Balance,
+}
+
+#[derive(Subcommand, Debug)]
+enum ExchangeCommand {
+    /// Import SOL deposits from a Kraken ledgers or Coinbase transaction history CSV
+    Import {
+        /// Exported CSV (format detected from its header)
+        file: PathBuf,
+    },
+
+    /// Match cached withdrawals to imported deposits and write the reconciliation CSV
+    Match {
+        /// Hours after a withdrawal within which the exchange must credit it
+        #[arg(long, default_value_t = exchange::DEFAULT_WINDOW_HOURS)]
+        window_hours: u64,
+
+        /// Largest amount difference (SOL) still treated as the same transfer
+        #[arg(long, default_value_t = exchange::DEFAULT_TOLERANCE_SOL)]
+        tolerance_sol: f64,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum CardCommand {
+    /// Import charges from a credit card statement CSV
+    Import {
+        /// Exported statement (columns detected from its header)
+        file: PathBuf,
+    },
+
+    /// Match imported charges to card-paid expenses and write the reconciliation CSV
+    Match {
+        /// Payment method of the expenses to reconcile
+        #[arg(long, default_value = card::DEFAULT_PAID_WITH)]
+        paid_with: String,
+
+        /// Days between a charge and its expense still treated as the same bill
+        #[arg(long, default_value_t = card::DEFAULT_WINDOW_DAYS)]
+        window_days: i64,
+
+        /// Largest amount difference (USD) still treated as the same bill
+        #[arg(long, default_value_t = card::DEFAULT_TOLERANCE_USD)]
+        tolerance_usd: f64,
+
+        /// Only reconcile charges in this period (e.g., 2025-01..2025-03)
+        #[arg(long)]
+        period: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum DedupeCommand {
+    /// List unresolved likely duplicates and write duplicates.csv
+    Scan {
+        /// Days apart two rows may be and still count as the same event
+        #[arg(long, default_value_t = dedupe::DEFAULT_WINDOW_DAYS)]
+        window_days: i64,
+    },
+
+    /// Mark a flagged pair as two real, separate rows
+    Dismiss {
+        /// Pair ID from 'dedupe scan'
+        pair_id: String,
+    },
+
+    /// Keep one side of a flagged pair and reject the other (a stored expense)
+    Drop {
+        /// Pair ID from 'dedupe scan'
+        pair_id: String,
+
+        /// Key of the row to drop (e.g. expense:42)
+        key: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum SourcesCommand {
+    /// List registered sources, whether they're enabled, and their last sync
+    List,
+}
+
+#[derive(Subcommand, Debug)]
+enum DiagnosticsCommand {
+    /// Write a zip with versions, row counts, redacted config, recent runs and logs (no financial rows)
+    Bundle {
+        /// Zip path (default: <output-dir>/diagnostics-<timestamp>.zip)
+        #[arg(long)]
+        path: Option<PathBuf>,
+
+        /// Log file to include, redacted (repeatable; e.g. saved `fly logs` output)
+        #[arg(long = "log")]
+        logs: Vec<PathBuf>,
+
+        /// Number of recent ingestion runs to include
+        #[arg(long, default_value = "50")]
+        runs: u32,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum TxCommand {
+    /// Build an unsigned transaction (base64) for signing with an offline key or multisig
+    Build {
+        #[command(subcommand)]
+        operation: TxOperation,
+    },
+}
+
+/// Options shared by every `tx build` operation
+#[derive(clap::Args, Debug)]
+struct TxArgs {
+    /// Amount in SOL
+    #[arg(long)]
+    sol: f64,
+
+    /// Recent or durable-nonce blockhash to sign against (the builder never queries RPC)
+    #[arg(long)]
+    blockhash: String,
+
+    /// Console output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
+}
+
+#[derive(Subcommand, Debug)]
+enum TxOperation {
+    /// Withdraw vote-account rewards (signed by the withdraw authority)
+    WithdrawRewards {
+        /// Destination (default: the withdraw authority)
+        #[arg(long)]
+        to: Option<String>,
+
+        #[command(flatten)]
+        args: TxArgs,
+    },
+
+    /// Transfer SOL to the identity to cover vote fees
+    FundIdentity {
+        /// Source wallet (default: the withdraw authority)
+        #[arg(long)]
+        from: Option<String>,
+
+        #[command(flatten)]
+        args: TxArgs,
+    },
+
+    /// Transfer SOL to the DoubleZero deposit account (doublezero.deposit_account)
+    DoublezeroPrepay {
+        /// Source wallet (default: the identity)
+        #[arg(long)]
+        from: Option<String>,
+
+        #[command(flatten)]
+        args: TxArgs,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum VoteCostsCommand {
+    /// Import vote costs from Dune Analytics JSON export
+    Import {
+        /// Path to JSON file with historical vote cost data
+        file: PathBuf,
+    },
+
+    /// Show cached vote cost data
+    List,
+
+    /// Estimate vote costs for missing epochs
+    Estimate {
+        /// Starting epoch
+        #[arg(long)]
+        start: u64,
+
+        /// Ending epoch
+        #[arg(long)]
+        end: u64,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum LeaderSlotsCommand {
+    /// Import leader slots from Dune Analytics JSON export
+    Import {
+        /// Path to JSON file with historical leader slot data
+        file: PathBuf,
+
+        /// RPC URL (uses private endpoint by default)
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+
+    /// Show cached leader fee data
+    List,
+}
+
+#[derive(Subcommand, Debug)]
+enum ExpenseCommand {
+    /// List all expenses
+    List,
+
+    /// Add a new expense
+    Add {
+        /// Date (YYYY-MM-DD)
+        #[arg(long)]
+        date: String,
+
+        /// Vendor name
+        #[arg(long)]
+        vendor: String,
+
+        /// Category: Hosting, Contractor, Hardware, Software, VoteFees, Other
+        #[arg(long)]
+        category: String,
+
+        /// Description
+        #[arg(long)]
+        description: String,
+
+        /// Amount in USD
+        #[arg(long)]
+        amount: f64,
+
+        /// Payment method (e.g., "Credit Card", "USD", "SOL")
+        #[arg(long, default_value = "USD")]
+        paid_with: String,
+
+        /// Invoice ID (optional)
+        #[arg(long)]
+        invoice_id: Option<String>,
+
+        /// Paying entity id from [[entities]] when another entity paid it (default: validator owner)
+        #[arg(long)]
+        entity: Option<String>,
+    },
+
+    /// Delete an expense by ID
+    Delete {
+        /// Expense ID to delete
+        id: i64,
+    },
+
+    /// Import expenses from CSV file
+    Import {
+        /// Path to CSV file
+        file: PathBuf,
+    },
+
+    /// Export expenses to CSV file
+    Export {
+        /// Path to output CSV file
+        file: PathBuf,
+    },
+
+    /// List expenses awaiting review (imports and Notion sync) and rejected ones
+    Pending,
+
+    /// Approve expenses so the reports count them
+    Approve {
+        /// Expense IDs to approve
+        ids: Vec<i64>,
+
+        /// Approve every pending expense
+        #[arg(long, conflicts_with = "ids")]
+        all_pending: bool,
+    },
+
+    /// Reject expenses (kept, but never counted)
+    Reject {
+        /// Expense IDs to reject
+        #[arg(required = true)]
+        ids: Vec<i64>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum RecurringCommand {
+    /// List all recurring expenses
+    List,
+
+    /// Add a new recurring expense
+    Add {
+        /// Vendor name
+        #[arg(long)]
+        vendor: String,
+
+        /// Category: Hosting, Contractor, Hardware, Software, VoteFees, Other
+        #[arg(long)]
+        category: String,
+
+        /// Description
+        #[arg(long)]
+        description: String,
+
+        /// Monthly amount in USD
+        #[arg(long)]
+        amount: f64,
+
+        /// Payment method (e.g., "Credit Card", "USD", "SOL")
+        #[arg(long, default_value = "USD")]
+        paid_with: String,
+
+        /// Start date (YYYY-MM-DD) - day is used as billing day each month
+        #[arg(long)]
+        start_date: String,
+
+        /// End date (YYYY-MM-DD) - optional, omit for ongoing expenses
+        #[arg(long)]
+        end_date: Option<String>,
+
+        /// Paying entity id from [[entities]] when another entity pays it (default: validator owner)
+        #[arg(long)]
+        entity: Option<String>,
+    },
+
+    /// Delete a recurring expense by ID
+    Delete {
+        /// Recurring expense ID to delete
+        id: i64,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum DuneCommand {
+    /// Import inflation rewards from Dune
+    Rewards {
+        /// Start date for query (YYYY-MM-DD)
+        #[arg(long)]
+        since: String,
+    },
+
+    /// Import leader slot fees from Dune
+    LeaderFees {
+        /// Start date for query (YYYY-MM-DD)
+        #[arg(long)]
+        since: String,
+    },
+
+    /// Import vote transaction costs from Dune
+    VoteCosts {
+        /// Start date for query (YYYY-MM-DD)
+        #[arg(long)]
+        since: String,
+    },
+
+    /// Import SOL transfers from Dune
+    Transfers {
+        /// Start date for query (YYYY-MM-DD)
+        #[arg(long)]
+        since: String,
+    },
+
+    /// Import network-wide median leader fees per block (baseline for `client-uplift`)
+    NetworkFees {
+        /// Start date for query (YYYY-MM-DD)
+        #[arg(long)]
+        since: String,
+    },
+
+    /// Import all data types from Dune
+    All {
+        /// Start date for query (YYYY-MM-DD)
+        #[arg(long)]
+        since: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ImportCommand {
+    /// Fetch per-epoch history and seed reward epochs the cache doesn't have yet
+    Fetch {
+        /// Aggregators to query, in priority order (default: both; Solana Beach is skipped without a key)
+        #[arg(long = "source", value_enum)]
+        sources: Vec<history_import::ImportSource>,
+
+        /// First epoch to import (default: first_reward_epoch)
+        #[arg(long)]
+        from: Option<u64>,
+
+        /// Last epoch to import (default: last completed epoch)
+        #[arg(long)]
+        to: Option<u64>,
+    },
+
+    /// Show imported epochs and whether the on-chain backfill has confirmed them
+    Status {
+        /// Console output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output: OutputFormat,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum PositionCommand {
+    /// Show current position (balance sheet snapshot)
+    Now {
+        /// Console output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output: OutputFormat,
+    },
+
+    /// Run reconciliation check (expected vs actual balances)
+    Reconcile {
+        /// Console output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output: OutputFormat,
+    },
+
+    /// Explain reconciliation variance (diagnostics + untracked assets)
+    Explain,
+
+    /// Show stake accounts owned by validator
+    Stake,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+
+    // Create directories
+    std::fs::create_dir_all(&args.data_dir)?;
+    std::fs::create_dir_all(&args.output_dir)?;
+
+    offline::install(args.offline);
+
+    // Commands that run without a config file keep the defaults (mainnet, UTC). A config
+    // that exists but doesn't parse is an error: falling back would open the mainnet cache.
+    let file_config = if resolve_config_path(args.config.as_ref()).exists() {
+        Some(load_config_file(args.config.as_ref())?)
+    } else {
+        None
+    };
+
+    // Each cluster gets its own cache so testnet/devnet runs never touch mainnet books
+    let cluster = file_config.as_ref().map(|c| c.validator.cluster).unwrap_or_default();
+    cluster::install(cluster);
+
+    // Open cache database (in data directory)
+    let cache_path = args.data_dir.join(cluster.cache_filename());
+    let cache = Cache::open(&cache_path).await?;
+
+    // Operator address tags take precedence over the built-in address labels
+    install_address_tags(&cache).await?;
+
+    // Install the accounting timezone before anything converts block times to dates. The
+    // cache is re-dated under the ingestion lock below if it was built under another one.
+    let mut accounting_tz = None;
+    if let Some(file_config) = &file_config {
+        let tz = file_config.accounting_timezone()?;
+        timezone::install(tz);
+        accounting_tz = Some(tz);
+
+        sources::install(file_config.sources.clone());
+
+        // Community label lists extend the bundled address labels; unreachable lists are skipped
+        if !file_config.label_sources().is_empty() {
+            addresses::load_community_labels(file_config.label_sources()).await;
+        }
+    }
+
+    // Full syncs and the other cache writers (see `writes_cache`) may only run one at a time
+    // across processes (manual CLI runs vs bp-web's scheduler). Offline runs only read, and
+    // a dry run syncs into a scratch copy. Re-dating the cache after a timezone change
+    // rewrites every dated table, so any command takes the lock for it.
+    let timezone_changed = match accounting_tz {
+        Some(tz) => cache.accounting_timezone_changed(tz.name()).await?,
+        None => false,
+    };
+    let needs_lock = timezone_changed || (!args.offline && !args.dry_run && writes_cache(args.command.as_ref()));
+    let lock_holder = format!("{} (pid {})", args.run_trigger, std::process::id());
+    let lock = if needs_lock {
+        let Some(lock) = IngestionLock::acquire(&cache, &lock_holder, args.lock_wait).await? else {
+            let reason = "another ingestion held the cache lock";
+            if let Err(e) = cache.record_skipped_ingestion_run(&args.run_trigger, reason).await {
+                eprintln!("Warning: Failed to record skipped ingestion run: {}", e);
+            }
+            std::process::exit(constants::EXIT_INGESTION_LOCKED);
+        };
+        Some(lock)
+    } else {
+        None
+    };
+
+    // Everything that can fail while the lock is held runs in `run_locked`, so the lock is
+    // released on every path out of it
+    let sync_timezone = accounting_tz.filter(|_| lock.is_some());
+    let result = run_locked(args, &cache, sync_timezone).await;
+    if let Some(lock) = lock {
+        lock.release().await;
+    }
+
+    if let Err(e) = &result
+        && let Some(failure) = e.downcast_ref::<strict::StrictFailure>()
+    {
+        eprintln!("Error: {}", failure);
+        std::process::exit(failure.exit_code());
+    }
+    result
+}
+
+/// The part of a run that happens under the ingestion lock (when one was needed):
+/// re-dating the cache for `sync_timezone`, then the command itself
+async fn run_locked(args: Args, cache: &Cache, sync_timezone: Option<Tz>) -> Result<()> {
+    if let Some(tz) = sync_timezone
+        && let Some(previous) = cache.sync_accounting_timezone(tz.name()).await?
+    {
+        println!(
+            "Accounting timezone changed from {} to {}: re-dated cached rows; daily prices and income lots will be rebuilt.",
+            previous,
+            tz.name()
+        );
+    }
+
+    // Offline, commands that only work against live data stop here instead of timing out
+    if args.offline
+        && let Some(what) = args.command.as_ref().and_then(network_only_command)
+    {
+        offline::print_staleness(&offline::staleness(cache).await?);
+        anyhow::bail!("`{}` needs the network; run it without --offline", what);
+    }
+
+    if args.dry_run && args.command.is_some() {
+        anyhow::bail!("--dry-run previews the full sync; run it without a subcommand");
+    }
+
+    if let Some(command) = args.command {
+        // Handle subcommands
+        handle_command(
+            command,
+            cache,
+            args.config.as_ref(),
+            &args.output_dir,
+            args.strict,
+            args.lock_wait,
+        )
+        .await
+    } else if args.offline {
+        // Nothing is ingested, so there is no run to record
+        run_offline_report(args, cache).await
+    } else if args.dry_run {
+        // Neither is anything written to the real cache
+        run_dry_run(args, cache).await
+    } else {
+        // No subcommand - run the main report generation, recording the attempt in run history
+        let run_id = cache.start_ingestion_run(&args.run_trigger).await?;
+        let mut run_log = IngestionRunLog::default();
+        let result = run_report_generation(args, cache, &mut run_log).await;
+        if let Err(e) = cache
+            .finish_ingestion_run(run_id, &run_log, result.as_ref().err())
+            .await
+        {
+            eprintln!("Warning: Failed to record ingestion run: {}", e);
+        }
+        if result.is_ok() {
+            heartbeat::ping(heartbeat::REPORT).await;
+        }
+        result
+    }
+}
+
+/// Whether the command (None = full sync) bulk-writes the cache and so must hold the
+/// ingestion lock. The keeper takes the lock itself, around each withdrawal it records.
+fn writes_cache(command: Option<&Command>) -> bool {
+    matches!(
+        command,
+        None | Some(Command::Tax { .. })
+            | Some(Command::ImportArchive { .. })
+            | Some(Command::Recategorize { .. })
+            | Some(Command::Prices {
+                action: PricesCommand::Backfill { .. } | PricesCommand::Revalue { .. },
+            })
+            | Some(Command::Import {
+                action: ImportCommand::Fetch { .. },
+            })
+    )
+}
+
+/// The command name if it can only run against live data (RPC, external APIs, the
+/// local metrics endpoint); everything else works from the cache under `--offline`
+fn network_only_command(command: &Command) -> Option<&'static str> {
+    match command {
+        Command::LeaderSlots {
+            action: LeaderSlotsCommand::Import { .. },
+        } => Some("leader-slots import"),
+        Command::Dune { .. } => Some("dune"),
+        Command::Import {
+            action: ImportCommand::Fetch { .. },
+        } => Some("import fetch"),
+        Command::Prices {
+            action: PricesCommand::Backfill { .. },
+        } => Some("prices backfill"),
+        Command::Position { .. } => Some("position"),
+        Command::Metrics { .. } => Some("metrics"),
+        Command::BamCheck { .. } => Some("bam-check"),
+        Command::VerifyIncome { .. } => Some("verify-income"),
+        Command::SyncSheets => Some("sync-sheets"),
+        Command::PublishS3 => Some("publish-s3"),
+        Command::Keeper { .. } => Some("keeper"),
+        _ => None,
+    }
+}
+
+/// Handle expense management subcommands
+async fn handle_command(
+    command: Command,
+    cache: &Cache,
+    config_path: Option<&PathBuf>,
+    output_dir: &Path,
+    strict: bool,
+    lock_wait: u64,
+) -> Result<()> {
+    match command {
+        Command::Expense { action } => handle_expense_command(action, cache, config_path).await,
+        Command::Recurring { action } => handle_recurring_command(action, cache, config_path).await,
+        Command::LeaderSlots { action } => handle_leader_slots_command(action, cache, config_path).await,
+        Command::VoteCosts { action } => handle_vote_costs_command(action, cache).await,
+        Command::Dune { action } => handle_dune_command(action, cache, config_path).await,
+        Command::Import { action } => handle_import_command(action, cache, config_path).await,
+        Command::Position { action } => handle_position_command(action, cache, config_path, strict).await,
+        Command::Prices { action } => handle_prices_command(action, cache, config_path, output_dir, strict).await,
+        Command::Coverage { from, to, gaps_only } => handle_coverage_command(cache, from, to, gaps_only).await,
+        Command::Recategorize { dry_run } => handle_recategorize_command(cache, config_path, dry_run).await,
+        Command::Addresses { action } => handle_addresses_command(action, cache, config_path).await,
+        Command::Runs { action } => handle_runs_command(action, cache).await,
+        Command::Split { action } => handle_split_command(action, cache, config_path).await,
+        Command::Fiat { action } => handle_fiat_command(action, cache, config_path).await,
+        Command::Exchange { action } => handle_exchange_command(action, cache, config_path, output_dir).await,
+        Command::Card { action } => handle_card_command(action, cache, config_path, output_dir).await,
+        Command::Dedupe { action } => handle_dedupe_command(action, cache, config_path, output_dir).await,
+        Command::Sources { action } => handle_sources_command(action, cache, config_path).await,
+        Command::Metrics { action } => handle_metrics_command(action, cache, config_path).await,
+        Command::Summary { period, output } => handle_summary_command(cache, config_path, period, output).await,
+        Command::IncomeLots { period, output } => handle_income_lots_command(cache, config_path, period, output).await,
+        Command::SyncSheets => handle_sync_sheets_command(config_path, output_dir).await,
+        Command::PublishS3 => handle_publish_s3_command(config_path, output_dir).await,
+        Command::CompareReports { dir_a, dir_b, output } => handle_compare_reports_command(&dir_a, &dir_b, output),
+        Command::VerifyReport { file } => handle_verify_report_command(cache, &file).await,
+        Command::IncomeProof { period } => handle_income_proof_command(cache, config_path, output_dir, period).await,
+        Command::VerifyIncome { period, sample, output } => {
+            handle_verify_income_command(cache, config_path, period, sample, output).await
+        }
+        Command::LossHarvest { as_of } => handle_loss_harvest_command(cache, config_path, output_dir, as_of).await,
+        Command::PlanWithdrawal { usd, tax_rate, output } => {
+            handle_plan_withdrawal_command(cache, config_path, usd, tax_rate, output).await
+        }
+        Command::BamCheck { from, to, output } => handle_bam_check_command(cache, config_path, from, to, output).await,
+        Command::ClientUplift { output } => handle_client_uplift_command(cache, config_path, output).await,
+        Command::Keeper { once, dry_run } => {
+            let file_config = load_config_file(config_path)?;
+            let config = config::Config::from_file(&file_config, None)?;
+            let keeper = file_config
+                .keeper
+                .as_ref()
+                .context("No [keeper] section in config.toml (see config.toml.example)")?;
+            keeper::run(cache, &config, keeper, once, dry_run, lock_wait).await
+        }
+        Command::Tx { action } => handle_tx_command(action, config_path),
+        Command::Stats { output } => handle_stats_command(cache, output).await,
+        Command::Diagnostics { action } => handle_diagnostics_command(action, cache, config_path, output_dir).await,
+        Command::ExportArchive { path, anonymize } => {
+            handle_export_archive_command(cache, config_path, output_dir, path, anonymize).await
+        }
+        Command::ImportArchive {
+            path,
+            replace,
+            config_out,
+        } => handle_import_archive_command(cache, &path, replace, config_out.as_deref()).await,
+        Command::Tui { refresh } => {
+            let file_config = load_config_file(config_path)?;
+            let config = config::Config::from_file(&file_config, None)?;
+            tui::run(cache, &config, refresh).await
+        }
+        Command::Tax {
+            period,
+            rpc_url,
+            no_cache,
+            verbose,
+        } => handle_tax_command(cache, config_path, output_dir, period, rpc_url, no_cache, verbose).await,
+        Command::TaxPackage { period, path } => {
+            handle_tax_package_command(cache, config_path, output_dir, period, path).await
+        }
+    }
+}
+
+/// Handle price cache subcommands
+async fn handle_prices_command(
+    action: PricesCommand,
+    cache: &Cache,
+    config_path: Option<&PathBuf>,
+    output_dir: &Path,
+    strict: bool,
+) -> Result<()> {
+    match action {
+        PricesCommand::Backfill { from, to } => {
+            let from_date = parse_yyyy_mm_dd("--from", &from)?;
+            let to_date = match to {
+                Some(to) => parse_yyyy_mm_dd("--to", &to)?,
+                None => timezone::today(),
+            };
+            anyhow::ensure!(from_date <= to_date, "--from must not be after --to");
+
+            let file_config = load_config_file(config_path)?;
+            anyhow::ensure!(
+                file_config.validator.cluster.is_mainnet(),
+                "Price backfill is mainnet-only; {} runs report USD values as N/A",
+                file_config.validator.cluster.as_str()
+            );
+            let existing = cache.get_prices().await?;
+            let missing = prices::missing_price_dates(&existing, from_date, to_date);
+
+            if missing.is_empty() {
+                println!(
+                    "All dates from {} to {} already have cached prices.",
+                    from_date, to_date
+                );
+                return Ok(());
+            }
+
+            let (first, last) = (missing[0], missing[missing.len() - 1]);
+            println!(
+                "Backfilling {} missing daily prices between {} and {}...",
+                missing.len(),
+                first,
+                last
+            );
+
+            let fetched = prices::fetch_price_range(
+                first,
+                last,
+                &file_config.api_keys.coingecko,
+                file_config.api_keys.dune.as_deref(),
+            )
+            .await?;
+
+            let wanted: std::collections::HashSet<String> =
+                missing.iter().map(|d| d.format("%Y-%m-%d").to_string()).collect();
+            let filled: prices::PriceQuotes = fetched.into_iter().filter(|(d, _)| wanted.contains(d)).collect();
+            cache.store_price_quotes(&filled).await?;
+
+            println!("  Filled {} of {} missing dates", filled.len(), wanted.len());
+
+            let mut still_missing: Vec<&String> = wanted.iter().filter(|d| !filled.contains_key(*d)).collect();
+            if !still_missing.is_empty() {
+                still_missing.sort();
+                println!("  Still missing ({}):", still_missing.len());
+                for date in still_missing.iter().take(20) {
+                    println!("    {}", date);
+                }
+                if still_missing.len() > 20 {
+                    println!("    ... and {} more", still_missing.len() - 20);
+                }
+            }
+
+            if !filled.is_empty() {
+                println!("\nRun 'validator-accounting prices revalue' to regenerate reports with the new prices.");
+            }
+            Ok(())
+        }
+
+        PricesCommand::Revalue { period } => {
+            let config = load_report_config(config_path)?;
+            let period = period.resolve(&config)?;
+
+            println!("Revaluing reports from cached data...");
+            let inputs = CachedReportInputs::load(cache, &config).await?;
+            println!(
+                "  {} reward epochs, {} transfers, {} expenses, {} daily prices, {} hourly prices\n",
+                inputs.rewards.len(),
+                inputs.transfer_count,
+                inputs.expenses.len(),
+                inputs.prices.len(),
+                inputs.hourly_prices.len()
+            );
+
+            let report_data = inputs.report_data(&config);
+            let metadata = build_report_metadata(cache, config_path, &report_data, period.as_ref()).await?;
+            let fallback_rows = reports::generate_all_reports(output_dir, &report_data, period.as_ref(), &metadata)?;
+            record_income_lots(cache, &report_data).await?;
+            record_fingerprints(cache, output_dir).await?;
+            hooks::run(&config.hooks, output_dir, &metadata).await?;
+            reports::print_summary(&report_data, period.as_ref());
+
+            println!("\nDone! Reports written to: {}", output_dir.display());
+
+            if strict {
+                let coverage = cache.get_epoch_coverage(Some(config.first_reward_epoch), None).await?;
+                strict::StrictFailure::check(
+                    [
+                        strict::check_epoch_coverage(&coverage),
+                        strict::check_fallback_prices(fallback_rows),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+                )?;
+            }
+            Ok(())
+        }
+
+        PricesCommand::Verify {
+            from,
+            to,
+            against,
+            tolerance_pct,
+        } => {
+            let from_date = parse_yyyy_mm_dd("--from", &from)?;
+            let to_date = match to {
+                Some(to) => parse_yyyy_mm_dd("--to", &to)?,
+                None => timezone::today(),
+            };
+            anyhow::ensure!(from_date <= to_date, "--from must not be after --to");
+            anyhow::ensure!(tolerance_pct >= 0.0, "--tolerance-pct must not be negative");
+
+            let cached = cache
+                .get_audited_prices(
+                    &from_date.format("%Y-%m-%d").to_string(),
+                    &to_date.format("%Y-%m-%d").to_string(),
+                )
+                .await?;
+            if cached.is_empty() {
+                println!("No cached prices between {} and {}.", from_date, to_date);
+                return Ok(());
+            }
+
+            let file_config = load_config_file(config_path)?;
+            println!(
+                "Fetching {} prices from {} to {}...",
+                against.as_str(),
+                from_date,
+                to_date
+            );
+            let other = prices::fetch_price_range_from(
+                against,
+                from_date,
+                to_date,
+                &file_config.api_keys.coingecko,
+                file_config.api_keys.dune.as_deref(),
+            )
+            .await?;
+
+            price_audit::print(&price_audit::verify(&cached, &other, against, tolerance_pct));
+            Ok(())
+        }
+    }
+}
+
+/// Load config for commands that report from the cache, deriving the DoubleZero deposit PDA if unset
+fn load_report_config(config_path: Option<&PathBuf>) -> Result<config::Config> {
+    let file_config = load_config_file(config_path)?;
+    let mut config = config::Config::from_file(&file_config, None)?;
+
+    // Deriving the PDA asks the DoubleZero CLI, which queries the cluster
+    #[allow(clippy::collapsible_if)]
+    if config.doublezero_enabled && config.doublezero_deposit_account.is_none() && !offline::enabled() {
+        if let Some(pda) = doublezero::derive_deposit_account_from_cli(&config.identity, &config.rpc_url) {
+            config.doublezero_deposit_account = Some(pda);
+        }
+    }
+
+    Ok(config)
+}
+
+/// Everything `ReportData` borrows, loaded from the cache only (no RPC or price API calls)
+struct CachedReportInputs {
+    rewards: Vec<transactions::EpochReward>,
+    transfer_count: usize,
+    categorized: transactions::CategorizedTransfers,
+    mev_claims: Vec<jito::MevClaim>,
+    bam_claims: Vec<bam::BamClaim>,
+    leader_fees: Vec<leader_fees::EpochLeaderFees>,
+    network_fee_medians: Vec<client_uplift::NetworkFeeMedian>,
+    doublezero_fees: Vec<doublezero::DoubleZeroFee>,
+    vote_costs: Vec<vote_costs::EpochVoteCost>,
+    epoch_performance: Vec<unit_economics::EpochPerformance>,
+    delegator_stake: Vec<delegators::DelegatorStake>,
+    expenses: Vec<Expense>,
+    fiat_deposits: Vec<fiat::FiatDeposit>,
+    prices: prices::PriceCache,
+    hourly_prices: prices::HourlyPriceCache,
+    aggregates: cache::MonthlyAggregates,
+    exceptions: Vec<validation::DataException>,
+}
+
+impl CachedReportInputs {
+    async fn load(cache: &Cache, config: &config::Config) -> Result<Self> {
+        // SQLite binds epochs as i64, so cap the open-ended range there.
+        let start_epoch = config.first_reward_epoch;
+        let end_epoch = i64::MAX as u64;
+
+        let rewards = cache.get_epoch_rewards(start_epoch, end_epoch).await?;
+        let transfers = cache.get_all_transfers().await?;
+        let mut categorized = transactions::categorize_transfers(&transfers, config);
+        categorized.splits = cache.get_withdrawal_splits().await?;
+
+        let mut expenses = cache.get_approved_expenses().await?;
+        let recurring = cache.get_recurring_expenses().await?;
+        if !recurring.is_empty()
+            && let Some(expanded) = expand_recurring_for_report(&recurring, &rewards, &config.bootstrap_date)
+        {
+            expenses.extend(expanded);
+        }
+
+        let hourly_prices = if config.hourly_pricing_enabled {
+            cache.get_hourly_prices().await?
+        } else {
+            prices::HourlyPriceCache::new()
+        };
+        let prices = cache.get_prices().await?;
+        expenses.extend(owner_draw::split_expenses(&categorized, &prices, &hourly_prices));
+        expenses.extend(failed_fees::monthly_expenses(
+            &cache.get_failed_tx_fees().await?,
+            &prices,
+        ));
+        expenses.extend(ops_fees::monthly_expenses(&cache.get_ops_fees().await?, &prices));
+
+        let mut inputs = Self {
+            transfer_count: transfers.len(),
+            categorized,
+            mev_claims: cache.get_mev_claims(start_epoch, end_epoch).await?,
+            bam_claims: cache.get_bam_claims(start_epoch, end_epoch).await?,
+            leader_fees: cache.get_leader_fees(start_epoch, end_epoch).await?,
+            network_fee_medians: cache.get_network_fee_medians(start_epoch, end_epoch).await?,
+            doublezero_fees: cache.get_doublezero_fees(start_epoch, end_epoch).await?,
+            vote_costs: cache.get_vote_costs(start_epoch, end_epoch).await?,
+            epoch_performance: cache.get_epoch_performance(start_epoch, end_epoch).await?,
+            delegator_stake: cache.get_delegator_stake(start_epoch, end_epoch).await?,
+            expenses,
+            fiat_deposits: cache.get_fiat_deposits().await?,
+            prices,
+            hourly_prices,
+            aggregates: cache.get_monthly_aggregates(start_epoch, end_epoch).await?,
+            rewards,
+            exceptions: Vec::new(),
+        };
+        inputs.exceptions = validation::quarantine(
+            validation::ReportRows {
+                rewards: &mut inputs.rewards,
+                mev_claims: &mut inputs.mev_claims,
+                bam_claims: &mut inputs.bam_claims,
+                leader_fees: &mut inputs.leader_fees,
+                doublezero_fees: &mut inputs.doublezero_fees,
+                vote_costs: &mut inputs.vote_costs,
+                expenses: &mut inputs.expenses,
+                prices: &mut inputs.prices,
+                aggregates: &mut inputs.aggregates,
+            },
+            &timezone::today().to_string(),
+        );
+        Ok(inputs)
+    }
+
+    fn report_data<'a>(&'a self, config: &'a config::Config) -> reports::ReportData<'a> {
+        reports::ReportData {
+            rewards: &self.rewards,
+            categorized: &self.categorized,
+            mev_claims: &self.mev_claims,
+            bam_claims: &self.bam_claims,
+            leader_fees: &self.leader_fees,
+            network_fee_medians: &self.network_fee_medians,
+            doublezero_fees: &self.doublezero_fees,
+            vote_costs: &self.vote_costs,
+            epoch_performance: &self.epoch_performance,
+            delegator_stake: &self.delegator_stake,
+            expenses: &self.expenses,
+            fiat_deposits: &self.fiat_deposits,
+            prices: &self.prices,
+            hourly_prices: &self.hourly_prices,
+            aggregates: &self.aggregates,
+            exceptions: &self.exceptions,
+            config,
+        }
+    }
+}
+
+/// Print the P&L summary from cached data (no files written)
+async fn handle_summary_command(
+    cache: &Cache,
+    config_path: Option<&PathBuf>,
+    period: PeriodArgs,
+    output: OutputFormat,
+) -> Result<()> {
+    let config = load_report_config(config_path)?;
+    let period = period.resolve(&config)?;
+    let inputs = CachedReportInputs::load(cache, &config).await?;
+    let report_data = inputs.report_data(&config);
+
+    if output.is_json() {
+        output::print_json(&reports::compute_summary(&report_data, period.as_ref()))
+    } else {
+        reports::print_summary(&report_data, period.as_ref());
+        Ok(())
+    }
+}
+
+async fn handle_metrics_command(action: MetricsCommand, cache: &Cache, config_path: Option<&PathBuf>) -> Result<()> {
+    let file_config = load_config_file(config_path)?;
+    let config = config::Config::from_file(&file_config, None)?;
+    let metrics = file_config
+        .metrics
+        .as_ref()
+        .context("No [metrics] section in config.toml (see config.toml.example)")?;
+
+    match action {
+        MetricsCommand::Scrape { watch } => {
+            let mut interval = tokio::time::interval(Duration::from_secs(metrics.interval_secs.max(1)));
+            loop {
+                interval.tick().await;
+                match client_metrics::scrape(cache, &config, metrics).await {
+                    Ok(sample) => println!(
+                        "Slot {} (epoch {}): produced {}, skipped {}, vote latency {}",
+                        sample.slot,
+                        sample.epoch,
+                        sample.produced.map_or_else(|| "-".to_string(), |v| v.to_string()),
+                        sample.skipped.map_or_else(|| "-".to_string(), |v| v.to_string()),
+                        sample
+                            .vote_latency_slots
+                            .map_or_else(|| "-".to_string(), |v| format!("{:.2} slots", v))
+                    ),
+                    Err(e) if watch => eprintln!("Scrape failed: {:#}", e),
+                    Err(e) => return Err(e),
+                }
+                if !watch {
+                    return Ok(());
+                }
+            }
+        }
+
+        MetricsCommand::Check { output } => {
+            let rpc_client = rpc::new_rpc_client(&config.rpc_url, CommitmentConfig::confirmed());
+            let epoch = rpc_client.get_epoch_info()?.epoch;
+            let samples = cache.get_metric_samples(epoch).await?;
+            let Some(check) = client_metrics::cross_check(&rpc_client, &config, &samples)? else {
+                anyhow::bail!(
+                    "Need at least two metrics samples in epoch {} (run `metrics scrape` a few times first)",
+                    epoch
+                );
+            };
+
+            if output.is_json() {
+                return output::print_json(&serde_json::json!({
+                    "check": check,
+                    "matches": check.matches(),
+                }));
+            }
+
+            let fmt = |v: Option<u64>| v.map_or_else(|| "- (counter missing or reset)".to_string(), |v| v.to_string());
+            println!(
+                "Epoch {}, slots {}-{} ({} samples)\n",
+                check.epoch,
+                check.first_slot,
+                check.last_slot,
+                samples.len()
+            );
+            println!("{:<10} {:>12} {:>12}", "", "Client", "Chain");
+            println!(
+                "{:<10} {:>12} {:>12}",
+                "Produced",
+                fmt(check.client_produced),
+                check.chain_produced
+            );
+            println!(
+                "{:<10} {:>12} {:>12}",
+                "Skipped",
+                fmt(check.client_skipped),
+                check.chain_skipped
+            );
+            if check.matches() {
+                println!("\nClient metrics agree with the chain.");
+            } else {
+                println!(
+                    "\nClient metrics disagree with the chain: check the metric names in [metrics], or whether the client restarted."
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Recompute expected BAM payouts and flag epochs whose reward never arrived
+async fn handle_bam_check_command(
+    cache: &Cache,
+    config_path: Option<&PathBuf>,
+    from: Option<u64>,
+    to: Option<u64>,
+    output: OutputFormat,
+) -> Result<()> {
+    let file_config = load_config_file(config_path)?;
+    let config = config::Config::from_file(&file_config, None)?;
+    if !config.bam_enabled {
+        anyhow::bail!("BAM tracking is disabled ([bam] enabled = false)");
+    }
+    let params = bam_verify::BamParams::from_config(file_config.bam.as_ref());
+
+    let rpc_client = rpc::new_rpc_client(&config.rpc_url, CommitmentConfig::confirmed());
+    let current_epoch = rpc_client.get_epoch_info()?.epoch;
+    let start_epoch = from.unwrap_or(config.bam_first_epoch);
+    let end_epoch = to.unwrap_or(current_epoch);
+
+    let checks = bam_verify::verify(cache, &config, &params, start_epoch, end_epoch, current_epoch).await?;
+    if output.is_json() {
+        output::print_json(&checks)
+    } else {
+        bam_verify::print_checks(&checks, &params);
+        Ok(())
+    }
+}
+
+/// Compare realized block revenue with the vanilla-client baseline (cached data only)
+async fn handle_client_uplift_command(
+    cache: &Cache,
+    config_path: Option<&PathBuf>,
+    output: OutputFormat,
+) -> Result<()> {
+    let config = load_report_config(config_path)?;
+    let start_epoch = config.first_reward_epoch;
+    let end_epoch = i64::MAX as u64;
+
+    let leader_fees = cache.get_leader_fees(start_epoch, end_epoch).await?;
+    let mev_claims = cache.get_mev_claims(start_epoch, end_epoch).await?;
+    let medians = cache.get_network_fee_medians(start_epoch, end_epoch).await?;
+    let rows = client_uplift::compare(&leader_fees, &mev_claims, &medians);
+
+    if output.is_json() {
+        return output::print_json(&serde_json::json!({
+            "epochs": rows,
+            "totals": client_uplift::totals(&rows),
+        }));
+    }
+
+    let compared: std::collections::HashSet<u64> = rows.iter().map(|r| r.epoch).collect();
+    let missing: Vec<u64> = leader_fees
+        .iter()
+        .map(|f| f.epoch)
+        .filter(|e| !compared.contains(e))
+        .collect();
+    client_uplift::print_comparison(&rows, &missing);
+    Ok(())
+}
+
+/// Record income lots for every receipt in the report data (when enabled)
+async fn record_income_lots(cache: &Cache, report_data: &reports::ReportData<'_>) -> Result<()> {
+    if !report_data.config.income_lots_enabled {
+        return Ok(());
+    }
+    let (lots, skipped) = income_lots::build_income_lots(report_data);
+    cache.upsert_income_lots(&lots).await?;
+    println!("  Recorded {} income lots at FMV on receipt", lots.len());
+    if skipped > 0 {
+        println!(
+            "  Warning: {} receipts have no date and were not recorded as lots",
+            skipped
+        );
+    }
+    Ok(())
+}
+
+/// Push the generated reports to Google Sheets
+async fn handle_sync_sheets_command(config_path: Option<&PathBuf>, output_dir: &Path) -> Result<()> {
+    let file_config = load_config_file(config_path)?;
+    let gsheets = file_config
+        .gsheets
+        .as_ref()
+        .context("No [gsheets] section in config.toml")?;
+    let tabs = exports::gsheets::sync(gsheets, output_dir).await?;
+    println!("Synced {} tab(s) to Google Sheets", tabs);
+    Ok(())
+}
+
+/// Upload the generated reports to S3-compatible storage
+async fn handle_publish_s3_command(config_path: Option<&PathBuf>, output_dir: &Path) -> Result<()> {
+    let file_config = load_config_file(config_path)?;
+    let s3 = file_config.s3.as_ref().context("No [s3] section in config.toml")?;
+    let (prefix, objects) = exports::s3::publish(s3, output_dir).await?;
+    println!("Uploaded {} object(s) to s3://{}/{}/", objects, s3.bucket, prefix);
+    Ok(())
+}
+
+/// Compare the ledgers of two report directories
+fn handle_compare_reports_command(dir_a: &Path, dir_b: &Path, output: OutputFormat) -> Result<()> {
+    let comparison = report_compare::compare(&report_compare::load_set(dir_a)?, &report_compare::load_set(dir_b)?);
+    if output.is_json() {
+        return output::print_json(&comparison);
+    }
+    report_compare::print_comparison(&comparison, dir_a, dir_b);
+    Ok(())
+}
+
+/// Check a report against the fingerprints recorded when reports were generated
+async fn handle_verify_report_command(cache: &Cache, file: &Path) -> Result<()> {
+    let fingerprint = fingerprint::fingerprint_file(file)?;
+    let matches = cache.find_report_fingerprints(&fingerprint.root).await?;
+    if let Some(first) = matches.first() {
+        println!(
+            "Verified: {} matches {} as generated at {} UTC ({} rows, root {})",
+            file.display(),
+            first.file_name,
+            first.generated_at,
+            first.leaves,
+            fingerprint.root
+        );
+        return Ok(());
+    }
+
+    println!(
+        "NOT VERIFIED: {} matches no generated report (root {}, {} rows)",
+        file.display(),
+        fingerprint.root,
+        fingerprint.leaves
+    );
+    let name = file
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if let Some(latest) = cache.get_report_fingerprints_for(&name).await?.first() {
+        println!(
+            "  Latest {} was generated at {} UTC with {} rows; this copy was edited or comes from elsewhere.",
+            latest.file_name, latest.generated_at, latest.leaves
+        );
+    }
+    anyhow::bail!("{} does not match any generated report", file.display())
+}
+
+/// Income proof rows from the cache, limited to the period
+async fn load_income_proofs(
+    cache: &Cache,
+    config: &config::Config,
+    period: Option<&period::ReportPeriod>,
+) -> Result<Vec<income_proof::IncomeProof>> {
+    let inputs = CachedReportInputs::load(cache, config).await?;
+    Ok(income_proof::build_proofs(&inputs.report_data(config))
+        .into_iter()
+        .filter(|proof| period.is_none_or(|p| proof.date.as_deref().is_some_and(|date| p.contains(date))))
+        .collect())
+}
+
+/// Write income_proof.csv: every income lamport with its evidence and a verify command
+async fn handle_income_proof_command(
+    cache: &Cache,
+    config_path: Option<&PathBuf>,
+    output_dir: &Path,
+    period: PeriodArgs,
+) -> Result<()> {
+    let config = load_report_config(config_path)?;
+    let period = period.resolve(&config)?;
+    let proofs = load_income_proofs(cache, &config, period.as_ref()).await?;
+    if proofs.is_empty() {
+        println!("No income in the cache for this period. Run 'validator-accounting' to fetch it.");
+        return Ok(());
+    }
+
+    std::fs::create_dir_all(output_dir)?;
+    income_proof::write_proof_csv(output_dir, &proofs, &config)?;
+    println!(
+        "{} income receipts; re-check a sample against RPC with 'validator-accounting verify-income'",
+        proofs.len()
+    );
+    Ok(())
+}
+
+/// Re-check a sample of income proof rows against RPC; fails when any row mismatches
+async fn handle_verify_income_command(
+    cache: &Cache,
+    config_path: Option<&PathBuf>,
+    period: PeriodArgs,
+    sample: usize,
+    output: OutputFormat,
+) -> Result<()> {
+    let config = load_report_config(config_path)?;
+    let period = period.resolve(&config)?;
+    let proofs = load_income_proofs(cache, &config, period.as_ref()).await?;
+    let sampled = income_proof::sample(&proofs, sample);
+
+    let rpc_client = rpc::new_rpc_client(&config.rpc_url, CommitmentConfig::confirmed());
+    let checks = income_proof::verify(&rpc_client, &config, &sampled).await;
+    if output.is_json() {
+        output::print_json(&checks)?;
+    } else {
+        income_proof::print_checks(&checks, proofs.len());
+    }
+
+    let mismatches = checks
+        .iter()
+        .filter(|c| matches!(c.outcome, income_proof::CheckOutcome::Mismatch(_)))
+        .count();
+    if mismatches > 0 {
+        anyhow::bail!("{} income row(s) disagree with the chain", mismatches);
+    }
+    Ok(())
+}
+
+/// List cached income lots with per-source totals
+async fn handle_income_lots_command(
+    cache: &Cache,
+    config_path: Option<&PathBuf>,
+    period: PeriodArgs,
+    output: OutputFormat,
+) -> Result<()> {
+    let config = load_report_config(config_path)?;
+    let period = period.resolve(&config)?;
+    let lots: Vec<income_lots::IncomeLot> = cache
+        .get_income_lots()
+        .await?
+        .into_iter()
+        .filter(|lot| period.as_ref().is_none_or(|p| p.contains(&lot.date)))
+        .collect();
+
+    if output.is_json() {
+        return output::print_json(&lots);
+    }
+
+    if lots.is_empty() {
+        if config.income_lots_enabled {
+            println!("No income lots recorded. Run 'validator-accounting' or 'prices revalue' to record them.");
+        } else {
+            println!("Income lots are disabled. Set `income_lots = true` under [reporting] in config.toml.");
+        }
+        return Ok(());
+    }
+
+    println!(
+        "{:<12} {:<12} {:<20} {:>14} {:<8} {:>14} {:>10} {:>12}",
+        "Date", "Source", "Reference", "Amount", "Asset", "SOL", "Price", "FMV (USD)"
+    );
+    println!("{}", "-".repeat(110));
+    let mut totals: std::collections::BTreeMap<&str, (f64, f64)> = std::collections::BTreeMap::new();
+    for lot in &lots {
+        println!(
+            "{:<12} {:<12} {:<20} {:>14.6} {:<8} {:>14.6} {:>10.2} {:>12.2}",
+            lot.date,
+            lot.source,
+            shorten_address(&lot.reference),
+            lot.amount,
+            lot.asset,
+            lot.amount_sol,
+            lot.price_usd,
+            lot.fmv_usd
+        );
+        let total = totals.entry(lot.source.as_str()).or_insert((0.0, 0.0));
+        total.0 += lot.amount_sol;
+        total.1 += lot.fmv_usd;
+    }
+
+    println!(
+        "
+Totals by source:"
+    );
+    for (source, (sol, usd)) in &totals {
+        println!("  {:<12} {:>14.6} SOL  ${:>12.2}", source, sol, usd);
+    }
+    Ok(())
+}
+
+/// Build an unsigned transaction from configured addresses (no RPC, no keys)
+fn handle_tx_command(action: TxCommand, config_path: Option<&PathBuf>) -> Result<()> {
+    let TxCommand::Build { operation } = action;
+    let file_config = load_config_file(config_path)?;
+    let config = config::Config::from_file(&file_config, None)?;
+    let parse_pubkey = |label: &str, value: Option<String>, default: Pubkey| match value {
+        Some(s) => Pubkey::from_str(&s).with_context(|| format!("Invalid {} address '{}'", label, s)),
+        None => Ok(default),
+    };
+
+    let (tx, output) = match operation {
+        TxOperation::WithdrawRewards { to, args } => {
+            let to = parse_pubkey("--to", to, config.withdraw_authority)?;
+            let tx = tx_builder::withdraw_rewards(
+                &config.vote_account,
+                &config.withdraw_authority,
+                &to,
+                tx_builder::sol_to_lamports(args.sol)?,
+                tx_builder::parse_blockhash(&args.blockhash)?,
+            )?;
+            (tx, args.output)
+        }
+        TxOperation::FundIdentity { from, args } => {
+            let from = parse_pubkey("--from", from, config.withdraw_authority)?;
+            let tx = tx_builder::transfer(
+                "fund-identity",
+                &from,
+                &config.identity,
+                tx_builder::sol_to_lamports(args.sol)?,
+                tx_builder::parse_blockhash(&args.blockhash)?,
+            )?;
+            (tx, args.output)
+        }
+        TxOperation::DoublezeroPrepay { from, args } => {
+            let deposit = config
+                .doublezero_deposit_account
+                .context("Set doublezero.deposit_account in config.toml to build a DoubleZero prepayment")?;
+            let from = parse_pubkey("--from", from, config.identity)?;
+            let tx = tx_builder::transfer(
+                "doublezero-prepay",
+                &from,
+                &deposit,
+                tx_builder::sol_to_lamports(args.sol)?,
+                tx_builder::parse_blockhash(&args.blockhash)?,
+            )?;
+            (tx, args.output)
+        }
+    };
+
+    if output.is_json() {
+        output::print_json(&tx)
+    } else {
+        tx_builder::print_unsigned(&tx);
+        Ok(())
+    }
+}
+
+/// Plan a USD withdrawal against the seed capital still to be returned
+async fn handle_plan_withdrawal_command(
+    cache: &Cache,
+    config_path: Option<&PathBuf>,
+    usd: f64,
+    tax_rate: Option<f64>,
+    output: OutputFormat,
+) -> Result<()> {
+    let config = load_report_config(config_path)?;
+    let transfers = cache.get_all_transfers().await?;
+    let mut categorized = transactions::categorize_transfers(&transfers, &config);
+    categorized.splits = cache.get_withdrawal_splits().await?;
+    let remaining_capital = tax_report::remaining_seed_capital(&categorized, &config);
+
+    let prices = cache.get_prices().await?;
+    let (price, price_source) = prices::get_price_with_source(&prices, &timezone::today().to_string());
+    let plan = withdrawal_plan::plan_withdrawal(
+        usd,
+        price,
+        price_source,
+        remaining_capital,
+        tax_rate.or(config.estimated_tax_rate),
+    )?;
+
+    if output.is_json() {
+        output::print_json(&plan)
+    } else {
+        withdrawal_plan::print_plan(&plan);
+        Ok(())
+    }
+}
+
+/// Handle USD cash account subcommands
+async fn handle_fiat_command(action: FiatCommand, cache: &Cache, config_path: Option<&PathBuf>) -> Result<()> {
+    match action {
+        FiatCommand::Deposit { date, amount, memo } => {
+            NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                .with_context(|| format!("Invalid date '{}'. Use YYYY-MM-DD", date))?;
+            anyhow::ensure!(amount.is_finite() && amount != 0.0, "Amount must be a non-zero number");
+            let deposit = fiat::FiatDeposit {
+                id: None,
+                date,
+                amount_usd: amount,
+                memo,
+            };
+            let id = cache.add_fiat_deposit(&deposit).await?;
+            println!(
+                "Recorded deposit #{}: ${:.2} on {}{}",
+                id,
+                deposit.amount_usd,
+                deposit.date,
+                if deposit.memo.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({})", deposit.memo)
+                }
+            );
+            Ok(())
+        }
+        FiatCommand::Delete { id } => {
+            if cache.delete_fiat_deposit(id).await? {
+                println!("Deleted deposit #{}", id);
+            } else {
+                println!("No deposit with ID {}", id);
+            }
+            Ok(())
+        }
+        FiatCommand::List => {
+            let deposits = cache.get_fiat_deposits().await?;
+            if deposits.is_empty() {
+                println!(
+                    "No deposits recorded. Add one with 'validator-accounting fiat deposit --date <YYYY-MM-DD> --amount <USD>'."
+                );
+                return Ok(());
+            }
+            println!("{:<6} {:<12} {:>12} Memo", "ID", "Date", "Amount");
+            println!("{}", "-".repeat(50));
+            for deposit in &deposits {
+                println!(
+                    "{:<6} {:<12} {:>12.2} {}",
+                    deposit.id.unwrap_or_default(),
+                    deposit.date,
+                    deposit.amount_usd,
+                    deposit.memo
+                );
+            }
+            Ok(())
+        }
+        FiatCommand::Balance => {
+            let config = load_report_config(config_path)?;
+            let fiat_config = config
+                .fiat
+                .as_ref()
+                .context("No [fiat] section in config.toml (see config.toml.example)")?;
+            let inputs = CachedReportInputs::load(cache, &config).await?;
+            let statement = fiat::statement(fiat_config, &inputs.expenses, &inputs.fiat_deposits, timezone::today());
+            println!(
+                "{:<8} {:>12} {:>12} {:>12} {:>12}",
+                "Month", "Opening", "Deposits", "Expenses", "Closing"
+            );
+            println!("{}", "-".repeat(60));
+            for m in &statement.months {
+                println!(
+                    "{:<8} {:>12.2} {:>12.2} {:>12.2} {:>12.2}",
+                    m.month, m.opening_usd, m.deposits_usd, m.expenses_usd, m.closing_usd
+                );
+            }
+            fiat::print(&statement);
+            Ok(())
+        }
+    }
+}
+
+/// Write the advisory loss-harvesting / wash-sale window CSV from income lots
+async fn handle_loss_harvest_command(
+    cache: &Cache,
+    config_path: Option<&PathBuf>,
+    output_dir: &Path,
+    as_of: Option<String>,
+) -> Result<()> {
+    let config = load_report_config(config_path)?;
+    let as_of = match as_of {
+        Some(date) => NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+            .with_context(|| format!("Invalid --as-of date '{}'. Use YYYY-MM-DD", date))?,
+        None => timezone::today(),
+    };
+
+    let lots = cache.get_income_lots().await?;
+    if lots.is_empty() {
+        println!("No income lots recorded. Set `income_lots = true` under [reporting] and run 'validator-accounting'.");
+        return Ok(());
+    }
+
+    let transfers = cache.get_all_transfers().await?;
+    let categorized = transactions::categorize_transfers(&transfers, &config);
+    let dispositions: Vec<loss_harvest::Disposition> = categorized
+        .withdrawals
+        .iter()
+        .filter_map(|t| {
+            Some(loss_harvest::Disposition {
+                date: t.date.clone()?,
+                amount_sol: t.amount_sol,
+            })
+        })
+        .collect();
+
+    let prices = cache.get_prices().await?;
+    let (price, price_source) = prices::get_price_with_source(&prices, &as_of.to_string());
+    let held = loss_harvest::held_lots(&lots, &dispositions, as_of, price, config.bam_jitosol_rate);
+
+    println!("Loss-harvesting analysis (advisory, not tax advice) as of {}", as_of);
+    println!(
+        "  {} income lots, {} withdrawals, {} lots still held at {:.2} USD/SOL ({})",
+        lots.len(),
+        dispositions.len(),
+        held.len(),
+        price,
+        price_source.as_str()
+    );
+    let total_loss = loss_harvest::write_advisory_csv(output_dir, &lots, &held, as_of, price, price_source.as_str())?;
+    let loss_lots = held.iter().filter(|h| h.harvestable_loss_usd() > 0.0).count();
+    println!(
+        "  Estimated harvestable loss: ${:.2} across {} lots",
+        total_loss, loss_lots
+    );
+    Ok(())
+}
+
+/// Print cache row counts
+async fn handle_stats_command(cache: &Cache, output: OutputFormat) -> Result<()> {
+    let stats = cache.stats().await?;
+    if output.is_json() {
+        output::print_json(&stats)
+    } else {
+        println!("Cache: {}", stats);
+        Ok(())
+    }
+}
+
+/// Handle diagnostics subcommands
+async fn handle_diagnostics_command(
+    action: DiagnosticsCommand,
+    cache: &Cache,
+    config_path: Option<&PathBuf>,
+    output_dir: &Path,
+) -> Result<()> {
+    let DiagnosticsCommand::Bundle { path, logs, runs } = action;
+    let path = path.unwrap_or_else(|| {
+        output_dir.join(format!(
+            "diagnostics-{}.zip",
+            chrono::Utc::now().format("%Y%m%d-%H%M%S")
+        ))
+    });
+    let mut anonymizer = archive::Anonymizer::default();
+
+    // Config first, so its addresses get the lowest stand-in numbers
+    let config_file = resolve_config_path(config_path);
+    let config_toml = if config_file.exists() {
+        let text = std::fs::read_to_string(&config_file)?;
+        let redacted = anonymizer.anonymize_config(archive::redact_config(&text)?)?;
+        Some(toml::to_string_pretty(&redacted)?)
+    } else {
+        None
+    };
+
+    let mut schema = std::collections::BTreeMap::new();
+    let mut row_counts = std::collections::BTreeMap::new();
+    for table in cache.archive_table_names().await? {
+        row_counts.insert(table.clone(), cache.table_row_count(&table).await?);
+        schema.insert(table.clone(), cache.table_columns(&table).await?);
+    }
+
+    let mut recent_runs = cache.get_ingestion_runs(runs).await?;
+    diagnostics::redact_runs(&mut recent_runs, &mut anonymizer);
+
+    let mut log_files = Vec::new();
+    for log in &logs {
+        let text = std::fs::read_to_string(log).with_context(|| format!("Failed to read {}", log.display()))?;
+        let name = log
+            .file_name()
+            .map_or_else(|| "log.txt".to_string(), |n| n.to_string_lossy().into_owned());
+        log_files.push((name, diagnostics::redact_log(&text, &mut anonymizer)));
+    }
+
+    let summary = diagnostics::BundleSummary {
+        created_at: chrono::Utc::now().to_rfc3339(),
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        cluster: cluster::current().as_str().to_string(),
+        sqlite_version: cache.sqlite_version().await?,
+        schema_fingerprint: diagnostics::schema_fingerprint(&schema),
+        schema,
+        row_counts,
+        config_found: config_toml.is_some(),
+        logs: log_files.iter().map(|(name, _)| name.clone()).collect(),
+    };
+    diagnostics::write(&path, &summary, config_toml.as_deref(), &recent_runs, &log_files)?;
+
+    let failed = recent_runs.iter().filter(|r| !r.errors.is_empty()).count();
+    println!("Wrote {}", path.display());
+    println!(
+        "  {} tables, {} recent runs ({} with errors), {} log files",
+        summary.schema.len(),
+        recent_runs.len(),
+        failed,
+        log_files.len()
+    );
+    println!("Addresses, signatures and credentials are redacted; review the zip before attaching it.");
+    Ok(())
+}
+
+/// Write every cache table to a portable archive
+async fn handle_export_archive_command(
+    cache: &Cache,
+    config_path: Option<&PathBuf>,
+    output_dir: &Path,
+    path: Option<PathBuf>,
+    anonymize: bool,
+) -> Result<()> {
+    let path = path.unwrap_or_else(|| {
+        output_dir.join(format!(
+            "validator-archive-{}.tar.zst",
+            chrono::Utc::now().format("%Y-%m-%d")
+        ))
+    });
+
+    // The archive is still useful without a config (e.g. a cache copied off a server)
+    let config_file = resolve_config_path(config_path);
+    let (mut config, validator) = if config_file.exists() {
+        let text = std::fs::read_to_string(&config_file)?;
+        let file_config = FileConfig::load(&config_file)?;
+        (Some(archive::redact_config(&text)?), Some(file_config.validator))
+    } else {
+        println!("No config found; archiving the cache only.");
+        (None, None)
+    };
+
+    let mut anonymizer = archive::Anonymizer::default();
+    let mut anonymize_str = |s: String| {
+        let mut value = serde_json::Value::String(s);
+        if anonymize {
+            anonymizer.anonymize(&mut value);
+        }
+        value.as_str().map(str::to_string)
+    };
+    let vote_account = validator.as_ref().and_then(|v| anonymize_str(v.vote_account.clone()));
+    let identity = validator.as_ref().and_then(|v| anonymize_str(v.identity.clone()));
+
+    let mut tables = std::collections::BTreeMap::new();
+    let mut manifest_tables = Vec::new();
+    for name in cache.archive_table_names().await? {
+        let (columns, mut rows) = cache.export_table(&name).await?;
+        if anonymize {
+            rows.iter_mut().for_each(|row| anonymizer.anonymize(row));
+        }
+        manifest_tables.push(archive::ArchiveTable {
+            name: name.clone(),
+            columns,
+            rows: rows.len() as u64,
+        });
+        tables.insert(name, rows);
+    }
+    if anonymize && let Some(table) = config.take() {
+        config = Some(anonymizer.anonymize_config(table)?);
+    }
+
+    let manifest = archive::ArchiveManifest {
+        format_version: archive::ARCHIVE_FORMAT_VERSION,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        cluster: cluster::current().as_str().to_string(),
+        anonymized: anonymize,
+        vote_account,
+        identity,
+        commission_percent: validator.as_ref().map(|v| v.commission_percent),
+        tables: manifest_tables,
+    };
+    archive::write(&path, &manifest, config.as_ref(), &tables)?;
+
+    let rows: u64 = manifest.tables.iter().map(|t| t.rows).sum();
+    println!(
+        "Archived {} tables ({} rows){} to {}",
+        manifest.tables.len(),
+        rows,
+        if anonymize { ", anonymized," } else { "" },
+        path.display()
+    );
+    Ok(())
+}
+
+/// Load an archive into the cache
+async fn handle_import_archive_command(
+    cache: &Cache,
+    path: &Path,
+    replace: bool,
+    config_out: Option<&Path>,
+) -> Result<()> {
+    let archive = archive::read(path)?;
+    let manifest = &archive.manifest;
+
+    let cluster = cluster::current();
+    anyhow::ensure!(
+        manifest.cluster == cluster.as_str(),
+        "Archive is from {} but this cache is {}; set [validator] cluster to match",
+        manifest.cluster,
+        cluster.as_str()
+    );
+
+    println!(
+        "Archive v{} from validator-accounting {}, created {}",
+        manifest.format_version, manifest.tool_version, manifest.created_at
+    );
+    if let Some(vote_account) = &manifest.vote_account {
+        println!("  Vote account: {}", vote_account);
+    }
+    if manifest.anonymized {
+        println!("  Anonymized: addresses will not match a real config");
+    }
+
+    let written = cache.import_tables(&archive.tables, replace).await?;
+    for (table, rows) in &written {
+        println!("  {:<28} {:>8} rows", table, rows);
+    }
+    let skipped: Vec<&String> = archive
+        .tables
+        .keys()
+        .filter(|t| !written.iter().any(|(w, _)| w == *t))
+        .collect();
+    if !skipped.is_empty() {
+        println!(
+            "  Skipped tables unknown to this version: {}",
+            skipped.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", ")
+        );
+    }
+
+    if let Some(config_out) = config_out {
+        let config = archive.config_toml.as_deref().context("Archive has no config.toml")?;
+        anyhow::ensure!(
+            !config_out.exists(),
+            "{} already exists; not overwriting",
+            config_out.display()
+        );
+        std::fs::write(config_out, config)?;
+        println!(
+            "Wrote config to {} (add [api_keys] before running reports)",
+            config_out.display()
+        );
+    }
+
+    let rows: u64 = written.iter().map(|(_, r)| r).sum();
+    println!("Imported {} rows into {} tables", rows, written.len());
+    Ok(())
+}
+
+/// Print the epoch coverage matrix
+async fn handle_coverage_command(cache: &Cache, from: Option<u64>, to: Option<u64>, gaps_only: bool) -> Result<()> {
+    let coverage = cache.get_epoch_coverage(from, to).await?;
+
+    if coverage.is_empty() {
+        println!("No per-epoch data cached.");
+        println!("\nRun 'validator-accounting' to fetch rewards, fees and claims first.");
+        return Ok(());
+    }
+
+    let mark = |present: bool, applies: bool| match (applies, present) {
+        (false, _) => "n/a",
+        (true, true) => "ok",
+        (true, false) => "--",
+    };
+
+    println!(
+        "{:<8} {:<12} {:>8} {:>8} {:>6} {:>6} {:>8} {:>6}",
+        "Epoch", "Date", "Rewards", "Leader", "MEV", "BAM", "Votes", "DZ"
+    );
+    println!("{}", "-".repeat(70));
+
+    let mut gap_epochs = 0;
+    let mut gaps_by_source: std::collections::BTreeMap<&str, usize> = std::collections::BTreeMap::new();
+    for row in &coverage {
+        let missing = row.missing_sources();
+        if !missing.is_empty() {
+            gap_epochs += 1;
+            for source in &missing {
+                *gaps_by_source.entry(source).or_insert(0) += 1;
+            }
+        } else if gaps_only {
+            continue;
+        }
+
+        println!(
+            "{:<8} {:<12} {:>8} {:>8} {:>6} {:>6} {:>8} {:>6}",
+            row.epoch,
+            transactions::epoch_to_date(row.epoch),
+            mark(row.rewards, true),
+            mark(row.leader_fees, true),
+            mark(row.mev, true),
+            mark(row.bam, row.bam_applies()),
+            mark(row.vote_costs, true),
+            mark(row.doublezero, row.doublezero_applies()),
+        );
+    }
+
+    println!("{}", "-".repeat(70));
+    println!(
+        "{} epoch(s) {}-{}, {} with gaps",
+        coverage.len(),
+        coverage[0].epoch,
+        coverage[coverage.len() - 1].epoch,
+        gap_epochs
+    );
+    for (source, count) in &gaps_by_source {
+        println!("  {:<12} missing in {} epoch(s)", source, count);
+    }
+    Ok(())
+}
+
+/// Re-label cached transfers with the current address book and config, reporting what changed
+async fn handle_recategorize_command(cache: &Cache, config_path: Option<&PathBuf>, dry_run: bool) -> Result<()> {
+    let config = load_report_config(config_path)?;
+    let mut transfers = cache.get_all_transfers().await?;
+
+    if transfers.is_empty() {
+        println!("No cached transfers to recategorize.");
+        return Ok(());
+    }
+
+    let before = transactions::categorize_transfers(&transfers, &config);
+
+    let mut changed: Vec<(transactions::SolTransfer, transactions::SolTransfer)> = Vec::new();
+    for transfer in &mut transfers {
+        let previous = transfer.clone();
+        if transactions::relabel_transfer(transfer, &config) {
+            changed.push((previous, transfer.clone()));
+        }
+    }
+
+    if changed.is_empty() {
+        println!(
+            "All {} cached transfers already match current labels and rules.",
+            transfers.len()
+        );
+        return Ok(());
+    }
+
+    let after = transactions::categorize_transfers(&transfers, &config);
+
+    let mut transitions: std::collections::BTreeMap<String, usize> = std::collections::BTreeMap::new();
+    for (old, new) in &changed {
+        if old.from_category != new.from_category {
+            let key = format!("from {:?} -> {:?}", old.from_category, new.from_category);
+            *transitions.entry(key).or_insert(0) += 1;
+        }
+        if old.to_category != new.to_category {
+            let key = format!("to   {:?} -> {:?}", old.to_category, new.to_category);
+            *transitions.entry(key).or_insert(0) += 1;
+        }
+    }
+
+    println!("{} of {} cached transfers changed:\n", changed.len(), transfers.len());
+    println!("{:<12} {:<14} {:>12}  Change", "Date", "Signature", "Amount");
+    println!("{}", "-".repeat(90));
+    for (old, new) in changed.iter().take(50) {
+        let side = |old_label: &str, new_label: &str| {
+            if old_label == new_label {
+                new_label.to_string()
+            } else {
+                format!("{} => {}", old_label, new_label)
+            }
+        };
+        println!(
+            "{:<12} {:<14} {:>12}  {} -> {}",
+            new.date.as_deref().unwrap_or("-"),
+            shorten_address(&new.signature),
+            format!("{:.4} SOL", new.amount_sol),
+            side(&old.from_label, &new.from_label),
+            side(&old.to_label, &new.to_label),
+        );
+    }
+    if changed.len() > 50 {
+        println!("... and {} more", changed.len() - 50);
+    }
+
+    if !transitions.is_empty() {
+        println!("\nCategory changes:");
+        for (transition, count) in &transitions {
+            println!("  {:<50} {}", transition, count);
+        }
+    }
+
+    let builtin = rules::builtin_rules(&config);
+    let mut rule_hits: std::collections::BTreeMap<&str, usize> = std::collections::BTreeMap::new();
+    for transfer in &transfers {
+        if let Some(direction) = rules::TransferDirection::of(transfer, &config) {
+            let rule = rules::matching_rule(transfer, direction, &config, &builtin);
+            *rule_hits
+                .entry(rule.map_or("(no rule: other)", |r| r.name.as_str()))
+                .or_insert(0) += 1;
+        }
+    }
+    println!("\nRules matched:");
+    for (rule, count) in &rule_hits {
+        println!("  {:<50} {}", rule, count);
+    }
+
+    println!("\nReport buckets (before -> after):");
+    for ((bucket, old_count), (_, new_count)) in transfer_bucket_counts(&before)
+        .into_iter()
+        .zip(transfer_bucket_counts(&after))
+    {
+        let marker = if old_count != new_count { "  *" } else { "" };
+        println!("  {:<22} {:>5} -> {:<5}{}", bucket, old_count, new_count, marker);
+    }
+
+    if dry_run {
+        println!("\nDry run: cache not modified.");
+    } else {
+        let updated_transfers: Vec<transactions::SolTransfer> = changed.into_iter().map(|(_, new)| new).collect();
+        let updated = cache.update_transfer_labels(&updated_transfers).await?;
+        println!("\nUpdated {} cached transfers.", updated);
+        println!("Run 'validator-accounting prices revalue' to regenerate reports with the new categories.");
+    }
+    Ok(())
+}
+
+/// Load operator address tags from the cache into the address label registry
+async fn install_address_tags(cache: &Cache) -> Result<()> {
+    let tags = cache
+        .get_address_tags()
+        .await?
+        .into_iter()
+        .filter_map(|tag| {
+            let pubkey = Pubkey::from_str(&tag.address).ok()?;
+            let label = addresses::AddressLabel {
+                category: tag.category,
+                name: tag.label,
+                description: None,
+                source: Some(addresses::LabelSource::Tag),
+            };
+            Some((pubkey, label))
+        })
+        .collect();
+    addresses::install_tags(tags);
+    Ok(())
+}
+
+async fn handle_addresses_command(
+    action: AddressesCommand,
+    cache: &Cache,
+    config_path: Option<&PathBuf>,
+) -> Result<()> {
+    match action {
+        AddressesCommand::Tag {
+            address,
+            label,
+            category,
+        } => {
+            let config = load_report_config(config_path)?;
+            let pubkey = Pubkey::from_str(&address).with_context(|| format!("Invalid address: {}", address))?;
+            if config.is_our_account(&pubkey) || config.is_personal_wallet(&pubkey) {
+                anyhow::bail!(
+                    "{} is one of your own accounts in config.toml; tags are for counterparties",
+                    address
+                );
+            }
+            let category: addresses::AddressCategory = category.parse()?;
+            cache.upsert_address_tag(&address, &label, category).await?;
+            println!("Tagged {} as {} ({:?})", address, label, category);
+            relabel_cached_transfers(cache, &config).await
+        }
+        AddressesCommand::Untag { address } => {
+            let config = load_report_config(config_path)?;
+            if !cache.delete_address_tag(&address).await? {
+                println!("{} is not tagged.", address);
+                return Ok(());
+            }
+            println!("Removed tag for {}", address);
+            relabel_cached_transfers(cache, &config).await
+        }
+        AddressesCommand::List => {
+            let tags = cache.get_address_tags().await?;
+            if tags.is_empty() {
+                println!(
+                    "No tagged addresses. Add one with 'validator-accounting addresses tag <ADDRESS> --label <LABEL>'."
+                );
+                return Ok(());
+            }
+            println!("{:<46} {:<14} {:<20} Label", "Address", "Category", "Tagged");
+            println!("{}", "-".repeat(100));
+            for tag in &tags {
+                println!(
+                    "{:<46} {:<14} {:<20} {}",
+                    tag.address,
+                    format!("{:?}", tag.category),
+                    tag.created_at,
+                    tag.label
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Reinstall address tags and rewrite labels on every cached transfer they affect
+async fn relabel_cached_transfers(cache: &Cache, config: &config::Config) -> Result<()> {
+    install_address_tags(cache).await?;
+    let mut transfers = cache.get_all_transfers().await?;
+    let changed: Vec<transactions::SolTransfer> = transfers
+        .iter_mut()
+        .filter_map(|transfer| transactions::relabel_transfer(transfer, config).then(|| transfer.clone()))
+        .collect();
+    let updated = cache.update_transfer_labels(&changed).await?;
+    println!("Relabeled {} cached transfers.", updated);
+    if updated > 0 {
+        println!("Run 'validator-accounting prices revalue' to regenerate reports with the new labels.");
+    }
+    Ok(())
+}
+
+/// Transfer counts per report bucket, in ledger order
+fn transfer_bucket_counts(categorized: &transactions::CategorizedTransfers) -> [(&'static str, usize); 9] {
+    [
+        ("Seeding", categorized.seeding.len()),
+        ("SFDP reimbursements", categorized.sfdp_reimbursements.len()),
+        ("MEV deposits", categorized.mev_deposits.len()),
+        ("DoubleZero payments", categorized.doublezero_payments.len()),
+        ("Donations", categorized.donations.len()),
+        ("Vote funding", categorized.vote_funding.len()),
+        ("Withdrawals", categorized.withdrawals.len()),
+        ("Rent deposits/refunds", categorized.rent.len()),
+        ("Other", categorized.other.len()),
+    ]
+}
+
+/// Handle ingestion run history subcommands
+/// Record, remove or list owner draw / business expense splits of withdrawals
+async fn handle_split_command(action: SplitCommand, cache: &Cache, config_path: Option<&PathBuf>) -> Result<()> {
+    let config = load_report_config(config_path)?;
+    let transfers = cache.get_all_transfers().await?;
+    let categorized = transactions::categorize_transfers(&transfers, &config);
+
+    match action {
+        SplitCommand::Set {
+            signature,
+            business_pct,
+            category,
+            description,
+        } => {
+            owner_draw::validate_business_pct(business_pct)?;
+            let Some(withdrawal) = categorized.withdrawals.iter().find(|t| t.signature == signature) else {
+                anyhow::bail!(
+                    "{} is not a cached withdrawal. Run 'validator-accounting' to refresh transfers first.",
+                    signature
+                );
+            };
+            let split = owner_draw::WithdrawalSplit {
+                signature,
+                business_pct,
+                category: parse_category(&category)?,
+                description,
+            };
+            cache.upsert_withdrawal_split(&split).await?;
+            println!(
+                "Split {:.6} SOL withdrawal on {}: {:.6} SOL owner draw, {:.6} SOL {} expense ({})",
+                withdrawal.amount_sol,
+                withdrawal.date.as_deref().unwrap_or("-"),
+                split.draw_sol(withdrawal),
+                split.business_sol(withdrawal),
+                split.category,
+                split.description
+            );
+            println!("Regenerate reports to apply it.");
+            Ok(())
+        }
+        SplitCommand::Remove { signature } => {
+            if cache.delete_withdrawal_split(&signature).await? {
+                println!("Removed split for {}", signature);
+            } else {
+                println!("{} is not split.", signature);
+            }
+            Ok(())
+        }
+        SplitCommand::List => {
+            let splits = cache.get_withdrawal_splits().await?;
+            if splits.is_empty() {
+                println!(
+                    "No split withdrawals. Add one with 'validator-accounting split set <SIGNATURE> --business-pct <PCT> --category <CATEGORY> --description <TEXT>'."
+                );
+                return Ok(());
+            }
+            println!(
+                "{:<12} {:<20} {:>12} {:>12} {:>12} {:<10} Description",
+                "Date", "Signature", "Amount", "Draw", "Business", "Category"
+            );
+            println!("{}", "-".repeat(110));
+            for split in &splits {
+                let withdrawal = categorized.withdrawals.iter().find(|t| t.signature == split.signature);
+                let short_sig = &split.signature[..split.signature.len().min(16)];
+                match withdrawal {
+                    Some(w) => println!(
+                        "{:<12} {:<20} {:>12.6} {:>12.6} {:>12.6} {:<10} {}",
+                        w.date.as_deref().unwrap_or("-"),
+                        short_sig,
+                        w.amount_sol,
+                        split.draw_sol(w),
+                        split.business_sol(w),
+                        format!("{}", split.category),
+                        split.description
+                    ),
+                    None => println!(
+                        "{:<12} {:<20} {:>12} {:>12} {:>12} {:<10} {} (withdrawal not cached; ignored)",
+                        "-",
+                        short_sig,
+                        "-",
+                        "-",
+                        format!("{:.0}%", split.business_pct),
+                        format!("{}", split.category),
+                        split.description
+                    ),
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Import a card statement, or reconcile imported charges against card-paid expenses
+async fn handle_card_command(
+    action: CardCommand,
+    cache: &Cache,
+    config_path: Option<&PathBuf>,
+    output_dir: &Path,
+) -> Result<()> {
+    match action {
+        CardCommand::Import { file } => {
+            let lines = card::import_statement(&file)?;
+            let inserted = cache.store_statement_lines(&lines).await?;
+            println!(
+                "Imported {} charges from {} ({} new, {} already cached)",
+                lines.len(),
+                file.display(),
+                inserted,
+                lines.len() - inserted
+            );
+            Ok(())
+        }
+        CardCommand::Match {
+            paid_with,
+            window_days,
+            tolerance_usd,
+            period,
+        } => {
+            let period = period.as_deref().map(period::ReportPeriod::parse).transpose()?;
+            let mut lines = cache.get_statement_lines().await?;
+            if let Some(period) = &period {
+                lines.retain(|l| period.contains(&l.date));
+            }
+            if lines.is_empty() {
+                println!("No card statement charges imported. Add some with 'validator-accounting card import <CSV>'.");
+                return Ok(());
+            }
+
+            let config = load_report_config(config_path)?;
+            let inputs = CachedReportInputs::load(cache, &config).await?;
+            let matches = card::reconcile(&lines, &inputs.expenses, &paid_with, window_days, tolerance_usd);
+
+            std::fs::create_dir_all(output_dir)?;
+            card::write_reconciliation_csv(output_dir, &matches, &paid_with, window_days)?;
+
+            let count = |status: card::CardStatus| matches.iter().filter(|m| m.status == status).count();
+            println!(
+                "{} charges: {} matched, {} matched on amount only, {} missing an expense; {} expense(s) with no charge",
+                lines.len(),
+                count(card::CardStatus::Matched),
+                count(card::CardStatus::AmountOnly),
+                count(card::CardStatus::MissingExpense),
+                count(card::CardStatus::PossibleDuplicate)
+            );
+            for m in &matches {
+                match (m.status, m.line, m.expense) {
+                    (card::CardStatus::MissingExpense, Some(line), _) => println!(
+                        "  ! missing expense   {} {:>10.2} USD  {}",
+                        line.date, line.amount_usd, line.description
+                    ),
+                    (card::CardStatus::PossibleDuplicate, _, Some(expense)) => println!(
+                        "  ! possible duplicate {} {:>10.2} USD  {} (expense {})",
+                        expense.date,
+                        expense.amount_usd,
+                        expense.vendor,
+                        expense
+                            .id
+                            .map(|id| format!("#{}", id))
+                            .unwrap_or_else(|| "recurring".to_string())
+                    ),
+                    _ => {}
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Import exchange deposit history, or match it to cached withdrawals
+async fn handle_exchange_command(
+    action: ExchangeCommand,
+    cache: &Cache,
+    config_path: Option<&PathBuf>,
+    output_dir: &Path,
+) -> Result<()> {
+    match action {
+        ExchangeCommand::Import { file } => {
+            let deposits = exchange::import_deposits(&file)?;
+            let inserted = cache.store_exchange_deposits(&deposits).await?;
+            println!(
+                "Imported {} SOL deposits from {} ({} new, {} already cached)",
+                deposits.len(),
+                file.display(),
+                inserted,
+                deposits.len() - inserted
+            );
+            Ok(())
+        }
+        ExchangeCommand::Match {
+            window_hours,
+            tolerance_sol,
+        } => {
+            let config = load_report_config(config_path)?;
+            let deposits = cache.get_exchange_deposits().await?;
+            if deposits.is_empty() {
+                println!("No exchange deposits imported. Add some with 'validator-accounting exchange import <CSV>'.");
+                return Ok(());
+            }
+
+            let transfers = cache.get_all_transfers().await?;
+            let categorized = transactions::categorize_transfers(&transfers, &config);
+            let matches = exchange::match_withdrawals(&categorized.withdrawals, &deposits, window_hours, tolerance_sol);
+
+            let receipts: Vec<(&str, &exchange::ExchangeDeposit)> = matches
+                .iter()
+                .filter_map(|m| match &m.status {
+                    exchange::MatchStatus::ConfirmedReceived(d) => Some((m.withdrawal.signature.as_str(), d)),
+                    _ => None,
+                })
+                .collect();
+            cache.replace_withdrawal_receipts(&receipts).await?;
+
+            std::fs::create_dir_all(output_dir)?;
+            exchange::write_reconciliation_csv(output_dir, &matches, &deposits, window_hours)?;
+
+            let unmatched: Vec<_> = matches
+                .iter()
+                .filter(|m| m.status == exchange::MatchStatus::Unmatched)
+                .collect();
+            println!(
+                "{} withdrawals: {} confirmed received, {} unmatched",
+                matches.len(),
+                receipts.len(),
+                unmatched.len()
+            );
+            for m in &unmatched {
+                println!(
+                    "  ! {} {:>14.6} SOL to {} ({})",
+                    m.withdrawal.date.as_deref().unwrap_or("-"),
+                    m.withdrawal.amount_sol,
+                    m.withdrawal.to_label,
+                    m.withdrawal.signature
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Scan for likely duplicates, or resolve a flagged pair
+async fn handle_dedupe_command(
+    action: DedupeCommand,
+    cache: &Cache,
+    config_path: Option<&PathBuf>,
+    output_dir: &Path,
+) -> Result<()> {
+    let config = load_report_config(config_path)?;
+    let mut inputs = CachedReportInputs::load(cache, &config).await?;
+    // Pending imports are where duplicates usually hide, so scan them too
+    inputs.expenses.extend(
+        cache
+            .get_unapproved_expenses()
+            .await?
+            .into_iter()
+            .filter(|e| e.status == ExpenseStatus::Pending),
+    );
+    let entries = dedupe::collect_entries(&inputs.report_data(&config));
+    let resolved = cache.get_resolved_duplicate_ids().await?;
+
+    match action {
+        DedupeCommand::Scan { window_days } => {
+            let pairs = dedupe::find_duplicates(&entries, window_days, &resolved);
+            if pairs.is_empty() {
+                println!("No likely duplicates across {} income/expense rows.", entries.len());
+                return Ok(());
+            }
+
+            println!(
+                "{:<9} {:<8} {:<11} {:<12} {:<24} {:>14}  Counterparty",
+                "Pair", "Side", "Date", "Source", "Key", "Amount"
+            );
+            println!("{}", "-".repeat(100));
+            for pair in &pairs {
+                for (i, entry) in [pair.first, pair.second].into_iter().enumerate() {
+                    println!(
+                        "{:<9} {:<8} {:<11} {:<12} {:<24} {:>14.6}  {}",
+                        if i == 0 { pair.id.as_str() } else { "" },
+                        entry.side.as_str(),
+                        entry.date,
+                        entry.source,
+                        truncate(&entry.key, 23),
+                        entry.amount,
+                        truncate(&entry.counterparty, 30),
+                    );
+                }
+            }
+            println!("{}", "-".repeat(100));
+            println!("{} likely duplicate pair(s)", pairs.len());
+            std::fs::create_dir_all(output_dir)?;
+            dedupe::write_duplicates_csv(output_dir, &pairs)?;
+            println!(
+                "
+Resolve with 'dedupe dismiss <PAIR>' (not a duplicate) or 'dedupe drop <PAIR> <KEY>'"
+            );
+            Ok(())
+        }
+
+        DedupeCommand::Dismiss { pair_id } => {
+            let pairs = dedupe::find_duplicates(&entries, i64::MAX, &resolved);
+            let pair = pairs
+                .iter()
+                .find(|p| p.id == pair_id)
+                .with_context(|| format!("No unresolved duplicate pair {}", pair_id))?;
+            cache
+                .resolve_duplicate(&pair.id, &pair.first.key, &pair.second.key, "not_duplicate")
+                .await?;
+            println!(
+                "Dismissed {}: {} and {} are separate rows",
+                pair.id, pair.first.key, pair.second.key
+            );
+            Ok(())
+        }
 
-    /// Run reconciliation check (expected vs actual balances)
-    Reconcile,
+        DedupeCommand::Drop { pair_id, key } => {
+            let pairs = dedupe::find_duplicates(&entries, i64::MAX, &resolved);
+            let pair = pairs
+                .iter()
+                .find(|p| p.id == pair_id)
+                .with_context(|| format!("No unresolved duplicate pair {}", pair_id))?;
+            if !pair.contains(&key) {
+                anyhow::bail!(
+                    "{} is not part of pair {} ({} / {})",
+                    key,
+                    pair.id,
+                    pair.first.key,
+                    pair.second.key
+                );
+            }
+            let Some(id) = key.strip_prefix("expense:").and_then(|id| id.parse::<i64>().ok()) else {
+                anyhow::bail!(
+                    "{} is derived from on-chain data or recurring templates; drop the other side or fix its source",
+                    key
+                );
+            };
+            cache.set_expense_status(&[id], ExpenseStatus::Rejected).await?;
+            cache
+                .resolve_duplicate(&pair.id, &pair.first.key, &pair.second.key, &format!("dropped {}", key))
+                .await?;
+            println!("Rejected expense {} as a duplicate (pair {})", id, pair.id);
+            Ok(())
+        }
+    }
+}
 
-    /// Explain reconciliation variance (diagnostics + untracked assets)
-    Explain,
+async fn handle_runs_command(action: RunsCommand, cache: &Cache) -> Result<()> {
+    match action {
+        RunsCommand::List { limit, verbose } => {
+            let runs = cache.get_ingestion_runs(limit).await?;
 
-    /// Show stake accounts owned by validator
-    Stake,
-}
+            if runs.is_empty() {
+                println!("No ingestion runs recorded.");
+                println!("\nRuns are recorded each time 'validator-accounting' generates reports.");
+                return Ok(());
+            }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let args = Args::parse();
+            println!(
+                "{:<6} {:<20} {:<20} {:<10} {:<10} {:>8} {:>7}",
+                "ID", "Started (UTC)", "Finished (UTC)", "Trigger", "Status", "Rows", "Errors"
+            );
+            println!("{}", "-".repeat(87));
 
-    // Create directories
-    std::fs::create_dir_all(&args.data_dir)?;
-    std::fs::create_dir_all(&args.output_dir)?;
+            for run in &runs {
+                let total_rows: u64 = run.row_counts.values().sum();
+                println!(
+                    "{:<6} {:<20} {:<20} {:<10} {:<10} {:>8} {:>7}",
+                    run.id,
+                    run.started_at,
+                    run.finished_at.as_deref().unwrap_or("-"),
+                    truncate(&run.triggered_by, 10),
+                    run.status,
+                    total_rows,
+                    run.errors.len(),
+                );
 
-    // Open cache database (in data directory)
-    let cache_path = args.data_dir.join(constants::CACHE_FILENAME);
-    let cache = Cache::open(&cache_path).await?;
+                if verbose {
+                    for (source, rows) in &run.row_counts {
+                        println!("         {:<24} {:>8}", source, rows);
+                    }
+                    for error in &run.errors {
+                        println!("         ! {}", error);
+                    }
+                }
+            }
 
-    // Handle subcommands
-    if let Some(command) = args.command {
-        return handle_command(command, &cache, args.config.as_ref(), &args.output_dir).await;
+            println!("\n{} run(s) shown", runs.len());
+            Ok(())
+        }
     }
-
-    // No subcommand - run the main report generation
-    run_report_generation(args, cache).await
 }
 
-/// Handle expense management subcommands
-async fn handle_command(
-    command: Command,
-    cache: &Cache,
-    config_path: Option<&PathBuf>,
-    output_dir: &Path,
-) -> Result<()> {
-    match command {
-        Command::Expense { action } => handle_expense_command(action, cache).await,
-        Command::Recurring { action } => handle_recurring_command(action, cache).await,
-        Command::LeaderSlots { action } => handle_leader_slots_command(action, cache, config_path).await,
-        Command::VoteCosts { action } => handle_vote_costs_command(action, cache).await,
-        Command::Dune { action } => handle_dune_command(action, cache, config_path).await,
-        Command::Position { action } => handle_position_command(action, cache, config_path).await,
-        Command::Tax {
-            year,
-            rpc_url,
-            no_cache,
-            verbose,
-        } => handle_tax_command(cache, config_path, output_dir, year, rpc_url, no_cache, verbose).await,
+/// Handle data source subcommands
+async fn handle_sources_command(action: SourcesCommand, cache: &Cache, config_path: Option<&PathBuf>) -> Result<()> {
+    match action {
+        SourcesCommand::List => {
+            let file_config = load_config_file(config_path)?;
+            let registry = sources::Registry::builtin();
+            let health: std::collections::HashMap<String, cache::SourceHealth> = cache
+                .get_source_health()
+                .await?
+                .into_iter()
+                .map(|h| (h.name.clone(), h))
+                .collect();
+
+            println!(
+                "{:<14} {:<8} {:<9} {:<20} {:>8} {:>8}  Description",
+                "Source", "Enabled", "Status", "Last success (UTC)", "Rows", "Failures"
+            );
+            println!("{}", "-".repeat(100));
+
+            for status in sources::statuses(&registry, &file_config) {
+                let enabled = match (status.enabled, status.missing_config) {
+                    (false, _) => "no",
+                    (true, Some(_)) => "no cfg",
+                    (true, None) => "yes",
+                };
+                let h = health.get(status.name);
+                println!(
+                    "{:<14} {:<8} {:<9} {:<20} {:>8} {:>8}  {}",
+                    status.name,
+                    enabled,
+                    h.map(|h| h.status()).unwrap_or("never"),
+                    h.and_then(|h| h.last_success_at.as_deref()).unwrap_or("-"),
+                    h.map(|h| h.last_rows.to_string()).unwrap_or_else(|| "-".to_string()),
+                    h.map(|h| h.consecutive_failures).unwrap_or(0),
+                    status.description
+                );
+                if let Some(reason) = status.missing_config.filter(|_| status.enabled) {
+                    println!("{:<14} ! not configured: {}", "", reason);
+                }
+                if let Some(h) = h.filter(|h| h.consecutive_failures > 0)
+                    && let Some(error) = &h.last_error
+                {
+                    println!(
+                        "{:<14} ! {} {}",
+                        "",
+                        h.last_error_at.as_deref().unwrap_or(""),
+                        truncate(error, 80)
+                    );
+                }
+            }
+
+            println!("\nEnable or disable a source with [sources.<name>] enabled = true|false in config.toml.");
+            Ok(())
+        }
     }
 }
 
@@ -642,7 +3459,7 @@ async fn handle_vote_costs_command(action: VoteCostsCommand, cache: &Cache) -> R
 }
 
 /// Handle expense subcommands
-async fn handle_expense_command(action: ExpenseCommand, cache: &Cache) -> Result<()> {
+async fn handle_expense_command(action: ExpenseCommand, cache: &Cache, config_path: Option<&PathBuf>) -> Result<()> {
     match action {
         ExpenseCommand::List => {
             let expenses = cache.get_expenses().await?;
@@ -651,33 +3468,49 @@ async fn handle_expense_command(action: ExpenseCommand, cache: &Cache) -> Result
                 println!("\nUse 'validator-accounting expense add' to add expenses");
                 println!("Or 'validator-accounting expense import <file.csv>' to import from CSV");
             } else {
-                println!(
-                    "{:<4} {:<12} {:<15} {:<12} {:>10}  Description",
-                    "ID", "Date", "Vendor", "Category", "Amount"
-                );
-                println!("{}", "-".repeat(80));
-
-                let mut total = 0.0;
-                for expense in &expenses {
-                    let id = expense.id.map(|i| i.to_string()).unwrap_or_default();
+                print_expense_table(&expenses);
+                let unapproved = expenses.iter().filter(|e| e.status != ExpenseStatus::Approved).count();
+                if unapproved > 0 {
                     println!(
-                        "{:<4} {:<12} {:<15} {:<12} ${:>9.2}  {}",
-                        id,
-                        expense.date,
-                        truncate(&expense.vendor, 14),
-                        expense.category,
-                        expense.amount_usd,
-                        truncate(&expense.description, 30),
+                        "{} not approved (excluded from reports); see 'validator-accounting expense pending'",
+                        unapproved
                     );
-                    total += expense.amount_usd;
                 }
-                println!("{}", "-".repeat(80));
-                println!("{:>54} ${:>9.2}", "Total:", total);
-                println!("\n{} expense(s)", expenses.len());
             }
             Ok(())
         }
 
+        ExpenseCommand::Pending => {
+            let expenses = cache.get_unapproved_expenses().await?;
+            if expenses.is_empty() {
+                println!("No expenses awaiting review.");
+            } else {
+                print_expense_table(&expenses);
+                println!(
+                    "Approve with 'expense approve <ID>...' (or --all-pending), reject with 'expense reject <ID>...'"
+                );
+            }
+            Ok(())
+        }
+
+        ExpenseCommand::Approve { ids, all_pending } => {
+            let updated = if all_pending {
+                cache.approve_pending_expenses().await?
+            } else if ids.is_empty() {
+                anyhow::bail!("Give expense IDs to approve, or --all-pending");
+            } else {
+                cache.set_expense_status(&ids, ExpenseStatus::Approved).await?
+            };
+            println!("Approved {} expense(s)", updated);
+            Ok(())
+        }
+
+        ExpenseCommand::Reject { ids } => {
+            let updated = cache.set_expense_status(&ids, ExpenseStatus::Rejected).await?;
+            println!("Rejected {} expense(s)", updated);
+            Ok(())
+        }
+
         ExpenseCommand::Add {
             date,
             vendor,
@@ -686,8 +3519,10 @@ async fn handle_expense_command(action: ExpenseCommand, cache: &Cache) -> Result
             amount,
             paid_with,
             invoice_id,
+            entity,
         } => {
             let category = parse_category(&category)?;
+            let entity = resolve_paying_entity(config_path, entity)?;
 
             let expense = Expense {
                 id: None,
@@ -698,6 +3533,8 @@ async fn handle_expense_command(action: ExpenseCommand, cache: &Cache) -> Result
                 amount_usd: amount,
                 paid_with,
                 invoice_id,
+                entity,
+                status: ExpenseStatus::Approved,
             };
 
             let id = cache.add_expense(&expense).await?;
@@ -730,8 +3567,40 @@ async fn handle_expense_command(action: ExpenseCommand, cache: &Cache) -> Result
     }
 }
 
+/// Print expenses as a table with a total line
+fn print_expense_table(expenses: &[Expense]) {
+    println!(
+        "{:<4} {:<12} {:<15} {:<12} {:>10}  {:<9} Description",
+        "ID", "Date", "Vendor", "Category", "Amount", "Status"
+    );
+    println!("{}", "-".repeat(90));
+
+    let mut total = 0.0;
+    for expense in expenses {
+        let id = expense.id.map(|i| i.to_string()).unwrap_or_default();
+        println!(
+            "{:<4} {:<12} {:<15} {:<12} ${:>9.2}  {:<9} {}",
+            id,
+            expense.date,
+            truncate(&expense.vendor, 14),
+            expense.category,
+            expense.amount_usd,
+            expense.status,
+            truncate(&expense.description, 30),
+        );
+        total += expense.amount_usd;
+    }
+    println!("{}", "-".repeat(90));
+    println!("{:>54} ${:>9.2}", "Total:", total);
+    println!("\n{} expense(s)", expenses.len());
+}
+
 /// Handle recurring expense subcommands
-async fn handle_recurring_command(action: RecurringCommand, cache: &Cache) -> Result<()> {
+async fn handle_recurring_command(
+    action: RecurringCommand,
+    cache: &Cache,
+    config_path: Option<&PathBuf>,
+) -> Result<()> {
     match action {
         RecurringCommand::List => {
             let recurring = cache.get_recurring_expenses().await?;
@@ -782,8 +3651,10 @@ async fn handle_recurring_command(action: RecurringCommand, cache: &Cache) -> Re
             paid_with,
             start_date,
             end_date,
+            entity,
         } => {
             let category = parse_category(&category)?;
+            let entity = resolve_paying_entity(config_path, entity)?;
             let start = parse_yyyy_mm_dd("start_date", &start_date)?;
             let end_date = end_date
                 .as_deref()
@@ -808,6 +3679,7 @@ async fn handle_recurring_command(action: RecurringCommand, cache: &Cache) -> Re
                 paid_with,
                 start_date: start.format("%Y-%m-%d").to_string(),
                 end_date: end_date.map(|d| d.format("%Y-%m-%d").to_string()),
+                entity,
             };
 
             let id = cache.add_recurring_expense(&expense).await?;
@@ -827,6 +3699,164 @@ async fn handle_recurring_command(action: RecurringCommand, cache: &Cache) -> Re
 }
 
 /// Handle Dune Analytics import subcommands
+/// Handle aggregator import subcommands
+async fn handle_import_command(action: ImportCommand, cache: &Cache, config_path: Option<&PathBuf>) -> Result<()> {
+    let file_config = load_config_file(config_path)?;
+    let config = config::Config::from_file(&file_config, None)?;
+
+    match action {
+        ImportCommand::Fetch { sources, from, to } => {
+            let explicit = !sources.is_empty();
+            let sources = if explicit {
+                sources
+            } else {
+                vec![
+                    history_import::ImportSource::Stakewiz,
+                    history_import::ImportSource::SolanaBeach,
+                ]
+            };
+            let solana_beach_key = file_config.api_keys.solanabeach.as_deref().filter(|k| !k.is_empty());
+
+            let from = from.unwrap_or(config.first_reward_epoch);
+            let to = match to {
+                Some(to) => to,
+                None => {
+                    let rpc_client = rpc::new_rpc_client(&config.rpc_url, CommitmentConfig::confirmed());
+                    rpc_client.get_epoch_info()?.epoch.saturating_sub(1)
+                }
+            };
+            anyhow::ensure!(from <= to, "--from must not be after --to");
+
+            let vote_account = config.vote_account.to_string();
+            let mut sets = Vec::new();
+            for source in sources {
+                if source == history_import::ImportSource::SolanaBeach && solana_beach_key.is_none() && !explicit {
+                    println!("Skipping Solana Beach (no api_keys.solanabeach configured)");
+                    continue;
+                }
+                println!("Fetching epoch history from {}...", source.as_str());
+                let rows = history_import::fetch(source, &vote_account, solana_beach_key).await?;
+                println!("  {} epochs", rows.len());
+                sets.push(rows);
+            }
+
+            let imports: Vec<_> = history_import::merge(sets)
+                .into_iter()
+                .filter(|i| (from..=to).contains(&i.epoch))
+                .collect();
+            if imports.is_empty() {
+                println!("No epochs between {} and {} in the aggregator history.", from, to);
+                return Ok(());
+            }
+
+            // Rows already fetched on-chain win; earlier unverified imports may be replaced
+            let pending = cache.get_unverified_import_epochs().await?;
+            let onchain: std::collections::BTreeSet<u64> = cache
+                .get_epoch_rewards(from, to)
+                .await?
+                .into_iter()
+                .map(|r| r.epoch)
+                .filter(|e| !pending.contains(e))
+                .collect();
+            let seeded = history_import::seed_rows(&imports, &onchain, config.commission_percent);
+
+            cache.store_reward_imports(&imports).await?;
+            let already_onchain: Vec<u64> = imports
+                .iter()
+                .map(|i| i.epoch)
+                .filter(|e| onchain.contains(e))
+                .collect();
+            cache.verify_imports_from_cache(&already_onchain).await?;
+            cache.seed_imported_rewards(&seeded).await?;
+
+            let with_stake = imports.iter().filter(|i| i.activated_stake_lamports.is_some()).count();
+            println!(
+                "\nImported epochs {}-{}: {} reward epochs seeded, {} already cached on-chain, {} with stake",
+                from,
+                to,
+                seeded.len(),
+                already_onchain.len(),
+                with_stake
+            );
+            if !seeded.is_empty() {
+                println!("Seeded epochs are unverified until the next report run refetches them on-chain.");
+                println!("Check progress with `validator-accounting import status`.");
+            }
+        }
+
+        ImportCommand::Status { output } => {
+            let imports = cache.get_reward_imports().await?;
+            let rows: Vec<_> = imports
+                .iter()
+                .map(|i| {
+                    (
+                        i,
+                        history_import::verify_status(i.commission_lamports, i.onchain_lamports),
+                    )
+                })
+                .collect();
+
+            if output.is_json() {
+                let json: Vec<_> = rows
+                    .iter()
+                    .map(|(i, status)| serde_json::json!({ "import": i, "verification": status }))
+                    .collect();
+                return output::print_json(&json);
+            }
+
+            if rows.is_empty() {
+                println!("No aggregator imports (run `import fetch`).");
+                return Ok(());
+            }
+
+            let count = |f: fn(&history_import::VerifyStatus) -> bool| rows.iter().filter(|(_, s)| f(s)).count();
+            println!(
+                "{} imported epochs: {} verified, {} pending, {} mismatched, {} stake only\n",
+                rows.len(),
+                count(|s| *s == history_import::VerifyStatus::Verified),
+                count(|s| *s == history_import::VerifyStatus::Pending),
+                count(|s| matches!(s, history_import::VerifyStatus::Mismatch { .. })),
+                count(|s| *s == history_import::VerifyStatus::StakeOnly),
+            );
+
+            let sol = |v: Option<u64>| {
+                v.map_or_else(
+                    || "-".to_string(),
+                    |l| format!("{:.6}", l as f64 / constants::LAMPORTS_PER_SOL_U64 as f64),
+                )
+            };
+            println!(
+                "{:>6}  {:<22} {:>14} {:>14} {:>14}  Status",
+                "Epoch", "Source", "Imported SOL", "On-chain SOL", "Stake SOL"
+            );
+            for (i, status) in &rows {
+                let status = match status {
+                    history_import::VerifyStatus::Pending => "pending".to_string(),
+                    history_import::VerifyStatus::Verified => "verified".to_string(),
+                    history_import::VerifyStatus::StakeOnly => "stake only".to_string(),
+                    history_import::VerifyStatus::Mismatch { delta_lamports } => format!(
+                        "MISMATCH ({:+.6} SOL)",
+                        *delta_lamports as f64 / constants::LAMPORTS_PER_SOL_U64 as f64
+                    ),
+                };
+                println!(
+                    "{:>6}  {:<22} {:>14} {:>14} {:>14}  {}",
+                    i.epoch,
+                    i.source,
+                    sol(i.commission_lamports),
+                    sol(i.onchain_lamports),
+                    i.activated_stake_lamports.map_or_else(
+                        || "-".to_string(),
+                        |l| format!("{:.0}", l as f64 / constants::LAMPORTS_PER_SOL_U64 as f64)
+                    ),
+                    status
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
 async fn handle_dune_command(action: DuneCommand, cache: &Cache, config_path: Option<&PathBuf>) -> Result<()> {
     // Load config to get API key and validator addresses
     let file_config = load_config_file(config_path)?;
@@ -922,6 +3952,30 @@ async fn handle_dune_command(action: DuneCommand, cache: &Cache, config_path: Op
             println!("\nData cached to database.");
         }
 
+        DuneCommand::NetworkFees { since } => {
+            println!("Importing network median block fees since {}...\n", since);
+
+            let medians = client.fetch_network_fee_medians(&since).await?;
+
+            if medians.is_empty() {
+                println!("No network fees found.");
+                return Ok(());
+            }
+
+            cache.store_network_fee_medians(&medians).await?;
+
+            println!("\nImported {} epochs:", medians.len());
+            for median in &medians {
+                println!(
+                    "  Epoch {}: {:.6} SOL median over {} blocks",
+                    median.epoch,
+                    median.median_block_fees_lamports as f64 / 1e9,
+                    median.blocks_sampled
+                );
+            }
+            println!("\nData cached to database.");
+        }
+
         DuneCommand::Transfers { since } => {
             println!("Importing SOL transfers since {}...\n", since);
 
@@ -1011,7 +4065,12 @@ async fn handle_dune_command(action: DuneCommand, cache: &Cache, config_path: Op
 }
 
 /// Handle position tracking subcommands
-async fn handle_position_command(action: PositionCommand, cache: &Cache, config_path: Option<&PathBuf>) -> Result<()> {
+async fn handle_position_command(
+    action: PositionCommand,
+    cache: &Cache,
+    config_path: Option<&PathBuf>,
+    strict: bool,
+) -> Result<()> {
     // Load config and create RPC client
     let file_config = load_config_file(config_path)?;
     let config = config::Config::from_file(&file_config, None)?;
@@ -1020,7 +4079,8 @@ async fn handle_position_command(action: PositionCommand, cache: &Cache, config_
     let dune_api_key = file_config.api_keys.dune.as_deref();
 
     match action {
-        PositionCommand::Now => {
+        PositionCommand::Now { output } => {
+            let progress = output.redirect_progress();
             println!("Validator Position Snapshot");
             println!("============================\n");
 
@@ -1037,7 +4097,7 @@ async fn handle_position_command(action: PositionCommand, cache: &Cache, config_
 
             // Discover stake accounts (using snapshot_slot for consistency tracking)
             let stake_accounts =
-                positions::discover_stake_accounts(&rpc_client, &config.withdraw_authority, snapshot_slot).await?;
+                positions::discover_treasury_stake_accounts(&rpc_client, &config, snapshot_slot).await?;
             // Keep stake account list fresh for reconciliation calculations.
             cache.store_stake_accounts(&stake_accounts).await?;
 
@@ -1105,6 +4165,7 @@ async fn handle_position_command(action: PositionCommand, cache: &Cache, config_
                     positions::AccountType::VoteAccount
                         | positions::AccountType::Identity
                         | positions::AccountType::WithdrawAuthority
+                        | positions::AccountType::SquadsVault
                 ) {
                     continue;
                 }
@@ -1116,6 +4177,20 @@ async fn handle_position_command(action: PositionCommand, cache: &Cache, config_
                 );
             }
 
+            if !config.business_wallets.is_empty() {
+                println!("\nBusiness Wallets (watch-only):");
+                for balance in balances
+                    .iter()
+                    .filter(|b| b.account_type == positions::AccountType::BusinessWallet)
+                {
+                    println!(
+                        "  {} {:>15}",
+                        balance.account,
+                        format!("{:.4} SOL", balance.balance_sol())
+                    );
+                }
+            }
+
             if position.token_accounts_lamports > 0 {
                 println!("\nToken Accounts (ATAs):");
                 println!(
@@ -1160,6 +4235,23 @@ async fn handle_position_command(action: PositionCommand, cache: &Cache, config_
                 );
             }
 
+            // Proposals don't move funds until executed; show what is still pending
+            let squads_proposals = match config.squads_multisig {
+                Some(multisig) => match squads::fetch_multisig(&rpc_client, &multisig)
+                    .and_then(|info| squads::fetch_proposals(&rpc_client, &multisig).map(|proposals| (proposals, info)))
+                {
+                    Ok((proposals, info)) => {
+                        print_squads_proposals(&multisig, &proposals, &info);
+                        Some((proposals, info))
+                    }
+                    Err(e) => {
+                        eprintln!("Warning: Failed to fetch Squads proposals: {:#}", e);
+                        None
+                    }
+                },
+                None => None,
+            };
+
             println!("\nTotals:");
             println!(
                 "  Total liquid:  {} SOL",
@@ -1175,25 +4267,60 @@ async fn handle_position_command(action: PositionCommand, cache: &Cache, config_
             );
 
             // Store snapshot in cache
-            let date = chrono::DateTime::from_timestamp(block_time.unwrap_or(0), 0)
-                .map(|dt| dt.format("%Y-%m-%d").to_string())
-                .unwrap_or_else(|| {
-                    let now = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap_or_default()
-                        .as_secs();
-                    chrono::DateTime::from_timestamp(now as i64, 0)
-                        .map(|dt| dt.format("%Y-%m-%d").to_string())
-                        .unwrap_or_else(|| "unknown".to_string())
-                });
+            let date = timezone::timestamp_to_date(block_time.unwrap_or(0))
+                .unwrap_or_else(|| timezone::today().format("%Y-%m-%d").to_string());
             let epoch = rpc_client.get_epoch_info()?.epoch;
-            cache.store_balance_snapshot(&position, &date, epoch).await?;
+            cache
+                .store_balance_snapshot(&position, &date, block_time, epoch)
+                .await?;
             println!("\nSnapshot stored to cache.");
 
+            drop(progress);
+            if output.is_json() {
+                let stake_accounts: Vec<_> = stake_accounts
+                    .iter()
+                    .map(|stake| {
+                        serde_json::json!({
+                            "account": stake.account.to_string(),
+                            "balance_lamports": stake.balance_lamports,
+                            "state": stake.state,
+                            "voter": stake.voter.map(|v| v.to_string()),
+                            "lockup_epoch": stake.lockup_epoch,
+                            "is_liquid": stake.is_liquid,
+                        })
+                    })
+                    .collect();
+                let squads = squads_proposals.map(|(proposals, info)| {
+                    let proposals: Vec<_> = proposals
+                        .iter()
+                        .map(|p| {
+                            serde_json::json!({
+                                "proposal": p,
+                                "label": p.label(&info),
+                                "pending": p.is_pending(&info),
+                            })
+                        })
+                        .collect();
+                    serde_json::json!({
+                        "vault": config.squads_vault.map(|v| v.to_string()),
+                        "multisig": info,
+                        "proposals": proposals,
+                    })
+                });
+                output::print_json(&serde_json::json!({
+                    "date": date,
+                    "epoch": epoch,
+                    "position": position,
+                    "stake_accounts": stake_accounts,
+                    "squads": squads,
+                }))?;
+            }
+
             Ok(())
         }
 
-        PositionCommand::Reconcile => {
+        PositionCommand::Reconcile { output } => {
+            let progress = output.redirect_progress();
             println!("Position Reconciliation");
             println!("=======================\n");
 
@@ -1205,7 +4332,7 @@ async fn handle_position_command(action: PositionCommand, cache: &Cache, config_
                     .await?;
             let jitosol_rate = positions::fetch_jitosol_exchange_rate(&rpc_client).await?;
             let stake_accounts =
-                positions::discover_stake_accounts(&rpc_client, &config.withdraw_authority, snapshot_slot).await?;
+                positions::discover_treasury_stake_accounts(&rpc_client, &config, snapshot_slot).await?;
             cache.store_stake_accounts(&stake_accounts).await?;
 
             let completed_end = current_epoch.saturating_sub(1);
@@ -1282,11 +4409,11 @@ async fn handle_position_command(action: PositionCommand, cache: &Cache, config_
             // DoubleZero liabilities are accruals, not cash outflows, so they are not part of
             // `Lifetime expenses` above. Show them separately for transparency.
             let snapshot_epoch = snapshot_slot / constants::SLOTS_PER_EPOCH;
-            if let Ok(dz_liability) = cache
+            let dz_liability = cache
                 .get_total_doublezero_liability_lamports_up_to(snapshot_epoch)
                 .await
-                && dz_liability > 0
-            {
+                .unwrap_or(0);
+            if dz_liability > 0 {
                 println!(
                     "  DoubleZero liability (accrued): {} SOL",
                     positions::lamports_to_sol_string(dz_liability, 4)
@@ -1322,6 +4449,16 @@ async fn handle_position_command(action: PositionCommand, cache: &Cache, config_
                     positions::lamports_to_sol_string(position.token_accounts_withdrawable_lamports, 4)
                 );
             }
+            // The off-chain side: the USD account fiat expenses are paid from
+            if let Some(fiat_config) = &config.fiat {
+                let inputs = CachedReportInputs::load(cache, &config).await?;
+                fiat::print(&fiat::statement(
+                    fiat_config,
+                    &inputs.expenses,
+                    &inputs.fiat_deposits,
+                    timezone::today(),
+                ));
+            }
             println!();
             println!("Reconciliation:");
             println!(
@@ -1341,18 +4478,15 @@ async fn handle_position_command(action: PositionCommand, cache: &Cache, config_
 
             // Show external transfer details to help explain variance
             // Exclude: validator accounts, personal wallet, token accounts, and DeFi protocols
-            let mut internal_addresses = vec![
-                config.vote_account.to_string(),
-                config.identity.to_string(),
-                config.withdraw_authority.to_string(),
-            ];
+            let mut internal_addresses: Vec<String> =
+                config.ledger_accounts().iter().map(ToString::to_string).collect();
             internal_addresses.extend(config.personal_wallets.iter().map(ToString::to_string));
 
             // Add token accounts (ATAs) for each wallet - these are also "internal"
             // since they hold the user's tokens (wSOL, mSOL, USDC, jitoSOL)
             // Include vote_account for completeness (though vote accounts rarely hold tokens)
-            for wallet in [&config.vote_account, &config.identity, &config.withdraw_authority] {
-                internal_addresses.extend(positions::compute_common_atas(wallet));
+            for wallet in config.ledger_accounts() {
+                internal_addresses.extend(positions::compute_common_atas(&wallet));
             }
             for wallet in &config.personal_wallets {
                 internal_addresses.extend(positions::compute_common_atas(wallet));
@@ -1442,6 +4576,21 @@ async fn handle_position_command(action: PositionCommand, cache: &Cache, config_
                 }
             }
 
+            drop(progress);
+            if output.is_json() {
+                output::print_json(&serde_json::json!({
+                    "snapshot_epoch": snapshot_epoch,
+                    "tolerance_lamports": constants::RECONCILIATION_TOLERANCE_LAMPORTS,
+                    "reconciliation": reconciliation,
+                    "position": position,
+                    "doublezero_liability_lamports": dz_liability,
+                    "external_transfers": transfer_summary,
+                }))?;
+            }
+
+            if strict {
+                strict::StrictFailure::check(strict::check_reconciliation(&reconciliation).into_iter().collect())?;
+            }
             Ok(())
         }
 
@@ -1458,7 +4607,7 @@ async fn handle_position_command(action: PositionCommand, cache: &Cache, config_
             let jitosol_rate = positions::fetch_jitosol_exchange_rate(&rpc_client).await?;
 
             let stake_accounts =
-                positions::discover_stake_accounts(&rpc_client, &config.withdraw_authority, snapshot_slot).await?;
+                positions::discover_treasury_stake_accounts(&rpc_client, &config, snapshot_slot).await?;
             cache.store_stake_accounts(&stake_accounts).await?;
 
             let completed_end = current_epoch.saturating_sub(1);
@@ -1552,7 +4701,7 @@ async fn handle_position_command(action: PositionCommand, cache: &Cache, config_
             // Get current slot for snapshot tracking
             let snapshot_slot = rpc_client.get_slot()?;
             let stake_accounts =
-                positions::discover_stake_accounts(&rpc_client, &config.withdraw_authority, snapshot_slot).await?;
+                positions::discover_treasury_stake_accounts(&rpc_client, &config, snapshot_slot).await?;
 
             if stake_accounts.is_empty() {
                 println!(
@@ -1698,7 +4847,7 @@ async fn handle_tax_command(
     cache: &Cache,
     config_path: Option<&PathBuf>,
     output_dir: &Path,
-    year_filter: Option<i32>,
+    period: PeriodArgs,
     rpc_url: Option<String>,
     no_cache: bool,
     verbose: bool,
@@ -1706,9 +4855,14 @@ async fn handle_tax_command(
     println!("Block Parliament — Withdrawal-Based Tax Report");
     println!("===============================================\n");
 
+    if offline::enabled() {
+        return offline_tax_report(cache, config_path, output_dir, period).await;
+    }
+
     // Load config
     let file_config = load_config_file(config_path)?;
     let mut config = config::Config::from_file(&file_config, rpc_url)?;
+    let period = period.resolve(&config)?;
     println!("Vote Account: {}", config.vote_account);
     println!("Identity: {}", config.identity);
     println!("RPC: {}\n", mask_api_key(&config.rpc_url));
@@ -1734,11 +4888,15 @@ async fn handle_tax_command(
         fetch_transfers_with_cache(cache, &config, no_cache, verbose, dune_api_key, &config.bootstrap_date).await?;
     println!("  Found {} SOL transfers", transfers.len());
 
-    let categorized = transactions::categorize_transfers(&transfers, &config);
+    let mut categorized = transactions::categorize_transfers(&transfers, &config);
+    categorized.splits = cache.get_withdrawal_splits().await?;
     let outgoing_other_count = categorized
         .other
         .iter()
-        .filter(|t| config.is_our_account(&t.from) && !config.is_our_account(&t.to))
+        .filter(|t| {
+            let date = t.date.as_deref();
+            config.is_our_account_on(&t.from, date) && !config.is_our_account_on(&t.to, date)
+        })
         .count();
     println!(
         "  {} external withdrawal(s) identified ({} to known exchanges, {} to other addresses)\n",
@@ -1800,7 +4958,7 @@ async fn handle_tax_command(
         }
     }
 
-    let mut all_expenses = cache.get_expenses().await?;
+    let mut all_expenses = cache.get_approved_expenses().await?;
 
     // Expand recurring expenses
     let recurring = cache.get_recurring_expenses().await?;
@@ -1815,7 +4973,7 @@ async fn handle_tax_command(
                 .min()
         });
         let end_month = month_key_from_date(&epoch_end_date).or_else(|| {
-            let current_month = Utc::now().date_naive().format("%Y-%m").to_string();
+            let current_month = timezone::today().format("%Y-%m").to_string();
             recurring
                 .iter()
                 .filter_map(|r| r.end_date.as_deref().and_then(month_key_from_date))
@@ -1855,6 +5013,28 @@ async fn handle_tax_command(
     .await?;
     println!("  {} daily prices cached\n", price_cache.len());
 
+    // Hourly prices for large withdrawals (BAM claims aren't part of the tax report)
+    let hourly_prices = if config.hourly_pricing_enabled {
+        fetch_hourly_prices_with_cache(cache, &config, &categorized, &mut [])
+            .await
+            .unwrap_or_else(|e| {
+                eprintln!("  Warning: Failed to fetch hourly prices: {}", e);
+                prices::HourlyPriceCache::new()
+            })
+    } else {
+        prices::HourlyPriceCache::new()
+    };
+
+    // Business shares of split withdrawals, valued like the withdrawals
+    all_expenses.extend(owner_draw::split_expenses(&categorized, &price_cache, &hourly_prices));
+
+    // Fees paid on failed and operational transactions, one expense per month each
+    all_expenses.extend(failed_fees::monthly_expenses(
+        &cache.get_failed_tx_fees().await?,
+        &price_cache,
+    ));
+    all_expenses.extend(ops_fees::monthly_expenses(&cache.get_ops_fees().await?, &price_cache));
+
     // Create output dir and generate report
     std::fs::create_dir_all(output_dir)?;
 
@@ -1865,10 +5045,99 @@ async fn handle_tax_command(
         vote_costs: &vote_costs,
         expenses: &all_expenses,
         prices: &price_cache,
+        hourly_prices: &hourly_prices,
+    };
+
+    tax_report::generate_tax_report(output_dir, &tax_data, period.as_ref())?;
+
+    Ok(())
+}
+
+/// Build the tax-year workpaper zip from cached data (run the main report first to refresh it)
+async fn handle_tax_package_command(
+    cache: &Cache,
+    config_path: Option<&PathBuf>,
+    output_dir: &Path,
+    period: PeriodArgs,
+    path: Option<PathBuf>,
+) -> Result<()> {
+    let config = load_report_config(config_path)?;
+    let period = period
+        .resolve(&config)?
+        .context("tax-package needs --year or --period")?;
+    let suffix = period.file_suffix();
+    let path = path.unwrap_or_else(|| output_dir.join(format!("tax_package{}.zip", suffix)));
+
+    println!("Tax Package {} ({} to {})", period.label, period.start, period.end);
+    println!("==========================================\n");
+
+    let inputs = CachedReportInputs::load(cache, &config).await?;
+    let report_data = inputs.report_data(&config);
+
+    // Generate into a scratch directory, then zip only the workpapers
+    let staging = output_dir.join(format!(".tax_package{}", suffix));
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging)?;
+    }
+    std::fs::create_dir_all(&staging)?;
+
+    let metadata = build_report_metadata(cache, config_path, &report_data, Some(&period)).await?;
+    let fallback_rows = reports::generate_all_reports(&staging, &report_data, Some(&period), &metadata)?;
+    let mut files = Vec::new();
+    for ledger in [
+        constants::INCOME_LEDGER_FILENAME,
+        constants::EXPENSE_LEDGER_FILENAME,
+        constants::TREASURY_LEDGER_FILENAME,
+    ] {
+        let kept = tax_package::filter_ledger(&staging.join(ledger), &period)?;
+        println!("  {}: {} row(s) in {}", ledger, kept, period.label);
+        files.push(ledger.to_string());
+    }
+    files.push(constants::SUMMARY_FILENAME.to_string());
+    files.push(constants::GLOSSARY_FILENAME.to_string());
+    if fallback_rows > 0 {
+        files.push(constants::PRICE_WARNINGS_FILENAME.to_string());
+    }
+
+    let tax_data = tax_report::TaxReportData {
+        config: &config,
+        categorized: &inputs.categorized,
+        doublezero_fees: &inputs.doublezero_fees,
+        vote_costs: &inputs.vote_costs,
+        expenses: &inputs.expenses,
+        prices: &inputs.prices,
+        hourly_prices: &inputs.hourly_prices,
     };
+    for written in tax_report::generate_tax_report(&staging, &tax_data, Some(&period))? {
+        if let Some(name) = written.file_name() {
+            files.push(name.to_string_lossy().into_owned());
+        }
+    }
 
-    tax_report::generate_tax_report(output_dir, &tax_data, year_filter)?;
+    let (tax_rows, _) = tax_report::build_tax_rows(&tax_data, Some(&period));
+    let tax_totals = tax_package::TaxTotals::from_rows(&tax_rows);
+    let summary = reports::compute_summary(&report_data, Some(&period));
+    tax_package::write_reconciliation(&staging, &tax_package::book_to_tax(&summary, &tax_totals))?;
+    files.push(tax_package::RECONCILIATION_FILENAME.to_string());
 
+    let cover = tax_package::cover_sheet(&config, &period, &summary, &tax_totals, fallback_rows, &files);
+    std::fs::write(
+        staging.join(tax_package::COVER_SHEET_FILENAME),
+        tax_package::render_pdf(&cover),
+    )?;
+    files.insert(0, tax_package::COVER_SHEET_FILENAME.to_string());
+
+    tax_package::write_zip(&path, &staging, &files)?;
+    std::fs::remove_dir_all(&staging)?;
+
+    println!("\nWrote {} ({} files)", path.display(), files.len());
+    if fallback_rows > 0 {
+        println!(
+            "  ⚠ {} ledger row(s) use the fallback price; see {} in the package",
+            fallback_rows,
+            constants::PRICE_WARNINGS_FILENAME
+        );
+    }
     Ok(())
 }
 
@@ -1879,14 +5148,38 @@ fn parse_category(s: &str) -> Result<ExpenseCategory> {
         "hardware" => Ok(ExpenseCategory::Hardware),
         "software" => Ok(ExpenseCategory::Software),
         "votefees" | "vote_fees" | "vote-fees" => Ok(ExpenseCategory::VoteFees),
+        "failedtxfees" | "failed_tx_fees" | "failed-tx-fees" => Ok(ExpenseCategory::FailedTxFees),
+        "opsfees" | "ops_fees" | "ops-fees" => Ok(ExpenseCategory::OpsFees),
         "other" => Ok(ExpenseCategory::Other),
         _ => anyhow::bail!(
-            "Invalid category '{}'. Use: Hosting, Contractor, Hardware, Software, VoteFees, Other",
+            "Invalid category '{}'. Use: Hosting, Contractor, Hardware, Software, VoteFees, FailedTxFees, OpsFees, Other",
             s
         ),
     }
 }
 
+/// Check an `--entity` against [[entities]]; the validator owner's id is stored as None
+fn resolve_paying_entity(config_path: Option<&PathBuf>, entity: Option<String>) -> Result<Option<String>> {
+    let Some(id) = entity else {
+        return Ok(None);
+    };
+    let file_config = load_config_file(config_path)?;
+    let config = config::Config::from_file(&file_config, None)?;
+    let Some(found) = config.entity(&id) else {
+        anyhow::bail!(
+            "Unknown entity '{}'. Configured entities: {}",
+            id,
+            config
+                .entities
+                .iter()
+                .map(|e| e.id.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    };
+    Ok(config.paying_entity(Some(&found.id)).map(|e| e.id.clone()))
+}
+
 /// Truncate string for display
 fn truncate(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
@@ -1898,15 +5191,61 @@ fn truncate(s: &str, max_len: usize) -> String {
     }
 }
 
-fn parse_yyyy_mm_dd(field: &str, value: &str) -> Result<NaiveDate> {
-    NaiveDate::parse_from_str(value, "%Y-%m-%d")
-        .with_context(|| format!("Invalid {} '{}'. Expected YYYY-MM-DD.", field, value))
-}
+fn parse_yyyy_mm_dd(field: &str, value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .with_context(|| format!("Invalid {} '{}'. Expected YYYY-MM-DD.", field, value))
+}
+
+fn month_key_from_date(date: &str) -> Option<String> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .map(|d| d.format("%Y-%m").to_string())
+}
+
+/// Expand recurring expenses over the report period.
+///
+/// Prefers the reward date range; if unavailable, derives it from the recurring rules.
+/// Never expands before the bootstrap month. Returns None if no valid range can be derived.
+fn expand_recurring_for_report(
+    recurring: &[RecurringExpense],
+    rewards: &[transactions::EpochReward],
+    bootstrap_date: &str,
+) -> Option<Vec<Expense>> {
+    let reward_months: Vec<String> = rewards
+        .iter()
+        .filter_map(|r| r.date.as_deref())
+        .filter_map(month_key_from_date)
+        .collect();
+    let bootstrap_month = month_key_from_date(bootstrap_date).unwrap_or_else(|| "2025-11".to_string());
+
+    let mut start_month = reward_months.iter().min().cloned();
+    let mut end_month = reward_months.iter().max().cloned();
+
+    if start_month.is_none() || end_month.is_none() {
+        let current_month = timezone::today().format("%Y-%m").to_string();
+        start_month = recurring
+            .iter()
+            .filter_map(|r| month_key_from_date(&r.start_date))
+            .min();
+        end_month = recurring
+            .iter()
+            .filter_map(|r| {
+                r.end_date
+                    .as_deref()
+                    .and_then(month_key_from_date)
+                    .or_else(|| Some(current_month.clone()))
+            })
+            .max();
+    }
 
-fn month_key_from_date(date: &str) -> Option<String> {
-    NaiveDate::parse_from_str(date, "%Y-%m-%d")
-        .ok()
-        .map(|d| d.format("%Y-%m").to_string())
+    let (mut start_month, mut end_month) = (start_month?, end_month?);
+    if start_month < bootstrap_month {
+        start_month = bootstrap_month;
+    }
+    if end_month < start_month {
+        end_month = start_month.clone();
+    }
+    Some(expenses::expand_recurring_expenses(recurring, &start_month, &end_month))
 }
 
 async fn ensure_vote_costs_cached(cache: &Cache, start_epoch: u64, end_epoch: u64) -> Result<()> {
@@ -2011,12 +5350,8 @@ async fn ensure_jitosol_token_flows_cached(cache: &Cache, config: &config::Confi
         .context("Failed to build JSON-RPC client")?;
 
     let mint = Pubkey::from_str(constants::JITOSOL_MINT).expect("Invalid JITOSOL_MINT constant");
-    // Track both identity + withdraw authority. Deduplicate if they are the same pubkey.
-    let mut owners: Vec<Pubkey> = vec![config.identity, config.withdraw_authority];
-    owners.sort();
-    owners.dedup();
-
-    for owner in owners {
+    // Track identity + withdraw authority, current and retired (deduplicated).
+    for owner in config.signing_keys() {
         let owner_str = owner.to_string();
 
         let token_accounts: RpcTokenAccountsByOwnerResult = rpc_call(
@@ -2274,6 +5609,32 @@ async fn maybe_sync_dune_transfers_incremental(
     Ok(true)
 }
 
+/// Print open Squads proposals, pending ones first
+fn print_squads_proposals(multisig: &Pubkey, proposals: &[squads::Proposal], info: &squads::MultisigInfo) {
+    let pending = proposals.iter().filter(|p| p.is_pending(info)).count();
+    println!(
+        "\nSquads Multisig {} (threshold {}, {} pending proposal(s)):",
+        shorten_address(&multisig.to_string()),
+        info.threshold,
+        pending
+    );
+    if proposals.is_empty() {
+        println!("  No open proposals.");
+        return;
+    }
+    println!("  {:>6} {:28} {:>10}", "Index", "Status", "Approvals");
+    println!("  {}", "-".repeat(46));
+    let (pending, settled): (Vec<_>, Vec<_>) = proposals.iter().partition(|p| p.is_pending(info));
+    for proposal in pending.into_iter().chain(settled) {
+        println!(
+            "  {:>6} {:28} {:>10}",
+            format!("#{}", proposal.transaction_index),
+            proposal.label(info),
+            format!("{}/{}", proposal.approvals, info.threshold),
+        );
+    }
+}
+
 async fn print_spl_token_summary(rpc_url: &str, config: &config::Config, top_n: usize) -> Result<()> {
     let client = reqwest_012::Client::builder()
         .timeout(Duration::from_secs(45))
@@ -2281,10 +5642,16 @@ async fn print_spl_token_summary(rpc_url: &str, config: &config::Config, top_n:
         .context("Failed to build JSON-RPC client")?;
 
     // Only show tokens for core owners (personal wallet is intentionally excluded from position).
-    let owners = [
+    let mut owners = vec![
         ("identity", config.identity),
         ("withdraw_authority", config.withdraw_authority),
     ];
+    if let Some(vault) = config.squads_vault.filter(|v| *v != config.withdraw_authority) {
+        owners.push(("squads_vault", vault));
+    }
+    for wallet in &config.business_wallets {
+        owners.push(("business_wallet", *wallet));
+    }
 
     let mut all_tokens: Vec<(String, String, f64)> = Vec::new(); // (owner_label, mint, ui_amount)
 
@@ -2566,13 +5933,14 @@ async fn summarize_recent_jitosol_inflows(rpc_url: &str, identity: &Pubkey, limi
 }
 
 /// Run the main report generation workflow
-async fn run_report_generation(args: Args, cache: Cache) -> Result<()> {
+async fn run_report_generation(args: Args, cache: &Cache, run_log: &mut IngestionRunLog) -> Result<()> {
     println!("Block Parliament Validator Financial Tracker");
     println!("=============================================\n");
 
     // Load config file and initialize runtime configuration
     let file_config = load_config_file(args.config.as_ref())?;
     let mut config = config::Config::from_file(&file_config, args.rpc_url)?;
+    let period = args.period.resolve(&config)?;
     println!("Vote Account: {}", config.vote_account);
     println!("Identity: {}", config.identity);
     println!("RPC: {}\n", mask_api_key(&config.rpc_url));
@@ -2594,7 +5962,8 @@ async fn run_report_generation(args: Args, cache: Cache) -> Result<()> {
 
     // Get current epoch to know what's "complete" vs "in progress"
     let rpc_client = rpc::new_rpc_client(&config.rpc_url, CommitmentConfig::confirmed());
-    let current_epoch = rpc_client.get_epoch_info()?.epoch;
+    let epoch_info = rpc_client.get_epoch_info()?;
+    let current_epoch = epoch_info.epoch;
     println!("Current epoch: {}\n", current_epoch);
 
     let start_epoch = args.start_epoch.unwrap_or(config.first_reward_epoch);
@@ -2605,8 +5974,8 @@ async fn run_report_generation(args: Args, cache: Cache) -> Result<()> {
 
     // Step 1: Fetch inflation rewards by epoch (with caching)
     println!("Fetching inflation rewards...");
-    let rewards = fetch_rewards_with_cache(
-        &cache,
+    let mut rewards = fetch_rewards_with_cache(
+        cache,
         &config,
         start_epoch,
         end_epoch,
@@ -2615,12 +5984,13 @@ async fn run_report_generation(args: Args, cache: Cache) -> Result<()> {
         dune_api_key,
     )
     .await?;
+    run_log.record_rows("epoch_rewards", rewards.len());
     println!("  Found {} epochs with rewards\n", rewards.len());
 
     // Step 2: Fetch all SOL transfers to/from our accounts (with caching)
     println!("Loading transaction history...");
     let transfers = fetch_transfers_with_cache(
-        &cache,
+        cache,
         &config,
         args.no_cache,
         args.verbose,
@@ -2628,11 +5998,13 @@ async fn run_report_generation(args: Args, cache: Cache) -> Result<()> {
         &config.bootstrap_date,
     )
     .await?;
+    run_log.record_rows("sol_transfers", transfers.len());
     println!("  Found {} SOL transfers\n", transfers.len());
 
     // Step 3: Categorize transfers
     println!("Categorizing transactions...");
-    let categorized = transactions::categorize_transfers(&transfers, &config);
+    let mut categorized = transactions::categorize_transfers(&transfers, &config);
+    categorized.splits = cache.get_withdrawal_splits().await?;
 
     println!("  Initial seeding: {} transfers", categorized.seeding.len());
     println!(
@@ -2644,37 +6016,57 @@ async fn run_report_generation(args: Args, cache: Cache) -> Result<()> {
         "  DoubleZero payments: {} transfers",
         categorized.doublezero_payments.len()
     );
+    println!("  Donations: {} transfers", categorized.donations.len());
     println!("  Vote fee funding: {} transfers", categorized.vote_funding.len());
     println!("  Withdrawals: {} transfers", categorized.withdrawals.len());
+    println!("  Rent deposits/refunds: {} transfers", categorized.rent.len());
     println!("  Other: {} transfers\n", categorized.other.len());
 
-    // Step 4: Fetch Jito MEV claims (with caching)
-    println!("Fetching Jito MEV claims...");
-    let mev_claims = fetch_mev_with_cache(&cache, &config, start_epoch, end_epoch, current_epoch, args.no_cache)
-        .await
-        .unwrap_or_else(|e| {
-            eprintln!("  Warning: Failed to fetch MEV claims: {}", e);
-            Vec::new()
-        });
+    // Step 4: Sync external data sources into the cache (MEV tips, vote credits, delegators,
+    // Notion hours, prices, ...); everything below reads them back from the cache
+    let sync_ctx = sources::SyncContext {
+        cache,
+        config: &config,
+        file_config: &file_config,
+        start_epoch,
+        end_epoch,
+        current_epoch,
+        no_cache: args.no_cache,
+        rewards: &rewards,
+        transfers: &transfers,
+    };
+    sources::Registry::builtin().sync_all(&sync_ctx, run_log).await?;
+
+    // MEV claims only exist for completed epochs; providers no longer configured are dropped
+    let mev_providers: Vec<String> = mev::configured_providers(&config)
+        .iter()
+        .map(|p| p.name().to_string())
+        .collect();
+    let mut mev_claims: Vec<_> = cache
+        .get_mev_claims(start_epoch, end_epoch.min(current_epoch.saturating_sub(1)))
+        .await?
+        .into_iter()
+        .filter(|c| mev_providers.contains(&c.provider))
+        .collect();
     let total_mev = jito::total_mev_sol(&mev_claims);
-    println!(
-        "  Found {} MEV claims totaling {:.6} SOL\n",
-        mev_claims.len(),
-        total_mev
-    );
+    println!("MEV: {} claims totaling {:.6} SOL\n", mev_claims.len(), total_mev);
 
     // Step 4.5: Fetch BAM claims (jitoSOL rewards, with caching)
-    let bam_claims = if config.bam_enabled {
+    let mut bam_claims = if config.bam_enabled {
         println!("Fetching BAM rewards (jitoSOL)...");
-        fetch_bam_with_cache(&cache, &config, start_epoch, end_epoch, current_epoch, args.no_cache)
+        fetch_bam_with_cache(cache, &config, start_epoch, end_epoch, current_epoch, args.no_cache)
             .await
             .unwrap_or_else(|e| {
                 eprintln!("  Warning: Failed to fetch BAM claims: {}", e);
+                run_log.record_error("bam_claims", &e);
                 Vec::new()
             })
     } else {
         Vec::new()
     };
+    if config.bam_enabled {
+        run_log.record_rows("bam_claims", bam_claims.len());
+    }
 
     if !bam_claims.is_empty() {
         let total_bam_jitosol = bam::total_bam_jitosol(&bam_claims);
@@ -2691,8 +6083,8 @@ async fn run_report_generation(args: Args, cache: Cache) -> Result<()> {
 
     // Step 5: Fetch leader slot fees (with caching - this is the slow one!)
     println!("Fetching leader slot fees...");
-    let leader_fees = fetch_leader_fees_with_cache(
-        &cache,
+    let mut leader_fees = fetch_leader_fees_with_cache(
+        cache,
         &config,
         start_epoch,
         end_epoch,
@@ -2703,8 +6095,10 @@ async fn run_report_generation(args: Args, cache: Cache) -> Result<()> {
     .await
     .unwrap_or_else(|e| {
         eprintln!("  Warning: Failed to fetch leader fees: {}", e);
+        run_log.record_error("leader_fees", &e);
         Vec::new()
     });
+    run_log.record_rows("leader_fees", leader_fees.len());
     let total_leader_fees = leader_fees::total_leader_fees_sol(&leader_fees);
     println!(
         "  Found {} epochs with leader fees totaling {:.6} SOL\n",
@@ -2712,8 +6106,42 @@ async fn run_report_generation(args: Args, cache: Cache) -> Result<()> {
         total_leader_fees
     );
 
+    // Step 5.1: Cache the leader schedule and check leader fee completeness
+    for epoch in [current_epoch, current_epoch + 1] {
+        match leader_fees::fetch_leader_slot_count(&config, epoch).await {
+            // The next epoch's schedule may not be published yet
+            Ok(0) => {}
+            Ok(count) => cache.store_leader_schedule_count(epoch, count).await?,
+            Err(e) => eprintln!("  Warning: Failed to fetch leader schedule for epoch {}: {}", epoch, e),
+        }
+    }
+    let completed_end = end_epoch.min(current_epoch.saturating_sub(1));
+    let expected_slots = cache.get_leader_schedule_counts(start_epoch, completed_end).await?;
+    let gaps = leader_fees::find_leader_fee_gaps(&leader_fees, &expected_slots);
+    if !gaps.is_empty() {
+        println!(
+            "  Warning: {} of {} scheduled epoch(s) have incomplete leader fees:",
+            gaps.len(),
+            expected_slots.len()
+        );
+        for gap in &gaps {
+            println!(
+                "    Epoch {}: {} of {} leader slots accounted for",
+                gap.epoch, gap.observed_slots, gap.expected_slots
+            );
+            run_log.record_error(
+                "leader_fees",
+                format!(
+                    "epoch {} incomplete ({} of {} leader slots)",
+                    gap.epoch, gap.observed_slots, gap.expected_slots
+                ),
+            );
+        }
+        println!();
+    }
+
     // Step 5.5: Compute DoubleZero fees (block reward sharing)
-    let doublezero_fees = if config.doublezero_enabled {
+    let mut doublezero_fees = if config.doublezero_enabled {
         println!("Computing DoubleZero fees...");
         let fees = doublezero::compute_fees(&config, &leader_fees, start_epoch, end_epoch, current_epoch);
         if !fees.is_empty() {
@@ -2727,6 +6155,7 @@ async fn run_report_generation(args: Args, cache: Cache) -> Result<()> {
         } else {
             println!("  No DoubleZero fees found\n");
         }
+        run_log.record_rows("doublezero_fees", fees.len());
         fees
     } else {
         Vec::new()
@@ -2756,6 +6185,7 @@ async fn run_report_generation(args: Args, cache: Cache) -> Result<()> {
     }
     // Sort by epoch after adding estimates
     vote_costs.sort_by_key(|c| c.epoch);
+    run_log.record_rows("vote_costs", vote_costs.len());
 
     // Persist estimates so reconciliation works even if the user never ran `vote-costs estimate`.
     // We only store for epochs that were missing, so we won't overwrite imported data.
@@ -2784,75 +6214,19 @@ async fn run_report_generation(args: Args, cache: Cache) -> Result<()> {
         }
     }
 
-    // Step 7: Load expenses (database + recurring + Notion contractor hours)
-    println!("Loading expenses...");
-
-    // Refresh cached Notion contractor rows first, so reports/web read from DB.
-    if let Some(notion_config) = &file_config.notion {
-        println!("  Fetching contractor hours from Notion...");
-        match notion::fetch_hours_log(notion_config).await {
-            Ok(hours_entries) => {
-                let summary = notion::hours_summary(&hours_entries);
-                println!(
-                    "    Found {} entries: {:.1}h total (${:.2}), {:.1}h unpaid (${:.2})",
-                    summary.total_entries,
-                    summary.total_hours,
-                    summary.total_amount,
-                    summary.unpaid_hours,
-                    summary.unpaid_amount
-                );
-
-                let contractor_expenses = notion::hours_to_expenses(&hours_entries);
-                cache.sync_notion_expenses(&contractor_expenses).await?;
-            }
-            Err(e) => {
-                eprintln!("    Warning: Failed to fetch Notion data: {}", e);
-            }
-        }
-    }
+    // Step 6.1: Vote credits, delegated stake and delegator snapshots (synced in step 4)
+    let epoch_performance = cache.get_epoch_performance(start_epoch, end_epoch).await?;
+    let delegator_stake = cache.get_delegator_stake(start_epoch, end_epoch).await?;
 
-    let mut all_expenses = cache.get_expenses().await?;
+    // Step 7: Load expenses (database, including synced Notion contractor hours, + recurring)
+    println!("Loading expenses...");
+    let mut all_expenses = cache.get_approved_expenses().await?;
     let _db_expense_count = all_expenses.len();
 
     // Expand recurring expenses into individual entries for the report period
     let recurring = cache.get_recurring_expenses().await?;
     if !recurring.is_empty() {
-        // Prefer reward date range; if unavailable, derive from recurring rules.
-        let reward_months: Vec<String> = rewards
-            .iter()
-            .filter_map(|r| r.date.as_deref())
-            .filter_map(month_key_from_date)
-            .collect();
-        let bootstrap_month = month_key_from_date(&config.bootstrap_date).unwrap_or_else(|| "2025-11".to_string());
-
-        let mut start_month = reward_months.iter().min().cloned();
-        let mut end_month = reward_months.iter().max().cloned();
-
-        if start_month.is_none() || end_month.is_none() {
-            let current_month = Utc::now().date_naive().format("%Y-%m").to_string();
-            start_month = recurring
-                .iter()
-                .filter_map(|r| month_key_from_date(&r.start_date))
-                .min();
-            end_month = recurring
-                .iter()
-                .filter_map(|r| {
-                    r.end_date
-                        .as_deref()
-                        .and_then(month_key_from_date)
-                        .or_else(|| Some(current_month.clone()))
-                })
-                .max();
-        }
-
-        if let (Some(mut start_month), Some(mut end_month)) = (start_month, end_month) {
-            if start_month < bootstrap_month {
-                start_month = bootstrap_month;
-            }
-            if end_month < start_month {
-                end_month = start_month.clone();
-            }
-            let expanded = expenses::expand_recurring_expenses(&recurring, &start_month, &end_month);
+        if let Some(expanded) = expand_recurring_for_report(&recurring, &rewards, &config.bootstrap_date) {
             println!(
                 "  Expanded {} recurring expenses into {} monthly entries",
                 recurring.len(),
@@ -2864,6 +6238,7 @@ async fn run_report_generation(args: Args, cache: Cache) -> Result<()> {
         }
     }
 
+    run_log.record_rows("expenses", all_expenses.len());
     let total_expense = expenses::total_expenses(&all_expenses);
     if all_expenses.is_empty() {
         println!("  No expenses recorded\n");
@@ -2875,44 +6250,366 @@ async fn run_report_generation(args: Args, cache: Cache) -> Result<()> {
         );
     }
 
-    // Step 8: Fetch historical prices (with caching)
-    println!("Fetching historical SOL prices...");
-    let price_cache = fetch_prices_with_cache(
-        &cache,
-        &rewards,
-        &transfers,
-        &config.coingecko_api_key,
-        dune_api_key,
-        args.no_cache,
-    )
-    .await?;
-    println!("  Cached {} daily prices\n", price_cache.len());
+    // Step 8: Historical prices (synced in step 4; none off mainnet)
+    let mut price_cache = if cluster::prices_enabled() {
+        cache.get_prices().await?
+    } else {
+        prices::PriceCache::new()
+    };
+    println!("Loaded {} daily prices\n", price_cache.len());
+
+    // Step 8.5: Fetch hourly prices for large withdrawals and BAM claims
+    let hourly_prices = if config.hourly_pricing_enabled {
+        println!("Fetching hourly SOL prices for large events...");
+        let hourly = fetch_hourly_prices_with_cache(cache, &config, &categorized, &mut bam_claims)
+            .await
+            .unwrap_or_else(|e| {
+                eprintln!("  Warning: Failed to fetch hourly prices: {}", e);
+                run_log.record_error("hourly_prices", &e);
+                prices::HourlyPriceCache::new()
+            });
+        run_log.record_rows("hourly_prices", hourly.len());
+        println!("  Cached {} hourly prices\n", hourly.len());
+        hourly
+    } else {
+        prices::HourlyPriceCache::new()
+    };
+
+    // Business shares of split withdrawals become expenses once prices are known
+    let split_expenses = owner_draw::split_expenses(&categorized, &price_cache, &hourly_prices);
+    if !split_expenses.is_empty() {
+        println!(
+            "  {} withdrawal split(s) add ${:.2} of reimbursed business expenses\n",
+            split_expenses.len(),
+            expenses::total_expenses(&split_expenses)
+        );
+        all_expenses.extend(split_expenses);
+    }
+
+    // Fees paid on failed (non-vote) transactions, one expense per month
+    let failed_fee_expenses = failed_fees::monthly_expenses(&cache.get_failed_tx_fees().await?, &price_cache);
+    if !failed_fee_expenses.is_empty() {
+        println!(
+            "  Failed transactions cost ${:.2} in fees across {} month(s)\n",
+            expenses::total_expenses(&failed_fee_expenses),
+            failed_fee_expenses.len()
+        );
+        all_expenses.extend(failed_fee_expenses);
+    }
+
+    // Fees paid on operational (claim, withdrawal, management) transactions, kept out of vote costs
+    let ops_fee_expenses = ops_fees::monthly_expenses(&cache.get_ops_fees().await?, &price_cache);
+    if !ops_fee_expenses.is_empty() {
+        println!(
+            "  Operational transactions cost ${:.2} in fees across {} month(s)\n",
+            expenses::total_expenses(&ops_fee_expenses),
+            ops_fee_expenses.len()
+        );
+        all_expenses.extend(ops_fee_expenses);
+    }
 
     // Step 9: Generate reports
-    if let Some(year) = args.year {
-        println!("Generating reports for year {}...", year);
+    if let Some(period) = &period {
+        println!("Generating reports for {}...", period.label);
     } else {
         println!("Generating reports...");
     }
+    // The summary aggregates the per-epoch tables in SQL (everything above is cached by now)
+    let mut aggregates = cache.get_monthly_aggregates(start_epoch, end_epoch).await?;
+    if !config.doublezero_enabled {
+        aggregates.doublezero_fees = Default::default();
+    }
+    // Imported separately (`dune network-fees`); client_uplift.csv is written once present
+    let network_fee_medians = cache.get_network_fee_medians(start_epoch, end_epoch).await?;
+    let fiat_deposits = cache.get_fiat_deposits().await?;
+    // Impossible rows (negative amounts, future dates, ...) go to exceptions.csv instead
+    let exceptions = validation::quarantine(
+        validation::ReportRows {
+            rewards: &mut rewards,
+            mev_claims: &mut mev_claims,
+            bam_claims: &mut bam_claims,
+            leader_fees: &mut leader_fees,
+            doublezero_fees: &mut doublezero_fees,
+            vote_costs: &mut vote_costs,
+            expenses: &mut all_expenses,
+            prices: &mut price_cache,
+            aggregates: &mut aggregates,
+        },
+        &timezone::today().to_string(),
+    );
     let report_data = reports::ReportData {
         rewards: &rewards,
         categorized: &categorized,
         mev_claims: &mev_claims,
         bam_claims: &bam_claims,
         leader_fees: &leader_fees,
+        network_fee_medians: &network_fee_medians,
         doublezero_fees: &doublezero_fees,
         vote_costs: &vote_costs,
+        epoch_performance: &epoch_performance,
+        delegator_stake: &delegator_stake,
         expenses: &all_expenses,
+        fiat_deposits: &fiat_deposits,
         prices: &price_cache,
+        hourly_prices: &hourly_prices,
+        aggregates: &aggregates,
+        exceptions: &exceptions,
         config: &config,
     };
-    reports::generate_all_reports(&args.output_dir, &report_data, args.year)?;
+    let metadata = build_report_metadata(cache, args.config.as_ref(), &report_data, period.as_ref()).await?;
+    let fallback_rows = reports::generate_all_reports(&args.output_dir, &report_data, period.as_ref(), &metadata)?;
+    record_income_lots(cache, &report_data).await?;
+    write_unapproved_expenses(cache, &args.output_dir).await?;
+    warn_duplicates(cache, &report_data).await?;
+    record_fingerprints(cache, &args.output_dir).await?;
+    // A dry run's reports are scratch copies, so they aren't handed to hooks or uploaded
+    if !args.dry_run {
+        hooks::run(&config.hooks, &args.output_dir, &metadata).await?;
+        if let Some(gsheets) = file_config.gsheets.as_ref().filter(|g| g.sync_after_ingestion) {
+            match exports::gsheets::sync(gsheets, &args.output_dir).await {
+                Ok(tabs) => println!("  Synced {} tab(s) to Google Sheets", tabs),
+                Err(e) => eprintln!("  Warning: Google Sheets sync failed: {:#}", e),
+            }
+        }
+        if let Some(s3) = file_config.s3.as_ref().filter(|s| s.upload_after_run) {
+            match exports::s3::publish(s3, &args.output_dir).await {
+                Ok((prefix, objects)) => println!("  Uploaded {} object(s) to s3://{}/{}/", objects, s3.bucket, prefix),
+                Err(e) => eprintln!("  Warning: S3 upload failed: {:#}", e),
+            }
+        }
+    }
+
+    // Step 10: Print summary, then whether identity can cover the coming vote fees
+    reports::print_summary(&report_data, period.as_ref());
+    if let Err(e) = print_vote_topup(&rpc_client, &config, &epoch_info, &vote_costs) {
+        eprintln!("  Warning: Could not check identity balance for vote fees: {}", e);
+    }
+
+    println!("\nDone! Reports written to: {}", args.output_dir.display());
 
-    // Step 10: Print summary
-    reports::print_summary(&report_data, args.year);
+    // Step 11: Strict checks (completed epochs only; the current epoch never has data yet)
+    if args.strict {
+        let completed_end = end_epoch.min(current_epoch.saturating_sub(1));
+        let coverage = cache.get_epoch_coverage(Some(start_epoch), Some(completed_end)).await?;
+        strict::StrictFailure::check(
+            [
+                strict::check_epoch_coverage(&coverage),
+                strict::check_fallback_prices(fallback_rows),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Metadata stamped into the reports: cache state, pricing, tool version and config hash
+async fn build_report_metadata(
+    cache: &Cache,
+    config_path: Option<&PathBuf>,
+    report_data: &reports::ReportData<'_>,
+    period: Option<&period::ReportPeriod>,
+) -> Result<report_metadata::ReportMetadata> {
+    let runs = cache.get_ingestion_runs(20).await?;
+    let config_toml = std::fs::read(resolve_config_path(config_path)).ok();
+    Ok(report_metadata::ReportMetadata::new(
+        report_data,
+        period,
+        &runs,
+        config_toml.as_deref(),
+    ))
+}
+
+/// List pending and rejected expenses next to the reports they were left out of
+/// (removing a stale list once everything is reviewed)
+async fn write_unapproved_expenses(cache: &Cache, output_dir: &Path) -> Result<()> {
+    let path = output_dir.join(constants::UNAPPROVED_EXPENSES_FILENAME);
+    let unapproved = cache.get_unapproved_expenses().await?;
+    if unapproved.is_empty() {
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+        }
+        return Ok(());
+    }
+
+    expenses::export_to_csv(&unapproved, &path)?;
+    let pending: Vec<_> = unapproved
+        .iter()
+        .filter(|e| e.status == ExpenseStatus::Pending)
+        .cloned()
+        .collect();
+    println!(
+        "  {} expense(s) awaiting review (${:.2}) and {} rejected are not in the reports: {}",
+        pending.len(),
+        expenses::total_expenses(&pending),
+        unapproved.len() - pending.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Fingerprint every report in the output directory so copies can be checked later
+async fn record_fingerprints(cache: &Cache, output_dir: &Path) -> Result<()> {
+    let mut recorded = 0;
+    for path in fingerprint::report_files(output_dir)? {
+        let file_name = path
+            .strip_prefix(output_dir)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        if cache
+            .store_report_fingerprint(&file_name, &fingerprint::fingerprint_file(&path)?)
+            .await?
+        {
+            recorded += 1;
+        }
+    }
+    if recorded > 0 {
+        println!(
+            "  Fingerprinted {} new report version(s); check a copy with 'validator-accounting verify-report <file>'",
+            recorded
+        );
+    }
+    Ok(())
+}
+
+/// Point at likely double-counted rows in the reports just written
+async fn warn_duplicates(cache: &Cache, report_data: &reports::ReportData<'_>) -> Result<()> {
+    let entries = dedupe::collect_entries(report_data);
+    let resolved = cache.get_resolved_duplicate_ids().await?;
+    let pairs = dedupe::find_duplicates(&entries, dedupe::DEFAULT_WINDOW_DAYS, &resolved);
+    if !pairs.is_empty() {
+        println!(
+            "  Warning: {} likely duplicate income/expense pair(s); review with 'validator-accounting dedupe scan'",
+            pairs.len()
+        );
+    }
+    Ok(())
+}
+
+/// Generate every report from the cache alone (`--offline`), after listing what is stale
+/// `--dry-run`: the full sync against a scratch copy of the cache (reports go to a scratch
+/// directory), then the per-table row changes against the starting snapshot
+async fn run_dry_run(mut args: Args, cache: &Cache) -> Result<()> {
+    let scratch = std::env::temp_dir().join(format!("validator-accounting-dry-run-{}", std::process::id()));
+    if scratch.exists() {
+        std::fs::remove_dir_all(&scratch)?;
+    }
+    std::fs::create_dir_all(scratch.join("output"))?;
+
+    let base_path = scratch.join("base.sqlite");
+    let synced_path = scratch.join("synced.sqlite");
+    let result: Result<()> = async {
+        cache.snapshot_to(&base_path).await?;
+        std::fs::copy(&base_path, &synced_path)?;
+        let synced = Cache::open(&synced_path).await?;
+
+        args.output_dir = scratch.join("output");
+        run_report_generation(args, &synced, &mut IngestionRunLog::default()).await?;
+        dry_run::print(&dry_run::compare(&synced, &base_path).await?);
+        Ok(())
+    }
+    .await;
+
+    if let Err(e) = std::fs::remove_dir_all(&scratch) {
+        eprintln!("Warning: Failed to remove {}: {}", scratch.display(), e);
+    }
+    result
+}
+
+async fn run_offline_report(args: Args, cache: &Cache) -> Result<()> {
+    println!("Block Parliament Validator Financial Tracker");
+    println!("=============================================\n");
 
+    let config = load_report_config(args.config.as_ref())?;
+    let period = args.period.resolve(&config)?;
+    println!("Vote Account: {}", config.vote_account);
+    println!("Identity: {}\n", config.identity);
+    offline::print_staleness(&offline::staleness(cache).await?);
+
+    let inputs = CachedReportInputs::load(cache, &config).await?;
+    let report_data = inputs.report_data(&config);
+    if let Some(period) = &period {
+        println!("Generating reports for {} from cache...", period.label);
+    } else {
+        println!("Generating reports from cache...");
+    }
+    let metadata = build_report_metadata(cache, args.config.as_ref(), &report_data, period.as_ref()).await?;
+    let fallback_rows = reports::generate_all_reports(&args.output_dir, &report_data, period.as_ref(), &metadata)?;
+    record_income_lots(cache, &report_data).await?;
+    write_unapproved_expenses(cache, &args.output_dir).await?;
+    warn_duplicates(cache, &report_data).await?;
+    record_fingerprints(cache, &args.output_dir).await?;
+    hooks::run(&config.hooks, &args.output_dir, &metadata).await?;
+    reports::print_summary(&report_data, period.as_ref());
     println!("\nDone! Reports written to: {}", args.output_dir.display());
 
+    if args.strict {
+        let completed_end = offline::estimated_current_epoch().saturating_sub(1);
+        let coverage = cache
+            .get_epoch_coverage(Some(config.first_reward_epoch), Some(completed_end))
+            .await?;
+        strict::StrictFailure::check(
+            [
+                strict::check_epoch_coverage(&coverage),
+                strict::check_fallback_prices(fallback_rows),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Tax report from cached transfers, expenses and prices (`tax --offline`)
+async fn offline_tax_report(
+    cache: &Cache,
+    config_path: Option<&PathBuf>,
+    output_dir: &Path,
+    period: PeriodArgs,
+) -> Result<()> {
+    let config = load_report_config(config_path)?;
+    let period = period.resolve(&config)?;
+    offline::print_staleness(&offline::staleness(cache).await?);
+
+    let inputs = CachedReportInputs::load(cache, &config).await?;
+    std::fs::create_dir_all(output_dir)?;
+    let tax_data = tax_report::TaxReportData {
+        config: &config,
+        categorized: &inputs.categorized,
+        doublezero_fees: &inputs.doublezero_fees,
+        vote_costs: &inputs.vote_costs,
+        expenses: &inputs.expenses,
+        prices: &inputs.prices,
+        hourly_prices: &inputs.hourly_prices,
+    };
+    tax_report::generate_tax_report(output_dir, &tax_data, period.as_ref())?;
+    Ok(())
+}
+
+/// Recommend an identity top-up when its balance won't cover the projected vote fees
+fn print_vote_topup(
+    rpc_client: &solana_client::rpc_client::RpcClient,
+    config: &config::Config,
+    epoch_info: &solana_sdk::epoch_info::EpochInfo,
+    vote_costs: &[vote_costs::EpochVoteCost],
+) -> Result<()> {
+    let identity_lamports = rpc_client.get_balance(&config.identity)?;
+    let vote_lamports = rpc_client.get_balance(&config.vote_account)?;
+    let vote_rent = rpc_client.get_minimum_balance_for_rent_exemption(constants::VOTE_ACCOUNT_SIZE)?;
+    let topup = vote_topup::assess(
+        identity_lamports,
+        vote_lamports.saturating_sub(vote_rent),
+        vote_topup::recent_cost_per_epoch(vote_costs),
+        epoch_info.slot_index,
+        epoch_info.slots_in_epoch,
+        chrono::Utc::now().timestamp(),
+    );
+    vote_topup::print_recommendation(&topup);
     Ok(())
 }
 
@@ -2943,6 +6640,9 @@ async fn fetch_rewards_with_cache(
 
     // Find missing completed epochs (exclude current - it's always "missing" but not fetchable)
     let missing = cache.get_missing_reward_epochs(start_epoch, completed_end).await?;
+    // Epochs seeded by `import fetch` are refetched to verify them, but an epoch the chain
+    // can't serve keeps its imported amount rather than being cached as zero
+    let pending_imports = cache.get_unverified_import_epochs().await?;
 
     if !missing.is_empty() {
         let epoch_word = if missing.len() == 1 { "epoch" } else { "epochs" };
@@ -2955,6 +6655,7 @@ async fn fetch_rewards_with_cache(
             match transactions::fetch_inflation_rewards(config, *epoch, Some(*epoch)).await {
                 Ok(mut fetched) if !fetched.is_empty() => {
                     cache.store_epoch_rewards(&fetched).await?;
+                    rewards.retain(|r| r.epoch != *epoch);
                     rewards.append(&mut fetched);
                 }
                 _ => {
@@ -2980,12 +6681,13 @@ async fn fetch_rewards_with_cache(
 
                         // Track which epochs were filled by Dune
                         let filled_epochs: std::collections::HashSet<u64> = needed.iter().map(|r| r.epoch).collect();
+                        rewards.retain(|r| !filled_epochs.contains(&r.epoch));
                         rewards.extend(needed);
 
                         // Cache epochs that remain unfilled (no data exists)
                         let unfilled: Vec<_> = rpc_failures
                             .iter()
-                            .filter(|e| !filled_epochs.contains(e))
+                            .filter(|e| !filled_epochs.contains(e) && !pending_imports.contains(e))
                             .map(|&epoch| transactions::EpochReward {
                                 epoch,
                                 effective_slot: epoch * constants::SLOTS_PER_EPOCH,
@@ -3004,6 +6706,7 @@ async fn fetch_rewards_with_cache(
                         // Dune returned data but none for our requested epochs
                         let empty_epochs: Vec<_> = rpc_failures
                             .iter()
+                            .filter(|e| !pending_imports.contains(e))
                             .map(|&epoch| transactions::EpochReward {
                                 epoch,
                                 effective_slot: epoch * constants::SLOTS_PER_EPOCH,
@@ -3042,7 +6745,7 @@ async fn fetch_rewards_with_cache(
     Ok(rewards)
 }
 
-/// Fetch MEV claims with caching
+/// Fetch MEV claims from every configured provider, with caching
 ///
 /// MEV claims only exist for completed epochs (distributed at epoch boundaries),
 /// so we only need to check for missing completed epochs, not the current epoch.
@@ -3054,60 +6757,58 @@ async fn fetch_mev_with_cache(
     current_epoch: u64,
     no_cache: bool,
 ) -> Result<Vec<jito::MevClaim>> {
-    if no_cache {
-        let claims = jito::fetch_mev_claims(config).await?;
-        cache.store_mev_claims(&claims).await?;
-        let mut filtered: Vec<_> = claims
-            .into_iter()
-            .filter(|c| c.epoch >= start_epoch && c.epoch <= end_epoch)
-            .collect();
-        filtered.sort_by_key(|c| c.epoch);
-        return Ok(filtered);
-    }
-
     // MEV claims only exist for completed epochs
     let completed_end = end_epoch.min(current_epoch.saturating_sub(1));
+    let cached = cache.get_mev_claims(start_epoch, completed_end).await?;
 
-    // Get cached claims for completed epochs
-    let mut claims = cache.get_mev_claims(start_epoch, completed_end).await?;
-    let cached_count = claims.len();
-
-    // Check if we need to fetch from Jito API
-    // The Jito API returns all epochs with MEV for this validator at once.
-    // If we have MEV data for a recent completed epoch, we're up to date.
-    // (Epochs without MEV rewards are not returned by the API, so checking
-    // for "missing" epochs would cause constant re-fetching.)
-    let has_recent_data = claims.iter().any(|c| c.epoch >= completed_end.saturating_sub(1));
-
-    if !has_recent_data {
-        println!(
-            "    Fetching from Jito API (need data through epoch {})...",
-            completed_end
-        );
-        let fresh_claims = jito::fetch_mev_claims(config).await?;
-
-        // Store completed epochs in cache
-        let completed: Vec<_> = fresh_claims
+    let mut claims = Vec::new();
+    for provider in mev::configured_providers(config) {
+        let mut provider_claims: Vec<_> = cached
             .iter()
-            .filter(|c| c.epoch < current_epoch)
+            .filter(|c| c.provider == provider.name())
             .cloned()
             .collect();
-        if !completed.is_empty() {
-            cache.store_mev_claims(&completed).await?;
-            println!("    Cached {} completed epochs", completed.len());
+
+        // Providers report only epochs with tips, so a claim for a recent completed
+        // epoch means we're up to date (checking for "missing" epochs would re-fetch
+        // constantly for epochs that simply had no MEV).
+        let has_recent_data = provider_claims
+            .iter()
+            .any(|c| c.epoch >= completed_end.saturating_sub(1));
+
+        if no_cache || !has_recent_data {
+            println!(
+                "    Fetching {} tips (need data through epoch {})...",
+                provider.name(),
+                completed_end
+            );
+            let fresh_claims = provider.fetch_claims(config, start_epoch, completed_end).await?;
+
+            // Store completed epochs in cache
+            let completed: Vec<_> = fresh_claims
+                .iter()
+                .filter(|c| c.epoch < current_epoch)
+                .cloned()
+                .collect();
+            if !completed.is_empty() {
+                cache.store_mev_claims(&completed).await?;
+                println!("    Cached {} completed epochs", completed.len());
+            }
+
+            // Filter to requested range
+            provider_claims = fresh_claims
+                .into_iter()
+                .filter(|c| c.epoch >= start_epoch && c.epoch <= end_epoch)
+                .collect();
+        } else {
+            println!("    ({} {} epochs from cache)", provider_claims.len(), provider.name());
         }
 
-        // Filter to requested range
-        claims = fresh_claims
-            .into_iter()
-            .filter(|c| c.epoch >= start_epoch && c.epoch <= end_epoch)
-            .collect();
-    } else {
-        println!("    ({} epochs from cache)", cached_count);
+        claims.extend(provider_claims);
     }
 
     // Sort by epoch
-    claims.sort_by_key(|c| c.epoch);
+    claims.sort_by(|a, b| (a.epoch, &a.provider).cmp(&(b.epoch, &b.provider)));
 
     Ok(claims)
 }
@@ -3373,10 +7074,19 @@ async fn fetch_prices_with_cache(
     dune_api_key: Option<&str>,
     no_cache: bool,
 ) -> Result<prices::PriceCache> {
+    // Testnet/devnet SOL has no market price; reports show USD as N/A
+    if !cluster::prices_enabled() {
+        println!(
+            "    (skipped: USD valuation is disabled on {})",
+            cluster::current().as_str()
+        );
+        return Ok(prices::PriceCache::new());
+    }
+
     if no_cache {
-        let prices = prices::fetch_historical_prices(rewards, transfers, api_key, dune_api_key).await?;
-        cache.store_prices(&prices).await?;
-        return Ok(prices);
+        let quotes = prices::fetch_historical_prices(rewards, transfers, api_key, dune_api_key).await?;
+        cache.store_price_quotes(&quotes).await?;
+        return Ok(prices::quoted_prices(&quotes));
     }
 
     // Get cached prices
@@ -3391,10 +7101,8 @@ async fn fetch_prices_with_cache(
     // Merge new prices into cache
     let new_count = new_prices.len();
     if new_count > 0 {
-        for (date, price) in new_prices {
-            price_cache.insert(date, price);
-        }
-        cache.store_prices(&price_cache).await?;
+        cache.store_price_quotes(&new_prices).await?;
+        price_cache.extend(prices::quoted_prices(&new_prices));
     }
 
     if cached_count > 0 {
@@ -3408,6 +7116,88 @@ async fn fetch_prices_with_cache(
     Ok(price_cache)
 }
 
+/// Fetch hourly prices around large withdrawals and BAM claims (with caching)
+///
+/// BAM claims from the Kobe API carry no timestamp, so their block time is resolved
+/// from the claim-status account's signatures once and written back to the cache.
+/// Failures are non-fatal: affected events keep their daily price.
+async fn fetch_hourly_prices_with_cache(
+    cache: &Cache,
+    config: &config::Config,
+    categorized: &transactions::CategorizedTransfers,
+    bam_claims: &mut [bam::BamClaim],
+) -> Result<prices::HourlyPriceCache> {
+    if !cluster::prices_enabled() {
+        return Ok(prices::HourlyPriceCache::new());
+    }
+
+    let mut hourly = cache.get_hourly_prices().await?;
+    let cached_count = hourly.len();
+
+    // Resolve BAM claim block times that the API didn't provide
+    if bam_claims.iter().any(|c| c.claimed_at.is_none()) {
+        let rpc_client = rpc::new_rpc_client(&config.rpc_url, CommitmentConfig::confirmed());
+        let mut resolved = Vec::new();
+        for claim in bam_claims.iter_mut().filter(|c| c.claimed_at.is_none()) {
+            match bam::fetch_claim_block_time(&rpc_client, claim) {
+                Ok(Some(ts)) => {
+                    claim.claimed_at = chrono::DateTime::from_timestamp(ts, 0).map(|dt| dt.to_rfc3339());
+                    resolved.push(claim.clone());
+                }
+                Ok(None) => {}
+                Err(e) => eprintln!(
+                    "    Warning: block time lookup failed for BAM epoch {}: {}",
+                    claim.epoch, e
+                ),
+            }
+            tokio::time::sleep(Duration::from_millis(constants::RPC_SIGNATURE_DELAY_MS)).await;
+        }
+        if !resolved.is_empty() {
+            cache.store_bam_claims(&resolved).await?;
+        }
+    }
+
+    let mut event_timestamps: Vec<i64> = categorized
+        .withdrawals
+        .iter()
+        .filter(|w| w.amount_sol >= config.hourly_min_withdrawal_sol)
+        .filter_map(|w| w.timestamp)
+        .collect();
+    event_timestamps.extend(bam_claims.iter().filter_map(|c| c.claimed_at_timestamp()));
+    event_timestamps.sort_unstable();
+    event_timestamps.dedup();
+
+    let mut fetched_windows = 0;
+    for ts in event_timestamps {
+        // One window covers every event within a day of it
+        if prices::nearest_hourly_price(&hourly, ts).is_some() {
+            continue;
+        }
+        if fetched_windows > 0 {
+            tokio::time::sleep(Duration::from_millis(constants::HOURLY_PRICE_FETCH_DELAY_MS)).await;
+        }
+        match prices::fetch_hourly_prices_around(ts, &config.coingecko_api_key).await {
+            Ok(window) => {
+                hourly.extend(window);
+                fetched_windows += 1;
+            }
+            Err(e) => eprintln!("    Warning: hourly price fetch failed for {}: {}", ts, e),
+        }
+    }
+
+    if fetched_windows > 0 {
+        cache.store_hourly_prices(&hourly).await?;
+        println!(
+            "    ({} from cache, {} new from {} windows)",
+            cached_count,
+            hourly.len() - cached_count,
+            fetched_windows
+        );
+    }
+
+    Ok(hourly)
+}
+
 /// Fetch SOL transfers with caching - only fetch new transactions since last run
 /// Falls back to Dune Analytics if RPC fails and API key is configured
 async fn fetch_transfers_with_cache(
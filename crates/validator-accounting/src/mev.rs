@@ -0,0 +1,237 @@
+//! MEV tip providers
+//!
+//! Jito is the default source, but validators can also run alternative tip routers.
+//! Each source implements `MevProvider` and produces `MevClaim`s tagged with its
+//! name, so every provider feeds the same `mev_claims` table (keyed by epoch and
+//! provider) and the reports sum across them.
+//!
+//! `TipDistributionProvider` covers Jito-style tip-distribution programs generically:
+//! each epoch's distribution account is a PDA of `[seed, vote account, epoch]` under
+//! the program, and claims move lamports from it to the recipient. The claims are
+//! found by scanning that PDA's transactions and summing the recipient's balance
+//! increases.
+
+use anyhow::{Context, Result};
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::UiTransactionEncoding;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::time::Duration;
+use tokio::time::sleep;
+
+use crate::config::{Config, TipDistributionConfig};
+use crate::jito::{self, MevClaim};
+use crate::transactions::{self, epoch_to_date};
+use crate::{constants, rpc};
+
+/// Boxed future returned by provider fetches (keeps the trait object-safe)
+pub type ProviderFuture<'a> = Pin<Box<dyn Future<Output = Result<Vec<MevClaim>>> + Send + 'a>>;
+
+/// A source of per-epoch MEV tip income
+pub trait MevProvider: Send + Sync {
+    /// Name recorded in `mev_claims.provider`
+    fn name(&self) -> &str;
+
+    /// Claims for `start_epoch..=end_epoch` (providers that only serve full history
+    /// may return more; callers filter)
+    fn fetch_claims<'a>(&'a self, config: &'a Config, start_epoch: u64, end_epoch: u64) -> ProviderFuture<'a>;
+}
+
+/// Jito tips from the Jito API
+pub struct JitoProvider;
+
+impl MevProvider for JitoProvider {
+    fn name(&self) -> &str {
+        jito::PROVIDER
+    }
+
+    fn fetch_claims<'a>(&'a self, config: &'a Config, _start_epoch: u64, _end_epoch: u64) -> ProviderFuture<'a> {
+        Box::pin(jito::fetch_mev_claims(config))
+    }
+}
+
+/// A tip-distribution program configured under `[[mev.tip_distribution]]`
+#[derive(Debug, Clone)]
+pub struct TipDistributionSource {
+    pub name: String,
+    pub program_id: Pubkey,
+    pub seed: String,
+    /// Claim recipient (None: the vote account)
+    pub recipient: Option<Pubkey>,
+}
+
+impl TipDistributionSource {
+    pub fn from_config(source: &TipDistributionConfig) -> Result<Self> {
+        anyhow::ensure!(
+            !source.name.is_empty() && source.name != jito::PROVIDER,
+            "mev.tip_distribution name must be set and not \"{}\"",
+            jito::PROVIDER
+        );
+        Ok(Self {
+            name: source.name.clone(),
+            program_id: Pubkey::from_str(&source.program_id)
+                .with_context(|| format!("Invalid mev.tip_distribution program_id for {}", source.name))?,
+            seed: source.seed.clone(),
+            recipient: source
+                .recipient
+                .as_deref()
+                .map(Pubkey::from_str)
+                .transpose()
+                .with_context(|| format!("Invalid mev.tip_distribution recipient for {}", source.name))?,
+        })
+    }
+
+    /// The distribution account for `vote_account` in `epoch`
+    pub fn distribution_account(&self, vote_account: &Pubkey, epoch: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[self.seed.as_bytes(), vote_account.as_ref(), &epoch.to_le_bytes()],
+            &self.program_id,
+        )
+        .0
+    }
+}
+
+/// Tips found by scanning a tip-distribution program's per-epoch accounts
+pub struct TipDistributionProvider {
+    pub source: TipDistributionSource,
+}
+
+impl MevProvider for TipDistributionProvider {
+    fn name(&self) -> &str {
+        &self.source.name
+    }
+
+    fn fetch_claims<'a>(&'a self, config: &'a Config, start_epoch: u64, end_epoch: u64) -> ProviderFuture<'a> {
+        Box::pin(self.scan(config, start_epoch, end_epoch))
+    }
+}
+
+impl TipDistributionProvider {
+    async fn scan(&self, config: &Config, start_epoch: u64, end_epoch: u64) -> Result<Vec<MevClaim>> {
+        let client = rpc::new_rpc_client(&config.rpc_url, CommitmentConfig::confirmed());
+        println!(
+            "    Scanning {} tip distribution accounts for epochs {}-{}...",
+            self.source.name, start_epoch, end_epoch
+        );
+
+        let mut claims = Vec::new();
+        for epoch in start_epoch..=end_epoch {
+            let mut received: u64 = 0;
+            // An epoch around a vote account migration can pay out to both accounts
+            for vote_account in config.vote_accounts_for_epoch(epoch) {
+                let recipient = self.source.recipient.unwrap_or(vote_account);
+                let account = self.source.distribution_account(&vote_account, epoch);
+                let signatures = client.get_signatures_for_address_with_config(
+                    &account,
+                    GetConfirmedSignaturesForAddress2Config {
+                        before: None,
+                        until: None,
+                        limit: Some(100),
+                        commitment: Some(CommitmentConfig::confirmed()),
+                    },
+                )?;
+                sleep(Duration::from_millis(constants::RPC_SIGNATURE_DELAY_MS)).await;
+
+                for sig_info in signatures.iter().filter(|s| s.err.is_none()) {
+                    let Ok(signature) = Signature::from_str(&sig_info.signature) else {
+                        continue;
+                    };
+                    sleep(Duration::from_millis(constants::RPC_TRANSACTION_DELAY_MS)).await;
+                    let tx = client.get_transaction_with_config(
+                        &signature,
+                        RpcTransactionConfig {
+                            encoding: Some(UiTransactionEncoding::JsonParsed),
+                            commitment: Some(CommitmentConfig::confirmed()),
+                            max_supported_transaction_version: Some(0),
+                        },
+                    )?;
+                    let Some(meta) = &tx.transaction.meta else {
+                        continue;
+                    };
+                    let Some(keys) = transactions::extract_account_keys(&tx.transaction.transaction, false) else {
+                        continue;
+                    };
+                    if !keys.contains(&self.source.program_id) {
+                        continue;
+                    }
+                    received += recipient_credit(&keys, &meta.pre_balances, &meta.post_balances, &recipient);
+                }
+            }
+
+            if received > 0 {
+                println!("      Epoch {}: {:.4} SOL", epoch, received as f64 / 1e9);
+                claims.push(MevClaim {
+                    epoch,
+                    provider: self.source.name.clone(),
+                    // The distribution account's total isn't tracked, only our share
+                    total_tips_lamports: 0,
+                    commission_lamports: received,
+                    amount_sol: received as f64 / 1e9,
+                    date: Some(epoch_to_date(epoch)),
+                });
+            }
+        }
+
+        Ok(claims)
+    }
+}
+
+/// Lamports `recipient` gained in a transaction (0 if it isn't in it or lost lamports)
+fn recipient_credit(keys: &[Pubkey], pre_balances: &[u64], post_balances: &[u64], recipient: &Pubkey) -> u64 {
+    keys.iter()
+        .position(|k| k == recipient)
+        .and_then(|i| Some(post_balances.get(i)?.saturating_sub(*pre_balances.get(i)?)))
+        .unwrap_or(0)
+}
+
+/// Providers enabled in config, Jito first
+pub fn configured_providers(config: &Config) -> Vec<Box<dyn MevProvider>> {
+    let mut providers: Vec<Box<dyn MevProvider>> = Vec::new();
+    if config.jito_mev_enabled {
+        providers.push(Box::new(JitoProvider));
+    }
+    for source in &config.tip_distribution_sources {
+        providers.push(Box::new(TipDistributionProvider { source: source.clone() }));
+    }
+    providers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn credits_only_recipient_increases() {
+        let recipient = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let keys = [other, recipient];
+
+        assert_eq!(recipient_credit(&keys, &[10, 100], &[5, 150], &recipient), 50);
+        assert_eq!(recipient_credit(&keys, &[10, 100], &[5, 90], &recipient), 0);
+        assert_eq!(recipient_credit(&keys[..1], &[10], &[5], &recipient), 0);
+    }
+
+    #[test]
+    fn distribution_account_is_per_epoch() {
+        let source = TipDistributionSource {
+            name: "router".to_string(),
+            program_id: Pubkey::new_unique(),
+            seed: "TIP_DISTRIBUTION_ACCOUNT".to_string(),
+            recipient: None,
+        };
+        let vote = Pubkey::new_unique();
+        assert_eq!(
+            source.distribution_account(&vote, 900),
+            source.distribution_account(&vote, 900)
+        );
+        assert_ne!(
+            source.distribution_account(&vote, 900),
+            source.distribution_account(&vote, 901)
+        );
+    }
+}
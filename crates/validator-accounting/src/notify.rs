@@ -0,0 +1,35 @@
+//! Operator notifications
+//!
+//! Posts to the same chat webhook as bp-web (`NOTIFY_WEBHOOK_URL`), carrying the text
+//! as both `text` (Slack) and `content` (Discord). Unset means notifications are off.
+
+use anyhow::{Result, bail};
+use std::time::Duration;
+
+/// Longest message sent in one post (Discord rejects content over 2000 characters)
+const MAX_MESSAGE_CHARS: usize = 1900;
+
+fn webhook_url() -> Option<String> {
+    std::env::var("NOTIFY_WEBHOOK_URL")
+        .ok()
+        .filter(|url| !url.trim().is_empty())
+}
+
+/// Post `message` to the notification webhook, if one is configured
+pub async fn send(message: &str) -> Result<()> {
+    let Some(url) = webhook_url() else {
+        return Ok(());
+    };
+
+    let message: String = message.chars().take(MAX_MESSAGE_CHARS).collect();
+    let client = reqwest::Client::builder().timeout(Duration::from_secs(10)).build()?;
+    let response = client
+        .post(&url)
+        .json(&serde_json::json!({ "text": message, "content": message }))
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        bail!("notification webhook returned {}", response.status());
+    }
+    Ok(())
+}
@@ -7,7 +7,7 @@ use anyhow::{Context, Result};
 use serde::Deserialize;
 
 use crate::config::NotionConfig;
-use crate::expenses::{Expense, ExpenseCategory};
+use crate::expenses::{Expense, ExpenseCategory, ExpenseStatus};
 
 const NOTION_API_BASE: &str = "https://api.notion.com/v1";
 const NOTION_VERSION: &str = "2022-06-28";
@@ -221,6 +221,9 @@ pub fn hours_to_expenses(entries: &[HoursLogEntry]) -> Vec<Expense> {
             // Tag Notion-derived rows so cache sync can replace only this subset.
             paid_with: if entry.paid { "Notion Paid" } else { "Notion Unpaid" }.to_string(),
             invoice_id: Some(entry.page_id.clone()),
+            entity: None,
+            // Reviewed before it counts; the sync keeps earlier decisions per page
+            status: ExpenseStatus::Pending,
         })
         .collect()
 }
@@ -0,0 +1,169 @@
+//! Offline mode (`--offline`)
+//!
+//! For reviewing the books on a machine without network access, e.g. an airgapped
+//! laptop holding a copy of the cache. Reports, the tax report and every other
+//! cache-backed command run from the cache alone. Commands that only make sense
+//! against live data (balances, API imports, metrics scrapes, the keeper) fail
+//! immediately instead of hanging on connection timeouts, and print how far each
+//! kind of cached data lags behind today. It is installed once at startup, like the
+//! cluster.
+
+use anyhow::Result;
+use chrono::NaiveDate;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::cache::Cache;
+use crate::{timezone, transactions};
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Per-epoch tables checked for staleness, with display names
+const EPOCH_TABLES: [(&str, &str); 6] = [
+    ("epoch_rewards", "Inflation rewards"),
+    ("leader_fees", "Leader fees"),
+    ("mev_claims", "MEV tips"),
+    ("vote_costs", "Vote costs"),
+    ("epoch_performance", "Vote credits"),
+    ("delegator_stake", "Delegator snapshots"),
+];
+
+/// Dated tables checked for staleness, with display names
+const DATE_TABLES: [(&str, &str); 2] = [("sol_transfers", "SOL transfers"), ("prices", "Daily prices")];
+
+/// Install offline mode (set once at startup)
+pub fn install(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+/// Whether `--offline` is in effect
+pub fn enabled() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Fail fast if offline; `what` names the thing that needs the network
+pub fn ensure_online(what: &str) -> Result<()> {
+    if enabled() {
+        anyhow::bail!("{} needs the network, but --offline is set", what);
+    }
+    Ok(())
+}
+
+/// Approximate current epoch (no RPC: estimated from the clock)
+pub fn estimated_current_epoch() -> u64 {
+    transactions::estimate_epoch(chrono::Utc::now().timestamp())
+}
+
+/// How far one kind of cached data lags behind today
+#[derive(Debug, Clone, PartialEq)]
+pub struct Staleness {
+    pub data: &'static str,
+    /// Newest cached epoch or date, None if nothing is cached
+    pub cached_through: Option<String>,
+    /// Epochs or days missing since then (0 = current)
+    pub behind: u64,
+    /// "epoch" or "day"
+    pub unit: &'static str,
+}
+
+impl Staleness {
+    /// Per-epoch data, compared with the last completed epoch
+    pub fn for_epochs(data: &'static str, latest: Option<u64>, last_completed: u64) -> Self {
+        Self {
+            data,
+            cached_through: latest.map(|e| format!("epoch {}", e)),
+            behind: latest.map_or(0, |e| last_completed.saturating_sub(e)),
+            unit: "epoch",
+        }
+    }
+
+    /// Dated data, compared with yesterday (today is never complete)
+    pub fn for_dates(data: &'static str, latest: Option<NaiveDate>, today: NaiveDate) -> Self {
+        Self {
+            data,
+            cached_through: latest.map(|d| d.to_string()),
+            behind: latest.map_or(0, |d| (today - d).num_days().saturating_sub(1).max(0) as u64),
+            unit: "day",
+        }
+    }
+
+    pub fn is_stale(&self) -> bool {
+        self.cached_through.is_none() || self.behind > 0
+    }
+
+    /// "3 epochs behind", "current", or "nothing cached"
+    pub fn describe(&self) -> String {
+        match (&self.cached_through, self.behind) {
+            (None, _) => "nothing cached".to_string(),
+            (Some(_), 0) => "current".to_string(),
+            (Some(_), 1) => format!("1 {} behind", self.unit),
+            (Some(_), n) => format!("{} {}s behind", n, self.unit),
+        }
+    }
+}
+
+/// Staleness of every cached dataset the reports use
+pub async fn staleness(cache: &Cache) -> Result<Vec<Staleness>> {
+    let last_completed = estimated_current_epoch().saturating_sub(1);
+    let today = timezone::today();
+
+    let mut items = Vec::new();
+    for (table, data) in EPOCH_TABLES {
+        let latest = cache.latest_cached_epoch(table).await?;
+        items.push(Staleness::for_epochs(data, latest, last_completed));
+    }
+    for (table, data) in DATE_TABLES {
+        let latest = cache
+            .latest_cached_date(table)
+            .await?
+            .and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok());
+        items.push(Staleness::for_dates(data, latest, today));
+    }
+    Ok(items)
+}
+
+/// Print the staleness table (current epoch is an estimate offline)
+pub fn print_staleness(items: &[Staleness]) {
+    println!(
+        "Offline: cache only, no network (current epoch ~{}, estimated from the clock)",
+        estimated_current_epoch()
+    );
+    for item in items {
+        println!(
+            "  {} {:<20} {:<14} {}",
+            if item.is_stale() { "!" } else { " " },
+            item.data,
+            item.cached_through.as_deref().unwrap_or("-"),
+            item.describe()
+        );
+    }
+    if items.iter().any(Staleness::is_stale) {
+        println!("  Data marked ! would be refreshed by a run without --offline.");
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_staleness_counts_missing_epochs_and_days() {
+        let rewards = Staleness::for_epochs("Inflation rewards", Some(905), 908);
+        assert_eq!(rewards.describe(), "3 epochs behind");
+        assert!(rewards.is_stale());
+
+        let current = Staleness::for_epochs("Leader fees", Some(908), 908);
+        assert_eq!(current.describe(), "current");
+        assert!(!current.is_stale());
+
+        let empty = Staleness::for_epochs("MEV tips", None, 908);
+        assert_eq!(empty.describe(), "nothing cached");
+        assert!(empty.is_stale());
+
+        let today = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let prices = Staleness::for_dates("Daily prices", NaiveDate::from_ymd_opt(2026, 3, 9), today);
+        assert_eq!(prices.describe(), "current");
+        let prices = Staleness::for_dates("Daily prices", NaiveDate::from_ymd_opt(2026, 3, 7), today);
+        assert_eq!(prices.describe(), "2 days behind");
+    }
+}
@@ -0,0 +1,281 @@
+//! Fees paid on operational transactions
+//!
+//! Besides votes, the identity and withdraw authority sign claims, withdrawals and
+//! account management, usually with a priority fee on top of the base fee. Those fees
+//! are a real operating cost, but lumping them into vote costs would throw off the SFDP
+//! reimbursement math, so they get their own `ops_fees` table and expense category.
+//!
+//! For the identity, nearly every signature is a vote. Rather than fetch each one, the
+//! scan drops signatures that also appear on the vote account over the same slot range
+//! and only fetches what is left. Failed transactions are tracked by `failed_fees`.
+
+use anyhow::Result;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::{EncodedTransaction, UiInstruction, UiMessage, UiParsedInstruction};
+use std::collections::{BTreeMap, HashSet};
+
+use crate::constants;
+use crate::expenses::{Expense, ExpenseCategory, ExpenseStatus};
+use crate::failed_fees::{self, ScanState};
+use crate::prices::{PriceCache, get_price};
+use crate::timezone;
+
+/// Vendor shown on ops-fee expenses
+pub const OPS_FEES_VENDOR: &str = "Solana Network";
+
+/// Fee paid by one of our accounts on a successful non-vote transaction
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpsFee {
+    pub signature: String,
+    /// Fee payer (one of our signing accounts)
+    pub account: String,
+    pub slot: u64,
+    pub timestamp: Option<i64>,
+    pub date: Option<String>,
+    /// Total fee (base + priority)
+    pub fee_lamports: u64,
+    /// Part of the fee above the base fee per signature
+    pub priority_fee_lamports: u64,
+    /// First program invoked, ignoring Compute Budget instructions
+    pub program: Option<String>,
+}
+
+/// Scan `account` for successful non-vote transactions it paid for, newest first, from
+/// where `state` says the previous scan stopped (see `failed_fees::list_signatures`).
+///
+/// `vote_account` is set when `account` is the identity: signatures also seen on the vote
+/// account over the same slot range are votes and are skipped without being fetched.
+///
+/// Returns the fees found and the state to store for the next scan.
+pub fn scan(
+    client: &RpcClient,
+    account: &Pubkey,
+    vote_account: Option<&Pubkey>,
+    state: &ScanState,
+) -> Result<(Vec<OpsFee>, ScanState)> {
+    let (signatures, next) = failed_fees::list_signatures(client, account, state)?;
+    let mut candidates: Vec<_> = signatures.into_iter().filter(|s| s.err.is_none()).collect();
+
+    if let Some(vote_account) = vote_account
+        && let Some(oldest) = candidates.iter().map(|s| s.slot).min()
+    {
+        let votes = failed_fees::list_signatures_since(client, vote_account, oldest)?;
+        let votes: HashSet<String> = votes.into_iter().map(|s| s.signature).collect();
+        candidates.retain(|s| !votes.contains(&s.signature));
+    }
+
+    let mut fees = Vec::new();
+    for candidate in candidates {
+        let Some(tx) = failed_fees::fetch_transaction(client, &candidate.signature)? else {
+            continue;
+        };
+        let Some(meta) = tx.transaction.meta.as_ref() else {
+            continue;
+        };
+        let encoded = &tx.transaction.transaction;
+        if meta.err.is_some()
+            || !failed_fees::is_fee_payer(encoded, account)
+            || failed_fees::is_vote_transaction(encoded)
+        {
+            continue;
+        }
+        fees.push(OpsFee {
+            signature: candidate.signature,
+            account: account.to_string(),
+            slot: tx.slot,
+            timestamp: tx.block_time,
+            date: tx.block_time.and_then(timezone::timestamp_to_date),
+            fee_lamports: meta.fee,
+            priority_fee_lamports: priority_fee(meta.fee, signature_count(encoded)),
+            program: first_program(encoded),
+        });
+    }
+
+    Ok((fees, next))
+}
+
+/// Fee above the base fee for `signatures` signatures
+fn priority_fee(fee_lamports: u64, signatures: usize) -> u64 {
+    fee_lamports.saturating_sub(signatures as u64 * constants::LAMPORTS_PER_SIGNATURE)
+}
+
+fn signature_count(tx: &EncodedTransaction) -> usize {
+    match tx {
+        EncodedTransaction::Json(ui_tx) => ui_tx.signatures.len().max(1),
+        _ => 1,
+    }
+}
+
+/// First top-level program other than Compute Budget
+fn first_program(tx: &EncodedTransaction) -> Option<String> {
+    let EncodedTransaction::Json(ui_tx) = tx else {
+        return None;
+    };
+    let UiMessage::Parsed(message) = &ui_tx.message else {
+        return None;
+    };
+    message
+        .instructions
+        .iter()
+        .filter_map(|ix| match ix {
+            UiInstruction::Parsed(UiParsedInstruction::Parsed(pi)) => Some(pi.program_id.clone()),
+            UiInstruction::Parsed(UiParsedInstruction::PartiallyDecoded(pd)) => Some(pd.program_id.clone()),
+            UiInstruction::Compiled(_) => None,
+        })
+        .find(|program| program != constants::COMPUTE_BUDGET_PROGRAM)
+}
+
+/// One expense per month: the month's ops-transaction fees, each valued at its day's price
+pub fn monthly_expenses(fees: &[OpsFee], prices: &PriceCache) -> Vec<Expense> {
+    // month -> (latest date, count, SOL, priority SOL, USD)
+    let mut months: BTreeMap<String, (String, usize, f64, f64, f64)> = BTreeMap::new();
+    for fee in fees {
+        let Some(date) = fee.date.as_deref().filter(|d| d.len() >= 10) else {
+            continue;
+        };
+        let sol = fee.fee_lamports as f64 / 1e9;
+        let entry = months.entry(date[..7].to_string()).or_default();
+        if entry.0.as_str() < date {
+            entry.0 = date.to_string();
+        }
+        entry.1 += 1;
+        entry.2 += sol;
+        entry.3 += fee.priority_fee_lamports as f64 / 1e9;
+        entry.4 += sol * get_price(prices, date);
+    }
+
+    months
+        .into_iter()
+        .map(|(month, (date, count, sol, priority_sol, usd))| Expense {
+            id: None,
+            date,
+            vendor: OPS_FEES_VENDOR.to_string(),
+            category: ExpenseCategory::OpsFees,
+            description: format!(
+                "Fees for {} operational transaction(s) in {} ({:.6} SOL, {:.6} SOL priority)",
+                count, month, sol, priority_sol
+            ),
+            amount_usd: usd,
+            paid_with: "SOL".to_string(),
+            invoice_id: Some(format!("ops-fees:{}", month)),
+            entity: None,
+            status: ExpenseStatus::Approved,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::failed_fees::ScanCursor;
+    use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+    use solana_sdk::signature::Signature;
+
+    fn fee(signature: &str, date: &str, fee_lamports: u64) -> OpsFee {
+        OpsFee {
+            signature: signature.to_string(),
+            account: Pubkey::new_unique().to_string(),
+            slot: 0,
+            timestamp: None,
+            date: Some(date.to_string()),
+            fee_lamports,
+            priority_fee_lamports: priority_fee(fee_lamports, 1),
+            program: None,
+        }
+    }
+
+    #[test]
+    fn test_priority_fee_is_fee_above_base() {
+        assert_eq!(priority_fee(5_000, 1), 0);
+        assert_eq!(priority_fee(105_000, 1), 100_000);
+        assert_eq!(priority_fee(110_000, 2), 100_000);
+        assert_eq!(priority_fee(4_000, 1), 0);
+    }
+
+    #[test]
+    fn test_monthly_expenses_group_and_value_fees() {
+        let fees = vec![
+            fee("a", "2025-06-03", 1_005_000),
+            fee("b", "2025-06-20", 5_000),
+            fee("c", "2025-07-01", 2_005_000),
+        ];
+        let mut prices = PriceCache::new();
+        prices.insert("2025-06-03".to_string(), 100.0.into());
+        prices.insert("2025-06-20".to_string(), 200.0.into());
+        prices.insert("2025-07-01".to_string(), 150.0.into());
+
+        let expenses = monthly_expenses(&fees, &prices);
+        assert_eq!(expenses.len(), 2);
+        assert_eq!(expenses[0].date, "2025-06-20");
+        assert_eq!(expenses[0].category, ExpenseCategory::OpsFees);
+        // 0.001005 SOL at $100 + 0.000005 SOL at $200
+        assert!((expenses[0].amount_usd - 0.1015).abs() < 1e-9);
+        assert!(expenses[0].description.contains("0.001000 SOL priority"));
+        assert_eq!(expenses[1].invoice_id.as_deref(), Some("ops-fees:2025-07"));
+    }
+
+    /// Signatures of a busy account, newest (highest slot) first
+    fn listing(count: u8) -> Vec<RpcConfirmedTransactionStatusWithSignature> {
+        (0..count)
+            .map(|i| RpcConfirmedTransactionStatusWithSignature {
+                signature: Signature::from([i + 1; 64]).to_string(),
+                slot: 1_000 - i as u64,
+                err: None,
+                memo: None,
+                block_time: None,
+                confirmation_status: None,
+            })
+            .collect()
+    }
+
+    /// Pages of 2 between `before` and `until` (both exclusive), like getSignaturesForAddress
+    fn fetch_from(
+        all: &[RpcConfirmedTransactionStatusWithSignature],
+    ) -> impl FnMut(Option<Signature>, Option<Signature>) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>>
+    {
+        move |before, until| {
+            let position = |sig: Signature| all.iter().position(|s| s.signature == sig.to_string());
+            let start = before.and_then(position).map_or(0, |i| i + 1);
+            let end = until.and_then(position).unwrap_or(all.len());
+            Ok(all[start.min(end)..end].iter().take(2).cloned().collect())
+        }
+    }
+
+    fn cursor(s: &RpcConfirmedTransactionStatusWithSignature) -> ScanCursor {
+        ScanCursor {
+            signature: s.signature.clone(),
+            slot: s.slot,
+        }
+    }
+
+    #[test]
+    fn test_capped_scan_resumes_before_moving_the_cursor() {
+        // The previous scan stopped at the 8th signature; 7 newer ones arrived since
+        let all = listing(8);
+        let state = ScanState {
+            cursor: Some(cursor(&all[7])),
+            resume: None,
+        };
+
+        // Capped at 4: the cursor stays put and the unscanned rest is remembered
+        let (first, state) = failed_fees::page_signatures(fetch_from(&all), &state, 4).unwrap();
+        assert_eq!(first.len(), 4);
+        assert_eq!(state.cursor, Some(cursor(&all[7])));
+        let resume = state.resume.clone().unwrap();
+        assert_eq!((resume.top, resume.before), (cursor(&all[0]), cursor(&all[3])));
+
+        // The next scan lists the rest, then moves the cursor to where the pass started
+        let (second, state) = failed_fees::page_signatures(fetch_from(&all), &state, 4).unwrap();
+        let seen: Vec<_> = first.iter().chain(&second).map(|s| s.signature.clone()).collect();
+        let expected: Vec<_> = all[..7].iter().map(|s| s.signature.clone()).collect();
+        assert_eq!(seen, expected);
+        assert_eq!(
+            state,
+            ScanState {
+                cursor: Some(cursor(&all[0])),
+                resume: None,
+            }
+        );
+    }
+}
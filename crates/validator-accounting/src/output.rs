@@ -0,0 +1,72 @@
+//! Machine-readable command output (`--output json`)
+//!
+//! In JSON mode a command writes exactly one JSON document to stdout so it can be piped
+//! into `jq` or a monitoring check. Commands like `position now` sync the cache first and
+//! that path prints progress all the way down, so while the command works stdout is pointed
+//! at stderr (`StdoutToStderr`) and restored just before the document is written.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::io::Write;
+
+/// Console output format for commands that support `--output`
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable console output
+    #[default]
+    Text,
+    /// A single JSON document on stdout (progress goes to stderr)
+    Json,
+}
+
+impl OutputFormat {
+    pub fn is_json(self) -> bool {
+        self == OutputFormat::Json
+    }
+
+    /// In JSON mode, send stdout to stderr until the returned guard is dropped
+    pub fn redirect_progress(self) -> Option<StdoutToStderr> {
+        self.is_json().then(StdoutToStderr::new)
+    }
+}
+
+/// Redirects stdout to stderr while alive (a no-op on non-Unix platforms)
+pub struct StdoutToStderr {
+    #[cfg(unix)]
+    saved: Option<std::os::fd::OwnedFd>,
+}
+
+impl StdoutToStderr {
+    fn new() -> Self {
+        let _ = std::io::stdout().flush();
+
+        #[cfg(unix)]
+        {
+            let saved = nix::unistd::dup(std::io::stdout()).ok();
+            if saved.is_some() {
+                let _ = nix::unistd::dup2_stdout(std::io::stderr());
+            }
+            Self { saved }
+        }
+
+        #[cfg(not(unix))]
+        Self {}
+    }
+}
+
+impl Drop for StdoutToStderr {
+    fn drop(&mut self) {
+        let _ = std::io::stdout().flush();
+
+        #[cfg(unix)]
+        if let Some(saved) = self.saved.take() {
+            let _ = nix::unistd::dup2_stdout(saved);
+        }
+    }
+}
+
+/// Write `value` to stdout as pretty-printed JSON
+pub fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
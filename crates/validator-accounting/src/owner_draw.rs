@@ -0,0 +1,153 @@
+//! Owner draw vs business expense splits for mixed-use withdrawals (`split`)
+//!
+//! The personal wallet is mixed-use: some of what is withdrawn to it pays business costs
+//! (a hosting bill paid from the personal card, hardware bought with the withdrawn SOL).
+//! A split marks a percentage of one withdrawal as a reimbursed business expense with an
+//! expense category; the rest stays an owner draw. The business share is added to the
+//! expenses (expense ledger, P&L, Schedule C) valued at the withdrawal's price, the
+//! treasury ledger shows the two shares on separate rows, and only the owner-draw share
+//! counts as a taxable withdrawal.
+
+use anyhow::Result;
+
+use crate::expenses::{Expense, ExpenseCategory, ExpenseStatus};
+use crate::prices::{HourlyPriceCache, PriceCache, get_event_price};
+use crate::transactions::{CategorizedTransfers, SolTransfer};
+
+/// Vendor shown on expenses derived from splits
+pub const SPLIT_VENDOR: &str = "Owner-paid (withdrawal split)";
+
+/// Share of one withdrawal that reimbursed a business expense
+#[derive(Debug, Clone, PartialEq)]
+pub struct WithdrawalSplit {
+    pub signature: String,
+    /// Percent of the withdrawal that was a business expense (0-100); the rest is an owner draw
+    pub business_pct: f64,
+    pub category: ExpenseCategory,
+    pub description: String,
+}
+
+impl WithdrawalSplit {
+    pub fn business_sol(&self, transfer: &SolTransfer) -> f64 {
+        transfer.amount_sol * self.business_pct / 100.0
+    }
+
+    pub fn draw_sol(&self, transfer: &SolTransfer) -> f64 {
+        transfer.amount_sol - self.business_sol(transfer)
+    }
+}
+
+/// Reject percentages outside 0-100 (a 100% split is a fully reimbursed expense)
+pub fn validate_business_pct(pct: f64) -> Result<()> {
+    if !(0.0..=100.0).contains(&pct) || pct.is_nan() {
+        anyhow::bail!("--business-pct must be between 0 and 100 (got {})", pct);
+    }
+    Ok(())
+}
+
+impl CategorizedTransfers {
+    /// The split recorded for a transfer, if any
+    pub fn split_for(&self, signature: &str) -> Option<&WithdrawalSplit> {
+        self.splits.iter().find(|s| s.signature == signature)
+    }
+
+    /// SOL of a transfer that left as an owner draw (all of it unless split)
+    pub fn draw_sol(&self, transfer: &SolTransfer) -> f64 {
+        self.split_for(&transfer.signature)
+            .map_or(transfer.amount_sol, |split| split.draw_sol(transfer))
+    }
+}
+
+/// Expenses for the business share of each split withdrawal, valued like the withdrawal itself.
+///
+/// Splits whose withdrawal isn't cached (or is no longer categorized as a withdrawal) are skipped.
+pub fn split_expenses(
+    categorized: &CategorizedTransfers,
+    prices: &PriceCache,
+    hourly_prices: &HourlyPriceCache,
+) -> Vec<Expense> {
+    categorized
+        .withdrawals
+        .iter()
+        .filter_map(|transfer| {
+            let split = categorized.split_for(&transfer.signature)?;
+            let date = transfer.date.clone()?;
+            let business_sol = split.business_sol(transfer);
+            if business_sol <= 0.0 {
+                return None;
+            }
+            let (price, _) = get_event_price(prices, hourly_prices, &date, transfer.timestamp);
+            Some(Expense {
+                id: None,
+                date,
+                vendor: SPLIT_VENDOR.to_string(),
+                category: split.category,
+                description: format!(
+                    "{} ({:.0}% of {:.6} SOL withdrawal {})",
+                    split.description,
+                    split.business_pct,
+                    transfer.amount_sol,
+                    &transfer.signature[..transfer.signature.len().min(16)]
+                ),
+                amount_usd: business_sol * price,
+                paid_with: "SOL".to_string(),
+                invoice_id: Some(format!("split:{}", transfer.signature)),
+                entity: None,
+                status: ExpenseStatus::Approved,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::addresses::AddressCategory;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn withdrawal(signature: &str, amount_sol: f64) -> SolTransfer {
+        SolTransfer {
+            signature: signature.to_string(),
+            slot: 0,
+            timestamp: None,
+            date: Some("2025-06-01".to_string()),
+            from: Pubkey::new_unique(),
+            to: Pubkey::new_unique(),
+            amount_lamports: (amount_sol * 1e9) as u64,
+            amount_sol,
+            from_label: String::new(),
+            to_label: String::new(),
+            from_category: AddressCategory::ValidatorSelf,
+            to_category: AddressCategory::PersonalWallet,
+            memo: None,
+            program: None,
+        }
+    }
+
+    #[test]
+    fn test_split_withdrawal_into_draw_and_expense() {
+        let categorized = CategorizedTransfers {
+            withdrawals: vec![withdrawal("split-sig", 10.0), withdrawal("plain-sig", 4.0)],
+            splits: vec![WithdrawalSplit {
+                signature: "split-sig".to_string(),
+                business_pct: 30.0,
+                category: ExpenseCategory::Hosting,
+                description: "Colo invoice".to_string(),
+            }],
+            ..Default::default()
+        };
+        assert!((categorized.draw_sol(&categorized.withdrawals[0]) - 7.0).abs() < 1e-9);
+        assert_eq!(categorized.draw_sol(&categorized.withdrawals[1]), 4.0);
+
+        let mut prices = PriceCache::new();
+        prices.insert("2025-06-01".to_string(), 150.0.into());
+        let expenses = split_expenses(&categorized, &prices, &HourlyPriceCache::new());
+        assert_eq!(expenses.len(), 1);
+        assert_eq!(expenses[0].category, ExpenseCategory::Hosting);
+        assert!((expenses[0].amount_usd - 450.0).abs() < 1e-6);
+        assert_eq!(expenses[0].invoice_id.as_deref(), Some("split:split-sig"));
+
+        assert!(validate_business_pct(100.0).is_ok());
+        assert!(validate_business_pct(120.0).is_err());
+    }
+}
@@ -0,0 +1,147 @@
+//! Reporting periods: calendar years, fiscal years, and custom month ranges
+//!
+//! `--year` and `--period` both resolve to a `ReportPeriod`. Fiscal years are
+//! named after the calendar year they end in (FY2026 = 2025-07-01..2026-06-30
+//! when the fiscal year starts in July), matching US federal convention.
+
+use anyhow::{Context, Result};
+use chrono::{Months, NaiveDate};
+
+/// An inclusive date range that reports are filtered to
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportPeriod {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// Human-readable name ("2025", "FY2026", "2025-07..2026-06")
+    pub label: String,
+}
+
+impl ReportPeriod {
+    /// The (fiscal) year ending in `year`; with `fiscal_start_month = 1` this is the calendar year
+    pub fn year(year: i32, fiscal_start_month: u32) -> Result<Self> {
+        let start_year = if fiscal_start_month == 1 { year } else { year - 1 };
+        let start = NaiveDate::from_ymd_opt(start_year, fiscal_start_month, 1)
+            .with_context(|| format!("Invalid fiscal year start month {}", fiscal_start_month))?;
+        let end = start + Months::new(12) - chrono::Duration::days(1);
+        let label = if fiscal_start_month == 1 {
+            year.to_string()
+        } else {
+            format!("FY{}", year)
+        };
+        Ok(Self { start, end, label })
+    }
+
+    /// Parse `YYYY-MM..YYYY-MM` (whole months) or `YYYY-MM-DD..YYYY-MM-DD`
+    pub fn parse(s: &str) -> Result<Self> {
+        let (from, to) = s
+            .split_once("..")
+            .with_context(|| format!("Invalid period '{}'. Use YYYY-MM..YYYY-MM (e.g. 2025-07..2026-06)", s))?;
+        let start = parse_bound(from.trim(), false)?;
+        let end = parse_bound(to.trim(), true)?;
+        anyhow::ensure!(start <= end, "Period '{}' ends before it starts", s);
+        Ok(Self {
+            start,
+            end,
+            label: s.trim().to_string(),
+        })
+    }
+
+    /// Is a `YYYY-MM-DD` date (time suffix ignored) inside the period?
+    pub fn contains(&self, date: &str) -> bool {
+        date.get(..10)
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+            .is_some_and(|d| d >= self.start && d <= self.end)
+    }
+
+    /// Does a `YYYY-MM` month overlap the period?
+    pub fn contains_month(&self, month: &str) -> bool {
+        let Ok(first) = NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d") else {
+            return false;
+        };
+        let last = first + Months::new(1) - chrono::Duration::days(1);
+        first <= self.end && last >= self.start
+    }
+
+    /// Suffix for output filenames (e.g. "_2025", "_FY2026", "_2025-07_2026-06")
+    pub fn file_suffix(&self) -> String {
+        format!("_{}", self.label.replace("..", "_"))
+    }
+}
+
+/// Fiscal year label a `YYYY-MM` month belongs to ("2025" or "FY2026")
+///
+/// Used to reset YTD totals and group annual totals at the fiscal boundary.
+pub fn fiscal_year_label(month: &str, fiscal_start_month: u32) -> String {
+    let year: i32 = month.get(..4).and_then(|y| y.parse().ok()).unwrap_or(0);
+    let month_num: u32 = month.get(5..7).and_then(|m| m.parse().ok()).unwrap_or(1);
+    if fiscal_start_month == 1 {
+        year.to_string()
+    } else if month_num >= fiscal_start_month {
+        format!("FY{}", year + 1)
+    } else {
+        format!("FY{}", year)
+    }
+}
+
+fn parse_bound(s: &str, is_end: bool) -> Result<NaiveDate> {
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return Ok(date);
+    }
+    let first = NaiveDate::parse_from_str(&format!("{}-01", s), "%Y-%m-%d")
+        .with_context(|| format!("Invalid period bound '{}'. Use YYYY-MM or YYYY-MM-DD", s))?;
+    Ok(if is_end {
+        first + Months::new(1) - chrono::Duration::days(1)
+    } else {
+        first
+    })
+}
+
+/// Resolve `--year` / `--period` into a report period
+pub fn resolve(year: Option<i32>, period: Option<&str>, fiscal_start_month: u32) -> Result<Option<ReportPeriod>> {
+    match (year, period) {
+        (Some(_), Some(_)) => anyhow::bail!("Use either --year or --period, not both"),
+        (Some(year), None) => ReportPeriod::year(year, fiscal_start_month).map(Some),
+        (None, Some(period)) => ReportPeriod::parse(period).map(Some),
+        (None, None) => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fiscal_year_spans_start_month_to_previous_month() {
+        let fy = ReportPeriod::year(2026, 7).unwrap();
+        assert_eq!(fy.start, NaiveDate::from_ymd_opt(2025, 7, 1).unwrap());
+        assert_eq!(fy.end, NaiveDate::from_ymd_opt(2026, 6, 30).unwrap());
+        assert_eq!(fy.label, "FY2026");
+        assert!(fy.contains("2025-07-01"));
+        assert!(fy.contains("2026-06-30T23:00:00Z"));
+        assert!(!fy.contains("2026-07-01"));
+
+        let calendar = ReportPeriod::year(2025, 1).unwrap();
+        assert_eq!(calendar.end, NaiveDate::from_ymd_opt(2025, 12, 31).unwrap());
+        assert_eq!(calendar.label, "2025");
+    }
+
+    #[test]
+    fn test_parse_month_range() {
+        let period = ReportPeriod::parse("2025-07..2026-06").unwrap();
+        assert_eq!(period.start, NaiveDate::from_ymd_opt(2025, 7, 1).unwrap());
+        assert_eq!(period.end, NaiveDate::from_ymd_opt(2026, 6, 30).unwrap());
+        assert!(period.contains_month("2025-07"));
+        assert!(!period.contains_month("2025-06"));
+        assert_eq!(period.file_suffix(), "_2025-07_2026-06");
+
+        assert!(ReportPeriod::parse("2026-06..2025-07").is_err());
+        assert!(ReportPeriod::parse("2025").is_err());
+    }
+
+    #[test]
+    fn test_fiscal_year_label_resets_at_boundary() {
+        assert_eq!(fiscal_year_label("2025-06", 7), "FY2025");
+        assert_eq!(fiscal_year_label("2025-07", 7), "FY2026");
+        assert_eq!(fiscal_year_label("2025-07", 1), "2025");
+    }
+}
@@ -92,6 +92,8 @@ pub enum AccountType {
     JitosolTokenAccount,
     StakeAccount,
     PersonalWallet,
+    SquadsVault,
+    BusinessWallet,
 }
 
 impl std::fmt::Display for AccountType {
@@ -104,6 +106,8 @@ impl std::fmt::Display for AccountType {
             AccountType::JitosolTokenAccount => write!(f, "JitoSOL"),
             AccountType::StakeAccount => write!(f, "StakeAccount"),
             AccountType::PersonalWallet => write!(f, "PersonalWallet"),
+            AccountType::SquadsVault => write!(f, "SquadsVault"),
+            AccountType::BusinessWallet => write!(f, "BusinessWallet"),
         }
     }
 }
@@ -120,6 +124,8 @@ impl FromStr for AccountType {
             "JitoSOL" => Ok(AccountType::JitosolTokenAccount),
             "StakeAccount" => Ok(AccountType::StakeAccount),
             "PersonalWallet" => Ok(AccountType::PersonalWallet),
+            "SquadsVault" => Ok(AccountType::SquadsVault),
+            "BusinessWallet" => Ok(AccountType::BusinessWallet),
             _ => anyhow::bail!("Invalid account type: {}", s),
         }
     }
@@ -230,6 +236,10 @@ pub struct ValidatorPosition {
     pub vote_account_withdrawable: u64,
     pub identity_lamports: u64,
     pub withdraw_authority_lamports: u64,
+    /// Squads multisig vault (0 when no multisig is configured)
+    pub squads_vault_lamports: u64,
+    /// Watch-only business wallets (sum)
+    pub business_wallets_lamports: u64,
 
     // Token accounts (ATAs) owned by the core owners (lamports held directly in those accounts)
     pub token_accounts_lamports: u64,
@@ -317,11 +327,17 @@ pub async fn fetch_all_balances_atomic(client: &RpcClient, config: &Config) -> R
         (config.identity, AccountType::Identity),
         (config.withdraw_authority, AccountType::WithdrawAuthority),
     ];
+    if let Some(vault) = config.squads_vault {
+        accounts_with_types.push((vault, AccountType::SquadsVault));
+    }
+    for wallet in &config.business_wallets {
+        accounts_with_types.push((*wallet, AccountType::BusinessWallet));
+    }
 
     // Include common ATAs for the core treasury owners so wrapped SOL (wSOL) and rent
     // held in token accounts is included in the position snapshot.
-    for owner in [&config.vote_account, &config.identity, &config.withdraw_authority] {
-        for ata_str in compute_common_atas(owner) {
+    for owner in config.treasury_accounts() {
+        for ata_str in compute_common_atas(&owner) {
             if let Ok(ata) = Pubkey::from_str(&ata_str) {
                 accounts_with_types.push((ata, AccountType::TokenAccount));
             }
@@ -352,7 +368,14 @@ pub async fn fetch_all_balances_atomic(client: &RpcClient, config: &Config) -> R
         let (pubkey, account_type) = &unique_accounts[i];
 
         if let Some(account) = maybe_account {
-            let rent_exempt = get_rent_exempt_for_type(client, *account_type)?;
+            // A business wallet may itself be a token account (e.g. a USDC operating ATA)
+            let rent_type =
+                if *account_type == AccountType::BusinessWallet && account.owner == *ata_programs::SPL_TOKEN_PROGRAM {
+                    AccountType::TokenAccount
+                } else {
+                    *account_type
+                };
+            let rent_exempt = get_rent_exempt_for_type(client, rent_type)?;
             let withdrawable = account.lamports.saturating_sub(rent_exempt);
 
             balances.push(AccountBalance {
@@ -564,6 +587,25 @@ pub async fn fetch_jitosol_exchange_rate(client: &RpcClient) -> Result<f64> {
     Ok(rate)
 }
 
+/// Discover stake accounts withdrawable by the withdraw authority, the Squads vault or a business wallet
+pub async fn discover_treasury_stake_accounts(
+    client: &RpcClient,
+    config: &Config,
+    snapshot_slot: u64,
+) -> Result<Vec<StakeAccountInfo>> {
+    let mut withdrawers = vec![config.withdraw_authority];
+    withdrawers.extend(config.squads_vault);
+    withdrawers.extend(&config.business_wallets);
+    let mut seen = HashSet::new();
+    withdrawers.retain(|w| seen.insert(*w));
+
+    let mut stake_accounts = Vec::new();
+    for withdrawer in &withdrawers {
+        stake_accounts.extend(discover_stake_accounts(client, withdrawer, snapshot_slot).await?);
+    }
+    Ok(stake_accounts)
+}
+
 /// Discover stake accounts owned by the validator's withdraw authority
 /// Returns stake accounts with properly parsed state, voter, lockup, and liquidity
 pub async fn discover_stake_accounts(
@@ -724,6 +766,43 @@ fn parse_stake_account(data: &[u8], current_epoch: Epoch) -> Result<ParsedStakeI
     }
 }
 
+/// Delegation of a stake account in the `Stake` state
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DelegatedStake {
+    pub withdrawer: Pubkey,
+    pub voter: Pubkey,
+    pub stake_lamports: u64,
+    pub activation_epoch: u64,
+    pub deactivation_epoch: u64,
+}
+
+/// Parse the delegation out of stake account data (None unless delegated)
+pub fn parse_delegation(data: &[u8]) -> Result<Option<DelegatedStake>> {
+    if data.len() < 4 {
+        anyhow::bail!("Stake account data too short: {} bytes", data.len());
+    }
+    if u32::from_le_bytes(data[0..4].try_into().unwrap()) != 2 {
+        return Ok(None);
+    }
+
+    let meta: Meta = bincode::deserialize(&data[4..]).context("Failed to deserialize Stake state meta")?;
+    let stake_offset = 4 + 120; // discriminant + Meta size
+    if data.len() < stake_offset + 8 {
+        anyhow::bail!("Stake account data too short for stake data");
+    }
+    let stake_data: StakeData =
+        bincode::deserialize(&data[stake_offset..]).context("Failed to deserialize Stake state delegation")?;
+
+    let delegation = stake_data.delegation;
+    Ok(Some(DelegatedStake {
+        withdrawer: meta.authorized.withdrawer,
+        voter: delegation.voter_pubkey,
+        stake_lamports: delegation.stake,
+        activation_epoch: delegation.activation_epoch,
+        deactivation_epoch: delegation.deactivation_epoch,
+    }))
+}
+
 /// Check if stake account is locked based on lockup configuration
 fn is_locked(meta: &Meta, current_epoch: Epoch) -> bool {
     // Lockup is in force if epoch hasn't passed
@@ -805,6 +884,9 @@ pub fn build_position_snapshot(
     let mut vote_withdrawable = 0u64;
     let mut identity_lamports = 0u64;
     let mut withdraw_auth_lamports = 0u64;
+    let mut squads_vault_lamports = 0u64;
+    let mut business_wallets_lamports = 0u64;
+    let mut business_wallets_withdrawable = 0u64;
     let mut token_accounts_lamports = 0u64;
     let mut token_accounts_withdrawable = 0u64;
 
@@ -824,6 +906,14 @@ pub fn build_position_snapshot(
             AccountType::WithdrawAuthority => {
                 withdraw_auth_lamports = balance.balance_lamports;
             }
+            AccountType::SquadsVault => {
+                squads_vault_lamports = balance.balance_lamports;
+            }
+            AccountType::BusinessWallet => {
+                business_wallets_lamports = business_wallets_lamports.saturating_add(balance.balance_lamports);
+                business_wallets_withdrawable =
+                    business_wallets_withdrawable.saturating_add(balance.withdrawable_lamports);
+            }
             AccountType::TokenAccount => {
                 token_accounts_lamports = token_accounts_lamports.saturating_add(balance.balance_lamports);
                 token_accounts_withdrawable = token_accounts_withdrawable.saturating_add(balance.withdrawable_lamports);
@@ -854,6 +944,8 @@ pub fn build_position_snapshot(
     let total_liquid = vote_withdrawable
         .saturating_add(identity_lamports)
         .saturating_add(withdraw_auth_lamports)
+        .saturating_add(squads_vault_lamports)
+        .saturating_add(business_wallets_withdrawable)
         .saturating_add(token_accounts_withdrawable)
         .saturating_add(stake_liquid)
         .saturating_add(jitosol_sol_equivalent); // Include jitoSOL in liquid
@@ -861,12 +953,18 @@ pub fn build_position_snapshot(
     // Locked = vote account rent-exempt portion + locked stake
     let vote_locked = vote_lamports.saturating_sub(vote_withdrawable);
     let token_locked = token_accounts_lamports.saturating_sub(token_accounts_withdrawable);
-    let total_locked = vote_locked.saturating_add(stake_locked).saturating_add(token_locked);
+    let business_locked = business_wallets_lamports.saturating_sub(business_wallets_withdrawable);
+    let total_locked = vote_locked
+        .saturating_add(stake_locked)
+        .saturating_add(token_locked)
+        .saturating_add(business_locked);
 
     // Total assets = all SOL + jitoSOL equivalent
     let total_assets = vote_lamports
         .saturating_add(identity_lamports)
         .saturating_add(withdraw_auth_lamports)
+        .saturating_add(squads_vault_lamports)
+        .saturating_add(business_wallets_lamports)
         .saturating_add(token_accounts_lamports)
         .saturating_add(stake_total)
         .saturating_add(jitosol_sol_equivalent);
@@ -905,6 +1003,8 @@ pub fn build_position_snapshot(
         vote_account_withdrawable: vote_withdrawable,
         identity_lamports,
         withdraw_authority_lamports: withdraw_auth_lamports,
+        squads_vault_lamports,
+        business_wallets_lamports,
         token_accounts_lamports,
         token_accounts_withdrawable_lamports: token_accounts_withdrawable,
         jitosol_lamports,
@@ -998,6 +1098,8 @@ mod tests {
             vote_account_withdrawable: 0,
             identity_lamports: 0,
             withdraw_authority_lamports: 0,
+            squads_vault_lamports: 0,
+            business_wallets_lamports: 0,
             token_accounts_lamports: 0,
             token_accounts_withdrawable_lamports: 0,
             jitosol_lamports: 0,
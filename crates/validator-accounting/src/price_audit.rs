@@ -0,0 +1,192 @@
+//! Price provenance (`price_audit` table) and `prices verify`
+//!
+//! Every daily price is cached together with the provider that returned it and the
+//! timestamp of the data point in the provider's response, so "where does this price
+//! come from?" still has an answer long after the fetch. `prices verify` re-fetches a
+//! date range from a second provider and lists the dates where the two disagree by more
+//! than a tolerance. Providers sample a day differently (CoinGecko's midnight point,
+//! Binance's daily close, Dune's average), so differences of a percent or two are normal.
+
+use chrono::DateTime;
+
+use crate::prices::{PriceProvider, PriceQuotes};
+
+/// A cached daily price with its provenance
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditedPrice {
+    pub date: String,
+    pub usd_price: f64,
+    /// None for prices cached before provenance was recorded
+    pub provider: Option<PriceProvider>,
+    pub source_timestamp: Option<i64>,
+    pub fetched_at: Option<String>,
+}
+
+/// A date where the cached price and the second provider disagree
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceDiscrepancy {
+    pub cached: AuditedPrice,
+    pub other_usd: f64,
+    /// (cached - other) / other, in percent
+    pub difference_pct: f64,
+}
+
+/// Outcome of `prices verify`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceVerification {
+    pub against: PriceProvider,
+    pub tolerance_pct: f64,
+    /// Dates priced by both the cache and `against`
+    pub compared: usize,
+    /// Of those, dates whose cached price came from `against` itself
+    pub same_provider: usize,
+    /// Cached dates `against` returned no price for
+    pub unavailable: Vec<String>,
+    pub discrepancies: Vec<PriceDiscrepancy>,
+}
+
+/// Compare `cached` prices with the quotes `against` returned for the same dates
+pub fn verify(
+    cached: &[AuditedPrice],
+    other: &PriceQuotes,
+    against: PriceProvider,
+    tolerance_pct: f64,
+) -> PriceVerification {
+    let mut verification = PriceVerification {
+        against,
+        tolerance_pct,
+        compared: 0,
+        same_provider: 0,
+        unavailable: Vec::new(),
+        discrepancies: Vec::new(),
+    };
+    for price in cached {
+        let Some(quote) = other.get(&price.date).filter(|q| q.usd_price > 0.0) else {
+            verification.unavailable.push(price.date.clone());
+            continue;
+        };
+        verification.compared += 1;
+        if price.provider == Some(against) {
+            verification.same_provider += 1;
+        }
+        let difference_pct = (price.usd_price - quote.usd_price) / quote.usd_price * 100.0;
+        if difference_pct.abs() > tolerance_pct {
+            verification.discrepancies.push(PriceDiscrepancy {
+                cached: price.clone(),
+                other_usd: quote.usd_price,
+                difference_pct,
+            });
+        }
+    }
+    verification
+}
+
+fn format_timestamp(timestamp: Option<i64>) -> String {
+    timestamp
+        .and_then(|ts| DateTime::from_timestamp(ts, 0))
+        .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Console report
+pub fn print(verification: &PriceVerification) {
+    let against = verification.against.as_str();
+    println!(
+        "Compared {} cached daily prices with {} (tolerance {:.1}%)",
+        verification.compared, against, verification.tolerance_pct
+    );
+    if verification.same_provider > 0 {
+        println!(
+            "  {} of them were cached from {} itself; verify against another provider for an independent check",
+            verification.same_provider, against
+        );
+    }
+    if !verification.unavailable.is_empty() {
+        println!(
+            "  {} date(s) not returned by {}: {}{}",
+            verification.unavailable.len(),
+            against,
+            verification
+                .unavailable
+                .iter()
+                .take(10)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", "),
+            if verification.unavailable.len() > 10 {
+                ", ..."
+            } else {
+                ""
+            }
+        );
+    }
+
+    if verification.discrepancies.is_empty() {
+        println!("  No material discrepancies.");
+        return;
+    }
+    println!("\n  {} material discrepancies:", verification.discrepancies.len());
+    println!(
+        "  {:<10} {:>10} {:<10} {:<20} {:<19} {:>10} {:>8}",
+        "Date", "Cached", "Source", "Source Time", "Fetched", against, "Diff"
+    );
+    for d in &verification.discrepancies {
+        println!(
+            "  {:<10} {:>10.2} {:<10} {:<20} {:<19} {:>10.2} {:>+7.1}%",
+            d.cached.date,
+            d.cached.usd_price,
+            d.cached.provider.map_or("unknown", |p| p.as_str()),
+            format_timestamp(d.cached.source_timestamp),
+            d.cached.fetched_at.as_deref().unwrap_or("-"),
+            d.other_usd,
+            d.difference_pct
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prices::PriceQuote;
+
+    fn cached(date: &str, usd_price: f64, provider: Option<PriceProvider>) -> AuditedPrice {
+        AuditedPrice {
+            date: date.to_string(),
+            usd_price,
+            provider,
+            source_timestamp: None,
+            fetched_at: None,
+        }
+    }
+
+    #[test]
+    fn test_verify_flags_differences_beyond_tolerance() {
+        let cached = [
+            cached("2025-11-01", 160.0, Some(PriceProvider::Coingecko)),
+            cached("2025-11-02", 150.0, Some(PriceProvider::Coingecko)),
+            cached("2025-11-03", 158.0, Some(PriceProvider::Binance)),
+            cached("2025-11-04", 155.0, None),
+        ];
+        let other: PriceQuotes = [("2025-11-01", 161.0), ("2025-11-02", 160.0), ("2025-11-03", 158.0)]
+            .into_iter()
+            .map(|(date, usd_price)| {
+                (
+                    date.to_string(),
+                    PriceQuote {
+                        usd_price,
+                        provider: PriceProvider::Binance,
+                        source_timestamp: None,
+                    },
+                )
+            })
+            .collect();
+
+        let verification = verify(&cached, &other, PriceProvider::Binance, 3.0);
+        assert_eq!(verification.compared, 3);
+        assert_eq!(verification.same_provider, 1);
+        assert_eq!(verification.unavailable, vec!["2025-11-04".to_string()]);
+        assert_eq!(verification.discrepancies.len(), 1);
+        assert_eq!(verification.discrepancies[0].cached.date, "2025-11-02");
+        assert!((verification.discrepancies[0].difference_pct + 6.25).abs() < 1e-9);
+    }
+}
@@ -1,18 +1,91 @@
 //! Historical SOL/USD price fetching (CoinGecko → Binance → Dune → hardcoded fallback)
 
 use anyhow::Result;
-use chrono::{Duration as ChronoDuration, NaiveDate, Utc};
-use serde::Deserialize;
-use std::collections::HashMap;
-use std::time::Duration;
-use tokio::time::sleep;
+use chrono::{Duration as ChronoDuration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
+use crate::cluster;
 use crate::constants;
 use crate::dune;
+use crate::sources::{self, RetryPolicy};
+use crate::timezone;
 use crate::transactions::{EpochReward, SolTransfer};
 
-/// Price cache mapping date strings to USD prices
-pub type PriceCache = HashMap<String, f64>;
+/// Price cache mapping date strings to USD prices and how each was stored
+pub type PriceCache = HashMap<String, CachedPrice>;
+
+/// A cached daily price (`prices.usd_price` and `prices.source`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CachedPrice {
+    pub usd_price: f64,
+    /// `Exact` for provider prices; `Fallback` for the placeholder rows older versions
+    /// persisted when every price source failed
+    pub source: PriceSource,
+}
+
+impl From<f64> for CachedPrice {
+    fn from(usd_price: f64) -> Self {
+        Self {
+            usd_price,
+            source: PriceSource::Exact,
+        }
+    }
+}
+
+/// Hourly price cache mapping the unix timestamp of the start of each UTC hour to USD prices
+pub type HourlyPriceCache = BTreeMap<i64, f64>;
+
+/// Daily price provider, in fallback order
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceProvider {
+    /// CoinGecko market chart (requires `api_keys.coingecko`)
+    Coingecko,
+    /// Binance SOLUSDT daily closes (no key needed)
+    Binance,
+    /// Dune `prices.usd` daily averages (requires `api_keys.dune`)
+    Dune,
+}
+
+impl PriceProvider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Coingecko => "coingecko",
+            Self::Binance => "binance",
+            Self::Dune => "dune",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "coingecko" => Some(Self::Coingecko),
+            "binance" => Some(Self::Binance),
+            "dune" => Some(Self::Dune),
+            _ => None,
+        }
+    }
+}
+
+/// A daily price and where it came from (recorded in the `price_audit` table)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceQuote {
+    pub usd_price: f64,
+    pub provider: PriceProvider,
+    /// Timestamp of the data point in the provider's response (unix seconds);
+    /// none for Dune's daily averages and the Binance ticker
+    pub source_timestamp: Option<i64>,
+}
+
+/// Price quotes keyed by date (YYYY-MM-DD)
+pub type PriceQuotes = HashMap<String, PriceQuote>;
+
+/// Just the prices of `quotes`
+pub fn quoted_prices(quotes: &PriceQuotes) -> PriceCache {
+    quotes
+        .iter()
+        .map(|(date, q)| (date.clone(), q.usd_price.into()))
+        .collect()
+}
 
 /// CoinGecko market chart response
 #[derive(Debug, Deserialize)]
@@ -29,6 +102,8 @@ struct SimplePriceResponse {
 #[derive(Debug, Deserialize)]
 struct SolanaPrice {
     usd: f64,
+    #[serde(default)]
+    last_updated_at: Option<i64>,
 }
 
 /// Fetch historical prices for all dates in rewards and transfers.
@@ -38,7 +113,7 @@ pub async fn fetch_historical_prices(
     transfers: &[SolTransfer],
     api_key: &str,
     dune_api_key: Option<&str>,
-) -> Result<PriceCache> {
+) -> Result<PriceQuotes> {
     fetch_historical_prices_with_cache(rewards, transfers, api_key, dune_api_key, None).await
 }
 
@@ -49,8 +124,8 @@ pub async fn fetch_historical_prices_with_cache(
     api_key: &str,
     dune_api_key: Option<&str>,
     existing_prices: Option<&PriceCache>,
-) -> Result<PriceCache> {
-    let mut cache = PriceCache::new();
+) -> Result<PriceQuotes> {
+    let mut cache = PriceQuotes::new();
 
     // Collect all unique dates we need prices for
     let mut date_set = std::collections::HashSet::<NaiveDate>::new();
@@ -81,11 +156,11 @@ pub async fn fetch_historical_prices_with_cache(
 
     if dates.is_empty() {
         // No dates to fetch, get current price if not cached
-        let today = Utc::now().format("%Y-%m-%d").to_string();
+        let today = timezone::today().format("%Y-%m-%d").to_string();
         if existing_prices.is_none_or(|p| !p.contains_key(&today))
-            && let Ok(price) = fetch_current_price(api_key).await
+            && let Ok(quote) = fetch_current_price(api_key).await
         {
-            cache.insert(today, price);
+            cache.insert(today, quote);
         }
         return Ok(cache);
     }
@@ -99,42 +174,38 @@ pub async fn fetch_historical_prices_with_cache(
     println!("    Fetching prices from {} to {}", min_date, max_date);
 
     match fetch_price_range(*min_date, *max_date, api_key, dune_api_key).await {
-        Ok(prices) => {
-            for (date, price) in prices {
-                cache.insert(date, price);
-            }
-        }
+        Ok(prices) => cache.extend(prices),
         Err(e) => {
+            // Leave the dates uncached rather than persisting a fixed price: reports fall back
+            // to the nearest known price, and `prices backfill` can fill the gap later.
             eprintln!("    ⚠️  WARNING: Failed to fetch historical prices: {}", e);
             eprintln!(
-                "    ⚠️  Using fallback price of ${:.2} for {} dates",
-                constants::FALLBACK_SOL_PRICE,
+                "    ⚠️  {} dates left unpriced; reports will use the nearest cached price",
                 dates.len()
             );
-            eprintln!("    ⚠️  Financial reports may be inaccurate!");
-            // Use fallback price
-            for date in &dates {
-                cache.insert(date.format("%Y-%m-%d").to_string(), constants::FALLBACK_SOL_PRICE);
-            }
+            eprintln!(
+                "    ⚠️  Run 'validator-accounting prices backfill --from {}' once price sources recover",
+                min_date
+            );
         }
     }
 
     // Ensure current price is available
-    if let Ok(price) = fetch_current_price(api_key).await {
-        let today = Utc::now().format("%Y-%m-%d").to_string();
-        cache.insert(today, price);
+    if let Ok(quote) = fetch_current_price(api_key).await {
+        let today = timezone::today().format("%Y-%m-%d").to_string();
+        cache.insert(today, quote);
     }
 
     Ok(cache)
 }
 
-/// Fetch price range — tries CoinGecko → Binance → Dune → fallback
-async fn fetch_price_range(
+/// Fetch price range — tries CoinGecko → Binance → Dune
+pub async fn fetch_price_range(
     from: NaiveDate,
     to: NaiveDate,
     api_key: &str,
     dune_api_key: Option<&str>,
-) -> Result<Vec<(String, f64)>> {
+) -> Result<PriceQuotes> {
     match fetch_price_range_coingecko(from, to, api_key).await {
         Ok(prices) => return Ok(prices),
         Err(cg_err) => {
@@ -162,10 +233,26 @@ async fn fetch_price_range(
     anyhow::bail!("All price sources failed (CoinGecko, Binance, Dune)")
 }
 
-/// Fetch price range from CoinGecko
-async fn fetch_price_range_coingecko(from: NaiveDate, to: NaiveDate, api_key: &str) -> Result<Vec<(String, f64)>> {
-    let client = reqwest::Client::new();
+/// Fetch a price range from one provider only, without fallback (`prices verify`)
+pub async fn fetch_price_range_from(
+    provider: PriceProvider,
+    from: NaiveDate,
+    to: NaiveDate,
+    api_key: &str,
+    dune_api_key: Option<&str>,
+) -> Result<PriceQuotes> {
+    match provider {
+        PriceProvider::Coingecko => fetch_price_range_coingecko(from, to, api_key).await,
+        PriceProvider::Binance => fetch_price_range_binance(from, to).await,
+        PriceProvider::Dune => {
+            let dune_key = dune_api_key.ok_or_else(|| anyhow::anyhow!("Dune prices need api_keys.dune"))?;
+            fetch_price_range_dune(from, to, dune_key).await
+        }
+    }
+}
 
+/// Fetch price range from CoinGecko
+async fn fetch_price_range_coingecko(from: NaiveDate, to: NaiveDate, api_key: &str) -> Result<PriceQuotes> {
     let from_ts = from.and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp();
     let to_ts = (to + ChronoDuration::days(1))
         .and_hms_opt(0, 0, 0)
@@ -173,6 +260,48 @@ async fn fetch_price_range_coingecko(from: NaiveDate, to: NaiveDate, api_key: &s
         .and_utc()
         .timestamp();
 
+    let data = fetch_market_chart_coingecko(from_ts, to_ts, api_key).await?;
+
+    let mut daily_prices = PriceQuotes::new();
+    for [timestamp_ms, price] in data.prices {
+        let timestamp = timestamp_ms as i64 / 1000;
+        if let Some(date) = timezone::timestamp_to_date(timestamp) {
+            daily_prices.insert(
+                date,
+                PriceQuote {
+                    usd_price: price,
+                    provider: PriceProvider::Coingecko,
+                    source_timestamp: Some(timestamp),
+                },
+            );
+        }
+    }
+
+    Ok(daily_prices)
+}
+
+/// Fetch hourly prices from CoinGecko for a window centered on `timestamp`.
+///
+/// CoinGecko returns hourly points automatically for ranges between 1 and 90 days.
+pub async fn fetch_hourly_prices_around(timestamp: i64, api_key: &str) -> Result<HourlyPriceCache> {
+    let from_ts = timestamp - constants::HOURLY_PRICE_WINDOW_SECS;
+    let to_ts = timestamp + constants::HOURLY_PRICE_WINDOW_SECS;
+
+    let data = fetch_market_chart_coingecko(from_ts, to_ts, api_key).await?;
+
+    let mut hourly = HourlyPriceCache::new();
+    for [timestamp_ms, price] in data.prices {
+        let ts = timestamp_ms as i64 / 1000;
+        hourly.insert(ts - ts.rem_euclid(3600), price);
+    }
+
+    Ok(hourly)
+}
+
+/// Call CoinGecko `market_chart/range` with retries (granularity depends on range length)
+async fn fetch_market_chart_coingecko(from_ts: i64, to_ts: i64, api_key: &str) -> Result<MarketChartResponse> {
+    let client = reqwest::Client::new();
+
     let url = format!(
         "{}{}&from={}&to={}",
         constants::COINGECKO_API_BASE,
@@ -181,66 +310,41 @@ async fn fetch_price_range_coingecko(from: NaiveDate, to: NaiveDate, api_key: &s
         to_ts
     );
 
-    let max_retries = 3;
-    let mut last_error = None;
-    let mut data: Option<MarketChartResponse> = None;
-
-    for attempt in 0..max_retries {
-        if attempt > 0 {
-            let delay = Duration::from_secs(2u64.pow(attempt as u32));
-            sleep(delay).await;
-        }
-
-        match client
-            .get(&url)
-            .header("Accept", "application/json")
-            .header("x-cg-demo-api-key", api_key)
-            .send()
+    let (client, url) = (&client, &url);
+    sources::request(COINGECKO_SOURCE, RetryPolicy::default(), move || async move {
+        let response = coingecko_get(client, url, api_key).await?;
+        response
+            .json::<MarketChartResponse>()
             .await
-        {
-            Ok(response) => {
-                if response.status().is_success() {
-                    match response.json::<MarketChartResponse>().await {
-                        Ok(d) => {
-                            data = Some(d);
-                            break;
-                        }
-                        Err(e) => {
-                            last_error = Some(anyhow::anyhow!("Parse error: {}", e));
-                        }
-                    }
-                } else if response.status().as_u16() == 429 {
-                    last_error = Some(anyhow::anyhow!("Rate limited (429)"));
-                    continue;
-                } else {
-                    last_error = Some(anyhow::anyhow!("CoinGecko API returned status: {}", response.status()));
-                }
-            }
-            Err(e) => {
-                last_error = Some(anyhow::anyhow!("Request failed: {}", e));
-            }
-        }
-    }
-
-    let data =
-        data.ok_or_else(|| last_error.unwrap_or_else(|| anyhow::anyhow!("Failed after {} retries", max_retries)))?;
+            .map_err(|e| anyhow::anyhow!("Parse error: {}", e))
+    })
+    .await
+}
 
-    let mut daily_prices: HashMap<String, f64> = HashMap::new();
-    for [timestamp_ms, price] in data.prices {
-        let timestamp = timestamp_ms as i64 / 1000;
-        if let Some(dt) = chrono::DateTime::from_timestamp(timestamp, 0) {
-            daily_prices.insert(dt.format("%Y-%m-%d").to_string(), price);
-        }
+/// Name of the CoinGecko data source (see `sources::CoinGeckoSource`)
+const COINGECKO_SOURCE: &str = "coingecko";
+
+/// GET a CoinGecko endpoint, failing on non-success statuses
+async fn coingecko_get(client: &reqwest::Client, url: &str, api_key: &str) -> Result<reqwest::Response> {
+    let response = client
+        .get(url)
+        .header("Accept", "application/json")
+        .header("x-cg-demo-api-key", api_key)
+        .send()
+        .await
+        .map_err(|e| anyhow::anyhow!("Request failed: {}", e))?;
+    match response.status() {
+        status if status.is_success() => Ok(response),
+        status if status.as_u16() == 429 => anyhow::bail!("Rate limited (429)"),
+        status => anyhow::bail!("CoinGecko API returned status: {}", status),
     }
-
-    Ok(daily_prices.into_iter().collect())
 }
 
 /// Fetch price range from Binance (no API key required).
 /// Klines endpoint returns up to 1000 daily candles per request.
-async fn fetch_price_range_binance(from: NaiveDate, to: NaiveDate) -> Result<Vec<(String, f64)>> {
+async fn fetch_price_range_binance(from: NaiveDate, to: NaiveDate) -> Result<PriceQuotes> {
     let client = reqwest::Client::new();
-    let mut all_prices: Vec<(String, f64)> = Vec::new();
+    let mut all_prices = PriceQuotes::new();
 
     // Paginate in chunks of 1000 days (Binance klines limit)
     let mut cursor = from;
@@ -276,16 +380,25 @@ async fn fetch_price_range_binance(from: NaiveDate, to: NaiveDate) -> Result<Vec
         }
 
         for kline in &klines {
-            if kline.len() < 5 {
+            if kline.len() < 7 {
                 continue;
             }
-            let open_time_ms = kline[0].as_i64().unwrap_or(0);
+            // Key the close by when it was observed (end of the UTC day) so it lands on
+            // the matching day in the accounting timezone.
+            let close_time_ms = kline[6].as_i64().unwrap_or(0);
             let close_price = kline[4].as_str().and_then(|s| s.parse::<f64>().ok()).unwrap_or(0.0);
 
             if close_price > 0.0
-                && let Some(dt) = chrono::DateTime::from_timestamp(open_time_ms / 1000, 0)
+                && let Some(date) = timezone::timestamp_to_date(close_time_ms / 1000)
             {
-                all_prices.push((dt.format("%Y-%m-%d").to_string(), close_price));
+                all_prices.insert(
+                    date,
+                    PriceQuote {
+                        usd_price: close_price,
+                        provider: PriceProvider::Binance,
+                        source_timestamp: Some(close_time_ms / 1000),
+                    },
+                );
             }
         }
 
@@ -313,7 +426,7 @@ async fn fetch_price_range_binance(from: NaiveDate, to: NaiveDate) -> Result<Vec
 
 /// Fetch price range from Dune `prices.usd` table (works from cloud IPs).
 /// Queries daily average SOL/USD prices for the given date range.
-async fn fetch_price_range_dune(from: NaiveDate, to: NaiveDate, dune_api_key: &str) -> Result<Vec<(String, f64)>> {
+async fn fetch_price_range_dune(from: NaiveDate, to: NaiveDate, dune_api_key: &str) -> Result<PriceQuotes> {
     let sql = format!(
         r#"
         SELECT
@@ -333,7 +446,7 @@ async fn fetch_price_range_dune(from: NaiveDate, to: NaiveDate, dune_api_key: &s
 
     let rows = dune::execute_sql(dune_api_key, &sql).await?;
 
-    let mut prices: Vec<(String, f64)> = Vec::new();
+    let mut prices = PriceQuotes::new();
     for row in &rows {
         let date = row.get("price_date").and_then(|v| v.as_str()).map(|s| s.to_string());
         let price = row.get("avg_price").and_then(|v| v.as_f64());
@@ -341,7 +454,14 @@ async fn fetch_price_range_dune(from: NaiveDate, to: NaiveDate, dune_api_key: &s
         if let (Some(d), Some(p)) = (date, price) {
             // Dune may return full timestamps; normalize to YYYY-MM-DD
             let date_str = if d.len() > 10 { d[..10].to_string() } else { d };
-            prices.push((date_str, p));
+            prices.insert(
+                date_str,
+                PriceQuote {
+                    usd_price: p,
+                    provider: PriceProvider::Dune,
+                    source_timestamp: None,
+                },
+            );
         }
     }
 
@@ -354,7 +474,7 @@ async fn fetch_price_range_dune(from: NaiveDate, to: NaiveDate, dune_api_key: &s
 }
 
 /// Fetch current SOL price — tries CoinGecko first, falls back to Binance
-pub async fn fetch_current_price(api_key: &str) -> Result<f64> {
+pub async fn fetch_current_price(api_key: &str) -> Result<PriceQuote> {
     match fetch_current_price_coingecko(api_key).await {
         Ok(price) => Ok(price),
         Err(_) => fetch_current_price_binance().await,
@@ -362,57 +482,30 @@ pub async fn fetch_current_price(api_key: &str) -> Result<f64> {
 }
 
 /// Fetch current SOL price from CoinGecko
-async fn fetch_current_price_coingecko(api_key: &str) -> Result<f64> {
+async fn fetch_current_price_coingecko(api_key: &str) -> Result<PriceQuote> {
     let client = reqwest::Client::new();
     let url = format!("{}{}", constants::COINGECKO_API_BASE, constants::COINGECKO_SIMPLE_PRICE);
 
-    let max_retries = 3;
-    let mut last_error = None;
-
-    for attempt in 0..max_retries {
-        if attempt > 0 {
-            let delay = Duration::from_secs(2u64.pow(attempt as u32));
-            sleep(delay).await;
-        }
-
-        match client
-            .get(&url)
-            .header("Accept", "application/json")
-            .header("x-cg-demo-api-key", api_key)
-            .send()
+    let (client, url) = (&client, &url);
+    let data = sources::request(COINGECKO_SOURCE, RetryPolicy::default(), move || async move {
+        coingecko_get(client, url, api_key)
+            .await?
+            .json::<SimplePriceResponse>()
             .await
-        {
-            Ok(response) => {
-                if response.status().is_success() {
-                    match response.json::<SimplePriceResponse>().await {
-                        Ok(data) => {
-                            return data
-                                .solana
-                                .map(|s| s.usd)
-                                .ok_or_else(|| anyhow::anyhow!("No SOL price in response"));
-                        }
-                        Err(e) => {
-                            last_error = Some(anyhow::anyhow!("Parse error: {}", e));
-                        }
-                    }
-                } else if response.status().as_u16() == 429 {
-                    last_error = Some(anyhow::anyhow!("Rate limited (429)"));
-                    continue;
-                } else {
-                    last_error = Some(anyhow::anyhow!("API returned status: {}", response.status()));
-                }
-            }
-            Err(e) => {
-                last_error = Some(anyhow::anyhow!("Request failed: {}", e));
-            }
-        }
-    }
-
-    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("Failed after {} retries", max_retries)))
+            .map_err(|e| anyhow::anyhow!("Parse error: {}", e))
+    })
+    .await?;
+    data.solana
+        .map(|s| PriceQuote {
+            usd_price: s.usd,
+            provider: PriceProvider::Coingecko,
+            source_timestamp: s.last_updated_at,
+        })
+        .ok_or_else(|| anyhow::anyhow!("No SOL price in response"))
 }
 
 /// Fetch current SOL price from Binance (no API key required)
-async fn fetch_current_price_binance() -> Result<f64> {
+async fn fetch_current_price_binance() -> Result<PriceQuote> {
     let client = reqwest::Client::new();
     let url = format!("{}{}", constants::BINANCE_API_BASE, constants::BINANCE_TICKER);
 
@@ -427,32 +520,144 @@ async fn fetch_current_price_binance() -> Result<f64> {
     data["price"]
         .as_str()
         .and_then(|s| s.parse::<f64>().ok())
+        .map(|usd_price| PriceQuote {
+            usd_price,
+            provider: PriceProvider::Binance,
+            source_timestamp: None,
+        })
         .ok_or_else(|| anyhow::anyhow!("No price in Binance response"))
 }
 
+/// Dates in `from..=to` that have no usable cached price.
+///
+/// Rows stored as fallback are treated as missing: older versions persisted the
+/// fallback when every price source failed.
+pub fn missing_price_dates(cache: &PriceCache, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+    from.iter_days()
+        .take_while(|d| *d <= to)
+        .filter(|d| {
+            cache
+                .get(&d.format("%Y-%m-%d").to_string())
+                .is_none_or(|p| p.source == PriceSource::Fallback)
+        })
+        .collect()
+}
+
+/// How a USD price was obtained for a report row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PriceSource {
+    /// Cached daily price for the exact date
+    Exact,
+    /// No price for the date; the closest cached date (at most `DAILY_PRICE_MAX_GAP_DAYS`
+    /// away) was used
+    Interpolated,
+    /// No usable cached price at all; `FALLBACK_SOL_PRICE` was used
+    Fallback,
+    /// Cached hourly price nearest the block time (large withdrawals, BAM claims)
+    Hourly,
+    /// Testnet/devnet run: SOL has no market price, USD values are reported as zero
+    #[serde(rename = "n/a")]
+    NotApplicable,
+}
+
+impl PriceSource {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "exact" => Some(PriceSource::Exact),
+            "interpolated" => Some(PriceSource::Interpolated),
+            "fallback" => Some(PriceSource::Fallback),
+            "hourly" => Some(PriceSource::Hourly),
+            "n/a" => Some(PriceSource::NotApplicable),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PriceSource::Exact => "exact",
+            PriceSource::Interpolated => "interpolated",
+            PriceSource::Fallback => "fallback",
+            PriceSource::Hourly => "hourly",
+            PriceSource::NotApplicable => "n/a",
+        }
+    }
+}
+
+impl std::fmt::Display for PriceSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Hourly price for the UTC hour nearest `timestamp`, if one is cached close enough.
+pub fn nearest_hourly_price(hourly: &HourlyPriceCache, timestamp: i64) -> Option<f64> {
+    let before = hourly.range(..=timestamp).next_back();
+    let after = hourly.range(timestamp..).next();
+
+    [before, after]
+        .into_iter()
+        .flatten()
+        .min_by_key(|(ts, _)| (**ts - timestamp).abs())
+        .filter(|(ts, _)| (**ts - timestamp).abs() <= constants::HOURLY_PRICE_MAX_GAP_SECS)
+        .map(|(_, price)| *price)
+}
+
+/// Price for an event with a known block time: nearest cached hour when available,
+/// otherwise the daily price for `date`.
+pub fn get_event_price(
+    daily: &PriceCache,
+    hourly: &HourlyPriceCache,
+    date: &str,
+    timestamp: Option<i64>,
+) -> (f64, PriceSource) {
+    if let Some(price) = timestamp
+        .filter(|_| cluster::prices_enabled())
+        .and_then(|ts| nearest_hourly_price(hourly, ts))
+    {
+        return (price, PriceSource::Hourly);
+    }
+    get_price_with_source(daily, date)
+}
+
 /// Get price for a specific date from cache, with fallback
 pub fn get_price(cache: &PriceCache, date: &str) -> f64 {
-    cache.get(date).copied().unwrap_or_else(|| {
-        // Try to find closest date
-        if let Ok(target) = NaiveDate::parse_from_str(date, "%Y-%m-%d") {
-            let mut closest_price = constants::FALLBACK_SOL_PRICE;
-            let mut closest_diff = i64::MAX;
-
-            for (d, p) in cache {
-                if let Ok(cached_date) = NaiveDate::parse_from_str(d, "%Y-%m-%d") {
-                    let diff = (target - cached_date).num_days().abs();
-                    if diff < closest_diff {
-                        closest_diff = diff;
-                        closest_price = *p;
-                    }
-                }
-            }
+    get_price_with_source(cache, date).0
+}
 
-            closest_price
-        } else {
-            constants::FALLBACK_SOL_PRICE
+/// Get price for a specific date along with how it was obtained.
+///
+/// A cached price keeps the source it was stored with. Without one, the closest cached
+/// date within `DAILY_PRICE_MAX_GAP_DAYS` is used; beyond that, `FALLBACK_SOL_PRICE`.
+/// Off mainnet every price is zero with source `n/a`.
+pub fn get_price_with_source(cache: &PriceCache, date: &str) -> (f64, PriceSource) {
+    if !cluster::prices_enabled() {
+        return (0.0, PriceSource::NotApplicable);
+    }
+    if let Some(cached) = cache.get(date) {
+        return (cached.usd_price, cached.source);
+    }
+
+    // Try to find closest date
+    let Ok(target) = NaiveDate::parse_from_str(date, "%Y-%m-%d") else {
+        return (constants::FALLBACK_SOL_PRICE, PriceSource::Fallback);
+    };
+
+    let mut closest: Option<(i64, CachedPrice)> = None;
+    for (d, p) in cache {
+        if let Ok(cached_date) = NaiveDate::parse_from_str(d, "%Y-%m-%d") {
+            let diff = (target - cached_date).num_days().abs();
+            if diff <= constants::DAILY_PRICE_MAX_GAP_DAYS && closest.is_none_or(|(best, _)| diff < best) {
+                closest = Some((diff, *p));
+            }
         }
-    })
+    }
+
+    match closest {
+        Some((_, p)) if p.source == PriceSource::Fallback => (p.usd_price, PriceSource::Fallback),
+        Some((_, p)) => (p.usd_price, PriceSource::Interpolated),
+        None => (constants::FALLBACK_SOL_PRICE, PriceSource::Fallback),
+    }
 }
 
 #[cfg(test)]
@@ -464,4 +669,101 @@ mod tests {
         let cache: PriceCache = Default::default();
         assert!(cache.is_empty());
     }
+
+    #[test]
+    fn test_missing_price_dates_includes_gaps_and_stored_fallbacks() {
+        use super::{CachedPrice, PriceCache, PriceSource, missing_price_dates};
+        use chrono::NaiveDate;
+
+        let mut cache = PriceCache::new();
+        cache.insert("2025-11-01".to_string(), 160.25.into());
+        cache.insert(
+            "2025-11-03".to_string(),
+            CachedPrice {
+                usd_price: 185.0,
+                source: PriceSource::Fallback,
+            },
+        );
+        cache.insert("2025-11-04".to_string(), 158.10.into());
+
+        let from = NaiveDate::from_ymd_opt(2025, 11, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2025, 11, 4).unwrap();
+        let missing = missing_price_dates(&cache, from, to);
+
+        assert_eq!(
+            missing,
+            vec![
+                NaiveDate::from_ymd_opt(2025, 11, 2).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 11, 3).unwrap(),
+            ]
+        );
+        assert!(missing_price_dates(&cache, to, from).is_empty());
+    }
+
+    #[test]
+    fn test_get_price_with_source_flags_soft_prices() {
+        use super::{CachedPrice, PriceCache, PriceSource, get_price_with_source};
+        use crate::constants;
+
+        let mut cache = PriceCache::new();
+        cache.insert("2025-11-01".to_string(), 160.25.into());
+        cache.insert(
+            "2025-11-05".to_string(),
+            CachedPrice {
+                usd_price: 171.0,
+                source: PriceSource::Fallback,
+            },
+        );
+        // A real market price that happens to equal the fallback constant stays exact
+        cache.insert("2025-11-20".to_string(), constants::FALLBACK_SOL_PRICE.into());
+
+        assert_eq!(
+            get_price_with_source(&cache, "2025-11-01"),
+            (160.25, PriceSource::Exact)
+        );
+        assert_eq!(
+            get_price_with_source(&cache, "2025-11-02"),
+            (160.25, PriceSource::Interpolated)
+        );
+        assert_eq!(get_price_with_source(&cache, "2025-11-05").1, PriceSource::Fallback);
+        assert_eq!(get_price_with_source(&cache, "2025-11-20").1, PriceSource::Exact);
+        assert_eq!(
+            get_price_with_source(&cache, "2025-11-12"),
+            (constants::FALLBACK_SOL_PRICE, PriceSource::Fallback)
+        );
+        assert_eq!(get_price_with_source(&cache, "unknown").1, PriceSource::Fallback);
+        assert_eq!(
+            get_price_with_source(&PriceCache::new(), "2025-11-01"),
+            (constants::FALLBACK_SOL_PRICE, PriceSource::Fallback)
+        );
+    }
+
+    #[test]
+    fn test_get_event_price_prefers_nearest_hour() {
+        use super::{HourlyPriceCache, PriceCache, PriceSource, get_event_price, nearest_hourly_price};
+
+        let mut daily = PriceCache::new();
+        daily.insert("2025-11-01".to_string(), 160.0.into());
+
+        // 2025-11-01 12:00 and 13:00 UTC
+        let noon = 1_761_998_400;
+        let hourly = HourlyPriceCache::from([(noon, 158.5), (noon + 3600, 161.2)]);
+
+        assert_eq!(nearest_hourly_price(&hourly, noon + 1200), Some(158.5));
+        assert_eq!(nearest_hourly_price(&hourly, noon + 2400), Some(161.2));
+        assert_eq!(nearest_hourly_price(&hourly, noon + 4 * 3600), None);
+
+        assert_eq!(
+            get_event_price(&daily, &hourly, "2025-11-01", Some(noon + 600)),
+            (158.5, PriceSource::Hourly)
+        );
+        assert_eq!(
+            get_event_price(&daily, &hourly, "2025-11-01", Some(noon + 6 * 3600)),
+            (160.0, PriceSource::Exact)
+        );
+        assert_eq!(
+            get_event_price(&daily, &hourly, "2025-11-01", None),
+            (160.0, PriceSource::Exact)
+        );
+    }
 }
@@ -0,0 +1,491 @@
+//! Side-by-side comparison of two report generations (`compare-reports`)
+//!
+//! Reads the income and expense ledgers of two output directories, totals USD per ledger,
+//! month and category, and explains each change by matching rows between the sets: rows
+//! for epochs past the older set's last epoch, other added or removed rows, the same row
+//! repriced, its SOL amount changed, or moved to another category. The metadata blocks
+//! (see `report_metadata`) are compared too, so a changed config or tool version shows up
+//! next to the numbers.
+
+use anyhow::{Context, Result};
+use csv::{ReaderBuilder, StringRecord};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::constants;
+use crate::report_metadata::COMMENT_PREFIX;
+
+/// Changes smaller than this are rounding, not changes
+const USD_EPSILON: f64 = 0.005;
+const SOL_EPSILON: f64 = 1e-9;
+
+/// Metadata that differs between any two generations and says nothing on its own
+const VOLATILE_METADATA: [&str; 1] = ["Generated (UTC)"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Ledger {
+    Income,
+    Expense,
+}
+
+impl Ledger {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Ledger::Income => "income",
+            Ledger::Expense => "expense",
+        }
+    }
+
+    fn file_name(self) -> &'static str {
+        match self {
+            Ledger::Income => constants::INCOME_LEDGER_FILENAME,
+            Ledger::Expense => constants::EXPENSE_LEDGER_FILENAME,
+        }
+    }
+
+    /// (category, USD value, row identity) column names; the identity is the signature or
+    /// invoice, falling back to date and description
+    fn columns(self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            Ledger::Income => ("Source", "USD_Value", "Tx_Signature"),
+            Ledger::Expense => ("Category", "Amount_USD", "Invoice_ID"),
+        }
+    }
+}
+
+/// One ledger row, reduced to what the comparison needs
+#[derive(Debug, Clone)]
+struct LedgerRow {
+    ledger: Ledger,
+    key: String,
+    month: String,
+    category: String,
+    epoch: Option<u64>,
+    amount_sol: Option<f64>,
+    usd: f64,
+}
+
+/// The ledgers and metadata block of one output directory
+#[derive(Debug, Default)]
+pub struct ReportSet {
+    metadata: Vec<(String, String)>,
+    rows: Vec<LedgerRow>,
+}
+
+/// Why rows changed between the two sets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reason {
+    /// Rows for epochs after the first set's last epoch
+    NewEpoch,
+    /// Other rows only in the second set (backfills, newly imported expenses)
+    Added,
+    /// Rows only in the first set
+    Removed,
+    /// Same row and SOL amount at a different USD value
+    Repriced,
+    /// Same row with a different SOL amount
+    AmountChanged,
+    /// Same row under a different category or source
+    Reclassified,
+}
+
+impl Reason {
+    pub fn label(self) -> &'static str {
+        match self {
+            Reason::NewEpoch => "New epochs",
+            Reason::Added => "Added rows",
+            Reason::Removed => "Removed rows",
+            Reason::Repriced => "Price updates",
+            Reason::AmountChanged => "Amount changes",
+            Reason::Reclassified => "Reclassifications",
+        }
+    }
+}
+
+/// USD total of one ledger/month/category in each set
+#[derive(Debug, Clone, Serialize)]
+pub struct CategoryDelta {
+    pub ledger: Ledger,
+    pub month: String,
+    pub category: String,
+    pub usd_a: f64,
+    pub usd_b: f64,
+}
+
+impl CategoryDelta {
+    pub fn delta(&self) -> f64 {
+        self.usd_b - self.usd_a
+    }
+}
+
+/// Rows attributed to one reason
+#[derive(Debug, Clone, Serialize)]
+pub struct ReasonTotal {
+    pub ledger: Ledger,
+    pub reason: Reason,
+    pub rows: usize,
+    /// Net USD change, except for reclassifications where it is the USD moved
+    pub usd: f64,
+}
+
+/// A metadata value that differs between the sets
+#[derive(Debug, Clone, Serialize)]
+pub struct MetadataChange {
+    pub label: String,
+    pub a: String,
+    pub b: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Comparison {
+    /// Per-ledger USD totals (a, b)
+    pub totals: BTreeMap<Ledger, (f64, f64)>,
+    /// Ledger/month/category totals that changed
+    pub deltas: Vec<CategoryDelta>,
+    pub reasons: Vec<ReasonTotal>,
+    /// Epochs only the second set has rows for
+    pub new_epochs: Option<(u64, u64)>,
+    pub metadata_changes: Vec<MetadataChange>,
+}
+
+/// Load the income and expense ledgers from a report directory
+pub fn load_set(dir: &Path) -> Result<ReportSet> {
+    let mut set = ReportSet::default();
+    for ledger in [Ledger::Income, Ledger::Expense] {
+        let path = dir.join(ledger.file_name());
+        let content = std::fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        if set.metadata.is_empty() {
+            set.metadata = metadata_block(&content)?;
+        }
+        set.rows
+            .extend(parse_ledger(ledger, &content).with_context(|| format!("Failed to parse {}", path.display()))?);
+    }
+    Ok(set)
+}
+
+/// `# Label,value` rows at the top of a stamped CSV
+fn metadata_block(content: &str) -> Result<Vec<(String, String)>> {
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(content.as_bytes());
+    let mut fields = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let Some(label) = record.get(0).and_then(|f| f.strip_prefix(COMMENT_PREFIX)) else {
+            break;
+        };
+        fields.push((label.trim().to_string(), record.get(1).unwrap_or_default().to_string()));
+    }
+    Ok(fields)
+}
+
+/// Index of the column named `name` (headers carry a plain-English suffix in parentheses)
+fn column(headers: &StringRecord, name: &str) -> Result<usize> {
+    headers
+        .iter()
+        .position(|h| h.split(" (").next() == Some(name))
+        .with_context(|| format!("Missing column {}", name))
+}
+
+fn parse_ledger(ledger: Ledger, content: &str) -> Result<Vec<LedgerRow>> {
+    let mut reader = ReaderBuilder::new()
+        .comment(Some(b'#'))
+        .flexible(true)
+        .from_reader(content.as_bytes());
+    let headers = reader.headers()?.clone();
+    let (category_col, usd_col, id_col) = ledger.columns();
+    let date = column(&headers, "Date")?;
+    let epoch = column(&headers, "Epoch")?;
+    let amount_sol = column(&headers, "Amount_SOL")?;
+    let category = column(&headers, category_col)?;
+    let usd = column(&headers, usd_col)?;
+    let id = column(&headers, id_col)?;
+    let description = column(&headers, "Description").ok();
+
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let field = |i: usize| record.get(i).unwrap_or_default().trim();
+        let identity = match (field(id), description) {
+            ("", Some(description)) => format!("{}|{}", field(date), field(description)),
+            (id, _) => id.to_string(),
+        };
+        // The same signature or epoch can back several rows (e.g. one per income source)
+        let occurrence = seen.entry(identity.clone()).or_default();
+        *occurrence += 1;
+        rows.push(LedgerRow {
+            ledger,
+            key: format!("{}#{}", identity, occurrence),
+            month: field(date).chars().take(7).collect(),
+            category: field(category).to_string(),
+            epoch: field(epoch).parse().ok(),
+            amount_sol: field(amount_sol).parse().ok(),
+            usd: field(usd).parse().unwrap_or(0.0),
+        });
+    }
+    Ok(rows)
+}
+
+/// Compare an older report set `a` with a newer one `b`
+pub fn compare(a: &ReportSet, b: &ReportSet) -> Comparison {
+    let mut totals: BTreeMap<Ledger, (f64, f64)> = BTreeMap::new();
+    let mut by_category: BTreeMap<(Ledger, String, String), (f64, f64)> = BTreeMap::new();
+    for row in &a.rows {
+        totals.entry(row.ledger).or_default().0 += row.usd;
+        by_category
+            .entry((row.ledger, row.month.clone(), row.category.clone()))
+            .or_default()
+            .0 += row.usd;
+    }
+    for row in &b.rows {
+        totals.entry(row.ledger).or_default().1 += row.usd;
+        by_category
+            .entry((row.ledger, row.month.clone(), row.category.clone()))
+            .or_default()
+            .1 += row.usd;
+    }
+    let deltas = by_category
+        .into_iter()
+        .filter(|(_, (usd_a, usd_b))| (usd_b - usd_a).abs() >= USD_EPSILON)
+        .map(|((ledger, month, category), (usd_a, usd_b))| CategoryDelta {
+            ledger,
+            month,
+            category,
+            usd_a,
+            usd_b,
+        })
+        .collect();
+
+    let last_epoch_a = a.rows.iter().filter_map(|r| r.epoch).max();
+    let rows_a: HashMap<(Ledger, &str), &LedgerRow> = a.rows.iter().map(|r| ((r.ledger, r.key.as_str()), r)).collect();
+    let rows_b: HashMap<(Ledger, &str), &LedgerRow> = b.rows.iter().map(|r| ((r.ledger, r.key.as_str()), r)).collect();
+
+    let mut reasons: BTreeMap<(Ledger, Reason), (usize, f64)> = BTreeMap::new();
+    let mut new_epochs: Option<(u64, u64)> = None;
+    for row in &b.rows {
+        let (reason, usd) = match rows_a.get(&(row.ledger, row.key.as_str())) {
+            None => match row.epoch {
+                Some(epoch) if last_epoch_a.is_none_or(|last| epoch > last) => {
+                    new_epochs = Some(match new_epochs {
+                        Some((first, last)) => (first.min(epoch), last.max(epoch)),
+                        None => (epoch, epoch),
+                    });
+                    (Reason::NewEpoch, row.usd)
+                }
+                _ => (Reason::Added, row.usd),
+            },
+            Some(old) if old.category != row.category => (Reason::Reclassified, row.usd),
+            Some(old) if amount_changed(old.amount_sol, row.amount_sol) => (Reason::AmountChanged, row.usd - old.usd),
+            Some(old) if (row.usd - old.usd).abs() >= USD_EPSILON => (Reason::Repriced, row.usd - old.usd),
+            Some(_) => continue,
+        };
+        let entry = reasons.entry((row.ledger, reason)).or_default();
+        entry.0 += 1;
+        entry.1 += usd;
+    }
+    for row in a
+        .rows
+        .iter()
+        .filter(|r| !rows_b.contains_key(&(r.ledger, r.key.as_str())))
+    {
+        let entry = reasons.entry((row.ledger, Reason::Removed)).or_default();
+        entry.0 += 1;
+        entry.1 -= row.usd;
+    }
+
+    Comparison {
+        totals,
+        deltas,
+        reasons: reasons
+            .into_iter()
+            .map(|((ledger, reason), (rows, usd))| ReasonTotal {
+                ledger,
+                reason,
+                rows,
+                usd,
+            })
+            .collect(),
+        new_epochs,
+        metadata_changes: metadata_changes(&a.metadata, &b.metadata),
+    }
+}
+
+fn amount_changed(a: Option<f64>, b: Option<f64>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => (a - b).abs() > SOL_EPSILON,
+        (a, b) => a.is_some() != b.is_some(),
+    }
+}
+
+fn metadata_changes(a: &[(String, String)], b: &[(String, String)]) -> Vec<MetadataChange> {
+    let lookup = |fields: &[(String, String)], label: &str| {
+        fields
+            .iter()
+            .find(|(l, _)| l == label)
+            .map_or_else(|| "(none)".to_string(), |(_, v)| v.clone())
+    };
+    let mut changes: Vec<MetadataChange> = Vec::new();
+    for label in a.iter().chain(b).map(|(label, _)| label.as_str()) {
+        if VOLATILE_METADATA.contains(&label) || changes.iter().any(|c| c.label == label) {
+            continue;
+        }
+        let (value_a, value_b) = (lookup(a, label), lookup(b, label));
+        if value_a != value_b {
+            changes.push(MetadataChange {
+                label: label.to_string(),
+                a: value_a,
+                b: value_b,
+            });
+        }
+    }
+    changes
+}
+
+/// Print the comparison (`a` and `b` name the two directories)
+pub fn print_comparison(comparison: &Comparison, a: &Path, b: &Path) {
+    println!("Comparing {} (A) with {} (B)\n", a.display(), b.display());
+
+    if !comparison.metadata_changes.is_empty() {
+        println!("Generation settings that changed:");
+        for change in &comparison.metadata_changes {
+            println!("  {}: {} -> {}", change.label, change.a, change.b);
+        }
+        println!();
+    }
+
+    for (ledger, (usd_a, usd_b)) in &comparison.totals {
+        println!(
+            "{:<8} ${:>14.2} -> ${:>14.2}  ({:+.2})",
+            ledger.as_str(),
+            usd_a,
+            usd_b,
+            usd_b - usd_a
+        );
+    }
+
+    if comparison.deltas.is_empty() {
+        println!("\nNo month or category changed.");
+        return;
+    }
+
+    println!(
+        "\n{:<8} {:<8} {:<40} {:>12} {:>12} {:>12}",
+        "Ledger", "Month", "Category", "A (USD)", "B (USD)", "Change"
+    );
+    println!("{}", "-".repeat(97));
+    for delta in &comparison.deltas {
+        let category: String = delta.category.chars().take(40).collect();
+        println!(
+            "{:<8} {:<8} {:<40} {:>12.2} {:>12.2} {:>+12.2}",
+            delta.ledger.as_str(),
+            delta.month,
+            category,
+            delta.usd_a,
+            delta.usd_b,
+            delta.delta()
+        );
+    }
+    println!("{}", "-".repeat(97));
+
+    println!("\nWhy:");
+    for total in &comparison.reasons {
+        let label = match (total.reason, comparison.new_epochs) {
+            (Reason::NewEpoch, Some((first, last))) => format!("New epochs {}-{}", first, last),
+            (reason, _) => reason.label().to_string(),
+        };
+        let usd = if total.reason == Reason::Reclassified {
+            format!("${:.2} moved", total.usd)
+        } else {
+            format!("{:+.2} USD", total.usd)
+        };
+        println!(
+            "  {:<8} {:<22} {:>6} row(s)  {}",
+            total.ledger.as_str(),
+            label,
+            total.rows,
+            usd
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INCOME_HEADER: &str = "Date (YYYY-MM-DD),Epoch (Solana ~2-day period),Source (plain English),Amount_SOL (SOL),USD_Price (USD per 1 SOL),USD_Value (Amount_SOL * USD_Price),Tx_Signature (tx id or epoch-N)\n";
+
+    fn income(rows: &str) -> ReportSet {
+        ReportSet {
+            metadata: Vec::new(),
+            rows: parse_ledger(Ledger::Income, &format!("# Config hash,abc\n{}{}", INCOME_HEADER, rows)).unwrap(),
+        }
+    }
+
+    fn reason(comparison: &Comparison, reason: Reason) -> Option<(usize, f64)> {
+        comparison
+            .reasons
+            .iter()
+            .find(|r| r.reason == reason)
+            .map(|r| (r.rows, (r.usd * 100.0).round() / 100.0))
+    }
+
+    #[test]
+    fn test_compare_attributes_changes_to_reasons() {
+        let a = income(concat!(
+            "2025-01-01,700,Commission,1.0,100.00,100.00,epoch-700\n",
+            "2025-01-03,701,Commission,1.0,100.00,100.00,epoch-701\n",
+            "2025-01-05,702,MEV,2.0,100.00,200.00,sigA\n",
+            "2025-01-06,702,Donation,0.5,100.00,50.00,sigB\n",
+        ));
+        let b = income(concat!(
+            "2025-01-01,700,Commission,1.0,110.00,110.00,epoch-700\n",
+            "2025-01-03,701,Commission,1.5,100.00,150.00,epoch-701\n",
+            "2025-01-05,702,Block fees,2.0,100.00,200.00,sigA\n",
+            "2025-02-01,716,Commission,1.0,120.00,120.00,epoch-716\n",
+        ));
+        let comparison = compare(&a, &b);
+
+        assert_eq!(reason(&comparison, Reason::Repriced), Some((1, 10.0)));
+        assert_eq!(reason(&comparison, Reason::AmountChanged), Some((1, 50.0)));
+        assert_eq!(reason(&comparison, Reason::Reclassified), Some((1, 200.0)));
+        assert_eq!(reason(&comparison, Reason::NewEpoch), Some((1, 120.0)));
+        assert_eq!(reason(&comparison, Reason::Removed), Some((1, -50.0)));
+        assert_eq!(reason(&comparison, Reason::Added), None);
+        assert_eq!(comparison.new_epochs, Some((716, 716)));
+
+        let delta = |month: &str, category: &str| {
+            comparison
+                .deltas
+                .iter()
+                .find(|d| d.month == month && d.category == category)
+                .map(|d| d.delta())
+        };
+        assert_eq!(delta("2025-01", "Commission"), Some(60.0));
+        assert_eq!(delta("2025-01", "MEV"), Some(-200.0));
+        assert_eq!(delta("2025-01", "Block fees"), Some(200.0));
+        assert_eq!(delta("2025-02", "Commission"), Some(120.0));
+        assert_eq!(comparison.totals[&Ledger::Income], (450.0, 580.0));
+    }
+
+    #[test]
+    fn test_metadata_changes_skip_generation_time() {
+        let fields = |generated: &str, hash: &str| {
+            vec![
+                ("Generated (UTC)".to_string(), generated.to_string()),
+                ("Config hash".to_string(), hash.to_string()),
+            ]
+        };
+        let content = "# Generated (UTC),2025-06-01 12:00:00\n# Config hash,abc\nDate,Amount\n";
+        assert_eq!(metadata_block(content).unwrap(), fields("2025-06-01 12:00:00", "abc"));
+
+        let changes = metadata_changes(&fields("t1", "abc"), &fields("t2", "def"));
+        assert_eq!(changes.len(), 1);
+        assert_eq!((changes[0].a.as_str(), changes[0].b.as_str()), ("abc", "def"));
+        assert!(metadata_changes(&fields("t1", "abc"), &fields("t2", "abc")).is_empty());
+    }
+}
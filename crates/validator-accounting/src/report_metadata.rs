@@ -0,0 +1,229 @@
+//! Snapshot-at-generation metadata stamped into every report
+//!
+//! Each CSV written by `generate_all_reports` starts with `# Label,value` rows, and
+//! `report.html` shows the same block in its footer: when it was generated, which
+//! ingestion run the cache data comes from, the epochs and period covered, how SOL was
+//! priced, the tool version and a hash of the config. A report handed around on its own
+//! then says how to reproduce it. Readers of the CSVs skip the block with
+//! `ReaderBuilder::comment(Some(b'#'))`.
+
+use anyhow::{Context, Result};
+use csv::Writer;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+use crate::cache::IngestionRun;
+use crate::constants;
+use crate::period::ReportPeriod;
+use crate::reports::ReportData;
+
+/// Metadata rows start with this, so CSV readers can treat them as comments
+pub const COMMENT_PREFIX: &str = "#";
+
+/// Top-level CSVs written by `generate_all_reports` (entity reports are found by walking
+/// their directory)
+const REPORT_CSV_FILENAMES: [&str; 14] = [
+    constants::INCOME_LEDGER_FILENAME,
+    constants::EXPENSE_LEDGER_FILENAME,
+    constants::TREASURY_LEDGER_FILENAME,
+    constants::SUMMARY_FILENAME,
+    constants::GLOSSARY_FILENAME,
+    constants::PRICE_WARNINGS_FILENAME,
+    constants::EXCEPTIONS_FILENAME,
+    constants::CLIENT_UPLIFT_FILENAME,
+    constants::EPOCH_PNL_FILENAME,
+    constants::UNIT_ECONOMICS_FILENAME,
+    constants::REVENUE_STATS_FILENAME,
+    constants::DELEGATORS_FILENAME,
+    constants::SFDP_STAKE_FILENAME,
+    constants::FIAT_CASH_FILENAME,
+];
+
+/// Where and how a set of reports was generated
+#[derive(Debug, Clone)]
+pub struct ReportMetadata {
+    /// UTC, `YYYY-MM-DD HH:MM:SS`
+    pub generated_at: String,
+    /// Latest completed ingestion run, e.g. "run #42 (success, finished 2025-06-01 12:00:00)"
+    pub data_version: String,
+    /// First and last reward epoch in the period, if any
+    pub epochs: Option<(u64, u64)>,
+    /// Period label, or "All time"
+    pub period: String,
+    pub pricing_policy: String,
+    pub tool_version: String,
+    /// First 16 hex digits of the config file's SHA-256 ("none" without a file)
+    pub config_hash: String,
+}
+
+impl ReportMetadata {
+    pub fn new(
+        data: &ReportData,
+        period: Option<&ReportPeriod>,
+        runs: &[IngestionRun],
+        config_toml: Option<&[u8]>,
+    ) -> Self {
+        let epochs = data
+            .rewards
+            .iter()
+            .filter(|r| period.is_none_or(|p| r.date.as_deref().is_some_and(|d| p.contains(d))))
+            .map(|r| r.epoch)
+            .fold(None, |range: Option<(u64, u64)>, epoch| match range {
+                Some((first, last)) => Some((first.min(epoch), last.max(epoch))),
+                None => Some((epoch, epoch)),
+            });
+        Self {
+            generated_at: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            data_version: data_version(runs),
+            epochs,
+            period: period.map_or_else(|| "All time".to_string(), |p| p.label.clone()),
+            pricing_policy: pricing_policy(data),
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            config_hash: config_toml.map_or_else(|| "none".to_string(), config_hash),
+        }
+    }
+
+    /// Label/value pairs, in the order they are printed
+    pub fn fields(&self) -> Vec<(String, String)> {
+        let epochs = match self.epochs {
+            Some((first, last)) => format!("{}-{}", first, last),
+            None => "none".to_string(),
+        };
+        [
+            ("Generated (UTC)", self.generated_at.clone()),
+            ("Cache data", self.data_version.clone()),
+            ("Epochs", epochs),
+            ("Period", self.period.clone()),
+            ("Pricing", self.pricing_policy.clone()),
+            ("Tool version", self.tool_version.clone()),
+            ("Config hash", self.config_hash.clone()),
+        ]
+        .into_iter()
+        .map(|(label, value)| (label.to_string(), value))
+        .collect()
+    }
+}
+
+/// The newest ingestion run that finished loading data
+fn data_version(runs: &[IngestionRun]) -> String {
+    runs.iter()
+        .find(|run| run.finished_at.is_some() && matches!(run.status.as_str(), "success" | "partial"))
+        .map_or_else(
+            || "no completed ingestion run".to_string(),
+            |run| {
+                format!(
+                    "run #{} ({}, finished {})",
+                    run.id,
+                    run.status,
+                    run.finished_at.as_deref().unwrap_or_default()
+                )
+            },
+        )
+}
+
+fn pricing_policy(data: &ReportData) -> String {
+    let mut policy = "CoinGecko daily SOL/USD".to_string();
+    if data.config.hourly_pricing_enabled {
+        policy.push_str(&format!(
+            "; hourly for withdrawals >= {} SOL and BAM claims",
+            data.config.hourly_min_withdrawal_sol
+        ));
+    }
+    policy.push_str(&format!("; ${} fallback when missing", constants::FALLBACK_SOL_PRICE));
+    policy
+}
+
+/// Short SHA-256 of the raw config file (any edit, including comments, changes it)
+pub fn config_hash(config_toml: &[u8]) -> String {
+    Sha256::digest(config_toml)
+        .iter()
+        .take(8)
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// The metadata block as CSV rows (`# Label,value`)
+fn csv_block(meta: &ReportMetadata) -> Result<String> {
+    let mut wtr = Writer::from_writer(Vec::new());
+    for (label, value) in meta.fields() {
+        wtr.write_record([format!("{} {}", COMMENT_PREFIX, label), value])?;
+    }
+    let bytes = wtr
+        .into_inner()
+        .map_err(|e| anyhow::anyhow!("Failed to flush CSV: {}", e))?;
+    Ok(String::from_utf8(bytes)?)
+}
+
+/// Whether a CSV already starts with a metadata block
+fn is_stamped(content: &str) -> bool {
+    content.starts_with(COMMENT_PREFIX)
+}
+
+/// Prepend the metadata block to each report CSV written by this run
+///
+/// Files that already carry a block are left from an earlier run (e.g. `client_uplift.csv`
+/// once network fee medians are gone) and keep the metadata they were generated with.
+pub fn stamp_csv_reports(output_dir: &Path, meta: &ReportMetadata) -> Result<usize> {
+    let mut paths: Vec<PathBuf> = REPORT_CSV_FILENAMES.iter().map(|name| output_dir.join(name)).collect();
+    let entities_dir = output_dir.join(constants::ENTITIES_DIRNAME);
+    if entities_dir.is_dir() {
+        paths.extend(
+            crate::fingerprint::report_files(&entities_dir)?
+                .into_iter()
+                .filter(|p| p.extension().is_some_and(|ext| ext == "csv")),
+        );
+    }
+
+    let block = csv_block(meta)?;
+    let mut stamped = 0;
+    for path in paths.iter().filter(|p| p.exists()) {
+        let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        if is_stamped(&content) {
+            continue;
+        }
+        std::fs::write(path, format!("{}{}", block, content))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        stamped += 1;
+    }
+    Ok(stamped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use csv::ReaderBuilder;
+
+    fn meta() -> ReportMetadata {
+        ReportMetadata {
+            generated_at: "2025-06-01 12:00:00".to_string(),
+            data_version: "run #7 (success, finished 2025-06-01 11:58:00)".to_string(),
+            epochs: Some((700, 790)),
+            period: "2025".to_string(),
+            pricing_policy: "CoinGecko daily SOL/USD, with a comma".to_string(),
+            tool_version: "0.1.0".to_string(),
+            config_hash: config_hash(b"[validator]\n"),
+        }
+    }
+
+    #[test]
+    fn test_stamped_csv_reads_back_with_comment_rows_skipped() {
+        let dir = std::env::temp_dir().join(format!("bp-report-metadata-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(constants::SUMMARY_FILENAME);
+        std::fs::write(&path, "Month,Revenue\n2025-01,1.50\n").unwrap();
+
+        assert_eq!(stamp_csv_reports(&dir, &meta()).unwrap(), 1);
+        // A second pass leaves the block alone
+        assert_eq!(stamp_csv_reports(&dir, &meta()).unwrap(), 0);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("# Generated (UTC),2025-06-01 12:00:00\n"));
+        assert!(content.contains("# Pricing,\"CoinGecko daily SOL/USD, with a comma\"\n"));
+        assert_eq!(content.matches("# Config hash,").count(), 1);
+
+        let mut reader = ReaderBuilder::new().comment(Some(b'#')).from_reader(content.as_bytes());
+        assert_eq!(reader.headers().unwrap(), vec!["Month", "Revenue"]);
+        assert_eq!(reader.records().count(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
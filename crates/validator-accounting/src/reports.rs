@@ -2,19 +2,39 @@
 
 use anyhow::Result;
 use csv::Writer;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::path::Path;
+use std::str::FromStr;
 
+use crate::addresses::{self, AddressCategory};
+use crate::anomalies;
 use crate::bam::BamClaim;
+use crate::cache::{GroupedAmounts, MonthlyAggregates};
+use crate::client_uplift::{self, NetworkFeeMedian};
+use crate::cluster;
 use crate::config::Config;
 use crate::constants;
+use crate::delegators::{self, DelegatorStake};
 use crate::doublezero::DoubleZeroFee;
+use crate::entities;
+use crate::epoch_pnl;
 use crate::expenses::{Expense, ExpenseCategory};
+use crate::fiat::{self, FiatDeposit};
 use crate::html_report;
 use crate::jito::MevClaim;
+use crate::key_rotation::{KeyChange, KeyRole};
 use crate::leader_fees::EpochLeaderFees;
-use crate::prices::{PriceCache, get_price};
-use crate::transactions::{CategorizedTransfers, EpochReward};
+use crate::period::{ReportPeriod, fiscal_year_label};
+use crate::prices::{HourlyPriceCache, PriceCache, PriceSource, get_event_price, get_price};
+use crate::report_metadata::{self, ReportMetadata};
+use crate::revenue_stats;
+use crate::sfdp;
+use crate::squads;
+use crate::timezone;
+use crate::transactions::{CategorizedTransfers, EpochReward, SolTransfer};
+use crate::unit_economics::{self, EpochPerformance};
+use crate::validation::{self, DataException};
 use crate::vote_costs::EpochVoteCost;
 
 /// Bundled report data to reduce function argument counts
@@ -24,15 +44,90 @@ pub struct ReportData<'a> {
     pub mev_claims: &'a [MevClaim],
     pub bam_claims: &'a [BamClaim],
     pub leader_fees: &'a [EpochLeaderFees],
+    /// Network median block fees for the Jito vs vanilla what-if (empty until imported)
+    pub network_fee_medians: &'a [NetworkFeeMedian],
     pub doublezero_fees: &'a [DoubleZeroFee],
     pub vote_costs: &'a [EpochVoteCost],
+    /// Vote credits and delegated stake per epoch (unit economics)
+    pub epoch_performance: &'a [EpochPerformance],
+    /// Per-delegator stake snapshots (concentration and churn)
+    pub delegator_stake: &'a [DelegatorStake],
     pub expenses: &'a [Expense],
+    /// Deposits into the USD cash account (`[fiat]`)
+    pub fiat_deposits: &'a [FiatDeposit],
     pub prices: &'a PriceCache,
+    /// Hourly prices around large withdrawals and BAM claims (may be empty)
+    pub hourly_prices: &'a HourlyPriceCache,
+    /// Per-epoch sources pre-aggregated in SQL; the summary uses these, the ledgers use the rows
+    pub aggregates: &'a MonthlyAggregates,
+    /// Rows `validation::quarantine` left out of everything above (exceptions.csv)
+    pub exceptions: &'a [DataException],
     pub config: &'a Config,
 }
 
-/// Generate all CSV reports
-pub fn generate_all_reports(output_dir: &Path, data: &ReportData, year_filter: Option<i32>) -> Result<()> {
+/// A ledger row valued with `FALLBACK_SOL_PRICE` because no cached price was usable
+struct FallbackPricedRow {
+    ledger: &'static str,
+    date: String,
+    reference: String,
+    amount_sol: f64,
+    price: f64,
+}
+
+/// Price lookups for the ledgers, remembering every row that had to use the fallback price
+struct PricedRows<'a> {
+    daily: &'a PriceCache,
+    hourly: &'a HourlyPriceCache,
+    fallback: Vec<FallbackPricedRow>,
+}
+
+impl<'a> PricedRows<'a> {
+    fn new(daily: &'a PriceCache, hourly: &'a HourlyPriceCache) -> Self {
+        Self {
+            daily,
+            hourly,
+            fallback: Vec::new(),
+        }
+    }
+
+    /// Daily price for `date`
+    fn lookup(&mut self, ledger: &'static str, date: &str, reference: &str, amount_sol: f64) -> (f64, PriceSource) {
+        self.lookup_at(ledger, date, None, reference, amount_sol)
+    }
+
+    /// Nearest hourly price to `timestamp` when cached, otherwise the daily price for `date`
+    fn lookup_at(
+        &mut self,
+        ledger: &'static str,
+        date: &str,
+        timestamp: Option<i64>,
+        reference: &str,
+        amount_sol: f64,
+    ) -> (f64, PriceSource) {
+        let (price, source) = get_event_price(self.daily, self.hourly, date, timestamp);
+        if source == PriceSource::Fallback {
+            self.fallback.push(FallbackPricedRow {
+                ledger,
+                date: date.to_string(),
+                reference: reference.to_string(),
+                amount_sol,
+                price,
+            });
+        }
+        (price, source)
+    }
+}
+
+/// Generate all CSV reports, each stamped with `metadata`
+///
+/// Returns the number of ledger rows valued with the fallback price.
+pub fn generate_all_reports(
+    output_dir: &Path,
+    data: &ReportData,
+    period: Option<&ReportPeriod>,
+    metadata: &ReportMetadata,
+) -> Result<usize> {
+    let mut priced = PricedRows::new(data.prices, data.hourly_prices);
     generate_income_ledger(
         output_dir,
         data.rewards,
@@ -40,30 +135,65 @@ pub fn generate_all_reports(output_dir: &Path, data: &ReportData, year_filter: O
         data.mev_claims,
         data.bam_claims,
         data.leader_fees,
-        data.prices,
+        &data.config.key_changes(),
+        &mut priced,
     )?;
     generate_expense_ledger(
         output_dir,
         data.expenses,
         data.vote_costs,
         data.doublezero_fees,
-        data.prices,
         data.config,
+        &mut priced,
     )?;
-    generate_treasury_ledger(output_dir, data.categorized, data.prices)?;
-    generate_summary(output_dir, data, year_filter)?;
-    generate_glossary(output_dir)?;
-    html_report::generate_html_report(output_dir, data, year_filter)?;
+    generate_treasury_ledger(output_dir, data.categorized, data.config, &mut priced)?;
+    generate_summary(output_dir, data, period)?;
+    generate_glossary(output_dir, data.config)?;
+    if !data.network_fee_medians.is_empty() {
+        let uplift = client_uplift::compare(data.leader_fees, data.mev_claims, data.network_fee_medians);
+        client_uplift::generate_csv(output_dir, &uplift)?;
+    }
+    epoch_pnl::generate_csv(output_dir, &epoch_pnl::compute(data))?;
+    unit_economics::generate_csv(output_dir, &unit_economics::by_month(data))?;
+    revenue_stats::generate_csv(
+        output_dir,
+        &revenue_stats::compute(data, |d| period.is_none_or(|p| p.contains(d))),
+    )?;
+    if !data.delegator_stake.is_empty() {
+        delegators::generate_csv(output_dir, &delegators::analyze(data.delegator_stake))?;
+        let sfdp_rows = sfdp::compute(data);
+        if !sfdp_rows.is_empty() {
+            sfdp::generate_csv(output_dir, &sfdp_rows)?;
+        }
+    }
+    if !data.config.entities.is_empty() {
+        let ledger = entities::inter_entity_ledger(
+            data.config,
+            data.expenses,
+            data.categorized,
+            data.prices,
+            data.hourly_prices,
+        );
+        entities::generate_reports(output_dir, data.config, data.expenses, &ledger, period)?;
+    }
+    if let Some(fiat_config) = &data.config.fiat {
+        let statement = fiat::statement(fiat_config, data.expenses, data.fiat_deposits, timezone::today());
+        fiat::generate_csv(output_dir, &statement)?;
+    }
+    generate_price_warnings(output_dir, &priced.fallback)?;
+    validation::generate_csv(output_dir, data.exceptions)?;
+    html_report::generate_html_report(output_dir, data, period, metadata)?;
+    report_metadata::stamp_csv_reports(output_dir, metadata)?;
 
     // Older versions generated a separate glossary/data-dictionary CSV. Remove it to
     // avoid accidentally sharing stale context alongside the ledgers.
     let _ = std::fs::remove_file(output_dir.join("report_context.csv"));
 
-    Ok(())
+    Ok(priced.fallback.len())
 }
 
 /// Generate glossary.csv (accountant-oriented data dictionary)
-fn generate_glossary(output_dir: &Path) -> Result<()> {
+fn generate_glossary(output_dir: &Path, config: &Config) -> Result<()> {
     let path = output_dir.join(constants::GLOSSARY_FILENAME);
     let mut wtr = Writer::from_path(&path)?;
 
@@ -181,6 +311,26 @@ fn generate_glossary(output_dir: &Path) -> Result<()> {
         "Computed by this tool from BAM SOL-equivalent and daily SOL USD price (CoinGecko, cached).",
         "Because BAM is paid in jitoSOL, confirm whether you need the jitoSOL spot USD price at receipt instead of a SOL-equivalent proxy.",
     )?;
+    row(
+        "donations_sol",
+        "Donations",
+        "revenue",
+        "SOL",
+        "Tips sent to the validator's public donation address (e.g. via the website's Solana Pay QR code).",
+        "Small, irregular income that is not tied to validator performance.",
+        "On-chain SOL transfers into the configured `donation_address` (RPC transaction scan; cached locally).",
+        "Gifts to a business are usually still taxable income; confirm treatment with your accountant.",
+    )?;
+    row(
+        "donations_usd",
+        "Donations (USD valuation)",
+        "revenue",
+        "USD",
+        "USD valuation of donations at the selected daily SOL price.",
+        "Used for USD books/tax reporting of donation income.",
+        "Computed by this tool from donation SOL and daily SOL USD price (CoinGecko, cached).",
+        "Valued like other SOL income received on the same day.",
+    )?;
 
     // Pricing/valuation mechanics used throughout the CSVs
     row(
@@ -190,9 +340,19 @@ fn generate_glossary(output_dir: &Path) -> Result<()> {
         "USD per SOL",
         "Daily SOL USD price used to value SOL-denominated amounts.",
         "Drives USD revenue/expense totals and tax reporting values if you rely on this output.",
-        "CoinGecko daily price (UTC), cached locally; falls back to a fixed price if API fails.",
+        "CoinGecko daily price keyed by day in the accounting timezone, cached locally; nearest cached day if a date is missing, fixed fallback price only if nothing is cached.",
         "If you must use a different pricing policy (spot at receipt time, different provider, local timezone), revalue externally and use this report for SOL-denominated quantities and traceability.",
     )?;
+    row(
+        "price_source",
+        "Price source",
+        "metadata",
+        "",
+        "How the SOL USD price on a row was obtained: hourly (nearest hour to the block time, large withdrawals and BAM claims only), exact (cached price for that day), interpolated (nearest cached day), or fallback (fixed placeholder price).",
+        "Interpolated and fallback rows carry soft USD values that may need revaluation.",
+        "Computed by this tool at report time from the local price cache.",
+        "Every fallback-priced row is also listed in price_warnings.csv. Ask for a price backfill and regenerated reports before relying on those USD values.",
+    )?;
     row(
         "usd_value",
         "USD value (valuation)",
@@ -348,7 +508,7 @@ fn generate_glossary(output_dir: &Path) -> Result<()> {
         "USD",
         "Sum of revenue streams for the period in USD.",
         "Top-line measure for P&L reporting.",
-        "Computed by this tool (commission + leader fees + MEV tips + BAM), valued at daily SOL USD prices.",
+        "Computed by this tool (commission + leader fees + MEV tips + BAM + donations), valued at daily SOL USD prices.",
         "If you revalue items with a different pricing policy, recompute totals externally.",
     )?;
     row(
@@ -373,6 +533,20 @@ fn generate_glossary(output_dir: &Path) -> Result<()> {
     )?;
 
     // Helpful meta fields that appear in ledgers
+    let timezone_definition = format!(
+        "All dates in these reports are calendar dates in {}: block timestamps for rewards, transfers and prices are converted to this timezone before taking the date.",
+        config.accounting_timezone.name()
+    );
+    row(
+        "accounting_timezone",
+        "Accounting timezone",
+        "assumption",
+        "",
+        &timezone_definition,
+        "Events near midnight (and month/year ends) can land on a different day, month or tax year depending on timezone.",
+        "config.toml [reporting] timezone (default UTC).",
+        "Confirm this matches the timezone your books are kept in. Changing it re-dates cached rows on the next run.",
+    )?;
     row(
         "epoch",
         "Epoch (Solana)",
@@ -413,15 +587,33 @@ fn generate_glossary(output_dir: &Path) -> Result<()> {
         "",
         "Which on-chain accounts are considered 'in scope' for this validator's books (vote/identity/withdraw authority and any configured personal wallet used for seeding/flows).",
         "Determines whether transfers are treated as internal movements vs external (potential distributions, contributions, etc.).",
-        "config.toml validator addresses (vote_account, identity, withdraw_authority, personal_wallet/personal_wallets) plus derived token accounts (ATAs) where applicable.",
-        "Confirm which wallets legally belong to the reporting entity. If a personal wallet is mixed-use, treasury transfers may require manual classification (owner distribution vs business transfer).",
+        "config.toml validator addresses (vote_account, identity, withdraw_authority, personal_wallet/personal_wallets, business_wallets), retired keys within their dates ([[validator.retired_keys]]), the Squads multisig vault if [squads] is configured, plus derived token accounts (ATAs) where applicable.",
+        "Confirm which wallets legally belong to the reporting entity. If a personal wallet is mixed-use, treasury transfers may require manual classification (owner distribution vs business transfer); record those with `split set`, which splits a withdrawal into an owner draw and a reimbursed business expense. For a Squads multisig the vault is the business account; members who propose or execute only pay fees.",
     )?;
+    let key_changes = config.key_changes();
+    if !key_changes.is_empty() {
+        let definition = key_changes
+            .iter()
+            .map(KeyChange::describe)
+            .collect::<Vec<_>>()
+            .join("; ");
+        row(
+            "key_changes",
+            "Key rotations and vote account migrations",
+            "metadata",
+            "",
+            &definition,
+            "Rewards, fees and transfers before each date are on the old key; the books treat both keys as the same validator.",
+            "config.toml [[validator.retired_keys]].",
+            "Rewards and fees from both sides of a change are merged per epoch (income_ledger.csv notes the first epoch after a vote account migration). Balances left on an old key after its last day are not counted in positions.",
+        )?;
+    }
     row(
         "treasury_transfer_types",
         "Treasury transfer types",
         "metadata",
         "",
-        "High-level labels used in treasury_ledger.csv: Capital Contribution, Internal Transfer, Prepayment, Withdrawal, Other.",
+        "High-level labels used in treasury_ledger.csv: Capital Contribution, Internal Transfer, Prepayment, Withdrawal, Owner Draw, Reimbursed Expense, Other.",
         "Prevents treating balance sheet movements as revenue/expense.",
         "Generated by this tool based on known addresses and transfer direction.",
         "Withdrawals are not automatically expenses; they may represent owner distributions or moving funds to an exchange. Review and reclassify as needed.",
@@ -448,10 +640,10 @@ fn generate_glossary(output_dir: &Path) -> Result<()> {
     )?;
     row(
         "year_filter_behavior",
-        "Year filter behavior (--year)",
+        "Period filter behavior (--year / --period)",
         "assumption",
         "",
-        "The --year flag filters summary.csv and the printed console summary; the ledgers are not currently year-filtered.",
+        "The --year and --period flags filter summary.csv and the printed console summary; the ledgers are not currently period-filtered. --year means the fiscal year ending in that year when reporting.fiscal_year_start_month is set, and summary YTD and annual totals reset at the fiscal year start.",
         "If you hand the accountant only a single year's summary but the ledgers include multiple years, it can look inconsistent.",
         "validator-accounting CLI behavior.",
         "If you want year-filtered ledgers, we can implement it so all CSVs align to the same period.",
@@ -508,6 +700,45 @@ fn generate_glossary(output_dir: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Generate price_warnings.csv listing every ledger row valued with the fallback price
+fn generate_price_warnings(output_dir: &Path, rows: &[FallbackPricedRow]) -> Result<()> {
+    let path = output_dir.join(constants::PRICE_WARNINGS_FILENAME);
+    let mut wtr = Writer::from_path(&path)?;
+
+    wtr.write_record([
+        "Ledger",
+        "Date (YYYY-MM-DD)",
+        "Reference (epoch-N or tx id)",
+        "Amount_SOL (SOL, Solana cryptocurrency)",
+        "USD_Price_Used (fallback, USD per 1 SOL)",
+    ])?;
+
+    for row in rows {
+        wtr.write_record([
+            row.ledger,
+            &row.date,
+            &row.reference,
+            &format!("{:.6}", row.amount_sol),
+            &format!("{:.2}", row.price),
+        ])?;
+    }
+
+    wtr.flush()?;
+    println!("  Generated: {}", path.display());
+
+    if !rows.is_empty() {
+        eprintln!(
+            "  Warning: {} ledger row(s) valued with the fallback price of ${:.2}/SOL; their USD values are soft (see {}).",
+            rows.len(),
+            constants::FALLBACK_SOL_PRICE,
+            constants::PRICE_WARNINGS_FILENAME
+        );
+        eprintln!("    Run 'validator-accounting prices backfill --from <date>' and 'prices revalue' to fix.");
+    }
+
+    Ok(())
+}
+
 /// Generate income_ledger.csv
 fn generate_income_ledger(
     output_dir: &Path,
@@ -516,7 +747,8 @@ fn generate_income_ledger(
     mev_claims: &[MevClaim],
     bam_claims: &[BamClaim],
     leader_fees: &[EpochLeaderFees],
-    prices: &PriceCache,
+    key_changes: &[KeyChange],
+    priced: &mut PricedRows,
 ) -> Result<()> {
     let path = output_dir.join(constants::INCOME_LEDGER_FILENAME);
     let mut wtr = Writer::from_path(&path)?;
@@ -531,15 +763,30 @@ fn generate_income_ledger(
         "From_Label (who/what is it?)",
         "Amount_SOL (SOL, Solana cryptocurrency)",
         "USD_Price (USD per 1 SOL)",
+        "Price_Source (hourly/exact/interpolated/fallback)",
         "USD_Value (Amount_SOL * USD_Price)",
         "Tx_Signature (tx id or epoch-N)",
         "Notes (plain English)",
     ])?;
 
+    // The first reward epoch after a vote account migration notes the hand-over
+    let mut migration_notes: HashMap<u64, String> = HashMap::new();
+    for change in key_changes.iter().filter(|c| c.role == KeyRole::VoteAccount) {
+        if let Some(epoch) = rewards
+            .iter()
+            .filter(|r| r.date.as_deref().is_some_and(|d| d >= change.date.as_str()))
+            .map(|r| r.epoch)
+            .min()
+        {
+            migration_notes.insert(epoch, change.describe());
+        }
+    }
+
     // Commission rewards
     for reward in rewards {
         let date = reward.date.as_deref().unwrap_or("unknown");
-        let price = get_price(prices, date);
+        let (price, price_source) =
+            priced.lookup("income", date, &format!("epoch-{}", reward.epoch), reward.amount_sol);
         let usd_value = reward.amount_sol * price;
 
         wtr.write_record([
@@ -551,12 +798,19 @@ fn generate_income_ledger(
             "Staking inflation reward (to validator vote account)",
             &format!("{:.6}", reward.amount_sol),
             &format!("{:.2}", price),
+            price_source.as_str(),
             &format!("{:.2}", usd_value),
             &format!("epoch-{}", reward.epoch),
-            &format!(
-                "Staking reward payout. Validator keeps {}% commission from delegated stake rewards.",
-                reward.commission
-            ),
+            &match migration_notes.get(&reward.epoch) {
+                Some(migration) => format!(
+                    "Staking reward payout. Validator keeps {}% commission from delegated stake rewards. {}; rewards from both vote accounts are one continuous history.",
+                    reward.commission, migration
+                ),
+                None => format!(
+                    "Staking reward payout. Validator keeps {}% commission from delegated stake rewards.",
+                    reward.commission
+                ),
+            },
         ])?;
     }
 
@@ -577,7 +831,7 @@ fn generate_income_ledger(
         }
 
         let date = transfer.date.as_deref().unwrap_or("unknown");
-        let price = get_price(prices, date);
+        let (price, price_source) = priced.lookup("income", date, &transfer.signature, transfer.amount_sol);
         let usd_value = transfer.amount_sol * price;
 
         wtr.write_record([
@@ -589,45 +843,55 @@ fn generate_income_ledger(
             &transfer.from_label,
             &format!("{:.6}", transfer.amount_sol),
             &format!("{:.2}", price),
+            price_source.as_str(),
             &format!("{:.2}", usd_value),
             &transfer.signature[..16],
             "Extra validator income from optional 'tips' paid via Jito (often for transaction priority). Fallback row: inferred from on-chain transfer (no per-epoch API claim data).",
         ])?;
     }
 
-    // MEV claims from Jito API (primary source)
+    // MEV claims per provider (Jito API, plus any scanned tip-distribution programs)
     for claim in mev_claims {
         let date = claim.date.as_deref().unwrap_or("unknown");
-        let price = get_price(prices, date);
+        let reference = claim.reference();
+        let (price, price_source) = priced.lookup("income", date, &reference, claim.amount_sol);
         let usd_value = claim.amount_sol * price;
+        let provider = claim.provider_label();
+        let notes = if claim.total_tips_lamports > 0 {
+            format!(
+                "Extra validator income from optional 'tips' paid via {} (often for transaction priority). Validator received ~{}% of {:.4} SOL of tips for this epoch.",
+                provider,
+                (claim.commission_lamports as f64 / claim.total_tips_lamports as f64 * 100.0).round() as u64,
+                claim.total_tips_lamports as f64 / 1e9
+            )
+        } else {
+            format!(
+                "Extra validator income from optional 'tips' paid via {} (often for transaction priority). Amount is what the validator received from the tip distribution account for this epoch.",
+                provider
+            )
+        };
 
         wtr.write_record([
             date,
             &claim.epoch.to_string(),
             "Income (Revenue)",
-            "MEV tips (Jito)",
-            "Jito tip distribution",
+            &format!("MEV tips ({})", provider),
+            &format!("{} tip distribution", provider),
             "MEV tip payout (to validator vote account)",
             &format!("{:.6}", claim.amount_sol),
             &format!("{:.2}", price),
+            price_source.as_str(),
             &format!("{:.2}", usd_value),
-            &format!("epoch-{}", claim.epoch),
-            &format!(
-                "Extra validator income from optional 'tips' paid via Jito (often for transaction priority). Validator received ~{}% of {:.4} SOL of tips for this epoch.",
-                if claim.total_tips_lamports > 0 {
-                    (claim.commission_lamports as f64 / claim.total_tips_lamports as f64 * 100.0).round() as u64
-                } else {
-                    0
-                },
-                claim.total_tips_lamports as f64 / 1e9
-            ),
+            &reference,
+            &notes,
         ])?;
     }
 
     // Leader slot fees (block production rewards)
     for fees in leader_fees {
         let date = fees.date.as_deref().unwrap_or("unknown");
-        let price = get_price(prices, date);
+        let (price, price_source) =
+            priced.lookup("income", date, &format!("epoch-{}", fees.epoch), fees.total_fees_sol);
         let usd_value = fees.total_fees_sol * price;
 
         wtr.write_record([
@@ -639,6 +903,7 @@ fn generate_income_ledger(
             "Transaction fees earned for producing blocks",
             &format!("{:.6}", fees.total_fees_sol),
             &format!("{:.2}", price),
+            price_source.as_str(),
             &format!("{:.2}", usd_value),
             &format!("epoch-{}", fees.epoch),
             &format!(
@@ -651,7 +916,13 @@ fn generate_income_ledger(
     // BAM claims (jitoSOL rewards per JIP-31)
     for claim in bam_claims {
         let date = claim.date.as_deref().unwrap_or("unknown");
-        let price = get_price(prices, date);
+        let (price, price_source) = priced.lookup_at(
+            "income",
+            date,
+            claim.claimed_at_timestamp(),
+            &claim.tx_signature,
+            claim.amount_sol_equivalent,
+        );
         // Use the SOL-equivalent value for USD calculation
         let usd_value = claim.amount_sol_equivalent * price;
         let jitosol_amount = claim.amount_jitosol_lamports as f64 / 1e9;
@@ -665,6 +936,7 @@ fn generate_income_ledger(
             "jitoSOL reward payout (to validator token account)",
             &format!("{:.6}", claim.amount_sol_equivalent),
             &format!("{:.2}", price),
+            price_source.as_str(),
             &format!("{:.2}", usd_value),
             &claim.tx_signature[..claim.tx_signature.len().min(16)],
             &format!(
@@ -675,6 +947,28 @@ fn generate_income_ledger(
         ])?;
     }
 
+    // Donations (tips to the donation address, e.g. via the website's Solana Pay QR code)
+    for transfer in &categorized.donations {
+        let date = transfer.date.as_deref().unwrap_or("unknown");
+        let (price, price_source) = priced.lookup("income", date, &transfer.signature, transfer.amount_sol);
+        let usd_value = transfer.amount_sol * price;
+
+        wtr.write_record([
+            date,
+            "",
+            "Income (Revenue)",
+            "Donations",
+            &transfer.from.to_string(),
+            &transfer.from_label,
+            &format!("{:.6}", transfer.amount_sol),
+            &format!("{:.2}", price),
+            price_source.as_str(),
+            &format!("{:.2}", usd_value),
+            &transfer.signature[..transfer.signature.len().min(16)],
+            &treasury_note("Tip sent to the validator's public donation address.", transfer),
+        ])?;
+    }
+
     wtr.flush()?;
     println!("  Generated: {}", path.display());
 
@@ -687,8 +981,8 @@ fn generate_expense_ledger(
     expenses: &[Expense],
     vote_costs: &[EpochVoteCost],
     doublezero_fees: &[DoubleZeroFee],
-    prices: &PriceCache,
     config: &Config,
+    priced: &mut PricedRows,
 ) -> Result<()> {
     let path = output_dir.join(constants::EXPENSE_LEDGER_FILENAME);
     let mut wtr = Writer::from_path(&path)?;
@@ -703,6 +997,7 @@ fn generate_expense_ledger(
         "Description (plain English)",
         "Amount_SOL (SOL, Solana cryptocurrency)",
         "Amount_USD (gross valuation on Date)",
+        "Price_Source (exact/interpolated/fallback; blank if paid in USD)",
         "Paid_With (asset)",
         "SFDP_Coverage (% of vote fees reimbursed by Solana Foundation program)",
         "Net_Amount_USD (gross * (1 - coverage))",
@@ -712,7 +1007,8 @@ fn generate_expense_ledger(
     // Vote costs per epoch (actual on-chain data)
     for cost in vote_costs {
         let date = cost.date.as_deref().unwrap_or("unknown");
-        let price = get_price(prices, date);
+        let (price, price_source) =
+            priced.lookup("expense", date, &format!("epoch-{}", cost.epoch), cost.total_fee_sol);
         let gross_usd = cost.total_fee_sol * price;
 
         // Calculate SFDP coverage for this epoch's date
@@ -733,6 +1029,7 @@ fn generate_expense_ledger(
             ),
             &format!("{:.6}", cost.total_fee_sol),
             &format!("{:.2}", gross_usd),
+            price_source.as_str(),
             "SOL",
             &format!("{:.0}%", coverage * 100.0),
             &format!("{:.2}", net_usd),
@@ -743,7 +1040,7 @@ fn generate_expense_ledger(
     // DoubleZero fees (block reward sharing)
     for fee in doublezero_fees {
         let date = fee.date.as_deref().unwrap_or("unknown");
-        let price = get_price(prices, date);
+        let (price, price_source) = priced.lookup("expense", date, &format!("epoch-{}", fee.epoch), fee.liability_sol);
         let usd_value = fee.liability_sol * price;
         let fee_base_sol = fee.fee_base_lamports as f64 / 1e9;
         let rate_percent = fee.fee_rate_bps as f64 / 100.0;
@@ -761,6 +1058,7 @@ fn generate_expense_ledger(
             ),
             &format!("{:.6}", fee.liability_sol),
             &format!("{:.2}", usd_value),
+            price_source.as_str(),
             "SOL",
             "",
             &format!("{:.2}", usd_value),
@@ -780,6 +1078,7 @@ fn generate_expense_ledger(
             &expense.description,
             "", // No SOL amount
             &format!("{:.2}", expense_usd),
+            "", // Already denominated in USD
             &expense.paid_with,
             "", // No SFDP coverage for off-chain expenses
             &format!("{:.2}", expense_usd),
@@ -794,7 +1093,12 @@ fn generate_expense_ledger(
 }
 
 /// Generate treasury_ledger.csv (transfers, seeding, withdrawals)
-fn generate_treasury_ledger(output_dir: &Path, categorized: &CategorizedTransfers, prices: &PriceCache) -> Result<()> {
+fn generate_treasury_ledger(
+    output_dir: &Path,
+    categorized: &CategorizedTransfers,
+    config: &Config,
+    priced: &mut PricedRows,
+) -> Result<()> {
     let path = output_dir.join(constants::TREASURY_LEDGER_FILENAME);
     let mut wtr = Writer::from_path(&path)?;
 
@@ -809,6 +1113,7 @@ fn generate_treasury_ledger(output_dir: &Path, categorized: &CategorizedTransfer
         "Accounting_Treatment (Income/Expense/Balance Sheet)",
         "Amount_SOL (SOL, Solana cryptocurrency)",
         "USD_Value (valuation on Date)",
+        "Price_Source (hourly/exact/interpolated/fallback)",
         "Tx_Signature (tx id)",
         "Notes (plain English)",
     ])?;
@@ -816,7 +1121,7 @@ fn generate_treasury_ledger(output_dir: &Path, categorized: &CategorizedTransfer
     // Initial seeding
     for transfer in &categorized.seeding {
         let date = transfer.date.as_deref().unwrap_or("unknown");
-        let price = get_price(prices, date);
+        let (price, price_source) = priced.lookup("treasury", date, &transfer.signature, transfer.amount_sol);
         let usd_value = transfer.amount_sol * price;
 
         wtr.write_record([
@@ -829,15 +1134,19 @@ fn generate_treasury_ledger(output_dir: &Path, categorized: &CategorizedTransfer
             "Balance Sheet (Owner contribution)",
             &format!("{:.6}", transfer.amount_sol),
             &format!("{:.2}", usd_value),
+            price_source.as_str(),
             &transfer.signature[..16],
-            "Owner capital contribution to fund validator operations (balance sheet movement, not income).",
+            &treasury_note(
+                "Owner capital contribution to fund validator operations (balance sheet movement, not income).",
+                transfer,
+            ),
         ])?;
     }
 
     // Vote funding (internal transfers)
     for transfer in &categorized.vote_funding {
         let date = transfer.date.as_deref().unwrap_or("unknown");
-        let price = get_price(prices, date);
+        let (price, price_source) = priced.lookup("treasury", date, &transfer.signature, transfer.amount_sol);
         let usd_value = transfer.amount_sol * price;
 
         wtr.write_record([
@@ -850,15 +1159,19 @@ fn generate_treasury_ledger(output_dir: &Path, categorized: &CategorizedTransfer
             "Balance Sheet (Internal transfer)",
             &format!("{:.6}", transfer.amount_sol),
             &format!("{:.2}", usd_value),
+            price_source.as_str(),
             &transfer.signature[..16],
-            "Move funds between internal validator wallets to pay on-chain transaction fees (not income).",
+            &treasury_note(
+                "Move funds between internal validator wallets to pay on-chain transaction fees (not income).",
+                transfer,
+            ),
         ])?;
     }
 
     // DoubleZero payments (prepaid network fees)
     for transfer in &categorized.doublezero_payments {
         let date = transfer.date.as_deref().unwrap_or("unknown");
-        let price = get_price(prices, date);
+        let (price, price_source) = priced.lookup("treasury", date, &transfer.signature, transfer.amount_sol);
         let usd_value = transfer.amount_sol * price;
 
         wtr.write_record([
@@ -871,17 +1184,78 @@ fn generate_treasury_ledger(output_dir: &Path, categorized: &CategorizedTransfer
             "Balance Sheet (Prepayment/deposit)",
             &format!("{:.6}", transfer.amount_sol),
             &format!("{:.2}", usd_value),
+            price_source.as_str(),
             &transfer.signature[..16],
-            "Deposit to DoubleZero to prepay network fee obligations (balance sheet movement; expense recorded as fees accrue).",
+            &treasury_note(
+                "Deposit to DoubleZero to prepay network fee obligations (balance sheet movement; expense recorded as fees accrue).",
+                transfer,
+            ),
         ])?;
     }
 
     // Withdrawals
     for transfer in &categorized.withdrawals {
         let date = transfer.date.as_deref().unwrap_or("unknown");
-        let price = get_price(prices, date);
+        let (price, price_source) = priced.lookup_at(
+            "treasury",
+            date,
+            transfer.timestamp,
+            &transfer.signature,
+            transfer.amount_sol,
+        );
         let usd_value = transfer.amount_sol * price;
 
+        // Split withdrawals: one row per share (the business share is also in the expense ledger)
+        if let Some(split) = categorized.split_for(&transfer.signature) {
+            let draw_sol = split.draw_sol(transfer);
+            let business_sol = split.business_sol(transfer);
+            if draw_sol > 0.0 {
+                wtr.write_record([
+                    date,
+                    "Owner Draw",
+                    &transfer.from.to_string(),
+                    &transfer.from_label,
+                    &transfer.to.to_string(),
+                    &transfer.to_label,
+                    "Balance Sheet (Owner distribution)",
+                    &format!("{:.6}", draw_sol),
+                    &format!("{:.2}", draw_sol * price),
+                    price_source.as_str(),
+                    &transfer.signature[..16],
+                    &treasury_note(
+                        &format!(
+                            "Owner draw share ({:.0}%) of a mixed-use withdrawal to the personal wallet.",
+                            100.0 - split.business_pct
+                        ),
+                        transfer,
+                    ),
+                ])?;
+            }
+            if business_sol > 0.0 {
+                wtr.write_record([
+                    date,
+                    "Reimbursed Expense",
+                    &transfer.from.to_string(),
+                    &transfer.from_label,
+                    &transfer.to.to_string(),
+                    &transfer.to_label,
+                    &format!("Expense ({})", split.category),
+                    &format!("{:.6}", business_sol),
+                    &format!("{:.2}", business_sol * price),
+                    price_source.as_str(),
+                    &transfer.signature[..16],
+                    &treasury_note(
+                        &format!(
+                            "Business share ({:.0}%) of a mixed-use withdrawal that paid a business cost: {}. Listed in the expense ledger.",
+                            split.business_pct, split.description
+                        ),
+                        transfer,
+                    ),
+                ])?;
+            }
+            continue;
+        }
+
         wtr.write_record([
             date,
             "Withdrawal",
@@ -892,15 +1266,52 @@ fn generate_treasury_ledger(output_dir: &Path, categorized: &CategorizedTransfer
             "Balance Sheet (Transfer out)",
             &format!("{:.6}", transfer.amount_sol),
             &format!("{:.2}", usd_value),
+            price_source.as_str(),
             &transfer.signature[..16],
-            "Transfer out to exchange/personal wallet (owner distribution or asset movement; not automatically income/expense).",
+            &treasury_note(
+                "Transfer out to exchange/personal wallet (owner distribution or asset movement; not automatically income/expense).",
+                transfer,
+            ),
+        ])?;
+    }
+
+    // Rent-exempt deposits for new accounts and refunds from closed ones
+    for transfer in &categorized.rent {
+        let date = transfer.date.as_deref().unwrap_or("unknown");
+        let (price, price_source) = priced.lookup("treasury", date, &transfer.signature, transfer.amount_sol);
+        let usd_value = transfer.amount_sol * price;
+        let (entry_type, note) = if config.is_our_account_on(&transfer.from, transfer.date.as_deref()) {
+            (
+                "Rent Deposit",
+                "Rent-exempt reserve funding a new stake or token account (refundable deposit; balance sheet, not an expense).",
+            )
+        } else {
+            (
+                "Rent Refund",
+                "Rent-exempt reserve returned when a token account was closed (return of a deposit; not income).",
+            )
+        };
+
+        wtr.write_record([
+            date,
+            entry_type,
+            &transfer.from.to_string(),
+            &transfer.from_label,
+            &transfer.to.to_string(),
+            &transfer.to_label,
+            "Balance Sheet (Rent-exempt deposit)",
+            &format!("{:.6}", transfer.amount_sol),
+            &format!("{:.2}", usd_value),
+            price_source.as_str(),
+            &transfer.signature[..16],
+            &treasury_note(note, transfer),
         ])?;
     }
 
     // Other transfers
     for transfer in &categorized.other {
         let date = transfer.date.as_deref().unwrap_or("unknown");
-        let price = get_price(prices, date);
+        let (price, price_source) = priced.lookup("treasury", date, &transfer.signature, transfer.amount_sol);
         let usd_value = transfer.amount_sol * price;
 
         wtr.write_record([
@@ -913,8 +1324,12 @@ fn generate_treasury_ledger(output_dir: &Path, categorized: &CategorizedTransfer
             "Balance Sheet (Transfer)",
             &format!("{:.6}", transfer.amount_sol),
             &format!("{:.2}", usd_value),
+            price_source.as_str(),
             &transfer.signature[..16],
-            "Uncategorized transfer (typically a balance sheet movement, not P&L).",
+            &treasury_note(
+                "Uncategorized transfer (typically a balance sheet movement, not P&L).",
+                transfer,
+            ),
         ])?;
     }
 
@@ -924,8 +1339,35 @@ fn generate_treasury_ledger(output_dir: &Path, categorized: &CategorizedTransfer
     Ok(())
 }
 
+/// Treasury ledger note: the standard explanation plus the transfer's memo, which label source
+/// identified the counterparty and, for transfers made by a program other than the System
+/// Program, which program moved the SOL
+fn treasury_note(note: &str, transfer: &SolTransfer) -> String {
+    let mut note = note.to_string();
+    if transfer.program.as_deref().is_some_and(squads::is_squads_program) {
+        note.push_str(" Executed via Squads multisig proposal.");
+    } else if let Some(program) = transfer.program.as_deref()
+        && let Ok(program_id) = solana_sdk::pubkey::Pubkey::from_str(program)
+    {
+        let label = addresses::get_label(&program_id);
+        if label.category != AddressCategory::SystemProgram {
+            note.push_str(&format!(" Sent via program: {}.", label.name));
+        }
+    }
+    if let Some((name, source)) = [&transfer.from, &transfer.to].into_iter().find_map(|pubkey| {
+        let label = addresses::get_label(pubkey);
+        label.source.map(|source| (label.name, source))
+    }) {
+        note.push_str(&format!(" Counterparty \"{}\" per {}.", name, source));
+    }
+    if let Some(memo) = &transfer.memo {
+        note.push_str(&format!(" Memo: \"{}\".", memo));
+    }
+    note
+}
+
 /// Generate summary.csv (monthly P&L with annual summaries)
-fn generate_summary(output_dir: &Path, data: &ReportData, year_filter: Option<i32>) -> Result<()> {
+fn generate_summary(output_dir: &Path, data: &ReportData, period: Option<&ReportPeriod>) -> Result<()> {
     let path = output_dir.join(constants::SUMMARY_FILENAME);
     let mut wtr = Writer::from_path(&path)?;
 
@@ -933,14 +1375,10 @@ fn generate_summary(output_dir: &Path, data: &ReportData, year_filter: Option<i3
     let mut monthly: HashMap<String, MonthlyData> = HashMap::new();
 
     // Commission
-    for reward in data.rewards {
-        if let Some(date) = &reward.date {
-            let month = &date[..7];
-            let price = get_price(data.prices, date);
-            let entry = monthly.entry(month.to_string()).or_default();
-            entry.commission_sol += reward.amount_sol;
-            entry.commission_usd += reward.amount_sol * price;
-        }
+    for (month, (sol, usd)) in data.aggregates.commission.valued(data.prices) {
+        let entry = monthly.entry(month).or_default();
+        entry.commission_sol += sol;
+        entry.commission_usd += usd;
     }
 
     // SFDP reimbursements
@@ -969,14 +1407,10 @@ fn generate_summary(output_dir: &Path, data: &ReportData, year_filter: Option<i3
         }
     } else {
         // Primary: use Jito API data (per-epoch, accurate)
-        for claim in data.mev_claims {
-            if let Some(date) = &claim.date {
-                let month = &date[..7];
-                let price = get_price(data.prices, date);
-                let entry = monthly.entry(month.to_string()).or_default();
-                entry.mev_sol += claim.amount_sol;
-                entry.mev_usd += claim.amount_sol * price;
-            }
+        for (month, (sol, usd)) in data.aggregates.mev_claims.valued(data.prices) {
+            let entry = monthly.entry(month).or_default();
+            entry.mev_sol += sol;
+            entry.mev_usd += usd;
         }
     }
 
@@ -984,7 +1418,7 @@ fn generate_summary(output_dir: &Path, data: &ReportData, year_filter: Option<i3
     for claim in data.bam_claims {
         if let Some(date) = &claim.date {
             let month = &date[..7];
-            let price = get_price(data.prices, date);
+            let (price, _) = get_event_price(data.prices, data.hourly_prices, date, claim.claimed_at_timestamp());
             let entry = monthly.entry(month.to_string()).or_default();
             entry.bam_sol += claim.amount_sol_equivalent;
             entry.bam_usd += claim.amount_sol_equivalent * price;
@@ -992,54 +1426,37 @@ fn generate_summary(output_dir: &Path, data: &ReportData, year_filter: Option<i3
     }
 
     // Leader fees from block production
-    for fees in data.leader_fees {
-        if let Some(date) = &fees.date {
-            if date.len() < 7 {
-                continue;
-            }
-            let month = &date[..7];
-            let price = get_price(data.prices, date);
-            let entry = monthly.entry(month.to_string()).or_default();
-            entry.leader_fees_sol += fees.total_fees_sol;
-            entry.leader_fees_usd += fees.total_fees_sol * price;
-        }
+    for (month, (sol, usd)) in data.aggregates.leader_fees.valued(data.prices) {
+        let entry = monthly.entry(month).or_default();
+        entry.leader_fees_sol += sol;
+        entry.leader_fees_usd += usd;
     }
 
-    // Vote costs by month (with SFDP coverage calculation)
-    for cost in data.vote_costs {
-        if let Some(date) = &cost.date {
-            if date.len() < 7 {
-                continue;
-            }
+    // Donations
+    for transfer in &data.categorized.donations {
+        if let Some(date) = &transfer.date {
             let month = &date[..7];
             let price = get_price(data.prices, date);
-            let gross_usd = cost.total_fee_sol * price;
-
-            // Calculate SFDP coverage for net cost
-            let parsed_date = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
-                .unwrap_or_else(|_| chrono::NaiveDate::parse_from_str(constants::FALLBACK_DATE, "%Y-%m-%d").unwrap());
-            let coverage = data.config.sfdp_coverage_percent(&parsed_date);
-            let net_usd = gross_usd * (1.0 - coverage);
-
             let entry = monthly.entry(month.to_string()).or_default();
-            entry.vote_costs_sol += cost.total_fee_sol;
-            entry.vote_costs_gross_usd += gross_usd;
-            entry.vote_costs_net_usd += net_usd;
+            entry.donations_sol += transfer.amount_sol;
+            entry.donations_usd += transfer.amount_sol * price;
         }
     }
 
+    // Vote costs by month (aggregated per day for the SFDP coverage calculation)
+    for (date, (sol, gross_usd)) in data.aggregates.vote_costs.valued(data.prices) {
+        let net_usd = gross_usd * (1.0 - sfdp_coverage(data.config, &date));
+        let entry = monthly.entry(date[..7].to_string()).or_default();
+        entry.vote_costs_sol += sol;
+        entry.vote_costs_gross_usd += gross_usd;
+        entry.vote_costs_net_usd += net_usd;
+    }
+
     // DoubleZero fees by month
-    for fee in data.doublezero_fees {
-        if let Some(date) = &fee.date {
-            if date.len() < 7 {
-                continue;
-            }
-            let month = &date[..7];
-            let price = get_price(data.prices, date);
-            let entry = monthly.entry(month.to_string()).or_default();
-            entry.doublezero_sol += fee.liability_sol;
-            entry.doublezero_usd += fee.liability_sol * price;
-        }
+    for (month, (sol, usd)) in data.aggregates.doublezero_fees.valued(data.prices) {
+        let entry = monthly.entry(month).or_default();
+        entry.doublezero_sol += sol;
+        entry.doublezero_usd += usd;
     }
 
     // DoubleZero payments by month (prepayments to deposit PDA)
@@ -1076,6 +1493,8 @@ fn generate_summary(output_dir: &Path, data: &ReportData, year_filter: Option<i3
         "MEV_USD (Jito MEV tips, USD)",
         "BAM_SOL (Jito BAM incentives, SOL-equiv)",
         "BAM_USD (Jito BAM incentives, USD)",
+        "Donations_SOL (tips to the donation address, SOL)",
+        "Donations_USD (tips to the donation address, USD)",
         "Total_Revenue_USD (sum of revenue items)",
         "Vote_Costs_SOL (on-chain vote tx fees, SOL)",
         "Vote_Costs_Gross_USD (before SFDP reimbursement)",
@@ -1090,34 +1509,36 @@ fn generate_summary(output_dir: &Path, data: &ReportData, year_filter: Option<i3
         "Other_Expenses_USD (off-chain expenses)",
         "Total_Expenses_USD (vote net + DoubleZero + other)",
         "Net_Profit_USD (revenue - expenses)",
-        "YTD_Profit_USD (resets each Jan)",
+        "YTD_Profit_USD (resets each fiscal year)",
     ])?;
 
     let mut months: Vec<_> = monthly.keys().cloned().collect();
     months.sort();
 
-    // Filter by year if specified
-    let months: Vec<_> = if let Some(year) = year_filter {
-        let year_prefix = format!("{}-", year);
-        months.into_iter().filter(|m| m.starts_with(&year_prefix)).collect()
+    // Filter by period if specified
+    let months: Vec<_> = if let Some(period) = period {
+        months.into_iter().filter(|m| period.contains_month(m)).collect()
     } else {
         months
     };
 
-    // Track annual totals for summary rows
+    // Track annual totals for summary rows (keyed by fiscal year)
+    let fiscal_start_month = data.config.fiscal_year_start_month;
     let mut annual_totals: HashMap<String, MonthlyData> = HashMap::new();
     let mut ytd = 0.0;
     let mut current_year: Option<String> = None;
 
     for month in &months {
-        let year = &month[..4];
+        let year = fiscal_year_label(month, fiscal_start_month);
+        let year = year.as_str();
         let data = &monthly[month];
-        // SFDP is expense offset, not revenue. BAM rewards are revenue.
-        let total_revenue = data.commission_usd + data.leader_fees_usd + data.mev_usd + data.bam_usd;
+        // SFDP is expense offset, not revenue. BAM rewards and donations are revenue.
+        let total_revenue =
+            data.commission_usd + data.leader_fees_usd + data.mev_usd + data.bam_usd + data.donations_usd;
         let total_expenses = data.vote_costs_net_usd + data.doublezero_usd + data.other_expenses_usd;
         let net_profit = total_revenue - total_expenses;
 
-        // Reset YTD at year boundary
+        // Reset YTD at fiscal year boundary
         if current_year.as_deref() != Some(year) {
             current_year = Some(year.to_string());
             ytd = 0.0;
@@ -1134,6 +1555,8 @@ fn generate_summary(output_dir: &Path, data: &ReportData, year_filter: Option<i3
         annual.mev_usd += data.mev_usd;
         annual.bam_sol += data.bam_sol;
         annual.bam_usd += data.bam_usd;
+        annual.donations_sol += data.donations_sol;
+        annual.donations_usd += data.donations_usd;
         annual.sfdp_sol += data.sfdp_sol;
         annual.sfdp_usd += data.sfdp_usd;
         annual.vote_costs_sol += data.vote_costs_sol;
@@ -1159,6 +1582,8 @@ fn generate_summary(output_dir: &Path, data: &ReportData, year_filter: Option<i3
             &format!("{:.2}", data.mev_usd),
             &format!("{:.4}", data.bam_sol),
             &format!("{:.2}", data.bam_usd),
+            &format!("{:.4}", data.donations_sol),
+            &format!("{:.2}", data.donations_usd),
             &format!("{:.2}", total_revenue),
             &format!("{:.4}", data.vote_costs_sol),
             &format!("{:.2}", data.vote_costs_gross_usd),
@@ -1183,8 +1608,9 @@ fn generate_summary(output_dir: &Path, data: &ReportData, year_filter: Option<i3
 
     for year in &years {
         let data = &annual_totals[year];
-        // SFDP is expense offset, not revenue. BAM rewards are revenue.
-        let total_revenue = data.commission_usd + data.leader_fees_usd + data.mev_usd + data.bam_usd;
+        // SFDP is expense offset, not revenue. BAM rewards and donations are revenue.
+        let total_revenue =
+            data.commission_usd + data.leader_fees_usd + data.mev_usd + data.bam_usd + data.donations_usd;
         let total_expenses = data.vote_costs_net_usd + data.doublezero_usd + data.other_expenses_usd;
         let net_profit = total_revenue - total_expenses;
 
@@ -1202,6 +1628,8 @@ fn generate_summary(output_dir: &Path, data: &ReportData, year_filter: Option<i3
             &format!("{:.2}", data.mev_usd),
             &format!("{:.4}", data.bam_sol),
             &format!("{:.2}", data.bam_usd),
+            &format!("{:.4}", data.donations_sol),
+            &format!("{:.2}", data.donations_usd),
             &format!("{:.2}", total_revenue),
             &format!("{:.4}", data.vote_costs_sol),
             &format!("{:.2}", data.vote_costs_gross_usd),
@@ -1236,6 +1664,8 @@ struct MonthlyData {
     mev_usd: f64,
     bam_sol: f64,
     bam_usd: f64,
+    donations_sol: f64,
+    donations_usd: f64,
     sfdp_sol: f64,
     sfdp_usd: f64,
     vote_costs_sol: f64,
@@ -1248,46 +1678,74 @@ struct MonthlyData {
     other_expenses_usd: f64,
 }
 
+/// SFDP vote cost coverage for a "YYYY-MM-DD" date
+fn sfdp_coverage(config: &Config, date: &str) -> f64 {
+    let parsed_date = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .unwrap_or_else(|_| chrono::NaiveDate::parse_from_str(constants::FALLBACK_DATE, "%Y-%m-%d").unwrap());
+    config.sfdp_coverage_percent(&parsed_date)
+}
+
 /// Normalize -0.0 to 0.0 for cleaner display
 fn normalize_zero(val: f64) -> f64 {
     if val == 0.0 { 0.0 } else { val }
 }
 
-/// Print summary to console
-pub fn print_summary(data: &ReportData, year_filter: Option<i32>) {
-    // Helper to check if a date matches the year filter
-    let matches_year = |date: &str| -> bool {
-        if let Some(year) = year_filter {
-            date.starts_with(&format!("{}-", year))
-        } else {
-            true
-        }
+/// Period totals behind the console summary (and `summary --output json`)
+#[derive(Debug, Clone, Serialize)]
+pub struct SummaryTotals {
+    /// Report period label ("2025", "FY2026", ...), or None for all history
+    pub period: Option<String>,
+    /// Accounting timezone the dates were attributed in
+    pub timezone: String,
+    pub commission_sol: f64,
+    pub commission_usd: f64,
+    pub leader_fees_sol: f64,
+    pub leader_fees_usd: f64,
+    pub mev_sol: f64,
+    pub mev_usd: f64,
+    pub bam_sol: f64,
+    pub bam_usd: f64,
+    pub donations_sol: f64,
+    pub donations_usd: f64,
+    pub total_revenue_sol: f64,
+    pub total_revenue_usd: f64,
+    pub vote_costs_sol: f64,
+    pub vote_costs_gross_usd: f64,
+    pub sfdp_offset_usd: f64,
+    pub vote_costs_net_usd: f64,
+    pub doublezero_sol: f64,
+    pub doublezero_usd: f64,
+    pub doublezero_paid_sol: f64,
+    pub doublezero_paid_usd: f64,
+    pub doublezero_outstanding_sol: f64,
+    pub doublezero_outstanding_usd: f64,
+    pub hosting_usd: f64,
+    pub contractor_usd: f64,
+    /// All off-chain expenses (hosting and contractor included)
+    pub offchain_expenses_usd: f64,
+    pub total_expenses_usd: f64,
+    pub net_profit_usd: f64,
+    pub seeding_sol: f64,
+    pub capital_transfer_count: usize,
+    pub unit_economics: report_template::UnitEconomics,
+}
+
+/// Compute the summary totals for a period (all history when `period` is None)
+pub fn compute_summary(data: &ReportData, period: Option<&ReportPeriod>) -> SummaryTotals {
+    // Helper to check if a date falls in the report period
+    let matches_period = |date: &str| -> bool { period.is_none_or(|p| p.contains(date)) };
+
+    // Sum SQL month aggregates inside the period (periods are whole months)
+    let period_total = |amounts: &GroupedAmounts| -> (f64, f64) {
+        amounts
+            .valued(data.prices)
+            .into_iter()
+            .filter(|(month, _)| period.is_none_or(|p| p.contains_month(month)))
+            .fold((0.0, 0.0), |(sol, usd), (_, (s, u))| (sol + s, usd + u))
     };
 
-    println!("\n============================================================");
-    if let Some(year) = year_filter {
-        println!("                FINANCIAL SUMMARY ({})", year);
-    } else {
-        println!("                    FINANCIAL SUMMARY");
-    }
-    println!("============================================================\n");
-
-    // Calculate totals (filtered by year if specified)
-    let total_commission_sol: f64 = data
-        .rewards
-        .iter()
-        .filter(|r| r.date.as_deref().map(&matches_year).unwrap_or(false))
-        .map(|r| r.amount_sol)
-        .sum();
-    let total_commission_usd: f64 = data
-        .rewards
-        .iter()
-        .filter(|r| r.date.as_deref().map(&matches_year).unwrap_or(false))
-        .map(|r| {
-            let price = get_price(data.prices, r.date.as_deref().unwrap_or(constants::FALLBACK_DATE));
-            r.amount_sol * price
-        })
-        .sum();
+    // Calculate totals (filtered by period if specified)
+    let (total_commission_sol, total_commission_usd) = period_total(&data.aggregates.commission);
 
     // MEV: Use Jito API claims as source of truth to avoid double-counting.
     // Only use mev_deposits as fallback when mev_claims is empty.
@@ -1297,14 +1755,14 @@ pub fn print_summary(data: &ReportData, year_filter: Option<i32>) {
             .categorized
             .mev_deposits
             .iter()
-            .filter(|t| t.date.as_deref().map(&matches_year).unwrap_or(false))
+            .filter(|t| t.date.as_deref().map(&matches_period).unwrap_or(false))
             .map(|t| t.amount_sol)
             .sum();
         let mev_usd: f64 = data
             .categorized
             .mev_deposits
             .iter()
-            .filter(|t| t.date.as_deref().map(&matches_year).unwrap_or(false))
+            .filter(|t| t.date.as_deref().map(&matches_period).unwrap_or(false))
             .map(|t| {
                 let price = get_price(data.prices, t.date.as_deref().unwrap_or(constants::FALLBACK_DATE));
                 t.amount_sol * price
@@ -1313,57 +1771,40 @@ pub fn print_summary(data: &ReportData, year_filter: Option<i32>) {
         (mev_sol, mev_usd)
     } else {
         // Primary: use Jito API data
-        let mev_sol: f64 = data
-            .mev_claims
-            .iter()
-            .filter(|c| c.date.as_deref().map(&matches_year).unwrap_or(false))
-            .map(|c| c.amount_sol)
-            .sum();
-        let mev_usd: f64 = data
-            .mev_claims
-            .iter()
-            .filter(|c| c.date.as_deref().map(&matches_year).unwrap_or(false))
-            .map(|c| {
-                let price = get_price(data.prices, c.date.as_deref().unwrap_or(constants::FALLBACK_DATE));
-                c.amount_sol * price
-            })
-            .sum();
-        (mev_sol, mev_usd)
+        period_total(&data.aggregates.mev_claims)
     };
 
     // BAM rewards (jitoSOL converted to SOL equivalent)
     let total_bam_sol: f64 = data
         .bam_claims
         .iter()
-        .filter(|c| c.date.as_deref().map(&matches_year).unwrap_or(false))
+        .filter(|c| c.date.as_deref().map(&matches_period).unwrap_or(false))
         .map(|c| c.amount_sol_equivalent)
         .sum();
     let total_bam_usd: f64 = data
         .bam_claims
         .iter()
-        .filter(|c| c.date.as_deref().map(&matches_year).unwrap_or(false))
+        .filter(|c| c.date.as_deref().map(&matches_period).unwrap_or(false))
         .map(|c| {
-            let price = get_price(data.prices, c.date.as_deref().unwrap_or(constants::FALLBACK_DATE));
+            let date = c.date.as_deref().unwrap_or(constants::FALLBACK_DATE);
+            let (price, _) = get_event_price(data.prices, data.hourly_prices, date, c.claimed_at_timestamp());
             c.amount_sol_equivalent * price
         })
         .sum();
 
     // Leader fees from block production
-    let total_leader_fees_sol: f64 = data
-        .leader_fees
-        .iter()
-        .filter(|f| f.date.as_deref().map(&matches_year).unwrap_or(false))
-        .map(|f| f.total_fees_sol)
-        .sum();
-    let total_leader_fees_usd: f64 = data
-        .leader_fees
+    let (total_leader_fees_sol, total_leader_fees_usd) = period_total(&data.aggregates.leader_fees);
+
+    // Donations (tips to the donation address)
+    let (total_donations_sol, total_donations_usd) = data
+        .categorized
+        .donations
         .iter()
-        .filter(|f| f.date.as_deref().map(&matches_year).unwrap_or(false))
-        .map(|f| {
-            let price = get_price(data.prices, f.date.as_deref().unwrap_or(constants::FALLBACK_DATE));
-            f.total_fees_sol * price
-        })
-        .sum();
+        .filter(|t| t.date.as_deref().map(&matches_period).unwrap_or(false))
+        .fold((0.0, 0.0), |(sol, usd), t| {
+            let price = get_price(data.prices, t.date.as_deref().unwrap_or(constants::FALLBACK_DATE));
+            (sol + t.amount_sol, usd + t.amount_sol * price)
+        });
 
     // Note: SFDP is tracked as expense offset, not calculated separately for revenue
 
@@ -1371,66 +1812,38 @@ pub fn print_summary(data: &ReportData, year_filter: Option<i32>) {
         .categorized
         .seeding
         .iter()
-        .filter(|t| t.date.as_deref().map(&matches_year).unwrap_or(false))
+        .filter(|t| t.date.as_deref().map(&matches_period).unwrap_or(false))
         .map(|t| t.amount_sol)
         .sum();
 
-    // Vote costs (with SFDP coverage)
-    let total_vote_costs_sol: f64 = data
-        .vote_costs
-        .iter()
-        .filter(|c| c.date.as_deref().map(&matches_year).unwrap_or(false))
-        .map(|c| c.total_fee_sol)
-        .sum();
+    // Vote costs (with SFDP coverage), aggregated per day
+    let mut total_vote_costs_sol = 0.0;
     let mut total_vote_costs_gross_usd = 0.0;
     let mut total_vote_costs_net_usd = 0.0;
 
-    for cost in data.vote_costs {
-        let date = cost.date.as_deref().unwrap_or(constants::FALLBACK_DATE);
-        if !matches_year(date) {
+    for (date, (sol, gross_usd)) in data.aggregates.vote_costs.valued(data.prices) {
+        if !matches_period(&date) {
             continue;
         }
-        let price = get_price(data.prices, date);
-        let gross_usd = cost.total_fee_sol * price;
-
-        // Calculate SFDP coverage
-        let parsed_date = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
-            .unwrap_or_else(|_| chrono::NaiveDate::parse_from_str(constants::FALLBACK_DATE, "%Y-%m-%d").unwrap());
-        let coverage = data.config.sfdp_coverage_percent(&parsed_date);
-        let net_usd = gross_usd * (1.0 - coverage);
-
+        total_vote_costs_sol += sol;
         total_vote_costs_gross_usd += gross_usd;
-        total_vote_costs_net_usd += net_usd;
+        total_vote_costs_net_usd += gross_usd * (1.0 - sfdp_coverage(data.config, &date));
     }
 
     // DoubleZero fees
-    let total_doublezero_sol: f64 = data
-        .doublezero_fees
-        .iter()
-        .filter(|f| f.date.as_deref().map(&matches_year).unwrap_or(false))
-        .map(|f| f.liability_sol)
-        .sum();
-    let total_doublezero_usd: f64 = data
-        .doublezero_fees
-        .iter()
-        .filter(|f| f.date.as_deref().map(&matches_year).unwrap_or(false))
-        .map(|f| {
-            let price = get_price(data.prices, f.date.as_deref().unwrap_or(constants::FALLBACK_DATE));
-            f.liability_sol * price
-        })
-        .sum();
+    let (total_doublezero_sol, total_doublezero_usd) = period_total(&data.aggregates.doublezero_fees);
     let total_doublezero_paid_sol: f64 = data
         .categorized
         .doublezero_payments
         .iter()
-        .filter(|t| t.date.as_deref().map(&matches_year).unwrap_or(false))
+        .filter(|t| t.date.as_deref().map(&matches_period).unwrap_or(false))
         .map(|t| t.amount_sol)
         .sum();
     let total_doublezero_paid_usd: f64 = data
         .categorized
         .doublezero_payments
         .iter()
-        .filter(|t| t.date.as_deref().map(&matches_year).unwrap_or(false))
+        .filter(|t| t.date.as_deref().map(&matches_period).unwrap_or(false))
         .map(|t| {
             let price = get_price(data.prices, t.date.as_deref().unwrap_or(constants::FALLBACK_DATE));
             t.amount_sol * price
@@ -1443,109 +1856,169 @@ pub fn print_summary(data: &ReportData, year_filter: Option<i32>) {
     let total_other_expenses: f64 = data
         .expenses
         .iter()
-        .filter(|e| matches_year(&e.date))
+        .filter(|e| matches_period(&e.date))
         .map(|e| e.amount_usd)
         .sum();
     let hosting_expenses: f64 = data
         .expenses
         .iter()
-        .filter(|e| e.category == ExpenseCategory::Hosting && matches_year(&e.date))
+        .filter(|e| e.category == ExpenseCategory::Hosting && matches_period(&e.date))
         .map(|e| e.amount_usd)
         .sum();
     let contractor_expenses: f64 = data
         .expenses
         .iter()
-        .filter(|e| e.category == ExpenseCategory::Contractor && matches_year(&e.date))
+        .filter(|e| e.category == ExpenseCategory::Contractor && matches_period(&e.date))
         .map(|e| e.amount_usd)
         .sum();
 
-    // SFDP is an expense offset, not revenue. BAM rewards are revenue.
-    let total_revenue_usd = total_commission_usd + total_leader_fees_usd + total_mev_usd + total_bam_usd;
+    // SFDP is an expense offset, not revenue. BAM rewards and donations are revenue.
+    let total_revenue_usd =
+        total_commission_usd + total_leader_fees_usd + total_mev_usd + total_bam_usd + total_donations_usd;
     let total_expenses_usd = total_vote_costs_net_usd + total_doublezero_usd + total_other_expenses;
     let net_profit = total_revenue_usd - total_expenses_usd;
 
     // Normalize values to avoid displaying -0.0
-    let total_commission_sol = normalize_zero(total_commission_sol);
-    let total_commission_usd = normalize_zero(total_commission_usd);
-    let total_leader_fees_sol = normalize_zero(total_leader_fees_sol);
-    let total_leader_fees_usd = normalize_zero(total_leader_fees_usd);
-    let total_mev_sol = normalize_zero(total_mev_sol);
-    let total_mev_usd = normalize_zero(total_mev_usd);
-    let total_bam_sol = normalize_zero(total_bam_sol);
-    let total_bam_usd = normalize_zero(total_bam_usd);
-    let total_doublezero_sol = normalize_zero(total_doublezero_sol);
-    let total_doublezero_usd = normalize_zero(total_doublezero_usd);
-    let total_doublezero_paid_sol = normalize_zero(total_doublezero_paid_sol);
-    let total_doublezero_paid_usd = normalize_zero(total_doublezero_paid_usd);
-    let total_doublezero_outstanding_sol = normalize_zero(total_doublezero_outstanding_sol);
-    let _total_doublezero_outstanding_usd = normalize_zero(total_doublezero_outstanding_usd);
-    let total_seeding_sol = normalize_zero(total_seeding_sol);
+    SummaryTotals {
+        period: period.map(|p| p.label.clone()),
+        timezone: data.config.accounting_timezone.name().to_string(),
+        commission_sol: normalize_zero(total_commission_sol),
+        commission_usd: normalize_zero(total_commission_usd),
+        leader_fees_sol: normalize_zero(total_leader_fees_sol),
+        leader_fees_usd: normalize_zero(total_leader_fees_usd),
+        mev_sol: normalize_zero(total_mev_sol),
+        mev_usd: normalize_zero(total_mev_usd),
+        bam_sol: normalize_zero(total_bam_sol),
+        bam_usd: normalize_zero(total_bam_usd),
+        donations_sol: normalize_zero(total_donations_sol),
+        donations_usd: normalize_zero(total_donations_usd),
+        total_revenue_sol: normalize_zero(
+            total_commission_sol + total_leader_fees_sol + total_mev_sol + total_bam_sol + total_donations_sol,
+        ),
+        total_revenue_usd,
+        vote_costs_sol: total_vote_costs_sol,
+        vote_costs_gross_usd: total_vote_costs_gross_usd,
+        sfdp_offset_usd: total_vote_costs_gross_usd - total_vote_costs_net_usd,
+        vote_costs_net_usd: total_vote_costs_net_usd,
+        doublezero_sol: normalize_zero(total_doublezero_sol),
+        doublezero_usd: normalize_zero(total_doublezero_usd),
+        doublezero_paid_sol: normalize_zero(total_doublezero_paid_sol),
+        doublezero_paid_usd: normalize_zero(total_doublezero_paid_usd),
+        doublezero_outstanding_sol: normalize_zero(total_doublezero_outstanding_sol),
+        doublezero_outstanding_usd: normalize_zero(total_doublezero_outstanding_usd),
+        hosting_usd: hosting_expenses,
+        contractor_usd: contractor_expenses,
+        offchain_expenses_usd: total_other_expenses,
+        total_expenses_usd,
+        net_profit_usd: net_profit,
+        seeding_sol: normalize_zero(total_seeding_sol),
+        capital_transfer_count: data.categorized.seeding.len() + data.categorized.vote_funding.len(),
+        unit_economics: unit_economics::compute(data, matches_period),
+    }
+}
+
+/// Print summary to console
+pub fn print_summary(data: &ReportData, period: Option<&ReportPeriod>) {
+    let t = compute_summary(data, period);
+
+    println!("\n============================================================");
+    if let Some(label) = &t.period {
+        println!("                FINANCIAL SUMMARY ({})", label);
+    } else {
+        println!("                    FINANCIAL SUMMARY");
+    }
+    println!("============================================================");
+    println!("  Dates in {}\n", t.timezone);
+    let cluster = cluster::current();
+    if !cluster.is_mainnet() {
+        println!("  Cluster: {} - USD valuation disabled (N/A)\n", cluster.as_str());
+    }
 
     println!("REVENUE:");
     println!(
         "  Commission:         {:>10.4} SOL  ${:>10.2}",
-        total_commission_sol, total_commission_usd
+        t.commission_sol, t.commission_usd
     );
     println!(
         "  Leader Fees:        {:>10.4} SOL  ${:>10.2}",
-        total_leader_fees_sol, total_leader_fees_usd
-    );
-    println!(
-        "  Jito MEV:           {:>10.4} SOL  ${:>10.2}",
-        total_mev_sol, total_mev_usd
+        t.leader_fees_sol, t.leader_fees_usd
     );
-    if total_bam_sol > 0.0 || !data.bam_claims.is_empty() {
+    println!("  Jito MEV:           {:>10.4} SOL  ${:>10.2}", t.mev_sol, t.mev_usd);
+    if t.bam_sol > 0.0 || !data.bam_claims.is_empty() {
+        println!("  BAM Rewards:        {:>10.4} SOL  ${:>10.2}", t.bam_sol, t.bam_usd);
+    }
+    if t.donations_sol > 0.0 {
         println!(
-            "  BAM Rewards:        {:>10.4} SOL  ${:>10.2}",
-            total_bam_sol, total_bam_usd
+            "  Donations:          {:>10.4} SOL  ${:>10.2}",
+            t.donations_sol, t.donations_usd
         );
     }
     println!("  ─────────────────────────────────────────────");
     println!(
         "  Total Revenue:      {:>10.4} SOL  ${:>10.2}",
-        total_commission_sol + total_leader_fees_sol + total_mev_sol + total_bam_sol,
-        total_revenue_usd
+        t.total_revenue_sol, t.total_revenue_usd
     );
 
     println!("\nEXPENSES:");
     println!(
         "  Vote Fees (gross):  {:>10.4} SOL  ${:>10.2}",
-        total_vote_costs_sol, total_vote_costs_gross_usd
+        t.vote_costs_sol, t.vote_costs_gross_usd
     );
-    println!(
-        "  SFDP Offset:                   -${:>10.2}",
-        total_vote_costs_gross_usd - total_vote_costs_net_usd
-    );
-    println!("  Vote Fees (net):                ${:>10.2}", total_vote_costs_net_usd);
-    let show_doublezero = total_doublezero_sol > 0.0
-        || total_doublezero_paid_sol > 0.0
-        || total_doublezero_outstanding_sol.abs() > 0.000001;
+    println!("  SFDP Offset:                   -${:>10.2}", t.sfdp_offset_usd);
+    println!("  Vote Fees (net):                ${:>10.2}", t.vote_costs_net_usd);
+    let show_doublezero =
+        t.doublezero_sol > 0.0 || t.doublezero_paid_sol > 0.0 || t.doublezero_outstanding_sol.abs() > 0.000001;
     if show_doublezero {
         println!(
             "  DoubleZero Fees:    {:>10.4} SOL  ${:>10.2}",
-            total_doublezero_sol, total_doublezero_usd
+            t.doublezero_sol, t.doublezero_usd
         );
-        if total_doublezero_paid_sol > 0.0 || total_doublezero_outstanding_sol.abs() > 0.000001 {
+        if t.doublezero_paid_sol > 0.0 || t.doublezero_outstanding_sol.abs() > 0.000001 {
             println!(
                 "  DoubleZero Paid:    {:>10.4} SOL  ${:>10.2}",
-                total_doublezero_paid_sol, total_doublezero_paid_usd
+                t.doublezero_paid_sol, t.doublezero_paid_usd
             );
         }
     }
-    println!("  Hosting:                        ${:>10.2}", hosting_expenses);
-    println!("  Contractor:                     ${:>10.2}", contractor_expenses);
+    println!("  Hosting:                        ${:>10.2}", t.hosting_usd);
+    println!("  Contractor:                     ${:>10.2}", t.contractor_usd);
     println!("  ─────────────────────────────────────────────");
-    println!("  Total Expenses:                 ${:>10.2}", total_expenses_usd);
+    println!("  Total Expenses:                 ${:>10.2}", t.total_expenses_usd);
 
     println!("\nPROFIT/LOSS:");
-    println!("  Net Profit:                     ${:>10.2}", net_profit);
+    println!("  Net Profit:                     ${:>10.2}", t.net_profit_usd);
 
-    println!("\nCAPITAL:");
-    println!("  Initial Seeding:    {:>10.4} SOL", total_seeding_sol);
-    println!(
-        "  Transfers found:    {}",
-        data.categorized.seeding.len() + data.categorized.vote_funding.len()
+    unit_economics::print(&t.unit_economics);
+    revenue_stats::print(&revenue_stats::compute(data, |d| period.is_none_or(|p| p.contains(d))));
+    anomalies::print(
+        &anomalies::compute(data, |d| period.is_none_or(|p| p.contains(d))),
+        data.config.anomalies.sigma,
+    );
+    delegators::print(&delegators::analyze(data.delegator_stake));
+    let sfdp_rows = sfdp::compute(data);
+    let period_rows: Vec<_> = sfdp_rows
+        .iter()
+        .filter(|r| r.date.as_deref().is_some_and(|d| period.is_none_or(|p| p.contains(d))))
+        .cloned()
+        .collect();
+    sfdp::print(
+        &period_rows,
+        sfdp::project(&sfdp_rows, data.config.sfdp_stake_end()).as_ref(),
+        timezone::today(),
     );
 
+    if let Some(fiat_config) = &data.config.fiat {
+        fiat::print(&fiat::statement(
+            fiat_config,
+            data.expenses,
+            data.fiat_deposits,
+            timezone::today(),
+        ));
+    }
+
+    println!("\nCAPITAL:");
+    println!("  Initial Seeding:    {:>10.4} SOL", t.seeding_sol);
+    println!("  Transfers found:    {}", t.capital_transfer_count);
+
     println!("============================================================");
 }
@@ -0,0 +1,125 @@
+//! Revenue seasonality and volatility (revenue_stats.csv)
+//!
+//! Per-epoch revenue swings with leader slot luck and MEV, so a single month says
+//! little about what to expect. This reports the mean and standard deviation of
+//! per-epoch revenue, how much of each month's MEV came from its top epochs, and the
+//! average revenue of epochs by the weekday they are dated on. Epochs without revenue
+//! (usually the one still in progress) are left out. The statistics live in
+//! `report_template` so report.html and `/financials` show the same panel.
+
+use anyhow::Result;
+use csv::WriterBuilder;
+use report_template::{EpochRevenue, RevenueStats};
+use std::path::Path;
+
+use crate::constants;
+use crate::epoch_pnl;
+use crate::reports::ReportData;
+
+/// Epochs that earned anything, oldest first
+pub fn epochs(data: &ReportData) -> Vec<EpochRevenue> {
+    epoch_pnl::compute(data)
+        .into_iter()
+        .filter(|row| row.revenue_sol() > 0.0)
+        .map(|row| EpochRevenue {
+            epoch: row.epoch,
+            revenue_sol: row.revenue_sol(),
+            commission_sol: row.commission_sol,
+            leader_fees_sol: row.leader_fees_sol,
+            mev_sol: row.mev_sol,
+            date: row.date,
+        })
+        .collect()
+}
+
+/// Statistics over the epochs dated on days accepted by `include`
+pub fn compute(data: &ReportData, include: impl Fn(&str) -> bool) -> RevenueStats {
+    let epochs: Vec<EpochRevenue> = epochs(data)
+        .into_iter()
+        .filter(|e| e.date.as_deref().is_some_and(&include))
+        .collect();
+    RevenueStats::from_epochs(&epochs)
+}
+
+fn opt(value: Option<f64>, decimals: usize) -> String {
+    value.map_or(String::new(), |v| format!("{:.*}", decimals, v))
+}
+
+/// Generate revenue_stats.csv: the summary, then MEV concentration by month, then weekdays
+pub fn generate_csv(output_dir: &Path, stats: &RevenueStats) -> Result<()> {
+    let path = output_dir.join(constants::REVENUE_STATS_FILENAME);
+    let mut wtr = WriterBuilder::new().flexible(true).from_path(&path)?;
+
+    wtr.write_record(["Metric", "Value"])?;
+    for (metric, value) in [
+        ("Epochs", stats.epochs.to_string()),
+        ("Mean_Revenue_Per_Epoch_SOL", format!("{:.6}", stats.mean_sol)),
+        ("Stddev_Revenue_Per_Epoch_SOL", format!("{:.6}", stats.stddev_sol)),
+        ("Coefficient_Of_Variation_Pct", opt(stats.cv_percent, 1)),
+        ("Min_Revenue_Per_Epoch_SOL", format!("{:.6}", stats.min_sol)),
+        ("Max_Revenue_Per_Epoch_SOL", format!("{:.6}", stats.max_sol)),
+        ("Avg_MEV_Share_Top_Epochs_Pct", opt(stats.avg_mev_top_share_percent, 1)),
+    ] {
+        wtr.write_record([metric.to_string(), value])?;
+    }
+
+    wtr.write_record([""])?;
+    wtr.write_record([
+        "Month".to_string(),
+        "Epochs".to_string(),
+        "MEV_SOL".to_string(),
+        format!("Top{}_Epochs_Share_Pct", stats.mev_top_epochs),
+    ])?;
+    for m in &stats.mev_concentration {
+        wtr.write_record([
+            m.month.clone(),
+            m.epochs.to_string(),
+            format!("{:.6}", m.mev_sol),
+            format!("{:.1}", m.top_share_percent),
+        ])?;
+    }
+
+    wtr.write_record([""])?;
+    wtr.write_record(["Weekday", "Epochs", "Mean_Revenue_SOL", "Vs_Mean_Pct"])?;
+    for w in &stats.weekdays {
+        wtr.write_record([
+            w.weekday.to_string(),
+            w.epochs.to_string(),
+            format!("{:.6}", w.mean_sol),
+            opt(w.vs_mean_percent, 1),
+        ])?;
+    }
+
+    wtr.flush()?;
+    println!("  Generated: {}", path.display());
+    Ok(())
+}
+
+/// Console section for the financial summary
+pub fn print(stats: &RevenueStats) {
+    if stats.is_empty() {
+        return;
+    }
+    println!("\nREVENUE VOLATILITY ({} epochs):", stats.epochs);
+    println!(
+        "  Revenue / Epoch:       {:.4} ± {:.4} SOL (range {:.4} - {:.4})",
+        stats.mean_sol, stats.stddev_sol, stats.min_sol, stats.max_sol
+    );
+    if let Some(cv) = stats.cv_percent {
+        println!("  Volatility (CV):       {:.1}%", cv);
+    }
+    if let Some(share) = stats.avg_mev_top_share_percent {
+        println!(
+            "  MEV From Top {} Epochs: {:.1}% of each month's MEV on average",
+            stats.mev_top_epochs, share
+        );
+    }
+    let weekdays: Vec<String> = stats
+        .weekdays
+        .iter()
+        .filter_map(|w| w.vs_mean_percent.map(|p| format!("{} {:+.0}%", w.weekday, p)))
+        .collect();
+    if !weekdays.is_empty() {
+        println!("  Weekday Effect:        {}", weekdays.join(", "));
+    }
+}
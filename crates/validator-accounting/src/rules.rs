@@ -0,0 +1,553 @@
+//! Ordered rules that decide which report bucket a transfer lands in
+//!
+//! Operator rules from `[[categorization.rules]]` are evaluated first, in file order, followed
+//! by the built-in rules (DoubleZero deposit, internal funding, seeding, donations, SFDP, MEV,
+//! withdrawals).
+//! The first rule that matches decides the bucket; a transfer no rule matches goes to `other`.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+use crate::addresses::AddressCategory;
+use crate::config::{Config, TransferRuleConfig};
+use crate::constants;
+use crate::transactions::{self, SolTransfer};
+
+/// Report bucket a rule assigns (see `CategorizedTransfers`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferBucket {
+    Seeding,
+    SfdpReimbursement,
+    MevDeposit,
+    DoublezeroPayment,
+    Donation,
+    VoteFunding,
+    Withdrawal,
+    /// Rent-exempt deposits for new accounts and rent refunds from closed ones
+    Rent,
+    Other,
+}
+
+/// Which way a transfer moves relative to the validator accounts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferDirection {
+    /// External address -> one of our accounts
+    Incoming,
+    /// One of our accounts -> external address
+    Outgoing,
+    /// Between our own accounts
+    Internal,
+}
+
+impl TransferDirection {
+    /// Direction of `transfer`, or None if it doesn't touch our accounts
+    pub fn of(transfer: &SolTransfer, config: &Config) -> Option<Self> {
+        let date = transfer.date.as_deref();
+        match (
+            config.is_our_account_on(&transfer.from, date),
+            config.is_our_account_on(&transfer.to, date),
+        ) {
+            (true, true) => Some(TransferDirection::Internal),
+            (false, true) => Some(TransferDirection::Incoming),
+            (true, false) => Some(TransferDirection::Outgoing),
+            (false, false) => None,
+        }
+    }
+}
+
+/// A parsed categorization rule; every condition that is set must match
+#[derive(Debug, Clone)]
+pub struct TransferRule {
+    pub name: String,
+    pub bucket: TransferBucket,
+    pub direction: Option<TransferDirection>,
+    /// Counterparty must be one of these addresses (empty = any)
+    pub counterparties: Vec<Pubkey>,
+    /// Counterparty must have one of these categories (empty = any)
+    pub counterparty_categories: Vec<AddressCategory>,
+    /// Our side of the transfer must be one of these addresses (empty = any)
+    pub accounts: Vec<Pubkey>,
+    pub min_lamports: Option<u64>,
+    pub max_lamports: Option<u64>,
+    /// Program that moved the SOL must be one of these (empty = any)
+    pub programs: Vec<String>,
+    /// Lowercased text the transfer's memo must contain
+    pub memo_contains: Option<String>,
+    /// Replaces the counterparty's label on matched transfers
+    pub label: Option<String>,
+}
+
+impl TransferRule {
+    /// Parse and validate a rule from config.toml
+    pub fn from_config(rule: &TransferRuleConfig) -> Result<Self> {
+        let counterparties = rule
+            .counterparty
+            .iter()
+            .map(|addr| {
+                Pubkey::from_str(addr)
+                    .with_context(|| format!("Invalid counterparty address '{}' in rule '{}'", addr, rule.name))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let accounts = rule
+            .account
+            .iter()
+            .map(|addr| {
+                Pubkey::from_str(addr)
+                    .with_context(|| format!("Invalid account address '{}' in rule '{}'", addr, rule.name))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let to_lamports = |sol: Option<f64>, field: &str| -> Result<Option<u64>> {
+            match sol {
+                Some(sol) => {
+                    anyhow::ensure!(
+                        sol.is_finite() && sol >= 0.0,
+                        "{} in rule '{}' must be a non-negative number",
+                        field,
+                        rule.name
+                    );
+                    Ok(Some((sol * constants::LAMPORTS_PER_SOL_U64 as f64).round() as u64))
+                }
+                None => Ok(None),
+            }
+        };
+        for program in &rule.program {
+            Pubkey::from_str(program)
+                .with_context(|| format!("Invalid program address '{}' in rule '{}'", program, rule.name))?;
+        }
+        let min_lamports = to_lamports(rule.min_sol, "min_sol")?;
+        let max_lamports = to_lamports(rule.max_sol, "max_sol")?;
+        if let (Some(min), Some(max)) = (min_lamports, max_lamports) {
+            anyhow::ensure!(min <= max, "min_sol exceeds max_sol in rule '{}'", rule.name);
+        }
+
+        Ok(Self {
+            name: rule.name.clone(),
+            bucket: rule.bucket,
+            direction: rule.direction,
+            counterparties,
+            counterparty_categories: rule.counterparty_category.clone(),
+            accounts,
+            min_lamports,
+            max_lamports,
+            programs: rule.program.clone(),
+            memo_contains: rule.memo_contains.as_ref().map(|m| m.to_lowercase()),
+            label: rule.label.clone(),
+        })
+    }
+
+    fn matches(&self, transfer: &SolTransfer, direction: TransferDirection, config: &Config) -> bool {
+        if self.direction.is_some_and(|d| d != direction) {
+            return false;
+        }
+        if self.min_lamports.is_some_and(|min| transfer.amount_lamports < min)
+            || self.max_lamports.is_some_and(|max| transfer.amount_lamports > max)
+        {
+            return false;
+        }
+        if !self.programs.is_empty()
+            && !transfer
+                .program
+                .as_ref()
+                .is_some_and(|program| self.programs.contains(program))
+        {
+            return false;
+        }
+        if let Some(needle) = &self.memo_contains
+            && !transfer
+                .memo
+                .as_ref()
+                .is_some_and(|memo| memo.to_lowercase().contains(needle))
+        {
+            return false;
+        }
+
+        if !self.accounts.is_empty() && !self.accounts.contains(our_account(transfer, direction)) {
+            return false;
+        }
+
+        let counterparty = counterparty(transfer, direction);
+        if !self.counterparties.is_empty() && !self.counterparties.contains(counterparty) {
+            return false;
+        }
+        if !self.counterparty_categories.is_empty() {
+            let (_, category) =
+                transactions::label_and_category_for_address(counterparty, transfer.date.as_deref(), config);
+            if !self.counterparty_categories.contains(&category) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// The address on the other side of a transfer (the destination for internal transfers)
+fn counterparty(transfer: &SolTransfer, direction: TransferDirection) -> &Pubkey {
+    match direction {
+        TransferDirection::Incoming => &transfer.from,
+        TransferDirection::Outgoing | TransferDirection::Internal => &transfer.to,
+    }
+}
+
+/// Our address on a transfer (the source for internal transfers)
+fn our_account(transfer: &SolTransfer, direction: TransferDirection) -> &Pubkey {
+    match direction {
+        TransferDirection::Incoming => &transfer.to,
+        TransferDirection::Outgoing | TransferDirection::Internal => &transfer.from,
+    }
+}
+
+/// Built-in rules, evaluated after the operator's rules
+pub fn builtin_rules(config: &Config) -> Vec<TransferRule> {
+    let rule = |name: &str, bucket, direction, categories: &[AddressCategory]| TransferRule {
+        name: name.to_string(),
+        bucket,
+        direction: Some(direction),
+        counterparties: Vec::new(),
+        counterparty_categories: categories.to_vec(),
+        accounts: Vec::new(),
+        min_lamports: None,
+        max_lamports: None,
+        programs: Vec::new(),
+        memo_contains: None,
+        label: None,
+    };
+
+    let mut rules = Vec::new();
+    if let Some(deposit) = config.doublezero_deposit_account {
+        rules.push(TransferRule {
+            counterparties: vec![deposit],
+            label: Some("DoubleZero Deposit".to_string()),
+            ..rule(
+                "DoubleZero deposit",
+                TransferBucket::DoublezeroPayment,
+                TransferDirection::Outgoing,
+                &[],
+            )
+        });
+    }
+    // Transfers with a related entity's wallets settle inter-entity balances; never withdrawals
+    for entity in config.entities.iter().skip(1).filter(|e| !e.wallets.is_empty()) {
+        rules.push(TransferRule {
+            direction: None,
+            counterparties: entity.wallets.clone(),
+            label: Some(format!("Inter-entity: {}", entity.name)),
+            ..rule(
+                "Inter-entity transfer",
+                TransferBucket::Other,
+                TransferDirection::Outgoing,
+                &[],
+            )
+        });
+    }
+    rules.extend([
+        rule(
+            "Internal transfer",
+            TransferBucket::VoteFunding,
+            TransferDirection::Internal,
+            &[],
+        ),
+        rule(
+            "Seeding from personal wallet",
+            TransferBucket::Seeding,
+            TransferDirection::Incoming,
+            &[AddressCategory::PersonalWallet],
+        ),
+    ]);
+    if let Some(donation) = config.donation_address {
+        rules.push(TransferRule {
+            accounts: vec![donation],
+            ..rule(
+                "Donation to tip address",
+                TransferBucket::Donation,
+                TransferDirection::Incoming,
+                &[],
+            )
+        });
+    }
+    rules.extend([
+        rule(
+            "SFDP reimbursement",
+            TransferBucket::SfdpReimbursement,
+            TransferDirection::Incoming,
+            &[AddressCategory::SolanaFoundation],
+        ),
+        rule(
+            "Jito MEV deposit",
+            TransferBucket::MevDeposit,
+            TransferDirection::Incoming,
+            &[AddressCategory::JitoMev],
+        ),
+        // Account creation funds the new account's rent-exempt reserve; closing it refunds the reserve
+        TransferRule {
+            programs: [
+                constants::STAKE_PROGRAM,
+                constants::SPL_TOKEN_PROGRAM,
+                constants::SPL_TOKEN_2022_PROGRAM,
+                constants::ASSOCIATED_TOKEN_PROGRAM,
+            ]
+            .map(str::to_string)
+            .to_vec(),
+            max_lamports: Some(constants::RENT_MAX_LAMPORTS),
+            ..rule(
+                "Rent deposit for a new account",
+                TransferBucket::Rent,
+                TransferDirection::Outgoing,
+                &[],
+            )
+        },
+        TransferRule {
+            programs: [constants::SPL_TOKEN_PROGRAM, constants::SPL_TOKEN_2022_PROGRAM]
+                .map(str::to_string)
+                .to_vec(),
+            max_lamports: Some(constants::RENT_MAX_LAMPORTS),
+            ..rule(
+                "Rent refund from a closed account",
+                TransferBucket::Rent,
+                TransferDirection::Incoming,
+                &[],
+            )
+        },
+        rule(
+            "Withdrawal to exchange or personal wallet",
+            TransferBucket::Withdrawal,
+            TransferDirection::Outgoing,
+            &[AddressCategory::Exchange, AddressCategory::PersonalWallet],
+        ),
+    ]);
+    rules
+}
+
+/// First rule matching a transfer (operator rules, then `builtin`, which the caller builds
+/// once per batch with `builtin_rules`)
+pub fn matching_rule<'a>(
+    transfer: &SolTransfer,
+    direction: TransferDirection,
+    config: &'a Config,
+    builtin: &'a [TransferRule],
+) -> Option<&'a TransferRule> {
+    config
+        .transfer_rules
+        .iter()
+        .chain(builtin)
+        .find(|rule| rule.matches(transfer, direction, config))
+}
+
+/// Bucket for one transfer, with the counterparty label override of the matching rule
+pub fn classify<'a>(
+    transfer: &SolTransfer,
+    direction: TransferDirection,
+    config: &'a Config,
+    builtin: &'a [TransferRule],
+) -> (TransferBucket, Option<&'a str>) {
+    matching_rule(transfer, direction, config, builtin)
+        .map(|rule| (rule.bucket, rule.label.as_deref()))
+        .unwrap_or((TransferBucket::Other, None))
+}
+
+/// Apply a rule's label override to the counterparty side of `transfer`
+pub fn apply_label(transfer: &mut SolTransfer, direction: TransferDirection, label: &str) {
+    match direction {
+        TransferDirection::Incoming => transfer.from_label = label.to_string(),
+        TransferDirection::Outgoing | TransferDirection::Internal => transfer.to_label = label.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::tests::test_config;
+
+    fn transfer(from: Pubkey, to: Pubkey, sol: u64) -> SolTransfer {
+        SolTransfer {
+            signature: "sig".to_string(),
+            slot: 1,
+            timestamp: None,
+            date: None,
+            from,
+            to,
+            amount_lamports: sol * constants::LAMPORTS_PER_SOL_U64,
+            amount_sol: sol as f64,
+            from_label: String::new(),
+            to_label: String::new(),
+            from_category: AddressCategory::Unknown,
+            to_category: AddressCategory::Unknown,
+            memo: None,
+            program: None,
+        }
+    }
+
+    fn rule_config(toml_rule: &str) -> TransferRuleConfig {
+        toml::from_str(toml_rule).unwrap()
+    }
+
+    #[test]
+    fn test_builtin_rules_bucket_personal_wallet_flows() {
+        let config = test_config(None);
+        let builtin = builtin_rules(&config);
+        let personal = config.personal_wallets[0];
+
+        let seed = transfer(personal, config.vote_account, 10);
+        let direction = TransferDirection::of(&seed, &config).unwrap();
+        assert_eq!(direction, TransferDirection::Incoming);
+        assert_eq!(classify(&seed, direction, &config, &builtin).0, TransferBucket::Seeding);
+
+        let payout = transfer(config.withdraw_authority, personal, 10);
+        let direction = TransferDirection::of(&payout, &config).unwrap();
+        assert_eq!(
+            classify(&payout, direction, &config, &builtin).0,
+            TransferBucket::Withdrawal
+        );
+
+        let unknown = transfer(config.identity, Pubkey::new_unique(), 1);
+        let direction = TransferDirection::of(&unknown, &config).unwrap();
+        assert_eq!(
+            classify(&unknown, direction, &config, &builtin).0,
+            TransferBucket::Other
+        );
+    }
+
+    #[test]
+    fn test_rent_deposits_and_refunds() {
+        let config = test_config(None);
+        let builtin = builtin_rules(&config);
+        let with_program = |from, to, lamports: u64, program: &str| SolTransfer {
+            amount_lamports: lamports,
+            amount_sol: lamports as f64 / 1e9,
+            program: Some(program.to_string()),
+            ..transfer(from, to, 0)
+        };
+
+        let ata = with_program(
+            config.withdraw_authority,
+            Pubkey::new_unique(),
+            2_039_280,
+            constants::ASSOCIATED_TOKEN_PROGRAM,
+        );
+        let direction = TransferDirection::of(&ata, &config).unwrap();
+        assert_eq!(classify(&ata, direction, &config, &builtin).0, TransferBucket::Rent);
+
+        let refund = with_program(
+            Pubkey::new_unique(),
+            config.withdraw_authority,
+            2_039_280,
+            constants::SPL_TOKEN_PROGRAM,
+        );
+        let direction = TransferDirection::of(&refund, &config).unwrap();
+        assert_eq!(classify(&refund, direction, &config, &builtin).0, TransferBucket::Rent);
+
+        // Funding a stake account with principal is not rent
+        let stake = with_program(
+            config.withdraw_authority,
+            Pubkey::new_unique(),
+            100 * constants::LAMPORTS_PER_SOL_U64,
+            constants::STAKE_PROGRAM,
+        );
+        let direction = TransferDirection::of(&stake, &config).unwrap();
+        assert_eq!(classify(&stake, direction, &config, &builtin).0, TransferBucket::Other);
+    }
+
+    #[test]
+    fn test_tips_to_donation_address_are_donations() {
+        let mut config = test_config(None);
+        let donation = Pubkey::new_unique();
+        config.donation_address = Some(donation);
+        let builtin = builtin_rules(&config);
+
+        let tip = transfer(Pubkey::new_unique(), donation, 1);
+        let direction = TransferDirection::of(&tip, &config).unwrap();
+        assert_eq!(classify(&tip, direction, &config, &builtin).0, TransferBucket::Donation);
+
+        // The same sender paying another of our accounts isn't a tip
+        let other = transfer(tip.from, config.identity, 1);
+        assert_eq!(classify(&other, direction, &config, &builtin).0, TransferBucket::Other);
+
+        // Moving tips into the treasury is internal
+        let sweep = transfer(donation, config.withdraw_authority, 1);
+        let direction = TransferDirection::of(&sweep, &config).unwrap();
+        assert_eq!(
+            classify(&sweep, direction, &config, &builtin).0,
+            TransferBucket::VoteFunding
+        );
+    }
+
+    #[test]
+    fn test_operator_rule_runs_before_builtin_rules() {
+        let mut config = test_config(None);
+        let dao = Pubkey::new_unique();
+        config.transfer_rules = vec![
+            TransferRule::from_config(&rule_config(&format!(
+                r#"
+                name = "DAO treasury payout"
+                bucket = "withdrawal"
+                direction = "outgoing"
+                counterparty = ["{}"]
+                min_sol = 5.0
+                label = "DAO Treasury"
+                "#,
+                dao
+            )))
+            .unwrap(),
+        ];
+        let builtin = builtin_rules(&config);
+
+        let large = transfer(config.withdraw_authority, dao, 50);
+        let direction = TransferDirection::of(&large, &config).unwrap();
+        assert_eq!(
+            classify(&large, direction, &config, &builtin),
+            (TransferBucket::Withdrawal, Some("DAO Treasury"))
+        );
+
+        // Below min_sol falls through to the built-in rules
+        let small = transfer(config.withdraw_authority, dao, 1);
+        assert_eq!(
+            classify(&small, direction, &config, &builtin),
+            (TransferBucket::Other, None)
+        );
+    }
+
+    #[test]
+    fn test_memo_rule_matches_case_insensitively() {
+        let mut config = test_config(None);
+        config.transfer_rules = vec![
+            TransferRule::from_config(&rule_config(
+                r#"
+                name = "Tagged hosting refunds"
+                bucket = "other"
+                direction = "incoming"
+                memo_contains = "REFUND"
+                label = "Hosting Refund"
+                "#,
+            ))
+            .unwrap(),
+        ];
+        let builtin = builtin_rules(&config);
+
+        let mut refund = transfer(Pubkey::new_unique(), config.identity, 2);
+        let direction = TransferDirection::of(&refund, &config).unwrap();
+        assert_eq!(classify(&refund, direction, &config, &builtin).1, None);
+
+        refund.memo = Some("Refund for March".to_string());
+        assert_eq!(
+            classify(&refund, direction, &config, &builtin),
+            (TransferBucket::Other, Some("Hosting Refund"))
+        );
+    }
+
+    #[test]
+    fn test_rule_rejects_inverted_amount_range() {
+        let rule = rule_config(
+            r#"
+            name = "bad"
+            bucket = "other"
+            min_sol = 10.0
+            max_sol = 1.0
+            "#,
+        );
+        assert!(TransferRule::from_config(&rule).is_err());
+    }
+}
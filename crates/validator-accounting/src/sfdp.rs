@@ -0,0 +1,253 @@
+//! SFDP stake match modeling (sfdp_stake.csv)
+//!
+//! Splits revenue between stake the Solana Foundation delegated through SFDP and stake
+//! the validator attracted on its own, to show how much income depends on the program
+//! and what goes away when Foundation stake is withdrawn. Foundation stake is what the
+//! delegator snapshots attribute to Solana Foundation withdraw authorities (see
+//! `labels.toml`); the modeled match applies the program rule - 1:1 on the remaining
+//! ("residential") stake up to `SFDP_MATCH_CAP_SOL` - as a cross-check, e.g. before the
+//! Foundation has delegated. Each epoch's revenue (commission, leader fees, MEV, BAM)
+//! is attributed pro rata to stake, using the latest snapshot at or before the epoch.
+
+use anyhow::Result;
+use chrono::NaiveDate;
+use csv::Writer;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::addresses;
+use crate::constants;
+use crate::epoch_pnl;
+use crate::reports::ReportData;
+
+/// Stake and revenue split of one epoch
+#[derive(Debug, Clone, PartialEq)]
+pub struct SfdpEpoch {
+    pub epoch: u64,
+    pub date: Option<String>,
+    /// Delegator snapshot the split comes from
+    pub snapshot_epoch: u64,
+    pub total_stake_sol: f64,
+    pub foundation_stake_sol: f64,
+    /// What the match rule would give for the residential stake
+    pub modeled_match_sol: f64,
+    pub revenue_sol: f64,
+    pub price_usd: f64,
+}
+
+impl SfdpEpoch {
+    pub fn residential_stake_sol(&self) -> f64 {
+        self.total_stake_sol - self.foundation_stake_sol
+    }
+
+    /// Foundation share of the stake (0-1)
+    pub fn foundation_share(&self) -> f64 {
+        if self.total_stake_sol > 0.0 {
+            self.foundation_stake_sol / self.total_stake_sol
+        } else {
+            0.0
+        }
+    }
+
+    pub fn sfdp_revenue_sol(&self) -> f64 {
+        self.revenue_sol * self.foundation_share()
+    }
+
+    pub fn organic_revenue_sol(&self) -> f64 {
+        self.revenue_sol - self.sfdp_revenue_sol()
+    }
+}
+
+/// Revenue at risk when Foundation stake leaves
+#[derive(Debug, Clone, PartialEq)]
+pub struct SfdpProjection {
+    /// Expected end of Foundation delegation (None if unknown)
+    pub end_date: Option<NaiveDate>,
+    /// Foundation share of stake in the latest snapshot (0-1)
+    pub foundation_share: f64,
+    /// Monthly revenue attributed to Foundation stake over the trailing epochs
+    pub monthly_sfdp_revenue_sol: f64,
+    pub monthly_sfdp_revenue_usd: f64,
+    /// Monthly revenue that remains after the cliff
+    pub monthly_organic_revenue_usd: f64,
+}
+
+/// Foundation match the program rule gives for `residential_sol`
+pub fn modeled_match_sol(residential_sol: f64) -> f64 {
+    residential_sol.clamp(0.0, constants::SFDP_MATCH_CAP_SOL)
+}
+
+fn is_foundation(delegator: &str) -> bool {
+    Pubkey::from_str(delegator).is_ok_and(|pubkey| addresses::is_solana_foundation(&pubkey))
+}
+
+/// One row per epoch with revenue and a delegator snapshot at or before it, oldest first
+pub fn compute(data: &ReportData) -> Vec<SfdpEpoch> {
+    // snapshot epoch -> (total, foundation) lamports
+    let mut snapshots: BTreeMap<u64, (u64, u64)> = BTreeMap::new();
+    for d in data.delegator_stake {
+        let entry = snapshots.entry(d.epoch).or_default();
+        entry.0 += d.stake_lamports;
+        if is_foundation(&d.delegator) {
+            entry.1 += d.stake_lamports;
+        }
+    }
+
+    let to_sol = |lamports: u64| lamports as f64 / constants::LAMPORTS_PER_SOL;
+    epoch_pnl::compute(data)
+        .into_iter()
+        .filter(|row| row.revenue_sol() > 0.0)
+        .filter_map(|row| {
+            let (&snapshot_epoch, &(total, foundation)) = snapshots.range(..=row.epoch).next_back()?;
+            let total_stake_sol = to_sol(total);
+            let foundation_stake_sol = to_sol(foundation);
+            Some(SfdpEpoch {
+                epoch: row.epoch,
+                date: row.date.clone(),
+                snapshot_epoch,
+                total_stake_sol,
+                foundation_stake_sol,
+                modeled_match_sol: modeled_match_sol(total_stake_sol - foundation_stake_sol),
+                revenue_sol: row.revenue_sol(),
+                price_usd: row.price_usd,
+            })
+        })
+        .collect()
+}
+
+/// Project monthly revenue at risk from the trailing `SFDP_TRAILING_EPOCHS` epochs
+pub fn project(rows: &[SfdpEpoch], end_date: Option<NaiveDate>) -> Option<SfdpProjection> {
+    let latest = rows.last()?;
+    let trailing = &rows[rows.len().saturating_sub(constants::SFDP_TRAILING_EPOCHS)..];
+    let epochs_per_month = 30.0 * 86_400.0 / constants::EPOCH_DURATION_SECONDS as f64;
+    let per_epoch = |f: &dyn Fn(&SfdpEpoch) -> f64| trailing.iter().map(f).sum::<f64>() / trailing.len() as f64;
+
+    Some(SfdpProjection {
+        end_date,
+        foundation_share: latest.foundation_share(),
+        monthly_sfdp_revenue_sol: per_epoch(&|r| r.sfdp_revenue_sol()) * epochs_per_month,
+        monthly_sfdp_revenue_usd: per_epoch(&|r| r.sfdp_revenue_sol() * r.price_usd) * epochs_per_month,
+        monthly_organic_revenue_usd: per_epoch(&|r| r.organic_revenue_sol() * r.price_usd) * epochs_per_month,
+    })
+}
+
+/// Generate sfdp_stake.csv
+pub fn generate_csv(output_dir: &Path, rows: &[SfdpEpoch]) -> Result<()> {
+    let path = output_dir.join(constants::SFDP_STAKE_FILENAME);
+    let mut wtr = Writer::from_path(&path)?;
+
+    wtr.write_record([
+        "Epoch",
+        "Date (YYYY-MM-DD)",
+        "Snapshot_Epoch",
+        "Total_Stake_SOL",
+        "Foundation_Stake_SOL",
+        "Residential_Stake_SOL",
+        "Modeled_Match_SOL",
+        "Foundation_Share_Pct",
+        "Revenue_SOL",
+        "SFDP_Revenue_SOL",
+        "Organic_Revenue_SOL",
+        "SOL_Price_USD",
+        "SFDP_Revenue_USD",
+    ])?;
+
+    for row in rows {
+        wtr.write_record([
+            row.epoch.to_string(),
+            row.date.clone().unwrap_or_default(),
+            row.snapshot_epoch.to_string(),
+            format!("{:.4}", row.total_stake_sol),
+            format!("{:.4}", row.foundation_stake_sol),
+            format!("{:.4}", row.residential_stake_sol()),
+            format!("{:.4}", row.modeled_match_sol),
+            format!("{:.2}", row.foundation_share() * 100.0),
+            format!("{:.6}", row.revenue_sol),
+            format!("{:.6}", row.sfdp_revenue_sol()),
+            format!("{:.6}", row.organic_revenue_sol()),
+            format!("{:.2}", row.price_usd),
+            format!("{:.2}", row.sfdp_revenue_sol() * row.price_usd),
+        ])?;
+    }
+
+    wtr.flush()?;
+    println!("  Generated: {}", path.display());
+    Ok(())
+}
+
+/// Console section for the financial summary: the period's split, then the cliff
+pub fn print(period_rows: &[SfdpEpoch], projection: Option<&SfdpProjection>, today: NaiveDate) {
+    let Some(projection) = projection else {
+        return;
+    };
+    let sfdp_usd: f64 = period_rows.iter().map(|r| r.sfdp_revenue_sol() * r.price_usd).sum();
+    let organic_usd: f64 = period_rows.iter().map(|r| r.organic_revenue_sol() * r.price_usd).sum();
+
+    println!("\nSFDP DEPENDENCE:");
+    println!("  From SFDP stake:                ${:>10.2}", sfdp_usd);
+    println!("  From organic stake:             ${:>10.2}", organic_usd);
+    println!(
+        "  Foundation share of stake:      {:>10.1}%",
+        projection.foundation_share * 100.0
+    );
+    println!(
+        "  Monthly revenue at risk:        ${:>10.2}  ({:.4} SOL)",
+        projection.monthly_sfdp_revenue_usd, projection.monthly_sfdp_revenue_sol
+    );
+    println!(
+        "  Monthly revenue after cliff:    ${:>10.2}",
+        projection.monthly_organic_revenue_usd
+    );
+    match projection.end_date {
+        Some(end) if end > today => println!(
+            "  SFDP stake expected to end:     {} ({} days)",
+            end,
+            (end - today).num_days()
+        ),
+        Some(end) => println!("  SFDP stake expected to end:     {} (passed)", end),
+        None => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn epoch(epoch: u64, total: f64, foundation: f64, revenue: f64) -> SfdpEpoch {
+        SfdpEpoch {
+            epoch,
+            date: None,
+            snapshot_epoch: epoch,
+            total_stake_sol: total,
+            foundation_stake_sol: foundation,
+            modeled_match_sol: modeled_match_sol(total - foundation),
+            revenue_sol: revenue,
+            price_usd: 100.0,
+        }
+    }
+
+    #[test]
+    fn test_match_is_capped() {
+        assert_eq!(modeled_match_sol(40_000.0), 40_000.0);
+        assert_eq!(modeled_match_sol(250_000.0), constants::SFDP_MATCH_CAP_SOL);
+    }
+
+    #[test]
+    fn test_revenue_split_and_projection() {
+        let row = epoch(900, 200_000.0, 50_000.0, 2.0);
+        assert_eq!(row.foundation_share(), 0.25);
+        assert_eq!(row.sfdp_revenue_sol(), 0.5);
+        assert_eq!(row.organic_revenue_sol(), 1.5);
+        assert_eq!(row.modeled_match_sol, 100_000.0);
+
+        let rows = vec![epoch(899, 100_000.0, 0.0, 1.0), row];
+        let projection = project(&rows, None).unwrap();
+        assert_eq!(projection.foundation_share, 0.25);
+        // Trailing average 0.25 SOL/epoch at 15 epochs per month
+        assert!((projection.monthly_sfdp_revenue_sol - 3.75).abs() < 1e-9);
+        assert!((projection.monthly_organic_revenue_usd - 1_875.0).abs() < 1e-9);
+        assert_eq!(project(&[], None), None);
+    }
+}
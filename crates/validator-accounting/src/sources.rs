@@ -0,0 +1,668 @@
+//! External data sources synced on every report run
+//!
+//! Each integration implements `DataSource`: it pulls from its API and writes into the
+//! cache, and the report pipeline reads everything back from the cache afterwards. The
+//! orchestrator only walks the `Registry`, so adding an integration means writing a
+//! source and registering it in `Registry::builtin` - nothing in `run_report_generation`
+//! changes.
+//!
+//! Sources can be switched on or off under `[sources.<name>]`; `sfdp` is the delegator
+//! snapshot the SFDP stake match model (and concentration/churn) is built from. Every
+//! sync updates the source's row in `source_health`, shown by `sources list`.
+//!
+//! HTTP calls go through `request`, which applies one retry policy everywhere:
+//! exponential backoff from `base_delay`, from `rate_limit_delay` instead after an HTTP
+//! 429, and at least `min_interval` between requests to the same source. Attempts and
+//! spacing can be tuned per source in config.
+
+use anyhow::{Context, Result};
+use solana_commitment_config::CommitmentConfig;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+
+use crate::cache::{Cache, IngestionRunLog};
+use crate::config::{Config, FileConfig, SourceSettings};
+use crate::key_rotation::KeyRole;
+use crate::transactions::{self, EpochReward, SolTransfer};
+use crate::{delegators, dune, failed_fees, history_import, notion, offline, ops_fees, rpc, unit_economics};
+
+static SETTINGS: OnceLock<BTreeMap<String, SourceSettings>> = OnceLock::new();
+
+/// When each source may next be called (see `RetryPolicy::min_interval`)
+static NEXT_REQUEST: Mutex<BTreeMap<&'static str, Instant>> = Mutex::new(BTreeMap::new());
+
+/// Install the `[sources.*]` overrides (first call wins; defaults apply if never called)
+pub fn install(settings: BTreeMap<String, SourceSettings>) {
+    let _ = SETTINGS.set(settings);
+}
+
+fn settings(name: &str) -> Option<&'static SourceSettings> {
+    SETTINGS.get().and_then(|s| s.get(name))
+}
+
+// =============================================================================
+// Retry Policy
+// =============================================================================
+
+/// How requests to a source are retried and spaced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts per request, including the first
+    pub max_attempts: u32,
+    /// Backoff before the first retry, doubled for each one after
+    pub base_delay: Duration,
+    /// Backoff base after an HTTP 429
+    pub rate_limit_delay: Duration,
+    /// Minimum spacing between requests to the source
+    pub min_interval: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_secs(2),
+            rate_limit_delay: Duration::from_secs(30),
+            min_interval: Duration::ZERO,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait before retry `retry` (1 = first retry)
+    pub fn delay_for(&self, retry: u32, rate_limited: bool) -> Duration {
+        let base = if rate_limited {
+            self.rate_limit_delay
+        } else {
+            self.base_delay
+        };
+        base * 2u32.pow(retry.saturating_sub(1).min(10))
+    }
+
+    /// Apply `[sources.<name>]` overrides
+    pub fn with_settings(self, settings: Option<&SourceSettings>) -> Self {
+        let Some(settings) = settings else {
+            return self;
+        };
+        Self {
+            max_attempts: settings.max_attempts.unwrap_or(self.max_attempts).max(1),
+            min_interval: settings
+                .min_interval_ms
+                .map(Duration::from_millis)
+                .unwrap_or(self.min_interval),
+            ..self
+        }
+    }
+}
+
+/// Whether an error came from the remote side rate limiting us
+pub fn is_rate_limited(error: &anyhow::Error) -> bool {
+    let message = format!("{:#}", error);
+    message.contains("429") || message.contains("Too Many Requests") || message.contains("Rate limited")
+}
+
+/// Run one request to `source` under its retry policy (`defaults` plus config overrides)
+pub async fn request<T, F, Fut>(source: &'static str, defaults: RetryPolicy, mut call: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    offline::ensure_online(source)?;
+    let policy = defaults.with_settings(settings(source));
+    let mut attempt = 1;
+    loop {
+        throttle(source, policy.min_interval).await;
+        match call().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < policy.max_attempts => {
+                let rate_limited = is_rate_limited(&e);
+                let delay = policy.delay_for(attempt, rate_limited);
+                println!(
+                    "    {} retry {}/{} after {:?}{}: {}",
+                    source,
+                    attempt,
+                    policy.max_attempts - 1,
+                    delay,
+                    if rate_limited { " (rate limited)" } else { "" },
+                    e
+                );
+                sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Reserve the next request slot for `source`, waiting until it comes up
+async fn throttle(source: &'static str, min_interval: Duration) {
+    if min_interval.is_zero() {
+        return;
+    }
+    let wait = {
+        let mut next = NEXT_REQUEST.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let slot = next.get(source).copied().filter(|t| *t > now).unwrap_or(now);
+        next.insert(source, slot + min_interval);
+        slot - now
+    };
+    if !wait.is_zero() {
+        sleep(wait).await;
+    }
+}
+
+// =============================================================================
+// Sources
+// =============================================================================
+
+/// Boxed future returned by `DataSource::sync` (keeps the trait object-safe)
+pub type SyncFuture<'a> = Pin<Box<dyn Future<Output = Result<usize>> + Send + 'a>>;
+
+/// What a sync may read: the run's config, epoch range, and the core on-chain data
+pub struct SyncContext<'a> {
+    pub cache: &'a Cache,
+    pub config: &'a Config,
+    pub file_config: &'a FileConfig,
+    pub start_epoch: u64,
+    pub end_epoch: u64,
+    pub current_epoch: u64,
+    pub no_cache: bool,
+    pub rewards: &'a [EpochReward],
+    pub transfers: &'a [SolTransfer],
+}
+
+impl SyncContext<'_> {
+    /// Last epoch with final data
+    fn completed_end(&self) -> u64 {
+        self.end_epoch.min(self.current_epoch.saturating_sub(1))
+    }
+}
+
+/// An external integration that loads data into the cache
+pub trait DataSource: Send + Sync {
+    /// Name used in config (`[sources.<name>]`), health and run logs
+    fn name(&self) -> &'static str;
+
+    /// One-line description for `sources list`
+    fn description(&self) -> &'static str;
+
+    /// Whether the source runs when config doesn't say
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    /// Why the source can't run with this config (missing section or key)
+    fn missing_config(&self, _file_config: &FileConfig) -> Option<&'static str> {
+        None
+    }
+
+    /// Whether a failed sync aborts the run (otherwise it's logged and the cache is used)
+    fn required(&self) -> bool {
+        false
+    }
+
+    /// Fetch and cache; returns the rows loaded
+    fn sync<'a>(&'a self, ctx: &'a SyncContext<'a>) -> SyncFuture<'a>;
+}
+
+/// Whether a source is switched on, given its default and `[sources.<name>]`
+pub fn is_enabled(enabled_by_default: bool, settings: Option<&SourceSettings>) -> bool {
+    settings.and_then(|s| s.enabled).unwrap_or(enabled_by_default)
+}
+
+/// MEV tips from Jito and any configured tip-distribution programs
+pub struct JitoSource;
+
+impl DataSource for JitoSource {
+    fn name(&self) -> &'static str {
+        "jito"
+    }
+
+    fn description(&self) -> &'static str {
+        "MEV tips (Jito and [[mev.tip_distribution]] programs)"
+    }
+
+    fn sync<'a>(&'a self, ctx: &'a SyncContext<'a>) -> SyncFuture<'a> {
+        Box::pin(async move {
+            let claims = crate::fetch_mev_with_cache(
+                ctx.cache,
+                ctx.config,
+                ctx.start_epoch,
+                ctx.end_epoch,
+                ctx.current_epoch,
+                ctx.no_cache,
+            )
+            .await?;
+            Ok(claims.len())
+        })
+    }
+}
+
+/// Daily SOL/USD prices (CoinGecko, with Binance and Dune fallbacks)
+pub struct CoinGeckoSource;
+
+impl DataSource for CoinGeckoSource {
+    fn name(&self) -> &'static str {
+        "coingecko"
+    }
+
+    fn description(&self) -> &'static str {
+        "Daily SOL/USD prices (Binance and Dune fallbacks)"
+    }
+
+    // Every USD figure depends on prices
+    fn required(&self) -> bool {
+        true
+    }
+
+    fn sync<'a>(&'a self, ctx: &'a SyncContext<'a>) -> SyncFuture<'a> {
+        Box::pin(async move {
+            let prices = crate::fetch_prices_with_cache(
+                ctx.cache,
+                ctx.rewards,
+                ctx.transfers,
+                &ctx.config.coingecko_api_key,
+                ctx.file_config.api_keys.dune.as_deref(),
+                ctx.no_cache,
+            )
+            .await?;
+            Ok(prices.len())
+        })
+    }
+}
+
+/// Contractor hours from the Notion hours log, cached as expenses
+pub struct NotionSource;
+
+impl DataSource for NotionSource {
+    fn name(&self) -> &'static str {
+        "notion"
+    }
+
+    fn description(&self) -> &'static str {
+        "Contractor hours log (Notion)"
+    }
+
+    fn missing_config(&self, file_config: &FileConfig) -> Option<&'static str> {
+        file_config.notion.is_none().then_some("no [notion] section")
+    }
+
+    fn sync<'a>(&'a self, ctx: &'a SyncContext<'a>) -> SyncFuture<'a> {
+        Box::pin(async move {
+            let notion_config = ctx.file_config.notion.as_ref().context("No [notion] section")?;
+            let entries = request(self.name(), RetryPolicy::default(), || {
+                notion::fetch_hours_log(notion_config)
+            })
+            .await?;
+
+            let summary = notion::hours_summary(&entries);
+            println!(
+                "  Found {} entries: {:.1}h total (${:.2}), {:.1}h unpaid (${:.2})",
+                summary.total_entries,
+                summary.total_hours,
+                summary.total_amount,
+                summary.unpaid_hours,
+                summary.unpaid_amount
+            );
+
+            let contractor_expenses = notion::hours_to_expenses(&entries);
+            ctx.cache.sync_notion_expenses(&contractor_expenses).await?;
+            Ok(contractor_expenses.len())
+        })
+    }
+}
+
+/// Vote credits and delegated stake per epoch (unit economics)
+pub struct VoteCreditsSource;
+
+impl DataSource for VoteCreditsSource {
+    fn name(&self) -> &'static str {
+        "vote_credits"
+    }
+
+    fn description(&self) -> &'static str {
+        "Vote credits and delegated stake (RPC)"
+    }
+
+    fn sync<'a>(&'a self, ctx: &'a SyncContext<'a>) -> SyncFuture<'a> {
+        Box::pin(async move {
+            let rpc_client = rpc::new_rpc_client(&ctx.config.rpc_url, CommitmentConfig::confirmed());
+            let rows = unit_economics::fetch(&rpc_client, &ctx.config.vote_account, ctx.current_epoch)?;
+            ctx.cache.store_epoch_performance(&rows).await?;
+            Ok(rows.len())
+        })
+    }
+}
+
+/// Delegator snapshot of the current epoch (SFDP stake match, concentration, churn)
+pub struct SfdpSource;
+
+impl DataSource for SfdpSource {
+    fn name(&self) -> &'static str {
+        "sfdp"
+    }
+
+    fn description(&self) -> &'static str {
+        "Delegator stake snapshot for SFDP match and concentration (RPC)"
+    }
+
+    fn sync<'a>(&'a self, ctx: &'a SyncContext<'a>) -> SyncFuture<'a> {
+        Box::pin(async move {
+            let rpc_client = rpc::new_rpc_client(&ctx.config.rpc_url, CommitmentConfig::confirmed());
+            let rows = delegators::fetch(&rpc_client, &ctx.config.vote_account, ctx.current_epoch)?;
+            ctx.cache.store_delegator_snapshot(ctx.current_epoch, &rows).await?;
+            Ok(rows.len())
+        })
+    }
+}
+
+/// Fees paid on failed non-vote transactions signed by our accounts
+///
+/// Each signer is scanned only back to where its last scan stopped.
+pub struct FailedTxFeesSource;
+
+impl DataSource for FailedTxFeesSource {
+    fn name(&self) -> &'static str {
+        "failed_tx_fees"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fees paid on failed non-vote transactions (RPC)"
+    }
+
+    fn sync<'a>(&'a self, ctx: &'a SyncContext<'a>) -> SyncFuture<'a> {
+        Box::pin(async move {
+            let rpc_client = rpc::new_rpc_client(&ctx.config.rpc_url, CommitmentConfig::confirmed());
+            let mut found = 0;
+            for (_, account) in failed_fees::signing_accounts(ctx.config) {
+                let key = account.to_string();
+                let state = ctx.cache.get_failed_tx_scan_state(&key).await?;
+                let (mut fees, next) = failed_fees::scan(&rpc_client, &account, &state)?;
+                // A retired key's fees count only while it was ours
+                fees.retain(|fee| ctx.config.is_our_account_on(&account, fee.date.as_deref()));
+                ctx.cache.store_failed_tx_fees(&key, &fees, &next).await?;
+                found += fees.len();
+            }
+            Ok(found)
+        })
+    }
+}
+
+/// Fees paid on successful non-vote transactions (claims, withdrawals, management)
+///
+/// Kept apart from vote costs; each signer is scanned only back to where its last scan stopped.
+pub struct OpsFeesSource;
+
+impl DataSource for OpsFeesSource {
+    fn name(&self) -> &'static str {
+        "ops_fees"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fees and priority fees paid on non-vote transactions (RPC)"
+    }
+
+    fn sync<'a>(&'a self, ctx: &'a SyncContext<'a>) -> SyncFuture<'a> {
+        Box::pin(async move {
+            let rpc_client = rpc::new_rpc_client(&ctx.config.rpc_url, CommitmentConfig::confirmed());
+            let mut found = 0;
+            for (role, account) in failed_fees::signing_accounts(ctx.config) {
+                // The identity's votes also touch the vote account, which is how they are skipped
+                let vote_account = (role == KeyRole::Identity).then_some(&ctx.config.vote_account);
+                let key = account.to_string();
+                let state = ctx.cache.get_ops_fee_scan_state(&key).await?;
+                let (mut fees, next) = ops_fees::scan(&rpc_client, &account, vote_account, &state)?;
+                fees.retain(|fee| ctx.config.is_our_account_on(&account, fee.date.as_deref()));
+                ctx.cache.store_ops_fees(&key, &fees, &next).await?;
+                found += fees.len();
+            }
+            Ok(found)
+        })
+    }
+}
+
+/// Stakewiz epoch history, recorded as reward imports and checked against cached rewards
+///
+/// Off by default: the report fetches rewards on-chain. When on, `import status` shows
+/// whether Stakewiz agrees with the chain for every epoch in the run.
+pub struct StakewizSource;
+
+impl DataSource for StakewizSource {
+    fn name(&self) -> &'static str {
+        "stakewiz"
+    }
+
+    fn description(&self) -> &'static str {
+        "Stakewiz epoch history (cross-check of on-chain rewards)"
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn sync<'a>(&'a self, ctx: &'a SyncContext<'a>) -> SyncFuture<'a> {
+        Box::pin(async move {
+            let vote_account = ctx.config.vote_account.to_string();
+            let vote_account = vote_account.as_str();
+            let rows = request(self.name(), RetryPolicy::default(), || {
+                history_import::fetch(history_import::ImportSource::Stakewiz, vote_account, None)
+            })
+            .await?;
+
+            let range = ctx.start_epoch..=ctx.completed_end();
+            let imports: Vec<_> = rows.into_iter().filter(|i| range.contains(&i.epoch)).collect();
+            ctx.cache.store_reward_imports(&imports).await?;
+
+            let onchain: Vec<u64> = ctx
+                .rewards
+                .iter()
+                .map(|r| r.epoch)
+                .filter(|e| imports.iter().any(|i| i.epoch == *e))
+                .collect();
+            ctx.cache.verify_imports_from_cache(&onchain).await?;
+            Ok(imports.len())
+        })
+    }
+}
+
+/// Network median block fees from Dune (client uplift comparison)
+pub struct DuneSource;
+
+impl DataSource for DuneSource {
+    fn name(&self) -> &'static str {
+        "dune"
+    }
+
+    fn description(&self) -> &'static str {
+        "Network median block fees (Dune, uses query credits)"
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn missing_config(&self, file_config: &FileConfig) -> Option<&'static str> {
+        file_config
+            .api_keys
+            .dune
+            .as_deref()
+            .is_none_or(str::is_empty)
+            .then_some("no api_keys.dune")
+    }
+
+    fn sync<'a>(&'a self, ctx: &'a SyncContext<'a>) -> SyncFuture<'a> {
+        Box::pin(async move {
+            let api_key = ctx.file_config.api_keys.dune.clone().context("No api_keys.dune")?;
+            let client = dune::DuneClient::new(api_key, ctx.config);
+
+            // Only epochs after the newest cached median
+            let completed_end = ctx.completed_end();
+            let cached = ctx
+                .cache
+                .get_network_fee_medians(ctx.start_epoch, completed_end)
+                .await?;
+            let from = cached.iter().map(|m| m.epoch + 1).max().unwrap_or(ctx.start_epoch);
+            if from > completed_end {
+                return Ok(0);
+            }
+            let since = transactions::epoch_to_date(from);
+
+            let client = &client;
+            let since = since.as_str();
+            let medians = request(self.name(), RetryPolicy::default(), || {
+                client.fetch_network_fee_medians(since)
+            })
+            .await?;
+            ctx.cache.store_network_fee_medians(&medians).await?;
+            Ok(medians.len())
+        })
+    }
+}
+
+// =============================================================================
+// Registry
+// =============================================================================
+
+/// Sources in sync order
+#[derive(Default)]
+pub struct Registry {
+    sources: Vec<Box<dyn DataSource>>,
+}
+
+impl Registry {
+    /// Every built-in integration
+    pub fn builtin() -> Self {
+        let mut registry = Self::default();
+        registry
+            .register(JitoSource)
+            .register(VoteCreditsSource)
+            .register(SfdpSource)
+            .register(FailedTxFeesSource)
+            .register(OpsFeesSource)
+            .register(NotionSource)
+            .register(StakewizSource)
+            .register(DuneSource)
+            .register(CoinGeckoSource);
+        registry
+    }
+
+    pub fn register(&mut self, source: impl DataSource + 'static) -> &mut Self {
+        self.sources.push(Box::new(source));
+        self
+    }
+
+    pub fn sources(&self) -> impl Iterator<Item = &dyn DataSource> {
+        self.sources.iter().map(|s| s.as_ref())
+    }
+
+    /// Sync every enabled, configured source, recording health and run-log rows
+    ///
+    /// Failures are logged and the run continues on cached data, unless the source
+    /// is `required`.
+    pub async fn sync_all(&self, ctx: &SyncContext<'_>, run_log: &mut IngestionRunLog) -> Result<()> {
+        for source in self.sources() {
+            let name = source.name();
+            if !is_enabled(source.enabled_by_default(), ctx.file_config.sources.get(name)) {
+                continue;
+            }
+            if let Some(reason) = source.missing_config(ctx.file_config) {
+                println!("Skipping {} ({})\n", name, reason);
+                continue;
+            }
+
+            println!("Syncing {}: {}...", name, source.description());
+            match source.sync(ctx).await {
+                Ok(rows) => {
+                    ctx.cache.record_source_success(name, rows).await?;
+                    run_log.record_rows(name, rows);
+                    println!("  {} rows\n", rows);
+                }
+                Err(e) => {
+                    ctx.cache.record_source_failure(name, &format!("{:#}", e)).await?;
+                    if source.required() {
+                        return Err(e.context(format!("{} sync failed", name)));
+                    }
+                    eprintln!("  Warning: {} sync failed: {:#}\n", name, e);
+                    run_log.record_error(name, &e);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A registered source with its effective on/off state
+pub struct SourceStatus {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub enabled: bool,
+    pub missing_config: Option<&'static str>,
+}
+
+/// Status of every registered source under `file_config`
+pub fn statuses(registry: &Registry, file_config: &FileConfig) -> Vec<SourceStatus> {
+    registry
+        .sources()
+        .map(|source| SourceStatus {
+            name: source.name(),
+            description: source.description(),
+            enabled: is_enabled(source.enabled_by_default(), file_config.sources.get(source.name())),
+            missing_config: source.missing_config(file_config),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_and_is_longer_when_rate_limited() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.delay_for(1, false), Duration::from_secs(2));
+        assert_eq!(policy.delay_for(3, false), Duration::from_secs(8));
+        assert_eq!(policy.delay_for(1, true), Duration::from_secs(30));
+        assert_eq!(policy.delay_for(2, true), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_settings_override_policy_and_enabled() {
+        let settings = SourceSettings {
+            enabled: Some(true),
+            max_attempts: Some(0),
+            min_interval_ms: Some(1500),
+        };
+        let policy = RetryPolicy::default().with_settings(Some(&settings));
+        assert_eq!(policy.max_attempts, 1);
+        assert_eq!(policy.min_interval, Duration::from_millis(1500));
+        assert_eq!(policy.base_delay, Duration::from_secs(2));
+        assert_eq!(RetryPolicy::default().with_settings(None), RetryPolicy::default());
+
+        assert!(is_enabled(false, Some(&settings)));
+        assert!(!is_enabled(false, None));
+        assert!(is_enabled(true, Some(&SourceSettings::default())));
+    }
+
+    #[test]
+    fn test_rate_limit_detection() {
+        assert!(is_rate_limited(&anyhow::anyhow!("Rate limited (429)")));
+        assert!(is_rate_limited(
+            &anyhow::anyhow!("HTTP 429 Too Many Requests").context("stakewiz")
+        ));
+        assert!(!is_rate_limited(&anyhow::anyhow!("Jito API returned status: 500")));
+    }
+
+    #[test]
+    fn test_builtin_names_are_unique() {
+        let registry = Registry::builtin();
+        let mut names: Vec<_> = registry.sources().map(|s| s.name()).collect();
+        let count = names.len();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), count);
+    }
+}
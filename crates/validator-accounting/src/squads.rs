@@ -0,0 +1,328 @@
+//! Squads v4 multisig awareness
+//!
+//! When the withdraw authority (or any treasury wallet) is a Squads multisig, funds live in a
+//! vault PDA and move only when a member executes an approved proposal. The member who proposes
+//! or executes pays fees and proposal rent, but the vault is the business account.
+//!
+//! Account layouts (Anchor, borsh-encoded after an 8-byte discriminator):
+//! - `Multisig`: create_key, config_authority, threshold: u16, time_lock: u32,
+//!   transaction_index: u64, stale_transaction_index: u64, ...
+//! - `Proposal`: multisig, transaction_index: u64, status (enum, mostly with an i64 timestamp),
+//!   bump: u8, approved/rejected/cancelled: Vec<Pubkey>
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+use crate::constants;
+
+/// Anchor discriminator of `Proposal` accounts (sha256("account:Proposal")[..8])
+const PROPOSAL_DISCRIMINATOR: [u8; 8] = [26, 94, 189, 187, 116, 136, 53, 33];
+
+/// Anchor discriminator of `Multisig` accounts (sha256("account:Multisig")[..8])
+const MULTISIG_DISCRIMINATOR: [u8; 8] = [224, 116, 121, 186, 68, 161, 79, 236];
+
+/// Squads v4 program ID
+pub fn program_id() -> Pubkey {
+    Pubkey::from_str(constants::SQUADS_V4_PROGRAM).expect("Invalid SQUADS_V4_PROGRAM constant")
+}
+
+/// Was this instruction program the Squads v4 program?
+pub fn is_squads_program(program: &str) -> bool {
+    program == constants::SQUADS_V4_PROGRAM
+}
+
+/// Derive the vault PDA holding the multisig's funds (`vault_index` 0 is the default vault)
+pub fn vault_pda(multisig: &Pubkey, vault_index: u8) -> Pubkey {
+    let (vault, _bump) = Pubkey::find_program_address(
+        &[b"multisig", multisig.as_ref(), b"vault", &[vault_index]],
+        &program_id(),
+    );
+    vault
+}
+
+/// Multisig settings that matter for proposal status
+#[derive(Debug, Clone, Serialize)]
+pub struct MultisigInfo {
+    pub threshold: u16,
+    /// Index of the latest transaction created
+    pub transaction_index: u64,
+    /// Proposals at or below this index can no longer execute (members/threshold changed)
+    pub stale_transaction_index: u64,
+}
+
+/// Proposal status as stored on-chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalStatus {
+    Draft,
+    Active,
+    Rejected,
+    Approved,
+    Executing,
+    Executed,
+    Cancelled,
+}
+
+impl std::fmt::Display for ProposalStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProposalStatus::Draft => write!(f, "draft"),
+            ProposalStatus::Active => write!(f, "active"),
+            ProposalStatus::Rejected => write!(f, "rejected"),
+            ProposalStatus::Approved => write!(f, "approved"),
+            ProposalStatus::Executing => write!(f, "executing"),
+            ProposalStatus::Executed => write!(f, "executed"),
+            ProposalStatus::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+/// One multisig proposal
+#[derive(Debug, Clone, Serialize)]
+pub struct Proposal {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub address: Pubkey,
+    pub transaction_index: u64,
+    pub status: ProposalStatus,
+    /// When the proposal entered its current status (not recorded for `Executing`)
+    pub status_timestamp: Option<i64>,
+    pub approvals: usize,
+    pub rejections: usize,
+}
+
+impl Proposal {
+    /// Pending vs executed label for display: pending proposals have not moved funds yet
+    pub fn label(&self, multisig: &MultisigInfo) -> &'static str {
+        match self.status {
+            ProposalStatus::Executed => "executed",
+            ProposalStatus::Rejected | ProposalStatus::Cancelled => "closed",
+            _ if self.transaction_index <= multisig.stale_transaction_index => "stale",
+            ProposalStatus::Approved => "pending (ready to execute)",
+            _ => "pending",
+        }
+    }
+
+    /// Still waiting to be executed (not executed, rejected, cancelled or stale)
+    pub fn is_pending(&self, multisig: &MultisigInfo) -> bool {
+        self.label(multisig).starts_with("pending")
+    }
+}
+
+fn serialize_pubkey<S: serde::Serializer>(pubkey: &Pubkey, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(&pubkey.to_string())
+}
+
+/// Fetch the multisig account's threshold and transaction indexes
+pub fn fetch_multisig(client: &RpcClient, multisig: &Pubkey) -> Result<MultisigInfo> {
+    let account = client
+        .get_account(multisig)
+        .with_context(|| format!("Failed to fetch Squads multisig {}", multisig))?;
+    anyhow::ensure!(
+        account.owner == program_id(),
+        "{} is not a Squads v4 multisig (owner {})",
+        multisig,
+        account.owner
+    );
+    parse_multisig(&account.data)
+}
+
+/// Fetch all open proposal accounts for a multisig, ordered by transaction index
+///
+/// Executed proposals whose accounts were closed to reclaim rent no longer show up here;
+/// their transfers are still in the ledger (executed via the Squads program).
+pub fn fetch_proposals(client: &RpcClient, multisig: &Pubkey) -> Result<Vec<Proposal>> {
+    let filters = vec![
+        RpcFilterType::Memcmp(Memcmp::new(
+            0,
+            MemcmpEncodedBytes::Bytes(PROPOSAL_DISCRIMINATOR.to_vec()),
+        )),
+        RpcFilterType::Memcmp(Memcmp::new(8, MemcmpEncodedBytes::Base58(multisig.to_string()))),
+    ];
+    let config = RpcProgramAccountsConfig {
+        filters: Some(filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(solana_account_decoder::UiAccountEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    #[allow(deprecated)]
+    let accounts = client
+        .get_program_accounts_with_config(&program_id(), config)
+        .context("Failed to fetch Squads proposals")?;
+
+    let mut proposals = Vec::new();
+    for (address, account) in accounts {
+        match parse_proposal(address, &account.data) {
+            Ok(proposal) => proposals.push(proposal),
+            Err(e) => eprintln!("Warning: Failed to parse Squads proposal {}: {}", address, e),
+        }
+    }
+    proposals.sort_by_key(|p| p.transaction_index);
+    Ok(proposals)
+}
+
+fn parse_multisig(data: &[u8]) -> Result<MultisigInfo> {
+    anyhow::ensure!(
+        data.get(..8) == Some(&MULTISIG_DISCRIMINATOR[..]),
+        "Not a Squads multisig account"
+    );
+    // discriminator (8) + create_key (32) + config_authority (32)
+    let mut reader = Reader { data, offset: 72 };
+    let threshold = reader.u16()?;
+    let _time_lock = reader.u32()?;
+    let transaction_index = reader.u64()?;
+    let stale_transaction_index = reader.u64()?;
+    Ok(MultisigInfo {
+        threshold,
+        transaction_index,
+        stale_transaction_index,
+    })
+}
+
+fn parse_proposal(address: Pubkey, data: &[u8]) -> Result<Proposal> {
+    anyhow::ensure!(
+        data.get(..8) == Some(&PROPOSAL_DISCRIMINATOR[..]),
+        "Not a Squads proposal account"
+    );
+    // discriminator (8) + multisig (32)
+    let mut reader = Reader { data, offset: 40 };
+    let transaction_index = reader.u64()?;
+    let (status, status_timestamp) = match reader.u8()? {
+        0 => (ProposalStatus::Draft, Some(reader.i64()?)),
+        1 => (ProposalStatus::Active, Some(reader.i64()?)),
+        2 => (ProposalStatus::Rejected, Some(reader.i64()?)),
+        3 => (ProposalStatus::Approved, Some(reader.i64()?)),
+        4 => (ProposalStatus::Executing, None),
+        5 => (ProposalStatus::Executed, Some(reader.i64()?)),
+        6 => (ProposalStatus::Cancelled, Some(reader.i64()?)),
+        other => anyhow::bail!("Unknown proposal status {}", other),
+    };
+    let _bump = reader.u8()?;
+    let approvals = reader.pubkey_vec_len()?;
+    let rejections = reader.pubkey_vec_len()?;
+    Ok(Proposal {
+        address,
+        transaction_index,
+        status,
+        status_timestamp,
+        approvals,
+        rejections,
+    })
+}
+
+/// Minimal little-endian borsh reader for the fields we need
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let bytes = self
+            .data
+            .get(self.offset..self.offset + N)
+            .context("Squads account data too short")?;
+        self.offset += N;
+        Ok(bytes.try_into().expect("slice length checked"))
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take()?))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+
+    fn i64(&mut self) -> Result<i64> {
+        Ok(i64::from_le_bytes(self.take()?))
+    }
+
+    /// Read a `Vec<Pubkey>` length and skip its elements
+    fn pubkey_vec_len(&mut self) -> Result<usize> {
+        let len = self.u32()? as usize;
+        let end = self.offset + len * 32;
+        anyhow::ensure!(end <= self.data.len(), "Squads account data too short");
+        self.offset = end;
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proposal_data(transaction_index: u64, status: u8, approvals: u32) -> Vec<u8> {
+        let mut data = PROPOSAL_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(&transaction_index.to_le_bytes());
+        data.push(status);
+        if status != 4 {
+            data.extend_from_slice(&1_765_843_200i64.to_le_bytes());
+        }
+        data.push(255); // bump
+        data.extend_from_slice(&approvals.to_le_bytes());
+        for _ in 0..approvals {
+            data.extend_from_slice(Pubkey::new_unique().as_ref());
+        }
+        data.extend_from_slice(&0u32.to_le_bytes()); // rejected
+        data.extend_from_slice(&0u32.to_le_bytes()); // cancelled
+        data
+    }
+
+    #[test]
+    fn test_parse_proposal_and_label() {
+        let multisig = MultisigInfo {
+            threshold: 2,
+            transaction_index: 7,
+            stale_transaction_index: 3,
+        };
+
+        let active = parse_proposal(Pubkey::new_unique(), &proposal_data(6, 1, 1)).unwrap();
+        assert_eq!(active.transaction_index, 6);
+        assert_eq!(active.status, ProposalStatus::Active);
+        assert_eq!(active.status_timestamp, Some(1_765_843_200));
+        assert_eq!(active.approvals, 1);
+        assert_eq!(active.label(&multisig), "pending");
+        assert!(active.is_pending(&multisig));
+
+        let approved = parse_proposal(Pubkey::new_unique(), &proposal_data(7, 3, 2)).unwrap();
+        assert_eq!(approved.label(&multisig), "pending (ready to execute)");
+
+        let executed = parse_proposal(Pubkey::new_unique(), &proposal_data(5, 5, 2)).unwrap();
+        assert_eq!(executed.label(&multisig), "executed");
+        assert!(!executed.is_pending(&multisig));
+
+        // Approved before the member set changed: can no longer execute
+        let stale = parse_proposal(Pubkey::new_unique(), &proposal_data(2, 3, 2)).unwrap();
+        assert_eq!(stale.label(&multisig), "stale");
+
+        let executing = parse_proposal(Pubkey::new_unique(), &proposal_data(4, 4, 2)).unwrap();
+        assert_eq!(executing.status_timestamp, None);
+    }
+
+    #[test]
+    fn test_parse_rejects_other_accounts() {
+        let mut data = proposal_data(1, 1, 0);
+        data[0] ^= 0xff;
+        assert!(parse_proposal(Pubkey::new_unique(), &data).is_err());
+        assert!(parse_multisig(&data).is_err());
+    }
+}
@@ -0,0 +1,158 @@
+//! `--strict` verification for unattended runs (cron, CI)
+//!
+//! A normal run prints warnings and exits 0 as long as reports were written. With `--strict`,
+//! the problems worth paging on turn into a `StrictFailure` error that `main` maps to a
+//! dedicated exit code, so a wrapper script can tell "reconciliation is off" from "an epoch
+//! has no data" from "USD values used the fallback price" without parsing output.
+
+use crate::cache::EpochCoverage;
+use crate::constants;
+use crate::positions::{self, ReconciliationResult, ReconciliationStatus};
+
+/// A condition that fails a `--strict` run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StrictViolation {
+    /// Actual balance differs from expected by more than `RECONCILIATION_TOLERANCE_LAMPORTS`
+    ReconciliationVariance { difference_lamports: i64 },
+    /// Epochs in the window with at least one applicable source missing
+    MissingEpochData { epochs: Vec<u64> },
+    /// Ledger rows valued with `FALLBACK_SOL_PRICE`
+    FallbackPrices { rows: usize },
+}
+
+impl StrictViolation {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            StrictViolation::ReconciliationVariance { .. } => constants::EXIT_STRICT_RECONCILIATION_VARIANCE,
+            StrictViolation::MissingEpochData { .. } => constants::EXIT_STRICT_MISSING_EPOCH_DATA,
+            StrictViolation::FallbackPrices { .. } => constants::EXIT_STRICT_FALLBACK_PRICES,
+        }
+    }
+}
+
+impl std::fmt::Display for StrictViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StrictViolation::ReconciliationVariance { difference_lamports } => write!(
+                f,
+                "reconciliation variance of {} SOL exceeds tolerance of {} SOL",
+                positions::signed_lamports_to_sol_string(*difference_lamports, 4),
+                positions::lamports_to_sol_string(constants::RECONCILIATION_TOLERANCE_LAMPORTS.unsigned_abs(), 4)
+            ),
+            StrictViolation::MissingEpochData { epochs } => {
+                let shown: Vec<String> = epochs.iter().take(10).map(ToString::to_string).collect();
+                write!(f, "{} epoch(s) missing data: {}", epochs.len(), shown.join(", "))?;
+                if epochs.len() > shown.len() {
+                    write!(f, ", ...")?;
+                }
+                Ok(())
+            }
+            StrictViolation::FallbackPrices { rows } => {
+                write!(f, "{} ledger row(s) valued with the fallback SOL price", rows)
+            }
+        }
+    }
+}
+
+/// Error returned by a `--strict` run that completed but found violations
+#[derive(Debug)]
+pub struct StrictFailure(pub Vec<StrictViolation>);
+
+impl StrictFailure {
+    /// Fail with `violations`, or succeed if there are none
+    pub fn check(violations: Vec<StrictViolation>) -> anyhow::Result<()> {
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(StrictFailure(violations).into())
+        }
+    }
+
+    /// Exit code of the most severe violation (reconciliation, then missing data, then prices)
+    pub fn exit_code(&self) -> i32 {
+        self.0
+            .iter()
+            .map(StrictViolation::exit_code)
+            .min()
+            .unwrap_or(constants::EXIT_STRICT_RECONCILIATION_VARIANCE)
+    }
+}
+
+impl std::fmt::Display for StrictFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "strict check failed")?;
+        for violation in &self.0 {
+            write!(f, "\n  - {}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for StrictFailure {}
+
+pub fn check_reconciliation(result: &ReconciliationResult) -> Option<StrictViolation> {
+    (result.status == ReconciliationStatus::Variance).then_some(StrictViolation::ReconciliationVariance {
+        difference_lamports: result.difference_lamports,
+    })
+}
+
+pub fn check_epoch_coverage(coverage: &[EpochCoverage]) -> Option<StrictViolation> {
+    let epochs: Vec<u64> = coverage
+        .iter()
+        .filter(|row| !row.missing_sources().is_empty())
+        .map(|row| row.epoch)
+        .collect();
+    (!epochs.is_empty()).then_some(StrictViolation::MissingEpochData { epochs })
+}
+
+pub fn check_fallback_prices(rows: usize) -> Option<StrictViolation> {
+    (rows > 0).then_some(StrictViolation::FallbackPrices { rows })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_code_prefers_reconciliation_variance() {
+        let failure = StrictFailure(vec![
+            StrictViolation::FallbackPrices { rows: 3 },
+            StrictViolation::ReconciliationVariance {
+                difference_lamports: 5_000_000,
+            },
+            StrictViolation::MissingEpochData { epochs: vec![900] },
+        ]);
+        assert_eq!(failure.exit_code(), constants::EXIT_STRICT_RECONCILIATION_VARIANCE);
+
+        let failure = StrictFailure(vec![
+            StrictViolation::FallbackPrices { rows: 3 },
+            StrictViolation::MissingEpochData { epochs: vec![900] },
+        ]);
+        assert_eq!(failure.exit_code(), constants::EXIT_STRICT_MISSING_EPOCH_DATA);
+    }
+
+    #[test]
+    fn test_epoch_coverage_ignores_sources_that_do_not_apply() {
+        let complete_before_bam = EpochCoverage {
+            epoch: constants::BAM_FIRST_EPOCH - 1,
+            rewards: true,
+            leader_fees: true,
+            mev: true,
+            bam: false,
+            vote_costs: true,
+            doublezero: true,
+        };
+        assert_eq!(check_epoch_coverage(&[complete_before_bam.clone()]), None);
+
+        let missing_rewards = EpochCoverage {
+            rewards: false,
+            ..complete_before_bam
+        };
+        assert_eq!(
+            check_epoch_coverage(&[missing_rewards]),
+            Some(StrictViolation::MissingEpochData {
+                epochs: vec![constants::BAM_FIRST_EPOCH - 1]
+            })
+        );
+    }
+}
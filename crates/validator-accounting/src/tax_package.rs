@@ -0,0 +1,477 @@
+//! Tax-year workpaper package (`tax-package --year 2025`)
+//!
+//! Bundles what a CPA asks for at year end into one zip: the income, expense and treasury
+//! ledgers cut to the tax year, the withdrawal-based tax report with its Schedule C mapping,
+//! a book-to-tax reconciliation, the glossary, and a cover-sheet PDF stating the
+//! methodology and assumptions behind the numbers. Everything comes from the cache, so the
+//! package is reproducible from an archive.
+//!
+//! The cover sheet is a plain text PDF written by hand (Helvetica, US Letter), which keeps
+//! a PDF library out of the dependency tree.
+
+use anyhow::{Context, Result};
+use csv::{ReaderBuilder, Writer};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::period::ReportPeriod;
+use crate::report_metadata::COMMENT_PREFIX;
+use crate::reports::SummaryTotals;
+use crate::tax_report::TaxRow;
+
+pub const COVER_SHEET_FILENAME: &str = "cover_sheet.pdf";
+pub const RECONCILIATION_FILENAME: &str = "book_tax_reconciliation.csv";
+
+const PAGE_WIDTH: f64 = 612.0;
+const PAGE_HEIGHT: f64 = 792.0;
+const MARGIN: f64 = 54.0;
+const FONT_SIZE: f64 = 10.0;
+const LEADING: f64 = 14.0;
+/// Characters per line at 10pt Helvetica inside the margins
+const WRAP_COLUMNS: usize = 100;
+
+/// One line of the cover sheet
+#[derive(Debug, Clone, PartialEq)]
+pub enum Line {
+    Title(String),
+    Heading(String),
+    Text(String),
+    Blank,
+}
+
+/// Keep the metadata block, the header and the rows whose first column (the date) falls
+/// in `period`
+///
+/// Returns the filtered CSV and the number of rows kept.
+pub fn filter_csv_by_date(csv_text: &str, period: &ReportPeriod) -> Result<(String, usize)> {
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(csv_text.as_bytes());
+    let mut wtr = Writer::from_writer(Vec::new());
+    let mut kept = 0;
+    let mut header_written = false;
+    for record in reader.records() {
+        let record = record?;
+        let is_metadata = !header_written && record.get(0).is_some_and(|f| f.starts_with(COMMENT_PREFIX));
+        if is_metadata || !header_written {
+            header_written = !is_metadata;
+            wtr.write_record(&record)?;
+        } else if record.get(0).is_some_and(|date| period.contains(date)) {
+            wtr.write_record(&record)?;
+            kept += 1;
+        }
+    }
+    let bytes = wtr
+        .into_inner()
+        .map_err(|e| anyhow::anyhow!("Failed to flush CSV: {}", e))?;
+    Ok((String::from_utf8(bytes)?, kept))
+}
+
+/// Rewrite a ledger CSV in place with only the rows dated inside `period`
+pub fn filter_ledger(path: &Path, period: &ReportPeriod) -> Result<usize> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let (filtered, kept) = filter_csv_by_date(&text, period)?;
+    std::fs::write(path, filtered)?;
+    Ok(kept)
+}
+
+/// Tax report totals by entry type
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaxTotals {
+    pub revenue_usd: f64,
+    pub expenses_usd: f64,
+    pub reimbursements_usd: f64,
+    pub return_of_capital_usd: f64,
+}
+
+impl TaxTotals {
+    pub fn from_rows(rows: &[TaxRow]) -> Self {
+        let mut t = Self::default();
+        for row in rows {
+            match row.entry_type.as_str() {
+                "Revenue" => t.revenue_usd += row.usd_value,
+                "Expense" => t.expenses_usd += row.usd_value,
+                "Reimbursement" => t.reimbursements_usd += row.usd_value,
+                "Return of Capital" => t.return_of_capital_usd += row.usd_value,
+                _ => {}
+            }
+        }
+        t
+    }
+
+    /// Net profit on the withdrawal basis (reimbursements offset expenses)
+    pub fn net_usd(&self) -> f64 {
+        self.revenue_usd - (self.expenses_usd - self.reimbursements_usd)
+    }
+}
+
+/// One line of the book-to-tax reconciliation
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconciliationLine {
+    pub line: &'static str,
+    pub book_usd: f64,
+    pub tax_usd: f64,
+    pub note: &'static str,
+}
+
+impl ReconciliationLine {
+    pub fn difference_usd(&self) -> f64 {
+        self.book_usd - self.tax_usd
+    }
+}
+
+/// Tie the accrual ledgers (book) to the withdrawal-based tax report
+pub fn book_to_tax(summary: &SummaryTotals, tax: &TaxTotals) -> Vec<ReconciliationLine> {
+    let tax_expenses_net = tax.expenses_usd - tax.reimbursements_usd;
+    vec![
+        ReconciliationLine {
+            line: "Revenue",
+            book_usd: summary.total_revenue_usd,
+            tax_usd: tax.revenue_usd,
+            note: "Book: earned on receipt. Tax: recognized when distributed to the owner. \
+                   The difference is income retained in (or drawn from prior years out of) the treasury.",
+        },
+        ReconciliationLine {
+            line: "Expenses (net of SFDP reimbursements)",
+            book_usd: summary.total_expenses_usd,
+            tax_usd: tax_expenses_net,
+            note: "Both deduct expenses when incurred; differences come from DoubleZero accrual \
+                   timing and SFDP reimbursement presentation.",
+        },
+        ReconciliationLine {
+            line: "Net profit",
+            book_usd: summary.net_profit_usd,
+            tax_usd: tax.net_usd(),
+            note: "",
+        },
+        ReconciliationLine {
+            line: "Return of capital",
+            book_usd: 0.0,
+            tax_usd: tax.return_of_capital_usd,
+            note: "Withdrawals treated as a return of contributed capital, not income.",
+        },
+    ]
+}
+
+/// Write the book-to-tax reconciliation CSV
+pub fn write_reconciliation(output_dir: &Path, lines: &[ReconciliationLine]) -> Result<PathBuf> {
+    let path = output_dir.join(RECONCILIATION_FILENAME);
+    let mut wtr = Writer::from_path(&path)?;
+    wtr.write_record(["Line", "Book (USD)", "Tax (USD)", "Difference (USD)", "Notes"])?;
+    for l in lines {
+        wtr.write_record([
+            l.line,
+            &format!("{:.2}", l.book_usd),
+            &format!("{:.2}", l.tax_usd),
+            &format!("{:.2}", l.difference_usd()),
+            l.note,
+        ])?;
+    }
+    wtr.flush()?;
+    Ok(path)
+}
+
+/// Cover sheet: scope, headline figures, methodology, assumptions and contents
+pub fn cover_sheet(
+    config: &Config,
+    period: &ReportPeriod,
+    summary: &SummaryTotals,
+    tax: &TaxTotals,
+    fallback_rows: usize,
+    files: &[String],
+) -> Vec<Line> {
+    let money = |v: f64| format!("${:.2}", v);
+    let mut lines = vec![
+        Line::Title(format!("Block Parliament - Tax Workpapers {}", period.label)),
+        Line::Text(format!("Period: {} to {}", period.start, period.end)),
+        Line::Text(format!(
+            "Prepared: {} by validator-accounting {}",
+            chrono::Utc::now().format("%Y-%m-%d %H:%M UTC"),
+            env!("CARGO_PKG_VERSION")
+        )),
+        Line::Text(format!("Vote account: {}", config.vote_account)),
+        Line::Text(format!("Identity: {}", config.identity)),
+        Line::Blank,
+        Line::Heading("Summary (USD)".to_string()),
+        Line::Text(format!("Tax revenue (distributions): {}", money(tax.revenue_usd))),
+        Line::Text(format!("Tax expenses: {}", money(tax.expenses_usd))),
+        Line::Text(format!("SFDP reimbursements: {}", money(tax.reimbursements_usd))),
+        Line::Text(format!("Tax net profit: {}", money(tax.net_usd()))),
+        Line::Text(format!("Return of capital: {}", money(tax.return_of_capital_usd))),
+        Line::Text(format!("Book revenue (earned): {}", money(summary.total_revenue_usd))),
+        Line::Text(format!("Book net profit: {}", money(summary.net_profit_usd))),
+        Line::Blank,
+        Line::Heading("Methodology".to_string()),
+        Line::Text(
+            "Revenue is recognized when SOL is distributed from the business accounts (vote account and \
+             identity) to the owner or other external beneficiaries, valued at the SOL/USD price on the \
+             withdrawal date. Transfers between the business accounts are ignored."
+                .to_string(),
+        ),
+        Line::Text(
+            "Expenses are deducted in the period incurred: vote transaction fees per epoch, DoubleZero fees \
+             as accrued, and off-chain costs (hosting, contractors, software) on their invoice dates. SFDP \
+             vote-fee reimbursements are reported separately and offset vote fees."
+                .to_string(),
+        ),
+        Line::Text(
+            "The ledgers record income on receipt (book basis); book_tax_reconciliation.csv ties them to the \
+             tax report."
+                .to_string(),
+        ),
+        Line::Blank,
+        Line::Heading("Assumptions".to_string()),
+        Line::Text(
+            "SOL/USD prices are daily closes (CoinGecko, then Binance and Dune); a missing date uses the \
+             nearest cached date."
+                .to_string(),
+        ),
+    ];
+    if config.hourly_pricing_enabled {
+        lines.push(Line::Text(format!(
+            "Withdrawals of {} SOL or more are valued at the hourly price nearest the block time.",
+            config.hourly_min_withdrawal_sol
+        )));
+    }
+    lines.push(Line::Text(format!(
+        "Block times are attributed to dates in {}.",
+        config.accounting_timezone.name()
+    )));
+    lines.push(Line::Text(if config.fiscal_year_start_month == 1 {
+        "The tax year is the calendar year.".to_string()
+    } else {
+        format!("The fiscal year starts in month {}.", config.fiscal_year_start_month)
+    }));
+    if config.income_lots_enabled {
+        lines.push(Line::Text(
+            "Income lots carry cost basis at fair market value on receipt; dispositions are matched first-in \
+             first-out."
+                .to_string(),
+        ));
+    }
+    if fallback_rows > 0 {
+        lines.push(Line::Text(format!(
+            "WARNING: {} ledger row(s) were valued with the hardcoded fallback price; see price warnings \
+             before relying on the figures.",
+            fallback_rows
+        )));
+    }
+    lines.push(Line::Text(
+        "Amounts are rounded to cents; the package is not tax advice.".to_string(),
+    ));
+    lines.push(Line::Blank);
+    lines.push(Line::Heading("Contents".to_string()));
+    lines.extend(files.iter().map(|f| Line::Text(format!("- {}", f))));
+    lines
+}
+
+/// Greedy word wrap at `width` characters
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.len() + 1 + word.len() > width {
+            out.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() || out.is_empty() {
+        out.push(current);
+    }
+    out
+}
+
+/// Escape a string for a PDF literal, replacing what WinAnsi Helvetica can't show
+fn pdf_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | '(' | ')' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\u{2013}' | '\u{2014}' | '\u{2212}' => out.push('-'),
+            '\u{2018}' | '\u{2019}' => out.push('\''),
+            '\u{201C}' | '\u{201D}' => out.push('"'),
+            '\u{00B7}' | '\u{2022}' => out.push('*'),
+            c if c.is_ascii() && !c.is_ascii_control() => out.push(c),
+            _ => out.push('?'),
+        }
+    }
+    out
+}
+
+/// Render `lines` as a paginated PDF document
+pub fn render_pdf(lines: &[Line]) -> Vec<u8> {
+    // (font resource, size, text) per rendered row; None is a blank row
+    let mut rows: Vec<Option<(&str, f64, String)>> = Vec::new();
+    for line in lines {
+        match line {
+            Line::Title(t) => rows.push(Some(("F2", 14.0, t.clone()))),
+            Line::Heading(t) => rows.push(Some(("F2", 11.0, t.clone()))),
+            Line::Text(t) => rows.extend(wrap(t, WRAP_COLUMNS).into_iter().map(|l| Some(("F1", FONT_SIZE, l)))),
+            Line::Blank => rows.push(None),
+        }
+    }
+    let per_page = ((PAGE_HEIGHT - 2.0 * MARGIN) / LEADING) as usize;
+    let pages: Vec<&[Option<(&str, f64, String)>]> = if rows.is_empty() {
+        vec![&[]]
+    } else {
+        rows.chunks(per_page).collect()
+    };
+
+    // Objects: 1 catalog, 2 page tree, 3-4 fonts, then a page and its content stream per page
+    let mut objects: Vec<Vec<u8>> = Vec::new();
+    let kids: Vec<String> = (0..pages.len()).map(|i| format!("{} 0 R", 5 + 2 * i)).collect();
+    objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+    objects.push(format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), pages.len()).into_bytes());
+    objects.push(b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_vec());
+    objects.push(b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>".to_vec());
+    for (i, page) in pages.iter().enumerate() {
+        let mut content = String::from("BT\n");
+        content.push_str(&format!("{:.0} {:.0} Td\n", MARGIN, PAGE_HEIGHT - MARGIN));
+        for row in page.iter() {
+            if let Some((font, size, text)) = row {
+                content.push_str(&format!("/{} {:.0} Tf\n({}) Tj\n", font, size, pdf_escape(text)));
+            }
+            content.push_str(&format!("0 -{:.0} Td\n", LEADING));
+        }
+        content.push_str("ET\n");
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {:.0} {:.0}] \
+                 /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                PAGE_WIDTH,
+                PAGE_HEIGHT,
+                6 + 2 * i
+            )
+            .into_bytes(),
+        );
+        objects.push(format!("<< /Length {} >>\nstream\n{}endstream", content.len(), content).into_bytes());
+    }
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, body) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+        pdf.extend_from_slice(body);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+    let xref_offset = pdf.len();
+    pdf.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes());
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref_offset
+        )
+        .as_bytes(),
+    );
+    pdf
+}
+
+/// Zip `files` (names relative to `dir`) into `path`
+pub fn write_zip(path: &Path, dir: &Path, files: &[String]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = std::fs::File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    for name in files {
+        let bytes = std::fs::read(dir.join(name)).with_context(|| format!("Failed to read {}", name))?;
+        zip.start_file(name.as_str(), options)?;
+        zip.write_all(&bytes)?;
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tax_row(entry_type: &str, usd_value: f64) -> TaxRow {
+        TaxRow {
+            date: "2025-03-01".to_string(),
+            entry_type: entry_type.to_string(),
+            category: String::new(),
+            description: String::new(),
+            sol_amount: None,
+            sol_price_usd: None,
+            price_source: None,
+            usd_value,
+            destination: String::new(),
+            tx_signature: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_filter_csv_by_date_keeps_header_and_period_rows() {
+        let period = ReportPeriod::year(2025, 1).unwrap();
+        let csv_text =
+            "Date (YYYY-MM-DD),Amount\n2024-12-31,1\n2025-01-01,2\n\"2025-12-31\",3\nunknown,4\n2026-01-01,5\n";
+        let (filtered, kept) = filter_csv_by_date(csv_text, &period).unwrap();
+        assert_eq!(kept, 2);
+        assert_eq!(filtered, "Date (YYYY-MM-DD),Amount\n2025-01-01,2\n2025-12-31,3\n");
+
+        let stamped = format!("# Generated (UTC),2026-01-02 03:04:05\n{}", csv_text);
+        let (filtered, kept) = filter_csv_by_date(&stamped, &period).unwrap();
+        assert_eq!(kept, 2);
+        assert!(filtered.starts_with("# Generated (UTC),2026-01-02 03:04:05\nDate (YYYY-MM-DD),Amount\n"));
+    }
+
+    #[test]
+    fn test_tax_totals_net_profit() {
+        let totals = TaxTotals::from_rows(&[
+            tax_row("Revenue", 1_000.0),
+            tax_row("Expense", 300.0),
+            tax_row("Reimbursement", 100.0),
+            tax_row("Return of Capital", 50.0),
+        ]);
+        assert_eq!(totals.net_usd(), 800.0);
+        assert_eq!(totals.return_of_capital_usd, 50.0);
+    }
+
+    #[test]
+    fn test_wrap_and_escape() {
+        assert_eq!(wrap("aaa bbb ccc", 7), vec!["aaa bbb", "ccc"]);
+        assert_eq!(wrap("", 7), vec![""]);
+        assert_eq!(pdf_escape("Net (USD) — 5\\"), "Net \\(USD\\) - 5\\\\");
+    }
+
+    #[test]
+    fn test_render_pdf_xref_points_at_objects() {
+        let lines: Vec<Line> = (0..120).map(|i| Line::Text(format!("line {}", i))).collect();
+        let pdf = render_pdf(&lines);
+        let text = String::from_utf8(pdf.clone()).unwrap();
+        assert!(text.starts_with("%PDF-1.4\n"));
+        assert!(text.ends_with("%%EOF\n"));
+        // 120 lines at 48 per page
+        assert!(text.contains("/Count 3 >>"));
+
+        let xref_offset: usize = text.lines().rev().nth(1).unwrap().parse().unwrap();
+        assert!(text[xref_offset..].starts_with("xref\n"));
+        let entries: Vec<usize> = text[xref_offset..]
+            .lines()
+            .skip(3)
+            .take_while(|l| l.ends_with(" n "))
+            .map(|l| l[..10].parse().unwrap())
+            .collect();
+        assert_eq!(entries.len(), 10);
+        for (i, offset) in entries.into_iter().enumerate() {
+            assert!(text[offset..].starts_with(&format!("{} 0 obj\n", i + 1)));
+        }
+    }
+}
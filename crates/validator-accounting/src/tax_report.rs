@@ -12,8 +12,9 @@
 //! This is a parallel, non-destructive feature that does not modify existing reports.
 
 use anyhow::Result;
-use chrono::{Datelike, NaiveDate};
+use chrono::NaiveDate;
 use csv::Writer;
+use solana_sdk::pubkey::Pubkey;
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
@@ -21,7 +22,9 @@ use crate::addresses;
 use crate::config::Config;
 use crate::doublezero::DoubleZeroFee;
 use crate::expenses::Expense;
-use crate::prices::{PriceCache, get_price};
+use crate::key_rotation::KeyRole;
+use crate::period::ReportPeriod;
+use crate::prices::{HourlyPriceCache, PriceCache, PriceSource, get_event_price, get_price_with_source};
 use crate::transactions::{CategorizedTransfers, SolTransfer};
 use crate::vote_costs::EpochVoteCost;
 
@@ -38,6 +41,7 @@ pub struct TaxReportData<'a> {
     pub vote_costs: &'a [EpochVoteCost],
     pub expenses: &'a [Expense],
     pub prices: &'a PriceCache,
+    pub hourly_prices: &'a HourlyPriceCache,
 }
 
 /// A single row in the tax report CSV.
@@ -49,40 +53,20 @@ pub struct TaxRow {
     pub description: String,
     pub sol_amount: Option<f64>,
     pub sol_price_usd: Option<f64>,
+    pub price_source: Option<PriceSource>, // None for USD-denominated rows
     pub usd_value: f64,
     pub destination: String,  // for withdrawals
     pub tx_signature: String, // for on-chain events
 }
 
 /// Build normalized tax rows used by CSV output and HTML tax timeline rendering.
-pub fn build_tax_rows(data: &TaxReportData, year_filter: Option<i32>) -> (Vec<TaxRow>, usize) {
+pub fn build_tax_rows(data: &TaxReportData, period: Option<&ReportPeriod>) -> (Vec<TaxRow>, usize) {
     let mut rows = Vec::new();
     let mut skipped_unknown_dates: usize = 0;
 
     // ── Revenue: qualifying distribution outflows crossing tax boundary,
-    // offset by seeding capital. Use both categorized buckets while applying
-    // the same candidate policy in one place.
-    let mut all_outgoing: Vec<&SolTransfer> = data
-        .categorized
-        .withdrawals
-        .iter()
-        .filter(|t| is_taxable_external_withdrawal_candidate(t, data.config))
-        .collect();
-    for t in &data.categorized.other {
-        if is_taxable_external_withdrawal_candidate(t, data.config) {
-            all_outgoing.push(t);
-        }
-    }
-    let total_seeded_sol: f64 = (data.config.initial_treasury_lamports as f64 / 1e9)
-        + data.categorized.seeding.iter().map(|s| s.amount_sol).sum::<f64>();
-    add_withdrawal_rows(
-        &mut rows,
-        &all_outgoing,
-        data.prices,
-        year_filter,
-        &mut skipped_unknown_dates,
-        total_seeded_sol,
-    );
+    // offset by seeding capital.
+    add_withdrawal_rows(&mut rows, data, period, &mut skipped_unknown_dates);
 
     // ── Expenses: vote fees (SOL burned on-chain, net of SFDP) ─────────
     add_vote_cost_rows(
@@ -90,7 +74,7 @@ pub fn build_tax_rows(data: &TaxReportData, year_filter: Option<i32>) -> (Vec<Ta
         data.vote_costs,
         data.prices,
         data.config,
-        year_filter,
+        period,
         &mut skipped_unknown_dates,
     );
 
@@ -99,12 +83,12 @@ pub fn build_tax_rows(data: &TaxReportData, year_filter: Option<i32>) -> (Vec<Ta
         &mut rows,
         data.doublezero_fees,
         data.prices,
-        year_filter,
+        period,
         &mut skipped_unknown_dates,
     );
 
     // ── Expenses: off-chain costs (hosting, contractors, hardware, etc.)
-    add_offchain_expense_rows(&mut rows, data.expenses, year_filter, &mut skipped_unknown_dates);
+    add_offchain_expense_rows(&mut rows, data.expenses, period, &mut skipped_unknown_dates);
 
     // Sort all rows by date, then revenue before expenses
     rows.sort_by(|a, b| {
@@ -114,21 +98,59 @@ pub fn build_tax_rows(data: &TaxReportData, year_filter: Option<i32>) -> (Vec<Ta
     (rows, skipped_unknown_dates)
 }
 
+/// Seed capital (SOL) not yet returned by withdrawals, across all time.
+///
+/// Withdrawals consume capital before any of them counts as revenue, so this is
+/// what the next withdrawal can still take out tax-free.
+pub fn remaining_seed_capital(categorized: &CategorizedTransfers, config: &Config) -> f64 {
+    let withdrawn: f64 = taxable_withdrawals(categorized, config)
+        .iter()
+        .map(|t| categorized.draw_sol(t))
+        .sum();
+    (total_seeded_sol(categorized, config) - withdrawn).max(0.0)
+}
+
+/// Initial treasury plus every seeding transfer
+fn total_seeded_sol(categorized: &CategorizedTransfers, config: &Config) -> f64 {
+    (config.initial_treasury_lamports as f64 / 1e9) + categorized.seeding.iter().map(|s| s.amount_sol).sum::<f64>()
+}
+
+/// Outflows subject to the seed-capital offset. Uses both categorized buckets
+/// while applying the same candidate policy in one place.
+fn taxable_withdrawals<'a>(categorized: &'a CategorizedTransfers, config: &Config) -> Vec<&'a SolTransfer> {
+    categorized
+        .withdrawals
+        .iter()
+        .chain(&categorized.other)
+        .filter(|t| is_taxable_external_withdrawal_candidate(t, config))
+        .collect()
+}
+
 fn is_taxable_external_withdrawal_candidate(t: &SolTransfer, config: &Config) -> bool {
-    let from_business_source = t.from == config.vote_account || t.from == config.identity;
+    let date = t.date.as_deref();
+    let is_identity = |pubkey: &Pubkey| config.holds_role(pubkey, KeyRole::Identity, date);
+    let is_vote_account = |pubkey: &Pubkey| config.holds_role(pubkey, KeyRole::VoteAccount, date);
+    let from_business_source = is_vote_account(&t.from) || is_identity(&t.from);
     if !from_business_source {
         return false;
     }
 
     // Internal business move (vote <-> identity) is never a withdrawal event.
-    if t.to == config.vote_account || t.to == config.identity {
+    if is_vote_account(&t.to) || is_identity(&t.to) {
+        return false;
+    }
+
+    // Payments to a related entity settle the inter-entity ledger, not owner distributions.
+    if config.entity_for_wallet(&t.to).is_some() {
         return false;
     }
 
     // Identity outflows are often protocol operational; treat only known
     // beneficiary channels as taxable-distribution candidates.
-    if t.from == config.identity {
-        return t.to == config.withdraw_authority || config.is_personal_wallet(&t.to) || addresses::is_exchange(&t.to);
+    if is_identity(&t.from) {
+        return config.holds_role(&t.to, KeyRole::WithdrawAuthority, date)
+            || config.is_personal_wallet(&t.to)
+            || addresses::is_exchange(&t.to);
     }
 
     // Vote-account outflows to any external destination are candidates.
@@ -136,8 +158,14 @@ fn is_taxable_external_withdrawal_candidate(t: &SolTransfer, config: &Config) ->
 }
 
 /// Generate the tax report CSV and print a console summary.
-pub fn generate_tax_report(output_dir: &Path, data: &TaxReportData, year_filter: Option<i32>) -> Result<()> {
-    let (rows, skipped_unknown_dates) = build_tax_rows(data, year_filter);
+///
+/// Returns the paths written (tax report, Schedule C, Schedule C other expenses).
+pub fn generate_tax_report(
+    output_dir: &Path,
+    data: &TaxReportData,
+    period: Option<&ReportPeriod>,
+) -> Result<Vec<PathBuf>> {
+    let (rows, skipped_unknown_dates) = build_tax_rows(data, period);
 
     // Write CSV
     let path = output_dir.join(TAX_REPORT_FILENAME);
@@ -150,6 +178,7 @@ pub fn generate_tax_report(output_dir: &Path, data: &TaxReportData, year_filter:
         "Description",
         "SOL Amount",
         "SOL Price (USD)",
+        "Price Source",
         "USD Value",
         "Destination",
         "Tx Signature",
@@ -163,6 +192,7 @@ pub fn generate_tax_report(output_dir: &Path, data: &TaxReportData, year_filter:
             &row.description,
             &row.sol_amount.map_or(String::new(), |v| format!("{:.6}", v)),
             &row.sol_price_usd.map_or(String::new(), |v| format!("{:.2}", v)),
+            row.price_source.map_or("", |s| s.as_str()),
             &format!("{:.2}", row.usd_value),
             &row.destination,
             &row.tx_signature,
@@ -171,10 +201,10 @@ pub fn generate_tax_report(output_dir: &Path, data: &TaxReportData, year_filter:
 
     wtr.flush()?;
 
-    let (schedule_c_path, schedule_c_other_expenses_path) = write_schedule_c_csv(output_dir, &rows, year_filter)?;
+    let (schedule_c_path, schedule_c_other_expenses_path) = write_schedule_c_csv(output_dir, &rows, period)?;
 
     // Console summary
-    print_tax_summary(&rows, year_filter);
+    print_tax_summary(&rows, period);
 
     if skipped_unknown_dates > 0 {
         eprintln!(
@@ -183,21 +213,26 @@ pub fn generate_tax_report(output_dir: &Path, data: &TaxReportData, year_filter:
         );
     }
 
+    print_fallback_price_warnings(&rows);
+
     println!("\nTax report written to: {}", path.display());
+    println!("Dates are in {}", data.config.accounting_timezone.name());
     println!("Schedule C mapping written to: {}", schedule_c_path.display());
     println!(
         "Schedule C other expenses detail written to: {}",
         schedule_c_other_expenses_path.display()
     );
 
-    Ok(())
+    Ok(vec![path, schedule_c_path, schedule_c_other_expenses_path])
 }
 
-fn write_schedule_c_csv(output_dir: &Path, rows: &[TaxRow], year_filter: Option<i32>) -> Result<(PathBuf, PathBuf)> {
-    let year_label = year_filter
-        .map(|year| year.to_string())
-        .unwrap_or_else(|| "all".to_string());
-    let suffix = year_filter.map(|year| format!("_{}", year)).unwrap_or_default();
+fn write_schedule_c_csv(
+    output_dir: &Path,
+    rows: &[TaxRow],
+    period: Option<&ReportPeriod>,
+) -> Result<(PathBuf, PathBuf)> {
+    let year_label = period.map(|p| p.label.clone()).unwrap_or_else(|| "all".to_string());
+    let suffix = period.map(|p| p.file_suffix()).unwrap_or_default();
 
     let income_1099 = 0.0;
     let income_not_1099: f64 = normalize_currency(
@@ -428,49 +463,58 @@ fn normalize_currency(value: f64) -> f64 {
 
 fn add_withdrawal_rows(
     rows: &mut Vec<TaxRow>,
-    withdrawals: &[&SolTransfer],
-    prices: &PriceCache,
-    year_filter: Option<i32>,
+    data: &TaxReportData,
+    period: Option<&ReportPeriod>,
     skipped: &mut usize,
-    total_seeded_sol: f64,
 ) {
+    let categorized = data.categorized;
+
     // Sort withdrawals chronologically so capital is consumed in order.
     // ISO-8601 string sort is correct for YYYY-MM-DD; "unknown" sorts after
     // all real dates, so unknown-dated entries consume capital last (safest).
-    let mut sorted: Vec<&&SolTransfer> = withdrawals.iter().collect();
+    let mut sorted = taxable_withdrawals(categorized, data.config);
     sorted.sort_by(|a, b| a.date.cmp(&b.date));
 
-    let mut remaining_capital = total_seeded_sol;
+    let mut remaining_capital = total_seeded_sol(categorized, data.config);
 
     for w in sorted {
         let date = w.date.as_deref().unwrap_or("unknown");
 
-        // Always consume capital regardless of year filter — prior-year
-        // withdrawals must reduce the pool so the current year is correct.
-        let capital_portion = w.amount_sol.min(remaining_capital);
-        let revenue_portion = w.amount_sol - capital_portion;
+        // Only the owner-draw share of a split withdrawal is a distribution; the
+        // business share is deducted as an expense (see `owner_draw`).
+        let draw_sol = categorized.draw_sol(w);
+
+        // Always consume capital regardless of period filter — earlier
+        // withdrawals must reduce the pool so the current period is correct.
+        let capital_portion = draw_sol.min(remaining_capital);
+        let revenue_portion = draw_sol - capital_portion;
         remaining_capital -= capital_portion;
 
-        // Only emit rows for the requested year
-        if !matches_year(date, year_filter, skipped) {
+        // Only emit rows for the requested period
+        if !matches_period(date, period, skipped) {
             continue;
         }
-        let price = get_price(prices, date);
+        let (price, price_source) = get_event_price(data.prices, data.hourly_prices, date, w.timestamp);
 
         let dest_label = if w.to_label.is_empty() {
             shorten_pubkey(&w.to.to_string())
         } else {
             w.to_label.clone()
         };
+        let split_note = categorized
+            .split_for(&w.signature)
+            .map(|s| format!(" (owner draw share; {:.0}% was a business expense)", s.business_pct))
+            .unwrap_or_default();
 
         if capital_portion > 0.0 {
             rows.push(TaxRow {
                 date: date.to_string(),
                 entry_type: "Return of Capital".to_string(),
                 category: "Withdrawal".to_string(),
-                description: format!("Return of seed capital to {}", dest_label),
+                description: format!("Return of seed capital to {}{}", dest_label, split_note),
                 sol_amount: Some(capital_portion),
                 sol_price_usd: Some(price),
+                price_source: Some(price_source),
                 usd_value: capital_portion * price,
                 destination: dest_label.clone(),
                 tx_signature: w.signature.clone(),
@@ -482,9 +526,10 @@ fn add_withdrawal_rows(
                 date: date.to_string(),
                 entry_type: "Revenue".to_string(),
                 category: "Withdrawal".to_string(),
-                description: format!("External withdrawal to {}", dest_label),
+                description: format!("External withdrawal to {}{}", dest_label, split_note),
                 sol_amount: Some(revenue_portion),
                 sol_price_usd: Some(price),
+                price_source: Some(price_source),
                 usd_value: revenue_portion * price,
                 destination: dest_label,
                 tx_signature: w.signature.clone(),
@@ -498,15 +543,15 @@ fn add_vote_cost_rows(
     vote_costs: &[EpochVoteCost],
     prices: &PriceCache,
     config: &Config,
-    year_filter: Option<i32>,
+    period: Option<&ReportPeriod>,
     skipped: &mut usize,
 ) {
     for vc in vote_costs {
         let date = vc.date.as_deref().unwrap_or("unknown");
-        if !matches_year(date, year_filter, skipped) {
+        if !matches_period(date, period, skipped) {
             continue;
         }
-        let price = get_price(prices, date);
+        let (price, price_source) = get_price_with_source(prices, date);
         let gross_usd = vc.total_fee_sol * price;
 
         // Calculate SFDP coverage for this epoch
@@ -535,6 +580,7 @@ fn add_vote_cost_rows(
             description,
             sol_amount: Some(vc.total_fee_sol),
             sol_price_usd: Some(price),
+            price_source: Some(price_source),
             usd_value: gross_usd,
             destination: String::new(),
             tx_signature: String::new(),
@@ -553,6 +599,7 @@ fn add_vote_cost_rows(
                 ),
                 sol_amount: Some(reimbursed_sol),
                 sol_price_usd: Some(price),
+                price_source: Some(price_source),
                 usd_value: reimbursed_usd,
                 destination: String::new(),
                 tx_signature: String::new(),
@@ -565,15 +612,15 @@ fn add_doublezero_rows(
     rows: &mut Vec<TaxRow>,
     fees: &[DoubleZeroFee],
     prices: &PriceCache,
-    year_filter: Option<i32>,
+    period: Option<&ReportPeriod>,
     skipped: &mut usize,
 ) {
     for fee in fees {
         let date = fee.date.as_deref().unwrap_or("unknown");
-        if !matches_year(date, year_filter, skipped) {
+        if !matches_period(date, period, skipped) {
             continue;
         }
-        let price = get_price(prices, date);
+        let (price, price_source) = get_price_with_source(prices, date);
         let usd_value = fee.liability_sol * price;
 
         rows.push(TaxRow {
@@ -586,6 +633,7 @@ fn add_doublezero_rows(
             ),
             sol_amount: Some(fee.liability_sol),
             sol_price_usd: Some(price),
+            price_source: Some(price_source),
             usd_value,
             destination: String::new(),
             tx_signature: String::new(),
@@ -596,11 +644,11 @@ fn add_doublezero_rows(
 fn add_offchain_expense_rows(
     rows: &mut Vec<TaxRow>,
     expenses: &[Expense],
-    year_filter: Option<i32>,
+    period: Option<&ReportPeriod>,
     skipped: &mut usize,
 ) {
     for exp in expenses {
-        if !matches_year(&exp.date, year_filter, skipped) {
+        if !matches_period(&exp.date, period, skipped) {
             continue;
         }
 
@@ -611,6 +659,7 @@ fn add_offchain_expense_rows(
             description: format!("{} - {}", exp.vendor, exp.description),
             sol_amount: None, // off-chain expenses are already in USD
             sol_price_usd: None,
+            price_source: None,
             usd_value: exp.amount_usd,
             destination: String::new(),
             tx_signature: String::new(),
@@ -620,8 +669,8 @@ fn add_offchain_expense_rows(
 
 // ─── Console summary ──────────────────────────────────────────────────────
 
-fn print_tax_summary(rows: &[TaxRow], year_filter: Option<i32>) {
-    let year_label = year_filter.map(|y| format!(" ({})", y)).unwrap_or_default();
+fn print_tax_summary(rows: &[TaxRow], period: Option<&ReportPeriod>) {
+    let year_label = period.map(|p| format!(" ({})", p.label)).unwrap_or_default();
 
     println!("\n══════════════════════════════════════════════════");
     println!("  TAX REPORT SUMMARY{}", year_label);
@@ -715,21 +764,50 @@ fn print_tax_summary(rows: &[TaxRow], year_filter: Option<i32>) {
     println!("  ═════════════════════════════════════════════");
 }
 
+/// List rows whose USD value rests on the fallback price, so the soft numbers are called out.
+fn print_fallback_price_warnings(rows: &[TaxRow]) {
+    let fallback_rows: Vec<&TaxRow> = rows
+        .iter()
+        .filter(|r| r.price_source == Some(PriceSource::Fallback))
+        .collect();
+    if fallback_rows.is_empty() {
+        return;
+    }
+
+    eprintln!(
+        "\n  ⚠ Warning: {} row(s) valued with the fallback SOL price; their USD values are soft:",
+        fallback_rows.len()
+    );
+    for row in &fallback_rows {
+        eprintln!(
+            "    {}  {:<18} {:<40} ${:.2}",
+            row.date,
+            row.entry_type,
+            truncate_description(&row.description, 40),
+            row.usd_value
+        );
+    }
+    eprintln!("    Run 'validator-accounting prices backfill --from <date>' and regenerate to fix.");
+}
+
 // ─── Helpers ──────────────────────────────────────────────────────────────
 
-fn matches_year(date: &str, year_filter: Option<i32>, skipped: &mut usize) -> bool {
-    // Warn about unparseable dates regardless of year filter
+fn truncate_description(s: &str, max: usize) -> String {
+    if s.chars().count() > max {
+        format!("{}...", s.chars().take(max - 3).collect::<String>())
+    } else {
+        s.to_string()
+    }
+}
+
+fn matches_period(date: &str, period: Option<&ReportPeriod>, skipped: &mut usize) -> bool {
+    // Warn about unparseable dates regardless of period filter
     if date == "unknown" || NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
         *skipped += 1;
-        // If no year filter, still include the row (fallback price will be used)
-        return year_filter.is_none();
+        // If no period filter, still include the row (fallback price will be used)
+        return period.is_none();
     }
-    let Some(year) = year_filter else {
-        return true;
-    };
-    NaiveDate::parse_from_str(date, "%Y-%m-%d")
-        .map(|d| d.year() == year)
-        .unwrap_or(false)
+    period.is_none_or(|p| p.contains(date))
 }
 
 fn shorten_pubkey(addr: &str) -> String {
@@ -0,0 +1,98 @@
+//! Accounting timezone for attributing block timestamps to calendar dates
+//!
+//! Block times are Unix timestamps; which day (and month, and tax year) an event lands
+//! on depends on the timezone the books are kept in. The timezone comes from
+//! `[reporting] timezone` and is installed once at startup, so every conversion —
+//! rewards (via `epoch_to_date`), transfers, balance snapshots and daily prices — agrees.
+
+use std::sync::OnceLock;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+
+static ACCOUNTING_TIMEZONE: OnceLock<Tz> = OnceLock::new();
+
+/// Install the accounting timezone (first call wins; defaults to UTC if never called)
+pub fn install(tz: Tz) {
+    let _ = ACCOUNTING_TIMEZONE.set(tz);
+}
+
+/// The accounting timezone in effect
+pub fn accounting_timezone() -> Tz {
+    ACCOUNTING_TIMEZONE.get().copied().unwrap_or(Tz::UTC)
+}
+
+/// Calendar date of a Unix timestamp in the accounting timezone
+pub fn timestamp_to_naive_date(timestamp: i64) -> Option<NaiveDate> {
+    date_in_timezone(timestamp, accounting_timezone())
+}
+
+/// `YYYY-MM-DD` of a Unix timestamp in the accounting timezone
+pub fn timestamp_to_date(timestamp: i64) -> Option<String> {
+    timestamp_to_naive_date(timestamp).map(|d| d.format("%Y-%m-%d").to_string())
+}
+
+/// Today's date in the accounting timezone
+pub fn today() -> NaiveDate {
+    Utc::now().with_timezone(&accounting_timezone()).date_naive()
+}
+
+fn date_in_timezone(timestamp: i64, tz: Tz) -> Option<NaiveDate> {
+    DateTime::from_timestamp(timestamp, 0).map(|dt| dt.with_timezone(&tz).date_naive())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_late_evening_eastern_stays_on_previous_day() {
+        // 2025-12-31 23:30 US/Eastern = 2026-01-01 04:30 UTC
+        let ts = 1_767_241_800;
+        assert_eq!(date_in_timezone(ts, Tz::UTC), NaiveDate::from_ymd_opt(2026, 1, 1));
+        assert_eq!(
+            date_in_timezone(ts, chrono_tz::America::New_York),
+            NaiveDate::from_ymd_opt(2025, 12, 31)
+        );
+    }
+
+    #[test]
+    fn test_us_eastern_alias_parses() {
+        let tz: Tz = "US/Eastern".parse().unwrap();
+        // 2025-07-01 02:00 UTC = 2025-06-30 22:00 EDT
+        assert_eq!(
+            date_in_timezone(1_751_335_200, tz),
+            NaiveDate::from_ymd_opt(2025, 6, 30)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_timezone_change_redates_failed_fees() {
+        use crate::cache::Cache;
+        use crate::failed_fees::FailedTxFee;
+
+        let dir = std::env::temp_dir().join(format!("va-timezone-redate-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let cache = Cache::open(&dir.join("cache.sqlite")).await.unwrap();
+        cache.sync_accounting_timezone("America/New_York").await.unwrap();
+
+        // Stored while the books were kept in US/Eastern: 2025-12-31 23:30 EST
+        let fee = FailedTxFee {
+            signature: "sig".to_string(),
+            account: "identity".to_string(),
+            slot: 1,
+            timestamp: Some(1_767_241_800),
+            date: Some("2025-12-31".to_string()),
+            fee_lamports: 5000,
+            error: "InstructionError".to_string(),
+        };
+        cache.store_failed_tx_fees("identity", &[fee], None).await.unwrap();
+
+        // No timezone is installed in tests, so dates are re-derived in UTC
+        let previous = cache.sync_accounting_timezone("UTC").await.unwrap();
+        assert_eq!(previous.as_deref(), Some("America/New_York"));
+        let fees = cache.get_failed_tx_fees().await.unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(fees[0].date.as_deref(), Some("2026-01-01"));
+    }
+}
@@ -1,7 +1,6 @@
 //! On-chain transaction fetching and parsing
 
 use anyhow::Result;
-use chrono::DateTime;
 use serde::Serialize;
 use serde_json::Value as JsonValue;
 use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
@@ -20,10 +19,14 @@ use tokio::time::sleep;
 use crate::addresses::{self, AddressCategory};
 use crate::config::Config;
 use crate::constants;
+use crate::owner_draw::WithdrawalSplit;
 use crate::rpc;
+use crate::rules::{self, TransferBucket, TransferDirection};
+use crate::squads;
+use crate::timezone;
 
 /// Extract account keys from transaction (works for both legacy and versioned)
-fn extract_account_keys(tx: &EncodedTransaction, _debug: bool) -> Option<Vec<Pubkey>> {
+pub fn extract_account_keys(tx: &EncodedTransaction, _debug: bool) -> Option<Vec<Pubkey>> {
     match tx {
         EncodedTransaction::Json(ui_tx) => {
             // JSON-parsed transaction - works for both legacy and versioned
@@ -113,6 +116,10 @@ pub struct SolTransfer {
     pub to_label: String,
     pub from_category: AddressCategory,
     pub to_category: AddressCategory,
+    /// SPL memo text attached to the transaction (multiple memos joined with "; ")
+    pub memo: Option<String>,
+    /// Program whose instruction moved the SOL (the top-level caller for CPI transfers)
+    pub program: Option<String>,
 }
 
 /// Categorized transfers
@@ -126,12 +133,34 @@ pub struct CategorizedTransfers {
     pub mev_deposits: Vec<SolTransfer>,
     /// DoubleZero deposit payments (prepaid network fees)
     pub doublezero_payments: Vec<SolTransfer>,
+    /// Tips received at the donation address
+    pub donations: Vec<SolTransfer>,
     /// Internal transfers to fund vote account
     pub vote_funding: Vec<SolTransfer>,
     /// Withdrawals to exchanges or personal
     pub withdrawals: Vec<SolTransfer>,
+    /// Rent-exempt deposits for new accounts and refunds from closed accounts (balance sheet)
+    pub rent: Vec<SolTransfer>,
     /// Other/uncategorized
     pub other: Vec<SolTransfer>,
+    /// Owner draw vs business expense splits of withdrawals (loaded separately from the cache)
+    pub splits: Vec<WithdrawalSplit>,
+}
+
+impl CategorizedTransfers {
+    fn bucket_mut(&mut self, bucket: TransferBucket) -> &mut Vec<SolTransfer> {
+        match bucket {
+            TransferBucket::Seeding => &mut self.seeding,
+            TransferBucket::SfdpReimbursement => &mut self.sfdp_reimbursements,
+            TransferBucket::MevDeposit => &mut self.mev_deposits,
+            TransferBucket::DoublezeroPayment => &mut self.doublezero_payments,
+            TransferBucket::Donation => &mut self.donations,
+            TransferBucket::VoteFunding => &mut self.vote_funding,
+            TransferBucket::Withdrawal => &mut self.withdrawals,
+            TransferBucket::Rent => &mut self.rent,
+            TransferBucket::Other => &mut self.other,
+        }
+    }
 }
 
 /// Fetch inflation rewards for a range of epochs
@@ -167,13 +196,16 @@ async fn fetch_inflation_rewards_internal(
         // Rate limiting
         sleep(Duration::from_millis(constants::EPOCH_REWARD_DELAY_MS)).await;
 
-        match client.get_inflation_reward(&[config.vote_account], Some(epoch)) {
+        // Across a vote account migration both accounts are asked, and their rewards merge
+        match client.get_inflation_reward(&config.vote_accounts_for_epoch(epoch), Some(epoch)) {
             Ok(result) => {
-                if let Some(Some(reward)) = result.first() {
-                    let amount_sol = reward.amount as f64 / 1e9;
+                let paid: Vec<_> = result.iter().flatten().collect();
+                if let Some(reward) = paid.iter().max_by_key(|r| r.amount) {
+                    let amount_lamports: u64 = paid.iter().map(|r| r.amount).sum();
+                    let amount_sol = amount_lamports as f64 / 1e9;
                     rewards.push(EpochReward {
                         epoch,
-                        amount_lamports: reward.amount,
+                        amount_lamports,
                         amount_sol,
                         commission: reward.commission.unwrap_or(config.commission_percent),
                         effective_slot: reward.effective_slot,
@@ -209,6 +241,20 @@ pub async fn fetch_sol_transfers(config: &Config, verbose: bool) -> Result<Vec<S
     // SFDP reimbursement address is excluded — it is a global address with thousands of
     // transactions to all validators; SFDP transfers are covered by Dune fallback instead.
     let mut tracked_accounts = vec![("withdraw authority".to_string(), config.withdraw_authority)];
+    if let Some(vault) = config.squads_vault.filter(|v| *v != config.withdraw_authority) {
+        tracked_accounts.push(("Squads vault".to_string(), vault));
+    }
+    for (idx, wallet) in config.business_wallets.iter().enumerate() {
+        tracked_accounts.push((format!("business wallet {}", idx + 1), *wallet));
+    }
+    if let Some(donation) = config.donation_address {
+        tracked_accounts.push(("donation address".to_string(), donation));
+    }
+    // Retired keys stopped voting at rotation, so their newest signatures are the
+    // hand-over transfers; the cap keeps a busy old identity from dominating the scan.
+    for key in &config.retired_keys {
+        tracked_accounts.push((format!("retired {}", key.role.label().to_lowercase()), key.pubkey));
+    }
     for (idx, wallet) in config.personal_wallets.iter().enumerate() {
         let label = if idx == 0 {
             "personal wallet".to_string()
@@ -575,6 +621,19 @@ pub async fn fetch_transfers_for_account(
 /// transfers are already captured here; SFDP→vote_account transfers are covered by Dune fallback.
 pub fn get_tracked_accounts(config: &Config) -> Vec<(String, Pubkey)> {
     let mut accounts = vec![("withdraw_authority".to_string(), config.withdraw_authority)];
+    if let Some(vault) = config.squads_vault.filter(|v| *v != config.withdraw_authority) {
+        accounts.push(("squads_vault".to_string(), vault));
+    }
+    for (idx, wallet) in config.business_wallets.iter().enumerate() {
+        accounts.push((format!("business_wallet_{}", idx + 1), *wallet));
+    }
+    if let Some(donation) = config.donation_address {
+        accounts.push(("donation_address".to_string(), donation));
+    }
+    // Keyed by address so scan progress survives reordering the config entries
+    for key in &config.retired_keys {
+        accounts.push((format!("retired_{}", key.pubkey), key.pubkey));
+    }
     for (idx, wallet) in config.personal_wallets.iter().enumerate() {
         let label = if idx == 0 {
             "personal_wallet".to_string()
@@ -612,7 +671,14 @@ fn parse_sol_transfers_debug(
     }
 
     let timestamp = tx.block_time;
-    let date = timestamp.and_then(|ts| DateTime::from_timestamp(ts, 0).map(|dt| dt.format("%Y-%m-%d").to_string()));
+    let date = timestamp.and_then(timezone::timestamp_to_date);
+    let memo = transaction_memo(tx);
+
+    // In a Squads multisig transaction the vault is the business account. The member who proposes,
+    // approves or executes only pays fees and proposal rent, so those flows aren't treasury transfers.
+    let squads_vault = config
+        .squads_vault
+        .filter(|_| account_keys.contains(&squads::program_id()));
 
     let mut transfers = Vec::new();
 
@@ -621,30 +687,58 @@ fn parse_sol_transfers_debug(
     if let EncodedTransaction::Json(ui_tx) = &tx.transaction.transaction
         && let UiMessage::Parsed(parsed_msg) = &ui_tx.message
     {
-        let mut parsed_transfers: Vec<(Pubkey, Pubkey, u64)> = Vec::new();
-
-        parsed_transfers.extend(system_transfers_from_ui_instructions(&parsed_msg.instructions));
+        let mut parsed_transfers: Vec<(Pubkey, Pubkey, u64, Option<String>)> = Vec::new();
+
+        for ix in &parsed_msg.instructions {
+            if let Some((from, to, lamports)) = parse_system_transfer_from_ui_instruction(ix) {
+                let program = created_account_owner(ix).or_else(|| ui_instruction_program_id(ix));
+                parsed_transfers.push((from, to, lamports, program));
+            } else if let Some((from, to, lamports)) = parse_close_account_refund(ix, &account_keys, pre_balances) {
+                parsed_transfers.push((from, to, lamports, ui_instruction_program_id(ix)));
+            }
+        }
 
         // Inner instructions can contain System transfers too (e.g., via CPI).
+        // Attribute those to the top-level program that made the call.
         if let OptionSerializer::Some(inner) = meta.inner_instructions.as_ref() {
             for inner_ixs in inner {
-                parsed_transfers.extend(system_transfers_from_ui_instructions(&inner_ixs.instructions));
+                let program = parsed_msg
+                    .instructions
+                    .get(inner_ixs.index as usize)
+                    .and_then(ui_instruction_program_id);
+                for (from, to, lamports) in system_transfers_from_ui_instructions(&inner_ixs.instructions) {
+                    parsed_transfers.push((from, to, lamports, program.clone()));
+                }
+                for ix in &inner_ixs.instructions {
+                    if let Some((from, to, lamports)) = parse_close_account_refund(ix, &account_keys, pre_balances) {
+                        parsed_transfers.push((from, to, lamports, program.clone()));
+                    }
+                }
             }
         }
 
-        for (from, to, amount_lamports) in parsed_transfers {
+        for (from, to, amount_lamports, program) in parsed_transfers {
             let min_lamports = constants::MIN_TRANSFER_LAMPORTS.max(0) as u64;
             if amount_lamports < min_lamports {
                 continue;
             }
 
             // Only keep transfers that touch accounts we care about.
-            if !config.is_relevant_account(&from) && !config.is_relevant_account(&to) {
+            if !config.is_relevant_account_on(&from, date.as_deref())
+                && !config.is_relevant_account_on(&to, date.as_deref())
+            {
+                continue;
+            }
+            if let Some(vault) = squads_vault
+                && program.as_deref().is_some_and(squads::is_squads_program)
+                && from != vault
+                && to != vault
+            {
                 continue;
             }
 
-            let (from_label, from_category) = label_and_category_for_address(&from, config);
-            let (to_label, to_category) = label_and_category_for_address(&to, config);
+            let (from_label, from_category) = label_and_category_for_address(&from, date.as_deref(), config);
+            let (to_label, to_category) = label_and_category_for_address(&to, date.as_deref(), config);
 
             transfers.push(SolTransfer {
                 signature: signature.to_string(),
@@ -659,6 +753,8 @@ fn parse_sol_transfers_debug(
                 to_label,
                 from_category,
                 to_category,
+                memo: memo.clone(),
+                program,
             });
         }
 
@@ -674,7 +770,7 @@ fn parse_sol_transfers_debug(
         let diff = post as i64 - pre as i64;
 
         let account = &account_keys[i];
-        let is_relevant = config.is_relevant_account(account);
+        let is_relevant = config.is_relevant_account_on(account, date.as_deref());
 
         // Debug: show significant balance changes
         if debug && diff.abs() >= constants::MIN_TRANSFER_LAMPORTS {
@@ -689,7 +785,7 @@ fn parse_sol_transfers_debug(
         }
 
         // Only care about our relevant accounts and significant changes
-        if !is_relevant {
+        if !is_relevant || squads_vault.is_some_and(|vault| *account != vault) {
             continue;
         }
 
@@ -732,7 +828,7 @@ fn parse_sol_transfers_debug(
         }
 
         if let Some(j) = best_j {
-            let other_is_relevant = config.is_relevant_account(&account_keys[j]);
+            let other_is_relevant = config.is_relevant_account_on(&account_keys[j], date.as_deref());
 
             // Avoid mirrored duplicates when both sides are relevant.
             // In those cases, prefer emitting from the debited side (diff < 0),
@@ -748,8 +844,8 @@ fn parse_sol_transfers_debug(
                 (account, &account_keys[j])
             };
 
-            let (from_label, from_category) = label_and_category_for_address(from, config);
-            let (to_label, to_category) = label_and_category_for_address(to, config);
+            let (from_label, from_category) = label_and_category_for_address(from, date.as_deref(), config);
+            let (to_label, to_category) = label_and_category_for_address(to, date.as_deref(), config);
 
             transfers.push(SolTransfer {
                 signature: signature.to_string(),
@@ -764,6 +860,8 @@ fn parse_sol_transfers_debug(
                 to_label,
                 from_category,
                 to_category,
+                memo: memo.clone(),
+                program: None,
             });
         }
     }
@@ -771,6 +869,103 @@ fn parse_sol_transfers_debug(
     if transfers.is_empty() { None } else { Some(transfers) }
 }
 
+/// Program id of a top-level or inner instruction, whatever its encoding
+fn ui_instruction_program_id(ix: &UiInstruction) -> Option<String> {
+    match ix {
+        UiInstruction::Parsed(UiParsedInstruction::Parsed(pi)) => Some(pi.program_id.clone()),
+        UiInstruction::Parsed(UiParsedInstruction::PartiallyDecoded(pd)) => Some(pd.program_id.clone()),
+        UiInstruction::Compiled(_) => None,
+    }
+}
+
+/// SPL memo text from a transaction's instructions, falling back to the Memo program's log lines
+fn transaction_memo(tx: &EncodedConfirmedTransactionWithStatusMeta) -> Option<String> {
+    let mut memos = Vec::new();
+
+    if let EncodedTransaction::Json(ui_tx) = &tx.transaction.transaction
+        && let UiMessage::Parsed(parsed_msg) = &ui_tx.message
+    {
+        memos.extend(
+            parsed_msg
+                .instructions
+                .iter()
+                .filter_map(parse_memo_from_ui_instruction),
+        );
+        if let Some(OptionSerializer::Some(inner)) = tx.transaction.meta.as_ref().map(|m| &m.inner_instructions) {
+            for inner_ixs in inner {
+                memos.extend(inner_ixs.instructions.iter().filter_map(parse_memo_from_ui_instruction));
+            }
+        }
+    }
+
+    if memos.is_empty()
+        && let Some(OptionSerializer::Some(logs)) = tx.transaction.meta.as_ref().map(|m| &m.log_messages)
+    {
+        memos.extend(logs.iter().filter_map(|line| parse_memo_log_line(line)));
+    }
+
+    if memos.is_empty() { None } else { Some(memos.join("; ")) }
+}
+
+fn parse_memo_from_ui_instruction(ix: &UiInstruction) -> Option<String> {
+    match ix {
+        UiInstruction::Parsed(UiParsedInstruction::Parsed(pi)) if pi.program == "spl-memo" => {
+            pi.parsed.as_str().map(str::to_string)
+        }
+        _ => None,
+    }
+}
+
+/// Owner program of the account a System `createAccount` creates, so the rent-exempt deposit
+/// funding a new stake or token account is attributed to the program that will own it
+fn created_account_owner(ix: &UiInstruction) -> Option<String> {
+    let UiInstruction::Parsed(UiParsedInstruction::Parsed(pi)) = ix else {
+        return None;
+    };
+    if pi.program != "system" && pi.program_id != "11111111111111111111111111111111" {
+        return None;
+    }
+    let obj = pi.parsed.as_object()?;
+    match obj.get("type")?.as_str()? {
+        "createAccount" | "createAccountWithSeed" => {}
+        _ => return None,
+    }
+    obj.get("info")?.get("owner")?.as_str().map(str::to_string)
+}
+
+/// SPL Token `closeAccount`: the closed account's whole balance (its rent-exempt reserve) moves
+/// to the destination without a System transfer, so read the amount from the pre-balances
+fn parse_close_account_refund(
+    ix: &UiInstruction,
+    account_keys: &[Pubkey],
+    pre_balances: &[u64],
+) -> Option<(Pubkey, Pubkey, u64)> {
+    let UiInstruction::Parsed(UiParsedInstruction::Parsed(pi)) = ix else {
+        return None;
+    };
+    if pi.program_id != constants::SPL_TOKEN_PROGRAM && pi.program_id != constants::SPL_TOKEN_2022_PROGRAM {
+        return None;
+    }
+    let obj = pi.parsed.as_object()?;
+    if obj.get("type")?.as_str()? != "closeAccount" {
+        return None;
+    }
+    let info = obj.get("info")?.as_object()?;
+    let from = Pubkey::from_str(info.get("account")?.as_str()?).ok()?;
+    let to = Pubkey::from_str(info.get("destination")?.as_str()?).ok()?;
+    let index = account_keys.iter().position(|k| *k == from)?;
+    let lamports = *pre_balances.get(index)?;
+    Some((from, to, lamports))
+}
+
+/// Parse `Program log: Memo (len 5): "hello"` as logged by the SPL Memo program
+fn parse_memo_log_line(line: &str) -> Option<String> {
+    let rest = line.strip_prefix("Program log: Memo (len ")?;
+    let (_, quoted) = rest.split_once("): ")?;
+    let text = quoted.strip_prefix('"')?.strip_suffix('"')?;
+    Some(text.replace("\\\"", "\""))
+}
+
 fn system_transfers_from_ui_instructions(instructions: &[UiInstruction]) -> Vec<(Pubkey, Pubkey, u64)> {
     let mut transfers = Vec::new();
     for ix in instructions {
@@ -833,74 +1028,79 @@ fn json_u64(v: &JsonValue) -> Option<u64> {
     None
 }
 
-fn label_and_category_for_address(pubkey: &Pubkey, config: &Config) -> (String, AddressCategory) {
+/// Label and category for an address on `date`: our accounts (including keys retired after
+/// `date`) and personal wallets first, then the address book
+pub fn label_and_category_for_address(
+    pubkey: &Pubkey,
+    date: Option<&str>,
+    config: &Config,
+) -> (String, AddressCategory) {
     if *pubkey == config.vote_account {
         ("Vote Account".to_string(), AddressCategory::ValidatorSelf)
     } else if *pubkey == config.identity {
         ("Identity Account".to_string(), AddressCategory::ValidatorSelf)
     } else if *pubkey == config.withdraw_authority {
         ("Withdraw Authority".to_string(), AddressCategory::ValidatorSelf)
+    } else if config.squads_vault.as_ref() == Some(pubkey) {
+        ("Squads Vault".to_string(), AddressCategory::ValidatorSelf)
+    } else if config.is_business_wallet(pubkey) {
+        ("Business Wallet".to_string(), AddressCategory::ValidatorSelf)
+    } else if config.donation_address.as_ref() == Some(pubkey) {
+        ("Donation Address".to_string(), AddressCategory::ValidatorSelf)
     } else if config.is_personal_wallet(pubkey) {
         ("Personal Wallet".to_string(), AddressCategory::PersonalWallet)
+    } else if let Some(key) = config.retired_key(pubkey, date) {
+        (key.label(), AddressCategory::ValidatorSelf)
     } else {
         let label = addresses::get_label(pubkey);
         (label.name, label.category)
     }
 }
 
-/// Categorize transfers based on sender/receiver
+/// Re-apply current address labels and categories to a stored transfer
+///
+/// Returns true if any label or category changed.
+pub fn relabel_transfer(transfer: &mut SolTransfer, config: &Config) -> bool {
+    let date = transfer.date.as_deref();
+    let (from_label, from_category) = label_and_category_for_address(&transfer.from, date, config);
+    let (to_label, to_category) = label_and_category_for_address(&transfer.to, date, config);
+
+    let changed = from_label != transfer.from_label
+        || to_label != transfer.to_label
+        || from_category != transfer.from_category
+        || to_category != transfer.to_category;
+
+    transfer.from_label = from_label;
+    transfer.to_label = to_label;
+    transfer.from_category = from_category;
+    transfer.to_category = to_category;
+    changed
+}
+
+/// Categorize transfers into report buckets using the categorization rules
+/// (operator rules from config first, then the built-in rules; see `rules`)
 pub fn categorize_transfers(transfers: &[SolTransfer], config: &Config) -> CategorizedTransfers {
     let mut categorized = CategorizedTransfers::default();
+    let builtin = rules::builtin_rules(config);
 
     for transfer in transfers {
-        if let Some(deposit) = config.doublezero_deposit_account
-            && transfer.to == deposit
-            && config.is_our_account(&transfer.from)
-        {
-            let mut labeled = transfer.clone();
-            labeled.to_label = "DoubleZero Deposit".to_string();
-            categorized.doublezero_payments.push(labeled);
+        // Transfers that don't touch our accounts aren't ours to categorize
+        let Some(direction) = TransferDirection::of(transfer, config) else {
             continue;
-        }
+        };
 
-        // Check if this is incoming to our accounts
-        let is_incoming = config.is_our_account(&transfer.to);
-        let is_outgoing = config.is_our_account(&transfer.from);
-
-        if is_incoming {
-            // Categorize incoming transfers
-            if config.is_our_account(&transfer.from) {
-                // Internal transfer (identity/withdraw authority -> vote/identity)
-                categorized.vote_funding.push(transfer.clone());
-            } else if config.is_personal_wallet(&transfer.from) {
-                // From external personal wallet = seeding
-                categorized.seeding.push(transfer.clone());
-            } else if addresses::is_solana_foundation(&transfer.from) {
-                // From SF = SFDP reimbursement
-                categorized.sfdp_reimbursements.push(transfer.clone());
-            } else if addresses::is_jito(&transfer.from) {
-                // From Jito = MEV deposit
-                categorized.mev_deposits.push(transfer.clone());
-            } else {
-                categorized.other.push(transfer.clone());
-            }
-        } else if is_outgoing {
-            // Outgoing transfers
-            if config.is_our_account(&transfer.to) {
-                // Internal transfer
-                categorized.vote_funding.push(transfer.clone());
-            } else if addresses::is_exchange(&transfer.to) || config.is_personal_wallet(&transfer.to) {
-                categorized.withdrawals.push(transfer.clone());
-            } else {
-                categorized.other.push(transfer.clone());
-            }
+        let (bucket, label) = rules::classify(transfer, direction, config, &builtin);
+        let mut transfer = transfer.clone();
+        if let Some(label) = label {
+            rules::apply_label(&mut transfer, direction, label);
         }
+        categorized.bucket_mut(bucket).push(transfer);
     }
 
     categorized
 }
 
-/// Convert epoch number to approximate date
+/// Convert epoch number to approximate date (in the accounting timezone)
 /// Calibrated: epoch 896 = 2025-12-16
 pub fn epoch_to_date(epoch: u64) -> String {
     // Support epochs before and after the reference epoch while still saturating safely.
@@ -911,15 +1111,28 @@ pub fn epoch_to_date(epoch: u64) -> String {
     let timestamp_i128 = (constants::REFERENCE_EPOCH_TIMESTAMP as i128).saturating_add(duration);
     let timestamp = timestamp_i128.clamp(i64::MIN as i128, i64::MAX as i128) as i64;
 
-    DateTime::from_timestamp(timestamp, 0)
-        .map(|dt| dt.format("%Y-%m-%d").to_string())
-        .unwrap_or_else(|| "unknown".to_string())
+    timezone::timestamp_to_date(timestamp).unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Approximate epoch at a unix timestamp (the inverse of `epoch_to_date`)
+pub fn estimate_epoch(timestamp: i64) -> u64 {
+    let elapsed = timestamp.saturating_sub(constants::REFERENCE_EPOCH_TIMESTAMP);
+    (constants::REFERENCE_EPOCH + elapsed.div_euclid(constants::EPOCH_DURATION_SECONDS)).max(0) as u64
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_memo_log_line() {
+        assert_eq!(
+            parse_memo_log_line(r#"Program log: Memo (len 11): "invoice #42""#),
+            Some("invoice #42".to_string())
+        );
+        assert_eq!(parse_memo_log_line("Program log: Instruction: Transfer"), None);
+    }
+
     #[test]
     fn test_epoch_to_date() {
         assert_eq!(epoch_to_date(895), "2025-12-14");
@@ -928,4 +1141,16 @@ mod tests {
         assert_eq!(epoch_to_date(904), "2026-01-01");
         assert_eq!(epoch_to_date(0), "2021-01-19");
     }
+
+    #[test]
+    fn test_estimate_epoch() {
+        let reference = constants::REFERENCE_EPOCH_TIMESTAMP;
+        assert_eq!(estimate_epoch(reference), 896);
+        assert_eq!(
+            estimate_epoch(reference + constants::EPOCH_DURATION_SECONDS * 4 + 60),
+            900
+        );
+        assert_eq!(estimate_epoch(reference - 1), 895);
+        assert_eq!(estimate_epoch(0), 0);
+    }
 }
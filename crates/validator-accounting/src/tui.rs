@@ -0,0 +1,486 @@
+//! Terminal dashboard (`validator-accounting tui`)
+//!
+//! A read-only view of the local cache for operators who live in SSH sessions: current
+//! position (latest `position now` snapshot), month-to-date P&L, recent transfers,
+//! ingestion status and, when `metrics scrape` is running, client performance. It never fetches from the network, so it is safe to leave running
+//! alongside scheduled ingestions; panels pick up new data on the next refresh.
+
+use anyhow::Result;
+use chrono::NaiveDate;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Row, Table};
+use ratatui::{DefaultTerminal, Frame};
+use std::time::{Duration, Instant};
+
+use crate::cache::{BalanceSnapshot, Cache, IngestionRun};
+use crate::client_metrics::MetricSample;
+use crate::config::Config;
+use crate::expenses;
+use crate::positions::lamports_to_sol_string;
+use crate::prices::{PriceCache, get_price};
+use crate::timezone;
+use crate::transactions::SolTransfer;
+
+/// Transfers shown in the recent transfers panel
+const RECENT_TRANSFER_COUNT: usize = 15;
+/// Ingestion runs shown in the status panel
+const RECENT_RUN_COUNT: u32 = 5;
+/// How long to wait for a key press before re-checking the refresh timer
+const INPUT_POLL_MS: u64 = 250;
+
+/// Run the dashboard until the user quits (q / Esc)
+pub async fn run(cache: &Cache, config: &Config, refresh_secs: u64) -> Result<()> {
+    // Load once before taking over the terminal so cache errors print normally
+    let dashboard = Dashboard::load(cache, config).await?;
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, cache, config, dashboard, refresh_secs).await;
+    ratatui::restore();
+    result
+}
+
+async fn event_loop(
+    terminal: &mut DefaultTerminal,
+    cache: &Cache,
+    config: &Config,
+    mut dashboard: Dashboard,
+    refresh_secs: u64,
+) -> Result<()> {
+    let refresh = Duration::from_secs(refresh_secs.max(1));
+    let mut last_refresh = Instant::now();
+
+    loop {
+        terminal.draw(|frame| render(frame, &dashboard))?;
+
+        let mut force_refresh = false;
+        if event::poll(Duration::from_millis(INPUT_POLL_MS))?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char('r') => force_refresh = true,
+                _ => {}
+            }
+        }
+
+        if force_refresh || last_refresh.elapsed() >= refresh {
+            // Keep showing the previous data if a refresh fails (e.g. the cache is locked)
+            match Dashboard::load(cache, config).await {
+                Ok(fresh) => dashboard = fresh,
+                Err(e) => dashboard.refresh_error = Some(e.to_string()),
+            }
+            last_refresh = Instant::now();
+        }
+    }
+}
+
+// =============================================================================
+// Data
+// =============================================================================
+
+/// Everything the dashboard shows, loaded from the cache in one pass
+struct Dashboard {
+    position: Option<BalanceSnapshot>,
+    month_to_date: MonthToDate,
+    transfers: Vec<SolTransfer>,
+    runs: Vec<IngestionRun>,
+    lock_holder: Option<(String, String)>,
+    performance: Option<Performance>,
+    loaded_at: String,
+    refresh_error: Option<String>,
+}
+
+/// Month-to-date revenue and expenses, valued at daily SOL prices
+#[derive(Debug, Default)]
+struct MonthToDate {
+    month: String,
+    commission_usd: f64,
+    leader_fees_usd: f64,
+    mev_usd: f64,
+    bam_usd: f64,
+    /// Net of SFDP coverage
+    vote_costs_usd: f64,
+    doublezero_usd: f64,
+    other_expenses_usd: f64,
+}
+
+/// Latest client metrics sample and the epoch's first, for this-epoch counts
+#[derive(Debug)]
+struct Performance {
+    first: MetricSample,
+    latest: MetricSample,
+}
+
+impl Performance {
+    /// Produced and skipped leader slots this epoch (None if a counter is missing or reset)
+    fn epoch_counts(&self) -> (Option<u64>, Option<u64>) {
+        let delta = |first: Option<u64>, latest: Option<u64>| latest?.checked_sub(first?);
+        (
+            delta(self.first.produced, self.latest.produced),
+            delta(self.first.skipped, self.latest.skipped),
+        )
+    }
+}
+
+impl MonthToDate {
+    fn revenue_usd(&self) -> f64 {
+        self.commission_usd + self.leader_fees_usd + self.mev_usd + self.bam_usd
+    }
+
+    fn expenses_usd(&self) -> f64 {
+        self.vote_costs_usd + self.doublezero_usd + self.other_expenses_usd
+    }
+}
+
+impl Dashboard {
+    async fn load(cache: &Cache, config: &Config) -> Result<Self> {
+        let today = timezone::today();
+        let month = today.format("%Y-%m").to_string();
+        let today_str = today.format("%Y-%m-%d").to_string();
+        let prices = cache.get_prices().await?;
+        let price = |date: Option<&str>| mtd_price(date, &month, &today_str, &prices);
+
+        // Epoch-keyed sources: read everything cached and keep rows dated this month
+        let (start, end) = (config.first_reward_epoch, u32::MAX as u64);
+        let mut mtd = MonthToDate {
+            month: month.clone(),
+            ..Default::default()
+        };
+
+        for r in cache.get_epoch_rewards(start, end).await? {
+            if let Some(p) = price(r.date.as_deref()) {
+                mtd.commission_usd += r.amount_sol * p;
+            }
+        }
+        for f in cache.get_leader_fees(start, end).await? {
+            if let Some(p) = price(f.date.as_deref()) {
+                mtd.leader_fees_usd += f.total_fees_sol * p;
+            }
+        }
+        for c in cache.get_mev_claims(start, end).await? {
+            if let Some(p) = price(c.date.as_deref()) {
+                mtd.mev_usd += c.amount_sol * p;
+            }
+        }
+        for c in cache.get_bam_claims(start, end).await? {
+            if let Some(p) = price(c.date.as_deref()) {
+                mtd.bam_usd += c.amount_sol_equivalent * p;
+            }
+        }
+        for v in cache.get_vote_costs(start, end).await? {
+            if let Some(p) = price(v.date.as_deref()) {
+                let coverage = v
+                    .date
+                    .as_deref()
+                    .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+                    .map(|d| config.sfdp_coverage_percent(&d))
+                    .unwrap_or(0.0);
+                mtd.vote_costs_usd += v.total_fee_sol * p * (1.0 - coverage);
+            }
+        }
+        for f in cache.get_doublezero_fees(start, end).await? {
+            if let Some(p) = price(f.date.as_deref()) {
+                mtd.doublezero_usd += f.liability_sol * p;
+            }
+        }
+
+        let mut offchain = cache.get_approved_expenses().await?;
+        let recurring = cache.get_recurring_expenses().await?;
+        offchain.extend(expenses::expand_recurring_expenses(&recurring, &month, &month));
+        mtd.other_expenses_usd = offchain
+            .iter()
+            .filter(|e| is_month_to_date(&e.date, &month, &today_str))
+            .map(|e| e.amount_usd)
+            .sum();
+
+        // Cached transfers come back newest first
+        let transfers = cache
+            .get_all_transfers()
+            .await?
+            .into_iter()
+            .take(RECENT_TRANSFER_COUNT)
+            .collect();
+
+        let performance = match cache.get_latest_metric_sample().await? {
+            Some(latest) => cache
+                .get_metric_samples(latest.epoch)
+                .await?
+                .into_iter()
+                .next()
+                .map(|first| Performance { first, latest }),
+            None => None,
+        };
+
+        Ok(Self {
+            position: cache.get_latest_balance_snapshot().await?,
+            month_to_date: mtd,
+            transfers,
+            runs: cache.get_ingestion_runs(RECENT_RUN_COUNT).await?,
+            lock_holder: cache.get_ingestion_lock_holder().await?,
+            performance,
+            loaded_at: chrono::Utc::now()
+                .with_timezone(&timezone::accounting_timezone())
+                .format("%H:%M:%S")
+                .to_string(),
+            refresh_error: None,
+        })
+    }
+}
+
+/// Is a `YYYY-MM-DD` date inside `month` and not after `today`?
+fn is_month_to_date(date: &str, month: &str, today: &str) -> bool {
+    date.get(..10)
+        .is_some_and(|d| d.starts_with(month) && d.get(7..8) == Some("-") && d <= today)
+}
+
+/// Daily price for a row dated within the month to date (None if the row is outside it)
+fn mtd_price(date: Option<&str>, month: &str, today: &str, prices: &PriceCache) -> Option<f64> {
+    let date = date.filter(|d| is_month_to_date(d, month, today))?;
+    Some(get_price(prices, date))
+}
+
+// =============================================================================
+// Rendering
+// =============================================================================
+
+fn render(frame: &mut Frame, dashboard: &Dashboard) {
+    let [top, bottom, footer] =
+        Layout::vertical([Constraint::Length(11), Constraint::Min(8), Constraint::Length(1)]).areas(frame.area());
+    let [position_area, pnl_area] = Layout::horizontal([Constraint::Percentage(50); 2]).areas(top);
+    let [transfers_area, runs_area] =
+        Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(bottom);
+
+    frame.render_widget(position_panel(dashboard.position.as_ref()), position_area);
+    frame.render_widget(pnl_panel(&dashboard.month_to_date), pnl_area);
+    render_transfers(frame, &dashboard.transfers, transfers_area);
+    let runs_area = match &dashboard.performance {
+        Some(performance) => {
+            let [performance_area, runs_area] =
+                Layout::vertical([Constraint::Length(7), Constraint::Min(3)]).areas(runs_area);
+            frame.render_widget(performance_panel(performance), performance_area);
+            runs_area
+        }
+        None => runs_area,
+    };
+    frame.render_widget(runs_panel(&dashboard.runs, dashboard.lock_holder.as_ref()), runs_area);
+
+    let mut footer_spans = vec![Span::raw(format!(
+        " q quit · r refresh · updated {} {}",
+        dashboard.loaded_at,
+        timezone::accounting_timezone().name()
+    ))];
+    if let Some(err) = &dashboard.refresh_error {
+        footer_spans.push(Span::styled(
+            format!(" · refresh failed: {}", err),
+            Style::default().fg(Color::Red),
+        ));
+    }
+    frame.render_widget(
+        Paragraph::new(Line::from(footer_spans)).style(Style::default().fg(Color::DarkGray)),
+        footer,
+    );
+}
+
+fn label_value(label: &str, value: String) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!("{:<20}", label), Style::default().fg(Color::DarkGray)),
+        Span::raw(value),
+    ])
+}
+
+fn sol(lamports: u64) -> String {
+    format!("{:>14} SOL", lamports_to_sol_string(lamports, 4))
+}
+
+fn usd(amount: f64) -> String {
+    let sign = if amount < 0.0 { "-" } else { "" };
+    format!("{:>13}", format!("{}${:.2}", sign, amount.abs()))
+}
+
+fn position_panel(position: Option<&BalanceSnapshot>) -> Paragraph<'static> {
+    let block = Block::bordered().title(" Position ");
+    let Some(p) = position else {
+        return Paragraph::new(vec![
+            Line::raw("No balance snapshot cached yet."),
+            Line::raw("Run `validator-accounting position now` to record one."),
+        ])
+        .block(block);
+    };
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(format!("{:<20}", "Total assets"), Style::default().fg(Color::DarkGray)),
+            Span::styled(sol(p.total_lamports), Style::default().add_modifier(Modifier::BOLD)),
+        ]),
+        label_value("Vote account", sol(p.vote_account_lamports)),
+        label_value("Identity", sol(p.identity_lamports)),
+        label_value("Withdraw authority", sol(p.withdraw_authority_lamports)),
+    ];
+    if p.squads_vault_lamports > 0 {
+        lines.push(label_value("Squads vault", sol(p.squads_vault_lamports)));
+    }
+    if p.business_wallets_lamports > 0 {
+        lines.push(label_value("Business wallets", sol(p.business_wallets_lamports)));
+    }
+    lines.extend([
+        label_value("Stake (liquid)", sol(p.stake_liquid_lamports)),
+        label_value("Stake (locked)", sol(p.stake_locked_lamports)),
+        label_value("jitoSOL", sol(p.jitosol_lamports)),
+        Line::styled(
+            format!("as of {} · epoch {} · slot {}", p.date, p.epoch, p.snapshot_slot),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]);
+
+    Paragraph::new(lines).block(block)
+}
+
+fn pnl_panel(mtd: &MonthToDate) -> Paragraph<'static> {
+    let net = mtd.revenue_usd() - mtd.expenses_usd();
+    let net_color = if net >= 0.0 { Color::Green } else { Color::Red };
+
+    Paragraph::new(vec![
+        label_value("Commission", usd(mtd.commission_usd)),
+        label_value("Leader fees", usd(mtd.leader_fees_usd)),
+        label_value("MEV tips", usd(mtd.mev_usd)),
+        label_value("BAM", usd(mtd.bam_usd)),
+        label_value("Vote costs (net)", usd(-mtd.vote_costs_usd)),
+        label_value("DoubleZero", usd(-mtd.doublezero_usd)),
+        label_value("Other expenses", usd(-mtd.other_expenses_usd)),
+        Line::from(vec![
+            Span::styled(format!("{:<20}", "Net P&L"), Style::default().fg(Color::DarkGray)),
+            Span::styled(usd(net), Style::default().fg(net_color).add_modifier(Modifier::BOLD)),
+        ]),
+    ])
+    .block(Block::bordered().title(format!(" Month to date ({}) ", mtd.month)))
+}
+
+fn render_transfers(frame: &mut Frame, transfers: &[SolTransfer], area: Rect) {
+    let rows: Vec<Row> = transfers
+        .iter()
+        .map(|t| {
+            Row::new(vec![
+                t.date.clone().unwrap_or_else(|| "unknown".to_string()),
+                format!("{:.4}", t.amount_sol),
+                t.from_label.clone(),
+                t.to_label.clone(),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Length(12),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(Row::new(vec!["Date", "SOL", "From", "To"]).style(Style::default().add_modifier(Modifier::BOLD)))
+    .block(Block::bordered().title(" Recent transfers "));
+
+    frame.render_widget(table, area);
+}
+
+fn performance_panel(performance: &Performance) -> Paragraph<'static> {
+    let (produced, skipped) = performance.epoch_counts();
+    let count = |v: Option<u64>| v.map_or_else(|| "-".to_string(), |v| format!("{:>14}", v));
+    let skip_rate = match (produced, skipped) {
+        (Some(p), Some(s)) if p + s > 0 => format!("{:>13.1}%", s as f64 / (p + s) as f64 * 100.0),
+        _ => "-".to_string(),
+    };
+    let latest = &performance.latest;
+    let scraped = chrono::DateTime::from_timestamp(latest.scraped_at, 0)
+        .map(|t| {
+            t.with_timezone(&timezone::accounting_timezone())
+                .format("%H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_default();
+
+    Paragraph::new(vec![
+        label_value("Produced", count(produced)),
+        label_value("Skipped", count(skipped)),
+        label_value("Skip rate", skip_rate),
+        label_value(
+            "Vote latency",
+            latest
+                .vote_latency_slots
+                .map_or_else(|| "-".to_string(), |v| format!("{:>8.2} slots", v)),
+        ),
+        Line::styled(
+            format!("slot {} · scraped {}", latest.slot, scraped),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ])
+    .block(Block::bordered().title(format!(" Client (epoch {}) ", latest.epoch)))
+}
+
+fn runs_panel(runs: &[IngestionRun], lock_holder: Option<&(String, String)>) -> Paragraph<'static> {
+    let mut lines = Vec::new();
+
+    match lock_holder {
+        Some((holder, since)) => lines.push(Line::styled(
+            format!("Ingesting now: {} (since {} UTC)", holder, since),
+            Style::default().fg(Color::Yellow),
+        )),
+        None => lines.push(Line::styled("Idle", Style::default().fg(Color::DarkGray))),
+    }
+
+    if runs.is_empty() {
+        lines.push(Line::raw("No ingestion runs recorded."));
+    }
+    for run in runs {
+        let color = match run.status.as_str() {
+            "success" => Color::Green,
+            "partial" | "running" | "skipped" => Color::Yellow,
+            _ => Color::Red,
+        };
+        let rows: u64 = run.row_counts.values().sum();
+        lines.push(Line::from(vec![
+            Span::raw(format!("{} ", run.started_at)),
+            Span::styled(format!("{:<8}", run.status), Style::default().fg(color)),
+            Span::raw(format!(" {} rows", rows)),
+        ]));
+        if let Some(error) = run.errors.first() {
+            lines.push(Line::styled(format!("  ! {}", error), Style::default().fg(Color::Red)));
+        }
+    }
+
+    Paragraph::new(lines).block(Block::bordered().title(" Ingestion "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_month_to_date_excludes_other_months_and_future_days() {
+        assert!(is_month_to_date("2026-03-01", "2026-03", "2026-03-15"));
+        assert!(is_month_to_date("2026-03-15", "2026-03", "2026-03-15"));
+        assert!(!is_month_to_date("2026-03-16", "2026-03", "2026-03-15"));
+        assert!(!is_month_to_date("2026-02-28", "2026-03", "2026-03-15"));
+        assert!(!is_month_to_date("unknown", "2026-03", "2026-03-15"));
+    }
+
+    #[test]
+    fn test_month_to_date_totals() {
+        let mtd = MonthToDate {
+            commission_usd: 100.0,
+            leader_fees_usd: 50.0,
+            mev_usd: 25.0,
+            bam_usd: 5.0,
+            vote_costs_usd: 40.0,
+            doublezero_usd: 10.0,
+            other_expenses_usd: 30.0,
+            ..Default::default()
+        };
+        assert_eq!(mtd.revenue_usd(), 180.0);
+        assert_eq!(mtd.expenses_usd(), 80.0);
+    }
+}
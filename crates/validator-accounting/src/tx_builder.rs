@@ -0,0 +1,214 @@
+//! Unsigned transaction builder for routine treasury operations
+//!
+//! Prepares legacy transactions for withdrawing vote-account rewards, funding the
+//! identity and prepaying DoubleZero, serialized as base64 for signing elsewhere
+//! (an offline key via `solana ... --sign-only`, a hardware wallet, or a multisig).
+//! Strictly offline: nothing here loads a keypair or talks to an RPC node, so the
+//! recent (or durable nonce) blockhash has to be supplied by the caller. Instruction
+//! data is encoded by hand (bincode layout), like the stake account parsing in
+//! `positions.rs`, to avoid pulling in the program crates.
+
+use anyhow::{Context, Result, ensure};
+use base64::Engine;
+use serde::Serialize;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::message::Message;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::Transaction;
+use std::str::FromStr;
+
+const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
+pub const VOTE_PROGRAM_ID: &str = "Vote111111111111111111111111111111111111111";
+
+/// `SystemInstruction::Transfer` variant index
+const SYSTEM_TRANSFER: u32 = 2;
+/// `VoteInstruction::Withdraw` variant index
+const VOTE_WITHDRAW: u32 = 3;
+
+/// A prepared transaction and what it does
+#[derive(Debug, Clone, Serialize)]
+pub struct UnsignedTx {
+    pub operation: &'static str,
+    pub from: String,
+    pub to: String,
+    pub lamports: u64,
+    /// Must sign (and pays the fee)
+    pub signer: String,
+    pub blockhash: String,
+    /// Bincode-serialized transaction with an empty signature slot, base64
+    pub transaction_base64: String,
+}
+
+/// Convert a SOL amount from the command line to lamports
+pub fn sol_to_lamports(sol: f64) -> Result<u64> {
+    ensure!(
+        sol.is_finite() && sol > 0.0,
+        "Amount must be a positive SOL amount (got {})",
+        sol
+    );
+    Ok((sol * 1e9).round() as u64)
+}
+
+/// Parse a base58 blockhash
+pub fn parse_blockhash(blockhash: &str) -> Result<Hash> {
+    Hash::from_str(blockhash).with_context(|| format!("Invalid blockhash '{}'", blockhash))
+}
+
+fn program_id(id: &str) -> Pubkey {
+    Pubkey::from_str(id).expect("valid program id")
+}
+
+/// Instruction data: little-endian u32 variant index followed by a u64 amount
+fn amount_instruction_data(variant: u32, lamports: u64) -> Vec<u8> {
+    let mut data = Vec::with_capacity(12);
+    data.extend_from_slice(&variant.to_le_bytes());
+    data.extend_from_slice(&lamports.to_le_bytes());
+    data
+}
+
+/// System program transfer of `lamports` from `from` (signer) to `to`
+fn system_transfer(from: &Pubkey, to: &Pubkey, lamports: u64) -> Instruction {
+    Instruction::new_with_bytes(
+        program_id(SYSTEM_PROGRAM_ID),
+        &amount_instruction_data(SYSTEM_TRANSFER, lamports),
+        vec![AccountMeta::new(*from, true), AccountMeta::new(*to, false)],
+    )
+}
+
+/// Vote program withdrawal of `lamports` from `vote_account` to `to`, signed by the withdraw authority
+pub fn vote_withdraw(vote_account: &Pubkey, withdraw_authority: &Pubkey, to: &Pubkey, lamports: u64) -> Instruction {
+    Instruction::new_with_bytes(
+        program_id(VOTE_PROGRAM_ID),
+        &amount_instruction_data(VOTE_WITHDRAW, lamports),
+        vec![
+            AccountMeta::new(*vote_account, false),
+            AccountMeta::new(*to, false),
+            AccountMeta::new_readonly(*withdraw_authority, true),
+        ],
+    )
+}
+
+fn build(
+    operation: &'static str,
+    instruction: Instruction,
+    from: &Pubkey,
+    to: &Pubkey,
+    lamports: u64,
+    signer: &Pubkey,
+    blockhash: Hash,
+) -> Result<UnsignedTx> {
+    let message = Message::new_with_blockhash(&[instruction], Some(signer), &blockhash);
+    let transaction = Transaction::new_unsigned(message);
+    let bytes = bincode::serialize(&transaction).context("Failed to serialize transaction")?;
+    Ok(UnsignedTx {
+        operation,
+        from: from.to_string(),
+        to: to.to_string(),
+        lamports,
+        signer: signer.to_string(),
+        blockhash: blockhash.to_string(),
+        transaction_base64: base64::engine::general_purpose::STANDARD.encode(bytes),
+    })
+}
+
+/// Withdraw vote-account rewards to `to` (signed by the withdraw authority)
+pub fn withdraw_rewards(
+    vote_account: &Pubkey,
+    withdraw_authority: &Pubkey,
+    to: &Pubkey,
+    lamports: u64,
+    blockhash: Hash,
+) -> Result<UnsignedTx> {
+    build(
+        "withdraw-rewards",
+        vote_withdraw(vote_account, withdraw_authority, to, lamports),
+        vote_account,
+        to,
+        lamports,
+        withdraw_authority,
+        blockhash,
+    )
+}
+
+/// Plain SOL transfer (identity funding, DoubleZero prepayment)
+pub fn transfer(
+    operation: &'static str,
+    from: &Pubkey,
+    to: &Pubkey,
+    lamports: u64,
+    blockhash: Hash,
+) -> Result<UnsignedTx> {
+    build(
+        operation,
+        system_transfer(from, to, lamports),
+        from,
+        to,
+        lamports,
+        from,
+        blockhash,
+    )
+}
+
+/// Print the transaction summary and payload
+pub fn print_unsigned(tx: &UnsignedTx) {
+    println!("Unsigned transaction: {}", tx.operation);
+    println!("  From:      {}", tx.from);
+    println!("  To:        {}", tx.to);
+    println!(
+        "  Amount:    {:.9} SOL ({} lamports)",
+        tx.lamports as f64 / 1e9,
+        tx.lamports
+    );
+    println!("  Signer:    {} (fee payer)", tx.signer);
+    println!("  Blockhash: {}", tx.blockhash);
+    println!("\n{}", tx.transaction_base64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(tx: &UnsignedTx) -> Transaction {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(&tx.transaction_base64)
+            .unwrap();
+        bincode::deserialize(&bytes).unwrap()
+    }
+
+    #[test]
+    fn transfer_encodes_a_system_transfer_with_one_empty_signature() {
+        let (from, to) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let tx = transfer("fund-identity", &from, &to, 1_500_000_000, Hash::new_unique()).unwrap();
+        let decoded = decode(&tx);
+
+        assert_eq!(decoded.signatures.len(), 1);
+        assert!(decoded.signatures[0] == Default::default());
+        assert_eq!(decoded.message.account_keys[0], from);
+        let ix = &decoded.message.instructions[0];
+        assert_eq!(
+            decoded.message.account_keys[ix.program_id_index as usize],
+            program_id(SYSTEM_PROGRAM_ID)
+        );
+        assert_eq!(ix.data, [2, 0, 0, 0, 0, 0x2f, 0x68, 0x59, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn withdraw_is_signed_by_the_authority_only() {
+        let (vote, authority, to) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let tx = withdraw_rewards(&vote, &authority, &to, 42, Hash::new_unique()).unwrap();
+        let decoded = decode(&tx);
+
+        assert_eq!(decoded.message.header.num_required_signatures, 1);
+        assert_eq!(decoded.message.account_keys[0], authority);
+        assert_eq!(decoded.message.instructions[0].data[..4], [3, 0, 0, 0]);
+        assert_eq!(tx.signer, authority.to_string());
+    }
+
+    #[test]
+    fn rejects_bad_amounts_and_blockhashes() {
+        assert!(sol_to_lamports(0.0).is_err());
+        assert_eq!(sol_to_lamports(0.000000001).unwrap(), 1);
+        assert!(parse_blockhash("not-a-hash").is_err());
+    }
+}
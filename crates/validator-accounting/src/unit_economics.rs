@@ -0,0 +1,263 @@
+//! Unit economics (cost per block, revenue per leader slot, vote fee per credit, $ per delegated SOL)
+//!
+//! Benchmarks the operation against its own output rather than in absolute dollars.
+//! Vote credits and delegated stake come from the vote account (`getVoteAccounts`):
+//! credits for the last ~64 completed epochs and the stake of the epoch each run sees,
+//! so stake history builds up run by run (aggregator imports fill older epochs).
+//! Costs are gross of the SFDP vote-fee offset. Ratios are derived in `report_template`
+//! so the summary, report.html and `/financials` share one definition.
+
+use anyhow::{Context, Result};
+use csv::Writer;
+use report_template::{UnitEconomics, UnitTotals};
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcGetVoteAccountsConfig;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::constants;
+use crate::prices::get_price;
+use crate::reports::ReportData;
+use crate::transactions::epoch_to_date;
+
+/// Vote credits and delegated stake of one epoch (either may be unknown)
+#[derive(Debug, Clone, PartialEq)]
+pub struct EpochPerformance {
+    pub epoch: u64,
+    pub vote_credits: Option<u64>,
+    pub active_stake_lamports: Option<u64>,
+}
+
+/// Credits earned per completed epoch from a vote account's `epoch_credits`
+/// (`(epoch, credits, previous_credits)`) plus the stake seen for the current epoch
+pub fn from_vote_account(
+    epoch_credits: &[(u64, u64, u64)],
+    activated_stake_lamports: u64,
+    current_epoch: u64,
+) -> Vec<EpochPerformance> {
+    let mut rows: Vec<EpochPerformance> = epoch_credits
+        .iter()
+        .filter(|(epoch, _, _)| *epoch < current_epoch)
+        .map(|&(epoch, credits, previous)| EpochPerformance {
+            epoch,
+            vote_credits: Some(credits.saturating_sub(previous)),
+            active_stake_lamports: None,
+        })
+        .collect();
+    rows.push(EpochPerformance {
+        epoch: current_epoch,
+        vote_credits: None,
+        active_stake_lamports: Some(activated_stake_lamports),
+    });
+    rows
+}
+
+/// Fetch credits and current stake for `vote_account` (delinquent accounts included)
+pub fn fetch(rpc_client: &RpcClient, vote_account: &Pubkey, current_epoch: u64) -> Result<Vec<EpochPerformance>> {
+    let status = rpc_client.get_vote_accounts_with_config(RpcGetVoteAccountsConfig {
+        vote_pubkey: Some(vote_account.to_string()),
+        keep_unstaked_delinquents: Some(true),
+        ..Default::default()
+    })?;
+    let info = status
+        .current
+        .into_iter()
+        .chain(status.delinquent)
+        .find(|v| v.vote_pubkey == vote_account.to_string())
+        .context("Vote account not found in getVoteAccounts")?;
+    Ok(from_vote_account(
+        &info.epoch_credits,
+        info.activated_stake,
+        current_epoch,
+    ))
+}
+
+/// Sum everything the KPIs need for dates accepted by `include`
+fn totals(data: &ReportData, include: impl Fn(&str) -> bool) -> UnitTotals {
+    let mut t = UnitTotals::default();
+    let dated = |date: &Option<String>| date.as_deref().filter(|d| include(d));
+
+    for r in data.rewards {
+        if let Some(date) = dated(&r.date) {
+            t.revenue_sol += r.amount_sol;
+            t.revenue_usd += r.amount_sol * get_price(data.prices, date);
+        }
+    }
+    for f in data.leader_fees {
+        if let Some(date) = dated(&f.date) {
+            t.revenue_sol += f.total_fees_sol;
+            t.revenue_usd += f.total_fees_sol * get_price(data.prices, date);
+            t.blocks_produced += f.blocks_produced;
+            t.leader_slots += f.leader_slots;
+        }
+    }
+    for c in data.mev_claims {
+        if let Some(date) = dated(&c.date) {
+            t.revenue_sol += c.amount_sol;
+            t.revenue_usd += c.amount_sol * get_price(data.prices, date);
+        }
+    }
+    for c in data.bam_claims {
+        if let Some(date) = dated(&c.date) {
+            t.revenue_sol += c.amount_sol_equivalent;
+            t.revenue_usd += c.amount_sol_equivalent * get_price(data.prices, date);
+        }
+    }
+
+    let credits: BTreeMap<u64, u64> = data
+        .epoch_performance
+        .iter()
+        .filter_map(|p| Some((p.epoch, p.vote_credits?)))
+        .collect();
+    for v in data.vote_costs {
+        if let Some(date) = dated(&v.date) {
+            t.costs_usd += v.total_fee_sol * get_price(data.prices, date);
+            if let Some(&epoch_credits) = credits.get(&v.epoch) {
+                t.credited_vote_fee_lamports += v.total_fee_lamports;
+                t.vote_credits += epoch_credits;
+            }
+        }
+    }
+    for d in data.doublezero_fees {
+        if let Some(date) = dated(&d.date) {
+            t.costs_usd += d.liability_sol * get_price(data.prices, date);
+        }
+    }
+    for e in data.expenses {
+        if include(&e.date) {
+            t.costs_usd += e.amount_usd;
+        }
+    }
+
+    for p in data.epoch_performance {
+        if let Some(stake) = p.active_stake_lamports
+            && include(&epoch_to_date(p.epoch))
+        {
+            t.delegated_sol_epochs += stake as f64 / constants::LAMPORTS_PER_SOL_U64 as f64;
+            t.stake_epochs += 1;
+        }
+    }
+    t
+}
+
+/// KPIs over the dates accepted by `include`
+pub fn compute(data: &ReportData, include: impl Fn(&str) -> bool) -> UnitEconomics {
+    UnitEconomics::from_totals(&totals(data, include))
+}
+
+/// KPIs per month ("YYYY-MM"), oldest first
+pub fn by_month(data: &ReportData) -> Vec<(String, UnitEconomics)> {
+    let months: std::collections::BTreeSet<String> = data
+        .rewards
+        .iter()
+        .filter_map(|r| r.date.as_deref())
+        .chain(data.leader_fees.iter().filter_map(|f| f.date.as_deref()))
+        .filter_map(|d| d.get(..7))
+        .map(str::to_string)
+        .collect();
+    months
+        .into_iter()
+        .map(|month| {
+            let kpis = compute(data, |d| d.starts_with(&month));
+            (month, kpis)
+        })
+        .collect()
+}
+
+fn opt(value: Option<f64>, decimals: usize) -> String {
+    value.map_or(String::new(), |v| format!("{:.*}", decimals, v))
+}
+
+/// Generate unit_economics.csv (one row per month)
+pub fn generate_csv(output_dir: &Path, months: &[(String, UnitEconomics)]) -> Result<()> {
+    let path = output_dir.join(constants::UNIT_ECONOMICS_FILENAME);
+    let mut wtr = Writer::from_path(&path)?;
+
+    wtr.write_record([
+        "Month",
+        "Blocks_Produced",
+        "Leader_Slots",
+        "Vote_Credits",
+        "Avg_Delegated_SOL",
+        "Cost_Per_Block_USD",
+        "Revenue_Per_Leader_Slot_SOL",
+        "Vote_Fee_Per_Credit_Lamports",
+        "USD_Per_Delegated_SOL",
+    ])?;
+
+    for (month, k) in months {
+        wtr.write_record([
+            month.clone(),
+            k.blocks_produced.to_string(),
+            k.leader_slots.to_string(),
+            k.vote_credits.to_string(),
+            opt(k.avg_delegated_sol, 0),
+            opt(k.cost_per_block_usd, 4),
+            opt(k.revenue_per_leader_slot_sol, 6),
+            opt(k.vote_fee_per_credit_lamports, 1),
+            opt(k.usd_per_delegated_sol, 6),
+        ])?;
+    }
+
+    wtr.flush()?;
+    println!("  Generated: {}", path.display());
+    Ok(())
+}
+
+/// Console section for the financial summary
+pub fn print(k: &UnitEconomics) {
+    if k.is_empty() {
+        return;
+    }
+    let show = |v: Option<f64>, f: &dyn Fn(f64) -> String| v.map_or("n/a".to_string(), f);
+    println!("\nUNIT ECONOMICS:");
+    println!(
+        "  Cost / Block:          {}",
+        show(k.cost_per_block_usd, &|v| format!("${:.4}", v))
+    );
+    println!(
+        "  Revenue / Leader Slot: {}",
+        show(k.revenue_per_leader_slot_sol, &|v| format!("{:.6} SOL", v))
+    );
+    println!(
+        "  Vote Fee / Credit:     {}",
+        show(k.vote_fee_per_credit_lamports, &|v| format!("{:.1} lamports", v))
+    );
+    println!(
+        "  Revenue / Staked SOL:  {}",
+        show(k.usd_per_delegated_sol, &|v| format!("${:.4}", v))
+    );
+    println!(
+        "  ({} blocks, {} leader slots, {} credits, avg stake {})",
+        k.blocks_produced,
+        k.leader_slots,
+        k.vote_credits,
+        show(k.avg_delegated_sol, &|v| format!("{:.0} SOL", v))
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_vote_account_skips_current_epoch_credits() {
+        let rows = from_vote_account(&[(799, 1_000, 600), (800, 1_300, 1_000)], 5_000, 800);
+        assert_eq!(
+            rows,
+            vec![
+                EpochPerformance {
+                    epoch: 799,
+                    vote_credits: Some(400),
+                    active_stake_lamports: None,
+                },
+                EpochPerformance {
+                    epoch: 800,
+                    vote_credits: None,
+                    active_stake_lamports: Some(5_000),
+                },
+            ]
+        );
+    }
+}
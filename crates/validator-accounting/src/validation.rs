@@ -0,0 +1,383 @@
+//! Sanity checks on report inputs (exceptions.csv)
+//!
+//! Some cached rows cannot be right: a negative reward or fee, a date after today, a
+//! commission above 100%, a SOL price of zero or less. Rather than let them skew every
+//! total, the rows are quarantined before any report is built: left out of the report data
+//! and listed in exceptions.csv with the reason. They stay in the cache, so once the source
+//! is fixed (or the rows re-fetched) the next run picks them up again.
+//!
+//! Expenses may be negative (refunds, vendor credits), so only malformed ones are held
+//! back: an amount that is not a number or a date that is not YYYY-MM-DD. Their category
+//! cannot be missing; unknown categories are read as `Other`.
+
+use anyhow::Result;
+use chrono::NaiveDate;
+use csv::Writer;
+use std::path::Path;
+
+use crate::bam::BamClaim;
+use crate::cache::{GroupedAmounts, MonthlyAggregates};
+use crate::constants;
+use crate::doublezero::DoubleZeroFee;
+use crate::expenses::Expense;
+use crate::jito::MevClaim;
+use crate::leader_fees::EpochLeaderFees;
+use crate::prices::PriceCache;
+use crate::transactions::EpochReward;
+use crate::vote_costs::EpochVoteCost;
+
+/// A quarantined row and why
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataException {
+    /// Table the row came from, e.g. "mev_claims"
+    pub source: &'static str,
+    /// `epoch-N`, the expense vendor, or the price date
+    pub reference: String,
+    pub date: Option<String>,
+    pub reason: String,
+}
+
+/// The report inputs the checks cover, screened in place
+pub struct ReportRows<'a> {
+    pub rewards: &'a mut Vec<EpochReward>,
+    pub mev_claims: &'a mut Vec<MevClaim>,
+    pub bam_claims: &'a mut Vec<BamClaim>,
+    pub leader_fees: &'a mut Vec<EpochLeaderFees>,
+    pub doublezero_fees: &'a mut Vec<DoubleZeroFee>,
+    pub vote_costs: &'a mut Vec<EpochVoteCost>,
+    pub expenses: &'a mut Vec<Expense>,
+    pub prices: &'a mut PriceCache,
+    /// Rebuilt from the remaining rows when a source it sums lost any
+    pub aggregates: &'a mut MonthlyAggregates,
+}
+
+fn negative(value: f64, what: &str) -> Option<String> {
+    (!value.is_finite() || value < 0.0).then(|| format!("{} is negative or not a number ({})", what, value))
+}
+
+fn not_a_number(value: f64, what: &str) -> Option<String> {
+    (!value.is_finite()).then(|| format!("{} is not a number ({})", what, value))
+}
+
+fn malformed_date(date: &str) -> Option<String> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .is_err()
+        .then(|| format!("date {:?} is not YYYY-MM-DD", date))
+}
+
+fn future(date: Option<&str>, today: &str) -> Option<String> {
+    date.filter(|d| d.get(..10).unwrap_or(d) > today)
+        .map(|d| format!("dated {}, after today ({})", d, today))
+}
+
+/// Drop the rows `check` finds a problem with, recording each as an exception
+fn retain<T>(
+    rows: &mut Vec<T>,
+    exceptions: &mut Vec<DataException>,
+    source: &'static str,
+    describe: impl Fn(&T) -> (String, Option<String>),
+    check: impl Fn(&T) -> Option<String>,
+) -> bool {
+    let before = exceptions.len();
+    rows.retain(|row| match check(row) {
+        Some(reason) => {
+            let (reference, date) = describe(row);
+            exceptions.push(DataException {
+                source,
+                reference,
+                date,
+                reason,
+            });
+            false
+        }
+        None => true,
+    });
+    exceptions.len() > before
+}
+
+fn epoch_ref(epoch: u64, date: &Option<String>) -> (String, Option<String>) {
+    (format!("epoch-{}", epoch), date.clone())
+}
+
+/// Quarantine impossible rows from `rows` (dates are compared with `today`, YYYY-MM-DD).
+/// Returns the quarantined rows in source order.
+pub fn quarantine(rows: ReportRows<'_>, today: &str) -> Vec<DataException> {
+    let mut exceptions = Vec::new();
+    let ex = &mut exceptions;
+
+    let rewards = retain(
+        rows.rewards,
+        ex,
+        "epoch_rewards",
+        |r| epoch_ref(r.epoch, &r.date),
+        |r| {
+            negative(r.amount_sol, "commission")
+                .or_else(|| (r.commission > 100).then(|| format!("commission rate of {}% exceeds 100%", r.commission)))
+                .or_else(|| future(r.date.as_deref(), today))
+        },
+    );
+    let mev = retain(
+        rows.mev_claims,
+        ex,
+        "mev_claims",
+        |c| (c.reference(), c.date.clone()),
+        |c| negative(c.amount_sol, "MEV commission").or_else(|| future(c.date.as_deref(), today)),
+    );
+    retain(
+        rows.bam_claims,
+        ex,
+        "bam_claims",
+        |c| epoch_ref(c.epoch, &c.date),
+        |c| {
+            negative(c.amount_sol_equivalent, "BAM reward")
+                .or_else(|| {
+                    c.jitosol_sol_rate
+                        .filter(|rate| rate.is_nan() || *rate <= 0.0)
+                        .map(|rate| format!("jitoSOL rate of {} is not positive", rate))
+                })
+                .or_else(|| future(c.date.as_deref(), today))
+        },
+    );
+    let leader_fees = retain(
+        rows.leader_fees,
+        ex,
+        "leader_fees",
+        |f| epoch_ref(f.epoch, &f.date),
+        |f| negative(f.total_fees_sol, "leader fees").or_else(|| future(f.date.as_deref(), today)),
+    );
+    // The current epoch's estimate is dated at its (future) end
+    let doublezero = retain(
+        rows.doublezero_fees,
+        ex,
+        "doublezero_fees",
+        |f| epoch_ref(f.epoch, &f.date),
+        |f| {
+            negative(f.liability_sol, "DoubleZero liability").or_else(|| {
+                if f.is_estimate {
+                    None
+                } else {
+                    future(f.date.as_deref(), today)
+                }
+            })
+        },
+    );
+    let vote_costs = retain(
+        rows.vote_costs,
+        ex,
+        "vote_costs",
+        |v| epoch_ref(v.epoch, &v.date),
+        |v| negative(v.total_fee_sol, "vote fees").or_else(|| future(v.date.as_deref(), today)),
+    );
+    retain(
+        rows.expenses,
+        ex,
+        "expenses",
+        |e| (e.vendor.clone(), Some(e.date.clone())),
+        |e| {
+            not_a_number(e.amount_usd, "amount")
+                .or_else(|| malformed_date(&e.date))
+                .or_else(|| future(Some(e.date.as_str()), today))
+        },
+    );
+
+    let mut bad_prices: Vec<(String, f64)> = rows
+        .prices
+        .iter()
+        .filter(|(_, price)| !price.usd_price.is_finite() || price.usd_price <= 0.0)
+        .map(|(date, price)| (date.clone(), price.usd_price))
+        .collect();
+    bad_prices.sort_by(|a, b| a.0.cmp(&b.0));
+    let prices = !bad_prices.is_empty();
+    for (date, price) in bad_prices {
+        rows.prices.remove(&date);
+        ex.push(DataException {
+            source: "prices",
+            reference: date.clone(),
+            date: Some(date),
+            reason: format!("SOL price of ${} is not positive", price),
+        });
+    }
+
+    // The summary sums these tables in SQL; redo the sums without the quarantined rows
+    let aggregates = rows.aggregates;
+    let prices_ref = &*rows.prices;
+    let rebuild = |group: &mut GroupedAmounts, changed: bool, remaining: Vec<(Option<&str>, f64)>, by_month: bool| {
+        if (changed || prices) && !group.is_empty() {
+            *group = GroupedAmounts::from_rows(remaining, prices_ref, by_month);
+        }
+    };
+    rebuild(
+        &mut aggregates.commission,
+        rewards,
+        rows.rewards.iter().map(|r| (r.date.as_deref(), r.amount_sol)).collect(),
+        true,
+    );
+    rebuild(
+        &mut aggregates.leader_fees,
+        leader_fees,
+        rows.leader_fees
+            .iter()
+            .map(|f| (f.date.as_deref(), f.total_fees_sol))
+            .collect(),
+        true,
+    );
+    rebuild(
+        &mut aggregates.mev_claims,
+        mev,
+        rows.mev_claims
+            .iter()
+            .map(|c| (c.date.as_deref(), c.amount_sol))
+            .collect(),
+        true,
+    );
+    rebuild(
+        &mut aggregates.doublezero_fees,
+        doublezero,
+        rows.doublezero_fees
+            .iter()
+            .map(|f| (f.date.as_deref(), f.liability_sol))
+            .collect(),
+        true,
+    );
+    rebuild(
+        &mut aggregates.vote_costs,
+        vote_costs,
+        rows.vote_costs
+            .iter()
+            .map(|v| (v.date.as_deref(), v.total_fee_sol))
+            .collect(),
+        false,
+    );
+
+    exceptions
+}
+
+/// Write exceptions.csv (header only when nothing was quarantined)
+pub fn generate_csv(output_dir: &Path, exceptions: &[DataException]) -> Result<()> {
+    let path = output_dir.join(constants::EXCEPTIONS_FILENAME);
+    let mut wtr = Writer::from_path(&path)?;
+
+    wtr.write_record(["Source", "Reference", "Date (YYYY-MM-DD)", "Reason"])?;
+    for e in exceptions {
+        wtr.write_record([e.source, &e.reference, e.date.as_deref().unwrap_or(""), &e.reason])?;
+    }
+
+    wtr.flush()?;
+    println!("  Generated: {}", path.display());
+
+    if !exceptions.is_empty() {
+        eprintln!(
+            "  Warning: {} row(s) failed sanity checks and were left out of the reports (see {}).",
+            exceptions.len(),
+            constants::EXCEPTIONS_FILENAME
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reward(epoch: u64, amount_sol: f64, commission: u8, date: &str) -> EpochReward {
+        EpochReward {
+            epoch,
+            amount_lamports: (amount_sol.max(0.0) * 1e9) as u64,
+            amount_sol,
+            commission,
+            effective_slot: 0,
+            date: Some(date.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_quarantines_impossible_rows_and_resums_aggregates() {
+        let mut rewards = vec![
+            reward(800, 1.0, 5, "2025-06-01"),
+            reward(801, -2.0, 5, "2025-06-03"),
+            reward(802, 1.0, 150, "2025-06-05"),
+            reward(803, 1.0, 5, "2025-07-30"),
+        ];
+        let mut prices = PriceCache::from([
+            ("2025-06-01".to_string(), 150.0.into()),
+            ("2025-06-03".to_string(), 0.0.into()),
+        ]);
+        let mut aggregates = MonthlyAggregates::default();
+        // Stand-in for the SQL sums, which include every row
+        aggregates.commission =
+            GroupedAmounts::from_rows(rewards.iter().map(|r| (r.date.as_deref(), r.amount_sol)), &prices, true);
+
+        let exceptions = quarantine(
+            ReportRows {
+                rewards: &mut rewards,
+                mev_claims: &mut Vec::new(),
+                bam_claims: &mut Vec::new(),
+                leader_fees: &mut Vec::new(),
+                doublezero_fees: &mut Vec::new(),
+                vote_costs: &mut Vec::new(),
+                expenses: &mut Vec::new(),
+                prices: &mut prices,
+                aggregates: &mut aggregates,
+            },
+            "2025-07-01",
+        );
+
+        let quarantined: Vec<(&str, &str)> = exceptions.iter().map(|e| (e.source, e.reference.as_str())).collect();
+        assert_eq!(
+            quarantined,
+            vec![
+                ("epoch_rewards", "epoch-801"),
+                ("epoch_rewards", "epoch-802"),
+                ("epoch_rewards", "epoch-803"),
+                ("prices", "2025-06-03"),
+            ]
+        );
+        assert!(exceptions[1].reason.contains("exceeds 100%"));
+        assert_eq!(rewards.len(), 1);
+        assert!(!prices.contains_key("2025-06-03"));
+        assert_eq!(aggregates.commission.priced.get("2025-06"), Some(&(1.0, 150.0)));
+        assert!(aggregates.commission.unpriced.is_empty());
+    }
+
+    #[test]
+    fn test_keeps_refunds_and_quarantines_malformed_expenses() {
+        let expense = |vendor: &str, date: &str, amount_usd: f64| Expense {
+            id: None,
+            date: date.to_string(),
+            vendor: vendor.to_string(),
+            category: crate::expenses::ExpenseCategory::Hosting,
+            description: String::new(),
+            amount_usd,
+            paid_with: "USD".to_string(),
+            invoice_id: None,
+            entity: None,
+            status: Default::default(),
+        };
+        let mut expenses = vec![
+            expense("Hetzner", "2025-06-01", 120.0),
+            expense("Hetzner refund", "2025-06-02", -40.0),
+            expense("Broken", "2025-06-03", f64::NAN),
+            expense("Typo", "06/04/2025", 10.0),
+        ];
+
+        let exceptions = quarantine(
+            ReportRows {
+                rewards: &mut Vec::new(),
+                mev_claims: &mut Vec::new(),
+                bam_claims: &mut Vec::new(),
+                leader_fees: &mut Vec::new(),
+                doublezero_fees: &mut Vec::new(),
+                vote_costs: &mut Vec::new(),
+                expenses: &mut expenses,
+                prices: &mut PriceCache::new(),
+                aggregates: &mut MonthlyAggregates::default(),
+            },
+            "2025-07-01",
+        );
+
+        let quarantined: Vec<&str> = exceptions.iter().map(|e| e.reference.as_str()).collect();
+        assert_eq!(quarantined, ["Broken", "Typo"]);
+        assert_eq!(expenses.len(), 2);
+        assert_eq!(expenses[1].amount_usd, -40.0);
+    }
+}
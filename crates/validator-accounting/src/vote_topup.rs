@@ -0,0 +1,156 @@
+//! Identity top-up recommendations for vote fees
+//!
+//! Vote transaction fees are paid from the identity account every slot. If it runs
+//! dry the validator stops voting, so after each run we project the vote fees for the
+//! rest of the epoch (plus one buffer epoch) from recent per-epoch vote costs and,
+//! when the identity balance falls short, recommend "transfer X SOL to identity by
+//! <date>", where the date is when the current balance would run out.
+
+use serde::Serialize;
+
+use crate::timezone;
+use crate::vote_costs::{EpochVoteCost, TYPICAL_COST_PER_EPOCH_SOL};
+
+/// Recent epochs the vote fee run-rate is averaged over
+const RUN_RATE_EPOCHS: usize = 5;
+
+/// Full epochs of vote fees to hold beyond the current one
+const BUFFER_EPOCHS: f64 = 1.0;
+
+/// Target slot time used to project slots onto the wall clock
+const SLOT_DURATION_MS: f64 = 400.0;
+
+/// Identity runway against projected vote fees
+#[derive(Debug, Clone, Serialize)]
+pub struct VoteTopUp {
+    pub identity_balance_sol: f64,
+    /// Vote account balance above its rent-exempt reserve (a funding source)
+    pub vote_withdrawable_sol: f64,
+    pub cost_per_epoch_sol: f64,
+    /// Projected vote fees for the slots left in the current epoch
+    pub rest_of_epoch_cost_sol: f64,
+    /// Rest of the epoch plus the buffer epoch
+    pub target_sol: f64,
+    /// SOL to move to identity (0 when the balance already covers the target)
+    pub transfer_sol: f64,
+    /// Date (accounting timezone) the identity balance runs out at the current rate
+    pub runs_out_on: Option<String>,
+}
+
+/// Average vote cost per epoch over the latest cached epochs; estimated rows are
+/// skipped, and the typical cost is used when nothing measured is cached yet
+pub fn recent_cost_per_epoch(costs: &[EpochVoteCost]) -> f64 {
+    let mut measured: Vec<&EpochVoteCost> = costs.iter().filter(|c| c.source != "estimated").collect();
+    measured.sort_by_key(|c| std::cmp::Reverse(c.epoch));
+    measured.truncate(RUN_RATE_EPOCHS);
+    if measured.is_empty() {
+        return TYPICAL_COST_PER_EPOCH_SOL;
+    }
+    measured.iter().map(|c| c.total_fee_sol).sum::<f64>() / measured.len() as f64
+}
+
+/// Compare the identity balance with the vote fees still to come
+pub fn assess(
+    identity_lamports: u64,
+    vote_withdrawable_lamports: u64,
+    cost_per_epoch_sol: f64,
+    slot_index: u64,
+    slots_in_epoch: u64,
+    now_timestamp: i64,
+) -> VoteTopUp {
+    let identity_balance_sol = identity_lamports as f64 / 1e9;
+    let slots_left = slots_in_epoch.saturating_sub(slot_index) as f64;
+    let cost_per_slot = if slots_in_epoch == 0 {
+        0.0
+    } else {
+        cost_per_epoch_sol / slots_in_epoch as f64
+    };
+    let rest_of_epoch_cost_sol = slots_left * cost_per_slot;
+    let target_sol = rest_of_epoch_cost_sol + BUFFER_EPOCHS * cost_per_epoch_sol;
+    // Round up to the cent so the recommendation is a clean amount that still covers the target
+    let transfer_sol = ((target_sol - identity_balance_sol).max(0.0) * 100.0).ceil() / 100.0;
+
+    let runs_out_on = (cost_per_slot > 0.0)
+        .then(|| {
+            let seconds = identity_balance_sol / cost_per_slot * SLOT_DURATION_MS / 1000.0;
+            timezone::timestamp_to_date(now_timestamp.saturating_add(seconds as i64))
+        })
+        .flatten();
+
+    VoteTopUp {
+        identity_balance_sol,
+        vote_withdrawable_sol: vote_withdrawable_lamports as f64 / 1e9,
+        cost_per_epoch_sol,
+        rest_of_epoch_cost_sol,
+        target_sol,
+        transfer_sol,
+        runs_out_on,
+    }
+}
+
+/// Print the recommendation under the console summary
+pub fn print_recommendation(topup: &VoteTopUp) {
+    println!("\nVOTE FEES:");
+    println!(
+        "  Identity balance:   {:>10.4} SOL  (vote fees ~{:.4} SOL/epoch)",
+        topup.identity_balance_sol, topup.cost_per_epoch_sol
+    );
+    println!(
+        "  Needed:             {:>10.4} SOL  (rest of epoch + {} buffer epoch)",
+        topup.target_sol, BUFFER_EPOCHS
+    );
+    if topup.transfer_sol > 0.0 {
+        let by = topup.runs_out_on.as_deref().unwrap_or("as soon as possible");
+        println!("  → Transfer {:.2} SOL to identity by {}", topup.transfer_sol, by);
+        if topup.vote_withdrawable_sol >= topup.transfer_sol {
+            println!(
+                "    (vote account has {:.4} SOL withdrawable above rent)",
+                topup.vote_withdrawable_sol
+            );
+        }
+        println!(
+            "    Prepare it with: validator-accounting tx build fund-identity --sol {:.2} --blockhash <BLOCKHASH>",
+            topup.transfer_sol
+        );
+    } else {
+        println!("  Identity balance covers projected vote fees");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cost(epoch: u64, sol: f64, source: &str) -> EpochVoteCost {
+        EpochVoteCost {
+            epoch,
+            vote_count: 0,
+            total_fee_lamports: (sol * 1e9) as u64,
+            total_fee_sol: sol,
+            source: source.to_string(),
+            date: None,
+        }
+    }
+
+    #[test]
+    fn run_rate_uses_latest_measured_epochs() {
+        let mut costs: Vec<EpochVoteCost> = (900..906).map(|e| cost(e, 2.0, "rpc")).collect();
+        costs[0].total_fee_sol = 100.0; // epoch 900 falls outside the window
+        costs.push(cost(906, 50.0, "estimated"));
+        assert!((recent_cost_per_epoch(&costs) - 2.0).abs() < 1e-12);
+        assert_eq!(recent_cost_per_epoch(&[]), TYPICAL_COST_PER_EPOCH_SOL);
+    }
+
+    #[test]
+    fn recommends_a_transfer_when_identity_falls_short() {
+        // Half an epoch left at 2 SOL/epoch: need 1 + 2 = 3 SOL, hold 1.5
+        let topup = assess(1_500_000_000, 10_000_000_000, 2.0, 216_000, 432_000, 1_767_225_600);
+        assert!((topup.target_sol - 3.0).abs() < 1e-9);
+        assert!((topup.transfer_sol - 1.5).abs() < 1e-9);
+        // 1.5 SOL lasts 0.75 epochs = 324,000 slots = 36 hours from 2026-01-01 00:00 UTC
+        assert_eq!(topup.runs_out_on.as_deref(), Some("2026-01-02"));
+
+        let covered = assess(5_000_000_000, 0, 2.0, 216_000, 432_000, 1_767_225_600);
+        assert_eq!(covered.transfer_sol, 0.0);
+    }
+}
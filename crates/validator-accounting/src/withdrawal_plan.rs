@@ -0,0 +1,150 @@
+//! Withdrawal planning against remaining seed capital (advisory only)
+//!
+//! Answers "if I take $X out today, what does it cost me in tax?" using the same
+//! method as the tax report: withdrawals first return the remaining seed capital
+//! (non-taxable), and only the SOL beyond it is taxable revenue, valued at the
+//! current SOL price. The estimated tax is the taxable amount times a flat rate the
+//! operator supplies; nothing here is authoritative tax advice.
+
+use anyhow::{Result, ensure};
+use serde::Serialize;
+
+use crate::prices::PriceSource;
+
+/// What a withdrawal of `target_usd` would look like today
+#[derive(Debug, Clone, Serialize)]
+pub struct WithdrawalPlan {
+    pub target_usd: f64,
+    pub sol_price_usd: f64,
+    pub price_source: PriceSource,
+    /// SOL to move out of the business accounts
+    pub sol_to_move: f64,
+    /// Portion that returns seed capital (non-taxable)
+    pub return_of_capital_sol: f64,
+    pub return_of_capital_usd: f64,
+    /// Portion beyond the remaining seed capital (taxable revenue)
+    pub taxable_sol: f64,
+    pub taxable_usd: f64,
+    /// Flat rate applied to the taxable portion, if one was given
+    pub tax_rate: Option<f64>,
+    pub estimated_tax_usd: Option<f64>,
+    /// Seed capital (SOL) not yet returned, before and after this withdrawal
+    pub remaining_capital_before_sol: f64,
+    pub remaining_capital_after_sol: f64,
+}
+
+/// Split a USD withdrawal into return of capital and taxable revenue
+pub fn plan_withdrawal(
+    target_usd: f64,
+    sol_price_usd: f64,
+    price_source: PriceSource,
+    remaining_capital_sol: f64,
+    tax_rate: Option<f64>,
+) -> Result<WithdrawalPlan> {
+    ensure!(
+        target_usd.is_finite() && target_usd > 0.0,
+        "Withdrawal amount must be a positive USD amount (got {})",
+        target_usd
+    );
+    ensure!(
+        sol_price_usd.is_finite() && sol_price_usd > 0.0,
+        "No usable SOL price (got {})",
+        sol_price_usd
+    );
+    if let Some(rate) = tax_rate {
+        ensure!(
+            (0.0..=1.0).contains(&rate),
+            "Tax rate must be between 0 and 1 (got {})",
+            rate
+        );
+    }
+
+    let remaining = remaining_capital_sol.max(0.0);
+    let sol_to_move = target_usd / sol_price_usd;
+    let return_of_capital_sol = sol_to_move.min(remaining);
+    let taxable_sol = sol_to_move - return_of_capital_sol;
+    let taxable_usd = taxable_sol * sol_price_usd;
+
+    Ok(WithdrawalPlan {
+        target_usd,
+        sol_price_usd,
+        price_source,
+        sol_to_move,
+        return_of_capital_sol,
+        return_of_capital_usd: return_of_capital_sol * sol_price_usd,
+        taxable_sol,
+        taxable_usd,
+        tax_rate,
+        estimated_tax_usd: tax_rate.map(|rate| taxable_usd * rate),
+        remaining_capital_before_sol: remaining,
+        remaining_capital_after_sol: remaining - return_of_capital_sol,
+    })
+}
+
+/// Print the plan for the console
+pub fn print_plan(plan: &WithdrawalPlan) {
+    println!("Withdrawal plan (advisory, not tax advice)");
+    println!(
+        "  Target:             ${:.2} at {:.2} USD/SOL ({})",
+        plan.target_usd,
+        plan.sol_price_usd,
+        plan.price_source.as_str()
+    );
+    println!("  SOL to move:        {:.6} SOL", plan.sol_to_move);
+    println!(
+        "  Return of capital:  {:.6} SOL = ${:.2} (non-taxable)",
+        plan.return_of_capital_sol, plan.return_of_capital_usd
+    );
+    println!(
+        "  Taxable revenue:    {:.6} SOL = ${:.2}",
+        plan.taxable_sol, plan.taxable_usd
+    );
+    match (plan.tax_rate, plan.estimated_tax_usd) {
+        (Some(rate), Some(tax)) => println!(
+            "  Estimated tax:      ${:.2} at {:.1}%  (net ${:.2})",
+            tax,
+            rate * 100.0,
+            plan.target_usd - tax
+        ),
+        _ => println!("  Estimated tax:      pass --tax-rate or set [reporting] estimated_tax_rate"),
+    }
+    println!(
+        "  Seed capital left:  {:.6} SOL before, {:.6} SOL after",
+        plan.remaining_capital_before_sol, plan.remaining_capital_after_sol
+    );
+    if plan.price_source == PriceSource::Fallback {
+        println!("  Warning: no cached SOL price near today; run 'prices backfill' for an accurate plan");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capital_is_returned_before_anything_is_taxable() {
+        // $5,000 at $200 = 25 SOL; 10 SOL of seed capital left
+        let plan = plan_withdrawal(5_000.0, 200.0, PriceSource::Exact, 10.0, Some(0.3)).unwrap();
+        assert!((plan.sol_to_move - 25.0).abs() < 1e-9);
+        assert!((plan.return_of_capital_sol - 10.0).abs() < 1e-9);
+        assert!((plan.taxable_sol - 15.0).abs() < 1e-9);
+        assert!((plan.taxable_usd - 3_000.0).abs() < 1e-6);
+        assert!((plan.estimated_tax_usd.unwrap() - 900.0).abs() < 1e-6);
+        assert_eq!(plan.remaining_capital_after_sol, 0.0);
+    }
+
+    #[test]
+    fn withdrawal_within_remaining_capital_is_untaxed() {
+        let plan = plan_withdrawal(1_000.0, 250.0, PriceSource::Exact, 10.0, Some(0.3)).unwrap();
+        assert_eq!(plan.taxable_sol, 0.0);
+        assert_eq!(plan.estimated_tax_usd, Some(0.0));
+        assert!((plan.remaining_capital_after_sol - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rejects_unusable_inputs() {
+        assert!(plan_withdrawal(0.0, 200.0, PriceSource::Exact, 0.0, None).is_err());
+        assert!(plan_withdrawal(100.0, 0.0, PriceSource::Exact, 0.0, None).is_err());
+        assert!(plan_withdrawal(100.0, 200.0, PriceSource::Exact, 0.0, Some(1.5)).is_err());
+    }
+}
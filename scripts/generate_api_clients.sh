@@ -0,0 +1,57 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+REPO_ROOT="$(cd "${SCRIPT_DIR}/.." && pwd)"
+OUT_DIR="${API_CLIENTS_DIR:-${REPO_ROOT}/clients}"
+
+usage() {
+  cat <<'USAGE'
+Usage: generate_api_clients.sh [--out DIR]
+
+Writes the public API's OpenAPI spec (from `bp-web --print-openapi`, so it always
+matches the server types) and generates clients from it:
+
+  DIR/openapi.json             the spec
+  DIR/typescript/bp-api.d.ts   TypeScript types (openapi-typescript)
+  DIR/rust/                    Rust reqwest client crate (openapi-generator)
+
+Needs cargo and npx. Default DIR: <repo>/clients (or $API_CLIENTS_DIR).
+USAGE
+}
+
+while [[ $# -gt 0 ]]; do
+  case "$1" in
+    --out)
+      OUT_DIR="$2"
+      shift 2
+      ;;
+    -h|--help)
+      usage
+      exit 0
+      ;;
+    *)
+      echo "Unknown argument: $1" >&2
+      usage >&2
+      exit 1
+      ;;
+  esac
+done
+
+mkdir -p "${OUT_DIR}/typescript"
+SPEC="${OUT_DIR}/openapi.json"
+
+echo "Writing ${SPEC}..."
+cargo run --quiet --manifest-path "${REPO_ROOT}/Cargo.toml" -p bp-web --features ssr -- --print-openapi > "${SPEC}"
+
+echo "Generating TypeScript types..."
+npx --yes openapi-typescript "${SPEC}" --output "${OUT_DIR}/typescript/bp-api.d.ts"
+
+echo "Generating Rust client..."
+npx --yes @openapitools/openapi-generator-cli generate \
+  --input-spec "${SPEC}" \
+  --generator-name rust \
+  --output "${OUT_DIR}/rust" \
+  --additional-properties packageName=bp-api-client,library=reqwest
+
+echo "Done: clients in ${OUT_DIR}"